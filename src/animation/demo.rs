@@ -1,296 +1,582 @@
-use crossterm::{
-    cursor::{Hide, Show},
-    ExecutableCommand,
-};
-use owo_colors::OwoColorize;
-use std::io::{stdout, Write};
-use std::thread;
-use std::time::Duration;
+//! `termgfx demo`: a guided tour of the commands
+//!
+//! Each step types out a real command line and runs it, so what is shown is
+//! exactly what the command prints. Arrow keys move between steps and
+//! sections; the current command can be copied or opened in Studio. When
+//! stdin or stdout isn't a terminal the steps are played back one after
+//! another instead.
 
 use crate::animation::effects;
-use crate::charts::{bar, pie::PieChart, sparkline};
-use crate::output::{banner, progress, styled_box};
+use crate::interactive::{clipboard, studio};
+use crate::output::{banner, color};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use owo_colors::OwoColorize;
+use std::io::{self, IsTerminal, Write};
+use std::process::Command;
+
+/// Tour sections, in order
+const SECTIONS: [&str; 6] = ["boxes", "charts", "data", "progress", "animation", "tui"];
+
+/// One screen of the tour
+struct Step {
+    section: &'static str,
+    title: &'static str,
+    blurb: &'static str,
+    /// The command line after `termgfx`
+    args: &'static [&'static str],
+    /// Studio component and parameter values showing the same thing
+    studio: Option<(&'static str, &'static [(&'static str, &'static str)])>,
+    /// Takes over the terminal, so it only runs in the interactive tour
+    interactive: bool,
+}
 
-fn wait(secs: f64) {
-    thread::sleep(Duration::from_secs_f64(secs));
+const STEPS: &[Step] = &[
+    Step {
+        section: "boxes",
+        title: "Styled boxes",
+        blurb: "Status messages with a style preset and a border.",
+        args: &[
+            "box",
+            "Deploy finished",
+            "--style",
+            "success",
+            "--border",
+            "rounded",
+        ],
+        studio: Some((
+            "box",
+            &[("message", "Deploy finished"), ("style", "success")],
+        )),
+        interactive: false,
+    },
+    Step {
+        section: "boxes",
+        title: "Titles and footers",
+        blurb: "Labels set into the border, aligned left, center or right.",
+        args: &[
+            "box",
+            "All services healthy",
+            "--title",
+            "Status",
+            "--footer",
+            "v1.2.3",
+            "--title-align",
+            "center",
+        ],
+        studio: Some((
+            "box",
+            &[
+                ("message", "All services healthy"),
+                ("title", "Status"),
+                ("footer", "v1.2.3"),
+                ("title-align", "center"),
+            ],
+        )),
+        interactive: false,
+    },
+    Step {
+        section: "boxes",
+        title: "Banners",
+        blurb: "Large letters with a color gradient.",
+        args: &["banner", "termgfx", "--gradient", "cyan-purple"],
+        studio: Some(("banner", &[("text", "termgfx")])),
+        interactive: false,
+    },
+    Step {
+        section: "charts",
+        title: "Sparklines",
+        blurb: "A whole series in one line of text.",
+        args: &["sparkline", "20,35,28,45,52,48,60,75,82,68,55,42"],
+        studio: Some((
+            "sparkline",
+            &[("data", "20,35,28,45,52,48,60,75,82,68,55,42")],
+        )),
+        interactive: false,
+    },
+    Step {
+        section: "charts",
+        title: "Bar charts",
+        blurb: "Labeled values scaled to the terminal.",
+        args: &["chart", "bar", "--data", "Q1:120,Q2:150,Q3:180,Q4:220"],
+        studio: Some(("chart bar", &[("data", "Q1:120,Q2:150,Q3:180,Q4:220")])),
+        interactive: false,
+    },
+    Step {
+        section: "charts",
+        title: "Pie charts",
+        blurb: "Shares of a whole, with a legend.",
+        args: &[
+            "chart",
+            "pie",
+            "--data",
+            "Chrome:65,Safari:19,Firefox:10,Other:6",
+        ],
+        studio: Some((
+            "chart pie",
+            &[("data", "Chrome:65,Safari:19,Firefox:10,Other:6")],
+        )),
+        interactive: false,
+    },
+    Step {
+        section: "charts",
+        title: "Gauges",
+        blurb: "A single value against its range.",
+        args: &["gauge", "75", "--label", "CPU", "--style", "semicircle"],
+        studio: Some(("gauge", &[("value", "75"), ("label", "CPU")])),
+        interactive: false,
+    },
+    Step {
+        section: "data",
+        title: "Tables",
+        blurb: "Rows from flags, CSV files or JSON, sortable by any column.",
+        args: &[
+            "table",
+            "--headers",
+            "Name,Role,Status",
+            "--rows",
+            "Ada,Engineer,Active|Linus,Maintainer,Away",
+        ],
+        studio: Some((
+            "table",
+            &[
+                ("headers", "Name,Role,Status"),
+                ("rows", "Ada,Engineer,Active|Linus,Maintainer,Away"),
+            ],
+        )),
+        interactive: false,
+    },
+    Step {
+        section: "data",
+        title: "Trees",
+        blurb: "Nested structures from a compact path syntax or JSON.",
+        args: &["tree", "project>src,docs>main.rs,lib.rs"],
+        studio: Some(("tree", &[("structure", "project>src,docs>main.rs,lib.rs")])),
+        interactive: false,
+    },
+    Step {
+        section: "progress",
+        title: "Progress bars",
+        blurb: "A percentage drawn with a gradient.",
+        args: &["progress", "75", "--style", "gradient"],
+        studio: Some(("progress", &[("percent", "75"), ("style", "gradient")])),
+        interactive: false,
+    },
+    Step {
+        section: "progress",
+        title: "Bar styles",
+        blurb: "Blocks, thin and classic styles suit plainer terminals.",
+        args: &["progress", "40", "--style", "blocks"],
+        studio: Some(("progress", &[("percent", "40"), ("style", "blocks")])),
+        interactive: false,
+    },
+    Step {
+        section: "progress",
+        title: "Animated progress",
+        blurb: "A bar that fills over a set duration.",
+        args: &["animate", "-t", "progress", "-D", "1.5"],
+        studio: None,
+        interactive: false,
+    },
+    Step {
+        section: "animation",
+        title: "Typewriter",
+        blurb: "Text revealed a character at a time.",
+        args: &[
+            "animate",
+            "-t",
+            "typewriter",
+            "--text",
+            "Hello, World!",
+            "--speed",
+            "25",
+        ],
+        studio: None,
+        interactive: false,
+    },
+    Step {
+        section: "animation",
+        title: "Counters",
+        blurb: "Numbers counting up, with a prefix or suffix.",
+        args: &[
+            "animate", "-t", "counter", "--to", "50000", "--prefix", "$", "-D", "1.5",
+        ],
+        studio: None,
+        interactive: false,
+    },
+    Step {
+        section: "animation",
+        title: "Growing bars",
+        blurb: "A bar chart that builds up from zero.",
+        args: &[
+            "animate",
+            "-t",
+            "bars",
+            "--data",
+            "Q1:120,Q2:150,Q3:180",
+            "-D",
+            "1.5",
+        ],
+        studio: None,
+        interactive: false,
+    },
+    Step {
+        section: "tui",
+        title: "TUI mode",
+        blurb: "A grid of live widgets. Press q to come back to the tour.",
+        args: &[
+            "tui",
+            "--layout",
+            "2x2",
+            "--widgets",
+            "box:Hello,gauge:75,sparkline:1;2;3;4;5,log:Lines",
+        ],
+        studio: None,
+        interactive: true,
+    },
+];
+
+/// Index of the first step in `section`
+fn section_start(section: &str) -> Option<usize> {
+    STEPS.iter().position(|s| s.section == section)
 }
 
-fn typewriter_print(text: &str, delay_ms: u64) {
-    for ch in text.chars() {
-        print!("{}", ch);
-        stdout().flush().unwrap();
-        thread::sleep(Duration::from_millis(delay_ms));
+/// `args` as a command line a shell would accept
+pub fn command_line(args: &[&str]) -> String {
+    let mut line = String::from("termgfx");
+    for arg in args {
+        line.push(' ');
+        let plain = !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ",.:/-_=%".contains(c));
+        if plain {
+            line.push_str(arg);
+        } else if arg.contains(['$', '"', '`', '\\', '!']) {
+            line.push_str(&format!("'{}'", arg.replace('\'', r"'\''")));
+        } else {
+            line.push_str(&format!("\"{}\"", arg));
+        }
     }
+    line
 }
 
-fn section_header(title: &str) {
-    println!();
-    println!("{}", format!("━━━ {} ━━━", title).cyan().bold());
-    println!();
+/// Run a step's command, passing on this run's color settings
+fn execute(step: &Step) -> io::Result<()> {
+    io::stdout().flush()?;
+    let colors = if color::enabled() { "always" } else { "never" };
+    Command::new(std::env::current_exe()?)
+        .args(["--color", colors, "--color-depth", color::depth().name()])
+        .args(step.args)
+        .status()?;
+    Ok(())
 }
 
-/// Run the full demo showcase
+/// Run the tour, starting at `section` when given
 pub fn run_demo(section: Option<&str>) {
-    let mut stdout = stdout();
-    stdout.execute(Hide).unwrap();
+    let start = match section.map(str::to_lowercase).as_deref() {
+        None | Some("all") => None,
+        Some("animations") => Some("animation"),
+        Some(name) => match SECTIONS.iter().find(|s| **s == name) {
+            Some(name) => Some(*name),
+            None => {
+                eprintln!(
+                    "Error: Unknown section: {}. Available: {}, all",
+                    name,
+                    SECTIONS.join(", ")
+                );
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let result = if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        Tour::new(start.and_then(section_start).unwrap_or(0)).run()
+    } else {
+        play_through(start)
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
 
-    match section {
-        None => run_full_demo(),
-        Some("boxes") => demo_boxes(),
-        Some("charts") => demo_charts(),
-        Some("progress") => demo_progress(),
-        Some("animation") | Some("animations") => demo_animations(),
-        Some("tui") => demo_tui(),
-        Some("all") => run_full_demo(),
-        Some(s) => {
-            stdout.execute(Show).unwrap();
-            eprintln!(
-                "Unknown section: {}. Available: boxes, charts, progress, animation, tui, all",
-                s
+/// Print every step (or one section's) without waiting for keys
+fn play_through(section: Option<&str>) -> io::Result<()> {
+    if section.is_none() {
+        println!();
+        banner::render("termgfx", Some("cyan-purple"));
+    }
+    let mut current = "";
+    for step in STEPS
+        .iter()
+        .filter(|s| section.is_none_or(|name| s.section == name))
+    {
+        if step.section != current {
+            current = step.section;
+            println!();
+            println!(
+                "{}",
+                format!("━━━ {} ━━━", current.to_uppercase()).cyan().bold()
             );
-            return;
+        }
+        println!();
+        println!("{}", step.title.bold());
+        println!("{}", step.blurb.bright_black());
+        println!("{} {}", "$".bright_black(), command_line(step.args));
+        if step.interactive {
+            println!("{}", "(interactive; run it in a terminal)".bright_black());
+        } else {
+            execute(step)?;
         }
     }
-
-    stdout.execute(Show).unwrap();
-}
-
-fn run_full_demo() {
-    // Intro
-    println!();
-    banner::render("termgfx", Some("cyan-purple"));
-    println!();
-    wait(0.3);
-
-    typewriter_print("  ", 0);
-    effects::typewriter("Terminal Graphics Library - Animated Demo", 40.0);
-    wait(0.5);
-
-    // Sections
-    demo_boxes();
-    wait(0.5);
-
-    demo_progress();
-    wait(0.5);
-
-    demo_charts();
-    wait(0.5);
-
-    demo_animations();
-    wait(0.5);
-
-    // Outro
-    println!();
-    banner::render("Complete!", Some("green-cyan"));
-    println!();
-    typewriter_print("  ", 0);
-    effects::typewriter("Thanks for watching the demo!", 30.0);
-    println!();
+    Ok(())
 }
 
-fn demo_boxes() {
-    section_header("STYLED BOXES");
-
-    let styles = [
-        ("info", "Information message"),
-        ("success", "Operation completed!"),
-        ("warning", "Please review this"),
-        ("danger", "Critical error!"),
-    ];
-
-    for (style, msg) in styles {
-        print!("  ");
-        styled_box::render(msg, style, "rounded", None);
-        wait(0.4);
-    }
+/// What a key press asks the tour to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Next,
+    Previous,
+    NextSection,
+    PreviousSection,
+    Section(usize),
+    Copy,
+    Studio,
+    Replay,
+    Quit,
 }
 
-fn demo_progress() {
-    section_header("PROGRESS BARS");
-
-    // Static examples
-    let styles = ["gradient", "blocks", "thin", "classic"];
-
-    for (i, style) in styles.iter().enumerate() {
-        let percent = 25 + (i as u8 * 25);
-        print!("  {:>8}: ", style.bright_black());
-        progress::render(percent, style, None, None);
-        wait(0.3);
+fn action(key: event::KeyEvent) -> Option<Action> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Some(Action::Quit);
     }
-
-    println!();
-    print!("  ");
-    typewriter_print("Animated progress: ", 20);
-    println!();
-    print!("  ");
-    effects::progress(2.0, "gradient");
-    wait(0.3);
+    Some(match key.code {
+        KeyCode::Right | KeyCode::Enter | KeyCode::Char(' ' | 'l' | 'n') => Action::Next,
+        KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h' | 'p') => Action::Previous,
+        KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') => Action::NextSection,
+        KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') => Action::PreviousSection,
+        KeyCode::Char(c @ '1'..='9') => Action::Section(c as usize - '1' as usize),
+        KeyCode::Char('c') => Action::Copy,
+        KeyCode::Char('s') => Action::Studio,
+        KeyCode::Char('r') => Action::Replay,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => return None,
+    })
 }
 
-fn demo_charts() {
-    section_header("CHARTS");
-
-    // Sparkline
-    print!("  ");
-    typewriter_print("CPU Usage: ", 20);
-    println!();
-    print!("  ");
-    sparkline::render("20,35,28,45,52,48,60,75,82,68,55,42,38,25,30");
-    wait(0.5);
-
-    // Animated sparkline
-    println!();
-    print!("  ");
-    typewriter_print("Building chart: ", 20);
-    println!();
-    print!("  ");
-    effects::chart_build("10,25,15,40,35,50,45,60,55,70,65,80", 1.5);
-    wait(0.5);
-
-    // Bar chart
-    println!();
-    print!("  ");
-    typewriter_print("Sales by Quarter:", 20);
-    println!();
-    bar::render("Q1:120,Q2:150,Q3:180,Q4:220");
-    wait(0.5);
-
-    // Pie chart
-    println!();
-    print!("  ");
-    typewriter_print("Market Share:", 20);
-    println!();
-    let pie_chart = PieChart::new("Chrome:65,Safari:19,Firefox:10,Other:6", false, 500);
-    pie_chart.render();
-    wait(0.3);
+/// Position in the interactive tour
+struct Tour {
+    index: usize,
 }
 
-fn demo_animations() {
-    section_header("ANIMATIONS");
+impl Tour {
+    fn new(index: usize) -> Self {
+        Tour { index }
+    }
 
-    // Typewriter
-    print!("  ");
-    typewriter_print("Typewriter effect: ", 20);
-    effects::typewriter("Hello, World!", 25.0);
-    wait(0.3);
+    fn step(&self) -> &'static Step {
+        &STEPS[self.index]
+    }
 
-    // Counter
-    println!();
-    print!("  ");
-    typewriter_print("Counter: ", 20);
-    effects::counter(0, 100, 1.5, "", "%");
-    wait(0.3);
+    fn section(&self) -> usize {
+        SECTIONS
+            .iter()
+            .position(|s| *s == self.step().section)
+            .unwrap_or(0)
+    }
 
-    // Counter with prefix
-    println!();
-    print!("  ");
-    typewriter_print("Revenue: ", 20);
-    effects::counter(0, 50000, 2.0, "$", "");
-    wait(0.3);
+    /// Move to `action`'s step; true if the step changed
+    fn navigate(&mut self, action: Action) -> bool {
+        let before = self.index;
+        let current = self.section();
+        self.index = match action {
+            Action::Next => (self.index + 1).min(STEPS.len() - 1),
+            Action::Previous => self.index.saturating_sub(1),
+            Action::NextSection => SECTIONS[current + 1..]
+                .iter()
+                .find_map(|s| section_start(s))
+                .unwrap_or(self.index),
+            Action::PreviousSection => {
+                let start = section_start(SECTIONS[current]).unwrap_or(0);
+                if self.index > start || current == 0 {
+                    start
+                } else {
+                    section_start(SECTIONS[current - 1]).unwrap_or(0)
+                }
+            }
+            Action::Section(n) => SECTIONS
+                .get(n)
+                .and_then(|s| section_start(s))
+                .unwrap_or(self.index),
+            _ => self.index,
+        };
+        self.index != before
+    }
 
-    // Multiple progress bars
-    println!();
-    print!("  ");
-    typewriter_print("Multi-step process:", 20);
-    println!();
+    fn run(&mut self) -> io::Result<()> {
+        self.draw(true)?;
+        loop {
+            let key = read_key()?;
+            let Some(action) = action(key) else {
+                continue;
+            };
+            match action {
+                Action::Quit => break,
+                Action::Replay => self.draw(true)?,
+                Action::Copy => {
+                    let line = command_line(self.step().args);
+                    if clipboard::copy(&line) {
+                        status(&format!("{} Copied: {}", "✓".green(), line));
+                    } else {
+                        status("Clipboard unavailable (needs pbcopy or xclip)");
+                    }
+                }
+                Action::Studio => match self.step().studio {
+                    Some((component, values)) => {
+                        if let Err(e) = studio::run_studio_with(component, values) {
+                            status(&format!("{} {}", "✗".red(), e));
+                        } else {
+                            self.draw(false)?;
+                        }
+                    }
+                    None => status("This step has no Studio component"),
+                },
+                navigation => {
+                    if self.navigate(navigation) {
+                        self.draw(true)?;
+                    }
+                }
+            }
+        }
+        println!();
+        Ok(())
+    }
 
-    let steps = ["Downloading", "Installing", "Configuring", "Verifying"];
-    for step in steps {
-        print!("    {} ", format!("{}:", step).bright_black());
-        effects::progress(0.8, "thin");
-        print!("    {} ", "✓".green().bold());
-        println!("{}", step.green());
-        wait(0.2);
+    /// Clear the screen and show the current step; `fresh` types the
+    /// command out before running it
+    fn draw(&self, fresh: bool) -> io::Result<()> {
+        let step = self.step();
+        print!("\x1b[2J\x1b[H");
+        let tabs: Vec<String> = SECTIONS
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let label = format!(" {} {} ", i + 1, s);
+                if i == self.section() {
+                    label.black().on_cyan().bold().to_string()
+                } else {
+                    label.bright_black().to_string()
+                }
+            })
+            .collect();
+        println!(
+            "{}  {}  {}",
+            "termgfx tour".cyan().bold(),
+            tabs.join(""),
+            format!("{}/{}", self.index + 1, STEPS.len()).bright_black()
+        );
+        println!();
+        println!("{}", step.title.bold());
+        println!("{}", step.blurb.bright_black());
+        println!();
+
+        let line = command_line(step.args);
+        print!("{} ", "$".bright_black());
+        if fresh {
+            effects::typewriter(&line, 60.0);
+        } else {
+            println!("{}", line);
+        }
+        println!();
+        execute(step)?;
+
+        println!();
+        let studio = if step.studio.is_some() {
+            " · s studio"
+        } else {
+            ""
+        };
+        println!(
+            "{}",
+            format!(
+                "←/→ step · ↑/↓ section · 1-{} jump · c copy{} · r replay · q quit",
+                SECTIONS.len(),
+                studio
+            )
+            .bright_black()
+        );
+        io::stdout().flush()
     }
 }
 
-fn demo_tui() {
-    section_header("TUI MODE");
+/// Show a one-line message under the footer
+fn status(message: &str) {
+    print!("\r\x1b[2K{}", message);
+    let _ = io::stdout().flush();
+}
 
-    // Show TUI capabilities
-    print!("  ");
-    typewriter_print("Interactive TUI with widget grid layouts", 30);
-    println!();
-    println!();
+/// Wait for a key press, in raw mode only while waiting
+fn read_key() -> io::Result<event::KeyEvent> {
+    enable_raw_mode()?;
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => break Ok(key),
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        }
+    };
+    disable_raw_mode()?;
+    key
+}
 
-    // Simulated TUI preview
-    println!(
-        "  {}",
-        "┌─────────────────────┬─────────────────────┐".cyan()
-    );
-    println!(
-        "  {}  {}  {}  {}  {}",
-        "│".cyan(),
-        "Box Widget".white().bold(),
-        " ".repeat(8),
-        "│".cyan(),
-        format!("Gauge: {}%", "75").yellow()
-    );
-    println!(
-        "  {}  Hello World!  {}  {}  {}",
-        "│".cyan(),
-        " ".repeat(7),
-        "│".cyan(),
-        "████████░░░░".green()
-    );
-    println!(
-        "  {}",
-        "├─────────────────────┼─────────────────────┤".cyan()
-    );
-    println!(
-        "  {}  {}  {}  {}",
-        "│".cyan(),
-        "Sparkline".magenta(),
-        " ".repeat(10),
-        "│".cyan()
-    );
-    println!(
-        "  {}  {}  {}  {}",
-        "│".cyan(),
-        "▂▄▆█▆▄▂▃▅▇▅▃".magenta(),
-        " ".repeat(7),
-        "│".cyan()
-    );
-    println!(
-        "  {}",
-        "└─────────────────────┴─────────────────────┘".cyan()
-    );
-    println!();
-    wait(0.5);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_line_quotes_when_needed() {
+        assert_eq!(
+            command_line(&["box", "Deploy finished", "--style", "success"]),
+            "termgfx box \"Deploy finished\" --style success"
+        );
+        assert_eq!(
+            command_line(&["animate", "--prefix", "$"]),
+            "termgfx animate --prefix '$'"
+        );
+        assert_eq!(
+            command_line(&["tree", "project>src"]),
+            "termgfx tree \"project>src\""
+        );
+    }
 
-    // Widget types
-    print!("  ");
-    typewriter_print("Widget types: ", 20);
-    let widgets = ["box", "gauge", "sparkline", "log"];
-    for (i, w) in widgets.iter().enumerate() {
-        if i > 0 {
-            print!(", ");
+    #[test]
+    fn test_every_section_has_steps() {
+        for section in SECTIONS {
+            assert!(section_start(section).is_some(), "{} is empty", section);
         }
-        print!("{}", w.green());
-        wait(0.2);
+        assert!(STEPS.iter().all(|s| SECTIONS.contains(&s.section)));
     }
-    println!();
-    wait(0.3);
 
-    // Example commands
-    println!();
-    print!("  ");
-    typewriter_print("Try it:", 20);
-    println!();
-    println!();
-    println!("  {} termgfx tui --layout 2x2 --widgets \"box:Hello,gauge:75,sparkline:1;2;3;4;5,log:Lines\"", "$".bright_black());
-    println!();
-    println!(
-        "  {} termgfx tui --config dashboard.json",
-        "$".bright_black()
-    );
-    println!();
-    wait(0.5);
-
-    // Controls
-    print!("  ");
-    typewriter_print("Controls: ", 20);
-    print!("{} quit  ", "[q]".yellow());
-    print!("{} refresh  ", "[r]".yellow());
-    println!("{} exit", "[Ctrl+C]".yellow());
+    #[test]
+    fn test_navigation() {
+        let mut tour = Tour::new(0);
+        assert!(!tour.navigate(Action::Previous));
+        assert!(tour.navigate(Action::Next));
+        assert_eq!(tour.index, 1);
+
+        // Down jumps to the next section's first step, up back to this one
+        assert!(tour.navigate(Action::NextSection));
+        assert_eq!(tour.step().section, "charts");
+        assert!(tour.navigate(Action::Next));
+        assert!(tour.navigate(Action::PreviousSection));
+        assert_eq!(tour.index, section_start("charts").unwrap());
+        assert!(tour.navigate(Action::PreviousSection));
+        assert_eq!(tour.index, 0);
+
+        assert!(tour.navigate(Action::Section(5)));
+        assert_eq!(tour.step().section, "tui");
+        assert!(!tour.navigate(Action::Next));
+        assert!(!tour.navigate(Action::Section(9)));
+    }
 }
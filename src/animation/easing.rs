@@ -0,0 +1,129 @@
+//! Easing curves that shape how an animation's progress moves over time
+//!
+//! Each curve maps linear time `t` in 0.0-1.0 to progress, starting at 0.0
+//! and ending at 1.0. Elastic overshoots in between, so callers drawing a
+//! bounded quantity should clamp.
+
+use std::f64::consts::PI;
+
+/// Names accepted by [`Easing::from_name`]
+pub const EASINGS: [&str; 7] = [
+    "linear",
+    "ease-in",
+    "ease-out",
+    "ease-in-out",
+    "cubic",
+    "bounce",
+    "elastic",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Quadratic: slow start
+    EaseIn,
+    /// Quadratic: slow finish
+    EaseOut,
+    /// Quadratic: slow start and finish
+    EaseInOut,
+    /// Cubic ease-in-out, a steeper middle than EaseInOut
+    Cubic,
+    /// Lands and bounces to rest
+    Bounce,
+    /// Springs past the end and settles back
+    Elastic,
+}
+
+impl Easing {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "linear" => Some(Easing::Linear),
+            "ease-in" => Some(Easing::EaseIn),
+            "ease-out" => Some(Easing::EaseOut),
+            "ease-in-out" | "ease" => Some(Easing::EaseInOut),
+            "cubic" => Some(Easing::Cubic),
+            "bounce" => Some(Easing::Bounce),
+            "elastic" => Some(Easing::Elastic),
+            _ => None,
+        }
+    }
+
+    /// Progress at time `t`, clamped to 0.0-1.0 first
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::Cubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::Bounce => bounce_out(t),
+            Easing::Elastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+        }
+    }
+}
+
+fn bounce_out(t: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curves_start_at_zero_and_end_at_one() {
+        for name in EASINGS {
+            let easing = Easing::from_name(name).unwrap();
+            assert!(easing.apply(0.0).abs() < 1e-9, "{} at 0", name);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-9, "{} at 1", name);
+        }
+    }
+
+    #[test]
+    fn test_curve_shapes() {
+        assert_eq!(Easing::Linear.apply(0.5), 0.5);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert!(Easing::Elastic.apply(0.2) > 1.0);
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Easing::from_name("ease_in_out"), Some(Easing::EaseInOut));
+        assert_eq!(Easing::from_name("wobble"), None);
+    }
+}
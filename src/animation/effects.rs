@@ -1,13 +1,16 @@
-use crate::animation::engine::Animator;
+use crate::animation::easing::Easing;
+use crate::animation::engine::{Animator, Frames};
+use crate::animation::policy;
+use crate::output::color;
 use owo_colors::OwoColorize;
 
 /// Animate a progress bar from 0 to 100%
-pub fn progress(duration_secs: f64, style: &str) {
+pub fn progress(duration_secs: f64, style: &str, easing: Easing) {
     let style = style.to_string();
-    let animator = Animator::default();
+    let animator = Animator::default().with_easing(easing);
 
     animator.run_timed(duration_secs, move |_frame, progress| {
-        let percent = (progress * 100.0) as u8;
+        let percent = (progress * 100.0).clamp(0.0, 100.0) as u8;
         render_progress_inline(percent, &style)
     });
 }
@@ -68,11 +71,16 @@ fn render_progress_inline(percent: u8, style: &str) -> String {
 /// Typewriter effect - reveal text character by character
 pub fn typewriter(text: &str, chars_per_sec: f64) {
     use crossterm::{cursor::Hide, cursor::Show, ExecutableCommand};
-    use std::io::{stdout, Write};
+    use std::io::Write;
     use std::thread;
     use std::time::Duration;
 
-    let mut stdout = stdout();
+    if !policy::enabled() {
+        println!("{}", text);
+        return;
+    }
+
+    let mut stdout = color::stdout();
     stdout.execute(Hide).unwrap();
 
     let delay = Duration::from_secs_f64(1.0 / chars_per_sec);
@@ -88,10 +96,10 @@ pub fn typewriter(text: &str, chars_per_sec: f64) {
 }
 
 /// Animate a counter from start to end value
-pub fn counter(from: i64, to: i64, duration_secs: f64, prefix: &str, suffix: &str) {
+pub fn counter(from: i64, to: i64, duration_secs: f64, prefix: &str, suffix: &str, easing: Easing) {
     let prefix = prefix.to_string();
     let suffix = suffix.to_string();
-    let animator = Animator::default();
+    let animator = Animator::default().with_easing(easing);
 
     animator.run_timed(duration_secs, move |_frame, progress| {
         let range = (to - from) as f64;
@@ -106,36 +114,28 @@ pub fn counter(from: i64, to: i64, duration_secs: f64, prefix: &str, suffix: &st
 }
 
 /// Animate chart data appearing progressively
-pub fn chart_build(data: &str, duration_secs: f64) {
+pub fn chart_build(data: &str, duration_secs: f64, easing: Easing) {
     use crossterm::{
         cursor::{Hide, MoveToColumn, Show},
         terminal::{Clear, ClearType},
         ExecutableCommand,
     };
-    use std::io::{stdout, Write};
-    use std::thread;
-    use std::time::{Duration, Instant};
+    use std::io::Write;
 
     let points: Vec<&str> = data.split(',').collect();
     if points.is_empty() {
         return;
     }
+    if !policy::enabled() {
+        println!("{}", render_sparkline_inline(data));
+        return;
+    }
 
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
     stdout.execute(Hide).unwrap();
 
-    let start = Instant::now();
-    let duration = Duration::from_secs_f64(duration_secs);
-    let delay = Duration::from_millis(50);
-
-    loop {
-        let elapsed = start.elapsed();
-        if elapsed >= duration {
-            break;
-        }
-
-        let progress = elapsed.as_secs_f64() / duration_secs;
-        let show_count = ((points.len() as f64 * progress) as usize)
+    for progress in Frames::new(frame_count(duration_secs), secs(duration_secs), easing) {
+        let show_count = ((points.len() as f64 * progress.clamp(0.0, 1.0)) as usize)
             .max(1)
             .min(points.len());
 
@@ -148,15 +148,9 @@ pub fn chart_build(data: &str, duration_secs: f64) {
         let sparkline = render_sparkline_inline(&partial_data);
         print!("{}", sparkline);
         stdout.flush().unwrap();
-
-        thread::sleep(delay);
     }
 
-    // Final render
-    stdout.execute(MoveToColumn(0)).unwrap();
-    stdout.execute(Clear(ClearType::CurrentLine)).unwrap();
-    println!("{}", render_sparkline_inline(data));
-
+    println!();
     stdout.execute(Show).unwrap();
 }
 
@@ -190,15 +184,13 @@ fn render_sparkline_inline(data: &str) -> String {
 }
 
 /// Animate bar chart bars growing
-pub fn bars_build(data: &str, duration_secs: f64) {
+pub fn bars_build(data: &str, duration_secs: f64, easing: Easing) {
     use crossterm::{
-        cursor::{Hide, MoveTo, Show},
+        cursor::{Hide, MoveToPreviousLine, Show},
         terminal::{Clear, ClearType},
         ExecutableCommand,
     };
-    use std::io::{stdout, Write};
-    use std::thread;
-    use std::time::{Duration, Instant};
+    use std::io::Write;
 
     // Parse data: "Label:Value,Label:Value"
     let items: Vec<(&str, f64)> = data
@@ -220,51 +212,416 @@ pub fn bars_build(data: &str, duration_secs: f64) {
     let max_val = items.iter().map(|(_, v)| *v).fold(0.0_f64, f64::max);
     let bar_width = 20;
 
-    let mut stdout = stdout();
-    stdout.execute(Hide).unwrap();
-
-    // Print initial empty lines
-    for _ in 0..items.len() {
-        println!();
+    if !policy::enabled() {
+        for (label, value) in &items {
+            println!(
+                "{:>8} {} {:.0}",
+                label.bright_black(),
+                "█".repeat(bar_fill(*value, max_val, bar_width, 1.0)).cyan(),
+                value
+            );
+        }
+        return;
     }
 
-    let start = Instant::now();
-    let duration = Duration::from_secs_f64(duration_secs);
-    let delay = Duration::from_millis(50);
+    let mut stdout = color::stdout();
+    stdout.execute(Hide).unwrap();
 
-    loop {
-        let elapsed = start.elapsed();
-        let progress = (elapsed.as_secs_f64() / duration_secs).min(1.0);
+    let mut drawn = false;
+    for progress in Frames::new(frame_count(duration_secs), secs(duration_secs), easing) {
+        // Elastic and bounce curves overshoot; bars stop at their value
+        let progress = progress.clamp(0.0, 1.0);
+        if drawn {
+            stdout
+                .execute(MoveToPreviousLine(items.len() as u16))
+                .unwrap();
+        }
+        drawn = true;
 
-        // Move cursor up to redraw
-        for (i, (label, value)) in items.iter().enumerate() {
-            let row = items.len() - 1 - i;
-            stdout.execute(MoveTo(0, row as u16)).unwrap();
+        for (label, value) in &items {
             stdout.execute(Clear(ClearType::CurrentLine)).unwrap();
-
-            let current_val = value * progress;
-            let filled = ((current_val / max_val) * bar_width as f64) as usize;
-            let bar = "█".repeat(filled);
-
-            print!(
+            let bar = "█".repeat(bar_fill(*value, max_val, bar_width, progress));
+            println!(
                 "{:>8} {} {:.0}",
                 label.bright_black(),
                 bar.cyan(),
-                current_val
+                value * progress
             );
         }
         stdout.flush().unwrap();
+    }
+
+    stdout.execute(Show).unwrap();
+}
 
-        if elapsed >= duration {
-            break;
+/// Cells filled for `value` once `progress` of the animation has run
+fn bar_fill(value: f64, max: f64, width: usize, progress: f64) -> usize {
+    if max <= 0.0 {
+        return 0;
+    }
+    ((value * progress.clamp(0.0, 1.0) / max).max(0.0) * width as f64) as usize
+}
+
+/// One frame every 50ms, like the other timed effects
+fn frame_count(duration_secs: f64) -> usize {
+    (duration_secs / 0.05).round() as usize
+}
+
+fn secs(duration_secs: f64) -> std::time::Duration {
+    std::time::Duration::try_from_secs_f64(duration_secs).unwrap_or_default()
+}
+
+/// Look of the fullscreen effects (matrix, confetti, fireworks)
+#[derive(Debug, Clone)]
+pub struct SceneOptions {
+    /// How busy the screen gets, 0.0-1.0
+    pub density: f64,
+    /// Colors to draw with; each effect has its own default palette
+    pub colors: Vec<(u8, u8, u8)>,
+}
+
+impl SceneOptions {
+    /// `colors` is a comma-separated list of names or hex codes
+    pub fn new(density: f64, colors: Option<&str>) -> Self {
+        Self {
+            density: density.clamp(0.0, 1.0),
+            colors: colors
+                .map(|list| {
+                    list.split(',')
+                        .map(|c| crate::output::progress::parse_color(c.trim()))
+                        .collect()
+                })
+                .unwrap_or_default(),
         }
-        thread::sleep(delay);
     }
 
-    // Move below the chart
-    stdout.execute(MoveTo(0, items.len() as u16)).unwrap();
-    println!();
-    stdout.execute(Show).unwrap();
+    fn palette(&self, default: &[(u8, u8, u8)]) -> Vec<(u8, u8, u8)> {
+        if self.colors.is_empty() {
+            default.to_vec()
+        } else {
+            self.colors.clone()
+        }
+    }
+}
+
+const FESTIVE: [(u8, u8, u8); 6] = [
+    (255, 87, 87),
+    (255, 193, 7),
+    (76, 217, 100),
+    (0, 188, 212),
+    (156, 39, 176),
+    (255, 128, 171),
+];
+
+/// Katakana rain, one falling trail per column
+pub fn matrix(duration_secs: f64, options: &SceneOptions) {
+    const GLYPHS: &[char] = &[
+        'ｱ', 'ｲ', 'ｳ', 'ｴ', 'ｵ', 'ｶ', 'ｷ', 'ｸ', 'ｹ', 'ｺ', 'ｻ', 'ｼ', 'ｽ', 'ｾ', 'ｿ', 'ﾀ', 'ﾁ', 'ﾂ',
+        'ﾃ', 'ﾄ', 'ﾅ', 'ﾆ', 'ﾇ', 'ﾈ', 'ﾉ', '0', '1', '2', '3', '4', '5', '7', '8', '9', 'Z', ':',
+    ];
+    struct Trail {
+        head: f64,
+        speed: f64,
+        len: usize,
+    }
+
+    let color = options.palette(&[(0, 255, 70)])[0];
+    let mut trails: Vec<Option<Trail>> = Vec::new();
+    fullscreen(duration_secs, |canvas, rng, frame| {
+        trails.resize_with(canvas.cols, || None);
+        for (x, slot) in trails.iter_mut().enumerate() {
+            if slot.is_none() && rng.chance(options.density * 0.06) {
+                *slot = Some(Trail {
+                    head: 0.0,
+                    speed: rng.range(0.3, 1.0),
+                    len: rng.range(6.0, (canvas.rows as f64 / 2.0).max(7.0)) as usize,
+                });
+            }
+            let Some(trail) = slot else {
+                continue;
+            };
+            trail.head += trail.speed;
+            for k in 0..trail.len {
+                let y = trail.head - k as f64;
+                // Glyphs change now and then rather than every frame
+                let glyph = GLYPHS[(x * 31 + y as usize * 17 + frame / 8) % GLYPHS.len()];
+                let shade = if k == 0 {
+                    mix(color, (255, 255, 255), 0.7)
+                } else {
+                    fade(color, 1.0 - k as f64 / trail.len as f64)
+                };
+                canvas.plot(x as f64, y, glyph, shade);
+            }
+            if trail.head - trail.len as f64 > canvas.rows as f64 {
+                *slot = None;
+            }
+        }
+    });
+}
+
+/// Confetti fluttering down from the top
+pub fn confetti(duration_secs: f64, options: &SceneOptions) {
+    const PIECES: &[char] = &['▪', '▮', '•', '◆', '■', '●', '▴'];
+    struct Piece {
+        x: f64,
+        y: f64,
+        fall: f64,
+        phase: f64,
+        glyph: char,
+        color: (u8, u8, u8),
+    }
+
+    let palette = options.palette(&FESTIVE);
+    let mut pieces: Vec<Piece> = Vec::new();
+    fullscreen(duration_secs, |canvas, rng, frame| {
+        let spawn = (canvas.cols as f64 * options.density * 0.08).ceil() as usize;
+        for _ in 0..spawn {
+            pieces.push(Piece {
+                x: rng.range(0.0, canvas.cols as f64),
+                y: 0.0,
+                fall: rng.range(0.25, 0.7),
+                phase: rng.range(0.0, std::f64::consts::TAU),
+                glyph: *rng.pick(PIECES),
+                color: *rng.pick(&palette),
+            });
+        }
+        for piece in &mut pieces {
+            piece.y += piece.fall;
+            piece.x += (piece.phase + frame as f64 * 0.2).sin() * 0.4;
+            canvas.plot(piece.x, piece.y, piece.glyph, piece.color);
+        }
+        pieces.retain(|p| p.y < canvas.rows as f64);
+    });
+}
+
+/// Rockets rise from the bottom and burst into falling sparks
+pub fn fireworks(duration_secs: f64, options: &SceneOptions) {
+    struct Rocket {
+        x: f64,
+        y: f64,
+        burst_at: f64,
+        color: (u8, u8, u8),
+    }
+    struct Spark {
+        x: f64,
+        y: f64,
+        vx: f64,
+        vy: f64,
+        life: f64,
+        decay: f64,
+        color: (u8, u8, u8),
+    }
+
+    let palette = options.palette(&FESTIVE);
+    let mut rockets: Vec<Rocket> = Vec::new();
+    let mut sparks: Vec<Spark> = Vec::new();
+    fullscreen(duration_secs, |canvas, rng, _| {
+        let (cols, rows) = (canvas.cols as f64, canvas.rows as f64);
+        if rockets.is_empty() && sparks.is_empty() || rng.chance(options.density * 0.1) {
+            rockets.push(Rocket {
+                x: rng.range(cols * 0.1, cols * 0.9),
+                y: rows - 1.0,
+                burst_at: rng.range(rows * 0.15, rows * 0.5),
+                color: *rng.pick(&palette),
+            });
+        }
+
+        rockets.retain_mut(|rocket| {
+            rocket.y -= 0.8;
+            if rocket.y > rocket.burst_at {
+                canvas.plot(rocket.x, rocket.y, '|', fade(rocket.color, 0.6));
+                return true;
+            }
+            let count = rng.range(24.0, 40.0) as usize;
+            for _ in 0..count {
+                let angle = rng.range(0.0, std::f64::consts::TAU);
+                let speed = rng.range(0.3, 1.0);
+                sparks.push(Spark {
+                    x: rocket.x,
+                    y: rocket.y,
+                    // Cells are about twice as tall as wide
+                    vx: angle.cos() * speed * 2.0,
+                    vy: angle.sin() * speed,
+                    life: 1.0,
+                    decay: rng.range(0.025, 0.05),
+                    color: rocket.color,
+                });
+            }
+            false
+        });
+
+        for spark in &mut sparks {
+            spark.x += spark.vx;
+            spark.y += spark.vy;
+            spark.vx *= 0.92;
+            spark.vy = spark.vy * 0.92 + 0.04;
+            spark.life -= spark.decay;
+            let glyph = match spark.life {
+                l if l > 0.6 => '✦',
+                l if l > 0.3 => '*',
+                _ => '·',
+            };
+            canvas.plot(spark.x, spark.y, glyph, fade(spark.color, spark.life));
+        }
+        sparks.retain(|s| s.life > 0.0);
+    });
+}
+
+/// Run `draw` on a fresh canvas each frame in the alternate screen until
+/// `duration_secs` pass or the user quits (q, Esc or Ctrl+C)
+///
+/// Nothing is drawn when stdout isn't a terminal or animations are off.
+fn fullscreen<F>(duration_secs: f64, mut draw: F)
+where
+    F: FnMut(&mut Canvas, &mut Rng, usize),
+{
+    use crate::interactive::screen::{self, Screen};
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+    use std::io::{IsTerminal, Write};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    if !std::io::stdout().is_terminal() || !policy::enabled() {
+        return;
+    }
+
+    let animator = Animator::default();
+    let keys = std::io::stdin().is_terminal() && terminal::enable_raw_mode().is_ok();
+    let mut stdout = color::stdout();
+    let Ok(area) = Screen::enter(&mut stdout, None) else {
+        if keys {
+            terminal::disable_raw_mode().ok();
+        }
+        return;
+    };
+
+    let mut rng = Rng::seeded();
+    let start = Instant::now();
+    let mut frame = 0;
+    while animator.is_running() && start.elapsed().as_secs_f64() < duration_secs {
+        while keys && event::poll(Duration::ZERO).unwrap_or(false) {
+            if let Ok(Event::Key(key)) = event::read() {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.kind == KeyEventKind::Press
+                    && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+                {
+                    animator.stop();
+                }
+            }
+        }
+
+        let (cols, rows) = screen::size();
+        let mut canvas = Canvas::new(cols as usize, rows as usize);
+        draw(&mut canvas, &mut rng, frame);
+        canvas.draw(&mut stdout).ok();
+        stdout.flush().ok();
+        frame += 1;
+        thread::sleep(Duration::from_millis(40));
+    }
+
+    area.leave(&mut stdout).ok();
+    if keys {
+        terminal::disable_raw_mode().ok();
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+/// One frame of a fullscreen effect: a glyph and color per cell
+struct Canvas {
+    cols: usize,
+    rows: usize,
+    cells: Vec<Option<(char, Rgb)>>,
+}
+
+impl Canvas {
+    fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            cells: vec![None; cols * rows],
+        }
+    }
+
+    /// Set the cell at (x, y); points off the screen are dropped
+    fn plot(&mut self, x: f64, y: f64, glyph: char, color: (u8, u8, u8)) {
+        if x < 0.0 || y < 0.0 {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if x < self.cols && y < self.rows {
+            self.cells[y * self.cols + x] = Some((glyph, color));
+        }
+    }
+
+    fn draw(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
+        use crossterm::{cursor::MoveTo, QueueableCommand};
+
+        for (y, row) in self.cells.chunks(self.cols.max(1)).enumerate() {
+            let mut line = String::new();
+            for cell in row {
+                match cell {
+                    Some((glyph, (r, g, b))) => {
+                        line.push_str(&format!("\x1b[38;2;{};{};{}m{}", r, g, b, glyph))
+                    }
+                    None => line.push(' '),
+                }
+            }
+            out.queue(MoveTo(0, y as u16))?;
+            write!(out, "{}\x1b[0m", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Small xorshift generator; effects only need something that looks random
+struct Rng(u64);
+
+impl Rng {
+    fn seeded() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Rng(nanos | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in `low..high`
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        low + unit * (high - low)
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        self.range(0.0, 1.0) < p
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next() as usize % items.len()]
+    }
+}
+
+fn fade((r, g, b): (u8, u8, u8), amount: f64) -> (u8, u8, u8) {
+    let amount = amount.clamp(0.0, 1.0);
+    (
+        (r as f64 * amount) as u8,
+        (g as f64 * amount) as u8,
+        (b as f64 * amount) as u8,
+    )
+}
+
+fn mix(a: (u8, u8, u8), b: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let lerp = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t) as u8;
+    (lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
 }
 
 /// Run an animation effect by name
@@ -280,9 +637,11 @@ pub fn run(
     style: &str,
     prefix: Option<&str>,
     suffix: Option<&str>,
+    easing: Easing,
+    scene: &SceneOptions,
 ) {
     match effect_type {
-        "progress" => progress(duration, style),
+        "progress" => progress(duration, style, easing),
         "typewriter" => {
             if let Some(t) = text {
                 typewriter(t, speed);
@@ -290,21 +649,44 @@ pub fn run(
                 eprintln!("Error: --text required for typewriter effect");
             }
         }
-        "counter" => counter(from, to, duration, prefix.unwrap_or(""), suffix.unwrap_or("")),
+        "counter" => counter(
+            from,
+            to,
+            duration,
+            prefix.unwrap_or(""),
+            suffix.unwrap_or(""),
+            easing,
+        ),
         "chart-build" | "sparkline" => {
             if let Some(d) = data {
-                chart_build(d, duration);
+                chart_build(d, duration, easing);
             } else {
                 eprintln!("Error: --data required for chart-build effect");
             }
         }
         "bars" | "bar-build" => {
             if let Some(d) = data {
-                bars_build(d, duration);
+                bars_build(d, duration, easing);
             } else {
                 eprintln!("Error: --data required for bars effect");
             }
         }
-        _ => eprintln!("Unknown animation type: {}. Available: progress, typewriter, counter, chart-build, bars", effect_type),
+        "matrix" => matrix(duration, scene),
+        "confetti" => confetti(duration, scene),
+        "fireworks" => fireworks(duration, scene),
+        _ => eprintln!("Unknown animation type: {}. Available: progress, typewriter, counter, chart-build, bars, matrix, confetti, fireworks", effect_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_fill_never_overshoots() {
+        assert_eq!(bar_fill(50.0, 100.0, 20, 1.0), 10);
+        assert_eq!(bar_fill(100.0, 100.0, 20, 1.3), 20);
+        assert_eq!(bar_fill(100.0, 100.0, 20, -0.2), 0);
+        assert_eq!(bar_fill(10.0, 0.0, 20, 1.0), 0);
     }
 }
@@ -1,9 +1,12 @@
+use crate::animation::easing::Easing;
+use crate::animation::policy;
+use crate::output::color;
 use crossterm::{
-    cursor::{Hide, MoveTo, MoveToColumn, Show},
+    cursor::{Hide, MoveTo, MoveToColumn, MoveToPreviousLine, Show},
     terminal::{Clear, ClearType},
     ExecutableCommand,
 };
-use std::io::{stdout, Write};
+use std::io::{IsTerminal, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -14,6 +17,7 @@ use std::time::{Duration, Instant};
 pub struct Animator {
     running: Arc<AtomicBool>,
     frame_delay: Duration,
+    easing: Easing,
 }
 
 #[allow(dead_code)]
@@ -30,16 +34,27 @@ impl Animator {
         Self {
             running,
             frame_delay: Duration::from_millis(frame_delay_ms),
+            easing: Easing::Linear,
         }
     }
 
+    /// Shape the progress passed to timed animations with `easing`
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
     /// Run animation loop for a fixed duration
     /// render_fn takes (frame_index, progress 0.0-1.0) and returns content to display
     pub fn run_timed<F>(&self, duration_secs: f64, mut render_fn: F)
     where
         F: FnMut(usize, f64) -> String,
     {
-        let mut stdout = stdout();
+        if !policy::enabled() {
+            println!("{}", render_fn(0, 1.0));
+            return;
+        }
+        let mut stdout = color::stdout();
         stdout.execute(Hide).unwrap();
 
         let start = Instant::now();
@@ -52,7 +67,7 @@ impl Animator {
                 break;
             }
 
-            let progress = elapsed.as_secs_f64() / duration_secs;
+            let progress = self.easing.apply(elapsed.as_secs_f64() / duration_secs);
 
             // Clear current line and render
             stdout.execute(MoveToColumn(0)).unwrap();
@@ -81,7 +96,13 @@ impl Animator {
     where
         F: FnMut(usize, f64) -> Vec<String>,
     {
-        let mut stdout = stdout();
+        if !policy::enabled() {
+            for line in render_fn(0, 1.0) {
+                println!("{}", line);
+            }
+            return;
+        }
+        let mut stdout = color::stdout();
         stdout.execute(Hide).unwrap();
 
         // Print initial empty lines
@@ -99,7 +120,7 @@ impl Animator {
                 break;
             }
 
-            let progress = elapsed.as_secs_f64() / duration_secs;
+            let progress = self.easing.apply(elapsed.as_secs_f64() / duration_secs);
             let contents = render_fn(frame, progress);
 
             // Move up and redraw all lines
@@ -124,6 +145,64 @@ impl Animator {
         stdout.flush().unwrap();
     }
 
+    /// Redraw a block of lines in place each frame until `render_fn` returns
+    /// `None`, then leave the last block on screen
+    ///
+    /// render_fn takes the frame index and the time since the start. Returns
+    /// false when cancelled by Ctrl+C or [`Animator::stop`]. When stdout isn't
+    /// a terminal only the last block is printed.
+    pub fn run_block<F>(&self, mut render_fn: F) -> bool
+    where
+        F: FnMut(usize, Duration) -> Option<Vec<String>>,
+    {
+        let mut stdout = color::stdout();
+        let redraw = std::io::stdout().is_terminal();
+        if redraw {
+            stdout.execute(Hide).ok();
+        }
+
+        let start = Instant::now();
+        let mut last: Vec<String> = Vec::new();
+        let mut drawn = 0;
+        let mut frame = 0;
+
+        while self.running.load(Ordering::SeqCst) {
+            let Some(lines) = render_fn(frame, start.elapsed()) else {
+                break;
+            };
+            if redraw {
+                if drawn > 0 {
+                    stdout.execute(MoveToPreviousLine(drawn as u16)).ok();
+                }
+                stdout.execute(Clear(ClearType::FromCursorDown)).ok();
+                // \r\n so lines start at column 0 in raw mode too
+                for line in &lines {
+                    write!(stdout, "{}\r\n", line).ok();
+                }
+                stdout.flush().ok();
+                drawn = lines.len();
+            }
+            last = lines;
+            frame += 1;
+            thread::sleep(self.frame_delay);
+        }
+
+        if !redraw {
+            for line in &last {
+                writeln!(stdout, "{}", line).ok();
+            }
+        } else {
+            stdout.execute(Show).ok();
+        }
+        stdout.flush().ok();
+        self.is_running()
+    }
+
+    /// Cancel the animation, as Ctrl+C does
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
     /// Check if animation is still running (not cancelled)
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
@@ -135,3 +214,46 @@ impl Default for Animator {
         Self::new(50) // 50ms = 20fps for smooth animations
     }
 }
+
+/// Frame scheduler for step-based animations
+///
+/// Yields the eased progress (0.0-1.0) of `steps + 1` frames spread evenly
+/// over `duration`, sleeping between frames. With animations off only the
+/// final frame is yielded.
+pub struct Frames {
+    step: usize,
+    steps: usize,
+    started: bool,
+    delay: Duration,
+    easing: Easing,
+}
+
+impl Frames {
+    pub fn new(steps: usize, duration: Duration, easing: Easing) -> Self {
+        let steps = steps.max(1);
+        Self {
+            step: if policy::enabled() { 0 } else { steps },
+            steps,
+            started: false,
+            delay: duration / steps as u32,
+            easing,
+        }
+    }
+}
+
+impl Iterator for Frames {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        if self.step > self.steps {
+            return None;
+        }
+        if self.started {
+            thread::sleep(self.delay);
+        }
+        self.started = true;
+        let progress = self.easing.apply(self.step as f64 / self.steps as f64);
+        self.step += 1;
+        Some(progress)
+    }
+}
@@ -1,3 +1,5 @@
 pub mod demo;
+pub mod easing;
 pub mod effects;
 pub mod engine;
+pub mod policy;
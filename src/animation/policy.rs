@@ -0,0 +1,62 @@
+//! Whether animations play this run
+//!
+//! Every animated renderer asks [`enabled`] before drawing frames; when
+//! animations are off it prints the finished output straight away, so CI
+//! logs get one clean copy instead of a stream of redraws.
+
+use std::env;
+use std::sync::OnceLock;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Fix the policy for this run; `--no-animation` wins over the environment.
+/// Later calls have no effect.
+pub fn init(no_animation: bool) {
+    ENABLED.get_or_init(|| !no_animation && from_env(|name| env::var(name).ok()));
+}
+
+/// Whether animations play; read from the environment until [`init`]
+pub fn enabled() -> bool {
+    *ENABLED.get_or_init(|| from_env(|name| env::var(name).ok()))
+}
+
+/// `animate` unless animations are off for this run
+pub fn gate(animate: bool) -> bool {
+    animate && enabled()
+}
+
+/// Off when TERMGFX_NO_ANIMATION is set to anything but `0`, or when
+/// TERMGFX_ANIMATE is `0`, `false`, `no`, `off` or `never`
+fn from_env(var: impl Fn(&str) -> Option<String>) -> bool {
+    if var("TERMGFX_NO_ANIMATION").is_some_and(|v| !v.is_empty() && v != "0") {
+        return false;
+    }
+    !var("TERMGFX_ANIMATE").is_some_and(|v| {
+        matches!(
+            v.trim().to_lowercase().as_str(),
+            "0" | "false" | "no" | "off" | "never"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_from_env() {
+        assert!(from_env(env_of(&[])));
+        assert!(!from_env(env_of(&[("TERMGFX_NO_ANIMATION", "1")])));
+        assert!(from_env(env_of(&[("TERMGFX_NO_ANIMATION", "0")])));
+        assert!(!from_env(env_of(&[("TERMGFX_ANIMATE", "false")])));
+        assert!(from_env(env_of(&[("TERMGFX_ANIMATE", "1")])));
+    }
+}
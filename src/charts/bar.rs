@@ -1,12 +1,16 @@
+use crate::animation::easing::Easing;
+use crate::animation::engine::Frames;
+use crate::animation::policy;
+use crate::output::color;
+use crate::output::profile::{self, Phase};
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
+    cursor::{Hide, MoveToPreviousLine, Show},
     terminal::{Clear, ClearType},
     ExecutableCommand,
 };
 use owo_colors::OwoColorize;
-use std::io::{stdout, IsTerminal, Write};
-use std::thread;
-use std::time::{Duration, Instant};
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
 
 const COLORS: [u8; 8] = [
     196, // Red
@@ -19,45 +23,45 @@ const COLORS: [u8; 8] = [
     201, // Magenta
 ];
 
-pub fn render(data: &str) {
-    render_animated(data, false);
-}
-
 /// Render bar chart with optional animation
-pub fn render_animated(data: &str, animate: bool) {
-    let entries = parse_data(data);
+pub fn render_animated(data: &str, animate: bool, easing: Easing) {
+    let animate = policy::gate(animate);
+    let entries = profile::time(Phase::Parse, || parse_data(data));
 
     if entries.is_empty() {
         eprintln!("Error: No valid data provided");
         return;
     }
 
-    // Find max value for scaling
-    let max_value = entries
-        .iter()
-        .map(|(_, v)| *v)
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(1.0);
-
-    // Get terminal width, default to 80
-    let term_width = crossterm::terminal::size()
-        .map(|(w, _)| w as usize)
-        .unwrap_or(80);
-
-    // Calculate max label width
-    let max_label_width = entries
-        .iter()
-        .map(|(label, _)| label.len())
-        .max()
-        .unwrap_or(0);
-
-    // Reserve space for label, spacing, value display
-    let value_display_width = max_value.to_string().len().max(6); // At least 6 for "100.00"
-    let available_width = term_width.saturating_sub(max_label_width + value_display_width + 5);
-    let bar_max_width = available_width.max(20); // Minimum 20 chars for bars
+    let (max_value, max_label_width, bar_max_width) = profile::time(Phase::Layout, || {
+        // Find max value for scaling
+        let max_value = entries
+            .iter()
+            .map(|(_, v)| *v)
+            .max_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap_or(1.0);
+
+        // Get terminal width, default to 80
+        let term_width = crossterm::terminal::size()
+            .map(|(w, _)| w as usize)
+            .unwrap_or(80);
+
+        // Calculate max label width
+        let max_label_width = entries
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+
+        // Reserve space for label, spacing, value display
+        let value_display_width = max_value.to_string().len().max(6); // At least 6 for "100.00"
+        let available_width = term_width.saturating_sub(max_label_width + value_display_width + 5);
+        let bar_max_width = available_width.max(20); // Minimum 20 chars for bars
+        (max_value, max_label_width, bar_max_width)
+    });
 
     if animate && std::io::stdout().is_terminal() {
-        render_animated_bars(&entries, max_value, max_label_width, bar_max_width);
+        render_animated_bars(&entries, max_value, max_label_width, bar_max_width, easing);
     } else {
         render_static_bars(&entries, max_value, max_label_width, bar_max_width);
     }
@@ -100,27 +104,23 @@ fn render_animated_bars(
     max_value: f64,
     max_label_width: usize,
     bar_max_width: usize,
+    easing: Easing,
 ) {
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
     stdout.execute(Hide).unwrap();
 
-    // Print initial empty lines
-    for _ in 0..entries.len() {
-        println!();
-    }
-
-    let start = Instant::now();
-    let duration = Duration::from_secs_f64(1.5);
-    let delay = Duration::from_millis(50);
-
-    loop {
-        let elapsed = start.elapsed();
-        let progress = (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0);
+    let mut drawn = false;
+    for progress in Frames::new(30, Duration::from_millis(1500), easing) {
+        // Elastic and bounce curves overshoot; bars never grow past their value
+        let progress = progress.clamp(0.0, 1.0);
+        if drawn {
+            stdout
+                .execute(MoveToPreviousLine(entries.len() as u16))
+                .unwrap();
+        }
+        drawn = true;
 
-        // Move cursor up to redraw
         for (i, (label, value)) in entries.iter().enumerate() {
-            let row = (entries.len() - 1 - i) as u16;
-            stdout.execute(MoveTo(0, row)).unwrap();
             stdout.execute(Clear(ClearType::CurrentLine)).unwrap();
 
             let color = COLORS[i % COLORS.len()];
@@ -139,7 +139,7 @@ fn render_animated_bars(
                 format!("{:.2}", current_val)
             };
 
-            print!(
+            println!(
                 "{:<width$}  {}  {}",
                 label.truecolor(200, 200, 200),
                 bar.color(owo_colors::XtermColors::from(color)),
@@ -148,14 +148,8 @@ fn render_animated_bars(
             );
         }
         stdout.flush().unwrap();
-
-        if elapsed >= duration {
-            break;
-        }
-        thread::sleep(delay);
     }
 
-    stdout.execute(MoveTo(0, entries.len() as u16)).unwrap();
     stdout.execute(Show).unwrap();
 }
 
@@ -0,0 +1,290 @@
+use super::format_value;
+use crate::text;
+use owo_colors::OwoColorize;
+
+const COLORS: [u8; 8] = [
+    196, // Red
+    208, // Orange
+    226, // Yellow
+    46,  // Green
+    51,  // Cyan
+    21,  // Blue
+    129, // Purple
+    201, // Magenta
+];
+
+/// Density shades for violin mode, from empty to densest
+const SHADES: [char; 6] = [' ', '·', '░', '▒', '▓', '█'];
+
+/// Five-number summary plus outliers for one series
+#[derive(Debug, PartialEq)]
+struct Summary {
+    q1: f64,
+    median: f64,
+    q3: f64,
+    low_whisker: f64,
+    high_whisker: f64,
+    outliers: Vec<f64>,
+}
+
+/// Render a box plot (or violin plot) for "name:v1,v2;name:v1,v2" data
+pub fn render(data: &str, violin: bool) {
+    let series = parse_data(data);
+    if series.is_empty() {
+        eprintln!("Error: No valid data provided");
+        std::process::exit(1);
+    }
+
+    let min = series
+        .iter()
+        .flat_map(|(_, v)| v.iter().copied())
+        .fold(f64::INFINITY, f64::min);
+    let max = series
+        .iter()
+        .flat_map(|(_, v)| v.iter().copied())
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let term_width = crossterm::terminal::size()
+        .map(|(w, _)| w as usize)
+        .unwrap_or(80);
+    let label_width = series
+        .iter()
+        .map(|(l, _)| text::display_width(l))
+        .max()
+        .unwrap_or(0);
+    let plot_width = term_width.saturating_sub(label_width + 16).clamp(20, 80);
+
+    for (idx, (label, values)) in series.iter().enumerate() {
+        let color = owo_colors::XtermColors::from(COLORS[idx % COLORS.len()]);
+        let summary = summarize(values);
+        let row = if violin {
+            violin_row(values, &summary, min, max, plot_width)
+        } else {
+            box_row(&summary, min, max, plot_width)
+        };
+        println!(
+            "{}{} {}  {}",
+            " ".repeat(label_width - text::display_width(label)),
+            label.truecolor(200, 200, 200),
+            row.color(color),
+            format!("med {}", format_value(summary.median)).truecolor(150, 150, 150),
+        );
+    }
+
+    // Shared value axis
+    let mid = (min + max) / 2.0;
+    let mut axis = vec!['─'; plot_width];
+    axis[0] = '└';
+    axis[plot_width - 1] = '┘';
+    axis[plot_width / 2] = '┴';
+    println!(
+        "{:>width$} {}",
+        "",
+        axis.iter().collect::<String>().truecolor(100, 100, 100),
+        width = label_width
+    );
+    let (min_str, mid_str, max_str) = (format_value(min), format_value(mid), format_value(max));
+    let mid_col = (plot_width / 2).saturating_sub(mid_str.len() / 2);
+    let mut labels = min_str.clone();
+    labels.push_str(&" ".repeat(mid_col.saturating_sub(labels.len()).max(1)));
+    labels.push_str(&mid_str);
+    labels.push_str(
+        &" ".repeat(
+            plot_width
+                .saturating_sub(labels.len() + max_str.len())
+                .max(1),
+        ),
+    );
+    labels.push_str(&max_str);
+    println!(
+        "{:>width$} {}",
+        "",
+        labels.truecolor(150, 150, 150),
+        width = label_width
+    );
+}
+
+/// Compute quartiles (linear interpolation) and Tukey whiskers at 1.5×IQR
+fn summarize(values: &[f64]) -> Summary {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let q1 = quantile(&sorted, 0.25);
+    let median = quantile(&sorted, 0.5);
+    let q3 = quantile(&sorted, 0.75);
+    let iqr = q3 - q1;
+    let (low_fence, high_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+    let inside: Vec<f64> = sorted
+        .iter()
+        .copied()
+        .filter(|v| *v >= low_fence && *v <= high_fence)
+        .collect();
+    let outliers = sorted
+        .iter()
+        .copied()
+        .filter(|v| *v < low_fence || *v > high_fence)
+        .collect();
+
+    Summary {
+        q1,
+        median,
+        q3,
+        low_whisker: inside.first().copied().unwrap_or(q1),
+        high_whisker: inside.last().copied().unwrap_or(q3),
+        outliers,
+    }
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+/// Map a value onto a column in `0..width`
+fn column(value: f64, min: f64, max: f64, width: usize) -> usize {
+    if max <= min {
+        return width / 2;
+    }
+    (((value - min) / (max - min)) * (width - 1) as f64).round() as usize
+}
+
+/// ├───▓▓▓┃▓▓▓───┤  ○
+fn box_row(summary: &Summary, min: f64, max: f64, width: usize) -> String {
+    let mut row = vec![' '; width];
+    let lw = column(summary.low_whisker, min, max, width);
+    let hw = column(summary.high_whisker, min, max, width);
+    let q1 = column(summary.q1, min, max, width);
+    let q3 = column(summary.q3, min, max, width);
+
+    for cell in row.iter_mut().take(hw + 1).skip(lw) {
+        *cell = '─';
+    }
+    for cell in row.iter_mut().take(q3 + 1).skip(q1) {
+        *cell = '▓';
+    }
+    if lw < q1 {
+        row[lw] = '├';
+    }
+    if hw > q3 {
+        row[hw] = '┤';
+    }
+    row[column(summary.median, min, max, width)] = '┃';
+    for &outlier in &summary.outliers {
+        row[column(outlier, min, max, width)] = '○';
+    }
+    row.into_iter().collect()
+}
+
+/// Shade each column by a Gaussian kernel density estimate of the values
+fn violin_row(values: &[f64], summary: &Summary, min: f64, max: f64, width: usize) -> String {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    // Silverman's rule of thumb, with a floor so constant series still render
+    let span = (max - min).max(f64::EPSILON);
+    let bandwidth = (1.06 * std_dev * n.powf(-0.2)).max(span / width as f64);
+
+    let density: Vec<f64> = (0..width)
+        .map(|col| {
+            let x = min + span * col as f64 / (width - 1) as f64;
+            values
+                .iter()
+                .map(|v| (-0.5 * ((x - v) / bandwidth).powi(2)).exp())
+                .sum()
+        })
+        .collect();
+    let peak = density.iter().copied().fold(0.0, f64::max);
+
+    let mut row: Vec<char> = density
+        .iter()
+        .map(|d| {
+            let level = if peak > 0.0 { d / peak } else { 0.0 };
+            SHADES[((level * (SHADES.len() - 1) as f64).round() as usize).min(SHADES.len() - 1)]
+        })
+        .collect();
+    row[column(summary.median, min, max, width)] = '┃';
+    row.into_iter().collect()
+}
+
+fn parse_data(data: &str) -> Vec<(String, Vec<f64>)> {
+    data.split(';')
+        .filter_map(|entry| {
+            let (label, values) = match entry.split_once(':') {
+                Some(parts) => parts,
+                None => {
+                    eprintln!("Warning: Invalid series '{}' (expected name:v1,v2)", entry);
+                    return None;
+                }
+            };
+            let values: Vec<f64> = values
+                .split(',')
+                .filter_map(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite())
+                .collect();
+            if values.is_empty() {
+                eprintln!("Warning: Series '{}' has no numeric values", label.trim());
+                return None;
+            }
+            Some((label.trim().to_string(), values))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_data_multiple_series() {
+        let result = parse_data("run1:1,2,3,4;run2:2,3,5,8");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0], ("run1".to_string(), vec![1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(result[1].1, vec![2.0, 3.0, 5.0, 8.0]);
+    }
+
+    #[test]
+    fn test_parse_data_skips_invalid_series() {
+        let result = parse_data("good:1,2;bad;empty:x,y");
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_summarize_quartiles() {
+        let summary = summarize(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(summary.q1, 2.0);
+        assert_eq!(summary.median, 3.0);
+        assert_eq!(summary.q3, 4.0);
+        assert!(summary.outliers.is_empty());
+    }
+
+    #[test]
+    fn test_summarize_outliers() {
+        let summary = summarize(&[1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 50.0]);
+        assert_eq!(summary.outliers, vec![50.0]);
+        assert_eq!(summary.high_whisker, 4.0);
+    }
+
+    #[test]
+    fn test_box_row_markers() {
+        let summary = summarize(&[0.0, 25.0, 50.0, 75.0, 100.0]);
+        let row = box_row(&summary, 0.0, 100.0, 21);
+        assert!(row.starts_with('├'));
+        assert!(row.ends_with('┤'));
+        assert_eq!(row.chars().nth(10), Some('┃'));
+    }
+
+    #[test]
+    fn test_violin_row_width() {
+        let values = [1.0, 2.0, 2.0, 3.0];
+        let summary = summarize(&values);
+        let row = violin_row(&values, &summary, 1.0, 3.0, 30);
+        assert_eq!(row.chars().count(), 30);
+        assert!(row.contains('┃'));
+    }
+}
@@ -0,0 +1,179 @@
+use super::format_value;
+use crate::output::style::StylePreset;
+use crate::text;
+use owo_colors::OwoColorize;
+
+/// Render a funnel chart for "Stage:Value,Stage:Value" data
+pub fn render(data: &str, style: &str) {
+    let preset = match StylePreset::find(style) {
+        Some(p) => p,
+        None => {
+            eprintln!(
+                "Error: Unknown style '{}'. Run 'termgfx style list' for options",
+                style
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let stages = match parse_data(data) {
+        Ok(stages) => stages,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let term_width = crossterm::terminal::size()
+        .map(|(w, _)| w as usize)
+        .unwrap_or(80);
+    let label_width = stages
+        .iter()
+        .map(|(l, _)| text::display_width(l))
+        .max()
+        .unwrap_or(0);
+    let value_width = stages
+        .iter()
+        .map(|(_, v)| format_value(*v).len())
+        .max()
+        .unwrap_or(0);
+    // Leave room for the label, value and the "↓ 100.0% of prev · 100.0% of total" note
+    let funnel_width = term_width
+        .saturating_sub(label_width + value_width + 40)
+        .clamp(10, 60);
+
+    let rows = layout(&stages, funnel_width);
+    let total = stages[0].1;
+    let last = stages.len().saturating_sub(1).max(1) as f64;
+
+    for (idx, ((label, value), (pad, bar))) in stages.iter().zip(&rows).enumerate() {
+        let color = blend(
+            preset.colors.primary,
+            preset.colors.secondary,
+            idx as f64 / last,
+        );
+        let note = if idx == 0 {
+            "100%".to_string()
+        } else {
+            format!(
+                "↓ {} of prev · {} of total",
+                percent(*value, stages[idx - 1].1),
+                percent(*value, total)
+            )
+        };
+        println!(
+            "{}{} {}{}{} {:>vw$}  {}",
+            " ".repeat(label_width - text::display_width(label)),
+            label.truecolor(200, 200, 200),
+            " ".repeat(*pad),
+            "█".repeat(*bar).truecolor(color.0, color.1, color.2),
+            " ".repeat(funnel_width - pad - bar),
+            format_value(*value).bold(),
+            note.truecolor(150, 150, 150),
+            vw = value_width
+        );
+    }
+}
+
+/// Left padding and bar width for each stage, centered within `width` columns
+fn layout(stages: &[(String, f64)], width: usize) -> Vec<(usize, usize)> {
+    let max = stages.iter().map(|(_, v)| *v).fold(0.0_f64, f64::max);
+    stages
+        .iter()
+        .map(|(_, value)| {
+            let bar = if max > 0.0 {
+                ((value / max) * width as f64).round() as usize
+            } else {
+                0
+            };
+            // Keep non-zero stages visible
+            let bar = if *value > 0.0 { bar.max(1) } else { bar };
+            ((width - bar) / 2, bar)
+        })
+        .collect()
+}
+
+/// "30.0%" style share of `part` in `whole`, or "—" when undefined
+fn percent(part: f64, whole: f64) -> String {
+    if whole > 0.0 {
+        format!("{:.1}%", part / whole * 100.0)
+    } else {
+        "—".to_string()
+    }
+}
+
+/// Linear blend between two RGB colors
+fn blend(from: (u8, u8, u8), to: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+fn parse_data(data: &str) -> Result<Vec<(String, f64)>, String> {
+    let mut stages = Vec::new();
+    for entry in data.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (label, value) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid stage '{}' (expected Label:Value)", entry))?;
+        let (label, value) = (label.trim(), value.trim());
+        let value: f64 = value
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' for {}", value, label))?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(format!("Stage '{}' must be a non-negative number", label));
+        }
+        stages.push((label.to_string(), value));
+    }
+
+    if stages.is_empty() {
+        return Err("No valid data provided".to_string());
+    }
+    Ok(stages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_data() {
+        let stages = parse_data("Visits:1000, Signups:300,Paid:50").unwrap();
+        assert_eq!(stages.len(), 3);
+        assert_eq!(stages[1], ("Signups".to_string(), 300.0));
+    }
+
+    #[test]
+    fn test_parse_data_rejects_invalid() {
+        assert!(parse_data("Visits").is_err());
+        assert!(parse_data("Visits:abc").is_err());
+        assert!(parse_data("Visits:-5").is_err());
+        assert!(parse_data("").is_err());
+    }
+
+    #[test]
+    fn test_layout_centers_bars() {
+        let stages = parse_data("A:100,B:50,C:0").unwrap();
+        let rows = layout(&stages, 40);
+        assert_eq!(rows[0], (0, 40));
+        assert_eq!(rows[1], (10, 20));
+        assert_eq!(rows[2], (20, 0));
+    }
+
+    #[test]
+    fn test_layout_keeps_small_stages_visible() {
+        let stages = parse_data("A:10000,B:1").unwrap();
+        assert_eq!(layout(&stages, 20)[1].1, 1);
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(300.0, 1000.0), "30.0%");
+        assert_eq!(percent(50.0, 300.0), "16.7%");
+        assert_eq!(percent(5.0, 0.0), "—");
+    }
+
+    #[test]
+    fn test_blend_endpoints() {
+        assert_eq!(blend((0, 0, 0), (200, 100, 50), 0.0), (0, 0, 0));
+        assert_eq!(blend((0, 0, 0), (200, 100, 50), 1.0), (200, 100, 50));
+    }
+}
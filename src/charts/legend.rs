@@ -0,0 +1,316 @@
+//! Legends for charts with several series or slices
+//!
+//! `auto` puts the legend beside the chart when both fit the terminal,
+//! under it when its labels fit there, and otherwise collapses it to a row
+//! of swatches. `toggle` shows the collapsed row and expands it on `l`.
+
+use crate::output::color;
+use crate::text;
+use crossterm::cursor::MoveUp;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::ExecutableCommand;
+use std::io::{self, IsTerminal, Write};
+
+/// Names accepted by [`LegendPosition::from_name`]
+pub const POSITIONS: [&str; 6] = ["auto", "right", "below", "collapsed", "toggle", "none"];
+
+/// Columns between a chart and the legend on its right
+const GAP: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LegendPosition {
+    /// Right, below or collapsed, whichever fits the width
+    #[default]
+    Auto,
+    Right,
+    Below,
+    /// Swatches only, on one line
+    Collapsed,
+    /// Collapsed, expanding on a key press in a terminal
+    Toggle,
+    None,
+}
+
+impl LegendPosition {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(LegendPosition::Auto),
+            "right" => Some(LegendPosition::Right),
+            "below" | "bottom" => Some(LegendPosition::Below),
+            "collapsed" => Some(LegendPosition::Collapsed),
+            "toggle" => Some(LegendPosition::Toggle),
+            "none" => Some(LegendPosition::None),
+            _ => None,
+        }
+    }
+}
+
+/// A colored swatch and what it stands for
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendEntry {
+    pub swatch: String,
+    pub label: String,
+}
+
+impl LegendEntry {
+    pub fn new(swatch: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            swatch: swatch.into(),
+            label: label.into(),
+        }
+    }
+
+    fn line(&self) -> String {
+        format!("{} {}", self.swatch, self.label)
+    }
+}
+
+fn terminal_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(w, _)| w as usize)
+        .unwrap_or(80)
+}
+
+/// The placement `auto` settles on for `chart` and `entries` in `width`
+fn resolve(
+    position: LegendPosition,
+    chart: &[String],
+    entries: &[LegendEntry],
+    width: usize,
+) -> LegendPosition {
+    if entries.is_empty() {
+        return LegendPosition::None;
+    }
+    if position != LegendPosition::Auto {
+        return position;
+    }
+    let chart_width = chart.iter().map(|l| text::display_width(l)).max();
+    let legend_width = entries.iter().map(|e| text::display_width(&e.line())).max();
+    let legend_width = legend_width.unwrap_or(0);
+    if chart_width.unwrap_or(0) + GAP + legend_width <= width {
+        LegendPosition::Right
+    } else if legend_width + 2 <= width {
+        LegendPosition::Below
+    } else {
+        LegendPosition::Collapsed
+    }
+}
+
+/// `chart` with the legend placed; `toggle` draws the collapsed row
+pub fn compose(
+    chart: &[String],
+    entries: &[LegendEntry],
+    position: LegendPosition,
+    width: usize,
+) -> Vec<String> {
+    let mut lines: Vec<String> = chart.to_vec();
+    match resolve(position, chart, entries, width) {
+        LegendPosition::Right => {
+            let chart_width = chart.iter().map(|l| text::display_width(l)).max();
+            let column = chart_width.unwrap_or(0) + GAP;
+            let room = width.saturating_sub(column).max(1);
+            // Centered against the chart, which may have blank rows around it
+            let top = lines.len().saturating_sub(entries.len()) / 2;
+            lines.resize(lines.len().max(entries.len()), String::new());
+            for (line, entry) in lines.iter_mut().skip(top).zip(entries) {
+                let pad = column.saturating_sub(text::display_width(line));
+                line.push_str(&" ".repeat(pad));
+                line.push_str(&text::truncate(&entry.line(), room, "…"));
+            }
+        }
+        LegendPosition::Below => {
+            lines.push(String::new());
+            lines.extend(below(entries, width));
+        }
+        LegendPosition::Collapsed => {
+            lines.push(String::new());
+            lines.push(collapsed(entries, "--legend below lists them", width));
+        }
+        LegendPosition::Toggle => {
+            lines.push(String::new());
+            lines.push(collapsed(entries, "l: legend", width));
+        }
+        LegendPosition::Auto | LegendPosition::None => {}
+    }
+    lines
+}
+
+fn below(entries: &[LegendEntry], width: usize) -> Vec<String> {
+    entries
+        .iter()
+        .map(|e| {
+            format!(
+                "  {}",
+                text::truncate(&e.line(), width.saturating_sub(2), "…")
+            )
+        })
+        .collect()
+}
+
+fn collapsed(entries: &[LegendEntry], hint: &str, width: usize) -> String {
+    let swatches: Vec<&str> = entries.iter().map(|e| e.swatch.as_str()).collect();
+    let line = format!(
+        "  {}  ({} entries, {})",
+        swatches.join(" "),
+        entries.len(),
+        hint
+    );
+    text::truncate(&line, width, "…")
+}
+
+/// Print `chart` with its legend; `toggle` waits for keys in a terminal
+pub fn print(chart: &[String], entries: &[LegendEntry], position: LegendPosition) {
+    let width = terminal_width();
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    let position = match position {
+        // Nobody can press a key, so list the entries
+        LegendPosition::Toggle if !interactive => LegendPosition::Below,
+        p => p,
+    };
+    for line in compose(chart, entries, position, width) {
+        println!("{}", line);
+    }
+    if position == LegendPosition::Toggle && !entries.is_empty() {
+        toggle(entries, width).ok();
+    }
+}
+
+/// Swap the collapsed row for the full list on `l`, until q, Esc or Enter
+fn toggle(entries: &[LegendEntry], width: usize) -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    let result = toggle_keys(entries, width);
+    terminal::disable_raw_mode()?;
+    result
+}
+
+/// The key loop of [`toggle`], run in raw mode
+fn toggle_keys(entries: &[LegendEntry], width: usize) -> io::Result<()> {
+    let mut stdout = color::stdout();
+    let mut shown = 1;
+    let mut expanded = false;
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('l') | KeyCode::Char(' ') => {
+                expanded = !expanded;
+                stdout.execute(MoveUp(shown as u16))?;
+                stdout.execute(Clear(ClearType::FromCursorDown))?;
+                let lines = if expanded {
+                    below(entries, width)
+                } else {
+                    vec![collapsed(entries, "l: legend", width)]
+                };
+                for line in &lines {
+                    write!(stdout, "{}\r\n", line)?;
+                }
+                stdout.flush()?;
+                shown = lines.len();
+            }
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => break,
+            KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chart(width: usize) -> Vec<String> {
+        vec!["#".repeat(width); 3]
+    }
+
+    fn entries() -> Vec<LegendEntry> {
+        vec![
+            LegendEntry::new("██", "Alpha: 50.0%"),
+            LegendEntry::new("▓▓", "Beta: 30.0%"),
+            LegendEntry::new("▒▒", "Gamma: 20.0%"),
+            LegendEntry::new("░░", "Delta: 0.0%"),
+        ]
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            LegendPosition::from_name("Right"),
+            Some(LegendPosition::Right)
+        );
+        assert_eq!(
+            LegendPosition::from_name("bottom"),
+            Some(LegendPosition::Below)
+        );
+        assert_eq!(LegendPosition::from_name("left"), None);
+        for name in POSITIONS {
+            assert!(LegendPosition::from_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_auto_moves_with_width() {
+        let entries = entries();
+        // 20 chart + 3 gap + 15 legend
+        assert_eq!(
+            resolve(LegendPosition::Auto, &chart(20), &entries, 80),
+            LegendPosition::Right
+        );
+        assert_eq!(
+            resolve(LegendPosition::Auto, &chart(70), &entries, 80),
+            LegendPosition::Below
+        );
+        assert_eq!(
+            resolve(LegendPosition::Auto, &chart(10), &entries, 12),
+            LegendPosition::Collapsed
+        );
+        assert_eq!(
+            resolve(LegendPosition::Right, &chart(70), &entries, 80),
+            LegendPosition::Right
+        );
+        assert_eq!(
+            resolve(LegendPosition::Auto, &chart(10), &[], 80),
+            LegendPosition::None
+        );
+    }
+
+    #[test]
+    fn test_right_is_centered_on_tall_charts() {
+        let chart = vec!["#".to_string(); 9];
+        let lines = compose(&chart, &entries(), LegendPosition::Right, 80);
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[1], "#");
+        assert_eq!(lines[2], "#   ██ Alpha: 50.0%");
+        assert_eq!(lines[5], "#   ░░ Delta: 0.0%");
+    }
+
+    #[test]
+    fn test_right_pads_chart_and_extends_short_charts() {
+        let lines = compose(&chart(5), &entries(), LegendPosition::Right, 80);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "#####   ██ Alpha: 50.0%");
+        assert_eq!(lines[3], "        ░░ Delta: 0.0%");
+    }
+
+    #[test]
+    fn test_below_and_collapsed() {
+        let lines = compose(&chart(5), &entries(), LegendPosition::Below, 80);
+        assert_eq!(lines.len(), 3 + 1 + 4);
+        assert_eq!(lines[4], "  ██ Alpha: 50.0%");
+
+        let lines = compose(&chart(5), &entries(), LegendPosition::Collapsed, 80);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[4].starts_with("  ██ ▓▓ ▒▒ ░░  (4 entries"));
+
+        let narrow = compose(&chart(5), &entries(), LegendPosition::Collapsed, 20);
+        assert_eq!(text::display_width(&narrow[4]), 20);
+
+        let none = compose(&chart(5), &entries(), LegendPosition::None, 80);
+        assert_eq!(none, chart(5));
+    }
+}
@@ -1,3 +1,7 @@
+use crate::animation::easing::Easing;
+use crate::animation::engine::Frames;
+use crate::animation::policy;
+use crate::output::color;
 use crossterm::{
     cursor::{Hide, MoveTo, MoveToColumn, Show},
     terminal::{Clear, ClearType},
@@ -7,36 +11,92 @@ use owo_colors::OwoColorize;
 use std::io::{stdout, IsTerminal, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::thread;
 use std::time::Duration;
 
 const BRAILLE_OFFSET: u32 = 0x2800;
 const HEIGHT: usize = 10;
 
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Horizon band shades, lightest first; values above the baseline
+const POSITIVE_BANDS: [(u8, u8, u8); 4] =
+    [(158, 202, 225), (66, 146, 198), (8, 81, 156), (8, 48, 107)];
+/// Horizon band shades for values below the baseline
+const NEGATIVE_BANDS: [(u8, u8, u8); 4] = [
+    (252, 174, 145),
+    (251, 106, 74),
+    (222, 45, 38),
+    (165, 15, 21),
+];
+pub const MAX_BANDS: usize = 4;
+
+/// How the series is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineStyle {
+    /// Braille line plot, `HEIGHT` rows tall
+    Braille,
+    /// Single-row horizon chart folded into this many bands
+    Horizon { bands: usize },
+}
+
+impl LineStyle {
+    pub fn from_name(s: &str, bands: usize) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "braille" | "line" => Some(LineStyle::Braille),
+            "horizon" => Some(LineStyle::Horizon {
+                bands: bands.clamp(1, MAX_BANDS),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// One horizon column: the band the value reaches and how far into it
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HorizonCell {
+    /// Zero-based band index the value tops out in
+    band: usize,
+    /// Eighths filled in that band, 1..=8
+    eighths: usize,
+    negative: bool,
+}
+
 pub struct LineChart<'a> {
     data: &'a str,
     title: Option<&'a str>,
+    style: LineStyle,
     animate: bool,
     animation_time_ms: u64,
+    easing: Easing,
 }
 
 impl<'a> LineChart<'a> {
     pub fn new(
         data: &'a str,
         title: Option<&'a str>,
+        style: LineStyle,
         animate: bool,
         animation_time_ms: u64,
     ) -> Self {
         Self {
             data,
             title,
+            style,
             animate,
             animation_time_ms,
+            easing: Easing::Linear,
         }
     }
 
+    /// Shape how fast points are revealed when animating
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
     pub fn render(&self) {
-        if self.animate {
+        if let LineStyle::Horizon { bands } = self.style {
+            self._render_horizon(bands);
+        } else if policy::gate(self.animate) {
             self._render_animated();
         } else {
             self._render_static();
@@ -134,6 +194,34 @@ impl<'a> LineChart<'a> {
         println!("{}", axis_line.bright_black());
     }
 
+    fn _render_horizon(&self, bands: usize) {
+        let values: Vec<f64> = self
+            .data
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+
+        if values.is_empty() {
+            eprintln!("Error: No valid data points provided");
+            return;
+        }
+
+        if let Some(title_text) = self.title {
+            println!("{}", title_text.bright_cyan().bold());
+            println!();
+        }
+
+        let term_width = crossterm::terminal::size()
+            .map(|(w, _)| w as usize)
+            .ok()
+            .filter(|w| *w > 0)
+            .unwrap_or(80);
+        let values = downsample(&values, term_width);
+        let (baseline, extent) = horizon_scale(&values);
+        println!("{}", horizon_line(&values, bands, baseline, extent));
+        println!("{}", horizon_legend(bands, baseline, extent).bright_black());
+    }
+
     fn _render_animated(&self) {
         let values: Vec<f64> = self
             .data
@@ -158,7 +246,7 @@ impl<'a> LineChart<'a> {
             r.store(false, Ordering::SeqCst);
         });
 
-        let mut stdout = stdout();
+        let mut stdout = color::stdout();
 
         // Hide cursor
         let _ = stdout.execute(Hide);
@@ -175,47 +263,53 @@ impl<'a> LineChart<'a> {
         let mut current_canvas = vec![vec![0u8; width]; HEIGHT * 4];
 
         let total_elements = values.len();
-        let delay_per_element = if total_elements > 0 {
-            Duration::from_millis(self.animation_time_ms / total_elements as u64)
-        } else {
-            Duration::from_millis(0)
-        };
+        let frames = Frames::new(
+            total_elements,
+            Duration::from_millis(self.animation_time_ms),
+            self.easing,
+        );
 
         let max_label_width = format!("{:.1}", max_val).len();
         let num_lines_before_chart = if self.title.is_some() { 2 } else { 0 };
 
-        for i in 0..total_elements {
+        let mut plotted = 0;
+        for progress in frames {
             if !running.load(Ordering::SeqCst) {
                 break;
             }
 
-            // Update canvas with new point
-            let val = values[i];
-            let normalized = ((val - min_val) / range).clamp(0.0, 1.0);
-            let y = ((HEIGHT * 4 - 1) as f64 * normalized) as usize;
-            let x = i * 2;
+            // Elastic and bounce curves overshoot; never reveal more points than exist
+            let shown = (progress.clamp(0.0, 1.0) * total_elements as f64).round() as usize;
+            for i in plotted..shown {
+                // Update canvas with new point
+                let val = values[i];
+                let normalized = ((val - min_val) / range).clamp(0.0, 1.0);
+                let y = ((HEIGHT * 4 - 1) as f64 * normalized) as usize;
+                let x = i * 2;
+
+                if x < width && y < HEIGHT * 4 {
+                    current_canvas[HEIGHT * 4 - 1 - y][x] = 1;
+                }
 
-            if x < width && y < HEIGHT * 4 {
-                current_canvas[HEIGHT * 4 - 1 - y][x] = 1;
-            }
+                if i > 0 {
+                    let prev_val = values[i - 1];
+                    let prev_normalized = ((prev_val - min_val) / range).clamp(0.0, 1.0);
+                    let prev_y = ((HEIGHT * 4 - 1) as f64 * prev_normalized) as usize;
 
-            if i > 0 {
-                let prev_val = values[i - 1];
-                let prev_normalized = ((prev_val - min_val) / range).clamp(0.0, 1.0);
-                let prev_y = ((HEIGHT * 4 - 1) as f64 * prev_normalized) as usize;
+                    let y_start = prev_y.min(y);
+                    let y_end = prev_y.max(y);
 
-                let y_start = prev_y.min(y);
-                let y_end = prev_y.max(y);
-
-                for y_pos in y_start..=y_end {
-                    if y_pos < HEIGHT * 4 {
-                        let x_interp = i * 2 - 1;
-                        if x_interp < width {
-                            current_canvas[HEIGHT * 4 - 1 - y_pos][x_interp] = 1;
+                    for y_pos in y_start..=y_end {
+                        if y_pos < HEIGHT * 4 {
+                            let x_interp = i * 2 - 1;
+                            if x_interp < width {
+                                current_canvas[HEIGHT * 4 - 1 - y_pos][x_interp] = 1;
+                            }
                         }
                     }
                 }
             }
+            plotted = plotted.max(shown);
 
             // Clear previous chart drawing and redraw
             let _ = stdout.execute(MoveTo(0, num_lines_before_chart as u16));
@@ -267,7 +361,6 @@ impl<'a> LineChart<'a> {
             let axis_line = " ".repeat(max_label_width + 1) + &"─".repeat(width / 2);
             let _ = writeln!(stdout, "{}", axis_line.bright_black());
             let _ = stdout.flush();
-            thread::sleep(delay_per_element);
         }
 
         // Final render to ensure the complete chart is displayed if animation finishes or is interrupted
@@ -283,3 +376,163 @@ impl<'a> LineChart<'a> {
         let _ = stdout.execute(Show);
     }
 }
+
+/// Average neighbouring points so the series fits in `width` columns
+fn downsample(values: &[f64], width: usize) -> Vec<f64> {
+    if values.len() <= width || width == 0 {
+        return values.to_vec();
+    }
+    (0..width)
+        .map(|i| {
+            let start = i * values.len() / width;
+            let end = ((i + 1) * values.len() / width).max(start + 1);
+            values[start..end].iter().sum::<f64>() / (end - start) as f64
+        })
+        .collect()
+}
+
+/// Baseline and largest distance from it
+///
+/// Series that cross zero fold around zero so sign stays visible; series
+/// entirely on one side fold around their minimum to use every band.
+fn horizon_scale(values: &[f64]) -> (f64, f64) {
+    let max_val = values.iter().cloned().fold(f64::MIN, f64::max);
+    let min_val = values.iter().cloned().fold(f64::MAX, f64::min);
+    let baseline = if min_val < 0.0 && max_val > 0.0 {
+        0.0
+    } else {
+        min_val
+    };
+    let extent = (max_val - baseline).abs().max((min_val - baseline).abs());
+    (baseline, if extent < f64::EPSILON { 1.0 } else { extent })
+}
+
+fn horizon_cell(value: f64, bands: usize, baseline: f64, extent: f64) -> Option<HorizonCell> {
+    let offset = value - baseline;
+    let levels = ((offset.abs() / extent).clamp(0.0, 1.0) * (bands * 8) as f64).round() as usize;
+    if levels == 0 {
+        return None;
+    }
+    Some(HorizonCell {
+        band: (levels - 1) / 8,
+        eighths: (levels - 1) % 8 + 1,
+        negative: offset < 0.0,
+    })
+}
+
+/// The folded row: each band's fill is drawn over the solid color of the band below
+fn horizon_line(values: &[f64], bands: usize, baseline: f64, extent: f64) -> String {
+    values
+        .iter()
+        .map(|&v| match horizon_cell(v, bands, baseline, extent) {
+            None => " ".to_string(),
+            Some(cell) => {
+                let palette = if cell.negative {
+                    &NEGATIVE_BANDS
+                } else {
+                    &POSITIVE_BANDS
+                };
+                let (r, g, b) = palette[cell.band];
+                let block = BLOCKS[cell.eighths - 1];
+                if cell.band == 0 {
+                    block.truecolor(r, g, b).to_string()
+                } else {
+                    let (br, bg, bb) = palette[cell.band - 1];
+                    block
+                        .truecolor(r, g, b)
+                        .on_truecolor(br, bg, bb)
+                        .to_string()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Swatch per band with the value where it ends
+fn horizon_legend(bands: usize, baseline: f64, extent: f64) -> String {
+    let step = extent / bands as f64;
+    let mut parts = vec![format!("base {:.1}", baseline)];
+    for (band, &(r, g, b)) in POSITIVE_BANDS.iter().enumerate().take(bands) {
+        parts.push(format!(
+            "{} ≤{:.1}",
+            "█".truecolor(r, g, b),
+            baseline + step * (band + 1) as f64
+        ));
+    }
+    parts.join("  ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*m")
+            .unwrap()
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    #[test]
+    fn test_line_style_from_name() {
+        assert_eq!(LineStyle::from_name("braille", 3), Some(LineStyle::Braille));
+        assert_eq!(
+            LineStyle::from_name("Horizon", 9),
+            Some(LineStyle::Horizon { bands: MAX_BANDS })
+        );
+        assert_eq!(LineStyle::from_name("bars", 3), None);
+    }
+
+    #[test]
+    fn test_horizon_scale_folds_around_zero_when_crossing() {
+        assert_eq!(horizon_scale(&[-4.0, 2.0, 1.0]), (0.0, 4.0));
+        assert_eq!(horizon_scale(&[10.0, 30.0]), (10.0, 20.0));
+        assert_eq!(horizon_scale(&[5.0, 5.0]), (5.0, 1.0));
+    }
+
+    #[test]
+    fn test_horizon_cell_bands() {
+        // 3 bands over 0..12: each band covers 4 units, 0.5 per eighth
+        assert_eq!(horizon_cell(0.0, 3, 0.0, 12.0), None);
+        assert_eq!(
+            horizon_cell(2.0, 3, 0.0, 12.0),
+            Some(HorizonCell {
+                band: 0,
+                eighths: 4,
+                negative: false
+            })
+        );
+        assert_eq!(
+            horizon_cell(12.0, 3, 0.0, 12.0),
+            Some(HorizonCell {
+                band: 2,
+                eighths: 8,
+                negative: false
+            })
+        );
+        assert_eq!(
+            horizon_cell(-6.0, 3, 0.0, 12.0),
+            Some(HorizonCell {
+                band: 1,
+                eighths: 4,
+                negative: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_horizon_line_is_one_cell_per_value() {
+        let values = [0.0, 1.0, 5.0, 12.0];
+        let line = horizon_line(&values, 3, 0.0, 12.0);
+        assert_eq!(plain(&line), " ▂▂█");
+        // The second band sits on the solid first-band color
+        assert!(line.contains("48;2;158;202;225"));
+    }
+
+    #[test]
+    fn test_downsample_averages_to_width() {
+        let values: Vec<f64> = (0..8).map(f64::from).collect();
+        assert_eq!(downsample(&values, 4), vec![0.5, 2.5, 4.5, 6.5]);
+        assert_eq!(downsample(&values, 20), values);
+    }
+}
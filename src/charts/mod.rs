@@ -1,4 +1,35 @@
 pub mod bar;
+pub mod boxplot;
+pub mod funnel;
+pub mod legend;
 pub mod line;
 pub mod pie;
+pub mod radar;
+pub mod ridgeline;
 pub mod sparkline;
+
+/// A value label for charts: whole numbers without decimals, others with up
+/// to two
+pub fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        let text = format!("{:.2}", value);
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(42.0), "42");
+        assert_eq!(format_value(-3.0), "-3");
+        assert_eq!(format_value(2.5), "2.5");
+        assert_eq!(format_value(1.0 / 3.0), "0.33");
+        assert_eq!(format_value(0.999), "1");
+        assert_eq!(format_value(2e9), "2000000000");
+    }
+}
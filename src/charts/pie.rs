@@ -1,3 +1,7 @@
+use super::legend::{self, LegendEntry, LegendPosition};
+use crate::animation::policy;
+use crate::design::{Color, SeriesColors};
+use crate::output::color;
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
     terminal::{Clear, ClearType},
@@ -11,23 +15,13 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
-const COLORS: [u8; 8] = [
-    196, // Red
-    208, // Orange
-    226, // Yellow
-    46,  // Green
-    51,  // Cyan
-    21,  // Blue
-    129, // Purple
-    201, // Magenta
-];
-
 const BLOCKS: [&str; 8] = ["█", "▓", "▒", "░", "▄", "▀", "▐", "▌"];
 
 pub struct PieChart<'a> {
     data: &'a str,
     animate: bool,
     animation_time_ms: u64,
+    legend: LegendPosition,
 }
 
 impl<'a> PieChart<'a> {
@@ -36,11 +30,18 @@ impl<'a> PieChart<'a> {
             data,
             animate,
             animation_time_ms,
+            legend: LegendPosition::Auto,
         }
     }
 
+    /// Place the legend at `position` instead of fitting it to the width
+    pub fn with_legend(mut self, position: LegendPosition) -> Self {
+        self.legend = position;
+        self
+    }
+
     pub fn render(&self) {
-        if self.animate {
+        if policy::gate(self.animate) {
             self._render_animated();
         } else {
             self._render_static();
@@ -70,21 +71,11 @@ impl<'a> PieChart<'a> {
             })
             .collect();
 
-        // Render the pie chart
-        render_circle(&segments);
-
-        // Render legend
-        println!();
-        for (idx, (label, _, percentage)) in segments.iter().enumerate() {
-            let block = BLOCKS[idx % BLOCKS.len()];
-            let color = COLORS[idx % COLORS.len()];
-            println!(
-                "  {} {}: {:.1}%",
-                block.repeat(2).color(owo_colors::XtermColors::from(color)),
-                label,
-                percentage
-            );
-        }
+        legend::print(
+            &circle_lines(&segments),
+            &legend_entries(&segments),
+            self.legend,
+        );
     }
 
     fn _render_animated(&self) {
@@ -122,7 +113,7 @@ impl<'a> PieChart<'a> {
             r.store(false, Ordering::SeqCst);
         });
 
-        let mut stdout = stdout();
+        let mut stdout = color::stdout();
         let _ = stdout.execute(Hide); // Hide cursor
 
         let total_segments = full_segments.len();
@@ -134,9 +125,16 @@ impl<'a> PieChart<'a> {
 
         let mut rendered_segments: Vec<(String, f64, f64)> = Vec::new();
 
-        // Determine height of the pie chart output + legend
-        // Circle height is fixed at 18 lines (0..18)
-        // Legend has 1 empty line + 1 line per segment
+        // Keys are read once the static chart is drawn, so animate collapsed
+        let position = match self.legend {
+            LegendPosition::Toggle => LegendPosition::Collapsed,
+            p => p,
+        };
+        let width = crossterm::terminal::size()
+            .map(|(w, _)| w as usize)
+            .unwrap_or(80);
+        // Circle height is fixed at 18 lines; a legend below adds at most
+        // 1 empty line + 1 line per segment
         let total_output_lines = 18 + 1 + total_segments;
 
         for segment in full_segments.into_iter() {
@@ -154,21 +152,15 @@ impl<'a> PieChart<'a> {
             }
             let _ = stdout.execute(MoveTo(0, 0)); // Move back to top-left
 
-            // Render current state
-            render_circle(&rendered_segments);
-
-            // Render legend for currently displayed segments
-            let _ = writeln!(stdout);
-            for (idx, (label, _, percentage)) in rendered_segments.iter().enumerate() {
-                let block = BLOCKS[idx % BLOCKS.len()];
-                let color = COLORS[idx % COLORS.len()];
-                let _ = writeln!(
-                    stdout,
-                    "  {} {}: {:.1}%",
-                    block.repeat(2).color(owo_colors::XtermColors::from(color)),
-                    label,
-                    percentage
-                );
+            // Render current state, with the legend for displayed segments
+            let lines = legend::compose(
+                &circle_lines(&rendered_segments),
+                &legend_entries(&rendered_segments),
+                position,
+                width,
+            );
+            for line in lines {
+                let _ = writeln!(stdout, "{}", line);
             }
             let _ = stdout.flush();
             thread::sleep(delay_per_segment);
@@ -188,7 +180,35 @@ impl<'a> PieChart<'a> {
     }
 } // Close impl<'a> PieChart<'a>
 
-fn render_circle(segments: &[(String, f64, f64)]) {
+/// Slice colors by label, so a slice matches its series in other charts
+fn slice_colors(segments: &[(String, f64, f64)]) -> Vec<Color> {
+    let labels: Vec<&str> = segments
+        .iter()
+        .map(|(label, _, _)| label.as_str())
+        .collect();
+    SeriesColors::current().assign(&labels)
+}
+
+fn legend_entries(segments: &[(String, f64, f64)]) -> Vec<LegendEntry> {
+    let colors = slice_colors(segments);
+    segments
+        .iter()
+        .zip(&colors)
+        .enumerate()
+        .map(|(idx, ((label, _, percentage), color))| {
+            let block = BLOCKS[idx % BLOCKS.len()];
+            LegendEntry::new(
+                block
+                    .repeat(2)
+                    .truecolor(color.r, color.g, color.b)
+                    .to_string(),
+                format!("{}: {:.1}%", label, percentage),
+            )
+        })
+        .collect()
+}
+
+fn circle_lines(segments: &[(String, f64, f64)]) -> Vec<String> {
     let radius = 9.0;
     let center_x = 10.0;
     let center_y = 9.0;
@@ -203,8 +223,12 @@ fn render_circle(segments: &[(String, f64, f64)]) {
         cumulative_angle += angle_span;
     }
 
+    let colors = slice_colors(segments);
+
     // Render the circle grid
+    let mut lines = Vec::with_capacity(18);
     for y in 0..18 {
+        let mut line = String::new();
         for x in 0..20 {
             let dx = x as f64 - center_x;
             let dy = (y as f64 - center_y) * 2.0; // Adjust for character aspect ratio
@@ -222,14 +246,15 @@ fn render_circle(segments: &[(String, f64, f64)]) {
                     .unwrap_or(segment_angles.last().unwrap().2);
 
                 let block = BLOCKS[segment_idx % BLOCKS.len()];
-                let color = COLORS[segment_idx % COLORS.len()];
-                print!("{}", block.color(owo_colors::XtermColors::from(color)));
+                let color = colors[segment_idx];
+                line.push_str(&block.truecolor(color.r, color.g, color.b).to_string());
             } else {
-                print!(" ");
+                line.push(' ');
             }
         }
-        println!();
+        lines.push(line);
     }
+    lines
 }
 
 fn parse_data(data: &str) -> Vec<(String, f64)> {
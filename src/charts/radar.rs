@@ -0,0 +1,371 @@
+use super::format_value;
+use super::legend::{self, LegendEntry, LegendPosition};
+use owo_colors::OwoColorize;
+use std::f64::consts::PI;
+
+const BRAILLE_OFFSET: u32 = 0x2800;
+
+/// Layer bits stored per Braille dot
+const GRID: u8 = 1;
+const SERIES_A: u8 = 2;
+const SERIES_B: u8 = 4;
+
+/// A character cell of the rendered chart: glyph plus the layers drawn in it
+type Cell = (char, u8);
+
+/// Render a radar (spider) chart for "axis:value,axis:value" data
+pub fn render(
+    data: &str,
+    compare: Option<&str>,
+    max: Option<f64>,
+    size: usize,
+    legend: LegendPosition,
+) {
+    let grid = match build(data, compare, max, size) {
+        Ok(grid) => grid,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let lines: Vec<String> = grid
+        .iter()
+        .map(|row| paint_row(row).trim_end().to_string())
+        .collect();
+    let entries = match compare {
+        Some(_) => vec![
+            LegendEntry::new("━━".bright_cyan().to_string(), "data"),
+            LegendEntry::new("━━".bright_magenta().to_string(), "compare"),
+        ],
+        None => Vec::new(),
+    };
+    legend::print(&lines, &entries, legend);
+}
+
+/// Lay out the chart as a grid of cells: Braille plot with axis labels around it
+fn build(
+    data: &str,
+    compare: Option<&str>,
+    max: Option<f64>,
+    size: usize,
+) -> Result<Vec<Vec<Cell>>, String> {
+    let series = parse_data(data)?;
+    if series.len() < 3 {
+        return Err("Radar chart needs at least 3 axes".to_string());
+    }
+    let compare_values = match compare {
+        Some(c) => Some(align_series(&series, &parse_data(c)?)?),
+        None => None,
+    };
+
+    let max = match max {
+        Some(m) if m > 0.0 => m,
+        Some(_) => return Err("--max must be greater than 0".to_string()),
+        None => series
+            .iter()
+            .map(|(_, v)| *v)
+            .chain(compare_values.iter().flatten().copied())
+            .fold(0.0_f64, f64::max)
+            .max(f64::EPSILON),
+    };
+
+    // Braille dots are roughly square, so the same radius works on both axes
+    let radius = (size.max(2) * 4) as f64;
+    let mut canvas = Canvas::new(size.max(2) * 4 + 1, size.max(2) * 2 + 1);
+    let center = (radius, (canvas.height * 4 / 2) as f64);
+    let n = series.len();
+    let point = |axis: usize, fraction: f64| -> (f64, f64) {
+        let angle = -PI / 2.0 + 2.0 * PI * axis as f64 / n as f64;
+        (
+            center.0 + radius * fraction * angle.cos(),
+            center.1 + radius * fraction * angle.sin(),
+        )
+    };
+
+    // Spokes plus rings at 50% and 100%
+    for axis in 0..n {
+        canvas.line(center, point(axis, 1.0), GRID);
+        for ring in [0.5, 1.0] {
+            canvas.line(point(axis, ring), point((axis + 1) % n, ring), GRID);
+        }
+    }
+
+    let values: Vec<f64> = series.iter().map(|(_, v)| *v).collect();
+    let mut layers = vec![(values, SERIES_A)];
+    if let Some(values) = compare_values {
+        layers.push((values, SERIES_B));
+    }
+    for (values, layer) in &layers {
+        for axis in 0..n {
+            let next = (axis + 1) % n;
+            canvas.line(
+                point(axis, (values[axis] / max).clamp(0.0, 1.0)),
+                point(next, (values[next] / max).clamp(0.0, 1.0)),
+                *layer,
+            );
+        }
+    }
+
+    // Labels go in margins around the plot
+    let labels: Vec<String> = series
+        .iter()
+        .map(|(name, value)| format!("{} ({})", name, format_value(*value)))
+        .collect();
+    let margin = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) + 1;
+    let mut grid = vec![vec![(' ', 0); canvas.width + margin * 2]; canvas.height + 2];
+    for (row, cells) in canvas.cells().into_iter().enumerate() {
+        for (col, cell) in cells.into_iter().enumerate() {
+            grid[row + 1][col + margin] = cell;
+        }
+    }
+
+    for (axis, label) in labels.iter().enumerate() {
+        let angle = -PI / 2.0 + 2.0 * PI * axis as f64 / n as f64;
+        let (x, y) = point(axis, 1.0);
+        let col = (x / 2.0) as usize + margin;
+        let row = (y / 4.0) as usize + 1;
+        let len = label.chars().count();
+
+        let row = if angle.sin() < -0.6 {
+            row.saturating_sub(1)
+        } else if angle.sin() > 0.6 {
+            row + 1
+        } else {
+            row
+        };
+        let start = if angle.cos() > 0.3 {
+            col + 2
+        } else if angle.cos() < -0.3 {
+            col.saturating_sub(len + 1)
+        } else {
+            col.saturating_sub(len / 2)
+        };
+
+        if let Some(cells) = grid.get_mut(row) {
+            for (i, ch) in label.chars().enumerate() {
+                if let Some(cell) = cells.get_mut(start + i) {
+                    *cell = (ch, 0);
+                }
+            }
+        }
+    }
+
+    // Drop blank rows left over when the polygon doesn't reach the bottom
+    while grid
+        .last()
+        .is_some_and(|row| row.iter().all(|(c, _)| *c == ' '))
+    {
+        grid.pop();
+    }
+
+    Ok(grid)
+}
+
+/// Colorize a row of cells by the layers drawn in each
+fn paint_row(row: &[Cell]) -> String {
+    let mut out = String::new();
+    for &(ch, layers) in row {
+        let text = ch.to_string();
+        let painted = if layers & SERIES_A != 0 && layers & SERIES_B != 0 {
+            text.bright_white().to_string()
+        } else if layers & SERIES_B != 0 {
+            text.bright_magenta().to_string()
+        } else if layers & SERIES_A != 0 {
+            text.bright_cyan().to_string()
+        } else if layers & GRID != 0 {
+            text.bright_black().to_string()
+        } else {
+            text
+        };
+        out.push_str(&painted);
+    }
+    out
+}
+
+/// Braille dot canvas where each dot remembers which layers touched it
+struct Canvas {
+    width: usize,
+    height: usize,
+    dots: Vec<Vec<u8>>,
+}
+
+impl Canvas {
+    /// Canvas of `width` × `height` characters (2×4 dots each)
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            dots: vec![vec![0; width * 2]; height * 4],
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, layer: u8) {
+        if x >= 0 && y >= 0 {
+            if let Some(dot) = self
+                .dots
+                .get_mut(y as usize)
+                .and_then(|row| row.get_mut(x as usize))
+            {
+                *dot |= layer;
+            }
+        }
+    }
+
+    /// Bresenham line between two dot coordinates
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), layer: u8) {
+        let (mut x0, mut y0) = (from.0.round() as i64, from.1.round() as i64);
+        let (x1, y1) = (to.0.round() as i64, to.1.round() as i64);
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.set(x0, y0, layer);
+            if x0 == x1 && y0 == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+    }
+
+    /// Collapse dots into Braille characters, merging the layers of each cell
+    fn cells(&self) -> Vec<Vec<Cell>> {
+        (0..self.height)
+            .map(|row| {
+                (0..self.width)
+                    .map(|col| {
+                        let mut bits: u32 = 0;
+                        let mut layers = 0;
+                        for dy in 0..4 {
+                            for dx in 0..2 {
+                                let dot = self.dots[row * 4 + dy][col * 2 + dx];
+                                if dot != 0 {
+                                    bits |= 1 << braille_bit(dx, dy);
+                                    layers |= dot;
+                                }
+                            }
+                        }
+                        if bits == 0 {
+                            (' ', 0)
+                        } else {
+                            (char::from_u32(BRAILLE_OFFSET + bits).unwrap_or(' '), layers)
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Unicode Braille bit index for the dot at column `dx` (0-1), row `dy` (0-3)
+fn braille_bit(dx: usize, dy: usize) -> u32 {
+    match (dx, dy) {
+        (0, 3) => 6,
+        (1, 3) => 7,
+        (0, y) => y as u32,
+        (_, y) => y as u32 + 3,
+    }
+}
+
+/// Reorder `other` to match the axes of `base`
+fn align_series(base: &[(String, f64)], other: &[(String, f64)]) -> Result<Vec<f64>, String> {
+    base.iter()
+        .map(|(name, _)| {
+            other
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| *v)
+                .ok_or_else(|| format!("--compare is missing axis '{}'", name))
+        })
+        .collect()
+}
+
+fn parse_data(data: &str) -> Result<Vec<(String, f64)>, String> {
+    let mut axes = Vec::new();
+    for entry in data.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (name, value) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid axis '{}' (expected name:value)", entry))?;
+        let (name, value) = (name.trim(), value.trim());
+        let value: f64 = value
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' for {}", value, name))?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(format!("Axis '{}' must be a non-negative number", name));
+        }
+        axes.push((name.to_string(), value));
+    }
+    Ok(axes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(grid: &[Vec<Cell>]) -> Vec<String> {
+        grid.iter()
+            .map(|row| row.iter().map(|(c, _)| *c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let axes = parse_data("speed:8, power:6,defense:9").unwrap();
+        assert_eq!(axes.len(), 3);
+        assert_eq!(axes[1], ("power".to_string(), 6.0));
+        assert!(parse_data("speed").is_err());
+        assert!(parse_data("speed:-1").is_err());
+    }
+
+    #[test]
+    fn test_build_requires_three_axes() {
+        assert!(build("a:1,b:2", None, None, 6).is_err());
+    }
+
+    #[test]
+    fn test_build_places_labels() {
+        let grid = build("speed:8,power:6,defense:9", None, Some(10.0), 6).unwrap();
+        let lines = text(&grid);
+        // First axis points straight up, so its label sits on the top row
+        assert!(lines[0].contains("speed (8)"));
+        assert!(lines.iter().any(|l| l.contains("power (6)")));
+        assert!(lines.iter().any(|l| l.contains("defense (9)")));
+    }
+
+    #[test]
+    fn test_build_marks_series_layers() {
+        let grid = build("a:5,b:5,c:5,d:5", Some("a:10,b:10,c:10,d:10"), None, 6).unwrap();
+        let layers: u8 = grid.iter().flatten().fold(0, |acc, (_, l)| acc | l);
+        assert_eq!(layers, GRID | SERIES_A | SERIES_B);
+    }
+
+    #[test]
+    fn test_align_series_reorders_and_checks_axes() {
+        let base = parse_data("a:1,b:2,c:3").unwrap();
+        let other = parse_data("c:30,a:10,b:20").unwrap();
+        assert_eq!(align_series(&base, &other).unwrap(), vec![10.0, 20.0, 30.0]);
+        let missing = parse_data("a:1,b:2").unwrap();
+        assert!(align_series(&base, &missing).is_err());
+    }
+
+    #[test]
+    fn test_braille_bits_cover_all_dots() {
+        let mut bits = 0u32;
+        for dx in 0..2 {
+            for dy in 0..4 {
+                bits |= 1 << braille_bit(dx, dy);
+            }
+        }
+        assert_eq!(bits, 0xFF);
+    }
+}
@@ -0,0 +1,392 @@
+use super::format_value;
+use crate::design::SeriesColors;
+use crate::output::input;
+use owo_colors::OwoColorize;
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One labeled row of values; `None` marks a gap
+#[derive(Debug, Clone, PartialEq)]
+struct Series {
+    name: String,
+    values: Vec<Option<f64>>,
+}
+
+/// Series sharing one X axis
+#[derive(Debug, PartialEq)]
+struct Ridgeline {
+    x_labels: Vec<String>,
+    series: Vec<Series>,
+}
+
+/// Render stacked sparklines from a CSV file or inline "name:v1,v2;name:v1,v2" data
+///
+/// CSV input is wide: the first column holds X labels and every other column
+/// is one series, named by the header row.
+pub fn render(file: Option<&str>, data: Option<&str>, height: usize, shared_scale: bool) {
+    let parsed = match (file, data) {
+        (_, Some(data)) => parse_inline(data),
+        (Some(file), None) => input::read_text(file)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse_csv(&text)),
+        (None, None) => Err("Provide --file or --data".to_string()),
+    };
+    let chart = match parsed {
+        Ok(chart) => chart,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let term_width = crossterm::terminal::size()
+        .map(|(w, _)| w as usize)
+        .ok()
+        .filter(|w| *w > 0)
+        .unwrap_or(80);
+    for line in ridgeline_lines(&chart, term_width, height.max(1), shared_scale) {
+        println!("{}", line);
+    }
+}
+
+fn parse_csv(text: &str) -> Result<Ridgeline, String> {
+    let mut rows = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.split(',').map(str::trim).collect::<Vec<_>>());
+    let header = rows.next().ok_or("CSV file is empty")?;
+    if header.len() < 2 {
+        return Err("CSV needs an X column and at least one series column".to_string());
+    }
+
+    let mut series: Vec<Series> = header[1..]
+        .iter()
+        .map(|name| Series {
+            name: name.to_string(),
+            values: Vec::new(),
+        })
+        .collect();
+    let mut x_labels = Vec::new();
+    for (line, row) in rows.enumerate() {
+        x_labels.push(row[0].to_string());
+        for (col, s) in series.iter_mut().enumerate() {
+            let cell = row.get(col + 1).copied().unwrap_or("");
+            s.values.push(parse_cell(cell).map_err(|_| {
+                format!(
+                    "Invalid value '{}' in row {}, column {}",
+                    cell,
+                    line + 2,
+                    s.name
+                )
+            })?);
+        }
+    }
+
+    if x_labels.is_empty() {
+        return Err("CSV has a header but no data rows".to_string());
+    }
+    Ok(Ridgeline { x_labels, series })
+}
+
+fn parse_inline(data: &str) -> Result<Ridgeline, String> {
+    let mut series = Vec::new();
+    for entry in data.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let (name, values) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid series '{}' (expected name:v1,v2,...)", entry))?;
+        let values = values
+            .split(',')
+            .map(|v| parse_cell(v.trim()).map_err(|_| format!("Invalid value '{}' in {}", v, name)))
+            .collect::<Result<Vec<_>, _>>()?;
+        series.push(Series {
+            name: name.trim().to_string(),
+            values,
+        });
+    }
+    if series.is_empty() {
+        return Err("No valid data provided".to_string());
+    }
+
+    let points = series.iter().map(|s| s.values.len()).max().unwrap_or(0);
+    for s in &mut series {
+        s.values.resize(points, None);
+    }
+    Ok(Ridgeline {
+        x_labels: (1..=points).map(|i| i.to_string()).collect(),
+        series,
+    })
+}
+
+/// Empty cells are gaps; anything else must be a finite number
+fn parse_cell(cell: &str) -> Result<Option<f64>, ()> {
+    if cell.is_empty() {
+        return Ok(None);
+    }
+    match cell.parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(Some(v)),
+        _ => Err(()),
+    }
+}
+
+/// Average consecutive points into at most `width` buckets
+fn downsample(values: &[Option<f64>], width: usize) -> Vec<Option<f64>> {
+    if values.len() <= width || width == 0 {
+        return values.to_vec();
+    }
+    (0..width)
+        .map(|i| {
+            let start = i * values.len() / width;
+            let end = ((i + 1) * values.len() / width).max(start + 1);
+            let bucket: Vec<f64> = values[start..end].iter().flatten().copied().collect();
+            if bucket.is_empty() {
+                None
+            } else {
+                Some(bucket.iter().sum::<f64>() / bucket.len() as f64)
+            }
+        })
+        .collect()
+}
+
+fn bounds(values: &[Option<f64>]) -> Option<(f64, f64)> {
+    let mut iter = values.iter().flatten();
+    let first = *iter.next()?;
+    Some(iter.fold((first, first), |(lo, hi), v| (lo.min(*v), hi.max(*v))))
+}
+
+/// Rows of block characters, top first, for one series `height` lines tall
+fn spark_rows(values: &[Option<f64>], (lo, hi): (f64, f64), height: usize) -> Vec<String> {
+    let levels = height * BLOCKS.len();
+    let filled: Vec<Option<usize>> = values
+        .iter()
+        .map(|v| {
+            v.map(|v| {
+                let t = if hi > lo { (v - lo) / (hi - lo) } else { 0.5 };
+                // Keep the lowest value visible as a thin baseline
+                ((t * (levels - 1) as f64).round() as usize + 1).min(levels)
+            })
+        })
+        .collect();
+
+    (0..height)
+        .rev()
+        .map(|row| {
+            filled
+                .iter()
+                .map(|f| match f {
+                    Some(f) if *f > row * BLOCKS.len() => {
+                        BLOCKS[(*f - row * BLOCKS.len()).min(BLOCKS.len()) - 1]
+                    }
+                    _ => ' ',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Build the chart: one labeled ridge per series, then a shared X axis
+fn ridgeline_lines(
+    chart: &Ridgeline,
+    term_width: usize,
+    height: usize,
+    shared_scale: bool,
+) -> Vec<String> {
+    let label_width = chart
+        .series
+        .iter()
+        .map(|s| s.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let ranges: Vec<String> = chart
+        .series
+        .iter()
+        .map(|s| match bounds(&s.values) {
+            Some((lo, hi)) => format!("{}–{}", format_value(lo), format_value(hi)),
+            None => "no data".to_string(),
+        })
+        .collect();
+    let range_width = ranges.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    let points = chart.x_labels.len();
+    let plot_width = points.min(
+        term_width
+            .saturating_sub(label_width + range_width + 5)
+            .max(8),
+    );
+
+    let samples: Vec<Vec<Option<f64>>> = chart
+        .series
+        .iter()
+        .map(|s| downsample(&s.values, plot_width))
+        .collect();
+    let shared = bounds(
+        &chart
+            .series
+            .iter()
+            .flat_map(|s| s.values.clone())
+            .collect::<Vec<_>>(),
+    );
+
+    // By name, so a series keeps its color when others come and go
+    let names: Vec<&str> = chart.series.iter().map(|s| s.name.as_str()).collect();
+    let colors = SeriesColors::current().assign(&names);
+
+    let mut lines = Vec::new();
+    for (idx, (series, values)) in chart.series.iter().zip(&samples).enumerate() {
+        let (r, g, b) = (colors[idx].r, colors[idx].g, colors[idx].b);
+        let scale = if shared_scale {
+            shared
+        } else {
+            bounds(&series.values)
+        };
+        let rows = match scale {
+            Some(scale) => spark_rows(values, scale, height),
+            None => vec![" ".repeat(values.len()); height],
+        };
+        for (row_idx, row) in rows.iter().enumerate() {
+            let last = row_idx + 1 == rows.len();
+            let label = if last { series.name.as_str() } else { "" };
+            let mut line = format!(
+                "{:>lw$} {} {}",
+                label.bold(),
+                "│".bright_black(),
+                row.truecolor(r, g, b),
+                lw = label_width
+            );
+            if last {
+                line.push_str(&format!("  {}", ranges[idx].bright_black()));
+            }
+            lines.push(line);
+        }
+    }
+
+    // Shared X axis with first, middle and last labels under their columns
+    let width = samples.first().map(|v| v.len()).unwrap_or(0);
+    lines.push(format!(
+        "{} {}{}",
+        " ".repeat(label_width),
+        "└".bright_black(),
+        "─".repeat(width + 1).bright_black()
+    ));
+    lines.push(format!(
+        "{} {}",
+        " ".repeat(label_width + 3),
+        axis_labels(&chart.x_labels, width).bright_black()
+    ));
+    lines
+}
+
+/// First, middle and last X labels spread across `width` columns
+fn axis_labels(labels: &[String], width: usize) -> String {
+    let (Some(first), Some(last)) = (labels.first(), labels.last()) else {
+        return String::new();
+    };
+    let mut axis = first.clone();
+    if labels.len() == 1 {
+        return axis;
+    }
+    let len = |s: &str| s.chars().count();
+    let middle = &labels[labels.len() / 2];
+    let middle_col = (width / 2).saturating_sub(len(middle) / 2);
+    // Only show the middle label when it has a space on both sides
+    if labels.len() > 2
+        && middle_col > len(&axis)
+        && middle_col + len(middle) < width.saturating_sub(len(last))
+    {
+        axis.push_str(&" ".repeat(middle_col - len(&axis)));
+        axis.push_str(middle);
+    }
+    let gap = width.saturating_sub(len(&axis) + len(last)).max(1);
+    axis.push_str(&" ".repeat(gap));
+    axis.push_str(last);
+    axis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*m")
+            .unwrap()
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_csv_wide_format() {
+        let chart = parse_csv("time,web,db\n00:00,1,5\n00:05,,6\n00:10,3,7\n").unwrap();
+        assert_eq!(chart.x_labels, vec!["00:00", "00:05", "00:10"]);
+        assert_eq!(chart.series[0].name, "web");
+        assert_eq!(chart.series[0].values, vec![Some(1.0), None, Some(3.0)]);
+        assert_eq!(chart.series[1].values[2], Some(7.0));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(parse_csv("").is_err());
+        assert!(parse_csv("time\n1\n").is_err());
+        assert!(parse_csv("time,web\n").is_err());
+        assert!(parse_csv("time,web\n1,abc\n").is_err());
+        assert!(parse_inline("web").is_err());
+    }
+
+    #[test]
+    fn test_parse_inline_pads_short_series() {
+        let chart = parse_inline("a:1,2,3;b:4").unwrap();
+        assert_eq!(chart.x_labels, vec!["1", "2", "3"]);
+        assert_eq!(chart.series[1].values, vec![Some(4.0), None, None]);
+    }
+
+    #[test]
+    fn test_downsample_averages_buckets() {
+        let values: Vec<Option<f64>> = (1..=6).map(|v| Some(v as f64)).collect();
+        assert_eq!(
+            downsample(&values, 3),
+            vec![Some(1.5), Some(3.5), Some(5.5)]
+        );
+        assert_eq!(
+            downsample(&[None, None, Some(4.0), None], 2),
+            vec![None, Some(4.0)]
+        );
+    }
+
+    #[test]
+    fn test_spark_rows_heights() {
+        let values = [Some(0.0), Some(10.0), None];
+        assert_eq!(spark_rows(&values, (0.0, 10.0), 1), vec!["▁█ "]);
+        assert_eq!(spark_rows(&values, (0.0, 10.0), 2), vec![" █ ", "▁█ "]);
+    }
+
+    #[test]
+    fn test_rows_align_with_shared_axis() {
+        let chart = parse_inline("web:1,2,3,4;database:4,3,2,1").unwrap();
+        let lines: Vec<String> = ridgeline_lines(&chart, 80, 1, false)
+            .iter()
+            .map(|l| plain(l))
+            .collect();
+        assert!(lines[0].starts_with("     web │ ▁▃▆█"));
+        assert!(lines[1].starts_with("database │ █▆▃▁"));
+        assert!(lines[0].ends_with("1–4"));
+        assert_eq!(lines[2], "         └─────");
+        assert_eq!(lines[3], "            1  4");
+    }
+
+    #[test]
+    fn test_axis_labels() {
+        let labels: Vec<String> = ["00:00", "00:05", "00:10"].map(String::from).to_vec();
+        assert_eq!(axis_labels(&labels, 24), "00:00     00:05    00:10");
+        assert_eq!(axis_labels(&labels, 12), "00:00  00:10");
+        assert_eq!(axis_labels(&labels[..1], 12), "00:00");
+    }
+
+    #[test]
+    fn test_shared_scale_compares_magnitudes() {
+        let chart = parse_inline("small:1,2;big:10,20").unwrap();
+        let lines: Vec<String> = ridgeline_lines(&chart, 80, 1, true)
+            .iter()
+            .map(|l| plain(l))
+            .collect();
+        assert!(lines[0].contains("▁▁"));
+        assert!(lines[1].contains("▄█"));
+    }
+}
@@ -1,5 +1,8 @@
+use crate::animation::policy;
+use crate::interactive::screen;
+use crate::output::{color, sources, watch};
 use owo_colors::OwoColorize;
-use std::io::{stdout, Write};
+use std::io::Write;
 use std::thread;
 use std::time::Duration;
 
@@ -9,9 +12,35 @@ pub fn render(data: &str) {
     render_animated(data, false, 500);
 }
 
+/// Follow a system reading, adding one every `interval` and redrawing until
+/// Ctrl+C; keeps as many readings as the terminal is wide
+pub fn render_source(source: &str, interval: &str) {
+    let result = watch::parse_refresh(interval).and_then(|interval| {
+        let source = sources::Source::parse(source)?;
+        let label = source.label();
+        let mut sampler = sources::Sampler::new(source)?;
+        let mut history: Vec<f64> = Vec::new();
+        sources::watch(&mut sampler, interval, |value| {
+            let (cols, _) = screen::size();
+            let width = (cols as usize).saturating_sub(label.len() + 12).max(1);
+            history.push(value);
+            let skip = history.len().saturating_sub(width);
+            history.drain(..skip);
+            let data: Vec<String> = history.iter().map(|v| v.to_string()).collect();
+            print!("{} {:>8.1} ", label.cyan(), value);
+            render(&data.join(","));
+        })
+    });
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
 /// Render sparkline with optional animation
 /// animation_time_ms: total animation duration in milliseconds (delay is calculated per value)
 pub fn render_animated(data: &str, animate: bool, animation_time_ms: u64) {
+    let animate = policy::gate(animate);
     let values: Vec<f64> = data
         .split(',')
         .filter_map(|s| s.trim().parse::<f64>().ok())
@@ -32,7 +61,7 @@ pub fn render_animated(data: &str, animate: bool, animation_time_ms: u64) {
     let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
     let range = max - min;
 
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
     // Calculate delay per value: total_time / number_of_values
     let delay = if animate && !values.is_empty() {
         Duration::from_millis(animation_time_ms / values.len() as u64)
@@ -188,6 +188,147 @@ pub const CHART_COLORS: [Color; 8] = [
     Color::new(219, 109, 202), // Pink
 ];
 
+/// Colorblind-safe chart cycle (Okabe & Ito), with grey in place of black
+/// so every color shows on dark terminals
+pub const COLORBLIND_COLORS: [Color; 8] = [
+    Color::new(230, 159, 0),   // Orange
+    Color::new(86, 180, 233),  // Sky blue
+    Color::new(0, 158, 115),   // Bluish green
+    Color::new(240, 228, 66),  // Yellow
+    Color::new(0, 114, 178),   // Blue
+    Color::new(213, 94, 0),    // Vermillion
+    Color::new(204, 121, 167), // Reddish purple
+    Color::new(153, 153, 153), // Grey
+];
+
+/// Cycles [`SeriesColors::parse`] accepts by name
+pub const SERIES_CYCLES: [&str; 2] = ["default", "colorblind"];
+
+/// Environment variable overriding the theme's series colors
+pub const SERIES_COLORS_ENV: &str = "TERMGFX_SERIES_COLORS";
+
+/// Colors handed out to chart series
+///
+/// By name, a series hashes into the cycle, so `api` is drawn in the same
+/// color in every chart and on every refresh, whatever else is plotted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesColors {
+    cycle: Vec<Color>,
+}
+
+impl Default for SeriesColors {
+    fn default() -> Self {
+        Self {
+            cycle: CHART_COLORS.to_vec(),
+        }
+    }
+}
+
+impl SeriesColors {
+    /// A named cycle, or comma-separated `#RRGGBB` colors
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let cycle = match spec.trim().to_lowercase().as_str() {
+            "default" => CHART_COLORS.to_vec(),
+            "colorblind" => COLORBLIND_COLORS.to_vec(),
+            _ => spec
+                .split(',')
+                .map(|hex| {
+                    Color::from_hex(hex.trim()).ok_or_else(|| {
+                        format!(
+                            "'{}' is not a #RRGGBB color or a cycle ({})",
+                            hex.trim(),
+                            SERIES_CYCLES.join(", ")
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(Self { cycle })
+    }
+
+    /// `$TERMGFX_SERIES_COLORS`, else the active theme's cycle
+    pub fn current() -> Self {
+        let theme = super::theme::active().map(|t| t.series_colors.as_str());
+        let env = std::env::var(SERIES_COLORS_ENV).ok();
+        if let Some(spec) = env.as_deref().filter(|s| !s.is_empty()) {
+            match Self::parse(spec) {
+                Ok(colors) => return colors,
+                Err(e) => eprintln!("Warning: {} in {}", e, SERIES_COLORS_ENV),
+            }
+        }
+        // Themes are validated when loaded
+        theme
+            .and_then(|spec| Self::parse(spec).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn colors(&self) -> &[Color] {
+        &self.cycle
+    }
+
+    /// The color of the `index`th series, cycling
+    pub fn at(&self, index: usize) -> Color {
+        self.cycle[index % self.cycle.len()]
+    }
+
+    /// The color `name` hashes to
+    pub fn for_name(&self, name: &str) -> Color {
+        self.cycle[self.slot(name)]
+    }
+
+    fn slot(&self, name: &str) -> usize {
+        // FNV-1a: stable across runs, platforms and Rust versions
+        let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        (hash % self.cycle.len() as u64) as usize
+    }
+
+    /// Colors for the series `names` of one chart
+    ///
+    /// Each name gets [`for_name`](Self::for_name) unless another name hashed
+    /// to the same color; then the alphabetically later one takes the next
+    /// free color. Assignment depends only on the set of names, not their
+    /// order, and repeated names share a color.
+    pub fn assign(&self, names: &[&str]) -> Vec<Color> {
+        let mut distinct: Vec<&str> = names.to_vec();
+        distinct.sort_unstable();
+        distinct.dedup();
+
+        let mut taken = vec![false; self.cycle.len()];
+        let mut slots: Vec<Option<usize>> = vec![None; distinct.len()];
+        for (i, name) in distinct.iter().enumerate() {
+            let home = self.slot(name);
+            if !taken[home] {
+                taken[home] = true;
+                slots[i] = Some(home);
+            }
+        }
+        for (i, name) in distinct.iter().enumerate() {
+            if slots[i].is_some() {
+                continue;
+            }
+            let home = self.slot(name);
+            // With more series than colors, some have to share
+            let free = (1..self.cycle.len())
+                .map(|step| (home + step) % self.cycle.len())
+                .find(|slot| !taken[*slot]);
+            if let Some(slot) = free {
+                taken[slot] = true;
+            }
+            slots[i] = Some(free.unwrap_or(home));
+        }
+
+        names
+            .iter()
+            .map(|name| {
+                let i = distinct.binary_search(name).unwrap_or_default();
+                self.cycle[slots[i].unwrap_or_default()]
+            })
+            .collect()
+    }
+}
+
 /// Gradient colors for visual effects
 pub const GRADIENT_PRESETS: [(&str, Color, Color); 6] = [
     ("ocean", Color::new(0, 119, 182), Color::new(0, 180, 216)),
@@ -485,6 +626,65 @@ mod tests {
         assert_eq!(CHART_COLORS.len(), 8);
     }
 
+    #[test]
+    fn test_series_colors_parse() {
+        assert_eq!(
+            SeriesColors::parse("default").unwrap(),
+            SeriesColors::default()
+        );
+        let colorblind = SeriesColors::parse("Colorblind").unwrap();
+        assert_eq!(colorblind.colors(), COLORBLIND_COLORS);
+        let custom = SeriesColors::parse("#FF0000, 00FF00").unwrap();
+        assert_eq!(custom.colors(), [named::RED, named::GREEN]);
+        assert_eq!(custom.at(3), named::GREEN);
+        assert!(SeriesColors::parse("#FF0000,teal")
+            .unwrap_err()
+            .contains("'teal' is not a #RRGGBB color"));
+    }
+
+    #[test]
+    fn test_series_color_for_name_is_stable() {
+        let colors = SeriesColors::default();
+        // Pinned so a change to the hash shows up as a test failure
+        assert_eq!(colors.slot("api"), 7);
+        assert_eq!(colors.slot("db"), 3);
+        assert_eq!(colors.for_name("api"), colors.for_name("api"));
+        assert_eq!(colors.for_name("api"), CHART_COLORS[7]);
+    }
+
+    #[test]
+    fn test_series_assign_is_unique_and_order_independent() {
+        let colors = SeriesColors::default();
+        let names = [
+            "web-1", "web-2", "web-3", "db-1", "db-2", "cache", "api", "queue",
+        ];
+        let assigned = colors.assign(&names);
+        let mut distinct = assigned.clone();
+        distinct.sort_by_key(|c| c.to_hex());
+        distinct.dedup();
+        assert_eq!(distinct.len(), names.len());
+
+        let mut reversed = names;
+        reversed.reverse();
+        let mut again = colors.assign(&reversed);
+        again.reverse();
+        assert_eq!(again, assigned);
+
+        // A series alone keeps the color its name hashes to
+        assert_eq!(colors.assign(&["api"]), [colors.for_name("api")]);
+        // Repeated names share a color
+        let twice = colors.assign(&["api", "db", "api"]);
+        assert_eq!(twice[0], twice[2]);
+        // More series than colors wrap instead of failing
+        assert_eq!(
+            SeriesColors::parse("#000000")
+                .unwrap()
+                .assign(&["a", "b"])
+                .len(),
+            2
+        );
+    }
+
     #[test]
     fn test_palette_light() {
         let p = Palette::light();
@@ -5,6 +5,8 @@ pub mod spacing;
 pub mod theme;
 
 #[allow(unused_imports)]
-pub use colors::{palette, Color, Palette, CHART_COLORS, GRADIENT_PRESETS};
+pub use colors::{
+    palette, Color, Palette, SeriesColors, CHART_COLORS, COLORBLIND_COLORS, GRADIENT_PRESETS,
+};
 #[allow(unused_imports)]
 pub use spacing::{sp, spm, Spacing, SpacingConfig, SpacingLevel};
@@ -66,6 +66,7 @@ impl SpacingLevel {
     }
 
     /// Parse from string
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "none" | "0" => Some(SpacingLevel::None),
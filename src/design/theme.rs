@@ -2,11 +2,22 @@
 
 #![allow(dead_code)]
 
-use anyhow::{Context, Result};
+use crate::design::colors::{Color, SeriesColors};
+use crate::output::atomic;
+use crate::output::gradient::Gradient;
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Overrides where the theme file is read from and written to
+pub const THEME_FILE_ENV: &str = "TERMGFX_THEME_FILE";
+
+/// Border styles a theme may pick
+pub const BORDER_STYLES: [&str; 5] = ["single", "double", "rounded", "thick", "ascii"];
 
 /// Color configuration for a theme
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +65,27 @@ pub struct Theme {
     pub spacing: Spacing,
     pub typography: Typography,
     pub is_dark: bool,
+    /// Default box border: single, double, rounded, thick, ascii
+    #[serde(default = "default_border_style")]
+    pub border_style: String,
+    /// Default gradient for banners
+    #[serde(default = "default_gradient")]
+    pub gradient: String,
+    /// Chart series colors: a cycle name or comma-separated #RRGGBB
+    #[serde(default = "default_series_colors")]
+    pub series_colors: String,
+}
+
+fn default_border_style() -> String {
+    "rounded".to_string()
+}
+
+fn default_gradient() -> String {
+    "cyan-purple".to_string()
+}
+
+fn default_series_colors() -> String {
+    "default".to_string()
 }
 
 /// Available built-in theme presets
@@ -81,6 +113,7 @@ impl ThemePreset {
         ]
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "dark" => Some(ThemePreset::Dark),
@@ -134,7 +167,7 @@ impl Theme {
 
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let json = serde_json::to_string_pretty(self).context("Failed to serialize theme")?;
-        fs::write(path, json).context("Failed to write theme file")?;
+        atomic::write(path, json).context("Failed to write theme file")?;
         Ok(())
     }
 
@@ -179,6 +212,9 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: true,
+            border_style: "rounded".to_string(),
+            gradient: "cyan-purple".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
@@ -201,6 +237,9 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: false,
+            border_style: "single".to_string(),
+            gradient: "blue-purple".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
@@ -223,6 +262,9 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: true,
+            border_style: "rounded".to_string(),
+            gradient: "green-cyan".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
@@ -245,6 +287,9 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: true,
+            border_style: "rounded".to_string(),
+            gradient: "pink-yellow".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
@@ -267,6 +312,9 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: true,
+            border_style: "thick".to_string(),
+            gradient: "red-orange".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
@@ -289,6 +337,9 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: true,
+            border_style: "single".to_string(),
+            gradient: "green-cyan".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
@@ -311,12 +362,328 @@ impl Theme {
             spacing: Self::default_spacing(),
             typography: Self::default_typography(),
             is_dark: true,
+            border_style: "double".to_string(),
+            gradient: "red-orange".to_string(),
+            series_colors: default_series_colors(),
         }
     }
 
     pub fn current() -> Self {
         Self::from_env()
     }
+
+    /// A preset or a theme from `file` by name; user themes win over presets
+    pub fn resolve(name: &str, file: &ThemeFile) -> Result<Self> {
+        if let Some(user) = file.themes.get(name) {
+            let base = user.base.as_deref().unwrap_or("dark");
+            let preset = ThemePreset::from_str(base)
+                .ok_or_else(|| anyhow!("Theme '{}' is based on unknown preset '{}'", name, base))?;
+            let theme = Self::load_preset(preset).merged(name, user);
+            theme
+                .validate()
+                .with_context(|| format!("Invalid theme '{}'", name))?;
+            return Ok(theme);
+        }
+        ThemePreset::from_str(name)
+            .map(Self::load_preset)
+            .ok_or_else(|| anyhow!("Theme '{}' not found", name))
+    }
+
+    /// This theme with the fields `user` sets replaced
+    fn merged(mut self, name: &str, user: &UserTheme) -> Self {
+        self.name = name.to_string();
+        let set = |field: &mut String, value: &Option<String>| {
+            if let Some(v) = value {
+                *field = v.clone();
+            }
+        };
+        set(&mut self.description, &user.description);
+        set(&mut self.border_style, &user.border_style);
+        set(&mut self.gradient, &user.gradient);
+        set(&mut self.series_colors, &user.series_colors);
+        let (c, o) = (&mut self.colors, &user.colors);
+        set(&mut c.primary, &o.primary);
+        set(&mut c.secondary, &o.secondary);
+        set(&mut c.success, &o.success);
+        set(&mut c.warning, &o.warning);
+        set(&mut c.danger, &o.danger);
+        set(&mut c.info, &o.info);
+        set(&mut c.background, &o.background);
+        set(&mut c.foreground, &o.foreground);
+        set(&mut c.surface, &o.surface);
+        set(&mut c.border, &o.border);
+        if let Some(dark) = user.is_dark {
+            self.is_dark = dark;
+        }
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !BORDER_STYLES.contains(&self.border_style.as_str()) {
+            bail!(
+                "unknown border style '{}' (use {})",
+                self.border_style,
+                BORDER_STYLES.join(", ")
+            );
+        }
+        if Gradient::from_name(&self.gradient).is_none() {
+            bail!("unknown gradient '{}'", self.gradient);
+        }
+        if let Err(e) = SeriesColors::parse(&self.series_colors) {
+            bail!("series_colors: {}", e);
+        }
+        let c = &self.colors;
+        for color in [
+            &c.primary,
+            &c.secondary,
+            &c.success,
+            &c.warning,
+            &c.danger,
+            &c.info,
+            &c.background,
+            &c.foreground,
+            &c.surface,
+            &c.border,
+        ] {
+            if Color::from_hex(color).is_none() {
+                bail!("'{}' is not a #RRGGBB color", color);
+            }
+        }
+        Ok(())
+    }
+
+    /// RGB of a `#RRGGBB` theme color, white when malformed
+    pub fn rgb(hex: &str) -> (u8, u8, u8) {
+        Color::from_hex(hex)
+            .map(|c| (c.r, c.g, c.b))
+            .unwrap_or((255, 255, 255))
+    }
+
+    /// Color for a semantic box/preset style (info, success, warning, danger)
+    pub fn semantic_color(&self, style: &str) -> Option<(u8, u8, u8)> {
+        let hex = match style.to_lowercase().as_str() {
+            "info" => &self.colors.info,
+            "success" => &self.colors.success,
+            "warning" => &self.colors.warning,
+            "danger" | "error" => &self.colors.danger,
+            "gradient" => &self.colors.secondary,
+            _ => return None,
+        };
+        Some(Self::rgb(hex))
+    }
+}
+
+/// Colors a user theme overrides; unset ones come from its base preset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub danger: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+}
+
+/// A theme defined in the theme file, layered over a preset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserTheme {
+    /// Preset to start from (dark when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gradient: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series_colors: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_dark: Option<bool>,
+    pub colors: ColorOverrides,
+}
+
+impl UserTheme {
+    /// The overrides that turn preset `base` into `theme`
+    pub fn from_theme(base: ThemePreset, theme: &Theme) -> Self {
+        let preset = Theme::load_preset(base);
+        let changed = |value: &String, from: &String| (value != from).then(|| value.clone());
+        let (c, p) = (&theme.colors, &preset.colors);
+        UserTheme {
+            base: Some(base.as_str().to_string()),
+            description: None,
+            border_style: changed(&theme.border_style, &preset.border_style),
+            gradient: changed(&theme.gradient, &preset.gradient),
+            series_colors: changed(&theme.series_colors, &preset.series_colors),
+            is_dark: (theme.is_dark != preset.is_dark).then_some(theme.is_dark),
+            colors: ColorOverrides {
+                primary: changed(&c.primary, &p.primary),
+                secondary: changed(&c.secondary, &p.secondary),
+                success: changed(&c.success, &p.success),
+                warning: changed(&c.warning, &p.warning),
+                danger: changed(&c.danger, &p.danger),
+                info: changed(&c.info, &p.info),
+                background: changed(&c.background, &p.background),
+                foreground: changed(&c.foreground, &p.foreground),
+                surface: changed(&c.surface, &p.surface),
+                border: changed(&c.border, &p.border),
+            },
+        }
+    }
+}
+
+/// Contents of `~/.config/termgfx/theme.toml` (or `theme.json`)
+///
+/// ```toml
+/// theme = "ocean"
+///
+/// [themes.ocean]
+/// base = "nord"
+/// border_style = "double"
+/// gradient = "green-cyan"
+/// series_colors = "colorblind"
+/// colors = { primary = "#88C0D0" }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeFile {
+    /// Theme used when neither `--theme` nor `TERMGFX_THEME` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, UserTheme>,
+}
+
+impl ThemeFile {
+    /// Where the theme file lives: `$TERMGFX_THEME_FILE`, else `theme.toml` in
+    /// the termgfx config directory (`theme.json` if only that exists)
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(THEME_FILE_ENV) {
+            return Some(PathBuf::from(path));
+        }
+        let dir = dirs::config_dir()?.join("termgfx");
+        let json = dir.join("theme.json");
+        let toml = dir.join("theme.toml");
+        Some(if !toml.exists() && json.exists() {
+            json
+        } else {
+            toml
+        })
+    }
+
+    /// Parse a theme file; `.json` files are JSON, anything else TOML
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read theme file {}", path.display()))?;
+        if is_json(path) {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))
+        } else {
+            toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+        }
+    }
+
+    /// The user's theme file, empty when there is none
+    pub fn load_user() -> Result<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load(path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let content = if is_json(path) {
+            serde_json::to_string_pretty(self).context("Failed to serialize theme file")?
+        } else {
+            toml::to_string_pretty(self).context("Failed to serialize theme file")?
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context("Failed to create config directory")?;
+        }
+        atomic::write(path, content)
+            .with_context(|| format!("Failed to write theme file {}", path.display()))
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// Where the active theme came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeSource {
+    Flag,
+    Env,
+    File,
+}
+
+static ACTIVE: OnceLock<Option<(Theme, ThemeSource)>> = OnceLock::new();
+
+/// Pick the theme for this run: `--theme`, then `TERMGFX_THEME`, then the
+/// theme file's `theme` key. Without any of them commands keep their
+/// built-in look, as they do when the environment or the theme file names
+/// a theme that can't be loaded; only a bad `--theme` is an error.
+pub fn init(flag: Option<&str>) -> Result<()> {
+    let file = ThemeFile::load_user().unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}; ignoring it", e);
+        ThemeFile::default()
+    });
+    let active = configured(flag, &file)?;
+    ACTIVE.get_or_init(|| active);
+    Ok(())
+}
+
+/// The configured theme; `None` until [`init`] picks one
+pub fn active() -> Option<&'static Theme> {
+    active_with_source().map(|(theme, _)| theme)
+}
+
+pub fn active_with_source() -> Option<(&'static Theme, ThemeSource)> {
+    ACTIVE
+        .get()?
+        .as_ref()
+        .map(|(theme, source)| (theme, *source))
+}
+
+/// The theme `--theme`, `TERMGFX_THEME` or `file` picks, if any. One named
+/// by the environment or the file that doesn't resolve is reported and
+/// skipped.
+pub fn configured(flag: Option<&str>, file: &ThemeFile) -> Result<Option<(Theme, ThemeSource)>> {
+    let env_name = env::var("TERMGFX_THEME").ok().filter(|s| !s.is_empty());
+    let chosen = match (flag, env_name) {
+        (Some(name), _) => Some((name.to_string(), ThemeSource::Flag)),
+        (None, Some(name)) => Some((name, ThemeSource::Env)),
+        (None, None) => file.theme.clone().map(|name| (name, ThemeSource::File)),
+    };
+    let Some((name, source)) = chosen else {
+        return Ok(None);
+    };
+    match Theme::resolve(&name, file) {
+        Ok(theme) => Ok(Some((theme, source))),
+        Err(e) if source == ThemeSource::Flag => Err(e),
+        Err(e) => {
+            eprintln!("Warning: {:#}; using the built-in theme", e);
+            Ok(None)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -429,4 +796,101 @@ mod tests {
         assert_eq!(theme.name, "dark");
         env::remove_var("TERMGFX_THEME");
     }
+
+    const USER_FILE: &str = r##"
+theme = "ocean"
+
+[themes.ocean]
+base = "nord"
+border_style = "double"
+series_colors = "colorblind"
+colors = { primary = "#112233" }
+
+[themes.broken]
+gradient = "plaid"
+
+[themes.muddy]
+series_colors = "#112233,brown"
+"##;
+
+    #[test]
+    fn test_resolve_user_theme_merges_over_base() {
+        let file: ThemeFile = toml::from_str(USER_FILE).unwrap();
+        assert_eq!(file.theme.as_deref(), Some("ocean"));
+        let theme = Theme::resolve("ocean", &file).unwrap();
+        let nord = Theme::nord();
+        assert_eq!(theme.name, "ocean");
+        assert_eq!(theme.border_style, "double");
+        assert_eq!(theme.colors.primary, "#112233");
+        assert_eq!(theme.colors.success, nord.colors.success);
+        assert_eq!(theme.gradient, nord.gradient);
+        assert_eq!(theme.series_colors, "colorblind");
+        assert_eq!(nord.series_colors, "default");
+    }
+
+    #[test]
+    fn test_resolve_presets_and_errors() {
+        let file: ThemeFile = toml::from_str(USER_FILE).unwrap();
+        assert_eq!(Theme::resolve("dracula", &file).unwrap().name, "dracula");
+        let err = Theme::resolve("nope", &file).unwrap_err();
+        assert_eq!(err.to_string(), "Theme 'nope' not found");
+        let err = Theme::resolve("broken", &file).unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown gradient 'plaid'"));
+        let err = Theme::resolve("muddy", &file).unwrap_err();
+        assert!(format!("{:#}", err).contains("'brown' is not a #RRGGBB color"));
+    }
+
+    #[test]
+    fn test_presets_are_valid_themes() {
+        for preset in ThemePreset::all() {
+            Theme::load_preset(preset).validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_theme_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file: ThemeFile = toml::from_str(USER_FILE).unwrap();
+        for name in ["theme.toml", "theme.json"] {
+            let path = dir.path().join(name);
+            file.save(&path).unwrap();
+            let loaded = ThemeFile::load(&path).unwrap();
+            assert_eq!(loaded.theme.as_deref(), Some("ocean"));
+            assert_eq!(
+                loaded.themes["ocean"].colors.primary.as_deref(),
+                Some("#112233")
+            );
+        }
+    }
+
+    #[test]
+    fn test_user_theme_from_theme_keeps_only_changes() {
+        let mut theme = Theme::nord();
+        theme.colors.primary = "#112233".to_string();
+        theme.border_style = "thick".to_string();
+        let user = UserTheme::from_theme(ThemePreset::Nord, &theme);
+        assert_eq!(user.base.as_deref(), Some("nord"));
+        assert_eq!(user.colors.primary.as_deref(), Some("#112233"));
+        assert_eq!(user.border_style.as_deref(), Some("thick"));
+        assert_eq!(user.colors.success, None);
+        assert_eq!(user.gradient, None);
+        assert_eq!(user.is_dark, None);
+
+        let mut file = ThemeFile::default();
+        file.themes.insert("mine".to_string(), user);
+        let resolved = Theme::resolve("mine", &file).unwrap();
+        assert_eq!(resolved.colors.primary, "#112233");
+        assert_eq!(resolved.colors.success, theme.colors.success);
+        assert_eq!(resolved.border_style, "thick");
+    }
+
+    #[test]
+    fn test_semantic_color() {
+        let theme = Theme::dark();
+        assert_eq!(
+            theme.semantic_color("Success"),
+            Some(Theme::rgb(&theme.colors.success))
+        );
+        assert_eq!(theme.semantic_color("neutral"), None);
+    }
 }
@@ -0,0 +1,278 @@
+//! Headless rendering for CI bots
+//!
+//! `termgfx ci-comment` runs another termgfx command with colors forced on,
+//! turns the captured output into an SVG and prints a Markdown snippet to
+//! post as a pull request comment. An uploader command can publish the SVG
+//! first, so the snippet links to a URL instead of a local path.
+
+use super::terminal;
+use crate::animation::demo::command_line;
+use crate::interactive::repl::split_args;
+use crate::output::cache;
+use std::env;
+use std::process::{Command, Stdio};
+
+/// Uploader command used when --upload is not given
+pub const UPLOADER_ENV: &str = "TERMGFX_UPLOADER";
+
+pub const FORMATS: &[&str] = &["md-svg", "md-text", "svg"];
+
+/// What `ci-comment` prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentFormat {
+    /// Markdown image of the SVG, with the text in a collapsed block
+    MdSvg,
+    /// Markdown with only the plain text in a code block
+    MdText,
+    /// The SVG itself
+    Svg,
+}
+
+impl CommentFormat {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "md-svg" => Some(CommentFormat::MdSvg),
+            "md-text" => Some(CommentFormat::MdText),
+            "svg" => Some(CommentFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+pub struct CiOptions<'a> {
+    pub command: &'a str,
+    pub format: &'a str,
+    pub output: &'a str,
+    pub title: Option<&'a str>,
+    pub upload: Option<&'a str>,
+    pub background: &'a str,
+    pub theme: Option<&'a str>,
+}
+
+/// Arguments of `command`, without a leading "termgfx"
+fn command_args(command: &str) -> Result<Vec<String>, String> {
+    let mut args = split_args(command)?;
+    if args.first().is_some_and(|a| a == "termgfx") {
+        args.remove(0);
+    }
+    match args.first().map(String::as_str) {
+        None => Err("Empty --command".to_string()),
+        Some("ci-comment") => Err("ci-comment can't render itself".to_string()),
+        Some(name) if !cache::renders_once(name, &args) => {
+            Err(format!("'{}' is live and can't be rendered headless", name))
+        }
+        Some(_) => Ok(args),
+    }
+}
+
+/// Run termgfx with `args` and return what it printed, colors included
+///
+/// Colors are forced through the environment rather than `--color`, so a
+/// command that sets its own color flags still parses.
+pub(super) fn capture(
+    args: &[String],
+    theme: Option<&str>,
+    stdin: Stdio,
+) -> Result<String, String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let mut command = Command::new(exe);
+    command
+        .env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .env("COLORTERM", "truecolor");
+    if let Some(theme) = theme {
+        command.args(["--theme", theme]);
+    }
+    let output = command
+        .args(args)
+        .stdin(stdin)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.trim().trim_start_matches("Error: ");
+        return Err(if message.is_empty() {
+            format!("Command failed: {}", args.join(" "))
+        } else {
+            format!("Command failed: {}", message)
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The uploader command for `file`: `{file}` is replaced, else appended
+fn uploader_command(template: &str, file: &str) -> String {
+    let quoted = if cfg!(windows) {
+        format!("\"{}\"", file)
+    } else {
+        format!("'{}'", file.replace('\'', r"'\''"))
+    };
+    if template.contains("{file}") {
+        template.replace("{file}", &quoted)
+    } else {
+        format!("{} {}", template, quoted)
+    }
+}
+
+/// Run the uploader and return the URL on the last line it prints
+fn upload(template: &str, file: &str) -> Result<String, String> {
+    let command = uploader_command(template, file);
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", &command]).output()
+    } else {
+        Command::new("sh").args(["-c", &command]).output()
+    }
+    .map_err(|e| format!("Uploader failed: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Uploader failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "Uploader printed no URL".to_string())
+}
+
+/// A fence longer than any run of backticks in `text`
+fn fence(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// The Markdown comment; `image` is the SVG's URL or path, if shown
+fn snippet(command: &str, title: Option<&str>, image: Option<&str>, text: &str) -> String {
+    let mut out = format!("<!-- {} -->\n", command.replace("--", "- -"));
+    if let Some(title) = title {
+        out.push_str(&format!("**{}**\n\n", title));
+    }
+    let fence = fence(text);
+    let block = format!("{}text\n{}\n{}\n", fence, text, fence);
+    match image {
+        Some(image) => {
+            let alt = command.replace(['[', ']'], "");
+            out.push_str(&format!("![{}]({})\n\n", alt, image));
+            out.push_str("<details><summary>Text output</summary>\n\n");
+            out.push_str(&block);
+            out.push_str("\n</details>\n");
+        }
+        None => out.push_str(&block),
+    }
+    out
+}
+
+fn build(options: &CiOptions) -> Result<String, String> {
+    let format = CommentFormat::from_name(options.format).ok_or_else(|| {
+        format!(
+            "Invalid format '{}' (use {})",
+            options.format,
+            FORMATS.join(", ")
+        )
+    })?;
+    let args = command_args(options.command)?;
+    let ansi = capture(&args, options.theme, Stdio::null())?;
+    let command = command_line(&args.iter().map(String::as_str).collect::<Vec<_>>());
+
+    if format == CommentFormat::MdText {
+        return Ok(snippet(
+            &command,
+            options.title,
+            None,
+            &terminal::plain(&ansi),
+        ));
+    }
+    let svg = terminal::to_svg(&ansi, options.background);
+    if format == CommentFormat::Svg {
+        return Ok(svg);
+    }
+
+    crate::output::atomic::write(options.output, &svg)
+        .map_err(|e| format!("Failed to write {}: {}", options.output, e))?;
+    let uploader = options
+        .upload
+        .map(str::to_string)
+        .or_else(|| env::var(UPLOADER_ENV).ok().filter(|u| !u.trim().is_empty()));
+    let image = match uploader {
+        Some(template) => upload(&template, options.output)?,
+        None => options.output.to_string(),
+    };
+    Ok(snippet(
+        &command,
+        options.title,
+        Some(&image),
+        &terminal::plain(&ansi),
+    ))
+}
+
+/// Print the comment for `options.command`
+pub fn render(options: &CiOptions) {
+    match build(options) {
+        Ok(out) => print!("{}", out),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_args() {
+        assert_eq!(
+            command_args("termgfx chart bar --data 'A:1,B:2'").unwrap(),
+            vec!["chart", "bar", "--data", "A:1,B:2"]
+        );
+        assert!(command_args("").is_err());
+        assert!(command_args("select a b").unwrap_err().contains("is live"));
+        assert!(command_args("ci-comment --command box").is_err());
+    }
+
+    #[test]
+    fn test_uploader_command() {
+        assert_eq!(
+            uploader_command("curl -F f=@{file} https://x", "a b.svg"),
+            "curl -F f=@'a b.svg' https://x"
+        );
+        assert_eq!(
+            uploader_command("upload", "it's.svg"),
+            r"upload 'it'\''s.svg'"
+        );
+    }
+
+    #[test]
+    fn test_fence_outgrows_backticks() {
+        assert_eq!(fence("plain"), "```");
+        assert_eq!(fence("a ```` b"), "`````");
+    }
+
+    #[test]
+    fn test_snippet() {
+        let md = snippet(
+            "termgfx box hi",
+            Some("Preview"),
+            Some("https://x/a.svg"),
+            "hi",
+        );
+        assert!(md.starts_with("<!-- termgfx box hi -->\n**Preview**\n\n"));
+        assert!(md.contains("![termgfx box hi](https://x/a.svg)"));
+        assert!(md.contains("<details><summary>Text output</summary>\n\n```text\nhi\n```\n"));
+
+        let md = snippet("termgfx chart bar --data A:1", None, None, "A 1");
+        assert!(md.starts_with("<!-- termgfx chart bar - -data A:1 -->\n```text\n"));
+        assert!(!md.contains("<details>"));
+    }
+
+    #[test]
+    fn test_format_names() {
+        for name in FORMATS {
+            assert!(CommentFormat::from_name(name).is_some());
+        }
+        assert_eq!(CommentFormat::from_name("png"), None);
+    }
+}
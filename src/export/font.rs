@@ -0,0 +1,561 @@
+//! Glyphs for rasterizing terminal cells
+//!
+//! Text comes from DejaVu Sans Mono (Bitstream Vera license), rasterized
+//! once at 10×20 pixels per cell with 4 bits of coverage per pixel: ASCII,
+//! Latin-1 and the symbols termgfx prints. Block elements, box drawing and
+//! Braille are drawn from their geometry instead, so bars, borders and plots
+//! join across cells without gaps.
+
+pub(super) const CELL_WIDTH: usize = 10;
+pub(super) const CELL_HEIGHT: usize = 20;
+/// Row the glyphs stand on
+const BASELINE: usize = 15;
+
+/// Coverage of each pixel in a cell, from 0 (background) to 255 (foreground)
+pub(super) type Mask = [[u8; CELL_WIDTH]; CELL_HEIGHT];
+
+const EMPTY: Mask = [[0; CELL_WIDTH]; CELL_HEIGHT];
+
+/// The glyph for `c`, or `None` when there is none to draw it with
+pub(super) fn mask(c: char) -> Option<Mask> {
+    if c == ' ' {
+        return Some(EMPTY);
+    }
+    if let Some(mask) = block(c).or_else(|| box_drawing(c)).or_else(|| braille(c)) {
+        return Some(mask);
+    }
+    let index = GLYPHS.binary_search_by_key(&c, |(g, _)| *g).ok()?;
+    let rows = &GLYPHS[index].1;
+    let mut mask = EMPTY;
+    for (row, bits) in mask.iter_mut().zip(rows) {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            let nibble = (bits >> (4 * (CELL_WIDTH - 1 - x))) & 0xf;
+            *pixel = nibble as u8 * 17;
+        }
+    }
+    Some(mask)
+}
+
+/// Outline drawn in place of characters without a glyph, `cells` wide
+pub(super) fn missing(cells: usize) -> Vec<Vec<u8>> {
+    let width = CELL_WIDTH * cells.max(1);
+    let mut rows = vec![vec![0; width]; CELL_HEIGHT];
+    let (top, bottom) = (4, BASELINE);
+    for (y, row) in rows.iter_mut().enumerate().take(bottom + 1).skip(top) {
+        if y == top || y == bottom {
+            row[1..width - 1].fill(255);
+        } else {
+            row[1] = 255;
+            row[width - 2] = 255;
+        }
+    }
+    rows
+}
+
+/// Thicken strokes by a pixel, for bold text
+pub(super) fn embolden(mask: &mut Mask) {
+    for row in mask.iter_mut() {
+        for x in (1..CELL_WIDTH).rev() {
+            row[x] = row[x].max(row[x - 1]);
+        }
+    }
+}
+
+/// Lean the glyph right above the baseline, for italic text
+pub(super) fn slant(mask: &mut Mask) {
+    for (y, row) in mask.iter_mut().enumerate() {
+        let shift = BASELINE.saturating_sub(y) / 5;
+        if shift > 0 {
+            row.rotate_right(shift);
+            row[..shift].fill(0);
+        }
+    }
+}
+
+/// Fill the part of `mask` in columns `x` and rows `y` with `value`
+fn fill(mask: &mut Mask, x: std::ops::Range<usize>, y: std::ops::Range<usize>, value: u8) {
+    for row in &mut mask[y] {
+        row[x.clone()].fill(value);
+    }
+}
+
+/// Block elements (U+2580–U+259F)
+fn block(c: char) -> Option<Mask> {
+    const W: usize = CELL_WIDTH;
+    const H: usize = CELL_HEIGHT;
+    let eighths_down = |n: usize| H - (H * n).div_ceil(8);
+    let eighths_across = |n: usize| (W * n).div_ceil(8);
+    let mut mask = EMPTY;
+    match c {
+        '▀' => fill(&mut mask, 0..W, 0..H / 2, 255),
+        '▁'..='█' => {
+            let n = c as usize - '▀' as usize;
+            fill(&mut mask, 0..W, eighths_down(n)..H, 255);
+        }
+        '▉'..='▏' => {
+            let n = '▐' as usize - c as usize;
+            fill(&mut mask, 0..eighths_across(n), 0..H, 255);
+        }
+        '▐' => fill(&mut mask, W / 2..W, 0..H, 255),
+        '░' => fill(&mut mask, 0..W, 0..H, 64),
+        '▒' => fill(&mut mask, 0..W, 0..H, 128),
+        '▓' => fill(&mut mask, 0..W, 0..H, 192),
+        '▔' => fill(&mut mask, 0..W, 0..H - eighths_down(1), 255),
+        '▕' => fill(&mut mask, W - eighths_across(1)..W, 0..H, 255),
+        '▖'..='▟' => {
+            // Upper left, upper right, lower left, lower right
+            let quadrants: [bool; 4] = match c {
+                '▖' => [false, false, true, false],
+                '▗' => [false, false, false, true],
+                '▘' => [true, false, false, false],
+                '▙' => [true, false, true, true],
+                '▚' => [true, false, false, true],
+                '▛' => [true, true, true, false],
+                '▜' => [true, true, false, true],
+                '▝' => [false, true, false, false],
+                '▞' => [false, true, true, false],
+                _ => [false, true, true, true],
+            };
+            for (i, on) in quadrants.iter().enumerate() {
+                if *on {
+                    let x = if i % 2 == 0 { 0..W / 2 } else { W / 2..W };
+                    let y = if i < 2 { 0..H / 2 } else { H / 2..H };
+                    fill(&mut mask, x, y, 255);
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(mask)
+}
+
+/// Weight of each arm of a box drawing character: up, right, down, left
+///
+/// 1 is light, 2 heavy and 3 double. Dashed lines draw solid and rounded
+/// corners square.
+fn arms(c: char) -> Option<[u8; 4]> {
+    Some(match c {
+        '─' | '┄' | '┈' | '╌' => [0, 1, 0, 1],
+        '━' | '┅' | '┉' | '╍' => [0, 2, 0, 2],
+        '│' | '┆' | '┊' | '╎' => [1, 0, 1, 0],
+        '┃' | '┇' | '┋' | '╏' => [2, 0, 2, 0],
+        '┌' | '╭' => [0, 1, 1, 0],
+        '┐' | '╮' => [0, 0, 1, 1],
+        '└' | '╰' => [1, 1, 0, 0],
+        '┘' | '╯' => [1, 0, 0, 1],
+        '├' => [1, 1, 1, 0],
+        '┤' => [1, 0, 1, 1],
+        '┬' => [0, 1, 1, 1],
+        '┴' => [1, 1, 0, 1],
+        '┼' => [1, 1, 1, 1],
+        '┏' => [0, 2, 2, 0],
+        '┓' => [0, 0, 2, 2],
+        '┗' => [2, 2, 0, 0],
+        '┛' => [2, 0, 0, 2],
+        '┣' => [2, 2, 2, 0],
+        '┫' => [2, 0, 2, 2],
+        '┳' => [0, 2, 2, 2],
+        '┻' => [2, 2, 0, 2],
+        '╋' => [2, 2, 2, 2],
+        '═' => [0, 3, 0, 3],
+        '║' => [3, 0, 3, 0],
+        '╔' => [0, 3, 3, 0],
+        '╗' => [0, 0, 3, 3],
+        '╚' => [3, 3, 0, 0],
+        '╝' => [3, 0, 0, 3],
+        '╠' => [3, 3, 3, 0],
+        '╣' => [3, 0, 3, 3],
+        '╦' => [0, 3, 3, 3],
+        '╩' => [3, 3, 0, 3],
+        '╬' => [3, 3, 3, 3],
+        '╴' => [0, 0, 0, 1],
+        '╵' => [1, 0, 0, 0],
+        '╶' => [0, 1, 0, 0],
+        '╷' => [0, 0, 1, 0],
+        '╸' => [0, 0, 0, 2],
+        '╹' => [2, 0, 0, 0],
+        '╺' => [0, 2, 0, 0],
+        '╻' => [0, 0, 2, 0],
+        _ => return None,
+    })
+}
+
+/// Box drawing (U+2500–U+257F): arms from the middle of the cell to its edges
+fn box_drawing(c: char) -> Option<Mask> {
+    const W: usize = CELL_WIDTH;
+    const H: usize = CELL_HEIGHT;
+    let [up, right, down, left] = arms(c)?;
+    let (cx, cy) = (W / 2, H / 2);
+    // Strokes of each weight as (offset from the middle, thickness)
+    let strokes = |weight: u8| -> &'static [(isize, usize)] {
+        match weight {
+            1 => &[(0, 1)],
+            2 => &[(-1, 3)],
+            3 => &[(-2, 1), (2, 1)],
+            _ => &[],
+        }
+    };
+    // Rows or columns the strokes of a weight cover, around the middle
+    let reach = |weight: u8| -> (isize, isize) {
+        match weight {
+            2 => (-1, 2),
+            3 => (-2, 3),
+            _ => (0, 1),
+        }
+    };
+    let at = |middle: usize, offset: isize| middle.saturating_add_signed(offset);
+    // Arms run into the middle as far as the crossing strokes, so they join
+    let (top, bottom) = reach(left.max(right));
+    let (start, end) = reach(up.max(down));
+    let mut mask = EMPTY;
+    for (offset, thickness) in strokes(up) {
+        let x = at(cx, *offset);
+        fill(&mut mask, x..x + thickness, 0..at(cy, bottom), 255);
+    }
+    for (offset, thickness) in strokes(down) {
+        let x = at(cx, *offset);
+        fill(&mut mask, x..x + thickness, at(cy, top)..H, 255);
+    }
+    for (offset, thickness) in strokes(left) {
+        let y = at(cy, *offset);
+        fill(&mut mask, 0..at(cx, end), y..y + thickness, 255);
+    }
+    for (offset, thickness) in strokes(right) {
+        let y = at(cy, *offset);
+        fill(&mut mask, at(cx, start)..W, y..y + thickness, 255);
+    }
+    Some(mask)
+}
+
+/// Braille (U+2800–U+28FF): two columns of four dots
+fn braille(c: char) -> Option<Mask> {
+    let bits = (c as u32).checked_sub(0x2800).filter(|b| *b <= 0xff)?;
+    // Bit for each dot, by column then row
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    let (dx, dy) = (CELL_WIDTH / 2, CELL_HEIGHT / 4);
+    let mut mask = EMPTY;
+    for (col, dots) in DOTS.iter().enumerate() {
+        for (row, bit) in dots.iter().enumerate() {
+            if bits & bit != 0 {
+                let (x, y) = (col * dx + dx / 2 - 1, row * dy + dy / 2 - 1);
+                fill(&mut mask, x..x + 2, y..y + 2, 255);
+            }
+        }
+    }
+    Some(mask)
+}
+
+/// Glyph rows, one hex digit of coverage per pixel from the left
+#[rustfmt::skip]
+static GLYPHS: &[(char, [u64; CELL_HEIGHT])] = &[
+    ('!', [0, 0, 0, 0x770000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xbc0000, 0x550000, 0, 0x770000, 0xcc0000, 0x550000, 0, 0, 0, 0]),
+    ('"', [0, 0, 0, 0x29229200, 0x3f33f300, 0x3f33f300, 0x3f33f300, 0x2c22c200, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('#', [0, 0, 0, 0x530350, 0xe50aa0, 0x4f10e50, 0x7d02f20, 0xfffffffff, 0x33f73ba33, 0x4f10e500, 0x7d02f200, 0xfffffffff0, 0x33f73ba330, 0x4f10e5000, 0x7d02f1000, 0x440250000, 0, 0, 0, 0]),
+    ('$', [0, 0, 0x120000, 0x390000, 0x5a2000, 0x5efffe20, 0x4f7391520, 0x6f0390000, 0x5f5390000, 0xbfdd5100, 0x5aeee40, 0x391cd0, 0x3908f0, 0x330390bd0, 0x5fdbdde40, 0x158b5100, 0x390000, 0x390000, 0, 0]),
+    ('%', [0, 0, 0, 0x11000000, 0x1bfe800000, 0x8b12e40000, 0xc600a60000, 0x8b12e40021, 0x1bfe715bb2, 0x28d8200, 0x15bb528940, 0x28201e87e4, 0x6a007a, 0x5b008a, 0x1dbae3, 0x16620, 0, 0, 0, 0]),
+    ('&', [0, 0, 0, 0x18cca000, 0xbe77b000, 0xf7000000, 0xf7000000, 0xae100000, 0x1bfb00000, 0xbb5f70057, 0x4f208e309a, 0x8e000bd1a8, 0x7f1002ebe4, 0x3f90005fb0, 0x8fb9aede3, 0x49c94164, 0, 0, 0, 0]),
+    ('\'', [0, 0, 0, 0x750000, 0xc90000, 0xc90000, 0xc90000, 0xa70000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('(', [0, 0, 0x13100, 0x7d000, 0x1e5000, 0x8e0000, 0xd90000, 0x2f50000, 0x6f30000, 0x8f00000, 0x9f00000, 0x7f10000, 0x5f40000, 0x1f70000, 0xbb0000, 0x5f1000, 0xd8000, 0x38000, 0, 0]),
+    (')', [0, 0, 0x13100000, 0xd700000, 0x5e10000, 0xe80000, 0x9d0000, 0x5f2000, 0x3f6000, 0xf8000, 0xf9000, 0x1f7000, 0x4f5000, 0x7f1000, 0xbb0000, 0x2f50000, 0x8d00000, 0x8300000, 0, 0]),
+    ('*', [0, 0, 0, 0x550000, 0x210660120, 0x4d6666d40, 0x18ee8100, 0x2aeea200, 0x5d5665d50, 0x110660110, 0x550000, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('+', [0, 0, 0, 0, 0, 0, 0x990000, 0x990000, 0x990000, 0x1666bb6661, 0x3ffffffff3, 0x990000, 0x990000, 0x990000, 0x550000, 0, 0, 0, 0, 0]),
+    (',', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff3000, 0xff2000, 0x2fb0000, 0x5f30000, 0x7800000, 0, 0]),
+    ('-', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x2cccc200, 0x29999200, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('.', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff0000, 0xff0000, 0x660000, 0, 0, 0, 0]),
+    ('/', [0, 0, 0, 0x860, 0x5f40, 0xbb00, 0x4f400, 0xbd000, 0x2f5000, 0xad0000, 0x2f70000, 0x8e10000, 0x1e700000, 0x8e100000, 0x1e8000000, 0x7f1000000, 0x1ea0000000, 0, 0, 0]),
+    ('0', [0, 0, 0, 0x18cc8100, 0xbe88eb00, 0x4f6006f40, 0xaf1001fa0, 0xcc0000cc0, 0xdc0770cd0, 0xfc0ff0cf0, 0xdc0540cd0, 0xcd0000dc0, 0x8f1001f80, 0x4f7007f40, 0xafaafa00, 0x7bb7000, 0, 0, 0, 0]),
+    ('1', [0, 0, 0, 0x25894000, 0xffef6000, 0x523f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0xacdfdca0, 0x56666650, 0, 0, 0, 0]),
+    ('2', [0, 0, 0, 0x27bcb7100, 0x9ea9afb00, 0x410007f50, 0x3f70, 0x4f50, 0xbe10, 0x7f400, 0x6f6000, 0x5f80000, 0x4e900000, 0x4e9000000, 0xcfccccc70, 0x566666640, 0, 0, 0, 0]),
+    ('3', [0, 0, 0, 0x48bcb7100, 0x9da9afb10, 0x100006f50, 0x3f60, 0x7f40, 0x79ce600, 0xacde600, 0x7f50, 0xea0, 0xeb0, 0x400006f80, 0xceb9dfd10, 0x48aca6100, 0, 0, 0, 0]),
+    ('4', [0, 0, 0, 0x59500, 0x1ef900, 0xa9f900, 0x4e1f900, 0x1d60f900, 0x8c00f900, 0x3f300f900, 0xc9000f900, 0x3fdcccfec2, 0x299999fd92, 0xf900, 0xf900, 0x6400, 0, 0, 0, 0]),
+    ('5', [0, 0, 0, 0x499999700, 0x6fdccca00, 0x6f3000000, 0x6f3000000, 0x6f7762000, 0x6fedff600, 0x22002bf30, 0x2f80, 0xf90, 0x1f90, 0x20000af40, 0xcda9df900, 0x49bc95000, 0, 0, 0, 0]),
+    ('6', [0, 0, 0, 0x5acb800, 0x8fc99d00, 0x2f9000000, 0x8e1000000, 0xbb1575000, 0xdaddcfb10, 0xff8004f80, 0xdf1000dc0, 0xcc0000af0, 0xae0000cd0, 0x5f5002f90, 0xbea9ed20, 0x17ab8200, 0, 0, 0, 0]),
+    ('7', [0, 0, 0, 0x799999970, 0xaccccdf90, 0x5f30, 0xbd00, 0x2f700, 0x8f100, 0xea000, 0x5f5000, 0xbe0000, 0x2f80000, 0x8f30000, 0xdc00000, 0x16300000, 0, 0, 0, 0]),
+    ('8', [0, 0, 0, 0x29cc9200, 0x2ed77de20, 0x8f2002f80, 0x9f0000f90, 0x6f2002f60, 0x8e99e800, 0x8ecce800, 0x8f4004f80, 0xdc0000cd0, 0xec0000ce0, 0xbe2002eb0, 0x3ee99ee30, 0x28bb8200, 0, 0, 0, 0]),
+    ('9', [0, 0, 0, 0x3acc7100, 0x3ed78eb00, 0xbe1005f40, 0xea0000f80, 0xf90000fc0, 0xdc0001fc0, 0x8f501aec0, 0x1bfdebac0, 0x4650cb0, 0x1f70, 0x1001ae10, 0xeaaef600, 0x7ab83000, 0, 0, 0, 0]),
+    (':', [0, 0, 0, 0, 0, 0, 0x330000, 0xff0000, 0xff0000, 0x660000, 0, 0, 0, 0xff0000, 0xff0000, 0x660000, 0, 0, 0, 0]),
+    (';', [0, 0, 0, 0, 0, 0, 0x330000, 0xff0000, 0xff0000, 0x660000, 0, 0, 0, 0xff3000, 0xff2000, 0x2fb0000, 0x5f30000, 0x7800000, 0, 0]),
+    ('<', [0, 0, 0, 0, 0, 0, 0x172, 0x4afe2, 0x17dfb610, 0x1afd820000, 0x3fd4000000, 0x4afe82000, 0x17dfc61, 0x4ae3, 0x11, 0, 0, 0, 0, 0]),
+    ('=', [0, 0, 0, 0, 0, 0, 0, 0, 0x3ffffffff3, 0x1666666661, 0, 0x2cccccccc2, 0x2999999992, 0, 0, 0, 0, 0, 0, 0]),
+    ('>', [0, 0, 0, 0, 0, 0, 0x2710000000, 0x2efa400000, 0x16bfd7100, 0x28dfa1, 0x4df3, 0x28efa40, 0x16cfd71000, 0x3ea4000000, 0x1100000000, 0, 0, 0, 0, 0]),
+    ('?', [0, 0, 0, 0x28cc9200, 0xfc9aed10, 0x40005f50, 0x4f50, 0x1cd10, 0x1bd200, 0xae2000, 0xe90000, 0xf90000, 0x320000, 0x950000, 0xf90000, 0x640000, 0, 0, 0, 0]),
+    ('@', [0, 0, 0, 0, 0x5acb710, 0x1ae867db0, 0x8d20002e4, 0x2f400462a8, 0x7c00beced9, 0xa805e202e9, 0xc609a000a9, 0xc609900099, 0xa806e101e9, 0x7c01bd9dd9, 0x2f30169444, 0x9d1000000, 0x1bd621410, 0x7cffe40, 0, 0]),
+    ('A', [0, 0, 0, 0x1991000, 0x5ff5000, 0xacda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('B', [0, 0, 0, 0x599997100, 0x9fcccee20, 0x9f0002fa0, 0x9f0000cc0, 0x9f0002fa0, 0x9f99aeb20, 0x9fccceb20, 0x9f0001dd0, 0x9f00007f2, 0x9f00007f3, 0x9f0001de1, 0x9fccdfe50, 0x466665100, 0, 0, 0, 0]),
+    ('C', [0, 0, 0, 0x29cca40, 0x4ed99cc0, 0x1ec100040, 0x7f4000000, 0xbf0000000, 0xcd0000000, 0xcc0000000, 0xcd0000000, 0xaf1000000, 0x6f4000000, 0x1ed100050, 0x4eea9dc0, 0x18bb820, 0, 0, 0, 0]),
+    ('D', [0, 0, 0, 0x799951000, 0xceccfe400, 0xcc001be20, 0xcc0002f80, 0xcc0000ec0, 0xcc0000ce0, 0xcc0000cf0, 0xcc0000ce0, 0xcc0000ec0, 0xcc0004f80, 0xcc003de10, 0xcecefd400, 0x566640000, 0, 0, 0, 0]),
+    ('E', [0, 0, 0, 0x499999970, 0x6fdcccca0, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fa999950, 0x6fdcccc70, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fdccccc0, 0x266666660, 0, 0, 0, 0]),
+    ('F', [0, 0, 0, 0x299999990, 0x3feccccc0, 0x3f9000000, 0x3f9000000, 0x3f9000000, 0x3fd999950, 0x3fecccc70, 0x3f9000000, 0x3f9000000, 0x3f9000000, 0x3f9000000, 0x3f9000000, 0x164000000, 0, 0, 0, 0]),
+    ('G', [0, 0, 0, 0x5acb810, 0x9fb99e90, 0x5f8000150, 0xbe1000000, 0xfa0000000, 0x2f90000000, 0x3f9005ccc0, 0x2f90049df0, 0xeb00009f0, 0xbe10009f0, 0x4f90009f0, 0x8fc9afd0, 0x48ca610, 0, 0, 0, 0]),
+    ('H', [0, 0, 0, 0x770000770, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xce9999ec0, 0xceccccec0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0x550000550, 0, 0, 0, 0]),
+    ('I', [0, 0, 0, 0x499999940, 0x5cceecc50, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x5cceecc50, 0x266666620, 0, 0, 0, 0]),
+    ('J', [0, 0, 0, 0x9999700, 0xcccec00, 0xcc00, 0xcc00, 0xcc00, 0xcc00, 0xcc00, 0xcc00, 0xcc00, 0xfb00, 0x270002f800, 0x3fdaaee200, 0x48bb82000, 0, 0, 0, 0]),
+    ('K', [0, 0, 0, 0x770000495, 0xcc0004eb1, 0xcc004eb10, 0xcc02db100, 0xcc2dc1000, 0xcddf50000, 0xcfddd1000, 0xce24f8000, 0xcc00af300, 0xcc001ed10, 0xcc0005f90, 0xcc0000af4, 0x550000264, 0, 0, 0, 0]),
+    ('L', [0, 0, 0, 0x294000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3f6000000, 0x3fdccccc2, 0x166666661, 0, 0, 0, 0]),
+    ('M', [0, 0, 0, 0x4990000992, 0x6ff4004ff3, 0x6fb900abf3, 0x6f6e01e6f3, 0x6f3d45d3f3, 0x6f389a83f3, 0x6f33ee33f3, 0x6f30dd03f3, 0x6f302203f3, 0x6f300003f3, 0x6f300003f3, 0x6f300003f3, 0x2610000161, 0, 0, 0, 0]),
+    ('N', [0, 0, 0, 0x793000770, 0xcfa000cc0, 0xcff100cc0, 0xccd700cc0, 0xcc7d00cc0, 0xcc1f40cc0, 0xcc0ab0cc0, 0xcc04f2cc0, 0xcc00d8cc0, 0xcc007edc0, 0xcc001efc0, 0xcc0009fc0, 0x550002650, 0, 0, 0, 0]),
+    ('O', [0, 0, 0, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0x1bfaafb00, 0x17bb7100, 0, 0, 0, 0]),
+    ('P', [0, 0, 0, 0x499997200, 0x6fdccef60, 0x6f3002de1, 0x6f30007f3, 0x6f30007f3, 0x6f3002de1, 0x6fdccef60, 0x6fa998200, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x261000000, 0, 0, 0, 0]),
+    ('Q', [0, 0, 0, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0xbfaafb10, 0x17bee200, 0x2dd20, 0x2710, 0, 0]),
+    ('R', [0, 0, 0, 0x799985000, 0xceccdfb10, 0xcc0008f60, 0xcc0002f90, 0xcc0002f90, 0xcc001af40, 0xcffffd400, 0xcd66ae500, 0xcc000be10, 0xcc0002f80, 0xcc0000be1, 0xcc00004f8, 0x550000055, 0, 0, 0, 0]),
+    ('S', [0, 0, 0, 0x28cca710, 0x2ee99ae30, 0xae2000110, 0xcb0000000, 0xbe2000000, 0x5fea72000, 0x4bfffa10, 0x4af80, 0xcc0, 0xad0, 0x710002eb0, 0xcfb9aee30, 0x179ca8100, 0, 0, 0, 0]),
+    ('T', [0, 0, 0, 0x5999999995, 0x7ccceeccc7, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x550000, 0, 0, 0, 0]),
+    ('U', [0, 0, 0, 0x770000770, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xbc0000cb0, 0x8f2002f80, 0x2deaaed20, 0x18bb8100, 0, 0, 0, 0]),
+    ('V', [0, 0, 0, 0x4920000294, 0x3f700007f3, 0xeb0000be0, 0xae0000ea0, 0x5f4004f50, 0x1f8008f10, 0xbb00bb00, 0x7f11f700, 0x2f55f200, 0xd88d000, 0x8dd8000, 0x4ff4000, 0x1661000, 0, 0, 0, 0]),
+    ('W', [0, 0, 0, 0x9500000059, 0xcb000000bc, 0xac000000ca, 0x8f003200f8, 0x6f11ff11f6, 0x3f34ee43f3, 0x1f57bb75f1, 0xe6a78a6e0, 0xc9d44d9c0, 0x9bf11fb90, 0x7fd00df70, 0x5f8008f50, 0x162002610, 0, 0, 0, 0]),
+    ('X', [0, 0, 0, 0x1960000394, 0x9e2000dd1, 0x1ea007f40, 0x6f41e900, 0xbd9e100, 0x2ff5000, 0x2ef3000, 0xaecc000, 0x5f53f700, 0x1eb00ae10, 0x9e2001ea0, 0x3f800007f4, 0x4610000164, 0, 0, 0, 0]),
+    ('Y', [0, 0, 0, 0x5920000295, 0x1ea0000ae1, 0x7f3004f70, 0x1db00bd10, 0x5f55f500, 0xbddb000, 0x2ff2000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x550000, 0, 0, 0, 0]),
+    ('Z', [0, 0, 0, 0x599999994, 0x7cccccef5, 0x2eb0, 0xbe20, 0x5f600, 0x1eb000, 0xae2000, 0x4f60000, 0x1db00000, 0x9e200000, 0x4f6000000, 0xbfcccccc5, 0x566666662, 0, 0, 0, 0]),
+    ('[', [0, 0, 0x1333100, 0x3fff300, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f30000, 0x3f86100, 0x2999200, 0, 0]),
+    ('\\', [0, 0, 0, 0x1950000000, 0xad0000000, 0x2f5000000, 0xbd000000, 0x4f400000, 0xbb00000, 0x5f40000, 0xda0000, 0x5f2000, 0x1ea000, 0x7f100, 0x1e800, 0x8e10, 0x1e70, 0, 0, 0]),
+    (']', [0, 0, 0x13331000, 0x3fff3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x3f3000, 0x168f3000, 0x29992000, 0, 0]),
+    ('^', [0, 0, 0, 0x1881000, 0x9ee9000, 0x7e44e700, 0x4f6006f40, 0x2e600006e2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('_', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x3333333333, 0x6666666666]),
+    ('`', [0, 0, 0x5c200000, 0x9c10000, 0x1b80000, 0x130000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('a', [0, 0, 0, 0, 0, 0, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('b', [0, 0, 0x130000000, 0x6f0000000, 0x6f0000000, 0x6f0000000, 0x6f2aca200, 0x6fd97de20, 0x6f8001ea0, 0x6f3000ae0, 0x6f00009f0, 0x6f10009f0, 0x6f4000ad0, 0x6f9002e90, 0x6fda9ed20, 0x2618c8200, 0, 0, 0, 0]),
+    ('c', [0, 0, 0, 0, 0, 0, 0x28cc820, 0x4ed87b90, 0xec100020, 0x4f5000000, 0x6f3000000, 0x6f3000000, 0x4f6000000, 0xdd100020, 0x4ee99c90, 0x18bb810, 0, 0, 0, 0]),
+    ('d', [0, 0, 0x310, 0xf60, 0xf60, 0xf60, 0x2aca2f60, 0x2ed79df60, 0x9e2008f60, 0xda0003f60, 0xf90000f60, 0xf90000f60, 0xdb0003f60, 0x8e2008f60, 0x1de9adf60, 0x28c81620, 0, 0, 0, 0]),
+    ('e', [0, 0, 0, 0, 0, 0, 0x17bc9200, 0x1be97ce20, 0x7f4001da0, 0xdb00008e0, 0xfeccccdf0, 0xfb6666660, 0xda0000000, 0x7f4000020, 0x1bfa9ae90, 0x5aca720, 0, 0, 0, 0]),
+    ('f', [0, 0, 0x2320, 0x4eff90, 0xcc3320, 0xf80000, 0x499fb9950, 0x499fb9950, 0xf60000, 0xf60000, 0xf60000, 0xf60000, 0xf60000, 0xf60000, 0xf60000, 0x620000, 0, 0, 0, 0]),
+    ('g', [0, 0, 0, 0, 0, 0, 0x2aca2940, 0x2ed79df60, 0x9e2008f60, 0xda0002f60, 0xf90000f60, 0xf90001f60, 0xdb0004f60, 0x8f400af60, 0x1bebcbf60, 0x16961f60, 0x4f40, 0xa434dd00, 0xbfffb200, 0]),
+    ('h', [0, 0, 0x130000000, 0x6f0000000, 0x6f0000000, 0x6f0000000, 0x6f18cb400, 0x6fba8de10, 0x6f7003f60, 0x6f2000f80, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x260000640, 0, 0, 0, 0]),
+    ('i', [0, 0, 0x220000, 0x9c0000, 0x7a0000, 0, 0x99970000, 0x99dc0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x599de9970, 0x466666650, 0, 0, 0, 0]),
+    ('j', [0, 0, 0x131000, 0x3f6000, 0x2c5000, 0, 0x79994000, 0x79af6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x3f6000, 0x4f4000, 0x266be1000, 0x6ffc40000, 0]),
+    ('k', [0, 0, 0x31000000, 0xf6000000, 0xf6000000, 0xf6000000, 0xf6001871, 0xf601bd20, 0xf61bd200, 0xf7bd2000, 0xfeee2000, 0xfb1dc100, 0xf604f800, 0xf6008f40, 0xf6000ce2, 0x62000263, 0, 0, 0, 0]),
+    ('l', [0, 0, 0x233310000, 0xcfff30000, 0x6f30000, 0x6f30000, 0x6f30000, 0x6f30000, 0x6f30000, 0x6f30000, 0x6f30000, 0x6f30000, 0x5f30000, 0x2f70000, 0xafcc50, 0x56620, 0, 0, 0, 0]),
+    ('m', [0, 0, 0, 0, 0, 0, 0x295ba1ab40, 0x3fc7ee8be0, 0x3f50bc04f2, 0x3f309c03f3, 0x3f309c03f3, 0x3f309c03f3, 0x3f309c03f3, 0x3f309c03f3, 0x3f309c03f3, 0x1610450161, 0, 0, 0, 0]),
+    ('n', [0, 0, 0, 0, 0, 0, 0x4918cb400, 0x6fba8de10, 0x6f7003f60, 0x6f2000f80, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x260000640, 0, 0, 0, 0]),
+    ('o', [0, 0, 0, 0, 0, 0, 0x18cc8100, 0x1de88ed10, 0x8f3003f70, 0xbd0000db0, 0xcb0000bc0, 0xcb0000bc0, 0xbd0000db0, 0x7f4004f70, 0x1deaaed10, 0x17bb7100, 0, 0, 0, 0]),
+    ('p', [0, 0, 0, 0, 0, 0, 0x492aca200, 0x6fd97de20, 0x6f8002e90, 0x6f3000bd0, 0x6f00009f0, 0x6f00009f0, 0x6f3000bd0, 0x6f8002e80, 0x6fda9ed10, 0x6f28c8200, 0x6f0000000, 0x6f0000000, 0x6f0000000, 0]),
+    ('q', [0, 0, 0, 0, 0, 0, 0x18ca2950, 0x1de8adf90, 0x7f2007f90, 0xbd0001f90, 0xcb0000f90, 0xcb0000f90, 0xbd0001f90, 0x7f2007f90, 0x1de8adf90, 0x18c92f90, 0xf90, 0xf90, 0xf90, 0]),
+    ('r', [0, 0, 0, 0, 0, 0, 0x943aca2, 0xf9ea9c6, 0xfe40001, 0xfa00000, 0xf600000, 0xf600000, 0xf600000, 0xf600000, 0xf600000, 0x6200000, 0, 0, 0, 0]),
+    ('s', [0, 0, 0, 0, 0, 0, 0x18cca500, 0xdd86ab00, 0x3f4000000, 0x2f8000000, 0x9feb8100, 0x37afd10, 0x5f50, 0x110004f40, 0x3ea99eb00, 0x169ca7100, 0, 0, 0, 0]),
+    ('t', [0, 0, 0, 0x2300000, 0x9f00000, 0x9f00000, 0x99df99940, 0x99df99940, 0x9f00000, 0x9f00000, 0x9f00000, 0x9f00000, 0x9f00000, 0x6f20000, 0x1eea940, 0x166620, 0, 0, 0, 0]),
+    ('u', [0, 0, 0, 0, 0, 0, 0x490000950, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f1001f90, 0x4f5007f90, 0x1de9abf90, 0x29b81640, 0, 0, 0, 0]),
+    ('v', [0, 0, 0, 0, 0, 0, 0x1940000491, 0xcb0000bc0, 0x7f1001f70, 0x1f6006f10, 0xbc00cb00, 0x6f22f600, 0x1e77e100, 0xacca000, 0x5ff5000, 0x1661000, 0, 0, 0, 0]),
+    ('w', [0, 0, 0, 0, 0, 0, 0x8500000058, 0xbb000000bb, 0x8e000000e8, 0x4f20bb02f4, 0x1f51ee15f1, 0xd85bb58d0, 0x8c9779c80, 0x5fd22df50, 0x2fd00df20, 0x64004600, 0, 0, 0, 0]),
+    ('x', [0, 0, 0, 0, 0, 0, 0x870000780, 0x4f5006f40, 0x8e22e800, 0xbddb000, 0x2ee2000, 0x5ff5000, 0x2e88e200, 0x1dc00cd10, 0xae2002ea0, 0x1630000361, 0, 0, 0, 0]),
+    ('y', [0, 0, 0, 0, 0, 0, 0x1950000392, 0xbd0000ae0, 0x5f3001e80, 0x1e9006f20, 0x8e10bb00, 0x2f52f600, 0xbb8e100, 0x5fe9000, 0x1ef4000, 0xbd0000, 0x2f70000, 0x24be10000, 0x9fc400000, 0]),
+    ('z', [0, 0, 0, 0, 0, 0, 0x299999940, 0x29999bf60, 0x1dc10, 0xae200, 0x7f4000, 0x4f70000, 0x2ea00000, 0x1cc100000, 0x6fdcccc50, 0x266666620, 0, 0, 0, 0]),
+    ('{', [0, 0, 0x310, 0x2cff30, 0x8f3000, 0xac0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x5e80000, 0x3ffb10000, 0x135f70000, 0xcc0000, 0xcc0000, 0xcc0000, 0xac0000, 0x8e2000, 0x2dff30, 0x2310, 0]),
+    ('|', [0, 0, 0x220000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0xc90000, 0x540000]),
+    ('}', [0, 0, 0x130000000, 0x3ffc20000, 0x3f80000, 0xc90000, 0xc90000, 0xc90000, 0xcb0000, 0x8e5000, 0x1bff30, 0x7f5310, 0xcb0000, 0xc90000, 0xc90000, 0xc90000, 0x2e80000, 0x3ffd20000, 0x132000000, 0]),
+    ('~', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0x6bc930042, 0x3e99cfdde2, 0x1100037720, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('¡', [0, 0, 0, 0, 0, 0, 0x770000, 0xcc0000, 0x550000, 0, 0x550000, 0xbc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x770000, 0]),
+    ('¢', [0, 0, 0, 0x11000, 0x66000, 0x66000, 0x18dda30, 0x2ddbaa80, 0xbe166000, 0x2f7066000, 0x3f5066000, 0x3f5066000, 0x1f8066000, 0xbe266010, 0x2debbb90, 0x17bd820, 0x66000, 0x66000, 0, 0]),
+    ('£', [0, 0, 0, 0x4bcb60, 0x5fc78b0, 0xbe10000, 0xeb00000, 0xf900000, 0xf900000, 0x7cfecc700, 0x46fb66400, 0xf900000, 0xf900000, 0xf900000, 0xacfecccc0, 0x566666660, 0, 0, 0, 0]),
+    ('¥', [0, 0, 0, 0x5920000295, 0x1ea0000ae1, 0x7f3004f70, 0x1db00bd10, 0x5f55f400, 0xccedddcc0, 0x2ff2000, 0x999ee9990, 0x333dd3330, 0xcc0000, 0xcc0000, 0xcc0000, 0x550000, 0, 0, 0, 0]),
+    ('§', [0, 0, 0, 0x17cca400, 0xae768800, 0xca000000, 0xae400000, 0x4ffa2000, 0x2e44ce500, 0x6f0009f20, 0x3f7000f60, 0x6eb24f20, 0x2bfe500, 0x7f800, 0xac00, 0x7414ea00, 0x8effb200, 0, 0, 0]),
+    ('©', [0, 0, 0, 0, 0x4774000, 0x2c9669c20, 0x1c328853c1, 0x855d547058, 0xd0d400000d, 0xc0f300000c, 0xd0c400000d, 0x855d747058, 0x1d426754d1, 0x2c9669c20, 0x4664000, 0, 0, 0, 0, 0]),
+    ('«', [0, 0, 0, 0, 0, 0, 0, 0x2800450, 0x2d906e40, 0x4e907e500, 0xea05f5000, 0x6e51bd200, 0x5e609d30, 0x4b00860, 0x100010, 0, 0, 0, 0, 0]),
+    ('®', [0, 0, 0, 0, 0x4774000, 0x2c9669c20, 0x1c366623c1, 0x850f35e158, 0xd00f01d20d, 0xc00f9e700c, 0xd00f07a00d, 0x850f01d458, 0x1d430025d1, 0x2c9669c20, 0x4664000, 0, 0, 0, 0, 0]),
+    ('°', [0, 0, 0, 0x5bb5000, 0x4e55e400, 0x87007800, 0x7a00a700, 0x1dddb100, 0x1440000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('±', [0, 0, 0, 0, 0, 0, 0x990000, 0x990000, 0x1666bb6661, 0x3ffffffff3, 0x990000, 0x990000, 0x770000, 0, 0x2cccccccc2, 0x1666666661, 0, 0, 0, 0]),
+    ('²', [0, 0, 0, 0x2acb4000, 0x4406e100, 0x1f200, 0xa8000, 0x990000, 0x9900000, 0x5fccc200, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('³', [0, 0, 0, 0x2acb7000, 0x1404f400, 0x2f300, 0xdf9000, 0x1d500, 0xd600, 0x5dadb100, 0x2320000, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('µ', [0, 0, 0, 0, 0, 0, 0x490000950, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f1001f90, 0x6f5005f90, 0x6feaaedd7, 0x6f3ab54b5, 0x6f0000000, 0x6f0000000, 0x6f0000000, 0]),
+    ('¶', [0, 0, 0, 0x17999940, 0x4effb6d60, 0xefff90c60, 0x2ffff90c60, 0x1ffff90c60, 0xbfff90c60, 0x1aff90c60, 0x1890c60, 0x690c60, 0x690c60, 0x690c60, 0x690c60, 0x690c60, 0x690c60, 0, 0, 0]),
+    ('·', [0, 0, 0, 0, 0, 0, 0, 0, 0x990000, 0xff0000, 0xff0000, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('¹', [0, 0, 0, 0x17970000, 0x278c0000, 0x6c0000, 0x6c0000, 0x6c0000, 0x6c0000, 0xcdec500, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('»', [0, 0, 0, 0, 0, 0, 0, 0x540092000, 0x4e609d200, 0x4e709e40, 0x4f509e0, 0x2db15e70, 0x2d906e600, 0x6800b4000, 0x100020000, 0, 0, 0, 0, 0]),
+    ('¼', [0, 0, 0x4ce9000000, 0x2269000000, 0x69000000, 0x69000000, 0x69000000, 0x69000000, 0x2cccc51470, 0x148bea70, 0x7bda720000, 0x320000cf00, 0x79f00, 0x3a3f00, 0x1c23f00, 0x5ecdfa0, 0x1335f20, 0x2900, 0, 0]),
+    ('½', [0, 0, 0x4ce9000000, 0x2269000000, 0x69000000, 0x69000000, 0x69000000, 0x69000000, 0x2cccc51470, 0x148bea70, 0x7bda753100, 0x3200bbae40, 0x1007c0, 0xaa0, 0x6b10, 0x6b100, 0x6d4320, 0x799970, 0, 0]),
+    ('¾', [0, 0, 0xecdb10000, 0x100d60000, 0x25e30000, 0x7bb20000, 0xa80000, 0x1201c80000, 0x2bfd911470, 0x148bea70, 0x7bda720000, 0x320000cf00, 0x79f00, 0x3a3f00, 0x1c23f00, 0x5ecdfa0, 0x1335f20, 0x2900, 0, 0]),
+    ('¿', [0, 0, 0, 0, 0, 0, 0x590000, 0x9f0000, 0x460000, 0x460000, 0x9f0000, 0xae0000, 0x4e90000, 0x4eb10000, 0x1eb100000, 0x6f4000000, 0x5f5000500, 0x1dfa9de00, 0x18bb8200, 0]),
+    ('À', [0x9d10000, 0xb80000, 0x130000, 0x1991000, 0x5ff5000, 0xacda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('Á', [0x1d9000, 0x8b0000, 0x310000, 0x1991000, 0x5ff5000, 0xacda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('Â', [0x2ee2000, 0x1d66d100, 0x12002100, 0x1991000, 0x5ff5000, 0xacda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('Ã', [0x2ba35700, 0x985de300, 0, 0x1991000, 0x5ff5000, 0xacda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('Ä', [0x49229400, 0x6f33f600, 0, 0x1991000, 0x5ff5000, 0xacda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('Å', [0x5dd5000, 0x1e33e100, 0x3d00d300, 0xd88d000, 0x5ff5000, 0xadda000, 0xe88e000, 0x4f44f400, 0x8e00e800, 0xda00bd00, 0x2f7007f20, 0x7ffffff70, 0xcd6666dc0, 0x1f800008f1, 0x6f400004f6, 0x4610000164, 0, 0, 0, 0]),
+    ('Æ', [0, 0, 0, 0x7999994, 0xedeecc5, 0x4f2c9000, 0x8d0c9000, 0xc80c9000, 0x1f50cd992, 0x5f10cecc2, 0xac00c9000, 0xeffff9000, 0x2f866d9000, 0x7f100c9000, 0xbb000cecc7, 0x5400056664, 0, 0, 0, 0]),
+    ('Ç', [0, 0, 0, 0x29cca40, 0x4ed99cc0, 0x1ec100040, 0x7f4000000, 0xbf0000000, 0xcd0000000, 0xcc0000000, 0xcd0000000, 0xaf1000000, 0x6f4000000, 0x1ed100050, 0x4eea9dc0, 0x18bd820, 0xd200, 0x75e500, 0x9a8100, 0]),
+    ('È', [0x7d10000, 0x9b0000, 0x31000, 0x499999970, 0x6fdcccca0, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fa999950, 0x6fdcccc70, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fdccccc0, 0x266666660, 0, 0, 0, 0]),
+    ('É', [0xbb100, 0x7d1000, 0x210000, 0x499999970, 0x6fdcccca0, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fa999950, 0x6fdcccc70, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fdccccc0, 0x266666660, 0, 0, 0, 0]),
+    ('Ê', [0x1de4000, 0xb74d200, 0x13002200, 0x499999970, 0x6fdcccca0, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fa999950, 0x6fdcccc70, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fdccccc0, 0x266666660, 0, 0, 0, 0]),
+    ('Ë', [0x29409500, 0x3f60f900, 0, 0x499999970, 0x6fdcccca0, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fa999950, 0x6fdcccc70, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6f3000000, 0x6fdccccc0, 0x266666660, 0, 0, 0, 0]),
+    ('Ì', [0x9d10000, 0xb80000, 0x130000, 0x499999940, 0x5cceecc50, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x5cceecc50, 0x266666620, 0, 0, 0, 0]),
+    ('Í', [0x1d9000, 0x8b0000, 0x310000, 0x499999940, 0x5cceecc50, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x5cceecc50, 0x266666620, 0, 0, 0, 0]),
+    ('Î', [0x2ee2000, 0x1d66d100, 0x12002100, 0x499999940, 0x5cceecc50, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x5cceecc50, 0x266666620, 0, 0, 0, 0]),
+    ('Ï', [0x49229400, 0x6f33f600, 0, 0x499999940, 0x5cceecc50, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x5cceecc50, 0x266666620, 0, 0, 0, 0]),
+    ('Ñ', [0x2bb45700, 0x985de300, 0, 0x793000770, 0xcfa000cc0, 0xcff100cc0, 0xccd700cc0, 0xcc7d00cc0, 0xcc1f40cc0, 0xcc0ab0cc0, 0xcc04f2cc0, 0xcc00d8cc0, 0xcc007edc0, 0xcc001efc0, 0xcc0009fc0, 0x550002650, 0, 0, 0, 0]),
+    ('Ò', [0x9d10000, 0xb80000, 0x130000, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0x1bfaafb00, 0x17bb7100, 0, 0, 0, 0]),
+    ('Ó', [0x1d9000, 0x8b0000, 0x310000, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0x1bfaafb00, 0x17bb7100, 0, 0, 0, 0]),
+    ('Ô', [0x2ee2000, 0x1d66d100, 0x12002100, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0x1bfaafb00, 0x17bb7100, 0, 0, 0, 0]),
+    ('Õ', [0x2ba35700, 0x985de300, 0, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0x1bfaafb00, 0x17bb7100, 0, 0, 0, 0]),
+    ('Ö', [0x49229400, 0x6f33f600, 0, 0x18cc8100, 0x1de99ed10, 0x7f5005f70, 0xbe0000eb0, 0xec0000ce0, 0xfa0000af0, 0xf900009f0, 0xfa0000af0, 0xec0000ce0, 0xbe0000eb0, 0x6f5005f60, 0x1bfaafb00, 0x17bb7100, 0, 0, 0, 0]),
+    ('×', [0, 0, 0, 0, 0, 0, 0x130000310, 0x8e4004e80, 0x1be44eb10, 0x1beeb100, 0x4ff4000, 0x4ebbe400, 0x4eb11be40, 0x6b1001b60, 0, 0, 0, 0, 0, 0]),
+    ('Ø', [0, 0, 0x10, 0x18cc8188, 0x1de99ece1, 0x7f5005f80, 0xbe000afb0, 0xec005dde0, 0xfa02e39f0, 0xf90b709f0, 0xf97c00af0, 0xede200ce0, 0xbf6000eb0, 0xaf4005f60, 0x4eceaafb00, 0xa517bb7100, 0, 0, 0, 0]),
+    ('Ù', [0x9d10000, 0xb80000, 0x130000, 0x770000770, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xbc0000cb0, 0x8f2002f80, 0x2deaaed20, 0x18bb8100, 0, 0, 0, 0]),
+    ('Ú', [0x1d9000, 0x8b0000, 0x310000, 0x770000770, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xbc0000cb0, 0x8f2002f80, 0x2deaaed20, 0x18bb8100, 0, 0, 0, 0]),
+    ('Û', [0x2ee2000, 0x1d66d100, 0x12002100, 0x770000770, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xbc0000cb0, 0x8f2002f80, 0x2deaaed20, 0x18bb8100, 0, 0, 0, 0]),
+    ('Ü', [0x49229400, 0x6f33f600, 0, 0x770000770, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xcc0000cc0, 0xbc0000cb0, 0x8f2002f80, 0x2deaaed20, 0x18bb8100, 0, 0, 0, 0]),
+    ('Ý', [0x1d9000, 0x8b0000, 0x310000, 0x5920000295, 0x1ea0000ae1, 0x7f3004f70, 0x1db00bd10, 0x5f55f500, 0xbddb000, 0x2ff2000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0xcc0000, 0x550000, 0, 0, 0, 0]),
+    ('ß', [0, 0, 0x210000, 0x5effd400, 0x2fa33be10, 0x6f2003f40, 0x6f008d820, 0x6f07e2000, 0x6f0ac0000, 0x6f07f7000, 0x6f019fc20, 0x6f0004dd1, 0x6f00004f4, 0x6f00004f4, 0x6f3b89ed1, 0x2618bb710, 0, 0, 0, 0]),
+    ('à', [0, 0, 0x5c200000, 0x9c10000, 0x1b80000, 0x130000, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('á', [0, 0, 0x2c500, 0x1c9000, 0x8b1000, 0x310000, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('â', [0, 0, 0xaa0000, 0x7cc7000, 0x2e22e200, 0x12002100, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('ã', [0, 0, 0x5402400, 0x6dd78800, 0x752bb200, 0, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('ä', [0, 0, 0, 0x6f33f600, 0x49229400, 0, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('å', [0, 0x7ff7000, 0x2e12e200, 0x3d00d300, 0xbbbb000, 0x550000, 0x16acc8100, 0x3ea78dd10, 0x110002f70, 0x1333f90, 0x1afffff90, 0xae4000f90, 0xe90001f90, 0xdb0008f90, 0x7fb9bcf90, 0x5ab81640, 0, 0, 0, 0]),
+    ('æ', [0, 0, 0, 0, 0, 0, 0x18bb54bc70, 0x3b78fea8f5, 0xad00b9, 0x9c009a, 0x5bceeccec, 0x5f86bd6665, 0x9b009c0000, 0x9b00be1001, 0x5faaedc9a9, 0x6ba328c82, 0, 0, 0, 0]),
+    ('ç', [0, 0, 0, 0, 0, 0, 0x28cc820, 0x4ed87b90, 0xec100020, 0x4f5000000, 0x6f3000000, 0x6f3000000, 0x4f6000000, 0xdd100020, 0x4ee99c90, 0x18bd810, 0xd400, 0x75e500, 0x9a8100, 0]),
+    ('è', [0, 0, 0x4c300000, 0x8d10000, 0xaa0000, 0x131000, 0x17bc9200, 0x1be97ce20, 0x7f4001da0, 0xdb00008e0, 0xfeccccdf0, 0xfb6666660, 0xda0000000, 0x7f4000020, 0x1bfa9ae90, 0x5aca720, 0, 0, 0, 0]),
+    ('é', [0, 0, 0x1b600, 0xbb000, 0x7d1000, 0x310000, 0x17bc9200, 0x1be97ce20, 0x7f4001da0, 0xdb00008e0, 0xfeccccdf0, 0xfb6666660, 0xda0000000, 0x7f4000020, 0x1bfa9ae90, 0x5aca720, 0, 0, 0, 0]),
+    ('ê', [0, 0, 0x9b1000, 0x5db8000, 0x1e41d300, 0x12002200, 0x17bc9200, 0x1be97ce20, 0x7f4001da0, 0xdb00008e0, 0xfeccccdf0, 0xfb6666660, 0xda0000000, 0x7f4000020, 0x1bfa9ae90, 0x5aca720, 0, 0, 0, 0]),
+    ('ë', [0, 0, 0, 0x3f63f900, 0x29429500, 0, 0x17bc9200, 0x1be97ce20, 0x7f4001da0, 0xdb00008e0, 0xfeccccdf0, 0xfb6666660, 0xda0000000, 0x7f4000020, 0x1bfa9ae90, 0x5aca720, 0, 0, 0, 0]),
+    ('ì', [0, 0, 0x5c200000, 0x9c10000, 0x1b80000, 0x130000, 0x99970000, 0x99dc0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x599de9970, 0x466666650, 0, 0, 0, 0]),
+    ('í', [0, 0, 0x2c500, 0x1c9000, 0x8b1000, 0x310000, 0x99970000, 0x99dc0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x599de9970, 0x466666650, 0, 0, 0, 0]),
+    ('î', [0, 0, 0xaa0000, 0x7cc7000, 0x2e22e200, 0x12002100, 0x99970000, 0x99dc0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x599de9970, 0x466666650, 0, 0, 0, 0]),
+    ('ï', [0, 0, 0, 0x3f60f900, 0x29409500, 0, 0x99970000, 0x99dc0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x9c0000, 0x599de9970, 0x466666650, 0, 0, 0, 0]),
+    ('ñ', [0, 0, 0x5402400, 0x6dd78800, 0x752bb200, 0, 0x4918cb400, 0x6fba8de10, 0x6f7003f60, 0x6f2000f80, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x260000640, 0, 0, 0, 0]),
+    ('ò', [0, 0, 0x5c200000, 0x9c10000, 0x1b80000, 0x130000, 0x18cc8100, 0x1de88ed10, 0x8f3003f70, 0xbd0000db0, 0xcb0000bc0, 0xcb0000bc0, 0xbd0000db0, 0x7f4004f70, 0x1deaaed10, 0x17bb7100, 0, 0, 0, 0]),
+    ('ó', [0, 0, 0x2c500, 0x1c9000, 0x8b1000, 0x310000, 0x18cc8100, 0x1de88ed10, 0x8f3003f70, 0xbd0000db0, 0xcb0000bc0, 0xcb0000bc0, 0xbd0000db0, 0x7f4004f70, 0x1deaaed10, 0x17bb7100, 0, 0, 0, 0]),
+    ('ô', [0, 0, 0xaa0000, 0x7cc7000, 0x2e22e200, 0x12002100, 0x18cc8100, 0x1de88ed10, 0x8f3003f70, 0xbd0000db0, 0xcb0000bc0, 0xcb0000bc0, 0xbd0000db0, 0x7f4004f70, 0x1deaaed10, 0x17bb7100, 0, 0, 0, 0]),
+    ('õ', [0, 0, 0x5402400, 0x6dd78800, 0x752bb200, 0, 0x18cc8100, 0x1de88ed10, 0x8f3003f70, 0xbd0000db0, 0xcb0000bc0, 0xcb0000bc0, 0xbd0000db0, 0x7f4004f70, 0x1deaaed10, 0x17bb7100, 0, 0, 0, 0]),
+    ('ö', [0, 0, 0, 0x6f33f600, 0x49229400, 0, 0x18cc8100, 0x1de88ed10, 0x8f3003f70, 0xbd0000db0, 0xcb0000bc0, 0xcb0000bc0, 0xbd0000db0, 0x7f4004f70, 0x1deaaed10, 0x17bb7100, 0, 0, 0, 0]),
+    ('÷', [0, 0, 0, 0, 0, 0, 0xcc0000, 0xff0000, 0x330000, 0x1666666661, 0x3ffffffff3, 0, 0xcc0000, 0xff0000, 0x330000, 0, 0, 0, 0, 0]),
+    ('ø', [0, 0, 0, 0, 0, 0x10, 0x18cc82d4, 0x1de88ef80, 0x8f200af80, 0xbc006cdb0, 0xc904d2bc0, 0xca2e40bc0, 0xbdd500db0, 0x7f9004f70, 0x9eeaaed10, 0x6b17bb7100, 0x100000000, 0, 0, 0]),
+    ('ù', [0, 0, 0x5c200000, 0x9c10000, 0x1b80000, 0x130000, 0x490000950, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f1001f90, 0x4f5007f90, 0x1de9abf90, 0x29b81640, 0, 0, 0, 0]),
+    ('ú', [0, 0, 0x2c500, 0x1c9000, 0x8b1000, 0x310000, 0x490000950, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f1001f90, 0x4f5007f90, 0x1de9abf90, 0x29b81640, 0, 0, 0, 0]),
+    ('û', [0, 0, 0xaa0000, 0x7cc7000, 0x2e22e200, 0x12002100, 0x490000950, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f1001f90, 0x4f5007f90, 0x1de9abf90, 0x29b81640, 0, 0, 0, 0]),
+    ('ü', [0, 0, 0, 0x6f33f600, 0x49229400, 0, 0x490000950, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f0000f90, 0x6f1001f90, 0x4f5007f90, 0x1de9abf90, 0x29b81640, 0, 0, 0, 0]),
+    ('ý', [0, 0, 0x2c500, 0x1c9000, 0x8b1000, 0x310000, 0x1950000392, 0xbd0000ae0, 0x5f3001e80, 0x1e9006f20, 0x8e10bb00, 0x2f52f600, 0xbb8e100, 0x5fe9000, 0x1ef4000, 0xbd0000, 0x2f70000, 0x24be10000, 0x9fc400000, 0]),
+    ('ÿ', [0, 0, 0, 0x6f33f600, 0x49229400, 0, 0x1950000392, 0xbd0000ae0, 0x5f3001e80, 0x1e9006f20, 0x8e10bb00, 0x2f52f600, 0xbb8e100, 0x5fe9000, 0x1ef4000, 0xbd0000, 0x2f70000, 0x24be10000, 0x9fc400000, 0]),
+    ('–', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x9999999999, 0x9999999999, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('—', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x9999999999, 0x9999999999, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('‘', [0, 0, 0x22000, 0x1e7000, 0x7f2000, 0x1ee0000, 0x3fc0000, 0x2970000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('’', [0, 0, 0x132000, 0x6f9000, 0x7f8000, 0xae2000, 0xe80000, 0x2910000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('“', [0, 0, 0x2200310, 0x1e704f20, 0x8f20bd00, 0x1ed05fa00, 0x3fc09f600, 0x297059400, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('”', [0, 0, 0x13202310, 0x6f90cf30, 0x7f80cf20, 0xbe11fb00, 0xe804f400, 0x291048000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('•', [0, 0, 0, 0, 0, 0, 0, 0x8ff8000, 0x4ffff400, 0x6ffff600, 0x2effe200, 0x3aa3000, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('…', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x6f90ff0cf6, 0x6f90ff0cf6, 0x2640660562, 0, 0, 0, 0]),
+    ('‹', [0, 0, 0, 0, 0, 0, 0, 0x27000, 0x4e7000, 0x5e60000, 0x2f700000, 0x9e40000, 0x7e4000, 0x69000, 0x1000, 0, 0, 0, 0, 0]),
+    ('›', [0, 0, 0, 0, 0, 0, 0, 0x7200000, 0x7e40000, 0x6e5000, 0x7f200, 0x4e9000, 0x4e70000, 0x9600000, 0x1000000, 0, 0, 0, 0, 0]),
+    ('€', [0, 0, 0, 0x18cca30, 0x1de87b90, 0x8e200020, 0xea000000, 0x15f8333100, 0x5bfa999100, 0x6f3000000, 0x6dfdcb1000, 0x3f6000000, 0xea000000, 0x8f400040, 0x1bea9d90, 0x17ab820, 0, 0, 0, 0]),
+    ('™', [0, 0, 0, 0x9995770492, 0x3a52cd3cd3, 0x930c5d8c3, 0x930c361c3, 0x930c300c3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('←', [0, 0, 0, 0, 0, 0, 0, 0, 0x64000000, 0x6e4000000, 0x4fecccccc5, 0x3ec9999994, 0x4e4000000, 0x42000000, 0, 0, 0, 0, 0, 0]),
+    ('↑', [0, 0, 0, 0, 0, 0, 0x330000, 0x4ee4000, 0x4ecce400, 0x44994400, 0x990000, 0x990000, 0x990000, 0x990000, 0x990000, 0x440000, 0, 0, 0, 0]),
+    ('→', [0, 0, 0, 0, 0, 0, 0, 0, 0x4600, 0x4e60, 0x5ccccccef4, 0x4999999ce3, 0x4e40, 0x2400, 0, 0, 0, 0, 0, 0]),
+    ('↓', [0, 0, 0, 0, 0, 0, 0x440000, 0x990000, 0x990000, 0x990000, 0x990000, 0x990000, 0x24994200, 0x5ecce500, 0x6ff6000, 0x440000, 0, 0, 0, 0]),
+    ('↔', [0, 0, 0, 0, 0, 0, 0, 0, 0x64004600, 0x6e4004e60, 0x4feccccef4, 0x3ec9999ce3, 0x4e4004e40, 0x42002400, 0, 0, 0, 0, 0, 0]),
+    ('↕', [0, 0, 0, 0, 0, 0, 0x330000, 0x4ee4000, 0x4ecce400, 0x44994400, 0x990000, 0x990000, 0x24994200, 0x5ecce500, 0x6ff6000, 0x440000, 0, 0, 0, 0]),
+    ('⇒', [0, 0, 0, 0, 0, 0, 0, 0, 0x4600, 0x266666af60, 0x13333335e4, 0x26666667e3, 0x1333337e40, 0x2400, 0, 0, 0, 0, 0, 0]),
+    ('−', [0, 0, 0, 0, 0, 0, 0, 0, 0, 0x1666666661, 0x3ffffffff3, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ('∙', [0, 0, 0, 0, 0, 0, 0, 0x2882000, 0x1effe100, 0x6ffff600, 0x5ffff400, 0x9ff9000, 0x220000, 0, 0, 0, 0, 0, 0, 0]),
+    ('√', [0, 0x22, 0xd8, 0x2e1, 0x7a0, 0xc50, 0x2f10, 0x10006b00, 0x4cf100b600, 0x25c501f200, 0x7a06c000, 0x3e0a7000, 0xe5e2000, 0x9dd0000, 0x5f80000, 0x1c30000, 0, 0, 0, 0]),
+    ('∞', [0, 0, 0, 0, 0, 0, 0, 0x597107940, 0x4e7c88c7e2, 0x8701ee1078, 0x9600bb0069, 0x8804ee2088, 0x2ebe56eae2, 0x264004620, 0, 0, 0, 0, 0, 0]),
+    ('≈', [0, 0, 0, 0, 0, 0, 0, 0x34100001, 0x2dfffa66b3, 0x27115bfea1, 0x45100001, 0x2dfffa66b3, 0x27115bfea1, 0, 0, 0, 0, 0, 0, 0]),
+    ('≠', [0, 0, 0, 0, 0, 0x230, 0x1db0, 0xad20, 0x3ffffffff3, 0x1666af8661, 0x2ea0000, 0x2ccdfdccc2, 0x29de999992, 0x5f5000000, 0xb80000000, 0x10000000, 0, 0, 0, 0]),
+    ('≤', [0, 0, 0, 0, 0, 0, 0x11, 0x27bf3, 0x38cfea61, 0x2dfd840000, 0x2efb720000, 0x149efd841, 0x48df3, 0x31, 0x2cccccccc2, 0x1666666661, 0, 0, 0, 0]),
+    ('≥', [0, 0, 0, 0, 0, 0, 0x1100000000, 0x3fb7200000, 0x16aefc8300, 0x48dfd2, 0x27bfe2, 0x148dfe9410, 0x3fd8400000, 0x1300000000, 0x2cccccccc2, 0x1666666661, 0, 0, 0, 0]),
+    ('■', [0, 0, 0, 0, 0, 0, 0xcccccccccc, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0xffffffffff, 0x3333333333, 0, 0, 0]),
+    ('□', [0, 0, 0, 0, 0, 0, 0xcccccccccc, 0xf33333333f, 0xf00000000f, 0xf00000000f, 0xf00000000f, 0xf00000000f, 0xf00000000f, 0xf00000000f, 0xf00000000f, 0xfccccccccf, 0x3333333333, 0, 0, 0]),
+    ('▪', [0, 0, 0, 0, 0, 0, 0, 0, 0x3ffffff30, 0x3ffffff30, 0x3ffffff30, 0x3ffffff30, 0x3ffffff30, 0x3ffffff30, 0x166666610, 0, 0, 0, 0, 0]),
+    ('▫', [0, 0, 0, 0, 0, 0, 0, 0, 0x3ecccce30, 0x3c0000c30, 0x3c0000c30, 0x3c0000c30, 0x3c0000c30, 0x3e9999e30, 0x166666610, 0, 0, 0, 0, 0]),
+    ('▲', [0, 0, 0, 0, 0, 0, 0x220000, 0xaa0000, 0x2ff2000, 0xaffa000, 0x2ffff200, 0xaffffa00, 0x2ffffff20, 0xaffffffa0, 0x2ffffffff2, 0xaffffffffa, 0x3333333333, 0, 0, 0]),
+    ('△', [0, 0, 0, 0, 0, 0, 0x220000, 0xaa0000, 0x2bb2000, 0xa44a000, 0x2b00b200, 0xa4004a00, 0x2b0000b20, 0xa400004a0, 0x2b000000b2, 0xadccccccda, 0x3333333333, 0, 0, 0]),
+    ('▶', [0, 0, 0, 0, 0, 0, 0x8100000000, 0xfe81000000, 0xfffe810000, 0xfffffe8100, 0xfffffffe81, 0xffffffffe6, 0xffffffe710, 0xffffe71000, 0xffe7100000, 0xe710000000, 0x1000000000, 0, 0, 0]),
+    ('▷', [0, 0, 0, 0, 0, 0, 0x8100000000, 0xfc81000000, 0xf05c810000, 0xf0005c8100, 0xf000005c81, 0xf0000018e6, 0xf00018d710, 0xf018d71000, 0xf8d7100000, 0xe710000000, 0x1000000000, 0, 0, 0]),
+    ('►', [0, 0, 0, 0, 0, 0, 0, 0, 0xd840000000, 0xfffe940000, 0xffffffe940, 0xfffffffea4, 0xffffea5100, 0xfe94000000, 0x4000000000, 0, 0, 0, 0, 0]),
+    ('▼', [0, 0, 0, 0, 0, 0, 0xacccccccca, 0x5ffffffff5, 0xdffffffd0, 0x5ffffff50, 0xdffffd00, 0x5ffff500, 0xdffd000, 0x5ff5000, 0xdd0000, 0x550000, 0, 0, 0, 0]),
+    ('▽', [0, 0, 0, 0, 0, 0, 0xacccccccca, 0x5e333333e5, 0xd500005d0, 0x5d0000d50, 0xd5005d00, 0x5d00d500, 0xd55d000, 0x5dd5000, 0xdd0000, 0x550000, 0, 0, 0, 0]),
+    ('◀', [0, 0, 0, 0, 0, 0, 0x18, 0x18ef, 0x18efff, 0x18efffff, 0x18efffffff, 0x6effffffff, 0x17effffff, 0x17effff, 0x17eff, 0x17e, 0x1, 0, 0, 0]),
+    ('◁', [0, 0, 0, 0, 0, 0, 0x18, 0x18cf, 0x18c50f, 0x18c5000f, 0x18c500000f, 0x6e8100000f, 0x17d81000f, 0x17d810f, 0x17d8f, 0x17e, 0x1, 0, 0, 0]),
+    ('◄', [0, 0, 0, 0, 0, 0, 0, 0, 0x48d, 0x49efff, 0x49effffff, 0x4aefffffff, 0x15aeffff, 0x49ef, 0x4, 0, 0, 0, 0, 0]),
+    ('◆', [0, 0, 0, 0, 0, 0, 0x440000, 0x4ee4000, 0x4effe400, 0x4effffe40, 0x4effffffe4, 0x8ffffffff8, 0x9ffffff90, 0x9ffff900, 0x9ff9000, 0x990000, 0, 0, 0, 0]),
+    ('◇', [0, 0, 0, 0, 0, 0, 0x440000, 0x4ee4000, 0x4e44e400, 0x4e4004e40, 0x4e400004e4, 0x8c100001c8, 0x9b1001b90, 0x9b11b900, 0x9bb9000, 0x990000, 0, 0, 0, 0]),
+    ('◉', [0, 0, 0, 0, 0, 0, 0x18cc8100, 0x5e8448e50, 0x3e3affa3e3, 0xa5bffffb5a, 0xe4ffffff4e, 0xf3ffffff3f, 0xb5effffe5b, 0x7b4effe4b7, 0xab4554ba0, 0x7edde700, 0x220000, 0, 0, 0]),
+    ('○', [0, 0, 0, 0, 0, 0, 0x18cc8100, 0x5e8448e50, 0x3e200002e3, 0xa50000005a, 0xe10000001e, 0xf00000000f, 0xb40000004b, 0x7b000000b7, 0xab2002ba0, 0x7edde700, 0x220000, 0, 0, 0]),
+    ('●', [0, 0, 0, 0, 0, 0, 0x18cc8100, 0x5effffe50, 0x3ffffffff3, 0xaffffffffa, 0xeffffffffe, 0xffffffffff, 0xbffffffffb, 0x7ffffffff7, 0xaffffffa0, 0x7effe700, 0x220000, 0, 0, 0]),
+    ('★', [0, 0, 0, 0, 0, 0, 0, 0x440000, 0x880000, 0xdd0000, 0x2cffffffc2, 0x18ffff810, 0xdffd000, 0x3fbbf300, 0x77007700, 0x20000100, 0, 0, 0, 0]),
+    ('☆', [0, 0, 0, 0, 0, 0, 0, 0x440000, 0x550000, 0x650000, 0x2a761167a2, 0x53003500, 0x5005000, 0x34664300, 0x74004700, 0x10000100, 0, 0, 0, 0]),
+    ('☐', [0, 0, 0, 0, 0, 0, 0, 0xb999999b0, 0x900000090, 0x900000090, 0x900000090, 0x900000090, 0x900000090, 0x900000090, 0xa333333a0, 0x666666660, 0, 0, 0, 0]),
+    ('☑', [0, 0, 0, 0, 0, 0, 0, 0x3b999999b3, 0x3600000263, 0x3600007163, 0x3600054063, 0x3600190063, 0x3648740063, 0x3607e00063, 0x3833433383, 0x2999999992, 0, 0, 0, 0]),
+    ('☒', [0, 0, 0, 0, 0, 0, 0, 0x666666660, 0x910000190, 0x959009590, 0x906996090, 0x900bb0090, 0x909669090, 0x976006790, 0xa333333a0, 0x666666660, 0, 0, 0, 0]),
+    ('♠', [0, 0, 0, 0, 0x110000, 0xaa0000, 0x6ff5000, 0x3effe300, 0x2dffffd10, 0xaffffffa0, 0xeffffffe0, 0xeffccffe0, 0x4db44bd40, 0x550000, 0x440000, 0, 0, 0, 0, 0]),
+    ('♣', [0, 0, 0, 0, 0x1771000, 0xbffd000, 0x2ffff300, 0x1effe100, 0x38ff8300, 0x2dffffffd2, 0x8ffffffff8, 0x8fffeefff8, 0x1dfd55dfd1, 0x20550200, 0x440000, 0, 0, 0, 0, 0]),
+    ('♥', [0, 0, 0, 0, 0x484004840, 0x5fff55fff5, 0xdfffddfffd, 0xdffffffffd, 0x9ffffffff9, 0x2effffffe2, 0x4ffffff40, 0x7ffff600, 0x9ff9000, 0x1bb0000, 0x210000, 0, 0, 0, 0, 0]),
+    ('♦', [0, 0, 0, 0, 0x220000, 0xbb1000, 0x8ff8000, 0x5ffff500, 0x2effffe20, 0xaffffffa0, 0x2effffe20, 0x4ffff400, 0x8ff7000, 0xbb0000, 0x110000, 0, 0, 0, 0, 0]),
+    ('⚠', [0, 0, 0, 0, 0, 0, 0x10000, 0x570000, 0x1891000, 0x7777000, 0x18568100, 0x81351800, 0x270130720, 0x810220180, 0x3500220054, 0x4666666664, 0, 0, 0, 0]),
+    ('⚡', [0, 0, 0, 0, 0x10, 0x650, 0x2a400, 0x6d2000, 0x1bc20000, 0xafc84100, 0x25afb00, 0x2c9100, 0x2d50000, 0x2a200000, 0x450000000, 0x200000000, 0, 0, 0, 0]),
+    ('✓', [0, 0, 0, 0, 0, 0, 0x1a70, 0xad10, 0x7e200, 0x2e5000, 0x120d90000, 0xbc7d10000, 0x7ff300000, 0x2e8000000, 0, 0, 0, 0, 0, 0]),
+    ('✔', [0, 0, 0, 0, 0, 0, 0x262, 0x8fd2, 0x9fe30, 0x7ff400, 0x2a64ff7000, 0x6fedfa0000, 0x2fffd10000, 0xcfe200000, 0x395000000, 0, 0, 0, 0, 0]),
+    ('✗', [0, 0, 0, 0, 0, 0x220, 0xb702db0, 0xbd1dd10, 0x5fde200, 0x1ef5000, 0x5ff7000, 0x2edde100, 0x1de24f800, 0x8f7008400, 0xed1000000, 0x730000000, 0, 0, 0, 0]),
+    ('✘', [0, 0, 0, 0, 0x20, 0x2600009d3, 0xcf7009ff5, 0x8fe29ff60, 0x2ffeff700, 0x9fffa000, 0x5fff5000, 0x2efffe200, 0x1dffdffd10, 0x8ff72efe20, 0x7fa0049400, 0x410000000, 0, 0, 0, 0]),
+    ('❮', [0, 0, 0, 0, 0x13310, 0x1dfc00, 0x8fe200, 0x2ff7000, 0xcfc0000, 0x7ff30000, 0x1ef800000, 0x1dfb00000, 0x3ff60000, 0x8fe2000, 0x1dfb000, 0x4ff600, 0x8cb10, 0, 0, 0]),
+    ('❯', [0, 0, 0, 0, 0x133100000, 0xcfd10000, 0x2ef80000, 0x7ff2000, 0xcfc000, 0x3ff700, 0x8fe10, 0xbfd10, 0x6ff300, 0x2ef8000, 0xbfd1000, 0x6ff40000, 0x1bc800000, 0, 0, 0]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(mask: &Mask) -> usize {
+        mask.iter().flatten().filter(|v| **v > 127).count()
+    }
+
+    #[test]
+    fn test_glyph_table_is_sorted() {
+        assert!(GLYPHS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_text_glyphs() {
+        assert_eq!(mask(' '), Some(EMPTY));
+        let a = mask('A').unwrap();
+        assert!(coverage(&a) > 20);
+        // Nothing below the baseline but descenders
+        assert!(a[BASELINE + 1..].iter().flatten().all(|v| *v == 0));
+        let g = mask('g').unwrap();
+        assert!(g[BASELINE + 1..].iter().flatten().any(|v| *v > 127));
+        assert!(mask('é').is_some());
+        assert!(mask('✓').is_some());
+        assert!(mask('中').is_none());
+    }
+
+    #[test]
+    fn test_blocks_fill_their_share_of_the_cell() {
+        let full = CELL_WIDTH * CELL_HEIGHT;
+        assert_eq!(coverage(&mask('█').unwrap()), full);
+        assert_eq!(coverage(&mask('▀').unwrap()), full / 2);
+        assert_eq!(coverage(&mask('▄').unwrap()), full / 2);
+        assert_eq!(coverage(&mask('▌').unwrap()), full / 2);
+        assert_eq!(coverage(&mask('▚').unwrap()), full / 2);
+        assert_eq!(coverage(&mask('▟').unwrap()), full * 3 / 4);
+        let lower = mask('▁').unwrap();
+        assert!(lower[CELL_HEIGHT - 1].iter().all(|v| *v == 255));
+        assert!(lower[0].iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_box_lines_reach_the_edges() {
+        let horizontal = mask('─').unwrap();
+        assert!(horizontal[CELL_HEIGHT / 2].iter().all(|v| *v == 255));
+        let corner = mask('┌').unwrap();
+        assert_eq!(corner[CELL_HEIGHT / 2][CELL_WIDTH - 1], 255);
+        assert_eq!(corner[CELL_HEIGHT - 1][CELL_WIDTH / 2], 255);
+        assert_eq!(corner[0][CELL_WIDTH / 2], 0);
+        assert!(coverage(&mask('━').unwrap()) > coverage(&horizontal));
+    }
+
+    #[test]
+    fn test_braille_dots() {
+        assert_eq!(mask('⠀'), Some(EMPTY));
+        assert_eq!(coverage(&mask('⠁').unwrap()), 4);
+        assert_eq!(coverage(&mask('⣿').unwrap()), 32);
+    }
+
+    #[test]
+    fn test_bold_and_italic() {
+        let plain = mask('l').unwrap();
+        let mut bold = plain;
+        embolden(&mut bold);
+        assert!(coverage(&bold) > coverage(&plain));
+        let mut italic = plain;
+        slant(&mut italic);
+        assert_ne!(italic, plain);
+        assert_eq!(italic[BASELINE], plain[BASELINE]);
+    }
+}
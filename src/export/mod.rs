@@ -1,15 +1,22 @@
-//! Export functionality for termgfx output to SVG and PNG formats
+//! Export functionality for termgfx output to SVG, PNG and HTML, and
+//! for recordings to GIF, APNG and WebM
 
 #![allow(dead_code)]
 #![allow(clippy::upper_case_acronyms)]
 
+pub mod ci;
+mod font;
+pub mod raster;
+pub mod snapshot;
 pub mod svg;
+pub mod terminal;
 
 /// Supported export formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFormat {
     SVG,
     PNG,
+    HTML,
 }
 
 impl ExportFormat {
@@ -18,6 +25,7 @@ impl ExportFormat {
         match s.to_lowercase().as_str() {
             "svg" => Some(ExportFormat::SVG),
             "png" => Some(ExportFormat::PNG),
+            "html" => Some(ExportFormat::HTML),
             _ => None,
         }
     }
@@ -27,6 +35,7 @@ impl ExportFormat {
         match self {
             ExportFormat::SVG => "svg",
             ExportFormat::PNG => "png",
+            ExportFormat::HTML => "html",
         }
     }
 }
@@ -65,6 +74,7 @@ mod tests {
         assert_eq!(ExportFormat::from_str("SVG"), Some(ExportFormat::SVG));
         assert_eq!(ExportFormat::from_str("png"), Some(ExportFormat::PNG));
         assert_eq!(ExportFormat::from_str("PNG"), Some(ExportFormat::PNG));
+        assert_eq!(ExportFormat::from_str("html"), Some(ExportFormat::HTML));
         assert_eq!(ExportFormat::from_str("gif"), None);
     }
 
@@ -72,6 +82,7 @@ mod tests {
     fn test_export_format_extension() {
         assert_eq!(ExportFormat::SVG.extension(), "svg");
         assert_eq!(ExportFormat::PNG.extension(), "png");
+        assert_eq!(ExportFormat::HTML.extension(), "html");
     }
 
     #[test]
@@ -0,0 +1,571 @@
+//! Recordings drawn to GIF, APNG or WebM
+//!
+//! The recorded output is replayed onto a [`Screen`] the size of the
+//! terminal it came from and sampled `fps` times a second. Each screen that
+//! differs from the one before is drawn cell by cell with the [`font`]
+//! glyphs in the active theme's colors, optionally under a window title bar.
+//! GIF and APNG hold a frame until the screen changes; WebM is encoded by
+//! ffmpeg from a constant-rate stream.
+
+use super::font::{self, CELL_HEIGHT, CELL_WIDTH};
+use super::terminal::{self, mix, Cell, Rgb, Screen};
+use crate::design::theme::{self, Theme};
+use crate::output::{
+    atomic,
+    record::{Recording, TimeRange},
+};
+use crate::text;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame, ImageFormat, RgbImage};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+pub const FORMATS: [&str; 3] = ["gif", "apng", "webm"];
+pub const CHROMES: [&str; 2] = ["none", "mac"];
+
+/// Space between the terminal and the edge of the image
+const PADDING: u32 = 12;
+/// Height of the `mac` title bar
+const TITLE_BAR: u32 = 30;
+/// Red, yellow and green window buttons
+const BUTTONS: [Rgb; 3] = [(255, 95, 86), (255, 189, 46), (39, 201, 63)];
+/// How long the last screen stays up before the animation loops
+const LAST_FRAME_MS: u32 = 2000;
+/// GIF delays are in hundredths of a second, so faster rates can't be timed
+const MAX_FPS: u32 = 50;
+/// Frame rate when `--fps` isn't given
+pub const DEFAULT_FPS: u32 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RasterFormat {
+    Gif,
+    Apng,
+    /// VP9 via ffmpeg
+    Webm,
+}
+
+impl RasterFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "gif" => Some(RasterFormat::Gif),
+            "apng" | "png" => Some(RasterFormat::Apng),
+            "webm" => Some(RasterFormat::Webm),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            RasterFormat::Gif => "GIF",
+            RasterFormat::Apng => "APNG",
+            RasterFormat::Webm => "WebM",
+        }
+    }
+}
+
+/// Window drawn around the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Chrome {
+    #[default]
+    None,
+    /// Title bar with the recording's title and traffic-light buttons
+    Mac,
+}
+
+impl Chrome {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(Chrome::None),
+            "mac" | "macos" => Some(Chrome::Mac),
+            _ => None,
+        }
+    }
+}
+
+pub struct RasterOptions {
+    pub format: RasterFormat,
+    pub fps: u32,
+    pub chrome: Chrome,
+    /// Longest pause kept, in seconds; overrides the recording's own limit
+    pub idle_limit: Option<f64>,
+    /// Part of the recording to draw
+    pub range: TimeRange,
+}
+
+/// What the terminal showed at one moment
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    lines: Vec<Vec<Option<Cell>>>,
+    cursor: Option<(usize, usize)>,
+}
+
+/// Draws snapshots of a `cols` × `rows` terminal
+struct Canvas {
+    cols: usize,
+    rows: usize,
+    background: Rgb,
+    foreground: Rgb,
+    /// Title bar color
+    surface: Rgb,
+    chrome: Chrome,
+    title: Option<String>,
+}
+
+/// Draw `input` as an animation at `output`
+pub fn export(input: &str, output: &str, options: &RasterOptions) {
+    match try_export(input, output, options) {
+        Ok(frames) => println!(
+            "✅ {} exported to {} ({} frames)",
+            options.format.label(),
+            output,
+            frames
+        ),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn try_export(input: &str, output: &str, options: &RasterOptions) -> Result<usize, String> {
+    if options.fps == 0 || options.fps > MAX_FPS {
+        return Err(format!("--fps must be between 1 and {}", MAX_FPS));
+    }
+    let mut recording = Recording::load(input)?;
+    recording.clip(options.range)?;
+    let (cols, rows) = recording.size();
+    let (cols, rows) = (cols.max(1) as usize, rows.max(1) as usize);
+    let frames = frames(
+        &recording.output(options.idle_limit),
+        cols,
+        rows,
+        options.fps,
+    );
+    let title = recording.title().map(str::to_string);
+    let canvas = Canvas::themed(cols, rows, options.chrome, title);
+    let bytes = match options.format {
+        RasterFormat::Gif => encode_gif(&canvas, &frames)?,
+        RasterFormat::Apng => encode_apng(&canvas, &frames)?,
+        RasterFormat::Webm => {
+            let tmp = atomic::temp_path(Path::new(output));
+            let result = encode_webm(&canvas, &frames, options.fps, &tmp, output).and_then(|_| {
+                fs::rename(&tmp, output).map_err(|e| format!("Failed to write {}: {}", output, e))
+            });
+            if result.is_err() {
+                fs::remove_file(&tmp).ok();
+            }
+            return result.map(|_| frames.len());
+        }
+    };
+    atomic::write(output, bytes).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    Ok(frames.len())
+}
+
+/// `ansi` drawn once as a PNG, as large as what it shows
+pub fn still(ansi: &str) -> Result<Vec<u8>, String> {
+    let lines = terminal::grid(ansi);
+    let cols = lines.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let canvas = Canvas::themed(cols, lines.len(), Chrome::None, None);
+    let image = canvas.draw(&Snapshot {
+        lines,
+        cursor: None,
+    });
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgb8(image)
+        .write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Each distinct screen and how many milliseconds it stays up
+fn frames(output: &[(f64, String)], cols: usize, rows: usize, fps: u32) -> Vec<(Snapshot, u32)> {
+    let mut screen = Screen::sized(cols, rows);
+    let end = output.last().map_or(0.0, |(time, _)| *time);
+    let ticks = (end * fps as f64).ceil() as u64;
+    // Tick times rounded to whole milliseconds, so delays don't drift
+    let at = |tick: u64| (tick * 1000 / fps as u64) as u32;
+    let mut frames: Vec<(Snapshot, u32)> = Vec::new();
+    let mut next = 0;
+    for tick in 0..=ticks {
+        let time = tick as f64 / fps as f64;
+        while let Some((_, data)) = output.get(next).filter(|(t, _)| *t <= time) {
+            screen.write(data);
+            next += 1;
+        }
+        let snapshot = Snapshot {
+            lines: screen.visible(),
+            cursor: screen.cursor(),
+        };
+        let duration = at(tick + 1) - at(tick);
+        match frames.last_mut() {
+            Some((last, ms)) if *last == snapshot => *ms += duration,
+            _ => frames.push((snapshot, duration)),
+        }
+    }
+    if let Some((_, ms)) = frames.last_mut() {
+        *ms += LAST_FRAME_MS;
+    }
+    frames
+}
+
+fn pixel((r, g, b): Rgb) -> image::Rgb<u8> {
+    image::Rgb([r, g, b])
+}
+
+fn fill(image: &mut RgbImage, left: u32, top: u32, width: u32, height: u32, color: Rgb) {
+    for y in top..(top + height).min(image.height()) {
+        for x in left..(left + width).min(image.width()) {
+            image.put_pixel(x, y, pixel(color));
+        }
+    }
+}
+
+/// Draw `mask` in `color` with its top left corner at (`left`, `top`)
+fn blend<R: AsRef<[u8]>>(image: &mut RgbImage, left: u32, top: u32, mask: &[R], color: Rgb) {
+    for (dy, row) in mask.iter().enumerate() {
+        for (dx, coverage) in row.as_ref().iter().enumerate() {
+            let (x, y) = (left + dx as u32, top + dy as u32);
+            if *coverage == 0 || x >= image.width() || y >= image.height() {
+                continue;
+            }
+            let image::Rgb([r, g, b]) = *image.get_pixel(x, y);
+            image.put_pixel(x, y, pixel(mix((r, g, b), color, *coverage)));
+        }
+    }
+}
+
+impl Canvas {
+    /// A canvas in the active theme's colors, dark by default
+    fn themed(cols: usize, rows: usize, chrome: Chrome, title: Option<String>) -> Self {
+        let theme = theme::active().cloned().unwrap_or_else(Theme::dark);
+        Canvas {
+            cols,
+            rows,
+            background: Theme::rgb(&theme.colors.background),
+            foreground: Theme::rgb(&theme.colors.foreground),
+            surface: Theme::rgb(&theme.colors.surface),
+            chrome,
+            title,
+        }
+    }
+
+    fn top(&self) -> u32 {
+        match self.chrome {
+            Chrome::None => PADDING,
+            Chrome::Mac => TITLE_BAR + PADDING,
+        }
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (
+            self.cols as u32 * CELL_WIDTH as u32 + 2 * PADDING,
+            self.rows as u32 * CELL_HEIGHT as u32 + PADDING + self.top(),
+        )
+    }
+
+    fn draw(&self, snapshot: &Snapshot) -> RgbImage {
+        let (width, height) = self.size();
+        let mut image = RgbImage::from_pixel(width, height, pixel(self.background));
+        if self.chrome == Chrome::Mac {
+            self.draw_title_bar(&mut image);
+        }
+        let mut cursor = snapshot
+            .cursor
+            .filter(|(row, col)| *row < self.rows && *col < self.cols);
+        for (row, line) in snapshot.lines.iter().enumerate().take(self.rows) {
+            for (col, cell) in line.iter().enumerate().take(self.cols) {
+                let Some(cell) = cell else {
+                    continue;
+                };
+                let under_cursor = cursor == Some((row, col));
+                if under_cursor {
+                    cursor = None;
+                }
+                self.draw_cell(&mut image, row, col, cell, under_cursor);
+            }
+        }
+        // A cursor over an empty cell is a plain block
+        if let Some((row, col)) = cursor {
+            let (x, y) = self.origin(row, col);
+            let (w, h) = (CELL_WIDTH as u32, CELL_HEIGHT as u32);
+            fill(&mut image, x, y, w, h, self.foreground);
+        }
+        image
+    }
+
+    /// Top left pixel of a cell
+    fn origin(&self, row: usize, col: usize) -> (u32, u32) {
+        (
+            PADDING + (col * CELL_WIDTH) as u32,
+            self.top() + (row * CELL_HEIGHT) as u32,
+        )
+    }
+
+    fn draw_cell(&self, image: &mut RgbImage, row: usize, col: usize, cell: &Cell, cursor: bool) {
+        let Some(c) = cell.text.chars().next() else {
+            return;
+        };
+        let cells = text::char_width(c).clamp(1, 2).min(self.cols - col);
+        let (mut fg, mut bg) = cell.style.paint(self.foreground, self.background);
+        if cursor {
+            (fg, bg) = (bg, fg);
+        }
+        let (x, y) = self.origin(row, col);
+        let width = (cells * CELL_WIDTH) as u32;
+        if bg != self.background {
+            fill(image, x, y, width, CELL_HEIGHT as u32, bg);
+        }
+        match font::mask(c) {
+            Some(mut mask) => {
+                if cell.style.bold {
+                    font::embolden(&mut mask);
+                }
+                if cell.style.italic {
+                    font::slant(&mut mask);
+                }
+                blend(image, x, y, &mask, fg);
+            }
+            None => blend(image, x, y, &font::missing(cells), fg),
+        }
+        if cell.style.underline {
+            fill(image, x, y + CELL_HEIGHT as u32 - 3, width, 1, fg);
+        }
+    }
+
+    fn draw_title_bar(&self, image: &mut RgbImage) {
+        fill(image, 0, 0, image.width(), TITLE_BAR, self.surface);
+        let (cy, radius) = (TITLE_BAR as f32 / 2.0, 6.0);
+        for (i, color) in BUTTONS.iter().enumerate() {
+            let cx = PADDING as f32 + radius + i as f32 * 20.0;
+            for y in 0..TITLE_BAR {
+                for x in (cx - radius - 1.0) as u32..(cx + radius + 2.0) as u32 {
+                    let d = ((x as f32 + 0.5 - cx).powi(2) + (y as f32 + 0.5 - cy).powi(2)).sqrt();
+                    let coverage = (radius + 0.5 - d).clamp(0.0, 1.0);
+                    if coverage > 0.0 {
+                        blend(image, x, y, &[[(coverage * 255.0) as u8]], *color);
+                    }
+                }
+            }
+        }
+        let Some(title) = &self.title else {
+            return;
+        };
+        // Centered, clear of the buttons on both sides
+        let room =
+            (image.width() as usize).saturating_sub(2 * (PADDING as usize + 64)) / CELL_WIDTH;
+        let title: Vec<char> = title
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(room)
+            .collect();
+        let left = (image.width() - (title.len() * CELL_WIDTH) as u32) / 2;
+        let top = (TITLE_BAR - CELL_HEIGHT as u32) / 2;
+        let color = mix(self.surface, self.foreground, 200);
+        for (i, c) in title.iter().enumerate() {
+            if let Some(mask) = font::mask(*c) {
+                blend(image, left + (i * CELL_WIDTH) as u32, top, &mask, color);
+            }
+        }
+    }
+}
+
+fn encode_gif(canvas: &Canvas, frames: &[(Snapshot, u32)]) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut bytes, 10);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+        for (snapshot, ms) in frames {
+            let image = DynamicImage::ImageRgb8(canvas.draw(snapshot)).into_rgba8();
+            let delay = Delay::from_numer_denom_ms(*ms, 1);
+            encoder
+                .encode_frame(Frame::from_parts(image, 0, 0, delay))
+                .map_err(|e| format!("Failed to encode GIF: {}", e))?;
+        }
+    }
+    Ok(bytes)
+}
+
+fn encode_apng(canvas: &Canvas, frames: &[(Snapshot, u32)]) -> Result<Vec<u8>, String> {
+    let error = |e: png::EncodingError| format!("Failed to encode APNG: {}", e);
+    let (width, height) = canvas.size();
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(error)?;
+    let mut writer = encoder.write_header().map_err(error)?;
+    for (snapshot, ms) in frames {
+        // Hundredths of a second fit pauses of up to 11 minutes in a u16
+        let delay = (ms / 10).clamp(1, u16::MAX as u32) as u16;
+        writer.set_frame_delay(delay, 100).map_err(error)?;
+        writer
+            .write_image_data(canvas.draw(snapshot).as_raw())
+            .map_err(error)?;
+    }
+    writer.finish().map_err(error)?;
+    Ok(bytes)
+}
+
+/// Stream frames to ffmpeg, repeating each for as many ticks as it lasts;
+/// the video goes to `path`, and errors name `output`
+fn encode_webm(
+    canvas: &Canvas,
+    frames: &[(Snapshot, u32)],
+    fps: u32,
+    path: &Path,
+    output: &str,
+) -> Result<(), String> {
+    let (width, height) = canvas.size();
+    let mut child = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-loglevel",
+            "error",
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "rgb24",
+        ])
+        .args(["-s", &format!("{}x{}", width, height)])
+        .args(["-r", &fps.to_string(), "-i", "-"])
+        // yuv420p needs even dimensions
+        .args(["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2"])
+        .args(["-c:v", "libvpx-vp9", "-pix_fmt", "yuv420p"])
+        // The temporary name ends in .tmp, which ffmpeg can't map to a container
+        .args(["-f", "webm"])
+        .arg(path)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|_| "WebM export needs ffmpeg on PATH (https://ffmpeg.org)".to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        'frames: for (snapshot, ms) in frames {
+            let image = canvas.draw(snapshot);
+            for _ in 0..(ms * fps).div_ceil(1000).max(1) {
+                // ffmpeg exiting early is reported by its status below
+                if stdin.write_all(image.as_raw()).is_err() {
+                    break 'frames;
+                }
+            }
+        }
+    }
+    let status = child.wait().map_err(|e| format!("ffmpeg failed: {}", e))?;
+    if !status.success() {
+        return Err(format!("ffmpeg could not encode {}", output));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas(chrome: Chrome) -> Canvas {
+        Canvas {
+            cols: 4,
+            rows: 2,
+            background: (0, 0, 0),
+            foreground: (255, 255, 255),
+            surface: (40, 40, 40),
+            chrome,
+            title: Some("demo".to_string()),
+        }
+    }
+
+    fn output(events: &[(f64, &str)]) -> Vec<(f64, String)> {
+        events.iter().map(|(t, s)| (*t, s.to_string())).collect()
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(RasterFormat::from_name("APNG"), Some(RasterFormat::Apng));
+        assert_eq!(RasterFormat::from_name("mp4"), None);
+        for name in FORMATS {
+            assert!(RasterFormat::from_name(name).is_some());
+        }
+        for name in CHROMES {
+            assert!(Chrome::from_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_frames_merge_unchanged_screens() {
+        let frames = frames(&output(&[(0.0, "a"), (0.25, "b"), (1.0, "c")]), 4, 2, 10);
+        // "a" until 0.3s (the first tick after "b"), "ab" until 1.0s, then "abc"
+        let durations: Vec<u32> = frames.iter().map(|(_, ms)| *ms).collect();
+        assert_eq!(durations, [300, 700, 100 + LAST_FRAME_MS]);
+        let text: String = frames[2].0.lines[0]
+            .iter()
+            .flatten()
+            .map(|c| c.text.as_str())
+            .collect();
+        assert_eq!(text, "abc");
+        assert_eq!(frames[2].0.cursor, Some((0, 3)));
+    }
+
+    #[test]
+    fn test_frames_of_empty_recording() {
+        let frames = frames(&[], 4, 2, 10);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].1, 100 + LAST_FRAME_MS);
+    }
+
+    #[test]
+    fn test_draw_cells_and_cursor() {
+        let canvas = canvas(Chrome::None);
+        let mut screen = Screen::sized(4, 2);
+        screen.write("\x1b[41m█\x1b[0m");
+        let snapshot = Snapshot {
+            lines: screen.visible(),
+            cursor: screen.cursor(),
+        };
+        let image = canvas.draw(&snapshot);
+        assert_eq!(image.dimensions(), (4 * 10 + 24, 2 * 20 + 24));
+        // The full block covers its cell in the foreground color
+        assert_eq!(
+            image.get_pixel(PADDING + 5, PADDING + 10).0,
+            [255, 255, 255]
+        );
+        // The cursor block sits in the next cell
+        assert_eq!(
+            image.get_pixel(PADDING + 15, PADDING + 10).0,
+            [255, 255, 255]
+        );
+        assert_eq!(image.get_pixel(PADDING + 25, PADDING + 10).0, [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_mac_chrome_adds_title_bar() {
+        let image = canvas(Chrome::Mac).draw(&Snapshot {
+            lines: vec![Vec::new(); 2],
+            cursor: None,
+        });
+        assert_eq!(image.height(), 2 * 20 + 24 + TITLE_BAR);
+        assert_eq!(image.get_pixel(1, 1).0, [40, 40, 40]);
+        // Red button
+        assert_eq!(image.get_pixel(PADDING + 6, TITLE_BAR / 2).0, [255, 95, 86]);
+    }
+
+    #[test]
+    fn test_encoders() {
+        let frames = frames(&output(&[(0.0, "hi"), (0.5, "!")]), 4, 2, 10);
+        let gif = encode_gif(&canvas(Chrome::None), &frames).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        let apng = encode_apng(&canvas(Chrome::Mac), &frames).unwrap();
+        assert!(apng.starts_with(b"\x89PNG"));
+        assert!(apng.windows(4).any(|w| w == b"acTL"));
+    }
+
+    #[test]
+    fn test_still_fits_output() {
+        let png = still("\x1b[31mhello\x1b[0m\nhi\n").unwrap();
+        let image = image::load_from_memory(&png).unwrap();
+        assert_eq!(image.width(), 5 * CELL_WIDTH as u32 + 2 * PADDING);
+        assert_eq!(image.height(), 2 * CELL_HEIGHT as u32 + 2 * PADDING);
+    }
+}
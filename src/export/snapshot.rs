@@ -0,0 +1,140 @@
+//! `--export out.svg` for commands with static output, and `--format` for
+//! printing one as markup
+//!
+//! For `--export`, like `--cache`, the command runs again in a child
+//! process, here with colors forced on. Its output is replayed onto a grid
+//! of cells and written as an SVG, drawn with the built-in font as a PNG, or
+//! split into styled spans for HTML, in the theme's colors. `--format` needs
+//! no child: box, table and progress hand over the [`Doc`] they would print.
+
+use super::{ci, raster, terminal, ExportFormat};
+use crate::design::theme::{self, Theme};
+use crate::output::profile::{self, Phase};
+use crate::output::{atomic, cache, color};
+use crate::render::Doc;
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+
+/// Commands whose output `--export` can capture
+pub const COMMANDS: [&str; 6] = ["box", "banner", "chart", "table", "gauge", "heatmap"];
+
+/// Values of `--format` on box, table and progress
+pub const FORMATS: [&str; 3] = ["ansi", "html", "svg"];
+
+/// Print `doc` as `format` (html or svg); an unknown format exits with an
+/// error
+pub fn print(format: &str, doc: &Doc) {
+    let theme = theme::active().cloned().unwrap_or_else(Theme::dark);
+    let result = profile::time(Phase::Render, || match ExportFormat::from_str(format) {
+        Some(ExportFormat::HTML) => Ok(html(doc, &theme).into_bytes()),
+        Some(ExportFormat::SVG) => {
+            Ok(terminal::to_svg(&doc.to_ansi(), &theme.colors.background).into_bytes())
+        }
+        _ => Err(format!(
+            "Invalid format '{}' (use {})",
+            format,
+            FORMATS.join(", ")
+        )),
+    });
+    match result {
+        Ok(bytes) => {
+            profile::time(Phase::Write, || {
+                let mut stdout = std::io::stdout();
+                let _ = stdout.write_all(&bytes);
+                let _ = stdout.flush();
+            });
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run `command` and save its output at `path`, then exit
+///
+/// `args` is the full command line after the program name, still holding
+/// the `--export` flag.
+pub fn run(command: &str, path: &str, args: &[String]) -> ! {
+    match export(command, path, args) {
+        Ok(()) => {
+            eprintln!("Exported to: {}", path);
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn export(command: &str, path: &str, args: &[String]) -> Result<(), String> {
+    if !COMMANDS.contains(&command) {
+        return Err(format!(
+            "--export works with {}, not '{}'",
+            COMMANDS.join(", "),
+            command
+        ));
+    }
+    let format = format(path)?;
+    let ansi = ci::capture(&cache::strip_flag(args, "--export"), None, Stdio::inherit())?;
+    let bytes = render(&ansi, format)?;
+    atomic::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+fn render(ansi: &str, format: ExportFormat) -> Result<Vec<u8>, String> {
+    let theme = theme::active().cloned().unwrap_or_else(Theme::dark);
+    Ok(match format {
+        ExportFormat::SVG => terminal::to_svg(ansi, &theme.colors.background).into_bytes(),
+        ExportFormat::PNG => raster::still(ansi)?,
+        ExportFormat::HTML => html(&Doc::from_ansi(ansi), &theme).into_bytes(),
+    })
+}
+
+/// `doc` as a standalone `<pre>` in the theme's colors
+fn html(doc: &Doc, theme: &Theme) -> String {
+    let palette: [(u8, u8, u8); 16] = std::array::from_fn(|n| color::palette_rgb(n as u8));
+    doc.to_html_page(&palette, &theme.colors.background, &theme.colors.foreground)
+}
+
+/// The format named by `path`'s extension
+fn format(path: &str) -> Result<ExportFormat, String> {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(ExportFormat::from_str)
+        .ok_or_else(|| format!("Can't export to {} (use a .svg, .png or .html file)", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(format("docs/box.svg"), Ok(ExportFormat::SVG));
+        assert_eq!(format("BOX.PNG"), Ok(ExportFormat::PNG));
+        assert_eq!(format("page.html"), Ok(ExportFormat::HTML));
+        assert!(format("box.gif")
+            .unwrap_err()
+            .contains(".svg, .png or .html"));
+        assert!(format("box").is_err());
+    }
+
+    #[test]
+    fn test_html_keeps_colors() {
+        let html = html(
+            &Doc::from_ansi("\x1b[1;32mok\x1b[0m <tag>\n"),
+            &Theme::dark(),
+        );
+        assert!(html.contains(".termgfx .green { color: #"));
+        assert!(html.contains("<span class=\"green bold\">ok</span> &lt;tag&gt;</pre>"));
+    }
+
+    #[test]
+    fn test_rejects_other_commands() {
+        let err = export("select", "out.svg", &[]).unwrap_err();
+        assert!(err.starts_with("--export works with box, banner"));
+    }
+}
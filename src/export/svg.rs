@@ -31,6 +31,41 @@ impl SvgBuilder {
         self.content.push(svg_line);
     }
 
+    /// Add text with terminal attributes, keeping runs of spaces
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_styled_text(
+        &mut self,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: &str,
+        font_size: u32,
+        bold: bool,
+        italic: bool,
+        underline: bool,
+    ) {
+        let mut attrs = String::new();
+        if bold {
+            attrs.push_str(r#" font-weight="bold""#);
+        }
+        if italic {
+            attrs.push_str(r#" font-style="italic""#);
+        }
+        if underline {
+            attrs.push_str(r#" text-decoration="underline""#);
+        }
+        let svg_line = format!(
+            r#"  <text x="{}" y="{}" font-family="monospace" font-size="{}" fill="{}" xml:space="preserve"{}>{}</text>"#,
+            x,
+            y,
+            font_size,
+            color,
+            attrs,
+            escape_xml(text)
+        );
+        self.content.push(svg_line);
+    }
+
     /// Add a rectangle to the SVG
     #[allow(clippy::too_many_arguments)]
     pub fn add_rect(
@@ -0,0 +1,119 @@
+//! Captured terminal output to SVG
+//!
+//! ANSI output is replayed onto a grid of character cells by
+//! [`crate::output::vt`]. Each run of cells with the same SGR attributes
+//! becomes one SVG text element over an optional background rectangle.
+
+use super::svg::SvgBuilder;
+use super::{ExportConfig, ExportFormat};
+use crate::output::vt::Style;
+pub(super) use crate::output::vt::{grid, mix, plain, Cell, Rgb, Screen};
+use crate::text;
+
+const FONT_SIZE: u32 = 14;
+/// Advance of one cell in a 14px monospace font
+const CELL_WIDTH: f32 = 8.4;
+const LINE_HEIGHT: f32 = 18.0;
+const PADDING: f32 = 16.0;
+/// Text color when the output sets none
+const DEFAULT_FG: (u8, u8, u8) = (212, 212, 212);
+
+/// Foreground and background to draw, after `inverse`
+fn colors(style: &Style) -> (Rgb, Option<Rgb>) {
+    if style.inverse {
+        (
+            style.bg.unwrap_or((30, 30, 30)),
+            Some(style.fg.unwrap_or(DEFAULT_FG)),
+        )
+    } else {
+        (style.fg.unwrap_or(DEFAULT_FG), style.bg)
+    }
+}
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Render `ansi` as an SVG image of a terminal with `background`
+pub fn to_svg(ansi: &str, background: &str) -> String {
+    let rows = grid(ansi);
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let config = ExportConfig {
+        format: ExportFormat::SVG,
+        width: (cols as f32 * CELL_WIDTH + 2.0 * PADDING).ceil() as u32,
+        height: (rows.len().max(1) as f32 * LINE_HEIGHT + 2.0 * PADDING).ceil() as u32,
+        scale: 1.0,
+        background: background.to_string(),
+        font_size: FONT_SIZE,
+    };
+    let mut builder = SvgBuilder::new(config);
+
+    for (y, row) in rows.iter().enumerate() {
+        let top = PADDING + y as f32 * LINE_HEIGHT;
+        let mut col = 0;
+        while col < row.len() {
+            let Some(first) = &row[col] else {
+                col += 1;
+                continue;
+            };
+            // Extend the run while cells keep the same style
+            let style = first.style;
+            let start = col;
+            let mut text = String::new();
+            while col < row.len() {
+                match &row[col] {
+                    Some(cell) if cell.style == style => {
+                        text.push_str(&cell.text);
+                        col += cell.text.chars().next().map_or(1, text::char_width);
+                    }
+                    _ => break,
+                }
+            }
+
+            let x = PADDING + start as f32 * CELL_WIDTH;
+            let (fg, bg) = colors(&style);
+            if let Some(bg) = bg {
+                let width = (col - start) as f32 * CELL_WIDTH;
+                builder.add_rect(x, top, width, LINE_HEIGHT, &hex(bg), "none", 0.0);
+            }
+            if text.trim().is_empty() {
+                continue;
+            }
+            let fg = if style.dim {
+                let (r, g, b) = fg;
+                (r / 2, g / 2, b / 2)
+            } else {
+                fg
+            };
+            builder.add_styled_text(
+                x,
+                top + LINE_HEIGHT * 0.75,
+                &text,
+                &hex(fg),
+                FONT_SIZE,
+                style.bold,
+                style.italic,
+                style.underline,
+            );
+        }
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_runs_and_backgrounds() {
+        let svg = to_svg("\x1b[1;32mok\x1b[0m plain\n\x1b[44m  \x1b[0m", "#1e1e1e");
+        assert!(svg.starts_with("<?xml"));
+        assert!(
+            svg.contains(r##"fill="#00cd00" xml:space="preserve" font-weight="bold">ok</text>"##)
+        );
+        assert!(svg.contains(">plain</text>") || svg.contains("> plain</text>"));
+        // The blue background run becomes a rectangle, the spaces no text
+        assert!(svg.contains(r##"fill="#0000ee" stroke="none""##));
+        assert!(svg.contains(r##"fill="#1e1e1e""##));
+    }
+}
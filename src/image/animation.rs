@@ -0,0 +1,209 @@
+//! Animated GIF, APNG and WebP playback
+//!
+//! Kitty receives every frame up front and runs the animation itself, so
+//! the command returns immediately. Other terminals get frames drawn with
+//! text cells (halfblock, quadblock or Braille) redrawn in place until the
+//! loops run out or Ctrl+C is pressed.
+
+use super::{kitty_command, png_bytes, shrink_to, ImageOptions, Placement};
+use crate::output::{color, watch};
+use image::codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder};
+use image::{AnimationDecoder, DynamicImage, ImageFormat};
+use std::io::{Cursor, Write};
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+/// Delays shorter than this are treated as unset, as browsers do
+const MIN_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// One fully composited frame
+pub struct Frame {
+    pub image: DynamicImage,
+    pub delay: Duration,
+}
+
+/// Frames of an animated GIF, APNG or WebP; `None` for still images
+pub fn decode(bytes: &[u8]) -> Option<Vec<Frame>> {
+    let frames = match image::guess_format(bytes).ok()? {
+        ImageFormat::Gif => GifDecoder::new(Cursor::new(bytes)).ok()?.into_frames(),
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(bytes)).ok()?;
+            if !decoder.is_apng().ok()? {
+                return None;
+            }
+            decoder.apng().ok()?.into_frames()
+        }
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(Cursor::new(bytes)).ok()?;
+            if !decoder.has_animation() {
+                return None;
+            }
+            decoder.into_frames()
+        }
+        _ => return None,
+    };
+    let frames: Vec<Frame> = frames
+        .collect_frames()
+        .ok()?
+        .into_iter()
+        .map(|frame| {
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            let delay = Duration::from_secs_f64(numer as f64 / denom.max(1) as f64 / 1000.0);
+            Frame {
+                image: DynamicImage::ImageRgba8(frame.into_buffer()),
+                delay: if delay < MIN_DELAY {
+                    DEFAULT_DELAY
+                } else {
+                    delay
+                },
+            }
+        })
+        .collect();
+    (frames.len() > 1).then_some(frames)
+}
+
+/// Per-frame delays, with `--fps` replacing the file's own timing
+fn delays(frames: &[Frame], fps: Option<f64>) -> Vec<Duration> {
+    frames
+        .iter()
+        .map(|f| fps.map_or(f.delay, |fps| Duration::from_secs_f64(1.0 / fps)))
+        .collect()
+}
+
+/// Hand all frames to kitty and let it loop them
+pub fn play_kitty(
+    frames: &[Frame],
+    placement: &Placement,
+    options: &ImageOptions,
+) -> anyhow::Result<()> {
+    let delays = delays(frames, options.fps);
+    let id = std::process::id() % 0xffff + 1;
+    let mut out = placement.pad();
+    for (i, (frame, delay)) in frames.iter().zip(&delays).enumerate() {
+        let png = png_bytes(&shrink_to(&frame.image, placement))?;
+        let control = if i == 0 {
+            format!(
+                "f=100,a=T,i={},c={},r={},q=2",
+                id, placement.cols, placement.rows
+            )
+        } else {
+            format!("f=100,a=f,i={},z={},q=2", id, delay.as_millis())
+        };
+        out.push_str(&kitty_command(&control, &png));
+    }
+    // The root frame's gap, then start: v=1 loops forever, v=n plays n-1 times
+    let loops = options.loops.filter(|n| *n > 0).map_or(1, |n| n + 1);
+    out.push_str(&format!(
+        "\x1b_Ga=a,i={},r=1,z={},q=2\x1b\\\x1b_Ga=a,i={},s=3,v={},q=2\x1b\\",
+        id,
+        delays[0].as_millis(),
+        id,
+        loops
+    ));
+    let mut stdout = color::stdout();
+    stdout.write_all(out.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Redraw frames drawn by `draw` in place
+pub fn play_cells(
+    frames: &[Frame],
+    placement: &Placement,
+    options: &ImageOptions,
+    draw: fn(&DynamicImage, &Placement, &ImageOptions) -> String,
+) -> anyhow::Result<()> {
+    let delays = delays(frames, options.fps);
+    let rendered: Vec<String> = frames
+        .iter()
+        .map(|f| draw(&f.image, placement, options))
+        .collect();
+    let loops = options.loops.unwrap_or(0);
+    let running = watch::setup_ctrl_c();
+    let mut stdout = color::stdout();
+    write!(stdout, "\x1b[?25l")?;
+
+    let mut played = 0;
+    let mut first = true;
+    'playback: loop {
+        for (frame, delay) in rendered.iter().zip(&delays) {
+            if !running.load(Ordering::SeqCst) {
+                break 'playback;
+            }
+            // Each frame goes out in one write so none is ever half drawn
+            let mut out = String::with_capacity(frame.len() + 8);
+            if !first {
+                out.push_str(&format!("\x1b[{}A\r", placement.rows));
+            }
+            out.push_str(frame);
+            stdout.write_all(out.as_bytes())?;
+            stdout.flush()?;
+            first = false;
+
+            let step = Duration::from_millis(10);
+            let mut slept = Duration::ZERO;
+            while slept < *delay && running.load(Ordering::SeqCst) {
+                thread::sleep(step.min(*delay - slept));
+                slept += step;
+            }
+        }
+        played += 1;
+        if loops != 0 && played >= loops {
+            break;
+        }
+    }
+
+    write!(stdout, "\x1b[?25h")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Rgba, RgbaImage};
+
+    fn gif(delays_ms: &[u32]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+            for (i, ms) in delays_ms.iter().enumerate() {
+                let shade = (i * 80) as u8;
+                let buffer = RgbaImage::from_pixel(4, 4, Rgba([shade, 0, 0, 255]));
+                let frame =
+                    image::Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(*ms, 1));
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_decode_animated_gif() {
+        let frames = decode(&gif(&[50, 200, 0])).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].delay, Duration::from_millis(50));
+        assert_eq!(frames[1].delay, Duration::from_millis(200));
+        // An unset delay falls back to the browser default
+        assert_eq!(frames[2].delay, DEFAULT_DELAY);
+    }
+
+    #[test]
+    fn test_single_frame_is_not_animated() {
+        assert!(decode(&gif(&[100])).is_none());
+        assert!(decode(b"not an image").is_none());
+    }
+
+    #[test]
+    fn test_fps_overrides_delays() {
+        let frames = decode(&gif(&[50, 200])).unwrap();
+        assert_eq!(delays(&frames, Some(10.0)), [Duration::from_millis(100); 2]);
+        assert_eq!(
+            delays(&frames, None),
+            [Duration::from_millis(50), Duration::from_millis(200)]
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Linear barcodes: Code 128, EAN-13 and EAN-8
+//!
+//! Bars are drawn with the same quiet zone and block drawing as QR codes,
+//! with the encoded text centered underneath.
+
+use super::symbol;
+use crate::text;
+use std::io::{self, Write};
+
+/// Light modules left and right of the bars
+const QUIET_ZONE: usize = 10;
+
+pub const SYMBOLOGIES: [&str; 3] = ["code128", "ean13", "ean8"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Symbology {
+    Code128,
+    Ean13,
+    Ean8,
+}
+
+impl Symbology {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('-', "").as_str() {
+            "code128" => Some(Symbology::Code128),
+            "ean13" | "ean" => Some(Symbology::Ean13),
+            "ean8" => Some(Symbology::Ean8),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarcodeOptions {
+    pub symbology: Symbology,
+    /// Cells per module
+    pub size: u32,
+    /// Lines of bars
+    pub height: u32,
+    /// Draw dark bars as blocks, for light terminal backgrounds
+    pub invert: bool,
+    /// Print the encoded text under the bars
+    pub text: bool,
+}
+
+/// Bar and space widths of Code 128 values 0-105; the stop pattern is `STOP`
+const CODE128: [&str; 106] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232",
+];
+const STOP: &str = "2331112";
+const CODE_B: u32 = 100;
+const START_B: u32 = 104;
+const START_C: u32 = 105;
+
+/// EAN digit patterns, dark = '1': L (odd parity), G (even parity), R (right half)
+const EAN_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+const EAN_G: [&str; 10] = [
+    "0100111", "0110011", "0011011", "0100001", "0011101", "0111001", "0000101", "0010001",
+    "0001001", "0010111",
+];
+const EAN_R: [&str; 10] = [
+    "1110010", "1100110", "1101100", "1000010", "1011100", "1001110", "1010000", "1000100",
+    "1001000", "1110100",
+];
+/// L/G choice for the left six digits of EAN-13, chosen by the first digit
+const EAN13_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+/// Render `data` as a barcode
+pub fn render(data: &str, options: &BarcodeOptions) {
+    if let Err(e) = try_render(data, options) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn try_render(data: &str, options: &BarcodeOptions) -> anyhow::Result<()> {
+    if options.size == 0 || options.height == 0 {
+        anyhow::bail!("--size and --height must be at least 1");
+    }
+    let (bars, label) = encode(data, options.symbology)?;
+    print!("{}", frame(&bars, &label, options));
+    io::stdout().flush()?;
+    Ok(())
+}
+
+/// Dark modules of the barcode and the text to print under it
+fn encode(data: &str, symbology: Symbology) -> anyhow::Result<(Vec<bool>, String)> {
+    match symbology {
+        Symbology::Code128 => Ok((code128(data)?, data.to_string())),
+        Symbology::Ean13 => {
+            let digits = ean_digits(data, 13)?;
+            let label = format!(
+                "{} {} {}",
+                digits[0],
+                join(&digits[1..7]),
+                join(&digits[7..])
+            );
+            Ok((ean(&digits), label))
+        }
+        Symbology::Ean8 => {
+            let digits = ean_digits(data, 8)?;
+            let label = format!("{} {}", join(&digits[..4]), join(&digits[4..]));
+            Ok((ean(&digits), label))
+        }
+    }
+}
+
+fn join(digits: &[u32]) -> String {
+    digits.iter().map(u32::to_string).collect()
+}
+
+/// Expand bar/space widths like "212222" into modules, starting with a bar
+fn widths(pattern: &str, bars: &mut Vec<bool>) {
+    for (i, width) in pattern.chars().enumerate() {
+        let width = width.to_digit(10).unwrap_or(1) as usize;
+        bars.extend(std::iter::repeat_n(i % 2 == 0, width));
+    }
+}
+
+/// Code 128 values for `data`: code set C for runs of digit pairs, B otherwise
+fn code128_values(data: &str) -> anyhow::Result<Vec<u32>> {
+    if data.is_empty() {
+        anyhow::bail!("Nothing to encode");
+    }
+    if let Some(c) = data.chars().find(|c| !(' '..='~').contains(c)) {
+        anyhow::bail!("Code 128 encodes printable ASCII only, not {:?}", c);
+    }
+    let digits_only = data.len() >= 4 && data.bytes().all(|b| b.is_ascii_digit());
+    let mut values = Vec::new();
+    if digits_only {
+        values.push(START_C);
+        let pairs = data.len() / 2 * 2;
+        for pair in data.as_bytes()[..pairs].chunks(2) {
+            values.push(((pair[0] - b'0') * 10 + (pair[1] - b'0')) as u32);
+        }
+        // An odd digit left over switches to set B
+        if let Some(last) = data.as_bytes().get(pairs) {
+            values.push(CODE_B);
+            values.push((*last - b' ') as u32);
+        }
+    } else {
+        values.push(START_B);
+        values.extend(data.bytes().map(|b| (b - b' ') as u32));
+    }
+    let checksum = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| i.max(1) as u32 * v)
+        .sum::<u32>()
+        % 103;
+    values.push(checksum);
+    Ok(values)
+}
+
+fn code128(data: &str) -> anyhow::Result<Vec<bool>> {
+    let mut bars = Vec::new();
+    for value in code128_values(data)? {
+        widths(CODE128[value as usize], &mut bars);
+    }
+    widths(STOP, &mut bars);
+    Ok(bars)
+}
+
+/// Digits of an EAN code of `len` digits, given with or without its check
+/// digit (a given one must be correct)
+fn ean_digits(data: &str, len: usize) -> anyhow::Result<Vec<u32>> {
+    let digits: Vec<u32> = data
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-'))
+        .map(|c| c.to_digit(10))
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow::anyhow!("EAN-{} takes digits only, got '{}'", len, data))?;
+    if digits.len() != len && digits.len() != len - 1 {
+        anyhow::bail!(
+            "EAN-{} takes {} digits, or {} without the check digit; got {}",
+            len,
+            len,
+            len - 1,
+            digits.len()
+        );
+    }
+    let check = ean_check(&digits[..len - 1]);
+    match digits.get(len - 1) {
+        Some(&given) if given != check => anyhow::bail!(
+            "Invalid EAN-{} check digit {} (expected {})",
+            len,
+            given,
+            check
+        ),
+        Some(_) => Ok(digits),
+        None => Ok([digits, vec![check]].concat()),
+    }
+}
+
+/// Weights alternate 3, 1 from the rightmost data digit
+fn ean_check(data: &[u32]) -> u32 {
+    let sum: u32 = data
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d * 3 } else { *d })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+/// EAN-13 or EAN-8 modules: guard, left half, center guard, right half, guard
+fn ean(digits: &[u32]) -> Vec<bool> {
+    let (parity, left, right) = if digits.len() == 13 {
+        (
+            EAN13_PARITY[digits[0] as usize],
+            &digits[1..7],
+            &digits[7..],
+        )
+    } else {
+        ("LLLL", &digits[..4], &digits[4..])
+    };
+    let mut pattern = String::from("101");
+    for (d, set) in left.iter().zip(parity.chars()) {
+        let table = if set == 'G' { &EAN_G } else { &EAN_L };
+        pattern.push_str(table[*d as usize]);
+    }
+    pattern.push_str("01010");
+    for d in right {
+        pattern.push_str(EAN_R[*d as usize]);
+    }
+    pattern.push_str("101");
+    pattern.chars().map(|c| c == '1').collect()
+}
+
+/// Bars as blocks, then the label centered under them
+fn frame(bars: &[bool], label: &str, options: &BarcodeOptions) -> String {
+    let grid = symbol::with_quiet_zone(&[bars.to_vec()], QUIET_ZONE, 0);
+    let pixels = symbol::scale(&grid, options.size as usize, options.height as usize * 2);
+    let mut out = symbol::halfblocks(&pixels, options.invert);
+    if options.text {
+        let width = pixels.first().map_or(0, Vec::len);
+        let label = text::truncate(label, width, "…");
+        let pad = width.saturating_sub(text::display_width(&label)) / 2;
+        out.push_str(&format!("{}{}\n", " ".repeat(pad), label));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code128_table() {
+        for pattern in CODE128 {
+            let sum: u32 = pattern.chars().filter_map(|c| c.to_digit(10)).sum();
+            assert_eq!(sum, 11, "{}", pattern);
+        }
+        let mut unique = CODE128.to_vec();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 106);
+    }
+
+    #[test]
+    fn test_code128_values() {
+        // Start B, "W" is 87 - 32, then the weighted sum mod 103
+        let values = code128_values("Wikipedia").unwrap();
+        assert_eq!(values[0], START_B);
+        assert_eq!(values[1], 55);
+        assert_eq!(*values.last().unwrap(), 88);
+
+        // Digits pack in pairs, an odd one out switches to B
+        assert_eq!(
+            code128_values("123456789").unwrap()[..7],
+            [START_C, 12, 34, 56, 78, CODE_B, 25]
+        );
+        assert!(code128_values("café").is_err());
+    }
+
+    #[test]
+    fn test_code128_width() {
+        // 11 modules per symbol plus a 13-module stop
+        let bars = code128("AB").unwrap();
+        assert_eq!(bars.len(), 11 * 4 + 13);
+        assert!(bars[0] && *bars.last().unwrap());
+    }
+
+    #[test]
+    fn test_ean_check_digit() {
+        assert_eq!(ean_digits("590123412345", 13).unwrap()[12], 7);
+        assert_eq!(ean_digits("9638507", 8).unwrap()[7], 4);
+        assert!(ean_digits("5901234123457", 13).is_ok());
+        let err = ean_digits("5901234123450", 13).unwrap_err();
+        assert!(err.to_string().contains("expected 7"));
+        assert!(ean_digits("12ab", 8).is_err());
+        assert!(ean_digits("123", 8).is_err());
+    }
+
+    #[test]
+    fn test_ean_modules() {
+        let digits = ean_digits("5901234123457", 13).unwrap();
+        let bars = ean(&digits);
+        assert_eq!(bars.len(), 95);
+        // Left digit 9 uses L, then 0 uses G under parity "LGGLLG"
+        let modules: String = bars[3..17]
+            .iter()
+            .map(|b| if *b { '1' } else { '0' })
+            .collect();
+        assert_eq!(modules, "00010110100111");
+        assert_eq!(ean(&ean_digits("96385074", 8).unwrap()).len(), 67);
+    }
+
+    #[test]
+    fn test_frame_label_centered() {
+        let options = BarcodeOptions {
+            symbology: Symbology::Ean8,
+            size: 1,
+            height: 2,
+            invert: false,
+            text: true,
+        };
+        let (bars, label) = encode("96385074", Symbology::Ean8).unwrap();
+        assert_eq!(label, "9638 5074");
+        let out = frame(&bars, &label, &options);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].chars().count(), 67 + 2 * QUIET_ZONE);
+        assert_eq!(lines[0], lines[1]);
+        // Quiet zone lit, first guard bar dark
+        assert!(lines[0].starts_with(&format!("{} ", "█".repeat(QUIET_ZONE))));
+        assert_eq!(lines[2].trim(), "9638 5074");
+    }
+}
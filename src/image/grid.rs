@@ -0,0 +1,443 @@
+//! Several images as a grid of thumbnails
+//!
+//! Every image is scaled to fit the same box, keeping its shape, and
+//! centered in it. Text-cell protocols lay the boxes out with the layout
+//! engine; protocols that draw pixels get one composed image per grid row.
+//! Captions go on a line under each row.
+
+use super::{
+    braille_frame, decode_image, halfblock_frame, load_bytes, quadblock_frame, render_iterm2,
+    render_kitty, render_sixel, Dimension, ImageAlign, ImageOptions, Placement, Protocol,
+    CELL_HEIGHT_PX, CELL_WIDTH_PX,
+};
+use crate::output::{input, layout};
+use crate::text;
+use image::{imageops, DynamicImage, GenericImageView, RgbaImage};
+use std::path::Path;
+
+/// Columns and, optionally, rows of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSize {
+    pub cols: u32,
+    pub rows: Option<u32>,
+}
+
+impl GridSize {
+    /// Parse "3x2" or just "3" for three columns and as many rows as needed
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        let (cols, rows) = match s.split_once('x') {
+            Some((cols, rows)) => (cols, Some(rows.trim().parse().ok().filter(|r| *r > 0)?)),
+            None => (s.as_str(), None),
+        };
+        let cols = cols.trim().parse().ok().filter(|c| *c > 0)?;
+        Some(GridSize { cols, rows })
+    }
+}
+
+/// Layout of a multi-image display
+#[derive(Debug, Clone, Copy)]
+pub struct GridOptions {
+    /// Defaults to as many columns as fit, up to 4
+    pub size: Option<GridSize>,
+    /// Blank cells between thumbnails, and lines between rows
+    pub gap: u32,
+    /// File names under each thumbnail
+    pub captions: bool,
+}
+
+/// `*` and `?` wildcard match over a whole file name
+fn wildcard(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            wildcard(&pattern[1..], name) || (!name.is_empty() && wildcard(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => wildcard(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) => p == n && wildcard(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// `paths` with glob patterns in the file name expanded, for shells that
+/// pass them through (quoted, or on Windows)
+pub fn expand(paths: &[String]) -> anyhow::Result<Vec<String>> {
+    let mut out = Vec::new();
+    for path in paths {
+        let is_pattern = path.contains(['*', '?']) && !Path::new(path).exists();
+        if !is_pattern || path.starts_with("http://") || path.starts_with("https://") {
+            out.push(path.clone());
+            continue;
+        }
+        let pattern = Path::new(path);
+        let dir = pattern
+            .parent()
+            .filter(|d| !d.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let name: Vec<char> = pattern
+            .file_name()
+            .map(|n| n.to_string_lossy().chars().collect())
+            .unwrap_or_default();
+        let mut matches: Vec<String> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|e| e.path().is_file())
+                    .filter(|e| {
+                        let file: Vec<char> = e.file_name().to_string_lossy().chars().collect();
+                        wildcard(&name, &file)
+                    })
+                    .map(|e| {
+                        let file = e.file_name().to_string_lossy().into_owned();
+                        match pattern.parent().filter(|d| !d.as_os_str().is_empty()) {
+                            Some(parent) => parent.join(file).to_string_lossy().into_owned(),
+                            None => file,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if matches.is_empty() {
+            anyhow::bail!("No images match '{}'", path);
+        }
+        matches.sort();
+        out.extend(matches);
+    }
+    Ok(out)
+}
+
+/// Grid geometry in cells: columns, and each thumbnail box's size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Layout {
+    cols: u32,
+    box_cols: u32,
+    box_rows: u32,
+    indent: u32,
+}
+
+impl Layout {
+    fn new(count: usize, term: (usize, usize), options: &ImageOptions, grid: &GridOptions) -> Self {
+        let term_width = term.0.max(1) as u32;
+        let cols = grid
+            .size
+            .map_or(count.clamp(1, 4) as u32, |s| s.cols)
+            .min(count.max(1) as u32);
+        let gaps = grid.gap * (cols - 1);
+        // --width and --height size each thumbnail; by default they share
+        // the terminal width and are about square in pixels
+        let box_cols = match options.width {
+            Some(Dimension::Percent(p)) => (term_width as f32 * p / 100.0) as u32,
+            Some(Dimension::Cells(n)) => n,
+            None => term_width.saturating_sub(gaps) / cols,
+        }
+        .max(1);
+        let box_rows = match options.height {
+            Some(Dimension::Percent(p)) => (term.1 as f32 * p / 100.0) as u32,
+            Some(Dimension::Cells(n)) => n,
+            None => box_cols * CELL_WIDTH_PX / CELL_HEIGHT_PX,
+        }
+        .max(1);
+        let free = term_width.saturating_sub(cols * box_cols + gaps);
+        let indent = match options.align {
+            ImageAlign::Left => 0,
+            ImageAlign::Center => free / 2,
+            ImageAlign::Right => free,
+        };
+        Layout {
+            cols,
+            box_cols,
+            box_rows,
+            indent,
+        }
+    }
+
+    /// Where `img` lands inside its box: as large as fits, same shape
+    fn fit(&self, img: &DynamicImage) -> Placement {
+        let options = ImageOptions {
+            width: Some(Dimension::Cells(self.box_cols)),
+            height: Some(Dimension::Cells(self.box_rows)),
+            preserve_aspect: true,
+            ..ImageOptions::default()
+        };
+        let box_size = (self.box_cols as usize, self.box_rows as usize);
+        let mut placement = Placement::new(img.dimensions(), box_size, &options);
+        placement.rows = placement.rows.min(self.box_rows);
+        placement.indent = 0;
+        placement
+    }
+}
+
+/// Caption for `path`: its file name, shortened to fit `width`
+fn caption(path: &str, width: u32) -> String {
+    let name = if path == input::STDIN {
+        "stdin"
+    } else {
+        path.rsplit(['/', '\\'])
+            .find(|s| !s.is_empty())
+            .unwrap_or(path)
+    };
+    text::truncate(name, width as usize, "…")
+}
+
+/// One thumbnail as text lines filling its box, image centered
+fn text_cell(
+    draw: fn(&DynamicImage, &Placement, &ImageOptions) -> String,
+    img: &DynamicImage,
+    layout: &Layout,
+    options: &ImageOptions,
+) -> String {
+    let placement = layout.fit(img);
+    let frame = draw(img, &placement, options);
+    let left = " ".repeat(((layout.box_cols - placement.cols) / 2) as usize);
+    let right = " ".repeat((layout.box_cols - placement.cols) as usize - left.len());
+    let blank = " ".repeat(layout.box_cols as usize);
+    let top = (layout.box_rows - placement.rows) / 2;
+
+    let mut lines: Vec<String> = Vec::with_capacity(layout.box_rows as usize);
+    lines.extend((0..top).map(|_| blank.clone()));
+    lines.extend(frame.lines().map(|l| format!("{}{}{}", left, l, right)));
+    lines.resize(layout.box_rows as usize, blank);
+    lines.join("\n")
+}
+
+/// One grid row of thumbnails composed into a single transparent image
+fn compose_row(images: &[DynamicImage], layout: &Layout, gap: u32) -> DynamicImage {
+    let cols = images.len() as u32 * layout.box_cols + gap * (images.len() as u32 - 1);
+    let mut canvas = RgbaImage::new(cols * CELL_WIDTH_PX, layout.box_rows * CELL_HEIGHT_PX);
+    for (i, img) in images.iter().enumerate() {
+        let placement = layout.fit(img);
+        let (w, h) = placement.pixels();
+        let thumb = img.resize_exact(w, h, imageops::FilterType::Lanczos3);
+        let box_x = i as u32 * (layout.box_cols + gap) * CELL_WIDTH_PX;
+        let x = box_x + (layout.box_cols * CELL_WIDTH_PX - w) / 2;
+        let y = (layout.box_rows * CELL_HEIGHT_PX - h) / 2;
+        imageops::overlay(&mut canvas, &thumb, x as i64, y as i64);
+    }
+    DynamicImage::ImageRgba8(canvas)
+}
+
+/// Draw `paths` as a grid of thumbnails
+pub fn render(
+    paths: &[String],
+    protocol: Protocol,
+    options: &ImageOptions,
+    grid: &GridOptions,
+) -> anyhow::Result<()> {
+    let mut paths = paths.to_vec();
+    if let Some(limit) = grid
+        .size
+        .and_then(|s| s.rows.map(|rows| (s.cols * rows) as usize))
+    {
+        if paths.len() > limit {
+            eprintln!(
+                "Showing {} of {} images; a bigger --grid shows more",
+                limit,
+                paths.len()
+            );
+            paths.truncate(limit);
+        }
+    }
+    let images = paths
+        .iter()
+        .map(|path| {
+            let bytes = load_bytes(path)?;
+            decode_image(path, &bytes).map_err(|e| anyhow::anyhow!("{}: {}", path, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let term = crossterm::terminal::size()
+        .map(|(w, h)| (w as usize, h as usize))
+        .unwrap_or((80, 24));
+    let layout = Layout::new(images.len(), term, options, grid);
+    let pad = " ".repeat(layout.indent as usize);
+    let gap = grid.gap as usize;
+
+    let rows = images.chunks(layout.cols as usize);
+    let captions = paths.chunks(layout.cols as usize);
+    for (i, (row, names)) in rows.zip(captions).enumerate() {
+        if i > 0 {
+            print!("{}", "\n".repeat(gap));
+        }
+        let draw = match protocol {
+            Protocol::Halfblock => Some(halfblock_frame as fn(&_, &_, &_) -> String),
+            Protocol::Quadblock => Some(quadblock_frame as fn(&_, &_, &_) -> String),
+            Protocol::Braille => Some(braille_frame as fn(&_, &_, &_) -> String),
+            Protocol::Kitty | Protocol::Sixel | Protocol::ITerm2 => None,
+        };
+        match draw {
+            Some(draw) => {
+                let cells: Vec<String> = row
+                    .iter()
+                    .map(|img| text_cell(draw, img, &layout, options))
+                    .collect();
+                for line in layout::side_by_side(&cells, gap, "left") {
+                    println!("{}{}", pad, line.trim_end());
+                }
+            }
+            None => {
+                let composed = compose_row(row, &layout, grid.gap);
+                let placement = Placement {
+                    cols: composed.width() / CELL_WIDTH_PX,
+                    rows: layout.box_rows,
+                    indent: layout.indent,
+                };
+                match protocol {
+                    Protocol::Kitty => render_kitty(&composed, &placement)?,
+                    Protocol::ITerm2 => render_iterm2(&composed, &placement)?,
+                    _ => render_sixel(&composed, &placement, options)?,
+                }
+                if protocol != Protocol::Sixel {
+                    println!();
+                }
+            }
+        }
+        if grid.captions {
+            let line: Vec<String> = names
+                .iter()
+                .map(|path| {
+                    let name = caption(path, layout.box_cols);
+                    let free = layout.box_cols as usize - text::display_width(&name);
+                    format!(
+                        "{}{}{}",
+                        " ".repeat(free / 2),
+                        name,
+                        " ".repeat(free - free / 2)
+                    )
+                })
+                .collect();
+            println!("{}{}", pad, line.join(&" ".repeat(gap)).trim_end());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_size_parse() {
+        assert_eq!(
+            GridSize::parse("3x2"),
+            Some(GridSize {
+                cols: 3,
+                rows: Some(2)
+            })
+        );
+        assert_eq!(
+            GridSize::parse("4"),
+            Some(GridSize {
+                cols: 4,
+                rows: None
+            })
+        );
+        assert_eq!(GridSize::parse("0x2"), None);
+        assert_eq!(GridSize::parse("3x"), None);
+        assert_eq!(GridSize::parse("wide"), None);
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let m = |p: &str, n: &str| {
+            wildcard(
+                &p.chars().collect::<Vec<_>>(),
+                &n.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert!(m("*.png", "shot.png"));
+        assert!(m("shot-?.png", "shot-1.png"));
+        assert!(m("*", ""));
+        assert!(!m("*.png", "shot.jpg"));
+        assert!(!m("shot-?.png", "shot-10.png"));
+    }
+
+    #[test]
+    fn test_expand_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.png", "a.png", "c.jpg"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let pattern = dir.path().join("*.png").to_string_lossy().into_owned();
+        let expanded = expand(&[pattern]).unwrap();
+        assert_eq!(
+            expanded,
+            [
+                dir.path().join("a.png").to_string_lossy(),
+                dir.path().join("b.png").to_string_lossy()
+            ]
+        );
+        // Plain paths pass through untouched
+        assert_eq!(expand(&["x.gif".to_string()]).unwrap(), ["x.gif"]);
+        let none = dir.path().join("*.webp").to_string_lossy().into_owned();
+        assert!(expand(&[none]).is_err());
+    }
+
+    fn grid(size: Option<&str>) -> GridOptions {
+        GridOptions {
+            size: size.and_then(GridSize::parse),
+            gap: 1,
+            captions: false,
+        }
+    }
+
+    #[test]
+    fn test_layout_shares_terminal_width() {
+        let options = ImageOptions::default();
+        let layout = Layout::new(6, (80, 24), &options, &grid(Some("3x2")));
+        // 80 columns less two gaps, split three ways; square-ish boxes
+        assert_eq!(layout.cols, 3);
+        assert_eq!(layout.box_cols, 26);
+        assert_eq!(layout.box_rows, 13);
+
+        // Fewer images than columns: no empty columns
+        assert_eq!(Layout::new(2, (80, 24), &options, &grid(None)).cols, 2);
+
+        let options = ImageOptions {
+            width: Some(Dimension::Cells(10)),
+            height: Some(Dimension::Cells(4)),
+            align: ImageAlign::Center,
+            ..ImageOptions::default()
+        };
+        let layout = Layout::new(3, (80, 24), &options, &grid(None));
+        assert_eq!((layout.box_cols, layout.box_rows), (10, 4));
+        assert_eq!(layout.indent, (80 - 32) / 2);
+    }
+
+    #[test]
+    fn test_thumbnails_keep_their_shape() {
+        let layout = Layout::new(2, (42, 24), &ImageOptions::default(), &grid(None));
+        // A wide image fills the width, a tall one the height
+        let wide = DynamicImage::new_rgba8(400, 100);
+        let tall = DynamicImage::new_rgba8(100, 400);
+        assert_eq!(layout.fit(&wide).cols, layout.box_cols);
+        assert!(layout.fit(&wide).rows < layout.box_rows);
+        assert_eq!(layout.fit(&tall).rows, layout.box_rows);
+        assert!(layout.fit(&tall).cols < layout.box_cols);
+
+        let cell = text_cell(halfblock_frame, &wide, &layout, &ImageOptions::default());
+        assert_eq!(cell.lines().count(), layout.box_rows as usize);
+        assert!(cell
+            .lines()
+            .all(|l| text::display_width(l) == layout.box_cols as usize));
+    }
+
+    #[test]
+    fn test_compose_row_size() {
+        let layout = Layout::new(3, (80, 24), &ImageOptions::default(), &grid(None));
+        let images = vec![DynamicImage::new_rgba8(10, 10); 3];
+        let row = compose_row(&images, &layout, 1);
+        assert_eq!(
+            row.dimensions(),
+            (
+                (3 * layout.box_cols + 2) * CELL_WIDTH_PX,
+                layout.box_rows * CELL_HEIGHT_PX
+            )
+        );
+    }
+
+    #[test]
+    fn test_caption() {
+        assert_eq!(caption("shots/home.png", 20), "home.png");
+        assert_eq!(caption("-", 20), "stdin");
+        assert_eq!(caption("a-very-long-name.png", 8), "a-very-…");
+    }
+}
@@ -1,6 +1,19 @@
-use image::{DynamicImage, GenericImageView, ImageFormat};
+mod animation;
+pub mod barcode;
+mod grid;
+pub mod qr;
+pub mod quantize;
+mod symbol;
+mod thumbnail;
+
+use crate::output::profile::{self, Phase};
+use crate::output::{color, input};
+use image::{DynamicImage, GenericImageView, ImageFormat, RgbaImage};
 use std::env;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+
+pub use grid::{GridOptions, GridSize};
+pub use thumbnail::{halfblocks, is_image, Thumbnail};
 
 /// Protocol to use for rendering images
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -9,46 +22,250 @@ enum Protocol {
     Sixel,
     ITerm2,
     Halfblock,
+    /// ▘▝▖▗ quadrants, two colors per cell
+    Quadblock,
+    /// Monochrome 2x4 dots per cell
+    Braille,
+}
+
+/// Approximate size of a terminal cell in pixels, used to map image pixels
+/// to cells for every protocol
+const CELL_WIDTH_PX: u32 = 8;
+const CELL_HEIGHT_PX: u32 = 16;
+
+/// A requested image dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dimension {
+    /// A number of terminal cells
+    Cells(u32),
+    /// Share of the terminal width or height
+    Percent(f32),
+}
+
+impl Dimension {
+    /// Parse "40" (cells) or "50%"
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        match s.strip_suffix('%') {
+            Some(pct) => pct
+                .trim()
+                .parse::<f32>()
+                .ok()
+                .filter(|p| *p > 0.0 && *p <= 100.0)
+                .map(Dimension::Percent),
+            None => s
+                .parse::<u32>()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(Dimension::Cells),
+        }
+    }
+
+    /// Size in cells given the terminal extent along this axis
+    fn cells(self, extent: usize) -> f32 {
+        match self {
+            Dimension::Cells(n) => n as f32,
+            Dimension::Percent(p) => extent as f32 * p / 100.0,
+        }
+    }
+}
+
+/// Horizontal placement of the image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl ImageAlign {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "left" => Some(ImageAlign::Left),
+            "center" | "centre" => Some(ImageAlign::Center),
+            "right" => Some(ImageAlign::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Sizing and placement shared by all protocols
+///
+/// With neither dimension the image shrinks to fit the terminal. With one,
+/// the other follows the image's aspect ratio. With both the image is
+/// stretched to fill them, unless `preserve_aspect` fits it inside instead.
+///
+/// Animated images play when stdout is a terminal or `loops` is set; `loops`
+/// of 0 (the default) repeats until Ctrl+C, and `fps` overrides the file's
+/// frame delays.
+///
+/// Sixel colors come from `palette`; `dither` spreads rounding error when
+/// reducing colors, for sixel, for Braille dots, and for halfblock and
+/// quadblock on 256/16 color terminals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImageOptions {
+    pub width: Option<Dimension>,
+    pub height: Option<Dimension>,
+    pub align: ImageAlign,
+    pub preserve_aspect: bool,
+    pub loops: Option<u32>,
+    pub fps: Option<f64>,
+    pub palette: quantize::Palette,
+    pub dither: bool,
+}
+
+/// Where an image lands on screen, in cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    cols: u32,
+    rows: u32,
+    indent: u32,
 }
 
-pub fn render(path: &str, protocol_arg: &str) {
-    match render_image(path, protocol_arg) {
-        Ok(_) => {}
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+impl Placement {
+    fn new(image_px: (u32, u32), term: (usize, usize), options: &ImageOptions) -> Self {
+        let (term_width, term_height) = term;
+        let natural_cols = image_px.0.max(1) as f32 / CELL_WIDTH_PX as f32;
+        let natural_rows = image_px.1.max(1) as f32 / CELL_HEIGHT_PX as f32;
+        let width = options.width.map(|w| w.cells(term_width));
+        let height = options.height.map(|h| h.cells(term_height));
+
+        let (mut cols, mut rows) = match (width, height) {
+            (Some(w), Some(h)) if options.preserve_aspect => {
+                let scale = (w / natural_cols).min(h / natural_rows);
+                (natural_cols * scale, natural_rows * scale)
+            }
+            (Some(w), Some(h)) => (w, h),
+            (Some(w), None) => (w, w * natural_rows / natural_cols),
+            (None, Some(h)) => (h * natural_cols / natural_rows, h),
+            (None, None) => {
+                let fit_cols = term_width.saturating_sub(2).max(1) as f32 / natural_cols;
+                let fit_rows = term_height.saturating_sub(4).max(1) as f32 / natural_rows;
+                let scale = fit_cols.min(fit_rows).min(1.0);
+                (natural_cols * scale, natural_rows * scale)
+            }
+        };
+        // Never wider than the terminal; keep the shape unless stretching
+        let max_cols = term_width.max(1) as f32;
+        if cols > max_cols {
+            if width.is_none() || height.is_none() || options.preserve_aspect {
+                rows *= max_cols / cols;
+            }
+            cols = max_cols;
         }
+
+        let cols = (cols.round() as u32).max(1);
+        let rows = (rows.round() as u32).max(1);
+        let free = (term_width as u32).saturating_sub(cols);
+        let indent = match options.align {
+            ImageAlign::Left => 0,
+            ImageAlign::Center => free / 2,
+            ImageAlign::Right => free,
+        };
+        Placement { cols, rows, indent }
+    }
+
+    /// Pixel size for protocols that draw exact pixels
+    fn pixels(&self) -> (u32, u32) {
+        (self.cols * CELL_WIDTH_PX, self.rows * CELL_HEIGHT_PX)
+    }
+
+    fn pad(&self) -> String {
+        " ".repeat(self.indent as usize)
+    }
+}
+
+/// Show an image, or several (or a glob) as a grid of thumbnails
+pub fn render(paths: &[String], protocol_arg: &str, options: &ImageOptions, grid: &GridOptions) {
+    let result = grid::expand(paths).and_then(|paths| match paths.as_slice() {
+        // A single image without --grid shows as usual
+        [path] if grid.size.is_none() => render_image(path, protocol_arg, options),
+        _ => grid::render(&paths, protocol(protocol_arg)?, options, grid),
+    });
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
     }
 }
 
-fn render_image(path: &str, protocol_arg: &str) -> anyhow::Result<()> {
-    let img = load_image(path)?;
+fn protocol(protocol_arg: &str) -> anyhow::Result<Protocol> {
     let protocol = if protocol_arg == "auto" {
         detect_protocol()
     } else {
         parse_protocol(protocol_arg)?
     };
-    let (term_width, term_height) = crossterm::terminal::size()
+    #[cfg(feature = "tracing")]
+    tracing::debug!(requested = protocol_arg, ?protocol, "image protocol");
+    Ok(protocol)
+}
+
+fn render_image(path: &str, protocol_arg: &str, options: &ImageOptions) -> anyhow::Result<()> {
+    let protocol = protocol(protocol_arg)?;
+    let term = crossterm::terminal::size()
         .map(|(w, h)| (w as usize, h as usize))
         .unwrap_or((80, 24));
-    match protocol {
-        Protocol::Kitty => render_kitty(&img, term_width, term_height),
-        Protocol::Sixel => render_sixel(&img, term_width, term_height),
-        Protocol::ITerm2 => render_iterm2(&img, term_width, term_height),
-        Protocol::Halfblock => render_halfblock(&img, term_width, term_height),
+
+    // Piped output gets the first frame only, unless --loop asks for more
+    let bytes = profile::time(Phase::Parse, || load_bytes(path))?;
+    let play = options.loops.is_some() || io::stdout().is_terminal();
+    if let Some(frames) = play.then(|| animation::decode(&bytes)).flatten() {
+        let placement = Placement::new(frames[0].image.dimensions(), term, options);
+        return profile::time(Phase::Render, || match protocol {
+            Protocol::Kitty => animation::play_kitty(&frames, &placement, options),
+            Protocol::Quadblock => {
+                animation::play_cells(&frames, &placement, options, quadblock_frame)
+            }
+            Protocol::Braille => animation::play_cells(&frames, &placement, options, braille_frame),
+            _ => animation::play_cells(&frames, &placement, options, halfblock_frame),
+        });
+    }
+
+    let img = profile::time(Phase::Parse, || decode_image(path, &bytes))?;
+    let placement = Placement::new(img.dimensions(), term, options);
+    // Encoders stream escapes as they go, so render and write are one phase
+    profile::time(Phase::Render, || match protocol {
+        Protocol::Kitty => render_kitty(&img, &placement),
+        Protocol::Sixel => render_sixel(&img, &placement, options),
+        Protocol::ITerm2 => render_iterm2(&img, &placement),
+        Protocol::Halfblock => render_cells(halfblock_frame(&img, &placement, options)),
+        Protocol::Quadblock => render_cells(quadblock_frame(&img, &placement, options)),
+        Protocol::Braille => render_cells(braille_frame(&img, &placement, options)),
+    })
+}
+
+/// `img` shrunk to at most the placement's pixel box, to keep payloads small
+/// for protocols that scale the image themselves
+fn shrink_to(img: &DynamicImage, placement: &Placement) -> DynamicImage {
+    let (max_w, max_h) = placement.pixels();
+    let (w, h) = img.dimensions();
+    if w > max_w || h > max_h {
+        img.resize(max_w, max_h, image::imageops::FilterType::Lanczos3)
+    } else {
+        img.clone()
     }
 }
 
-fn load_image(path: &str) -> anyhow::Result<DynamicImage> {
+fn load_bytes(path: &str) -> anyhow::Result<Vec<u8>> {
     if path.starts_with("http://") || path.starts_with("https://") {
         let response = ureq::get(path).call()?;
         let mut bytes = Vec::new();
         response.into_reader().read_to_end(&mut bytes)?;
-        let img = image::load_from_memory(&bytes)?;
-        Ok(img)
+        Ok(bytes)
     } else {
-        let img = image::open(path)?;
-        Ok(img)
+        Ok(input::read_bytes(path)?)
+    }
+}
+
+fn decode_image(path: &str, bytes: &[u8]) -> anyhow::Result<DynamicImage> {
+    // Format is sniffed from the magic bytes, falling back to the extension
+    // for formats without any (stdin and URLs have no extension)
+    match image::load_from_memory(bytes) {
+        Ok(img) => Ok(img),
+        Err(e) => match ImageFormat::from_path(path) {
+            Ok(format) => Ok(image::load_from_memory_with_format(bytes, format)?),
+            Err(_) => Err(e.into()),
+        },
     }
 }
 
@@ -90,211 +307,197 @@ fn parse_protocol(s: &str) -> anyhow::Result<Protocol> {
         "sixel" => Ok(Protocol::Sixel),
         "iterm2" => Ok(Protocol::ITerm2),
         "halfblock" => Ok(Protocol::Halfblock),
+        "quadblock" => Ok(Protocol::Quadblock),
+        "braille" => Ok(Protocol::Braille),
         _ => Err(anyhow::anyhow!(
-            "Invalid protocol: {}. Valid options: kitty, sixel, iterm2, halfblock",
+            "Invalid protocol: {}. Valid options: kitty, sixel, iterm2, halfblock, quadblock, braille",
             s
         )),
     }
 }
 
-fn render_kitty(img: &DynamicImage, term_width: usize, _term_height: usize) -> anyhow::Result<()> {
-    let max_width_px = ((term_width - 2) * 8) as u32;
-    let (img_width, img_height) = img.dimensions();
-    let scaled_img = if img_width > max_width_px {
-        let scale = max_width_px as f32 / img_width as f32;
-        let new_height = (img_height as f32 * scale) as u32;
-        img.resize(
-            max_width_px,
-            new_height,
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else {
-        img.clone()
-    };
+fn png_bytes(img: &DynamicImage) -> anyhow::Result<Vec<u8>> {
     let mut png_data = Vec::new();
-    scaled_img.write_to(&mut std::io::Cursor::new(&mut png_data), ImageFormat::Png)?;
-    let base64_data = base64_encode(&png_data);
-    print!("\x1b_Gf=100,a=T;{}\x1b\\", base64_data);
-    io::stdout().flush()?;
-    Ok(())
+    img.write_to(&mut std::io::Cursor::new(&mut png_data), ImageFormat::Png)?;
+    Ok(png_data)
 }
 
-fn render_sixel(img: &DynamicImage, term_width: usize, _term_height: usize) -> anyhow::Result<()> {
-    // Resize image to fit terminal width
-    // Assuming approx 8 pixels per character cell width
-    let max_width_px = (term_width as u32) * 8;
-    let (img_width, img_height) = img.dimensions();
-
-    let scaled_img = if img_width > max_width_px {
-        let scale = max_width_px as f32 / img_width as f32;
-        let new_height = (img_height as f32 * scale) as u32;
-        img.resize(
-            max_width_px,
-            new_height,
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else {
-        img.clone()
-    };
-
-    let rgba_img = scaled_img.to_rgba8();
-    let width = rgba_img.width();
-    let height = rgba_img.height();
-
-    // Quantize to 6x6x6 RGB cube (216 colors)
-    // Map: (r,g,b) -> index 0..215
-    let mut indexed_pixels = Vec::with_capacity((width * height) as usize);
-    let mut used_colors = [false; 216];
-
-    for pixel in rgba_img.pixels() {
-        // Handle transparency
-        if pixel[3] < 128 {
-            indexed_pixels.push(255); // Use 255 as marker for transparent
-            continue;
+/// Kitty graphics command carrying `payload`, split into the 4096-byte
+/// chunks the protocol requires
+fn kitty_command(control: &str, payload: &[u8]) -> String {
+    let encoded = base64_encode(payload);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(4096).collect();
+    let mut out = String::with_capacity(encoded.len() + chunks.len() * 16);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            out.push_str(&format!("\x1b_G{},m={};{}\x1b\\", control, more, chunk));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
         }
+    }
+    out
+}
 
-        let r = pixel[0];
-        let g = pixel[1];
-        let b = pixel[2];
+fn render_kitty(img: &DynamicImage, placement: &Placement) -> anyhow::Result<()> {
+    let png_data = png_bytes(&shrink_to(img, placement))?;
+    // c/r make the terminal scale the image to exactly this many cells
+    let control = format!("f=100,a=T,c={},r={}", placement.cols, placement.rows);
+    print!("{}{}", placement.pad(), kitty_command(&control, &png_data));
+    io::stdout().flush()?;
+    Ok(())
+}
 
-        // Map 0-255 to 0-5
-        let r_idx = (r as u16 * 5 + 127) / 255;
-        let g_idx = (g as u16 * 5 + 127) / 255;
-        let b_idx = (b as u16 * 5 + 127) / 255;
+fn render_sixel(
+    img: &DynamicImage,
+    placement: &Placement,
+    options: &ImageOptions,
+) -> anyhow::Result<()> {
+    print!("{}", sixel(img, placement, options));
+    println!(); // Newline after image
+    io::stdout().flush()?;
+    Ok(())
+}
 
-        let palette_index = (r_idx * 36 + g_idx * 6 + b_idx) as usize;
-        indexed_pixels.push(palette_index as u8);
-        used_colors[palette_index] = true;
-    }
+/// `img` as a sixel sequence, reduced to the `--palette` colors
+fn sixel(img: &DynamicImage, placement: &Placement, options: &ImageOptions) -> String {
+    let (width_px, height_px) = placement.pixels();
+    let rgba_img = img
+        .resize_exact(width_px, height_px, image::imageops::FilterType::Lanczos3)
+        .to_rgba8();
+    let width = rgba_img.width() as usize;
+    let height = rgba_img.height() as usize;
+    let palette = options.palette.colors(&rgba_img);
+    let indexed_pixels = quantize::index(&rgba_img, &palette, options.dither);
 
-    // Start Sixel sequence
     // DCS P1;P2;P3 q - P1=pixel aspect ratio, P2=background mode, P3=horizontal grid
     // "Pan;Pad;Ph;Pv" - aspect ratio numerator/denominator, horizontal/vertical extent
-    print!("\x1bP0;0;0q\"1;1;{};{}", width, height);
+    let mut out = format!("{}\x1bP0;0;0q\"1;1;{};{}", placement.pad(), width, height);
 
-    // Emit Palette
-    #[allow(clippy::needless_range_loop)]
-    for i in 0..216 {
+    // Register only the colors that are used, in percent as sixel expects
+    let mut used_colors = vec![false; palette.len()];
+    for i in indexed_pixels.iter().flatten() {
+        used_colors[*i as usize] = true;
+    }
+    let percent = |v: u8| (v as u32 * 100 + 127) / 255;
+    for (i, [r, g, b]) in palette.iter().enumerate() {
         if used_colors[i] {
-            let r_idx = i / 36;
-            let g_idx = (i % 36) / 6;
-            let b_idx = i % 6;
-
-            // Map 0..5 to 0..100 for Sixel
-            let r = (r_idx * 100 + 2) / 5;
-            let g = (g_idx * 100 + 2) / 5;
-            let b = (b_idx * 100 + 2) / 5;
-
-            print!("#{0};2;{1};{2};{3}", i, r, g, b);
+            out.push_str(&format!(
+                "#{};2;{};{};{}",
+                i,
+                percent(*r),
+                percent(*g),
+                percent(*b)
+            ));
         }
     }
 
-    // Encode bands
+    // Each band is six pixel rows; every color in it is drawn as one pass
+    // of columns, with "$" returning to the band start
     for y in (0..height).step_by(6) {
         let rows_in_band = std::cmp::min(6, height - y);
-
-        #[allow(clippy::needless_range_loop)]
-        for color_idx in 0..216 {
-            if !used_colors[color_idx] {
-                continue;
-            }
-
-            let mut has_pixels_for_color = false;
-            let mut color_cols = vec![0u8; width as usize];
-
-            for row_offset in 0..rows_in_band {
-                let img_y = y + row_offset;
-                for x in 0..width {
-                    let px_idx = (img_y * width + x) as usize;
-                    if indexed_pixels[px_idx] == color_idx as u8 {
-                        color_cols[x as usize] |= 1 << row_offset;
-                        has_pixels_for_color = true;
+        let band = &indexed_pixels[y * width..(y + rows_in_band) * width];
+        let mut band_colors: Vec<u8> = band.iter().flatten().copied().collect();
+        band_colors.sort_unstable();
+        band_colors.dedup();
+
+        for color_idx in band_colors {
+            let mut color_cols = vec![0u8; width];
+            for (offset, row) in band.chunks(width).enumerate() {
+                for (x, pixel) in row.iter().enumerate() {
+                    if *pixel == Some(color_idx) {
+                        color_cols[x] |= 1 << offset;
                     }
                 }
             }
 
-            if has_pixels_for_color {
-                print!("#{}", color_idx);
-
-                let mut x = 0;
-                while x < width as usize {
-                    let val = color_cols[x];
-                    let mut run_len = 1;
-                    while x + run_len < width as usize && color_cols[x + run_len] == val {
-                        run_len += 1;
-                    }
-
-                    let char_val = (val + 63) as char;
-                    if run_len > 1 {
-                        print!("!{}{}", run_len, char_val);
-                    } else {
-                        print!("{}", char_val);
-                    }
-                    x += run_len;
+            out.push_str(&format!("#{}", color_idx));
+            let mut x = 0;
+            while x < width {
+                let val = color_cols[x];
+                let mut run_len = 1;
+                while x + run_len < width && color_cols[x + run_len] == val {
+                    run_len += 1;
+                }
+                let char_val = (val + 63) as char;
+                if run_len > 1 {
+                    out.push_str(&format!("!{}{}", run_len, char_val));
+                } else {
+                    out.push(char_val);
                 }
-                print!("$");
+                x += run_len;
             }
+            out.push('$');
         }
-        print!("-");
+        out.push('-');
     }
 
-    print!("\x1b\\");
-    println!(); // Newline after image
-    io::stdout().flush()?;
-    Ok(())
+    out.push_str("\x1b\\");
+    out
 }
 
-fn render_iterm2(img: &DynamicImage, term_width: usize, _term_height: usize) -> anyhow::Result<()> {
-    let max_width_px = ((term_width - 2) * 10) as u32;
-    let (img_width, img_height) = img.dimensions();
-    let scaled_img = if img_width > max_width_px {
-        let scale = max_width_px as f32 / img_width as f32;
-        let new_height = (img_height as f32 * scale) as u32;
-        img.resize(
-            max_width_px,
-            new_height,
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else {
-        img.clone()
-    };
+fn render_iterm2(img: &DynamicImage, placement: &Placement) -> anyhow::Result<()> {
     let mut png_data = Vec::new();
-    scaled_img.write_to(&mut std::io::Cursor::new(&mut png_data), ImageFormat::Png)?;
+    shrink_to(img, placement)
+        .write_to(&mut std::io::Cursor::new(&mut png_data), ImageFormat::Png)?;
     let base64_data = base64_encode(&png_data);
-    print!("\x1b]1337;File=inline=1:{}\x07", base64_data);
+    print!(
+        "{}\x1b]1337;File=inline=1;width={};height={};preserveAspectRatio=0:{}\x07",
+        placement.pad(),
+        placement.cols,
+        placement.rows,
+        base64_data
+    );
     io::stdout().flush()?;
     Ok(())
 }
 
-fn render_halfblock(
-    img: &DynamicImage,
-    term_width: usize,
-    term_height: usize,
-) -> anyhow::Result<()> {
-    let max_chars_width = term_width - 2;
-    let max_chars_height = (term_height - 4) * 2;
-    let (img_width, img_height) = img.dimensions();
-    let width_scale = max_chars_width as f32 / img_width as f32;
-    let height_scale = max_chars_height as f32 / img_height as f32;
-    let scale = width_scale.min(height_scale);
-    let scaled_width = (img_width as f32 * scale) as u32;
-    let scaled_height = (img_height as f32 * scale) as u32;
-    let resized = img.resize_exact(
-        scaled_width,
-        scaled_height,
+/// Print a frame drawn with text cells
+fn render_cells(frame: String) -> anyhow::Result<()> {
+    print!("{}", frame);
+    io::stdout().flush()?;
+    Ok(())
+}
+
+/// `img` resized to `per_cell` pixels in each cell of the placement
+fn cell_pixels(img: &DynamicImage, placement: &Placement, per_cell: (u32, u32)) -> RgbaImage {
+    img.resize_exact(
+        placement.cols * per_cell.0,
+        placement.rows * per_cell.1,
         image::imageops::FilterType::Lanczos3,
-    );
-    let rgb_img = resized.to_rgb8();
-    let (width, height) = rgb_img.dimensions();
+    )
+    .to_rgba8()
+}
+
+/// With `--dither` on a 256 or 16 color terminal, pixels are dithered to
+/// the colors it can show instead of each being rounded to the nearest.
+fn dither_to_terminal(img: &mut RgbaImage, options: &ImageOptions) {
+    if let Some(palette) = options
+        .dither
+        .then(|| color::palette(color::depth()))
+        .flatten()
+    {
+        quantize::remap(img, &palette, true);
+    }
+}
+
+/// `img` drawn with ▀, two pixels stacked per cell, one line per row
+fn halfblock_frame(img: &DynamicImage, placement: &Placement, options: &ImageOptions) -> String {
+    let mut rgba_img = cell_pixels(img, placement, (1, 2));
+    dither_to_terminal(&mut rgba_img, options);
+    let (width, height) = rgba_img.dimensions();
+    let mut out = String::new();
     for y in (0..height).step_by(2) {
+        out.push_str(&placement.pad());
         for x in 0..width {
-            let top_pixel = rgb_img.get_pixel(x, y);
+            let top_pixel = rgba_img.get_pixel(x, y);
             let bottom_pixel = if y + 1 < height {
-                rgb_img.get_pixel(x, y + 1)
+                rgba_img.get_pixel(x, y + 1)
             } else {
                 top_pixel
             };
-            print!(
+            out.push_str(&format!(
                 "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
                 top_pixel[0],
                 top_pixel[1],
@@ -302,12 +505,149 @@ fn render_halfblock(
                 bottom_pixel[0],
                 bottom_pixel[1],
                 bottom_pixel[2]
-            );
+            ));
         }
-        println!("\x1b[0m");
+        out.push_str("\x1b[0m\n");
     }
-    io::stdout().flush()?;
-    Ok(())
+    out
+}
+
+/// Quadrant glyphs by which of the four pixels take the foreground:
+/// bit 0 top left, 1 top right, 2 bottom left, 3 bottom right
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Split four pixels into a foreground and background color
+///
+/// Every way of dividing the quadrants in two is tried, each side taking
+/// its average color, and the one closest to the pixels wins.
+fn split_quadrants(pixels: [[u8; 3]; 4]) -> (usize, [u8; 3], [u8; 3]) {
+    let mean = |mask: usize, on: bool| {
+        let chosen: Vec<&[u8; 3]> = (0..4)
+            .filter(|i| (mask >> i & 1 == 1) == on)
+            .map(|i| &pixels[i])
+            .collect();
+        let n = chosen.len().max(1) as u32;
+        let channel = |c: usize| (chosen.iter().map(|p| p[c] as u32).sum::<u32>() / n) as u8;
+        [channel(0), channel(1), channel(2)]
+    };
+    let distance = |a: &[u8; 3], b: &[u8; 3]| -> u32 {
+        (0..3)
+            .map(|c| (a[c] as i32 - b[c] as i32).pow(2) as u32)
+            .sum()
+    };
+    // Masks with the top left pixel in the foreground cover every split;
+    // a flat cell ties on all of them and is drawn as █
+    (1..16)
+        .step_by(2)
+        .rev()
+        .map(|mask| {
+            let (fg, bg) = (mean(mask, true), mean(mask, false));
+            let error: u32 = (0..4)
+                .map(|i| distance(&pixels[i], if mask >> i & 1 == 1 { &fg } else { &bg }))
+                .sum();
+            (error, mask, fg, bg)
+        })
+        .min_by_key(|(error, ..)| *error)
+        .map(|(_, mask, fg, bg)| (mask, fg, bg))
+        .unwrap_or((15, pixels[0], pixels[0]))
+}
+
+/// `img` drawn with quadrant blocks, 2x2 pixels per cell in two colors
+fn quadblock_frame(img: &DynamicImage, placement: &Placement, options: &ImageOptions) -> String {
+    let mut rgba_img = cell_pixels(img, placement, (2, 2));
+    dither_to_terminal(&mut rgba_img, options);
+    let rgb = |x: u32, y: u32| {
+        let p = rgba_img.get_pixel(x, y);
+        [p[0], p[1], p[2]]
+    };
+    let mut out = String::new();
+    for row in 0..placement.rows {
+        out.push_str(&placement.pad());
+        for col in 0..placement.cols {
+            let (x, y) = (col * 2, row * 2);
+            let (mask, fg, bg) =
+                split_quadrants([rgb(x, y), rgb(x + 1, y), rgb(x, y + 1), rgb(x + 1, y + 1)]);
+            out.push_str(&format!(
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m{}",
+                fg[0], fg[1], fg[2], bg[0], bg[1], bg[2], QUADRANTS[mask]
+            ));
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Braille dot bits by pixel position within the 2x4 cell, column first
+const BRAILLE_DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// `img` drawn with Braille dots, 2x4 pixels per cell, no color
+///
+/// Pixels brighter than the image's average luminance become dots, which
+/// suits the usual dark terminal background. With `--dither` the dots are
+/// spread by brightness instead, so shading survives. Transparent pixels
+/// stay empty.
+fn braille_frame(img: &DynamicImage, placement: &Placement, options: &ImageOptions) -> String {
+    let rgba_img = cell_pixels(img, placement, (2, 4));
+    let (width, height) = rgba_img.dimensions();
+    let mut luma: Vec<f32> = rgba_img
+        .pixels()
+        .map(|p| 0.299 * p[0] as f32 + 0.587 * p[1] as f32 + 0.114 * p[2] as f32)
+        .collect();
+    let opaque = |i: usize| rgba_img.as_raw()[i * 4 + 3] >= 128;
+    let visible: Vec<f32> = (0..luma.len())
+        .filter(|&i| opaque(i))
+        .map(|i| luma[i])
+        .collect();
+    // Dithering diffuses error against full black and white, so it needs
+    // the midpoint; plain thresholding adapts to the image's brightness
+    let threshold = if options.dither {
+        127.5
+    } else {
+        visible.iter().sum::<f32>() / visible.len().max(1) as f32
+    };
+
+    let mut on = vec![false; luma.len()];
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            let i = y * width as usize + x;
+            on[i] = opaque(i) && luma[i] > threshold;
+            if !options.dither {
+                continue;
+            }
+            // Floyd–Steinberg: push the rounding error to unvisited pixels
+            let error = luma[i] - if on[i] { 255.0 } else { 0.0 };
+            let mut spread = |dx: isize, dy: usize, weight: f32| {
+                let nx = x as isize + dx;
+                if nx >= 0 && (nx as u32) < width && ((y + dy) as u32) < height {
+                    luma[(y + dy) * width as usize + nx as usize] += error * weight;
+                }
+            };
+            spread(1, 0, 7.0 / 16.0);
+            spread(-1, 1, 3.0 / 16.0);
+            spread(0, 1, 5.0 / 16.0);
+            spread(1, 1, 1.0 / 16.0);
+        }
+    }
+
+    let mut out = String::new();
+    for row in 0..placement.rows as usize {
+        out.push_str(&placement.pad());
+        for col in 0..placement.cols as usize {
+            let mut bits = 0u8;
+            for (dx, column) in BRAILLE_DOTS.iter().enumerate() {
+                for (dy, bit) in column.iter().enumerate() {
+                    if on[(row * 4 + dy) * width as usize + col * 2 + dx] {
+                        bits |= bit;
+                    }
+                }
+            }
+            out.push(char::from_u32(0x2800 + bits as u32).unwrap_or(' '));
+        }
+        out.push('\n');
+    }
+    out
 }
 
 fn base64_encode(data: &[u8]) -> String {
@@ -351,6 +691,82 @@ mod tests {
         assert!(parse_protocol("invalid").is_err());
     }
 
+    fn place(width: Option<&str>, height: Option<&str>, preserve_aspect: bool) -> Placement {
+        let options = ImageOptions {
+            width: width.and_then(Dimension::parse),
+            height: height.and_then(Dimension::parse),
+            preserve_aspect,
+            ..Default::default()
+        };
+        // 160x160 px is 20x10 cells
+        Placement::new((160, 160), (80, 24), &options)
+    }
+
+    #[test]
+    fn test_dimension_parse() {
+        assert_eq!(Dimension::parse("40"), Some(Dimension::Cells(40)));
+        assert_eq!(Dimension::parse("50%"), Some(Dimension::Percent(50.0)));
+        assert_eq!(Dimension::parse("0"), None);
+        assert_eq!(Dimension::parse("150%"), None);
+        assert_eq!(Dimension::parse("wide"), None);
+    }
+
+    #[test]
+    fn test_placement_sizes() {
+        // Natural size fits, so it is kept
+        assert_eq!(
+            (place(None, None, false).cols, place(None, None, false).rows),
+            (20, 10)
+        );
+        // One dimension: the other follows the aspect ratio
+        let p = place(Some("40"), None, false);
+        assert_eq!((p.cols, p.rows), (40, 20));
+        let p = place(None, Some("5"), false);
+        assert_eq!((p.cols, p.rows), (10, 5));
+        // Percent of the terminal
+        let p = place(Some("50%"), None, false);
+        assert_eq!((p.cols, p.rows), (40, 20));
+        // Both: stretch, or fit inside with --preserve-aspect
+        let p = place(Some("30"), Some("5"), false);
+        assert_eq!((p.cols, p.rows), (30, 5));
+        let p = place(Some("30"), Some("5"), true);
+        assert_eq!((p.cols, p.rows), (10, 5));
+        // Never wider than the terminal
+        let p = place(Some("200"), None, false);
+        assert_eq!((p.cols, p.rows), (80, 40));
+    }
+
+    #[test]
+    fn test_placement_shrinks_large_images_to_terminal() {
+        let p = Placement::new((1600, 1600), (80, 24), &ImageOptions::default());
+        assert_eq!((p.cols, p.rows), (40, 20));
+    }
+
+    #[test]
+    fn test_placement_alignment() {
+        let mut options = ImageOptions {
+            width: Some(Dimension::Cells(20)),
+            ..Default::default()
+        };
+        assert_eq!(Placement::new((160, 160), (80, 24), &options).indent, 0);
+        options.align = ImageAlign::Center;
+        assert_eq!(Placement::new((160, 160), (80, 24), &options).indent, 30);
+        options.align = ImageAlign::Right;
+        assert_eq!(Placement::new((160, 160), (80, 24), &options).indent, 60);
+    }
+
+    #[test]
+    fn test_kitty_command_chunks_payload() {
+        let small = kitty_command("a=T", b"hi");
+        assert_eq!(small, "\x1b_Ga=T,m=0;aGk=\x1b\\");
+
+        let big = kitty_command("a=T", &[0u8; 6000]);
+        let parts: Vec<&str> = big.split("\x1b\\").filter(|p| !p.is_empty()).collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("\x1b_Ga=T,m=1;"));
+        assert!(parts[1].starts_with("\x1b_Gm=0;"));
+    }
+
     #[test]
     fn test_base64_encode() {
         let data = b"hello";
@@ -366,7 +782,12 @@ mod tests {
         }));
 
         // Capture stdout to verify Sixel output
-        let result = render_sixel(&img, 80, 24);
+        let options = ImageOptions::default();
+        let result = render_sixel(
+            &img,
+            &Placement::new(img.dimensions(), (80, 24), &options),
+            &options,
+        );
         assert!(result.is_ok(), "render_sixel should not return error");
 
         // The function should complete without calling halfblock fallback
@@ -386,7 +807,140 @@ mod tests {
 
         // We can't easily capture stdout in a unit test, but we can verify
         // the function completes successfully without panic
-        let result = render_sixel(&img, 80, 24);
+        let options = ImageOptions::default();
+        let result = render_sixel(
+            &img,
+            &Placement::new(img.dimensions(), (80, 24), &options),
+            &options,
+        );
         assert!(result.is_ok(), "Sixel rendering should succeed");
     }
+
+    /// Color registers defined in a sixel sequence
+    fn registers(sixel: &str) -> usize {
+        sixel.matches(";2;").count()
+    }
+
+    #[test]
+    fn test_sixel_palettes() {
+        // A horizontal gray ramp, 8x16 px so it maps to one cell
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(8, 16, |x, _| {
+            let v = (x * 32) as u8;
+            image::Rgba([v, v, v, 255])
+        }));
+        let mut options = ImageOptions::default();
+        let placement = Placement::new(img.dimensions(), (80, 24), &options);
+
+        // Adaptive keeps the exact grays; web rounds them to steps of 51
+        let adaptive = sixel(&img, &placement, &options);
+        assert!(adaptive.starts_with("\x1bP0;0;0q\"1;1;8;16"));
+        assert!(adaptive.ends_with("\x1b\\"));
+        assert!(registers(&adaptive) >= 8);
+        options.palette = quantize::Palette::Web;
+        assert!(registers(&sixel(&img, &placement, &options)) <= 6);
+
+        // A red image in gray uses gray registers only
+        let red = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            8,
+            16,
+            image::Rgba([255, 0, 0, 255]),
+        ));
+        options.palette = quantize::Palette::Gray;
+        let gray = sixel(&red, &placement, &options);
+        assert_eq!(registers(&gray), 1);
+        assert!(!gray.contains(";2;100;0;0"));
+    }
+
+    #[test]
+    fn test_sixel_skips_transparent_pixels() {
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::new(8, 16));
+        let options = ImageOptions::default();
+        let placement = Placement::new(img.dimensions(), (80, 24), &options);
+        assert_eq!(registers(&sixel(&img, &placement, &options)), 0);
+    }
+
+    #[test]
+    fn test_parse_cell_protocols() {
+        assert_eq!(parse_protocol("quadblock").unwrap(), Protocol::Quadblock);
+        assert_eq!(parse_protocol("Braille").unwrap(), Protocol::Braille);
+    }
+
+    #[test]
+    fn test_split_quadrants() {
+        let (w, k) = ([255, 255, 255], [0, 0, 0]);
+        // White on the left, black on the right: ▌ in white over black
+        assert_eq!(split_quadrants([w, k, w, k]), (5, w, k));
+        // Diagonal
+        assert_eq!(split_quadrants([w, k, k, w]), (9, w, k));
+        // One odd corner
+        assert_eq!(split_quadrants([k, k, k, w]), (7, k, w));
+        // A flat cell is a full block
+        assert_eq!(split_quadrants([w; 4]).0, 15);
+    }
+
+    #[test]
+    fn test_quadblock_frame() {
+        // Left half white, right half black, 2x2 pixels per cell
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(4, 2, |x, _| {
+            if x < 2 {
+                image::Rgba([255, 255, 255, 255])
+            } else {
+                image::Rgba([0, 0, 0, 255])
+            }
+        }));
+        let placement = Placement {
+            cols: 2,
+            rows: 1,
+            indent: 0,
+        };
+        let frame = quadblock_frame(&img, &placement, &ImageOptions::default());
+        assert_eq!(frame.matches('█').count(), 2);
+        assert!(frame.contains("38;2;255;255;255"));
+        assert!(frame.ends_with("\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_braille_frame() {
+        // Bright left column of dots on a dark cell
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(2, 4, |x, _| {
+            if x == 0 {
+                image::Rgba([255, 255, 255, 255])
+            } else {
+                image::Rgba([0, 0, 0, 255])
+            }
+        }));
+        let placement = Placement {
+            cols: 1,
+            rows: 1,
+            indent: 2,
+        };
+        let options = ImageOptions::default();
+        assert_eq!(braille_frame(&img, &placement, &options), "  ⡇\n");
+
+        // Transparent pixels never become dots
+        let clear = DynamicImage::ImageRgba8(image::RgbaImage::new(2, 4));
+        assert_eq!(braille_frame(&clear, &placement, &options), "  ⠀\n");
+
+        // Dithered mid gray lights about half the dots
+        let gray = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            8,
+            8,
+            image::Rgba([128, 128, 128, 255]),
+        ));
+        let placement = Placement {
+            cols: 4,
+            rows: 2,
+            indent: 0,
+        };
+        let options = ImageOptions {
+            dither: true,
+            ..ImageOptions::default()
+        };
+        let dots: u32 = braille_frame(&gray, &placement, &options)
+            .chars()
+            .filter(|c| ('\u{2800}'..='\u{28ff}').contains(c))
+            .map(|c| (c as u32 - 0x2800).count_ones())
+            .sum();
+        assert!((24..=40).contains(&dots), "{} dots", dots);
+    }
 }
@@ -0,0 +1,184 @@
+//! QR codes
+//!
+//! Drawn with half blocks like barcodes, or on kitty as an exact
+//! black-on-white image scaled to the same number of cells.
+
+use super::{detect_protocol, kitty_command, png_bytes, symbol, Protocol};
+use image::{DynamicImage, GrayImage, Luma};
+use qrcode::{Color, EcLevel, QrCode};
+use std::io::{self, IsTerminal, Write};
+
+/// Light modules around the code that scanners need to find its edges
+const QUIET_ZONE: usize = 4;
+/// Pixels per module in the kitty image, before the terminal scales it
+const KITTY_MODULE_PX: u32 = 8;
+
+pub const LEVELS: [&str; 4] = ["L", "M", "Q", "H"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QrOptions {
+    /// Cells per module
+    pub size: u32,
+    pub level: EcLevel,
+    /// Draw dark modules as blocks, for light terminal backgrounds
+    pub invert: bool,
+}
+
+/// Parse an error correction level: L, M, Q, H, or low/medium/quartile/high
+pub fn parse_level(s: &str) -> Option<EcLevel> {
+    match s.to_lowercase().as_str() {
+        "l" | "low" => Some(EcLevel::L),
+        "m" | "medium" => Some(EcLevel::M),
+        "q" | "quartile" => Some(EcLevel::Q),
+        "h" | "high" => Some(EcLevel::H),
+        _ => None,
+    }
+}
+
+/// Render `data` as a QR code; `protocol_arg` is auto, kitty or halfblock
+pub fn render(data: &str, protocol_arg: &str, options: &QrOptions) {
+    if let Err(e) = try_render(data, protocol_arg, options) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn try_render(data: &str, protocol_arg: &str, options: &QrOptions) -> anyhow::Result<()> {
+    let kitty = match protocol_arg.to_lowercase().as_str() {
+        "auto" => io::stdout().is_terminal() && detect_protocol() == Protocol::Kitty,
+        "kitty" => true,
+        "halfblock" => false,
+        _ => anyhow::bail!(
+            "Invalid protocol: {}. Valid options: auto, kitty, halfblock",
+            protocol_arg
+        ),
+    };
+    if options.size == 0 {
+        anyhow::bail!("--size must be at least 1");
+    }
+    let grid = modules(data, options.level)?;
+    let out = if kitty {
+        kitty_frame(&grid, options.size)?
+    } else {
+        halfblock_frame(&grid, options)
+    };
+    print!("{}", out);
+    io::stdout().flush()?;
+    Ok(())
+}
+
+/// Dark modules of the code for `data`, quiet zone included
+fn modules(data: &str, level: EcLevel) -> anyhow::Result<Vec<Vec<bool>>> {
+    let code = QrCode::with_error_correction_level(data, level).map_err(|e| match e {
+        qrcode::types::QrError::DataTooLong => anyhow::anyhow!(
+            "Text too long for a QR code ({} bytes); a lower --error-correction fits more",
+            data.len()
+        ),
+        e => anyhow::anyhow!("Cannot encode QR code: {}", e),
+    })?;
+    let grid: Vec<Vec<bool>> = code
+        .to_colors()
+        .chunks(code.width())
+        .map(|row| row.iter().map(|color| *color == Color::Dark).collect())
+        .collect();
+    Ok(symbol::with_quiet_zone(&grid, QUIET_ZONE, QUIET_ZONE))
+}
+
+/// Block characters for `grid`, each module `size` cells wide
+fn halfblock_frame(grid: &[Vec<bool>], options: &QrOptions) -> String {
+    let size = options.size as usize;
+    symbol::halfblocks(&symbol::scale(grid, size, size), options.invert)
+}
+
+/// Kitty graphics command showing `grid` at `size` cells per module
+fn kitty_frame(grid: &[Vec<bool>], size: u32) -> anyhow::Result<String> {
+    let side = grid.len() as u32;
+    let img = GrayImage::from_fn(side * KITTY_MODULE_PX, side * KITTY_MODULE_PX, |x, y| {
+        let dark = grid[(y / KITTY_MODULE_PX) as usize][(x / KITTY_MODULE_PX) as usize];
+        Luma([if dark { 0 } else { 255 }])
+    });
+    let png = png_bytes(&DynamicImage::ImageLuma8(img))?;
+    let cols = side * size;
+    let rows = cols.div_ceil(2);
+    let control = format!("f=100,a=T,c={},r={}", cols, rows);
+    Ok(format!("{}\n", kitty_command(&control, &png)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(size: u32, invert: bool) -> QrOptions {
+        QrOptions {
+            size,
+            level: EcLevel::M,
+            invert,
+        }
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("h"), Some(EcLevel::H));
+        assert_eq!(parse_level("Quartile"), Some(EcLevel::Q));
+        assert_eq!(parse_level("x"), None);
+        for level in LEVELS {
+            assert!(parse_level(level).is_some());
+        }
+    }
+
+    #[test]
+    fn test_modules_have_quiet_zone_and_finder() {
+        let grid = modules("https://example.com", EcLevel::M).unwrap();
+        // Version 2 is 25 modules wide
+        assert_eq!(grid.len(), 25 + 2 * QUIET_ZONE);
+        assert!(grid[..QUIET_ZONE].iter().flatten().all(|dark| !dark));
+        // Top-left finder pattern: dark ring around a light ring
+        let row = &grid[QUIET_ZONE][QUIET_ZONE..QUIET_ZONE + 7];
+        assert!(row.iter().all(|dark| *dark));
+        assert!(!grid[QUIET_ZONE + 1][QUIET_ZONE + 1]);
+    }
+
+    #[test]
+    fn test_higher_level_needs_bigger_code() {
+        let low = modules("https://example.com/some/path", EcLevel::L).unwrap();
+        let high = modules("https://example.com/some/path", EcLevel::H).unwrap();
+        assert!(high.len() > low.len());
+    }
+
+    #[test]
+    fn test_data_too_long() {
+        let err = modules(&"x".repeat(5000), EcLevel::H).unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[test]
+    fn test_halfblock_frame_shape() {
+        let grid = modules("hi", EcLevel::L).unwrap();
+        let side = grid.len();
+        let frame = halfblock_frame(&grid, &options(1, false));
+        let lines: Vec<&str> = frame.lines().collect();
+        assert_eq!(lines.len(), side.div_ceil(2));
+        assert!(lines.iter().all(|l| l.chars().count() == side));
+        // The quiet zone is light, so lit on a dark terminal
+        assert!(lines[0].chars().all(|c| c == '█'));
+
+        let inverted = halfblock_frame(&grid, &options(1, true));
+        assert!(inverted.lines().next().unwrap().chars().all(|c| c == ' '));
+
+        let double = halfblock_frame(&grid, &options(2, false));
+        assert_eq!(double.lines().count(), side);
+        assert_eq!(double.lines().next().unwrap().chars().count(), side * 2);
+    }
+
+    #[test]
+    fn test_kitty_frame_sizes_in_cells() {
+        let grid = modules("hi", EcLevel::L).unwrap();
+        let out = kitty_frame(&grid, 1).unwrap();
+        let side = grid.len();
+        assert!(out.starts_with(&format!(
+            "\x1b_Gf=100,a=T,c={},r={},",
+            side,
+            side.div_ceil(2)
+        )));
+    }
+}
@@ -0,0 +1,282 @@
+//! Color reduction for sixel and halfblock output
+//!
+//! Sixel images are drawn from at most 256 color registers, filled from an
+//! adaptive median-cut palette, the 216 web-safe colors or a gray ramp.
+//! Floyd–Steinberg dithering spreads each pixel's rounding error over its
+//! neighbours so gradients don't collapse into bands.
+
+use image::RgbaImage;
+use std::collections::HashMap;
+
+pub const PALETTES: [&str; 3] = ["adaptive", "web", "gray"];
+
+/// Color registers sixel terminals commonly provide
+pub const MAX_COLORS: usize = 256;
+
+/// Pixels with less alpha than this are left transparent
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Palette used for sixel output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    /// Median cut over the image's own colors
+    #[default]
+    Adaptive,
+    /// The 6×6×6 web-safe cube
+    Web,
+    /// 256 shades of gray
+    Gray,
+}
+
+impl Palette {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "adaptive" => Some(Palette::Adaptive),
+            "web" | "websafe" | "web-safe" => Some(Palette::Web),
+            "gray" | "grey" | "grayscale" => Some(Palette::Gray),
+            _ => None,
+        }
+    }
+
+    /// The palette's colors for `img`
+    pub fn colors(self, img: &RgbaImage) -> Vec<[u8; 3]> {
+        match self {
+            Palette::Adaptive => {
+                let pixels: Vec<[u8; 3]> = img
+                    .pixels()
+                    .filter(|p| p[3] >= ALPHA_THRESHOLD)
+                    .map(|p| [p[0], p[1], p[2]])
+                    .collect();
+                median_cut(&pixels, MAX_COLORS)
+            }
+            Palette::Web => web(),
+            Palette::Gray => (0..=255).map(|v| [v, v, v]).collect(),
+        }
+    }
+}
+
+/// The 216 web-safe colors, channels in steps of 51
+pub fn web() -> Vec<[u8; 3]> {
+    let mut colors = Vec::with_capacity(216);
+    for r in 0..6u8 {
+        for g in 0..6u8 {
+            for b in 0..6u8 {
+                colors.push([r * 51, g * 51, b * 51]);
+            }
+        }
+    }
+    colors
+}
+
+/// A box of distinct colors with their pixel counts
+struct ColorBox {
+    colors: Vec<([u8; 3], u32)>,
+}
+
+impl ColorBox {
+    /// The widest channel and its range
+    fn widest(&self) -> (usize, u8) {
+        (0..3)
+            .map(|c| {
+                let min = self.colors.iter().map(|(rgb, _)| rgb[c]).min().unwrap_or(0);
+                let max = self.colors.iter().map(|(rgb, _)| rgb[c]).max().unwrap_or(0);
+                (c, max - min)
+            })
+            .max_by_key(|&(_, range)| range)
+            .unwrap_or((0, 0))
+    }
+
+    /// Count-weighted average color
+    fn average(&self) -> [u8; 3] {
+        let total: u64 = self.colors.iter().map(|&(_, n)| n as u64).sum();
+        let mut sum = [0u64; 3];
+        for (rgb, n) in &self.colors {
+            for c in 0..3 {
+                sum[c] += rgb[c] as u64 * *n as u64;
+            }
+        }
+        sum.map(|s| ((s + total / 2) / total.max(1)) as u8)
+    }
+}
+
+/// Up to `max` colors representing `pixels`, by median cut
+///
+/// The box with the widest channel range is repeatedly split at the pixel
+/// median along that channel; each final box contributes its average.
+pub fn median_cut(pixels: &[[u8; 3]], max: usize) -> Vec<[u8; 3]> {
+    let mut counts: HashMap<[u8; 3], u32> = HashMap::new();
+    for rgb in pixels {
+        *counts.entry(*rgb).or_default() += 1;
+    }
+    if counts.len() <= max {
+        let mut colors: Vec<[u8; 3]> = counts.into_keys().collect();
+        colors.sort_unstable();
+        return colors;
+    }
+
+    let mut boxes = vec![ColorBox {
+        colors: counts.into_iter().collect(),
+    }];
+    while boxes.len() < max {
+        let Some((i, channel)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.colors.len() > 1)
+            .map(|(i, b)| (i, b.widest()))
+            .max_by_key(|&(_, (_, range))| range)
+            .map(|(i, (channel, _))| (i, channel))
+        else {
+            break;
+        };
+        let mut colors = std::mem::take(&mut boxes[i].colors);
+        colors.sort_unstable_by_key(|(rgb, _)| rgb[channel]);
+        let half: u64 = colors.iter().map(|&(_, n)| n as u64).sum::<u64>() / 2;
+        let mut seen = 0u64;
+        let mut split = colors.len() - 1;
+        for (j, (_, n)) in colors.iter().enumerate() {
+            seen += *n as u64;
+            if seen >= half {
+                split = j + 1;
+                break;
+            }
+        }
+        let upper = colors.split_off(split.clamp(1, colors.len() - 1));
+        boxes[i].colors = colors;
+        boxes.push(ColorBox { colors: upper });
+    }
+    boxes.iter().map(ColorBox::average).collect()
+}
+
+fn distance(a: [u8; 3], b: [i32; 3]) -> i32 {
+    (0..3).map(|c| (a[c] as i32 - b[c]).pow(2)).sum()
+}
+
+/// Palette index for every pixel of `img`, row by row; `None` where the
+/// pixel is transparent
+///
+/// With `dither`, Floyd–Steinberg error diffusion is applied: 7/16 of the
+/// error goes right, 3/16 down-left, 5/16 down and 1/16 down-right.
+pub fn index(img: &RgbaImage, palette: &[[u8; 3]], dither: bool) -> Vec<Option<u8>> {
+    let (width, height) = (img.width() as usize, img.height() as usize);
+    let mut out = Vec::with_capacity(width * height);
+    if palette.is_empty() {
+        out.resize(width * height, None);
+        return out;
+    }
+    let mut nearest_cache: HashMap<[i32; 3], u8> = HashMap::new();
+    let mut nearest = |target: [i32; 3]| -> u8 {
+        *nearest_cache.entry(target).or_insert_with(|| {
+            (0..palette.len())
+                .min_by_key(|&i| distance(palette[i], target))
+                .unwrap_or(0) as u8
+        })
+    };
+
+    // Error carried into this row and the next, with a cell of slack on
+    // each side so the edges need no special cases
+    let mut current = vec![[0i32; 3]; width + 2];
+    let mut next = vec![[0i32; 3]; width + 2];
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = img.get_pixel(x as u32, y as u32);
+            if pixel[3] < ALPHA_THRESHOLD {
+                out.push(None);
+                continue;
+            }
+            let carried = current[x + 1];
+            let target: [i32; 3] = std::array::from_fn(|c| {
+                (pixel[c] as i32 + if dither { carried[c] / 16 } else { 0 }).clamp(0, 255)
+            });
+            let i = nearest(target);
+            out.push(Some(i));
+            if dither {
+                let chosen = palette[i as usize];
+                for c in 0..3 {
+                    let error = target[c] - chosen[c] as i32;
+                    current[x + 2][c] += error * 7;
+                    next[x][c] += error * 3;
+                    next[x + 1][c] += error * 5;
+                    next[x + 2][c] += error;
+                }
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.iter_mut().for_each(|e| *e = [0; 3]);
+    }
+    out
+}
+
+/// `img` with every opaque pixel replaced by its palette color
+pub fn remap(img: &mut RgbaImage, palette: &[[u8; 3]], dither: bool) {
+    let indices = index(img, palette, dither);
+    for (pixel, i) in img.pixels_mut().zip(indices) {
+        if let Some(i) = i {
+            let [r, g, b] = palette[i as usize];
+            pixel.0 = [r, g, b, pixel[3]];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_palette_from_name() {
+        assert_eq!(Palette::from_name("adaptive"), Some(Palette::Adaptive));
+        assert_eq!(Palette::from_name("Web"), Some(Palette::Web));
+        assert_eq!(Palette::from_name("grey"), Some(Palette::Gray));
+        assert_eq!(Palette::from_name("rainbow"), None);
+    }
+
+    #[test]
+    fn test_fixed_palettes() {
+        let web = web();
+        assert_eq!(web.len(), 216);
+        assert_eq!(web[0], [0, 0, 0]);
+        assert_eq!(web[215], [255, 255, 255]);
+        let img = RgbaImage::new(1, 1);
+        assert_eq!(Palette::Gray.colors(&img).len(), MAX_COLORS);
+    }
+
+    #[test]
+    fn test_median_cut_keeps_few_colors_exact() {
+        let pixels = [[255, 0, 0], [0, 0, 255], [255, 0, 0]];
+        assert_eq!(median_cut(&pixels, 16), [[0, 0, 255], [255, 0, 0]]);
+    }
+
+    #[test]
+    fn test_median_cut_limits_and_spans_colors() {
+        // A full red ramp reduced to 4 colors spread across the range
+        let pixels: Vec<[u8; 3]> = (0..=255).map(|r| [r, 0, 0]).collect();
+        let mut colors = median_cut(&pixels, 4);
+        colors.sort_unstable();
+        assert_eq!(colors.len(), 4);
+        assert!(colors[0][0] < 64 && colors[3][0] > 192);
+        assert!(colors.iter().all(|c| c[1] == 0 && c[2] == 0));
+    }
+
+    #[test]
+    fn test_index_nearest_without_dither() {
+        let img = RgbaImage::from_pixel(4, 1, Rgba([100, 100, 100, 255]));
+        let indices = index(&img, &[[0, 0, 0], [255, 255, 255]], false);
+        assert_eq!(indices, vec![Some(0); 4]);
+    }
+
+    #[test]
+    fn test_dither_mixes_colors_to_match_average() {
+        // Mid gray on a black/white palette becomes a roughly even pattern
+        let img = RgbaImage::from_pixel(16, 16, Rgba([128, 128, 128, 255]));
+        let indices = index(&img, &[[0, 0, 0], [255, 255, 255]], true);
+        let white = indices.iter().filter(|i| **i == Some(1)).count();
+        assert!((100..=156).contains(&white), "{} white of 256", white);
+    }
+
+    #[test]
+    fn test_transparent_pixels_have_no_index() {
+        let mut img = RgbaImage::from_pixel(2, 1, Rgba([255, 255, 255, 255]));
+        img.put_pixel(0, 0, Rgba([255, 255, 255, 0]));
+        assert_eq!(index(&img, &[[255, 255, 255]], true), [None, Some(0)]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Drawing shared by QR codes and barcodes
+//!
+//! A symbol is a grid of modules, `true` where dark. It gets a light quiet
+//! zone, is scaled to whole pixels (one column by half a row), and drawn
+//! with ▀▄█ blocks. Lit pixels are the light modules, which reads right on
+//! dark terminals; `invert` lights the dark modules for light backgrounds.
+
+/// `grid` with `x` light modules added left and right and `y` above and below
+pub(super) fn with_quiet_zone(grid: &[Vec<bool>], x: usize, y: usize) -> Vec<Vec<bool>> {
+    let width = grid.first().map_or(0, Vec::len) + 2 * x;
+    let blank = vec![false; width];
+    let mut out = vec![blank.clone(); y];
+    for row in grid {
+        let mut line = vec![false; x];
+        line.extend(row);
+        line.resize(width, false);
+        out.push(line);
+    }
+    out.extend(std::iter::repeat_n(blank, y));
+    out
+}
+
+/// Each module repeated `sx` pixels across and `sy` down
+pub(super) fn scale(grid: &[Vec<bool>], sx: usize, sy: usize) -> Vec<Vec<bool>> {
+    grid.iter()
+        .flat_map(|row| {
+            let line: Vec<bool> = row
+                .iter()
+                .flat_map(|dark| std::iter::repeat_n(*dark, sx))
+                .collect();
+            std::iter::repeat_n(line, sy)
+        })
+        .collect()
+}
+
+/// Two pixel rows per line of block characters
+pub(super) fn halfblocks(pixels: &[Vec<bool>], invert: bool) -> String {
+    let lit = |dark: bool| dark == invert;
+    let mut out = String::new();
+    for pair in pixels.chunks(2) {
+        for (i, top) in pair[0].iter().enumerate() {
+            // An odd last row continues the quiet zone below
+            let bottom = pair.get(1).is_some_and(|row| row[i]);
+            out.push(match (lit(*top), lit(bottom)) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_zone_and_scale() {
+        let grid = with_quiet_zone(&[vec![true, false]], 1, 2);
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid[2], vec![false, true, false, false]);
+        assert!(grid[0].iter().all(|dark| !dark));
+
+        let pixels = scale(&[vec![true, false]], 2, 3);
+        assert_eq!(pixels.len(), 3);
+        assert_eq!(pixels[0], vec![true, true, false, false]);
+    }
+
+    #[test]
+    fn test_halfblocks() {
+        let pixels = vec![vec![true, false, false], vec![true, true, false]];
+        assert_eq!(halfblocks(&pixels, false), " ▀█\n");
+        assert_eq!(halfblocks(&pixels, true), "█▄ \n");
+        // A lone last row pairs with light quiet zone
+        assert_eq!(halfblocks(&[vec![true, false]], false), "▄█\n");
+    }
+}
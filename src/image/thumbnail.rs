@@ -0,0 +1,205 @@
+//! Small image previews for other components
+//!
+//! Kitty and iTerm2 get the image itself, scaled by the terminal into the
+//! cells; everywhere else it is drawn with half blocks. Either way the
+//! thumbnail covers exactly `cols` x `rows` cells and leaves the cursor where
+//! callers expect it, so it can sit inside a table row or a preview pane.
+
+use super::{
+    base64_encode, cell_pixels, decode_image, halfblock_frame, kitty_command, load_bytes,
+    png_bytes, protocol, shrink_to, ImageOptions, Placement, Protocol, CELL_HEIGHT_PX,
+    CELL_WIDTH_PX,
+};
+use image::{DynamicImage, GenericImageView, ImageFormat};
+use std::path::Path;
+
+/// An image scaled to fit a box of cells
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    placement: Placement,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Kitty(Vec<u8>),
+    ITerm2(Vec<u8>),
+    Cells(Vec<String>),
+}
+
+/// Whether `path` names a file the image module can decode, by extension
+pub fn is_image(path: &Path) -> bool {
+    ImageFormat::from_path(path).is_ok()
+}
+
+impl Thumbnail {
+    /// `path` scaled to fit in `max_cols` x `max_rows`, keeping its shape
+    pub fn load(path: &str, max_cols: u32, max_rows: u32) -> anyhow::Result<Self> {
+        let bytes = load_bytes(path)?;
+        let img = decode_image(path, &bytes)?;
+        Ok(Self::new(&img, protocol("auto")?, max_cols, max_rows))
+    }
+
+    fn new(img: &DynamicImage, protocol: Protocol, max_cols: u32, max_rows: u32) -> Self {
+        let placement = fit(img.dimensions(), max_cols, max_rows);
+        let kind = match protocol {
+            Protocol::Kitty => png_bytes(&shrink_to(img, &placement)).map(Kind::Kitty),
+            Protocol::ITerm2 => png_bytes(&shrink_to(img, &placement)).map(Kind::ITerm2),
+            _ => Err(anyhow::anyhow!("drawn with cells")),
+        }
+        .unwrap_or_else(|_| {
+            let frame = halfblock_frame(img, &placement, &ImageOptions::default());
+            Kind::Cells(frame.lines().map(str::to_string).collect())
+        });
+        Thumbnail { placement, kind }
+    }
+
+    /// Width and height in cells
+    pub fn size(&self) -> (u32, u32) {
+        (self.placement.cols, self.placement.rows)
+    }
+
+    /// The first row, for inline use: the cursor ends `cols` cells right
+    pub fn line(&self) -> String {
+        match &self.kind {
+            Kind::Cells(lines) => lines.first().cloned().unwrap_or_default(),
+            _ => format!(
+                "\x1b7{}\x1b8{}",
+                self.graphic(),
+                " ".repeat(self.placement.cols as usize)
+            ),
+        }
+    }
+
+    /// Escapes drawing the whole thumbnail with its top left at `col`, `row`
+    /// (zero-based)
+    pub fn draw_at(&self, col: u16, row: u16) -> String {
+        let goto = |r: u32| format!("\x1b[{};{}H", row as u32 + r + 1, col + 1);
+        match &self.kind {
+            Kind::Cells(lines) => (0..)
+                .zip(lines)
+                .map(|(r, line)| format!("{}{}", goto(r), line))
+                .collect(),
+            _ => format!("{}{}", goto(0), self.graphic()),
+        }
+    }
+
+    fn graphic(&self) -> String {
+        let Placement { cols, rows, .. } = self.placement;
+        match &self.kind {
+            // C=1 keeps the cursor still, q=2 stops replies landing on stdin
+            Kind::Kitty(png) => {
+                let control = format!("f=100,a=T,c={},r={},C=1,q=2", cols, rows);
+                kitty_command(&control, png)
+            }
+            Kind::ITerm2(png) => format!(
+                "\x1b]1337;File=inline=1;width={};height={};preserveAspectRatio=0:{}\x07",
+                cols,
+                rows,
+                base64_encode(png)
+            ),
+            Kind::Cells(_) => String::new(),
+        }
+    }
+}
+
+/// `path` scaled to fit in `max_cols` x `max_rows` as ▀ cells, for drawing
+/// into a cell grid: each row holds every cell's top and bottom color
+pub fn halfblocks(
+    path: &str,
+    max_cols: u32,
+    max_rows: u32,
+) -> anyhow::Result<Vec<Vec<[[u8; 3]; 2]>>> {
+    let img = decode_image(path, &load_bytes(path)?)?;
+    let placement = fit(img.dimensions(), max_cols, max_rows);
+    let pixels = cell_pixels(&img, &placement, (1, 2));
+    let rgb = |x, y| {
+        let [r, g, b, _] = pixels.get_pixel(x, y).0;
+        [r, g, b]
+    };
+    Ok((0..placement.rows)
+        .map(|row| {
+            (0..placement.cols)
+                .map(|col| [rgb(col, row * 2), rgb(col, row * 2 + 1)])
+                .collect()
+        })
+        .collect())
+}
+
+/// The largest box of cells within `max_cols` x `max_rows` with the image's
+/// shape
+fn fit(image_px: (u32, u32), max_cols: u32, max_rows: u32) -> Placement {
+    let natural_cols = image_px.0.max(1) as f32 / CELL_WIDTH_PX as f32;
+    let natural_rows = image_px.1.max(1) as f32 / CELL_HEIGHT_PX as f32;
+    let scale = (max_cols as f32 / natural_cols).min(max_rows as f32 / natural_rows);
+    Placement {
+        cols: ((natural_cols * scale).round() as u32).clamp(1, max_cols.max(1)),
+        rows: ((natural_rows * scale).round() as u32).clamp(1, max_rows.max(1)),
+        indent: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+    use image::{Rgba, RgbaImage};
+
+    fn image(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, Rgba([255, 0, 0, 255])))
+    }
+
+    #[test]
+    fn test_fit_keeps_shape() {
+        // 4:3 at 8x16 px cells is about 2.7 cells wide per row
+        let p = fit((640, 480), 8, 1);
+        assert_eq!((p.cols, p.rows), (3, 1));
+        let p = fit((640, 480), 40, 10);
+        assert_eq!((p.cols, p.rows), (27, 10));
+        let p = fit((100, 1000), 8, 1);
+        assert_eq!((p.cols, p.rows), (1, 1));
+    }
+
+    #[test]
+    fn test_cells_line_is_one_row() {
+        let thumb = Thumbnail::new(&image(128, 32), Protocol::Halfblock, 8, 1);
+        assert_eq!(thumb.size(), (8, 1));
+        let line = thumb.line();
+        assert_eq!(text::display_width(&line), 8);
+        assert!(line.contains("\x1b[38;2;255;0;0m"));
+    }
+
+    #[test]
+    fn test_graphics_line_restores_cursor() {
+        let thumb = Thumbnail::new(&image(128, 32), Protocol::Kitty, 8, 1);
+        let line = thumb.line();
+        assert!(line.starts_with("\x1b7\x1b_Gf=100,a=T,c=8,r=1,C=1,q=2,m=0;"));
+        assert!(line.ends_with("\x1b8        "));
+        assert_eq!(text::display_width(&line), 8);
+
+        let thumb = Thumbnail::new(&image(128, 32), Protocol::ITerm2, 8, 1);
+        assert!(thumb
+            .line()
+            .contains("\x1b]1337;File=inline=1;width=8;height=1;"));
+    }
+
+    #[test]
+    fn test_draw_at_positions_each_row() {
+        let thumb = Thumbnail::new(&image(16, 64), Protocol::Halfblock, 10, 2);
+        assert_eq!(thumb.size(), (1, 2));
+        let drawn = thumb.draw_at(40, 3);
+        assert!(drawn.starts_with("\x1b[4;41H"));
+        assert!(drawn.contains("\x1b[5;41H"));
+
+        let thumb = Thumbnail::new(&image(16, 64), Protocol::Kitty, 10, 2);
+        assert!(thumb.draw_at(40, 3).starts_with("\x1b[4;41H\x1b_G"));
+    }
+
+    #[test]
+    fn test_is_image() {
+        assert!(is_image(Path::new("photo.PNG")));
+        assert!(is_image(Path::new("a/b.jpeg")));
+        assert!(!is_image(Path::new("notes.txt")));
+        assert!(!is_image(Path::new("Makefile")));
+    }
+}
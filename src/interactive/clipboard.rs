@@ -0,0 +1,27 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Copy text to the system clipboard via pbcopy or xclip
+///
+/// Returns false when neither tool is available.
+pub fn copy(text: &str) -> bool {
+    let child = Command::new("pbcopy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .or_else(|_| {
+            Command::new("xclip")
+                .args(["-selection", "clipboard"])
+                .stdin(Stdio::piped())
+                .spawn()
+        });
+
+    match child {
+        Ok(mut child) => {
+            if let Some(stdin) = child.stdin.as_mut() {
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            child.wait().map(|s| s.success()).unwrap_or(false)
+        }
+        Err(_) => false,
+    }
+}
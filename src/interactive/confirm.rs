@@ -1,21 +1,41 @@
+use super::prompt;
+use crate::output::color;
 use crossterm::{
     cursor,
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     style::{Color, Print, ResetColor, SetForegroundColor},
     terminal,
 };
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, Write};
 
-/// Render a yes/no confirmation prompt
-pub fn render(prompt: &str, default: &str, style: &str) {
-    let default_bool = match default.to_lowercase().as_str() {
+/// Render a yes/no confirmation prompt, exiting 0 for yes and 1 for no
+///
+/// With `require_typed`, y/n is replaced by typing the phrase exactly; any
+/// other text answers no. Without a TTY the answer is `default` (see
+/// [`prompt`](super::prompt)); on a terminal a missing default means yes.
+pub fn render(
+    prompt_text: &str,
+    default: Option<&str>,
+    style: &str,
+    require_typed: Option<&str>,
+    no_tty_error: bool,
+) {
+    let parse = |answer: &str| match answer.to_lowercase().as_str() {
         "yes" | "y" | "true" => true,
         "no" | "n" | "false" => false,
         _ => true,
     };
 
-    let result = show_confirm_prompt(prompt, default_bool, style);
+    let result = match prompt::answer_without_tty("Confirm", default, no_tty_error) {
+        // An empty answer (--no-tty-error) is a no
+        Ok(Some(answer)) => Ok(!answer.is_empty() && parse(&answer)),
+        Ok(None) => match require_typed {
+            Some(phrase) => show_typed_prompt(prompt_text, phrase),
+            None => show_confirm_prompt(prompt_text, default.is_none_or(parse), style),
+        },
+        Err(e) => Err(io::Error::other(e)),
+    };
 
     match result {
         Ok(answer) => {
@@ -35,16 +55,9 @@ pub fn render(prompt: &str, default: &str, style: &str) {
 }
 
 fn show_confirm_prompt(prompt: &str, default: bool, style: &str) -> io::Result<bool> {
-    // Check for interactive terminal
-    if !std::io::stdin().is_terminal() {
-        return Err(io::Error::other(
-            "Confirm requires an interactive terminal (TTY)",
-        ));
-    }
-
     terminal::enable_raw_mode()?;
 
-    let mut stdout = io::stdout();
+    let mut stdout = color::stdout();
 
     let (prompt_color, bracket_color) = match style.to_lowercase().as_str() {
         "danger" => (Color::Red, Color::DarkRed),
@@ -80,7 +93,7 @@ fn show_confirm_prompt(prompt: &str, default: bool, style: &str) -> io::Result<b
                     KeyCode::Esc | KeyCode::Char('c') => {
                         execute!(stdout, Print("\n"))?;
                         terminal::disable_raw_mode()?;
-                        std::process::exit(130);
+                        prompt::exit_cancelled();
                     }
                     _ => {}
                 }
@@ -107,3 +120,107 @@ fn show_confirm_prompt(prompt: &str, default: bool, style: &str) -> io::Result<b
 
     Ok(answer_bool)
 }
+
+fn show_typed_prompt(prompt: &str, phrase: &str) -> io::Result<bool> {
+    terminal::enable_raw_mode()?;
+
+    let mut stdout = color::stdout();
+    execute!(
+        stdout,
+        SetForegroundColor(Color::Red),
+        Print(format!("⚠️  {}\r\n", prompt)),
+        SetForegroundColor(Color::DarkRed),
+        Print("Type "),
+        SetForegroundColor(Color::Red),
+        Print(phrase),
+        SetForegroundColor(Color::DarkRed),
+        Print(" to confirm: "),
+        ResetColor,
+        cursor::Show,
+        cursor::SavePosition,
+    )?;
+    stdout.flush()?;
+
+    let mut typed = String::new();
+    loop {
+        if let Event::Key(KeyEvent {
+            code, modifiers, ..
+        }) = event::read()?
+        {
+            let cancel = code == KeyCode::Esc
+                || (code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL));
+            if cancel {
+                execute!(stdout, Print("\r\n"))?;
+                terminal::disable_raw_mode()?;
+                prompt::exit_cancelled();
+            }
+            match code {
+                KeyCode::Enter => break,
+                KeyCode::Backspace => {
+                    typed.pop();
+                }
+                KeyCode::Char(c) => typed.push(c),
+                _ => continue,
+            }
+
+            // Matching prefix in green, anything past a mistake in red
+            let matched = matched_prefix(&typed, phrase);
+            let (good, bad) = typed.split_at(matched);
+            execute!(
+                stdout,
+                cursor::RestorePosition,
+                terminal::Clear(terminal::ClearType::UntilNewLine),
+                SetForegroundColor(Color::Green),
+                Print(good),
+                SetForegroundColor(Color::Red),
+                Print(bad),
+                ResetColor,
+            )?;
+            stdout.flush()?;
+        }
+    }
+
+    let confirmed = typed == phrase;
+    execute!(
+        stdout,
+        Print("\r\n"),
+        SetForegroundColor(if confirmed { Color::Green } else { Color::Red }),
+        Print(if confirmed {
+            "✓ Confirmed"
+        } else {
+            "✗ Phrase did not match"
+        }),
+        ResetColor,
+        Print("\r\n"),
+    )?;
+    stdout.flush()?;
+
+    terminal::disable_raw_mode()?;
+
+    Ok(confirmed)
+}
+
+/// Byte length of the longest prefix of `typed` that matches `phrase`
+fn matched_prefix(typed: &str, phrase: &str) -> usize {
+    typed
+        .char_indices()
+        .zip(phrase.chars())
+        .take_while(|((_, a), b)| a == b)
+        .last()
+        .map(|((idx, c), _)| idx + c.len_utf8())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matched_prefix() {
+        assert_eq!(matched_prefix("", "delete-prod"), 0);
+        assert_eq!(matched_prefix("delete", "delete-prod"), 6);
+        assert_eq!(matched_prefix("delx-prod", "delete-prod"), 3);
+        assert_eq!(matched_prefix("delete-prod!", "delete-prod"), 11);
+        assert_eq!(matched_prefix("été", "étés"), "été".len());
+    }
+}
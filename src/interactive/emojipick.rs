@@ -0,0 +1,748 @@
+//! `termgfx emojipick`: a searchable grid of emoji and Nerd Font icons
+//!
+//! Typing filters by name and keywords, Tab cycles categories and the arrow
+//! keys move through the grid. Enter prints the glyph, or its `:name:` code
+//! with `--code`. `--list` prints the matches instead, for scripts.
+
+use crate::output::{color, width};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    style::{Color, Print, ResetColor, SetForegroundColor, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::{self, IsTerminal, Write};
+
+/// One pickable glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    pub glyph: &'static str,
+    pub name: &'static str,
+    pub category: &'static str,
+    /// Extra search words
+    pub keywords: &'static str,
+}
+
+const fn g(
+    glyph: &'static str,
+    name: &'static str,
+    category: &'static str,
+    keywords: &'static str,
+) -> Glyph {
+    Glyph {
+        glyph,
+        name,
+        category,
+        keywords,
+    }
+}
+
+/// Categories in tab order; "all" comes first in the picker
+pub const CATEGORIES: &[&str] = &[
+    "smileys", "people", "nature", "food", "activity", "travel", "objects", "symbols", "flags",
+    "nerd",
+];
+
+/// Emoji use GitHub shortcode names; Nerd Font icons their `nf-` class names
+pub const GLYPHS: &[Glyph] = &[
+    g("😀", "grinning", "smileys", "happy smile face"),
+    g("😃", "smiley", "smileys", "happy smile face"),
+    g("😄", "smile", "smileys", "happy face"),
+    g("😁", "grin", "smileys", "happy teeth"),
+    g("😆", "laughing", "smileys", "happy lol"),
+    g("😅", "sweat_smile", "smileys", "relief phew"),
+    g("😂", "joy", "smileys", "tears laugh lol"),
+    g("🙂", "slightly_smiling_face", "smileys", "smile"),
+    g("😉", "wink", "smileys", "flirt"),
+    g("😊", "blush", "smileys", "happy proud"),
+    g("😇", "innocent", "smileys", "angel halo"),
+    g("😍", "heart_eyes", "smileys", "love crush"),
+    g("🤩", "star_struck", "smileys", "wow amazed"),
+    g("😘", "kissing_heart", "smileys", "love kiss"),
+    g("😋", "yum", "smileys", "tasty delicious"),
+    g("😎", "sunglasses", "smileys", "cool"),
+    g("🤓", "nerd_face", "smileys", "geek glasses"),
+    g("🤔", "thinking", "smileys", "hmm consider"),
+    g("🤨", "raised_eyebrow", "smileys", "suspicious skeptic"),
+    g("😐", "neutral_face", "smileys", "meh"),
+    g("😑", "expressionless", "smileys", "blank"),
+    g("🙄", "roll_eyes", "smileys", "eyeroll whatever"),
+    g("😏", "smirk", "smileys", "smug"),
+    g("😬", "grimacing", "smileys", "awkward oops"),
+    g("😴", "sleeping", "smileys", "tired zzz"),
+    g("🤯", "exploding_head", "smileys", "mind blown shocked"),
+    g("🥳", "partying_face", "smileys", "celebrate party"),
+    g("😕", "confused", "smileys", "unsure"),
+    g("😟", "worried", "smileys", "concern"),
+    g("😮", "open_mouth", "smileys", "surprised wow"),
+    g("😱", "scream", "smileys", "fear horror"),
+    g("😢", "cry", "smileys", "sad tear"),
+    g("😭", "sob", "smileys", "sad crying"),
+    g("😤", "triumph", "smileys", "frustrated huff"),
+    g("😡", "rage", "smileys", "angry mad"),
+    g("🤬", "cursing_face", "smileys", "angry swear"),
+    g("💀", "skull", "smileys", "dead death"),
+    g("💩", "poop", "smileys", "hankey"),
+    g("🤖", "robot", "smileys", "bot machine"),
+    g("👻", "ghost", "smileys", "halloween boo"),
+    g("👽", "alien", "smileys", "ufo space"),
+    g("👋", "wave", "people", "hello bye hand"),
+    g("👍", "thumbsup", "people", "+1 yes approve like"),
+    g("👎", "thumbsdown", "people", "-1 no dislike"),
+    g("👌", "ok_hand", "people", "okay perfect"),
+    g("✌️", "v", "people", "peace victory"),
+    g("🤞", "crossed_fingers", "people", "luck hope"),
+    g("👏", "clap", "people", "applause bravo"),
+    g("🙌", "raised_hands", "people", "hooray celebrate"),
+    g("🙏", "pray", "people", "please thanks"),
+    g("💪", "muscle", "people", "strong flex"),
+    g("👀", "eyes", "people", "look see watch"),
+    g("🧠", "brain", "people", "smart think"),
+    g("👉", "point_right", "people", "hand direction"),
+    g("👈", "point_left", "people", "hand direction"),
+    g("👆", "point_up_2", "people", "hand direction"),
+    g("👇", "point_down", "people", "hand direction"),
+    g("🤝", "handshake", "people", "deal agreement"),
+    g("✍️", "writing_hand", "people", "write sign"),
+    g("🧑‍💻", "technologist", "people", "developer coder programmer"),
+    g("🕵️", "detective", "people", "spy investigate"),
+    g("🥷", "ninja", "people", "stealth"),
+    g("🐛", "bug", "nature", "insect defect"),
+    g("🐞", "lady_beetle", "nature", "bug insect"),
+    g("🐝", "bee", "nature", "honeybee insect"),
+    g("🦀", "crab", "nature", "rust ferris"),
+    g("🐍", "snake", "nature", "python"),
+    g("🐳", "whale", "nature", "docker ocean"),
+    g("🐧", "penguin", "nature", "linux tux"),
+    g("🐢", "turtle", "nature", "slow"),
+    g("🐇", "rabbit2", "nature", "fast bunny"),
+    g("🦊", "fox_face", "nature", "firefox animal"),
+    g("🐱", "cat", "nature", "kitten pet"),
+    g("🐶", "dog", "nature", "puppy pet"),
+    g("🦄", "unicorn", "nature", "magic"),
+    g("🐙", "octopus", "nature", "github octocat"),
+    g("🦉", "owl", "nature", "wise night"),
+    g("🌱", "seedling", "nature", "plant grow new"),
+    g("🌲", "evergreen_tree", "nature", "forest"),
+    g("🌳", "deciduous_tree", "nature", "forest"),
+    g("🍀", "four_leaf_clover", "nature", "luck"),
+    g("🌸", "cherry_blossom", "nature", "flower spring"),
+    g("🌻", "sunflower", "nature", "flower summer"),
+    g("🍂", "fallen_leaf", "nature", "autumn"),
+    g("🌞", "sun_with_face", "nature", "sunny"),
+    g("☀️", "sunny", "nature", "weather sun"),
+    g("⛅", "partly_sunny", "nature", "weather cloud"),
+    g("🌧️", "cloud_with_rain", "nature", "weather rain"),
+    g(
+        "⛈️",
+        "cloud_with_lightning_and_rain",
+        "nature",
+        "weather storm",
+    ),
+    g("❄️", "snowflake", "nature", "weather cold freeze"),
+    g("🌈", "rainbow", "nature", "weather colors"),
+    g("🌊", "ocean", "nature", "wave water"),
+    g("🌙", "crescent_moon", "nature", "night dark mode"),
+    g("⭐", "star", "nature", "favorite"),
+    g("🌟", "star2", "nature", "glow shine"),
+    g("🔥", "fire", "nature", "hot lit flame"),
+    g("💧", "droplet", "nature", "water"),
+    g("⚡", "zap", "nature", "lightning fast power"),
+    g("🍎", "apple", "food", "fruit red"),
+    g("🍋", "lemon", "food", "fruit"),
+    g("🍌", "banana", "food", "fruit"),
+    g("🍉", "watermelon", "food", "fruit summer"),
+    g("🍒", "cherries", "food", "fruit"),
+    g("🥑", "avocado", "food", "fruit"),
+    g("🌶️", "hot_pepper", "food", "spicy chili"),
+    g("🍕", "pizza", "food", "slice"),
+    g("🍔", "hamburger", "food", "burger"),
+    g("🌮", "taco", "food", "mexican"),
+    g("🍜", "ramen", "food", "noodles"),
+    g("🍣", "sushi", "food", "japanese"),
+    g("🍪", "cookie", "food", "biscuit"),
+    g("🍩", "doughnut", "food", "donut"),
+    g("🎂", "birthday", "food", "cake"),
+    g("🍰", "cake", "food", "dessert"),
+    g("🍿", "popcorn", "food", "movie"),
+    g("☕", "coffee", "food", "cafe break"),
+    g("🍵", "tea", "food", "green"),
+    g("🍺", "beer", "food", "drink"),
+    g("🍻", "beers", "food", "cheers drink"),
+    g("🍷", "wine_glass", "food", "drink"),
+    g("🎉", "tada", "activity", "party celebrate hooray"),
+    g("🎊", "confetti_ball", "activity", "party celebrate"),
+    g("🎈", "balloon", "activity", "party"),
+    g("🎁", "gift", "activity", "present"),
+    g("🏆", "trophy", "activity", "win award"),
+    g("🥇", "1st_place_medal", "activity", "gold winner"),
+    g("🥈", "2nd_place_medal", "activity", "silver"),
+    g("🥉", "3rd_place_medal", "activity", "bronze"),
+    g("🏅", "medal_sports", "activity", "award"),
+    g("🎯", "dart", "activity", "target goal bullseye"),
+    g("🎮", "video_game", "activity", "controller gaming"),
+    g("🎲", "game_die", "activity", "dice random"),
+    g("🧩", "jigsaw", "activity", "puzzle plugin"),
+    g("🎨", "art", "activity", "design palette paint"),
+    g("🎬", "clapper", "activity", "film movie"),
+    g("🎵", "musical_note", "activity", "music"),
+    g("🎸", "guitar", "activity", "music rock"),
+    g("⚽", "soccer", "activity", "football sport"),
+    g("🏀", "basketball", "activity", "sport"),
+    g("🏁", "checkered_flag", "activity", "finish race done"),
+    g("🚀", "rocket", "travel", "launch ship deploy release"),
+    g("✈️", "airplane", "travel", "flight"),
+    g("🚗", "car", "travel", "automobile"),
+    g("🚲", "bike", "travel", "bicycle"),
+    g("🚂", "steam_locomotive", "travel", "train"),
+    g("🚢", "ship", "travel", "boat"),
+    g("⛵", "boat", "travel", "sailboat"),
+    g("🛸", "flying_saucer", "travel", "ufo"),
+    g("🚧", "construction", "travel", "wip work in progress"),
+    g("🚦", "vertical_traffic_light", "travel", "status"),
+    g("🚨", "rotating_light", "travel", "alert alarm siren"),
+    g("🏠", "house", "travel", "home"),
+    g("🏢", "office", "travel", "building work"),
+    g("🏗️", "building_construction", "travel", "build"),
+    g("🌍", "earth_africa", "travel", "globe world"),
+    g("🌐", "globe_with_meridians", "travel", "web internet world"),
+    g("🗺️", "world_map", "travel", "map"),
+    g("⏰", "alarm_clock", "objects", "time wake"),
+    g(
+        "⏳",
+        "hourglass_flowing_sand",
+        "objects",
+        "time wait pending",
+    ),
+    g("⌛", "hourglass", "objects", "time done"),
+    g("⏱️", "stopwatch", "objects", "time timer"),
+    g("📅", "date", "objects", "calendar"),
+    g("📆", "calendar", "objects", "date schedule"),
+    g("💻", "computer", "objects", "laptop"),
+    g("🖥️", "desktop_computer", "objects", "monitor screen"),
+    g("⌨️", "keyboard", "objects", "type input"),
+    g("🖱️", "computer_mouse", "objects", "click"),
+    g("📱", "iphone", "objects", "phone mobile"),
+    g("💾", "floppy_disk", "objects", "save"),
+    g("💿", "cd", "objects", "disk"),
+    g("🔋", "battery", "objects", "power charge"),
+    g("🔌", "electric_plug", "objects", "power plugin"),
+    g("💡", "bulb", "objects", "idea light tip"),
+    g("🔦", "flashlight", "objects", "light"),
+    g("📦", "package", "objects", "box release ship"),
+    g("📁", "file_folder", "objects", "directory"),
+    g("📂", "open_file_folder", "objects", "directory"),
+    g("📄", "page_facing_up", "objects", "document file"),
+    g("📝", "memo", "objects", "note write docs"),
+    g("📋", "clipboard", "objects", "copy paste list"),
+    g("📌", "pushpin", "objects", "pin"),
+    g("📎", "paperclip", "objects", "attach"),
+    g("✏️", "pencil2", "objects", "edit write"),
+    g("📚", "books", "objects", "library docs read"),
+    g("📖", "book", "objects", "read docs"),
+    g("📊", "bar_chart", "objects", "graph stats"),
+    g(
+        "📈",
+        "chart_with_upwards_trend",
+        "objects",
+        "graph up growth",
+    ),
+    g(
+        "📉",
+        "chart_with_downwards_trend",
+        "objects",
+        "graph down decline",
+    ),
+    g("📣", "mega", "objects", "announce megaphone"),
+    g("🔔", "bell", "objects", "notification alert"),
+    g("🔕", "no_bell", "objects", "mute silent"),
+    g("✉️", "envelope", "objects", "email mail"),
+    g("📬", "mailbox_with_mail", "objects", "inbox"),
+    g("🔒", "lock", "objects", "security private"),
+    g("🔓", "unlock", "objects", "open security"),
+    g("🔑", "key", "objects", "password secret"),
+    g("🛡️", "shield", "objects", "security protect"),
+    g("🔧", "wrench", "objects", "tool fix config"),
+    g("🔨", "hammer", "objects", "tool build"),
+    g("🛠️", "hammer_and_wrench", "objects", "tools build"),
+    g("⚙️", "gear", "objects", "settings config"),
+    g("🔩", "nut_and_bolt", "objects", "hardware"),
+    g("🧪", "test_tube", "objects", "test experiment"),
+    g("🔬", "microscope", "objects", "science inspect"),
+    g("🔭", "telescope", "objects", "explore"),
+    g("🧲", "magnet", "objects", "attract"),
+    g("🧹", "broom", "objects", "clean cleanup"),
+    g("🗑️", "wastebasket", "objects", "trash delete remove"),
+    g("🔍", "mag", "objects", "search find zoom"),
+    g("🔗", "link", "objects", "url chain"),
+    g("🏷️", "label", "objects", "tag"),
+    g("💰", "moneybag", "objects", "money cost"),
+    g("💎", "gem", "objects", "diamond ruby"),
+    g(
+        "✅",
+        "white_check_mark",
+        "symbols",
+        "done ok success pass yes",
+    ),
+    g("✔️", "heavy_check_mark", "symbols", "done ok success"),
+    g("☑️", "ballot_box_with_check", "symbols", "checkbox done"),
+    g("❌", "x", "symbols", "fail error no cross"),
+    g("❎", "negative_squared_cross_mark", "symbols", "no cross"),
+    g("⚠️", "warning", "symbols", "caution alert"),
+    g("⛔", "no_entry", "symbols", "stop forbidden"),
+    g("🚫", "no_entry_sign", "symbols", "forbidden prohibited"),
+    g("❗", "exclamation", "symbols", "important bang"),
+    g("❓", "question", "symbols", "help unknown"),
+    g("💯", "100", "symbols", "perfect score"),
+    g("ℹ️", "information_source", "symbols", "info"),
+    g("🆕", "new", "symbols", "fresh"),
+    g("🆗", "ok", "symbols", "okay"),
+    g("🆙", "up", "symbols", "update"),
+    g("🔴", "red_circle", "symbols", "status error"),
+    g("🟠", "orange_circle", "symbols", "status"),
+    g("🟡", "yellow_circle", "symbols", "status warning"),
+    g("🟢", "green_circle", "symbols", "status ok"),
+    g("🔵", "large_blue_circle", "symbols", "status info"),
+    g("🟣", "purple_circle", "symbols", "status"),
+    g("⚫", "black_circle", "symbols", "status"),
+    g("⚪", "white_circle", "symbols", "status"),
+    g("🟥", "red_square", "symbols", "block"),
+    g("🟩", "green_square", "symbols", "block"),
+    g("🟦", "blue_square", "symbols", "block"),
+    g("❤️", "heart", "symbols", "love red"),
+    g("🧡", "orange_heart", "symbols", "love"),
+    g("💛", "yellow_heart", "symbols", "love"),
+    g("💚", "green_heart", "symbols", "love"),
+    g("💙", "blue_heart", "symbols", "love"),
+    g("💜", "purple_heart", "symbols", "love"),
+    g("🖤", "black_heart", "symbols", "love"),
+    g("💔", "broken_heart", "symbols", "sad breaking"),
+    g("✨", "sparkles", "symbols", "shiny new magic"),
+    g("💥", "boom", "symbols", "collision explode crash"),
+    g("💫", "dizzy", "symbols", "star"),
+    g("💬", "speech_balloon", "symbols", "comment chat"),
+    g("💭", "thought_balloon", "symbols", "think"),
+    g("➡️", "arrow_right", "symbols", "next direction"),
+    g("⬅️", "arrow_left", "symbols", "back direction"),
+    g("⬆️", "arrow_up", "symbols", "direction"),
+    g("⬇️", "arrow_down", "symbols", "direction"),
+    g("↩️", "leftwards_arrow_with_hook", "symbols", "return undo"),
+    g(
+        "🔄",
+        "arrows_counterclockwise",
+        "symbols",
+        "refresh sync reload",
+    ),
+    g("🔁", "repeat", "symbols", "loop"),
+    g("➕", "heavy_plus_sign", "symbols", "add plus"),
+    g("➖", "heavy_minus_sign", "symbols", "remove minus"),
+    g("♻️", "recycle", "symbols", "reuse"),
+    g("©️", "copyright", "symbols", "license"),
+    g("™️", "tm", "symbols", "trademark"),
+    g("🏳️", "white_flag", "flags", "surrender"),
+    g("🏴", "black_flag", "flags", "waving"),
+    g("🚩", "triangular_flag_on_post", "flags", "red flag marker"),
+    g("🏳️‍🌈", "rainbow_flag", "flags", "pride"),
+    g("🇺🇸", "us", "flags", "united states america usa"),
+    g("🇬🇧", "gb", "flags", "united kingdom uk britain"),
+    g("🇫🇷", "fr", "flags", "france"),
+    g("🇩🇪", "de", "flags", "germany"),
+    g("🇪🇸", "es", "flags", "spain"),
+    g("🇮🇹", "it", "flags", "italy"),
+    g("🇲🇦", "morocco", "flags", "ma"),
+    g("🇯🇵", "jp", "flags", "japan"),
+    g("🇨🇳", "cn", "flags", "china"),
+    g("🇮🇳", "india", "flags", "in"),
+    g("🇧🇷", "brazil", "flags", "br"),
+    g("🇨🇦", "canada", "flags", "ca"),
+    g("🇪🇺", "eu", "flags", "european union"),
+    g("\u{f00c}", "nf-fa-check", "nerd", "done ok success"),
+    g("\u{f00d}", "nf-fa-close", "nerd", "times cross fail error"),
+    g("\u{f071}", "nf-fa-warning", "nerd", "alert caution"),
+    g("\u{f05a}", "nf-fa-info_circle", "nerd", "info"),
+    g("\u{f059}", "nf-fa-question_circle", "nerd", "help"),
+    g("\u{f005}", "nf-fa-star", "nerd", "favorite"),
+    g("\u{f004}", "nf-fa-heart", "nerd", "love"),
+    g("\u{f013}", "nf-fa-cog", "nerd", "settings gear config"),
+    g("\u{f015}", "nf-fa-home", "nerd", "house"),
+    g("\u{f002}", "nf-fa-search", "nerd", "find magnifier"),
+    g("\u{f023}", "nf-fa-lock", "nerd", "security"),
+    g("\u{f09c}", "nf-fa-unlock", "nerd", "security open"),
+    g("\u{f084}", "nf-fa-key", "nerd", "password"),
+    g("\u{f007}", "nf-fa-user", "nerd", "person account"),
+    g("\u{f0e0}", "nf-fa-envelope", "nerd", "email mail"),
+    g("\u{f0f3}", "nf-fa-bell", "nerd", "notification"),
+    g("\u{f017}", "nf-fa-clock_o", "nerd", "time"),
+    g("\u{f073}", "nf-fa-calendar", "nerd", "date"),
+    g("\u{f07b}", "nf-fa-folder", "nerd", "directory"),
+    g("\u{f07c}", "nf-fa-folder_open", "nerd", "directory"),
+    g("\u{f15b}", "nf-fa-file", "nerd", "document"),
+    g("\u{f121}", "nf-fa-code", "nerd", "source brackets"),
+    g("\u{f120}", "nf-fa-terminal", "nerd", "shell prompt console"),
+    g("\u{f188}", "nf-fa-bug", "nerd", "defect insect"),
+    g("\u{f135}", "nf-fa-rocket", "nerd", "launch deploy"),
+    g("\u{f0e7}", "nf-fa-bolt", "nerd", "lightning fast"),
+    g("\u{f0c2}", "nf-fa-cloud", "nerd", "weather server"),
+    g("\u{f019}", "nf-fa-download", "nerd", "save fetch"),
+    g("\u{f093}", "nf-fa-upload", "nerd", "send push"),
+    g("\u{f1f8}", "nf-fa-trash", "nerd", "delete remove"),
+    g("\u{f233}", "nf-fa-server", "nerd", "host rack"),
+    g("\u{f126}", "nf-fa-code_fork", "nerd", "git fork branch"),
+    g("\u{e702}", "nf-dev-git", "nerd", "vcs"),
+    g("\u{e725}", "nf-dev-git_branch", "nerd", "vcs"),
+    g("\u{e729}", "nf-dev-git_commit", "nerd", "vcs"),
+    g("\u{e727}", "nf-dev-git_merge", "nerd", "vcs"),
+    g("\u{e728}", "nf-dev-git_pull_request", "nerd", "vcs pr"),
+    g("\u{e709}", "nf-dev-github_badge", "nerd", "octocat"),
+    g("\u{e7a8}", "nf-dev-rust", "nerd", "language cargo"),
+    g("\u{e73c}", "nf-dev-python", "nerd", "language"),
+    g("\u{e74e}", "nf-dev-javascript", "nerd", "language js"),
+    g("\u{e724}", "nf-dev-go", "nerd", "language golang"),
+    g("\u{e738}", "nf-dev-java", "nerd", "language"),
+    g("\u{e7ba}", "nf-dev-react", "nerd", "javascript framework"),
+    g("\u{e736}", "nf-dev-html5", "nerd", "web"),
+    g("\u{e749}", "nf-dev-css3", "nerd", "web style"),
+    g("\u{e71e}", "nf-dev-npm", "nerd", "node package"),
+    g("\u{e7b0}", "nf-dev-docker", "nerd", "container whale"),
+    g("\u{e706}", "nf-dev-database", "nerd", "db storage"),
+    g("\u{e712}", "nf-dev-linux", "nerd", "os tux penguin"),
+    g("\u{e711}", "nf-dev-apple", "nerd", "os macos"),
+    g("\u{e70f}", "nf-dev-windows", "nerd", "os microsoft"),
+    g("\u{e73a}", "nf-dev-ubuntu", "nerd", "os linux"),
+    g("\u{e795}", "nf-dev-terminal", "nerd", "shell console"),
+    g("\u{e0a0}", "nf-pl-branch", "nerd", "powerline git"),
+    g(
+        "\u{e0b0}",
+        "nf-pl-left_hard_divider",
+        "nerd",
+        "powerline arrow separator",
+    ),
+    g(
+        "\u{e0b2}",
+        "nf-pl-right_hard_divider",
+        "nerd",
+        "powerline arrow separator",
+    ),
+];
+
+/// Glyphs in `category` ("all" for every one) matching `query`
+///
+/// Every word of the query has to appear in the name or keywords. Exact
+/// names come first, then names starting with the query, then names
+/// containing it, then keyword matches, each in catalog order.
+pub fn search(query: &str, category: &str) -> Vec<&'static Glyph> {
+    let query = query.trim().to_lowercase();
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let mut found: Vec<(usize, &'static Glyph)> = GLYPHS
+        .iter()
+        .filter(|g| category == "all" || g.category == category)
+        .filter(|g| {
+            words
+                .iter()
+                .all(|w| g.name.contains(w) || g.keywords.contains(w))
+        })
+        .map(|g| {
+            let rank = if query.is_empty() || g.name == query {
+                0
+            } else if g.name.starts_with(&query) {
+                1
+            } else if g.name.contains(&query) {
+                2
+            } else {
+                3
+            };
+            (rank, g)
+        })
+        .collect();
+    found.sort_by_key(|(rank, _)| *rank);
+    found.into_iter().map(|(_, g)| g).collect()
+}
+
+/// What Enter prints for `glyph`
+fn output(glyph: &Glyph, code: bool) -> String {
+    if code {
+        format!(":{}:", glyph.name)
+    } else {
+        glyph.glyph.to_string()
+    }
+}
+
+/// Columns a grid cell takes: a two-wide glyph between markers
+const CELL: usize = 4;
+
+/// Selection after an arrow key in a grid `cols` wide holding `len` glyphs
+fn step(current: usize, key: KeyCode, cols: usize, len: usize) -> usize {
+    let last = len.saturating_sub(1);
+    match key {
+        KeyCode::Left => current.saturating_sub(1),
+        KeyCode::Right => (current + 1).min(last),
+        KeyCode::Up => current.checked_sub(cols).unwrap_or(current),
+        KeyCode::Down if current + cols <= last => current + cols,
+        KeyCode::Home => 0,
+        KeyCode::End => last,
+        _ => current,
+    }
+}
+
+struct Picker {
+    query: String,
+    /// Index into "all" followed by CATEGORIES
+    tab: usize,
+    selected: usize,
+    code: bool,
+}
+
+impl Picker {
+    fn category(&self) -> &'static str {
+        match self.tab {
+            0 => "all",
+            n => CATEGORIES[n - 1],
+        }
+    }
+
+    fn run(&mut self) -> io::Result<Option<String>> {
+        let mut stdout = color::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, Hide)?;
+
+        let result = loop {
+            let matches = search(&self.query, self.category());
+            let (term_width, term_height) = terminal::size().unwrap_or((80, 24));
+            let cols = ((term_width as usize).saturating_sub(2) / CELL).clamp(1, 16);
+            if let Err(e) = self.draw(&mut stdout, &matches, cols, term_height as usize) {
+                break Err(e);
+            }
+
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+            let tabs = CATEGORIES.len() + 1;
+            match code {
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break Ok(None),
+                KeyCode::Esc => break Ok(None),
+                KeyCode::Enter => {
+                    if let Some(glyph) = matches.get(self.selected) {
+                        break Ok(Some(output(glyph, self.code)));
+                    }
+                }
+                KeyCode::Tab => {
+                    self.tab = (self.tab + 1) % tabs;
+                    self.selected = 0;
+                }
+                KeyCode::BackTab => {
+                    self.tab = (self.tab + tabs - 1) % tabs;
+                    self.selected = 0;
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.selected = 0;
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.selected = 0;
+                }
+                key => self.selected = step(self.selected, key, cols, matches.len()),
+            }
+        };
+
+        execute!(stdout, Show, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn draw(
+        &self,
+        stdout: &mut color::Stdout,
+        matches: &[&Glyph],
+        cols: usize,
+        term_height: usize,
+    ) -> io::Result<()> {
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Cyan),
+            Print("❯ Search: "),
+            ResetColor,
+            Print(&self.query),
+            Print("█\r\n")
+        )?;
+
+        let mut tabs = String::new();
+        for (i, name) in std::iter::once("all")
+            .chain(CATEGORIES.iter().copied())
+            .enumerate()
+        {
+            if i == self.tab {
+                tabs.push_str(&format!(" {} ", name.bold().reverse()));
+            } else {
+                tabs.push_str(&format!(" {} ", name.dark_grey()));
+            }
+        }
+        execute!(stdout, Print(tabs), Print("\r\n\r\n"))?;
+
+        // Scroll so the selected row stays in view
+        let visible = term_height.saturating_sub(7).max(1);
+        let row = self.selected / cols;
+        let first = row.saturating_sub(visible - 1);
+        for (r, line) in matches.chunks(cols).enumerate().skip(first).take(visible) {
+            let mut text = String::from(" ");
+            for (c, glyph) in line.iter().enumerate() {
+                let pad = " ".repeat(2usize.saturating_sub(width::str_width(glyph.glyph)));
+                if r * cols + c == self.selected {
+                    text.push_str(&format!(
+                        "{}{}{}{} ",
+                        "[".cyan(),
+                        glyph.glyph,
+                        pad,
+                        "]".cyan()
+                    ));
+                } else {
+                    text.push_str(&format!(" {}{}  ", glyph.glyph, pad));
+                }
+            }
+            execute!(stdout, Print(text), Print("\r\n"))?;
+        }
+
+        execute!(stdout, Print("\r\n"))?;
+        match matches.get(self.selected) {
+            Some(glyph) => execute!(
+                stdout,
+                Print(format!(" {} ", glyph.glyph)),
+                Print(format!(":{}:", glyph.name).bold()),
+                Print(format!("  {}", glyph.category).dark_grey()),
+                Print("\r\n")
+            )?,
+            None => execute!(stdout, Print(" No matches".dark_grey()), Print("\r\n"))?,
+        }
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!(
+                " {}/{}  ←↑↓→ move  Tab category  Enter pick  Esc cancel",
+                matches.len(),
+                GLYPHS.len()
+            )),
+            ResetColor
+        )?;
+        stdout.flush()
+    }
+}
+
+pub fn render(query: Option<&str>, category: &str, code: bool, list: bool) {
+    let tab = match category {
+        "all" => 0,
+        name => match CATEGORIES.iter().position(|c| *c == name) {
+            Some(i) => i + 1,
+            None => {
+                eprintln!(
+                    "Error: Unknown category: {}. Available: all, {}",
+                    name,
+                    CATEGORIES.join(", ")
+                );
+                std::process::exit(1);
+            }
+        },
+    };
+    let query = query.unwrap_or_default();
+
+    if list {
+        for glyph in search(query, category) {
+            let pad = " ".repeat(2usize.saturating_sub(width::str_width(glyph.glyph)));
+            println!(
+                "{}{}  {:<32} {}",
+                glyph.glyph,
+                pad,
+                output(glyph, true),
+                glyph.category
+            );
+        }
+        return;
+    }
+
+    if !io::stdin().is_terminal() {
+        eprintln!("Error: Emoji picker requires an interactive terminal (TTY); use --list to print matches");
+        std::process::exit(1);
+    }
+    let mut picker = Picker {
+        query: query.to_string(),
+        tab,
+        selected: 0,
+        code,
+    };
+    match picker.run() {
+        Ok(Some(out)) => println!("{}", out),
+        Ok(None) => {
+            eprintln!("Error: Cancelled");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_catalog_is_consistent() {
+        let mut names = HashSet::new();
+        for glyph in GLYPHS {
+            assert!(names.insert(glyph.name), "duplicate name {}", glyph.name);
+            assert!(CATEGORIES.contains(&glyph.category), "{}", glyph.name);
+            assert!(!glyph.name.contains(' ') && !glyph.name.contains(':'));
+        }
+        for category in CATEGORIES {
+            assert!(!search("", category).is_empty(), "{} is empty", category);
+        }
+    }
+
+    #[test]
+    fn test_search_ranks_names_before_keywords() {
+        let names: Vec<&str> = search("rocket", "all").iter().map(|g| g.name).collect();
+        assert_eq!(names, ["rocket", "nf-fa-rocket"]);
+
+        // "heart" is a name, a prefix and a substring of other names
+        let hearts = search("heart", "all");
+        assert_eq!(hearts[0].name, "heart");
+        assert_eq!(hearts[1].name, "heart_eyes");
+
+        // Keywords match, and every word has to
+        assert_eq!(search("deploy", "travel")[0].name, "rocket");
+        assert_eq!(search("git branch", "nerd").len(), 3);
+        assert!(search("Tada", "all").iter().any(|g| g.glyph == "🎉"));
+        assert!(search("tada", "food").is_empty());
+    }
+
+    #[test]
+    fn test_output() {
+        let tada = search("tada", "all")[0];
+        assert_eq!(output(tada, false), "🎉");
+        assert_eq!(output(tada, true), ":tada:");
+    }
+
+    #[test]
+    fn test_grid_step() {
+        // 10 glyphs in rows of 4: 0-3, 4-7, 8-9
+        assert_eq!(step(0, KeyCode::Left, 4, 10), 0);
+        assert_eq!(step(3, KeyCode::Right, 4, 10), 4);
+        assert_eq!(step(9, KeyCode::Right, 4, 10), 9);
+        assert_eq!(step(5, KeyCode::Down, 4, 10), 9);
+        // No glyph below: stay put
+        assert_eq!(step(7, KeyCode::Down, 4, 10), 7);
+        assert_eq!(step(2, KeyCode::Up, 4, 10), 2);
+        assert_eq!(step(6, KeyCode::Up, 4, 10), 2);
+        assert_eq!(step(6, KeyCode::End, 4, 10), 9);
+        assert_eq!(step(0, KeyCode::Right, 4, 0), 0);
+    }
+}
@@ -1,3 +1,7 @@
+use crate::image::{self, Thumbnail};
+use crate::interactive::screen;
+use crate::output::color;
+use crate::text;
 use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
@@ -9,8 +13,8 @@ use std::{
     borrow::Cow,
     collections::HashSet,
     fs,
-    io::{self, IsTerminal, Write},
-    path::PathBuf,
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
 };
 
 // --- Constants and Icons ---
@@ -25,6 +29,13 @@ const ICON_DIRECTORY: &str = "📁";
 const ICON_FILE: &str = "📄";
 const ICON_SELECTED: &str = "❯";
 const ICON_UNSELECTED: &str = " ";
+const ICON_MARKED: &str = "◉";
+const ICON_UNMARKED: &str = "○";
+
+/// Narrowest terminal that gets a preview beside the list
+const PREVIEW_MIN_COLS: u16 = 60;
+/// Most of a file read to preview it as text
+const PREVIEW_MAX_BYTES: u64 = 64 * 1024;
 
 // --- FilePicker Struct ---
 pub struct FilePicker {
@@ -36,6 +47,23 @@ pub struct FilePicker {
     allowed_extensions: Option<HashSet<String>>,
     height: Option<usize>,
     error_message: Option<String>,
+    /// Space marks entries and Enter returns all of them
+    multi: bool,
+    /// Marked entries, in the order they were marked
+    marked: Vec<PathBuf>,
+    show_hidden: bool,
+    /// Preview text files too, not only images
+    text_preview: bool,
+    /// The filter line doubles as a new file name
+    save: bool,
+    /// Preview of the selected file, kept while it stays selected
+    preview: Option<(PathBuf, Preview)>,
+}
+
+enum Preview {
+    Image(Option<Thumbnail>),
+    /// The first lines, or `None` for a binary or unreadable file
+    Text(Option<Vec<String>>),
 }
 
 #[derive(Debug, Clone)]
@@ -86,12 +114,19 @@ impl DirEntry {
 }
 
 // --- Render Function (Public API) ---
+/// Pick a file, or several with `multi`; with `save` the name may be one
+/// that doesn't exist yet
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     path: Option<String>,
     only_dirs: bool,
     ext: Option<String>,
     height: Option<usize>,
-) -> io::Result<PathBuf> {
+    multi: bool,
+    show_hidden: bool,
+    text_preview: bool,
+    save: bool,
+) -> io::Result<Vec<PathBuf>> {
     let initial_path = path
         .map(PathBuf::from)
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
@@ -104,6 +139,13 @@ pub fn render(
     });
 
     let mut picker = FilePicker::new(initial_path, only_dirs, allowed_extensions, height)?;
+    picker.multi = multi;
+    picker.text_preview = text_preview;
+    picker.save = save;
+    if show_hidden {
+        picker.show_hidden = true;
+        picker.load_current_path_items()?;
+    }
     picker.run()
 }
 
@@ -124,6 +166,12 @@ impl FilePicker {
             allowed_extensions,
             height,
             error_message: None,
+            multi: false,
+            marked: Vec::new(),
+            show_hidden: false,
+            text_preview: false,
+            save: false,
+            preview: None,
         };
         picker.load_current_path_items()?;
         Ok(picker)
@@ -146,6 +194,9 @@ impl FilePicker {
             .filter_map(|entry| entry.ok())
             .map(|entry| DirEntry::new(entry.path()))
             .filter(|entry| {
+                if !self.show_hidden && entry.file_name().starts_with('.') {
+                    return false;
+                }
                 // Apply 'only_dirs' filter
                 if self.only_dirs && !entry.is_dir {
                     return false;
@@ -178,7 +229,9 @@ impl FilePicker {
                 .retain(|item| item.file_name().to_lowercase().contains(&filter_lower));
         }
 
-        if self.items.is_empty() && !self.filter.is_empty() {
+        if self.save {
+            // An unmatched name is a new file, not an error
+        } else if self.items.is_empty() && !self.filter.is_empty() {
             self.error_message = Some(format!("No matches for \"{}\"", self.filter));
         } else if self.items.is_empty() {
             self.error_message = Some("Current directory is empty or inaccessible.".to_string());
@@ -187,7 +240,38 @@ impl FilePicker {
         Ok(())
     }
 
-    fn run(&mut self) -> io::Result<PathBuf> {
+    /// Mark or unmark the selected entry; directories only count with
+    /// `--directory`
+    fn toggle_mark(&mut self) {
+        let Some(entry) = self.items.get(self.selected_index) else {
+            return;
+        };
+        if entry.file_name() == ".." || entry.is_dir != self.only_dirs {
+            return;
+        }
+        match self.marked.iter().position(|p| *p == entry.path) {
+            Some(i) => {
+                self.marked.remove(i);
+            }
+            None => self.marked.push(entry.path.clone()),
+        }
+    }
+
+    /// In `--save` mode, where the typed name would be saved, unless it
+    /// names a directory to open instead
+    fn save_target(&self) -> Option<PathBuf> {
+        if !self.save || self.filter.is_empty() {
+            return None;
+        }
+        let opens_dir = self
+            .items
+            .get(self.selected_index)
+            .is_some_and(|entry| entry.is_dir && entry.file_name() == self.filter);
+        (!opens_dir).then(|| self.current_path.join(&self.filter))
+    }
+
+    #[allow(clippy::collapsible_match)]
+    fn run(&mut self) -> io::Result<Vec<PathBuf>> {
         // Check for interactive terminal
         if !std::io::stdin().is_terminal() {
             return Err(io::Error::other(
@@ -195,7 +279,7 @@ impl FilePicker {
             ));
         }
 
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
 
         terminal::enable_raw_mode()?;
         execute!(stdout, EnterAlternateScreen, Hide)?;
@@ -211,11 +295,17 @@ impl FilePicker {
                         }
                     }
                     KeyCode::Down => {
-                        if self.selected_index < self.items.len() - 1 {
+                        if self.selected_index + 1 < self.items.len() {
                             self.selected_index += 1;
                         }
                     }
                     KeyCode::Enter => {
+                        if let Some(target) = self.save_target() {
+                            break Ok(vec![target]);
+                        }
+                        if !self.marked.is_empty() {
+                            break Ok(self.marked.clone());
+                        }
                         if !self.items.is_empty() {
                             let selected_entry = &self.items[self.selected_index];
                             if selected_entry.is_dir {
@@ -233,7 +323,7 @@ impl FilePicker {
                                 self.load_current_path_items()?;
                             } else {
                                 // Selected a file
-                                break Ok(selected_entry.path.clone());
+                                break Ok(vec![selected_entry.path.clone()]);
                             }
                         }
                     }
@@ -249,6 +339,11 @@ impl FilePicker {
                             }
                         }
                     }
+                    KeyCode::Char(' ') if self.multi => self.toggle_mark(),
+                    KeyCode::Char('.') if self.filter.is_empty() && !self.save => {
+                        self.show_hidden = !self.show_hidden;
+                        self.load_current_path_items()?;
+                    }
                     KeyCode::Char(c) => {
                         if key_event.modifiers.contains(KeyModifiers::CONTROL) {
                             if c == 'c' {
@@ -276,8 +371,66 @@ impl FilePicker {
         result
     }
 
-    fn draw(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
-        let (_cols, rows) = terminal::size()?;
+    /// The selected entry, when it is an image (or any file with
+    /// `--preview`) and the terminal is wide enough to show it next to
+    /// the list
+    fn preview_path(&self, cols: u16) -> Option<PathBuf> {
+        let entry = self.items.get(self.selected_index)?;
+        (cols >= PREVIEW_MIN_COLS
+            && !entry.is_dir
+            && (self.text_preview || image::is_image(&entry.path)))
+        .then(|| entry.path.clone())
+    }
+
+    /// Draw the selected file in the right half, from `top` down
+    fn draw_preview(
+        &mut self,
+        stdout: &mut color::Stdout,
+        path: PathBuf,
+        (cols, rows): (u16, u16),
+        top: u16,
+    ) -> io::Result<()> {
+        let left = cols / 2 + 1;
+        let max_cols = (cols - left).saturating_sub(1) as u32;
+        let max_rows = rows.saturating_sub(top + 3).max(1) as u32;
+        if self.preview.as_ref().map(|(p, _)| p) != Some(&path) {
+            let preview = if image::is_image(&path) {
+                Preview::Image(Thumbnail::load(&path.to_string_lossy(), max_cols, max_rows).ok())
+            } else {
+                Preview::Text(head_lines(&path, max_rows as usize))
+            };
+            self.preview = Some((path, preview));
+        }
+        match self.preview.as_ref().map(|(_, preview)| preview) {
+            Some(Preview::Image(Some(thumb))) => execute!(stdout, Print(thumb.draw_at(left, top))),
+            Some(Preview::Text(Some(lines))) => {
+                for (i, line) in lines.iter().enumerate() {
+                    execute!(
+                        stdout,
+                        MoveTo(left, top + i as u16),
+                        Print(text::truncate(line, max_cols as usize, "…"))
+                    )?;
+                }
+                Ok(())
+            }
+            _ => execute!(
+                stdout,
+                MoveTo(left, top),
+                SetForegroundColor(Color::DarkGrey),
+                Print("No preview"),
+                ResetColor
+            ),
+        }
+    }
+
+    fn draw(&mut self, stdout: &mut color::Stdout) -> io::Result<()> {
+        let (cols, rows) = screen::size();
+        let preview = self.preview_path(cols);
+        // Names stop short of the preview pane
+        let name_width = match preview {
+            Some(_) => (cols / 2) as usize - 5,
+            None => usize::MAX,
+        };
         execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
 
         // Header: Current Path
@@ -295,7 +448,7 @@ impl FilePicker {
         execute!(
             stdout,
             SetForegroundColor(FILTER_COLOR),
-            Print("Filter: "),
+            Print(if self.save { "Name: " } else { "Filter: " }),
             SetForegroundColor(Color::White),
             Print(self.filter.clone()),
             Print("_"), // Indicate cursor
@@ -342,7 +495,13 @@ impl FilePicker {
                 ICON_UNSELECTED
             };
 
-            let item_name = item.file_name();
+            let mark = match (self.multi, self.marked.contains(&item.path)) {
+                (false, _) => "",
+                (true, true) => ICON_MARKED,
+                (true, false) => ICON_UNMARKED,
+            };
+
+            let item_name = text::truncate(&item.file_name(), name_width, "…");
 
             let mut style = SetForegroundColor(FILE_COLOR);
             if item.is_dir {
@@ -353,7 +512,15 @@ impl FilePicker {
 
             execute!(
                 stdout,
-                Print(format!("{} {} ", selector, icon)),
+                Print(format!("{} ", selector)),
+                SetForegroundColor(SELECTED_COLOR),
+                Print(if mark.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} ", mark)
+                }),
+                ResetColor,
+                Print(format!("{} ", icon)),
                 style,
                 Print(item_name),
                 ResetColor,
@@ -367,15 +534,51 @@ impl FilePicker {
         }
 
         // Help text
+        let mut help = String::from(
+            "↑↓: Navigate • Enter: Select/Open • Backspace: Up/Delete Filter • Esc: Cancel",
+        );
+        if self.save {
+            help.push_str(" • Type: Name");
+        } else {
+            help.push_str(" • Type: Filter • .: Hidden files");
+        }
+        if self.multi {
+            help.push_str(&format!(" • Space: Mark ({} marked)", self.marked.len()));
+        }
         execute!(
             stdout,
             Print("\n"),
             SetForegroundColor(Color::DarkGrey),
-            Print("↑↓: Navigate • Enter: Select/Open • Backspace: Up/Delete Filter • Esc: Cancel • Type: Filter"),
+            Print(help),
             ResetColor
         )?;
 
+        if let Some(path) = preview {
+            self.draw_preview(stdout, path, (cols, rows), start_row_for_items as u16)?;
+        }
+
         stdout.flush()?;
         Ok(())
     }
 }
+
+/// The first `count` lines of a text file, tabs expanded; `None` when it
+/// can't be read or looks binary
+fn head_lines(path: &Path, count: usize) -> Option<Vec<String>> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(PREVIEW_MAX_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    if bytes.contains(&0) {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&bytes)
+            .lines()
+            .take(count)
+            .map(|line| line.replace('\t', "    "))
+            .collect(),
+    )
+}
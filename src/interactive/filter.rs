@@ -1,20 +1,57 @@
+//! Fuzzy finder over lines of stdin, ranked fzf-style
+//!
+//! A query matches an item when its characters appear in order, not
+//! necessarily together. Matches score higher when they're consecutive or
+//! start a word (after a separator, at a camelCase hump, at the start), and
+//! lower for each gap between them; ties go to the shorter item.
+
+use super::prompt;
+use super::screen::Screen;
+use crate::output::{color, watch};
+use crate::text;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
-    event::{self, Event, KeyCode, KeyEvent},
-    execute,
-    style::{Color, Print, ResetColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal,
 };
-use std::collections::HashSet;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const SCORE_MATCH: i32 = 16;
+const GAP_START: i32 = 3;
+const GAP_EXTEND: i32 = 1;
+/// A match right after a separator such as space, `/`, `-` or `_`
+const BONUS_BOUNDARY: i32 = 8;
+/// A match at the start of the item
+const BONUS_START: i32 = 10;
+/// A match on a camelCase hump or the first digit of a number
+const BONUS_CAMEL: i32 = 7;
+const BONUS_CONSECUTIVE: i32 = 4;
+/// The first query character's bonus counts this many times
+const FIRST_CHAR_MULTIPLIER: i32 = 2;
 
 pub struct FuzzyFilter {
     items: Vec<String>,
+    /// What each item shows and is matched on (`--with-nth`)
+    display: Vec<String>,
     prompt: String,
     multi: bool,
     height: Option<usize>,
+    inline: bool,
+    preview: Option<String>,
 }
 
+/// Rows around the list: prompt, blank line, blank line, count
+const CHROME_ROWS: usize = 4;
+
+/// Narrowest terminal that gets a preview pane
+const PREVIEW_MIN_COLS: usize = 40;
+
 impl FuzzyFilter {
     pub fn new(
         items: Vec<String>,
@@ -23,16 +60,40 @@ impl FuzzyFilter {
         height: Option<usize>,
     ) -> Self {
         Self {
+            display: items.clone(),
             items,
             prompt: prompt.unwrap_or_else(|| "Filter:".to_string()),
             multi,
             height,
+            inline: false,
+            preview: None,
         }
     }
 
+    /// Draw below the prompt instead of on the alternate screen
+    pub fn inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
+    /// Show and match `display` instead of the items themselves; the items
+    /// are still what gets printed
+    pub fn display(mut self, display: Vec<String>) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Run `command` on the highlighted item and show its output to the
+    /// right; `{}` in it becomes the item, else the item is appended
+    pub fn preview(mut self, command: Option<String>) -> Self {
+        self.preview = command;
+        self
+    }
+
+    #[allow(clippy::collapsible_match)]
     pub fn render(&self) -> io::Result<Vec<String>> {
-        // Check for interactive terminal
-        if !std::io::stdin().is_terminal() {
+        // Keys come from the terminal when stdin carries the items
+        if !io::stdin().is_terminal() && std::fs::File::open("/dev/tty").is_err() {
             return Err(io::Error::other(
                 "Filter requires an interactive terminal (TTY)",
             ));
@@ -42,30 +103,76 @@ impl FuzzyFilter {
             return Ok(vec![]);
         }
 
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
         let mut query = String::new();
         let mut selected_idx = 0;
         let mut selected_items: HashSet<usize> = HashSet::new();
+        // Preview output by item, so moving back and forth doesn't rerun it
+        let mut previews: HashMap<usize, Vec<String>> = HashMap::new();
+        let worker = self.preview.clone().map(PreviewWorker::spawn);
+        // The item whose preview was last asked for and hasn't come back
+        let mut pending: Option<usize> = None;
+        let loading = vec!["Loading…".to_string()];
 
         terminal::enable_raw_mode()?;
-        execute!(stdout, EnterAlternateScreen, Hide)?;
+        let list_rows = self.height.unwrap_or(10).min(self.items.len());
+        let screen = Screen::enter(&mut stdout, self.inline.then_some(list_rows + CHROME_ROWS))?;
 
         let result = loop {
-            let matches = self.filter_items(&query);
-            self.render_ui(&mut stdout, &query, &matches, selected_idx, &selected_items)?;
+            if let Some(worker) = &worker {
+                for (index, lines) in worker.results.try_iter() {
+                    previews.insert(index, lines);
+                    if pending == Some(index) {
+                        pending = None;
+                    }
+                }
+            }
+            let matches = fuzzy_rank(&self.display, &query);
+            let preview = match (&worker, matches.get(selected_idx)) {
+                (Some(worker), Some(current)) => match previews.get(&current.index) {
+                    Some(lines) => Some(lines.as_slice()),
+                    None => {
+                        if pending != Some(current.index) {
+                            worker.request(current.index, &self.items[current.index]);
+                            pending = Some(current.index);
+                        }
+                        Some(loading.as_slice())
+                    }
+                },
+                _ => None,
+            };
+            self.render_ui(
+                &mut stdout,
+                &screen,
+                &query,
+                &matches,
+                selected_idx,
+                &selected_items,
+                preview,
+            )?;
 
-            if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+            // Keys still come in while a preview runs; redraw when it's back
+            if pending.is_some() && !event::poll(Duration::from_millis(50))? {
+                continue;
+            }
+            if let Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) = event::read()?
+            {
                 match code {
-                    KeyCode::Up | KeyCode::Char('k') if !matches.is_empty() => {
+                    KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                        break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+                    }
+                    KeyCode::Up if !matches.is_empty() => {
                         selected_idx = selected_idx.saturating_sub(1);
                     }
-                    KeyCode::Down | KeyCode::Char('j') if !matches.is_empty() => {
+                    KeyCode::Down if !matches.is_empty() => {
                         if selected_idx < matches.len() - 1 {
                             selected_idx += 1;
                         }
                     }
                     KeyCode::Char(' ') if self.multi && !matches.is_empty() => {
-                        let original_idx = matches[selected_idx].0;
+                        let original_idx = matches[selected_idx].index;
                         if selected_items.contains(&original_idx) {
                             selected_items.remove(&original_idx);
                         } else {
@@ -74,13 +181,11 @@ impl FuzzyFilter {
                     }
                     KeyCode::Enter if !matches.is_empty() => {
                         if self.multi {
-                            let result: Vec<String> = selected_items
-                                .iter()
-                                .map(|&idx| self.items[idx].clone())
-                                .collect();
-                            break Ok(result);
+                            let mut picked: Vec<usize> = selected_items.iter().copied().collect();
+                            picked.sort_unstable();
+                            break Ok(picked.iter().map(|&idx| self.items[idx].clone()).collect());
                         } else {
-                            break Ok(vec![matches[selected_idx].1.clone()]);
+                            break Ok(vec![self.items[matches[selected_idx].index].clone()]);
                         }
                     }
                     KeyCode::Char(c) => {
@@ -92,42 +197,39 @@ impl FuzzyFilter {
                         selected_idx = 0;
                     }
                     KeyCode::Esc => {
-                        break Ok(vec![]);
+                        break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
                     }
                     _ => {}
                 }
             }
         };
 
-        execute!(stdout, Show, LeaveAlternateScreen)?;
+        screen.leave(&mut stdout)?;
         terminal::disable_raw_mode()?;
 
         result
     }
 
-    fn filter_items(&self, query: &str) -> Vec<(usize, String)> {
-        if query.is_empty() {
-            return self.items.iter().cloned().enumerate().collect();
-        }
-
-        let query_lower = query.to_lowercase();
-        self.items
-            .iter()
-            .enumerate()
-            .filter(|(_, item)| item.to_lowercase().contains(&query_lower))
-            .map(|(i, item)| (i, item.clone()))
-            .collect()
-    }
-
+    #[allow(clippy::too_many_arguments)]
     fn render_ui(
         &self,
-        stdout: &mut io::Stdout,
+        stdout: &mut color::Stdout,
+        screen: &Screen,
         query: &str,
-        matches: &[(usize, String)],
+        matches: &[FuzzyMatch],
         selected_idx: usize,
         selected_items: &HashSet<usize>,
+        preview: Option<&[String]>,
     ) -> io::Result<()> {
-        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        screen.clear(stdout)?;
+
+        // The list takes the left half when there's a preview to show
+        let (cols, _) = super::screen::size();
+        let cols = cols as usize;
+        let list_cols = match preview {
+            Some(_) if cols >= PREVIEW_MIN_COLS => cols / 2,
+            _ => cols,
+        };
 
         // Prompt and query
         execute!(
@@ -137,16 +239,21 @@ impl FuzzyFilter {
             Print(" "),
             ResetColor,
             Print(query),
-            Print("█\n\n")
+            Print("█")
         )?;
 
-        // Calculate visible items
-        let max_height = self.height.unwrap_or(10).min(matches.len());
+        // Calculate visible items, scrolled so the current one shows
+        let max_height = self
+            .height
+            .unwrap_or(10)
+            .min(screen.rows().saturating_sub(CHROME_ROWS).max(1))
+            .min(matches.len());
+        let first = (selected_idx + 1).saturating_sub(max_height);
 
-        // Render matches
-        for (i, (original_idx, item)) in matches.iter().take(max_height).enumerate() {
+        // Render matches, the matched characters highlighted
+        for (i, found) in matches.iter().enumerate().skip(first).take(max_height) {
             let is_current = i == selected_idx;
-            let is_selected = selected_items.contains(original_idx);
+            let is_selected = selected_items.contains(&found.index);
 
             let prefix = if self.multi {
                 if is_selected {
@@ -159,23 +266,62 @@ impl FuzzyFilter {
             };
 
             let indicator = if is_current { "❯" } else { " " };
+            let base = if is_current {
+                Color::Green
+            } else {
+                Color::Reset
+            };
+            let lead = format!("{} {} ", indicator, prefix);
 
-            execute!(
+            queue!(
                 stdout,
-                SetForegroundColor(if is_current {
-                    Color::Green
-                } else {
-                    Color::Reset
-                }),
-                Print(format!("{} {} {}\n", indicator, prefix, item)),
-                ResetColor
+                screen.goto(i - first + 2),
+                SetForegroundColor(base),
+                Print(&lead)
             )?;
+            let mut room = list_cols.saturating_sub(text::display_width(&lead) + 1);
+            for (pos, c) in self.display[found.index].chars().enumerate() {
+                let width = text::display_width(c.encode_utf8(&mut [0; 4]));
+                if width > room {
+                    break;
+                }
+                room -= width;
+                if found.positions.contains(&pos) {
+                    queue!(
+                        stdout,
+                        SetForegroundColor(Color::Yellow),
+                        SetAttribute(Attribute::Bold),
+                        Print(c),
+                        SetAttribute(Attribute::NormalIntensity),
+                        SetForegroundColor(base)
+                    )?;
+                } else {
+                    queue!(stdout, Print(c))?;
+                }
+            }
+            queue!(stdout, ResetColor)?;
+        }
+
+        // Preview to the right of a divider, as tall as the drawing area
+        if let Some(lines) = preview.filter(|_| list_cols < cols) {
+            let preview_cols = cols - list_cols - 2;
+            for row in 0..screen.rows() {
+                let line = lines.get(row).map(String::as_str).unwrap_or("");
+                queue!(
+                    stdout,
+                    screen.at(list_cols as u16, row),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print("│ "),
+                    ResetColor,
+                    Print(text::truncate(line, preview_cols, "…"))
+                )?;
+            }
         }
 
         // Show count
         execute!(
             stdout,
-            Print("\n"),
+            screen.goto(max_height + 3),
             SetForegroundColor(Color::DarkGrey),
             Print(format!("{}/{} items", matches.len(), self.items.len())),
             ResetColor
@@ -185,7 +331,286 @@ impl FuzzyFilter {
     }
 }
 
-pub fn render(prompt: Option<String>, multi: bool, height: Option<usize>) {
+/// Runs the preview command off the input thread, so a slow one doesn't
+/// hold up typing; of the items asked for while it runs, only the newest is
+/// previewed next
+struct PreviewWorker {
+    requests: mpsc::Sender<(usize, String)>,
+    results: mpsc::Receiver<(usize, Vec<String>)>,
+}
+
+impl PreviewWorker {
+    fn spawn(command: String) -> Self {
+        let (requests, inbox) = mpsc::channel::<(usize, String)>();
+        let (outbox, results) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(mut request) = inbox.recv() {
+                request = inbox.try_iter().last().unwrap_or(request);
+                let (index, item) = request;
+                if outbox.send((index, run_preview(&command, &item))).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { requests, results }
+    }
+
+    fn request(&self, index: usize, item: &str) {
+        self.requests.send((index, item.to_string())).ok();
+    }
+}
+
+/// Output of the preview command for `item`, escapes stripped and tabs
+/// expanded so it can be cut to the pane
+fn run_preview(command: &str, item: &str) -> Vec<String> {
+    let quoted = format!("'{}'", item.replace('\'', r"'\''"));
+    let command = if command.contains("{}") {
+        command.replace("{}", &quoted)
+    } else {
+        format!("{} {}", command, quoted)
+    };
+    let output = watch::exec_command(&command).unwrap_or_else(|e| e);
+    output
+        .lines()
+        .map(|line| text::strip_ansi(line).replace('\t', "    "))
+        .collect()
+}
+
+/// An item the query matched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Position of the item in the list
+    pub index: usize,
+    pub score: i32,
+    /// Character positions in the item that matched the query, in order
+    pub positions: Vec<usize>,
+}
+
+/// Bonus for matching `c` when `prev` comes before it
+fn bonus(prev: Option<char>, c: char) -> i32 {
+    match prev {
+        None => BONUS_START,
+        Some(p) if !p.is_alphanumeric() && c.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(p) if p.is_lowercase() && c.is_uppercase() => BONUS_CAMEL,
+        Some(p) if !p.is_ascii_digit() && c.is_ascii_digit() => BONUS_CAMEL,
+        _ => 0,
+    }
+}
+
+/// Lowercase a character, keeping it one character so positions line up
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Score `item` against `query` (case-insensitively) and find where the
+/// query's characters matched; `None` when they don't all appear in order
+pub fn fuzzy_score(item: &str, query: &str) -> Option<(i32, Vec<usize>)> {
+    let query: Vec<char> = query.chars().map(fold).collect();
+    let chars: Vec<char> = item.chars().collect();
+    let lower: Vec<char> = chars.iter().copied().map(fold).collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    // Cheap check first: most items don't match at all
+    let mut rest = lower.iter();
+    if !query.iter().all(|q| rest.any(|c| c == q)) {
+        return None;
+    }
+
+    let (m, n) = (query.len(), chars.len());
+    let bonuses: Vec<i32> = (0..n)
+        .map(|j| bonus(j.checked_sub(1).map(|p| chars[p]), chars[j]))
+        .collect();
+    // score[i][j]: best score for query[..=i] with query[i] matched at j,
+    // and from[i][j] where query[i - 1] matched on that best path
+    let mut score = vec![vec![None::<i32>; n]; m];
+    let mut from = vec![vec![0usize; n]; m];
+    for i in 0..m {
+        // Best match of query[i - 1] with a gap between it and j, less the
+        // gap's penalty
+        let mut gapped: Option<(i32, usize)> = None;
+        for j in i..n {
+            if i > 0 && j >= 2 {
+                let extended = gapped.map(|(s, k)| (s - GAP_EXTEND, k));
+                let opened = score[i - 1][j - 2].map(|s| (s - GAP_START, j - 2));
+                gapped = extended.into_iter().chain(opened).max_by_key(|&(s, _)| s);
+            }
+            if lower[j] != query[i] {
+                continue;
+            }
+            let best = if i == 0 {
+                Some((SCORE_MATCH + bonuses[j] * FIRST_CHAR_MULTIPLIER, j))
+            } else {
+                let consecutive = score[i - 1][j - 1]
+                    .map(|s| (s + SCORE_MATCH + bonuses[j].max(BONUS_CONSECUTIVE), j - 1));
+                let after_gap = gapped.map(|(s, k)| (s + SCORE_MATCH + bonuses[j], k));
+                consecutive
+                    .into_iter()
+                    .chain(after_gap)
+                    .max_by_key(|&(s, _)| s)
+            };
+            if let Some((s, k)) = best {
+                score[i][j] = Some(s);
+                from[i][j] = k;
+            }
+        }
+    }
+
+    let (best, mut j) = (0..n)
+        .filter_map(|j| score[m - 1][j].map(|s| (s, j)))
+        .max_by_key(|&(s, j)| (s, std::cmp::Reverse(j)))?;
+    let mut positions = vec![j; m];
+    for i in (1..m).rev() {
+        j = from[i][j];
+        positions[i - 1] = j;
+    }
+    Some((best, positions))
+}
+
+/// Items matching `query`, best first; ties go to the shorter item, then
+/// the earlier one. An empty query keeps every item in order.
+pub fn fuzzy_rank(items: &[String], query: &str) -> Vec<FuzzyMatch> {
+    let mut matches: Vec<FuzzyMatch> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            fuzzy_score(item, query).map(|(score, positions)| FuzzyMatch {
+                index,
+                score,
+                positions,
+            })
+        })
+        .collect();
+    if !query.is_empty() {
+        matches.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.score),
+                items[m.index].chars().count(),
+                m.index,
+            )
+        });
+    }
+    matches
+}
+
+/// Fuzzy match of `query` against `items`, best first, returning (original
+/// index, item)
+pub fn fuzzy_match(items: &[String], query: &str) -> Vec<(usize, String)> {
+    fuzzy_rank(items, query)
+        .into_iter()
+        .map(|m| (m.index, items[m.index].clone()))
+        .collect()
+}
+
+/// Which fields of a line to show and match (`--with-nth`): 1-based,
+/// negative counting from the end, with `N..M` ranges open on either side
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpec {
+    ranges: Vec<(Option<i64>, Option<i64>)>,
+}
+
+impl FieldSpec {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid --with-nth '{}' (use N, N.., ..N or N..M, comma-separated)",
+                spec
+            )
+        };
+        let index = |s: &str| -> Result<Option<i64>, String> {
+            match s.trim() {
+                "" => Ok(None),
+                n => match n.parse::<i64>() {
+                    Ok(0) | Err(_) => Err(invalid()),
+                    Ok(n) => Ok(Some(n)),
+                },
+            }
+        };
+        let ranges = spec
+            .split(',')
+            .map(|part| match part.split_once("..") {
+                Some((from, to)) => Ok((index(from)?, index(to)?)),
+                None => match index(part)? {
+                    Some(n) => Ok((Some(n), Some(n))),
+                    None => Err(invalid()),
+                },
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { ranges })
+    }
+
+    /// The chosen fields of `line`, split at `delimiter` and kept with the
+    /// delimiters between them
+    pub fn apply(&self, line: &str, delimiter: &Regex) -> String {
+        // Each field with the delimiter that follows it
+        let mut fields: Vec<(&str, &str)> = Vec::new();
+        let mut start = 0;
+        for found in delimiter.find_iter(line) {
+            if found.end() == 0 {
+                continue;
+            }
+            fields.push((&line[start..found.start()], found.as_str()));
+            start = found.end();
+        }
+        fields.push((&line[start..], ""));
+
+        let len = fields.len() as i64;
+        let resolve = |n: i64| if n < 0 { len + n } else { n - 1 };
+        let mut chosen: Vec<usize> = Vec::new();
+        for &(from, to) in &self.ranges {
+            let from = from.map_or(0, resolve).max(0);
+            let to = to.map_or(len - 1, resolve).min(len - 1);
+            chosen.extend((from..=to).map(|i| i as usize));
+        }
+
+        // The last field has no delimiter of its own to carry
+        let fallback = fields
+            .iter()
+            .map(|&(_, after)| after)
+            .find(|after| !after.is_empty())
+            .unwrap_or(" ");
+        let mut out = String::new();
+        for (pos, &i) in chosen.iter().enumerate() {
+            out.push_str(fields[i].0);
+            if pos + 1 < chosen.len() {
+                out.push_str(
+                    Some(fields[i].1)
+                        .filter(|d| !d.is_empty())
+                        .unwrap_or(fallback),
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Pick lines from stdin; `with_nth` shows and matches only some fields of
+/// each (split at the `delimiter` regex, whitespace by default) while the
+/// whole line is printed
+pub fn render(
+    prompt: Option<String>,
+    multi: bool,
+    height: Option<usize>,
+    inline: bool,
+    preview: Option<String>,
+    delimiter: Option<String>,
+    with_nth: Option<String>,
+) {
+    let fields = match with_nth.as_deref().map(FieldSpec::parse).transpose() {
+        Ok(fields) => fields,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let splitter = match Regex::new(delimiter.as_deref().unwrap_or(r"\s+")) {
+        Ok(splitter) => splitter,
+        Err(e) => {
+            eprintln!("Error: Invalid --delimiter: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Read from stdin
     let stdin = io::stdin();
     let items: Vec<String> = stdin.lock().lines().map_while(Result::ok).collect();
@@ -195,7 +620,22 @@ pub fn render(prompt: Option<String>, multi: bool, height: Option<usize>) {
         std::process::exit(1);
     }
 
-    let filter = FuzzyFilter::new(items, prompt, multi, height);
+    let display = match &fields {
+        // Whitespace-split fields ignore the indentation, as in awk
+        Some(fields) if delimiter.is_none() => items
+            .iter()
+            .map(|item| fields.apply(item.trim_start(), &splitter))
+            .collect(),
+        Some(fields) => items
+            .iter()
+            .map(|item| fields.apply(item, &splitter))
+            .collect(),
+        None => items.clone(),
+    };
+    let filter = FuzzyFilter::new(items, prompt, multi, height)
+        .inline(inline)
+        .display(display)
+        .preview(preview);
 
     match filter.render() {
         Ok(selected) => {
@@ -203,9 +643,71 @@ pub fn render(prompt: Option<String>, multi: bool, height: Option<usize>) {
                 println!("{}", item);
             }
         }
+        Err(e) if prompt::is_cancel(&e) => prompt::exit_cancelled(),
         Err(e) => {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score_needs_characters_in_order() {
+        assert_eq!(fuzzy_score("foobar", "fb").unwrap().1, vec![0, 3]);
+        assert_eq!(fuzzy_score("FooBar", "fB").unwrap().1, vec![0, 3]);
+        assert!(fuzzy_score("foobar", "bf").is_none());
+        assert_eq!(fuzzy_score("anything", ""), Some((0, Vec::new())));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts_and_runs() {
+        // "src/main.rs" matches "mr" at word starts, beating a buried match
+        let (boundary, positions) = fuzzy_score("src/main.rs", "mr").unwrap();
+        assert_eq!(positions, vec![4, 9]);
+        let (buried, _) = fuzzy_score("summary", "mr").unwrap();
+        assert!(boundary > buried);
+
+        let (run, _) = fuzzy_score("xbarx", "bar").unwrap();
+        let (spread, _) = fuzzy_score("xbxaxr", "bar").unwrap();
+        assert!(run > spread);
+    }
+
+    #[test]
+    fn test_fuzzy_rank_orders_by_score_then_length() {
+        let list = items(&["foobar", "fb_thing", "nope", "fb"]);
+        let ranked: Vec<usize> = fuzzy_rank(&list, "fb").iter().map(|m| m.index).collect();
+        assert_eq!(ranked, vec![3, 1, 0]);
+        assert_eq!(fuzzy_rank(&list, "").len(), 4);
+    }
+
+    #[test]
+    fn test_field_spec() {
+        let ws = Regex::new(r"\s+").unwrap();
+        let line = "a1b2c3d4 pid 42 /usr/bin/app";
+        assert_eq!(FieldSpec::parse("2").unwrap().apply(line, &ws), "pid");
+        assert_eq!(
+            FieldSpec::parse("3..").unwrap().apply(line, &ws),
+            "42 /usr/bin/app"
+        );
+        assert_eq!(
+            FieldSpec::parse("-1,1").unwrap().apply(line, &ws),
+            "/usr/bin/app a1b2c3d4"
+        );
+        let colon = Regex::new(":").unwrap();
+        assert_eq!(
+            FieldSpec::parse("..2").unwrap().apply("root:x:0:0", &colon),
+            "root:x"
+        );
+        for bad in ["0", "x", "1..y", ""] {
+            assert!(FieldSpec::parse(bad).is_err(), "{}", bad);
+        }
+    }
+}
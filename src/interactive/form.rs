@@ -1,3 +1,7 @@
+use super::input::Validator;
+use super::output_format::{self, OutputFormat};
+use super::textarea::{self, TextArea};
+use crate::output::color;
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
     event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
@@ -7,15 +11,14 @@ use crossterm::{
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::{
-    fs,
-    io::{self, IsTerminal, Write},
-};
+use std::io::{self, IsTerminal, Write};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FieldType {
     Text,
+    /// Multi-line text, edited in a text area
+    Textarea,
     Password,
     Select,
     Multiselect,
@@ -33,6 +36,51 @@ pub struct Field {
     pub options: Vec<String>,
     #[serde(default)]
     pub value: String,
+    /// The answer can't be left empty
+    #[serde(default)]
+    pub required: bool,
+    /// Rule a non-empty answer must pass, as `input --validate` takes it
+    #[serde(default)]
+    pub validate: Option<String>,
+    /// Bounds for `"validate": "number"`
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Ask this field only when an earlier one got a certain answer
+    #[serde(default)]
+    pub when: Option<When>,
+}
+
+/// Condition on an earlier field's answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct When {
+    pub field: String,
+    /// The answer, or a list of answers any of which will do; a
+    /// multiselect matches when any of its picks does
+    pub equals: Answers,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answers {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl When {
+    fn matches(&self, answer: &str, field_type: &FieldType) -> bool {
+        let wanted = match &self.equals {
+            Answers::One(value) => std::slice::from_ref(value),
+            Answers::Any(values) => values.as_slice(),
+        };
+        match field_type {
+            FieldType::Multiselect => answer
+                .split(',')
+                .any(|pick| wanted.iter().any(|w| w == pick)),
+            _ => wanted.iter().any(|w| w == answer),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -44,19 +92,26 @@ pub struct Form {
     fields: Vec<Field>,
     current_field: usize,
     values: HashMap<String, String>,
+    /// Each field's parsed `validate` rule
+    validators: Vec<Option<Validator>>,
+    /// Why the last answer was turned down
+    error: Option<String>,
 }
 
 impl Form {
     pub fn new(fields: Vec<Field>) -> Self {
         Self {
+            validators: vec![None; fields.len()],
             fields,
             current_field: 0,
             values: HashMap::new(),
+            error: None,
         }
     }
 
+    /// Load the config from a JSON file, or from stdin for `-`
     pub fn from_config_file(path: &str) -> io::Result<Self> {
-        let content = fs::read_to_string(path)?;
+        let content = crate::output::input::read_text(path)?;
         let config: FormConfig = serde_json::from_str(&content)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
         Ok(Self::new(config.fields))
@@ -77,6 +132,7 @@ impl Form {
         let name = parts[0].to_string();
         let field_type = match parts[1].to_lowercase().as_str() {
             "text" => FieldType::Text,
+            "textarea" => FieldType::Textarea,
             "password" => FieldType::Password,
             "select" => FieldType::Select,
             "multiselect" => FieldType::Multiselect,
@@ -102,18 +158,22 @@ impl Form {
             label,
             options,
             value: String::new(),
+            required: false,
+            validate: None,
+            min: None,
+            max: None,
+            when: None,
         })
     }
 
-    pub fn run(&mut self, output_format: &str) -> io::Result<String> {
-        // Check for interactive terminal
+    pub fn run(&mut self, output_format: OutputFormat, no_tty_error: bool) -> io::Result<String> {
+        self.check_rules()?;
         if !std::io::stdin().is_terminal() {
-            return Err(io::Error::other(
-                "Form requires an interactive terminal (TTY)",
-            ));
+            self.answer_without_tty(no_tty_error)?;
+            return Ok(output_format.format(&self.answers()));
         }
 
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
         terminal::enable_raw_mode()?;
         execute!(stdout, EnterAlternateScreen, Hide)?;
 
@@ -123,12 +183,155 @@ impl Form {
         terminal::disable_raw_mode()?;
 
         match result {
-            Ok(_) => self.format_output(output_format),
+            Ok(_) => Ok(output_format.format(&self.answers())),
             Err(e) => Err(e),
         }
     }
 
-    fn run_form(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+    /// Parse each field's `validate` rule and make sure every `when` names
+    /// an earlier field
+    fn check_rules(&mut self) -> io::Result<()> {
+        let invalid = |name: &str, e: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Field '{}': {}", name, e),
+            )
+        };
+        for (idx, field) in self.fields.iter().enumerate() {
+            self.validators[idx] = field
+                .validate
+                .as_deref()
+                .map(|spec| Validator::parse(spec, field.min, field.max))
+                .transpose()
+                .map_err(|e| invalid(&field.name, e))?;
+            if let Some(when) = &field.when {
+                if !self.fields[..idx].iter().any(|f| f.name == when.field) {
+                    return Err(invalid(
+                        &field.name,
+                        format!("'when' needs an earlier field, not '{}'", when.field),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a field is asked, given the answers so far
+    fn is_shown(&self, idx: usize) -> bool {
+        let Some(when) = &self.fields[idx].when else {
+            return true;
+        };
+        let Some(source) = self.fields[..idx].iter().position(|f| f.name == when.field) else {
+            return false;
+        };
+        self.is_shown(source)
+            && self
+                .values
+                .get(&when.field)
+                .is_some_and(|answer| when.matches(answer, &self.fields[source].field_type))
+    }
+
+    /// Why `value` can't be a field's answer, if it can't
+    fn check_answer(&self, idx: usize, value: &str) -> Result<(), String> {
+        if value.trim().is_empty() {
+            return if self.fields[idx].required {
+                Err("an answer is required".to_string())
+            } else {
+                Ok(())
+            };
+        }
+        match &self.validators[idx] {
+            Some(validator) => validator.check(value),
+            None => Ok(()),
+        }
+    }
+
+    /// The first shown field whose answer is missing or doesn't pass
+    fn first_unanswered(&self) -> Option<(usize, String)> {
+        (0..self.fields.len())
+            .filter(|&idx| self.is_shown(idx))
+            .find_map(|idx| {
+                let value = self
+                    .values
+                    .get(&self.fields[idx].name)
+                    .map_or("", String::as_str);
+                self.check_answer(idx, value).err().map(|e| (idx, e))
+            })
+    }
+
+    fn next_shown(&self, from: usize) -> Option<usize> {
+        (from + 1..self.fields.len()).find(|&idx| self.is_shown(idx))
+    }
+
+    fn previous_shown(&self, from: usize) -> Option<usize> {
+        (0..from).rev().find(|&idx| self.is_shown(idx))
+    }
+
+    /// Forget answers to fields that are no longer asked
+    fn drop_hidden(&mut self) {
+        let hidden: Vec<String> = (0..self.fields.len())
+            .filter(|&idx| !self.is_shown(idx))
+            .map(|idx| self.fields[idx].name.clone())
+            .collect();
+        for name in hidden {
+            self.values.remove(&name);
+        }
+    }
+
+    /// Fill each shown field with its default `value`; fields without one
+    /// fail the form, or stay empty with `no_tty_error`. Defaults are held
+    /// to the same rules as typed answers.
+    fn answer_without_tty(&mut self, no_tty_error: bool) -> io::Result<()> {
+        let mut missing: Vec<&str> = Vec::new();
+        for idx in 0..self.fields.len() {
+            if !self.is_shown(idx) {
+                continue;
+            }
+            let field = &self.fields[idx];
+            if field.value.is_empty() && !no_tty_error {
+                missing.push(field.name.as_str());
+                continue;
+            }
+            if let Err(e) = self.check_answer(idx, &field.value) {
+                return Err(io::Error::other(format!(
+                    "Invalid --default for {}: {}",
+                    field.name, e
+                )));
+            }
+            self.values.insert(field.name.clone(), field.value.clone());
+        }
+        if !missing.is_empty() {
+            return Err(io::Error::other(format!(
+                "Form requires an interactive terminal (TTY); pass --default for: {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Set field defaults from `NAME=VALUE` pairs
+    pub fn set_defaults(&mut self, defaults: &[String]) -> io::Result<()> {
+        for default in defaults {
+            let Some((name, value)) = default.split_once('=') else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid --default '{}' (use NAME=VALUE)", default),
+                ));
+            };
+            match self.fields.iter_mut().find(|field| field.name == name) {
+                Some(field) => field.value = value.to_string(),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("--default for unknown field '{}'", name),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_form(&mut self, stdout: &mut color::Stdout) -> io::Result<()> {
         loop {
             self.render(stdout)?;
 
@@ -139,26 +342,34 @@ impl Form {
                 match code {
                     KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
                         // Shift+Tab - previous field
-                        if self.current_field > 0 {
-                            self.current_field -= 1;
+                        if let Some(idx) = self.previous_shown(self.current_field) {
+                            self.current_field = idx;
                         }
                     }
                     KeyCode::Tab => {
                         // Tab - next field
-                        if self.current_field < self.fields.len() - 1 {
-                            self.current_field += 1;
+                        if let Some(idx) = self.next_shown(self.current_field) {
+                            self.current_field = idx;
                         }
                     }
                     KeyCode::Enter => {
                         // Enter - handle current field
                         let current_field = &self.fields[self.current_field].clone();
                         let value = self.handle_field_input(stdout, current_field)?;
+                        if let Err(e) = self.check_answer(self.current_field, &value) {
+                            self.error = Some(format!("{}: {}", current_field.label, e));
+                            continue;
+                        }
+                        self.error = None;
                         self.values.insert(current_field.name.clone(), value);
 
-                        // Move to next field or finish
-                        if self.current_field < self.fields.len() - 1 {
-                            self.current_field += 1;
-                        } else {
+                        // Move to the next field, or review once all are answered
+                        if let Some(idx) = self.next_shown(self.current_field) {
+                            self.current_field = idx;
+                        } else if let Some((idx, e)) = self.first_unanswered() {
+                            self.error = Some(format!("{}: {}", self.fields[idx].label, e));
+                            self.current_field = idx;
+                        } else if self.review(stdout)? {
                             break;
                         }
                     }
@@ -170,10 +381,63 @@ impl Form {
             }
         }
 
+        self.drop_hidden();
         Ok(())
     }
 
-    fn render(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+    /// Show every answer before it's printed: Enter submits, Esc goes back
+    /// to the fields
+    fn review(&self, stdout: &mut color::Stdout) -> io::Result<bool> {
+        execute!(
+            stdout,
+            Clear(ClearType::All),
+            MoveTo(0, 0),
+            SetForegroundColor(Color::Cyan),
+            Print("📋 Review\n\n".bold()),
+            ResetColor
+        )?;
+        for (idx, field) in self.fields.iter().enumerate() {
+            if !self.is_shown(idx) {
+                continue;
+            }
+            let value = self.values.get(&field.name).cloned().unwrap_or_default();
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Green),
+                Print("✓ "),
+                ResetColor,
+                Print(format!("{}: ", field.label).bold()),
+                Print(display_value(field, &value)),
+                Print("\r\n")
+            )?;
+        }
+        execute!(
+            stdout,
+            Print("\n"),
+            SetForegroundColor(Color::DarkGrey),
+            Print("Enter: Submit • Esc: Back"),
+            ResetColor
+        )?;
+        stdout.flush()?;
+
+        loop {
+            if let Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) = event::read()?
+            {
+                match code {
+                    KeyCode::Enter => return Ok(true),
+                    KeyCode::Esc => return Ok(false),
+                    KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn render(&self, stdout: &mut color::Stdout) -> io::Result<()> {
         execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
 
         // Title
@@ -186,28 +450,32 @@ impl Form {
 
         // Render fields
         for (idx, field) in self.fields.iter().enumerate() {
+            if !self.is_shown(idx) {
+                continue;
+            }
             let is_current = idx == self.current_field;
             let has_value = self.values.contains_key(&field.name);
 
             let indicator = if is_current { "❯" } else { " " };
             let status = if has_value { "✓" } else { " " };
 
-            let field_display = if is_current {
-                field.label.clone().bold().to_string()
+            let label = if field.required {
+                format!("{} *", field.label)
             } else {
                 field.label.clone()
             };
-
-            let value_display = if let Some(value) = self.values.get(&field.name) {
-                if matches!(field.field_type, FieldType::Password) {
-                    "********".to_string()
-                } else {
-                    value.clone()
-                }
+            let field_display = if is_current {
+                label.bold().to_string()
             } else {
-                String::new()
+                label
             };
 
+            let value_display = self
+                .values
+                .get(&field.name)
+                .map(|value| display_value(field, value))
+                .unwrap_or_default();
+
             execute!(
                 stdout,
                 SetForegroundColor(if is_current {
@@ -231,6 +499,16 @@ impl Form {
             execute!(stdout, Print("\n"))?;
         }
 
+        if let Some(error) = &self.error {
+            execute!(
+                stdout,
+                Print("\n"),
+                SetForegroundColor(Color::Red),
+                Print(format!("✗ {}\n", error)),
+                ResetColor
+            )?;
+        }
+
         // Help text
         execute!(
             stdout,
@@ -244,7 +522,7 @@ impl Form {
         Ok(())
     }
 
-    fn handle_field_input(&self, stdout: &mut io::Stdout, field: &Field) -> io::Result<String> {
+    fn handle_field_input(&self, stdout: &mut color::Stdout, field: &Field) -> io::Result<String> {
         execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
 
         match &field.field_type {
@@ -252,6 +530,13 @@ impl Form {
                 let is_password = matches!(field.field_type, FieldType::Password);
                 self.input_field(stdout, &field.label, is_password)
             }
+            FieldType::Textarea => {
+                let initial = self.values.get(&field.name).unwrap_or(&field.value);
+                let mut area = TextArea::new(initial, 5);
+                let text = textarea::edit(stdout, &mut area, 0, &field.label);
+                execute!(stdout, Hide)?;
+                text
+            }
             FieldType::Number => {
                 let value = self.input_field(stdout, &field.label, false)?;
                 // Validate number
@@ -285,9 +570,10 @@ impl Form {
         }
     }
 
+    #[allow(clippy::collapsible_match)]
     fn input_field(
         &self,
-        stdout: &mut io::Stdout,
+        stdout: &mut color::Stdout,
         label: &str,
         password: bool,
     ) -> io::Result<String> {
@@ -343,9 +629,10 @@ impl Form {
         Ok(input)
     }
 
+    #[allow(clippy::collapsible_match)]
     fn select_field(
         &self,
-        stdout: &mut io::Stdout,
+        stdout: &mut color::Stdout,
         label: &str,
         options: &[String],
         multi: bool,
@@ -443,7 +730,7 @@ impl Form {
         }
     }
 
-    fn confirm_field(&self, stdout: &mut io::Stdout, label: &str) -> io::Result<bool> {
+    fn confirm_field(&self, stdout: &mut color::Stdout, label: &str) -> io::Result<bool> {
         execute!(
             stdout,
             SetForegroundColor(Color::Cyan),
@@ -481,37 +768,37 @@ impl Form {
         }
     }
 
-    fn format_output(&self, format: &str) -> io::Result<String> {
-        match format.to_lowercase().as_str() {
-            "json" => serde_json::to_string_pretty(&self.values)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-            "env" => {
-                let mut output = String::new();
-                for (key, value) in &self.values {
-                    output.push_str(&format!("{}={}\n", key.to_uppercase(), value));
-                }
-                Ok(output.trim_end().to_string())
-            }
-            "csv" => {
-                let mut output = String::new();
-                for (key, value) in &self.values {
-                    output.push_str(&format!("{},{}\n", key, value));
-                }
-                Ok(output.trim_end().to_string())
-            }
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("Unknown output format: {}", format),
-            )),
-        }
+    /// Answers in field order
+    fn answers(&self) -> Vec<(String, String)> {
+        self.fields
+            .iter()
+            .filter_map(|f| Some((f.name.clone(), self.values.get(&f.name)?.clone())))
+            .collect()
+    }
+}
+
+/// An answer as the form shows it: passwords hidden, line breaks marked
+fn display_value(field: &Field, value: &str) -> String {
+    match field.field_type {
+        FieldType::Password => "********".to_string(),
+        FieldType::Textarea => value.replace('\n', " ↵ "),
+        _ => value.to_string(),
     }
 }
 
+/// Run the form and print its answers; without a TTY print the defaults
+/// (`--default NAME=VALUE` or a config field's `value`) instead
 pub fn render(
     field_args: Vec<String>,
     config: Option<String>,
     output_format: String,
+    export_shell: bool,
+    output_file: Option<String>,
+    defaults: Vec<String>,
+    no_tty_error: bool,
 ) -> io::Result<()> {
+    let format = OutputFormat::parse(&output_format, export_shell)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     let mut form = if let Some(config_path) = config {
         Form::from_config_file(&config_path)?
     } else {
@@ -519,11 +806,8 @@ pub fn render(
         Form::new(fields?)
     };
 
-    match form.run(&output_format) {
-        Ok(output) => {
-            println!("{}", output);
-            Ok(())
-        }
-        Err(e) => Err(e),
-    }
+    form.set_defaults(&defaults)?;
+
+    let output = form.run(format, no_tty_error)?;
+    output_format::emit(&output, output_file.as_deref())
 }
@@ -1,79 +1,520 @@
+use super::prompt;
+use crate::output::calendar;
+use crate::output::color;
+use crate::text;
 use crossterm::{
     cursor,
     event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
-    style::{Color, Print, ResetColor, SetForegroundColor},
+    style::{Color, Print, ResetColor, SetForegroundColor, Stylize},
     terminal::{self, ClearType},
 };
-use std::io::{self, IsTerminal, Write};
+use regex::Regex;
+use std::io::{self, Write};
 
-pub fn render(prompt: &str, placeholder: Option<&str>, password: bool) {
-    match run_input(prompt, placeholder, password) {
-        Ok(input) => {
-            println!("{}", input);
+/// Structure enforced on the value while it is typed
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputFormat {
+    /// `#` digit, `A` letter, `*` letter or digit; anything else is a literal
+    /// inserted automatically
+    Mask(String),
+    /// Dotted quad, octets 0-255
+    Ipv4,
+    /// MAJOR.MINOR.PATCH with optional `-prerelease` / `+build`
+    Semver,
+    /// Number-unit pairs in d, h, m, s order, e.g. `1h30m`
+    Duration,
+    /// YYYY-MM-DD
+    Date,
+}
+
+const DATE_MASK: &str = "####-##-##";
+const DURATION_UNITS: [char; 4] = ['d', 'h', 'm', 's'];
+
+impl InputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "ipv4" | "ip" => Some(InputFormat::Ipv4),
+            "semver" | "version" => Some(InputFormat::Semver),
+            "duration" => Some(InputFormat::Duration),
+            "date" => Some(InputFormat::Date),
+            _ => None,
         }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+    }
+
+    /// Value after typing `c`, or `None` when `c` doesn't fit here
+    fn push(&self, value: &str, c: char) -> Option<String> {
+        match self {
+            InputFormat::Mask(mask) => mask_push(mask, value, c),
+            InputFormat::Ipv4 => ipv4_push(value, c),
+            InputFormat::Semver => semver_push(value, c),
+            InputFormat::Duration => duration_push(value, c),
+            InputFormat::Date => mask_push(DATE_MASK, value, c).filter(|next| date_prefix_ok(next)),
+        }
+    }
+
+    /// Value after backspace; separators that were inserted go with the
+    /// character before them
+    fn pop(&self, value: &str) -> String {
+        let mut out = value.to_string();
+        while out
+            .chars()
+            .last()
+            .is_some_and(|c| self.is_separator(&out, c))
+        {
+            out.pop();
+        }
+        out.pop();
+        out
+    }
+
+    fn is_separator(&self, value: &str, c: char) -> bool {
+        match self {
+            InputFormat::Mask(mask) => mask
+                .chars()
+                .nth(value.chars().count() - 1)
+                .is_some_and(|m| !is_slot(m)),
+            InputFormat::Date => c == '-',
+            InputFormat::Ipv4 => c == '.',
+            InputFormat::Semver | InputFormat::Duration => false,
+        }
+    }
+
+    /// Dimmed hint for what remains to be typed
+    fn ghost(&self, value: &str) -> String {
+        match self {
+            InputFormat::Mask(mask) => mask_ghost(mask, value),
+            InputFormat::Date => mask_ghost("YYYY-MM-DD", value),
+            InputFormat::Ipv4 => dotted_ghost(value, 4),
+            InputFormat::Semver => {
+                if value.contains(['-', '+']) {
+                    String::new()
+                } else {
+                    dotted_ghost(value, 3)
+                }
+            }
+            InputFormat::Duration => match value.chars().last() {
+                None => "1h30m".to_string(),
+                Some(c) if c.is_ascii_digit() => next_duration_unit(value)
+                    .map(String::from)
+                    .unwrap_or_default(),
+                Some(_) => String::new(),
+            },
+        }
+    }
+
+    /// Whether the finished value can be submitted
+    fn check(&self, value: &str) -> Result<(), String> {
+        let complete = match self {
+            InputFormat::Mask(mask) => value.chars().count() == mask.chars().count(),
+            InputFormat::Ipv4 => {
+                let octets: Vec<&str> = value.split('.').collect();
+                octets.len() == 4 && octets.iter().all(|o| !o.is_empty())
+            }
+            InputFormat::Semver => {
+                let (core, extra) = match value.find(['-', '+']) {
+                    Some(idx) => (&value[..idx], &value[idx + 1..]),
+                    None => (value, "x"),
+                };
+                let parts: Vec<&str> = core.split('.').collect();
+                parts.len() == 3 && parts.iter().all(|p| !p.is_empty()) && !extra.is_empty()
+            }
+            InputFormat::Duration => value.chars().last().is_some_and(|c| c.is_alphabetic()),
+            InputFormat::Date => value.len() == DATE_MASK.len() && date_prefix_ok(value),
+        };
+        if complete {
+            Ok(())
+        } else {
+            Err(format!("expected {}", self.describe()))
         }
     }
+
+    fn describe(&self) -> String {
+        match self {
+            InputFormat::Mask(mask) => mask.clone(),
+            InputFormat::Ipv4 => "an IPv4 address".to_string(),
+            InputFormat::Semver => "a version like 1.2.3".to_string(),
+            InputFormat::Duration => "a duration like 1h30m".to_string(),
+            InputFormat::Date => "a date as YYYY-MM-DD".to_string(),
+        }
+    }
+}
+
+fn is_slot(m: char) -> bool {
+    matches!(m, '#' | 'A' | '*')
 }
 
-fn run_input(prompt: &str, placeholder: Option<&str>, password: bool) -> io::Result<String> {
-    // Check for interactive terminal
-    if !std::io::stdin().is_terminal() {
-        return Err(io::Error::other(
-            "Input requires an interactive terminal (TTY)",
-        ));
+fn slot_accepts(m: char, c: char) -> bool {
+    match m {
+        '#' => c.is_ascii_digit(),
+        'A' => c.is_alphabetic(),
+        '*' => c.is_alphanumeric(),
+        _ => false,
     }
+}
 
-    let mut stdout = io::stdout();
-    let mut input = String::new();
+fn mask_push(mask: &str, value: &str, c: char) -> Option<String> {
+    let slots: Vec<char> = mask.chars().collect();
+    let mut out = value.to_string();
+    let mut pos = value.chars().count();
 
-    // Enable raw mode for character-by-character input
-    terminal::enable_raw_mode()?;
+    // Literals not yet inserted (a mask that starts with one); typing the
+    // literal itself just steps over it
+    while pos < slots.len() && !is_slot(slots[pos]) {
+        out.push(slots[pos]);
+        pos += 1;
+        if slots[pos - 1] == c {
+            return Some(out);
+        }
+    }
+    if pos >= slots.len() || !slot_accepts(slots[pos], c) {
+        return None;
+    }
+    out.push(c);
+    pos += 1;
+    while pos < slots.len() && !is_slot(slots[pos]) {
+        out.push(slots[pos]);
+        pos += 1;
+    }
+    Some(out)
+}
 
-    // Display the prompt
-    execute!(
-        stdout,
-        SetForegroundColor(Color::Cyan),
-        Print(prompt),
-        Print(" "),
-        ResetColor
-    )?;
+fn mask_ghost(mask: &str, value: &str) -> String {
+    mask.chars()
+        .skip(value.chars().count())
+        .map(|m| if is_slot(m) { '_' } else { m })
+        .collect()
+}
 
-    // Display placeholder if provided and input is empty
-    if let Some(placeholder_text) = placeholder {
-        execute!(
-            stdout,
-            SetForegroundColor(Color::DarkGrey),
-            Print(placeholder_text),
-            ResetColor
-        )?;
+/// Remaining `.0` groups for a value of `parts` dot-separated numbers
+fn dotted_ghost(value: &str, parts: usize) -> String {
+    if value.is_empty() {
+        return vec!["0"; parts].join(".");
+    }
+    let typed = value.split('.').count();
+    let mut ghost = if value.ends_with('.') {
+        "0".to_string()
+    } else {
+        String::new()
+    };
+    for _ in typed..parts {
+        ghost.push_str(".0");
+    }
+    ghost
+}
+
+fn ipv4_push(value: &str, c: char) -> Option<String> {
+    let octets = value.split('.').count();
+    let current = value.rsplit('.').next().unwrap_or("");
+    match c {
+        '.' if !current.is_empty() && octets < 4 => Some(format!("{}.", value)),
+        '0'..='9' => {
+            let fits = current != "0"
+                && current.len() < 3
+                && format!("{}{}", current, c).parse::<u16>().unwrap_or(256) <= 255;
+            let mut out = if fits {
+                value.to_string()
+            } else if octets < 4 {
+                format!("{}.", value)
+            } else {
+                return None;
+            };
+            out.push(c);
+            // Advance once no further digit could fit this octet
+            let octet = out.rsplit('.').next().unwrap_or("");
+            let full = octet == "0" || octet.len() == 3 || octet.parse::<u16>().unwrap_or(0) > 25;
+            if full && out.split('.').count() < 4 {
+                out.push('.');
+            }
+            Some(out)
+        }
+        _ => None,
+    }
+}
+
+fn semver_push(value: &str, c: char) -> Option<String> {
+    if let Some(idx) = value.find(['-', '+']) {
+        // Prerelease / build identifiers
+        let tail = &value[idx..];
+        let ok = c.is_ascii_alphanumeric()
+            || c == '.'
+            || (c == '-' && !tail.ends_with('+'))
+            || (c == '+' && !tail.contains('+') && !tail.ends_with('-'));
+        return ok.then(|| format!("{}{}", value, c));
+    }
+    let parts: Vec<&str> = value.split('.').collect();
+    let current = parts.last().copied().unwrap_or("");
+    let ok = match c {
+        '0'..='9' => current != "0",
+        '.' => !current.is_empty() && parts.len() < 3,
+        '-' | '+' => !current.is_empty() && parts.len() == 3,
+        _ => false,
+    };
+    ok.then(|| format!("{}{}", value, c))
+}
+
+fn next_duration_unit(value: &str) -> Option<char> {
+    let last = value
+        .chars()
+        .rfind(|c| c.is_alphabetic())
+        .and_then(|u| DURATION_UNITS.iter().position(|d| *d == u));
+    match last {
+        Some(idx) => DURATION_UNITS.get(idx + 1).copied(),
+        None => Some('s'),
+    }
+}
+
+fn duration_push(value: &str, c: char) -> Option<String> {
+    let c = c.to_ascii_lowercase();
+    let ends_with_digit = value.chars().last().is_some_and(|l| l.is_ascii_digit());
+    if c.is_ascii_digit() {
+        // A finished `...s` can't take more numbers
+        let done = value.ends_with('s');
+        return (!done).then(|| format!("{}{}", value, c));
+    }
+    let rank = DURATION_UNITS.iter().position(|d| *d == c)?;
+    let last_rank = value
+        .chars()
+        .rfind(|c| c.is_alphabetic())
+        .and_then(|u| DURATION_UNITS.iter().position(|d| *d == u));
+    let ordered = last_rank.is_none_or(|last| rank > last);
+    (ends_with_digit && ordered).then(|| format!("{}{}", value, c))
+}
+
+/// Whether a partial YYYY-MM-DD can still become a real date
+fn date_prefix_ok(value: &str) -> bool {
+    let digits =
+        |range: std::ops::Range<usize>| value.get(range).and_then(|s| s.parse::<u32>().ok());
+    let month_ok = match (digits(5..6), digits(5..7)) {
+        (_, Some(month)) => (1..=12).contains(&month),
+        (Some(tens), None) => tens <= 1,
+        _ => true,
+    };
+    let day_ok = match (digits(8..9), digits(8..10)) {
+        (_, Some(day)) => {
+            let (year, month) = (digits(0..4).unwrap_or(2000), digits(5..7).unwrap_or(1));
+            day >= 1 && day <= calendar::days_in_month(year as i64, month)
+        }
+        (Some(tens), None) => tens <= 3,
+        _ => true,
+    };
+    month_ok && day_ok
+}
+
+/// Names accepted by `--validate`
+pub const VALIDATORS: &str = "regex:<pattern>, number, email, nonempty";
+
+/// Rule the submitted value must pass; unlike a format it's only checked
+/// on Enter
+#[derive(Debug, Clone)]
+pub enum Validator {
+    Regex(Regex),
+    /// A number, optionally within bounds
+    Number {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Email,
+    NonEmpty,
+}
+
+impl Validator {
+    /// Parse a `--validate` value; `min` and `max` bound a `number`
+    pub fn parse(spec: &str, min: Option<f64>, max: Option<f64>) -> Result<Self, String> {
+        let validator = match spec.split_once(':') {
+            Some(("regex", pattern)) => {
+                // Anchored so the whole value has to match
+                let regex = Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|e| format!("Invalid --validate regex: {}", e))?;
+                Validator::Regex(regex)
+            }
+            None if spec == "number" => Validator::Number { min, max },
+            None if spec == "email" => Validator::Email,
+            None if spec == "nonempty" => Validator::NonEmpty,
+            _ => return Err(format!("Invalid validator '{}' (use {})", spec, VALIDATORS)),
+        };
+        if (min.is_some() || max.is_some()) && !matches!(validator, Validator::Number { .. }) {
+            return Err("--min and --max need --validate number".to_string());
+        }
+        Ok(validator)
+    }
+
+    /// Why `value` doesn't pass, if it doesn't
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            Validator::Regex(regex) if !regex.is_match(value) => {
+                // Show the pattern as it was given, without the anchors
+                let pattern = regex.as_str();
+                Err(format!("must match {}", &pattern[4..pattern.len() - 2]))
+            }
+            Validator::Number { min, max } => match value.trim().parse::<f64>() {
+                Err(_) => Err("must be a number".to_string()),
+                Ok(n) if min.is_some_and(|min| n < min) => {
+                    Err(format!("must be at least {}", min.unwrap_or_default()))
+                }
+                Ok(n) if max.is_some_and(|max| n > max) => {
+                    Err(format!("must be at most {}", max.unwrap_or_default()))
+                }
+                Ok(_) => Ok(()),
+            },
+            Validator::Email => {
+                let valid = value.split_once('@').is_some_and(|(user, domain)| {
+                    !user.is_empty()
+                        && !domain.contains('@')
+                        && domain.split('.').count() > 1
+                        && domain.split('.').all(|part| !part.is_empty())
+                        && !value.contains(char::is_whitespace)
+                });
+                if valid {
+                    Ok(())
+                } else {
+                    Err("must be an email address".to_string())
+                }
+            }
+            Validator::NonEmpty if value.trim().is_empty() => Err("can't be empty".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Everything an answer is held to: its format while typing, a length cap,
+/// and a validator on Enter
+#[derive(Debug, Clone, Default)]
+pub struct InputRules {
+    pub format: Option<InputFormat>,
+    pub validate: Option<Validator>,
+    pub char_limit: Option<usize>,
+}
+
+impl InputRules {
+    /// Value after typing `c`, or `None` when it doesn't fit
+    fn push(&self, value: &str, c: char) -> Option<String> {
+        if self
+            .char_limit
+            .is_some_and(|limit| value.chars().count() >= limit)
+        {
+            return None;
+        }
+        match &self.format {
+            Some(f) => f.push(value, c),
+            None => Some(format!("{}{}", value, c)),
+        }
+    }
+
+    fn pop(&self, value: &str) -> String {
+        match &self.format {
+            Some(f) => f.pop(value),
+            None => {
+                let mut out = value.to_string();
+                out.pop();
+                out
+            }
+        }
+    }
+
+    /// Whether the value can be submitted
+    fn check(&self, value: &str) -> Result<(), String> {
+        if let Some(format) = &self.format {
+            format.check(value)?;
+        }
+        if let Some(limit) = self.char_limit.filter(|&l| value.chars().count() > l) {
+            return Err(format!("at most {} characters", limit));
+        }
+        match &self.validate {
+            Some(validator) => validator.check(value),
+            None => Ok(()),
+        }
+    }
+
+    /// `value` as if typed key by key, so it fits the format and limit
+    fn typed(&self, value: &str) -> String {
+        value.chars().fold(String::new(), |typed, c| {
+            self.push(&typed, c).unwrap_or(typed)
+        })
+    }
+}
 
-        // Move cursor back to start of placeholder
-        let placeholder_width = placeholder_text.chars().count();
-        for _ in 0..placeholder_width {
-            execute!(stdout, cursor::MoveLeft(1))?;
+/// Prompt for a line, starting from `value`, and print it; without a TTY
+/// print `default` instead, which must pass `rules` like a typed answer
+pub fn render(
+    prompt_text: &str,
+    placeholder: Option<&str>,
+    password: bool,
+    rules: &InputRules,
+    value: Option<&str>,
+    default: Option<&str>,
+    no_tty_error: bool,
+) {
+    let result = match prompt::answer_without_tty("Input", default, no_tty_error) {
+        Ok(Some(answer)) if answer.is_empty() && default.is_none() => Ok(answer),
+        Ok(Some(answer)) => match rules.check(&answer) {
+            Err(e) => Err(io::Error::other(format!("Invalid --default: {}", e))),
+            Ok(()) => Ok(answer),
+        },
+        Ok(None) => run_input(
+            prompt_text,
+            placeholder,
+            password,
+            rules,
+            &rules.typed(value.unwrap_or("")),
+        ),
+        Err(e) => Err(io::Error::other(e)),
+    };
+    match result {
+        Ok(input) => {
+            println!("{}", input);
+        }
+        Err(e) if prompt::is_cancel(&e) => prompt::exit_cancelled(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
         }
     }
+}
 
-    stdout.flush()?;
+fn run_input(
+    prompt: &str,
+    placeholder: Option<&str>,
+    password: bool,
+    rules: &InputRules,
+    value: &str,
+) -> io::Result<String> {
+    let mut stdout = color::stdout();
+    let mut input = value.to_string();
+    let mut error: Option<String> = None;
+
+    // Enable raw mode for character-by-character input
+    terminal::enable_raw_mode()?;
 
     let result = loop {
+        draw(
+            &mut stdout,
+            prompt,
+            &input,
+            placeholder,
+            password,
+            rules,
+            error.as_deref(),
+        )?;
+
         // Read keyboard events
         if let Event::Key(key_event) = event::read()? {
+            error = None;
             match key_event {
-                // Enter key - submit input
+                // Enter key - submit input once it passes the rules
                 KeyEvent {
                     code: KeyCode::Enter,
                     ..
-                } => {
-                    break Ok(input.clone());
-                }
+                } => match rules.check(&input) {
+                    Err(e) => error = Some(e),
+                    Ok(()) => break Ok(input.clone()),
+                },
 
-                // Ctrl+C - cancel
+                // Esc or Ctrl+C - cancel
                 KeyEvent {
+                    code: KeyCode::Esc, ..
+                }
+                | KeyEvent {
                     code: KeyCode::Char('c'),
                     modifiers: KeyModifiers::CONTROL,
                     ..
@@ -89,34 +530,7 @@ fn run_input(prompt: &str, placeholder: Option<&str>, password: bool) -> io::Res
                     code: KeyCode::Backspace,
                     ..
                 } => {
-                    if !input.is_empty() {
-                        input.pop();
-
-                        // Move cursor back, clear character, move back again
-                        execute!(
-                            stdout,
-                            cursor::MoveLeft(1),
-                            terminal::Clear(ClearType::UntilNewLine)
-                        )?;
-
-                        // Redisplay placeholder if input is now empty
-                        if input.is_empty() {
-                            if let Some(placeholder_text) = placeholder {
-                                execute!(
-                                    stdout,
-                                    SetForegroundColor(Color::DarkGrey),
-                                    Print(placeholder_text),
-                                    ResetColor
-                                )?;
-
-                                // Move cursor back to start
-                                let placeholder_width = placeholder_text.chars().count();
-                                for _ in 0..placeholder_width {
-                                    execute!(stdout, cursor::MoveLeft(1))?;
-                                }
-                            }
-                        }
-                    }
+                    input = rules.pop(&input);
                 }
 
                 // Regular character input
@@ -125,18 +539,8 @@ fn run_input(prompt: &str, placeholder: Option<&str>, password: bool) -> io::Res
                     modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
                     ..
                 } => {
-                    // If this is the first character, clear placeholder
-                    if input.is_empty() && placeholder.is_some() {
-                        execute!(stdout, terminal::Clear(ClearType::UntilNewLine))?;
-                    }
-
-                    input.push(c);
-
-                    // Display character (or mask if password mode)
-                    if password {
-                        execute!(stdout, Print('*'))?;
-                    } else {
-                        execute!(stdout, Print(c))?;
+                    if let Some(next) = rules.push(&input, c) {
+                        input = next;
                     }
                 }
 
@@ -144,14 +548,244 @@ fn run_input(prompt: &str, placeholder: Option<&str>, password: bool) -> io::Res
                     // Ignore other keys
                 }
             }
-
-            stdout.flush()?;
         }
     };
 
-    // Disable raw mode and move to new line
+    // Disable raw mode, wipe any error line and move to a new line
     terminal::disable_raw_mode()?;
-    execute!(stdout, Print("\n"))?;
+    execute!(
+        stdout,
+        cursor::MoveToColumn(0),
+        terminal::Clear(ClearType::FromCursorDown),
+        Print(prompt_line(prompt, &input, password, rules)),
+        Print("\n")
+    )?;
 
     result
 }
+
+/// The prompt and the value as shown, passwords masked
+fn prompt_line(prompt: &str, input: &str, password: bool, rules: &InputRules) -> String {
+    let shown: String = if password {
+        // Literals a mask inserted stay visible so the structure is clear;
+        // everything typed is hidden
+        let mask = match &rules.format {
+            Some(InputFormat::Mask(mask)) => Some(mask.as_str()),
+            Some(InputFormat::Date) => Some(DATE_MASK),
+            _ => None,
+        };
+        input
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match mask.and_then(|m| m.chars().nth(i)) {
+                Some(m) if !is_slot(m) => c,
+                _ => '*',
+            })
+            .collect()
+    } else {
+        input.to_string()
+    };
+    format!("{} {}", prompt.with(Color::Cyan), shown)
+}
+
+/// Redraw the prompt line with the value, then the ghost or placeholder
+/// after the cursor and the character count; an error goes on the line below
+fn draw(
+    stdout: &mut color::Stdout,
+    prompt: &str,
+    input: &str,
+    placeholder: Option<&str>,
+    password: bool,
+    rules: &InputRules,
+    error: Option<&str>,
+) -> io::Result<()> {
+    let hint = match (input.is_empty(), placeholder) {
+        (true, Some(text)) => text.to_string(),
+        _ => rules
+            .format
+            .as_ref()
+            .map(|f| f.ghost(input))
+            .unwrap_or_default(),
+    };
+    let counter = rules
+        .char_limit
+        .map(|limit| format!("  {}/{}", input.chars().count(), limit))
+        .unwrap_or_default();
+
+    execute!(
+        stdout,
+        cursor::MoveToColumn(0),
+        terminal::Clear(ClearType::FromCursorDown),
+        Print(prompt_line(prompt, input, password, rules)),
+        SetForegroundColor(Color::DarkGrey),
+        Print(&hint),
+        Print(&counter),
+        ResetColor
+    )?;
+    if let Some(error) = error {
+        execute!(
+            stdout,
+            Print("\r\n"),
+            SetForegroundColor(Color::Red),
+            Print(format!("  ✗ {}", error)),
+            ResetColor,
+            cursor::MoveUp(1)
+        )?;
+    }
+
+    // Park the cursor right after the typed value
+    let column = text::display_width(prompt) + 1 + text::display_width(input);
+    execute!(stdout, cursor::MoveToColumn(column as u16))?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_all(format: &InputFormat, keys: &str) -> String {
+        keys.chars().fold(String::new(), |value, c| {
+            format.push(&value, c).unwrap_or(value)
+        })
+    }
+
+    #[test]
+    fn test_mask_inserts_literals_and_rejects_wrong_kinds() {
+        let ssn = InputFormat::Mask("###-##-####".to_string());
+        assert_eq!(type_all(&ssn, "123"), "123-");
+        assert_eq!(type_all(&ssn, "12x3-45"), "123-45-");
+        assert_eq!(type_all(&ssn, "1234567890"), "123-45-6789");
+        assert_eq!(ssn.ghost("123-4"), "_-____");
+        assert_eq!(ssn.pop("123-"), "12");
+        assert!(ssn.check("123-45-678").is_err());
+        assert!(ssn.check("123-45-6789").is_ok());
+    }
+
+    #[test]
+    fn test_mask_with_leading_literal() {
+        let phone = InputFormat::Mask("(###) ###".to_string());
+        assert_eq!(type_all(&phone, "555"), "(555) ");
+        assert_eq!(type_all(&phone, "(5"), "(5");
+    }
+
+    #[test]
+    fn test_ipv4_advances_octets() {
+        let ip = InputFormat::Ipv4;
+        assert_eq!(type_all(&ip, "192168"), "192.168.");
+        assert_eq!(type_all(&ip, "10.0"), "10.0.");
+        assert_eq!(type_all(&ip, "256"), "25.6");
+        assert_eq!(type_all(&ip, "1.2.3.4.5"), "1.2.3.45");
+        assert_eq!(ip.ghost("192.168."), "0.0");
+        assert_eq!(ip.pop("192.168."), "192.16");
+        assert!(ip.check("10.0.0").is_err());
+        assert!(ip.check("10.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn test_semver_parts_and_prerelease() {
+        let v = InputFormat::Semver;
+        assert_eq!(type_all(&v, "1.2.3.4"), "1.2.34");
+        assert_eq!(type_all(&v, "01.2"), "0.2");
+        assert_eq!(type_all(&v, "1.0.0-rc.1"), "1.0.0-rc.1");
+        assert_eq!(v.ghost("1."), "0.0");
+        assert!(v.check("1.2").is_err());
+        assert!(v.check("1.0.0-").is_err());
+        assert!(v.check("1.0.0+build.5").is_ok());
+    }
+
+    #[test]
+    fn test_duration_units_in_order() {
+        let d = InputFormat::Duration;
+        assert_eq!(type_all(&d, "1h30m"), "1h30m");
+        assert_eq!(type_all(&d, "30mh"), "30m");
+        assert_eq!(type_all(&d, "h5s9"), "5s");
+        assert_eq!(d.ghost("1h30"), "m");
+        assert_eq!(d.ghost("45"), "s");
+        assert!(d.check("90").is_err());
+        assert!(d.check("90s").is_ok());
+    }
+
+    #[test]
+    fn test_date_validates_month_and_day() {
+        let date = InputFormat::Date;
+        assert_eq!(type_all(&date, "20241"), "2024-1");
+        assert_eq!(type_all(&date, "20242"), "2024-");
+        assert_eq!(type_all(&date, "2024130"), "2024-10-");
+        assert_eq!(type_all(&date, "20240229"), "2024-02-29");
+        assert_eq!(type_all(&date, "20230229"), "2023-02-2");
+        assert_eq!(date.ghost("2024-0"), "M-DD");
+        assert_eq!(date.pop("2024-"), "202");
+        assert!(date.check("2024-02-29").is_ok());
+    }
+
+    #[test]
+    fn test_validators() {
+        let code = Validator::parse("regex:[A-Z]{3}", None, None).unwrap();
+        assert!(code.check("ABC").is_ok());
+        assert_eq!(code.check("ABCD"), Err("must match [A-Z]{3}".to_string()));
+
+        let port = Validator::parse("number", Some(1.0), Some(65535.0)).unwrap();
+        assert!(port.check("8080").is_ok());
+        assert_eq!(port.check("http"), Err("must be a number".to_string()));
+        assert_eq!(port.check("0"), Err("must be at least 1".to_string()));
+        assert_eq!(
+            port.check("70000"),
+            Err("must be at most 65535".to_string())
+        );
+
+        let email = Validator::parse("email", None, None).unwrap();
+        assert!(email.check("dev@example.com").is_ok());
+        for bad in ["dev", "@example.com", "dev@example", "dev@.com", "a b@c.de"] {
+            assert!(email.check(bad).is_err(), "{}", bad);
+        }
+
+        let required = Validator::parse("nonempty", None, None).unwrap();
+        assert!(required.check("  ").is_err());
+
+        assert!(Validator::parse("phone", None, None).is_err());
+        assert!(Validator::parse("regex:(", None, None).is_err());
+        assert!(Validator::parse("email", Some(1.0), None).is_err());
+    }
+
+    #[test]
+    fn test_rules_cap_length_and_prefill() {
+        let rules = InputRules {
+            char_limit: Some(3),
+            ..InputRules::default()
+        };
+        assert_eq!(rules.push("abc", 'd'), None);
+        assert_eq!(rules.typed("abcdef"), "abc");
+
+        let masked = InputRules {
+            format: Some(InputFormat::Mask("##-##".to_string())),
+            ..InputRules::default()
+        };
+        assert_eq!(masked.typed("1234"), "12-34");
+        assert!(masked.check("12-3").is_err());
+    }
+
+    #[test]
+    fn test_password_hides_everything_typed() {
+        let plain = InputRules::default();
+        assert!(prompt_line(">", "p@ss-w0rd!", true, &plain).ends_with(" **********"));
+        assert!(prompt_line(">", "p@ss", false, &plain).ends_with(" p@ss"));
+
+        let ssn = InputRules {
+            format: Some(InputFormat::Mask("###-##-####".to_string())),
+            ..InputRules::default()
+        };
+        assert!(prompt_line(">", "123-45-6", true, &ssn).ends_with(" ***-**-*"));
+        let ipv4 = InputRules {
+            format: Some(InputFormat::Ipv4),
+            ..InputRules::default()
+        };
+        assert!(prompt_line(">", "10.0.", true, &ipv4).ends_with(" *****"));
+    }
+
+    #[test]
+    fn test_format_from_name() {
+        assert_eq!(InputFormat::from_name("IPv4"), Some(InputFormat::Ipv4));
+        assert_eq!(InputFormat::from_name("semver"), Some(InputFormat::Semver));
+        assert_eq!(InputFormat::from_name("email"), None);
+    }
+}
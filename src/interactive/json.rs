@@ -0,0 +1,634 @@
+use crate::interactive::clipboard;
+use crate::output::color;
+use crate::output::tree::{get_depth_color, TreeChars};
+use crate::output::{input, query};
+use crate::text;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use owo_colors::OwoColorize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
+
+/// Path of the document root
+const ROOT: &str = ".";
+
+/// One visible line of the tree
+struct Row {
+    path: String,
+    line: String,
+    container: bool,
+}
+
+/// A node in document order, used for search
+struct Node {
+    path: String,
+    ancestors: Vec<String>,
+    text: String,
+}
+
+/// Interactive collapsible JSON tree
+pub struct JsonViewer {
+    root: Value,
+    title: String,
+    expanded: HashSet<String>,
+    cursor: usize,
+    scroll: usize,
+    query: String,
+    searching: bool,
+    status: Option<String>,
+}
+
+impl JsonViewer {
+    pub fn new(root: Value, title: String) -> Self {
+        let mut expanded = HashSet::new();
+        expanded.insert(ROOT.to_string());
+        Self {
+            root,
+            title,
+            expanded,
+            cursor: 0,
+            scroll: 0,
+            query: String::new(),
+            searching: false,
+            status: None,
+        }
+    }
+
+    /// Expand every object and array
+    pub fn expand_all(&mut self) {
+        let mut paths = Vec::new();
+        container_paths(&self.root, ROOT, &mut paths);
+        self.expanded.extend(paths);
+    }
+
+    /// Collapse everything below the root
+    pub fn collapse_all(&mut self) {
+        self.expanded.clear();
+        self.expanded.insert(ROOT.to_string());
+        self.cursor = 0;
+    }
+
+    /// Currently visible lines (without selection marker)
+    pub fn lines(&self) -> Vec<String> {
+        self.rows().into_iter().map(|r| r.line).collect()
+    }
+
+    /// Path of the selected node, in jq syntax
+    pub fn selected_path(&self) -> String {
+        self.rows()
+            .get(self.cursor)
+            .map(|r| r.path.clone())
+            .unwrap_or_else(|| ROOT.to_string())
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        let chars = TreeChars::unicode();
+        self.collect_rows(&self.root, ROOT, Label::Root, "", "", 0, &chars, &mut rows);
+        rows
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn collect_rows(
+        &self,
+        value: &Value,
+        path: &str,
+        label: Label,
+        prefix: &str,
+        connector: &str,
+        depth: usize,
+        chars: &TreeChars,
+        rows: &mut Vec<Row>,
+    ) {
+        let color = get_depth_color(depth);
+        let container = matches!(value, Value::Object(_) | Value::Array(_));
+        let open = container && self.expanded.contains(path);
+
+        let marker = match (container, open) {
+            (true, true) => "▾",
+            (true, false) => "▸",
+            _ => "•",
+        };
+        let name = match &label {
+            Label::Root => self.title.clone(),
+            Label::Key(key) => key.to_string(),
+            Label::Index(i) => format!("[{}]", i),
+        };
+        let detail = if container {
+            format!(" {}", summary(value)).bright_black().to_string()
+        } else {
+            format!(": {}", scalar(value))
+        };
+        rows.push(Row {
+            path: path.to_string(),
+            line: format!(
+                "{}{}{} {}{}",
+                prefix.style(color),
+                connector.style(color),
+                marker.style(color),
+                name.style(color).bold(),
+                detail
+            ),
+            container,
+        });
+
+        if !open {
+            return;
+        }
+        let children = children(value, path);
+        let child_prefix = match label {
+            Label::Root => String::new(),
+            _ if connector == chars.last => format!("{}{}", prefix, chars.space),
+            _ => format!("{}{}", prefix, chars.vertical),
+        };
+        let count = children.len();
+        for (i, (child_path, child_label, child)) in children.into_iter().enumerate() {
+            let child_connector = if i + 1 == count {
+                chars.last
+            } else {
+                chars.branch
+            };
+            self.collect_rows(
+                child,
+                &child_path,
+                child_label,
+                &child_prefix,
+                child_connector,
+                depth + 1,
+                chars,
+                rows,
+            );
+        }
+    }
+
+    fn toggle(&mut self) {
+        let rows = self.rows();
+        if let Some(row) = rows.get(self.cursor) {
+            if row.container && !self.expanded.remove(&row.path) {
+                self.expanded.insert(row.path.clone());
+            }
+        }
+    }
+
+    fn expand(&mut self) {
+        let rows = self.rows();
+        if let Some(row) = rows.get(self.cursor) {
+            if row.container {
+                self.expanded.insert(row.path.clone());
+            }
+        }
+    }
+
+    /// Collapse the selected node, or move to its parent if already collapsed
+    fn collapse(&mut self) {
+        let rows = self.rows();
+        let Some(row) = rows.get(self.cursor) else {
+            return;
+        };
+        if row.container && self.expanded.contains(&row.path) {
+            self.expanded.remove(&row.path);
+            return;
+        }
+        let nodes = nodes(&self.root);
+        let parent = nodes
+            .iter()
+            .find(|n| n.path == row.path)
+            .and_then(|n| n.ancestors.last().cloned());
+        if let Some(parent) = parent {
+            if let Some(idx) = rows.iter().position(|r| r.path == parent) {
+                self.cursor = idx;
+            }
+        }
+    }
+
+    /// Jump to the next (or previous) node matching the query, expanding its ancestors
+    fn find(&mut self, forward: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        let query = self.query.to_lowercase();
+        let nodes = nodes(&self.root);
+        let current = self.selected_path();
+        let start = nodes.iter().position(|n| n.path == current).unwrap_or(0);
+        let count = nodes.len();
+
+        let found = (1..=count)
+            .map(|step| {
+                if forward {
+                    (start + step) % count
+                } else {
+                    (start + count - step % count) % count
+                }
+            })
+            .find(|&idx| nodes[idx].text.to_lowercase().contains(&query));
+
+        match found {
+            Some(idx) => {
+                let node = &nodes[idx];
+                self.expanded.extend(node.ancestors.iter().cloned());
+                if let Some(pos) = self.rows().iter().position(|r| r.path == node.path) {
+                    self.cursor = pos;
+                }
+                self.status = Some(format!("Match: {}", node.path));
+            }
+            None => self.status = Some(format!("No match for '{}'", self.query)),
+        }
+    }
+
+    fn copy_path(&mut self) {
+        let path = self.selected_path();
+        self.status = Some(if clipboard::copy(&path) {
+            format!("✓ Copied {}", path)
+        } else {
+            format!("Path: {} (no clipboard tool found)", path)
+        });
+    }
+
+    /// Handle a key press; returns false when the viewer should close
+    fn handle_key(&mut self, code: KeyCode, page: usize) -> bool {
+        if self.searching {
+            match code {
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.query.clear();
+                }
+                KeyCode::Enter => {
+                    self.searching = false;
+                    self.find(true);
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Char(c) => self.query.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        self.status = None;
+        let last = self.rows().len().saturating_sub(1);
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::PageUp => self.cursor = self.cursor.saturating_sub(page),
+            KeyCode::PageDown => self.cursor = (self.cursor + page).min(last),
+            KeyCode::Home | KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::End | KeyCode::Char('G') => self.cursor = last,
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle(),
+            KeyCode::Right | KeyCode::Char('l') => self.expand(),
+            KeyCode::Left | KeyCode::Char('h') => self.collapse(),
+            KeyCode::Char('e') => self.expand_all(),
+            KeyCode::Char('E') => self.collapse_all(),
+            KeyCode::Char('/') => {
+                self.searching = true;
+                self.query.clear();
+            }
+            KeyCode::Char('n') => self.find(true),
+            KeyCode::Char('N') => self.find(false),
+            KeyCode::Char('y') | KeyCode::Char('c') => self.copy_path(),
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            _ => {}
+        }
+        true
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut stdout = color::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, Hide)?;
+
+        let result = (|| -> io::Result<()> {
+            loop {
+                let (_, rows) = screen_size()?;
+                let page = rows.saturating_sub(2).max(1);
+                self.render_ui(&mut stdout, page)?;
+
+                if let Event::Key(KeyEvent { code, kind, .. }) = event::read()? {
+                    if kind != KeyEventKind::Release && !self.handle_key(code, page) {
+                        return Ok(());
+                    }
+                }
+            }
+        })();
+
+        execute!(stdout, Show, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn render_ui(&mut self, stdout: &mut color::Stdout, page: usize) -> io::Result<()> {
+        let (cols, _) = screen_size()?;
+        let rows = self.rows();
+        self.cursor = self.cursor.min(rows.len().saturating_sub(1));
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + page {
+            self.scroll = self.cursor + 1 - page;
+        }
+
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        let header = format!("─── {} ", self.title);
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Cyan),
+            Print(text::truncate(&header, cols, "…")),
+            Print("─".repeat(cols.saturating_sub(text::display_width(&header)))),
+            ResetColor,
+            Print("\r\n")
+        )?;
+
+        for (i, row) in rows.iter().enumerate().skip(self.scroll).take(page) {
+            let marker = if i == self.cursor {
+                "❯ ".cyan().bold().to_string()
+            } else {
+                "  ".to_string()
+            };
+            execute!(
+                stdout,
+                Print(marker),
+                Print(text::truncate(&row.line, cols.saturating_sub(2), "…")),
+                Print("\r\n")
+            )?;
+        }
+        for _ in rows.len().saturating_sub(self.scroll).min(page)..page {
+            execute!(stdout, Print("~\r\n"))?;
+        }
+
+        let footer = if self.searching {
+            format!("/{}", self.query)
+        } else if let Some(status) = &self.status {
+            status.clone()
+        } else {
+            format!(
+                "{} | Enter:toggle | ←/→:collapse/expand | /:search n/N | y:copy path | e/E:all | q:quit",
+                self.selected_path()
+            )
+        };
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            Print(text::truncate(&footer, cols, "…")),
+            ResetColor
+        )?;
+        stdout.flush()
+    }
+}
+
+/// Terminal size, falling back to 80x24 when the terminal reports zero
+fn screen_size() -> io::Result<(usize, usize)> {
+    let (cols, rows) = terminal::size()?;
+    Ok(match (cols, rows) {
+        (0, _) | (_, 0) => (80, 24),
+        (c, r) => (c as usize, r as usize),
+    })
+}
+
+enum Label<'a> {
+    Root,
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Child nodes with their paths and labels
+fn children<'a>(value: &'a Value, path: &str) -> Vec<(String, Label<'a>, &'a Value)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| (key_path(path, k), Label::Key(k.as_str()), v))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (index_path(path, i), Label::Index(i), v))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Append an object key to a jq-style path
+pub fn key_path(parent: &str, key: &str) -> String {
+    let base = if parent == ROOT { "" } else { parent };
+    let plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{}.{}", base, key)
+    } else {
+        format!("{}[{}]", base, Value::String(key.to_string()))
+    }
+}
+
+/// Append an array index to a jq-style path
+pub fn index_path(parent: &str, index: usize) -> String {
+    let base = if parent == ROOT { "" } else { parent };
+    format!("{}[{}]", base, index)
+}
+
+fn container_paths(value: &Value, path: &str, out: &mut Vec<String>) {
+    if matches!(value, Value::Object(_) | Value::Array(_)) {
+        out.push(path.to_string());
+        for (child_path, _, child) in children(value, path) {
+            container_paths(child, &child_path, out);
+        }
+    }
+}
+
+/// All nodes in document order with their ancestors and searchable text
+fn nodes(root: &Value) -> Vec<Node> {
+    fn walk(
+        value: &Value,
+        path: String,
+        text: String,
+        ancestors: Vec<String>,
+        out: &mut Vec<Node>,
+    ) {
+        let text = match value {
+            Value::Object(_) | Value::Array(_) => text,
+            Value::String(s) => format!("{} {}", text, s),
+            other => format!("{} {}", text, other),
+        };
+        out.push(Node {
+            path: path.clone(),
+            ancestors: ancestors.clone(),
+            text,
+        });
+        let mut next = ancestors;
+        next.push(path.clone());
+        for (child_path, label, child) in children(value, &path) {
+            let text = match label {
+                Label::Key(k) => k.to_string(),
+                _ => String::new(),
+            };
+            walk(child, child_path, text, next.clone(), out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(root, ROOT.to_string(), String::new(), Vec::new(), &mut out);
+    out
+}
+
+fn summary(value: &Value) -> String {
+    match value {
+        Value::Object(map) => format!("{{{} {}}}", map.len(), plural(map.len(), "key")),
+        Value::Array(items) => format!("[{} {}]", items.len(), plural(items.len(), "item")),
+        _ => String::new(),
+    }
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(_) => value.to_string().green().to_string(),
+        Value::Number(n) => n.yellow().to_string(),
+        Value::Bool(b) => b.magenta().to_string(),
+        _ => "null".bright_black().to_string(),
+    }
+}
+
+/// View a JSON file (or `-` for stdin) as a collapsible tree
+///
+/// Without a terminal on stdout the fully expanded tree is printed instead.
+/// `query` (a jq filter) reshapes the document before it is shown.
+pub fn render(file: &str, query: Option<&str>) {
+    let text = match input::read_text(file) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let root: Value = match serde_json::from_str(text.trim()) {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!(
+                "Error: Invalid JSON in {}: {}",
+                input::display_name(file),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    let root = match query {
+        Some(q) => query::apply(root, q).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }),
+        None => root,
+    };
+
+    let mut viewer = JsonViewer::new(root, input::display_name(file).to_string());
+    if !io::stdout().is_terminal() {
+        viewer.expand_all();
+        for line in viewer.lines() {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    if let Err(e) = viewer.run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plain(lines: &[String]) -> Vec<String> {
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        lines
+            .iter()
+            .map(|l| ansi.replace_all(l, "").to_string())
+            .collect()
+    }
+
+    fn sample() -> JsonViewer {
+        JsonViewer::new(
+            json!({"name": "termgfx", "tags": ["cli", "tui"], "meta": {"stars": 42}}),
+            "data.json".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_paths_use_jq_syntax() {
+        assert_eq!(key_path(ROOT, "users"), ".users");
+        assert_eq!(index_path(".users", 0), ".users[0]");
+        assert_eq!(key_path(".a", "my key"), ".a[\"my key\"]");
+        assert_eq!(key_path(".a", "1st"), ".a[\"1st\"]");
+    }
+
+    #[test]
+    fn test_root_starts_expanded_one_level() {
+        let lines = plain(&sample().lines());
+        assert_eq!(lines[0], "▾ data.json {3 keys}");
+        assert!(lines.iter().any(|l| l.contains("▸ tags [2 items]")));
+        assert!(!lines.iter().any(|l| l.contains("cli")));
+    }
+
+    #[test]
+    fn test_toggle_expands_and_collapses() {
+        let mut viewer = sample();
+        let idx = plain(&viewer.lines())
+            .iter()
+            .position(|l| l.contains("tags"))
+            .unwrap();
+        viewer.cursor = idx;
+        viewer.toggle();
+        assert!(plain(&viewer.lines())
+            .iter()
+            .any(|l| l.contains("[0]: \"cli\"")));
+        viewer.toggle();
+        assert!(!plain(&viewer.lines()).iter().any(|l| l.contains("cli")));
+    }
+
+    #[test]
+    fn test_search_expands_to_match() {
+        let mut viewer = sample();
+        viewer.query = "stars".to_string();
+        viewer.find(true);
+        assert_eq!(viewer.selected_path(), ".meta.stars");
+
+        viewer.query = "TUI".to_string();
+        viewer.find(true);
+        assert_eq!(viewer.selected_path(), ".tags[1]");
+    }
+
+    #[test]
+    fn test_collapse_moves_to_parent() {
+        let mut viewer = sample();
+        viewer.query = "stars".to_string();
+        viewer.find(true);
+        viewer.collapse();
+        assert_eq!(viewer.selected_path(), ".meta");
+        viewer.collapse();
+        assert!(!plain(&viewer.lines()).iter().any(|l| l.contains("stars")));
+    }
+
+    #[test]
+    fn test_expand_all_draws_tree_connectors() {
+        let mut viewer = sample();
+        viewer.expand_all();
+        let lines = plain(&viewer.lines());
+        assert!(lines.contains(&"├── • name: \"termgfx\"".to_string()));
+        assert!(lines.contains(&"│   └── • stars: 42".to_string()));
+        assert!(lines.contains(&"    └── • [1]: \"tui\"".to_string()));
+    }
+}
@@ -0,0 +1,256 @@
+use crate::output::color;
+use crate::text;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// A pressed key shown on screen, collapsed when repeated
+struct KeyCap {
+    label: String,
+    count: usize,
+    pressed_at: Instant,
+}
+
+/// Echo pressed keys as key-cap boxes (like screenkey) until Ctrl+C
+pub fn render(echo: bool, fade_ms: u64, max_keys: usize, show_modifiers: bool) {
+    if let Err(e) = run_keys(echo, fade_ms, max_keys, show_modifiers) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_keys(echo: bool, fade_ms: u64, max_keys: usize, show_modifiers: bool) -> io::Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "Keys requires an interactive terminal (TTY)",
+        ));
+    }
+
+    let mut stdout = color::stdout();
+    let fade = Duration::from_millis(fade_ms);
+    let max_keys = max_keys.max(1);
+    let mut caps: Vec<KeyCap> = Vec::new();
+    let mut drawn: Option<Vec<(String, bool)>> = None;
+
+    terminal::enable_raw_mode()?;
+    execute!(
+        stdout,
+        SetForegroundColor(Color::DarkGrey),
+        Print("Press keys to echo them · Ctrl+C to quit\r\n"),
+        ResetColor,
+        cursor::Hide
+    )?;
+
+    let result = loop {
+        if event::poll(Duration::from_millis(50))? {
+            if let Event::Key(key_event) = event::read()? {
+                if key_event.kind == KeyEventKind::Release {
+                    continue;
+                }
+                if key_event.code == KeyCode::Char('c')
+                    && key_event.modifiers.contains(KeyModifiers::CONTROL)
+                {
+                    break Ok(());
+                }
+
+                let label = key_label(&key_event, show_modifiers);
+                if !echo {
+                    execute!(stdout, Print(&label), Print("\r\n"))?;
+                    continue;
+                }
+                match caps.last_mut() {
+                    Some(last) if last.label == label && last.pressed_at.elapsed() < fade => {
+                        last.count += 1;
+                        last.pressed_at = Instant::now();
+                    }
+                    _ => caps.push(KeyCap {
+                        label,
+                        count: 1,
+                        pressed_at: Instant::now(),
+                    }),
+                }
+                if caps.len() > max_keys {
+                    caps.drain(..caps.len() - max_keys);
+                }
+            }
+        }
+
+        if !echo {
+            continue;
+        }
+
+        caps.retain(|cap| cap.pressed_at.elapsed() < fade);
+        let visible: Vec<(String, bool)> = caps
+            .iter()
+            .map(|cap| {
+                let text = if cap.count > 1 {
+                    format!("{} ×{}", cap.label, cap.count)
+                } else {
+                    cap.label.clone()
+                };
+                (text, cap.pressed_at.elapsed() > fade / 2)
+            })
+            .collect();
+
+        // Only redraw when a key arrives, fades, or disappears
+        if drawn.as_ref() == Some(&visible) {
+            continue;
+        }
+        if drawn.is_some() {
+            execute!(stdout, cursor::MoveUp(3))?;
+        }
+        for row in 0..3 {
+            execute!(
+                stdout,
+                cursor::MoveToColumn(0),
+                terminal::Clear(ClearType::CurrentLine)
+            )?;
+            for (text, faded) in &visible {
+                let color = if *faded { Color::DarkGrey } else { Color::Cyan };
+                execute!(
+                    stdout,
+                    SetForegroundColor(color),
+                    Print(cap_rows(text)[row].clone()),
+                    Print(" "),
+                    ResetColor
+                )?;
+            }
+            execute!(stdout, Print("\r\n"))?;
+        }
+        drawn = Some(visible);
+        stdout.flush()?;
+    };
+
+    terminal::disable_raw_mode()?;
+    execute!(stdout, cursor::Show)?;
+
+    result
+}
+
+/// Human-readable name for a key press, e.g. "Ctrl+Shift+A" or "⏎ Enter"
+fn key_label(key: &KeyEvent, show_modifiers: bool) -> String {
+    let name = match key.code {
+        KeyCode::Char(' ') => "␣ Space".to_string(),
+        KeyCode::Char(c) => {
+            if show_modifiers
+                && key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            {
+                c.to_uppercase().to_string()
+            } else {
+                c.to_string()
+            }
+        }
+        KeyCode::Enter => "⏎ Enter".to_string(),
+        KeyCode::Tab => "⇥ Tab".to_string(),
+        KeyCode::BackTab => "⇤ Tab".to_string(),
+        KeyCode::Backspace => "⌫ Bksp".to_string(),
+        KeyCode::Delete => "⌦ Del".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    };
+
+    if !show_modifiers {
+        return name;
+    }
+
+    let mut parts = Vec::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        parts.push("Ctrl");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        parts.push("Alt");
+    }
+    if key.modifiers.contains(KeyModifiers::SUPER) {
+        parts.push("Super");
+    }
+    // Shift is already visible in the character itself
+    let shifted_char = matches!(key.code, KeyCode::Char(_))
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    if key.modifiers.contains(KeyModifiers::SHIFT) && !shifted_char {
+        parts.push("Shift");
+    }
+    parts.push(&name);
+    parts.join("+")
+}
+
+/// The three rows (top border, label, bottom border) of a key-cap box
+fn cap_rows(text: &str) -> [String; 3] {
+    let width = text::display_width(text) + 2;
+    [
+        format!("╭{}╮", "─".repeat(width)),
+        format!("│ {} │", text),
+        format!("╰{}╯", "─".repeat(width)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_key_label_plain_char() {
+        assert_eq!(
+            key_label(&key(KeyCode::Char('a'), KeyModifiers::NONE), true),
+            "a"
+        );
+    }
+
+    #[test]
+    fn test_key_label_with_modifiers() {
+        let k = key(
+            KeyCode::Char('s'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        );
+        assert_eq!(key_label(&k, true), "Ctrl+Shift+S");
+        assert_eq!(key_label(&k, false), "s");
+    }
+
+    #[test]
+    fn test_key_label_shifted_char_omits_shift() {
+        let k = key(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert_eq!(key_label(&k, true), "A");
+    }
+
+    #[test]
+    fn test_key_label_special_keys() {
+        assert_eq!(
+            key_label(&key(KeyCode::Enter, KeyModifiers::NONE), true),
+            "⏎ Enter"
+        );
+        assert_eq!(
+            key_label(&key(KeyCode::F(5), KeyModifiers::ALT), true),
+            "Alt+F5"
+        );
+    }
+
+    #[test]
+    fn test_cap_rows_widths_match() {
+        let rows = cap_rows("Ctrl+C");
+        let widths: Vec<usize> = rows.iter().map(|r| text::display_width(r)).collect();
+        assert_eq!(widths, vec![10, 10, 10]);
+    }
+}
@@ -1,11 +1,23 @@
+pub mod clipboard;
 pub mod confirm;
+pub mod emojipick;
 pub mod file;
 pub mod filter;
 pub mod form;
 pub mod input;
+pub mod json;
+pub mod keys;
+pub mod output_format;
 pub mod pager;
 pub mod playground;
+pub mod present;
+pub mod prompt;
+pub mod repl;
+pub mod review;
+pub mod screen;
 pub mod select;
 pub mod studio;
+pub mod table_view;
+pub mod textarea;
 pub mod tui;
 pub mod wizard;
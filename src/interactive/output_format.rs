@@ -0,0 +1,202 @@
+//! How `form` and `wizard` print their answers
+//!
+//! Answers keep the order of the fields or steps they came from. `shell`
+//! (`--export-shell`) prints `export KEY='value'` lines for `eval`, with
+//! keys made into valid variable names and values single-quoted.
+
+use crate::output::atomic;
+use std::io;
+
+/// Names listed in errors and help
+pub const FORMATS: &str = "json, env, csv, yaml, toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Env,
+    Csv,
+    Yaml,
+    Toml,
+    Shell,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(OutputFormat::Json),
+            "env" => Some(OutputFormat::Env),
+            "csv" => Some(OutputFormat::Csv),
+            "yaml" | "yml" => Some(OutputFormat::Yaml),
+            "toml" => Some(OutputFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// The format asked for on the command line; `--export-shell` wins
+    /// over `--output`
+    pub fn parse(name: &str, export_shell: bool) -> Result<Self, String> {
+        if export_shell {
+            return Ok(OutputFormat::Shell);
+        }
+        Self::from_name(name)
+            .ok_or_else(|| format!("Invalid output format '{}' (use {})", name, FORMATS))
+    }
+
+    /// `answers` as text in this format, without a trailing newline
+    pub fn format(self, answers: &[(String, String)]) -> String {
+        let lines: Vec<String> = match self {
+            OutputFormat::Json => {
+                let map: serde_json::Map<String, serde_json::Value> = answers
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone().into()))
+                    .collect();
+                return serde_json::to_string_pretty(&map).unwrap_or_default();
+            }
+            OutputFormat::Env => answers
+                .iter()
+                .map(|(key, value)| format!("{}={}", key.to_uppercase(), value))
+                .collect(),
+            OutputFormat::Csv => answers
+                .iter()
+                .map(|(key, value)| format!("{},{}", csv_field(key), csv_field(value)))
+                .collect(),
+            OutputFormat::Yaml => answers
+                .iter()
+                .map(|(key, value)| format!("{}: {}", yaml_key(key), json_string(value)))
+                .collect(),
+            OutputFormat::Toml => answers
+                .iter()
+                .map(|(key, value)| format!("{} = {}", toml_key(key), toml_string(value)))
+                .collect(),
+            OutputFormat::Shell => answers
+                .iter()
+                .map(|(key, value)| format!("export {}={}", shell_name(key), shell_quote(value)))
+                .collect(),
+        };
+        lines.join("\n")
+    }
+}
+
+/// Print `text`, or write it to `path` instead
+pub fn emit(text: &str, path: Option<&str>) -> io::Result<()> {
+    match path {
+        Some(path) => atomic::write(path, format!("{}\n", text))
+            .map_err(|e| io::Error::new(e.kind(), format!("Can't write {}: {}", path, e))),
+        None => {
+            println!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn yaml_key(key: &str) -> String {
+    if is_bare_key(key) && !key.starts_with('-') {
+        key.to_string()
+    } else {
+        json_string(key)
+    }
+}
+
+fn toml_key(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+/// Quoted when it holds a comma, quote or line break, quotes doubled
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A variable name from a key: uppercased, other characters as `_`, and
+/// never starting with a digit
+fn shell_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Vec<(String, String)> {
+        vec![
+            ("name".to_string(), "O'Brien".to_string()),
+            ("team-size".to_string(), "3".to_string()),
+            ("notes".to_string(), "a, \"b\"\nc".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_formats_keep_order_and_escape() {
+        assert_eq!(
+            OutputFormat::Yaml.format(&answers()),
+            "name: \"O'Brien\"\nteam-size: \"3\"\nnotes: \"a, \\\"b\\\"\\nc\""
+        );
+        assert_eq!(
+            OutputFormat::Toml.format(&answers()),
+            "name = \"O'Brien\"\nteam-size = \"3\"\nnotes = \"\"\"\na, \"b\"\nc\"\"\""
+        );
+        assert_eq!(
+            OutputFormat::Csv.format(&answers()),
+            "name,O'Brien\nteam-size,3\nnotes,\"a, \"\"b\"\"\nc\""
+        );
+    }
+
+    #[test]
+    fn test_export_shell_quotes_values() {
+        assert_eq!(
+            OutputFormat::Shell.format(&answers()[..2]),
+            "export NAME='O'\\''Brien'\nexport TEAM_SIZE='3'"
+        );
+        assert_eq!(shell_name("2fa"), "_2FA");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(OutputFormat::parse("YML", false), Ok(OutputFormat::Yaml));
+        assert_eq!(OutputFormat::parse("json", true), Ok(OutputFormat::Shell));
+        assert_eq!(
+            OutputFormat::parse("xml", false),
+            Err("Invalid output format 'xml' (use json, env, csv, yaml, toml)".to_string())
+        );
+    }
+}
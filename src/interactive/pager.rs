@@ -1,91 +1,364 @@
+use super::screen::{self, Screen};
+use crate::output::syntax::Highlighter;
+use crate::output::{color, input, markdown};
+use crate::text;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyModifiers},
     execute,
     style::{Color, Print, ResetColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal,
 };
-use std::io::{self, Read, Write};
+use regex::Regex;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
 
 pub struct Pager {
+    /// Lines as drawn: tabs expanded, only color sequences kept
     lines: Vec<String>,
+    /// The same lines without color, for searching and measuring
+    plain: Vec<String>,
     line_numbers: bool,
     title: Option<String>,
+    height: Option<usize>,
+    inline: bool,
+    wrap: bool,
+    /// Lines still arriving with `--follow`
+    incoming: Option<Receiver<String>>,
+}
+
+/// Lines shown at once when drawing inline without a `--height`
+const INLINE_HEIGHT: usize = 10;
+
+/// How often `--follow` checks for new lines while no key is pressed
+const FOLLOW_POLL: Duration = Duration::from_millis(100);
+
+const TAB_WIDTH: usize = 8;
+
+const HIGHLIGHT_ON: &str = "\x1b[7m";
+const HIGHLIGHT_OFF: &str = "\x1b[27m";
+
+/// Where the pager is looking and what it is searching for
+#[derive(Default)]
+struct View {
+    /// First row shown, counted in screen rows when wrapping
+    top: usize,
+    /// Columns scrolled to the right (without wrapping)
+    left: usize,
+    search: Option<Regex>,
+    /// Every match as (line, start, end), byte offsets into the plain line
+    matches: Vec<(usize, usize, usize)>,
+    current: Option<usize>,
+    /// The pattern being typed after `/`
+    input: Option<String>,
+    /// Shown in the footer until the next key
+    message: Option<String>,
 }
 
 impl Pager {
-    pub fn new(content: String, line_numbers: bool, title: Option<String>) -> Self {
-        let lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
-        Self {
-            lines,
+    /// Pager over pre-rendered lines, which may carry ANSI styling
+    pub fn from_lines(lines: Vec<String>, line_numbers: bool, title: Option<String>) -> Self {
+        let mut pager = Self {
+            lines: Vec::with_capacity(lines.len()),
+            plain: Vec::with_capacity(lines.len()),
             line_numbers,
             title,
+            height: None,
+            inline: false,
+            wrap: false,
+            incoming: None,
+        };
+        for line in lines {
+            pager.push(&line);
         }
+        pager
     }
 
-    pub fn render(&self) -> io::Result<()> {
-        if self.lines.is_empty() {
+    /// Show at most `height` lines at once
+    pub fn height(mut self, height: Option<usize>) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Draw below the prompt instead of on the alternate screen
+    pub fn inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
+    /// Start with long lines wrapped instead of cut off
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Keep adding lines from `incoming` while the pager is open
+    pub fn follow(mut self, incoming: Receiver<String>) -> Self {
+        self.incoming = Some(incoming);
+        self
+    }
+
+    fn push(&mut self, line: &str) {
+        let line = clean(line);
+        self.plain.push(text::strip_ansi(&line).into_owned());
+        self.lines.push(line);
+    }
+
+    pub fn render(mut self) -> io::Result<()> {
+        if self.lines.is_empty() && self.incoming.is_none() {
             return Ok(());
         }
 
-        let mut stdout = io::stdout();
-        let mut scroll_offset = 0usize;
+        let mut stdout = color::stdout();
+        let mut view = View::default();
 
         terminal::enable_raw_mode()?;
-        execute!(stdout, EnterAlternateScreen, Hide)?;
+        let inline_rows = self
+            .height
+            .unwrap_or(INLINE_HEIGHT)
+            .min(self.lines.len().max(1))
+            + 2;
+        let screen = Screen::enter(&mut stdout, self.inline.then_some(inline_rows))?;
 
         loop {
-            let (_, rows) = terminal::size()?;
-            let available_rows = rows as usize - 2; // Reserve for header/footer
+            // Reserve rows for header/footer
+            let mut available_rows = screen.rows().saturating_sub(2).max(1);
+            if let Some(height) = self.height {
+                available_rows = available_rows.min(height.max(1));
+            }
+            let width = self.content_width();
+            let rows = self.rows(width);
+            let max_top = rows.len().saturating_sub(available_rows);
 
-            self.render_ui(&mut stdout, scroll_offset, available_rows)?;
+            self.render_ui(&mut stdout, &screen, &view, &rows, available_rows)?;
 
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        scroll_offset = scroll_offset.saturating_sub(1);
-                    }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        if scroll_offset + available_rows < self.lines.len() {
-                            scroll_offset += 1;
+            if let Some(incoming) = &self.incoming {
+                if !event::poll(FOLLOW_POLL)? {
+                    let new: Vec<String> = incoming.try_iter().collect();
+                    if !new.is_empty() {
+                        let first = self.lines.len();
+                        for line in &new {
+                            self.push(line);
+                        }
+                        self.find_from(&mut view, first);
+                        // Stay at the end while following it
+                        if view.top >= max_top {
+                            let rows = self.rows(width).len();
+                            view.top = rows.saturating_sub(available_rows);
                         }
                     }
-                    KeyCode::PageUp | KeyCode::Char('b') => {
-                        scroll_offset = scroll_offset.saturating_sub(available_rows);
-                    }
-                    KeyCode::PageDown | KeyCode::Char('f') | KeyCode::Char(' ') => {
-                        let max_offset = self.lines.len().saturating_sub(available_rows);
-                        scroll_offset = (scroll_offset + available_rows).min(max_offset);
-                    }
-                    KeyCode::Home | KeyCode::Char('g') => {
-                        scroll_offset = 0;
+                    continue;
+                }
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            view.message = None;
+
+            if let Some(pattern) = &mut view.input {
+                match key.code {
+                    KeyCode::Enter => {
+                        let pattern = view.input.take().unwrap_or_default();
+                        if !pattern.is_empty() {
+                            self.search(&mut view, &pattern, &rows, available_rows);
+                        }
                     }
-                    KeyCode::End | KeyCode::Char('G') => {
-                        scroll_offset = self.lines.len().saturating_sub(available_rows);
+                    KeyCode::Esc => view.input = None,
+                    KeyCode::Backspace => {
+                        pattern.pop();
                     }
-                    KeyCode::Char('q') | KeyCode::Esc => {
-                        break;
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        view.input = None;
                     }
+                    KeyCode::Char(c) => pattern.push(c),
                     _ => {}
                 }
+                continue;
+            }
+
+            let max_left = if self.wrap {
+                0
+            } else {
+                let longest = self.plain.iter().map(|l| text::display_width(l)).max();
+                longest.unwrap_or(0).saturating_sub(width)
+            };
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    view.top = view.top.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    view.top = (view.top + 1).min(max_top);
+                }
+                KeyCode::PageUp | KeyCode::Char('b') => {
+                    view.top = view.top.saturating_sub(available_rows);
+                }
+                KeyCode::PageDown | KeyCode::Char('f') | KeyCode::Char(' ') => {
+                    view.top = (view.top + available_rows).min(max_top);
+                }
+                KeyCode::Home | KeyCode::Char('g') => {
+                    view.top = 0;
+                }
+                KeyCode::End | KeyCode::Char('G') => {
+                    view.top = max_top;
+                }
+                KeyCode::Left | KeyCode::Char('h') => {
+                    view.left = view.left.saturating_sub(width / 2);
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    view.left = (view.left + width / 2).min(max_left);
+                }
+                KeyCode::Char('w') => {
+                    // Keep the top line in view across the change
+                    let line = rows.get(view.top).map_or(0, |&(line, _)| line);
+                    self.wrap = !self.wrap;
+                    view.left = 0;
+                    view.top = self
+                        .rows(width)
+                        .iter()
+                        .position(|&(l, _)| l == line)
+                        .unwrap_or(0);
+                }
+                KeyCode::Char('/') => view.input = Some(String::new()),
+                KeyCode::Char('n') => self.step(&mut view, true, &rows, available_rows),
+                KeyCode::Char('N') => self.step(&mut view, false, &rows, available_rows),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    break;
+                }
+                _ => {}
             }
         }
 
-        execute!(stdout, Show, LeaveAlternateScreen)?;
+        screen.leave(&mut stdout)?;
         terminal::disable_raw_mode()?;
 
         Ok(())
     }
 
+    fn line_num_width(&self) -> usize {
+        if self.line_numbers {
+            self.lines.len().to_string().len() + 2
+        } else {
+            0
+        }
+    }
+
+    fn content_width(&self) -> usize {
+        let (cols, _) = screen::size();
+        (cols as usize).saturating_sub(self.line_num_width()).max(1)
+    }
+
+    /// Screen rows as (line, first column); a line takes several when
+    /// wrapping
+    fn rows(&self, width: usize) -> Vec<(usize, usize)> {
+        if !self.wrap {
+            return (0..self.lines.len()).map(|i| (i, 0)).collect();
+        }
+        self.plain
+            .iter()
+            .enumerate()
+            .flat_map(|(i, plain)| wrap_starts(plain, width).into_iter().map(move |c| (i, c)))
+            .collect()
+    }
+
+    /// Compile `pattern` and jump to its first match from the top of the
+    /// screen down
+    fn search(&self, view: &mut View, pattern: &str, rows: &[(usize, usize)], available: usize) {
+        let re = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(e) => {
+                let error = e.to_string();
+                let reason = error
+                    .lines()
+                    .last()
+                    .unwrap_or("")
+                    .trim_start_matches("error: ");
+                view.message = Some(format!("Invalid regex '{}': {}", pattern, reason));
+                return;
+            }
+        };
+        view.search = Some(re);
+        view.matches.clear();
+        view.current = None;
+        self.find_from(view, 0);
+        if view.matches.is_empty() {
+            view.message = Some(format!("Pattern not found: {}", pattern));
+            return;
+        }
+        let top_line = rows.get(view.top).map_or(0, |&(line, _)| line);
+        let first = view
+            .matches
+            .iter()
+            .position(|&(line, _, _)| line >= top_line)
+            .unwrap_or(0);
+        self.show_match(view, first, rows, available);
+    }
+
+    /// Add the matches in lines `first..`
+    fn find_from(&self, view: &mut View, first: usize) {
+        let Some(re) = &view.search else {
+            return;
+        };
+        for (i, plain) in self.plain.iter().enumerate().skip(first) {
+            view.matches.extend(
+                re.find_iter(plain)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| (i, m.start(), m.end())),
+            );
+        }
+    }
+
+    /// Move to the next (or previous) match, wrapping around
+    fn step(&self, view: &mut View, forward: bool, rows: &[(usize, usize)], available: usize) {
+        if view.search.is_none() {
+            view.message = Some("No search yet (press / to search)".to_string());
+            return;
+        }
+        let count = view.matches.len();
+        if count == 0 {
+            view.message = Some("Pattern not found".to_string());
+            return;
+        }
+        let next = match view.current {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None => 0,
+        };
+        self.show_match(view, next, rows, available);
+    }
+
+    /// Scroll so match `index` is on screen
+    fn show_match(&self, view: &mut View, index: usize, rows: &[(usize, usize)], available: usize) {
+        view.current = Some(index);
+        let (line, start, _) = view.matches[index];
+        let col = text::display_width(&self.plain[line][..start]);
+        let row = rows
+            .iter()
+            .rposition(|&(l, c)| l == line && c <= col)
+            .unwrap_or(0);
+        if row < view.top || row >= view.top + available {
+            view.top = row.min(rows.len().saturating_sub(available));
+        }
+        let width = self.content_width();
+        if !self.wrap && (col < view.left || col >= view.left + width) {
+            view.left = col.saturating_sub(width / 4);
+        }
+    }
+
     fn render_ui(
         &self,
-        stdout: &mut io::Stdout,
-        scroll_offset: usize,
+        stdout: &mut color::Stdout,
+        screen: &Screen,
+        view: &View,
+        rows: &[(usize, usize)],
         available_rows: usize,
     ) -> io::Result<()> {
-        let (cols, _) = terminal::size()?;
+        let (cols, _) = screen::size();
 
-        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        screen.clear(stdout)?;
 
         // Header
         let title = self.title.as_deref().unwrap_or("termgfx pager");
@@ -94,73 +367,102 @@ impl Pager {
             SetForegroundColor(Color::Cyan),
             Print(format!("─── {} ", title)),
         )?;
-        let remaining = cols as usize - title.len() - 5;
-        execute!(
-            stdout,
-            Print("─".repeat(remaining.min(cols as usize))),
-            ResetColor,
-            Print("\n")
-        )?;
+        let remaining = (cols as usize).saturating_sub(title.len() + 5);
+        execute!(stdout, Print("─".repeat(remaining)), ResetColor)?;
 
         // Content
-        let line_num_width = if self.line_numbers {
-            self.lines.len().to_string().len() + 2
-        } else {
-            0
-        };
-        let content_width = cols as usize - line_num_width;
+        let line_num_width = self.line_num_width();
+        let content_width = self.content_width();
 
-        for (i, line) in self
-            .lines
-            .iter()
-            .skip(scroll_offset)
-            .take(available_rows)
-            .enumerate()
-        {
-            let line_num = scroll_offset + i + 1;
+        let shown = &rows[view.top.min(rows.len())..];
+        for (i, &(line, start)) in shown.iter().take(available_rows).enumerate() {
+            execute!(stdout, screen.goto(i + 1))?;
 
             if self.line_numbers {
+                // Only the first row of a wrapped line is numbered
+                let number = if start == 0 {
+                    (line + 1).to_string()
+                } else {
+                    String::new()
+                };
                 execute!(
                     stdout,
                     SetForegroundColor(Color::DarkGrey),
-                    Print(format!("{:>width$} ", line_num, width = line_num_width - 1)),
+                    Print(format!("{:>width$} ", number, width = line_num_width - 1)),
                     ResetColor
                 )?;
             }
 
-            // Truncate line if too long
-            let display_line = if line.len() > content_width {
-                format!("{}…", &line[..content_width.saturating_sub(1)])
-            } else {
-                line.clone()
-            };
-
-            execute!(stdout, Print(display_line), Print("\n"))?;
+            let highlights: Vec<(usize, usize)> = view
+                .matches
+                .iter()
+                .filter(|&&(l, _, _)| l == line)
+                .map(|&(_, s, e)| (s, e))
+                .collect();
+            let start = if self.wrap { start } else { view.left };
+            execute!(
+                stdout,
+                Print(slice(&self.lines[line], start, content_width, &highlights))
+            )?;
         }
 
         // Fill remaining space
-        let displayed = self.lines.len().min(available_rows);
-        for _ in displayed..available_rows {
-            execute!(stdout, Print("~\n"))?;
+        let displayed = shown.len().min(available_rows);
+        for row in displayed..available_rows {
+            execute!(stdout, screen.goto(row + 1), Print("~"))?;
         }
 
         // Footer
-        let percent = if self.lines.len() <= available_rows {
+        execute!(stdout, screen.goto(available_rows + 1))?;
+        if let Some(pattern) = &view.input {
+            return execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(format!("/{}_", pattern)),
+                ResetColor
+            )
+            .and_then(|_| stdout.flush());
+        }
+        if let Some(message) = &view.message {
+            return execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(message),
+                ResetColor
+            )
+            .and_then(|_| stdout.flush());
+        }
+
+        let percent = if rows.len() <= available_rows {
             100
         } else {
-            ((scroll_offset as f64 / (self.lines.len() - available_rows) as f64) * 100.0) as usize
+            ((view.top as f64 / (rows.len() - available_rows) as f64) * 100.0) as usize
         };
+        let first_line = shown.first().map_or(0, |&(line, _)| line + 1);
+        let last_line = shown[..shown.len().min(available_rows)]
+            .last()
+            .map_or(0, |&(line, _)| line + 1);
+        let mut status = format!(
+            "Lines {}-{} of {} ({}%)",
+            first_line,
+            last_line,
+            self.lines.len(),
+            percent
+        );
+        if let Some(current) = view.current {
+            status.push_str(&format!(" | match {}/{}", current + 1, view.matches.len()));
+        }
+        if self.incoming.is_some() {
+            status.push_str(" | following");
+        }
+        status.push_str(
+            " | ↑/↓ ←/→:scroll | PgUp/PgDn:page | g/G:top/bottom | /:search n/N | w:wrap | q:quit",
+        );
 
         execute!(
             stdout,
             SetForegroundColor(Color::DarkGrey),
-            Print(format!(
-                "Lines {}-{} of {} ({}%) | ↑/↓:scroll | PgUp/PgDn:page | g/G:top/bottom | q:quit",
-                scroll_offset + 1,
-                (scroll_offset + available_rows).min(self.lines.len()),
-                self.lines.len(),
-                percent
-            )),
+            Print(text::truncate(&status, cols as usize, "…")),
             ResetColor
         )?;
 
@@ -168,30 +470,261 @@ impl Pager {
     }
 }
 
-pub fn render(line_numbers: bool, title: Option<String>) {
+/// `line` ready to draw: tabs expanded, and escape sequences other than
+/// colors dropped along with control characters, so every character takes
+/// the cells it is measured at
+fn clean(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut col = 0;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                let seq = text::take_escape(&mut chars);
+                if seq.starts_with('[') && seq.ends_with('m') {
+                    out.push(c);
+                    out.push_str(&seq);
+                }
+            }
+            '\t' => {
+                let spaces = TAB_WIDTH - col % TAB_WIDTH;
+                out.extend(std::iter::repeat_n(' ', spaces));
+                col += spaces;
+            }
+            c if c.is_control() => {}
+            c => {
+                out.push(c);
+                col += text::char_width(c);
+            }
+        }
+    }
+    out
+}
+
+/// The columns at which each row of `plain` starts when wrapped to `width`
+fn wrap_starts(plain: &str, width: usize) -> Vec<usize> {
+    let mut starts = vec![0];
+    let (mut col, mut row_start) = (0, 0);
+    for c in plain.chars() {
+        let w = text::char_width(c);
+        if col + w > row_start + width && col > row_start {
+            starts.push(col);
+            row_start = col;
+        }
+        col += w;
+    }
+    starts
+}
+
+/// The part of `line` from column `start`, at most `width` cells, keeping
+/// its colors; `highlights` are byte ranges of the plain line to show in
+/// reverse video
+fn slice(line: &str, start: usize, width: usize, highlights: &[(usize, usize)]) -> String {
+    let mut out = String::new();
+    let (mut col, mut byte) = (0, 0);
+    let mut styled = false;
+    let mut lit = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Kept even off screen, so colors carry into the visible part
+            out.push(c);
+            out.push_str(&text::take_escape(&mut chars));
+            styled = true;
+            if lit {
+                // The line's own reset would end the highlight
+                out.push_str(HIGHLIGHT_ON);
+            }
+            continue;
+        }
+        let w = text::char_width(c);
+        let at = byte;
+        byte += c.len_utf8();
+        if col < start {
+            col += w;
+            continue;
+        }
+        if col + w > start + width {
+            break;
+        }
+        col += w;
+        let inside = highlights.iter().any(|&(s, e)| at >= s && at < e);
+        if inside != lit {
+            out.push_str(if inside { HIGHLIGHT_ON } else { HIGHLIGHT_OFF });
+            lit = inside;
+            styled = true;
+        }
+        out.push(c);
+    }
+    if styled {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+/// `content` as lines to page: rendered as Markdown, highlighted as
+/// `language` (unknown languages stay plain), or as it is
+fn content_lines(
+    content: &str,
+    markdown: bool,
+    language: Option<&str>,
+    width: usize,
+) -> Vec<String> {
+    if markdown {
+        return markdown::render_lines(content, width);
+    }
+    let mut highlighter = language.map(Highlighter::new);
+    content
+        .lines()
+        .map(|line| match &mut highlighter {
+            Some(highlighter) => highlighter.line(line),
+            None => line.to_string(),
+        })
+        .collect()
+}
+
+/// Send each line of `file` (or stdin for `-`) as it is read, highlighted
+/// when `highlighter` is given; a file is then watched for more, like
+/// `tail -f`
+fn follow(file: &str, mut highlighter: Option<Highlighter>) -> io::Result<Receiver<String>> {
+    let (tx, rx) = mpsc::channel();
+    let mut style = move |line: String| match &mut highlighter {
+        Some(highlighter) => highlighter.line(&line),
+        None => line,
+    };
+    if input::is_stdin(file) {
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines().map_while(Result::ok) {
+                if tx.send(style(line)).is_err() {
+                    break;
+                }
+            }
+        });
+        return Ok(rx);
+    }
+
+    let mut reader = BufReader::new(File::open(file)?);
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) => thread::sleep(FOLLOW_POLL),
+                // Partial lines wait for the rest
+                Ok(_) if buf.ends_with(b"\n") => {
+                    let line = String::from_utf8_lossy(&buf);
+                    let line = line.trim_end_matches(['\n', '\r']).to_string();
+                    buf.clear();
+                    if tx.send(style(line)).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+    });
+    Ok(rx)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    file: &str,
+    line_numbers: bool,
+    title: Option<String>,
+    height: Option<usize>,
+    inline: bool,
+    wrap: bool,
+    follow_input: bool,
+    markdown: bool,
+    language: Option<&str>,
+) {
     // Check if stdin is a TTY (no piped input)
-    if atty::is(atty::Stream::Stdin) {
+    if input::is_stdin(file) && atty::is(atty::Stream::Stdin) {
         eprintln!("Error: No input provided. Pipe content to pager:");
         eprintln!("  cat file.txt | termgfx pager");
         eprintln!("  ls -la | termgfx pager --line-numbers");
         std::process::exit(1);
     }
+    let title = title.or_else(|| (!input::is_stdin(file)).then(|| file.to_string()));
 
-    // Read from stdin
-    let mut content = String::new();
-    if let Err(e) = io::stdin().read_to_string(&mut content) {
-        eprintln!("Error reading stdin: {}", e);
+    let pager = if follow_input {
+        let incoming = follow(file, language.map(Highlighter::new)).unwrap_or_else(|e| {
+            eprintln!("Error: {}: {}", file, e);
+            std::process::exit(1);
+        });
+        Pager::from_lines(Vec::new(), line_numbers, title).follow(incoming)
+    } else {
+        let content = input::read_text(file).unwrap_or_else(|e| {
+            eprintln!("Error reading {}: {}", input::display_name(file), e);
+            std::process::exit(1);
+        });
+        if content.trim().is_empty() {
+            eprintln!("Error: No content to display");
+            std::process::exit(1);
+        }
+        let (cols, _) = screen::size();
+        let lines = content_lines(&content, markdown, language, cols as usize);
+        Pager::from_lines(lines, line_numbers, title)
+    };
+
+    if let Err(e) = pager.height(height).inline(inline).wrap(wrap).render() {
+        eprintln!("Error: {}", e);
         std::process::exit(1);
     }
+}
 
-    if content.trim().is_empty() {
-        eprintln!("Error: No content to display");
-        std::process::exit(1);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_expands_tabs_and_keeps_colors() {
+        assert_eq!(clean("a\tb"), "a       b");
+        assert_eq!(clean("\x1b[31mred\x1b[0m\x1b[2K\r"), "\x1b[31mred\x1b[0m");
     }
 
-    let pager = Pager::new(content, line_numbers, title);
-    if let Err(e) = pager.render() {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+    #[test]
+    fn test_slice_scrolls_and_keeps_colors() {
+        assert_eq!(slice("abcdef", 2, 3, &[]), "cde");
+        assert_eq!(
+            slice("\x1b[32mabcdef\x1b[0m", 4, 10, &[]),
+            "\x1b[32mef\x1b[0m\x1b[0m"
+        );
+        // A wide character cut by the edge is left out
+        assert_eq!(slice("日本語", 0, 3, &[]), "日");
+    }
+
+    #[test]
+    fn test_slice_highlights_matches() {
+        assert_eq!(
+            slice("foo bar foo", 0, 20, &[(4, 7)]),
+            "foo \x1b[7mbar\x1b[27m foo\x1b[0m"
+        );
+        // Survives the line's own reset
+        assert_eq!(
+            slice("a\x1b[0mb", 0, 5, &[(0, 2)]),
+            "\x1b[7ma\x1b[0m\x1b[7mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_content_lines_highlight_or_render() {
+        let plain = content_lines("let x = 1;", false, Some("rust"), 80);
+        assert_ne!(plain[0], "let x = 1;");
+        assert_eq!(text::strip_ansi(&plain[0]), "let x = 1;");
+        // Unknown languages fall back to the text as it is
+        assert_eq!(
+            content_lines("a\nb", false, Some("klingon"), 80),
+            ["a", "b"]
+        );
+        let rendered = content_lines("# Title", true, None, 80);
+        assert!(!rendered.iter().any(|l| l.contains('#')));
+    }
+
+    #[test]
+    fn test_wrap_starts() {
+        assert_eq!(wrap_starts("abcdefg", 3), vec![0, 3, 6]);
+        assert_eq!(wrap_starts("", 3), vec![0]);
+        assert_eq!(wrap_starts("a日本", 2), vec![0, 1, 3]);
     }
 }
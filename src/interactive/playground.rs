@@ -1,3 +1,4 @@
+use crate::output::color;
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
     event::{self, Event, KeyCode, KeyEvent},
@@ -276,6 +277,7 @@ pub fn render() {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn run_playground() -> io::Result<()> {
     // Check for interactive terminal
     if !std::io::stdin().is_terminal() {
@@ -284,7 +286,7 @@ fn run_playground() -> io::Result<()> {
         ));
     }
 
-    let mut stdout = io::stdout();
+    let mut stdout = color::stdout();
     let mut app = PlaygroundApp::new();
 
     // Setup terminal
@@ -360,7 +362,7 @@ fn run_playground() -> io::Result<()> {
     result
 }
 
-fn render_ui(stdout: &mut io::Stdout, app: &PlaygroundApp) -> io::Result<()> {
+fn render_ui(stdout: &mut color::Stdout, app: &PlaygroundApp) -> io::Result<()> {
     execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
 
     let pages = ComponentPage::all();
@@ -523,7 +525,7 @@ fn render_ui(stdout: &mut io::Stdout, app: &PlaygroundApp) -> io::Result<()> {
     Ok(())
 }
 
-fn render_preview(stdout: &mut io::Stdout, app: &PlaygroundApp) -> io::Result<()> {
+fn render_preview(stdout: &mut color::Stdout, app: &PlaygroundApp) -> io::Result<()> {
     match app.current_page_type() {
         ComponentPage::Box => {
             render_box_preview(stdout, &app.box_params)?;
@@ -541,7 +543,7 @@ fn render_preview(stdout: &mut io::Stdout, app: &PlaygroundApp) -> io::Result<()
     Ok(())
 }
 
-fn render_box_preview(stdout: &mut io::Stdout, params: &BoxParams) -> io::Result<()> {
+fn render_box_preview(stdout: &mut color::Stdout, params: &BoxParams) -> io::Result<()> {
     // Simple box preview - just show the styled message
     let border_char = match params.border.as_str() {
         "rounded" => "╭─╮│╰─╯",
@@ -588,7 +590,7 @@ fn render_box_preview(stdout: &mut io::Stdout, params: &BoxParams) -> io::Result
     Ok(())
 }
 
-fn render_progress_preview(stdout: &mut io::Stdout, params: &ProgressParams) -> io::Result<()> {
+fn render_progress_preview(stdout: &mut color::Stdout, params: &ProgressParams) -> io::Result<()> {
     let width = 50;
     let filled = (width * params.percent as usize) / 100;
 
@@ -614,7 +616,7 @@ fn render_progress_preview(stdout: &mut io::Stdout, params: &ProgressParams) ->
     Ok(())
 }
 
-fn render_gauge_preview(stdout: &mut io::Stdout, params: &GaugeParams) -> io::Result<()> {
+fn render_gauge_preview(stdout: &mut color::Stdout, params: &GaugeParams) -> io::Result<()> {
     // Simple semicircle gauge
     execute!(stdout, Print("  "))?;
 
@@ -645,7 +647,10 @@ fn render_gauge_preview(stdout: &mut io::Stdout, params: &GaugeParams) -> io::Re
     Ok(())
 }
 
-fn render_sparkline_preview(stdout: &mut io::Stdout, params: &SparklineParams) -> io::Result<()> {
+fn render_sparkline_preview(
+    stdout: &mut color::Stdout,
+    params: &SparklineParams,
+) -> io::Result<()> {
     // Parse data and render mini chart
     let values: Vec<f64> = params
         .data
@@ -0,0 +1,355 @@
+//! Slide decks from Markdown: `termgfx present`
+//!
+//! Slides are separated by `---` lines and drawn with the Markdown renderer,
+//! so a `# Title` becomes a banner. A fenced `termgfx` block is replaced by
+//! the output of the commands in it, one per line, which is how charts and
+//! images get onto a slide.
+
+use super::repl::split_args;
+use super::screen::{self, Screen};
+use crate::output::{cache, color, input, markdown};
+use crate::text;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    style::Print,
+    terminal,
+};
+use owo_colors::OwoColorize;
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+const FENCES: [&str; 2] = ["```", "~~~"];
+
+/// Show the deck in `path` (`-` for stdin) from slide `start` (1-based).
+/// When stdout isn't a terminal every slide is printed, one after another.
+pub fn run(path: &str, start: usize) {
+    let text = match input::read_text(path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let slides = split_slides(&text);
+    if slides.is_empty() {
+        eprintln!("Error: No slides in {}", path);
+        std::process::exit(1);
+    }
+    if start == 0 || start > slides.len() {
+        eprintln!(
+            "Error: Invalid slide {} (the deck has {})",
+            start,
+            slides.len()
+        );
+        std::process::exit(1);
+    }
+
+    let result = if io::stdout().is_terminal() {
+        present(&slides, start - 1)
+    } else {
+        print_all(&slides)
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Split a deck on `---` lines outside code blocks; empty slides are dropped
+pub fn split_slides(text: &str) -> Vec<String> {
+    let mut slides = Vec::new();
+    let mut current = String::new();
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(f) if trimmed.starts_with(f) => fence = None,
+            Some(_) => {}
+            None => {
+                if trimmed.trim_end() == "---" {
+                    slides.push(std::mem::take(&mut current));
+                    continue;
+                }
+                fence = FENCES.into_iter().find(|f| trimmed.starts_with(f));
+            }
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    slides.push(current);
+
+    slides
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// The slide's lines at `width`, with `termgfx` blocks replaced by what
+/// their commands print
+pub fn slide_lines(source: &str, width: usize) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut pending = String::new();
+    let mut lines = source.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let Some(fence) = FENCES.into_iter().find(|f| trimmed.starts_with(f)) else {
+            pending.push_str(line);
+            pending.push('\n');
+            continue;
+        };
+
+        if trimmed[fence.len()..].trim() != "termgfx" {
+            // Other code blocks go to the Markdown renderer whole
+            pending.push_str(line);
+            pending.push('\n');
+            for line in lines.by_ref() {
+                pending.push_str(line);
+                pending.push('\n');
+                if line.trim_start().starts_with(fence) {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        append(&mut out, markdown::render_lines(&pending, width));
+        pending.clear();
+        for command in lines.by_ref() {
+            if command.trim_start().starts_with(fence) {
+                break;
+            }
+            let command = command.trim();
+            if command.is_empty() || command.starts_with('#') {
+                continue;
+            }
+            let output = match capture(command) {
+                Ok(output) => output.lines().map(str::to_string).collect(),
+                Err(e) => vec![format!("{} {}", "✗".red(), e)],
+            };
+            append(&mut out, output);
+        }
+    }
+    append(&mut out, markdown::render_lines(&pending, width));
+    out
+}
+
+/// Add a block of lines, separated from what came before by a blank line
+fn append(out: &mut Vec<String>, mut block: Vec<String>) {
+    while block.last().is_some_and(|l| l.trim().is_empty()) {
+        block.pop();
+    }
+    if block.is_empty() {
+        return;
+    }
+    if out.last().is_some_and(|l| !l.is_empty()) {
+        out.push(String::new());
+    }
+    out.extend(block);
+}
+
+/// Run one termgfx command and return what it printed, colors included
+fn capture(command: &str) -> Result<String, String> {
+    let mut args = split_args(command)?;
+    if args.first().is_some_and(|a| a == "termgfx") {
+        args.remove(0);
+    }
+    match args.first().map(String::as_str) {
+        None => return Ok(String::new()),
+        Some("present") => return Err("present can't run inside a slide".to_string()),
+        Some(name) if !cache::renders_once(name, &args) => {
+            return Err(format!("'{}' is live and can't run inside a slide", name))
+        }
+        Some(_) => {}
+    }
+
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    // Colors are forced on; the presenter's own writer strips them if needed
+    let output = Command::new(exe)
+        .env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().next().unwrap_or("").trim();
+        let message = message.trim_start_matches("Error: ");
+        return Err(if message.is_empty() {
+            format!("Command failed: {}", command)
+        } else {
+            format!("Command failed: {}", message)
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn print_all(slides: &[String]) -> io::Result<()> {
+    let (cols, _) = screen::size();
+    let width = cols as usize;
+    let mut stdout = color::stdout();
+    for (i, slide) in slides.iter().enumerate() {
+        if i > 0 {
+            writeln!(stdout)?;
+            writeln!(stdout, "{}", "─".repeat(width.min(100)).bright_black())?;
+            writeln!(stdout)?;
+        }
+        for line in slide_lines(slide, width) {
+            writeln!(stdout, "{}", line)?;
+        }
+    }
+    stdout.flush()
+}
+
+fn present(slides: &[String], start: usize) -> io::Result<()> {
+    let mut stdout = color::stdout();
+    terminal::enable_raw_mode()?;
+    let screen = Screen::enter(&mut stdout, None)?;
+    let result = navigate(&mut stdout, &screen, slides, start);
+    screen.leave(&mut stdout)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+/// ←/→ (also h/l, Space, PgUp/PgDn) move between slides, Home/End jump to
+/// the ends, q/Esc/Ctrl+C quit
+fn navigate(
+    stdout: &mut color::Stdout,
+    screen: &Screen,
+    slides: &[String],
+    start: usize,
+) -> io::Result<()> {
+    let last = slides.len() - 1;
+    let mut current = start.min(last);
+    // Slides are rendered once per width; termgfx blocks can be slow
+    let mut rendered: HashMap<(usize, usize), Vec<String>> = HashMap::new();
+
+    loop {
+        let width = screen::size().0 as usize;
+        let lines = rendered
+            .entry((current, width))
+            .or_insert_with(|| slide_lines(&slides[current], width.saturating_sub(4)));
+        draw(stdout, screen, lines, current, slides.len())?;
+
+        let Event::Key(key) = event::read()? else {
+            // Resizes just redraw
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match key.code {
+            KeyCode::Right
+            | KeyCode::Char('l')
+            | KeyCode::Char('n')
+            | KeyCode::Char(' ')
+            | KeyCode::PageDown
+            | KeyCode::Enter => current = (current + 1).min(last),
+            KeyCode::Left
+            | KeyCode::Char('h')
+            | KeyCode::Char('p')
+            | KeyCode::PageUp
+            | KeyCode::Backspace => current = current.saturating_sub(1),
+            KeyCode::Home | KeyCode::Char('g') => current = 0,
+            KeyCode::End | KeyCode::Char('G') => current = last,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The slide centered on screen, with the progress footer on the last row
+fn draw(
+    stdout: &mut color::Stdout,
+    screen: &Screen,
+    lines: &[String],
+    current: usize,
+    total: usize,
+) -> io::Result<()> {
+    let (cols, _) = screen::size();
+    let width = cols as usize;
+    let available = screen.rows().saturating_sub(2).max(1);
+
+    screen.clear(stdout)?;
+    let block_width = lines.iter().map(|l| text::display_width(l)).max();
+    let left = width.saturating_sub(block_width.unwrap_or(0)) / 2;
+    let top = available.saturating_sub(lines.len()) / 2;
+    for (i, line) in lines.iter().take(available).enumerate() {
+        let line = text::truncate(line, width.saturating_sub(left), "…");
+        execute!(
+            stdout,
+            screen.goto(top + i),
+            Print(" ".repeat(left)),
+            Print(line)
+        )?;
+    }
+
+    execute!(
+        stdout,
+        screen.goto(screen.rows().saturating_sub(1)),
+        Print(footer(current, total, width))
+    )?;
+    stdout.flush()
+}
+
+/// `━━━━━━──────  3/10          ←/→ navigate · q quit`
+fn footer(current: usize, total: usize, width: usize) -> String {
+    let hint = "←/→ navigate · q quit";
+    let count = format!("{}/{}", current + 1, total);
+    let bar_width = (width / 3).max(10);
+    let filled = bar_width * (current + 1) / total.max(1);
+    let gap = width.saturating_sub(bar_width + 2 + count.len() + text::display_width(hint));
+    format!(
+        "{}{}  {}{}{}",
+        "━".repeat(filled).cyan(),
+        "─".repeat(bar_width - filled).bright_black(),
+        count.bold(),
+        " ".repeat(gap),
+        hint.dimmed()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_slides() {
+        let deck = "# One\n\ntext\n---\n# Two\n```sh\n---\n```\n---\n\n---\n# Three\n";
+        let slides = split_slides(deck);
+        assert_eq!(slides.len(), 3);
+        assert_eq!(slides[0], "# One\n\ntext");
+        // A rule inside a code block doesn't split the slide
+        assert_eq!(slides[1], "# Two\n```sh\n---\n```");
+        assert_eq!(slides[2], "# Three");
+    }
+
+    #[test]
+    fn test_slide_lines_runs_termgfx_blocks() {
+        let slide = "Intro\n```termgfx\n# a comment\nrepl\n```\nOutro";
+        let lines: Vec<String> = slide_lines(slide, 80)
+            .iter()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect();
+        assert_eq!(lines.first().map(String::as_str), Some("Intro"));
+        assert_eq!(lines.last().map(String::as_str), Some("Outro"));
+        assert!(lines.iter().any(|l| l.contains("'repl' is live")));
+    }
+
+    #[test]
+    fn test_footer() {
+        let footer = text::strip_ansi(&footer(2, 4, 60)).into_owned();
+        assert!(footer.starts_with("━━━━━━━━━━━━━━━────"));
+        assert!(footer.contains(" 3/4 "));
+        assert!(footer.ends_with("q quit"));
+        assert_eq!(text::display_width(&footer), 60);
+    }
+}
@@ -0,0 +1,42 @@
+//! How prompts answer without a terminal, and the exit codes scripts see
+//!
+//! Exit codes follow gum: `confirm` exits 0 for yes and 1 for no, the other
+//! prompts print their answer and exit 0, and a prompt cancelled with Esc or
+//! Ctrl+C exits 130. When stdin isn't a TTY (CI, cron, a pipe) a prompt
+//! answers with its `--default`; without one it fails, unless
+//! `--no-tty-error` asks for an empty answer instead.
+
+use std::io::{self, IsTerminal};
+
+/// Exit code of a cancelled prompt, as for a shell interrupted by Ctrl+C
+pub const CANCELLED: i32 = 130;
+
+/// The answer to give without prompting: `None` when there's a terminal to
+/// prompt on, else the default (or an empty answer with `no_tty_error`)
+pub fn answer_without_tty(
+    name: &str,
+    default: Option<&str>,
+    no_tty_error: bool,
+) -> Result<Option<String>, String> {
+    if io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    match default {
+        Some(answer) => Ok(Some(answer.to_string())),
+        None if no_tty_error => Ok(Some(String::new())),
+        None => Err(format!(
+            "{} requires an interactive terminal (TTY); pass --default to answer without one",
+            name
+        )),
+    }
+}
+
+/// Whether a prompt's error means the user backed out
+pub fn is_cancel(e: &io::Error) -> bool {
+    e.kind() == io::ErrorKind::Interrupted
+}
+
+/// Leave quietly with [`CANCELLED`]
+pub fn exit_cancelled() -> ! {
+    std::process::exit(CANCELLED)
+}
@@ -0,0 +1,572 @@
+//! Interactive REPL for rapid experimentation
+//!
+//! Each entered line is run as a termgfx command and rendered above the
+//! prompt. History is kept in ~/.config/termgfx/repl_history.
+
+use crate::output::{atomic, color};
+use crate::text;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Maximum number of history entries kept on disk
+const MAX_HISTORY: usize = 500;
+
+const PROMPT: &str = "termgfx› ";
+
+const META_COMMANDS: [&str; 6] = [":clear", ":export", ":help", ":history", ":quit", ":theme"];
+
+/// Subcommand names and their long flags, used for tab completion
+pub type CompletionTable = Vec<(String, Vec<String>)>;
+
+/// Result of reading one line from the prompt
+enum ReadResult {
+    Line(String),
+    Exit,
+}
+
+pub fn render(completions: CompletionTable) {
+    if let Err(e) = run_repl(&completions) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_repl(completions: &CompletionTable) -> io::Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "REPL requires an interactive terminal (TTY)",
+        ));
+    }
+
+    let exe = std::env::current_exe()?;
+    let mut history = load_history();
+    let mut theme: Option<String> = None;
+    let mut last_command: Option<Vec<String>> = None;
+    let mut stdout = color::stdout();
+
+    execute!(
+        stdout,
+        SetForegroundColor(Color::DarkGrey),
+        Print("termgfx REPL · Tab completes · ↑/↓ history · :help for meta-commands\n"),
+        ResetColor
+    )?;
+
+    while let ReadResult::Line(line) = read_line(&history, completions)? {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if history.last().map(|s| s.as_str()) != Some(line) {
+            history.push(line.to_string());
+        }
+
+        if let Some(meta) = line.strip_prefix(':') {
+            let mut parts = meta.splitn(2, char::is_whitespace);
+            let name = parts.next().unwrap_or("");
+            let arg = parts.next().map(str::trim).filter(|s| !s.is_empty());
+            match name {
+                "q" | "quit" | "exit" => break,
+                "clear" => {
+                    execute!(
+                        stdout,
+                        terminal::Clear(ClearType::All),
+                        cursor::MoveTo(0, 0)
+                    )?;
+                }
+                "theme" => match arg {
+                    Some(name) => {
+                        theme = Some(name.to_string());
+                        println!("Theme set to '{}'", name);
+                    }
+                    None => println!(
+                        "Theme: {}",
+                        theme.as_deref().unwrap_or("(default from TERMGFX_THEME)")
+                    ),
+                },
+                "export" => match (arg, &last_command) {
+                    (Some(path), Some(args)) => {
+                        match export_output(&exe, args, theme.as_deref(), path) {
+                            Ok(()) => println!("Exported to: {}", path),
+                            Err(e) => eprintln!("Error: {}", e),
+                        }
+                    }
+                    (None, _) => eprintln!("Usage: :export <file>"),
+                    (_, None) => eprintln!("Error: Nothing to export yet"),
+                },
+                "history" => {
+                    for (i, entry) in history.iter().enumerate() {
+                        println!("{:>4}  {}", i + 1, entry);
+                    }
+                }
+                "help" => print_help(),
+                other => eprintln!("Unknown meta-command ':{}' (try :help)", other),
+            }
+            continue;
+        }
+
+        let mut args = match split_args(line) {
+            Ok(args) => args,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+        };
+        // Allow "termgfx box ..." as well as "box ..."
+        if args.first().map(|s| s.as_str()) == Some("termgfx") {
+            args.remove(0);
+        }
+        if args.first().map(|s| s.as_str()) == Some("repl") {
+            eprintln!("Error: Already in the REPL");
+            continue;
+        }
+
+        let mut command = Command::new(&exe);
+        command.args(&args);
+        if let Some(name) = &theme {
+            command.env("TERMGFX_THEME", name);
+        }
+        if let Err(e) = command.status() {
+            eprintln!("Error: {}", e);
+        }
+        last_command = Some(args);
+    }
+
+    save_history(&history);
+    Ok(())
+}
+
+/// Re-run a command and save its output at `path`: drawn through
+/// `--export` for .svg, .png and .html, as plain text for .txt, or with its
+/// escape codes for .ans
+fn export_output(
+    exe: &PathBuf,
+    args: &[String],
+    theme: Option<&str>,
+    path: &str,
+) -> Result<(), String> {
+    let mut command = Command::new(exe);
+    command.args(args).stdin(Stdio::null());
+    if let Some(name) = theme {
+        command.env("TERMGFX_THEME", name);
+    }
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("svg" | "png" | "html") => save_export(command, path),
+        Some("txt") => {
+            let output = command.output().map_err(|e| e.to_string())?;
+            let text = String::from_utf8_lossy(&output.stdout);
+            atomic::write(path, text::strip_ansi(&text).as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", path, e))
+        }
+        Some("ans") => {
+            command.env_remove("NO_COLOR").env("CLICOLOR_FORCE", "1");
+            let output = command.output().map_err(|e| e.to_string())?;
+            atomic::write(path, output.stdout)
+                .map_err(|e| format!("Failed to write {}: {}", path, e))
+        }
+        _ => Err(format!(
+            "Can't export to {} (use a .svg, .png, .html, .txt or .ans file)",
+            path
+        )),
+    }
+}
+
+/// Run `cmd` with `--export path`, which picks SVG, PNG or HTML from the
+/// extension, reporting the command's error if it fails
+pub fn save_export(mut cmd: Command, path: &str) -> Result<(), String> {
+    // Output is captured: the caller owns the terminal
+    let output = cmd
+        .args(["--export", path])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr.lines().next().unwrap_or("").trim();
+    Err(match message.trim_start_matches("Error: ") {
+        "" => format!("Command failed ({})", output.status),
+        message => message.to_string(),
+    })
+}
+
+fn print_help() {
+    println!("Enter any termgfx command without the 'termgfx' prefix, e.g.:");
+    println!("  box \"Hello\" --style success");
+    println!();
+    println!("Meta-commands:");
+    println!("  :clear           Clear the screen");
+    println!("  :theme <name>    Set the theme for following commands");
+    println!("  :export <file>   Save the last command's output (.svg .png .html .txt .ans)");
+    println!("  :history         Show command history");
+    println!("  :quit            Leave the REPL (also Ctrl+D)");
+}
+
+/// Read one line in raw mode with history navigation and tab completion
+#[allow(clippy::collapsible_match)]
+fn read_line(history: &[String], completions: &CompletionTable) -> io::Result<ReadResult> {
+    let mut stdout = color::stdout();
+    let mut buffer: Vec<char> = Vec::new();
+    let mut cursor_pos = 0;
+    let mut history_idx = history.len();
+
+    terminal::enable_raw_mode()?;
+    redraw(&mut stdout, &buffer, cursor_pos)?;
+
+    let result = loop {
+        if let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind,
+            ..
+        }) = event::read()?
+        {
+            if kind == KeyEventKind::Release {
+                continue;
+            }
+            match code {
+                KeyCode::Enter => break ReadResult::Line(buffer.iter().collect()),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    buffer.clear();
+                    cursor_pos = 0;
+                    execute!(stdout, Print("^C\r\n"))?;
+                }
+                KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    if buffer.is_empty() {
+                        break ReadResult::Exit;
+                    }
+                }
+                KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    execute!(
+                        stdout,
+                        terminal::Clear(ClearType::All),
+                        cursor::MoveTo(0, 0)
+                    )?;
+                }
+                KeyCode::Char(c) => {
+                    buffer.insert(cursor_pos, c);
+                    cursor_pos += 1;
+                }
+                KeyCode::Backspace => {
+                    if cursor_pos > 0 {
+                        cursor_pos -= 1;
+                        buffer.remove(cursor_pos);
+                    }
+                }
+                KeyCode::Delete => {
+                    if cursor_pos < buffer.len() {
+                        buffer.remove(cursor_pos);
+                    }
+                }
+                KeyCode::Left => cursor_pos = cursor_pos.saturating_sub(1),
+                KeyCode::Right => cursor_pos = (cursor_pos + 1).min(buffer.len()),
+                KeyCode::Home => cursor_pos = 0,
+                KeyCode::End => cursor_pos = buffer.len(),
+                KeyCode::Up => {
+                    if history_idx > 0 {
+                        history_idx -= 1;
+                        buffer = history[history_idx].chars().collect();
+                        cursor_pos = buffer.len();
+                    }
+                }
+                KeyCode::Down => {
+                    if history_idx < history.len() {
+                        history_idx += 1;
+                        buffer = history
+                            .get(history_idx)
+                            .map(|s| s.chars().collect())
+                            .unwrap_or_default();
+                        cursor_pos = buffer.len();
+                    }
+                }
+                KeyCode::Tab => {
+                    let line: String = buffer[..cursor_pos].iter().collect();
+                    let candidates = complete(&line, completions);
+                    let typed = line.chars().rev().take_while(|c| *c != ' ').count();
+                    let prefix = common_prefix(&candidates);
+                    if candidates.len() == 1 {
+                        let insert: Vec<char> = format!("{} ", &candidates[0][..])
+                            .chars()
+                            .skip(typed)
+                            .collect();
+                        for (i, c) in insert.iter().enumerate() {
+                            buffer.insert(cursor_pos + i, *c);
+                        }
+                        cursor_pos += insert.len();
+                    } else if candidates.len() > 1 {
+                        if prefix.chars().count() > typed {
+                            let insert: Vec<char> = prefix.chars().skip(typed).collect();
+                            for (i, c) in insert.iter().enumerate() {
+                                buffer.insert(cursor_pos + i, *c);
+                            }
+                            cursor_pos += insert.len();
+                        } else {
+                            execute!(
+                                stdout,
+                                Print("\r\n"),
+                                SetForegroundColor(Color::DarkGrey),
+                                Print(candidates.join("  ")),
+                                ResetColor,
+                                Print("\r\n")
+                            )?;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            redraw(&mut stdout, &buffer, cursor_pos)?;
+        }
+    };
+
+    terminal::disable_raw_mode()?;
+    execute!(stdout, Print("\n"))?;
+    Ok(result)
+}
+
+fn redraw(stdout: &mut color::Stdout, buffer: &[char], cursor_pos: usize) -> io::Result<()> {
+    let text: String = buffer.iter().collect();
+    let before: String = buffer[..cursor_pos].iter().collect();
+    execute!(
+        stdout,
+        cursor::MoveToColumn(0),
+        terminal::Clear(ClearType::CurrentLine),
+        SetForegroundColor(Color::Cyan),
+        Print(PROMPT),
+        ResetColor,
+        Print(&text),
+        cursor::MoveToColumn((text::display_width(PROMPT) + text::display_width(&before)) as u16)
+    )?;
+    stdout.flush()
+}
+
+/// Completion candidates for the last word of `line`
+fn complete(line: &str, table: &CompletionTable) -> Vec<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let current = if line.ends_with(' ') {
+        ""
+    } else {
+        words.last().copied().unwrap_or("")
+    };
+    let completing_first = words.len() <= 1 && !line.ends_with(' ');
+
+    let mut candidates: Vec<String> = if completing_first {
+        table
+            .iter()
+            .map(|(name, _)| name.clone())
+            .chain(META_COMMANDS.iter().map(|m| m.to_string()))
+            .filter(|name| name.starts_with(current))
+            .collect()
+    } else if current.starts_with('-') {
+        table
+            .iter()
+            .find(|(name, _)| Some(name.as_str()) == words.first().copied())
+            .map(|(_, flags)| {
+                flags
+                    .iter()
+                    .filter(|flag| flag.starts_with(current))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+fn common_prefix(candidates: &[String]) -> String {
+    let Some(first) = candidates.first() else {
+        return String::new();
+    };
+    let mut prefix: &str = first;
+    for candidate in &candidates[1..] {
+        while !candidate.starts_with(prefix) {
+            prefix = &prefix[..prefix.len() - prefix.chars().last().map_or(0, char::len_utf8)];
+        }
+    }
+    prefix.to_string()
+}
+
+/// Split a command line into arguments, honoring single/double quotes and backslashes
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+fn history_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("termgfx")
+        .join("repl_history")
+}
+
+fn load_history() -> Vec<String> {
+    fs::read_to_string(history_path())
+        .map(|content| content.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+fn save_history(history: &[String]) {
+    let path = history_path();
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let start = history.len().saturating_sub(MAX_HISTORY);
+    let _ = atomic::write(path, history[start..].join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> CompletionTable {
+        vec![
+            (
+                "box".to_string(),
+                vec!["--style".to_string(), "--border".to_string()],
+            ),
+            ("banner".to_string(), vec!["--gradient".to_string()]),
+            ("table".to_string(), vec![]),
+        ]
+    }
+
+    #[test]
+    fn test_save_export_passes_export_and_reports_errors() {
+        let dir = std::env::temp_dir().join(format!("termgfx-repl-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.svg");
+        let path = path.to_str().unwrap();
+
+        // `sh -c script --export PATH` sees the flag as $0 and the path as $1
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "[ \"$0\" = --export ] && printf '<svg/>' > \"$1\""]);
+        assert_eq!(save_export(cmd, path), Ok(()));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "<svg/>");
+
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo 'Error: --export works with box' >&2; exit 1"]);
+        assert_eq!(
+            save_export(cmd, path),
+            Err("--export works with box".to_string())
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_export_output_by_extension() {
+        let dir = std::env::temp_dir().join(format!("termgfx-repl-txt-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let printf = PathBuf::from("printf");
+        let args = ["\\033[31mred\\033[0m".to_string()];
+
+        let txt = dir.join("out.txt");
+        let txt = txt.to_str().unwrap();
+        assert_eq!(export_output(&printf, &args, None, txt), Ok(()));
+        assert_eq!(std::fs::read_to_string(txt).unwrap(), "red");
+
+        let ans = dir.join("out.ans");
+        let ans = ans.to_str().unwrap();
+        assert_eq!(export_output(&printf, &args, None, ans), Ok(()));
+        assert_eq!(std::fs::read_to_string(ans).unwrap(), "\x1b[31mred\x1b[0m");
+
+        let err = export_output(&printf, &args, None, "out.gif").unwrap_err();
+        assert!(err.contains("use a .svg, .png, .html, .txt or .ans file"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_split_args_quotes() {
+        let args = split_args(r#"box "Hello World" --style 'success'"#).unwrap();
+        assert_eq!(args, vec!["box", "Hello World", "--style", "success"]);
+    }
+
+    #[test]
+    fn test_split_args_escapes_and_empty_quotes() {
+        let args = split_args(r#"box Hello\ there """#).unwrap();
+        assert_eq!(args, vec!["box", "Hello there", ""]);
+    }
+
+    #[test]
+    fn test_split_args_unterminated() {
+        assert!(split_args("box \"oops").is_err());
+    }
+
+    #[test]
+    fn test_complete_subcommand() {
+        assert_eq!(complete("b", &table()), vec!["banner", "box"]);
+        assert_eq!(complete("ta", &table()), vec!["table"]);
+    }
+
+    #[test]
+    fn test_complete_meta_command() {
+        assert_eq!(complete(":th", &table()), vec![":theme"]);
+    }
+
+    #[test]
+    fn test_complete_flags() {
+        assert_eq!(complete("box \"Hi\" --s", &table()), vec!["--style"]);
+        assert_eq!(complete("box --", &table()), vec!["--border", "--style"]);
+        assert!(complete("box Hi", &table()).is_empty());
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        let c = vec!["banner".to_string(), "bar".to_string()];
+        assert_eq!(common_prefix(&c), "ba");
+    }
+}
@@ -0,0 +1,345 @@
+//! `diff --interactive`: accept or reject each hunk, then print a patch
+//!
+//! The review screen is drawn on stderr so stdout carries only the patch,
+//! which keeps `termgfx diff a b --interactive > keep.patch` and
+//! `$(termgfx diff ...)` usable from scripts. The patch is a unified diff
+//! of the accepted hunks, renumbered so it applies to the first file.
+
+use super::screen::size;
+use crate::output::color;
+use crate::text;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    style::{Color, Print, ResetColor, SetForegroundColor, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use similar::{ChangeTag, TextDiff};
+use std::io::{self, IsTerminal, Write};
+use std::ops::Range;
+
+/// What the reviewer decided for a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Decision {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// A group of changes with its context, as in a unified diff
+#[derive(Debug, Clone, PartialEq)]
+struct Hunk {
+    old: Range<usize>,
+    new: Range<usize>,
+    lines: Vec<(ChangeTag, String)>,
+}
+
+/// Review the hunks between `old` and `new` and print the accepted ones
+pub fn render(old: &str, new: &str, name1: &str, name2: &str, context: Option<usize>) {
+    let hunks = hunks(old, new, context.unwrap_or(3));
+    if hunks.is_empty() {
+        eprintln!("No differences");
+        return;
+    }
+    match run(&hunks, name1, name2) {
+        Ok(decisions) => {
+            let accepted: Vec<&Hunk> = hunks
+                .iter()
+                .zip(&decisions)
+                .filter(|(_, d)| **d == Decision::Accepted)
+                .map(|(hunk, _)| hunk)
+                .collect();
+            print!("{}", patch(&accepted, name1, name2));
+            let _ = io::stdout().flush();
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Hunks with `context` unchanged lines around each group of changes
+fn hunks(old: &str, new: &str, context: usize) -> Vec<Hunk> {
+    let diff = TextDiff::from_lines(old, new);
+    diff.grouped_ops(context)
+        .iter()
+        .filter_map(|group| {
+            let (first, last) = (group.first()?, group.last()?);
+            let lines = group
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| (change.tag(), change.value().to_string()))
+                .collect();
+            Some(Hunk {
+                old: first.old_range().start..last.old_range().end,
+                new: first.new_range().start..last.new_range().end,
+                lines,
+            })
+        })
+        .collect()
+}
+
+/// Unified diff of `hunks` against the first file; the new side's line
+/// numbers only count the hunks that are included
+fn patch(hunks: &[&Hunk], name1: &str, name2: &str) -> String {
+    if hunks.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("--- {}\n+++ {}\n", name1, name2);
+    let mut offset: isize = 0;
+    for hunk in hunks {
+        let new_start = (hunk.old.start as isize + offset) as usize;
+        let new_len = hunk.new.len();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(hunk.old.start, hunk.old.len()),
+            range(new_start, new_len)
+        ));
+        for (tag, line) in &hunk.lines {
+            let sign = match tag {
+                ChangeTag::Delete => '-',
+                ChangeTag::Insert => '+',
+                ChangeTag::Equal => ' ',
+            };
+            out.push(sign);
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+        offset += new_len as isize - hunk.old.len() as isize;
+    }
+    out
+}
+
+/// `start,len` for a hunk header, 1-based; an empty range names the line
+/// before it
+fn range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+type Screen = color::Plain<io::Stderr>;
+
+#[allow(clippy::collapsible_match)]
+fn run(hunks: &[Hunk], name1: &str, name2: &str) -> io::Result<Vec<Decision>> {
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "Diff review requires an interactive terminal (TTY)",
+        ));
+    }
+
+    let mut screen = color::Plain::new(io::stderr(), !color::enabled(), color::depth());
+    let mut decisions = vec![Decision::Pending; hunks.len()];
+    let (mut current, mut scroll) = (0, 0);
+
+    terminal::enable_raw_mode()?;
+    execute!(screen, EnterAlternateScreen, Hide)?;
+
+    let result = loop {
+        let (_, rows) = size();
+        // Title, status, a blank line and the key help
+        let body_rows = (rows as usize).saturating_sub(4).max(1);
+        draw(
+            &mut screen,
+            hunks,
+            &decisions,
+            current,
+            scroll,
+            body_rows,
+            (name1, name2),
+        )?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let next = |current: &mut usize, scroll: &mut usize| {
+            if *current + 1 < hunks.len() {
+                *current += 1;
+                *scroll = 0;
+            }
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+            }
+            KeyCode::Char('n') | KeyCode::Right | KeyCode::Tab => next(&mut current, &mut scroll),
+            KeyCode::Char('p') | KeyCode::Left | KeyCode::BackTab => {
+                if current > 0 {
+                    current -= 1;
+                    scroll = 0;
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('y') => {
+                decisions[current] = Decision::Accepted;
+                next(&mut current, &mut scroll);
+            }
+            KeyCode::Char('r') | KeyCode::Char('x') => {
+                decisions[current] = Decision::Rejected;
+                next(&mut current, &mut scroll);
+            }
+            KeyCode::Char('u') => decisions[current] = Decision::Pending,
+            KeyCode::Char('A') => decisions.fill(Decision::Accepted),
+            KeyCode::Char('R') => decisions.fill(Decision::Rejected),
+            KeyCode::Down | KeyCode::Char('j') => {
+                if scroll + body_rows < hunks[current].lines.len() + 1 {
+                    scroll += 1;
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => scroll = scroll.saturating_sub(1),
+            KeyCode::Enter | KeyCode::Char('q') => break Ok(decisions.clone()),
+            KeyCode::Esc => {
+                break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+            }
+            _ => {}
+        }
+    };
+
+    execute!(screen, Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn draw(
+    screen: &mut Screen,
+    hunks: &[Hunk],
+    decisions: &[Decision],
+    current: usize,
+    scroll: usize,
+    body_rows: usize,
+    (name1, name2): (&str, &str),
+) -> io::Result<()> {
+    let hunk = &hunks[current];
+    let count = |d: Decision| decisions.iter().filter(|x| **x == d).count();
+    let (label, color) = match decisions[current] {
+        Decision::Pending => ("pending", Color::Yellow),
+        Decision::Accepted => ("accepted", Color::Green),
+        Decision::Rejected => ("rejected", Color::Red),
+    };
+
+    execute!(
+        screen,
+        Clear(ClearType::All),
+        MoveTo(0, 0),
+        Print(format!("{} → {}", name1, name2).cyan().bold()),
+        MoveTo(0, 1),
+        Print(format!("Hunk {}/{} ", current + 1, hunks.len()).bold()),
+        SetForegroundColor(color),
+        Print(label),
+        ResetColor,
+        Print(
+            format!(
+                "  · {} accepted, {} rejected, {} pending",
+                count(Decision::Accepted),
+                count(Decision::Rejected),
+                count(Decision::Pending)
+            )
+            .dim()
+        ),
+    )?;
+
+    let header = format!(
+        "@@ -{} +{} @@",
+        range(hunk.old.start, hunk.old.len()),
+        range(hunk.new.start, hunk.new.len())
+    );
+    let (cols, _) = size();
+    let body = std::iter::once((None, header)).chain(
+        hunk.lines
+            .iter()
+            .map(|(tag, line)| (Some(*tag), line.trim_end_matches('\n').to_string())),
+    );
+    for (row, (tag, line)) in body.skip(scroll).take(body_rows).enumerate() {
+        let (sign, color) = match tag {
+            None => ("", Color::Cyan),
+            Some(ChangeTag::Delete) => ("-", Color::Red),
+            Some(ChangeTag::Insert) => ("+", Color::Green),
+            Some(ChangeTag::Equal) => (" ", Color::DarkGrey),
+        };
+        let text = text::truncate(&format!("{}{}", sign, line), cols as usize, "…");
+        execute!(
+            screen,
+            MoveTo(0, row as u16 + 3),
+            SetForegroundColor(color),
+            Print(text),
+            ResetColor
+        )?;
+    }
+
+    let (_, rows) = size();
+    execute!(
+        screen,
+        MoveTo(0, rows.saturating_sub(1)),
+        Print(
+            "a accept · r reject · u undo · n/p next/prev · A/R all · ↑/↓ scroll · Enter/q done · Esc cancel"
+                .dim()
+        )
+    )?;
+    screen.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    const NEW: &str = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+
+    #[test]
+    fn test_hunks_follow_grouped_ops() {
+        let hunks = hunks(OLD, NEW, 1);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].old.clone(), hunks[0].new.clone()), (0..3, 0..3));
+        assert_eq!(hunks[0].lines[1], (ChangeTag::Delete, "b\n".to_string()));
+        assert_eq!((hunks[1].old.clone(), hunks[1].new.clone()), (9..10, 9..12));
+    }
+
+    #[test]
+    fn test_patch_of_all_hunks_matches_a_unified_diff() {
+        let hunks = hunks(OLD, NEW, 1);
+        let all: Vec<&Hunk> = hunks.iter().collect();
+        assert_eq!(
+            patch(&all, "old.txt", "new.txt"),
+            "--- old.txt\n+++ new.txt\n\
+             @@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n\
+             @@ -10 +10,3 @@\n j\n+k\n+l\n"
+        );
+    }
+
+    #[test]
+    fn test_patch_renumbers_after_skipped_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "x\ny\na\nb\nc\nd\ne\nf\ng\nH\n";
+        let hunks = hunks(old, new, 0);
+        assert_eq!(hunks.len(), 2);
+        // Without the first hunk's two added lines, h is still line 8
+        assert_eq!(
+            patch(&[&hunks[1]], "a", "b"),
+            "--- a\n+++ b\n@@ -8 +8 @@\n-h\n+H\n"
+        );
+        assert_eq!(patch(&[], "a", "b"), "");
+    }
+
+    #[test]
+    fn test_patch_marks_missing_final_newline() {
+        let hunks = hunks("a\nb", "a\nc", 0);
+        let all: Vec<&Hunk> = hunks.iter().collect();
+        assert!(patch(&all, "a", "b")
+            .ends_with("-b\n\\ No newline at end of file\n+c\n\\ No newline at end of file\n"));
+    }
+
+    #[test]
+    fn test_range_headers() {
+        assert_eq!(range(0, 0), "0,0");
+        assert_eq!(range(4, 1), "5");
+        assert_eq!(range(4, 3), "5,3");
+    }
+}
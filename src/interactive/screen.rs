@@ -0,0 +1,116 @@
+//! Where a picker draws: the alternate screen, or a band of rows under the
+//! prompt (`--inline`)
+//!
+//! Inline mode leaves the scrollback alone. It reserves rows below the
+//! cursor, scrolling the prompt up if there aren't enough, and on exit
+//! clears them and puts the cursor back where the band started, so the
+//! shell prompt comes back right after the command as if nothing was drawn.
+
+use crossterm::{
+    cursor::{self, Hide, MoveTo, Show},
+    execute,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Screen {
+    inline: bool,
+    top: u16,
+    rows: u16,
+}
+
+impl Screen {
+    /// Take over the whole screen, or with `inline` the given number of
+    /// rows below the cursor (at most the terminal's height). Raw mode must
+    /// already be on so the cursor position can be read.
+    pub fn enter(out: &mut impl Write, inline: Option<usize>) -> io::Result<Self> {
+        let Some(wanted) = inline else {
+            execute!(out, EnterAlternateScreen, Hide)?;
+            return Ok(Self {
+                inline: false,
+                top: 0,
+                rows: 0,
+            });
+        };
+
+        let (_, term_rows) = size();
+        let rows = wanted.clamp(1, term_rows as usize) as u16;
+        let (col, row) = cursor::position().unwrap_or((0, term_rows - 1));
+        // Start on a fresh line if the cursor sits after some text
+        let start = if col > 0 { row + 1 } else { row };
+        // Newlines at the bottom scroll the prompt up into the scrollback
+        let needed = (start + rows).saturating_sub(term_rows);
+        if needed > 0 {
+            execute!(
+                out,
+                MoveTo(0, term_rows - 1),
+                Print("\n".repeat(needed as usize))
+            )?;
+        }
+        let top = start.min(term_rows - rows);
+        execute!(out, MoveTo(0, top), Clear(ClearType::FromCursorDown), Hide)?;
+        Ok(Self {
+            inline: true,
+            top,
+            rows,
+        })
+    }
+
+    /// Rows available to draw in; follows the terminal size when full-screen
+    pub fn rows(&self) -> usize {
+        if self.inline {
+            self.rows as usize
+        } else {
+            size().1 as usize
+        }
+    }
+
+    /// Blank the drawing area and move to its first row
+    pub fn clear(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.inline {
+            execute!(out, MoveTo(0, self.top), Clear(ClearType::FromCursorDown))
+        } else {
+            execute!(out, Clear(ClearType::All), MoveTo(0, 0))
+        }
+    }
+
+    /// Screen row the area starts on
+    pub fn top(&self) -> u16 {
+        self.top
+    }
+
+    /// Cursor movement to the start of `row` within the area
+    pub fn goto(&self, row: usize) -> MoveTo {
+        MoveTo(0, self.top + row as u16)
+    }
+
+    /// Cursor movement to column `col` of `row` within the area
+    pub fn at(&self, col: u16, row: usize) -> MoveTo {
+        MoveTo(col, self.top + row as u16)
+    }
+
+    /// Give the screen back: leave the alternate screen, or wipe the band
+    /// and return the cursor to its top
+    pub fn leave(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.inline {
+            execute!(
+                out,
+                MoveTo(0, self.top),
+                Clear(ClearType::FromCursorDown),
+                Show
+            )
+        } else {
+            execute!(out, Show, LeaveAlternateScreen)
+        }
+    }
+}
+
+/// Terminal columns and rows; 80x24 when unknown or reported as zero
+pub fn size() -> (u16, u16) {
+    terminal::size()
+        .ok()
+        .filter(|&(cols, rows)| cols > 0 && rows > 0)
+        .unwrap_or((80, 24))
+}
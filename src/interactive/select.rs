@@ -1,29 +1,72 @@
+use super::filter;
+use super::prompt;
+use super::screen::Screen;
+use crate::output::color;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     style::{Color, Print, ResetColor, SetForegroundColor, Stylize},
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal,
 };
 use std::{
     collections::HashSet,
-    io::{self, IsTerminal, Write},
+    io::{self, Write},
 };
 
-pub fn render(prompt: &str, options: &[String], multi: bool) {
+/// Rows around the options: prompt, blank line, blank line, help
+const CHROME_ROWS: usize = 4;
+
+/// Run the menu; options named in `danger` are drawn in red and need a
+/// second confirmation before they can be picked, and with `multi` those in
+/// `selected` start out ticked. An option written `label::description`
+/// shows its description and prints only its label. Typing filters the list;
+/// at most `height` options show at once; `inline` draws below the prompt
+/// instead of full-screen. Without a TTY the choice is `default` instead.
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    prompt_text: &str,
+    options: &[String],
+    multi: bool,
+    danger: &[String],
+    selected: &[String],
+    height: Option<usize>,
+    inline: bool,
+    default: Option<&str>,
+    no_tty_error: bool,
+) {
     if options.is_empty() {
         eprintln!("Error: No options provided");
         std::process::exit(1);
     }
-
-    match run_select(prompt, options, multi) {
-        Ok(selected) => {
-            if multi {
-                println!("{}", selected.join(","));
-            } else {
-                println!("{}", selected[0]);
-            }
+    let (labels, descriptions): (Vec<String>, Vec<Option<String>>) = options
+        .iter()
+        .map(|option| split_description(option))
+        .unzip();
+    let indices = option_indices(&labels, danger, "danger")
+        .and_then(|danger| Ok((danger, option_indices(&labels, selected, "selected")?)));
+    let (danger, selected) = match indices {
+        Ok(indices) => indices,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
         }
+    };
+
+    let menu = Menu {
+        prompt: prompt_text,
+        labels: &labels,
+        descriptions: &descriptions,
+        multi,
+        danger: &danger,
+    };
+    let result = match prompt::answer_without_tty("Select", default, no_tty_error) {
+        Ok(Some(answer)) => default_picks(&labels, multi, &answer).map_err(io::Error::other),
+        Ok(None) => run_select(&menu, selected, height, inline),
+        Err(e) => Err(io::Error::other(e)),
+    };
+    match result {
+        Ok(selected) => println!("{}", selected.join(",")),
+        Err(e) if prompt::is_cancel(&e) => prompt::exit_cancelled(),
         Err(e) => {
             eprintln!("Error: {}", e);
             std::process::exit(1);
@@ -31,105 +74,231 @@ pub fn render(prompt: &str, options: &[String], multi: bool) {
     }
 }
 
-fn run_select(prompt: &str, options: &[String], multi: bool) -> io::Result<Vec<String>> {
-    // Check for interactive terminal
-    if !std::io::stdin().is_terminal() {
-        return Err(io::Error::other(
-            "Select requires an interactive terminal (TTY)",
-        ));
+/// What the menu shows, fixed while it runs
+struct Menu<'a> {
+    prompt: &'a str,
+    labels: &'a [String],
+    descriptions: &'a [Option<String>],
+    multi: bool,
+    danger: &'a HashSet<usize>,
+}
+
+/// Split "label::description"; options without `::` have no description
+fn split_description(option: &str) -> (String, Option<String>) {
+    match option.split_once("::") {
+        Some((label, description)) => (
+            label.trim().to_string(),
+            Some(description.trim().to_string()).filter(|d| !d.is_empty()),
+        ),
+        None => (option.to_string(), None),
+    }
+}
+
+/// The options named by `--default`: one, or a comma-separated list with
+/// `multi`; each must be in the list
+fn default_picks(options: &[String], multi: bool, answer: &str) -> Result<Vec<String>, String> {
+    if answer.is_empty() {
+        return Ok(Vec::new());
     }
+    let picks: Vec<&str> = if multi {
+        answer.split(',').map(str::trim).collect()
+    } else {
+        vec![answer]
+    };
+    picks
+        .into_iter()
+        .map(|pick| {
+            options
+                .iter()
+                .find(|o| o.as_str() == pick)
+                .cloned()
+                .ok_or_else(|| format!("--default option '{}' is not in the list", pick))
+        })
+        .collect()
+}
 
-    let mut stdout = io::stdout();
-    let mut selected_idx = 0;
-    let mut selected_items: HashSet<usize> = HashSet::new();
+/// Positions of the options a `--<flag>` names; every name must be one of
+/// `options`
+fn option_indices(
+    options: &[String],
+    names: &[String],
+    flag: &str,
+) -> Result<HashSet<usize>, String> {
+    names
+        .iter()
+        .map(|name| {
+            options
+                .iter()
+                .position(|o| o == name)
+                .ok_or_else(|| format!("--{} option '{}' is not in the list", flag, name))
+        })
+        .collect()
+}
+
+/// The picked options that are destructive, in menu order
+fn destructive_picks(picks: &[usize], danger: &HashSet<usize>) -> Vec<usize> {
+    let mut found: Vec<usize> = picks
+        .iter()
+        .copied()
+        .filter(|i| danger.contains(i))
+        .collect();
+    found.sort_unstable();
+    found
+}
+
+#[allow(clippy::collapsible_match)]
+fn run_select(
+    menu: &Menu,
+    mut selected_items: HashSet<usize>,
+    height: Option<usize>,
+    inline: bool,
+) -> io::Result<Vec<String>> {
+    let mut stdout = color::stdout();
+    let labels = menu.labels;
+    // Position of the cursor among the options matching `query`
+    let mut cursor = 0;
+    let mut query = String::new();
+    // Picks waiting on the destructive-action confirmation
+    let mut pending: Option<Vec<usize>> = None;
 
     // Setup terminal
     terminal::enable_raw_mode()?;
-    execute!(stdout, EnterAlternateScreen, Hide)?;
+    let list_rows = height.unwrap_or(labels.len()).min(labels.len());
+    let screen = Screen::enter(&mut stdout, inline.then_some(list_rows + CHROME_ROWS))?;
 
     let result = loop {
+        let matches: Vec<usize> = filter::fuzzy_match(labels, &query)
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect();
+        cursor = cursor.min(matches.len().saturating_sub(1));
+
         // Render the prompt and options
+        let visible = list_rows.min(screen.rows().saturating_sub(CHROME_ROWS).max(1));
         render_menu(
             &mut stdout,
-            prompt,
-            options,
-            selected_idx,
+            &screen,
+            visible,
+            menu,
+            &query,
+            &matches,
+            cursor,
             &selected_items,
-            multi,
+            pending.as_deref(),
         )?;
 
         // Handle key events
-        if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+        if let Event::Key(KeyEvent {
+            code, modifiers, ..
+        }) = event::read()?
+        {
+            if let Some(picks) = pending.take() {
+                // Only an explicit y goes ahead; anything else returns to the menu
+                if matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    break Ok(picks.iter().map(|&idx| labels[idx].clone()).collect());
+                }
+                continue;
+            }
             match code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    selected_idx = selected_idx.saturating_sub(1);
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+                }
+                KeyCode::Up => {
+                    cursor = cursor.saturating_sub(1);
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if selected_idx < options.len() - 1 {
-                        selected_idx += 1;
+                KeyCode::Down => {
+                    if cursor + 1 < matches.len() {
+                        cursor += 1;
                     }
                 }
-                KeyCode::Char(' ') if multi => {
-                    if selected_items.contains(&selected_idx) {
-                        selected_items.remove(&selected_idx);
-                    } else {
-                        selected_items.insert(selected_idx);
+                KeyCode::Char(' ') if menu.multi => {
+                    if let Some(&idx) = matches.get(cursor) {
+                        if !selected_items.remove(&idx) {
+                            selected_items.insert(idx);
+                        }
                     }
                 }
                 KeyCode::Enter => {
-                    if multi {
-                        let mut result_vec = selected_items
-                            .iter()
-                            .map(|&idx| options[idx].clone())
-                            .collect::<Vec<String>>();
-                        result_vec
-                            .sort_by_key(|item| options.iter().position(|x| x == item).unwrap()); // Maintain original order
-                        break Ok(result_vec);
+                    let mut picks: Vec<usize> = if menu.multi {
+                        selected_items.iter().copied().collect()
                     } else {
-                        break Ok(vec![options[selected_idx].clone()]);
+                        matches.get(cursor).copied().into_iter().collect()
+                    };
+                    if picks.is_empty() && !menu.multi {
+                        continue;
+                    }
+                    picks.sort_unstable(); // Maintain original order
+                    if destructive_picks(&picks, menu.danger).is_empty() {
+                        break Ok(picks.iter().map(|&idx| labels[idx].clone()).collect());
                     }
+                    pending = Some(picks);
                 }
-                KeyCode::Esc | KeyCode::Char('q') => {
+                KeyCode::Backspace => {
+                    query.pop();
+                }
+                KeyCode::Esc if !query.is_empty() => query.clear(),
+                KeyCode::Esc => {
                     break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
                 }
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    cursor = 0;
+                }
                 _ => {}
             }
         }
     };
 
     // Cleanup terminal
-    execute!(stdout, Show, LeaveAlternateScreen)?;
+    screen.leave(&mut stdout)?;
     terminal::disable_raw_mode()?;
 
     result
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_menu(
-    stdout: &mut io::Stdout,
-    prompt: &str,
-    options: &[String],
-    selected_idx: usize,
+    stdout: &mut color::Stdout,
+    screen: &Screen,
+    visible: usize,
+    menu: &Menu,
+    query: &str,
+    matches: &[usize],
+    cursor: usize,
     selected_items: &HashSet<usize>,
-    multi: bool,
+    pending: Option<&[usize]>,
 ) -> io::Result<()> {
-    execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    screen.clear(stdout)?;
 
-    // Print prompt with emoji
+    // Print prompt with emoji, then the filter typed so far
     execute!(
         stdout,
         SetForegroundColor(Color::Cyan),
         Print("❯ "),
         ResetColor,
-        Print(prompt.to_string().bold()),
-        Print("\n\n")
+        Print(menu.prompt.to_string().bold()),
+        SetForegroundColor(Color::Yellow),
+        Print(format!(" {}", query)),
+        ResetColor,
     )?;
 
-    // Print options
-    for (idx, option) in options.iter().enumerate() {
-        let is_current = idx == selected_idx;
+    if matches.is_empty() {
+        execute!(
+            stdout,
+            screen.goto(2),
+            SetForegroundColor(Color::DarkGrey),
+            Print("  No matches"),
+            ResetColor,
+        )?;
+    }
+
+    // Print options, scrolled so the current one shows
+    let first = (cursor + 1).saturating_sub(visible);
+    for (row, &idx) in matches.iter().enumerate().skip(first).take(visible) {
+        let is_current = row == cursor;
         let is_selected_multi = selected_items.contains(&idx);
 
-        let prefix = if multi {
+        let prefix = if menu.multi {
             if is_selected_multi {
                 "[x]"
             } else {
@@ -140,40 +309,116 @@ fn render_menu(
         };
 
         let indicator = if is_current { "❯" } else { " " };
+        let option = &menu.labels[idx];
         let formatted_option = if is_current {
             option.clone().bold().to_string()
         } else {
             option.clone()
         };
+        let color = match (menu.danger.contains(&idx), is_current) {
+            (true, _) => Color::Red,
+            (false, true) => Color::Green,
+            (false, false) => Color::Reset,
+        };
+        let marker = if menu.danger.contains(&idx) {
+            " ⚠"
+        } else {
+            ""
+        };
 
         execute!(
             stdout,
-            SetForegroundColor(if is_current {
-                Color::Green
-            } else {
-                Color::Reset
+            screen.goto(row - first + 2),
+            SetForegroundColor(color),
+            Print(format!(
+                "{} {} {}{}",
+                indicator, prefix, formatted_option, marker
+            )),
+            SetForegroundColor(Color::DarkGrey),
+            Print(match &menu.descriptions[idx] {
+                Some(description) => format!("  {}", description),
+                None => String::new(),
             }),
-            Print(format!("{} {} {}", indicator, prefix, formatted_option)),
             ResetColor,
-            Print("\n")
         )?;
     }
 
-    // Print help text
-    let help_text = if multi {
-        "↑↓: Navigate • Space: Toggle • Enter: Select • Esc: Cancel"
+    if let Some(picks) = pending {
+        let names: Vec<&str> = destructive_picks(picks, menu.danger)
+            .iter()
+            .map(|&idx| menu.labels[idx].as_str())
+            .collect();
+        execute!(
+            stdout,
+            screen.goto(visible + 3),
+            SetForegroundColor(Color::Red),
+            Print(format!("⚠️  {} is destructive.", names.join(", ")).bold()),
+            Print(" Press y to confirm, any other key to go back"),
+            ResetColor,
+        )?;
+        stdout.flush()?;
+        return Ok(());
+    }
+
+    // Print help text, with the position once the list doesn't all fit
+    let help_text = if menu.multi {
+        "↑↓: Navigate • Type: Filter • Space: Toggle • Enter: Select • Esc: Cancel"
     } else {
-        "↑↓: Navigate • Enter: Select • Esc: Cancel"
+        "↑↓: Navigate • Type: Filter • Enter: Select • Esc: Cancel"
+    };
+    let position = if matches.len() > visible || !query.is_empty() {
+        format!("  {}/{}", (cursor + 1).min(matches.len()), matches.len())
+    } else {
+        String::new()
     };
 
     execute!(
         stdout,
-        Print("\n"),
+        screen.goto(visible + 3),
         SetForegroundColor(Color::DarkGrey),
         Print(help_text),
+        Print(position),
         ResetColor
     )?;
 
     stdout.flush()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_option_indices() {
+        let options = names(&["Restart", "Drop database", "Wipe cache"]);
+        let danger =
+            option_indices(&options, &names(&["Wipe cache", "Drop database"]), "danger").unwrap();
+        assert_eq!(danger, HashSet::from([1, 2]));
+        assert_eq!(
+            option_indices(&options, &names(&["Nuke"]), "selected"),
+            Err("--selected option 'Nuke' is not in the list".to_string())
+        );
+    }
+
+    #[test]
+    fn test_split_description() {
+        assert_eq!(
+            split_description("prod::Production cluster"),
+            ("prod".to_string(), Some("Production cluster".to_string()))
+        );
+        assert_eq!(split_description("dev"), ("dev".to_string(), None));
+        assert_eq!(split_description("qa::"), ("qa".to_string(), None));
+    }
+
+    #[test]
+    fn test_destructive_picks() {
+        let danger = HashSet::from([1, 2]);
+        assert_eq!(destructive_picks(&[0], &danger), Vec::<usize>::new());
+        assert_eq!(destructive_picks(&[2, 0, 1], &danger), vec![1, 2]);
+    }
+}
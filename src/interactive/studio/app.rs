@@ -10,13 +10,22 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 use std::collections::HashMap;
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, IsTerminal};
 
 use super::layout::{DragState, StudioLayout};
 use super::registry::{get_all_components, ComponentDef, ParamType};
+use super::shell::{self, ShellRun};
 use super::storage::StudioStorage;
+use super::theme_editor::ThemeEditor;
 use super::ui;
 use super::widgets::{DropdownState, SliderState, ToggleState};
+use crate::design::theme::{self, Theme, ThemeFile, ThemeSource};
+use crate::interactive::clipboard;
+use crate::interactive::filter::fuzzy_match;
+use crate::output::atomic;
+use crate::render::progress::ProgressState;
+use crate::render::spinner::{SpinnerState, FRAME_MS};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Widget editing mode
 #[derive(Debug, Clone, PartialEq)]
@@ -46,6 +55,202 @@ pub enum SidebarSection {
     Components,
 }
 
+/// One row of the sidebar list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SidebarRow {
+    Header(&'static str),
+    /// Index into the saved favorites
+    Favorite(usize),
+    /// Index into the recently used components
+    Recent(usize),
+    /// Index into the component registry
+    Component(usize),
+}
+
+/// Color theme for the studio chrome (borders, focus highlights)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StudioTheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl StudioTheme {
+    pub fn toggle(self) -> Self {
+        match self {
+            StudioTheme::Dark => StudioTheme::Light,
+            StudioTheme::Light => StudioTheme::Dark,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StudioTheme::Dark => "dark",
+            StudioTheme::Light => "light",
+        }
+    }
+
+    /// Color for focused borders and highlights
+    pub fn accent(self) -> Color {
+        match self {
+            StudioTheme::Dark => Color::Cyan,
+            StudioTheme::Light => Color::Blue,
+        }
+    }
+
+    /// Color for unfocused borders and hints
+    pub fn muted(self) -> Color {
+        match self {
+            StudioTheme::Dark => Color::DarkGray,
+            StudioTheme::Light => Color::Gray,
+        }
+    }
+}
+
+/// Terminal widths the preview can simulate (`None` fits the pane)
+pub const PREVIEW_WIDTHS: [Option<u16>; 4] = [None, Some(40), Some(80), Some(120)];
+
+/// Formats `e` can export the current component as, cycled with `E`
+pub const EXPORT_FORMATS: [&str; 3] = ["svg", "png", "html"];
+
+/// Color depth simulated in the preview pane
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorDepth {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
+
+impl ColorDepth {
+    pub fn next(self) -> Self {
+        match self {
+            ColorDepth::TrueColor => ColorDepth::Ansi256,
+            ColorDepth::Ansi256 => ColorDepth::Ansi16,
+            ColorDepth::Ansi16 => ColorDepth::Monochrome,
+            ColorDepth::Monochrome => ColorDepth::TrueColor,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorDepth::TrueColor => "truecolor",
+            ColorDepth::Ansi256 => "256 colors",
+            ColorDepth::Ansi16 => "16 colors",
+            ColorDepth::Monochrome => "no color",
+        }
+    }
+}
+
+/// Ticks a finished fill stays up before it starts over
+const PLAYBACK_HOLD: u32 = 12;
+
+/// What the preview's animation is showing
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaybackState {
+    Spinner(SpinnerState),
+    /// A progress bar or gauge filling up to its value, then starting over
+    Fill {
+        state: ProgressState,
+        hold: u32,
+    },
+}
+
+/// An animation playing in the Live Preview panel
+#[derive(Debug, Clone)]
+pub struct Playback {
+    pub state: PlaybackState,
+    pub paused: bool,
+    /// Param values the animation was started from; edits restart it
+    values: HashMap<String, String>,
+    last_tick: Instant,
+}
+
+impl Playback {
+    fn start(component: &ComponentDef, values: &HashMap<String, String>) -> Self {
+        let value = |name: &str, default: &str| {
+            values
+                .get(name)
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| default.to_string())
+        };
+        let state = if component.name == "spinner" {
+            PlaybackState::Spinner(SpinnerState::new(
+                &value("style", "dots"),
+                &value("message", ""),
+            ))
+        } else {
+            // The first param is the value the bar or gauge fills up to
+            let target = component
+                .params
+                .first()
+                .and_then(|p| values.get(p.name))
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.0);
+            PlaybackState::Fill {
+                state: ProgressState::new(target.clamp(0.0, 100.0) as u8, "blocks"),
+                hold: 0,
+            }
+        };
+        Self {
+            state,
+            paused: false,
+            values: values.clone(),
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Advance one frame
+    fn tick(&mut self) {
+        match &mut self.state {
+            PlaybackState::Spinner(spinner) => spinner.tick(),
+            PlaybackState::Fill { state, hold } => {
+                if state.is_done() {
+                    *hold += 1;
+                    if *hold >= PLAYBACK_HOLD {
+                        let target = state.percent();
+                        *state = ProgressState::new(target, "blocks");
+                        *hold = 0;
+                    }
+                } else {
+                    state.tick();
+                }
+            }
+        }
+    }
+
+    /// Percentage to draw a filling component at
+    pub fn percent(&self) -> Option<u8> {
+        match &self.state {
+            PlaybackState::Fill { state, .. } => Some(state.percent()),
+            PlaybackState::Spinner(_) => None,
+        }
+    }
+}
+
+/// Action run from the command palette
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteAction {
+    JumpToComponent(usize),
+    ToggleTheme,
+    CyclePreviewWidth,
+    CycleColorDepth,
+    TogglePlayback,
+    RunCommand,
+    OpenFavorites,
+    ExportPreview,
+    CycleExportFormat,
+    ExportCommand,
+    CopyCommand,
+    OpenThemeEditor,
+    SaveFavorite,
+    ResetParams,
+    ResetLayout,
+    ToggleHelp,
+    Quit,
+}
+
 /// Main application state
 pub struct StudioApp {
     pub components: Vec<ComponentDef>,
@@ -77,6 +282,32 @@ pub struct StudioApp {
     pub favorite_name_buffer: String,
     /// Flag indicating storage needs to be saved (batched writes)
     pub storage_dirty: bool,
+    /// Active color theme
+    pub theme: StudioTheme,
+    /// Whether the Ctrl+P command palette is open
+    pub show_palette: bool,
+    /// Fuzzy query typed into the command palette
+    pub palette_query: String,
+    /// Selected index within the filtered palette entries
+    pub palette_index: usize,
+    /// Simulated terminal width for the preview (`None` fits the pane)
+    pub preview_width: Option<u16>,
+    /// Simulated color depth for the preview
+    pub color_depth: ColorDepth,
+    /// File format `e` exports to, one of [`EXPORT_FORMATS`]
+    pub export_format: &'static str,
+    /// Animation playing in the preview, started with Space
+    pub playback: Option<Playback>,
+    /// The generated command running for real in the preview panel
+    pub shell: Option<ShellRun>,
+    /// Whether the Ctrl+O favorites list is open
+    pub show_favorites: bool,
+    /// Fuzzy query typed into the favorites list
+    pub favorites_query: String,
+    /// Selected index within the filtered favorites
+    pub favorites_index: usize,
+    /// The `T` theme editor, while open
+    pub theme_editor: Option<ThemeEditor>,
 }
 
 impl StudioApp {
@@ -113,6 +344,19 @@ impl StudioApp {
             naming_favorite: false,
             favorite_name_buffer: String::new(),
             storage_dirty: false,
+            theme: StudioTheme::default(),
+            show_palette: false,
+            palette_query: String::new(),
+            palette_index: 0,
+            preview_width: None,
+            color_depth: ColorDepth::default(),
+            export_format: EXPORT_FORMATS[0],
+            playback: None,
+            shell: None,
+            show_favorites: false,
+            favorites_query: String::new(),
+            favorites_index: 0,
+            theme_editor: None,
         }
     }
 
@@ -172,14 +416,15 @@ impl StudioApp {
                 self.selected_component = pos;
                 self.param_values = favorite.params;
                 self.selected_param = 0;
+                self.playback = None;
                 self.set_status(&format!("★ Loaded: {}", favorite.name));
             }
         }
     }
 
-    /// Load a history entry
+    /// Load the `index`th recently used component with its last params
     pub fn load_history(&mut self, index: usize) {
-        if let Some(entry) = self.storage.history.get(index).cloned() {
+        if let Some(entry) = self.storage.recent().get(index).cloned().cloned() {
             if let Some(pos) = self
                 .components
                 .iter()
@@ -188,6 +433,7 @@ impl StudioApp {
                 self.selected_component = pos;
                 self.param_values = entry.params;
                 self.selected_param = 0;
+                self.playback = None;
                 self.set_status("⏱ Restored from history");
             }
         }
@@ -217,8 +463,69 @@ impl StudioApp {
         }
     }
 
+    /// Sidebar rows: favorites and recently used components, when there are
+    /// any, then every component under its category
+    pub fn sidebar_rows(&self) -> Vec<SidebarRow> {
+        let mut rows = Vec::new();
+        if !self.storage.favorites.is_empty() {
+            rows.push(SidebarRow::Header("★ Favorites"));
+            rows.extend((0..self.storage.favorites.len()).map(SidebarRow::Favorite));
+        }
+        let recent = self.storage.recent().len();
+        if recent > 0 {
+            rows.push(SidebarRow::Header("Recent"));
+            rows.extend((0..recent).map(SidebarRow::Recent));
+        }
+        let mut current_category = "";
+        for (idx, component) in self.components.iter().enumerate() {
+            if component.category != current_category {
+                current_category = component.category;
+                rows.push(SidebarRow::Header(component.category));
+            }
+            rows.push(SidebarRow::Component(idx));
+        }
+        rows
+    }
+
+    /// Row of the sidebar entry that keys act on
+    pub fn sidebar_highlight(&self, rows: &[SidebarRow]) -> Option<usize> {
+        let target = match self.sidebar_section {
+            SidebarSection::Favorites => SidebarRow::Favorite(self.section_index),
+            SidebarSection::History => SidebarRow::Recent(self.section_index),
+            SidebarSection::Components => SidebarRow::Component(self.selected_component),
+        };
+        rows.iter().position(|row| *row == target)
+    }
+
+    /// First row shown in a sidebar `height` rows tall, so the highlighted
+    /// entry stays in view
+    pub fn sidebar_offset(&self, rows: &[SidebarRow], height: usize) -> usize {
+        let highlight = self.sidebar_highlight(rows).unwrap_or(0);
+        highlight.saturating_sub(height.saturating_sub(1))
+    }
+
+    /// Select `component` with `values` applied over its defaults, focusing
+    /// the params panel; false if there is no such component
+    pub fn open_component(&mut self, component: &str, values: &[(&str, &str)]) -> bool {
+        let Some(pos) = self.components.iter().position(|c| c.name == component) else {
+            return false;
+        };
+        self.selected_component = pos;
+        self.update_param_values();
+        for (name, value) in values {
+            if self.param_values.contains_key(*name) {
+                self.param_values
+                    .insert(name.to_string(), value.to_string());
+            }
+        }
+        self.focused_panel = FocusedPanel::Params;
+        true
+    }
+
     /// Update param values when component changes
     fn update_param_values(&mut self) {
+        self.playback = None;
+        self.shell = None;
         self.param_values.clear();
         let params: Vec<_> = self
             .components
@@ -236,12 +543,472 @@ impl StudioApp {
         self.selected_param = 0;
     }
 
+    /// All command palette entries, in display order
+    pub fn palette_entries(&self) -> Vec<(String, PaletteAction)> {
+        let mut entries: Vec<(String, PaletteAction)> = self
+            .components
+            .iter()
+            .enumerate()
+            .map(|(idx, c)| {
+                (
+                    format!("Go to {} ({})", c.name, c.category),
+                    PaletteAction::JumpToComponent(idx),
+                )
+            })
+            .collect();
+        entries.extend([
+            (
+                format!("Toggle theme ({})", self.theme.toggle().name()),
+                PaletteAction::ToggleTheme,
+            ),
+            (
+                "Cycle preview width (fit/40/80/120)".to_string(),
+                PaletteAction::CyclePreviewWidth,
+            ),
+            (
+                "Cycle preview color depth".to_string(),
+                PaletteAction::CycleColorDepth,
+            ),
+            (
+                "Play/pause preview animation".to_string(),
+                PaletteAction::TogglePlayback,
+            ),
+            (
+                "Run command in preview".to_string(),
+                PaletteAction::RunCommand,
+            ),
+            (
+                format!("Export preview as {}", self.export_format.to_uppercase()),
+                PaletteAction::ExportPreview,
+            ),
+            (
+                "Cycle export format".to_string(),
+                PaletteAction::CycleExportFormat,
+            ),
+            (
+                "Export command to file".to_string(),
+                PaletteAction::ExportCommand,
+            ),
+            (
+                "Copy command to clipboard".to_string(),
+                PaletteAction::CopyCommand,
+            ),
+            (
+                "Edit global theme...".to_string(),
+                PaletteAction::OpenThemeEditor,
+            ),
+            ("Save as favorite".to_string(), PaletteAction::SaveFavorite),
+            ("Load favorite...".to_string(), PaletteAction::OpenFavorites),
+            ("Reset parameters".to_string(), PaletteAction::ResetParams),
+            ("Reset layout".to_string(), PaletteAction::ResetLayout),
+            ("Toggle help".to_string(), PaletteAction::ToggleHelp),
+            ("Quit studio".to_string(), PaletteAction::Quit),
+        ]);
+        entries
+    }
+
+    /// Palette entries matching the current query
+    pub fn palette_matches(&self) -> Vec<(String, PaletteAction)> {
+        let entries = self.palette_entries();
+        let labels: Vec<String> = entries.iter().map(|(label, _)| label.clone()).collect();
+        fuzzy_match(&labels, &self.palette_query)
+            .into_iter()
+            .map(|(idx, label)| (label, entries[idx].1))
+            .collect()
+    }
+
+    /// Open the command palette with an empty query
+    pub fn open_palette(&mut self) {
+        self.show_palette = true;
+        self.palette_query.clear();
+        self.palette_index = 0;
+    }
+
+    fn handle_palette_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.show_palette = false;
+            }
+            KeyCode::Up => {
+                self.palette_index = self.palette_index.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                let len = self.palette_matches().len();
+                if self.palette_index + 1 < len {
+                    self.palette_index += 1;
+                }
+            }
+            KeyCode::Enter => {
+                let action = self
+                    .palette_matches()
+                    .get(self.palette_index)
+                    .map(|(_, action)| *action);
+                self.show_palette = false;
+                if let Some(action) = action {
+                    self.run_palette_action(action);
+                }
+            }
+            KeyCode::Backspace => {
+                self.palette_query.pop();
+                self.palette_index = 0;
+            }
+            KeyCode::Char(c) => {
+                self.palette_query.push(c);
+                self.palette_index = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Execute an action chosen from the command palette
+    pub fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::JumpToComponent(idx) => {
+                if idx != self.selected_component {
+                    self.selected_component = idx;
+                    self.update_param_values();
+                    self.add_to_history();
+                }
+                self.sidebar_section = SidebarSection::Components;
+                self.focused_panel = FocusedPanel::Params;
+            }
+            PaletteAction::ToggleTheme => {
+                self.theme = self.theme.toggle();
+                self.set_status(&format!("✓ Theme: {}", self.theme.name()));
+            }
+            PaletteAction::CyclePreviewWidth => self.cycle_preview_width(),
+            PaletteAction::CycleColorDepth => self.cycle_color_depth(),
+            PaletteAction::TogglePlayback => self.toggle_playback(),
+            PaletteAction::RunCommand => self.run_command(),
+            PaletteAction::OpenFavorites => self.open_favorites(),
+            PaletteAction::ExportPreview => self.export_preview(),
+            PaletteAction::CycleExportFormat => self.cycle_export_format(),
+            PaletteAction::ExportCommand => self.export_command(),
+            PaletteAction::CopyCommand => self.copy_command_to_clipboard(),
+            PaletteAction::OpenThemeEditor => self.open_theme_editor(),
+            PaletteAction::SaveFavorite => {
+                self.naming_favorite = true;
+                self.favorite_name_buffer.clear();
+            }
+            PaletteAction::ResetParams => {
+                self.update_param_values();
+                self.set_status("✓ Parameters reset to defaults");
+            }
+            PaletteAction::ResetLayout => {
+                self.layout.reset();
+                self.set_status("✓ Layout reset to defaults");
+            }
+            PaletteAction::ToggleHelp => {
+                self.show_help = !self.show_help;
+            }
+            PaletteAction::Quit => {
+                self.running = false;
+            }
+        }
+    }
+
+    /// Open the favorites list with an empty query
+    pub fn open_favorites(&mut self) {
+        if self.storage.favorites.is_empty() {
+            self.set_status("No favorites yet (Ctrl+S saves one)");
+            return;
+        }
+        self.show_favorites = true;
+        self.favorites_query.clear();
+        self.favorites_index = 0;
+    }
+
+    /// Indices of the favorites matching the query, best first
+    pub fn favorite_matches(&self) -> Vec<usize> {
+        let labels: Vec<String> = self
+            .storage
+            .favorites
+            .iter()
+            .map(|f| format!("{} {}", f.name, f.component))
+            .collect();
+        fuzzy_match(&labels, &self.favorites_query)
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    #[allow(clippy::collapsible_match)]
+    fn handle_favorites_key(&mut self, code: KeyCode) {
+        let selected = self.favorite_matches().get(self.favorites_index).copied();
+        match code {
+            KeyCode::Esc => {
+                self.show_favorites = false;
+            }
+            KeyCode::Up => {
+                self.favorites_index = self.favorites_index.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if self.favorites_index + 1 < self.favorite_matches().len() {
+                    self.favorites_index += 1;
+                }
+            }
+            KeyCode::Enter => {
+                self.show_favorites = false;
+                if let Some(index) = selected {
+                    self.load_favorite(index);
+                    self.sidebar_section = SidebarSection::Components;
+                    self.focused_panel = FocusedPanel::Params;
+                }
+            }
+            KeyCode::Delete => {
+                if let Some(index) = selected {
+                    self.delete_favorite(index);
+                    let len = self.favorite_matches().len();
+                    self.favorites_index = self.favorites_index.min(len.saturating_sub(1));
+                    if self.storage.favorites.is_empty() {
+                        self.show_favorites = false;
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.favorites_query.pop();
+                self.favorites_index = 0;
+            }
+            KeyCode::Char(c) => {
+                self.favorites_query.push(c);
+                self.favorites_index = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the theme editor on the theme this run uses, or on the theme
+    /// file's default as last saved
+    pub fn open_theme_editor(&mut self) {
+        let file = match ThemeFile::load_user() {
+            Ok(file) => file,
+            Err(e) => {
+                self.set_status(&format!("⚠ Theme file: {:#}", e));
+                return;
+            }
+        };
+        let active = match theme::active_with_source() {
+            Some((active, ThemeSource::Flag | ThemeSource::Env)) => Some(active.clone()),
+            _ => file
+                .theme
+                .as_deref()
+                .and_then(|name| Theme::resolve(name, &file).ok()),
+        };
+        self.theme_editor = Some(ThemeEditor::open(active.as_ref(), &file));
+    }
+
+    fn handle_theme_editor_key(&mut self, key: event::KeyEvent) {
+        let Some(editor) = &mut self.theme_editor else {
+            return;
+        };
+        if let Some(input) = &mut editor.input {
+            match key.code {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    if let Err(e) = editor.commit() {
+                        self.set_status(&format!("⚠ {}", e));
+                    }
+                }
+                KeyCode::Esc => editor.input = None,
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => editor.move_up(),
+            KeyCode::Down | KeyCode::Char('j') => editor.move_down(),
+            KeyCode::Left | KeyCode::Char('h') => editor.cycle(false),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => editor.cycle(true),
+            KeyCode::Enter if editor.field().is_text() => editor.start_input(),
+            KeyCode::Enter => editor.cycle(true),
+            KeyCode::Char('s') => match editor.save() {
+                Ok(path) => {
+                    let message = format!("✓ Saved theme '{}' to {}", editor.name, path.display());
+                    self.set_status(&message);
+                }
+                Err(e) => self.set_status(&format!("⚠ Save failed: {}", e)),
+            },
+            KeyCode::Esc | KeyCode::Char('q') => self.theme_editor = None,
+            _ => {}
+        }
+    }
+
+    /// Play the current component's animation in the preview, or pause and
+    /// resume it
+    pub fn toggle_playback(&mut self) {
+        if let Some(playback) = &mut self.playback {
+            playback.paused = !playback.paused;
+            return;
+        }
+        match self.current_component() {
+            Some(component) if component.is_animated() => {
+                self.playback = Some(Playback::start(component, &self.param_values));
+                self.shell = None;
+            }
+            Some(component) => {
+                let message = format!("'{}' has no animation to play", component.name);
+                self.set_status(&message);
+            }
+            None => {}
+        }
+    }
+
+    /// Advance a playing animation once its frame time has passed,
+    /// restarting it if the params were edited
+    pub fn tick_playback(&mut self) {
+        let Some(playback) = &self.playback else {
+            return;
+        };
+        if playback.values != self.param_values {
+            if let Some(component) = self.current_component() {
+                let paused = playback.paused;
+                let mut restarted = Playback::start(component, &self.param_values);
+                restarted.paused = paused;
+                self.playback = Some(restarted);
+            }
+            return;
+        }
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+        let frame = Duration::from_millis(FRAME_MS as u64);
+        if !playback.paused && playback.last_tick.elapsed() >= frame {
+            playback.tick();
+            playback.last_tick = Instant::now();
+        }
+    }
+
+    /// Run the generated command on a terminal the size of the preview,
+    /// replacing the simulated preview with its output
+    pub fn run_command(&mut self) {
+        let Some(component) = self.current_component() else {
+            return;
+        };
+        let command = component.generate_command(&self.param_values);
+        let size = match self.last_areas {
+            Some(areas) => {
+                let width = areas.preview.width.saturating_sub(2);
+                let height = areas.preview.height.saturating_sub(2);
+                (self.preview_width.unwrap_or(width), height)
+            }
+            None => (80, 24),
+        };
+        // Dropping a previous run stops it
+        self.shell = None;
+        match ShellRun::start(&command, (size.0.max(1), size.1.max(1))) {
+            Ok(run) => {
+                self.playback = None;
+                self.shell = Some(run);
+            }
+            Err(e) => self.set_status(&format!("⚠ Run failed: {}", e)),
+        }
+    }
+
+    /// Pick up new output from a running command; edits that change the
+    /// command go back to the simulated preview
+    pub fn tick_shell(&mut self) {
+        let command = self
+            .current_component()
+            .map(|c| c.generate_command(&self.param_values));
+        let Some(run) = &mut self.shell else {
+            return;
+        };
+        if command.as_deref() != Some(run.command.as_str()) {
+            self.shell = None;
+            return;
+        }
+        run.poll();
+    }
+
+    /// Step through the simulated preview widths
+    pub fn cycle_preview_width(&mut self) {
+        let pos = PREVIEW_WIDTHS
+            .iter()
+            .position(|w| *w == self.preview_width)
+            .unwrap_or(0);
+        self.preview_width = PREVIEW_WIDTHS[(pos + 1) % PREVIEW_WIDTHS.len()];
+        match self.preview_width {
+            Some(cols) => self.set_status(&format!("⇔ Preview width: {} cols", cols)),
+            None => self.set_status("⇔ Preview width: fit pane"),
+        }
+    }
+
+    /// Step through the simulated preview color depths
+    pub fn cycle_color_depth(&mut self) {
+        self.color_depth = self.color_depth.next();
+        self.set_status(&format!("◐ Preview colors: {}", self.color_depth.label()));
+    }
+
+    /// Step through the formats `e` exports to
+    pub fn cycle_export_format(&mut self) {
+        let pos = EXPORT_FORMATS
+            .iter()
+            .position(|f| *f == self.export_format)
+            .unwrap_or(0);
+        self.export_format = EXPORT_FORMATS[(pos + 1) % EXPORT_FORMATS.len()];
+        self.set_status(&format!(
+            "⇪ Export format: {}",
+            self.export_format.to_uppercase()
+        ));
+    }
+
+    /// Render the current component with its parameters through `--export`,
+    /// into `<component>-<timestamp>.<format>` in the working directory
+    pub fn export_preview(&mut self) {
+        let Some(component) = self.current_component() else {
+            return;
+        };
+        let command = component.generate_command(&self.param_values);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = export_path(component.name, timestamp, self.export_format);
+        match shell::export(&command, &path) {
+            Ok(()) => self.set_status(&format!("✓ Exported to {}", path)),
+            Err(e) => self.set_status(&format!("⚠ Export failed: {}", e)),
+        }
+    }
+
+    /// Write the current command to `termgfx-<component>.sh` in the working directory
+    fn export_command(&mut self) {
+        let Some(component) = self.current_component() else {
+            return;
+        };
+        let path = format!("termgfx-{}.sh", component.name);
+        let script = format!(
+            "#!/bin/sh\n{}\n",
+            component.generate_command(&self.param_values)
+        );
+        match atomic::write(&path, script) {
+            Ok(()) => self.set_status(&format!("✓ Exported to {}", path)),
+            Err(e) => self.set_status(&format!("⚠ Export failed: {}", e)),
+        }
+    }
+
     /// Handle key events
     fn handle_key(&mut self, key: event::KeyEvent) {
         if key.kind != KeyEventKind::Press {
             return;
         }
 
+        // The theme editor and command palette capture all input while open
+        if self.theme_editor.is_some() {
+            self.handle_theme_editor_key(key);
+            return;
+        }
+        if self.show_palette {
+            self.handle_palette_key(key.code);
+            return;
+        }
+        if self.show_favorites {
+            self.handle_favorites_key(key.code);
+            return;
+        }
+
         // Handle widget mode interactions
         if self.handle_widget_key(key.code) {
             return;
@@ -303,12 +1070,28 @@ impl StudioApp {
 
         // Global shortcuts
         match key.code {
+            KeyCode::Esc if self.shell.is_some() => {
+                // Stop a running command, then go back to the simulated preview
+                match &mut self.shell {
+                    Some(run) if run.is_running() => {
+                        run.stop();
+                        self.set_status("■ Command stopped");
+                    }
+                    _ => self.shell = None,
+                }
+            }
             KeyCode::Char('q') | KeyCode::Esc => {
                 self.running = false;
             }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.running = false;
             }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_palette();
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_favorites();
+            }
             KeyCode::Char('?') => {
                 self.show_help = !self.show_help;
             }
@@ -332,7 +1115,7 @@ impl StudioApp {
                 self.copy_command_to_clipboard();
             }
             KeyCode::Char('s') => {
-                // Start naming mode to save favorite
+                // Start naming mode to save favorite (also Ctrl+S)
                 self.naming_favorite = true;
                 self.favorite_name_buffer.clear();
             }
@@ -392,17 +1175,37 @@ impl StudioApp {
                 self.layout.reset();
                 self.set_status("✓ Layout reset to defaults");
             }
+            KeyCode::Char('w') => {
+                self.cycle_preview_width();
+            }
+            KeyCode::Char('C') => {
+                self.cycle_color_depth();
+            }
+            KeyCode::Char('e') => {
+                self.export_preview();
+            }
+            KeyCode::Char('E') => {
+                self.cycle_export_format();
+            }
+            KeyCode::Char('T') => {
+                self.open_theme_editor();
+            }
             _ => {
                 // Panel-specific navigation
                 match self.focused_panel {
                     FocusedPanel::Sidebar => self.handle_sidebar_key(key.code),
                     FocusedPanel::Params => self.handle_params_key(key.code),
-                    FocusedPanel::Preview => {} // Preview is read-only
+                    FocusedPanel::Preview => match key.code {
+                        KeyCode::Char(' ') => self.toggle_playback(),
+                        KeyCode::Enter => self.run_command(),
+                        _ => {}
+                    },
                 }
             }
         }
     }
 
+    #[allow(clippy::collapsible_match)]
     fn handle_sidebar_key(&mut self, code: KeyCode) {
         match self.sidebar_section {
             SidebarSection::Favorites => {
@@ -431,7 +1234,7 @@ impl StudioApp {
                 }
             }
             SidebarSection::History => {
-                let len = self.storage.history.len();
+                let len = self.storage.recent().len();
                 match code {
                     KeyCode::Down | KeyCode::Char('j') => {
                         if len > 0 && self.section_index < len - 1 {
@@ -476,7 +1279,7 @@ impl StudioApp {
                         } else {
                             // Switch to history section
                             self.sidebar_section = SidebarSection::History;
-                            self.section_index = self.storage.history.len().saturating_sub(1);
+                            self.section_index = self.storage.recent().len().saturating_sub(1);
                         }
                     }
                     KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
@@ -488,6 +1291,7 @@ impl StudioApp {
         }
     }
 
+    #[allow(clippy::collapsible_match)]
     fn handle_params_key(&mut self, code: KeyCode) {
         let param_count = self
             .current_component()
@@ -712,10 +1516,15 @@ impl StudioApp {
     }
 
     /// Handle mouse events
+    #[allow(clippy::collapsible_match)]
     pub fn handle_mouse(&mut self, event: crossterm::event::MouseEvent) {
         let Some(areas) = self.last_areas else {
             return;
         };
+        // The theme editor covers the panels
+        if self.theme_editor.is_some() {
+            return;
+        }
 
         let x = event.column;
         let y = event.row;
@@ -739,30 +1548,29 @@ impl StudioApp {
                 // Check which panel was clicked
                 if Self::point_in_rect(x, y, areas.sidebar) {
                     self.focused_panel = FocusedPanel::Sidebar;
-                    // Calculate which component was clicked
+                    // Calculate which entry was clicked
                     let inner_y = y.saturating_sub(areas.sidebar.y + 1); // Account for border
-                    let mut item_index = 0;
-                    let mut current_category = "";
-
-                    for (idx, component) in self.components.iter().enumerate() {
-                        // Skip category headers in click detection
-                        if component.category != current_category {
-                            current_category = component.category;
-                            if item_index == inner_y as usize {
-                                // Clicked on category header, ignore
-                                return;
-                            }
-                            item_index += 1;
+                    let rows = self.sidebar_rows();
+                    let height = areas.sidebar.height.saturating_sub(2) as usize;
+                    let row = self.sidebar_offset(&rows, height) + inner_y as usize;
+                    match rows.get(row) {
+                        Some(SidebarRow::Favorite(idx)) => {
+                            self.load_favorite(*idx);
+                            self.sidebar_section = SidebarSection::Components;
                         }
-
-                        if item_index == inner_y as usize {
-                            if idx != self.selected_component {
-                                self.selected_component = idx;
+                        Some(SidebarRow::Recent(idx)) => {
+                            self.load_history(*idx);
+                            self.sidebar_section = SidebarSection::Components;
+                        }
+                        Some(SidebarRow::Component(idx)) => {
+                            self.sidebar_section = SidebarSection::Components;
+                            if *idx != self.selected_component {
+                                self.selected_component = *idx;
                                 self.update_param_values();
                             }
-                            return;
                         }
-                        item_index += 1;
+                        // Category headers are not clickable
+                        Some(SidebarRow::Header(_)) | None => {}
                     }
                 } else if Self::point_in_rect(x, y, areas.params) {
                     self.focused_panel = FocusedPanel::Params;
@@ -785,6 +1593,9 @@ impl StudioApp {
                     if inner_x < 10 {
                         // Clicked near [c] Copy - trigger copy
                         self.copy_command_to_clipboard();
+                    } else if inner_x < 22 {
+                        // Clicked near [Enter] Run
+                        self.run_command();
                     }
                 }
             }
@@ -855,21 +1666,7 @@ impl StudioApp {
     fn copy_command_to_clipboard(&mut self) {
         if let Some(component) = self.current_component() {
             let cmd = component.generate_command(&self.param_values);
-            let copy_result = std::process::Command::new("pbcopy")
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .or_else(|_| {
-                    std::process::Command::new("xclip")
-                        .args(["-selection", "clipboard"])
-                        .stdin(std::process::Stdio::piped())
-                        .spawn()
-                });
-
-            if let Ok(mut child) = copy_result {
-                if let Some(stdin) = child.stdin.as_mut() {
-                    let _ = stdin.write_all(cmd.as_bytes());
-                }
-                let _ = child.wait();
+            if clipboard::copy(&cmd) {
                 self.set_status("✓ Command copied to clipboard!");
             }
         }
@@ -882,8 +1679,29 @@ impl Default for StudioApp {
     }
 }
 
+/// `chart-bar-1700000000.svg` for an export of `chart bar`
+fn export_path(component: &str, timestamp: u64, format: &str) -> String {
+    format!("{}-{}.{}", component.replace(' ', "-"), timestamp, format)
+}
+
 /// Run the studio TUI application
 pub fn run_studio() -> io::Result<()> {
+    run(StudioApp::new())
+}
+
+/// Run the studio opened on `component`, with `values` over its defaults
+pub fn run_studio_with(component: &str, values: &[(&str, &str)]) -> io::Result<()> {
+    let mut app = StudioApp::new();
+    if !app.open_component(component, values) {
+        return Err(io::Error::other(format!(
+            "Studio has no '{}' component",
+            component
+        )));
+    }
+    run(app)
+}
+
+fn run(mut app: StudioApp) -> io::Result<()> {
     // Check for interactive terminal
     if !std::io::stdin().is_terminal() {
         return Err(io::Error::other(
@@ -899,8 +1717,6 @@ pub fn run_studio() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = StudioApp::new();
-
     // Main loop
     while app.running {
         // Clear expired status messages
@@ -917,20 +1733,44 @@ pub fn run_studio() -> io::Result<()> {
                 ui::render_help_overlay(frame);
             }
 
+            if app.show_palette {
+                ui::render_command_palette(frame, &app);
+            }
+
+            if app.show_favorites {
+                ui::render_favorites(frame, &app);
+            }
+
+            if app.naming_favorite {
+                ui::render_name_prompt(frame, &app);
+            }
+
+            if app.theme_editor.is_some() {
+                ui::render_theme_editor(frame, &app);
+            }
+
             // Render status message if any
             if let Some((msg, _)) = &app.status_message {
                 ui::render_status_message(frame, msg);
             }
         })?;
 
-        // Handle events
-        if event::poll(std::time::Duration::from_millis(100))? {
+        // Handle events; poll faster while an animation or command runs
+        let running = app.shell.as_ref().is_some_and(|r| r.is_running());
+        let timeout = if running || app.playback.as_ref().is_some_and(|p| !p.paused) {
+            FRAME_MS as u64
+        } else {
+            100
+        };
+        if event::poll(Duration::from_millis(timeout))? {
             match event::read()? {
                 Event::Key(key) => app.handle_key(key),
                 Event::Mouse(mouse) => app.handle_mouse(mouse),
                 _ => {}
             }
         }
+        app.tick_playback();
+        app.tick_shell();
     }
 
     // Save any dirty storage before exit
@@ -961,6 +1801,18 @@ mod tests {
         assert_eq!(app.focused_panel, FocusedPanel::Sidebar);
     }
 
+    #[test]
+    fn test_open_component_applies_values() {
+        let mut app = StudioApp::new();
+        assert!(app.open_component("gauge", &[("value", "42"), ("bogus", "x")]));
+        assert_eq!(app.current_component().unwrap().name, "gauge");
+        assert_eq!(app.param_values["value"], "42");
+        assert_eq!(app.param_values["label"], "CPU");
+        assert!(!app.param_values.contains_key("bogus"));
+        assert_eq!(app.focused_panel, FocusedPanel::Params);
+        assert!(!app.open_component("nope", &[]));
+    }
+
     #[test]
     fn test_update_param_values() {
         let mut app = StudioApp::new();
@@ -1116,4 +1968,365 @@ mod tests {
         let _ = app.try_save_storage();
         assert!(!app.storage_dirty);
     }
+
+    #[test]
+    fn test_ctrl_p_opens_palette() {
+        let mut app = StudioApp::new();
+        app.handle_key(event::KeyEvent::new(
+            KeyCode::Char('p'),
+            KeyModifiers::CONTROL,
+        ));
+        assert!(app.show_palette);
+
+        // Typing goes to the palette query, not global shortcuts
+        app.handle_key(event::KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(app.running);
+        assert_eq!(app.palette_query, "q");
+
+        app.handle_key(event::KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.show_palette);
+        assert!(app.running);
+    }
+
+    /// An app with favorites and history that never touch the config file
+    fn app_with_saved() -> StudioApp {
+        let mut app = StudioApp::new();
+        app.storage = StudioStorage::default();
+        let params = |value: &str| HashMap::from([("message".to_string(), value.to_string())]);
+        app.storage
+            .add_favorite("hello".into(), "box".into(), params("hi"));
+        app.storage
+            .add_favorite("alert".into(), "banner".into(), params("ALERT"));
+        app.storage.add_history("box".into(), params("one"));
+        app.storage.add_history("banner".into(), params("two"));
+        app.storage.add_history("box".into(), params("three"));
+        app
+    }
+
+    #[test]
+    fn test_sidebar_rows_lead_with_favorites_and_recent() {
+        let app = app_with_saved();
+        let rows = app.sidebar_rows();
+        assert_eq!(
+            rows[..6],
+            [
+                SidebarRow::Header("★ Favorites"),
+                SidebarRow::Favorite(0),
+                SidebarRow::Favorite(1),
+                SidebarRow::Header("Recent"),
+                SidebarRow::Recent(0),
+                SidebarRow::Recent(1),
+            ]
+        );
+        assert!(matches!(rows[6], SidebarRow::Header(_)));
+
+        let mut empty = StudioApp::new();
+        empty.storage = StudioStorage::default();
+        assert!(matches!(empty.sidebar_rows()[1], SidebarRow::Component(0)));
+    }
+
+    #[test]
+    fn test_sidebar_highlight_scrolls_into_view() {
+        let mut app = app_with_saved();
+        let rows = app.sidebar_rows();
+        app.sidebar_section = SidebarSection::History;
+        app.section_index = 1;
+        assert_eq!(app.sidebar_highlight(&rows), Some(5));
+        assert_eq!(app.sidebar_offset(&rows, 10), 0);
+
+        app.sidebar_section = SidebarSection::Components;
+        app.selected_component = app.components.len() - 1;
+        assert_eq!(app.sidebar_highlight(&rows), Some(rows.len() - 1));
+        assert_eq!(app.sidebar_offset(&rows, 10), rows.len() - 10);
+    }
+
+    #[test]
+    fn test_load_history_uses_recent_components() {
+        let mut app = app_with_saved();
+        app.load_history(1);
+        assert_eq!(app.current_component().unwrap().name, "banner");
+        assert_eq!(app.param_values.get("message").unwrap(), "two");
+    }
+
+    #[test]
+    fn test_ctrl_o_loads_favorite() {
+        let mut app = app_with_saved();
+        app.handle_key(event::KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::CONTROL,
+        ));
+        assert!(app.show_favorites);
+
+        // Typing filters rather than triggering shortcuts
+        for c in "ban".chars() {
+            app.handle_key(event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(app.favorite_matches(), vec![0]);
+
+        app.handle_key(event::KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.show_favorites);
+        assert_eq!(app.focused_panel, FocusedPanel::Params);
+        assert_eq!(app.current_component().unwrap().name, "banner");
+        assert_eq!(app.param_values.get("message").unwrap(), "ALERT");
+    }
+
+    #[test]
+    fn test_ctrl_o_without_favorites() {
+        let mut app = StudioApp::new();
+        app.storage = StudioStorage::default();
+        app.handle_key(event::KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::CONTROL,
+        ));
+        assert!(!app.show_favorites);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_ctrl_s_starts_naming() {
+        let mut app = StudioApp::new();
+        app.handle_key(event::KeyEvent::new(
+            KeyCode::Char('s'),
+            KeyModifiers::CONTROL,
+        ));
+        assert!(app.naming_favorite);
+        app.handle_key(event::KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(app.favorite_name_buffer, "x");
+        app.handle_key(event::KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.naming_favorite);
+        assert!(app.running);
+    }
+
+    #[test]
+    fn test_palette_fuzzy_filters_entries() {
+        let mut app = StudioApp::new();
+        app.open_palette();
+        let all = app.palette_matches().len();
+        app.palette_query = "THEME".to_string();
+        let matches = app.palette_matches();
+        assert!(matches.len() < all);
+        assert_eq!(matches[0].1, PaletteAction::ToggleTheme);
+    }
+
+    #[test]
+    fn test_palette_jump_to_component() {
+        let mut app = StudioApp::new();
+        let target = app
+            .components
+            .iter()
+            .position(|c| c.name == "gauge")
+            .unwrap();
+        app.open_palette();
+        app.palette_query = "go to gauge".to_string();
+        app.handle_key(event::KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.show_palette);
+        assert_eq!(app.selected_component, target);
+        assert_eq!(app.focused_panel, FocusedPanel::Params);
+    }
+
+    #[test]
+    fn test_palette_toggle_theme() {
+        let mut app = StudioApp::new();
+        assert_eq!(app.theme, StudioTheme::Dark);
+        app.run_palette_action(PaletteAction::ToggleTheme);
+        assert_eq!(app.theme, StudioTheme::Light);
+        assert_eq!(app.status_message.as_ref().unwrap().0, "✓ Theme: light");
+    }
+
+    #[test]
+    fn test_cycle_preview_width() {
+        let mut app = StudioApp::new();
+        assert_eq!(app.preview_width, None);
+        let seen: Vec<Option<u16>> = (0..4)
+            .map(|_| {
+                app.handle_key(event::KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE));
+                app.preview_width
+            })
+            .collect();
+        assert_eq!(seen, vec![Some(40), Some(80), Some(120), None]);
+    }
+
+    #[test]
+    fn test_cycle_color_depth() {
+        let mut app = StudioApp::new();
+        app.handle_key(event::KeyEvent::new(
+            KeyCode::Char('C'),
+            KeyModifiers::SHIFT,
+        ));
+        assert_eq!(app.color_depth, ColorDepth::Ansi256);
+        app.run_palette_action(PaletteAction::CycleColorDepth);
+        app.run_palette_action(PaletteAction::CycleColorDepth);
+        assert_eq!(app.color_depth, ColorDepth::Monochrome);
+        app.cycle_color_depth();
+        assert_eq!(app.color_depth, ColorDepth::TrueColor);
+    }
+
+    #[test]
+    fn test_cycle_export_format() {
+        let mut app = StudioApp::new();
+        assert_eq!(app.export_format, "svg");
+        app.handle_key(event::KeyEvent::new(
+            KeyCode::Char('E'),
+            KeyModifiers::SHIFT,
+        ));
+        assert_eq!(app.export_format, "png");
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "⇪ Export format: PNG"
+        );
+        app.run_palette_action(PaletteAction::CycleExportFormat);
+        assert_eq!(app.export_format, "html");
+        assert!(app
+            .palette_entries()
+            .iter()
+            .any(|(label, _)| label == "Export preview as HTML"));
+        app.cycle_export_format();
+        assert_eq!(app.export_format, "svg");
+    }
+
+    #[test]
+    fn test_theme_editor_captures_keys() {
+        let mut app = StudioApp::new();
+        assert!(app
+            .palette_entries()
+            .iter()
+            .any(|(_, action)| *action == PaletteAction::OpenThemeEditor));
+        app.theme_editor = Some(ThemeEditor::open(None, &ThemeFile::default()));
+        let press = |app: &mut StudioApp, code| {
+            app.handle_key(event::KeyEvent::new(code, KeyModifiers::NONE));
+        };
+
+        // Down to the primary color, past name, base, mode, border, gradient, series
+        for _ in 0..6 {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Enter);
+        for _ in 0..7 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        for c in "#zz".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "⚠ '#zz' is not a #RRGGBB color"
+        );
+        for _ in 0..2 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        for c in "123456".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        let editor = app.theme_editor.as_ref().unwrap();
+        assert_eq!(editor.theme.colors.primary, "#123456");
+        assert_eq!(editor.input, None);
+
+        // q closes the editor, not the studio
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.theme_editor.is_none());
+        assert!(app.running);
+    }
+
+    #[test]
+    fn test_export_path() {
+        assert_eq!(
+            export_path("box", 1_700_000_000, "svg"),
+            "box-1700000000.svg"
+        );
+        assert_eq!(
+            export_path("chart bar", 1_700_000_000, "png"),
+            "chart-bar-1700000000.png"
+        );
+    }
+
+    #[test]
+    fn test_space_in_preview_plays_and_pauses() {
+        let mut app = StudioApp::new();
+        assert!(app.open_component("progress", &[("percent", "40")]));
+        app.focused_panel = FocusedPanel::Preview;
+        let space = event::KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE);
+
+        app.handle_key(space);
+        let playback = app.playback.as_mut().unwrap();
+        assert!(!playback.paused);
+        assert_eq!(playback.percent(), Some(0));
+        for _ in 0..crate::render::progress::STEPS {
+            playback.tick();
+        }
+        assert_eq!(playback.percent(), Some(40));
+
+        app.handle_key(space);
+        assert!(app.playback.as_ref().unwrap().paused);
+
+        // Switching component stops it
+        app.open_component("box", &[]);
+        assert!(app.playback.is_none());
+        app.toggle_playback();
+        assert!(app.playback.is_none());
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_playback_restarts_after_edit() {
+        let mut app = StudioApp::new();
+        app.open_component("spinner", &[("style", "line")]);
+        app.toggle_playback();
+        app.playback.as_mut().unwrap().tick();
+        assert_ne!(
+            app.playback.as_ref().unwrap().state,
+            PlaybackState::Spinner(SpinnerState::new("line", "Loading..."))
+        );
+
+        app.param_values
+            .insert("style".to_string(), "moon".to_string());
+        app.tick_playback();
+        assert_eq!(
+            app.playback.as_ref().unwrap().state,
+            PlaybackState::Spinner(SpinnerState::new("moon", "Loading..."))
+        );
+    }
+
+    /// A long-running stand-in for the current component's command
+    fn sleeping_shell(app: &StudioApp) -> ShellRun {
+        let command = app
+            .current_component()
+            .unwrap()
+            .generate_command(&app.param_values);
+        let mut cmd = std::process::Command::new("sleep");
+        cmd.arg("30");
+        ShellRun::spawn(&command, cmd, (20, 4)).unwrap()
+    }
+
+    #[test]
+    fn test_esc_stops_then_dismisses_command() {
+        let mut app = StudioApp::new();
+        app.shell = Some(sleeping_shell(&app));
+        let esc = event::KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+        app.handle_key(esc);
+        assert!(app.shell.as_ref().is_some_and(|r| !r.is_running()));
+        assert!(app.running);
+
+        app.handle_key(esc);
+        assert!(app.shell.is_none());
+        assert!(app.running);
+
+        app.handle_key(esc);
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn test_editing_params_drops_command_output() {
+        let mut app = StudioApp::new();
+        app.shell = Some(sleeping_shell(&app));
+        app.tick_shell();
+        assert!(app.shell.is_some());
+
+        app.param_values
+            .insert("message".to_string(), "changed".to_string());
+        app.tick_shell();
+        assert!(app.shell.is_none());
+    }
 }
@@ -5,9 +5,11 @@
 
 mod app;
 mod layout;
-mod registry;
+pub mod registry;
+mod shell;
 mod storage;
+mod theme_editor;
 mod ui;
 pub mod widgets;
 
-pub use app::run_studio;
+pub use app::{run_studio, run_studio_with};
@@ -1,16 +1,32 @@
 //! Component registry with metadata for all termgfx components
+//!
+//! One entry per command the studio can build, with every flag the command
+//! takes. The entries mirror the clap definitions in `main.rs`; the e2e
+//! tests compare them against each command's `--help` so they can't drift.
+//!
+//! Commands the studio can't preview have no entry:
+//!
+//! - prompts and apps that take over the terminal: `input`, `select`,
+//!   `confirm`, `write`, `file`, `filter`, `emojipick`, `form`, `wizard`,
+//!   `keys`, `stopwatch`, `pager`, `json`, `present`, `dashboard`, `watch`,
+//!   `footer`, `tui`, `repl`, `playground`, `studio`
+//! - `columns`, which only lays out text piped to it
+//! - tooling that runs files or manages settings rather than drawing a
+//!   component: `record`, `script`, `demo`, `style`, `palette`, `theme`,
+//!   `export`, `ci-comment`, `emoji-widths`, `help`
 
+use crate::render::{progress, spinner};
 use std::collections::HashMap;
 
 /// Parameter type for component configuration
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub enum ParamType {
     String,
     Number { min: f64, max: f64 },
     Enum(Vec<&'static str>),
     Bool,
     Data, // For comma-separated data like sparklines
+    List, // Several shell words, passed as typed (`'a b' c`)
 }
 
 /// A single parameter definition
@@ -21,6 +37,78 @@ pub struct ParamDef {
     pub param_type: ParamType,
     pub default: &'static str,
     pub description: &'static str,
+    /// Given as a positional argument instead of a `--name` flag
+    pub positional: bool,
+    /// Always passed, even at its default: the command needs it
+    pub required: bool,
+}
+
+impl ParamDef {
+    fn new(
+        name: &'static str,
+        param_type: ParamType,
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            param_type,
+            default,
+            description,
+            positional: false,
+            required: false,
+        }
+    }
+
+    pub fn text(name: &'static str, default: &'static str, description: &'static str) -> Self {
+        Self::new(name, ParamType::String, default, description)
+    }
+
+    pub fn number(
+        name: &'static str,
+        min: f64,
+        max: f64,
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self::new(name, ParamType::Number { min, max }, default, description)
+    }
+
+    pub fn choice(
+        name: &'static str,
+        options: &[&'static str],
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self::new(
+            name,
+            ParamType::Enum(options.to_vec()),
+            default,
+            description,
+        )
+    }
+
+    pub fn flag(name: &'static str, description: &'static str) -> Self {
+        Self::new(name, ParamType::Bool, "false", description)
+    }
+
+    pub fn data(name: &'static str, default: &'static str, description: &'static str) -> Self {
+        Self::new(name, ParamType::Data, default, description)
+    }
+
+    pub fn list(name: &'static str, default: &'static str, description: &'static str) -> Self {
+        Self::new(name, ParamType::List, default, description)
+    }
+
+    pub fn positional(mut self) -> Self {
+        self.positional = true;
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
 }
 
 /// Component definition with all metadata
@@ -34,26 +122,44 @@ pub struct ComponentDef {
 }
 
 impl ComponentDef {
+    /// Whether the preview can play an animation: spinners, and bars and
+    /// gauges filling up
+    pub fn is_animated(&self) -> bool {
+        matches!(self.name, "spinner" | "progress" | "gauge")
+    }
+
     /// Generate CLI command from current parameter values
     pub fn generate_command(&self, values: &HashMap<String, String>) -> String {
         let mut cmd = format!("termgfx {}", self.name);
 
         for param in &self.params {
-            if let Some(value) = values.get(param.name) {
-                if value != param.default && !value.is_empty() {
-                    match &param.param_type {
-                        ParamType::Bool => {
-                            if value == "true" {
-                                cmd.push_str(&format!(" --{}", param.name));
-                            }
-                        }
-                        ParamType::String | ParamType::Data => {
-                            cmd.push_str(&format!(" --{} \"{}\"", param.name, value));
-                        }
-                        _ => {
-                            cmd.push_str(&format!(" --{} {}", param.name, value));
-                        }
+            let value = values.get(param.name).map_or(param.default, |v| v.as_str());
+            if param.positional {
+                // Always given, even at its default, or the command would
+                // read stdin
+                match &param.param_type {
+                    ParamType::String | ParamType::Data => {
+                        cmd.push_str(&format!(" \"{}\"", value));
                     }
+                    _ if value.is_empty() => {}
+                    _ => cmd.push_str(&format!(" {}", value)),
+                }
+                continue;
+            }
+            if value.is_empty() || (value == param.default && !param.required) {
+                continue;
+            }
+            match &param.param_type {
+                ParamType::Bool => {
+                    if value == "true" {
+                        cmd.push_str(&format!(" --{}", param.name));
+                    }
+                }
+                ParamType::String | ParamType::Data => {
+                    cmd.push_str(&format!(" --{} \"{}\"", param.name, value));
+                }
+                _ => {
+                    cmd.push_str(&format!(" --{} {}", param.name, value));
                 }
             }
         }
@@ -71,32 +177,21 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Styled message boxes with borders",
             category: "Output",
             params: vec![
-                ParamDef {
-                    name: "message",
-                    param_type: ParamType::String,
-                    default: "Hello World!",
-                    description: "The message to display",
-                },
-                ParamDef {
-                    name: "style",
-                    param_type: ParamType::Enum(vec!["info", "success", "warning", "danger"]),
-                    default: "info",
-                    description: "Box style/color scheme",
-                },
-                ParamDef {
-                    name: "border",
-                    param_type: ParamType::Enum(vec![
-                        "rounded", "single", "double", "thick", "ascii",
-                    ]),
-                    default: "rounded",
-                    description: "Border style",
-                },
-                ParamDef {
-                    name: "emoji",
-                    param_type: ParamType::String,
-                    default: "",
-                    description: "Optional emoji prefix",
-                },
+                ParamDef::text("message", "Hello World!", "The message to display")
+                    .positional(),
+                ParamDef::text("file", "", "Read the message from a file"),
+                ParamDef::text("title", "", "Text in the top border"),
+                ParamDef::text("footer", "", "Text in the bottom border"),
+                ParamDef::choice("title-align", &["left", "center", "right"], "left", "Title and footer alignment"),
+                ParamDef::number("width", 0.0, 100.0, "", "Maximum box width; longer lines wrap (default: terminal width)"),
+                ParamDef::number("padding", 0.0, 100.0, "2", "Spaces between border and content"),
+                ParamDef::choice("style", &["info", "success", "warning", "danger", "gradient"], "info", "Box style/color scheme"),
+                ParamDef::choice("border", &["single", "double", "rounded", "thick"], "rounded", "Border style"),
+                ParamDef::choice("preset", &["corporate", "playful", "minimal", "retro", "neon", "elegant"], "", "Style preset: corporate, playful, minimal, retro, neon, elegant"),
+                ParamDef::text("emoji", "", "Optional emoji prefix"),
+                ParamDef::flag("animate", "Animate the box drawing"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
+                ParamDef::choice("format", &["ansi", "html", "svg"], "ansi", "Output format: ansi, html, svg"),
             ],
         },
         ComponentDef {
@@ -104,21 +199,17 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Progress bars with various styles",
             category: "Output",
             params: vec![
-                ParamDef {
-                    name: "percent",
-                    param_type: ParamType::Number {
-                        min: 0.0,
-                        max: 100.0,
-                    },
-                    default: "50",
-                    description: "Progress percentage (0-100)",
-                },
-                ParamDef {
-                    name: "style",
-                    param_type: ParamType::Enum(vec!["gradient", "blocks", "classic"]),
-                    default: "gradient",
-                    description: "Progress bar style",
-                },
+                ParamDef::number("percent", 0.0, 100.0, "50", "Progress percentage (0-100)")
+                    .positional(),
+                ParamDef::choice("style", &progress::STYLES, "gradient", "Progress bar style"),
+                ParamDef::text("from", "", "Start color for gradient (hex: #3fb950 or name: red, green, blue, cyan, magenta, yellow)"),
+                ParamDef::text("to", "", "End color for gradient (hex: #58a6ff or name: red, green, blue, cyan, magenta, yellow)"),
+                ParamDef::flag("animate", "Fill the bar up to the percentage"),
+                ParamDef::number("duration", 0.0, 4000.0, "1000", "Total animation duration in ms (default: 1000)"),
+                ParamDef::choice("easing", &["linear", "ease-in", "ease-out", "ease-in-out", "cubic", "bounce", "elastic"], "linear", "Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic"),
+                ParamDef::flag("inline", "Compact single line with no newline, for PS1 or tmux status bars"),
+                ParamDef::choice("shell", &["plain", "bash", "zsh", "tmux"], "plain", "Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)"),
+                ParamDef::choice("format", &["ansi", "html", "svg"], "ansi", "Output format: ansi, html, svg"),
             ],
         },
         ComponentDef {
@@ -126,27 +217,21 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Radial gauge indicators",
             category: "Output",
             params: vec![
-                ParamDef {
-                    name: "value",
-                    param_type: ParamType::Number {
-                        min: 0.0,
-                        max: 100.0,
-                    },
-                    default: "75",
-                    description: "Gauge value (0-100)",
-                },
-                ParamDef {
-                    name: "label",
-                    param_type: ParamType::String,
-                    default: "CPU",
-                    description: "Gauge label",
-                },
-                ParamDef {
-                    name: "style",
-                    param_type: ParamType::Enum(vec!["semicircle", "full", "minimal"]),
-                    default: "semicircle",
-                    description: "Gauge display style",
-                },
+                ParamDef::number("value", 0.0, 100.0, "75", "Gauge value (0-100)")
+                    .positional(),
+                ParamDef::number("min", 0.0, 100.0, "0", "Minimum value for the gauge range"),
+                ParamDef::number("max", 0.0, 400.0, "100", "Maximum value for the gauge range"),
+                ParamDef::text("label", "CPU", "Gauge label"),
+                ParamDef::choice("style", &["semicircle", "full", "minimal"], "semicircle", "Gauge display style"),
+                ParamDef::choice("accent", &["red", "green", "blue", "yellow", "cyan", "magenta", "white", "grey"], "", "Accent color: red, green, blue, yellow, cyan, magenta, white, grey"),
+                ParamDef::flag("animate", "Sweep the needle up to the value"),
+                ParamDef::choice("easing", &["linear", "ease-in", "ease-out", "ease-in-out", "cubic", "bounce", "elastic"], "linear", "Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic"),
+                ParamDef::flag("inline", "Compact single line with no newline, for PS1 or tmux status bars"),
+                ParamDef::choice("shell", &["plain", "bash", "zsh", "tmux"], "plain", "Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)"),
+                ParamDef::text("source", "", "Read the value from the system: cpu, mem, disk[:mount], net:<iface> (KB/s)"),
+                ParamDef::text("interval", "", "With --source, redraw with a new reading this often until Ctrl+C"),
+                ParamDef::text("multi", "", "Several gauges side by side: \"CPU:75,MEM:60,DISK:90\""),
+                ParamDef::text("thresholds", "", "Switch color as the value reaches each threshold: \"60:yellow,85:red\""),
             ],
         },
         ComponentDef {
@@ -154,24 +239,15 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "ASCII art text banners",
             category: "Output",
             params: vec![
-                ParamDef {
-                    name: "text",
-                    param_type: ParamType::String,
-                    default: "Hello",
-                    description: "Banner text",
-                },
-                ParamDef {
-                    name: "style",
-                    param_type: ParamType::Enum(vec!["gradient", "solid", "rainbow"]),
-                    default: "gradient",
-                    description: "Color style",
-                },
-                ParamDef {
-                    name: "font",
-                    param_type: ParamType::Enum(vec!["standard", "slant", "small", "big"]),
-                    default: "standard",
-                    description: "ASCII font",
-                },
+                ParamDef::text("title", "Welcome", "The title text")
+                    .positional(),
+                ParamDef::text("gradient", "", "Gradient colors (e.g., \"cyan-purple\"; default: theme's)"),
+                ParamDef::choice("align", &["left", "center", "right"], "left", "Alignment relative to terminal width: left, center, right"),
+                ParamDef::number("max-width", 0.0, 100.0, "", "Maximum banner width in columns (text wraps to fit)"),
+                ParamDef::number("padding", 0.0, 100.0, "1", "Blank lines above and below the text inside the frame"),
+                ParamDef::text("font", "", "ASCII font"),
+                ParamDef::flag("animate", "Animate the banner drawing"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
             ],
         },
         ComponentDef {
@@ -179,29 +255,134 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Loading spinners with messages",
             category: "Output",
             params: vec![
-                ParamDef {
-                    name: "message",
-                    param_type: ParamType::String,
-                    default: "Loading...",
-                    description: "Spinner message",
-                },
-                ParamDef {
-                    name: "style",
-                    param_type: ParamType::Enum(vec![
-                        "dots", "line", "arc", "bouncing", "clock", "circle", "bounce", "moon",
-                    ]),
-                    default: "dots",
-                    description: "Spinner animation style",
-                },
-                ParamDef {
-                    name: "duration",
-                    param_type: ParamType::Number {
-                        min: 1.0,
-                        max: 60.0,
-                    },
-                    default: "3",
-                    description: "Duration in seconds",
-                },
+                ParamDef::text("message", "Loading...", "Spinner message")
+                    .positional(),
+                ParamDef::choice("style", &spinner::STYLES, "dots", "Spinner animation style"),
+                ParamDef::number("duration", 1.0, 60.0, "3", "Duration in seconds"),
+            ],
+        },
+        ComponentDef {
+            name: "text",
+            description: "Print text with gradient colors and styling",
+            category: "Output",
+            params: vec![
+                ParamDef::text("text", "Hello, world", "The text to print")
+                    .positional(),
+                ParamDef::text("gradient", "", "Gradient colors (e.g., \"cyan-purple\")"),
+                ParamDef::flag("rainbow", "Rainbow colors (same as --gradient rainbow)"),
+                ParamDef::flag("bold", "Bold text"),
+                ParamDef::flag("italic", "Italic text"),
+                ParamDef::flag("underline", "Underlined text"),
+                ParamDef::number("spacing", 0.0, 100.0, "0", "Spaces between letters"),
+            ],
+        },
+        ComponentDef {
+            name: "animate",
+            description: "Run animation effects",
+            category: "Output",
+            params: vec![
+                ParamDef::choice("effect-type", &["progress", "typewriter", "counter", "chart-build", "bars", "matrix", "confetti", "fireworks"], "fireworks", "Animation type: progress, typewriter, counter, chart-build, bars, matrix, confetti, fireworks")
+                    .required(),
+                ParamDef::text("text", "", "Text content (for typewriter)"),
+                ParamDef::data("data", "", "Data (for chart-build, bars)"),
+                ParamDef::text("duration", "2.0", "Duration in seconds"),
+                ParamDef::text("speed", "30.0", "Speed (chars per second for typewriter)"),
+                ParamDef::number("from", 0.0, 100.0, "0", "From value (for counter)"),
+                ParamDef::number("to", 0.0, 400.0, "100", "To value (for counter)"),
+                ParamDef::text("style", "gradient", "Style (for progress)"),
+                ParamDef::text("prefix", "", "Prefix (for counter)"),
+                ParamDef::text("suffix", "", "Suffix (for counter)"),
+                ParamDef::choice("easing", &["linear", "ease-in", "ease-out", "ease-in-out", "cubic", "bounce", "elastic"], "linear", "Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic"),
+                ParamDef::text("density", "0.5", "How busy matrix, confetti and fireworks get, 0.0-1.0"),
+                ParamDef::text("colors", "", "Colors for matrix, confetti and fireworks: \"red,#ffd700,cyan\""),
+            ],
+        },
+        ComponentDef {
+            name: "notification",
+            description: "Desktop + terminal alerts",
+            category: "Output",
+            params: vec![
+                ParamDef::text("message", "Build finished", "Notification message")
+                    .positional(),
+                ParamDef::text("title", "", "Notification title"),
+                ParamDef::choice("style", &["info", "success", "warning", "error"], "info", "Style: info, success, warning, error"),
+                ParamDef::flag("sound", "Play sound with desktop notification"),
+                ParamDef::flag("terminal-only", "Show only terminal notification"),
+                ParamDef::flag("desktop-only", "Show only desktop notification"),
+                ParamDef::number("progress", 0.0, 100.0, "", "Percent done (0-100), shown as a progress bar"),
+                ParamDef::text("id", "", "Replace the notification sent earlier with this id"),
+                ParamDef::text("timeout", "", "How long the desktop notification stays up (e.g. 5s, 1m)"),
+                ParamDef::text("action", "", "Button as LABEL:COMMAND, run when clicked (repeatable; waits for the click)"),
+            ],
+        },
+        ComponentDef {
+            name: "stats",
+            description: "Display a compact stats bar with key-value pairs",
+            category: "Output",
+            params: vec![
+                ParamDef::data("data", "entries:500,size:2.3 MB,modified:2h ago", "Stats data: \"label:value,label:value\" or use --items")
+                    .positional(),
+                ParamDef::data("items", "", "Individual stat items (alternative to data)"),
+                ParamDef::choice("separator", &["pipe", "dot", "slash", "bar", "diamond", "arrow"], "pipe", "Separator style: pipe, dot, slash, bar, diamond, arrow"),
+                ParamDef::text("emoji", "", "Emoji prefix for the stats line"),
+                ParamDef::flag("json", "Output as JSON"),
+                ParamDef::flag("no-color", "Disable color coding"),
+            ],
+        },
+        ComponentDef {
+            name: "checklist",
+            description: "Display a checklist with checkboxes and optional data columns",
+            category: "Output",
+            params: vec![
+                ParamDef::data("items", "Task A:done:2h,Task B:pending:1h", "Items in format \"Label:status:col1:col2,...\" (status: done/pending)")
+                    .required(),
+                ParamDef::data("columns", "Duration", "Column headers (comma-separated)")
+                    .required(),
+                ParamDef::flag("json", "Output as JSON"),
+                ParamDef::flag("no-stats", "Hide stats summary"),
+            ],
+        },
+        ComponentDef {
+            name: "clock",
+            description: "Big digital clock in a banner font, updated every second until Ctrl+C",
+            category: "Output",
+            params: vec![
+                ParamDef::text("style", "block", "Font: block, slant, small, 3d, or a FIGlet .flf file"),
+                ParamDef::choice("format", &["24h", "12h"], "24h", "Hour format: 24h, 12h"),
+                ParamDef::flag("seconds", "Show seconds"),
+                ParamDef::flag("date", "Show the date under the clock"),
+                ParamDef::text("timezone", "local", "Timezone: local, UTC, an offset like +05:30, or a name like Europe/Paris"),
+            ],
+        },
+        ComponentDef {
+            name: "timer",
+            description: "Countdown timer with big digits and a progress ring; rings the bell when done",
+            category: "Output",
+            params: vec![
+                ParamDef::text("duration", "5m", "How long to count down: 90s, 5m, 1.5h")
+                    .positional(),
+                ParamDef::text("label", "", "Caption shown under the clock"),
+                ParamDef::choice("style", &["digital", "minimal"], "digital", "Style: digital, minimal"),
+                ParamDef::flag("notify", "Also send a desktop notification when time is up"),
+            ],
+        },
+        ComponentDef {
+            name: "battery",
+            description: "Battery level with charging icon, colored by charge left",
+            category: "Output",
+            params: vec![
+                ParamDef::flag("inline", "Compact single line with no newline, for PS1 or tmux status bars"),
+                ParamDef::choice("shell", &["plain", "bash", "zsh", "tmux"], "plain", "Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)"),
+            ],
+        },
+        ComponentDef {
+            name: "temps",
+            description: "Temperature gauges for CPU, GPU and other sensors",
+            category: "Output",
+            params: vec![
+                ParamDef::text("sensor", "", "Only show sensors whose label contains this text"),
+                ParamDef::flag("inline", "Compact single line with no newline, for PS1 or tmux status bars"),
+                ParamDef::choice("shell", &["plain", "bash", "zsh", "tmux"], "plain", "Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)"),
             ],
         },
         // CHARTS category
@@ -209,34 +390,144 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             name: "sparkline",
             description: "Inline mini charts",
             category: "Charts",
-            params: vec![ParamDef {
-                name: "data",
-                param_type: ParamType::Data,
-                default: "1,4,2,8,5,7,3,9,6",
-                description: "Comma-separated numeric values",
-            }],
+            params: vec![
+                ParamDef::data("data", "1,4,2,8,5,7,3,9,6", "Comma-separated numeric values")
+                    .positional(),
+                ParamDef::flag("animate", "Animate the sparkline building"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
+                ParamDef::text("source", "", "Follow a system reading instead: cpu, mem, disk[:mount], net:<iface> (KB/s)"),
+                ParamDef::text("interval", "1s", "How often --source adds a reading"),
+            ],
         },
         ComponentDef {
             name: "chart bar",
             description: "Horizontal bar charts",
             category: "Charts",
-            params: vec![ParamDef {
-                name: "data",
-                param_type: ParamType::Data,
-                default: "Sales:100,Costs:60,Profit:40",
-                description: "Label:value pairs",
-            }],
+            params: vec![
+                ParamDef::data("data", "Sales:100,Costs:60,Profit:40", "Label:value pairs")
+                    .required(),
+                ParamDef::flag("animate", "Animate bars growing"),
+                ParamDef::choice("easing", &["linear", "ease-in", "ease-out", "ease-in-out", "cubic", "bounce", "elastic"], "linear", "Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic"),
+            ],
+        },
+        ComponentDef {
+            name: "chart line",
+            description: "Line chart",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("data", "3,7,2,9,4,8,5", "Comma-separated values")
+                    .required(),
+                ParamDef::text("title", "", "Chart title"),
+                ParamDef::choice("style", &["braille", "horizon"], "braille", "Style: braille, horizon (one folded row for dense series)"),
+                ParamDef::number("bands", 0.0, 100.0, "3", "Number of horizon bands (1-4)"),
+                ParamDef::flag("animate", "Animate line drawing point by point"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
+                ParamDef::choice("easing", &["linear", "ease-in", "ease-out", "ease-in-out", "cubic", "bounce", "elastic"], "linear", "Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic"),
+            ],
         },
         ComponentDef {
             name: "chart pie",
             description: "ASCII pie charts",
             category: "Charts",
-            params: vec![ParamDef {
-                name: "data",
-                param_type: ParamType::Data,
-                default: "A:40,B:30,C:20,D:10",
-                description: "Label:value pairs",
-            }],
+            params: vec![
+                ParamDef::data("data", "A:40,B:30,C:20,D:10", "Label:value pairs")
+                    .required(),
+                ParamDef::flag("animate", "Animate slices appearing one by one"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
+                ParamDef::choice("legend", &["auto", "right", "below", "collapsed", "toggle", "none"], "auto", "Legend placement: auto, right, below, collapsed, toggle, none"),
+            ],
+        },
+        ComponentDef {
+            name: "chart boxplot",
+            description: "Box plot with median, quartiles, whiskers and outliers",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("data", "api:12,15,14,30,13;db:8,9,7,22,10", "Series in format \"name:v1,v2,v3;name:v1,v2\"")
+                    .required(),
+                ParamDef::flag("violin", "Show a density (violin) plot instead of a box"),
+            ],
+        },
+        ComponentDef {
+            name: "chart funnel",
+            description: "Funnel chart for conversion pipelines",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("data", "Visits:1000,Signups:400,Trials:150,Paid:60", "Stages in format \"Label:Value,Label:Value\"")
+                    .required(),
+                ParamDef::text("style", "info", "Style preset for bar colors (e.g. info, success, retro)"),
+            ],
+        },
+        ComponentDef {
+            name: "chart radar",
+            description: "Radar (spider) chart for multi-dimensional comparison",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("data", "speed:8,power:6,range:7,cost:4,comfort:9", "Axes in format \"name:value,name:value\" (at least 3)")
+                    .required(),
+                ParamDef::data("compare", "", "Second dataset to overlay, with the same axis names"),
+                ParamDef::number("max", 0.0, 100.0, "", "Value at the outer ring (default: largest value)"),
+                ParamDef::number("size", 0.0, 100.0, "8", "Radius in terminal rows"),
+                ParamDef::choice("legend", &["auto", "right", "below", "collapsed", "toggle", "none"], "auto", "Legend placement: auto, right, below, collapsed, toggle, none"),
+            ],
+        },
+        ComponentDef {
+            name: "chart ridgeline",
+            description: "Stacked sparklines with a shared X axis for comparing many series",
+            category: "Charts",
+            params: vec![
+                ParamDef::text("file", "", "Wide CSV file (`-` for stdin)"),
+                ParamDef::data("data", "cpu:3,5,8,6,4,7;mem:5,5,6,7,8,8;disk:2,2,3,2,4,3", "Inline series in format \"name:v1,v2,v3;name:v1,v2,v3\"")
+                    .required(),
+                ParamDef::number("height", 0.0, 100.0, "1", "Rows per series"),
+                ParamDef::flag("shared-scale", "Use one scale for all rows instead of scaling each row separately"),
+            ],
+        },
+        ComponentDef {
+            name: "heatmap",
+            description: "Display a 2D heatmap visualization",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("data", "1,2,3;4,5,6;7,8,9", "2D data: \"1,2,3;4,5,6;7,8,9\" (semicolon separates rows)")
+                    .required(),
+                ParamDef::text("file", "", "CSV file path (`-` for stdin)"),
+                ParamDef::data("x-labels", "", "X-axis labels (comma-separated)"),
+                ParamDef::data("y-labels", "", "Y-axis labels (comma-separated)"),
+                ParamDef::text("title", "", "Chart title"),
+                ParamDef::choice("colors", &["blue-red", "green-red", "viridis", "magma"], "blue-red", "Color scheme: blue-red, green-red, viridis, magma"),
+                ParamDef::flag("annotate", "Print each value inside its cell when it fits"),
+                ParamDef::choice("scale", &["linear", "log"], "linear", "Color scale: linear or log (log needs positive values)"),
+                ParamDef::text("null-color", "grey", "Color for missing cells (blank, NA, null): grey, red, ..., or none"),
+                ParamDef::flag("animate", "Animate the heatmap rendering"),
+            ],
+        },
+        ComponentDef {
+            name: "calendar",
+            description: "Display a calendar heatmap (GitHub-contribution style)",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("data", "2024-01-05:3,2024-01-06:10,2024-01-09:1", "Daily values: \"YYYY-MM-DD:value,YYYY-MM-DD:value\"")
+                    .required(),
+                ParamDef::text("file", "", "File with one \"YYYY-MM-DD:value\" entry per line (`-` for stdin)"),
+                ParamDef::text("from", "", "First date to show (default: earliest date in data)"),
+                ParamDef::text("to", "", "Last date to show (default: latest date in data)"),
+                ParamDef::text("title", "", "Chart title"),
+                ParamDef::choice("colors", &["blue-red", "green-red", "viridis", "magma"], "viridis", "Color scheme: blue-red, green-red, viridis, magma"),
+            ],
+        },
+        ComponentDef {
+            name: "timeline",
+            description: "Display a horizontal timeline",
+            category: "Charts",
+            params: vec![
+                ParamDef::data("events", "Start,Middle,End", "Events: \"Start,Middle,End\" or \"2024-01:Start,2024-06:Middle,2024-12:End\"; \"2024-01..2024-03:Design\" draws a bar on a date axis")
+                    .required(),
+                ParamDef::text("group", "", "A named lane of events as NAME=EVENTS (repeatable)"),
+                ParamDef::text("now", "", "Mark today (or the given YYYY[-MM[-DD]] date) on the date axis"),
+                ParamDef::choice("style", &["arrow", "line", "dots"], "arrow", "Style: arrow, line, dots"),
+                ParamDef::choice("accent", &["red", "green", "blue", "yellow", "magenta", "cyan", "white"], "", "Accent color: red, green, blue, yellow, magenta, cyan, white"),
+                ParamDef::flag("animate", "Animate the timeline"),
+                ParamDef::flag("vertical", "Render vertically"),
+            ],
         },
         // DATA category
         ComponentDef {
@@ -244,36 +535,167 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Formatted data tables",
             category: "Data",
             params: vec![
-                ParamDef {
-                    name: "headers",
-                    param_type: ParamType::String,
-                    default: "Name,Value,Status",
-                    description: "Comma-separated headers",
-                },
-                ParamDef {
-                    name: "rows",
-                    param_type: ParamType::Data,
-                    default: "Item1,100,OK|Item2,200,OK",
-                    description: "Pipe-separated rows",
-                },
-                ParamDef {
-                    name: "border",
-                    param_type: ParamType::Enum(vec!["rounded", "single", "double", "ascii"]),
-                    default: "rounded",
-                    description: "Border style",
-                },
+                ParamDef::data("headers", "Name,Value,Status", "Comma-separated headers")
+                    .required(),
+                ParamDef::data("rows", "Item1,100,OK|Item2,200,OK", "Pipe-separated rows")
+                    .required(),
+                ParamDef::text("file", "", "CSV, TSV or JSON file path (`-` for stdin, which is read when no other data is given)"),
+                ParamDef::text("json", "", "JSON file (`-` for stdin): an array of objects, one column per key"),
+                ParamDef::data("columns", "", "Columns to show, in order (header names or 1-based indexes, comma-separated)"),
+                ParamDef::data("hide", "", "Columns to leave out (header names or 1-based indexes, comma-separated)"),
+                ParamDef::text("query", "", "jq filter to reshape JSON input first (e.g. '.items[] | {name, count}')"),
+                ParamDef::text("sort", "", "Sort rows by this column (header name or 1-based index), optionally followed by :desc or :asc"),
+                ParamDef::flag("desc", "Sort in descending order"),
+                ParamDef::choice("sort-type", &["auto", "numeric", "size", "date", "semver", "string"], "auto", "How to compare sort cells: auto, numeric, size, date, semver, string"),
+                ParamDef::number("max-col-width", 0.0, 100.0, "", "Cut cells wider than this many columns, ending them with \"...\""),
+                ParamDef::flag("wrap", "Wrap cells wider than --max-col-width onto more lines instead"),
+                ParamDef::data("footer", "", "Summary row, comma-separated like a row; {sum}, {avg}, {min}, {max} and {count} are worked out from the column (e.g. \"Total,{sum}\")"),
+                ParamDef::choice("row-format", &["tsv", "json"], "tsv", "How Enter prints the row in --interactive: tsv, json"),
+                ParamDef::choice("border", &["single", "double", "rounded", "ascii", "none"], "rounded", "Border style"),
+                ParamDef::choice("alignment", &["left", "center", "right"], "left", "Column alignment: left, center, right"),
+                ParamDef::flag("animate", "Animate rows appearing one by one"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
+                ParamDef::choice("format", &["ansi", "html", "svg"], "ansi", "Output format: ansi, html, svg"),
             ],
         },
         ComponentDef {
             name: "tree",
             description: "Tree structure display",
             category: "Data",
-            params: vec![ParamDef {
-                name: "structure",
-                param_type: ParamType::String,
-                default: "root>child1,child2>leaf1,leaf2",
-                description: "Tree structure (> for children, , for siblings)",
-            }],
+            params: vec![
+                ParamDef::data("data", "root>src,docs>main.rs,lib.rs", "Tree data (e.g., \"root>child1,child2>grandchild\")")
+                    .positional(),
+                ParamDef::text("path", "", "JSON file path (`-` for stdin)"),
+                ParamDef::list("diff", "", "Compare two JSON files as one merged tree (either may be `-`)"),
+                ParamDef::text("dir", "", "Show a directory on disk, skipping what .gitignore excludes"),
+                ParamDef::number("depth", 0.0, 100.0, "", "With --dir, how many levels to descend"),
+                ParamDef::flag("all", "With --dir, include hidden files"),
+                ParamDef::flag("no-ignore", "With --dir, also show files .gitignore excludes"),
+                ParamDef::flag("size", "With --dir, show file sizes"),
+                ParamDef::flag("only-changes", "With --diff, fold identical subtrees and values into a summary line"),
+                ParamDef::text("query", "", "jq filter to reshape JSON input first (e.g. '.items[] | {name, count}')"),
+                ParamDef::flag("animate", "Animate tree nodes expanding"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
+            ],
+        },
+        ComponentDef {
+            name: "diff",
+            description: "Side-by-side diff of two files or directories",
+            category: "Data",
+            params: vec![
+                ParamDef::text("file1", "", "First file or directory path (`-` for stdin)")
+                    .positional(),
+                ParamDef::text("file2", "", "Second file or directory path (`-` for stdin)")
+                    .positional(),
+                ParamDef::flag("unified", "Use unified diff format"),
+                ParamDef::number("context", 0.0, 100.0, "", "Context lines for unified format"),
+                ParamDef::flag("word-diff", "Highlight the changed words within modified lines"),
+            ],
+        },
+        ComponentDef {
+            name: "join",
+            description: "Join content horizontally or vertically",
+            category: "Data",
+            params: vec![
+                ParamDef::list("inputs", "'Column A' 'Column B'", "Content pieces to join (optional if using stdin)")
+                    .positional(),
+                ParamDef::flag("vertical", "Join vertically instead of horizontally"),
+                ParamDef::number("gap", 0.0, 100.0, "2", "Gap between joined items (spaces/lines)"),
+                ParamDef::choice("align", &["left", "center", "right"], "left", "Alignment: left, center, right"),
+            ],
+        },
+        ComponentDef {
+            name: "stack",
+            description: "Stack content vertically with alignment",
+            category: "Data",
+            params: vec![
+                ParamDef::list("inputs", "Header Content Footer", "Content pieces to stack (optional if using stdin)")
+                    .positional(),
+                ParamDef::choice("align", &["left", "center", "right"], "left", "Alignment: left, center, right"),
+                ParamDef::number("gap", 0.0, 100.0, "1", "Gap between stacked items (blank lines)"),
+            ],
+        },
+        ComponentDef {
+            name: "code",
+            description: "Show a source file with syntax highlighting and line numbers",
+            category: "Data",
+            params: vec![
+                ParamDef::text("file", "src/main.rs", "Source file path (`-` for stdin)")
+                    .positional(),
+                ParamDef::text("lines", "", "Line range to show, e.g. 10-40, 10- or -40"),
+                ParamDef::text("lang", "", "Language for highlighting (default: detected from the extension)"),
+                ParamDef::flag("no-pager", "Print directly instead of opening the pager"),
+            ],
+        },
+        ComponentDef {
+            name: "md",
+            description: "Render a Markdown document",
+            category: "Data",
+            params: vec![
+                ParamDef::text("file", "README.md", "Markdown file path (`-` or omitted for stdin)")
+                    .positional(),
+            ],
+        },
+        ComponentDef {
+            name: "log",
+            description: "View a log with level colors, filtering and follow mode",
+            category: "Data",
+            params: vec![
+                ParamDef::text("file", "app.log", "Log file path (`-` or omitted for stdin)")
+                    .positional(),
+                ParamDef::text("filter", "", "Only show lines matching this regex"),
+                ParamDef::flag("relative", "Show the time since the previous timestamped line"),
+                ParamDef::flag("no-pager", "Print directly instead of opening the pager"),
+            ],
+        },
+        // MEDIA category
+        ComponentDef {
+            name: "image",
+            description: "Display an image in terminal",
+            category: "Media",
+            params: vec![
+                ParamDef::list("paths", "shots/*.png", "Paths or URLs to images, or a glob (`-` reads from stdin)")
+                    .positional(),
+                ParamDef::choice("protocol", &["auto", "kitty", "sixel", "iterm2", "halfblock", "quadblock", "braille"], "auto", "Protocol: auto, kitty, sixel, iterm2, halfblock, quadblock, braille"),
+                ParamDef::text("width", "", "Width in cells, or percent of the terminal (e.g. 40 or 50%); per thumbnail in a grid"),
+                ParamDef::text("height", "", "Height in cells, or percent of the terminal (e.g. 12 or 30%); per thumbnail in a grid"),
+                ParamDef::choice("align", &["left", "center", "right"], "left", "Horizontal alignment: left, center, right"),
+                ParamDef::flag("preserve-aspect", "With both --width and --height, fit inside them instead of stretching"),
+                ParamDef::number("loop", 0.0, 100.0, "", "Play an animated GIF/APNG/WebP this many times (0 = until Ctrl+C)"),
+                ParamDef::number("fps", 0.0, 100.0, "", "Playback speed in frames per second, overriding the file's delays"),
+                ParamDef::choice("palette", &["adaptive", "web", "gray"], "adaptive", "Sixel palette: adaptive (median cut), web, gray"),
+                ParamDef::flag("dither", "Dither when reducing colors (sixel, Braille, or block modes on 256/16 color terminals)"),
+                ParamDef::text("grid", "", "Lay several images out as COLSxROWS thumbnails, e.g. 3x2 (or just 3 columns)"),
+                ParamDef::number("gap", 0.0, 100.0, "1", "Cells between grid thumbnails, and lines between rows"),
+                ParamDef::flag("captions", "Show each file name under its thumbnail"),
+            ],
+        },
+        ComponentDef {
+            name: "barcode",
+            description: "Show a barcode with its text underneath",
+            category: "Media",
+            params: vec![
+                ParamDef::text("data", "5901234123457", "Text or digits to encode (`-` reads stdin)")
+                    .positional(),
+                ParamDef::choice("symbology", &["code128", "ean13", "ean8"], "code128", "Symbology: code128, ean13, ean8"),
+                ParamDef::number("size", 0.0, 100.0, "1", "Cells per module"),
+                ParamDef::number("height", 0.0, 100.0, "4", "Height of the bars in lines"),
+                ParamDef::flag("invert", "Draw dark bars instead of light spaces, for light terminal backgrounds"),
+                ParamDef::flag("no-text", "Leave out the text under the bars"),
+            ],
+        },
+        ComponentDef {
+            name: "qr",
+            description: "Show a QR code for a URL or any text",
+            category: "Media",
+            params: vec![
+                ParamDef::text("data", "https://example.com", "Text to encode (`-` reads stdin)")
+                    .positional(),
+                ParamDef::number("size", 0.0, 100.0, "1", "Cells per module"),
+                ParamDef::choice("error-correction", &["L", "M", "Q", "H"], "M", "Error correction level: L, M, Q, H (higher survives more damage but is bigger)"),
+                ParamDef::flag("invert", "Draw dark modules instead of light ones, for light terminal backgrounds"),
+                ParamDef::choice("protocol", &["auto", "kitty", "halfblock"], "auto", "Protocol: auto (kitty when available), kitty, halfblock"),
+            ],
         },
         // INTERACTIVE category
         ComponentDef {
@@ -281,30 +703,16 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Preview pane for data inspection",
             category: "Interactive",
             params: vec![
-                ParamDef {
-                    name: "title",
-                    param_type: ParamType::String,
-                    default: "Preview",
-                    description: "Pane title",
-                },
-                ParamDef {
-                    name: "items",
-                    param_type: ParamType::Data,
-                    default: "item1,item2,item3",
-                    description: "Items to display",
-                },
-                ParamDef {
-                    name: "style",
-                    param_type: ParamType::Enum(vec!["info", "success", "warning", "danger"]),
-                    default: "info",
-                    description: "Pane style",
-                },
-                ParamDef {
-                    name: "action",
-                    param_type: ParamType::String,
-                    default: "Select",
-                    description: "Action button label",
-                },
+                ParamDef::text("title", "Preview", "Pane title"),
+                ParamDef::data("items", "item1,item2,item3", "Items to display")
+                    .required(),
+                ParamDef::text("action", "Select", "Action button label"),
+                ParamDef::text("cancel", "Cancel", "Cancel button label"),
+                ParamDef::choice("style", &["info", "success", "warning", "danger"], "info", "Pane style"),
+                ParamDef::choice("border", &["single", "double", "rounded", "thick", "ascii"], "rounded", "Border style: single, double, rounded, thick, ascii"),
+                ParamDef::number("max-items", 0.0, 100.0, "20", "Maximum items to display (rest are truncated)"),
+                ParamDef::data("columns", "", "Column headers for tabular data (comma-separated)"),
+                ParamDef::flag("no-numbers", "Hide item numbers"),
             ],
         },
         ComponentDef {
@@ -312,24 +720,18 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Filter entries using regex patterns",
             category: "Interactive",
             params: vec![
-                ParamDef {
-                    name: "pattern",
-                    param_type: ParamType::String,
-                    default: "\\.log$",
-                    description: "Regex pattern to match",
-                },
-                ParamDef {
-                    name: "items",
-                    param_type: ParamType::Data,
-                    default: "app.log,config.json,error.log",
-                    description: "Items to filter",
-                },
-                ParamDef {
-                    name: "action",
-                    param_type: ParamType::String,
-                    default: "Apply",
-                    description: "Action button label",
-                },
+                ParamDef::text("pattern", "\\.log$", "Regex pattern to match")
+                    .required(),
+                ParamDef::data("items", "app.log,config.json,error.log", "Items to filter")
+                    .required(),
+                ParamDef::text("action", "Apply", "Action button label"),
+                ParamDef::text("cancel", "Cancel", "Cancel button label"),
+                ParamDef::choice("border", &["single", "double", "rounded", "thick", "ascii"], "rounded", "Border style: single, double, rounded, thick, ascii"),
+                ParamDef::number("max-items", 0.0, 100.0, "20", "Maximum items to display"),
+                ParamDef::flag("hide-non-matches", "Hide non-matching entries"),
+                ParamDef::flag("case-insensitive", "Case-insensitive matching"),
+                ParamDef::flag("invert", "Invert match (show non-matching entries)"),
+                ParamDef::flag("quiet", "Output only matching items (for piping)"),
             ],
         },
         ComponentDef {
@@ -337,18 +739,12 @@ pub fn get_all_components() -> Vec<ComponentDef> {
             description: "Warning box for destructive operations",
             category: "Interactive",
             params: vec![
-                ParamDef {
-                    name: "message",
-                    param_type: ParamType::String,
-                    default: "This action cannot be undone!",
-                    description: "Warning message",
-                },
-                ParamDef {
-                    name: "title",
-                    param_type: ParamType::String,
-                    default: "DANGER",
-                    description: "Box title",
-                },
+                ParamDef::text("message", "This action cannot be undone!", "Warning message")
+                    .positional(),
+                ParamDef::text("title", "DANGER", "Box title"),
+                ParamDef::choice("border", &["single", "double", "rounded", "thick"], "", "Border style: single, double, rounded, thick (default: double)"),
+                ParamDef::flag("animate", "Animate the box drawing"),
+                ParamDef::number("animation-time", 0.0, 2000.0, "500", "Total animation duration in ms (default: 500)"),
             ],
         },
     ]
@@ -362,6 +758,7 @@ pub fn get_components_by_category() -> Vec<(&'static str, Vec<ComponentDef>)> {
         ("Output", vec![]),
         ("Charts", vec![]),
         ("Data", vec![]),
+        ("Media", vec![]),
         ("Interactive", vec![]),
     ];
 
@@ -401,12 +798,59 @@ mod tests {
         let cmd = box_component.generate_command(&values);
         assert!(cmd.contains("termgfx box"));
         assert!(cmd.contains("--style success"));
+        assert_eq!(cmd, "termgfx box \"Test\" --style success");
+    }
+
+    #[test]
+    fn test_generate_command_keeps_default_positional() {
+        let components = get_all_components();
+        let progress = components.iter().find(|c| c.name == "progress").unwrap();
+        let cmd = progress.generate_command(&HashMap::new());
+        assert_eq!(
+            cmd,
+            format!("termgfx progress {}", progress.params[0].default)
+        );
+    }
+
+    #[test]
+    fn test_generate_command_keeps_required_options() {
+        let components = get_all_components();
+        let find = |name: &str| components.iter().find(|c| c.name == name).unwrap();
+
+        let cmd = find("chart bar").generate_command(&HashMap::new());
+        assert_eq!(
+            cmd,
+            "termgfx chart bar --data \"Sales:100,Costs:60,Profit:40\""
+        );
+
+        // Lists are passed as typed, so each word stays its own argument
+        let mut values = HashMap::new();
+        values.insert("inputs".to_string(), "'a b' c".to_string());
+        values.insert("vertical".to_string(), "true".to_string());
+        let cmd = find("join").generate_command(&values);
+        assert_eq!(cmd, "termgfx join 'a b' c --vertical");
+    }
+
+    #[test]
+    fn test_is_animated() {
+        let components = get_all_components();
+        let animated = |name: &str| {
+            components
+                .iter()
+                .find(|c| c.name == name)
+                .unwrap()
+                .is_animated()
+        };
+        assert!(animated("spinner"));
+        assert!(animated("progress"));
+        assert!(animated("gauge"));
+        assert!(!animated("box"));
     }
 
     #[test]
     fn test_components_by_category() {
         let categories = get_components_by_category();
-        assert_eq!(categories.len(), 4);
+        assert_eq!(categories.len(), 5);
         assert!(categories.iter().any(|(name, _)| *name == "Output"));
     }
 }
@@ -0,0 +1,183 @@
+//! Running the generated command for real, in the preview panel or to
+//! export what it draws
+//!
+//! The command runs on a pseudo-terminal the size of the preview, and what
+//! it prints is replayed onto a terminal screen, so the panel shows exactly
+//! what a terminal would rather than the studio's own approximation.
+//! Exports go through the command's own `--export` flag.
+
+use crate::interactive::repl::{save_export, split_args};
+use crate::output::pty;
+use crate::output::vt::{Cell, Screen};
+use std::env;
+use std::io::{self, Read};
+use std::process::{Child, Command, ExitStatus};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A command running (or finished) on a pty, with the screen it drew
+pub struct ShellRun {
+    /// The command line it was started from
+    pub command: String,
+    /// Bytes read from the pty that haven't reached the screen yet
+    incoming: Arc<Mutex<Vec<u8>>>,
+    /// A UTF-8 character split across reads
+    pending: Vec<u8>,
+    screen: Screen,
+    child: Child,
+    status: Option<ExitStatus>,
+}
+
+impl ShellRun {
+    /// Run a generated `termgfx ...` command line with this binary, on a
+    /// terminal of `size` (columns, rows)
+    pub fn start(command: &str, size: (u16, u16)) -> io::Result<Self> {
+        Self::spawn(command, termgfx(command)?, size)
+    }
+
+    /// Run `cmd`, started for the command line `command`
+    pub(super) fn spawn(command: &str, cmd: Command, size: (u16, u16)) -> io::Result<Self> {
+        let (mut master, child) = pty::spawn(cmd, size)?;
+        let incoming = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&incoming);
+        thread::spawn(move || {
+            let mut buffer = [0u8; 4096];
+            loop {
+                match master.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(n) => match sink.lock() {
+                        Ok(mut sink) => sink.extend_from_slice(&buffer[..n]),
+                        Err(_) => break,
+                    },
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    // EIO once the command has exited
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(Self {
+            command: command.to_string(),
+            incoming,
+            pending: Vec::new(),
+            screen: Screen::sized(size.0 as usize, size.1 as usize),
+            child,
+            status: None,
+        })
+    }
+
+    /// Replay new output onto the screen and check whether the command exited
+    pub fn poll(&mut self) {
+        if let Ok(mut incoming) = self.incoming.lock() {
+            self.pending.append(&mut incoming);
+        }
+        let text = pty::take_utf8(&mut self.pending);
+        if !text.is_empty() {
+            self.screen.write(&text);
+        }
+        if self.status.is_none() {
+            self.status = self.child.try_wait().ok().flatten();
+        }
+    }
+
+    /// Exit status, once the command has finished
+    pub fn status(&self) -> Option<ExitStatus> {
+        self.status
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status.is_none()
+    }
+
+    /// Kill the command if it is still running
+    pub fn stop(&mut self) {
+        if self.is_running() {
+            self.child.kill().ok();
+            self.status = self.child.wait().ok();
+        }
+    }
+
+    /// The screen's rows, top to bottom
+    pub fn lines(&self) -> Vec<Vec<Option<Cell>>> {
+        self.screen.visible()
+    }
+}
+
+/// A generated `termgfx ...` command line, to run with this binary
+fn termgfx(command: &str) -> io::Result<Command> {
+    let mut args = split_args(command).map_err(io::Error::other)?;
+    if args.first().is_some_and(|a| a == "termgfx") {
+        args.remove(0);
+    }
+    let mut cmd = Command::new(env::current_exe()?);
+    cmd.args(args);
+    Ok(cmd)
+}
+
+/// Run a generated command line with `--export`, saving what it draws at
+/// `path` (the extension picks SVG, PNG or HTML)
+pub fn export(command: &str, path: &str) -> Result<(), String> {
+    let cmd = termgfx(command).map_err(|e| e.to_string())?;
+    save_export(cmd, path)
+}
+
+impl Drop for ShellRun {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait(run: &mut ShellRun) {
+        let start = Instant::now();
+        while run.is_running() && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+            run.poll();
+        }
+        // The reader may still be draining the pty
+        thread::sleep(Duration::from_millis(50));
+        run.poll();
+    }
+
+    fn text(line: &[Option<Cell>]) -> String {
+        line.iter().flatten().map(|c| c.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_output_is_replayed_with_colors() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "printf 'loading\\r\\033[31mdone\\033[0m\\n'; exit 3"]);
+        let mut run = ShellRun::spawn("test", cmd, (20, 4)).unwrap();
+        wait(&mut run);
+
+        assert_eq!(run.status().and_then(|s| s.code()), Some(3));
+        let lines = run.lines();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(text(&lines[0]), "doneing");
+        let first = lines[0][0].as_ref().unwrap();
+        assert_eq!(first.style.fg, Some((205, 0, 0)));
+    }
+
+    #[test]
+    fn test_runs_on_a_terminal_of_the_given_size() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "stty size"]);
+        let mut run = ShellRun::spawn("test", cmd, (33, 7)).unwrap();
+        wait(&mut run);
+        assert_eq!(text(&run.lines()[0]), "7 33");
+    }
+
+    #[test]
+    fn test_stop_kills_the_command() {
+        let mut cmd = Command::new("sleep");
+        cmd.arg("30");
+        let mut run = ShellRun::spawn("test", cmd, (20, 4)).unwrap();
+        assert!(run.is_running());
+        run.stop();
+        assert!(!run.is_running());
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Saves configuration to ~/.config/termgfx/studio.json
 
+use crate::output::atomic;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -12,6 +13,8 @@ use std::time::SystemTime;
 const MAX_FAVORITES: usize = 100;
 /// Maximum number of history entries to store
 const MAX_HISTORY: usize = 10;
+/// Recently used components listed at the top of the sidebar
+const MAX_RECENT: usize = 5;
 
 /// A saved favorite configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,12 +70,8 @@ impl StudioStorage {
     pub fn save(&self) -> std::io::Result<()> {
         let dir = Self::config_dir();
         fs::create_dir_all(&dir)?;
-        let path = Self::storage_path();
-        let temp_path = path.with_extension("json.tmp");
         let content = serde_json::to_string_pretty(self)?;
-        // Write to temp file first, then rename for atomic operation
-        fs::write(&temp_path, content)?;
-        fs::rename(&temp_path, path)
+        atomic::write(Self::storage_path(), content)
     }
 
     /// Add a favorite
@@ -136,8 +135,19 @@ impl StudioStorage {
         self.history.truncate(MAX_HISTORY);
     }
 
+    /// The latest history entry of each recently used component, newest first
+    pub fn recent(&self) -> Vec<&HistoryEntry> {
+        let mut recent: Vec<&HistoryEntry> = Vec::new();
+        for entry in &self.history {
+            if !recent.iter().any(|r| r.component == entry.component) {
+                recent.push(entry);
+            }
+        }
+        recent.truncate(MAX_RECENT);
+        recent
+    }
+
     /// Get relative time string (e.g., "2m ago", "1h ago")
-    #[allow(dead_code)]
     pub fn relative_time(timestamp: u64) -> String {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
@@ -247,6 +257,29 @@ mod tests {
         assert_eq!(storage.history.len(), 10);
     }
 
+    #[test]
+    fn test_recent_lists_each_component_once() {
+        let mut storage = StudioStorage::default();
+        for (component, id) in [("box", "1"), ("gauge", "2"), ("box", "3")] {
+            let mut params = HashMap::new();
+            params.insert("id".to_string(), id.to_string());
+            storage.add_history(component.to_string(), params);
+        }
+
+        let recent: Vec<(&str, &str)> = storage
+            .recent()
+            .iter()
+            .map(|e| (e.component.as_str(), e.params["id"].as_str()))
+            .collect();
+        assert_eq!(recent, vec![("box", "3"), ("gauge", "2")]);
+
+        for i in 0..6 {
+            storage.add_history(format!("c{}", i), HashMap::new());
+        }
+        assert_eq!(storage.recent().len(), 5);
+        assert_eq!(storage.recent()[0].component, "c5");
+    }
+
     #[test]
     fn test_relative_time() {
         let now = SystemTime::now()
@@ -0,0 +1,348 @@
+//! Editing the global theme from the studio
+//!
+//! The editor works on a whole [`Theme`]: a base preset with edits on top.
+//! Saving writes the edits to the theme file as a user theme over that
+//! preset and makes it the default, like `termgfx theme set` does.
+
+use crate::design::colors::{Color, SeriesColors, SERIES_CYCLES};
+use crate::design::theme::{Theme, ThemeFile, ThemePreset, UserTheme, BORDER_STYLES};
+use crate::output::gradient::GRADIENT_NAMES;
+use std::path::PathBuf;
+
+/// Theme colors in the order the editor lists them
+pub const COLOR_NAMES: [&str; 10] = [
+    "primary",
+    "secondary",
+    "success",
+    "warning",
+    "danger",
+    "info",
+    "background",
+    "foreground",
+    "surface",
+    "border",
+];
+
+/// What a row of the editor changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Name,
+    Base,
+    Mode,
+    Border,
+    Gradient,
+    Series,
+    /// One of [`COLOR_NAMES`]
+    Color(usize),
+}
+
+impl Field {
+    /// Every row, top to bottom
+    pub fn all() -> Vec<Field> {
+        let mut fields = vec![
+            Field::Name,
+            Field::Base,
+            Field::Mode,
+            Field::Border,
+            Field::Gradient,
+            Field::Series,
+        ];
+        fields.extend((0..COLOR_NAMES.len()).map(Field::Color));
+        fields
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::Base => "base",
+            Field::Mode => "mode",
+            Field::Border => "border",
+            Field::Gradient => "gradient",
+            Field::Series => "series",
+            Field::Color(idx) => COLOR_NAMES[idx],
+        }
+    }
+
+    /// Whether the value is typed in rather than picked from a list
+    pub fn is_text(self) -> bool {
+        matches!(self, Field::Name | Field::Series | Field::Color(_))
+    }
+}
+
+/// State of the theme editor screen
+pub struct ThemeEditor {
+    /// Name the theme is saved under
+    pub name: String,
+    /// Preset the theme starts from
+    pub base: ThemePreset,
+    /// The theme as edited so far
+    pub theme: Theme,
+    /// Selected row in [`Field::all`]
+    pub selected: usize,
+    /// Text typed into the selected field, while editing it
+    pub input: Option<String>,
+}
+
+impl ThemeEditor {
+    /// Edit `active` (the theme this run uses), or a copy of dark.
+    /// Presets are saved as a new `custom` theme over them.
+    pub fn open(active: Option<&Theme>, file: &ThemeFile) -> Self {
+        let (name, base, theme) = match active {
+            Some(theme) => match file.themes.get(&theme.name) {
+                Some(user) => (
+                    theme.name.clone(),
+                    user.base
+                        .as_deref()
+                        .and_then(ThemePreset::from_str)
+                        .unwrap_or(ThemePreset::Dark),
+                    theme.clone(),
+                ),
+                None => (
+                    "custom".to_string(),
+                    ThemePreset::from_str(&theme.name).unwrap_or(ThemePreset::Dark),
+                    theme.clone(),
+                ),
+            },
+            None => ("custom".to_string(), ThemePreset::Dark, Theme::dark()),
+        };
+        Self {
+            name,
+            base,
+            theme,
+            selected: 0,
+            input: None,
+        }
+    }
+
+    pub fn field(&self) -> Field {
+        Field::all()[self.selected]
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        self.selected = (self.selected + 1).min(Field::all().len() - 1);
+    }
+
+    /// The field's current value, as shown and typed
+    pub fn value(&self, field: Field) -> String {
+        match field {
+            Field::Name => self.name.clone(),
+            Field::Base => self.base.as_str().to_string(),
+            Field::Mode => if self.theme.is_dark { "dark" } else { "light" }.to_string(),
+            Field::Border => self.theme.border_style.clone(),
+            Field::Gradient => self.theme.gradient.clone(),
+            Field::Series => self.theme.series_colors.clone(),
+            Field::Color(idx) => self.color(idx).to_string(),
+        }
+    }
+
+    /// The `#RRGGBB` value of the [`COLOR_NAMES`] entry `idx`
+    pub fn color(&self, idx: usize) -> &str {
+        let c = &self.theme.colors;
+        [
+            &c.primary,
+            &c.secondary,
+            &c.success,
+            &c.warning,
+            &c.danger,
+            &c.info,
+            &c.background,
+            &c.foreground,
+            &c.surface,
+            &c.border,
+        ][idx]
+    }
+
+    fn color_mut(&mut self, idx: usize) -> &mut String {
+        let c = &mut self.theme.colors;
+        match idx {
+            0 => &mut c.primary,
+            1 => &mut c.secondary,
+            2 => &mut c.success,
+            3 => &mut c.warning,
+            4 => &mut c.danger,
+            5 => &mut c.info,
+            6 => &mut c.background,
+            7 => &mut c.foreground,
+            8 => &mut c.surface,
+            _ => &mut c.border,
+        }
+    }
+
+    /// Step the selected field to its next option (`forward`) or the
+    /// previous one. A new base starts over from that preset.
+    pub fn cycle(&mut self, forward: bool) {
+        fn step<T: PartialEq + Copy>(options: &[T], current: T, forward: bool) -> T {
+            let len = options.len();
+            let pos = options.iter().position(|o| *o == current);
+            let next = match (pos, forward) {
+                (Some(pos), true) => (pos + 1) % len,
+                (Some(pos), false) => (pos + len - 1) % len,
+                (None, _) => 0,
+            };
+            options[next]
+        }
+
+        match self.field() {
+            Field::Base => {
+                self.base = step(&ThemePreset::all(), self.base, forward);
+                self.theme = Theme::load_preset(self.base);
+            }
+            Field::Mode => self.theme.is_dark = !self.theme.is_dark,
+            Field::Border => {
+                let next = step(&BORDER_STYLES, self.theme.border_style.as_str(), forward);
+                self.theme.border_style = next.to_string();
+            }
+            Field::Gradient => {
+                let next = step(&GRADIENT_NAMES, self.theme.gradient.as_str(), forward);
+                self.theme.gradient = next.to_string();
+            }
+            Field::Series => {
+                let next = step(&SERIES_CYCLES, self.theme.series_colors.as_str(), forward);
+                self.theme.series_colors = next.to_string();
+            }
+            Field::Name | Field::Color(_) => {}
+        }
+    }
+
+    /// Start typing into the selected field, from its current value
+    pub fn start_input(&mut self) {
+        self.input = Some(self.value(self.field()));
+    }
+
+    /// Apply the typed text to the selected field. Invalid text is kept so
+    /// it can be fixed.
+    pub fn commit(&mut self) -> Result<(), String> {
+        let Some(text) = self.input.as_deref() else {
+            return Ok(());
+        };
+        let text = text.trim().to_string();
+        match self.field() {
+            Field::Name if text.is_empty() => return Err("The theme needs a name".to_string()),
+            Field::Name => self.name = text,
+            Field::Series => {
+                SeriesColors::parse(&text)?;
+                self.theme.series_colors = text;
+            }
+            Field::Color(idx) => match Color::from_hex(&text) {
+                Some(color) => *self.color_mut(idx) = color.to_hex(),
+                None => return Err(format!("'{}' is not a #RRGGBB color", text)),
+            },
+            _ => {}
+        }
+        self.input = None;
+        Ok(())
+    }
+
+    /// Add the theme to `file` and make it the default
+    pub fn apply(&self, file: &mut ThemeFile) -> Result<(), String> {
+        let mut updated = file.clone();
+        let mut user = UserTheme::from_theme(self.base, &self.theme);
+        user.description = file
+            .themes
+            .get(&self.name)
+            .and_then(|t| t.description.clone());
+        updated.themes.insert(self.name.clone(), user);
+        updated.theme = Some(self.name.clone());
+        Theme::resolve(&self.name, &updated).map_err(|e| format!("{:#}", e))?;
+        *file = updated;
+        Ok(())
+    }
+
+    /// Save to the user's theme file, returning its path
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let path = ThemeFile::path().ok_or("Could not determine config directory")?;
+        let mut file = ThemeFile::load_user().map_err(|e| format!("{:#}", e))?;
+        self.apply(&mut file)?;
+        file.save(&path).map_err(|e| format!("{:#}", e))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(editor: &mut ThemeEditor, field: Field) {
+        editor.selected = Field::all().iter().position(|f| *f == field).unwrap();
+    }
+
+    #[test]
+    fn test_open_from_active_theme() {
+        let file: ThemeFile =
+            toml::from_str("[themes.ocean]\nbase = \"nord\"\nborder_style = \"thick\"\n").unwrap();
+        let ocean = Theme::resolve("ocean", &file).unwrap();
+        let editor = ThemeEditor::open(Some(&ocean), &file);
+        assert_eq!(editor.name, "ocean");
+        assert_eq!(editor.base, ThemePreset::Nord);
+        assert_eq!(editor.theme.border_style, "thick");
+
+        let editor = ThemeEditor::open(Some(&Theme::dracula()), &file);
+        assert_eq!(editor.name, "custom");
+        assert_eq!(editor.base, ThemePreset::Dracula);
+
+        let editor = ThemeEditor::open(None, &file);
+        assert_eq!(editor.base, ThemePreset::Dark);
+    }
+
+    #[test]
+    fn test_cycle_choices() {
+        let mut editor = ThemeEditor::open(None, &ThemeFile::default());
+        select(&mut editor, Field::Border);
+        let start = editor.theme.border_style.clone();
+        editor.cycle(true);
+        assert_ne!(editor.theme.border_style, start);
+        editor.cycle(false);
+        assert_eq!(editor.theme.border_style, start);
+
+        select(&mut editor, Field::Mode);
+        editor.cycle(true);
+        assert!(!editor.theme.is_dark);
+
+        // A new base drops the edits
+        select(&mut editor, Field::Base);
+        editor.cycle(true);
+        assert_eq!(editor.base, ThemePreset::Light);
+        assert_eq!(editor.theme.colors.primary, Theme::light().colors.primary);
+    }
+
+    #[test]
+    fn test_commit_validates_colors() {
+        let mut editor = ThemeEditor::open(None, &ThemeFile::default());
+        select(&mut editor, Field::Color(0));
+        editor.input = Some("nope".to_string());
+        assert_eq!(
+            editor.commit(),
+            Err("'nope' is not a #RRGGBB color".to_string())
+        );
+        assert!(editor.input.is_some());
+
+        editor.input = Some(" 112233 ".to_string());
+        assert_eq!(editor.commit(), Ok(()));
+        assert_eq!(editor.theme.colors.primary, "#112233");
+        assert_eq!(editor.input, None);
+
+        select(&mut editor, Field::Series);
+        editor.input = Some("#112233,brown".to_string());
+        assert!(editor.commit().is_err());
+    }
+
+    #[test]
+    fn test_apply_adds_theme_as_default() {
+        let mut editor = ThemeEditor::open(Some(&Theme::nord()), &ThemeFile::default());
+        select(&mut editor, Field::Color(1));
+        editor.input = Some("#abcdef".to_string());
+        editor.commit().unwrap();
+
+        let mut file = ThemeFile::default();
+        editor.apply(&mut file).unwrap();
+        assert_eq!(file.theme.as_deref(), Some("custom"));
+        let saved = &file.themes["custom"];
+        assert_eq!(saved.base.as_deref(), Some("nord"));
+        assert_eq!(saved.colors.secondary.as_deref(), Some("#ABCDEF"));
+        assert_eq!(saved.colors.primary, None);
+    }
+}
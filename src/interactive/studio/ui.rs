@@ -1,14 +1,26 @@
 //! UI rendering for the studio TUI
 
+use owo_colors::AnsiColors;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    symbols::border,
+    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Wrap},
 };
 use std::collections::HashMap;
 
-use super::app::{FocusedPanel, StudioApp};
+use super::app::{ColorDepth, FocusedPanel, Playback, PlaybackState, SidebarRow, StudioApp};
 use super::layout::StudioAreas;
 use super::registry::{ComponentDef, ParamType};
+use super::shell::ShellRun;
+use super::storage::StudioStorage;
+use super::theme_editor::Field;
+use crate::design::colors::SeriesColors;
+use crate::design::theme::Theme;
+use crate::output::gradient::Gradient;
+use crate::output::styled_box;
+use crate::output::vt;
+use crate::render::{self, spinner::SpinnerState};
+use crate::text;
 
 /// Render the entire studio UI
 pub fn render(frame: &mut Frame, app: &StudioApp, areas: StudioAreas) {
@@ -22,9 +34,9 @@ pub fn render(frame: &mut Frame, app: &StudioApp, areas: StudioAreas) {
 fn render_sidebar(frame: &mut Frame, app: &StudioApp, area: Rect) {
     let focused = matches!(app.focused_panel, FocusedPanel::Sidebar);
     let border_style = if focused {
-        Style::default().fg(Color::Cyan).bold()
+        Style::default().fg(app.theme.accent()).bold()
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.muted())
     };
 
     let block = Block::default()
@@ -32,38 +44,33 @@ fn render_sidebar(frame: &mut Frame, app: &StudioApp, area: Rect) {
         .borders(Borders::ALL)
         .border_style(border_style);
 
-    let mut items: Vec<ListItem> = Vec::new();
-    let mut current_category = "";
-
-    for (idx, component) in app.components.iter().enumerate() {
-        // Add category header if changed
-        if component.category != current_category {
-            current_category = component.category;
-            let header = ListItem::new(Line::from(vec![Span::styled(
-                format!(" {} ", component.category.to_uppercase()),
-                Style::default().fg(Color::Yellow).bold(),
-            )]));
-            items.push(header);
-        }
-
-        // Add component item
-        let style = if idx == app.selected_component {
-            Style::default().fg(Color::Green).bold()
-        } else {
-            Style::default()
-        };
-
-        let marker = if idx == app.selected_component {
-            "▶ "
-        } else {
-            "  "
-        };
-        let item = ListItem::new(Line::from(vec![
-            Span::styled(marker, style),
-            Span::styled(component.name, style),
-        ]));
-        items.push(item);
-    }
+    let rows = app.sidebar_rows();
+    let highlight = app.sidebar_highlight(&rows);
+    let height = block.inner(area).height as usize;
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .skip(app.sidebar_offset(&rows, height))
+        .map(|(row, entry)| {
+            let label = match *entry {
+                SidebarRow::Header(title) => {
+                    return ListItem::new(Line::from(Span::styled(
+                        format!(" {} ", title.to_uppercase()),
+                        Style::default().fg(Color::Yellow).bold(),
+                    )));
+                }
+                SidebarRow::Favorite(idx) => app.storage.favorites[idx].name.clone(),
+                SidebarRow::Recent(idx) => app.storage.recent()[idx].component.clone(),
+                SidebarRow::Component(idx) => app.components[idx].name.to_string(),
+            };
+            if Some(row) == highlight {
+                let style = Style::default().fg(Color::Green).bold();
+                ListItem::new(Line::from(Span::styled(format!("▶ {}", label), style)))
+            } else {
+                ListItem::new(Line::from(format!("  {}", label)))
+            }
+        })
+        .collect();
 
     let list = List::new(items).block(block);
 
@@ -74,9 +81,9 @@ fn render_sidebar(frame: &mut Frame, app: &StudioApp, area: Rect) {
 fn render_params(frame: &mut Frame, app: &StudioApp, area: Rect) {
     let focused = matches!(app.focused_panel, FocusedPanel::Params);
     let border_style = if focused {
-        Style::default().fg(Color::Cyan).bold()
+        Style::default().fg(app.theme.accent()).bold()
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.muted())
     };
 
     let block = Block::default()
@@ -127,6 +134,7 @@ fn render_params(frame: &mut Frame, app: &StudioApp, area: Rect) {
                 ParamType::Enum(opts) => &opts.join("|"),
                 ParamType::Bool => "bool",
                 ParamType::Data => "data",
+                ParamType::List => "list",
             };
 
             let line = Line::from(vec![
@@ -145,7 +153,11 @@ fn render_params(frame: &mut Frame, app: &StudioApp, area: Rect) {
             lines.push(line);
         }
 
-        let paragraph = Paragraph::new(lines);
+        // Keep the selected param in view on components with many flags
+        let offset = app
+            .selected_param
+            .saturating_sub(inner.height.saturating_sub(1) as usize);
+        let paragraph = Paragraph::new(lines).scroll((offset as u16, 0));
         frame.render_widget(paragraph, inner);
     }
 }
@@ -154,30 +166,309 @@ fn render_params(frame: &mut Frame, app: &StudioApp, area: Rect) {
 fn render_preview(frame: &mut Frame, app: &StudioApp, area: Rect) {
     let focused = matches!(app.focused_panel, FocusedPanel::Preview);
     let border_style = if focused {
-        Style::default().fg(Color::Cyan).bold()
+        Style::default().fg(app.theme.accent()).bold()
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.muted())
     };
 
+    let width_label = match app.preview_width {
+        Some(cols) => format!("{} cols", cols),
+        None => "fit".to_string(),
+    };
+    let component = app.components.get(app.selected_component);
+    let playback_label = match (&app.shell, &app.playback) {
+        (Some(run), _) => match run.status() {
+            None => " · ⏵ running · Esc stops".to_string(),
+            Some(status) if status.success() => " · ✓ ran · Esc back".to_string(),
+            Some(status) => match status.code() {
+                Some(code) => format!(" · ✗ exit {} · Esc back", code),
+                None => " · ✗ killed · Esc back".to_string(),
+            },
+        },
+        (None, Some(playback)) if playback.paused => " · ⏸ paused".to_string(),
+        (None, Some(_)) => " · ▶ playing".to_string(),
+        (None, None) if focused && component.is_some_and(|c| c.is_animated()) => {
+            " · space: play · Enter: run".to_string()
+        }
+        (None, None) if component.is_some_and(|c| c.is_animated()) => " · space: play".to_string(),
+        (None, None) if focused => " · Enter: run".to_string(),
+        (None, None) => String::new(),
+    };
     let block = Block::default()
-        .title(" Live Preview ")
+        .title(format!(
+            " Live Preview · {} · {}{} ",
+            width_label,
+            app.color_depth.label(),
+            playback_label
+        ))
         .borders(Borders::ALL)
         .border_style(border_style);
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if let Some(component) = app.components.get(app.selected_component) {
-        let preview_text = generate_preview(component, &app.param_values);
-        let paragraph = Paragraph::new(preview_text).wrap(Wrap { trim: false });
-        frame.render_widget(paragraph, inner);
+    let Some(component) = component else {
+        return;
+    };
+
+    // Render into the simulated width; anything wider than the pane is clipped
+    let target = app.preview_width.unwrap_or(inner.width);
+    let mut content = inner;
+    if target < inner.width && inner.height > 1 {
+        content.width = target;
+        // Mark the simulated right edge of the terminal
+        let edge = Rect::new(inner.x + target, inner.y, 1, inner.height);
+        let marks: Vec<Line> = (0..inner.height).map(|_| Line::from("┊")).collect();
+        frame.render_widget(
+            Paragraph::new(marks).style(Style::default().fg(app.theme.muted())),
+            edge,
+        );
+    } else if target > inner.width && inner.height > 1 {
+        content.height -= 1;
+        let note = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                format!("(clipped: pane is {} cols)", inner.width),
+                Style::default().fg(app.theme.muted()).italic(),
+            ))),
+            note,
+        );
+    }
+
+    let lines = match (&app.shell, &app.playback) {
+        (Some(run), _) => shell_lines(run),
+        (None, Some(playback)) => {
+            playback_preview(component, &app.param_values, playback, target as usize)
+        }
+        (None, None) => generate_preview(component, &app.param_values, target as usize),
+    };
+    let preview_text = degrade_lines(lines, app.color_depth);
+    let paragraph = Paragraph::new(preview_text).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, content);
+}
+
+/// What a command run in the preview has drawn, one span per run of
+/// cells with the same attributes
+fn shell_lines(run: &ShellRun) -> Vec<Line<'static>> {
+    run.lines()
+        .iter()
+        .map(|row| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut current: Option<(vt::Style, String)> = None;
+            let mut covered = 0;
+            for cell in row {
+                let (style, text) = match cell {
+                    Some(cell) => {
+                        covered = cell.text.chars().next().map_or(1, text::char_width) - 1;
+                        (cell.style, cell.text.as_str())
+                    }
+                    // The second half of a wide character
+                    None if covered > 0 => {
+                        covered -= 1;
+                        continue;
+                    }
+                    None => (vt::Style::default(), " "),
+                };
+                match &mut current {
+                    Some((run_style, run_text)) if *run_style == style => run_text.push_str(text),
+                    _ => {
+                        if let Some((style, text)) = current.take() {
+                            spans.push(Span::styled(text, cell_style(style)));
+                        }
+                        current = Some((style, text.to_string()));
+                    }
+                }
+            }
+            if let Some((style, text)) = current {
+                spans.push(Span::styled(text, cell_style(style)));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// The ratatui style for SGR attributes
+fn cell_style(style: vt::Style) -> Style {
+    let rgb = |(r, g, b): vt::Rgb| Color::Rgb(r, g, b);
+    let mut out = Style::default();
+    if let Some(fg) = style.fg {
+        out = out.fg(rgb(fg));
+    }
+    if let Some(bg) = style.bg {
+        out = out.bg(rgb(bg));
+    }
+    for (on, modifier) in [
+        (style.bold, Modifier::BOLD),
+        (style.dim, Modifier::DIM),
+        (style.italic, Modifier::ITALIC),
+        (style.underline, Modifier::UNDERLINED),
+        (style.inverse, Modifier::REVERSED),
+    ] {
+        if on {
+            out = out.add_modifier(modifier);
+        }
+    }
+    out
+}
+
+/// Re-map every span color to what a terminal with `depth` can show
+fn degrade_lines(lines: Vec<Line<'static>>, depth: ColorDepth) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .map(|mut line| {
+            for span in line.spans.iter_mut() {
+                span.style.fg = span.style.fg.and_then(|c| degrade_color(c, depth));
+                span.style.bg = span.style.bg.and_then(|c| degrade_color(c, depth));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Closest color representable at `depth`, or `None` when color is unavailable
+fn degrade_color(color: Color, depth: ColorDepth) -> Option<Color> {
+    match depth {
+        ColorDepth::TrueColor => Some(color),
+        ColorDepth::Monochrome => None,
+        ColorDepth::Ansi256 => match color {
+            Color::Rgb(r, g, b) => Some(Color::Indexed(rgb_to_256(r, g, b))),
+            other => Some(other),
+        },
+        ColorDepth::Ansi16 => {
+            let rgb = match color {
+                Color::Rgb(r, g, b) => (r, g, b),
+                Color::Indexed(i) if i >= 16 => index_to_rgb(i),
+                Color::Indexed(i) => return Some(ANSI16[i as usize].0),
+                other => return Some(other),
+            };
+            ANSI16
+                .iter()
+                .min_by_key(|(_, c)| {
+                    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+                    d(rgb.0, c.0) + d(rgb.1, c.1) + d(rgb.2, c.2)
+                })
+                .map(|(named, _)| *named)
+        }
+    }
+}
+
+/// The 16 standard ANSI colors with their xterm RGB values
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Nearest xterm-256 index in the 6×6×6 color cube
+fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| ((v as u16 * 5 + 127) / 255) as u8;
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// RGB value of an xterm-256 index (cube and grayscale ramp)
+fn index_to_rgb(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let v = 8 + (index - 232) * 10;
+        return (v, v, v);
     }
+    let i = index.saturating_sub(16);
+    let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+    (level(i / 36), level((i / 6) % 6), level(i % 6))
 }
 
 /// Generate preview text for a component
+/// The preview at the playing animation's current frame
+fn playback_preview(
+    component: &ComponentDef,
+    values: &HashMap<String, String>,
+    playback: &Playback,
+    width: usize,
+) -> Vec<Line<'static>> {
+    match (
+        &playback.state,
+        playback.percent(),
+        component.params.first(),
+    ) {
+        (PlaybackState::Spinner(spinner), _, _) => doc_lines(&spinner.doc()),
+        (_, Some(percent), Some(param)) => {
+            let mut values = values.clone();
+            values.insert(param.name.to_string(), percent.to_string());
+            generate_preview(component, &values, width)
+        }
+        _ => generate_preview(component, values, width),
+    }
+}
+
+/// A render [`Doc`](render::Doc) as ratatui lines
+fn doc_lines(doc: &render::Doc) -> Vec<Line<'static>> {
+    const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    let color = |c: render::Color| match c {
+        render::Color::Named(n) => NAMED[n as usize & 15],
+        render::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    };
+
+    doc.lines()
+        .iter()
+        .map(|spans| {
+            Line::from(
+                spans
+                    .iter()
+                    .map(|span| {
+                        let mut style = Style::default();
+                        if let Some(fg) = span.style.fg {
+                            style = style.fg(color(fg));
+                        }
+                        if let Some(bg) = span.style.bg {
+                            style = style.bg(color(bg));
+                        }
+                        if span.style.bold {
+                            style = style.bold();
+                        }
+                        if span.style.dim {
+                            style = style.dim();
+                        }
+                        Span::styled(span.text.clone(), style)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
 fn generate_preview(
     component: &ComponentDef,
     values: &HashMap<String, String>,
+    width: usize,
 ) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
@@ -192,41 +483,30 @@ fn generate_preview(
                 .get("border")
                 .map(|s| s.as_str())
                 .unwrap_or("rounded");
-            let emoji = values.get("emoji").map(|s| s.as_str()).unwrap_or("");
-
-            let (tl, tr, bl, br, h, v) = match border {
-                "rounded" => ("╭", "╮", "╰", "╯", "─", "│"),
-                "double" => ("╔", "╗", "╚", "╝", "═", "║"),
-                "thick" => ("┏", "┓", "┗", "┛", "━", "┃"),
-                _ => ("┌", "┐", "└", "┘", "─", "│"),
+            let label = |name: &str| {
+                values
+                    .get(name)
+                    .map(|s| s.as_str())
+                    .filter(|s| !s.is_empty())
             };
-
-            let color = match style {
-                "success" => Color::Green,
-                "warning" => Color::Yellow,
-                "danger" => Color::Red,
-                _ => Color::Cyan,
-            };
-
-            let content = if emoji.is_empty() {
-                message.to_string()
-            } else {
-                format!("{} {}", emoji, message)
+            let title_align = values
+                .get("title-align")
+                .map(|s| s.as_str())
+                .unwrap_or("left");
+
+            // Same layout and colors as `termgfx box`, wrapped to the preview width
+            let options = styled_box::BoxOptions {
+                style,
+                border,
+                emoji: label("emoji"),
+                title: label("title"),
+                footer: label("footer"),
+                title_align: styled_box::TitleAlign::from_name(title_align)
+                    .unwrap_or(styled_box::TitleAlign::Left),
+                padding: 1,
+                max_width: Some(width),
             };
-            let width = content.len() + 2;
-
-            lines.push(Line::from(Span::styled(
-                format!("{}{}{}", tl, h.repeat(width), tr),
-                Style::default().fg(color),
-            )));
-            lines.push(Line::from(Span::styled(
-                format!("{} {} {}", v, content, v),
-                Style::default().fg(color),
-            )));
-            lines.push(Line::from(Span::styled(
-                format!("{}{}{}", bl, h.repeat(width), br),
-                Style::default().fg(color),
-            )));
+            lines.extend(doc_lines(&styled_box::box_doc(message, &options)));
         }
         "progress" => {
             let percent: u8 = values
@@ -238,8 +518,8 @@ fn generate_preview(
                 .map(|s| s.as_str())
                 .unwrap_or("gradient");
 
-            let width = 30;
-            let filled = (width * percent as usize) / 100;
+            let width = 30.min(width.saturating_sub(6)).max(1);
+            let filled = (width * percent.min(100) as usize) / 100;
 
             let (filled_char, empty_char) = match style {
                 "blocks" => ("█", "░"),
@@ -266,7 +546,7 @@ fn generate_preview(
                 .unwrap_or(75.0);
             let label = values.get("label").map(|s| s.as_str()).unwrap_or("CPU");
 
-            let segments = 20;
+            let segments = 20.min(width).max(1);
             let filled = ((value / 100.0) * segments as f64) as usize;
 
             let gauge: String = (0..segments)
@@ -279,6 +559,11 @@ fn generate_preview(
             )));
             lines.push(Line::from(format!("{}: {:.0}%", label, value)));
         }
+        "spinner" => {
+            let style = values.get("style").map(|s| s.as_str()).unwrap_or("dots");
+            let message = values.get("message").map(|s| s.as_str()).unwrap_or("");
+            lines.extend(doc_lines(&SpinnerState::new(style, message).doc()));
+        }
         "sparkline" => {
             let data = values
                 .get("data")
@@ -295,8 +580,10 @@ fn generate_preview(
                 let range = if max == min { 1.0 } else { max - min };
 
                 let chars = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+                // Keep the most recent points that fit
                 let spark: String = values
                     .iter()
+                    .skip(values.len().saturating_sub(width))
                     .map(|v| {
                         let idx = (((v - min) / range) * 7.0) as usize;
                         chars[idx.min(7)]
@@ -327,7 +614,7 @@ fn generate_preview(
 
 /// Render the command panel
 fn render_command(frame: &mut Frame, app: &StudioApp, area: Rect) {
-    let border_style = Style::default().fg(Color::DarkGray);
+    let border_style = Style::default().fg(app.theme.muted());
 
     let block = Block::default()
         .title(" Command ")
@@ -363,7 +650,7 @@ pub fn render_help_overlay(frame: &mut Frame) {
 
     // Center the help panel
     let help_width = 50;
-    let help_height = 22;
+    let help_height = 28;
     let x = (area.width.saturating_sub(help_width)) / 2;
     let y = (area.height.saturating_sub(help_height)) / 2;
     let help_area = Rect::new(x, y, help_width, help_height);
@@ -388,11 +675,19 @@ pub fn render_help_overlay(frame: &mut Frame) {
         (" Editing", ""),
         ("  Enter", "Edit parameter"),
         ("  Space", "Toggle bool / cycle enum"),
+        ("  Space (preview)", "Play/pause animation"),
+        ("  Enter (preview)", "Run command for real"),
         ("  r", "Reset to defaults"),
         ("  Esc", "Cancel edit"),
         ("", ""),
         (" Actions", ""),
+        ("  Ctrl+P", "Command palette"),
+        ("  Ctrl+S / Ctrl+O", "Save / load favorite"),
+        ("  w", "Cycle preview width"),
+        ("  Shift+C", "Cycle preview colors"),
         ("  c", "Copy command"),
+        ("  e / Shift+E", "Export / cycle format"),
+        ("  Shift+T", "Edit theme"),
         ("  ?", "Toggle this help"),
         ("  q / Esc", "Quit"),
         ("", ""),
@@ -419,6 +714,418 @@ pub fn render_help_overlay(frame: &mut Frame) {
     frame.render_widget(paragraph, help_area);
 }
 
+/// Render the Ctrl+P command palette overlay
+pub fn render_command_palette(frame: &mut Frame, app: &StudioApp) {
+    let area = frame.area();
+
+    let palette_width = 60.min(area.width);
+    let palette_height = 16.min(area.height);
+    let x = (area.width.saturating_sub(palette_width)) / 2;
+    let y = (area.height.saturating_sub(palette_height)) / 3;
+    let palette_area = Rect::new(x, y, palette_width, palette_height);
+
+    frame.render_widget(Clear, palette_area);
+
+    let block = Block::default()
+        .title(" Command Palette ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent()).bold())
+        .style(Style::default().bg(Color::Black));
+
+    let matches = app.palette_matches();
+    // Input line, separator and footer take three rows
+    let visible = (palette_height as usize).saturating_sub(5).max(1);
+    let offset = app.palette_index.saturating_sub(visible - 1);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(app.theme.accent()).bold()),
+            Span::styled(app.palette_query.clone(), Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(app.theme.accent())),
+        ]),
+        Line::from(""),
+    ];
+
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No matching commands",
+            Style::default().fg(app.theme.muted()).italic(),
+        )));
+    }
+
+    for (idx, (label, _)) in matches.iter().enumerate().skip(offset).take(visible) {
+        if idx == app.palette_index {
+            lines.push(Line::from(Span::styled(
+                format!("▶ {}", label),
+                Style::default().fg(Color::Green).bold(),
+            )));
+        } else {
+            lines.push(Line::from(format!("  {}", label)));
+        }
+    }
+
+    let inner = block.inner(palette_area);
+    frame.render_widget(Paragraph::new(lines).block(block), palette_area);
+
+    let footer_area = Rect::new(
+        inner.x,
+        inner.y + inner.height.saturating_sub(1),
+        inner.width,
+        1,
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!(
+                "{}/{}  ↑↓ select · Enter run · Esc close",
+                matches.len(),
+                app.palette_entries().len()
+            ),
+            Style::default().fg(app.theme.muted()),
+        ))),
+        footer_area,
+    );
+}
+
+/// Render the prompt for a new favorite's name
+pub fn render_name_prompt(frame: &mut Frame, app: &StudioApp) {
+    let area = frame.area();
+    let width = 50.min(area.width);
+    let prompt_area = Rect::new(
+        area.width.saturating_sub(width) / 2,
+        area.height.saturating_sub(3) / 3,
+        width,
+        3.min(area.height),
+    );
+
+    frame.render_widget(Clear, prompt_area);
+    let block = Block::default()
+        .title(" Save favorite as ")
+        .title_bottom(" Enter save · Esc cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent()).bold())
+        .style(Style::default().bg(Color::Black));
+    let line = Line::from(vec![
+        Span::styled("> ", Style::default().fg(app.theme.accent()).bold()),
+        Span::styled(
+            app.favorite_name_buffer.clone(),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled("█", Style::default().fg(app.theme.accent())),
+    ]);
+    frame.render_widget(Paragraph::new(line).block(block), prompt_area);
+}
+
+/// Render the Ctrl+O list of saved favorites
+pub fn render_favorites(frame: &mut Frame, app: &StudioApp) {
+    let area = frame.area();
+
+    let list_width = 60.min(area.width);
+    let list_height = 16.min(area.height);
+    let x = (area.width.saturating_sub(list_width)) / 2;
+    let y = (area.height.saturating_sub(list_height)) / 3;
+    let list_area = Rect::new(x, y, list_width, list_height);
+
+    frame.render_widget(Clear, list_area);
+
+    let block = Block::default()
+        .title(" ★ Favorites ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent()).bold())
+        .style(Style::default().bg(Color::Black));
+
+    let matches = app.favorite_matches();
+    // Input line, separator and footer take three rows
+    let visible = (list_height as usize).saturating_sub(5).max(1);
+    let offset = app.favorites_index.saturating_sub(visible - 1);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(app.theme.accent()).bold()),
+            Span::styled(
+                app.favorites_query.clone(),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("█", Style::default().fg(app.theme.accent())),
+        ]),
+        Line::from(""),
+    ];
+
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No matching favorites",
+            Style::default().fg(app.theme.muted()).italic(),
+        )));
+    }
+
+    for (idx, &favorite) in matches.iter().enumerate().skip(offset).take(visible) {
+        let favorite = &app.storage.favorites[favorite];
+        let (marker, style) = if idx == app.favorites_index {
+            ("▶ ", Style::default().fg(Color::Green).bold())
+        } else {
+            ("  ", Style::default())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}{}", marker, favorite.name), style),
+            Span::styled(
+                format!(
+                    "  {} · {}",
+                    favorite.component,
+                    StudioStorage::relative_time(favorite.created_at)
+                ),
+                Style::default().fg(app.theme.muted()),
+            ),
+        ]));
+    }
+
+    let inner = block.inner(list_area);
+    frame.render_widget(Paragraph::new(lines).block(block), list_area);
+
+    let footer_area = Rect::new(
+        inner.x,
+        inner.y + inner.height.saturating_sub(1),
+        inner.width,
+        1,
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!(
+                "{}/{}  ↑↓ select · Enter load · Del delete · Esc close",
+                matches.len(),
+                app.storage.favorites.len()
+            ),
+            Style::default().fg(app.theme.muted()),
+        ))),
+        footer_area,
+    );
+}
+
+/// Render the `T` theme editor over the whole screen: the theme's fields
+/// on the left, sample components drawn with it on the right
+pub fn render_theme_editor(frame: &mut Frame, app: &StudioApp) {
+    let Some(editor) = &app.theme_editor else {
+        return;
+    };
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" ✎ Theme Editor · {} ", editor.name))
+        .title_bottom(" ↑↓ select · ←→ change · Enter edit · s save · Esc close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent()).bold())
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [fields_area, preview_area] =
+        Layout::horizontal([Constraint::Length(36), Constraint::Min(0)]).areas(inner);
+
+    let hex = |value: &str| {
+        let (r, g, b) = Theme::rgb(value);
+        Color::Rgb(r, g, b)
+    };
+
+    let mut lines = vec![Line::from("")];
+    for (idx, field) in Field::all().into_iter().enumerate() {
+        if field == Field::Color(0) {
+            lines.push(Line::from(""));
+        }
+        let selected = idx == editor.selected;
+        let (marker, label_style) = if selected {
+            ("▶ ", Style::default().fg(Color::Green).bold())
+        } else {
+            ("  ", Style::default().fg(Color::White))
+        };
+        let mut spans = vec![Span::styled(
+            format!("{}{:12}", marker, field.label()),
+            label_style,
+        )];
+        match &editor.input {
+            Some(input) if selected => {
+                spans.push(Span::styled(
+                    input.clone(),
+                    Style::default().fg(Color::White),
+                ));
+                spans.push(Span::styled("█", Style::default().fg(app.theme.accent())));
+            }
+            _ => {
+                if let Field::Color(idx) = field {
+                    let swatch = Style::default().fg(hex(editor.color(idx)));
+                    spans.push(Span::styled("██ ", swatch));
+                }
+                let value = editor.value(field);
+                let value = if field.is_text() {
+                    value
+                } else {
+                    format!("‹ {} ›", value)
+                };
+                spans.push(Span::styled(value, Style::default().fg(app.theme.muted())));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines), fields_area);
+
+    let theme = &editor.theme;
+    let colors = &theme.colors;
+    let text = Style::default()
+        .fg(hex(&colors.foreground))
+        .bg(hex(&colors.background));
+    let preview = Block::default()
+        .title(" Preview ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.muted()))
+        .style(text);
+    let preview_inner = preview.inner(preview_area).inner(Margin::new(2, 1));
+    frame.render_widget(preview, preview_area);
+    let [box_area, samples_area] =
+        Layout::vertical([Constraint::Length(5), Constraint::Min(0)]).areas(preview_inner);
+
+    // A box, drawn with the theme's border style
+    let sample_box = Block::default()
+        .title(Span::styled(
+            " Deploy ",
+            Style::default().fg(hex(&colors.primary)).bold(),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border_set(&theme.border_style))
+        .border_style(Style::default().fg(hex(&colors.border)));
+    let box_lines = vec![
+        Line::from(vec![
+            Span::styled("✓ ", Style::default().fg(hex(&colors.success))),
+            Span::raw("Build finished in 3.2s"),
+        ]),
+        Line::from(Span::styled(
+            "3 services · 0 warnings",
+            Style::default().fg(hex(&colors.secondary)),
+        )),
+    ];
+    let box_rect = Rect {
+        width: box_area.width.min(36),
+        ..box_area
+    };
+    frame.render_widget(
+        Paragraph::new(box_lines).block(sample_box.padding(Padding::horizontal(1))),
+        box_rect,
+    );
+
+    let badge = |label: &str, color: &str| {
+        Span::styled(
+            format!(" {} ", label),
+            Style::default()
+                .fg(hex(&colors.background))
+                .bg(hex(color))
+                .bold(),
+        )
+    };
+    let mut samples = vec![
+        Line::from(""),
+        Line::from(vec![
+            badge("✓ success", &colors.success),
+            Span::raw(" "),
+            badge("⚠ warning", &colors.warning),
+            Span::raw(" "),
+            badge("✗ danger", &colors.danger),
+            Span::raw(" "),
+            badge("ℹ info", &colors.info),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Progress "),
+            Span::styled("█".repeat(16), Style::default().fg(hex(&colors.primary))),
+            Span::styled("░".repeat(8), Style::default().fg(hex(&colors.surface))),
+            Span::raw(" 67%"),
+        ]),
+        Line::from(""),
+    ];
+
+    // Series colors, as a bar chart would hand them out
+    let series = SeriesColors::parse(&theme.series_colors).unwrap_or_default();
+    for (idx, (name, value)) in [("api", 18), ("web", 12), ("db", 15), ("cache", 7)]
+        .into_iter()
+        .enumerate()
+    {
+        let color = series.at(idx);
+        samples.push(Line::from(vec![
+            Span::raw(format!("{:6}", name)),
+            Span::styled(
+                "█".repeat(value),
+                Style::default().fg(Color::Rgb(color.r, color.g, color.b)),
+            ),
+            Span::raw(format!(" {}", value)),
+        ]));
+    }
+    samples.push(Line::from(""));
+
+    // Banner text swept with the theme's gradient
+    let banner = "TERMGFX GRADIENT";
+    let gradient = Gradient::from_name(&theme.gradient).unwrap_or(Gradient::BluePurple);
+    let last = (banner.chars().count() - 1) as f32;
+    samples.push(Line::from(
+        banner
+            .chars()
+            .enumerate()
+            .map(|(idx, c)| {
+                let color = ansi_color(gradient.color_at(idx as f32 / last));
+                Span::styled(c.to_string(), Style::default().fg(color).bold())
+            })
+            .collect::<Vec<_>>(),
+    ));
+    samples.push(Line::from(""));
+    samples.push(Line::from(vec![
+        Span::styled("Primary", Style::default().fg(hex(&colors.primary)).bold()),
+        Span::raw(" · "),
+        Span::styled("Secondary", Style::default().fg(hex(&colors.secondary))),
+        Span::raw(" · body text on "),
+        Span::styled(
+            if theme.is_dark { "dark" } else { "light" },
+            Style::default().fg(hex(&colors.border)),
+        ),
+    ]));
+    frame.render_widget(Paragraph::new(samples), samples_area);
+}
+
+/// Ratatui glyphs for a theme border style
+fn border_set(style: &str) -> border::Set {
+    match style {
+        "single" => border::PLAIN,
+        "double" => border::DOUBLE,
+        "thick" => border::THICK,
+        "ascii" => border::Set {
+            top_left: "+",
+            top_right: "+",
+            bottom_left: "+",
+            bottom_right: "+",
+            vertical_left: "|",
+            vertical_right: "|",
+            horizontal_top: "-",
+            horizontal_bottom: "-",
+        },
+        _ => border::ROUNDED,
+    }
+}
+
+fn ansi_color(color: AnsiColors) -> Color {
+    match color {
+        AnsiColors::Black => Color::Black,
+        AnsiColors::Red => Color::Red,
+        AnsiColors::Green => Color::Green,
+        AnsiColors::Yellow => Color::Yellow,
+        AnsiColors::Blue => Color::Blue,
+        AnsiColors::Magenta => Color::Magenta,
+        AnsiColors::Cyan => Color::Cyan,
+        AnsiColors::White => Color::Gray,
+        AnsiColors::BrightBlack => Color::DarkGray,
+        AnsiColors::BrightRed => Color::LightRed,
+        AnsiColors::BrightGreen => Color::LightGreen,
+        AnsiColors::BrightYellow => Color::LightYellow,
+        AnsiColors::BrightBlue => Color::LightBlue,
+        AnsiColors::BrightMagenta => Color::LightMagenta,
+        AnsiColors::BrightCyan => Color::LightCyan,
+        AnsiColors::BrightWhite => Color::White,
+        AnsiColors::Default => Color::Reset,
+    }
+}
+
 /// Render a status message at the bottom of the screen
 pub fn render_status_message(frame: &mut Frame, message: &str) {
     let area = frame.area();
@@ -441,6 +1148,30 @@ pub fn render_status_message(frame: &mut Frame, message: &str) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shell_lines_keep_colors() {
+        let mut cmd = std::process::Command::new("printf");
+        cmd.arg("\\033[1;32mok\\033[0m 📊!");
+        let mut run = ShellRun::spawn("test", cmd, (20, 2)).unwrap();
+        let start = std::time::Instant::now();
+        while run.is_running() && start.elapsed() < std::time::Duration::from_secs(5) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            run.poll();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        run.poll();
+
+        let lines = shell_lines(&run);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].to_string(), "ok 📊!");
+        assert_eq!(lines[0].spans[0].content, "ok");
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Rgb(0, 205, 0)));
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
     #[test]
     fn test_generate_box_preview() {
         let component = ComponentDef {
@@ -454,7 +1185,7 @@ mod tests {
         values.insert("message".to_string(), "Hello".to_string());
         values.insert("style".to_string(), "success".to_string());
 
-        let lines = generate_preview(&component, &values);
+        let lines = generate_preview(&component, &values, 80);
         assert!(!lines.is_empty());
     }
 
@@ -470,7 +1201,88 @@ mod tests {
         let mut values = HashMap::new();
         values.insert("percent".to_string(), "75".to_string());
 
-        let lines = generate_preview(&component, &values);
+        let lines = generate_preview(&component, &values, 80);
         assert!(!lines.is_empty());
     }
+
+    #[test]
+    fn test_generate_preview_respects_width() {
+        let component = ComponentDef {
+            name: "box",
+            description: "Test",
+            category: "Output",
+            params: vec![],
+        };
+
+        let mut values = HashMap::new();
+        values.insert("message".to_string(), "x".repeat(100));
+
+        let lines = generate_preview(&component, &values, 40);
+        assert!(lines.iter().all(|l| l.width() <= 40));
+    }
+
+    #[test]
+    fn test_generate_box_preview_shows_title_and_footer() {
+        let component = ComponentDef {
+            name: "box",
+            description: "Test",
+            category: "Output",
+            params: vec![],
+        };
+
+        let mut values = HashMap::new();
+        values.insert("message".to_string(), "Ready".to_string());
+        values.insert("title".to_string(), "Deploy".to_string());
+        values.insert("footer".to_string(), "v1.2.3".to_string());
+        values.insert("title-align".to_string(), "center".to_string());
+
+        let lines: Vec<String> = generate_preview(&component, &values, 40)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert!(lines[0].contains(" Deploy "));
+        assert!(lines.last().unwrap().contains(" v1.2.3 "));
+    }
+
+    #[test]
+    fn test_spinner_preview_shows_first_frame() {
+        let component = ComponentDef {
+            name: "spinner",
+            description: "Test",
+            category: "Output",
+            params: vec![],
+        };
+
+        let mut values = HashMap::new();
+        values.insert("style".to_string(), "line".to_string());
+        values.insert("message".to_string(), "Loading".to_string());
+
+        let lines = generate_preview(&component, &values, 80);
+        assert_eq!(lines[0].to_string(), "| Loading");
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Cyan));
+    }
+
+    #[test]
+    fn test_degrade_color() {
+        assert_eq!(
+            degrade_color(Color::Rgb(1, 2, 3), ColorDepth::TrueColor),
+            Some(Color::Rgb(1, 2, 3))
+        );
+        assert_eq!(
+            degrade_color(Color::Rgb(255, 0, 0), ColorDepth::Ansi256),
+            Some(Color::Indexed(196))
+        );
+        assert_eq!(
+            degrade_color(Color::Rgb(250, 5, 5), ColorDepth::Ansi16),
+            Some(Color::LightRed)
+        );
+        assert_eq!(degrade_color(Color::Green, ColorDepth::Monochrome), None);
+    }
+
+    #[test]
+    fn test_index_to_rgb_roundtrip() {
+        assert_eq!(index_to_rgb(196), (255, 0, 0));
+        assert_eq!(rgb_to_256(255, 0, 0), 196);
+        assert_eq!(index_to_rgb(232), (8, 8, 8));
+    }
 }
@@ -0,0 +1,515 @@
+//! `termgfx table --interactive`: the table in a scrollable view
+//!
+//! Rows scroll up and down and columns left and right, `/` searches and
+//! highlights matches, `s` cycles the sort on the selected column, and
+//! Enter prints the selected row as TSV or JSON. When stdout is a pipe the
+//! view is drawn on the terminal instead, so the row is all that goes down
+//! the pipe.
+
+use super::screen;
+use crate::output::table_sort::{SortType, TableSort};
+use crate::text;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Cell, Paragraph, Row, Table, TableState},
+    TerminalOptions, Viewport,
+};
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Write};
+
+/// Widest a column is drawn; longer cells end in "…"
+const MAX_COL_WIDTH: usize = 40;
+
+/// Names accepted by [`RowFormat::from_name`]
+pub const ROW_FORMATS: &str = "tsv, json";
+
+/// How Enter prints the selected row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowFormat {
+    Tsv,
+    Json,
+}
+
+impl RowFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "tsv" => Some(RowFormat::Tsv),
+            "json" => Some(RowFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// `row` on one line: tab-separated cells, or a JSON object keyed by
+    /// header in column order
+    pub fn format(self, headers: &[String], row: &[String]) -> String {
+        match self {
+            RowFormat::Tsv => row
+                .iter()
+                .map(|cell| cell.replace(['\t', '\n', '\r'], " "))
+                .collect::<Vec<_>>()
+                .join("\t"),
+            RowFormat::Json => {
+                let fields: Vec<String> = headers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, header)| {
+                        let value = row.get(i).map_or("", String::as_str);
+                        format!(
+                            "{}:{}",
+                            serde_json::Value::from(header.as_str()),
+                            serde_json::Value::from(value)
+                        )
+                    })
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            }
+        }
+    }
+}
+
+/// Interactive table: rows in `order`, a cursor row and a selected column
+pub struct TableViewer {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    widths: Vec<usize>,
+    /// Indexes into `rows` in the order they're shown
+    order: Vec<usize>,
+    /// Sorted column and whether it's descending
+    sort: Option<(usize, bool)>,
+    cursor: usize,
+    column: usize,
+    /// First column drawn, for scrolling sideways
+    first_column: usize,
+    state: TableState,
+    page: usize,
+    query: String,
+    searching: bool,
+    status: Option<String>,
+}
+
+impl TableViewer {
+    pub fn new(headers: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        let plain = |cell: &String| text::strip_ansi(cell).into_owned();
+        let headers: Vec<String> = headers.iter().map(plain).collect();
+        let rows: Vec<Vec<String>> = rows.iter().map(|r| r.iter().map(plain).collect()).collect();
+        let widths = headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                // Room for the sort arrow after the header
+                let cells = rows
+                    .iter()
+                    .filter_map(|r| r.get(i))
+                    .map(|c| text::display_width(c));
+                cells
+                    .chain([text::display_width(header) + 2])
+                    .max()
+                    .unwrap_or(0)
+                    .min(MAX_COL_WIDTH)
+            })
+            .collect();
+        Self {
+            order: (0..rows.len()).collect(),
+            headers,
+            rows,
+            widths,
+            sort: None,
+            cursor: 0,
+            column: 0,
+            first_column: 0,
+            state: TableState::default(),
+            page: 10,
+            query: String::new(),
+            searching: false,
+            status: None,
+        }
+    }
+
+    /// The row under the cursor
+    pub fn selected(&self) -> Option<&[String]> {
+        self.order
+            .get(self.cursor)
+            .map(|&i| self.rows[i].as_slice())
+    }
+
+    /// Sort by the selected column: ascending, then descending, then back
+    /// to the original order; the cursor stays on the same row
+    fn cycle_sort(&mut self) {
+        let current = self.order.get(self.cursor).copied();
+        self.sort = match self.sort {
+            Some((col, false)) if col == self.column => Some((col, true)),
+            Some((col, true)) if col == self.column => None,
+            _ => Some((self.column, false)),
+        };
+        self.order = match self.sort {
+            Some((col, descending)) => {
+                let sort = TableSort {
+                    column: String::new(),
+                    sort_type: SortType::Auto,
+                    descending,
+                };
+                sort.order(col, &self.rows).0
+            }
+            None => (0..self.rows.len()).collect(),
+        };
+        if let Some(pos) = current.and_then(|row| self.order.iter().position(|&i| i == row)) {
+            self.cursor = pos;
+        }
+    }
+
+    /// Move to the next (or previous) row with a cell matching the query,
+    /// starting at the cursor row itself when `from_cursor` is set
+    fn find(&mut self, forward: bool, from_cursor: bool) {
+        if self.query.is_empty() || self.order.is_empty() {
+            return;
+        }
+        let count = self.order.len();
+        let start = if from_cursor { 0 } else { 1 };
+        let found = (start..=count)
+            .map(|step| {
+                if forward {
+                    (self.cursor + step) % count
+                } else {
+                    (self.cursor + count - step % count) % count
+                }
+            })
+            .find(|&pos| {
+                self.rows[self.order[pos]]
+                    .iter()
+                    .any(|cell| match_range(cell, &self.query).is_some())
+            });
+        self.status = match found {
+            Some(pos) => {
+                self.cursor = pos;
+                None
+            }
+            None => Some(format!("No match for '{}'", self.query)),
+        };
+    }
+
+    /// Handle a key press; `Some(true)` once a row is picked and
+    /// `Some(false)` when the view is closed without one
+    fn handle_key(&mut self, key: KeyEvent) -> Option<bool> {
+        if self.searching {
+            match key.code {
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.query.clear();
+                }
+                KeyCode::Enter => {
+                    self.searching = false;
+                    self.find(true, true);
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Char(c) => self.query.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        self.status = None;
+        let last = self.order.len().saturating_sub(1);
+        let last_column = self.headers.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(false)
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::PageUp => self.cursor = self.cursor.saturating_sub(self.page),
+            KeyCode::PageDown => self.cursor = (self.cursor + self.page).min(last),
+            KeyCode::Home | KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::End | KeyCode::Char('G') => self.cursor = last,
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.column = (self.column + 1).min(last_column),
+            KeyCode::Char('s') => self.cycle_sort(),
+            KeyCode::Char('/') => {
+                self.searching = true;
+                self.query.clear();
+            }
+            KeyCode::Char('n') => self.find(true, false),
+            KeyCode::Char('N') => self.find(false, false),
+            KeyCode::Enter if !self.order.is_empty() => return Some(true),
+            KeyCode::Char('q') | KeyCode::Esc => return Some(false),
+            _ => {}
+        }
+        None
+    }
+
+    /// Columns that fit in `width` cells, scrolled so the selected one
+    /// is among them
+    fn visible_columns(&mut self, width: usize) -> Vec<usize> {
+        let fits = |first: usize| {
+            let mut used = 0;
+            (first..self.headers.len())
+                .take_while(|&i| {
+                    used += self.widths[i] + 1;
+                    used <= width || i == first
+                })
+                .collect::<Vec<_>>()
+        };
+        self.first_column = self.first_column.min(self.column);
+        while !fits(self.first_column).contains(&self.column) {
+            self.first_column += 1;
+        }
+        fits(self.first_column)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [title, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let sorted = match self.sort {
+            Some((col, descending)) => format!(
+                " · sorted by {} {}",
+                self.headers[col],
+                if descending { "▼" } else { "▲" }
+            ),
+            None => String::new(),
+        };
+        let heading = format!("─── {} rows{} ", self.order.len(), sorted);
+        let fill = "─".repeat((title.width as usize).saturating_sub(text::display_width(&heading)));
+        frame.render_widget(
+            Paragraph::new(format!("{}{}", heading, fill)).style(Style::new().cyan()),
+            title,
+        );
+
+        // Two cells go to the row marker
+        let columns = self.visible_columns((body.width as usize).saturating_sub(2));
+        let header = Row::new(columns.iter().map(|&i| {
+            let arrow = match self.sort {
+                Some((col, true)) if col == i => " ▼",
+                Some((col, false)) if col == i => " ▲",
+                _ => "",
+            };
+            let label = text::truncate(
+                &format!("{}{}", self.headers[i], arrow),
+                self.widths[i],
+                "…",
+            );
+            let style = if i == self.column {
+                Style::new().cyan().bold().reversed()
+            } else {
+                Style::new().cyan().bold()
+            };
+            Cell::from(label).style(style)
+        }));
+        let query = if self.searching || self.query.is_empty() {
+            ""
+        } else {
+            self.query.as_str()
+        };
+        let rows = self.order.iter().map(|&row| {
+            Row::new(columns.iter().map(|&i| {
+                let cell = self.rows[row].get(i).map_or("", String::as_str);
+                highlighted(&text::truncate(cell, self.widths[i], "…"), query)
+            }))
+        });
+        let widths = columns
+            .iter()
+            .map(|&i| Constraint::Length(self.widths[i] as u16));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::new().bold().on_dark_gray())
+            .highlight_symbol("❯ ");
+        self.page = (body.height as usize).saturating_sub(1).max(1);
+        self.state.select(Some(self.cursor));
+        frame.render_stateful_widget(table, body, &mut self.state);
+
+        let line = if self.searching {
+            format!("/{}", self.query)
+        } else if let Some(status) = &self.status {
+            status.clone()
+        } else {
+            format!(
+                "row {}/{} | ↑↓/←→:move | /:search n/N | s:sort | Enter:print row | q:quit",
+                (self.cursor + 1).min(self.order.len()),
+                self.order.len()
+            )
+        };
+        frame.render_widget(Paragraph::new(line).dark_gray(), footer);
+    }
+
+    /// Show the table until a row is picked (true) or the view is closed
+    pub fn run(&mut self) -> io::Result<bool> {
+        // With stdout piped the view goes to the terminal itself
+        let mut out: Box<dyn Write> = if io::stdout().is_terminal() {
+            Box::new(io::stdout())
+        } else {
+            Box::new(OpenOptions::new().write(true).open("/dev/tty")?)
+        };
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen)?;
+        let backend = CrosstermBackend::new(out);
+        let mut terminal = match terminal::size() {
+            Ok((cols, rows)) if cols > 0 && rows > 0 => Terminal::new(backend)?,
+            _ => {
+                let (cols, rows) = screen::size();
+                let viewport = Viewport::Fixed(Rect::new(0, 0, cols, rows));
+                Terminal::with_options(backend, TerminalOptions { viewport })?
+            }
+        };
+
+        let result = (|| -> io::Result<bool> {
+            loop {
+                terminal.draw(|frame| self.draw(frame))?;
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Release {
+                        continue;
+                    }
+                    if let Some(picked) = self.handle_key(key) {
+                        return Ok(picked);
+                    }
+                }
+            }
+        })();
+
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        terminal::disable_raw_mode()?;
+        result
+    }
+}
+
+/// Byte range of the first case-insensitive match of `query` in `cell`
+fn match_range(cell: &str, query: &str) -> Option<(usize, usize)> {
+    if query.is_empty() {
+        return None;
+    }
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    cell.char_indices().find_map(|(start, _)| {
+        let mut end = start;
+        let mut rest = cell[start..].chars();
+        for q in query.chars() {
+            let c = rest.next()?;
+            if lower(c) != lower(q) {
+                return None;
+            }
+            end += c.len_utf8();
+        }
+        Some((start, end))
+    })
+}
+
+/// A cell with its match of `query` picked out
+fn highlighted<'a>(cell: &str, query: &str) -> Cell<'a> {
+    match match_range(cell, query) {
+        Some((start, end)) => Cell::from(Line::from(vec![
+            Span::raw(cell[..start].to_string()),
+            Span::styled(
+                cell[start..end].to_string(),
+                Style::new().black().on_yellow(),
+            ),
+            Span::raw(cell[end..].to_string()),
+        ])),
+        None => Cell::from(cell.to_string()),
+    }
+}
+
+/// Open the viewer and print the row picked with Enter in `row_format`
+/// (tsv or json); closing it without one counts as a cancel
+pub fn render(headers: Vec<String>, rows: Vec<Vec<String>>, row_format: &str) -> io::Result<()> {
+    let format = RowFormat::from_name(row_format).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid row format '{}' (use {})", row_format, ROW_FORMATS),
+        )
+    })?;
+    // Keys come from the terminal when stdin carries the data
+    if !io::stdin().is_terminal() && std::fs::File::open("/dev/tty").is_err() {
+        return Err(io::Error::other(
+            "Table viewer requires an interactive terminal (TTY)",
+        ));
+    }
+
+    let mut viewer = TableViewer::new(headers, rows);
+    match viewer.run()? {
+        true => {
+            let row = viewer.selected().unwrap_or_default();
+            println!("{}", format.format(&viewer.headers, row));
+            Ok(())
+        }
+        false => Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer() -> TableViewer {
+        let rows = [["b", "10"], ["a", "9"], ["c", "100"]]
+            .iter()
+            .map(|r| r.iter().map(|c| c.to_string()).collect())
+            .collect();
+        TableViewer::new(vec!["name".to_string(), "size".to_string()], rows)
+    }
+
+    fn press(viewer: &mut TableViewer, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '>' => KeyCode::Right,
+                c => KeyCode::Char(c),
+            };
+            viewer.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_sort_cycles_and_keeps_the_cursor_row() {
+        let mut viewer = viewer();
+        press(&mut viewer, "j>s");
+        assert_eq!(viewer.order, [1, 0, 2]);
+        assert_eq!(viewer.selected().unwrap()[0], "a");
+        press(&mut viewer, "s");
+        assert_eq!(viewer.order, [2, 0, 1]);
+        press(&mut viewer, "s");
+        assert_eq!(viewer.order, [0, 1, 2]);
+        assert_eq!(viewer.selected().unwrap()[0], "a");
+    }
+
+    #[test]
+    fn test_search_moves_to_matching_rows() {
+        let mut viewer = viewer();
+        press(&mut viewer, "/10\n");
+        assert_eq!(viewer.selected().unwrap()[0], "b");
+        press(&mut viewer, "n");
+        assert_eq!(viewer.selected().unwrap()[0], "c");
+        press(&mut viewer, "/zz\n");
+        assert_eq!(viewer.status.as_deref(), Some("No match for 'zz'"));
+        assert_eq!(match_range("Größe", "RÖß"), Some((1, 6)));
+    }
+
+    #[test]
+    fn test_row_formats() {
+        let headers = vec!["name".to_string(), "note".to_string()];
+        let row = vec!["a\tb".to_string(), "say \"hi\"".to_string()];
+        assert_eq!(RowFormat::Tsv.format(&headers, &row), "a b\tsay \"hi\"");
+        assert_eq!(
+            RowFormat::Json.format(&headers, &row),
+            r#"{"name":"a\tb","note":"say \"hi\""}"#
+        );
+    }
+
+    #[test]
+    fn test_columns_scroll_to_the_selected_one() {
+        let mut viewer = viewer();
+        viewer.widths = vec![30, 30];
+        assert_eq!(viewer.visible_columns(40), [0]);
+        press(&mut viewer, ">");
+        assert_eq!(viewer.visible_columns(40), [1]);
+        assert_eq!(viewer.visible_columns(80), [1]);
+    }
+}
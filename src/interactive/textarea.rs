@@ -0,0 +1,468 @@
+//! Multi-line text area: `termgfx write` and the form's `textarea` fields
+//!
+//! Lines wrap at word boundaries to the terminal width. Enter starts a new
+//! line, Ctrl+D submits, Esc or Ctrl+C cancels, and Ctrl+E hands the text
+//! to `$VISUAL` / `$EDITOR` and picks up whatever was saved.
+
+use super::prompt;
+use super::screen::{self, Screen};
+use crate::output::color;
+use crate::text;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetForegroundColor, Stylize},
+    terminal::{self, Clear, ClearType},
+};
+use std::io::{self, Write};
+use std::process::Command;
+
+/// Columns taken by the gutter drawn left of the text
+const GUTTER: usize = 2;
+
+const HELP: &str = "Ctrl+D submit • Ctrl+E $EDITOR • Esc cancel";
+
+/// What a key asks of whoever runs the area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Edit,
+    Submit,
+    Cancel,
+    OpenEditor,
+}
+
+/// Text being edited, with the cursor and how far it has scrolled
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<Vec<char>>,
+    row: usize,
+    /// Cursor position in the line, in characters
+    col: usize,
+    /// First visual row on screen
+    scroll: usize,
+    /// Visual rows shown at once
+    pub rows: usize,
+    pub placeholder: Option<String>,
+}
+
+impl TextArea {
+    /// An area holding `text`, cursor at its end
+    pub fn new(text: &str, rows: usize) -> Self {
+        let mut area = Self {
+            lines: Vec::new(),
+            row: 0,
+            col: 0,
+            scroll: 0,
+            rows: rows.max(1),
+            placeholder: None,
+        };
+        area.set_text(text);
+        area
+    }
+
+    pub fn placeholder(mut self, placeholder: Option<String>) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.lines = text
+            .split('\n')
+            .map(|line| line.chars().collect())
+            .collect();
+        self.row = self.lines.len() - 1;
+        self.col = self.lines[self.row].len();
+    }
+
+    /// Apply a key press
+    pub fn handle_key(&mut self, key: KeyEvent, width: usize) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('d') if ctrl => return Action::Submit,
+            KeyCode::Char('c') if ctrl => return Action::Cancel,
+            KeyCode::Char('e') if ctrl => return Action::OpenEditor,
+            KeyCode::Esc => return Action::Cancel,
+            KeyCode::Char(c) if !ctrl => self.insert(c),
+            KeyCode::Tab => self.insert('\t'),
+            KeyCode::Enter => self.newline(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.left(),
+            KeyCode::Right => self.right(),
+            KeyCode::Up => self.vertical(width, -1),
+            KeyCode::Down => self.vertical(width, 1),
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.lines[self.row].len(),
+            _ => {}
+        }
+        Action::Edit
+    }
+
+    fn insert(&mut self, c: char) {
+        self.lines[self.row].insert(self.col, c);
+        self.col += 1;
+    }
+
+    fn newline(&mut self) {
+        let rest = self.lines[self.row].split_off(self.col);
+        self.row += 1;
+        self.lines.insert(self.row, rest);
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            self.lines[self.row].remove(self.col);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+            self.lines[self.row].extend(line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.lines[self.row].remove(self.col);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].extend(next);
+        }
+    }
+
+    fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+        }
+    }
+
+    fn right(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    /// Move a visual row up or down, keeping the column where it can
+    fn vertical(&mut self, width: usize, step: isize) {
+        let layout = self.layout(width);
+        let (vrow, vcol) = self.cursor_at(&layout);
+        let Some(target) = vrow.checked_add_signed(step).filter(|&r| r < layout.len()) else {
+            return;
+        };
+        let (row, start, end) = layout[target];
+        let last = target + 1 == layout.len() || layout[target + 1].0 != row;
+        // Wrapped rows end where the next one starts, so stop short of that
+        let room = if last {
+            end
+        } else {
+            end.saturating_sub(1).max(start)
+        };
+        let mut col = start;
+        let mut used = 0;
+        while col < room {
+            let w = char_width(self.lines[row][col]);
+            if used + w > vcol {
+                break;
+            }
+            used += w;
+            col += 1;
+        }
+        self.row = row;
+        self.col = col;
+    }
+
+    /// Visual rows at `width` columns as (line, first char, end char);
+    /// lines break after the last space that fits, or mid-word when none does
+    fn layout(&self, width: usize) -> Vec<(usize, usize, usize)> {
+        let width = width.max(1);
+        let mut rows = Vec::new();
+        for (row, line) in self.lines.iter().enumerate() {
+            let mut start = 0;
+            loop {
+                let mut end = start;
+                let mut used = 0;
+                while end < line.len() && used + char_width(line[end]) <= width {
+                    used += char_width(line[end]);
+                    end += 1;
+                }
+                if end == line.len() {
+                    rows.push((row, start, end));
+                    break;
+                }
+                if let Some(space) = (start..end).rev().find(|&i| line[i] == ' ') {
+                    end = space + 1;
+                }
+                let end = end.max(start + 1);
+                rows.push((row, start, end));
+                start = end;
+            }
+        }
+        rows
+    }
+
+    /// The cursor's visual row and column within `layout`
+    fn cursor_at(&self, layout: &[(usize, usize, usize)]) -> (usize, usize) {
+        let vrow = layout
+            .iter()
+            .rposition(|&(row, start, _)| row == self.row && start <= self.col)
+            .unwrap_or(0);
+        let (_, start, _) = layout[vrow];
+        let vcol = self.lines[self.row][start..self.col]
+            .iter()
+            .copied()
+            .map(char_width)
+            .sum();
+        (vrow, vcol)
+    }
+
+    /// Draw `rows` rows from screen row `top`, scrolled so the cursor shows,
+    /// and leave the cursor on it
+    pub fn draw(&mut self, out: &mut impl Write, top: u16, width: usize) -> io::Result<()> {
+        let text_width = width.saturating_sub(GUTTER + 1).max(1);
+        let layout = self.layout(text_width);
+        let (vrow, vcol) = self.cursor_at(&layout);
+        if vrow < self.scroll {
+            self.scroll = vrow;
+        } else if vrow >= self.scroll + self.rows {
+            self.scroll = vrow + 1 - self.rows;
+        }
+
+        let empty = self.lines.len() == 1 && self.lines[0].is_empty();
+        for i in 0..self.rows {
+            let line = if empty && i == 0 {
+                self.placeholder
+                    .as_deref()
+                    .map(|p| text::truncate(p, text_width, "…").dark_grey().to_string())
+                    .unwrap_or_default()
+            } else if let Some(&(row, start, end)) = layout.get(self.scroll + i) {
+                let segment: String = self.lines[row][start..end].iter().collect();
+                segment.trim_end_matches(' ').replace('\t', " ")
+            } else {
+                String::new()
+            };
+            queue!(
+                out,
+                MoveTo(0, top + i as u16),
+                Clear(ClearType::UntilNewLine),
+                SetForegroundColor(Color::DarkGrey),
+                Print("│ "),
+                ResetColor,
+                Print(line)
+            )?;
+        }
+        queue!(
+            out,
+            MoveTo((GUTTER + vcol) as u16, top + (vrow - self.scroll) as u16),
+            Show
+        )?;
+        out.flush()
+    }
+}
+
+fn char_width(c: char) -> usize {
+    if c == '\t' {
+        1
+    } else {
+        text::display_width(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// Edit `area` below `header`, drawn from screen row `top`, until Ctrl+D.
+/// Raw mode must be on; cancelling returns an `Interrupted` error.
+pub fn edit(
+    out: &mut color::Stdout,
+    area: &mut TextArea,
+    top: u16,
+    header: &str,
+) -> io::Result<String> {
+    let header_rows = header.lines().count() as u16;
+    loop {
+        let width = screen::size().0 as usize;
+        queue!(out, Hide)?;
+        for (i, line) in header.lines().enumerate() {
+            queue!(
+                out,
+                MoveTo(0, top + i as u16),
+                Clear(ClearType::UntilNewLine),
+                SetForegroundColor(Color::Cyan),
+                Print(line.bold()),
+                ResetColor
+            )?;
+        }
+        queue!(
+            out,
+            MoveTo(0, top + header_rows + area.rows as u16),
+            Clear(ClearType::UntilNewLine),
+            SetForegroundColor(Color::DarkGrey),
+            Print(HELP),
+            ResetColor
+        )?;
+        area.draw(out, top + header_rows, width)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match area.handle_key(key, width.saturating_sub(GUTTER + 1)) {
+            Action::Edit => {}
+            Action::Submit => break Ok(area.text()),
+            Action::Cancel => {
+                break Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+            }
+            Action::OpenEditor => {
+                let edited = open_editor(&area.text());
+                execute!(out, Clear(ClearType::All))?;
+                let rows = area.rows;
+                *area = TextArea::new(&edited?, rows).placeholder(area.placeholder.take());
+            }
+        }
+    }
+}
+
+/// Hand `text` to `$VISUAL` or `$EDITOR` (vi when neither is set) in a
+/// temporary file and read back what was saved
+fn open_editor(text: &str) -> io::Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("termgfx-write-{}.txt", std::process::id()));
+    std::fs::write(&path, text)?;
+
+    terminal::disable_raw_mode()?;
+    // The editor gets the path as "$1" so it's never word-split
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(&path)
+        .status();
+    terminal::enable_raw_mode()?;
+
+    let saved = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    match status? {
+        s if s.success() => Ok(saved?.trim_end_matches('\n').to_string()),
+        s => Err(io::Error::other(format!("{} exited with {}", editor, s))),
+    }
+}
+
+/// `termgfx write`: edit below the prompt and print the text; without a TTY
+/// print `default` instead
+pub fn render(
+    header: Option<&str>,
+    placeholder: Option<&str>,
+    rows: usize,
+    value: Option<&str>,
+    default: Option<&str>,
+    no_tty_error: bool,
+) {
+    let result = match prompt::answer_without_tty("Write", default, no_tty_error) {
+        Ok(Some(answer)) => Ok(answer),
+        Ok(None) => run_write(header.unwrap_or(""), placeholder, rows, value.unwrap_or("")),
+        Err(e) => Err(io::Error::other(e)),
+    };
+    match result {
+        Ok(text) => println!("{}", text),
+        Err(e) if prompt::is_cancel(&e) => prompt::exit_cancelled(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_write(
+    header: &str,
+    placeholder: Option<&str>,
+    rows: usize,
+    value: &str,
+) -> io::Result<String> {
+    let mut stdout = color::stdout();
+    let mut area = TextArea::new(value, rows).placeholder(placeholder.map(String::from));
+    let header_rows = header.lines().count();
+
+    terminal::enable_raw_mode()?;
+    let screen = Screen::enter(&mut stdout, Some(header_rows + area.rows + 1))?;
+    let result = edit(&mut stdout, &mut area, screen.top(), header);
+    screen.leave(&mut stdout)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn typed(area: &mut TextArea, keys: &str) {
+        for c in keys.chars() {
+            let code = if c == '\n' {
+                KeyCode::Enter
+            } else {
+                KeyCode::Char(c)
+            };
+            area.handle_key(key(code), 80);
+        }
+    }
+
+    #[test]
+    fn test_typing_lines_and_backspace_joins() {
+        let mut area = TextArea::new("", 3);
+        typed(&mut area, "hello\nworld");
+        assert_eq!(area.text(), "hello\nworld");
+        area.handle_key(key(KeyCode::Home), 80);
+        area.handle_key(key(KeyCode::Backspace), 80);
+        assert_eq!(area.text(), "helloworld");
+        assert_eq!((area.row, area.col), (0, 5));
+    }
+
+    #[test]
+    fn test_control_keys() {
+        let mut area = TextArea::new("draft", 3);
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert_eq!(area.handle_key(ctrl('d'), 80), Action::Submit);
+        assert_eq!(area.handle_key(ctrl('e'), 80), Action::OpenEditor);
+        assert_eq!(area.handle_key(key(KeyCode::Esc), 80), Action::Cancel);
+        assert_eq!(area.text(), "draft");
+    }
+
+    #[test]
+    fn test_layout_wraps_at_words() {
+        let area = TextArea::new("the quick brown fox\nabcdefghij", 3);
+        assert_eq!(area.layout(10), vec![(0, 0, 10), (0, 10, 19), (1, 0, 10)]);
+        assert_eq!(area.layout(4)[..3], [(0, 0, 4), (0, 4, 8), (0, 8, 10)]);
+    }
+
+    #[test]
+    fn test_up_and_down_follow_wrapped_rows() {
+        let mut area = TextArea::new("the quick brown fox", 3);
+        area.col = 2;
+        area.vertical(10, 1);
+        assert_eq!((area.row, area.col), (0, 12));
+        area.vertical(10, -1);
+        assert_eq!((area.row, area.col), (0, 2));
+        area.vertical(10, -1);
+        assert_eq!((area.row, area.col), (0, 2));
+    }
+}
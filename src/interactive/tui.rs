@@ -1,3 +1,4 @@
+use crate::output::color;
 use crossterm::{
     event::{self, Event, KeyCode, KeyModifiers},
     execute,
@@ -71,7 +72,7 @@ impl TuiApp {
 
         // Setup terminal
         enable_raw_mode()?;
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
         execute!(stdout, EnterAlternateScreen)?;
 
         // Setup Ctrl+C handler
@@ -123,7 +124,7 @@ impl TuiApp {
     }
 
     fn render(&self) -> io::Result<()> {
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
 
         // Clear screen
         execute!(
@@ -170,7 +171,7 @@ impl TuiApp {
         width: u16,
         height: u16,
     ) -> io::Result<()> {
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
 
         // Draw border
         self.draw_border(x, y, width, height)?;
@@ -212,7 +213,7 @@ impl TuiApp {
     }
 
     fn draw_border(&self, x: u16, y: u16, width: u16, height: u16) -> io::Result<()> {
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
 
         // Guard against too-small dimensions
         if width < 3 || height < 3 {
@@ -245,7 +246,7 @@ impl TuiApp {
     }
 
     fn render_gauge(&self, value: f64, x: u16, y: u16, width: u16) -> io::Result<()> {
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
         let percentage = value.clamp(0.0, 100.0);
         let filled = ((width as f64) * percentage / 100.0) as usize;
 
@@ -262,7 +263,7 @@ impl TuiApp {
     }
 
     fn render_sparkline(&self, values: &[f64], x: u16, y: u16, width: u16) -> io::Result<()> {
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
 
         if values.is_empty() {
             return Ok(());
@@ -298,7 +299,7 @@ impl TuiApp {
     }
 
     fn cleanup(&self) -> io::Result<()> {
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
         execute!(stdout, LeaveAlternateScreen)?;
         disable_raw_mode()?;
         Ok(())
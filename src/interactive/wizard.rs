@@ -1,15 +1,21 @@
+use super::input::Validator;
+use super::output_format::{self, OutputFormat};
+use crate::output::color;
+use crate::render::progress::ProgressState;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
+    cursor::{Hide, MoveTo, MoveToNextLine, RestorePosition, SavePosition, Show},
     event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     style::{Color, Print, ResetColor, SetForegroundColor, Stylize},
     terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::{
-    fs,
     io::{self, IsTerminal, Write},
+    thread,
+    time::Duration,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,8 +41,36 @@ pub struct WizardStep {
     pub placeholder: Option<String>,
     #[serde(default)]
     pub password: bool,
+    /// Regex an input answer has to match as a whole
     #[serde(default)]
     pub validate: Option<String>,
+    #[serde(default)]
+    pub next: Option<Next>,
+}
+
+/// Where a step goes instead of the one after it: always to one step id,
+/// or to a step id per answer (answers not listed go on as usual)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Next {
+    Step(String),
+    ByAnswer(HashMap<String, String>),
+}
+
+impl Next {
+    fn target(&self, answer: &str) -> Option<&str> {
+        match self {
+            Next::Step(id) => Some(id),
+            Next::ByAnswer(targets) => targets.get(answer).map(String::as_str),
+        }
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        match self {
+            Next::Step(id) => vec![id.as_str()],
+            Next::ByAnswer(targets) => targets.values().map(String::as_str).collect(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -49,7 +83,11 @@ pub struct Wizard {
     title: Option<String>,
     steps: Vec<WizardStep>,
     current_step: usize,
+    /// Steps answered on the way to the current one, for going back
+    history: Vec<usize>,
     values: HashMap<String, String>,
+    validators: Vec<Option<Validator>>,
+    progress: ProgressState,
     can_go_back: bool,
 }
 
@@ -59,13 +97,17 @@ impl Wizard {
             title,
             steps,
             current_step: 0,
+            history: Vec::new(),
             values: HashMap::new(),
+            validators: Vec::new(),
+            progress: ProgressState::new(0, "blocks"),
             can_go_back: true,
         }
     }
 
+    /// Load the config from a JSON file, or from stdin for `-`
     pub fn from_config_file(path: &str) -> io::Result<Self> {
-        let content = fs::read_to_string(path)?;
+        let content = crate::output::input::read_text(path)?;
         let config: WizardConfig = serde_json::from_str(&content)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
         Ok(Self::new(config.title, config.steps))
@@ -114,18 +156,20 @@ impl Wizard {
             placeholder: None,
             password: false,
             validate: None,
+            next: None,
         })
     }
 
-    pub fn run(&mut self, output_format: &str) -> io::Result<String> {
-        // Check for interactive terminal
-        if !std::io::stdin().is_terminal() {
+    pub fn run(&mut self, output_format: OutputFormat) -> io::Result<String> {
+        self.check_rules()?;
+        // Keys come from the terminal when stdin carries the config
+        if !std::io::stdin().is_terminal() && std::fs::File::open("/dev/tty").is_err() {
             return Err(io::Error::other(
                 "Wizard requires an interactive terminal (TTY)",
             ));
         }
 
-        let mut stdout = io::stdout();
+        let mut stdout = color::stdout();
         terminal::enable_raw_mode()?;
         execute!(stdout, EnterAlternateScreen, Hide)?;
 
@@ -135,12 +179,61 @@ impl Wizard {
         terminal::disable_raw_mode()?;
 
         match result {
-            Ok(_) => self.format_output(output_format),
+            Ok(_) => Ok(output_format.format(&self.answers())),
             Err(e) => Err(e),
         }
     }
 
-    fn run_wizard(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+    /// Compile each step's `validate` regex and make sure every `next`
+    /// names a step
+    fn check_rules(&mut self) -> io::Result<()> {
+        let invalid = |id: &str, e: String| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("Step '{}': {}", id, e))
+        };
+        self.validators = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            let validator = match &step.validate {
+                Some(pattern) => Regex::new(&format!("^(?:{})$", pattern))
+                    .map(|regex| Some(Validator::Regex(regex)))
+                    .map_err(|e| invalid(&step.id, format!("invalid validate regex: {}", e)))?,
+                None => None,
+            };
+            self.validators.push(validator);
+            for target in step.next.iter().flat_map(Next::targets) {
+                if !self.steps.iter().any(|s| s.id == target) {
+                    return Err(invalid(
+                        &step.id,
+                        format!("'next' names unknown step '{}'", target),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn can_back(&self) -> bool {
+        self.can_go_back && !self.history.is_empty()
+    }
+
+    fn back(&mut self) {
+        if !self.can_go_back {
+            return;
+        }
+        if let Some(previous) = self.history.pop() {
+            self.current_step = previous;
+        }
+    }
+
+    /// The step after `step` once it's answered with `answer`
+    fn next_step(&self, step: &WizardStep, answer: &str) -> usize {
+        step.next
+            .as_ref()
+            .and_then(|next| next.target(answer))
+            .and_then(|id| self.steps.iter().position(|s| s.id == id))
+            .unwrap_or(self.current_step + 1)
+    }
+
+    fn run_wizard(&mut self, stdout: &mut color::Stdout) -> io::Result<()> {
         while self.current_step < self.steps.len() {
             let step = self.steps[self.current_step].clone();
 
@@ -152,27 +245,21 @@ impl Wizard {
                 StepType::Summary => {
                     self.render_summary(stdout)?;
                     // Wait for Enter to continue or Esc to go back
-                    if !self.wait_for_confirmation(stdout)? {
-                        if self.can_go_back && self.current_step > 0 {
-                            self.current_step -= 1;
-                            continue;
-                        }
-                    } else {
+                    if self.wait_for_confirmation(stdout)? {
                         break;
                     }
+                    self.back();
                 }
                 _ => {
                     match self.handle_step_input(stdout, &step) {
                         Ok(Some(value)) => {
+                            let next = self.next_step(&step, &value);
                             self.values.insert(step.id.clone(), value);
-                            self.current_step += 1;
-                        }
-                        Ok(None) => {
-                            // User pressed Back
-                            if self.can_go_back && self.current_step > 0 {
-                                self.current_step -= 1;
-                            }
+                            self.history.push(self.current_step);
+                            self.current_step = next;
                         }
+                        // User pressed Back
+                        Ok(None) => self.back(),
                         Err(e) => return Err(e),
                     }
                 }
@@ -182,7 +269,7 @@ impl Wizard {
         Ok(())
     }
 
-    fn render_step_header(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+    fn render_step_header(&mut self, stdout: &mut color::Stdout) -> io::Result<()> {
         execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
 
         // Title
@@ -202,23 +289,35 @@ impl Wizard {
             )?;
         }
 
-        // Progress indicator
-        let progress_text = format!("Step {}/{}", self.current_step + 1, self.steps.len());
-        execute!(
-            stdout,
-            SetForegroundColor(Color::DarkGrey),
-            Print(progress_text),
-            Print("\n\n"),
-            ResetColor
-        )?;
-
+        // Progress: the steps taken so far, plus the ones left if the
+        // wizard goes straight on from here
+        let step = self.history.len() + 1;
+        let total = self.history.len() + self.steps.len() - self.current_step;
+        self.progress
+            .set_target(((step - 1) * 100 / total.max(1)) as u8);
+        loop {
+            execute!(
+                stdout,
+                MoveTo(0, 2),
+                Print(self.progress.doc().to_ansi()),
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!("  Step {}/{}", step, total)),
+                ResetColor
+            )?;
+            stdout.flush()?;
+            if !self.progress.tick() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        execute!(stdout, MoveTo(0, 4))?;
         stdout.flush()?;
         Ok(())
     }
 
     fn handle_step_input(
         &self,
-        stdout: &mut io::Stdout,
+        stdout: &mut color::Stdout,
         step: &WizardStep,
     ) -> io::Result<Option<String>> {
         match &step.step_type {
@@ -227,6 +326,7 @@ impl Wizard {
                 &step.prompt,
                 step.password,
                 step.placeholder.as_deref(),
+                self.validators[self.current_step].as_ref(),
             ),
             StepType::Select => {
                 if step.options.is_empty() {
@@ -254,12 +354,14 @@ impl Wizard {
         }
     }
 
+    #[allow(clippy::collapsible_match)]
     fn input_step(
         &self,
-        stdout: &mut io::Stdout,
+        stdout: &mut color::Stdout,
         prompt: &str,
         password: bool,
         placeholder: Option<&str>,
+        validator: Option<&Validator>,
     ) -> io::Result<Option<String>> {
         execute!(
             stdout,
@@ -286,19 +388,46 @@ impl Wizard {
         stdout.flush()?;
 
         let mut input = String::new();
+        let mut error_shown = false;
 
         loop {
             if let Event::Key(KeyEvent {
                 code, modifiers, ..
             }) = event::read()?
             {
+                if error_shown && code != KeyCode::Enter {
+                    // The answer is being fixed; drop the last error
+                    execute!(
+                        stdout,
+                        SavePosition,
+                        MoveToNextLine(1),
+                        Clear(ClearType::CurrentLine),
+                        RestorePosition
+                    )?;
+                    error_shown = false;
+                }
                 match code {
                     KeyCode::Enter => {
+                        if let Some(Err(e)) = validator.map(|v| v.check(&input)) {
+                            execute!(
+                                stdout,
+                                SavePosition,
+                                MoveToNextLine(1),
+                                Clear(ClearType::CurrentLine),
+                                SetForegroundColor(Color::Red),
+                                Print(format!("✗ {}", e)),
+                                ResetColor,
+                                RestorePosition
+                            )?;
+                            error_shown = true;
+                            stdout.flush()?;
+                            continue;
+                        }
                         execute!(stdout, Print("\n\n"), Hide)?;
                         return Ok(Some(input));
                     }
                     KeyCode::Esc => {
-                        if self.can_go_back && self.current_step > 0 {
+                        if self.can_back() {
                             execute!(stdout, Print("\n\n"), Hide)?;
                             return Ok(None);
                         }
@@ -351,9 +480,10 @@ impl Wizard {
         }
     }
 
+    #[allow(clippy::collapsible_match)]
     fn select_step(
         &self,
-        stdout: &mut io::Stdout,
+        stdout: &mut color::Stdout,
         prompt: &str,
         options: &[String],
         multi: bool,
@@ -443,7 +573,7 @@ impl Wizard {
                         }
                     }
                     KeyCode::Esc => {
-                        if self.can_go_back && self.current_step > 0 {
+                        if self.can_back() {
                             return Ok(None);
                         }
                     }
@@ -453,7 +583,7 @@ impl Wizard {
         }
     }
 
-    fn confirm_step(&self, stdout: &mut io::Stdout, prompt: &str) -> io::Result<Option<bool>> {
+    fn confirm_step(&self, stdout: &mut color::Stdout, prompt: &str) -> io::Result<Option<bool>> {
         execute!(
             stdout,
             SetForegroundColor(Color::Cyan),
@@ -471,7 +601,7 @@ impl Wizard {
                     KeyCode::Char('n') | KeyCode::Char('N') => Some(false),
                     KeyCode::Enter => Some(true),
                     KeyCode::Esc => {
-                        if self.can_go_back && self.current_step > 0 {
+                        if self.can_back() {
                             execute!(stdout, Print("\n\n"), Hide)?;
                             return Ok(None);
                         } else {
@@ -496,7 +626,7 @@ impl Wizard {
         }
     }
 
-    fn render_summary(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+    fn render_summary(&self, stdout: &mut color::Stdout) -> io::Result<()> {
         execute!(
             stdout,
             SetForegroundColor(Color::Cyan),
@@ -504,11 +634,7 @@ impl Wizard {
             ResetColor
         )?;
 
-        for step in self.steps.iter() {
-            if matches!(step.step_type, StepType::Summary) {
-                continue;
-            }
-
+        for step in self.taken() {
             if let Some(value) = self.values.get(&step.id) {
                 execute!(
                     stdout,
@@ -540,13 +666,14 @@ impl Wizard {
         Ok(())
     }
 
-    fn wait_for_confirmation(&self, _stdout: &mut io::Stdout) -> io::Result<bool> {
+    #[allow(clippy::collapsible_match)]
+    fn wait_for_confirmation(&self, _stdout: &mut color::Stdout) -> io::Result<bool> {
         loop {
             if let Event::Key(KeyEvent { code, .. }) = event::read()? {
                 match code {
                     KeyCode::Enter => return Ok(true),
                     KeyCode::Esc => {
-                        if self.can_go_back && self.current_step > 0 {
+                        if self.can_back() {
                             return Ok(false);
                         }
                     }
@@ -556,22 +683,22 @@ impl Wizard {
         }
     }
 
-    fn format_output(&self, format: &str) -> io::Result<String> {
-        match format.to_lowercase().as_str() {
-            "json" => serde_json::to_string_pretty(&self.values)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-            "env" => {
-                let mut output = String::new();
-                for (key, value) in &self.values {
-                    output.push_str(&format!("{}={}\n", key.to_uppercase(), value));
-                }
-                Ok(output.trim_end().to_string())
-            }
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("Unknown output format: {}", format),
-            )),
-        }
+    /// The steps answered on the way here, once each, in the order they
+    /// were taken; steps skipped by a `next` jump are left out
+    fn taken(&self) -> Vec<&WizardStep> {
+        let mut seen = HashSet::new();
+        self.history
+            .iter()
+            .map(|&idx| &self.steps[idx])
+            .filter(|step| seen.insert(step.id.as_str()))
+            .collect()
+    }
+
+    fn answers(&self) -> Vec<(String, String)> {
+        self.taken()
+            .into_iter()
+            .filter_map(|step| Some((step.id.clone(), self.values.get(&step.id)?.clone())))
+            .collect()
     }
 }
 
@@ -580,7 +707,11 @@ pub fn render(
     config: Option<String>,
     title: Option<String>,
     output_format: String,
+    export_shell: bool,
+    output_file: Option<String>,
 ) -> io::Result<()> {
+    let format = OutputFormat::parse(&output_format, export_shell)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     let mut wizard = if let Some(config_path) = config {
         Wizard::from_config_file(&config_path)?
     } else {
@@ -591,11 +722,6 @@ pub fn render(
         Wizard::new(title, steps?)
     };
 
-    match wizard.run(&output_format) {
-        Ok(output) => {
-            println!("{}", output);
-            Ok(())
-        }
-        Err(e) => Err(e),
-    }
+    let output = wizard.run(format)?;
+    output_format::emit(&output, output_file.as_deref())
 }
@@ -3,20 +3,41 @@
 //! This library provides styled terminal output for CLI applications.
 //! It can be compiled to WebAssembly for browser-based demos.
 
+#[cfg(feature = "wasm")]
+use render::{Color, Doc, Style};
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+// Must come first: the print! family it defines shadows std's crate-wide
+#[cfg(feature = "cli")]
+#[macro_use]
+mod macros;
+
 // Re-export modules for CLI usage
 #[cfg(feature = "cli")]
+pub mod animation;
+#[cfg(feature = "cli")]
 pub mod charts;
 #[cfg(feature = "cli")]
+pub mod design;
+#[cfg(feature = "cli")]
 pub mod image;
 #[cfg(feature = "cli")]
 pub mod interactive;
 #[cfg(feature = "cli")]
 pub mod output;
+pub mod render;
 #[cfg(feature = "cli")]
 pub mod script;
+pub mod text;
+
+// The browser banner shares the CLI's built-in fonts
+#[cfg(all(feature = "wasm", not(feature = "cli")))]
+#[allow(dead_code)]
+#[path = "output/banner/fonts.rs"]
+mod banner_fonts;
+#[cfg(all(feature = "wasm", feature = "cli"))]
+use output::banner::fonts as banner_fonts;
 
 // ============================================================================
 // WASM Bindings - HTML Output for Browser
@@ -32,164 +53,42 @@ pub fn init() {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn render_box(message: &str, style: &str, border: &str) -> String {
-    let (color_class, emoji) = match style {
-        "success" => ("green", "✓"),
-        "warning" => ("yellow", "⚠"),
-        "danger" => ("red", "✗"),
-        "gradient" => ("magenta", "✨"),
-        _ => ("blue", "ℹ"),
-    };
-
-    let (tl, tr, bl, br, h, v) = match border {
-        "single" => ("┌", "┐", "└", "┘", "─", "│"),
-        "double" => ("╔", "╗", "╚", "╝", "═", "║"),
-        "thick" => ("┏", "┓", "┗", "┛", "━", "┃"),
-        _ => ("╭", "╮", "╰", "╯", "─", "│"),
-    };
-
-    let content = format!("{} {}", emoji, message);
-    let width = content.chars().count() + 4;
-    let h_line = h.repeat(width);
-
-    format!(
-        "<span class=\"{}\">{}{}{}</span>\n<span class=\"{}\">{}</span>  {}  <span class=\"{}\">{}</span>\n<span class=\"{}\">{}{}{}</span>",
-        color_class, tl, h_line, tr,
-        color_class, v, content, color_class, v,
-        color_class, bl, h_line, br
-    )
+    render::boxed::message_box(message, style, border).to_html()
 }
 
 /// Render an ASCII banner (HTML output)
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn render_banner(text: &str) -> String {
-    let chars: Vec<char> = text.chars().collect();
-    let mut lines = vec![String::new(); 4];
-
-    for ch in chars {
-        let pattern = get_ascii_char(ch);
-        for (i, line) in pattern.iter().enumerate() {
-            lines[i].push_str(line);
-            lines[i].push(' ');
-        }
-    }
-
-    let width = lines[0].len() + 2;
+    let rows = banner_fonts::Font::Builtin(banner_fonts::Builtin::Block).render(text);
+    let width = rows
+        .first()
+        .map(|row| text::display_width(row))
+        .unwrap_or(0)
+        + 2;
     let border = "═".repeat(width);
+    let cyan = Style::fg(Color::CYAN);
+    let magenta = Style::fg(Color::MAGENTA);
 
-    format!(
-        "<span class=\"cyan\">╔{}╗</span>\n<span class=\"cyan\">║</span> <span class=\"bright-white\">{}</span> <span class=\"magenta\">║</span>\n<span class=\"cyan\">║</span> <span class=\"bright-white\">{}</span> <span class=\"magenta\">║</span>\n<span class=\"cyan\">║</span> <span class=\"bright-white\">{}</span> <span class=\"magenta\">║</span>\n<span class=\"cyan\">║</span> <span class=\"bright-white\">{}</span> <span class=\"magenta\">║</span>\n<span class=\"magenta\">╚{}╝</span>",
-        border, lines[0], lines[1], lines[2], lines[3], border
-    )
-}
-
-fn get_ascii_char(ch: char) -> [&'static str; 4] {
-    match ch.to_ascii_uppercase() {
-        'A' => [" ██ ", "█  █", "████", "█  █"],
-        'B' => ["███ ", "█  █", "███ ", "████"],
-        'C' => [" ███", "█   ", "█   ", " ███"],
-        'D' => ["███ ", "█  █", "█  █", "███ "],
-        'E' => ["████", "███ ", "█   ", "████"],
-        'F' => ["████", "███ ", "█   ", "█   "],
-        'G' => [" ███", "█   ", "█ ██", " ███"],
-        'H' => ["█  █", "████", "█  █", "█  █"],
-        'I' => ["████", " ██ ", " ██ ", "████"],
-        'J' => ["████", "  █ ", "█ █ ", " █  "],
-        'K' => ["█ █ ", "██  ", "█ █ ", "█  █"],
-        'L' => ["█   ", "█   ", "█   ", "████"],
-        'M' => ["█  █", "████", "█  █", "█  █"],
-        'N' => ["█  █", "██ █", "█ ██", "█  █"],
-        'O' => [" ██ ", "█  █", "█  █", " ██ "],
-        'P' => ["███ ", "█  █", "███ ", "█   "],
-        'Q' => [" ██ ", "█  █", "█ █ ", " █ █"],
-        'R' => ["███ ", "█  █", "███ ", "█  █"],
-        'S' => [" ███", "██  ", "  ██", "███ "],
-        'T' => ["████", " ██ ", " ██ ", " ██ "],
-        'U' => ["█  █", "█  █", "█  █", " ██ "],
-        'V' => ["█  █", "█  █", " ██ ", " ██ "],
-        'W' => ["█  █", "█  █", "████", "█  █"],
-        'X' => ["█  █", " ██ ", " ██ ", "█  █"],
-        'Y' => ["█  █", " ██ ", " ██ ", " ██ "],
-        'Z' => ["████", "  █ ", " █  ", "████"],
-        '0' => [" ██ ", "█ ██", "██ █", " ██ "],
-        '1' => [" █  ", "██  ", " █  ", "███ "],
-        '2' => ["██  ", "  █ ", " █  ", "████"],
-        '3' => ["███ ", " ██ ", "  ██", "███ "],
-        '4' => ["█ █ ", "█ █ ", "████", "  █ "],
-        '5' => ["████", "██  ", "  ██", "██  "],
-        '6' => [" ██ ", "█   ", "███ ", " ██ "],
-        '7' => ["████", "  █ ", " █  ", "█   "],
-        '8' => [" ██ ", "████", "█  █", " ██ "],
-        '9' => [" ██ ", "███ ", "  █ ", " ██ "],
-        ' ' => ["    ", "    ", "    ", "    "],
-        _ => ["    ", "    ", "    ", "    "],
+    let mut doc = Doc::new();
+    doc.push(&format!("╔{}╗", border), cyan).newline();
+    for row in &rows {
+        doc.push("║", cyan)
+            .plain(" ")
+            .push(row, Style::fg(Color::BRIGHT_WHITE))
+            .plain(" ")
+            .push("║", magenta)
+            .newline();
     }
+    doc.push(&format!("╚{}╝", border), magenta);
+    doc.to_html()
 }
 
 /// Render a progress bar (HTML output)
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn render_progress(percent: u8, style: &str) -> String {
-    let width = 30;
-    let filled = (percent as usize * width) / 100;
-    let empty = width - filled;
-
-    match style {
-        "blocks" => {
-            let bar = "<span class=\"cyan\">█</span>".repeat(filled);
-            let empty_bar = "<span class=\"dim\">░</span>".repeat(empty);
-            format!(
-                "{}{} <span class=\"cyan bold\">{}%</span>",
-                bar, empty_bar, percent
-            )
-        }
-        "dots" => {
-            let bar = "<span class=\"cyan\">●</span>".repeat(filled);
-            let empty_bar = "<span class=\"dim\">○</span>".repeat(empty);
-            format!(
-                "{}{} <span class=\"cyan bold\">{}%</span>",
-                bar, empty_bar, percent
-            )
-        }
-        "animated" => {
-            let pattern = ['█', '▓', '▒', '░'];
-            let bar: String = (0..filled)
-                .map(|i| format!("<span class=\"cyan\">{}</span>", pattern[i % pattern.len()]))
-                .collect();
-            let empty_bar = "<span class=\"dim\"> </span>".repeat(empty);
-            format!(
-                "{}{} <span class=\"cyan bold\">{}%</span>",
-                bar, empty_bar, percent
-            )
-        }
-        _ => {
-            // Gradient: red -> yellow -> green
-            let bar: String = (0..filled)
-                .map(|i| {
-                    let pos = (i * 100) / width;
-                    if pos < 35 {
-                        "<span class=\"red\">█</span>"
-                    } else if pos < 70 {
-                        "<span class=\"yellow\">█</span>"
-                    } else {
-                        "<span class=\"green\">█</span>"
-                    }
-                })
-                .collect();
-            let empty_bar = "<span class=\"dim\">░</span>".repeat(empty);
-            let pct_class = if percent < 35 {
-                "red"
-            } else if percent < 70 {
-                "yellow"
-            } else {
-                "green"
-            };
-            format!(
-                "{}{} <span class=\"{} bold\">{}%</span>",
-                bar, empty_bar, pct_class, percent
-            )
-        }
-    }
+    render::progress::bar(percent, style).to_html()
 }
 
 /// Render a sparkline (plain text - no colors needed)
@@ -223,7 +122,9 @@ pub fn render_sparkline(data: &str) -> String {
         })
         .collect();
 
-    format!("<span class=\"cyan\">{}</span>", sparkline)
+    let mut doc = Doc::new();
+    doc.push(&sparkline, Style::fg(Color::CYAN));
+    doc.to_html()
 }
 
 /// Render a table (HTML output)
@@ -235,96 +136,7 @@ pub fn render_table(headers: &str, rows: &str, border: &str) -> String {
         .split('|')
         .map(|row| row.split(',').collect())
         .collect();
-
-    // Calculate column widths
-    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
-    for row in &rows {
-        for (i, cell) in row.iter().enumerate() {
-            if i < widths.len() {
-                let cell_width = cell.chars().count();
-                if cell_width > widths[i] {
-                    widths[i] = cell_width;
-                }
-            }
-        }
-    }
-
-    let (tl, tr, bl, br, h, v, cross, t_down, t_up, t_right, t_left) = match border {
-        "double" => ("╔", "╗", "╚", "╝", "═", "║", "╬", "╦", "╩", "╠", "╣"),
-        "heavy" => ("┏", "┓", "┗", "┛", "━", "┃", "╋", "┳", "┻", "┣", "┫"),
-        "single" => ("┌", "┐", "└", "┘", "─", "│", "┼", "┬", "┴", "├", "┤"),
-        _ => ("╭", "╮", "╰", "╯", "─", "│", "┼", "┬", "┴", "├", "┤"),
-    };
-
-    let mut output = String::new();
-
-    // Top border
-    output.push_str(&format!("<span class=\"dim\">{}</span>", tl));
-    for (i, w) in widths.iter().enumerate() {
-        output.push_str(&format!("<span class=\"dim\">{}</span>", h.repeat(w + 2)));
-        if i < widths.len() - 1 {
-            output.push_str(&format!("<span class=\"dim\">{}</span>", t_down));
-        }
-    }
-    output.push_str(&format!("<span class=\"dim\">{}</span>\n", tr));
-
-    // Headers
-    output.push_str(&format!("<span class=\"dim\">{}</span>", v));
-    for (i, header) in headers.iter().enumerate() {
-        let padding = widths[i] - header.chars().count();
-        output.push_str(&format!(
-            " <span class=\"cyan bold\">{}</span>{} ",
-            header,
-            " ".repeat(padding)
-        ));
-        output.push_str(&format!("<span class=\"dim\">{}</span>", v));
-    }
-    output.push('\n');
-
-    // Header separator
-    output.push_str(&format!("<span class=\"dim\">{}</span>", t_right));
-    for (i, w) in widths.iter().enumerate() {
-        output.push_str(&format!("<span class=\"dim\">{}</span>", h.repeat(w + 2)));
-        if i < widths.len() - 1 {
-            output.push_str(&format!("<span class=\"dim\">{}</span>", cross));
-        }
-    }
-    output.push_str(&format!("<span class=\"dim\">{}</span>\n", t_left));
-
-    // Rows
-    for (row_idx, row) in rows.iter().enumerate() {
-        output.push_str(&format!("<span class=\"dim\">{}</span>", v));
-        let row_class = if row_idx % 2 == 1 { "dim" } else { "" };
-        for (i, cell) in row.iter().enumerate() {
-            if i < widths.len() {
-                let padding = widths[i] - cell.chars().count();
-                if row_class.is_empty() {
-                    output.push_str(&format!(" {}{} ", cell, " ".repeat(padding)));
-                } else {
-                    output.push_str(&format!(
-                        " <span class=\"{}\">{}</span>{} ",
-                        row_class,
-                        cell,
-                        " ".repeat(padding)
-                    ));
-                }
-                output.push_str(&format!("<span class=\"dim\">{}</span>", v));
-            }
-        }
-        output.push('\n');
-    }
-
-    // Bottom border
-    output.push_str(&format!("<span class=\"dim\">{}</span>", bl));
-    for (i, w) in widths.iter().enumerate() {
-        output.push_str(&format!("<span class=\"dim\">{}</span>", h.repeat(w + 2)));
-        if i < widths.len() - 1 {
-            output.push_str(&format!("<span class=\"dim\">{}</span>", t_up));
-        }
-    }
-    output.push_str(&format!("<span class=\"dim\">{}</span>", br));
-
-    output
+    render::table::table(&headers, &rows, border).to_html()
 }
 
 /// Typewriter effect - returns array of partial strings for animation
@@ -344,29 +156,55 @@ pub fn typewriter_frames(message: &str) -> Vec<JsValue> {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn render_tree(json: &str) -> String {
+    let mut doc = Doc::new();
     match serde_json::from_str::<serde_json::Value>(json) {
-        Ok(value) => render_tree_value(&value, "", 0),
-        Err(_) => "<span class=\"red\">Error: Invalid JSON</span>".to_string(),
+        Ok(value) => render_tree_value(&mut doc, &value, &mut Vec::new()),
+        Err(_) => {
+            doc.push("Error: Invalid JSON", Style::fg(Color::RED));
+        }
     }
+    doc.to_html()
 }
 
+/// `ancestors` holds, per level above, whether that node was the last child
 #[cfg(feature = "wasm")]
-fn render_tree_value(value: &serde_json::Value, prefix: &str, depth: usize) -> String {
-    let colors = ["cyan", "blue", "green", "yellow", "magenta", "bright-blue"];
-    let color = colors[depth % colors.len()];
-
-    let mut output = String::new();
-
-    match value {
-        serde_json::Value::Object(map) => {
-            let entries: Vec<_> = map.iter().collect();
-            for (i, (key, val)) in entries.iter().enumerate() {
-                let is_last_item = i == entries.len() - 1;
-                let connector = if is_last_item {
-                    "└── "
-                } else {
-                    "├── "
-                };
+fn render_tree_value(doc: &mut Doc, value: &serde_json::Value, ancestors: &mut Vec<bool>) {
+    const COLORS: [Color; 6] = [
+        Color::CYAN,
+        Color::BLUE,
+        Color::GREEN,
+        Color::YELLOW,
+        Color::MAGENTA,
+        Color::Named(12),
+    ];
+    let color = Style::fg(COLORS[ancestors.len() % COLORS.len()]);
+
+    let children: Vec<(Option<&String>, &serde_json::Value)> = match value {
+        serde_json::Value::Object(map) => map.iter().map(|(k, v)| (Some(k), v)).collect(),
+        serde_json::Value::Array(arr) => arr.iter().map(|v| (None, v)).collect(),
+        _ => return,
+    };
+
+    for (i, (key, val)) in children.iter().enumerate() {
+        let is_last_item = i == children.len() - 1;
+        for &last in ancestors.iter() {
+            if last {
+                doc.plain("    ");
+            } else {
+                doc.push("│", Style::dimmed()).plain("   ");
+            }
+        }
+        doc.push(
+            if is_last_item {
+                "└── "
+            } else {
+                "├── "
+            },
+            color,
+        );
+
+        match key {
+            Some(key) => {
                 let icon = if val.is_object() {
                     "📁"
                 } else if val.is_array() {
@@ -374,61 +212,178 @@ fn render_tree_value(value: &serde_json::Value, prefix: &str, depth: usize) -> S
                 } else {
                     "📄"
                 };
-
-                output.push_str(&format!(
-                    "{}<span class=\"{}\">{}</span>{} <span class=\"bold\">{}</span>\n",
-                    prefix, color, connector, icon, key
-                ));
-
-                let new_prefix = format!(
-                    "{}{}",
-                    prefix,
-                    if is_last_item {
-                        "    "
-                    } else {
-                        "<span class=\"dim\">│</span>   "
-                    }
-                );
-                output.push_str(&render_tree_value(val, &new_prefix, depth + 1));
+                doc.plain(&format!("{} ", icon))
+                    .push(key, Style::PLAIN.bold());
             }
-        }
-        serde_json::Value::Array(arr) => {
-            for (i, val) in arr.iter().enumerate() {
-                let is_last_item = i == arr.len() - 1;
-                let connector = if is_last_item {
-                    "└── "
-                } else {
-                    "├── "
-                };
-
-                if val.is_string() {
-                    output.push_str(&format!(
-                        "{}<span class=\"{}\">{}</span><span class=\"green\">{}</span>\n",
-                        prefix,
-                        color,
-                        connector,
-                        val.as_str().unwrap()
-                    ));
-                } else {
-                    output.push_str(&format!(
-                        "{}<span class=\"{}\">{}</span>\n",
-                        prefix, color, connector
-                    ));
-                    let new_prefix = format!(
-                        "{}{}",
-                        prefix,
-                        if is_last_item {
-                            "    "
-                        } else {
-                            "<span class=\"dim\">│</span>   "
-                        }
-                    );
-                    output.push_str(&render_tree_value(val, &new_prefix, depth + 1));
+            None => {
+                if let Some(s) = val.as_str() {
+                    doc.push(s, Style::fg(Color::GREEN));
                 }
             }
         }
-        _ => {}
+        doc.newline();
+
+        if !val.is_string() || key.is_some() {
+            ancestors.push(is_last_item);
+            render_tree_value(doc, val, ancestors);
+            ancestors.pop();
+        }
+    }
+}
+
+/// Run a chart-like component on options from a JS object; errors come back
+/// as a red line, like `render_tree`'s
+#[cfg(feature = "wasm")]
+fn render_with(options: JsValue, component: fn(&render::Options) -> Result<Doc, String>) -> String {
+    serde_wasm_bindgen::from_value::<render::Options>(options)
+        .map_err(|e| e.to_string())
+        .and_then(|options| component(&options))
+        .unwrap_or_else(|e| Doc::error(&e))
+        .to_html()
+}
+
+/// Render a bar chart from `{ data: "Label:Value,..." }` (HTML output)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn render_bar_chart(options: JsValue) -> String {
+    render_with(options, render::chart::bar)
+}
+
+/// Render a line chart from `{ data: "1,4,2" }` (HTML output)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn render_line_chart(options: JsValue) -> String {
+    render_with(options, render::chart::line)
+}
+
+/// Render a pie chart from `{ data: "Label:Value,..." }` (HTML output)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn render_pie(options: JsValue) -> String {
+    render_with(options, render::chart::pie)
+}
+
+/// Render a gauge from `{ value, min, max, style }` (HTML output)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn render_gauge(options: JsValue) -> String {
+    render_with(options, render::gauge::gauge)
+}
+
+/// Render a heatmap from `{ data: "1,2;3,4", xLabels, yLabels }` (HTML output)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn render_heatmap(options: JsValue) -> String {
+    render_with(options, render::heatmap::heatmap)
+}
+
+/// Render a timeline from `{ data: "date:label,...", vertical }` (HTML output)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn render_timeline(options: JsValue) -> String {
+    render_with(options, render::timeline::timeline)
+}
+
+// ============================================================================
+// WASM Animation State - advanced by the page, one frame per tick()
+// ============================================================================
+
+/// A spinner for `requestAnimationFrame` loops; tick every `frame_ms()`
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct WasmSpinner {
+    state: render::spinner::SpinnerState,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl WasmSpinner {
+    #[wasm_bindgen(constructor)]
+    pub fn new(style: &str, message: &str) -> WasmSpinner {
+        WasmSpinner {
+            state: render::spinner::SpinnerState::new(style, message),
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.state.tick();
+    }
+
+    pub fn set_message(&mut self, message: &str) {
+        self.state.set_message(message);
     }
 
-    output
+    pub fn current_html(&self) -> String {
+        self.state.doc().to_html()
+    }
+
+    pub fn frame_ms(&self) -> u32 {
+        render::spinner::FRAME_MS
+    }
+}
+
+/// A progress bar filling up to its target; `tick()` is false once it's there
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct WasmProgress {
+    state: render::progress::ProgressState,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl WasmProgress {
+    #[wasm_bindgen(constructor)]
+    pub fn new(target: u8, style: &str) -> WasmProgress {
+        WasmProgress {
+            state: render::progress::ProgressState::new(target, style),
+        }
+    }
+
+    pub fn tick(&mut self) -> bool {
+        self.state.tick()
+    }
+
+    pub fn set_target(&mut self, target: u8) {
+        self.state.set_target(target);
+    }
+
+    pub fn percent(&self) -> u8 {
+        self.state.percent()
+    }
+
+    pub fn current_html(&self) -> String {
+        self.state.doc().to_html()
+    }
+}
+
+/// A bar chart whose bars grow in; takes the same options as
+/// `render_bar_chart`, and `tick()` is false once they're full length
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct WasmChartBuild {
+    state: Result<render::chart::BarBuildState, String>,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl WasmChartBuild {
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: JsValue) -> WasmChartBuild {
+        WasmChartBuild {
+            state: serde_wasm_bindgen::from_value::<render::Options>(options)
+                .map_err(|e| e.to_string())
+                .and_then(render::chart::BarBuildState::new),
+        }
+    }
+
+    pub fn tick(&mut self) -> bool {
+        self.state.as_mut().is_ok_and(|state| state.tick())
+    }
+
+    pub fn current_html(&self) -> String {
+        match &self.state {
+            Ok(state) => state.doc().to_html(),
+            Err(e) => Doc::error(e).to_html(),
+        }
+    }
 }
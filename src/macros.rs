@@ -0,0 +1,49 @@
+//! Crate-wide `print!` family that follows the color policy
+//!
+//! These shadow the std macros everywhere in the crate, so every command
+//! drops color sequences under `--color never` / `NO_COLOR` without each
+//! module checking. See `output::color`. `print!` and `println!` also time
+//! their formatting and writing for `--profile-render`.
+
+macro_rules! print {
+    ($($arg:tt)*) => {{
+        let text = $crate::output::profile::time($crate::output::profile::Phase::Render, || {
+            ::std::format!($($arg)*)
+        });
+        $crate::output::profile::time($crate::output::profile::Phase::Write, || {
+            ::std::print!("{}", $crate::output::color::apply(&text))
+        })
+    }};
+}
+
+macro_rules! println {
+    () => {
+        $crate::output::profile::time($crate::output::profile::Phase::Write, || {
+            ::std::println!()
+        })
+    };
+    ($($arg:tt)*) => {{
+        let text = $crate::output::profile::time($crate::output::profile::Phase::Render, || {
+            ::std::format!($($arg)*)
+        });
+        $crate::output::profile::time($crate::output::profile::Phase::Write, || {
+            ::std::println!("{}", $crate::output::color::apply(&text))
+        })
+    }};
+}
+
+#[allow(unused_macros)]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        ::std::eprint!("{}", $crate::output::color::apply(&::std::format!($($arg)*)))
+    };
+}
+
+macro_rules! eprintln {
+    () => {
+        ::std::eprintln!()
+    };
+    ($($arg:tt)*) => {
+        ::std::eprintln!("{}", $crate::output::color::apply(&::std::format!($($arg)*)))
+    };
+}
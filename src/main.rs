@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+
+#[macro_use]
+mod macros;
 
 mod animation;
 mod charts;
@@ -8,6 +11,13 @@ mod image;
 mod interactive;
 mod output;
 mod script;
+mod text;
+
+// Shared with the WASM build, which is the only user of some of it
+use termgfx::render;
+
+#[global_allocator]
+static ALLOCATOR: output::profile::CountingAlloc = output::profile::CountingAlloc;
 
 #[derive(Parser)]
 #[command(name = "termgfx")]
@@ -27,18 +37,47 @@ EXAMPLES:
   termgfx tree "root>src,docs>main.rs,lib.rs"
 
 QUICK REFERENCE:
-  Output:   box, banner, notification
-  Charts:   chart (bar/line/pie), sparkline, gauge, heatmap
-  Data:     table, tree, diff, timeline
-  Input:    input, select, confirm, file, filter, pager
+  Output:   box, banner, text, notification
+  Charts:   chart (bar/line/pie/boxplot/funnel/radar/ridgeline), sparkline, gauge, heatmap, calendar
+  Data:     table, tree, diff, timeline, md, code, log
+  Input:    input, select, confirm, file, filter, emojipick, pager, json, keys
   Animate:  spinner, progress, typewriter, animate
-  Utils:    image, record, script, dashboard, demo
+  Utils:    image, qr, barcode, record, script, present, dashboard, demo, repl, emoji-widths, footer, battery, temps
 
 For command details: termgfx <command> --help
 "#)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Print per-phase timings and allocation counts to stderr
+    #[arg(long, global = true, hide = true)]
+    profile_render: bool,
+    /// When to use colors: auto, always, never (auto honors NO_COLOR)
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: String,
+    /// Colors the terminal can show: auto, truecolor, 256, 16
+    ///
+    /// Gradients are quantized to fit; auto reads COLORTERM and TERM.
+    #[arg(long, global = true, value_name = "DEPTH", default_value = "auto")]
+    color_depth: String,
+    /// Reuse this command's output for a while, e.g. --cache 5m (static output only)
+    #[arg(long, global = true, value_name = "TTL")]
+    cache: Option<String>,
+    /// Save the output instead: .svg, .png or .html (box, banner, chart, table, gauge, heatmap)
+    #[arg(long, global = true, value_name = "PATH")]
+    export: Option<String>,
+    /// Theme for this run (preset or a theme from the theme file)
+    #[arg(long, global = true, value_name = "NAME")]
+    theme: Option<String>,
+    /// Trace detail: error, warn, info, debug, trace (needs the `tracing` feature)
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<String>,
+    /// Append traces to this file instead of stderr
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<String>,
+    /// Draw final frames only, ignoring --animate (also TERMGFX_NO_ANIMATION=1)
+    #[arg(long, global = true)]
+    no_animation: bool,
 }
 
 #[derive(Subcommand)]
@@ -50,14 +89,36 @@ enum Commands {
         after_help = "Styles: info, success, warning, danger, gradient\nBorders: single, double, rounded, thick, ascii\nPresets: corporate, playful, minimal, retro, neon, elegant"
     )]
     Box {
-        /// The message to display
-        message: String,
+        /// The message to display (omit or `-` to read stdin)
+        #[arg(conflicts_with = "file")]
+        message: Option<String>,
+        /// Read the message from a file
+        #[arg(short, long)]
+        file: Option<String>,
+        /// Read the message from stdin, keeping any ANSI colors it carries
+        #[arg(long, conflicts_with_all = ["message", "file"])]
+        stdin: bool,
+        /// Title embedded in the top border
+        #[arg(short, long)]
+        title: Option<String>,
+        /// Footer embedded in the bottom border
+        #[arg(long)]
+        footer: Option<String>,
+        /// Title and footer alignment: left, center, right
+        #[arg(long, default_value = "left")]
+        title_align: String,
+        /// Maximum box width; longer lines wrap (default: terminal width)
+        #[arg(short, long)]
+        width: Option<usize>,
+        /// Spaces between border and content
+        #[arg(short, long, default_value = "2")]
+        padding: usize,
         /// Style: info, success, warning, danger, gradient
         #[arg(short, long, default_value = "info")]
         style: String,
-        /// Border style: single, double, rounded, thick
-        #[arg(short, long, default_value = "rounded")]
-        border: String,
+        /// Border style: single, double, rounded, thick (default: theme's, else rounded)
+        #[arg(short, long)]
+        border: Option<String>,
         /// Style preset: corporate, playful, minimal, retro, neon, elegant
         #[arg(long)]
         preset: Option<String>,
@@ -70,6 +131,9 @@ enum Commands {
         /// Total animation duration in ms (default: 500)
         #[arg(long, default_value = "500")]
         animation_time: u64,
+        /// Output format: ansi, html, svg
+        #[arg(long, default_value = "ansi", conflicts_with = "animate")]
+        format: String,
         /// Show a demo of this command
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
@@ -86,9 +150,9 @@ enum Commands {
         /// Custom title (default: "⚠️  DANGER ZONE")
         #[arg(short, long)]
         title: Option<String>,
-        /// Border style: single, double, rounded, thick
-        #[arg(short, long, default_value = "double")]
-        border: String,
+        /// Border style: single, double, rounded, thick (default: double)
+        #[arg(short, long)]
+        border: Option<String>,
         /// Animate the box drawing
         #[arg(short, long)]
         animate: bool,
@@ -99,13 +163,27 @@ enum Commands {
     /// Display a styled banner with gradient colors
     ///
     /// Example: termgfx banner "Welcome" --gradient cyan-purple
-    #[command(after_help = "Gradients: cyan-purple, red-orange, green-cyan, pink-yellow")]
+    #[command(
+        after_help = "Gradients: cyan-purple, red-orange, green-cyan, pink-yellow, cyan-magenta, rainbow\nAlignment: left, center, right\nFonts: block, slant, small, 3d, or a FIGlet .flf file"
+    )]
     Banner {
         /// The title text
         title: String,
-        /// Gradient colors (e.g., "cyan-purple")
+        /// Gradient colors (e.g., "cyan-purple"; default: theme's)
         #[arg(short, long)]
         gradient: Option<String>,
+        /// Alignment relative to terminal width: left, center, right
+        #[arg(long, default_value = "left")]
+        align: String,
+        /// Maximum banner width in columns (text wraps to fit, large fonts shrink)
+        #[arg(long)]
+        max_width: Option<usize>,
+        /// Blank lines above and below the text inside the frame
+        #[arg(long, default_value = "1")]
+        padding: usize,
+        /// Large-letter font: block, slant, small, 3d, or a FIGlet .flf file
+        #[arg(long)]
+        font: Option<String>,
         /// Animate the banner drawing
         #[arg(short, long)]
         animate: bool,
@@ -116,6 +194,34 @@ enum Commands {
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
     },
+    /// Print text with gradient colors and styling
+    ///
+    /// Example: termgfx text "Hello, world" --gradient cyan-purple --bold
+    #[command(
+        after_help = "Gradients: cyan-purple, red-orange, green-cyan, pink-yellow, cyan-magenta, rainbow"
+    )]
+    Text {
+        /// The text to print
+        text: String,
+        /// Gradient colors (e.g., "cyan-purple")
+        #[arg(short, long)]
+        gradient: Option<String>,
+        /// Rainbow colors (same as --gradient rainbow)
+        #[arg(long, conflicts_with = "gradient")]
+        rainbow: bool,
+        /// Bold text
+        #[arg(short, long)]
+        bold: bool,
+        /// Italic text
+        #[arg(short, long)]
+        italic: bool,
+        /// Underlined text
+        #[arg(short, long)]
+        underline: bool,
+        /// Spaces between letters
+        #[arg(long, default_value = "0")]
+        spacing: usize,
+    },
     ///
     /// Example: termgfx spinner "Loading..." --style dots --duration 3
     #[command(after_help = "Styles: dots, line, arc, bouncing, clock, circle, bounce, moon")]
@@ -136,7 +242,7 @@ enum Commands {
     Progress {
         /// Progress percentage (0-100)
         percent: u8,
-        /// Style: gradient, modern, animated, blocks, classic, thin
+        /// Style: gradient, modern, animated, blocks, classic, thin, dots
         #[arg(short, long, default_value = "gradient")]
         style: String,
         /// Start color for gradient (hex: #3fb950 or name: red, green, blue, cyan, magenta, yellow)
@@ -151,22 +257,114 @@ enum Commands {
         /// Total animation duration in ms (default: 1000)
         #[arg(long, default_value = "1000")]
         duration: u64,
+        /// Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic
+        #[arg(long, default_value = "linear")]
+        easing: String,
+        /// Compact single line with no newline, for PS1 or tmux status bars
+        #[arg(long, conflicts_with = "animate")]
+        inline: bool,
+        /// Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)
+        #[arg(long, default_value = "plain", requires = "inline")]
+        shell: String,
+        /// Output format: ansi, html, svg
+        #[arg(long, default_value = "ansi", conflicts_with_all = ["animate", "inline"])]
+        format: String,
         /// Show a demo of this command
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
     },
     ///
     /// Example: termgfx chart bar --data "Sales:100,Costs:60,Profit:40"
-    #[command(after_help = "Types: bar, line, pie")]
+    #[command(after_help = "Types: bar, line, pie, boxplot, funnel, radar")]
     Chart {
         #[command(subcommand)]
         chart_type: ChartCommands,
     },
     /// Display an image in terminal
+    ///
+    /// Example: termgfx image shots/*.png --grid 3x2 --captions
     Image {
-        /// Path or URL to image
-        path: String,
-        /// Protocol: auto, kitty, sixel, halfblock
+        /// Paths or URLs to images, or a glob (`-` reads from stdin)
+        #[arg(required_unless_present = "stdin")]
+        paths: Vec<String>,
+        /// Read image bytes from stdin
+        #[arg(long, conflicts_with = "paths")]
+        stdin: bool,
+        /// Protocol: auto, kitty, sixel, iterm2, halfblock, quadblock, braille
+        #[arg(short, long, default_value = "auto")]
+        protocol: String,
+        /// Width in cells, or percent of the terminal (e.g. 40 or 50%); per thumbnail in a grid
+        #[arg(short, long)]
+        width: Option<String>,
+        /// Height in cells, or percent of the terminal (e.g. 12 or 30%); per thumbnail in a grid
+        #[arg(long)]
+        height: Option<String>,
+        /// Horizontal alignment: left, center, right
+        #[arg(long, default_value = "left")]
+        align: String,
+        /// With both --width and --height, fit inside them instead of stretching
+        #[arg(long)]
+        preserve_aspect: bool,
+        /// Play an animated GIF/APNG/WebP this many times (0 = until Ctrl+C)
+        #[arg(long = "loop", value_name = "N")]
+        loop_count: Option<u32>,
+        /// Playback speed in frames per second, overriding the file's delays
+        #[arg(long)]
+        fps: Option<f64>,
+        /// Sixel palette: adaptive (median cut), web, gray
+        #[arg(long, default_value = "adaptive")]
+        palette: String,
+        /// Dither when reducing colors (sixel, Braille, or block modes on 256/16 color terminals)
+        #[arg(long)]
+        dither: bool,
+        /// Lay several images out as COLSxROWS thumbnails, e.g. 3x2 (or just 3 columns)
+        #[arg(long, value_name = "COLSxROWS")]
+        grid: Option<String>,
+        /// Cells between grid thumbnails, and lines between rows
+        #[arg(long, default_value = "1")]
+        gap: u32,
+        /// Show each file name under its thumbnail
+        #[arg(long)]
+        captions: bool,
+    },
+    /// Show a barcode with its text underneath
+    ///
+    /// Example: termgfx barcode "5901234123457" --symbology ean13
+    Barcode {
+        /// Text or digits to encode (`-` reads stdin)
+        data: String,
+        /// Symbology: code128, ean13, ean8
+        #[arg(short = 'y', long, default_value = "code128")]
+        symbology: String,
+        /// Cells per module
+        #[arg(short, long, default_value = "1")]
+        size: u32,
+        /// Height of the bars in lines
+        #[arg(long, default_value = "4")]
+        height: u32,
+        /// Draw dark bars instead of light spaces, for light terminal backgrounds
+        #[arg(long)]
+        invert: bool,
+        /// Leave out the text under the bars
+        #[arg(long)]
+        no_text: bool,
+    },
+    /// Show a QR code for a URL or any text
+    ///
+    /// Example: termgfx qr "https://example.com" --error-correction H
+    Qr {
+        /// Text to encode (`-` reads stdin)
+        data: String,
+        /// Cells per module
+        #[arg(short, long, default_value = "1")]
+        size: u32,
+        /// Error correction level: L, M, Q, H (higher survives more damage but is bigger)
+        #[arg(short = 'e', long, default_value = "M")]
+        error_correction: String,
+        /// Draw dark modules instead of light ones, for light terminal backgrounds
+        #[arg(long)]
+        invert: bool,
+        /// Protocol: auto (kitty when available), kitty, halfblock
         #[arg(short, long, default_value = "auto")]
         protocol: String,
     },
@@ -180,8 +378,38 @@ enum Commands {
         /// Password mode (hide input)
         #[arg(long)]
         password: bool,
+        /// Input mask: # digit, A letter, * either; other characters are literals
+        #[arg(long, conflicts_with = "input_type")]
+        mask: Option<String>,
+        /// Structured value: ipv4, semver, duration, date
+        #[arg(long = "type")]
+        input_type: Option<String>,
+        /// Check the answer on Enter: regex:<pattern>, number, email, nonempty
+        #[arg(long, value_name = "RULE")]
+        validate: Option<String>,
+        /// Smallest number accepted by --validate number
+        #[arg(long, requires = "validate", allow_negative_numbers = true)]
+        min: Option<f64>,
+        /// Largest number accepted by --validate number
+        #[arg(long, requires = "validate", allow_negative_numbers = true)]
+        max: Option<f64>,
+        /// Most characters the answer may have; shows a counter
+        #[arg(long, value_name = "N")]
+        char_limit: Option<usize>,
+        /// Start with this text, ready to edit
+        #[arg(long)]
+        value: Option<String>,
+        /// Answer to give without a TTY (CI, pipes) instead of failing
+        #[arg(long)]
+        default: Option<String>,
+        /// Without a TTY and no --default, answer empty instead of failing
+        #[arg(long)]
+        no_tty_error: bool,
     },
     /// Select from a list of options
+    ///
+    /// Type to filter the list. Write an option as "label::description" to show a
+    /// description beside it; only the label is printed.
     Select {
         /// The prompt question
         prompt: String,
@@ -190,38 +418,93 @@ enum Commands {
         /// Enable multi-select
         #[arg(long)]
         multi: bool,
+        /// Mark an option as destructive: shown in red, needs confirming (repeatable)
+        #[arg(long, value_name = "OPTION")]
+        danger: Vec<String>,
+        /// Tick an option to start with (repeatable)
+        #[arg(long, value_name = "OPTION", requires = "multi")]
+        selected: Vec<String>,
+        /// Options shown at once; the list scrolls past this
+        #[arg(long, visible_alias = "limit")]
+        height: Option<usize>,
+        /// Draw below the prompt instead of taking over the screen, and clear it on exit
+        #[arg(long)]
+        inline: bool,
+        /// Option to pick (comma-separated with --multi) without a TTY (CI, pipes) instead of failing
+        #[arg(long)]
+        default: Option<String>,
+        /// Without a TTY and no --default, print nothing instead of failing
+        #[arg(long)]
+        no_tty_error: bool,
     },
     /// Yes/No confirmation prompt
     Confirm {
         /// The confirmation question
         prompt: String,
-        /// Default answer
-        #[arg(short, long, default_value = "yes")]
-        default: String,
+        /// Default answer (yes when unset); also the answer without a TTY
+        #[arg(short, long)]
+        default: Option<String>,
         /// Style: normal, danger
         #[arg(short = 'S', long, default_value = "normal")]
         style: String,
+        /// Require typing this exact phrase instead of y/n
+        #[arg(long, value_name = "PHRASE")]
+        require_typed: Option<String>,
+        /// Without a TTY and no --default, answer no instead of failing
+        #[arg(long)]
+        no_tty_error: bool,
+    },
+    /// Multi-line text prompt; prints what was written
+    ///
+    /// Example: termgfx write --header "Commit message" --placeholder "Describe the change"
+    #[command(after_help = "Keys: Enter new line, Ctrl+D submit, Ctrl+E open $EDITOR, Esc cancel")]
+    Write {
+        /// Text shown above the area
+        #[arg(long)]
+        header: Option<String>,
+        /// Dim text shown while the area is empty
+        #[arg(short = 'P', long)]
+        placeholder: Option<String>,
+        /// Rows of text visible at once
+        #[arg(long, default_value = "5")]
+        rows: usize,
+        /// Start with this text, ready to edit
+        #[arg(long)]
+        value: Option<String>,
+        /// Answer to give without a TTY (CI, pipes) instead of failing
+        #[arg(long)]
+        default: Option<String>,
+        /// Without a TTY and no --default, answer empty instead of failing
+        #[arg(long)]
+        no_tty_error: bool,
     },
     /// Display a sparkline mini-chart
     ///
     /// Example: termgfx sparkline "1,4,2,8,5,7,3,9,6" --animate
     Sparkline {
         /// Comma-separated values
-        data: String,
+        #[arg(required_unless_present = "source")]
+        data: Option<String>,
         /// Animate the sparkline building
         #[arg(short, long)]
         animate: bool,
         /// Total animation duration in ms (default: 500)
         #[arg(long, default_value = "500")]
         animation_time: u64,
+        /// Follow a system reading instead: cpu, mem, disk[:mount], net:<iface> (KB/s)
+        #[arg(long, conflicts_with = "data")]
+        source: Option<String>,
+        /// How often --source adds a reading
+        #[arg(long, default_value = "1s", requires = "source")]
+        interval: String,
         /// Show a demo of this command
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
     },
     Diff {
-        /// First file path
+        /// First file or directory path (`-` for stdin)
         file1: String,
-        /// Second file path
+        /// Second file or directory path (`-` for stdin)
         file2: String,
         /// Use unified diff format
         #[arg(long)]
@@ -229,6 +512,79 @@ enum Commands {
         /// Context lines for unified format
         #[arg(long)]
         context: Option<usize>,
+        /// Highlight the changed words within modified lines
+        #[arg(long)]
+        word_diff: bool,
+        /// Step through hunks, accept or reject each, then print a patch of
+        /// the accepted ones
+        #[arg(short, long, conflicts_with_all = ["unified", "word_diff"])]
+        interactive: bool,
+    },
+    /// Render a Markdown document
+    ///
+    /// Example: termgfx md README.md
+    #[command(
+        after_help = "Supports headings, bold/italic/strikethrough, inline code, code blocks,\nlists, task lists, block quotes, tables, links and horizontal rules"
+    )]
+    Md {
+        /// Markdown file path (`-` or omitted for stdin)
+        #[arg(default_value = "-")]
+        file: String,
+    },
+    /// Present a Markdown file as fullscreen slides split by `---`
+    ///
+    /// Example: termgfx present talk.md
+    #[command(
+        after_help = "A `# Title` becomes a banner; a ```termgfx code block is replaced by the output of\nthe commands in it, one per line (e.g. chart bar --data A:1,B:2)\nKeys: →/l/Space next, ←/h previous, Home/End first/last, q quit\nWhen stdout is not a terminal every slide is printed"
+    )]
+    Present {
+        /// Markdown file path (`-` for stdin)
+        file: String,
+        /// Slide to start on, counting from 1
+        #[arg(long, default_value = "1")]
+        start: usize,
+    },
+    /// Show a source file with syntax highlighting and line numbers
+    ///
+    /// Example: termgfx code src/main.rs --lines 10-40
+    #[command(
+        after_help = "Languages: rust, python, javascript/typescript, go, c/cpp/java, sh, sql, toml, yaml, json\nOutput taller than the terminal opens in the pager (j/k, PgUp/PgDn, g/G, q)"
+    )]
+    Code {
+        /// Source file path (`-` for stdin)
+        file: String,
+        /// Line range to show, e.g. 10-40, 10- or -40
+        #[arg(long)]
+        lines: Option<String>,
+        /// Language for highlighting (default: detected from the extension)
+        #[arg(long, visible_alias = "language")]
+        lang: Option<String>,
+        /// Print directly instead of opening the pager
+        #[arg(long)]
+        no_pager: bool,
+    },
+    /// View a log with level colors, filtering and follow mode
+    ///
+    /// Example: termgfx log app.log --follow --filter "db|cache"
+    #[command(
+        after_help = "Levels: ERROR/FATAL red, WARN yellow, INFO cyan, DEBUG/TRACE dim\nTimestamps are highlighted; --relative adds the gap since the previous entry\nOutput taller than the terminal opens in the pager unless --follow or --no-pager"
+    )]
+    Log {
+        /// Log file path (`-` or omitted for stdin)
+        #[arg(default_value = "-")]
+        file: String,
+        /// Keep printing new lines as they are appended (like tail -f)
+        #[arg(short, long)]
+        follow: bool,
+        /// Only show lines matching this regex
+        #[arg(long)]
+        filter: Option<String>,
+        /// Show the time since the previous timestamped line
+        #[arg(long)]
+        relative: bool,
+        /// Print directly instead of opening the pager
+        #[arg(long)]
+        no_pager: bool,
     },
     /// Display a formatted table from data
     ///
@@ -243,12 +599,52 @@ enum Commands {
         /// Row data (pipe-separated rows, comma-separated columns)
         #[arg(long)]
         rows: Option<String>,
-        /// CSV file path
+        /// CSV, TSV or JSON file path (`-` for stdin, which is read when no
+        /// other data is given)
         #[arg(short, long)]
         file: Option<String>,
-        /// Border style: single, double, rounded, none
-        #[arg(long, default_value = "single")]
-        border: String,
+        /// JSON file (`-` for stdin): an array of objects, one column per key
+        #[arg(long, value_name = "FILE", conflicts_with = "file")]
+        json: Option<String>,
+        /// Columns to show, in order (header names or 1-based indexes, comma-separated)
+        #[arg(long)]
+        columns: Option<String>,
+        /// Columns to leave out (header names or 1-based indexes, comma-separated)
+        #[arg(long)]
+        hide: Option<String>,
+        /// jq filter to reshape JSON input first (e.g. '.items[] | {name, count}')
+        #[arg(short, long)]
+        query: Option<String>,
+        /// Sort rows by this column (header name or 1-based index), optionally
+        /// followed by :desc or :asc
+        #[arg(long, value_name = "COLUMN[:desc]")]
+        sort: Option<String>,
+        /// Sort in descending order
+        #[arg(long, requires = "sort")]
+        desc: bool,
+        /// How to compare sort cells: auto, numeric, size, date, semver, string
+        #[arg(long, default_value = "auto", requires = "sort")]
+        sort_type: String,
+        /// Cut cells wider than this many columns, ending them with "..."
+        #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        max_col_width: Option<usize>,
+        /// Wrap cells wider than --max-col-width onto more lines instead
+        #[arg(long, requires = "max_col_width")]
+        wrap: bool,
+        /// Summary row, comma-separated like a row; {sum}, {avg}, {min}, {max}
+        /// and {count} are worked out from the column (e.g. "Total,{sum}")
+        #[arg(long)]
+        footer: Option<String>,
+        /// Browse the table: scroll, / to search, s to sort a column, Enter
+        /// prints the selected row
+        #[arg(short, long, conflicts_with_all = ["animate", "footer", "wrap"])]
+        interactive: bool,
+        /// How Enter prints the row in --interactive: tsv, json
+        #[arg(long, default_value = "tsv", requires = "interactive")]
+        row_format: String,
+        /// Border style: single, double, rounded, ascii, none (default: single)
+        #[arg(long)]
+        border: Option<String>,
         /// Column alignment: left, center, right
         #[arg(long, default_value = "left")]
         alignment: String,
@@ -258,18 +654,48 @@ enum Commands {
         /// Total animation duration in ms (default: 500)
         #[arg(long, default_value = "500")]
         animation_time: u64,
+        /// Output format: ansi, html, svg
+        #[arg(long, default_value = "ansi", conflicts_with = "animate")]
+        format: String,
         /// Show a demo of this command
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
     },
     ///
     /// Example: termgfx tree "root>src,docs>main.rs,lib.rs"
+    #[command(
+        after_help = "Diff mode: termgfx tree --diff old.json new.json [--only-changes]\n  + added (green)  - removed (red)  ~ changed (yellow)"
+    )]
     Tree {
         /// Tree data (e.g., "root>child1,child2>grandchild")
         data: Option<String>,
-        /// JSON file path
+        /// JSON file path (`-` for stdin)
         #[arg(short, long)]
         path: Option<String>,
+        /// Compare two JSON files as one merged tree (either may be `-`)
+        #[arg(long, num_args = 2, value_names = ["OLD", "NEW"], conflicts_with_all = ["data", "path"])]
+        diff: Option<Vec<String>>,
+        /// Show a directory on disk, skipping what .gitignore excludes
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["data", "path", "diff", "query"])]
+        dir: Option<String>,
+        /// With --dir, how many levels to descend
+        #[arg(short = 'L', long, requires = "dir")]
+        depth: Option<usize>,
+        /// With --dir, include hidden files
+        #[arg(long, requires = "dir")]
+        all: bool,
+        /// With --dir, also show files .gitignore excludes
+        #[arg(long, requires = "dir")]
+        no_ignore: bool,
+        /// With --dir, show file sizes
+        #[arg(long, requires = "dir")]
+        size: bool,
+        /// With --diff, fold identical subtrees and values into a summary line
+        #[arg(long, requires = "diff")]
+        only_changes: bool,
+        /// jq filter to reshape JSON input first (e.g. '.items[] | {name, count}')
+        #[arg(short, long, conflicts_with = "data")]
+        query: Option<String>,
         /// Animate tree nodes expanding
         #[arg(short, long)]
         animate: bool,
@@ -285,17 +711,22 @@ enum Commands {
 
     /// Run animation sequences from script files
     Script {
-        /// Script file path
+        /// Script file path (`-` for stdin)
         #[arg(short, long)]
         file: Option<String>,
-        /// Inline script commands (semicolon-separated)
+        /// Inline script commands (newline- or semicolon-separated)
         #[arg(short, long)]
         inline: Option<String>,
+        /// Re-run the script whenever the file changes, until Ctrl+C
+        #[arg(long, requires = "file")]
+        watch_file: bool,
     },
     /// Run animation effects
+    ///
+    /// Example: termgfx animate --type fireworks --duration 3 --density 0.8
     Animate {
-        /// Animation type: progress, typewriter, counter, chart-build, bars
-        #[arg(short = 't', long)]
+        /// Animation type: progress, typewriter, counter, chart-build, bars, matrix, confetti, fireworks
+        #[arg(short = 't', long, visible_alias = "type")]
         effect_type: String,
         /// Text content (for typewriter)
         #[arg(long)]
@@ -324,10 +755,24 @@ enum Commands {
         /// Suffix (for counter)
         #[arg(long)]
         suffix: Option<String>,
+        /// Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic
+        #[arg(long, default_value = "linear")]
+        easing: String,
+        /// How busy matrix, confetti and fireworks get, 0.0-1.0
+        #[arg(long, default_value = "0.5")]
+        density: f64,
+        /// Colors for matrix, confetti and fireworks: "red,#ffd700,cyan"
+        #[arg(long)]
+        colors: Option<String>,
     },
-    /// Run interactive demo showcase
+    /// Guided tour of termgfx: each step types a command and runs it
+    ///
+    /// Example: termgfx demo --section charts
+    #[command(
+        after_help = "Keys: ←/→ step, ↑/↓ section, 1-6 jump to section, c copy command, s open in Studio, r replay, q quit\nWhen output is piped, every step is played back in order"
+    )]
     Demo {
-        /// Demo section: boxes, charts, progress, animation, tui, all
+        /// Section to start at: boxes, charts, data, progress, animation, tui, all
         #[arg(short, long)]
         section: Option<String>,
     },
@@ -336,15 +781,22 @@ enum Commands {
     /// Example: termgfx timeline --events "Start,Middle,End" --style arrow
     #[command(after_help = "Styles: arrow, line, dots")]
     Timeline {
-        /// Events: "Start,Middle,End" or "2024-01:Start,2024-06:Middle,2024-12:End"
-        #[arg(short, long)]
-        events: String,
+        /// Events: "Start,Middle,End" or "2024-01:Start,2024-06:Middle,2024-12:End";
+        /// "2024-01..2024-03:Design" draws a bar on a date axis
+        #[arg(short, long, required_unless_present = "group")]
+        events: Option<String>,
+        /// A named lane of events as NAME=EVENTS (repeatable)
+        #[arg(short, long, value_name = "NAME=EVENTS")]
+        group: Vec<String>,
+        /// Mark today (or the given YYYY[-MM[-DD]] date) on the date axis
+        #[arg(long, num_args = 0..=1, default_missing_value = "today", conflicts_with = "vertical", value_name = "DATE")]
+        now: Option<String>,
         /// Style: arrow, line, dots
         #[arg(short, long, default_value = "arrow")]
         style: String,
-        /// Color: red, green, blue, yellow, magenta, cyan, white
+        /// Accent color: red, green, blue, yellow, magenta, cyan, white
         #[arg(long)]
-        color: Option<String>,
+        accent: Option<String>,
         /// Animate the timeline
         #[arg(short, long)]
         animate: bool,
@@ -371,6 +823,18 @@ enum Commands {
         /// Show only desktop notification
         #[arg(long)]
         desktop_only: bool,
+        /// Percent done (0-100), shown as a progress bar
+        #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+        progress: Option<u8>,
+        /// Replace the notification sent earlier with this id
+        #[arg(long)]
+        id: Option<String>,
+        /// How long the desktop notification stays up (e.g. 5s, 1m)
+        #[arg(long)]
+        timeout: Option<String>,
+        /// Button as LABEL:COMMAND, run when clicked (repeatable; waits for the click)
+        #[arg(long = "action", value_name = "LABEL:COMMAND")]
+        actions: Vec<String>,
     },
     /// Display a radial/dial gauge indicator
     ///
@@ -378,7 +842,8 @@ enum Commands {
     #[command(after_help = "Styles: semicircle, full, minimal")]
     Gauge {
         /// Value to display
-        value: f64,
+        #[arg(required_unless_present_any = ["source", "multi"])]
+        value: Option<f64>,
         /// Minimum value for the gauge range
         #[arg(long, default_value = "0")]
         min: f64,
@@ -391,16 +856,115 @@ enum Commands {
         /// Gauge style: semicircle, full, minimal
         #[arg(short, long, default_value = "semicircle")]
         style: String,
-        /// Color: red, green, blue, yellow, cyan, magenta, white, grey
+        /// Accent color: red, green, blue, yellow, cyan, magenta, white, grey
         #[arg(long)]
-        color: Option<String>,
+        accent: Option<String>,
         /// Animate the gauge from 0 to value
         #[arg(short, long)]
         animate: bool,
+        /// Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic
+        #[arg(long, default_value = "linear")]
+        easing: String,
+        /// Compact single line with no newline, for PS1 or tmux status bars
+        #[arg(long, conflicts_with = "animate")]
+        inline: bool,
+        /// Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)
+        #[arg(long, default_value = "plain", requires = "inline")]
+        shell: String,
+        /// Read the value from the system: cpu, mem, disk[:mount], net:<iface> (KB/s)
+        #[arg(long, conflicts_with = "value")]
+        source: Option<String>,
+        /// With --source, redraw with a new reading this often until Ctrl+C
+        #[arg(long, requires = "source", conflicts_with = "inline")]
+        interval: Option<String>,
+        /// Several gauges side by side: "CPU:75,MEM:60,DISK:90"
+        #[arg(long, conflicts_with_all = ["value", "source", "label", "inline", "animate"])]
+        multi: Option<String>,
+        /// Switch color as the value reaches each threshold: "60:yellow,85:red"
+        #[arg(long)]
+        thresholds: Option<String>,
         /// Show a demo of this command
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
     },
+    /// Big digital clock in a banner font, updated every second until Ctrl+C
+    ///
+    /// Prints the time once when stdout isn't a terminal.
+    ///
+    /// Example: termgfx clock --style block --format 24h --seconds --date
+    Clock {
+        /// Font: block, slant, small, 3d, or a FIGlet .flf file
+        #[arg(short, long, default_value = "block")]
+        style: String,
+        /// Hour format: 24h, 12h
+        #[arg(short, long, default_value = "24h")]
+        format: String,
+        /// Show seconds
+        #[arg(long)]
+        seconds: bool,
+        /// Show the date under the clock
+        #[arg(long)]
+        date: bool,
+        /// Timezone: local, UTC, an offset like +05:30, or a name like Europe/Paris
+        #[arg(long, default_value = "local")]
+        timezone: String,
+    },
+    /// Countdown timer with big digits and a progress ring; rings the bell when done
+    ///
+    /// Exits with status 1 when cancelled with Ctrl+C.
+    ///
+    /// Example: termgfx timer 5m --label "Deploy window" --style digital
+    Timer {
+        /// How long to count down: 90s, 5m, 1.5h
+        duration: String,
+        /// Caption shown under the clock
+        #[arg(short, long)]
+        label: Option<String>,
+        /// Style: digital, minimal
+        #[arg(short, long, default_value = "digital")]
+        style: String,
+        /// Also send a desktop notification when time is up
+        #[arg(long)]
+        notify: bool,
+    },
+    /// Stopwatch: space records a lap, enter stops, esc cancels
+    ///
+    /// Exits with status 1 when cancelled.
+    ///
+    /// Example: termgfx stopwatch --label "Build"
+    Stopwatch {
+        /// Caption shown under the clock
+        #[arg(short, long)]
+        label: Option<String>,
+        /// Style: digital, minimal
+        #[arg(short, long, default_value = "digital")]
+        style: String,
+    },
+    /// Battery level with charging icon, colored by charge left
+    ///
+    /// Example: termgfx battery --inline --shell tmux
+    Battery {
+        /// Compact single line with no newline, for PS1 or tmux status bars
+        #[arg(long)]
+        inline: bool,
+        /// Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)
+        #[arg(long, default_value = "plain", requires = "inline")]
+        shell: String,
+    },
+    /// Temperature gauges for CPU, GPU and other sensors
+    ///
+    /// Example: termgfx temps --sensor core
+    Temps {
+        /// Only show sensors whose label contains this text
+        #[arg(long)]
+        sensor: Option<String>,
+        /// Compact single line with no newline, for PS1 or tmux status bars
+        #[arg(long)]
+        inline: bool,
+        /// Escape colors for a prompt: plain, bash, zsh, tmux (with --inline)
+        #[arg(long, default_value = "plain", requires = "inline")]
+        shell: String,
+    },
     Dashboard {
         /// Layout: "2x2" or "3x1"
         #[arg(short, long, default_value = "2x2")]
@@ -408,7 +972,7 @@ enum Commands {
         /// Dashboard title
         #[arg(short, long)]
         title: Option<String>,
-        /// Panels: "box:Hello,progress:75,sparkline:1;2;3,gauge:50"
+        /// Panels: "box:Hello,progress:75,sparkline:1;2;3,gauge:50"; also chart, text, table:data.csv, tree:root>a,b, log:app.log, image:logo.png
         #[arg(short, long)]
         panels: Option<String>,
         /// Config file path (JSON)
@@ -417,6 +981,9 @@ enum Commands {
         /// Border style: single, double, rounded
         #[arg(long, default_value = "single")]
         border: String,
+        /// Stay open and refresh panels that have a `command` in the config, each at its own `interval`; q quits
+        #[arg(long)]
+        live: bool,
     },
     /// Display a 2D heatmap visualization
     ///
@@ -426,7 +993,7 @@ enum Commands {
         /// 2D data: "1,2,3;4,5,6;7,8,9" (semicolon separates rows)
         #[arg(short, long)]
         data: Option<String>,
-        /// CSV file path
+        /// CSV file path (`-` for stdin)
         #[arg(short, long)]
         file: Option<String>,
         /// X-axis labels (comma-separated)
@@ -441,13 +1008,49 @@ enum Commands {
         /// Color scheme: blue-red, green-red, viridis, magma
         #[arg(long, default_value = "blue-red")]
         colors: String,
+        /// Print each value inside its cell when it fits
+        #[arg(long)]
+        annotate: bool,
+        /// Color scale: linear or log (log needs positive values)
+        #[arg(long, default_value = "linear")]
+        scale: String,
+        /// Color for missing cells (blank, NA, null): grey, red, ..., or none
+        #[arg(long, default_value = "grey")]
+        null_color: String,
         /// Animate the heatmap rendering
         #[arg(short, long)]
         animate: bool,
     },
+    /// Display a calendar heatmap (GitHub-contribution style)
+    ///
+    /// Example: termgfx calendar --data "2024-01-05:3,2024-01-06:10,2024-01-09:1"
+    #[command(after_help = "Colors: blue-red, green-red, viridis, magma")]
+    Calendar {
+        /// Daily values: "YYYY-MM-DD:value,YYYY-MM-DD:value"
+        #[arg(short, long)]
+        data: Option<String>,
+        /// File with one "YYYY-MM-DD:value" entry per line (`-` for stdin)
+        #[arg(short, long)]
+        file: Option<String>,
+        /// First date to show (default: earliest date in data)
+        #[arg(long)]
+        from: Option<String>,
+        /// Last date to show (default: latest date in data)
+        #[arg(long)]
+        to: Option<String>,
+        /// Chart title
+        #[arg(short, long)]
+        title: Option<String>,
+        /// Color scheme: blue-red, green-red, viridis, magma
+        #[arg(long, default_value = "viridis")]
+        colors: String,
+    },
     /// Interactice file/directory picker
     ///
     /// Example: termgfx file --path /var --directory --ext rs,toml
+    #[command(
+        after_help = "Hidden files start hidden; press . (with an empty filter) to show them.\nExample: termgfx file --multi --preview\nExample: termgfx file --save --path ~/notes"
+    )]
     File {
         /// Initial path to start the picker
         #[arg(short, long)]
@@ -461,6 +1064,18 @@ enum Commands {
         /// Maximum height of the picker in terminal lines
         #[arg(long)]
         height: Option<usize>,
+        /// Mark several entries with space; prints one path per line
+        #[arg(short, long, conflicts_with = "save")]
+        multi: bool,
+        /// Start with hidden files shown
+        #[arg(long)]
+        all: bool,
+        /// Show the start of the highlighted text file on the right (images are always previewed)
+        #[arg(long)]
+        preview: bool,
+        /// Pick where to save: the filter line takes a new file name
+        #[arg(long, conflicts_with = "directory")]
+        save: bool,
     },
     /// Fuzzy filter items from stdin (like fzf/gum filter)
     ///
@@ -476,55 +1091,162 @@ enum Commands {
         /// Maximum height of the list
         #[arg(long)]
         height: Option<usize>,
+        /// Draw below the prompt instead of taking over the screen, and clear it on exit
+        #[arg(long)]
+        inline: bool,
+        /// Command run on the highlighted line, shown on the right; {} is the line
+        #[arg(long, value_name = "CMD")]
+        preview: Option<String>,
+        /// Field delimiter regex for --with-nth (default: whitespace)
+        #[arg(short, long, requires = "with_nth")]
+        delimiter: Option<String>,
+        /// Fields to show and match, e.g. 2, 2.., ..3, 1,-1; the whole line is printed
+        #[arg(long, value_name = "FIELDS")]
+        with_nth: Option<String>,
+    },
+    /// Pick an emoji or Nerd Font icon from a searchable grid
+    ///
+    /// Example: termgfx box "Shipped $(termgfx emojipick --query rocket)"
+    #[command(
+        after_help = "Keys: type to search, ←↑↓→ move, Tab/Shift+Tab category, Enter pick, Esc cancel\nCategories: all, smileys, people, nature, food, activity, travel, objects, symbols, flags, nerd"
+    )]
+    Emojipick {
+        /// Start with this search
+        #[arg(short, long)]
+        query: Option<String>,
+        /// Start in this category
+        #[arg(short, long, default_value = "all")]
+        category: String,
+        /// Print the :name: code instead of the glyph
+        #[arg(long)]
+        code: bool,
+        /// Print the matches instead of opening the picker
+        #[arg(long)]
+        list: bool,
     },
     /// Scrollable pager for viewing content (like less)
     ///
     /// Example: cat file.txt | termgfx pager --line-numbers
-    #[command(after_help = "Keys: ↑/↓ scroll, PgUp/PgDn page, g/G top/bottom, q quit")]
+    #[command(
+        after_help = "Keys: ↑/↓ scroll, ←/→ scroll sideways, PgUp/PgDn page, g/G top/bottom,\n      / regex search, n/N next/previous match, w toggle wrapping, q quit\nExample: termgfx pager app.log --follow"
+    )]
     Pager {
+        /// File to show (`-` or omitted for stdin)
+        #[arg(default_value = "-")]
+        file: String,
         /// Show line numbers
         #[arg(short, long)]
         line_numbers: bool,
         /// Title to display in header
         #[arg(short, long)]
         title: Option<String>,
+        /// Lines shown at once (10 with --inline, else the terminal's height)
+        #[arg(long)]
+        height: Option<usize>,
+        /// Draw below the prompt instead of taking over the screen, and clear it on exit
+        #[arg(long)]
+        inline: bool,
+        /// Wrap long lines instead of scrolling sideways (toggle with w)
+        #[arg(short, long)]
+        wrap: bool,
+        /// Keep reading as the input grows, staying at the end (like tail -f)
+        #[arg(short = 'F', long)]
+        follow: bool,
+        /// Render the input as Markdown first
+        #[arg(long, conflicts_with_all = ["language", "follow"])]
+        markdown: bool,
+        /// Syntax-highlight the input as this language (e.g. rust, python, json)
+        #[arg(long = "lang", visible_alias = "language")]
+        language: Option<String>,
+    },
+    /// Browse JSON as an interactive collapsible tree
+    ///
+    /// Example: curl -s https://api.github.com/repos/rust-lang/rust | termgfx json -
+    #[command(
+        after_help = "Keys: ↑/↓ move, Enter/Space toggle, ←/→ collapse/expand, e/E expand/collapse all,\n      / search, n/N next/previous match, y copy path (jq syntax), q quit\nWhen stdout is not a terminal the fully expanded tree is printed"
+    )]
+    Json {
+        /// JSON file path (`-` or omitted for stdin)
+        #[arg(default_value = "-")]
+        file: String,
+        /// jq filter to reshape JSON input first (e.g. '.items[] | {name, count}')
+        #[arg(short, long)]
+        query: Option<String>,
+    },
+    /// Echo pressed keys as key-cap boxes (like screenkey) for demo recordings
+    ///
+    /// Example: termgfx keys --echo --fade 1500
+    #[command(
+        after_help = "Keys: Ctrl+C to quit\nWithout --echo, key names are printed one per line"
+    )]
+    Keys {
+        /// Show keys as large key-cap boxes in real time
+        #[arg(short, long)]
+        echo: bool,
+        /// Time in ms before a key fades out
+        #[arg(long, default_value = "2000")]
+        fade: u64,
+        /// Maximum number of keys shown at once
+        #[arg(long, default_value = "6")]
+        max_keys: usize,
+        /// Hide modifier keys (Ctrl, Alt, Shift)
+        #[arg(long)]
+        no_modifiers: bool,
     },
     /// Multi-field interactive form for collecting inputs
     ///
     /// Example: termgfx form --field "name:text:Your name" --field "role:select:Role:Admin,User"
     #[command(
-        after_help = "Field types: text, password, select, multiselect, confirm, number\nOutput formats: json, env, csv"
+        after_help = "Field types: text, password, select, multiselect, confirm, number, textarea\nOutput formats: json, env, csv, yaml, toml"
     )]
     Form {
         /// Form fields in format "name:type:label[:options]"
         #[arg(short, long)]
         field: Vec<String>,
-        /// JSON config file path
+        /// JSON config file path (`-` reads stdin)
         #[arg(short, long)]
         config: Option<String>,
-        /// Output format: json, env, csv
+        /// Output format: json, env, csv, yaml, toml
         #[arg(short, long, default_value = "json")]
         output: String,
+        /// Print `export KEY='value'` lines for `eval` instead
+        #[arg(long)]
+        export_shell: bool,
+        /// Write the answers to this file instead of stdout
+        #[arg(long)]
+        output_file: Option<String>,
+        /// Answer for a field without a TTY, as NAME=VALUE (repeatable)
+        #[arg(long, value_name = "NAME=VALUE")]
+        default: Vec<String>,
+        /// Without a TTY, leave fields with no --default empty instead of failing
+        #[arg(long)]
+        no_tty_error: bool,
     },
     /// Multi-step wizard with navigation and progress tracking
     ///
     /// Example: termgfx wizard --step "input:name:Your name" --step "select:role:Role:Admin,User" --step "summary:summary:Review"
     #[command(
-        after_help = "Step types: input, select, multiselect, confirm, summary\nOutput formats: json, env\nNavigation: Enter=Next, Esc=Back"
+        after_help = "Step types: input, select, multiselect, confirm, summary\nOutput formats: json, env, csv, yaml, toml\nNavigation: Enter=Next, Esc=Back"
     )]
     Wizard {
         /// Wizard steps in format "type:id:prompt[:options]"
         #[arg(short, long)]
         step: Vec<String>,
-        /// JSON config file path
+        /// JSON config file path (`-` reads stdin)
         #[arg(short, long)]
         config: Option<String>,
         /// Wizard title
         #[arg(short, long)]
         title: Option<String>,
-        /// Output format: json, env
+        /// Output format: json, env, csv, yaml, toml
         #[arg(short, long, default_value = "json")]
         output: String,
+        /// Print `export KEY='value'` lines for `eval` instead
+        #[arg(long)]
+        export_shell: bool,
+        /// Write the answers to this file instead of stdout
+        #[arg(long)]
+        output_file: Option<String>,
     },
     /// Join content horizontally or vertically
     ///
@@ -595,6 +1317,23 @@ enum Commands {
         #[arg(short, long)]
         exit_on_error: bool,
     },
+    /// Run a command with a live status footer pinned to the bottom lines
+    ///
+    /// Example: termgfx footer --command "make test" --lines 3
+    #[command(
+        after_help = "The footer shows a spinner, elapsed time, line count and the latest error lines.\nExits with the command's exit code."
+    )]
+    Footer {
+        /// Shell command to run
+        #[arg(short, long)]
+        command: String,
+        /// Footer height: one status line plus recent errors
+        #[arg(short, long, default_value = "2")]
+        lines: usize,
+        /// Spinner style (dots, line, arc, bouncing, clock, circle, bounce, moon)
+        #[arg(short, long, default_value = "dots")]
+        style: String,
+    },
     /// Interactive TUI mode with event loop and multiple widgets
     ///
     /// Example: termgfx tui --layout 2x2 --widgets "box:Hello,gauge:75,sparkline:1;2;3;4,log:Line1"
@@ -613,6 +1352,13 @@ enum Commands {
         #[arg(short, long, default_value = "1000")]
         refresh: u64,
     },
+    /// Interactive REPL: run commands and see them render immediately
+    ///
+    /// Example: termgfx repl
+    #[command(
+        after_help = "Keys: Tab complete, ↑/↓ history, Ctrl+L clear, Ctrl+D quit\nMeta-commands: :clear, :theme <name>, :export <file>, :history, :help, :quit"
+    )]
+    Repl,
     /// Interactive playground/showcase for exploring components
     ///
     /// Example: termgfx playground
@@ -624,7 +1370,7 @@ enum Commands {
     ///
     /// Example: termgfx studio
     #[command(
-        after_help = "Navigation:\n  Tab/Shift+Tab  Cycle panels (Sidebar → Params → Preview)\n  1/2/3          Jump to panel (Sidebar/Params/Preview)\n  ↑/↓ j/k        Navigate items\n  h/←  l/→       Move between panels\n\nEditing:\n  Enter          Edit parameter\n  Space          Toggle bool / cycle enum values\n  r              Reset parameters to defaults\n  Esc            Cancel edit\n\nFavorites & History:\n  s              Save current config as favorite\n  f              Jump to Favorites section\n  Shift+H        Jump to History section\n  d              Delete favorite (in Favorites)\n  Enter          Load favorite/history entry\n\nResizing:\n  Ctrl+←/→       Resize sidebar width\n  Ctrl+↑/↓       Resize params panel height\n  Shift+R        Reset layout to defaults\n  Drag divider   Mouse drag to resize\n\nActions:\n  c              Copy command to clipboard\n  ?              Show Help overlay\n  q/Esc          Quit\n\nMouse:\n  Click          Select component/parameter/panel\n  Scroll         Navigate lists\n  Drag           Resize panels (on dividers)\n\nPanels:\n  Sidebar        Browse favorites, history, and components\n  Params         Edit component parameters\n  Preview        See live component preview\n  Command        Generated CLI command"
+        after_help = "Navigation:\n  Tab/Shift+Tab  Cycle panels (Sidebar → Params → Preview)\n  1/2/3          Jump to panel (Sidebar/Params/Preview)\n  ↑/↓ j/k        Navigate items\n  h/←  l/→       Move between panels\n\nEditing:\n  Enter          Edit parameter\n  Space          Toggle bool / cycle enum values\n  r              Reset parameters to defaults\n  Esc            Cancel edit\n\nFavorites & History:\n  s / Ctrl+S     Save current config as favorite\n  Ctrl+O         Browse and load favorites\n  f              Jump to Favorites section\n  Shift+H        Jump to History section\n  d              Delete favorite (in Favorites)\n  Enter          Load favorite/history entry\n\nResizing:\n  Ctrl+←/→       Resize sidebar width\n  Ctrl+↑/↓       Resize params panel height\n  Shift+R        Reset layout to defaults\n  Drag divider   Mouse drag to resize\n\nActions:\n  Ctrl+P         Command palette (jump, theme, export, ...)\n  w              Cycle preview width (fit/40/80/120 cols)\n  Shift+C        Cycle preview color depth\n  Space          Play/pause animation (Preview focused)\n  Enter          Run the command for real (Preview focused)\n  Esc            Stop it, then back to the simulated preview\n  c              Copy command to clipboard\n  e              Export preview to <component>-<time>.svg (box, banner, chart, table, gauge, heatmap)\n  Shift+E        Cycle export format (svg/png/html)\n  Shift+T        Edit the theme (colors, borders, gradient) and save it to the theme file\n  ?              Show Help overlay\n  q/Esc          Quit\n\nMouse:\n  Click          Select component/parameter/panel\n  Scroll         Navigate lists\n  Drag           Resize panels (on dividers)\n\nPanels:\n  Sidebar        Browse favorites, history, and components\n  Params         Edit component parameters\n  Preview        See live component preview\n  Command        Generated CLI command"
     )]
     Studio,
     /// Preview and manage style presets
@@ -645,11 +1391,11 @@ enum Commands {
         #[command(subcommand)]
         palette_command: Option<PaletteCommands>,
     },
-    /// Browse and preview theme presets
+    /// Browse and preview theme presets, or pick a default theme
     ///
-    /// Example: termgfx theme preview nord
+    /// Example: termgfx theme set nord
     #[command(
-        after_help = "Presets: dark, light, nord, dracula, monokai, solarized, gruvbox\nSet default via TERMGFX_THEME env var"
+        after_help = "Presets: dark, light, nord, dracula, monokai, solarized, gruvbox\nTheme file: ~/.config/termgfx/theme.toml (or $TERMGFX_THEME_FILE)\nPrecedence: --theme, then TERMGFX_THEME, then the theme file"
     )]
     Theme {
         #[command(subcommand)]
@@ -708,6 +1454,33 @@ enum Commands {
         #[command(subcommand)]
         export_command: ExportCommands,
     },
+    /// Render a command headless as SVG plus a Markdown snippet for PR comments
+    ///
+    /// Example: termgfx ci-comment --command "chart bar --data 'A:1,B:2'" --upload ./upload.sh
+    #[command(
+        name = "ci-comment",
+        after_help = "Formats: md-svg, md-text, svg\nUploader: --upload or $TERMGFX_UPLOADER, a shell command; {file} becomes the SVG path (else it is appended) and the last line printed is the image URL"
+    )]
+    CiComment {
+        /// termgfx command to render, e.g. "chart bar --data A:1,B:2"
+        #[arg(short, long)]
+        command: String,
+        /// Output format: md-svg, md-text, svg
+        #[arg(short, long, default_value = "md-svg")]
+        format: String,
+        /// Where to write the SVG for md-svg
+        #[arg(short, long, default_value = "termgfx.svg")]
+        output: String,
+        /// Bold heading above the image
+        #[arg(short, long)]
+        title: Option<String>,
+        /// Shell command that uploads the SVG and prints its URL
+        #[arg(long)]
+        upload: Option<String>,
+        /// Background color
+        #[arg(long, default_value = "#1e1e1e")]
+        background: String,
+    },
     /// Preview data before performing actions
     ///
     /// Example: termgfx preview --title "Files to delete" --items "file1.txt,file2.txt"
@@ -781,6 +1554,37 @@ enum Commands {
         #[arg(long)]
         quiet: bool,
     },
+    /// Show or calibrate emoji widths used when measuring boxes and banners
+    ///
+    /// Example: termgfx emoji-widths --detect
+    #[command(
+        after_help = "Overrides are stored in ~/.config/termgfx/emoji_widths.json
+--detect prints sample emoji and reads the cursor position back from the terminal"
+    )]
+    EmojiWidths {
+        /// Probe the terminal and save widths that differ from Unicode defaults
+        #[arg(long)]
+        detect: bool,
+        /// Set an override as EMOJI=WIDTH (repeatable)
+        #[arg(long, value_name = "EMOJI=WIDTH")]
+        set: Vec<String>,
+        /// Clear all overrides before applying --set/--detect
+        #[arg(long)]
+        reset: bool,
+    },
+    /// Update termgfx to the latest GitHub release
+    ///
+    /// Example: termgfx self-update --check
+    #[cfg(feature = "self-update")]
+    #[command(
+        after_help = "Shows the release notes since this version, then downloads the build for this platform
+and replaces the binary once it matches the release's SHA-256 checksum"
+    )]
+    SelfUpdate {
+        /// Only show what's new, without installing
+        #[arg(long)]
+        check: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -884,6 +1688,16 @@ enum ThemeCommands {
     },
     /// Show current active theme
     Current,
+    /// Show a theme's colors, border style and gradient
+    Show {
+        /// Theme name (defaults to the active theme)
+        name: Option<String>,
+    },
+    /// Make a theme the default by writing it to the theme file
+    Set {
+        /// Preset or user theme name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -896,12 +1710,21 @@ enum ChartCommands {
         /// Chart title
         #[arg(short, long)]
         title: Option<String>,
+        /// Style: braille, horizon (one folded row for dense series)
+        #[arg(short, long, default_value = "braille")]
+        style: String,
+        /// Number of horizon bands (1-4)
+        #[arg(long, default_value = "3")]
+        bands: usize,
         /// Animate line drawing point by point
         #[arg(short, long)]
         animate: bool,
         /// Total animation duration in ms (default: 500)
         #[arg(long, default_value = "500")]
         animation_time: u64,
+        /// Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic
+        #[arg(long, default_value = "linear")]
+        easing: String,
     },
     /// Bar chart
     Bar {
@@ -911,12 +1734,75 @@ enum ChartCommands {
         /// Animate bars growing
         #[arg(short, long)]
         animate: bool,
+        /// Animation curve: linear, ease-in, ease-out, ease-in-out, cubic, bounce, elastic
+        #[arg(long, default_value = "linear")]
+        easing: String,
         /// Show a demo of this command
         #[arg(long, help = "Show a demo of this command")]
         demo: bool,
     },
-    /// Pie chart (ASCII)
-    Pie {
+    /// Box plot with median, quartiles, whiskers and outliers
+    Boxplot {
+        /// Series in format "name:v1,v2,v3;name:v1,v2"
+        #[arg(short, long)]
+        data: String,
+        /// Show a density (violin) plot instead of a box
+        #[arg(long)]
+        violin: bool,
+    },
+    /// Funnel chart for conversion pipelines
+    Funnel {
+        /// Stages in format "Label:Value,Label:Value"
+        #[arg(short, long)]
+        data: String,
+        /// Style preset for bar colors (e.g. info, success, retro)
+        #[arg(short, long, default_value = "info")]
+        style: String,
+    },
+    /// Radar (spider) chart for multi-dimensional comparison
+    Radar {
+        /// Axes in format "name:value,name:value" (at least 3)
+        #[arg(short, long)]
+        data: String,
+        /// Second dataset to overlay, with the same axis names
+        #[arg(short, long)]
+        compare: Option<String>,
+        /// Value at the outer ring (default: largest value)
+        #[arg(short, long)]
+        max: Option<f64>,
+        /// Radius in terminal rows
+        #[arg(long, default_value = "8")]
+        size: usize,
+        /// Legend placement: auto, right, below, collapsed, toggle, none
+        #[arg(long, default_value = "auto")]
+        legend: String,
+    },
+    /// Stacked sparklines with a shared X axis for comparing many series
+    ///
+    /// Example: termgfx chart ridgeline --file series.csv
+    #[command(
+        after_help = "CSV format: first column is the X axis, one column per series
+  time,web-1,web-2,db-1
+  00:00,12,40,7
+  00:05,18,35,9
+Empty cells are gaps"
+    )]
+    Ridgeline {
+        /// Wide CSV file (`-` for stdin)
+        #[arg(short, long, conflicts_with = "data")]
+        file: Option<String>,
+        /// Inline series in format "name:v1,v2,v3;name:v1,v2,v3"
+        #[arg(short, long)]
+        data: Option<String>,
+        /// Rows per series
+        #[arg(long, default_value = "1")]
+        height: usize,
+        /// Use one scale for all rows instead of scaling each row separately
+        #[arg(long)]
+        shared_scale: bool,
+    },
+    /// Pie chart (ASCII)
+    Pie {
         /// Data in format "Label:Value,Label:Value"
         #[arg(short, long)]
         data: String,
@@ -926,6 +1812,9 @@ enum ChartCommands {
         /// Total animation duration in ms (default: 500)
         #[arg(long, default_value = "500")]
         animation_time: u64,
+        /// Legend placement: auto, right, below, collapsed, toggle, none
+        #[arg(long, default_value = "auto")]
+        legend: String,
     },
 }
 
@@ -935,24 +1824,78 @@ enum RecordCommands {
     Start {
         /// Output file path
         output: String,
+        /// Title stored in the recording
+        #[arg(long)]
+        title: Option<String>,
+        /// Author stored in the recording
+        #[arg(long)]
+        author: Option<String>,
+        /// Add a chapter marker after this many seconds without output
+        #[arg(long, value_name = "SECONDS")]
+        idle_markers: Option<f64>,
+        /// Record this command instead of an interactive shell
+        #[arg(short, long)]
+        command: Option<String>,
+        /// File format: cast (asciicast v2, for asciinema and agg), legacy
+        #[arg(short, long, default_value = "cast")]
+        format: String,
+    },
+    /// Add a chapter marker to the recording running in this shell
+    ///
+    /// Example: termgfx record mark "step 2"
+    Mark {
+        /// Chapter label
+        label: String,
     },
     /// Play terminal recording
+    #[command(
+        after_help = "Keys (interactive terminal): n/→ next chapter, p/← previous chapter, space pause, q quit"
+    )]
     Play {
-        /// Recording file path
+        /// Recording file path (`-` reads stdin)
         input: String,
         /// Playback speed multiplier
         #[arg(short, long, default_value = "1.0")]
         speed: f64,
+        /// Start at a chapter (1-based number or label)
+        #[arg(short, long)]
+        chapter: Option<String>,
+        /// Shorten pauses longer than this many seconds
+        #[arg(short, long, value_name = "SECONDS")]
+        idle_limit: Option<f64>,
     },
     /// Export recording to other formats
+    ///
+    /// Example: termgfx record export demo.cast -f gif demo.gif --chrome mac
+    ///
+    /// Example: termgfx record export demo.cast -f gif clip.gif --from 00:12 --to 00:45
+    #[command(
+        after_help = "gif, apng and webm are drawn in the --theme colors; webm needs ffmpeg on PATH\nTimes: seconds (45, 12.5), mm:ss or hh:mm:ss"
+    )]
     Export {
-        /// Input recording file
+        /// Input recording file (`-` reads stdin)
         input: String,
-        /// Output format: gif, json
+        /// Output format: gif, apng, webm, cast (asciicast v2), json (legacy)
         #[arg(short, long)]
         format: String,
         /// Output file path
         output: String,
+        /// Frames per second; gif, apng and webm default to 15, cast and json
+        /// keep every event unless given
+        #[arg(long)]
+        fps: Option<u32>,
+        /// Start of the part to export
+        #[arg(long, value_name = "TIME")]
+        from: Option<String>,
+        /// End of the part to export
+        #[arg(long, value_name = "TIME")]
+        to: Option<String>,
+        /// Window drawn around gif, apng and webm frames: none, mac
+        #[arg(long, default_value = "none")]
+        chrome: String,
+        /// Shorten pauses longer than this many seconds
+        #[arg(short, long, value_name = "SECONDS")]
+        idle_limit: Option<f64>,
     },
 }
 
@@ -966,11 +1909,98 @@ fn main() {
         return; // parse_from will print help and exit
     }
 
-    let cli = Cli::parse();
+    // Enabled before parsing so the allocator counts arguments too; without
+    // the flag it never counts
+    if args.iter().any(|a| a == "--profile-render") {
+        output::profile::enable();
+    }
+    let start = output::profile::Snapshot::now();
+    let matches = match Cli::command().try_get_matches() {
+        Ok(matches) => matches,
+        Err(e) if e.kind() == clap::error::ErrorKind::DisplayVersion => {
+            let _ = e.print();
+            output::update::version_notice();
+            std::process::exit(0);
+        }
+        Err(e) => e.exit(),
+    };
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    // `gauge --color green` and `timeline --color blue` predate the global
+    // flag; a color name there still sets the accent
+    if output::color::ColorChoice::from_name(&cli.color).is_none() {
+        if let Commands::Gauge { accent, .. } | Commands::Timeline { accent, .. } = &mut cli.command
+        {
+            let name = std::mem::replace(&mut cli.color, "auto".to_string());
+            eprintln!(
+                "Note: --color {0} is deprecated here, use --accent {0}",
+                name
+            );
+            accent.get_or_insert(name);
+        }
+    }
+    match output::color::ColorChoice::from_name(&cli.color) {
+        Some(choice) => output::color::init(choice),
+        None => {
+            eprintln!(
+                "Error: Invalid color mode '{}' (use {})",
+                cli.color,
+                output::color::COLOR_CHOICES.join(", ")
+            );
+            std::process::exit(1);
+        }
+    }
+    match output::color::ColorDepth::from_name(&cli.color_depth) {
+        Some(depth) => output::color::init_depth(depth),
+        None => {
+            eprintln!(
+                "Error: Invalid color depth '{}' (use {})",
+                cli.color_depth,
+                output::color::COLOR_DEPTHS.join(", ")
+            );
+            std::process::exit(1);
+        }
+    }
+    animation::policy::init(cli.no_animation);
+    if let Err(e) = output::trace::init(cli.log_level.as_deref(), cli.log_file.as_deref()) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    output::trace::environment(matches.subcommand_name().unwrap_or_default());
+    let _profile = if cli.profile_render {
+        output::profile::record(output::profile::Phase::Parse, start);
+        Some(output::profile::ReportGuard::new(
+            matches.subcommand_name().unwrap_or_default(),
+            start,
+        ))
+    } else {
+        None
+    };
+    // `theme` reads the configuration itself, so a broken one can be fixed
+    if !matches!(cli.command, Commands::Theme { .. }) {
+        if let Err(e) = design::theme::init(cli.theme.as_deref()) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+    if let Some(path) = &cli.export {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        export::snapshot::run(matches.subcommand_name().unwrap_or_default(), path, &args);
+    }
+    if let Some(ttl) = &cli.cache {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        output::cache::run(matches.subcommand_name().unwrap_or_default(), ttl, &args);
+    }
 
     match cli.command {
         Commands::Box {
             message,
+            file,
+            stdin,
+            title,
+            footer,
+            title_align,
+            width,
+            padding,
             style,
             border,
             emoji,
@@ -978,29 +2008,56 @@ fn main() {
             animation_time,
             demo,
             preset: _,
+            format,
         } => {
             if demo {
                 println!("Example: termgfx box \"Hello\" --style success");
                 println!();
                 // Run with demo values
-                output::styled_box::render_animated(
-                    "Hello World!",
-                    "success",
-                    "rounded",
-                    None,
-                    true,
-                    500,
-                );
+                let options = output::styled_box::BoxOptions {
+                    style: "success",
+                    ..Default::default()
+                };
+                output::styled_box::render_animated("Hello World!", &options, true, 500);
                 return;
             }
-            output::styled_box::render_animated(
-                &message,
-                &style,
-                &border,
-                emoji.as_deref(),
-                animate,
-                animation_time,
-            );
+            let message = if stdin { None } else { message };
+            let message =
+                match output::styled_box::read_message(message.as_deref(), file.as_deref()) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+            let title_align = match output::styled_box::TitleAlign::from_name(&title_align) {
+                Some(a) => a,
+                None => {
+                    eprintln!(
+                        "Error: Invalid title alignment '{}' (use left, center, right)",
+                        title_align
+                    );
+                    std::process::exit(1);
+                }
+            };
+            let themed = design::theme::active().map_or("rounded", |t| t.border_style.as_str());
+            let border =
+                border.unwrap_or_else(|| output::color::default_border(themed).to_string());
+            let options = output::styled_box::BoxOptions {
+                style: &style,
+                border: &border,
+                emoji: emoji.as_deref(),
+                title: title.as_deref(),
+                footer: footer.as_deref(),
+                title_align,
+                padding,
+                max_width: width,
+            };
+            if format != "ansi" {
+                export::snapshot::print(&format, &output::styled_box::box_doc(&message, &options));
+                return;
+            }
+            output::styled_box::render_animated(&message, &options, animate, animation_time);
         }
         Commands::DangerZone {
             message,
@@ -1009,6 +2066,8 @@ fn main() {
             animate,
             animation_time,
         } => {
+            let border =
+                border.unwrap_or_else(|| output::color::default_border("double").to_string());
             output::styled_box::render_danger_zone(
                 &message,
                 title.as_deref(),
@@ -1020,6 +2079,10 @@ fn main() {
         Commands::Banner {
             title,
             gradient,
+            align,
+            max_width,
+            padding,
+            font,
             animate,
             animation_time,
             demo,
@@ -1031,7 +2094,79 @@ fn main() {
                 output::banner::render_animated("Welcome", Some("cyan-purple"), true, 500);
                 return;
             }
-            output::banner::render_animated(&title, gradient.as_deref(), animate, animation_time);
+            let align = match output::banner::BannerAlign::from_name(&align) {
+                Some(a) => a,
+                None => {
+                    eprintln!(
+                        "Error: Invalid alignment '{}' (use left, center, right)",
+                        align
+                    );
+                    std::process::exit(1);
+                }
+            };
+            let font = match font.as_deref().map(output::banner::fonts::Font::from_spec) {
+                Some(Ok(font)) => Some(font),
+                Some(Err(e)) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+                None => None,
+            };
+            let layout = output::banner::BannerLayout {
+                align,
+                max_width,
+                padding,
+                font: font.as_ref(),
+            };
+            if let Some(max) = max_width.filter(|&max| max < layout.min_width()) {
+                eprintln!(
+                    "Error: --max-width {} is too narrow for the banner frame (needs at least {} with --padding {})",
+                    max,
+                    layout.min_width(),
+                    padding
+                );
+                std::process::exit(1);
+            }
+            let gradient = gradient.or_else(|| design::theme::active().map(|t| t.gradient.clone()));
+            output::banner::render_with_layout(
+                &title,
+                gradient.as_deref(),
+                &layout,
+                animate,
+                animation_time,
+            );
+        }
+        Commands::Text {
+            text,
+            gradient,
+            rainbow,
+            bold,
+            italic,
+            underline,
+            spacing,
+        } => {
+            let gradient = if rainbow {
+                Some(output::gradient::Gradient::Rainbow)
+            } else {
+                gradient.map(|name| {
+                    output::gradient::Gradient::from_name(&name).unwrap_or_else(|| {
+                        eprintln!(
+                            "Error: Invalid gradient '{}' (use {})",
+                            name,
+                            output::gradient::GRADIENT_NAMES.join(", ")
+                        );
+                        std::process::exit(1);
+                    })
+                })
+            };
+            let options = output::styled_text::TextOptions {
+                gradient,
+                bold,
+                italic,
+                underline,
+                spacing,
+            };
+            output::styled_text::render(&text, &options);
         }
         Commands::Spinner {
             message,
@@ -1047,13 +2182,40 @@ fn main() {
             to,
             animate,
             duration,
+            easing,
+            inline,
+            shell,
+            format,
             demo,
         } => {
+            let easing = self::easing(&easing);
+            if format != "ansi" {
+                let doc = output::progress::doc(percent, &style, from.as_deref(), to.as_deref());
+                export::snapshot::print(&format, &doc);
+                return;
+            }
+            if inline {
+                output::progress::render_inline(
+                    percent,
+                    &style,
+                    from.as_deref(),
+                    to.as_deref(),
+                    &shell,
+                );
+                return;
+            }
             if demo {
                 println!("Example: termgfx progress 75 --style gradient --animate");
                 println!();
                 // Run with demo values
-                output::progress::render_animated_progress(75, "gradient", None, None, 1000);
+                output::progress::render_animated_progress(
+                    75,
+                    "gradient",
+                    None,
+                    None,
+                    1000,
+                    animation::easing::Easing::Linear,
+                );
                 return;
             }
             if animate {
@@ -1063,6 +2225,7 @@ fn main() {
                     from.as_deref(),
                     to.as_deref(),
                     duration,
+                    easing,
                 );
             } else {
                 output::progress::render(percent, &style, from.as_deref(), to.as_deref());
@@ -1073,71 +2236,282 @@ fn main() {
                 ChartCommands::Line {
                     data,
                     title,
+                    style,
+                    bands,
                     animate,
                     animation_time,
+                    easing,
                 } => {
+                    let style = match charts::line::LineStyle::from_name(&style, bands) {
+                        Some(s) => s,
+                        None => {
+                            eprintln!(
+                                "Error: Invalid line style '{}' (use braille, horizon)",
+                                style
+                            );
+                            std::process::exit(1);
+                        }
+                    };
                     let line_chart = charts::line::LineChart::new(
                         &data,
                         title.as_deref(),
+                        style,
                         animate,
                         animation_time,
-                    );
+                    )
+                    .with_easing(self::easing(&easing));
                     line_chart.render();
                 }
                 ChartCommands::Bar {
                     data,
                     animate,
+                    easing,
                     demo,
                 } => {
+                    let easing = self::easing(&easing);
                     if demo {
                         println!(
                             "Example: termgfx chart bar --data \"Sales:100,Costs:60,Profit:40\""
                         );
                         println!();
                         // Run with demo values
-                        charts::bar::render_animated("Sales:100,Costs:60,Profit:40", true);
+                        charts::bar::render_animated("Sales:100,Costs:60,Profit:40", true, easing);
                         return;
                     }
-                    charts::bar::render_animated(&data, animate);
+                    charts::bar::render_animated(&data, animate, easing);
+                }
+                ChartCommands::Boxplot { data, violin } => {
+                    charts::boxplot::render(&data, violin);
+                }
+                ChartCommands::Funnel { data, style } => {
+                    charts::funnel::render(&data, &style);
+                }
+                ChartCommands::Radar {
+                    data,
+                    compare,
+                    max,
+                    size,
+                    legend,
+                } => {
+                    let legend = legend_position(&legend);
+                    charts::radar::render(&data, compare.as_deref(), max, size, legend);
+                }
+                ChartCommands::Ridgeline {
+                    file,
+                    data,
+                    height,
+                    shared_scale,
+                } => {
+                    charts::ridgeline::render(
+                        file.as_deref(),
+                        data.as_deref(),
+                        height,
+                        shared_scale,
+                    );
                 }
                 ChartCommands::Pie {
                     data,
                     animate,
                     animation_time,
+                    legend,
                 } => {
-                    let pie_chart = charts::pie::PieChart::new(&data, animate, animation_time);
+                    let pie_chart = charts::pie::PieChart::new(&data, animate, animation_time)
+                        .with_legend(legend_position(&legend));
                     pie_chart.render();
                 }
             }
         }
-        Commands::Image { path, protocol } => {
-            image::render(&path, &protocol);
+        Commands::Image {
+            paths,
+            stdin,
+            protocol,
+            width,
+            height,
+            align,
+            preserve_aspect,
+            loop_count,
+            fps,
+            palette,
+            dither,
+            grid,
+            gap,
+            captions,
+        } => {
+            let paths = if stdin {
+                vec![output::input::STDIN.to_string()]
+            } else {
+                paths
+            };
+            let size = grid.map(|g| {
+                image::GridSize::parse(&g).unwrap_or_else(|| {
+                    eprintln!(
+                        "Error: Invalid grid '{}' (use COLSxROWS like 3x2, or COLS)",
+                        g
+                    );
+                    std::process::exit(1);
+                })
+            });
+            let dimension = |value: Option<String>| {
+                value.map(|v| {
+                    image::Dimension::parse(&v).unwrap_or_else(|| {
+                        eprintln!(
+                            "Error: Invalid size '{}' (use cells like 40 or a percentage like 50%)",
+                            v
+                        );
+                        std::process::exit(1);
+                    })
+                })
+            };
+            if fps.is_some_and(|fps| !(fps > 0.0 && fps.is_finite())) {
+                eprintln!("Error: --fps must be greater than 0");
+                std::process::exit(1);
+            }
+            let Some(align) = image::ImageAlign::from_name(&align) else {
+                eprintln!(
+                    "Error: Invalid alignment '{}' (use left, center, right)",
+                    align
+                );
+                std::process::exit(1);
+            };
+            let Some(palette) = image::quantize::Palette::from_name(&palette) else {
+                eprintln!(
+                    "Error: Invalid palette '{}' (use {})",
+                    palette,
+                    image::quantize::PALETTES.join(", ")
+                );
+                std::process::exit(1);
+            };
+            let options = image::ImageOptions {
+                width: dimension(width),
+                height: dimension(height),
+                align,
+                preserve_aspect,
+                loops: loop_count,
+                fps,
+                palette,
+                dither,
+            };
+            let grid = image::GridOptions {
+                size,
+                gap,
+                captions,
+            };
+            image::render(&paths, &protocol, &options, &grid);
         }
         Commands::Input {
             prompt,
             placeholder,
             password,
+            mask,
+            input_type,
+            validate,
+            min,
+            max,
+            char_limit,
+            value,
+            default,
+            no_tty_error,
         } => {
-            interactive::input::render(&prompt, placeholder.as_deref(), password);
+            let format = match (mask, input_type) {
+                (Some(mask), _) => Some(interactive::input::InputFormat::Mask(mask)),
+                (None, Some(name)) => match interactive::input::InputFormat::from_name(&name) {
+                    Some(format) => Some(format),
+                    None => {
+                        eprintln!(
+                            "Error: Invalid input type '{}' (use ipv4, semver, duration, date)",
+                            name
+                        );
+                        std::process::exit(1);
+                    }
+                },
+                (None, None) => None,
+            };
+            let validate = match validate
+                .map(|spec| interactive::input::Validator::parse(&spec, min, max))
+                .transpose()
+            {
+                Ok(validate) => validate,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let rules = interactive::input::InputRules {
+                format,
+                validate,
+                char_limit,
+            };
+            interactive::input::render(
+                &prompt,
+                placeholder.as_deref(),
+                password,
+                &rules,
+                value.as_deref(),
+                default.as_deref(),
+                no_tty_error,
+            );
         }
         Commands::Select {
             prompt,
             options,
             multi,
+            danger,
+            selected,
+            height,
+            inline,
+            default,
+            no_tty_error,
         } => {
-            interactive::select::render(&prompt, &options, multi);
+            interactive::select::render(
+                &prompt,
+                &options,
+                multi,
+                &danger,
+                &selected,
+                height,
+                inline,
+                default.as_deref(),
+                no_tty_error,
+            );
         }
         Commands::Confirm {
             prompt,
             default,
             style,
+            require_typed,
+            no_tty_error,
         } => {
-            interactive::confirm::render(&prompt, &default, &style);
+            interactive::confirm::render(
+                &prompt,
+                default.as_deref(),
+                &style,
+                require_typed.as_deref(),
+                no_tty_error,
+            );
+        }
+        Commands::Write {
+            header,
+            placeholder,
+            rows,
+            value,
+            default,
+            no_tty_error,
+        } => {
+            interactive::textarea::render(
+                header.as_deref(),
+                placeholder.as_deref(),
+                rows,
+                value.as_deref(),
+                default.as_deref(),
+                no_tty_error,
+            );
         }
         Commands::Sparkline {
             data,
             animate,
             animation_time,
+            source,
+            interval,
             demo,
         } => {
             if demo {
@@ -1147,25 +2521,67 @@ fn main() {
                 charts::sparkline::render_animated("1,4,2,8,5,7,3,9,6", true, 500);
                 return;
             }
-            charts::sparkline::render_animated(&data, animate, animation_time);
+            if let Some(source) = source {
+                charts::sparkline::render_source(&source, &interval);
+                return;
+            }
+            charts::sparkline::render_animated(&data.unwrap_or_default(), animate, animation_time);
         }
         Commands::Diff {
             file1,
             file2,
             unified,
             context,
+            word_diff,
+            interactive,
         } => {
-            output::diff::render(&file1, &file2, unified, context);
+            output::diff::render(&file1, &file2, unified, context, word_diff, interactive);
+        }
+        Commands::Md { file } => {
+            output::markdown::render(&file);
+        }
+        Commands::Present { file, start } => {
+            interactive::present::run(&file, start);
+        }
+        Commands::Code {
+            file,
+            lines,
+            lang,
+            no_pager,
+        } => {
+            output::code::render(&file, lines.as_deref(), lang.as_deref(), no_pager);
+        }
+        Commands::Log {
+            file,
+            follow,
+            filter,
+            relative,
+            no_pager,
+        } => {
+            output::log::render(&file, follow, filter.as_deref(), relative, no_pager);
         }
         Commands::Table {
             headers,
             rows,
             file,
+            json,
+            columns,
+            hide,
+            query,
+            sort,
+            desc,
+            sort_type,
+            max_col_width,
+            wrap,
+            footer,
+            interactive,
+            row_format,
             border,
             alignment,
             animate,
             animation_time,
             demo,
+            format,
         } => {
             if demo {
                 println!(
@@ -1177,6 +2593,9 @@ fn main() {
                     Some("Name,Age"),
                     Some("Alice,30|Bob,25"),
                     None,
+                    None,
+                    None,
+                    &output::table::TableView::default(),
                     "single",
                     "left",
                     true,
@@ -1184,47 +2603,181 @@ fn main() {
                 );
                 return;
             }
-            output::table::render_animated(
+            let Some(sort_type) = output::table_sort::SortType::from_name(&sort_type) else {
+                eprintln!(
+                    "Error: Invalid sort type '{}' (use {})",
+                    sort_type,
+                    output::table_sort::SORT_TYPES.join(", ")
+                );
+                std::process::exit(1);
+            };
+            let view = output::table::TableView {
+                sort: sort.map(|spec| output::table_sort::TableSort::parse(&spec, sort_type, desc)),
+                columns: columns.as_deref(),
+                hide: hide.as_deref(),
+                max_col_width,
+                wrap,
+                footer: footer.as_deref(),
+            };
+            let (headers, rows) = output::table::load(
                 headers.as_deref(),
                 rows.as_deref(),
                 file.as_deref(),
-                &border,
-                &alignment,
+                json.as_deref(),
+                query.as_deref(),
+                &view,
+            )
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            if interactive {
+                match interactive::table_view::render(headers, rows, &row_format) {
+                    Ok(()) => {}
+                    Err(e) if interactive::prompt::is_cancel(&e) => {
+                        interactive::prompt::exit_cancelled()
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+            let border =
+                border.unwrap_or_else(|| output::color::default_border("single").to_string());
+            let options = output::table::TableOptions {
                 animate,
-                animation_time,
-            );
+                animation_time_ms: animation_time,
+                ..output::table::TableOptions::for_view(&view, &headers, &rows, &border, &alignment)
+            };
+            if format != "ansi" {
+                let doc = output::table::table_doc(&headers, &rows, &options);
+                export::snapshot::print(&format, &doc);
+                return;
+            }
+            output::table::render_table(&headers, &rows, &options);
         }
         Commands::Tree {
             data,
             path,
+            diff,
+            dir,
+            depth,
+            all,
+            no_ignore,
+            size,
+            only_changes,
+            query,
             animate,
             animation_time,
         } => {
-            output::tree::render_animated(
-                data.as_deref(),
-                path.as_deref(),
-                animate,
-                animation_time,
-            );
+            if let Some(root) = dir {
+                let options = output::tree::DirOptions {
+                    depth,
+                    all,
+                    no_ignore,
+                    sizes: size,
+                };
+                output::tree::render_dir(&root, &options, animate, animation_time);
+            } else if let Some(files) = diff {
+                output::tree::render_diff(&files[0], &files[1], only_changes, query.as_deref());
+            } else {
+                output::tree::render_animated(
+                    data.as_deref(),
+                    path.as_deref(),
+                    query.as_deref(),
+                    animate,
+                    animation_time,
+                );
+            }
         }
         Commands::Record { record_command } => match record_command {
-            RecordCommands::Start { output } => {
-                output::record::start(&output);
+            RecordCommands::Start {
+                output,
+                title,
+                author,
+                idle_markers,
+                command,
+                format,
+            } => {
+                let Some(format) = output::record::RecordFormat::from_name(&format) else {
+                    eprintln!(
+                        "Error: Invalid format '{}' (use {})",
+                        format,
+                        output::record::FORMATS.join(", ")
+                    );
+                    std::process::exit(1);
+                };
+                let options = output::record::RecordOptions {
+                    title: title.as_deref(),
+                    author: author.as_deref(),
+                    idle_marker: idle_markers,
+                    command: command.as_deref(),
+                    format,
+                };
+                output::record::start(&output, &options);
             }
-            RecordCommands::Play { input, speed } => {
-                output::record::play(&input, speed);
+            RecordCommands::Mark { label } => {
+                output::record::mark(&label);
+            }
+            RecordCommands::Play {
+                input,
+                speed,
+                chapter,
+                idle_limit,
+            } => {
+                output::record::play(&input, speed, chapter.as_deref(), idle_limit);
             }
             RecordCommands::Export {
                 input,
                 format,
                 output,
+                fps,
+                from,
+                to,
+                chrome,
+                idle_limit,
             } => {
-                output::record::export(&input, &format, &output);
+                let range = output::record::TimeRange::parse(from.as_deref(), to.as_deref())
+                    .unwrap_or_else(|e| {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    });
+                match export::raster::RasterFormat::from_name(&format) {
+                    Some(format) => {
+                        let Some(chrome) = export::raster::Chrome::from_name(&chrome) else {
+                            eprintln!(
+                                "Error: Invalid chrome '{}' (use {})",
+                                chrome,
+                                export::raster::CHROMES.join(", ")
+                            );
+                            std::process::exit(1);
+                        };
+                        let options = export::raster::RasterOptions {
+                            format,
+                            fps: fps.unwrap_or(export::raster::DEFAULT_FPS),
+                            chrome,
+                            idle_limit,
+                            range,
+                        };
+                        export::raster::export(&input, &output, &options);
+                    }
+                    None => output::record::export(&input, &format, &output, range, fps),
+                }
             }
         },
 
-        Commands::Script { file, inline } => {
-            script::run(file.as_deref(), inline.as_deref());
+        Commands::Script {
+            file,
+            inline,
+            watch_file,
+        } => {
+            let components: Vec<String> = Cli::command()
+                .get_subcommands()
+                .map(|c| c.get_name().to_string())
+                .collect();
+            script::run(file.as_deref(), inline.as_deref(), watch_file, &components);
         }
         Commands::Animate {
             effect_type,
@@ -1237,6 +2790,9 @@ fn main() {
             style,
             prefix,
             suffix,
+            easing,
+            density,
+            colors,
         } => {
             animation::effects::run(
                 &effect_type,
@@ -1249,6 +2805,8 @@ fn main() {
                 &style,
                 prefix.as_deref(),
                 suffix.as_deref(),
+                self::easing(&easing),
+                &animation::effects::SceneOptions::new(density, colors.as_deref()),
             );
         }
         Commands::Demo { section } => {
@@ -1256,15 +2814,19 @@ fn main() {
         }
         Commands::Timeline {
             events,
+            group,
+            now,
             style,
-            color,
+            accent,
             animate,
             vertical,
         } => {
             let args = output::timeline::TimelineArgs {
                 events,
+                groups: group,
+                now,
                 style,
-                color,
+                color: accent,
                 animate,
                 vertical,
             };
@@ -1280,7 +2842,31 @@ fn main() {
             sound,
             terminal_only,
             desktop_only,
+            progress,
+            id,
+            timeout,
+            actions,
         } => {
+            let timeout = timeout.map(|t| {
+                output::watch::parse_interval(&t).unwrap_or_else(|_| {
+                    eprintln!("Error: Invalid timeout '{}' (use e.g. 5s, 1m)", t);
+                    std::process::exit(1);
+                })
+            });
+            let actions = actions
+                .iter()
+                .map(|spec| output::notification::Action::parse(spec))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            let options = output::notification::NotificationOptions {
+                progress,
+                id,
+                timeout,
+                actions,
+            };
             output::notification::render(
                 &message,
                 title.as_deref(),
@@ -1288,6 +2874,7 @@ fn main() {
                 sound,
                 terminal_only,
                 desktop_only,
+                &options,
             );
         }
         Commands::Gauge {
@@ -1296,33 +2883,184 @@ fn main() {
             max,
             label,
             style,
-            color,
+            accent,
             animate,
+            easing,
+            inline,
+            shell,
+            source,
+            interval,
+            multi,
+            thresholds,
             demo,
         } => {
+            let easing = self::easing(&easing);
+            let thresholds = match thresholds
+                .as_deref()
+                .map(output::gauge::parse_thresholds)
+                .transpose()
+            {
+                Ok(thresholds) => thresholds.unwrap_or_default(),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let options = output::gauge::GaugeOptions {
+                min,
+                max,
+                label: label.as_deref(),
+                style: &style,
+                color: accent.as_deref(),
+                animate,
+                easing,
+            };
+            if let Some(source) = source {
+                output::gauge::render_source(
+                    &source,
+                    interval.as_deref(),
+                    &options,
+                    &thresholds,
+                    inline.then_some(shell.as_str()),
+                );
+                return;
+            }
+            if let Some(multi) = multi {
+                match output::gauge::parse_multi(&multi) {
+                    Ok(gauges) => output::gauge::render_multi(
+                        &gauges,
+                        min,
+                        max,
+                        &style,
+                        accent.as_deref(),
+                        &thresholds,
+                    ),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+            let value = value.unwrap_or_default();
+            let color = output::gauge::threshold_color(value, &thresholds)
+                .or(accent.as_deref())
+                .map(str::to_string);
+            if inline {
+                output::gauge::render_inline(
+                    value,
+                    min,
+                    max,
+                    label.as_deref(),
+                    color.as_deref(),
+                    &shell,
+                );
+                return;
+            }
             if demo {
                 println!("Example: termgfx gauge 75 --label \"CPU\" --style semicircle");
                 println!();
                 // Run with demo values
-                output::gauge::render(75.0, 0.0, 100.0, Some("CPU"), "semicircle", None, true);
+                output::gauge::render(
+                    75.0,
+                    &output::gauge::GaugeOptions {
+                        label: Some("CPU"),
+                        animate: true,
+                        ..Default::default()
+                    },
+                );
                 return;
             }
             output::gauge::render(
                 value,
-                min,
-                max,
-                label.as_deref(),
-                &style,
-                color.as_deref(),
-                animate,
+                &output::gauge::GaugeOptions {
+                    color: color.as_deref(),
+                    ..options
+                },
             );
         }
+        Commands::Clock {
+            style,
+            format,
+            seconds,
+            date,
+            timezone,
+        } => {
+            let twelve_hour = match format.as_str() {
+                "24h" => false,
+                "12h" => true,
+                _ => {
+                    eprintln!("Error: Invalid format '{}' (use 24h, 12h)", format);
+                    std::process::exit(1);
+                }
+            };
+            let font = output::banner::fonts::Font::from_spec(&style);
+            let zone = output::clock::Zone::parse(&timezone);
+            match (font, zone) {
+                (Ok(font), Ok(zone)) => {
+                    output::clock::render(&font, twelve_hour, seconds, date, &zone)
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Timer {
+            duration,
+            label,
+            style,
+            notify,
+        } => {
+            let duration = match output::watch::parse_interval(&duration) {
+                Ok(duration) => duration,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            if !output::timer::STYLES.contains(&style.as_str()) {
+                eprintln!(
+                    "Error: Invalid style '{}' (use {})",
+                    style,
+                    output::timer::STYLES.join(", ")
+                );
+                std::process::exit(1);
+            }
+            if !output::timer::render_timer(duration, label.as_deref(), &style, notify) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Stopwatch { label, style } => {
+            if !output::timer::STYLES.contains(&style.as_str()) {
+                eprintln!(
+                    "Error: Invalid style '{}' (use {})",
+                    style,
+                    output::timer::STYLES.join(", ")
+                );
+                std::process::exit(1);
+            }
+            if !output::timer::render_stopwatch(label.as_deref(), &style) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Battery { inline, shell } => {
+            output::sensors::render_battery(inline, &shell);
+        }
+        Commands::Temps {
+            sensor,
+            inline,
+            shell,
+        } => {
+            output::sensors::render_temps(sensor.as_deref(), inline, &shell);
+        }
         Commands::Dashboard {
             layout,
             title,
             panels,
             config,
             border,
+            live,
         } => {
             output::dashboard::render(
                 &layout,
@@ -1330,6 +3068,7 @@ fn main() {
                 panels.as_deref(),
                 config.as_deref(),
                 &border,
+                live,
             );
         }
         Commands::Heatmap {
@@ -1339,57 +3078,203 @@ fn main() {
             y_labels,
             title,
             colors,
+            annotate,
+            scale,
+            null_color,
             animate,
         } => {
+            let Some(scale) = output::heatmap::Scale::from_name(&scale) else {
+                eprintln!("Error: Invalid scale '{}' (use linear, log)", scale);
+                std::process::exit(1);
+            };
+            let options = output::heatmap::HeatmapOptions {
+                colors: &colors,
+                scale,
+                annotate,
+                null_color: &null_color,
+            };
             output::heatmap::render(
                 data.as_deref(),
                 file.as_deref(),
                 x_labels.as_deref(),
                 y_labels.as_deref(),
                 title.as_deref(),
-                &colors,
+                &options,
                 animate,
             );
         }
+        Commands::Calendar {
+            data,
+            file,
+            from,
+            to,
+            title,
+            colors,
+        } => {
+            output::calendar::render(
+                data.as_deref(),
+                file.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                title.as_deref(),
+                &colors,
+            );
+        }
         Commands::File {
             path,
             directory,
             ext,
             height,
-        } => match interactive::file::render(path, directory, ext, height) {
-            Ok(selected_path) => {
-                println!("{}", selected_path.display());
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
+            multi,
+            all,
+            preview,
+            save,
+        } => {
+            match interactive::file::render(path, directory, ext, height, multi, all, preview, save)
+            {
+                Ok(selected) => {
+                    for path in selected {
+                        println!("{}", path.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
             }
-        },
+        }
         Commands::Filter {
             prompt,
             multi,
             height,
+            inline,
+            preview,
+            delimiter,
+            with_nth,
         } => {
-            interactive::filter::render(prompt, multi, height);
+            interactive::filter::render(
+                prompt, multi, height, inline, preview, delimiter, with_nth,
+            );
+        }
+        Commands::Barcode {
+            data,
+            symbology,
+            size,
+            height,
+            invert,
+            no_text,
+        } => {
+            let Some(symbology) = image::barcode::Symbology::from_name(&symbology) else {
+                eprintln!(
+                    "Error: Invalid symbology '{}' (use {})",
+                    symbology,
+                    image::barcode::SYMBOLOGIES.join(", ")
+                );
+                std::process::exit(1);
+            };
+            let options = image::barcode::BarcodeOptions {
+                symbology,
+                size,
+                height,
+                invert,
+                text: !no_text,
+            };
+            image::barcode::render(&symbol_data(data), &options);
+        }
+        Commands::Qr {
+            data,
+            size,
+            error_correction,
+            invert,
+            protocol,
+        } => {
+            let Some(level) = image::qr::parse_level(&error_correction) else {
+                eprintln!(
+                    "Error: Invalid error correction '{}' (use {})",
+                    error_correction,
+                    image::qr::LEVELS.join(", ")
+                );
+                std::process::exit(1);
+            };
+            let options = image::qr::QrOptions {
+                size,
+                level,
+                invert,
+            };
+            image::qr::render(&symbol_data(data), &protocol, &options);
+        }
+        Commands::Emojipick {
+            query,
+            category,
+            code,
+            list,
+        } => {
+            interactive::emojipick::render(query.as_deref(), &category, code, list);
         }
         Commands::Pager {
+            file,
             line_numbers,
             title,
+            height,
+            inline,
+            wrap,
+            follow,
+            markdown,
+            language,
         } => {
-            interactive::pager::render(line_numbers, title);
+            interactive::pager::render(
+                &file,
+                line_numbers,
+                title,
+                height,
+                inline,
+                wrap,
+                follow,
+                markdown,
+                language.as_deref(),
+            );
+        }
+        Commands::Json { file, query } => {
+            interactive::json::render(&file, query.as_deref());
+        }
+        Commands::Keys {
+            echo,
+            fade,
+            max_keys,
+            no_modifiers,
+        } => {
+            interactive::keys::render(echo, fade, max_keys, !no_modifiers);
         }
         Commands::Form {
             field,
             config,
             output,
+            export_shell,
+            output_file,
+            default,
+            no_tty_error,
         } => {
             if field.is_empty() && config.is_none() {
                 eprintln!("Error: Provide at least one --field or a --config file");
                 std::process::exit(1);
             }
-            if let Err(e) = interactive::form::render(field, config, output) {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
+            match interactive::form::render(
+                field,
+                config,
+                output,
+                export_shell,
+                output_file,
+                default,
+                no_tty_error,
+            ) {
+                Ok(()) => {}
+                Err(e) if interactive::prompt::is_cancel(&e) => {
+                    interactive::prompt::exit_cancelled()
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
             }
         }
         Commands::Wizard {
@@ -1397,12 +3282,16 @@ fn main() {
             config,
             title,
             output,
+            export_shell,
+            output_file,
         } => {
             if step.is_empty() && config.is_none() {
                 eprintln!("Error: Provide at least one --step or a --config file");
                 std::process::exit(1);
             }
-            if let Err(e) = interactive::wizard::render(step, config, title, output) {
+            if let Err(e) =
+                interactive::wizard::render(step, config, title, output, export_shell, output_file)
+            {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
@@ -1456,7 +3345,7 @@ fn main() {
             differences,
             exit_on_error,
         } => {
-            let duration = match output::watch::parse_interval(&interval) {
+            let duration = match output::watch::parse_refresh(&interval) {
                 Ok(d) => d,
                 Err(e) => {
                     eprintln!("Error: {}", e);
@@ -1470,6 +3359,18 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Commands::Footer {
+            command,
+            lines,
+            style,
+        } => match output::footer::render(&command, lines, &style) {
+            Ok(0) => {}
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
         Commands::Tui {
             config,
             layout,
@@ -1481,6 +3382,19 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Commands::Repl => {
+            let completions = Cli::command()
+                .get_subcommands()
+                .map(|sub| {
+                    let flags = sub
+                        .get_arguments()
+                        .filter_map(|arg| arg.get_long().map(|l| format!("--{}", l)))
+                        .collect();
+                    (sub.get_name().to_string(), flags)
+                })
+                .collect();
+            interactive::repl::render(completions);
+        }
         Commands::Playground => {
             interactive::playground::render();
         }
@@ -1533,9 +3447,29 @@ fn main() {
             },
         },
         Commands::Theme { theme_command } => {
-            use design::theme::{Theme, ThemePreset};
+            use design::theme::{self, Theme, ThemeFile, ThemePreset, ThemeSource};
             use owo_colors::OwoColorize;
 
+            let (file, broken) = match ThemeFile::load_user() {
+                Ok(file) => (file, false),
+                Err(e) => {
+                    eprintln!("Warning: {:#}; ignoring it", e);
+                    (ThemeFile::default(), true)
+                }
+            };
+            let resolve = |name: &str| {
+                Theme::resolve(name, &file).unwrap_or_else(|e| {
+                    eprintln!("Error: {:#}", e);
+                    eprintln!("Use 'termgfx theme list' to see available themes");
+                    std::process::exit(1);
+                })
+            };
+            let active = theme::configured(cli.theme.as_deref(), &file).unwrap_or_else(|e| {
+                eprintln!("Warning: {:#}", e);
+                None
+            });
+            let active_name = active.as_ref().map(|(t, _)| t.name.as_str());
+
             match theme_command {
                 Some(ThemeCommands::List) | None => {
                     println!(
@@ -1544,22 +3478,52 @@ fn main() {
                     );
                     println!("{}", "━".repeat(50).truecolor(100, 100, 100));
                     println!();
-                    for preset in ThemePreset::all() {
-                        let theme = Theme::load_preset(preset);
-                        let (r, g, b) = parse_hex(&theme.colors.primary);
-                        let mode = if theme.is_dark { "dark" } else { "light" };
-                        println!(
-                            "  {} {} - {} ({})",
-                            "●".truecolor(r, g, b),
-                            preset.as_str().bold().truecolor(r, g, b),
-                            theme.description.truecolor(150, 150, 150),
-                            mode.truecolor(100, 100, 100)
-                        );
+                    let presets = ThemePreset::all().into_iter().map(Theme::load_preset);
+                    let user: Vec<Theme> = file
+                        .themes
+                        .keys()
+                        .filter_map(|name| match Theme::resolve(name, &file) {
+                            Ok(theme) => Some(theme),
+                            Err(e) => {
+                                eprintln!("Warning: {:#}", e);
+                                None
+                            }
+                        })
+                        .collect();
+                    let presets: Vec<Theme> = presets
+                        .filter(|t| !file.themes.contains_key(&t.name))
+                        .collect();
+                    for (heading, themes) in [("", presets), ("User themes", user)] {
+                        if themes.is_empty() {
+                            continue;
+                        }
+                        if !heading.is_empty() {
+                            println!();
+                            println!("  {}", heading.bold());
+                        }
+                        for theme in themes {
+                            let (r, g, b) = parse_hex(&theme.colors.primary);
+                            let mode = if theme.is_dark { "dark" } else { "light" };
+                            let marker = if active_name == Some(theme.name.as_str()) {
+                                format!(" {}", "← active".truecolor(r, g, b))
+                            } else {
+                                String::new()
+                            };
+                            println!(
+                                "  {} {} - {} ({}){}",
+                                "●".truecolor(r, g, b),
+                                theme.name.bold().truecolor(r, g, b),
+                                theme.description.truecolor(150, 150, 150),
+                                mode.truecolor(100, 100, 100),
+                                marker
+                            );
+                        }
                     }
                     println!();
                     println!(
                         "{}",
-                        "Set theme via TERMGFX_THEME env var".truecolor(100, 100, 100)
+                        "Set theme via TERMGFX_THEME env var or 'termgfx theme set <name>'"
+                            .truecolor(100, 100, 100)
                     );
                     println!(
                         "{}",
@@ -1567,21 +3531,10 @@ fn main() {
                     );
                 }
                 Some(ThemeCommands::Preview { name }) => {
-                    let theme_name = name.as_deref().unwrap_or("dark");
-                    match ThemePreset::from_str(theme_name) {
-                        Some(preset) => {
-                            let theme = Theme::load_preset(preset);
-                            render_theme_preview(&theme);
-                        }
-                        None => {
-                            eprintln!("Error: Theme '{}' not found", theme_name);
-                            eprintln!("Available: dark, light, nord, dracula, monokai, solarized, gruvbox");
-                            std::process::exit(1);
-                        }
-                    }
+                    render_theme_preview(&resolve(name.as_deref().unwrap_or("dark")));
                 }
                 Some(ThemeCommands::Current) => {
-                    let theme = Theme::from_env();
+                    let theme = active.as_ref().map_or_else(Theme::dark, |(t, _)| t.clone());
                     let (r, g, b) = parse_hex(&theme.colors.primary);
                     println!(
                         "{} {} ({})",
@@ -1594,6 +3547,96 @@ fn main() {
                         format!("Set via: TERMGFX_THEME={}", theme.name).truecolor(100, 100, 100)
                     );
                 }
+                Some(ThemeCommands::Show { name }) => {
+                    let (theme, source) = match name {
+                        Some(name) => (resolve(&name), None),
+                        None => match &active {
+                            Some((theme, source)) => (theme.clone(), Some(*source)),
+                            None => (Theme::dark(), None),
+                        },
+                    };
+                    let (r, g, b) = parse_hex(&theme.colors.primary);
+                    println!(
+                        "{} {} ({})",
+                        "●".truecolor(r, g, b),
+                        theme.name.bold().truecolor(r, g, b),
+                        if theme.is_dark { "dark" } else { "light" }
+                    );
+                    println!("  {}", theme.description.truecolor(150, 150, 150));
+                    println!();
+                    println!("  {:<12}{}", "border", theme.border_style);
+                    println!("  {:<12}{}", "gradient", theme.gradient);
+                    let series = design::SeriesColors::parse(&theme.series_colors);
+                    let swatches: String = series
+                        .unwrap_or_default()
+                        .colors()
+                        .iter()
+                        .map(|c| "██".truecolor(c.r, c.g, c.b).to_string())
+                        .collect();
+                    println!("  {:<12}{} {}", "series", swatches, theme.series_colors);
+                    let c = &theme.colors;
+                    for (label, hex) in [
+                        ("primary", &c.primary),
+                        ("secondary", &c.secondary),
+                        ("success", &c.success),
+                        ("warning", &c.warning),
+                        ("danger", &c.danger),
+                        ("info", &c.info),
+                        ("background", &c.background),
+                        ("foreground", &c.foreground),
+                        ("surface", &c.surface),
+                        ("border", &c.border),
+                    ] {
+                        let (r, g, b) = parse_hex(hex);
+                        println!("  {:<12}{} {}", label, "██".truecolor(r, g, b), hex);
+                    }
+                    if let Some(source) = source {
+                        let from = match source {
+                            ThemeSource::Flag => "--theme".to_string(),
+                            ThemeSource::Env => "TERMGFX_THEME".to_string(),
+                            ThemeSource::File => ThemeFile::path()
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_default(),
+                        };
+                        println!();
+                        println!(
+                            "{}",
+                            format!("  Set via: {}", from).truecolor(100, 100, 100)
+                        );
+                    }
+                }
+                Some(ThemeCommands::Set { name }) => {
+                    if broken {
+                        // Saving would drop the user themes it couldn't read
+                        eprintln!("Error: Fix or remove the theme file before setting a theme");
+                        std::process::exit(1);
+                    }
+                    let theme = resolve(&name);
+                    let Some(path) = ThemeFile::path() else {
+                        eprintln!("Error: Could not determine config directory");
+                        std::process::exit(1);
+                    };
+                    let mut file = file.clone();
+                    file.theme = Some(theme.name.clone());
+                    if let Err(e) = file.save(&path) {
+                        eprintln!("Error: {:#}", e);
+                        std::process::exit(1);
+                    }
+                    let (r, g, b) = parse_hex(&theme.colors.primary);
+                    println!(
+                        "{} Theme set to {} in {}",
+                        "✓".green(),
+                        theme.name.bold().truecolor(r, g, b),
+                        path.display()
+                    );
+                    if std::env::var("TERMGFX_THEME").is_ok_and(|v| !v.is_empty()) {
+                        println!(
+                            "{}",
+                            "Note: TERMGFX_THEME is set and takes precedence"
+                                .truecolor(100, 100, 100)
+                        );
+                    }
+                }
             }
         }
         Commands::Checklist {
@@ -1621,9 +3664,26 @@ fn main() {
                 no_color,
             );
         }
+        Commands::CiComment {
+            command,
+            format,
+            output,
+            title,
+            upload,
+            background,
+        } => {
+            export::ci::render(&export::ci::CiOptions {
+                command: &command,
+                format: &format,
+                output: &output,
+                title: title.as_deref(),
+                upload: upload.as_deref(),
+                background: &background,
+                theme: cli.theme.as_deref(),
+            });
+        }
         Commands::Export { export_command } => {
             use export::{svg::SvgBuilder, ExportConfig, ExportFormat};
-            use std::fs::File;
             use std::io::{self, Write};
 
             match export_command {
@@ -1656,9 +3716,10 @@ fn main() {
                     let svg = builder.build();
                     match output {
                         Some(path) => {
-                            let mut file =
-                                File::create(&path).expect("Failed to create output file");
-                            file.write_all(svg.as_bytes()).expect("Failed to write SVG");
+                            if let Err(e) = output::atomic::write(&path, &svg) {
+                                eprintln!("Error: Failed to write {}: {}", path, e);
+                                std::process::exit(1);
+                            }
                             eprintln!("Exported to: {}", path);
                         }
                         None => {
@@ -1697,9 +3758,10 @@ fn main() {
                     let svg = builder.build();
                     match output {
                         Some(path) => {
-                            let mut file =
-                                File::create(&path).expect("Failed to create output file");
-                            file.write_all(svg.as_bytes()).expect("Failed to write SVG");
+                            if let Err(e) = output::atomic::write(&path, &svg) {
+                                eprintln!("Error: Failed to write {}: {}", path, e);
+                                std::process::exit(1);
+                            }
                             eprintln!("Exported to: {}", path);
                         }
                         None => {
@@ -1754,9 +3816,10 @@ fn main() {
                     let svg = builder.build();
                     match output {
                         Some(path) => {
-                            let mut file =
-                                File::create(&path).expect("Failed to create output file");
-                            file.write_all(svg.as_bytes()).expect("Failed to write SVG");
+                            if let Err(e) = output::atomic::write(&path, &svg) {
+                                eprintln!("Error: Failed to write {}: {}", path, e);
+                                std::process::exit(1);
+                            }
                             eprintln!("Exported to: {}", path);
                         }
                         None => {
@@ -1847,10 +3910,55 @@ fn main() {
                 }
             }
         }
+        Commands::EmojiWidths { detect, set, reset } => {
+            output::width::render(detect, &set, reset);
+        }
+        #[cfg(feature = "self-update")]
+        Commands::SelfUpdate { check } => {
+            output::update::render(check);
+        }
     }
 }
 
 /// Parse hex color string to RGB tuple
+/// Text for qr and barcode: the argument, or stdin without its final newline
+fn symbol_data(data: String) -> String {
+    if !output::input::is_stdin(&data) {
+        return data;
+    }
+    match output::input::read_text(&data) {
+        Ok(text) => text.trim_end_matches(['\r', '\n']).to_string(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Parse `--legend` for multi-series charts, exiting on an unknown name
+fn legend_position(name: &str) -> charts::legend::LegendPosition {
+    charts::legend::LegendPosition::from_name(name).unwrap_or_else(|| {
+        eprintln!(
+            "Error: Invalid legend position '{}' (use {})",
+            name,
+            charts::legend::POSITIONS.join(", ")
+        );
+        std::process::exit(1);
+    })
+}
+
+/// Parse `--easing`, exiting on an unknown curve
+fn easing(name: &str) -> animation::easing::Easing {
+    animation::easing::Easing::from_name(name).unwrap_or_else(|| {
+        eprintln!(
+            "Error: Invalid easing '{}' (use {})",
+            name,
+            animation::easing::EASINGS.join(", ")
+        );
+        std::process::exit(1);
+    })
+}
+
 fn parse_hex(hex: &str) -> (u8, u8, u8) {
     let hex = hex.trim_start_matches('#');
     if hex.len() != 6 {
@@ -0,0 +1,70 @@
+//! Crash-safe file writes
+//!
+//! Exports and saved settings are written to a temporary file next to the
+//! target and renamed over it once complete, so an interrupted run leaves
+//! either the old file or the new one, never half of an SVG or recording.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Write `contents` to `path` atomically, like `fs::write`
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    let tmp = temp_path(path);
+    let result = File::create(&tmp).and_then(|mut file| {
+        file.write_all(contents.as_ref())?;
+        file.sync_all()
+    });
+    match result.and_then(|_| fs::rename(&tmp, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            fs::remove_file(&tmp).ok();
+            Err(e)
+        }
+    }
+}
+
+/// Hidden sibling of `path`, unique to this process; writers that need a
+/// path of their own (ffmpeg) fill it and rename it over `path` themselves
+pub fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_replaces_file_and_leaves_no_temp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.svg");
+        fs::write(&path, "old").unwrap();
+
+        write(&path, "<svg/>").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "<svg/>");
+        let names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["out.svg"]);
+    }
+
+    #[test]
+    fn test_failed_write_keeps_old_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("taken");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("keep"), "x").unwrap();
+
+        // Renaming a file over a non-empty directory fails
+        assert!(write(&target, "new").is_err());
+        assert!(target.join("keep").exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
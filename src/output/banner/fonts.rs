@@ -0,0 +1,438 @@
+//! Large-letter fonts for banners
+//!
+//! The built-in fonts share one bitmap glyph set covering printable ASCII and
+//! differ in how pixels are drawn: solid blocks, a sheared italic, half-block
+//! cells at half the height, or blocks with a drop shadow. Standard FIGlet
+//! `.flf` files can be loaded too; they are laid out at full width (no
+//! kerning or smushing).
+
+use std::collections::HashMap;
+use std::fs;
+
+/// Names accepted by [`Font::from_spec`] besides `.flf` paths
+pub const BUILTIN_FONTS: [&str; 4] = ["block", "slant", "small", "3d"];
+
+/// Rows in the bitmap glyphs: five for capitals plus one for descenders
+const GLYPH_ROWS: usize = 6;
+
+/// Bitmap glyphs, rows separated by `|`; `#` is a lit pixel. Missing rows are blank.
+const GLYPHS: &[(char, &str)] = &[
+    (' ', "..."),
+    ('!', "#|#|#|.|#"),
+    ('"', "#.#|#.#"),
+    ('#', ".#.#.|#####|.#.#.|#####|.#.#."),
+    ('$', ".###|##..|.##.|..##|###."),
+    ('%', "##..#|##.#.|..#..|.#.##|#..##"),
+    ('&', ".##..|#..#.|.##..|#..#.|.##.#"),
+    ('\'', "#|#"),
+    ('(', ".#|#.|#.|#.|.#"),
+    (')', "#.|.#|.#|.#|#."),
+    ('*', ".....|#.#.#|.###.|#.#.#"),
+    ('+', "...|.#.|###|.#."),
+    (',', "..|..|..|..|.#|#."),
+    ('-', "...|...|###"),
+    ('.', ".|.|.|.|#"),
+    ('/', "....#|...#.|..#..|.#...|#...."),
+    ('0', ".##.|#.##|#..#|##.#|.##."),
+    ('1', ".#.|##.|.#.|.#.|###"),
+    ('2', "###.|...#|.##.|#...|####"),
+    ('3', "###.|...#|.##.|...#|###."),
+    ('4', "#..#|#..#|####|...#|...#"),
+    ('5', "####|#...|###.|...#|###."),
+    ('6', ".##.|#...|###.|#..#|.##."),
+    ('7', "####|...#|..#.|.#..|.#.."),
+    ('8', ".##.|#..#|.##.|#..#|.##."),
+    ('9', ".##.|#..#|.###|...#|.##."),
+    (':', ".|#|.|#"),
+    (';', "..|.#|..|.#|#."),
+    ('<', "..#|.#.|#..|.#.|..#"),
+    ('=', "...|###|...|###"),
+    ('>', "#..|.#.|..#|.#.|#.."),
+    ('?', "###.|...#|.##.|....|.#.."),
+    ('@', ".###.|#...#|#.###|#.##.|.###."),
+    ('A', ".##.|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|###."),
+    ('C', ".###|#...|#...|#...|.###"),
+    ('D', "###.|#..#|#..#|#..#|###."),
+    ('E', "####|#...|###.|#...|####"),
+    ('F', "####|#...|###.|#...|#..."),
+    ('G', ".###|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#"),
+    ('I', "###|.#.|.#.|.#.|###"),
+    ('J', "...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|####"),
+    ('M', "#...#|##.##|#.#.#|#...#|#...#"),
+    ('N', "#..#|##.#|#.##|#..#|#..#"),
+    ('O', ".##.|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|###.|#...|#..."),
+    ('Q', ".##.|#..#|#..#|#.#.|.#.#"),
+    ('R', "###.|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|.##.|...#|###."),
+    ('T', "#####|..#..|..#..|..#..|..#.."),
+    ('U', "#..#|#..#|#..#|#..#|.##."),
+    ('V', "#...#|#...#|#...#|.#.#.|..#.."),
+    ('W', "#...#|#...#|#.#.#|##.##|#...#"),
+    ('X', "#...#|.#.#.|..#..|.#.#.|#...#"),
+    ('Y', "#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|.##.|#...|####"),
+    ('[', "##|#.|#.|#.|##"),
+    ('\\', "#....|.#...|..#..|...#.|....#"),
+    (']', "##|.#|.#|.#|##"),
+    ('^', ".#.|#.#"),
+    ('_', "....|....|....|....|####"),
+    ('`', "#.|.#"),
+    ('a', "....|.###|#..#|#..#|.###"),
+    ('b', "#...|###.|#..#|#..#|###."),
+    ('c', "....|.###|#...|#...|.###"),
+    ('d', "...#|.###|#..#|#..#|.###"),
+    ('e', "....|.##.|####|#...|.###"),
+    ('f', "..##|.#..|####|.#..|.#.."),
+    ('g', "....|.###|#..#|.###|...#|###."),
+    ('h', "#...|###.|#..#|#..#|#..#"),
+    ('i', ".#.|...|##.|.#.|###"),
+    ('j', "..#|...|..#|..#|..#|##."),
+    ('k', "#...|#..#|###.|#.#.|#..#"),
+    ('l', "##.|.#.|.#.|.#.|###"),
+    ('m', ".....|##.#.|#.#.#|#.#.#|#.#.#"),
+    ('n', "....|###.|#..#|#..#|#..#"),
+    ('o', "....|.##.|#..#|#..#|.##."),
+    ('p', "....|###.|#..#|###.|#...|#..."),
+    ('q', "....|.###|#..#|.###|...#|...#"),
+    ('r', "....|#.##|##..|#...|#..."),
+    ('s', "....|.###|##..|..##|###."),
+    ('t', ".#..|####|.#..|.#..|..##"),
+    ('u', "....|#..#|#..#|#..#|.###"),
+    ('v', ".....|#...#|#...#|.#.#.|..#.."),
+    ('w', ".....|#...#|#.#.#|#.#.#|.#.#."),
+    ('x', "....|#..#|.##.|.##.|#..#"),
+    ('y', "....|#..#|#..#|.###|...#|###."),
+    ('z', "....|####|..#.|.#..|####"),
+    ('{', ".##|.#.|#..|.#.|.##"),
+    ('|', "#|#|#|#|#"),
+    ('}', "##.|.#.|..#|.#.|##."),
+    ('~', "....|.#.#|#.#."),
+];
+
+/// How a built-in font draws bitmap pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Builtin {
+    /// Two-column solid blocks
+    Block,
+    /// Blocks sheared one column per row
+    Slant,
+    /// Half-block cells: two pixel rows per line
+    Small,
+    /// Blocks with a shaded drop shadow
+    ThreeD,
+}
+
+/// A banner font
+#[derive(Debug, Clone)]
+pub enum Font {
+    Builtin(Builtin),
+    Figlet(FigletFont),
+}
+
+/// Glyphs parsed from a FIGlet `.flf` file
+#[derive(Debug, Clone)]
+pub struct FigletFont {
+    height: usize,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl Font {
+    /// A built-in font by name, or a FIGlet font loaded from a `.flf` path
+    pub fn from_spec(spec: &str) -> Result<Font, String> {
+        let builtin = match spec.to_lowercase().as_str() {
+            "block" => Some(Builtin::Block),
+            "slant" => Some(Builtin::Slant),
+            "small" => Some(Builtin::Small),
+            "3d" => Some(Builtin::ThreeD),
+            _ => None,
+        };
+        if let Some(builtin) = builtin {
+            return Ok(Font::Builtin(builtin));
+        }
+        if spec.ends_with(".flf") {
+            let content =
+                fs::read_to_string(spec).map_err(|e| format!("Cannot read '{}': {}", spec, e))?;
+            return FigletFont::parse(&content).map(Font::Figlet);
+        }
+        Err(format!(
+            "Unknown font '{}' (use {} or a .flf file)",
+            spec,
+            BUILTIN_FONTS.join(", ")
+        ))
+    }
+
+    /// Rows of `text` drawn in this font, all the same display width
+    pub fn render(&self, text: &str) -> Vec<String> {
+        let rows = match self {
+            Font::Builtin(style) => draw_bitmap(&bitmap(text), *style),
+            Font::Figlet(font) => font.render(text),
+        };
+        pad_rows(rows)
+    }
+
+    /// The font to fall back to when this one is too wide; `None` means
+    /// plain text
+    pub fn smaller(&self) -> Option<Font> {
+        match self {
+            Font::Builtin(Builtin::Small) | Font::Figlet(_) => None,
+            Font::Builtin(_) => Some(Font::Builtin(Builtin::Small)),
+        }
+    }
+}
+
+impl FigletFont {
+    /// Parse a FIGlet font; only the required ASCII 32-126 glyphs are read
+    pub fn parse(content: &str) -> Result<FigletFont, String> {
+        let mut lines = content.lines();
+        let header = lines.next().ok_or("Empty font file")?;
+        if !header.starts_with("flf2a") {
+            return Err("Not a FIGlet font (missing flf2a header)".to_string());
+        }
+        let hardblank = header.chars().nth(5).unwrap_or('$');
+        let fields: Vec<usize> = header
+            .split_whitespace()
+            .skip(1)
+            .map(|f| f.parse().unwrap_or(0))
+            .collect();
+        let height = fields
+            .first()
+            .copied()
+            .filter(|h| *h > 0)
+            .ok_or("Invalid font height")?;
+        let comment_lines = fields.get(4).copied().unwrap_or(0);
+
+        let mut lines = lines.skip(comment_lines);
+        let mut glyphs = HashMap::new();
+        for code in 32u8..=126 {
+            let mut rows = Vec::with_capacity(height);
+            for _ in 0..height {
+                let line = lines
+                    .next()
+                    .ok_or_else(|| format!("Font ends before '{}'", code as char))?;
+                rows.push(strip_endmarks(line).replace(hardblank, " "));
+            }
+            glyphs.insert(code as char, rows);
+        }
+        Ok(FigletFont { height, glyphs })
+    }
+
+    fn render(&self, text: &str) -> Vec<String> {
+        let blank = vec![String::new(); self.height];
+        let mut rows = vec![String::new(); self.height];
+        for ch in text.chars() {
+            let glyph = self
+                .glyphs
+                .get(&ch)
+                .or_else(|| self.glyphs.get(&'?'))
+                .unwrap_or(&blank);
+            for (row, part) in rows.iter_mut().zip(glyph) {
+                row.push_str(part);
+            }
+        }
+        rows
+    }
+}
+
+/// Drop the endmark character (the last one on the line) and its repeats
+fn strip_endmarks(line: &str) -> &str {
+    match line.chars().last() {
+        Some(mark) => line.trim_end_matches(mark),
+        None => line,
+    }
+}
+
+/// Pixel grid for `text`, one blank column between glyphs
+fn bitmap(text: &str) -> Vec<Vec<bool>> {
+    let mut grid = vec![Vec::new(); GLYPH_ROWS];
+    for (idx, ch) in text.chars().enumerate() {
+        let pattern = glyph(ch);
+        let width = pattern.split('|').map(str::len).max().unwrap_or(0);
+        let mut rows = pattern.split('|');
+        for row in grid.iter_mut() {
+            if idx > 0 {
+                row.push(false);
+            }
+            let bits = rows.next().unwrap_or("");
+            row.extend((0..width).map(|i| bits.as_bytes().get(i) == Some(&b'#')));
+        }
+    }
+    // Only keep the descender row when something uses it
+    if grid.last().is_some_and(|row| !row.contains(&true)) {
+        grid.pop();
+    }
+    grid
+}
+
+fn glyph(ch: char) -> &'static str {
+    let lookup = |c: char| GLYPHS.iter().find(|(g, _)| *g == c).map(|(_, p)| *p);
+    lookup(ch).or_else(|| lookup('?')).unwrap_or("")
+}
+
+fn draw_bitmap(grid: &[Vec<bool>], style: Builtin) -> Vec<String> {
+    let lit = |r: usize, c: usize| {
+        grid.get(r)
+            .and_then(|row| row.get(c))
+            .copied()
+            .unwrap_or(false)
+    };
+    let width = grid.first().map(Vec::len).unwrap_or(0);
+    let height = grid.len();
+
+    match style {
+        Builtin::Block => grid
+            .iter()
+            .map(|row| row.iter().map(|&on| if on { "██" } else { "  " }).collect())
+            .collect(),
+        Builtin::Slant => grid
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                let indent = " ".repeat(height - 1 - r);
+                let body: String = row.iter().map(|&on| if on { "██" } else { "  " }).collect();
+                format!("{}{}", indent, body)
+            })
+            .collect(),
+        Builtin::Small => (0..height)
+            .step_by(2)
+            .map(|r| {
+                (0..width)
+                    .map(|c| match (lit(r, c), lit(r + 1, c)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect(),
+        Builtin::ThreeD => (0..=height)
+            .map(|r| {
+                (0..=width)
+                    .map(|c| {
+                        if lit(r, c) {
+                            "██"
+                        } else if r > 0 && c > 0 && lit(r - 1, c - 1) {
+                            "░░"
+                        } else {
+                            "  "
+                        }
+                    })
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+/// Right-pad rows to a common width and drop trailing spaces shared by all
+fn pad_rows(rows: Vec<String>) -> Vec<String> {
+    let width = rows
+        .iter()
+        .map(|r| r.trim_end().chars().count())
+        .max()
+        .unwrap_or(0);
+    rows.into_iter()
+        .map(|r| {
+            let mut row: String = r.chars().take(width).collect();
+            let len = row.chars().count();
+            row.push_str(&" ".repeat(width - len));
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_printable_ascii_has_a_glyph() {
+        for code in 32u8..=126 {
+            let ch = code as char;
+            assert!(
+                GLYPHS.iter().any(|(g, _)| *g == ch),
+                "missing glyph for {:?}",
+                ch
+            );
+        }
+        for (ch, pattern) in GLYPHS {
+            assert!(pattern.split('|').count() <= GLYPH_ROWS, "{:?}", ch);
+        }
+    }
+
+    #[test]
+    fn test_block_font_draws_pixels() {
+        let rows = Font::Builtin(Builtin::Block).render("I");
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], "██████");
+        assert_eq!(rows[1], "  ██  ");
+    }
+
+    #[test]
+    fn test_descender_row_only_when_used() {
+        let block = Font::Builtin(Builtin::Block);
+        assert_eq!(block.render("ab").len(), 5);
+        assert_eq!(block.render("ag").len(), 6);
+    }
+
+    #[test]
+    fn test_small_font_halves_height() {
+        let rows = Font::Builtin(Builtin::Small).render("HI");
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], "█  █ ▀█▀");
+        assert_eq!(rows[1], "█▀▀█  █ ");
+        assert_eq!(rows[2], "▀  ▀ ▀▀▀");
+    }
+
+    #[test]
+    fn test_slant_and_3d_shapes() {
+        let slant = Font::Builtin(Builtin::Slant).render("|");
+        assert_eq!(
+            slant,
+            vec!["    ██", "   ██ ", "  ██  ", " ██   ", "██    "]
+        );
+
+        let shadow = Font::Builtin(Builtin::ThreeD).render(".");
+        assert_eq!(shadow.len(), 6);
+        assert_eq!(shadow[4], "██  ");
+        assert_eq!(shadow[5], "  ░░");
+    }
+
+    #[test]
+    fn test_rows_share_one_width() {
+        for name in BUILTIN_FONTS {
+            let rows = Font::from_spec(name).unwrap().render("Hello, World!");
+            let width = rows[0].chars().count();
+            assert!(rows.iter().all(|r| r.chars().count() == width), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_from_spec_rejects_unknown_font() {
+        assert!(Font::from_spec("gothic").is_err());
+        assert!(Font::from_spec("missing.flf").is_err());
+    }
+
+    #[test]
+    fn test_parse_figlet_font() {
+        // Two-row font: every glyph is "<c>@" / "<c>@@" with $ as hardblank
+        let mut flf = String::from("flf2a$ 2 1 4 0 1\ncomment line\n");
+        for code in 32u8..=126 {
+            let c = if code == b' ' { '$' } else { code as char };
+            flf.push_str(&format!("{}{}@\n{}{}@@\n", c, c, c, c));
+        }
+        let font = FigletFont::parse(&flf).unwrap();
+        let rows = Font::Figlet(font).render("a b");
+        assert_eq!(rows, vec!["aa  bb", "aa  bb"]);
+    }
+
+    #[test]
+    fn test_parse_figlet_rejects_bad_header() {
+        assert!(FigletFont::parse("not a font").is_err());
+        assert!(FigletFont::parse("flf2a$ 2 1 4 0 0\nA@\n").is_err());
+    }
+}
@@ -0,0 +1,426 @@
+pub mod fonts;
+
+use crate::animation::policy;
+use crate::output::color;
+use crate::output::gradient::{self, Gradient};
+use crate::output::width;
+use crate::text;
+use fonts::Font;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+struct BorderChars {
+    top_left: &'static str,
+    top_right: &'static str,
+    bottom_left: &'static str,
+    bottom_right: &'static str,
+    horizontal: &'static str,
+    vertical: &'static str,
+}
+
+impl BorderChars {
+    fn double() -> Self {
+        BorderChars {
+            top_left: "╔",
+            top_right: "╗",
+            bottom_left: "╚",
+            bottom_right: "╝",
+            horizontal: "═",
+            vertical: "║",
+        }
+    }
+
+    fn ascii() -> Self {
+        BorderChars {
+            top_left: "+",
+            top_right: "+",
+            bottom_left: "+",
+            bottom_right: "+",
+            horizontal: "-",
+            vertical: "|",
+        }
+    }
+}
+
+fn get_terminal_width() -> usize {
+    use crossterm::terminal;
+    if let Ok((width, _)) = terminal::size() {
+        width as usize
+    } else {
+        80
+    }
+}
+
+/// Color one banner line; lower lines (`position` toward 1.0) lean to the end color
+fn apply_gradient(line: &str, gradient: Gradient, position: f32) -> String {
+    gradient::paint_range(line, gradient, position / 2.0, (1.0 + position) / 2.0)
+}
+
+/// Horizontal placement of the banner relative to the terminal width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BannerAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl BannerAlign {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "left" => Some(BannerAlign::Left),
+            "center" | "centre" => Some(BannerAlign::Center),
+            "right" => Some(BannerAlign::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Placement and sizing options for a banner
+#[derive(Debug, Clone, Copy)]
+pub struct BannerLayout<'a> {
+    pub align: BannerAlign,
+    /// Maximum total width including the frame; text wraps to fit
+    pub max_width: Option<usize>,
+    /// Blank lines above/below the text (horizontal padding is twice this)
+    pub padding: usize,
+    /// Draw the title in large letters; `None` keeps plain text
+    pub font: Option<&'a Font>,
+}
+
+impl BannerLayout<'_> {
+    /// Narrowest banner that fits the frame and padding
+    pub fn min_width(&self) -> usize {
+        2 + self.padding * 4 + 1
+    }
+}
+
+impl Default for BannerLayout<'_> {
+    fn default() -> Self {
+        BannerLayout {
+            align: BannerAlign::Left,
+            max_width: None,
+            padding: 1,
+            font: None,
+        }
+    }
+}
+
+pub fn render(title: &str, gradient: Option<&str>) {
+    render_animated(title, gradient, false, 500);
+}
+
+/// Render banner with optional animation
+/// animation_time_ms: total animation duration in milliseconds (delay is calculated per line)
+pub fn render_animated(title: &str, gradient: Option<&str>, animate: bool, animation_time_ms: u64) {
+    render_with_layout(
+        title,
+        gradient,
+        &BannerLayout::default(),
+        animate,
+        animation_time_ms,
+    );
+}
+
+/// Render banner with alignment, width limit and padding
+pub fn render_with_layout(
+    title: &str,
+    gradient: Option<&str>,
+    layout: &BannerLayout,
+    animate: bool,
+    animation_time_ms: u64,
+) {
+    let animate = policy::gate(animate);
+    let lines = banner_lines(title, gradient, layout, get_terminal_width());
+
+    let delay = if animate && !lines.is_empty() {
+        Duration::from_millis(animation_time_ms / lines.len() as u64)
+    } else {
+        Duration::ZERO
+    };
+    let mut stdout = color::stdout();
+
+    for line in &lines {
+        print_animated(line, animate, delay, &mut stdout);
+    }
+}
+
+/// Build the colored, aligned banner lines for a terminal `term_width` wide
+pub fn banner_lines(
+    title: &str,
+    gradient: Option<&str>,
+    layout: &BannerLayout,
+    term_width: usize,
+) -> Vec<String> {
+    let gradient_colors = gradient.and_then(Gradient::from_name).unwrap_or_default();
+    let lines = build_lines(title, term_width, layout);
+    let banner_width = lines
+        .first()
+        .map(|(line, _)| text::display_width(line.as_str()))
+        .unwrap_or(0);
+    let indent = " ".repeat(align_offset(layout.align, term_width, banner_width));
+
+    lines
+        .iter()
+        .map(|(line, position)| {
+            format!(
+                "{}{}",
+                indent,
+                apply_gradient(line, gradient_colors, *position)
+            )
+        })
+        .collect()
+}
+
+/// Build the uncolored banner lines, each paired with its gradient position
+fn build_lines(title: &str, term_width: usize, layout: &BannerLayout) -> Vec<(String, f32)> {
+    let borders = if color::requested() {
+        BorderChars::double()
+    } else {
+        BorderChars::ascii()
+    };
+    let parts: Vec<&str> = title.split('|').collect();
+    let main_title = parts[0].trim();
+    let subtitle = parts.get(1).map(|s| s.trim());
+    let h_padding = layout.padding * 2;
+    let frame = 2 + h_padding * 2;
+    // A font-rendered title can wrap at any word, so only its widest word must fit
+    let widest_word = |font: &Font| {
+        main_title
+            .split_whitespace()
+            .map(|word| rendered_width(font, word))
+            .max()
+            .unwrap_or(0)
+    };
+    // Step down to smaller fonts, then plain text, until the title fits
+    let mut font = layout.font.cloned();
+    if let Some(max) = layout.max_width {
+        let text_limit = max.min(term_width).saturating_sub(frame);
+        while let Some(current) = &font {
+            if widest_word(current) <= text_limit {
+                break;
+            }
+            font = current.smaller();
+        }
+    }
+    let title_width = match &font {
+        Some(font) => widest_word(font),
+        None => text::display_width(main_title),
+    };
+    let subtitle_width = subtitle.map(text::display_width).unwrap_or(0);
+    let min_content_width = title_width.max(subtitle_width) + frame;
+
+    let banner_width = match layout.max_width {
+        Some(max) => {
+            let limit = max.min(term_width).max(frame + 1);
+            let preferred = term_width.min(100).min(limit);
+            if min_content_width > preferred {
+                min_content_width.min(limit)
+            } else {
+                preferred
+            }
+        }
+        None if term_width > min_content_width => term_width.min(100),
+        None => min_content_width,
+    };
+    let inner_width = banner_width.saturating_sub(2);
+    let text_width = banner_width.saturating_sub(frame);
+
+    let mut text_lines = match &font {
+        Some(font) => font_lines(font, main_title, text_width),
+        None => width::wrap_words(main_title, text_width),
+    };
+    if let Some(sub) = subtitle {
+        text_lines.extend(width::wrap_words(sub, text_width));
+    }
+
+    let mut rows: Vec<String> = Vec::new();
+    rows.extend(std::iter::repeat_n(String::new(), layout.padding));
+    rows.extend(text_lines);
+    rows.extend(std::iter::repeat_n(String::new(), layout.padding));
+
+    let mut lines = Vec::with_capacity(rows.len() + 2);
+    lines.push((
+        format!(
+            "{}{}{}",
+            borders.top_left,
+            borders.horizontal.repeat(inner_width),
+            borders.top_right
+        ),
+        0.0,
+    ));
+    let count = rows.len();
+    for (idx, row) in rows.iter().enumerate() {
+        let position = (idx + 1) as f32 / (count + 1) as f32;
+        lines.push((banner_line(row, inner_width, &borders), position));
+    }
+    lines.push((
+        format!(
+            "{}{}{}",
+            borders.bottom_left,
+            borders.horizontal.repeat(inner_width),
+            borders.bottom_right
+        ),
+        1.0,
+    ));
+    lines
+}
+
+fn rendered_width(font: &Font, text: &str) -> usize {
+    font.render(text)
+        .first()
+        .map(|row| text::display_width(row))
+        .unwrap_or(0)
+}
+
+/// Rows of `title` drawn in `font`, wrapped at words to fit `max_width`
+fn font_lines(font: &Font, title: &str, max_width: usize) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    for word in title.split_whitespace() {
+        match segments.last_mut() {
+            Some(last) if rendered_width(font, &format!("{} {}", last, word)) <= max_width => {
+                last.push(' ');
+                last.push_str(word);
+            }
+            _ => segments.push(word.to_string()),
+        }
+    }
+    segments
+        .iter()
+        .flat_map(|segment| font.render(segment))
+        .collect()
+}
+
+/// Column at which a banner of `banner_width` starts for the given alignment
+fn align_offset(align: BannerAlign, term_width: usize, banner_width: usize) -> usize {
+    let free = term_width.saturating_sub(banner_width);
+    match align {
+        BannerAlign::Left => 0,
+        BannerAlign::Center => free / 2,
+        BannerAlign::Right => free,
+    }
+}
+
+fn print_animated(text: &str, animate: bool, delay: Duration, stdout: &mut color::Stdout) {
+    println!("{}", text);
+    if animate {
+        stdout.flush().unwrap();
+        thread::sleep(delay);
+    }
+}
+
+fn banner_line(text: &str, width: usize, borders: &BorderChars) -> String {
+    let text_width = text::display_width(text);
+    let available_space = width.saturating_sub(text_width);
+    let left_padding = available_space / 2;
+    let right_padding = available_space - left_padding;
+    format!(
+        "{}{}{}{}{}",
+        borders.vertical,
+        " ".repeat(left_padding),
+        text,
+        " ".repeat(right_padding),
+        borders.vertical
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_lines_respects_max_width() {
+        let layout = BannerLayout {
+            max_width: Some(16),
+            ..BannerLayout::default()
+        };
+        let lines = build_lines("A very long banner title", 120, &layout);
+        for (line, _) in &lines {
+            assert_eq!(text::display_width(line.as_str()), 16);
+        }
+    }
+
+    #[test]
+    fn test_build_lines_padding() {
+        let layout = BannerLayout {
+            padding: 3,
+            ..BannerLayout::default()
+        };
+        // top + 3 blank + title + 3 blank + bottom
+        assert_eq!(build_lines("Hi", 80, &layout).len(), 9);
+    }
+
+    #[test]
+    fn test_build_lines_with_font() {
+        let font = Font::from_spec("small").unwrap();
+        let layout = BannerLayout {
+            font: Some(&font),
+            ..BannerLayout::default()
+        };
+        // top + blank + 3 font rows + blank + bottom
+        let lines = build_lines("HI", 80, &layout);
+        assert_eq!(lines.len(), 7);
+        assert!(lines[2].0.contains("█  █ ▀█▀"));
+    }
+
+    #[test]
+    fn test_font_title_wraps_at_words() {
+        let font = Font::from_spec("small").unwrap();
+        let layout = BannerLayout {
+            font: Some(&font),
+            max_width: Some(34),
+            ..BannerLayout::default()
+        };
+        // Each word fits on its own, both together do not
+        let lines = build_lines("HELLO WORLD", 120, &layout);
+        assert_eq!(lines.len(), 2 + 2 + 6);
+        for (line, _) in &lines {
+            assert_eq!(text::display_width(line.as_str()), 34);
+        }
+    }
+
+    #[test]
+    fn test_font_downscales_to_fit_max_width() {
+        let font = Font::from_spec("block").unwrap();
+        let small = Font::from_spec("small").unwrap();
+        let small_width = rendered_width(&small, "HI");
+        assert!(rendered_width(&font, "HI") > small_width);
+
+        // Block is too wide, small fits: top + blank + 3 small rows + blank + bottom
+        let layout = BannerLayout {
+            font: Some(&font),
+            max_width: Some(small_width + 6),
+            ..BannerLayout::default()
+        };
+        let lines = build_lines("HI", 120, &layout);
+        assert_eq!(lines.len(), 7);
+        assert!(lines[2].0.contains("█  █ ▀█▀"));
+
+        // No font fits: plain text
+        let layout = BannerLayout {
+            max_width: Some(small_width + 5),
+            ..layout
+        };
+        let lines = build_lines("HI", 120, &layout);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].0.contains("HI"));
+    }
+
+    #[test]
+    fn test_min_width() {
+        assert_eq!(BannerLayout::default().min_width(), 7);
+        let layout = BannerLayout {
+            padding: 0,
+            ..BannerLayout::default()
+        };
+        assert_eq!(layout.min_width(), 3);
+    }
+
+    #[test]
+    fn test_align_offset() {
+        assert_eq!(align_offset(BannerAlign::Left, 100, 40), 0);
+        assert_eq!(align_offset(BannerAlign::Center, 100, 40), 30);
+        assert_eq!(align_offset(BannerAlign::Right, 100, 40), 60);
+        assert_eq!(align_offset(BannerAlign::Right, 30, 40), 0);
+    }
+}
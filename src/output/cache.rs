@@ -0,0 +1,361 @@
+//! Opt-in render cache (`--cache 5m`)
+//!
+//! The command runs once in a child process with stdout captured, and the
+//! bytes are stored under the user cache directory. Later runs with the same
+//! arguments, piped input, file contents, theme, color policy and terminal
+//! size replay them until the entry is older than the TTL, so dashboards and
+//! prompts that redraw the same big table or image skip the work.
+
+use crate::design::theme;
+use crate::output::{atomic, color, watch};
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+/// Environment variable overriding where entries are stored
+pub const CACHE_DIR_ENV: &str = "TERMGFX_CACHE_DIR";
+
+/// Commands whose output depends only on their arguments and input, each
+/// with the flags that make it live instead (wait for keys, follow a source)
+///
+/// Anything missing here is treated as live: it waits for input, shows the
+/// time, or has side effects, so its output can't be rendered headless.
+pub const STATIC_COMMANDS: &[(&str, &[&str])] = &[
+    ("box", &[]),
+    ("danger-zone", &[]),
+    ("banner", &[]),
+    ("text", &[]),
+    ("progress", &[]),
+    ("chart", &[]),
+    ("image", &[]),
+    ("barcode", &[]),
+    ("qr", &[]),
+    ("sparkline", &["--source"]),
+    ("diff", &["--interactive", "-i"]),
+    ("md", &[]),
+    ("code", &[]),
+    ("log", &["--follow", "-f"]),
+    ("table", &["--interactive", "-i"]),
+    ("tree", &[]),
+    ("timeline", &[]),
+    ("gauge", &["--source"]),
+    ("battery", &[]),
+    ("temps", &[]),
+    ("heatmap", &[]),
+    ("calendar", &[]),
+    ("join", &[]),
+    ("columns", &[]),
+    ("stack", &[]),
+    ("style", &[]),
+    ("palette", &[]),
+    ("stats", &[]),
+    ("preview", &[]),
+    ("regex-filter", &[]),
+];
+
+/// Commands that page long output on a terminal unless `--no-pager` is given
+const PAGED_COMMANDS: &[&str] = &["code", "log"];
+
+/// Whether `command` prints its output once and exits, given the rest of
+/// the command line
+pub fn renders_once(command: &str, args: &[String]) -> bool {
+    let Some((_, live)) = STATIC_COMMANDS.iter().find(|(name, _)| *name == command) else {
+        return false;
+    };
+    !args
+        .iter()
+        .any(|arg| live.contains(&arg.split('=').next().unwrap_or(arg)))
+}
+
+/// Whether `--cache` can store and replay this run's output: it renders
+/// once, isn't animated and doesn't open a pager
+pub fn replayable(command: &str, args: &[String]) -> bool {
+    let has = |flag: &str| {
+        args.iter()
+            .any(|arg| arg.split('=').next().unwrap_or(arg) == flag)
+    };
+    renders_once(command, args)
+        && !has("--animate")
+        && (!PAGED_COMMANDS.contains(&command) || has("--no-pager"))
+}
+
+/// Parse a TTL like "30s", "5m" or "1h"
+pub fn parse_ttl(ttl: &str) -> Result<Duration, String> {
+    if ttl.trim().starts_with('-') {
+        return Err(format!("Invalid cache TTL: {}", ttl));
+    }
+    watch::parse_interval(ttl).map_err(|_| format!("Invalid cache TTL: {}", ttl))
+}
+
+/// Directory holding cached renders
+pub fn dir() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CACHE_DIR_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    Some(dirs::cache_dir()?.join("termgfx").join("render"))
+}
+
+/// `args` (the command line after the program name) without `flag` and its
+/// value, e.g. `--cache 5m` or `--cache=5m`
+pub fn strip_flag(args: &[String], flag: &str) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == flag {
+            iter.next();
+        } else if !arg
+            .strip_prefix(flag)
+            .is_some_and(|rest| rest.starts_with('='))
+        {
+            out.push(arg.clone());
+        }
+    }
+    out
+}
+
+/// 64-bit FNV-1a; stable across builds, unlike `DefaultHasher`
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Add a length-prefixed field so ("ab", "c") and ("a", "bc") differ
+    fn field(&mut self, bytes: &[u8]) {
+        for &b in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Cache key for running `args` with `stdin` as input
+pub fn key(args: &[String], stdin: &[u8]) -> String {
+    let mut hash = Fnv::new();
+    hash.field(env!("CARGO_PKG_VERSION").as_bytes());
+    for arg in args {
+        hash.field(arg.as_bytes());
+        // Inputs read from files count as much as the arguments naming them,
+        // whether given on their own or as `--file=data.csv`
+        let path = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with('-') => value,
+            _ => arg.as_str(),
+        };
+        if Path::new(path).is_file() {
+            hash.field(&fs::read(path).unwrap_or_default());
+        }
+    }
+    hash.field(stdin);
+    hash.field(&[color::enabled() as u8]);
+    hash.field(format!("{:?}", color::depth()).as_bytes());
+    if let Some(active) = theme::active() {
+        hash.field(serde_json::to_string(active).unwrap_or_default().as_bytes());
+    }
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((0, 0));
+    hash.field(&cols.to_le_bytes());
+    hash.field(&rows.to_le_bytes());
+    format!("{:016x}", hash.0)
+}
+
+/// Stored output for `key` if it is younger than `ttl`
+fn lookup(dir: &Path, key: &str, ttl: Duration) -> Option<Vec<u8>> {
+    let path = dir.join(key);
+    let modified = fs::metadata(&path).ok()?.modified().ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if age > ttl {
+        return None;
+    }
+    fs::read(path).ok()
+}
+
+/// Store `output` for `key`, replacing the entry in one step
+fn store(dir: &Path, key: &str, output: &[u8]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    atomic::write(dir.join(key), output)
+}
+
+/// Run `command` through the cache and exit with its status
+///
+/// `args` is the full command line after the program name, still holding
+/// the `--cache` flag.
+pub fn run(command: &str, ttl: &str, args: &[String]) -> ! {
+    let ttl = match parse_ttl(ttl) {
+        Ok(ttl) => ttl,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if !renders_once(command, args) {
+        eprintln!(
+            "Error: --cache only applies to static output; '{}' is live",
+            command
+        );
+        std::process::exit(1);
+    }
+    if !replayable(command, args) {
+        eprintln!("Error: --cache can't replay an animation or the pager; drop --animate or add --no-pager");
+        std::process::exit(1);
+    }
+    let Some(dir) = dir() else {
+        eprintln!("Error: Could not determine cache directory");
+        std::process::exit(1);
+    };
+
+    let args = strip_flag(args, "--cache");
+    let mut stdin = Vec::new();
+    if !io::stdin().is_terminal() {
+        if let Err(e) = io::stdin().read_to_end(&mut stdin) {
+            eprintln!("Error: Failed to read stdin: {}", e);
+            std::process::exit(1);
+        }
+    }
+    let key = key(&args, &stdin);
+
+    if let Some(output) = lookup(&dir, &key, ttl) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(&output).and_then(|_| stdout.flush());
+        std::process::exit(0);
+    }
+
+    let output = env::current_exe().and_then(|exe| {
+        let mut child = Command::new(exe)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        if let Some(mut pipe) = child.stdin.take() {
+            // The child may exit without reading all of it
+            let _ = pipe.write_all(&stdin);
+        }
+        child.wait_with_output()
+    });
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut stdout = io::stdout();
+    let _ = stdout
+        .write_all(&output.stdout)
+        .and_then(|_| stdout.flush());
+    if !output.status.success() {
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    if let Err(e) = store(&dir, &key, &output.stdout) {
+        eprintln!("Warning: Could not write render cache: {}", e);
+    }
+    std::process::exit(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_ttl("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_ttl("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_ttl("-1s").unwrap_err(), "Invalid cache TTL: -1s");
+        assert!(parse_ttl("soon").is_err());
+    }
+
+    #[test]
+    fn test_strip_flag() {
+        assert_eq!(
+            strip_flag(
+                &args(&["--cache", "5m", "table", "--cache=1h", "-f", "x"]),
+                "--cache"
+            ),
+            args(&["table", "-f", "x"])
+        );
+        assert_eq!(
+            strip_flag(
+                &args(&["table", "--cached=x", "--export", "a.svg"]),
+                "--cache"
+            ),
+            args(&["table", "--cached=x", "--export", "a.svg"])
+        );
+    }
+
+    #[test]
+    fn test_key_depends_on_args_and_input() {
+        let base = key(&args(&["box", "hi"]), b"");
+        assert_eq!(base, key(&args(&["box", "hi"]), b""));
+        assert_ne!(base, key(&args(&["box", "hi!"]), b""));
+        assert_ne!(base, key(&args(&["box", "hi"]), b"data"));
+        assert_ne!(key(&args(&["ab", "c"]), b""), key(&args(&["a", "bc"]), b""));
+    }
+
+    #[test]
+    fn test_key_covers_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, "a,b\n1,2\n").unwrap();
+        let cmd = args(&["table", "-f", path.to_str().unwrap()]);
+        let before = key(&cmd, b"");
+        fs::write(&path, "a,b\n3,4\n").unwrap();
+        assert_ne!(before, key(&cmd, b""));
+    }
+
+    #[test]
+    fn test_key_covers_files_given_with_equals() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, "a,b\n1,2\n").unwrap();
+        let cmd = args(&["table", &format!("--file={}", path.display())]);
+        let before = key(&cmd, b"");
+        fs::write(&path, "a,b\n3,4\n").unwrap();
+        assert_ne!(before, key(&cmd, b""));
+    }
+
+    #[test]
+    fn test_replayable_commands() {
+        assert!(replayable("box", &args(&["box", "hi"])));
+        assert!(!replayable("json", &args(&["json"])));
+        assert!(!replayable(
+            "log",
+            &args(&["log", "a.log", "-f", "--no-pager"])
+        ));
+        assert!(!replayable("gauge", &args(&["gauge", "--source=uptime"])));
+        assert!(!replayable("chart", &args(&["chart", "bar", "--animate"])));
+        assert!(!replayable("code", &args(&["code", "main.rs"])));
+        assert!(replayable(
+            "code",
+            &args(&["code", "main.rs", "--no-pager"])
+        ));
+        // Live flags only count for the commands that have them
+        assert!(renders_once("chart", &args(&["chart", "bar", "--animate"])));
+        assert!(renders_once("box", &args(&["box", "-f"])));
+        assert!(!renders_once("table", &args(&["table", "-i"])));
+        assert!(!renders_once("diff", &args(&["diff", "--interactive"])));
+    }
+
+    #[test]
+    fn test_store_and_lookup_respect_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        store(dir.path(), "k", b"rendered").unwrap();
+        assert_eq!(
+            lookup(dir.path(), "k", Duration::from_secs(60)).as_deref(),
+            Some(&b"rendered"[..])
+        );
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(lookup(dir.path(), "k", Duration::from_millis(1)), None);
+        assert_eq!(lookup(dir.path(), "missing", Duration::from_secs(60)), None);
+    }
+}
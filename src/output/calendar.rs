@@ -0,0 +1,283 @@
+use crate::output::{heatmap, input};
+use std::collections::HashMap;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Render a GitHub-contribution style calendar heatmap
+pub fn render(
+    data: Option<&str>,
+    file: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    title: Option<&str>,
+    colors: &str,
+) {
+    let raw = match (data, file) {
+        (Some(d), _) => d.to_string(),
+        (None, Some(f)) => match input::read_text(f) {
+            Ok(content) => content.lines().collect::<Vec<_>>().join(","),
+            Err(e) => {
+                eprintln!("Error reading file: {}", e);
+                std::process::exit(1);
+            }
+        },
+        (None, None) => {
+            eprintln!("Error: Either --data or --file is required");
+            std::process::exit(1);
+        }
+    };
+
+    match build(&raw, from, to, title, colors) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Build the calendar lines for "YYYY-MM-DD:value" entries
+fn build(
+    raw: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+    title: Option<&str>,
+    colors: &str,
+) -> Result<Vec<String>, String> {
+    let entries = parse_entries(raw)?;
+    if entries.is_empty() && (from.is_none() || to.is_none()) {
+        return Err("Empty data provided".to_string());
+    }
+
+    let start = match from {
+        Some(s) => parse_date(s).ok_or_else(|| format!("Invalid --from date '{}'", s))?,
+        None => *entries.keys().min().unwrap(),
+    };
+    let end = match to {
+        Some(s) => parse_date(s).ok_or_else(|| format!("Invalid --to date '{}'", s))?,
+        None => *entries.keys().max().unwrap(),
+    };
+    if end < start {
+        return Err("--to date is before --from date".to_string());
+    }
+
+    let max_val = entries
+        .iter()
+        .filter(|(day, _)| **day >= start && **day <= end)
+        .map(|(_, v)| *v)
+        .fold(0.0_f64, f64::max);
+
+    // Columns are weeks starting on Sunday
+    let grid_start = start - weekday(start) as i64;
+    let weeks = ((end - grid_start) / 7 + 1) as usize;
+
+    let mut lines = Vec::new();
+    if let Some(t) = title {
+        lines.push(String::new());
+        lines.push(format!("  {}", t));
+        lines.push(String::new());
+    }
+
+    // Month labels above the first week and each week containing the 1st
+    let mut month_row = vec![' '; weeks * 2 + 2];
+    for week in 0..weeks {
+        let week_start = grid_start + week as i64 * 7;
+        let label_day = if week == 0 {
+            Some(start)
+        } else {
+            (week_start..week_start + 7)
+                .filter(|d| *d <= end)
+                .find(|d| civil_from_days(*d).2 == 1)
+        };
+        if let Some(day) = label_day {
+            let (_, month, _) = civil_from_days(day);
+            let col = week * 2;
+            if month_row[col.saturating_sub(1)..]
+                .iter()
+                .take(4)
+                .all(|c| *c == ' ')
+            {
+                for (i, ch) in MONTHS[(month - 1) as usize].chars().enumerate() {
+                    month_row[col + i] = ch;
+                }
+            }
+        }
+    }
+    lines.push(format!(
+        "      {}",
+        month_row.iter().collect::<String>().trim_end()
+    ));
+
+    let day_labels = ["", "Mon", "", "Wed", "", "Fri", ""];
+    for (dow, label) in day_labels.iter().enumerate() {
+        let mut line = format!("  {:<3} ", label);
+        for week in 0..weeks {
+            let day = grid_start + (week * 7 + dow) as i64;
+            if day < start || day > end {
+                line.push_str("  ");
+                continue;
+            }
+            match entries.get(&day) {
+                Some(&value) if value > 0.0 && max_val > 0.0 => {
+                    let normalized = value / max_val;
+                    line.push_str(&heatmap::paint(level_block(normalized), normalized, colors));
+                }
+                _ => line.push_str("\x1b[90m·\x1b[0m"),
+            }
+            line.push(' ');
+        }
+        lines.push(line.trim_end().to_string());
+    }
+
+    // Legend
+    let mut legend = String::from("      Less ");
+    for normalized in [0.25, 0.5, 0.75, 1.0] {
+        legend.push_str(&heatmap::paint(level_block(normalized), normalized, colors));
+        legend.push(' ');
+    }
+    legend.push_str("More");
+    lines.push(String::new());
+    lines.push(legend);
+
+    Ok(lines)
+}
+
+fn level_block(normalized: f64) -> &'static str {
+    if normalized <= 0.25 {
+        "░"
+    } else if normalized <= 0.5 {
+        "▒"
+    } else if normalized <= 0.75 {
+        "▓"
+    } else {
+        "█"
+    }
+}
+
+/// Parse "date:value" pairs, summing values that share a date
+fn parse_entries(raw: &str) -> Result<HashMap<i64, f64>, String> {
+    let mut entries = HashMap::new();
+    for pair in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (date, value) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid entry '{}' (use YYYY-MM-DD:value)", pair))?;
+        let day = parse_date(date).ok_or_else(|| format!("Invalid date '{}'", date.trim()))?;
+        let value: f64 = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' for {}", value.trim(), date.trim()))?;
+        *entries.entry(day).or_insert(0.0) += value;
+    }
+    Ok(entries)
+}
+
+/// Parse "YYYY-MM-DD" into days since 1970-01-01
+fn parse_date(s: &str) -> Option<i64> {
+    let mut parts = s.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day))
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of `days_from_civil`
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Day of week with Sunday = 0
+fn weekday(days: i64) -> u32 {
+    // 1970-01-01 was a Thursday
+    (days + 4).rem_euclid(7) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_roundtrip() {
+        let days = parse_date("2024-02-29").unwrap();
+        assert_eq!(civil_from_days(days), (2024, 2, 29));
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+    }
+
+    #[test]
+    fn test_parse_date_invalid() {
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("not-a-date"), None);
+    }
+
+    #[test]
+    fn test_weekday() {
+        // 2024-01-07 was a Sunday
+        assert_eq!(weekday(parse_date("2024-01-07").unwrap()), 0);
+        assert_eq!(weekday(parse_date("2024-01-10").unwrap()), 3);
+    }
+
+    #[test]
+    fn test_parse_entries_sums_duplicates() {
+        let entries = parse_entries("2024-01-05:3, 2024-01-05:2").unwrap();
+        assert_eq!(entries[&parse_date("2024-01-05").unwrap()], 5.0);
+    }
+
+    #[test]
+    fn test_build_has_weekday_rows_and_legend() {
+        let lines = build("2024-01-05:3,2024-02-10:10", None, None, None, "viridis").unwrap();
+        assert!(lines[0].contains("Jan"));
+        assert!(lines[0].contains("Feb"));
+        assert!(lines.iter().any(|l| l.trim_start().starts_with("Wed")));
+        assert!(lines.last().unwrap().contains("Less"));
+    }
+
+    #[test]
+    fn test_build_rejects_reversed_range() {
+        let result = build(
+            "2024-01-05:3",
+            Some("2024-02-01"),
+            Some("2024-01-01"),
+            None,
+            "viridis",
+        );
+        assert!(result.is_err());
+    }
+}
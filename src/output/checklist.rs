@@ -1,7 +1,7 @@
+use crate::text;
 use owo_colors::OwoColorize;
 use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
-use unicode_width::UnicodeWidthStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChecklistItem {
@@ -55,22 +55,19 @@ fn calculate_widths(items: &[ChecklistItem], headers: &[String]) -> (usize, Vec<
     // Label width (checkbox + space + label)
     let label_width = items
         .iter()
-        .map(|i| UnicodeWidthStr::width(i.label.as_str()))
+        .map(|i| text::display_width(&i.label))
         .max()
         .unwrap_or(10);
 
     // Column widths
-    let mut col_widths: Vec<usize> = headers
-        .iter()
-        .map(|h| UnicodeWidthStr::width(h.as_str()))
-        .collect();
+    let mut col_widths: Vec<usize> = headers.iter().map(|h| text::display_width(h)).collect();
 
     for item in items {
         for (i, col) in item.columns.iter().enumerate() {
             if i < col_widths.len() {
-                col_widths[i] = col_widths[i].max(UnicodeWidthStr::width(col.as_str()));
+                col_widths[i] = col_widths[i].max(text::display_width(col));
             } else {
-                col_widths.push(UnicodeWidthStr::width(col.as_str()));
+                col_widths.push(text::display_width(col));
             }
         }
     }
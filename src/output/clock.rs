@@ -0,0 +1,232 @@
+//! Big digital clock drawn with the banner fonts, updated every second
+
+use crate::animation::engine::Animator;
+use crate::output::banner::fonts::Font;
+use crate::output::calendar::civil_from_days;
+use crate::text::display_width;
+use owo_colors::OwoColorize;
+use std::io::IsTerminal;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Where the clock's time is taken from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Zone {
+    Local,
+    /// Seconds east of UTC
+    Fixed(i64),
+    /// An IANA name such as `Europe/Paris`, looked up in the system tzdata
+    Named(String),
+}
+
+impl Zone {
+    /// `local`, `UTC`, an offset like `+05:30` or `-8`, or an IANA name
+    pub fn parse(spec: &str) -> Result<Zone, String> {
+        let spec = spec.trim();
+        match spec.to_lowercase().as_str() {
+            "local" => return Ok(Zone::Local),
+            "utc" | "gmt" | "z" => return Ok(Zone::Fixed(0)),
+            _ => {}
+        }
+        if let Some(offset) = parse_offset(spec) {
+            return Ok(Zone::Fixed(offset));
+        }
+        if cfg!(unix) && zoneinfo_exists(spec) {
+            return Ok(Zone::Named(spec.to_string()));
+        }
+        Err(format!(
+            "Unknown timezone '{}' (use local, UTC, +HH:MM or a name like Europe/Paris)",
+            spec
+        ))
+    }
+
+    /// Seconds east of UTC at the instant `utc`
+    fn offset(&self, utc: i64) -> i64 {
+        match self {
+            Zone::Fixed(offset) => *offset,
+            Zone::Local | Zone::Named(_) => local_offset(utc),
+        }
+    }
+}
+
+/// Draw the time in `font` until Ctrl+C; when stdout isn't a terminal, print
+/// it once
+pub fn render(font: &Font, twelve_hour: bool, seconds: bool, date: bool, zone: &Zone) {
+    #[cfg(unix)]
+    if let Zone::Named(name) = zone {
+        // localtime_r reads the zone from TZ only once it's loaded; localtime
+        // reloads it as if by tzset, which libc binds on Windows only
+        std::env::set_var("TZ", name);
+        unsafe { libc::localtime(&0) };
+    }
+
+    let once = !std::io::stdout().is_terminal();
+    let animator = Animator::new(200);
+    animator.run_block(|frame, _| {
+        if once && frame > 0 {
+            return None;
+        }
+        let utc = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let local = utc + zone.offset(utc);
+        Some(clock_lines(font, local, twelve_hour, seconds, date, zone))
+    });
+}
+
+/// The clock for `local` (seconds since the epoch, already shifted to the zone)
+fn clock_lines(
+    font: &Font,
+    local: i64,
+    twelve_hour: bool,
+    seconds: bool,
+    date: bool,
+    zone: &Zone,
+) -> Vec<String> {
+    let rows = font.render(&time_text(local, twelve_hour, seconds));
+    let width = rows.iter().map(|r| display_width(r)).max().unwrap_or(0);
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|row| row.trim_end().cyan().bold().to_string())
+        .collect();
+
+    if date {
+        let mut text = date_text(local);
+        if let Zone::Named(name) = zone {
+            text = format!("{} · {}", text, name);
+        }
+        let pad = width.saturating_sub(display_width(&text)) / 2;
+        lines.push(format!("{}{}", " ".repeat(pad), text.dimmed()));
+    }
+    lines
+}
+
+/// `14:05`, `14:05:09`, `2:05 PM` or `2:05:09 PM`
+fn time_text(local: i64, twelve_hour: bool, seconds: bool) -> String {
+    let secs = local.rem_euclid(86_400);
+    let (hour, minute, second) = (secs / 3600, secs / 60 % 60, secs % 60);
+    let seconds = if seconds {
+        format!(":{:02}", second)
+    } else {
+        String::new()
+    };
+    if twelve_hour {
+        let suffix = if hour < 12 { "AM" } else { "PM" };
+        let hour = match hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        format!("{}:{:02}{} {}", hour, minute, seconds, suffix)
+    } else {
+        format!("{:02}:{:02}{}", hour, minute, seconds)
+    }
+}
+
+/// e.g. `Sunday, 18 October 2026`
+fn date_text(local: i64) -> String {
+    let days = local.div_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    // 1970-01-01 was a Thursday
+    let weekday = WEEKDAYS[(days + 4).rem_euclid(7) as usize];
+    format!(
+        "{}, {} {} {}",
+        weekday,
+        day,
+        MONTHS[month as usize - 1],
+        year
+    )
+}
+
+/// `+05:30`, `-0800` or `+5` as seconds east of UTC
+fn parse_offset(spec: &str) -> Option<i64> {
+    let sign = match spec.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let rest = &spec[1..];
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    (hours <= 14 && minutes < 60).then_some(sign * (hours * 3600 + minutes * 60))
+}
+
+fn zoneinfo_exists(name: &str) -> bool {
+    let dir = std::env::var("TZDIR").unwrap_or_else(|_| "/usr/share/zoneinfo".to_string());
+    !name.contains("..") && std::path::Path::new(&dir).join(name).is_file()
+}
+
+#[cfg(unix)]
+fn local_offset(utc: i64) -> i64 {
+    let time = utc as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return 0;
+    }
+    tm.tm_gmtoff as i64
+}
+
+/// Without the C library's zone rules, local time is UTC
+#[cfg(not(unix))]
+fn local_offset(_utc: i64) -> i64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_text() {
+        // 2026-10-18 14:05:09 UTC
+        let t = 1_792_332_309;
+        assert_eq!(time_text(t, false, false), "14:05");
+        assert_eq!(time_text(t, false, true), "14:05:09");
+        assert_eq!(time_text(t, true, false), "2:05 PM");
+        assert_eq!(time_text(t - 14 * 3600, true, true), "12:05:09 AM");
+    }
+
+    #[test]
+    fn test_date_text() {
+        assert_eq!(date_text(0), "Thursday, 1 January 1970");
+        assert_eq!(date_text(1_792_332_309), "Sunday, 18 October 2026");
+    }
+
+    #[test]
+    fn test_zone_parse() {
+        assert_eq!(Zone::parse("UTC"), Ok(Zone::Fixed(0)));
+        assert_eq!(Zone::parse("+05:30"), Ok(Zone::Fixed(19_800)));
+        assert_eq!(Zone::parse("-0800"), Ok(Zone::Fixed(-28_800)));
+        assert_eq!(Zone::parse("+5"), Ok(Zone::Fixed(18_000)));
+        assert!(Zone::parse("Mars/Olympus").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::interactive::pager::Pager;
+use crate::output::input;
+use crate::output::syntax::{self, Highlighter};
+use crate::text;
+use owo_colors::OwoColorize;
+use std::io::{self, IsTerminal};
+
+/// Print a source file with syntax highlighting, line numbers and a titled frame
+///
+/// Output taller than the terminal opens in the pager when stdout is a TTY.
+pub fn render(file: &str, range: Option<&str>, lang: Option<&str>, no_pager: bool) {
+    let text = match input::read_text(file) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let source: Vec<&str> = text.lines().collect();
+    let (start, end) = match range {
+        Some(spec) => match parse_range(spec, source.len()) {
+            Ok(range) => range,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => (1, source.len()),
+    };
+
+    let lang = lang
+        .map(|l| l.to_string())
+        .or_else(|| syntax::language_for_path(file).map(|l| l.to_string()))
+        .unwrap_or_default();
+    let mut title = input::display_name(file).to_string();
+    if range.is_some() {
+        title.push_str(&format!(":{}-{}", start, end));
+    }
+
+    let is_tty = io::stdout().is_terminal();
+    let (cols, rows) = crossterm::terminal::size()
+        .map(|(w, h)| (w as usize, h as usize))
+        .unwrap_or((80, 24));
+    let lines = code_lines(&source, start, end, &lang, &title, cols);
+
+    if is_tty && !no_pager && lines.len() > rows {
+        let pager = Pager::from_lines(lines, false, Some(title));
+        if let Err(e) = pager.render() {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    for line in lines {
+        println!("{}", line);
+    }
+}
+
+/// Parse `A-B`, `A-`, `-B` or `A` into an inclusive 1-based line range
+pub fn parse_range(spec: &str, total: usize) -> Result<(usize, usize), String> {
+    let invalid = || format!("Invalid line range '{}' (expected e.g. 10-40)", spec);
+    let number = |s: &str| s.trim().parse::<usize>().map_err(|_| invalid());
+
+    let (start, end) = match spec.split_once('-') {
+        Some((a, b)) => {
+            let start = if a.trim().is_empty() { 1 } else { number(a)? };
+            let end = if b.trim().is_empty() {
+                total
+            } else {
+                number(b)?
+            };
+            (start, end)
+        }
+        None => {
+            let line = number(spec)?;
+            (line, line)
+        }
+    };
+
+    if start == 0 || start > end {
+        return Err(invalid());
+    }
+    if start > total {
+        return Err(format!(
+            "Line range starts at {} but input has {} lines",
+            start, total
+        ));
+    }
+    Ok((start, end.min(total)))
+}
+
+/// Build the framed, highlighted listing for lines `start..=end`
+pub fn code_lines(
+    source: &[&str],
+    start: usize,
+    end: usize,
+    lang: &str,
+    title: &str,
+    width: usize,
+) -> Vec<String> {
+    let gutter = end.max(1).to_string().len();
+    // "│ " + gutter + " │ " + code + " │"
+    let chrome = gutter + 7;
+    let max_inner = width.saturating_sub(chrome).max(10);
+
+    // Highlight from the top so block comments opened before the range carry over
+    let mut highlighter = Highlighter::new(lang);
+    let mut body = Vec::new();
+    for (idx, line) in source.iter().enumerate().take(end) {
+        let line = line.replace('\t', "    ");
+        let highlighted = highlighter.line(&line);
+        if idx + 1 >= start {
+            body.push((idx + 1, highlighted));
+        }
+    }
+
+    let inner = body
+        .iter()
+        .map(|(_, l)| text::display_width(l))
+        .max()
+        .unwrap_or(0)
+        .max(text::display_width(title) + 2)
+        .min(max_inner);
+    let total = inner + gutter + 5;
+
+    let label = format!(" {} ", text::truncate(title, total.saturating_sub(3), "…"));
+    let mut lines = vec![format!(
+        "{}{}{}{}",
+        "╭─".bright_black(),
+        label.bright_cyan().bold(),
+        "─"
+            .repeat(total.saturating_sub(1 + text::display_width(&label)))
+            .bright_black(),
+        "╮".bright_black()
+    )];
+    for (number, code) in &body {
+        let code = text::truncate(code, inner, "…");
+        let pad = inner.saturating_sub(text::display_width(&code));
+        lines.push(format!(
+            "{} {} {} {}{} {}",
+            "│".bright_black(),
+            format!("{:>width$}", number, width = gutter).bright_black(),
+            "│".bright_black(),
+            code,
+            " ".repeat(pad),
+            "│".bright_black()
+        ));
+    }
+    lines.push(
+        format!("╰{}╯", "─".repeat(total))
+            .bright_black()
+            .to_string(),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[String]) -> Vec<String> {
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        lines
+            .iter()
+            .map(|l| ansi.replace_all(l, "").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_range_forms() {
+        assert_eq!(parse_range("10-40", 100), Ok((10, 40)));
+        assert_eq!(parse_range("90-", 100), Ok((90, 100)));
+        assert_eq!(parse_range("-5", 100), Ok((1, 5)));
+        assert_eq!(parse_range("7", 100), Ok((7, 7)));
+        assert_eq!(parse_range("10-400", 100), Ok((10, 100)));
+    }
+
+    #[test]
+    fn test_parse_range_rejects_invalid() {
+        assert!(parse_range("40-10", 100).is_err());
+        assert!(parse_range("0-3", 100).is_err());
+        assert!(parse_range("abc", 100).is_err());
+        assert!(parse_range("200-300", 100).is_err());
+    }
+
+    #[test]
+    fn test_code_lines_frame_and_numbers() {
+        let source = ["fn main() {", "    println!(\"hi\");", "}"];
+        let lines = plain(&code_lines(&source, 2, 3, "rust", "main.rs", 80));
+        assert!(lines[0].starts_with("╭─ main.rs "));
+        assert_eq!(lines[1], "│ 2 │     println!(\"hi\"); │");
+        assert!(lines[2].starts_with("│ 3 │ }"));
+        assert!(lines[3].starts_with('╰'));
+        // Every row has the same width
+        let widths: Vec<usize> = lines.iter().map(|l| text::display_width(l)).collect();
+        assert!(widths.iter().all(|w| *w == widths[0]));
+    }
+
+    #[test]
+    fn test_code_lines_truncate_to_width() {
+        let long = "x".repeat(200);
+        let source = [long.as_str()];
+        let lines = code_lines(&source, 1, 1, "", "wide.txt", 40);
+        assert!(lines.iter().all(|l| text::display_width(l) <= 40));
+    }
+}
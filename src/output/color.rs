@@ -0,0 +1,543 @@
+//! Color policy shared by every command
+//!
+//! `--color always|never|auto` decides whether ANSI colors are written. In
+//! auto mode colors are on when stdout is a terminal, unless `NO_COLOR` is
+//! set, `CLICOLOR=0` or `TERM=dumb`; `CLICOLOR_FORCE` turns them on even
+//! when the output is piped. Output goes through the crate's `print!` family,
+//! which drops SGR (color/style) sequences when colors are off but keeps
+//! cursor movement, so animations still work. Code writing to stdout
+//! directly uses [`stdout`] for the same filtering.
+//!
+//! Gradients are written as 24-bit colors. On terminals without truecolor
+//! the same layer quantizes them to the 256-color palette, or to the 16
+//! named colors as a last resort (`--color-depth`, `COLORTERM`, `TERM`).
+
+use crate::render::{self, CUBE_LEVELS};
+use crate::text;
+use std::borrow::Cow;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::OnceLock;
+
+/// Names accepted by [`ColorChoice::from_name`]
+pub const COLOR_CHOICES: [&str; 3] = ["auto", "always", "never"];
+
+/// When to emit colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether this choice turns colors on, given whether stdout is a
+    /// terminal and an environment lookup
+    fn resolve(self, tty: bool, var: impl Fn(&str) -> Option<String>) -> bool {
+        let set = |name: &str| var(name).is_some_and(|v| !v.is_empty());
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if set("CLICOLOR_FORCE") && var("CLICOLOR_FORCE").as_deref() != Some("0") {
+                    return true;
+                }
+                tty && !(set("NO_COLOR")
+                    || var("CLICOLOR").as_deref() == Some("0")
+                    || var("TERM").as_deref() == Some("dumb"))
+            }
+        }
+    }
+}
+
+/// Names accepted by [`ColorDepth::from_name`]
+pub const COLOR_DEPTHS: [&str; 4] = ["auto", "truecolor", "256", "16"];
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    #[default]
+    Auto,
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(ColorDepth::Auto),
+            "truecolor" | "24bit" | "24" => Some(ColorDepth::TrueColor),
+            "256" | "ansi256" => Some(ColorDepth::Ansi256),
+            "16" | "ansi16" | "ansi" => Some(ColorDepth::Ansi16),
+            _ => None,
+        }
+    }
+
+    /// Name accepted by `--color-depth`
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorDepth::Auto => "auto",
+            ColorDepth::TrueColor => "truecolor",
+            ColorDepth::Ansi256 => "256",
+            ColorDepth::Ansi16 => "16",
+        }
+    }
+
+    /// The concrete depth for this choice, given an environment lookup
+    ///
+    /// Auto trusts `COLORTERM`, then `TERM`; with no `TERM` at all the
+    /// output isn't going to a known terminal, so colors are kept exact.
+    fn resolve(self, var: impl Fn(&str) -> Option<String>) -> ColorDepth {
+        if self != ColorDepth::Auto {
+            return self;
+        }
+        if let Some("truecolor" | "24bit") = var("COLORTERM").as_deref() {
+            return ColorDepth::TrueColor;
+        }
+        let term = var("TERM").unwrap_or_default();
+        if term.is_empty()
+            || [
+                "direct",
+                "truecolor",
+                "kitty",
+                "alacritty",
+                "wezterm",
+                "foot",
+            ]
+            .iter()
+            .any(|t| term.contains(t))
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+}
+
+static CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+static ENABLED: OnceLock<bool> = OnceLock::new();
+static DEPTH: OnceLock<ColorDepth> = OnceLock::new();
+
+/// Fix the color policy for this run; later calls have no effect
+pub fn init(choice: ColorChoice) {
+    CHOICE.get_or_init(|| choice);
+}
+
+/// Whether colors are written; auto mode until [`init`] says otherwise
+pub fn enabled() -> bool {
+    *ENABLED.get_or_init(|| choice().resolve(io::stdout().is_terminal(), |n| env::var(n).ok()))
+}
+
+/// Whether colors would be written if stdout were a terminal
+///
+/// Prompt segments are always captured by the shell, and output redirected
+/// to a file keeps its Unicode borders; only an explicit request for plain
+/// output (`--color never`, `NO_COLOR`, ...) turns these off.
+pub fn requested() -> bool {
+    choice().resolve(true, |name| env::var(name).ok())
+}
+
+fn choice() -> ColorChoice {
+    *CHOICE.get_or_init(ColorChoice::default)
+}
+
+/// Fix the color depth for this run; later calls have no effect
+pub fn init_depth(depth: ColorDepth) {
+    DEPTH.get_or_init(|| depth.resolve(|name| env::var(name).ok()));
+}
+
+/// Colors the terminal can show; detected until [`init_depth`] says otherwise
+pub fn depth() -> ColorDepth {
+    *DEPTH.get_or_init(|| ColorDepth::Auto.resolve(|name| env::var(name).ok()))
+}
+
+/// `text` as it should be printed under the current policy
+pub fn apply(text: &str) -> Cow<'_, str> {
+    paint(text, enabled())
+}
+
+/// `text` for a shell prompt, which is colored even though it is captured
+pub fn apply_prompt(text: &str) -> Cow<'_, str> {
+    paint(text, requested())
+}
+
+fn paint(text: &str, colored: bool) -> Cow<'_, str> {
+    if !colored {
+        return text::strip_sgr(text);
+    }
+    match depth() {
+        ColorDepth::TrueColor | ColorDepth::Auto => Cow::Borrowed(text),
+        depth => text::map_sgr(text, |params| Some(quantize(params, depth))),
+    }
+}
+
+/// SGR parameters with 24-bit (and, for 16 colors, 256-palette) colors
+/// replaced by the nearest color `depth` can show
+///
+/// `38;2;255;128;0` becomes `38;5;208` for 256 colors or `91` for 16.
+pub fn quantize(params: &str, depth: ColorDepth) -> String {
+    if matches!(depth, ColorDepth::TrueColor | ColorDepth::Auto) {
+        return params.to_string();
+    }
+    let parts: Vec<&str> = params.split(';').collect();
+    let mut out: Vec<String> = Vec::with_capacity(parts.len());
+    let mut i = 0;
+    while i < parts.len() {
+        let target = parts[i];
+        let num = |j: usize| parts.get(j).and_then(|p| p.parse::<u8>().ok());
+        let rgb = match (target, parts.get(i + 1).copied()) {
+            ("38" | "48", Some("2")) => match (num(i + 2), num(i + 3), num(i + 4)) {
+                (Some(r), Some(g), Some(b)) => Some(((r, g, b), 5)),
+                _ => None,
+            },
+            ("38" | "48", Some("5")) if depth == ColorDepth::Ansi16 => {
+                num(i + 2).map(|n| (palette_rgb(n), 3))
+            }
+            _ => None,
+        };
+        let Some(((r, g, b), len)) = rgb else {
+            out.push(target.to_string());
+            i += 1;
+            continue;
+        };
+        let background = target == "48";
+        out.push(match depth {
+            ColorDepth::Ansi256 => format!("{};5;{}", target, rgb_to_256(r, g, b)),
+            _ => {
+                let idx = rgb_to_16(r, g, b);
+                let base = match (background, idx >= 8) {
+                    (false, false) => 30,
+                    (false, true) => 90,
+                    (true, false) => 40,
+                    (true, true) => 100,
+                };
+                (base + (idx % 8) as u16).to_string()
+            }
+        });
+        i += len;
+    }
+    out.join(";")
+}
+
+/// The 16 named colors as xterm draws them
+const ANSI16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// RGB of a 256-palette index
+pub fn palette_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI16[n as usize],
+        _ => render::indexed_rgb(n),
+    }
+}
+
+/// Nearest 256-palette index: the closer of the color cube and gray ramp
+pub fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = if avg < 8 {
+        232
+    } else {
+        (232 + ((avg - 8 + 5) / 10).min(23)) as usize
+    };
+
+    let target = (r, g, b);
+    if distance(palette_rgb(gray as u8), target) < distance(palette_rgb(cube as u8), target) {
+        gray as u8
+    } else {
+        cube as u8
+    }
+}
+
+/// Nearest of the 16 named colors (0-7 normal, 8-15 bright)
+pub fn rgb_to_16(r: u8, g: u8, b: u8) -> u8 {
+    (0..16u8)
+        .min_by_key(|&i| distance(ANSI16[i as usize], (r, g, b)))
+        .unwrap_or(7)
+}
+
+/// Colors `quantize` can produce at `depth`, or `None` when any color can
+/// be shown
+pub fn palette(depth: ColorDepth) -> Option<Vec<[u8; 3]>> {
+    let rgb = |(r, g, b): (u8, u8, u8)| [r, g, b];
+    match depth {
+        ColorDepth::TrueColor | ColorDepth::Auto => None,
+        // rgb_to_256 only picks from the cube and the gray ramp
+        ColorDepth::Ansi256 => Some((16..=255).map(|n| rgb(palette_rgb(n))).collect()),
+        ColorDepth::Ansi16 => Some(ANSI16.into_iter().map(rgb).collect()),
+    }
+}
+
+/// Border style to use when the user didn't pick one: plain ASCII when
+/// plain output was asked for, `styled` otherwise
+pub fn default_border(styled: &str) -> &str {
+    if requested() {
+        styled
+    } else {
+        "ascii"
+    }
+}
+
+/// Stdout that follows the color policy
+pub type Stdout = Plain<io::Stdout>;
+
+/// Handle to [`Stdout`]; use it instead of `io::stdout()` for styled output
+pub fn stdout() -> Stdout {
+    Plain::new(io::stdout(), !enabled(), depth())
+}
+
+/// Writer that drops SGR sequences when `strip` is set, and otherwise
+/// quantizes their colors to `depth`
+///
+/// Sequences may be split across writes, so a partial one is held back
+/// until its final byte arrives.
+pub struct Plain<W: Write> {
+    inner: W,
+    strip: bool,
+    depth: ColorDepth,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> Plain<W> {
+    pub fn new(inner: W, strip: bool, depth: ColorDepth) -> Self {
+        Self {
+            inner,
+            strip,
+            depth,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<W: Write> Write for Plain<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.strip && matches!(self.depth, ColorDepth::TrueColor | ColorDepth::Auto) {
+            return self.inner.write(buf);
+        }
+        let mut out = Vec::with_capacity(buf.len());
+        for &byte in buf {
+            if self.pending.is_empty() {
+                if byte == 0x1b {
+                    self.pending.push(byte);
+                } else {
+                    out.push(byte);
+                }
+                continue;
+            }
+            self.pending.push(byte);
+            let done = match self.pending.as_slice() {
+                // ESC followed by anything but `[` is not a CSI sequence
+                [_, second] => *second != b'[',
+                _ => (0x40..=0x7e).contains(&byte),
+            };
+            if done {
+                if !(self.pending[1] == b'[' && byte == b'm') {
+                    out.extend_from_slice(&self.pending);
+                } else if !self.strip {
+                    let params = String::from_utf8_lossy(&self.pending[2..self.pending.len() - 1]);
+                    out.extend_from_slice(
+                        format!("\x1b[{}m", quantize(&params, self.depth)).as_bytes(),
+                    );
+                }
+                self.pending.clear();
+            }
+        }
+        self.inner.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(choice: ColorChoice, vars: &[(&str, &str)]) -> bool {
+        choice.resolve(true, |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn test_from_name() {
+        for name in COLOR_CHOICES {
+            assert!(ColorChoice::from_name(name).is_some(), "{}", name);
+        }
+        assert_eq!(ColorChoice::from_name("NEVER"), Some(ColorChoice::Never));
+        assert_eq!(ColorChoice::from_name("sometimes"), None);
+    }
+
+    #[test]
+    fn test_auto_follows_environment() {
+        assert!(resolve(ColorChoice::Auto, &[]));
+        assert!(!resolve(ColorChoice::Auto, &[("NO_COLOR", "1")]));
+        assert!(resolve(ColorChoice::Auto, &[("NO_COLOR", "")]));
+        assert!(!resolve(ColorChoice::Auto, &[("CLICOLOR", "0")]));
+        assert!(!resolve(ColorChoice::Auto, &[("TERM", "dumb")]));
+        assert!(resolve(
+            ColorChoice::Auto,
+            &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]
+        ));
+        assert!(!resolve(
+            ColorChoice::Auto,
+            &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "0")]
+        ));
+    }
+
+    #[test]
+    fn test_auto_is_off_when_piped() {
+        let var = |name: &str| (name == "CLICOLOR_FORCE").then(|| "1".to_string());
+        assert!(!ColorChoice::Auto.resolve(false, |_| None));
+        assert!(ColorChoice::Auto.resolve(false, var));
+        assert!(ColorChoice::Always.resolve(false, |_| None));
+    }
+
+    #[test]
+    fn test_explicit_choice_ignores_environment() {
+        assert!(resolve(ColorChoice::Always, &[("NO_COLOR", "1")]));
+        assert!(!resolve(ColorChoice::Never, &[("CLICOLOR_FORCE", "1")]));
+    }
+
+    #[test]
+    fn test_plain_writer_strips_split_sequences() {
+        let mut w = Plain::new(Vec::new(), true, ColorDepth::TrueColor);
+        for chunk in ["\x1b[3", "1mred\x1b", "[0m \x1b[2K", "\x1b7ok"] {
+            w.write_all(chunk.as_bytes()).unwrap();
+        }
+        assert_eq!(String::from_utf8(w.inner).unwrap(), "red \x1b[2K\x1b7ok");
+    }
+
+    #[test]
+    fn test_plain_writer_passes_through_when_colors_on() {
+        let mut w = Plain::new(Vec::new(), false, ColorDepth::TrueColor);
+        w.write_all(b"\x1b[31mred\x1b[0m").unwrap();
+        assert_eq!(w.inner, b"\x1b[31mred\x1b[0m");
+    }
+
+    fn depth(vars: &[(&str, &str)]) -> ColorDepth {
+        ColorDepth::Auto.resolve(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn test_depth_detection() {
+        assert_eq!(depth(&[]), ColorDepth::TrueColor);
+        assert_eq!(
+            depth(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(depth(&[("TERM", "xterm-kitty")]), ColorDepth::TrueColor);
+        assert_eq!(depth(&[("TERM", "xterm-256color")]), ColorDepth::Ansi256);
+        assert_eq!(depth(&[("TERM", "linux")]), ColorDepth::Ansi16);
+        assert_eq!(
+            ColorDepth::Ansi16.resolve(|_| Some("truecolor".to_string())),
+            ColorDepth::Ansi16
+        );
+        for name in COLOR_DEPTHS {
+            assert!(ColorDepth::from_name(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_256() {
+        assert_eq!(rgb_to_256(255, 0, 0), 196);
+        assert_eq!(rgb_to_256(0, 0, 0), 16);
+        assert_eq!(rgb_to_256(255, 255, 255), 231);
+        assert_eq!(rgb_to_256(128, 128, 128), 244);
+        assert_eq!(rgb_to_256(88, 166, 255), 75);
+    }
+
+    #[test]
+    fn test_rgb_to_16() {
+        assert_eq!(rgb_to_16(250, 10, 10), 9);
+        assert_eq!(rgb_to_16(0, 190, 0), 2);
+        assert_eq!(rgb_to_16(20, 20, 20), 0);
+        assert_eq!(rgb_to_16(250, 250, 250), 15);
+        assert_eq!(rgb_to_16(225, 225, 225), 7);
+    }
+
+    #[test]
+    fn test_palette_colors_survive_quantize() {
+        assert!(palette(ColorDepth::TrueColor).is_none());
+        let colors = palette(ColorDepth::Ansi256).unwrap();
+        assert_eq!(colors.len(), 240);
+        for (n, [r, g, b]) in (16..=255u8).zip(colors) {
+            assert_eq!(rgb_to_256(r, g, b), n);
+        }
+        for (n, [r, g, b]) in (0..16u8).zip(palette(ColorDepth::Ansi16).unwrap()) {
+            assert_eq!(rgb_to_16(r, g, b), n);
+        }
+    }
+
+    #[test]
+    fn test_quantize_params() {
+        let seq = "1;38;2;255;0;0;48;2;0;0;0";
+        assert_eq!(quantize(seq, ColorDepth::TrueColor), seq);
+        assert_eq!(quantize(seq, ColorDepth::Ansi256), "1;38;5;196;48;5;16");
+        assert_eq!(quantize(seq, ColorDepth::Ansi16), "1;91;40");
+        assert_eq!(quantize("38;5;196", ColorDepth::Ansi16), "91");
+        assert_eq!(quantize("38;5;196", ColorDepth::Ansi256), "38;5;196");
+        assert_eq!(quantize("0", ColorDepth::Ansi16), "0");
+    }
+
+    #[test]
+    fn test_plain_writer_quantizes_split_sequences() {
+        let mut w = Plain::new(Vec::new(), false, ColorDepth::Ansi256);
+        for chunk in ["\x1b[38;2;2", "55;0;0m█\x1b", "[0m\x1b[2K"] {
+            w.write_all(chunk.as_bytes()).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(w.inner).unwrap(),
+            "\x1b[38;5;196m█\x1b[0m\x1b[2K"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Nested dashboard layouts: rows and columns of panels, sized by
+//! percentage or fixed cells, drawn with ratatui
+//!
+//! ```json
+//! "layout": {"rows": [
+//!     {"size": "30%", "panel": 0},
+//!     {"columns": [{"size": 24, "panel": 1}, {"panel": 2}]}
+//! ]}
+//! ```
+//!
+//! A node holds `rows` (stacked), `columns` (side by side) or one `panel`
+//! (an index into `panels`). A panel spans cells by sitting in a bigger
+//! node: above, panel 0 runs the full width over the two columns below.
+
+use super::{chart_values, panels, render_panel_content, sparkline_values, Panel};
+use ratatui::{
+    buffer::Buffer,
+    prelude::*,
+    widgets::{Bar, BarChart, BarGroup, Block, BorderType, Borders, Gauge, Paragraph, Sparkline},
+};
+use serde::{Deserialize, Serialize};
+
+/// Content rows a panel gets when the dashboard is printed once
+const PANEL_HEIGHT: u16 = 5;
+
+/// Columns a printed dashboard takes
+pub(super) const PRINT_WIDTH: u16 = 80;
+
+/// The `layout` field: a `"RxC"` grid or a tree of nodes
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub(super) enum LayoutSpec {
+    Grid(String),
+    Tree(Node),
+}
+
+// By hand rather than untagged, so a mistake in a tree reports what's wrong
+impl<'de> Deserialize<'de> for LayoutSpec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(grid) => Ok(LayoutSpec::Grid(grid)),
+            tree => serde_json::from_value(tree)
+                .map(LayoutSpec::Tree)
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Node {
+    /// Share of the parent: cells, or a percentage like "30%"; fills the
+    /// rest when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Size>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Vec<Node>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<Node>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(super) enum Size {
+    Cells(u16),
+    Text(String),
+}
+
+impl Size {
+    fn constraint(&self) -> Result<Constraint, String> {
+        match self {
+            Size::Cells(cells) => Ok(Constraint::Length(*cells)),
+            Size::Text(text) => text
+                .trim()
+                .strip_suffix('%')
+                .and_then(|p| p.trim().parse::<u16>().ok())
+                .filter(|p| *p <= 100)
+                .map(Constraint::Percentage)
+                .or_else(|| text.trim().parse().ok().map(Constraint::Length))
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid size '{}' (use a number of cells or a percentage like 30%)",
+                        text
+                    )
+                }),
+        }
+    }
+}
+
+impl Node {
+    /// A `rows` x `cols` grid with the panels in reading order
+    pub fn grid(rows: usize, cols: usize) -> Self {
+        Node {
+            rows: Some(
+                (0..rows)
+                    .map(|row| Node {
+                        columns: Some(
+                            (0..cols)
+                                .map(|col| Node {
+                                    panel: Some(row * cols + col),
+                                    ..Node::default()
+                                })
+                                .collect(),
+                        ),
+                        ..Node::default()
+                    })
+                    .collect(),
+            ),
+            ..Node::default()
+        }
+    }
+
+    /// Check every node has one kind of content, sizes parse, and panel
+    /// indices exist
+    pub fn validate(&self, panel_count: usize) -> Result<(), String> {
+        if let Some(size) = &self.size {
+            size.constraint()?;
+        }
+        match (&self.rows, &self.columns, self.panel) {
+            (Some(children), None, None) | (None, Some(children), None) => {
+                if children.is_empty() {
+                    return Err("Layout rows and columns need at least one node".to_string());
+                }
+                children.iter().try_for_each(|c| c.validate(panel_count))
+            }
+            (None, None, Some(panel)) if panel < panel_count => Ok(()),
+            (None, None, Some(panel)) => Err(format!(
+                "Layout refers to panel {}, but there are {} panels",
+                panel, panel_count
+            )),
+            _ => Err("Each layout node needs exactly one of rows, columns or panel".to_string()),
+        }
+    }
+
+    /// Rows the node needs when printed: fixed sizes as given, panels
+    /// [`PANEL_HEIGHT`] plus borders
+    fn natural_height(&self) -> u16 {
+        match (&self.rows, &self.columns) {
+            (Some(rows), _) => rows.iter().map(Node::row_height).sum(),
+            (_, Some(columns)) => columns.iter().map(Node::natural_height).max().unwrap_or(0),
+            _ => PANEL_HEIGHT + 2,
+        }
+    }
+
+    /// Height as a child of `rows`, where a fixed size is the height
+    fn row_height(&self) -> u16 {
+        match &self.size {
+            Some(Size::Cells(cells)) => *cells,
+            _ => self.natural_height(),
+        }
+    }
+
+    /// Areas for the children of `rows` or `columns`
+    fn split(children: &[Node], area: Rect, direction: Direction) -> Vec<Rect> {
+        let constraints = children.iter().map(|child| {
+            child
+                .size
+                .as_ref()
+                .and_then(|size| size.constraint().ok())
+                .unwrap_or(Constraint::Fill(1))
+        });
+        Layout::default()
+            .direction(direction)
+            .constraints(constraints)
+            .split(area)
+            .to_vec()
+    }
+
+    /// Every panel index with the area it was given
+    pub fn areas(&self, area: Rect) -> Vec<(usize, Rect)> {
+        match (&self.rows, &self.columns, self.panel) {
+            (Some(children), _, _) | (_, Some(children), _) => {
+                let direction = if self.rows.is_some() {
+                    Direction::Vertical
+                } else {
+                    Direction::Horizontal
+                };
+                children
+                    .iter()
+                    .zip(Node::split(children, area, direction))
+                    .flat_map(|(child, area)| child.areas(area))
+                    .collect()
+            }
+            (_, _, Some(panel)) => vec![(panel, area)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// ratatui's border for a `--border` name
+pub(super) fn border_type(name: &str) -> BorderType {
+    match name {
+        "double" => BorderType::Double,
+        "rounded" => BorderType::Rounded,
+        _ => BorderType::Plain,
+    }
+}
+
+/// Draw every panel of `node` into `area`; `errors` holds a message per
+/// panel whose last refresh failed
+pub(super) fn draw(
+    buf: &mut Buffer,
+    area: Rect,
+    node: &Node,
+    panels: &[Panel],
+    errors: &[Option<String>],
+    border: BorderType,
+) {
+    for (idx, area) in node.areas(area) {
+        let panel = &panels[idx];
+        let title = panel.title.as_deref().unwrap_or(&panel.panel_type);
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(border)
+            .title(format!(" {} ", title));
+        if let Some(error) = errors.get(idx).and_then(Option::as_ref) {
+            block = block.title_bottom(Line::from(format!(" {} ", error)).red());
+        }
+        draw_panel(buf, panel, block, area);
+    }
+}
+
+/// The dashboard as plain text, [`PRINT_WIDTH`] wide and as tall as the
+/// panels need
+pub(super) fn print(title: Option<&str>, node: &Node, panels: &[Panel], border: &str) -> String {
+    let area = Rect::new(0, 0, PRINT_WIDTH, node.natural_height());
+    let mut buf = Buffer::empty(area);
+    draw(&mut buf, area, node, panels, &[], border_type(border));
+
+    let mut out = String::new();
+    if let Some(title) = title {
+        let title = format!(" {} ", title);
+        let padding = (PRINT_WIDTH as usize).saturating_sub(title.len()) / 2;
+        out.push_str(&format!("{}{}\n\n", " ".repeat(padding), title));
+    }
+    for y in 0..area.height {
+        let mut line = String::new();
+        let mut style = String::new();
+        for x in 0..area.width {
+            let cell = &buf[(x, y)];
+            let cell_style = sgr(cell.fg, cell.bg, cell.modifier);
+            if cell_style != style {
+                line.push_str("\x1b[0m");
+                line.push_str(&cell_style);
+                style = cell_style;
+            }
+            line.push_str(cell.symbol());
+        }
+        let line = line.trim_end();
+        out.push_str(line.strip_prefix("\x1b[0m").unwrap_or(line));
+        if !style.is_empty() {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape sequence setting a cell's colors and weight; empty for the
+/// terminal's defaults
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    fn code(color: Color, base: u8) -> Option<String> {
+        let named = |n: u8| Some((base + n).to_string());
+        let bright = |n: u8| Some((base + 60 + n).to_string());
+        match color {
+            Color::Reset => None,
+            Color::Black => named(0),
+            Color::Red => named(1),
+            Color::Green => named(2),
+            Color::Yellow => named(3),
+            Color::Blue => named(4),
+            Color::Magenta => named(5),
+            Color::Cyan => named(6),
+            Color::Gray => named(7),
+            Color::DarkGray => bright(0),
+            Color::LightRed => bright(1),
+            Color::LightGreen => bright(2),
+            Color::LightYellow => bright(3),
+            Color::LightBlue => bright(4),
+            Color::LightMagenta => bright(5),
+            Color::LightCyan => bright(6),
+            Color::White => bright(7),
+            Color::Indexed(i) => Some(format!("{};5;{}", base + 8, i)),
+            Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+        }
+    }
+    let mut codes: Vec<String> = [code(fg, 30), code(bg, 40)].into_iter().flatten().collect();
+    if modifier.contains(Modifier::BOLD) {
+        codes.push("1".to_string());
+    }
+    if codes.is_empty() {
+        String::new()
+    } else {
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+/// A percentage from the first number in `content`, clamped to 0-100
+pub(super) fn percent(content: &str) -> u16 {
+    content
+        .split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .find_map(|s| s.parse::<f64>().ok())
+        .unwrap_or(0.0)
+        .clamp(0.0, 100.0) as u16
+}
+
+fn draw_panel(buf: &mut Buffer, panel: &Panel, block: Block, area: Rect) {
+    match panel.panel_type.as_str() {
+        "gauge" | "progress" => {
+            let color = if panel.panel_type == "progress" {
+                Color::Cyan
+            } else {
+                Color::Green
+            };
+            Gauge::default()
+                .block(block)
+                .gauge_style(Style::default().fg(color))
+                .percent(percent(&panel.content))
+                .render(area, buf);
+        }
+        "sparkline" => {
+            let values = sparkline_values(&panel.content);
+            let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            // Scale into 0-100 so negative and fractional values still draw
+            let data: Vec<u64> = values
+                .iter()
+                .map(|v| match max - min {
+                    range if range > 0.0 => ((v - min) / range * 100.0) as u64,
+                    _ => 50,
+                })
+                .collect();
+            // Newest values on the right when there are more than fit
+            let width = block.inner(area).width as usize;
+            let data = &data[data.len().saturating_sub(width)..];
+            Sparkline::default()
+                .block(block)
+                .data(data)
+                .max(100)
+                .style(Style::default().fg(Color::Cyan))
+                .render(area, buf);
+        }
+        "chart" => {
+            let bars: Vec<Bar> = chart_values(&panel.content)
+                .into_iter()
+                .map(|(label, value)| {
+                    Bar::default()
+                        .label(Line::from(label))
+                        .value(value.max(0.0).round() as u64)
+                })
+                .collect();
+            BarChart::default()
+                .block(block)
+                .direction(Direction::Horizontal)
+                .bar_width(1)
+                .bar_gap(0)
+                .bar_style(Style::default().fg(Color::Cyan))
+                .data(BarGroup::default().bars(&bars))
+                .render(area, buf);
+        }
+        "image" => {
+            let inner = block.inner(area);
+            block.render(area, buf);
+            let rows = match panels::image(&panel.content, inner.width, inner.height) {
+                Ok(rows) => rows,
+                Err(e) => return Paragraph::new(e).render(inner, buf),
+            };
+            let top = inner.y + inner.height.saturating_sub(rows.len() as u16) / 2;
+            for (y, row) in (top..).zip(&rows) {
+                let left = inner.x + inner.width.saturating_sub(row.len() as u16) / 2;
+                for (x, [fg, bg]) in (left..).zip(row) {
+                    buf[(x, y)]
+                        .set_symbol("▀")
+                        .set_fg(Color::Rgb(fg[0], fg[1], fg[2]))
+                        .set_bg(Color::Rgb(bg[0], bg[1], bg[2]));
+                }
+            }
+        }
+        _ => {
+            let inner = block.inner(area);
+            let lines = render_panel_content(panel, inner.width as usize, inner.height as usize);
+            Paragraph::new(lines.join("\n"))
+                .block(block)
+                .render(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(json: &str) -> Node {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_spec_is_grid_or_tree() {
+        let spec: LayoutSpec = serde_json::from_str(r#""2x2""#).unwrap();
+        assert!(matches!(spec, LayoutSpec::Grid(ref g) if g == "2x2"));
+        let spec: LayoutSpec =
+            serde_json::from_str(r#"{"columns": [{"panel": 0}, {"panel": 1}]}"#).unwrap();
+        assert!(matches!(spec, LayoutSpec::Tree(_)));
+    }
+
+    #[test]
+    fn test_areas_follow_sizes_and_spans() {
+        let node = tree(
+            r#"{"rows": [
+                {"size": 4, "panel": 0},
+                {"columns": [{"size": "25%", "panel": 1}, {"panel": 2}]}
+            ]}"#,
+        );
+        assert_eq!(
+            node.areas(Rect::new(0, 0, 80, 20)),
+            vec![
+                (0, Rect::new(0, 0, 80, 4)),
+                (1, Rect::new(0, 4, 20, 16)),
+                (2, Rect::new(20, 4, 60, 16)),
+            ]
+        );
+        assert_eq!(node.natural_height(), 4 + 7);
+    }
+
+    #[test]
+    fn test_grid_matches_reading_order() {
+        let areas = Node::grid(2, 2).areas(Rect::new(0, 0, 10, 10));
+        let order: Vec<usize> = areas.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(order, [0, 1, 2, 3]);
+        assert_eq!(areas[3].1, Rect::new(5, 5, 5, 5));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(tree(r#"{"rows": [{"panel": 0}]}"#).validate(1).is_ok());
+        let err = tree(r#"{"rows": [{"panel": 2}]}"#).validate(2).unwrap_err();
+        assert!(err.contains("panel 2"));
+        assert!(tree(r#"{"rows": [], "panel": 0}"#).validate(1).is_err());
+        assert!(tree(r#"{"rows": []}"#).validate(1).is_err());
+        let err = tree(r#"{"size": "half", "panel": 0}"#)
+            .validate(1)
+            .unwrap_err();
+        assert!(err.contains("Invalid size 'half'"));
+        let err = serde_json::from_str::<LayoutSpec>(r#"{"colums": []}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `colums`"));
+    }
+
+    #[test]
+    fn test_percent_and_chart_values() {
+        assert_eq!(percent("CPU 73.5%"), 73);
+        assert_eq!(percent("250"), 100);
+        assert_eq!(percent("none"), 0);
+        assert_eq!(
+            chart_values("api:3;db: 5\nbad"),
+            vec![("api".to_string(), 3.0), ("db".to_string(), 5.0)]
+        );
+    }
+}
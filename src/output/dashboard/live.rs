@@ -0,0 +1,198 @@
+//! `dashboard --live`: panels re-run their commands and repaint on their
+//! own schedule
+
+use super::layout::{self, Node};
+use super::{sparkline_values, DashboardConfig, Panel};
+use crate::interactive::screen;
+use crate::output::sources::Sampler;
+use crate::output::watch;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{prelude::*, widgets::Paragraph, TerminalOptions, Viewport};
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// Values a live sparkline keeps when its command prints one number at a time
+const SPARKLINE_HISTORY: usize = 120;
+
+/// What a panel's command last produced
+type Update = (usize, Result<String, String>);
+
+/// Run the dashboard full-screen until `q`, each panel with a `command` or
+/// a sampler refreshing every `interval` from its own thread so a slow
+/// command never holds up the others
+pub(super) fn run(
+    config: &DashboardConfig,
+    samplers: Vec<Option<Sampler>>,
+    node: &Node,
+    border_style: &str,
+) -> io::Result<()> {
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "Dashboard --live requires an interactive terminal (TTY)",
+        ));
+    }
+    let intervals: Vec<Duration> = config
+        .panels
+        .iter()
+        .map(|panel| watch::parse_refresh(panel.interval.as_deref().unwrap_or("1s")))
+        .collect::<Result<_, _>>()
+        .map_err(io::Error::other)?;
+
+    let mut panels = config.panels.clone();
+    let mut errors: Vec<Option<String>> = vec![None; panels.len()];
+    let running = Arc::new(AtomicBool::new(true));
+    let (sender, updates) = mpsc::channel::<Update>();
+    for (idx, (panel, sampler)) in panels.iter().zip(samplers).enumerate() {
+        let mut read: Box<dyn FnMut() -> Result<String, String> + Send> =
+            match (panel.command.clone(), sampler) {
+                (Some(command), _) => Box::new(move || watch::exec_command(&command)),
+                (None, Some(mut sampler)) => {
+                    Box::new(move || sampler.sample().map(|value| format!("{:.1}", value)))
+                }
+                (None, None) => continue,
+            };
+        let (sender, running, interval) = (sender.clone(), running.clone(), intervals[idx]);
+        thread::spawn(move || {
+            while running.load(Ordering::SeqCst) {
+                if sender.send((idx, read())).is_err() {
+                    break;
+                }
+                // Sleep in short steps so quitting doesn't wait on long intervals
+                let mut slept = Duration::ZERO;
+                while slept < interval && running.load(Ordering::SeqCst) {
+                    let step = (interval - slept).min(Duration::from_millis(100));
+                    thread::sleep(step);
+                    slept += step;
+                }
+            }
+        });
+    }
+
+    let mut term = enter_screen().inspect_err(|_| running.store(false, Ordering::SeqCst))?;
+    let border = layout::border_type(border_style);
+
+    let result = loop {
+        for (idx, output) in updates.try_iter() {
+            match output {
+                Ok(output) => {
+                    apply_output(&mut panels[idx], &output);
+                    errors[idx] = None;
+                }
+                Err(e) => errors[idx] = Some(e),
+            }
+        }
+
+        if let Err(e) = term.draw(|frame| {
+            let title = config.title.as_deref();
+            let [header, grid, footer] = Layout::vertical([
+                Constraint::Length(if title.is_some() { 1 } else { 0 }),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .areas(frame.area());
+            if let Some(title) = title {
+                frame.render_widget(
+                    Paragraph::new(title).bold().alignment(Alignment::Center),
+                    header,
+                );
+            }
+            frame.render_widget(Paragraph::new("q quit").dark_gray(), footer);
+            layout::draw(frame.buffer_mut(), grid, node, &panels, &errors, border);
+        }) {
+            break Err(e);
+        }
+
+        match event::poll(Duration::from_millis(100)) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => break Err(e),
+        }
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break Ok(()),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+
+    running.store(false, Ordering::SeqCst);
+    terminal::disable_raw_mode()?;
+    execute!(term.backend_mut(), LeaveAlternateScreen)?;
+    result
+}
+
+/// Raw mode and the alternate screen with a terminal drawing on it; the
+/// terminal is restored if any step fails
+fn enter_screen() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
+    terminal::enable_raw_mode()?;
+    let result = (|| {
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let backend = CrosstermBackend::new(stdout);
+        // Some ptys report a 0x0 size; draw at the fallback size there
+        match terminal::size() {
+            Ok((cols, rows)) if cols > 0 && rows > 0 => Terminal::new(backend),
+            _ => {
+                let (cols, rows) = screen::size();
+                let viewport = Viewport::Fixed(Rect::new(0, 0, cols, rows));
+                Terminal::with_options(backend, TerminalOptions { viewport })
+            }
+        }
+    })();
+    if result.is_err() {
+        execute!(io::stdout(), LeaveAlternateScreen).ok();
+        terminal::disable_raw_mode().ok();
+    }
+    result
+}
+
+/// Put a command's output into the panel. A sparkline command that prints
+/// a single number adds it to the history; anything else replaces the content.
+fn apply_output(panel: &mut Panel, output: &str) {
+    let output = output.trim();
+    if panel.panel_type == "sparkline" && sparkline_values(output).len() == 1 {
+        let mut values = sparkline_values(&panel.content);
+        values.extend(sparkline_values(output));
+        let skip = values.len().saturating_sub(SPARKLINE_HISTORY);
+        let values: Vec<String> = values[skip..].iter().map(|v| v.to_string()).collect();
+        panel.content = values.join(";");
+    } else {
+        panel.content = output.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panel(panel_type: &str, content: &str) -> Panel {
+        Panel {
+            panel_type: panel_type.to_string(),
+            content: content.to_string(),
+            ..Panel::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_output_appends_single_sparkline_values() {
+        let mut spark = panel("sparkline", "1;2");
+        apply_output(&mut spark, "3\n");
+        assert_eq!(spark.content, "1;2;3");
+        apply_output(&mut spark, "5 6 7");
+        assert_eq!(spark.content, "5 6 7");
+
+        let mut gauge = panel("gauge", "10");
+        apply_output(&mut gauge, " 73.5 ");
+        assert_eq!(gauge.content, "73.5");
+    }
+}
@@ -1,22 +1,62 @@
+mod layout;
+mod live;
+mod panels;
+
+use crate::output::sources::{self, Sampler};
+use crate::output::{color, watch};
+use layout::{LayoutSpec, Node};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io::{self, Write};
+use std::io::Write;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct DashboardConfig {
-    layout: String,
+    /// A `"RxC"` grid, or nested rows and columns (see [`layout`])
+    layout: LayoutSpec,
     #[serde(skip_serializing_if = "Option::is_none")]
     title: Option<String>,
     panels: Vec<Panel>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct Panel {
     #[serde(rename = "type")]
     panel_type: String,
+    /// Shown in the panel's top border instead of its type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    /// What the panel shows; a file path for `table` (CSV), `log` and `image`
+    #[serde(default)]
     content: String,
+    /// Shell command whose output replaces `content`: on every refresh in
+    /// `--live` mode, otherwise once when `content` is empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    command: Option<String>,
+    /// Built-in reading used like `command`: cpu, mem, disk[:mount] or
+    /// net:<iface>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    /// How often `command` or `source` refreshes: "500ms", "2s", "1m"
+    /// (default 1s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interval: Option<String>,
 }
 
+/// Panel types a dashboard can hold
+const PANEL_TYPES: [&str; 10] = [
+    "box",
+    "progress",
+    "sparkline",
+    "gauge",
+    "text",
+    "chart",
+    "table",
+    "tree",
+    "log",
+    "image",
+];
+
+#[derive(Debug)]
 struct Layout {
     rows: usize,
     cols: usize,
@@ -82,6 +122,19 @@ impl BorderStyle {
             },
         }
     }
+
+    /// `width` horizontal lines above a panel, with its title if it has one
+    fn segment(&self, title: Option<&str>, width: usize) -> String {
+        let line = self.horizontal.to_string();
+        match title {
+            Some(title) if width > 4 => {
+                let title = crate::text::truncate(title, width - 4, "…");
+                let rest = width.saturating_sub(crate::text::display_width(&title) + 3);
+                format!("{} {} {}", line, title, line.repeat(rest))
+            }
+            _ => line.repeat(width),
+        }
+    }
 }
 
 /// Parse layout string like "2x2" into rows and cols
@@ -108,6 +161,43 @@ fn parse_layout(layout_str: &str) -> Result<Layout, String> {
     Ok(Layout { rows, cols })
 }
 
+/// A sampler for each panel with a `source`
+fn samplers(panels: &[Panel]) -> Result<Vec<Option<Sampler>>, String> {
+    panels
+        .iter()
+        .map(|panel| match (&panel.command, &panel.source) {
+            (Some(_), Some(_)) => Err("A panel takes a command or a source, not both".to_string()),
+            (_, Some(source)) => sources::sampler(source).map(Some),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// The grid (when the layout is one) and the layout tree, checked against
+/// the panels
+fn resolve_layout(config: &DashboardConfig) -> Result<(Option<Layout>, Node), String> {
+    match &config.layout {
+        LayoutSpec::Grid(spec) => {
+            let layout = parse_layout(spec)?;
+            let expected_panels = layout.rows * layout.cols;
+            if config.panels.len() != expected_panels {
+                return Err(format!(
+                    "Expected {} panels for layout {}, but got {}",
+                    expected_panels,
+                    spec,
+                    config.panels.len()
+                ));
+            }
+            let node = Node::grid(layout.rows, layout.cols);
+            Ok((Some(layout), node))
+        }
+        LayoutSpec::Tree(node) => {
+            node.validate(config.panels.len())?;
+            Ok((None, node.clone()))
+        }
+    }
+}
+
 /// Parse panels string like "box:Hello,progress:75,sparkline:1;2;3"
 /// Sparkline values can use semicolons (preferred) or commas as delimiters
 /// Using semicolons avoids ambiguity with the panel separator commas
@@ -137,13 +227,11 @@ fn parse_panels(panels_str: &str) -> Result<Vec<Panel>, String> {
             .to_string();
 
         // Validate panel type
-        if !matches!(
-            panel_type.as_str(),
-            "box" | "progress" | "sparkline" | "gauge" | "text"
-        ) {
+        if !PANEL_TYPES.contains(&panel_type.as_str()) {
             return Err(format!(
-                "Unknown panel type: '{}'. Valid types: box, progress, sparkline, gauge, text",
-                panel_type
+                "Unknown panel type: '{}'. Valid types: {}",
+                panel_type,
+                PANEL_TYPES.join(", ")
             ));
         }
 
@@ -151,8 +239,9 @@ fn parse_panels(panels_str: &str) -> Result<Vec<Panel>, String> {
         let content_start = colon_pos + 1;
         let mut content_end = content_start;
 
-        // For sparkline, we need to find the next "type:" pattern, not just comma
-        if panel_type == "sparkline" {
+        // Sparkline values and tree children may hold commas, so find the next
+        // "type:" pattern instead
+        if panel_type == "sparkline" || panel_type == "tree" {
             // Look for next panel type pattern (word followed by colon)
             let mut i = content_start;
             while i < chars.len() {
@@ -169,10 +258,7 @@ fn parse_panels(panels_str: &str) -> Result<Vec<Panel>, String> {
                     }
                     if word_end < chars.len() && chars[word_end] == ':' {
                         let next_type: String = chars[j..word_end].iter().collect();
-                        if matches!(
-                            next_type.as_str(),
-                            "box" | "progress" | "sparkline" | "gauge" | "text"
-                        ) {
+                        if PANEL_TYPES.contains(&next_type.as_str()) {
                             content_end = i;
                             break;
                         }
@@ -199,6 +285,7 @@ fn parse_panels(panels_str: &str) -> Result<Vec<Panel>, String> {
         panels.push(Panel {
             panel_type,
             content,
+            ..Panel::default()
         });
 
         // Move to next panel (skip comma if present)
@@ -249,7 +336,7 @@ fn render_panel_content(panel: &Panel, width: usize, height: usize) -> Vec<Strin
             }
         }
         "progress" => {
-            let percent: u8 = panel.content.parse().unwrap_or(0).min(100);
+            let percent = layout::percent(&panel.content) as u8;
             let bar_width = width.saturating_sub(6); // Reserve space for "XX% "
             let filled = (bar_width * percent as usize) / 100;
 
@@ -270,18 +357,7 @@ fn render_panel_content(panel: &Panel, width: usize, height: usize) -> Vec<Strin
             }
         }
         "sparkline" => {
-            // Support both semicolon (preferred) and comma as delimiters
-            // Semicolon is cleaner since commas are used as panel separators
-            let delimiter = if panel.content.contains(';') {
-                ';'
-            } else {
-                ','
-            };
-            let values: Vec<f64> = panel
-                .content
-                .split(delimiter)
-                .filter_map(|s| s.trim().parse().ok())
-                .collect();
+            let values = sparkline_values(&panel.content);
 
             let padding = (height.saturating_sub(1)) / 2;
             for _ in 0..padding {
@@ -296,7 +372,7 @@ fn render_panel_content(panel: &Panel, width: usize, height: usize) -> Vec<Strin
             }
         }
         "gauge" => {
-            let percent: u8 = panel.content.parse().unwrap_or(0).min(100);
+            let percent = layout::percent(&panel.content) as u8;
 
             let padding = (height.saturating_sub(1)) / 2;
             for _ in 0..padding {
@@ -319,34 +395,62 @@ fn render_panel_content(panel: &Panel, width: usize, height: usize) -> Vec<Strin
                 lines.push(" ".repeat(width));
             }
         }
-        "text" => {
-            // Word-wrapped text
-            let words: Vec<&str> = panel.content.split_whitespace().collect();
-            let mut current_line = String::new();
-
-            for word in words {
-                if current_line.len() + word.len() < width {
-                    if !current_line.is_empty() {
-                        current_line.push(' ');
-                    }
-                    current_line.push_str(word);
+        "chart" => {
+            let bars = chart_values(&panel.content);
+            let max = bars.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+            let label_width = bars
+                .iter()
+                .map(|(l, _)| l.chars().count())
+                .max()
+                .unwrap_or(0);
+            for (label, value) in bars.iter().take(height) {
+                let value_text = format!(" {}", value);
+                let bar_width = width.saturating_sub(label_width + 1 + value_text.len());
+                let filled = if max > 0.0 {
+                    ((value.max(0.0) / max) * bar_width as f64).round() as usize
                 } else {
-                    if !current_line.is_empty() {
-                        lines.push(format!("{:width$}", current_line, width = width));
-                        current_line = String::new();
-                    }
-                    current_line.push_str(word);
-                }
-            }
-
-            if !current_line.is_empty() {
-                lines.push(format!("{:width$}", current_line, width = width));
+                    0
+                };
+                let line = format!(
+                    "{:<lw$} {}{}",
+                    label,
+                    "█".repeat(filled),
+                    value_text,
+                    lw = label_width
+                );
+                let pad = width.saturating_sub(line.chars().count());
+                lines.push(format!("{}{}", line, " ".repeat(pad)));
             }
 
             while lines.len() < height {
                 lines.push(" ".repeat(width));
             }
         }
+        "text" => {
+            // Word-wrapped text, keeping the line breaks of command output
+            for text_line in panel.content.lines() {
+                let mut current_line = String::new();
+                for word in text_line.split_whitespace() {
+                    if current_line.len() + word.len() < width {
+                        if !current_line.is_empty() {
+                            current_line.push(' ');
+                        }
+                        current_line.push_str(word);
+                    } else {
+                        if !current_line.is_empty() {
+                            lines.push(std::mem::take(&mut current_line));
+                        }
+                        current_line.push_str(word);
+                    }
+                }
+                lines.push(current_line);
+            }
+            lines = panels::fit(lines, width, height);
+        }
+        "table" => lines = panels::fit(panels::table(&panel.content, width), width, height),
+        "tree" => lines = panels::fit(panels::tree(&panel.content), width, height),
+        "log" => lines = panels::fit(panels::log_tail(&panel.content, height), width, height),
+        "image" => lines = panels::image_lines(&panel.content, width, height),
         _ => {
             // Fallback for unknown types
             for _ in 0..height {
@@ -359,6 +463,26 @@ fn render_panel_content(panel: &Panel, width: usize, height: usize) -> Vec<Strin
     lines
 }
 
+/// Sparkline numbers; semicolons (preferred, since commas separate panels),
+/// commas or whitespace between them
+fn sparkline_values(content: &str) -> Vec<f64> {
+    content
+        .split(|c: char| c == ';' || c == ',' || c.is_whitespace())
+        .filter_map(|s| s.trim().parse().ok())
+        .collect()
+}
+
+/// Chart bars from `Label:Value` pairs separated by semicolons or newlines
+fn chart_values(content: &str) -> Vec<(String, f64)> {
+    content
+        .split([';', '\n'])
+        .filter_map(|item| {
+            let (label, value) = item.rsplit_once(':')?;
+            Some((label.trim().to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
 /// Generate sparkline from values
 fn generate_sparkline(values: &[f64], max_width: usize) -> String {
     if values.is_empty() {
@@ -407,6 +531,7 @@ pub fn render(
     panels_str: Option<&str>,
     config_path: Option<&str>,
     border_style: &str,
+    live: bool,
 ) {
     // Load configuration
     let mut config = if let Some(path) = config_path {
@@ -419,7 +544,7 @@ pub fn render(
         }
     } else {
         DashboardConfig {
-            layout: layout_str.to_string(),
+            layout: LayoutSpec::Grid(layout_str.to_string()),
             title: title.map(|s| s.to_string()),
             panels: Vec::new(),
         }
@@ -439,31 +564,56 @@ pub fn render(
         config.title = title.map(|s| s.to_string());
     }
 
-    // Parse layout
-    let layout = match parse_layout(&config.layout) {
-        Ok(l) => l,
+    let (grid, node) = match resolve_layout(&config) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut samplers = match samplers(&config.panels) {
+        Ok(samplers) => samplers,
         Err(e) => {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
     };
 
-    // Validate panel count
-    let expected_panels = layout.rows * layout.cols;
-    if config.panels.len() != expected_panels {
-        eprintln!(
-            "Error: Expected {} panels for layout {}, but got {}",
-            expected_panels,
-            config.layout,
-            config.panels.len()
-        );
-        std::process::exit(1);
+    if live {
+        if let Err(e) = live::run(&config, samplers, &node, border_style) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
     }
 
+    // Without --live, commands and sources fill only the panels left empty
+    for (panel, sampler) in config.panels.iter_mut().zip(samplers.iter_mut()) {
+        if !panel.content.is_empty() {
+            continue;
+        }
+        let reading = match (&panel.command, sampler) {
+            (Some(command), _) => watch::exec_command(command),
+            (None, Some(sampler)) => sampler.sample().map(|value| format!("{:.1}", value)),
+            (None, None) => continue,
+        };
+        panel.content = reading.unwrap_or_else(|e| e);
+    }
+
+    // Nested layouts are drawn by ratatui; grids keep the hand-drawn output
+    let Some(layout) = grid else {
+        let output = layout::print(config.title.as_deref(), &node, &config.panels, border_style);
+        let mut handle = color::stdout();
+        handle.write_all(output.as_bytes()).unwrap();
+        handle.flush().unwrap();
+        return;
+    };
+
     let border = BorderStyle::from_name(border_style);
 
     // Calculate dimensions
-    let term_width = 80; // Default terminal width
+    let term_width = layout::PRINT_WIDTH as usize;
     let panel_width = (term_width / layout.cols).saturating_sub(2); // -2 for borders
     let panel_height = 5; // Fixed height per panel
 
@@ -481,7 +631,8 @@ pub fn render(
     // Top border
     output.push(border.top_left);
     for col in 0..layout.cols {
-        output.push_str(&border.horizontal.to_string().repeat(panel_width));
+        let panel = &config.panels[col];
+        output.push_str(&border.segment(panel.title.as_deref(), panel_width));
         if col < layout.cols - 1 {
             output.push(border.t_down);
         }
@@ -512,7 +663,8 @@ pub fn render(
         if row < layout.rows - 1 {
             output.push(border.t_right);
             for col in 0..layout.cols {
-                output.push_str(&border.horizontal.to_string().repeat(panel_width));
+                let panel = &config.panels[(row + 1) * layout.cols + col];
+                output.push_str(&border.segment(panel.title.as_deref(), panel_width));
                 if col < layout.cols - 1 {
                     output.push(border.cross);
                 }
@@ -534,8 +686,47 @@ pub fn render(
     output.push('\n');
 
     // Write to stdout
-    let stdout = io::stdout();
-    let mut handle = stdout.lock();
+    let mut handle = color::stdout();
     handle.write_all(output.as_bytes()).unwrap();
     handle.flush().unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_config_panels_take_command_and_interval() {
+        let config: DashboardConfig = serde_json::from_str(
+            r#"{"layout": "1x2", "panels": [
+                {"type": "gauge", "command": "echo 42", "interval": "500ms"},
+                {"type": "box", "content": "Hi"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.panels[0].command.as_deref(), Some("echo 42"));
+        assert_eq!(config.panels[0].content, "");
+        assert_eq!(config.panels[1].interval, None);
+    }
+
+    #[test]
+    fn test_resolve_layout_checks_panels() {
+        let config: DashboardConfig = serde_json::from_str(
+            r#"{"layout": "1x2", "panels": [{"type": "box", "content": "Hi"}]}"#,
+        )
+        .unwrap();
+        let err = resolve_layout(&config).unwrap_err();
+        assert_eq!(err, "Expected 2 panels for layout 1x2, but got 1");
+
+        let config: DashboardConfig = serde_json::from_str(
+            r#"{"layout": {"columns": [{"panel": 0}, {"panel": 0}]},
+                "panels": [{"type": "box", "title": "Logs"}]}"#,
+        )
+        .unwrap();
+        let (grid, node) = resolve_layout(&config).unwrap();
+        assert!(grid.is_none());
+        assert_eq!(node.areas(Rect::new(0, 0, 10, 5)).len(), 2);
+        assert_eq!(config.panels[0].title.as_deref(), Some("Logs"));
+    }
+}
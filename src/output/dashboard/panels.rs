@@ -0,0 +1,244 @@
+//! Panels that show a file: a CSV `table`, a `log` tail and an `image`
+//! thumbnail, plus the inline `tree`. Each is cut down to fit its cell.
+
+use crate::image::halfblocks;
+use crate::output::input;
+use crate::text;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// How far from the end of a log file to look for its last lines
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// Cells of one image row: top and bottom pixel colors
+pub(super) type ImageRow = Vec<[[u8; 3]; 2]>;
+
+/// A scaled image, or why it couldn't be read
+type Image = Result<Vec<ImageRow>, String>;
+
+thread_local! {
+    /// Scaled images by path and cell size, so live redraws don't decode
+    /// them again
+    static IMAGES: RefCell<HashMap<(String, u16, u16), Image>> =
+        RefCell::new(HashMap::new());
+}
+
+/// `lines` cut to `width` columns and `height` rows, padded to fill both
+pub(super) fn fit(lines: Vec<String>, width: usize, height: usize) -> Vec<String> {
+    let mut lines: Vec<String> = lines
+        .into_iter()
+        .take(height)
+        .map(|line| {
+            let line = text::truncate(&line, width, "…");
+            let pad = width.saturating_sub(text::display_width(&line));
+            format!("{}{}", line, " ".repeat(pad))
+        })
+        .collect();
+    lines.resize(height, " ".repeat(width));
+    lines
+}
+
+/// A CSV file (header row first) as columns that share `width`; the widest
+/// columns give way first
+pub(super) fn table(path: &str, width: usize) -> Vec<String> {
+    let content = match input::read_text(path) {
+        Ok(content) => content,
+        Err(e) => return vec![format!("{}: {}", path, e)],
+    };
+    let rows: Vec<Vec<&str>> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.split(',').map(str::trim).collect())
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return Vec::new();
+    }
+
+    let mut widths = vec![0; columns];
+    for row in &rows {
+        for (col, cell) in row.iter().enumerate() {
+            widths[col] = widths[col].max(text::display_width(cell));
+        }
+    }
+    let gaps = 3 * (columns - 1);
+    while widths.iter().sum::<usize>() + gaps > width {
+        let widest = (0..columns).max_by_key(|&col| widths[col]).unwrap_or(0);
+        if widths[widest] <= 1 {
+            break;
+        }
+        widths[widest] -= 1;
+    }
+
+    let line = |row: &[&str]| {
+        (0..columns)
+            .map(|col| {
+                let cell = text::truncate(row.get(col).copied().unwrap_or(""), widths[col], "…");
+                let pad = widths[col].saturating_sub(text::display_width(&cell));
+                format!("{}{}", cell, " ".repeat(pad))
+            })
+            .collect::<Vec<_>>()
+            .join(" │ ")
+    };
+    let rule = widths
+        .iter()
+        .map(|w| "─".repeat(*w))
+        .collect::<Vec<_>>()
+        .join("─┼─");
+    let mut lines = vec![line(&rows[0]), rule];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines
+}
+
+/// Inline tree data as taken by `termgfx tree`: "root>a,b>c", each `>`
+/// going a level deeper
+pub(super) fn tree(data: &str) -> Vec<String> {
+    let mut levels = data.split('>').map(str::trim);
+    let Some(root) = levels.next().filter(|root| !root.is_empty()) else {
+        return Vec::new();
+    };
+    let mut lines = vec![root.to_string()];
+    for (depth, level) in levels.enumerate() {
+        let children: Vec<&str> = level.split(',').map(str::trim).collect();
+        for (i, child) in children.iter().enumerate() {
+            let branch = if i + 1 == children.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            lines.push(format!("{}{} {}", "   ".repeat(depth), branch, child));
+        }
+    }
+    lines
+}
+
+/// The last `count` lines of a log file, escapes stripped
+pub(super) fn log_tail(path: &str, count: usize) -> Vec<String> {
+    match read_tail(path) {
+        Ok(content) => {
+            let lines: Vec<&str> = content.lines().collect();
+            lines[lines.len().saturating_sub(count)..]
+                .iter()
+                .map(|line| text::strip_ansi(line).into_owned())
+                .collect()
+        }
+        Err(e) => vec![format!("{}: {}", path, e)],
+    }
+}
+
+/// The end of a file, starting on a whole line when it had to skip ahead
+fn read_tail(path: &str) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let mut content = String::from_utf8_lossy(&bytes).into_owned();
+    if start > 0 {
+        content = content
+            .split_once('\n')
+            .map(|(_, rest)| rest.to_string())
+            .unwrap_or_default();
+    }
+    Ok(content)
+}
+
+/// An image scaled to fit `width` x `height` cells
+pub(super) fn image(path: &str, width: u16, height: u16) -> Image {
+    IMAGES.with(|images| {
+        images
+            .borrow_mut()
+            .entry((path.to_string(), width, height))
+            .or_insert_with(|| {
+                halfblocks(path, width.into(), height.into())
+                    .map_err(|e| format!("{}: {}", path, e))
+            })
+            .clone()
+    })
+}
+
+/// An image as lines of ▀ with truecolor escapes, centered in `width`
+pub(super) fn image_lines(path: &str, width: usize, height: usize) -> Vec<String> {
+    let rows = match image(path, width as u16, height as u16) {
+        Ok(rows) => rows,
+        Err(e) => return fit(vec![e], width, height),
+    };
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let left = width.saturating_sub(row.len()) / 2;
+            let cells: String = row
+                .iter()
+                .map(|[top, bottom]| {
+                    format!(
+                        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                        top[0], top[1], top[2], bottom[0], bottom[1], bottom[2]
+                    )
+                })
+                .collect();
+            format!(
+                "{}{}\x1b[0m{}",
+                " ".repeat(left),
+                cells,
+                " ".repeat(width.saturating_sub(left + row.len()))
+            )
+        })
+        .collect();
+    lines.resize(height, " ".repeat(width));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn file(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_fit_truncates_and_pads() {
+        let lines = fit(vec!["abcdefgh".to_string(), "ab".to_string()], 5, 3);
+        assert_eq!(lines, ["abcd…", "ab   ", "     "]);
+    }
+
+    #[test]
+    fn test_table_shrinks_widest_column() {
+        let csv = file("name,description\napi,handles every request\n");
+        let path = csv.path().to_str().unwrap();
+        assert_eq!(
+            table(path, 40),
+            [
+                "name │ description          ",
+                "─────┼──────────────────────",
+                "api  │ handles every request",
+            ]
+        );
+        let narrow = table(path, 16);
+        assert_eq!(narrow[2], "api  │ handles …");
+        assert!(narrow.iter().all(|l| text::display_width(l) <= 16));
+    }
+
+    #[test]
+    fn test_tree_levels() {
+        assert_eq!(
+            tree("app>src,tests>main.rs"),
+            ["app", "├─ src", "└─ tests", "   └─ main.rs"]
+        );
+        assert!(tree("").is_empty());
+    }
+
+    #[test]
+    fn test_log_tail_keeps_last_lines() {
+        let log = file("one\ntwo\n\x1b[31mthree\x1b[0m\n");
+        assert_eq!(log_tail(log.path().to_str().unwrap(), 2), ["two", "three"]);
+        assert!(log_tail("/no/such.log", 2)[0].starts_with("/no/such.log: "));
+    }
+}
@@ -1,43 +1,196 @@
+use crate::interactive::review;
+use crate::output::input;
+use crate::text;
 use owo_colors::{OwoColorize, Style};
-use similar::{ChangeTag, TextDiff};
+use similar::{ChangeTag, DiffOp, TextDiff};
+use std::collections::BTreeMap;
 use std::fs;
-use unicode_width::UnicodeWidthStr;
+use std::io;
+use std::path::{Path, PathBuf};
 
-/// Render a side-by-side diff of two files
-pub fn render(file1: &str, file2: &str, unified: bool, context: Option<usize>) {
-    // Read files
-    let content1 = match fs::read_to_string(file1) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("{} {}: {}", "Error reading".bright_red().bold(), file1, e);
+/// One line of a diff, split into runs; emphasized runs are the words that changed
+struct LineChange {
+    tag: ChangeTag,
+    old_index: Option<usize>,
+    new_index: Option<usize>,
+    segments: Vec<(bool, String)>,
+}
+
+impl LineChange {
+    fn text(&self) -> String {
+        self.segments.iter().map(|(_, s)| s.as_str()).collect()
+    }
+}
+
+/// Render a side-by-side diff of two files (either may be `-` for stdin)
+///
+/// Two directories are compared file by file and summarized as a tree.
+/// With `interactive`, the hunks are reviewed one by one instead and the
+/// accepted ones printed as a patch.
+pub fn render(
+    file1: &str,
+    file2: &str,
+    unified: bool,
+    context: Option<usize>,
+    word_diff: bool,
+    interactive: bool,
+) {
+    if let Err(e) = input::check_single_stdin([file1, file2]) {
+        eprintln!("{} {}", "Error:".bright_red().bold(), e);
+        std::process::exit(1);
+    }
+
+    let (is_dir1, is_dir2) = (Path::new(file1).is_dir(), Path::new(file2).is_dir());
+    if interactive && (is_dir1 || is_dir2) {
+        eprintln!(
+            "{} --interactive compares two files, not directories",
+            "Error:".bright_red().bold()
+        );
+        std::process::exit(1);
+    }
+    if is_dir1 && is_dir2 {
+        if let Err(e) = render_dirs(Path::new(file1), Path::new(file2)) {
+            eprintln!("{} {}", "Error:".bright_red().bold(), e);
             std::process::exit(1);
         }
-    };
+        return;
+    }
+    if is_dir1 || is_dir2 {
+        eprintln!(
+            "{} cannot compare a directory with a file",
+            "Error:".bright_red().bold()
+        );
+        std::process::exit(1);
+    }
 
-    let content2 = match fs::read_to_string(file2) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("{} {}: {}", "Error reading".bright_red().bold(), file2, e);
+    let bytes1 = read_or_exit(file1);
+    let bytes2 = read_or_exit(file2);
+    let (name1, name2) = (input::display_name(file1), input::display_name(file2));
+
+    // Like diff(1), don't try to line-diff binary content
+    if input::is_binary(&bytes1) || input::is_binary(&bytes2) {
+        if interactive {
+            eprintln!(
+                "{} --interactive can't review binary files",
+                "Error:".bright_red().bold()
+            );
             std::process::exit(1);
         }
-    };
+        if bytes1 == bytes2 {
+            println!("Binary files {} and {} are identical", name1, name2);
+        } else {
+            println!("Binary files {} and {} differ", name1, name2);
+        }
+        return;
+    }
+
+    let content1 = String::from_utf8_lossy(&bytes1);
+    let content2 = String::from_utf8_lossy(&bytes2);
+
+    if interactive {
+        review::render(&content1, &content2, name1, name2, context);
+        return;
+    }
 
     // Create diff
-    let diff = TextDiff::from_lines(&content1, &content2);
+    let diff = TextDiff::from_lines(content1.as_ref(), content2.as_ref());
 
     if unified {
-        render_unified(&diff, file1, file2, context);
+        render_unified(&diff, name1, name2, context, word_diff);
+    } else {
+        render_side_by_side(&diff, name1, name2, word_diff);
+    }
+}
+
+/// Changes for one diff op; with `word_diff`, replaced lines carry per-word emphasis
+fn line_changes<'a>(
+    diff: &'a TextDiff<'a, 'a, 'a, str>,
+    op: &DiffOp,
+    word_diff: bool,
+) -> Vec<LineChange> {
+    if word_diff {
+        diff.iter_inline_changes(op)
+            .map(|change| LineChange {
+                tag: change.tag(),
+                old_index: change.old_index(),
+                new_index: change.new_index(),
+                segments: change
+                    .iter_strings_lossy()
+                    .map(|(emphasized, text)| (emphasized, text.into_owned()))
+                    .collect(),
+            })
+            .collect()
     } else {
-        render_side_by_side(&diff, file1, file2);
+        diff.iter_changes(op)
+            .map(|change| LineChange {
+                tag: change.tag(),
+                old_index: change.old_index(),
+                new_index: change.new_index(),
+                segments: vec![(false, change.value().to_string())],
+            })
+            .collect()
+    }
+}
+
+/// Background highlight for changed words on a deleted or inserted line
+fn emphasis(tag: ChangeTag) -> Style {
+    match tag {
+        ChangeTag::Delete => Style::new().white().on_red().bold(),
+        ChangeTag::Insert => Style::new().black().on_green().bold(),
+        ChangeTag::Equal => Style::new().dimmed(),
+    }
+}
+
+/// Style each run of a line, dropping its trailing newline
+fn styled_segments(segments: &[(bool, String)], base: Style, emph: Style) -> String {
+    let mut out = String::new();
+    for (emphasized, text) in segments {
+        let text = text.trim_end_matches('\n');
+        if text.is_empty() {
+            continue;
+        }
+        let style = if *emphasized { emph } else { base };
+        out.push_str(&text.style(style).to_string());
+    }
+    out
+}
+
+/// Like `truncate_or_pad`, but keeps per-run styling
+fn fit_segments(segments: &[(bool, String)], width: usize, base: Style, emph: Style) -> String {
+    let text: String = segments.iter().map(|(_, s)| s.as_str()).collect();
+    if text::display_width(&text) > width {
+        return truncate_or_pad(&text, width).style(base).to_string();
+    }
+    let padding = width - text::display_width(&text);
+    format!(
+        "{}{}",
+        styled_segments(segments, base, emph),
+        " ".repeat(padding)
+    )
+}
+
+fn read_or_exit(path: &str) -> Vec<u8> {
+    match input::read_bytes(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!(
+                "{} {}: {}",
+                "Error reading".bright_red().bold(),
+                input::display_name(path),
+                e
+            );
+            std::process::exit(1);
+        }
     }
 }
 
 /// Render unified diff format
-fn render_unified(
-    diff: &TextDiff<'_, '_, '_, str>,
+fn render_unified<'a>(
+    diff: &'a TextDiff<'a, 'a, 'a, str>,
     file1: &str,
     file2: &str,
     context: Option<usize>,
+    word_diff: bool,
 ) {
     // Header
     println!("{} {}", "───".bright_cyan(), file1.bright_cyan().bold());
@@ -52,34 +205,35 @@ fn render_unified(
         }
 
         for op in group {
-            for change in diff.iter_changes(op) {
-                let (sign, style): (&str, Style) = match change.tag() {
+            for change in line_changes(diff, op, word_diff) {
+                let (sign, style): (&str, Style) = match change.tag {
                     ChangeTag::Delete => ("-", Style::new().bright_red()),
                     ChangeTag::Insert => ("+", Style::new().bright_green()),
                     ChangeTag::Equal => (" ", Style::new().dimmed()),
                 };
 
-                let line_num = change
-                    .old_index()
-                    .unwrap_or(change.new_index().unwrap_or(0))
-                    + 1;
+                let line_num = change.old_index.unwrap_or(change.new_index.unwrap_or(0)) + 1;
                 print!(
                     "{} ",
                     format!("{:>4}", line_num).style(Style::new().dimmed())
                 );
                 print!("{} ", sign.style(style));
-                print!("{}", change.value().style(style));
-
-                if !change.value().ends_with('\n') {
-                    println!();
-                }
+                println!(
+                    "{}",
+                    styled_segments(&change.segments, style, emphasis(change.tag))
+                );
             }
         }
     }
 }
 
 /// Render side-by-side diff format
-fn render_side_by_side(diff: &TextDiff<'_, '_, '_, str>, file1: &str, file2: &str) {
+fn render_side_by_side<'a>(
+    diff: &'a TextDiff<'a, 'a, 'a, str>,
+    file1: &str,
+    file2: &str,
+    word_diff: bool,
+) {
     // Get terminal width
     let term_width = termion::terminal_size()
         .map(|(w, _)| w as usize)
@@ -109,10 +263,12 @@ fn render_side_by_side(diff: &TextDiff<'_, '_, '_, str>, file1: &str, file2: &st
     let mut new_line = 1;
 
     for op in diff.ops() {
-        for change in diff.iter_changes(op) {
-            match change.tag() {
+        for change in line_changes(diff, op, word_diff) {
+            let mut segments = change.segments.clone();
+            match change.tag {
                 ChangeTag::Equal => {
-                    let line = change.value().trim_end_matches('\n');
+                    let text = change.text();
+                    let line = text.trim_end_matches('\n');
                     let left = format!(
                         "{:>4} {}",
                         old_line.to_string().dimmed(),
@@ -128,11 +284,16 @@ fn render_side_by_side(diff: &TextDiff<'_, '_, '_, str>, file1: &str, file2: &st
                     new_line += 1;
                 }
                 ChangeTag::Delete => {
-                    let line = change.value().trim_end_matches('\n');
+                    segments.insert(0, (false, "- ".to_string()));
                     let left = format!(
                         "{:>4} {}",
                         old_line.to_string().bright_red(),
-                        truncate_or_pad(&format!("- {}", line), col_width).bright_red()
+                        fit_segments(
+                            &segments,
+                            col_width,
+                            Style::new().bright_red(),
+                            emphasis(change.tag)
+                        )
                     );
                     let right = format!(
                         "{:>4} {}",
@@ -143,7 +304,7 @@ fn render_side_by_side(diff: &TextDiff<'_, '_, '_, str>, file1: &str, file2: &st
                     old_line += 1;
                 }
                 ChangeTag::Insert => {
-                    let line = change.value().trim_end_matches('\n');
+                    segments.insert(0, (false, "+ ".to_string()));
                     let left = format!(
                         "{:>4} {}",
                         "".dimmed(),
@@ -152,7 +313,12 @@ fn render_side_by_side(diff: &TextDiff<'_, '_, '_, str>, file1: &str, file2: &st
                     let right = format!(
                         "{:>4} {}",
                         new_line.to_string().bright_green(),
-                        truncate_or_pad(&format!("+ {}", line), col_width).bright_green()
+                        fit_segments(
+                            &segments,
+                            col_width,
+                            Style::new().bright_green(),
+                            emphasis(change.tag)
+                        )
                     );
                     println!("{}{}{}", left, separator.dimmed(), right);
                     new_line += 1;
@@ -164,16 +330,196 @@ fn render_side_by_side(diff: &TextDiff<'_, '_, '_, str>, file1: &str, file2: &st
     println!("{}", header_separator.bright_cyan());
 }
 
+/// How a file differs between two directory trees
+#[derive(Debug, PartialEq)]
+enum FileStatus {
+    Added,
+    Removed,
+    Modified { insertions: usize, deletions: usize },
+    BinaryModified,
+    Identical,
+}
+
+/// Compare two directories file by file and print a summary tree
+fn render_dirs(old: &Path, new: &Path) -> io::Result<()> {
+    let statuses = compare_dirs(old, new)?;
+
+    println!(
+        "{} {} {} {}",
+        "───".bright_cyan(),
+        old.display().to_string().bright_cyan().bold(),
+        "→".bright_cyan(),
+        new.display().to_string().bright_cyan().bold()
+    );
+    let changed: Vec<_> = statuses
+        .iter()
+        .filter(|(_, s)| **s != FileStatus::Identical)
+        .collect();
+    let identical = statuses.len() - changed.len();
+    if changed.is_empty() {
+        println!("{}", "No differences".dimmed());
+        return Ok(());
+    }
+
+    for line in summary_tree(&changed) {
+        println!("{}", line);
+    }
+
+    let (mut insertions, mut deletions) = (0, 0);
+    for (_, status) in &changed {
+        if let FileStatus::Modified {
+            insertions: i,
+            deletions: d,
+        } = status
+        {
+            insertions += i;
+            deletions += d;
+        }
+    }
+    println!();
+    println!(
+        "{} changed, {}, {}{}",
+        plural(changed.len(), "file"),
+        format!("{}(+)", plural(insertions, "insertion")).bright_green(),
+        format!("{}(-)", plural(deletions, "deletion")).bright_red(),
+        format!(", {} unchanged", identical).dimmed()
+    );
+    Ok(())
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, word)
+    } else {
+        format!("{} {}s", n, word)
+    }
+}
+
+/// Status of every file under either root, keyed by relative path
+fn compare_dirs(old: &Path, new: &Path) -> io::Result<BTreeMap<PathBuf, FileStatus>> {
+    let old_files = list_files(old)?;
+    let new_files = list_files(new)?;
+    let mut statuses = BTreeMap::new();
+
+    for rel in old_files.iter().chain(new_files.iter()) {
+        if statuses.contains_key(rel) {
+            continue;
+        }
+        let status = match (old_files.contains(rel), new_files.contains(rel)) {
+            (true, false) => FileStatus::Removed,
+            (false, true) => FileStatus::Added,
+            _ => compare_files(&old.join(rel), &new.join(rel))?,
+        };
+        statuses.insert(rel.clone(), status);
+    }
+    Ok(statuses)
+}
+
+fn compare_files(old: &Path, new: &Path) -> io::Result<FileStatus> {
+    let (a, b) = (fs::read(old)?, fs::read(new)?);
+    if a == b {
+        return Ok(FileStatus::Identical);
+    }
+    if input::is_binary(&a) || input::is_binary(&b) {
+        return Ok(FileStatus::BinaryModified);
+    }
+    let (a, b) = (String::from_utf8_lossy(&a), String::from_utf8_lossy(&b));
+    let diff = TextDiff::from_lines(a.as_ref(), b.as_ref());
+    let (mut insertions, mut deletions) = (0, 0);
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => insertions += 1,
+            ChangeTag::Delete => deletions += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    Ok(FileStatus::Modified {
+        insertions,
+        deletions,
+    })
+}
+
+/// Relative paths of all files below `root`, sorted
+fn list_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                walk(root, &path, out)?;
+            } else if let Ok(rel) = path.strip_prefix(root) {
+                out.push(rel.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(root, root, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// Tree of changed files: `+` added, `-` removed, `~` modified with line counts
+fn summary_tree(changed: &[(&PathBuf, &FileStatus)]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut printed_dirs: Vec<PathBuf> = Vec::new();
+
+    for (path, status) in changed {
+        // Print any directories along the way that haven't been shown yet
+        let mut dir = PathBuf::new();
+        let components: Vec<_> = path.components().collect();
+        for (depth, component) in components.iter().enumerate().take(components.len() - 1) {
+            dir.push(component);
+            if !printed_dirs.contains(&dir) {
+                lines.push(format!(
+                    "  {}📁 {}",
+                    "    ".repeat(depth),
+                    component.as_os_str().to_string_lossy().bright_cyan()
+                ));
+                printed_dirs.push(dir.clone());
+            }
+        }
+
+        let depth = components.len() - 1;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let indent = "    ".repeat(depth);
+        let line = match status {
+            FileStatus::Added => format!("+ {}{}", indent, name).bright_green().to_string(),
+            FileStatus::Removed => format!("- {}{}", indent, name).bright_red().to_string(),
+            FileStatus::BinaryModified => format!("~ {}{} (binary)", indent, name)
+                .yellow()
+                .to_string(),
+            FileStatus::Modified {
+                insertions,
+                deletions,
+            } => format!(
+                "{} {}{} {} {}",
+                "~".yellow(),
+                indent,
+                name.yellow(),
+                format!("+{}", insertions).bright_green(),
+                format!("-{}", deletions).bright_red()
+            ),
+            FileStatus::Identical => continue,
+        };
+        lines.push(line);
+    }
+    lines
+}
+
 /// Truncate or pad string to exact width
 fn truncate_or_pad(s: &str, width: usize) -> String {
-    let current_width = UnicodeWidthStr::width(s);
+    let current_width = text::display_width(s);
 
     if current_width > width {
         let mut result = String::new();
         let mut current = 0;
 
         for ch in s.chars() {
-            let ch_width = UnicodeWidthStr::width(ch.to_string().as_str());
+            let ch_width = text::char_width(ch);
             if current + ch_width > width.saturating_sub(3) {
                 result.push_str("...");
                 break;
@@ -192,6 +538,80 @@ fn truncate_or_pad(s: &str, width: usize) -> String {
 mod tests {
     use super::*;
 
+    fn plain(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*m")
+            .unwrap()
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    #[test]
+    fn test_word_diff_emphasizes_changed_words() {
+        let diff = TextDiff::from_lines("let x = 1;\n", "let x = 2;\n");
+        let changes: Vec<LineChange> = diff
+            .ops()
+            .iter()
+            .flat_map(|op| line_changes(&diff, op, true))
+            .collect();
+        let deleted = changes.iter().find(|c| c.tag == ChangeTag::Delete).unwrap();
+        assert!(deleted.segments.contains(&(true, "1;".to_string())));
+        assert!(deleted
+            .segments
+            .iter()
+            .any(|(e, s)| !e && s.contains("let")));
+    }
+
+    #[test]
+    fn test_fit_segments_pads_and_truncates() {
+        let segments = vec![(false, "ab".to_string()), (true, "cd".to_string())];
+        let base = Style::new();
+        let emph = Style::new().on_red();
+        assert_eq!(plain(&fit_segments(&segments, 6, base, emph)), "abcd  ");
+        assert_eq!(plain(&fit_segments(&segments, 3, base, emph)), "...");
+    }
+
+    #[test]
+    fn test_compare_dirs() {
+        let root = std::env::temp_dir().join(format!("termgfx_diff_dirs_{}", std::process::id()));
+        let (old, new) = (root.join("old"), root.join("new"));
+        fs::create_dir_all(old.join("src")).unwrap();
+        fs::create_dir_all(new.join("src")).unwrap();
+        fs::write(old.join("README"), "same\n").unwrap();
+        fs::write(new.join("README"), "same\n").unwrap();
+        fs::write(old.join("src/main.rs"), "a\nb\n").unwrap();
+        fs::write(new.join("src/main.rs"), "a\nc\nd\n").unwrap();
+        fs::write(old.join("gone.txt"), "x").unwrap();
+        fs::write(new.join("src/new.rs"), "y").unwrap();
+
+        let statuses = compare_dirs(&old, &new).unwrap();
+        assert_eq!(statuses[Path::new("README")], FileStatus::Identical);
+        assert_eq!(statuses[Path::new("gone.txt")], FileStatus::Removed);
+        assert_eq!(statuses[Path::new("src/new.rs")], FileStatus::Added);
+        assert_eq!(
+            statuses[Path::new("src/main.rs")],
+            FileStatus::Modified {
+                insertions: 2,
+                deletions: 1
+            }
+        );
+
+        let changed: Vec<_> = statuses
+            .iter()
+            .filter(|(_, s)| **s != FileStatus::Identical)
+            .collect();
+        let tree: Vec<String> = summary_tree(&changed).iter().map(|l| plain(l)).collect();
+        assert_eq!(
+            tree,
+            vec![
+                "- gone.txt",
+                "  📁 src",
+                "~     main.rs +2 -1",
+                "+     new.rs"
+            ]
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn test_truncate_or_pad() {
         assert_eq!(truncate_or_pad("hello", 10), "hello     ");
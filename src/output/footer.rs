@@ -0,0 +1,364 @@
+//! Live status footer around a wrapped command
+//!
+//! The bottom lines of the terminal are fenced off with a scroll region
+//! (DECSTBM), so the command's output scrolls normally above them while the
+//! footer keeps showing a spinner, the elapsed time and the latest errors.
+//! The command's stdout and stderr are piped, so programs that only color
+//! output for a terminal will print plain text.
+
+use crate::output::color;
+use crate::output::spinner;
+use crate::text;
+use owo_colors::OwoColorize;
+use std::collections::VecDeque;
+use std::io::{stdout, BufRead, BufReader, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const TICK: Duration = Duration::from_millis(100);
+
+/// A line of output from the wrapped command
+enum Event {
+    Out(String),
+    Err(String),
+}
+
+/// What the footer shows
+struct Status {
+    command: String,
+    start: Instant,
+    lines: usize,
+    errors: VecDeque<String>,
+}
+
+impl Status {
+    /// Remember `line` if it looks like an error, keeping the newest `keep`
+    fn record(&mut self, line: &str, from_stderr: bool, keep: usize) {
+        self.lines += 1;
+        if keep > 0 && is_error_line(line, from_stderr) {
+            if self.errors.len() == keep {
+                self.errors.pop_front();
+            }
+            self.errors
+                .push_back(text::strip_ansi(line).trim().to_string());
+        }
+    }
+}
+
+/// Run `command` under a footer `height` lines tall; returns its exit code
+pub fn render(command: &str, height: usize, style: &str) -> Result<i32, String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute command: {}", e))?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(out) = child.stdout.take() {
+        forward_lines(out, tx.clone(), Event::Out);
+    }
+    if let Some(err) = child.stderr.take() {
+        forward_lines(err, tx, Event::Err);
+    }
+
+    let interactive = stdout().is_terminal();
+    let mut status = Status {
+        command: command.to_string(),
+        start: Instant::now(),
+        lines: 0,
+        errors: VecDeque::new(),
+    };
+    let frames = spinner::get_spinner_frames(style);
+    let mut frame = 0;
+    let mut out = color::stdout();
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let mut region = None;
+    if interactive {
+        // Ctrl+C also reaches the child; we only need to restore the terminal
+        let flag = interrupted.clone();
+        ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst)).ok();
+        region = Some(reserve(&mut out, height)?);
+    }
+    let keep = height.saturating_sub(1);
+    let mut last_draw = Instant::now();
+
+    loop {
+        match rx.recv_timeout(TICK) {
+            Ok(Event::Out(line)) => {
+                status.record(&line, false, keep);
+                println!("{}", line);
+            }
+            Ok(Event::Err(line)) => {
+                status.record(&line, true, keep);
+                if interactive {
+                    println!("{}", line);
+                } else {
+                    eprintln!("{}", line);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if interrupted.load(Ordering::SeqCst) {
+            break;
+        }
+        // Redraw on a fixed tick so fast output doesn't flood the terminal
+        if let Some(size) = region.filter(|_| last_draw.elapsed() >= TICK) {
+            frame = (frame + 1) % frames.len();
+            region = Some(refresh(&mut out, size, height, &status, frames[frame])?);
+            last_draw = Instant::now();
+        }
+    }
+
+    let code = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for command: {}", e))?
+        .code()
+        .unwrap_or(130);
+    if let Some((_, rows)) = region {
+        release(&mut out, rows, height)?;
+    }
+
+    let summary = summary_line(command, code, status.start.elapsed());
+    if interactive {
+        println!("{}", summary);
+    } else {
+        eprintln!("{}", summary);
+    }
+    Ok(code)
+}
+
+/// Send each line of `source` down `tx` from a background thread
+fn forward_lines<R: Read + Send + 'static>(
+    source: R,
+    tx: Sender<Event>,
+    wrap: fn(String) -> Event,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut buf = Vec::new();
+        while matches!(reader.read_until(b'\n', &mut buf), Ok(n) if n > 0) {
+            let line = String::from_utf8_lossy(&buf)
+                .trim_end_matches(['\n', '\r'])
+                .to_string();
+            if tx.send(wrap(line)).is_err() {
+                break;
+            }
+            buf.clear();
+        }
+    });
+}
+
+fn terminal_size() -> (usize, usize) {
+    crossterm::terminal::size()
+        .map(|(w, h)| (w as usize, h as usize))
+        .ok()
+        .filter(|(w, h)| *w > 0 && *h > 0)
+        .unwrap_or((80, 24))
+}
+
+/// Footer height that still leaves at least half the screen for output
+fn clamp_height(height: usize, rows: usize) -> usize {
+    height.clamp(1, (rows / 2).max(1))
+}
+
+/// Escape sequence limiting scrolling to the rows above the footer
+fn scroll_region(rows: usize, height: usize) -> String {
+    format!("\x1b[1;{}r", rows.saturating_sub(height).max(1))
+}
+
+/// Make room for the footer and fence it off; returns the terminal size
+fn reserve(out: &mut color::Stdout, height: usize) -> Result<(usize, usize), String> {
+    let (cols, rows) = terminal_size();
+    let height = clamp_height(height, rows);
+    // Scroll existing content up so the footer doesn't cover it, then fence
+    // it off; DECSTBM homes the cursor, so save and restore around it
+    write!(
+        out,
+        "{}\x1b[{}A\x1b7{}\x1b8",
+        "\n".repeat(height),
+        height,
+        scroll_region(rows, height)
+    )
+    .and_then(|_| out.flush())
+    .map_err(|e| e.to_string())?;
+    Ok((cols, rows))
+}
+
+/// Redraw the footer, re-fencing it if the terminal was resized
+fn refresh(
+    out: &mut color::Stdout,
+    size: (usize, usize),
+    height: usize,
+    status: &Status,
+    frame: &str,
+) -> Result<(usize, usize), String> {
+    let (cols, rows) = terminal_size();
+    let height = clamp_height(height, rows);
+    let mut seq = String::from("\x1b7");
+    if (cols, rows) != size {
+        seq.push_str(&scroll_region(rows, height));
+    }
+    for (idx, line) in footer_lines(status, frame, height, cols).iter().enumerate() {
+        seq.push_str(&format!(
+            "\x1b[{};1H\x1b[2K{}",
+            rows - height + 1 + idx,
+            line
+        ));
+    }
+    seq.push_str("\x1b8");
+    write!(out, "{}", seq)
+        .and_then(|_| out.flush())
+        .map_err(|e| e.to_string())?;
+    Ok((cols, rows))
+}
+
+/// Drop the scroll region and clear the footer
+fn release(out: &mut color::Stdout, rows: usize, height: usize) -> Result<(), String> {
+    let height = clamp_height(height, rows);
+    let mut seq = String::from("\x1b7\x1b[r");
+    for row in rows - height + 1..=rows {
+        seq.push_str(&format!("\x1b[{};1H\x1b[2K", row));
+    }
+    seq.push_str("\x1b8");
+    write!(out, "{}", seq)
+        .and_then(|_| out.flush())
+        .map_err(|e| e.to_string())
+}
+
+/// Status row followed by the most recent errors, each fitted to `width`
+fn footer_lines(status: &Status, frame: &str, height: usize, width: usize) -> Vec<String> {
+    let elapsed = format_elapsed(status.start.elapsed());
+    // Command last, so a long one is what gets truncated
+    let head = format!(
+        "{} {} {} {} lines {} {}",
+        frame.cyan(),
+        elapsed,
+        "·".dimmed(),
+        status.lines,
+        "·".dimmed(),
+        status.command.bold()
+    );
+    let mut lines = vec![text::truncate(&head, width, "…")];
+    for idx in 0..height.saturating_sub(1) {
+        let line = match status.errors.get(idx) {
+            Some(err) => format!("{} {}", "✗".red(), err.red()),
+            None if idx == 0 => "no errors".dimmed().to_string(),
+            None => String::new(),
+        };
+        lines.push(text::truncate(&line, width, "…"));
+    }
+    lines
+}
+
+/// Whether a line of output should show up in the footer's error area
+fn is_error_line(line: &str, from_stderr: bool) -> bool {
+    let lower = text::strip_ansi(line).to_lowercase();
+    if lower.trim().is_empty() {
+        return false;
+    }
+    from_stderr || lower.contains("error") || lower.contains("fail")
+}
+
+fn format_elapsed(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        let secs = d.as_secs();
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+fn summary_line(command: &str, code: i32, elapsed: Duration) -> String {
+    let elapsed = format_elapsed(elapsed);
+    if code == 0 {
+        format!("{} {} finished in {}", "✓".green(), command, elapsed)
+    } else {
+        format!(
+            "{} {} exited with {} after {}",
+            "✗".red(),
+            command,
+            code,
+            elapsed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(command: &str) -> Status {
+        Status {
+            command: command.to_string(),
+            start: Instant::now(),
+            lines: 0,
+            errors: VecDeque::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_error_line() {
+        assert!(is_error_line("warning: unused", true));
+        assert!(is_error_line("test foo ... FAILED", false));
+        assert!(is_error_line(
+            "\x1b[31merror[E0308]\x1b[0m: mismatched",
+            false
+        ));
+        assert!(!is_error_line("Compiling termgfx", false));
+        assert!(!is_error_line("   ", true));
+    }
+
+    #[test]
+    fn test_record_keeps_newest_errors() {
+        let mut s = status("make");
+        for line in ["error: one", "ok", "error: two", "error: three"] {
+            s.record(line, false, 2);
+        }
+        assert_eq!(s.lines, 4);
+        assert_eq!(s.errors, ["error: two", "error: three"]);
+    }
+
+    #[test]
+    fn test_footer_lines_fit_width() {
+        let mut s = status("cargo test --workspace --all-features");
+        s.record("error: something went badly wrong here", true, 2);
+        let lines = footer_lines(&s, "⠋", 3, 24);
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            assert!(text::display_width(line) <= 24);
+        }
+        assert!(text::strip_ansi(&lines[1]).starts_with("✗ error"));
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_footer_without_errors() {
+        let lines = footer_lines(&status("ls"), "⠋", 2, 80);
+        assert!(text::strip_ansi(&lines[0]).starts_with("⠋ 0.0s · 0 lines · ls"));
+        assert_eq!(text::strip_ansi(&lines[1]), "no errors");
+    }
+
+    #[test]
+    fn test_scroll_region_and_height() {
+        assert_eq!(scroll_region(24, 2), "\x1b[1;22r");
+        assert_eq!(clamp_height(0, 24), 1);
+        assert_eq!(clamp_height(30, 24), 12);
+        assert_eq!(clamp_height(3, 1), 1);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m 05s");
+    }
+}
@@ -1,20 +1,57 @@
+use crate::animation::easing::Easing;
+use crate::animation::engine::Frames;
+use crate::animation::policy;
+use crate::output::color;
+use crate::output::inline::{self, Span};
+use crate::output::layout;
+use crate::output::progress;
+use crate::output::{sources, watch};
 use crossterm::{
     cursor,
     style::{Color, ResetColor, SetForegroundColor},
     terminal, ExecutableCommand,
 };
-use std::io::{self, Write};
+use std::io::Write;
 
-pub fn render(
-    value: f64,
-    min: f64,
-    max: f64,
-    label: Option<&str>,
-    style: &str,
-    color: Option<&str>,
-    animate: bool,
-) {
-    let mut stdout = io::stdout();
+/// How a single gauge is drawn
+#[derive(Debug, Clone, Copy)]
+pub struct GaugeOptions<'a> {
+    pub min: f64,
+    pub max: f64,
+    pub label: Option<&'a str>,
+    pub style: &'a str,
+    pub color: Option<&'a str>,
+    /// Sweep up from `min` before settling on the value
+    pub animate: bool,
+    pub easing: Easing,
+}
+
+impl Default for GaugeOptions<'_> {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 100.0,
+            label: None,
+            style: "semicircle",
+            color: None,
+            animate: false,
+            easing: Easing::Linear,
+        }
+    }
+}
+
+pub fn render(value: f64, options: &GaugeOptions) {
+    let GaugeOptions {
+        min,
+        max,
+        label,
+        style,
+        color,
+        animate,
+        easing,
+    } = *options;
+    let animate = policy::gate(animate);
+    let mut stdout = color::stdout();
 
     // Check if TTY for animations
     let is_tty = terminal::is_raw_mode_enabled().unwrap_or(false);
@@ -22,15 +59,11 @@ pub fn render(
 
     if should_animate {
         // Animate from 0 to value
-        let steps = 20;
-        let step_delay = std::time::Duration::from_millis(50);
-
-        for i in 0..=steps {
-            let current_value = (value * i as f64) / steps as f64;
+        for progress in Frames::new(20, std::time::Duration::from_millis(1000), easing) {
+            let current_value = value * progress;
             stdout.execute(cursor::MoveToColumn(0)).ok();
             render_gauge(&mut stdout, current_value, min, max, label, style, color);
             stdout.flush().ok();
-            std::thread::sleep(step_delay);
         }
         println!(); // Final newline
     } else {
@@ -39,8 +72,211 @@ pub fn render(
     }
 }
 
+/// A gauge of a system reading: printed once, inline with `shell`, or
+/// redrawn every `interval` until Ctrl+C. The source names the gauge unless
+/// there's a `label`.
+pub fn render_source(
+    source: &str,
+    interval: Option<&str>,
+    options: &GaugeOptions,
+    thresholds: &[Threshold],
+    shell: Option<&str>,
+) {
+    let result = sources::Source::parse(source).and_then(|source| {
+        let label = options
+            .label
+            .map(str::to_string)
+            .unwrap_or_else(|| source.label());
+        let options = GaugeOptions {
+            label: Some(&label),
+            animate: false,
+            ..*options
+        };
+        let mut sampler = sources::Sampler::new(source)?;
+        let interval = interval.map(watch::parse_refresh).transpose()?;
+        match (interval, shell) {
+            (Some(interval), _) => sources::watch(&mut sampler, interval, |value| {
+                let color = threshold_color(value, thresholds).or(options.color);
+                render(value, &GaugeOptions { color, ..options })
+            }),
+            (None, Some(shell)) => {
+                let value = sampler.sample()?;
+                let color = threshold_color(value, thresholds).or(options.color);
+                render_inline(value, options.min, options.max, options.label, color, shell);
+                Ok(())
+            }
+            (None, None) => {
+                let value = sampler.sample()?;
+                let color = threshold_color(value, thresholds).or(options.color);
+                render(value, &GaugeOptions { color, ..options });
+                Ok(())
+            }
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Several labelled gauges in a row, e.g. from `CPU:75,MEM:60,DISK:90`
+pub fn render_multi(
+    gauges: &[(String, f64)],
+    min: f64,
+    max: f64,
+    style: &str,
+    color: Option<&str>,
+    thresholds: &[Threshold],
+) {
+    let blocks: Vec<String> = gauges
+        .iter()
+        .map(|(label, value)| {
+            let color = threshold_color(*value, thresholds).or(color);
+            let mut block = Vec::new();
+            render_gauge(&mut block, *value, min, max, Some(label), style, color);
+            closed_lines(&String::from_utf8_lossy(&block))
+        })
+        .collect();
+
+    let mut stdout = color::stdout();
+    for line in layout::side_by_side(&blocks, 2, "left") {
+        writeln!(stdout, "{}", line.trim_end()).ok();
+    }
+}
+
+/// End every line with a reset and reopen the color it left off in, so
+/// colors don't bleed into the gauge next to it
+fn closed_lines(block: &str) -> String {
+    let mut active = String::new();
+    let mut out = String::new();
+    for line in block.lines() {
+        out.push_str(&format!("{}{}\x1b[0m\n", active, line));
+        if let Some(start) = line.rfind("\x1b[") {
+            let seq = &line[start..];
+            if let Some(end) = seq.find('m') {
+                active = match &seq[..=end] {
+                    "\x1b[0m" => String::new(),
+                    seq => seq.to_string(),
+                };
+            }
+        }
+    }
+    out
+}
+
+/// Parse `LABEL:VALUE,...` for `--multi`
+pub fn parse_multi(spec: &str) -> Result<Vec<(String, f64)>, String> {
+    spec.split(',')
+        .map(|item| {
+            item.rsplit_once(':')
+                .and_then(|(label, value)| {
+                    let value = value.trim().parse().ok()?;
+                    (!label.trim().is_empty()).then(|| (label.trim().to_string(), value))
+                })
+                .ok_or_else(|| format!("Invalid gauge '{}' (use LABEL:VALUE)", item))
+        })
+        .collect()
+}
+
+/// The gauge turns `color` once its value reaches `value`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    pub value: f64,
+    pub color: String,
+}
+
+/// Parse `VALUE:COLOR,...` (e.g. `60:yellow,85:red`), lowest value first
+pub fn parse_thresholds(spec: &str) -> Result<Vec<Threshold>, String> {
+    let mut thresholds = spec
+        .split(',')
+        .map(|item| {
+            item.split_once(':')
+                .and_then(|(value, color)| {
+                    let value = value.trim().parse().ok()?;
+                    let color = color.trim().to_lowercase();
+                    COLOR_NAMES
+                        .contains(&color.as_str())
+                        .then_some(Threshold { value, color })
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid threshold '{}' (use VALUE:COLOR with {})",
+                        item,
+                        COLOR_NAMES.join(", ")
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    thresholds.sort_by(|a, b| a.value.total_cmp(&b.value));
+    Ok(thresholds)
+}
+
+/// Color of the highest threshold `value` has reached, if any
+pub fn threshold_color(value: f64, thresholds: &[Threshold]) -> Option<&str> {
+    thresholds
+        .iter()
+        .rev()
+        .find(|t| value >= t.value)
+        .map(|t| t.color.as_str())
+}
+
+/// Print the gauge as a single prompt-safe line (no newline, no cursor moves)
+pub fn render_inline(
+    value: f64,
+    min: f64,
+    max: f64,
+    label: Option<&str>,
+    color: Option<&str>,
+    shell: &str,
+) {
+    let shell = inline::shell_or_exit(shell);
+    inline::print(&inline_spans(value, min, max, label, color), shell);
+}
+
+/// Label, an 8-segment meter and the percentage, e.g. `CPU ▰▰▰▰▰▰▱▱ 75%`
+pub fn inline_spans(
+    value: f64,
+    min: f64,
+    max: f64,
+    label: Option<&str>,
+    color: Option<&str>,
+) -> Vec<Span> {
+    const SEGMENTS: usize = 8;
+
+    let percentage = if max > min {
+        ((value - min) / (max - min) * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+    let color = color
+        .map(progress::parse_color)
+        .unwrap_or_else(|| inline::level_color(percentage));
+
+    let mut spans = Vec::new();
+    if let Some(l) = label {
+        spans.push(Span::colored(format!("{} ", l), (86, 214, 214)));
+    }
+    spans.extend(meter_spans(percentage, color, SEGMENTS));
+    spans.push(Span::plain(" "));
+    spans.push(Span::colored(format!("{:.0}%", percentage), color).bold());
+    spans.retain(|s| !s.text.is_empty());
+    spans
+}
+
+/// A `▰▰▰▱▱`-style meter `segments` wide, filled to `percentage`
+pub fn meter_spans(percentage: f64, color: (u8, u8, u8), segments: usize) -> Vec<Span> {
+    let filled =
+        ((segments as f64 * percentage.clamp(0.0, 100.0) / 100.0).round() as usize).min(segments);
+    let mut spans = vec![
+        Span::colored("▰".repeat(filled), color),
+        Span::colored("▱".repeat(segments - filled), (72, 79, 88)),
+    ];
+    spans.retain(|s| !s.text.is_empty());
+    spans
+}
+
 fn render_gauge(
-    stdout: &mut io::Stdout,
+    stdout: &mut impl Write,
     value: f64,
     min: f64,
     max: f64,
@@ -77,7 +313,7 @@ fn render_gauge(
 }
 
 fn render_semicircle_gauge(
-    stdout: &mut io::Stdout,
+    stdout: &mut impl Write,
     percentage: f64,
     value: f64,
     color: Color,
@@ -135,7 +371,7 @@ fn render_semicircle_gauge(
 }
 
 fn render_full_gauge(
-    stdout: &mut io::Stdout,
+    stdout: &mut impl Write,
     percentage: f64,
     value: f64,
     color: Color,
@@ -187,7 +423,7 @@ fn render_full_gauge(
 }
 
 fn render_minimal_gauge(
-    stdout: &mut io::Stdout,
+    stdout: &mut impl Write,
     percentage: f64,
     value: f64,
     color: Color,
@@ -227,6 +463,10 @@ fn render_minimal_gauge(
     stdout.execute(ResetColor).ok();
 }
 
+const COLOR_NAMES: [&str; 9] = [
+    "red", "green", "blue", "yellow", "cyan", "magenta", "white", "grey", "gray",
+];
+
 fn parse_color(color_name: &str) -> Color {
     match color_name.to_lowercase().as_str() {
         "red" => Color::Red,
@@ -240,3 +480,56 @@ fn parse_color(color_name: &str) -> Color {
         _ => Color::Green,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_thresholds_sorted() {
+        let thresholds = parse_thresholds("85:red, 60:Yellow").unwrap();
+        assert_eq!(
+            thresholds,
+            vec![
+                Threshold {
+                    value: 60.0,
+                    color: "yellow".to_string()
+                },
+                Threshold {
+                    value: 85.0,
+                    color: "red".to_string()
+                },
+            ]
+        );
+        assert!(parse_thresholds("60:purple").is_err());
+        assert!(parse_thresholds("high:red").is_err());
+    }
+
+    #[test]
+    fn test_threshold_color() {
+        let thresholds = parse_thresholds("60:yellow,85:red").unwrap();
+        assert_eq!(threshold_color(40.0, &thresholds), None);
+        assert_eq!(threshold_color(60.0, &thresholds), Some("yellow"));
+        assert_eq!(threshold_color(90.0, &thresholds), Some("red"));
+    }
+
+    #[test]
+    fn test_closed_lines_reopen_color() {
+        assert_eq!(
+            closed_lines("\x1b[90m╭╮\n│\x1b[0m"),
+            "\x1b[90m╭╮\x1b[0m\n\x1b[90m│\x1b[0m\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_multi() {
+        assert_eq!(
+            parse_multi("CPU:75,MEM:60.5").unwrap(),
+            vec![("CPU".to_string(), 75.0), ("MEM".to_string(), 60.5)]
+        );
+        assert_eq!(
+            parse_multi("CPU"),
+            Err("Invalid gauge 'CPU' (use LABEL:VALUE)".to_string())
+        );
+    }
+}
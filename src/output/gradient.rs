@@ -0,0 +1,161 @@
+//! Per-character gradient coloring
+//!
+//! Banners, the `text` command and anything else that sweeps color across a
+//! string share this engine. Two-color presets switch from the start color to
+//! the end color halfway through; the rainbow steps through six hues.
+//! Existing escape sequences in the input pass through untouched and take no
+//! position in the sweep.
+
+use crate::text;
+use owo_colors::{AnsiColors, OwoColorize};
+
+/// Names accepted by [`Gradient::from_name`]
+pub const GRADIENT_NAMES: [&str; 6] = [
+    "cyan-purple",
+    "red-orange",
+    "green-cyan",
+    "pink-yellow",
+    "cyan-magenta",
+    "rainbow",
+];
+
+const RAINBOW: [AnsiColors; 6] = [
+    AnsiColors::BrightRed,
+    AnsiColors::BrightYellow,
+    AnsiColors::BrightGreen,
+    AnsiColors::BrightCyan,
+    AnsiColors::BrightBlue,
+    AnsiColors::BrightMagenta,
+];
+
+/// A color sweep applied across the characters of a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Gradient {
+    BluePurple,
+    RedOrange,
+    GreenCyan,
+    PinkYellow,
+    #[default]
+    CyanMagenta,
+    Rainbow,
+}
+
+impl Gradient {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "blue-purple" | "cyan-purple" => Some(Gradient::BluePurple),
+            "red-orange" => Some(Gradient::RedOrange),
+            "green-cyan" => Some(Gradient::GreenCyan),
+            "pink-yellow" | "magenta-yellow" => Some(Gradient::PinkYellow),
+            "cyan-magenta" => Some(Gradient::CyanMagenta),
+            "rainbow" => Some(Gradient::Rainbow),
+            _ => None,
+        }
+    }
+
+    /// Color at `ratio` along the sweep, 0.0 being the first character
+    pub fn color_at(self, ratio: f32) -> AnsiColors {
+        let (start, end) = match self {
+            Gradient::BluePurple => (AnsiColors::BrightBlue, AnsiColors::BrightMagenta),
+            Gradient::RedOrange => (AnsiColors::BrightRed, AnsiColors::BrightYellow),
+            Gradient::GreenCyan => (AnsiColors::BrightGreen, AnsiColors::BrightCyan),
+            Gradient::PinkYellow => (AnsiColors::BrightMagenta, AnsiColors::BrightYellow),
+            Gradient::CyanMagenta => (AnsiColors::BrightCyan, AnsiColors::BrightMagenta),
+            Gradient::Rainbow => {
+                let idx = (ratio.clamp(0.0, 1.0) * RAINBOW.len() as f32) as usize;
+                return RAINBOW[idx.min(RAINBOW.len() - 1)];
+            }
+        };
+        if ratio < 0.5 {
+            start
+        } else {
+            end
+        }
+    }
+}
+
+/// Color `text` across the whole gradient
+pub fn paint(text: &str, gradient: Gradient) -> String {
+    paint_range(text, gradient, 0.0, 1.0)
+}
+
+/// Color `text` across the part of the gradient between `from` and `to`
+///
+/// A single character takes the midpoint of the range.
+pub fn paint_range(text: &str, gradient: Gradient, from: f32, to: f32) -> String {
+    let visible = text::strip_ansi(text).chars().count();
+    let mut result = String::with_capacity(text.len() * 8);
+    let mut idx = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            result.push(ch);
+            result.push_str(&text::take_escape(&mut chars));
+            continue;
+        }
+        let ratio = if visible > 1 {
+            idx as f32 / (visible - 1) as f32
+        } else {
+            0.5
+        };
+        let color = gradient.color_at(from + (to - from) * ratio);
+        result.push_str(&ch.color(color).to_string());
+        idx += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        for name in GRADIENT_NAMES {
+            assert!(Gradient::from_name(name).is_some(), "{}", name);
+        }
+        assert_eq!(
+            Gradient::from_name("Blue-Purple"),
+            Some(Gradient::BluePurple)
+        );
+        assert_eq!(Gradient::from_name("plaid"), None);
+    }
+
+    #[test]
+    fn test_two_color_gradient_switches_halfway() {
+        assert_eq!(Gradient::RedOrange.color_at(0.0), AnsiColors::BrightRed);
+        assert_eq!(Gradient::RedOrange.color_at(0.49), AnsiColors::BrightRed);
+        assert_eq!(Gradient::RedOrange.color_at(0.5), AnsiColors::BrightYellow);
+    }
+
+    #[test]
+    fn test_rainbow_covers_every_hue() {
+        let hues: Vec<_> = (0..=5)
+            .map(|i| Gradient::Rainbow.color_at(i as f32 / 5.0))
+            .collect();
+        assert_eq!(hues, RAINBOW.to_vec());
+    }
+
+    #[test]
+    fn test_paint_colors_each_character() {
+        let painted = paint("ab", Gradient::GreenCyan);
+        assert_eq!(painted, "\x1b[92ma\x1b[39m\x1b[96mb\x1b[39m");
+        assert_eq!(text::strip_ansi(&painted), "ab");
+    }
+
+    #[test]
+    fn test_paint_keeps_existing_escapes() {
+        let painted = paint("\x1b[1mab\x1b[0m", Gradient::GreenCyan);
+        assert!(painted.starts_with("\x1b[1m\x1b[92ma"));
+        assert!(painted.ends_with("\x1b[96mb\x1b[39m\x1b[0m"));
+    }
+
+    #[test]
+    fn test_single_character_takes_midpoint() {
+        assert_eq!(
+            paint_range("x", Gradient::RedOrange, 0.0, 0.4),
+            "\x1b[91mx\x1b[39m"
+        );
+        assert_eq!(paint("x", Gradient::RedOrange), "\x1b[93mx\x1b[39m");
+    }
+}
@@ -1,8 +1,67 @@
-use std::fs;
+use crate::animation::policy;
+use crate::output::input;
+use crate::text;
 use std::io::{self, IsTerminal};
 use std::thread;
 use std::time::Duration;
 
+/// How values map onto the color scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Linear,
+    /// Equal steps for equal ratios; every value must be positive
+    Log,
+}
+
+impl Scale {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Scale::Linear),
+            "log" => Some(Scale::Log),
+            _ => None,
+        }
+    }
+
+    /// Where `value` falls between `min` and `max`, from 0.0 to 1.0
+    fn normalize(self, value: f64, min: f64, max: f64) -> f64 {
+        let (value, min, max) = match self {
+            Scale::Linear => (value, min, max),
+            Scale::Log => (value.ln(), min.ln(), max.ln()),
+        };
+        if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        }
+    }
+
+    /// The value at `normalized` between `min` and `max`
+    fn value_at(self, normalized: f64, min: f64, max: f64) -> f64 {
+        match self {
+            Scale::Linear => min + (max - min) * normalized,
+            Scale::Log => (min.ln() + (max.ln() - min.ln()) * normalized).exp(),
+        }
+    }
+}
+
+/// How cells are drawn
+pub struct HeatmapOptions<'a> {
+    /// Color scheme: blue-red, green-red, viridis, magma
+    pub colors: &'a str,
+    pub scale: Scale,
+    /// Print each value inside its cell when it fits
+    pub annotate: bool,
+    /// Color for missing cells, or `none` to leave them blank
+    pub null_color: &'a str,
+}
+
+/// Widest a cell grows to fit an annotation or an x label; a space
+/// separates neighbours
+const MAX_CELL_WIDTH: usize = 4;
+
+/// Names accepted by `--null-color`
+const NULL_COLORS: &str = "grey, red, green, yellow, blue, magenta, cyan, white, none";
+
 /// Render a 2D heatmap visualization
 pub fn render(
     data: Option<&str>,
@@ -10,9 +69,10 @@ pub fn render(
     x_labels: Option<&str>,
     y_labels: Option<&str>,
     title: Option<&str>,
-    colors: &str,
+    options: &HeatmapOptions,
     animate: bool,
 ) {
+    let animate = policy::gate(animate);
     // Parse data from either inline or file
     let grid = match (data, file) {
         (Some(d), _) => parse_data(d),
@@ -31,39 +91,47 @@ pub fn render(
     let x_labels_vec = x_labels.map(|s| s.split(',').map(String::from).collect::<Vec<_>>());
     let y_labels_vec = y_labels.map(|s| s.split(',').map(String::from).collect::<Vec<_>>());
 
-    if animate && io::stdout().is_terminal() {
-        render_animated(&grid, &x_labels_vec, &y_labels_vec, title, colors);
-    } else {
-        render_static(&grid, &x_labels_vec, &y_labels_vec, title, colors);
+    let lines = match heatmap_lines(&grid, &x_labels_vec, &y_labels_vec, title, options) {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let animate = animate && io::stdout().is_terminal();
+    for line in lines {
+        println!("{}", line);
+        if animate {
+            let _ = io::Write::flush(&mut io::stdout());
+            thread::sleep(Duration::from_millis(100));
+        }
     }
 }
 
-fn parse_data(data: &str) -> Vec<Vec<f64>> {
+/// A cell's value; blank, `-`, `NA`, `null` and other non-numbers are missing
+fn parse_cell(cell: &str) -> Option<f64> {
+    cell.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Rows of cells; rows without a single number (such as a CSV header) are
+/// skipped
+fn parse_row(row: &str) -> Option<Vec<Option<f64>>> {
+    let cells: Vec<Option<f64>> = row.split(',').map(parse_cell).collect();
+    cells.iter().any(Option::is_some).then_some(cells)
+}
+
+fn parse_data(data: &str) -> Vec<Vec<Option<f64>>> {
     if data.trim().is_empty() {
         return Vec::new();
     }
 
-    data.split(';')
-        .map(|row| {
-            row.split(',')
-                .filter_map(|cell| cell.trim().parse::<f64>().ok())
-                .collect()
-        })
-        .filter(|row: &Vec<f64>| !row.is_empty())
-        .collect()
+    data.split(';').filter_map(parse_row).collect()
 }
 
-fn parse_file(path: &str) -> Vec<Vec<f64>> {
-    match fs::read_to_string(path) {
-        Ok(content) => content
-            .lines()
-            .map(|line| {
-                line.split(',')
-                    .filter_map(|cell| cell.trim().parse::<f64>().ok())
-                    .collect()
-            })
-            .filter(|row: &Vec<f64>| !row.is_empty())
-            .collect(),
+fn parse_file(path: &str) -> Vec<Vec<Option<f64>>> {
+    match input::read_text(path) {
+        Ok(content) => content.lines().filter_map(parse_row).collect(),
         Err(e) => {
             eprintln!("Error reading file: {}", e);
             std::process::exit(1);
@@ -71,133 +139,162 @@ fn parse_file(path: &str) -> Vec<Vec<f64>> {
     }
 }
 
-fn render_static(
-    grid: &[Vec<f64>],
+fn null_color_code(name: &str) -> Result<Option<u8>, String> {
+    let code = match name.to_lowercase().as_str() {
+        "none" => return Ok(None),
+        "grey" | "gray" => 90,
+        "red" => 31,
+        "green" => 32,
+        "yellow" => 33,
+        "blue" => 34,
+        "magenta" => 35,
+        "cyan" => 36,
+        "white" => 37,
+        _ => {
+            return Err(format!(
+                "Invalid null color '{}' (use {})",
+                name, NULL_COLORS
+            ))
+        }
+    };
+    Ok(Some(code))
+}
+
+/// The whole heatmap: title, x labels, one line per row, then the legend
+fn heatmap_lines(
+    grid: &[Vec<Option<f64>>],
     x_labels: &Option<Vec<String>>,
     y_labels: &Option<Vec<String>>,
     title: Option<&str>,
-    colors: &str,
-) {
-    // Print title if provided
-    if let Some(t) = title {
-        println!("\n  {}\n", t);
+    options: &HeatmapOptions,
+) -> Result<Vec<String>, String> {
+    let null_code = null_color_code(options.null_color)?;
+    let (min_val, max_val) = find_min_max(grid);
+    if options.scale == Scale::Log && min_val <= 0.0 {
+        return Err(format!(
+            "Log scale needs positive values (found {}); use --scale linear",
+            format_value(min_val)
+        ));
     }
 
-    // Find min and max values for normalization
-    let (min_val, max_val) = find_min_max(grid);
-    let range = max_val - min_val;
+    let mut lines = Vec::new();
+    if let Some(t) = title {
+        lines.extend([String::new(), format!("  {}", t), String::new()]);
+    }
 
     // Determine max width for all rows
     let max_cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
 
-    // Print x-axis labels
+    let cell = cell_width(grid, x_labels, options.annotate);
+
     if let Some(labels) = x_labels {
-        print!("      "); // Offset for y-labels
-        for (i, label) in labels.iter().take(max_cols).enumerate() {
-            if i > 0 {
-                print!(" ");
-            }
-            print!("{:^4}", label);
-        }
-        println!();
+        let labels: Vec<String> = labels
+            .iter()
+            .take(max_cols)
+            .map(|label| center(&text::truncate(label, cell, "…"), cell))
+            .collect();
+        lines.push(format!("      {}", labels.join(" ")));
     }
 
-    // Print grid with y-labels
     for (row_idx, row) in grid.iter().enumerate() {
-        // Print y-label if available
-        if let Some(labels) = y_labels {
-            if row_idx < labels.len() {
-                print!("{:>5} ", labels[row_idx]);
-            } else {
-                print!("      ");
-            }
-        } else {
-            print!("      ");
-        }
-
-        // Print cells
-        for (col_idx, &value) in row.iter().enumerate() {
-            if col_idx > 0 {
-                print!(" ");
-            }
-            let normalized = if range > 0.0 {
-                (value - min_val) / range
-            } else {
-                0.5
-            };
-            print!("{}", colorize_cell(normalized, colors));
-        }
-        println!();
+        let label = y_labels
+            .as_ref()
+            .and_then(|labels| labels.get(row_idx))
+            .map(|label| format!("{:>5} ", label))
+            .unwrap_or_else(|| " ".repeat(6));
+        let cells: Vec<String> = row
+            .iter()
+            .map(|value| match value {
+                Some(value) => {
+                    let normalized = options.scale.normalize(*value, min_val, max_val);
+                    let text = format_value(*value);
+                    if options.annotate && text.len() <= cell {
+                        annotated_cell(&text, cell, normalized, options.colors)
+                    } else {
+                        colorize_cell(normalized, cell, options.colors)
+                    }
+                }
+                None => match null_code {
+                    Some(code) => format!("\x1b[{}m{}\x1b[0m", code, "·".repeat(cell)),
+                    None => " ".repeat(cell),
+                },
+            })
+            .collect();
+        lines.push(format!("{}{}", label, cells.join(" ")));
     }
-    println!();
-}
 
-fn render_animated(
-    grid: &[Vec<f64>],
-    x_labels: &Option<Vec<String>>,
-    y_labels: &Option<Vec<String>>,
-    title: Option<&str>,
-    colors: &str,
-) {
-    let (min_val, max_val) = find_min_max(grid);
-    let range = max_val - min_val;
-    let max_cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    lines.push(String::new());
+    let width = (max_cols * (cell + 1)).saturating_sub(1).clamp(12, 40);
+    lines.extend(legend_lines(min_val, max_val, width, options));
+    Ok(lines)
+}
 
-    // Animate row by row
-    if let Some(t) = title {
-        println!("\n  {}\n", t);
-    }
+/// A gradient bar across the color scheme with the min, middle and max
+/// values under its ends and center
+fn legend_lines(min: f64, max: f64, width: usize, options: &HeatmapOptions) -> [String; 2] {
+    let bar: String = (0..width)
+        .map(|i| {
+            let normalized = i as f64 / (width - 1) as f64;
+            paint(
+                &block_for(normalized).to_string(),
+                normalized,
+                options.colors,
+            )
+        })
+        .collect();
 
-    // Print x-axis labels
-    if let Some(labels) = x_labels {
-        print!("      ");
-        for (i, label) in labels.iter().take(max_cols).enumerate() {
-            if i > 0 {
-                print!(" ");
-            }
-            print!("{:^4}", label);
-        }
-        println!();
+    let min_text = format_value(min);
+    let max_text = format_value(max);
+    let mid_text = format_value(options.scale.value_at(0.5, min, max));
+    let mut labels = vec![' '; width];
+    labels.splice(0..min_text.len(), min_text.chars());
+    let max_start = width.saturating_sub(max_text.len());
+    let mid_start = (width / 2).saturating_sub(mid_text.len() / 2);
+    // The middle label only where it clears both ends
+    if mid_start > min_text.len() && mid_start + mid_text.len() < max_start && max > min {
+        labels.splice(mid_start..mid_start + mid_text.len(), mid_text.chars());
     }
+    labels.truncate(max_start.max(min_text.len() + 1));
+    labels.extend(max_text.chars());
 
-    for (row_idx, row) in grid.iter().enumerate() {
-        // Print y-label
-        if let Some(labels) = y_labels {
-            if row_idx < labels.len() {
-                print!("{:>5} ", labels[row_idx]);
-            } else {
-                print!("      ");
-            }
-        } else {
-            print!("      ");
-        }
+    [
+        format!("      {}", bar),
+        format!("      {}", labels.iter().collect::<String>()),
+    ]
+}
 
-        // Animate cells in this row
-        for (col_idx, &value) in row.iter().enumerate() {
-            if col_idx > 0 {
-                print!(" ");
-            }
-            let normalized = if range > 0.0 {
-                (value - min_val) / range
-            } else {
-                0.5
-            };
-            print!("{}", colorize_cell(normalized, colors));
-            let _ = io::Write::flush(&mut io::stdout());
-            thread::sleep(Duration::from_millis(50));
-        }
-        println!();
-        thread::sleep(Duration::from_millis(100));
+/// A short form of `value` for cells and the legend: up to two decimals,
+/// with k, M and G for large numbers
+fn format_value(value: f64) -> String {
+    let abs = value.abs();
+    let (scaled, suffix) = if abs >= 1e9 {
+        (value / 1e9, "G")
+    } else if abs >= 1e6 {
+        (value / 1e6, "M")
+    } else if abs >= 1e4 {
+        (value / 1e3, "k")
+    } else {
+        (value, "")
+    };
+    let mut text = if scaled.abs() >= 100.0 || scaled.fract() == 0.0 {
+        format!("{:.0}", scaled)
+    } else if scaled.abs() >= 10.0 {
+        format!("{:.1}", scaled)
+    } else {
+        format!("{:.2}", scaled)
+    };
+    if text.contains('.') {
+        text = text.trim_end_matches('0').trim_end_matches('.').to_string();
     }
-    println!();
+    text + suffix
 }
 
-fn find_min_max(grid: &[Vec<f64>]) -> (f64, f64) {
+fn find_min_max(grid: &[Vec<Option<f64>>]) -> (f64, f64) {
     let mut min = f64::INFINITY;
     let mut max = f64::NEG_INFINITY;
 
     for row in grid {
-        for &val in row {
+        for &val in row.iter().flatten() {
             if val < min {
                 min = val;
             }
@@ -210,55 +307,181 @@ fn find_min_max(grid: &[Vec<f64>]) -> (f64, f64) {
     (min, max)
 }
 
-fn colorize_cell(normalized: f64, scheme: &str) -> String {
+/// Shade for a normalized value, so cells read without color too
+fn block_for(normalized: f64) -> char {
     // Use block characters with different densities: ░▒▓█
     let blocks = ['░', '▒', '▓', '█'];
     let idx = ((normalized * (blocks.len() - 1) as f64).round() as usize).min(blocks.len() - 1);
-    let block = blocks[idx];
+    blocks[idx]
+}
 
-    // Apply color scheme
-    let colored = match scheme {
+/// Cells are one column wide unless annotations or x labels need more,
+/// up to [`MAX_CELL_WIDTH`]
+fn cell_width(grid: &[Vec<Option<f64>>], x_labels: &Option<Vec<String>>, annotate: bool) -> usize {
+    let annotations = grid
+        .iter()
+        .flatten()
+        .flatten()
+        .filter(|_| annotate)
+        .map(|value| format_value(*value).len())
+        .filter(|width| *width <= MAX_CELL_WIDTH);
+    let labels = x_labels
+        .iter()
+        .flatten()
+        .map(|label| text::display_width(label).min(MAX_CELL_WIDTH));
+    annotations.chain(labels).max().unwrap_or(1).max(1)
+}
+
+/// `text` padded on both sides to `width` columns
+fn center(text: &str, width: usize) -> String {
+    let gap = width.saturating_sub(text::display_width(text));
+    format!(
+        "{}{}{}",
+        " ".repeat(gap / 2),
+        text,
+        " ".repeat(gap - gap / 2)
+    )
+}
+
+/// `text` centered in a cell on the scheme's color as background
+fn annotated_cell(text: &str, width: usize, normalized: f64, scheme: &str) -> String {
+    let text = center(text, width);
+    match scheme_color(normalized, scheme) {
+        Some(code) => {
+            // Dark text on the light backgrounds, white on the dark ones
+            let fg = if matches!(code, 32 | 33) { 30 } else { 97 };
+            format!("\x1b[{};{}m{}\x1b[0m", fg, code + 10, text)
+        }
+        None => text,
+    }
+}
+
+fn colorize_cell(normalized: f64, width: usize, scheme: &str) -> String {
+    // A full cell of the shade, so rows line up under the x labels
+    let block = block_for(normalized).to_string().repeat(width);
+    paint(&block, normalized, scheme)
+}
+
+/// Color `text` according to where `normalized` (0.0-1.0) falls in a color scheme
+pub fn paint(text: &str, normalized: f64, scheme: &str) -> String {
+    match scheme_color(normalized, scheme) {
+        Some(code) => format!("\x1b[{}m{}\x1b[0m", code, text),
+        None => text.to_string(), // No color
+    }
+}
+
+/// ANSI foreground color code for a normalized value in the given scheme
+fn scheme_color(normalized: f64, scheme: &str) -> Option<u8> {
+    let code = match scheme {
         "blue-red" => {
             if normalized < 0.33 {
-                format!("\x1b[34m{}\x1b[0m", block) // Blue
+                34 // Blue
             } else if normalized < 0.67 {
-                format!("\x1b[33m{}\x1b[0m", block) // Yellow
+                33 // Yellow
             } else {
-                format!("\x1b[31m{}\x1b[0m", block) // Red
+                31 // Red
             }
         }
         "green-red" => {
             if normalized < 0.5 {
-                format!("\x1b[32m{}\x1b[0m", block) // Green
+                32 // Green
             } else {
-                format!("\x1b[31m{}\x1b[0m", block) // Red
+                31 // Red
             }
         }
         "viridis" => {
             // Approximation of viridis: purple -> blue -> green -> yellow
             if normalized < 0.25 {
-                format!("\x1b[35m{}\x1b[0m", block) // Magenta
+                35 // Magenta
             } else if normalized < 0.5 {
-                format!("\x1b[34m{}\x1b[0m", block) // Blue
+                34 // Blue
             } else if normalized < 0.75 {
-                format!("\x1b[32m{}\x1b[0m", block) // Green
+                32 // Green
             } else {
-                format!("\x1b[33m{}\x1b[0m", block) // Yellow
+                33 // Yellow
             }
         }
         "magma" => {
             // Approximation of magma: black -> purple -> red -> yellow
             if normalized < 0.33 {
-                format!("\x1b[35m{}\x1b[0m", block) // Magenta
+                35 // Magenta
             } else if normalized < 0.67 {
-                format!("\x1b[31m{}\x1b[0m", block) // Red
+                31 // Red
             } else {
-                format!("\x1b[33m{}\x1b[0m", block) // Yellow
+                33 // Yellow
             }
         }
-        _ => format!("{}", block), // No color
+        _ => return None,
     };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Make it 4 characters wide for alignment
-    format!("{:^4}", colored)
+    fn options(scale: Scale, annotate: bool) -> HeatmapOptions<'static> {
+        HeatmapOptions {
+            colors: "none",
+            scale,
+            annotate,
+            null_color: "none",
+        }
+    }
+
+    #[test]
+    fn test_parse_missing_cells() {
+        assert_eq!(
+            parse_data("1,,3;NA,5,null;x,y"),
+            vec![
+                vec![Some(1.0), None, Some(3.0)],
+                vec![None, Some(5.0), None]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(3.0), "3");
+        assert_eq!(format_value(1.5), "1.5");
+        assert_eq!(format_value(0.123), "0.12");
+        assert_eq!(format_value(12345.0), "12.3k");
+        assert_eq!(format_value(2_000_000.0), "2M");
+    }
+
+    #[test]
+    fn test_log_scale() {
+        assert_eq!(Scale::Log.normalize(10.0, 1.0, 100.0), 0.5);
+        assert!((Scale::Log.value_at(0.5, 1.0, 100.0) - 10.0).abs() < 1e-9);
+        let grid = parse_data("0,10");
+        let err = heatmap_lines(&grid, &None, &None, None, &options(Scale::Log, false));
+        assert_eq!(
+            err.unwrap_err(),
+            "Log scale needs positive values (found 0); use --scale linear"
+        );
+    }
+
+    #[test]
+    fn test_annotate_and_legend() {
+        let grid = parse_data("1,,100");
+        let lines = heatmap_lines(&grid, &None, &None, None, &options(Scale::Log, true)).unwrap();
+        assert_eq!(lines[0], "       1      100");
+        assert_eq!(lines[2], "      ░░▒▒▒▒▓▓▓▓██");
+        assert_eq!(lines[3], "      1    10  100");
+    }
+
+    #[test]
+    fn test_cells_widen_only_as_needed() {
+        let grid = parse_data(&vec!["1"; 30].join(","));
+        let lines =
+            heatmap_lines(&grid, &None, &None, None, &options(Scale::Linear, false)).unwrap();
+        assert_eq!(text::display_width(&lines[0]), 6 + 30 * 2 - 1);
+
+        let labels = Some(vec!["Mon".to_string(), "月曜日です".to_string()]);
+        let grid = parse_data("1,2");
+        let lines =
+            heatmap_lines(&grid, &labels, &None, None, &options(Scale::Linear, false)).unwrap();
+        assert_eq!(lines[0], "      Mon  月… ");
+        assert_eq!(lines[1], "      ░░░░ ████");
+    }
 }
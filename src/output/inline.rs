@@ -0,0 +1,212 @@
+//! Single-line output for shell prompts and status bars
+//!
+//! Inline renderers emit no cursor movement and no trailing newline. Color
+//! escapes are wrapped in the target shell's zero-width markers so prompt
+//! length calculations stay correct.
+
+/// Prompt flavor that decides how styling is escaped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    /// Raw ANSI escapes
+    Plain,
+    /// Escapes wrapped in readline's `\001 \002` markers, which also work
+    /// inside `$(...)` in PS1 where `\[ \]` are not processed
+    Bash,
+    /// Escapes wrapped in `%{ %}`, literal `%` doubled
+    Zsh,
+    /// tmux `#[fg=...]` style directives, literal `#` doubled
+    Tmux,
+}
+
+impl Shell {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "plain" | "none" => Some(Shell::Plain),
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "tmux" => Some(Shell::Tmux),
+            _ => None,
+        }
+    }
+}
+
+/// A run of text with one color
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub color: Option<(u8, u8, u8)>,
+    pub bold: bool,
+}
+
+impl Span {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            bold: false,
+        }
+    }
+
+    pub fn colored(text: impl Into<String>, color: (u8, u8, u8)) -> Self {
+        Self {
+            text: text.into(),
+            color: Some(color),
+            bold: false,
+        }
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+}
+
+/// Shell named on the command line, exiting with an error if unknown
+pub fn shell_or_exit(name: &str) -> Shell {
+    match Shell::from_name(name) {
+        Some(shell) => shell,
+        None => {
+            eprintln!(
+                "Error: Unknown shell '{}' (expected plain, bash, zsh or tmux)",
+                name
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Append a span, merging it into the previous one when the style matches
+pub fn push(spans: &mut Vec<Span>, span: Span) {
+    if let Some(last) = spans.last_mut() {
+        if last.color == span.color && last.bold == span.bold {
+            last.text.push_str(&span.text);
+            return;
+        }
+    }
+    spans.push(span);
+}
+
+/// Encode spans for `shell`
+pub fn render(spans: &[Span], shell: Shell) -> String {
+    let mut out = String::new();
+    for span in spans {
+        let styled = span.color.is_some() || span.bold;
+        if shell == Shell::Tmux {
+            if styled {
+                let mut attrs = Vec::new();
+                if let Some((r, g, b)) = span.color {
+                    attrs.push(format!("fg=#{:02x}{:02x}{:02x}", r, g, b));
+                }
+                if span.bold {
+                    attrs.push("bold".to_string());
+                }
+                out.push_str(&format!("#[{}]", attrs.join(",")));
+            }
+            out.push_str(&span.text.replace('#', "##"));
+            if styled {
+                out.push_str("#[default]");
+            }
+            continue;
+        }
+
+        if styled {
+            let mut codes = Vec::new();
+            if span.bold {
+                codes.push("1".to_string());
+            }
+            if let Some((r, g, b)) = span.color {
+                codes.push(format!("38;2;{};{};{}", r, g, b));
+            }
+            out.push_str(&zero_width(&format!("\x1b[{}m", codes.join(";")), shell));
+        }
+        match shell {
+            Shell::Zsh => out.push_str(&span.text.replace('%', "%%")),
+            _ => out.push_str(&span.text),
+        }
+        if styled {
+            out.push_str(&zero_width("\x1b[0m", shell));
+        }
+    }
+    out
+}
+
+fn zero_width(escape: &str, shell: Shell) -> String {
+    match shell {
+        Shell::Bash => format!("\x01{}\x02", escape),
+        Shell::Zsh => format!("%{{{}%}}", escape),
+        _ => escape.to_string(),
+    }
+}
+
+/// Print spans without a trailing newline
+pub fn print(spans: &[Span], shell: Shell) {
+    use std::io::Write;
+    let text = render(spans, shell);
+    match shell {
+        Shell::Plain => print!("{}", text),
+        // Prompts capture the output, so stdout is never a terminal here
+        _ => std::print!("{}", crate::output::color::apply_prompt(&text)),
+    }
+    std::io::stdout().flush().ok();
+}
+
+/// Red/yellow/green by fill level, matching the block-style defaults
+pub fn level_color(percent: f64) -> (u8, u8, u8) {
+    if percent < 33.0 {
+        (255, 85, 85)
+    } else if percent < 66.0 {
+        (224, 175, 104)
+    } else {
+        (63, 185, 80)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Span> {
+        vec![Span::colored("██", (255, 0, 0)), Span::plain(" 50%")]
+    }
+
+    #[test]
+    fn test_plain_has_no_newline_or_cursor_moves() {
+        let out = render(&sample(), Shell::Plain);
+        assert_eq!(out, "\x1b[38;2;255;0;0m██\x1b[0m 50%");
+        assert!(!out.contains('\n'));
+    }
+
+    #[test]
+    fn test_zsh_wraps_escapes_and_doubles_percent() {
+        let out = render(&sample(), Shell::Zsh);
+        assert_eq!(out, "%{\x1b[38;2;255;0;0m%}██%{\x1b[0m%} 50%%");
+    }
+
+    #[test]
+    fn test_bash_wraps_escapes() {
+        let out = render(&sample(), Shell::Bash);
+        assert_eq!(out, "\x01\x1b[38;2;255;0;0m\x02██\x01\x1b[0m\x02 50%");
+    }
+
+    #[test]
+    fn test_tmux_uses_style_directives() {
+        let out = render(&[Span::colored("#1", (0, 255, 0)).bold()], Shell::Tmux);
+        assert_eq!(out, "#[fg=#00ff00,bold]##1#[default]");
+    }
+
+    #[test]
+    fn test_push_merges_same_style() {
+        let mut spans = Vec::new();
+        push(&mut spans, Span::colored("█", (1, 2, 3)));
+        push(&mut spans, Span::colored("█", (1, 2, 3)));
+        push(&mut spans, Span::plain("░"));
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].text, "██");
+    }
+
+    #[test]
+    fn test_shell_from_name() {
+        assert_eq!(Shell::from_name("ZSH"), Some(Shell::Zsh));
+        assert_eq!(Shell::from_name("fish"), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! Shared input resolution: file paths, with `-` meaning stdin
+
+use super::profile::{self, Phase};
+use std::fs;
+use std::io::{self, Read};
+
+/// Path argument that selects stdin
+pub const STDIN: &str = "-";
+
+/// Whether `path` refers to stdin
+pub fn is_stdin(path: &str) -> bool {
+    path == STDIN
+}
+
+/// Name to show in messages and headers ("<stdin>" for `-`)
+pub fn display_name(path: &str) -> &str {
+    if is_stdin(path) {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+/// Read raw bytes from a file, or from stdin when `path` is `-`
+pub fn read_bytes(path: &str) -> io::Result<Vec<u8>> {
+    profile::time(Phase::Parse, || {
+        if is_stdin(path) {
+            let mut buffer = Vec::new();
+            io::stdin().read_to_end(&mut buffer)?;
+            Ok(buffer)
+        } else {
+            fs::read(path)
+        }
+    })
+}
+
+/// Read UTF-8 text from a file, or from stdin when `path` is `-`
+///
+/// Binary input is rejected with `InvalidData` rather than decoded lossily.
+pub fn read_text(path: &str) -> io::Result<String> {
+    let bytes = read_bytes(path)?;
+    if is_binary(&bytes) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is binary, expected text", display_name(path)),
+        ));
+    }
+    String::from_utf8(bytes).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not valid UTF-8", display_name(path)),
+        )
+    })
+}
+
+/// Heuristic used by git and diff: a NUL byte in the first 8 KiB means binary
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(8192).any(|b| *b == 0)
+}
+
+/// Reject argument lists that would read stdin more than once
+pub fn check_single_stdin<'a>(paths: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+    if paths.into_iter().filter(|p| is_stdin(p)).count() > 1 {
+        return Err("stdin ('-') can only be used for one input".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name() {
+        assert_eq!(display_name("-"), "<stdin>");
+        assert_eq!(display_name("data.csv"), "data.csv");
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(is_binary(b"PNG\0\x01"));
+        assert!(!is_binary("héllo\nworld".as_bytes()));
+    }
+
+    #[test]
+    fn test_check_single_stdin() {
+        assert!(check_single_stdin(["-", "b.txt"]).is_ok());
+        assert!(check_single_stdin(["-", "-"]).is_err());
+    }
+
+    #[test]
+    fn test_read_text_rejects_binary_file() {
+        let path = std::env::temp_dir().join("termgfx_input_binary_test.bin");
+        fs::write(&path, b"\x89PNG\0\0").unwrap();
+        let err = read_text(path.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -1,33 +1,44 @@
+use crate::text::{self, display_width};
 use std::io::{self, Read};
-use unicode_width::UnicodeWidthStr;
 
-/// Strip ANSI escape codes to calculate actual display width
-fn strip_ansi(text: &str) -> String {
-    let mut result = String::new();
+/// Hard-wrap pre-rendered text at `width` visible columns
+///
+/// Escape sequences are copied through whole. Each wrapped line ends with a
+/// reset and the next one re-opens whatever styles were active, so every line
+/// can be printed on its own.
+pub fn wrap_ansi(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut active = String::new();
+    let mut used = 0;
     let mut chars = text.chars().peekable();
 
-    while let Some(c) = chars.next() {
-        if c == '\x1b' {
-            // Skip escape sequence
-            if chars.peek() == Some(&'[') {
-                chars.next();
-                while let Some(&c) = chars.peek() {
-                    chars.next();
-                    if c.is_alphabetic() {
-                        break;
-                    }
-                }
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            let seq = format!("{}{}", ch, text::take_escape(&mut chars));
+            if seq == "\x1b[0m" || seq == "\x1b[m" {
+                active.clear();
+            } else if seq.ends_with('m') {
+                active.push_str(&seq);
             }
-        } else {
-            result.push(c);
+            current.push_str(&seq);
+            continue;
         }
-    }
-    result
-}
 
-/// Calculate the actual display width of a string (without ANSI codes)
-fn display_width(text: &str) -> usize {
-    UnicodeWidthStr::width(strip_ansi(text).as_str())
+        let w = text::char_width(ch);
+        if used + w > width && used > 0 {
+            if !active.is_empty() {
+                current.push_str("\x1b[0m");
+            }
+            lines.push(std::mem::replace(&mut current, active.clone()));
+            used = 0;
+        }
+        current.push(ch);
+        used += w;
+    }
+    lines.push(current);
+    lines
 }
 
 /// Read content from stdin
@@ -37,6 +48,60 @@ fn read_stdin() -> io::Result<String> {
     Ok(buffer)
 }
 
+/// Lay blocks of text next to each other, `gap` columns apart
+///
+/// Each block is as wide as its widest line and shorter blocks are padded
+/// with blank lines. `align` places narrower lines within their block:
+/// left, center or right.
+pub fn side_by_side(inputs: &[String], gap: usize, align: &str) -> Vec<String> {
+    let contents: Vec<Vec<&str>> = inputs.iter().map(|s| s.lines().collect()).collect();
+    let max_lines = contents.iter().map(|c| c.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = contents
+        .iter()
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| display_width(line))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = Vec::with_capacity(max_lines);
+    for line_idx in 0..max_lines {
+        let mut row = String::new();
+        for (col_idx, content) in contents.iter().enumerate() {
+            let line = content.get(line_idx).unwrap_or(&"");
+            let width = widths[col_idx];
+            let line_width = display_width(line);
+            let padding = width.saturating_sub(line_width);
+
+            // Apply alignment
+            match align {
+                "right" => row.push_str(&format!("{}{}", " ".repeat(padding), line)),
+                "center" => {
+                    let left_pad = padding / 2;
+                    let right_pad = padding - left_pad;
+                    row.push_str(&format!(
+                        "{}{}{}",
+                        " ".repeat(left_pad),
+                        line,
+                        " ".repeat(right_pad)
+                    ));
+                }
+                _ => row.push_str(&format!("{}{}", line, " ".repeat(padding))), // left (default)
+            }
+
+            // Add gap between columns
+            if col_idx < contents.len() - 1 {
+                row.push_str(&" ".repeat(gap));
+            }
+        }
+        out.push(row);
+    }
+    out
+}
+
 /// Join content horizontally or vertically
 pub fn join(
     inputs: Vec<String>,
@@ -60,46 +125,8 @@ pub fn join(
         }
         println!();
     } else {
-        // Horizontal join: side by side
-        let contents: Vec<Vec<&str>> = inputs.iter().map(|s| s.lines().collect()).collect();
-        let max_lines = contents.iter().map(|c| c.len()).max().unwrap_or(0);
-        let widths: Vec<usize> = contents
-            .iter()
-            .map(|lines| {
-                lines
-                    .iter()
-                    .map(|line| display_width(line))
-                    .max()
-                    .unwrap_or(0)
-            })
-            .collect();
-
-        for line_idx in 0..max_lines {
-            for (col_idx, content) in contents.iter().enumerate() {
-                let line = content.get(line_idx).unwrap_or(&"");
-                let width = widths[col_idx];
-                let line_width = display_width(line);
-                let padding = width.saturating_sub(line_width);
-
-                // Apply alignment
-                let output = match align {
-                    "right" => format!("{:>width$}{}", "", line, width = padding),
-                    "center" => {
-                        let left_pad = padding / 2;
-                        let right_pad = padding - left_pad;
-                        format!("{}{}{}", " ".repeat(left_pad), line, " ".repeat(right_pad))
-                    }
-                    _ => format!("{}{}", line, " ".repeat(padding)), // left (default)
-                };
-
-                print!("{}", output);
-
-                // Add gap between columns
-                if col_idx < contents.len() - 1 {
-                    print!("{}", " ".repeat(gap));
-                }
-            }
-            println!();
+        for line in side_by_side(&inputs, gap, align) {
+            println!("{}", line);
         }
     }
 
@@ -140,12 +167,7 @@ pub fn columns(
 
             // Truncate if too wide, pad if too narrow
             if line_width > width {
-                let stripped = strip_ansi(line);
-                let truncated = stripped
-                    .chars()
-                    .take(width.saturating_sub(1))
-                    .collect::<String>();
-                print!("{}…", truncated);
+                print!("{}", text::truncate(line, width, "…"));
             } else {
                 let padding = width.saturating_sub(line_width);
                 print!("{}{}", line, " ".repeat(padding));
@@ -261,15 +283,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_strip_ansi() {
-        let colored = "\x1b[31mRed\x1b[0m";
-        assert_eq!(strip_ansi(colored), "Red");
+    fn test_wrap_ansi_carries_styles_across_lines() {
+        let lines = wrap_ansi("\x1b[31mabcdef\x1b[0mgh", 4);
+        assert_eq!(lines, vec!["\x1b[31mabcd\x1b[0m", "\x1b[31mef\x1b[0mgh"]);
+        assert!(lines.iter().all(|l| display_width(l) <= 4));
     }
 
     #[test]
-    fn test_display_width() {
-        assert_eq!(display_width("Hello"), 5);
-        assert_eq!(display_width("\x1b[31mRed\x1b[0m"), 3);
+    fn test_wrap_ansi_plain_text() {
+        assert_eq!(wrap_ansi("abcde", 2), vec!["ab", "cd", "e"]);
+        assert_eq!(wrap_ansi("日本語", 4), vec!["日本", "語"]);
     }
 
     #[test]
@@ -0,0 +1,360 @@
+//! Log viewer with level coloring, filtering and follow mode
+
+use crate::interactive::pager::Pager;
+use crate::output::calendar::days_from_civil;
+use crate::output::input;
+use owo_colors::{OwoColorize, Style};
+use regex::Regex;
+use std::fs::File;
+use std::io::{self, BufRead, IsTerminal, Read, Seek, SeekFrom};
+use std::thread;
+use std::time::Duration;
+
+/// How often `--follow` polls the file for new data
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Severity detected from a log line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn from_token(token: &str) -> Self {
+        match token.to_uppercase().as_str() {
+            "FATAL" | "CRIT" | "CRITICAL" | "ERROR" | "ERR" => Level::Error,
+            "WARN" | "WARNING" => Level::Warn,
+            "DEBUG" | "TRACE" => Level::Debug,
+            _ => Level::Info,
+        }
+    }
+
+    /// Style for the level token itself
+    fn token_style(self) -> Style {
+        match self {
+            Level::Error => Style::new().red().bold(),
+            Level::Warn => Style::new().yellow().bold(),
+            Level::Info => Style::new().cyan().bold(),
+            Level::Debug => Style::new().bright_black(),
+        }
+    }
+
+    /// Style for the rest of the line
+    fn line_style(self) -> Style {
+        match self {
+            Level::Error => Style::new().red(),
+            Level::Warn => Style::new().yellow(),
+            Level::Info => Style::new(),
+            Level::Debug => Style::new().bright_black(),
+        }
+    }
+}
+
+/// Formats log lines: level colors, highlighted timestamps, optional deltas
+pub struct LogStyler {
+    level_re: Regex,
+    timestamp_re: Regex,
+    filter: Option<Regex>,
+    relative: bool,
+    last_timestamp: Option<f64>,
+}
+
+impl LogStyler {
+    pub fn new(filter: Option<&str>, relative: bool) -> Result<Self, String> {
+        let filter = filter
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| format!("Invalid filter regex: {}", e))?;
+        Ok(Self {
+            level_re: Regex::new(
+                r"(?i)\b(FATAL|CRIT(?:ICAL)?|ERROR|ERR|WARN(?:ING)?|INFO|NOTICE|DEBUG|TRACE)\b",
+            )
+            .unwrap(),
+            timestamp_re: Regex::new(
+                r"(?:(\d{4})-(\d{2})-(\d{2})[T ])?(\d{2}):(\d{2}):(\d{2})(?:[.,](\d+))?(?:Z|[+-]\d{2}:?\d{2})?",
+            )
+            .unwrap(),
+            filter,
+            relative,
+            last_timestamp: None,
+        })
+    }
+
+    /// Level named by a line and where its token sits
+    fn detect<'a>(&self, line: &'a str) -> Option<(Level, regex::Match<'a>)> {
+        self.level_re
+            .captures(line)
+            .and_then(|c| c.get(1))
+            .map(|m| (Level::from_token(m.as_str()), m))
+    }
+
+    /// Styled line, or None when it does not match `--filter`
+    pub fn format(&mut self, line: &str) -> Option<String> {
+        if let Some(filter) = &self.filter {
+            if !filter.is_match(line) {
+                return None;
+            }
+        }
+
+        let detected = self.detect(line);
+        let level_match = detected.map(|(_, m)| m);
+        let line_style = detected.map_or(Style::new(), |(level, _)| level.line_style());
+
+        let mut out = String::new();
+        let mut pos = 0;
+
+        if let Some(ts) = self.timestamp_re.captures(line) {
+            let whole = ts.get(0).unwrap();
+            // Only treat it as a timestamp if it precedes the level token
+            if level_match.is_none_or(|m| whole.end() <= m.start()) {
+                out.push_str(&styled(&line[..whole.start()], line_style));
+                out.push_str(&whole.as_str().blue().to_string());
+                if self.relative {
+                    if let Some(seconds) = timestamp_seconds(&ts) {
+                        out.push_str(&self.delta(seconds));
+                    }
+                }
+                pos = whole.end();
+            }
+        }
+
+        if let Some((level, m)) = detected.filter(|(_, m)| m.start() >= pos) {
+            out.push_str(&styled(&line[pos..m.start()], line_style));
+            out.push_str(&styled(m.as_str(), level.token_style()));
+            pos = m.end();
+        }
+        out.push_str(&styled(&line[pos..], line_style));
+        Some(out)
+    }
+
+    /// " (+1.2s)" since the previous timestamped line; long gaps stand out
+    fn delta(&mut self, seconds: f64) -> String {
+        let previous = self.last_timestamp.replace(seconds);
+        let Some(previous) = previous else {
+            return String::new();
+        };
+        let mut gap = seconds - previous;
+        if gap < -43_200.0 {
+            // Time-only stamps wrapping past midnight
+            gap += 86_400.0;
+        }
+        let text = format!(" (+{})", format_gap(gap.max(0.0)));
+        if gap >= 60.0 {
+            text.red().bold().to_string()
+        } else if gap >= 1.0 {
+            text.yellow().to_string()
+        } else {
+            text.bright_black().to_string()
+        }
+    }
+}
+
+fn styled(text: &str, style: Style) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        text.style(style).to_string()
+    }
+}
+
+/// Seconds since the epoch (or since midnight for time-only stamps)
+fn timestamp_seconds(caps: &regex::Captures) -> Option<f64> {
+    let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<i64>().ok());
+    let time = num(4)? * 3600 + num(5)? * 60 + num(6)?;
+    let fraction = caps
+        .get(7)
+        .and_then(|m| format!("0.{}", m.as_str()).parse::<f64>().ok())
+        .unwrap_or(0.0);
+    let days = match (num(1), num(2), num(3)) {
+        (Some(y), Some(m), Some(d)) => days_from_civil(y, m as u32, d as u32),
+        _ => 0,
+    };
+    Some((days * 86_400 + time) as f64 + fraction)
+}
+
+/// Compact duration: 350ms, 1.2s, 2m05s, 1h02m
+pub fn format_gap(seconds: f64) -> String {
+    if seconds < 1.0 {
+        format!("{}ms", (seconds * 1000.0).round() as u64)
+    } else if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else if seconds < 3600.0 {
+        let s = seconds as u64;
+        format!("{}m{:02}s", s / 60, s % 60)
+    } else {
+        let s = seconds as u64;
+        format!("{}h{:02}m", s / 3600, (s % 3600) / 60)
+    }
+}
+
+/// Split a buffer into complete lines, returning them and the bytes consumed
+fn complete_lines(buffer: &[u8]) -> (Vec<String>, usize) {
+    let Some(last_newline) = buffer.iter().rposition(|b| *b == b'\n') else {
+        return (Vec::new(), 0);
+    };
+    let lines = String::from_utf8_lossy(&buffer[..last_newline])
+        .split('\n')
+        .map(|l| l.trim_end_matches('\r').to_string())
+        .collect();
+    (lines, last_newline + 1)
+}
+
+/// View a log file (or `-` for stdin)
+pub fn render(file: &str, follow: bool, filter: Option<&str>, relative: bool, no_pager: bool) {
+    let mut styler = match LogStyler::new(filter, relative) {
+        Ok(styler) => styler,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = if follow {
+        if input::is_stdin(file) {
+            stream_stdin(&mut styler)
+        } else {
+            follow_file(file, &mut styler)
+        }
+    } else {
+        show(file, &mut styler, no_pager)
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Print the whole log, scrolling in the pager when it is taller than the terminal
+fn show(file: &str, styler: &mut LogStyler, no_pager: bool) -> io::Result<()> {
+    let text = input::read_text(file)?;
+    let lines: Vec<String> = text.lines().filter_map(|l| styler.format(l)).collect();
+
+    let rows = crossterm::terminal::size()
+        .map(|(_, h)| h as usize)
+        .unwrap_or(24);
+    if io::stdout().is_terminal() && !no_pager && lines.len() > rows {
+        let title = input::display_name(file).to_string();
+        return Pager::from_lines(lines, true, Some(title)).render();
+    }
+
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Print stdin line by line as it arrives
+fn stream_stdin(styler: &mut LogStyler) -> io::Result<()> {
+    for line in io::stdin().lock().lines() {
+        if let Some(styled) = styler.format(&line?) {
+            println!("{}", styled);
+        }
+    }
+    Ok(())
+}
+
+/// Print the file, then keep printing appended lines like `tail -f`
+fn follow_file(path: &str, styler: &mut LogStyler) -> io::Result<()> {
+    let mut pos = 0u64;
+    loop {
+        let len = std::fs::metadata(path)?.len();
+        if len < pos {
+            // Truncated or rotated: start over
+            pos = 0;
+        }
+        if len > pos {
+            let mut file = File::open(path)?;
+            file.seek(SeekFrom::Start(pos))?;
+            let mut buffer = Vec::new();
+            file.read_to_end(&mut buffer)?;
+
+            let (lines, consumed) = complete_lines(&buffer);
+            for line in lines {
+                if let Some(styled) = styler.format(&line) {
+                    println!("{}", styled);
+                }
+            }
+            pos += consumed as u64;
+        }
+        thread::sleep(FOLLOW_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*m")
+            .unwrap()
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    #[test]
+    fn test_level_detection() {
+        let styler = LogStyler::new(None, false).unwrap();
+        let level = |line| styler.detect(line).map(|(level, _)| level);
+        assert_eq!(level("2024-01-01 ERROR boom"), Some(Level::Error));
+        assert_eq!(level("[warning] disk"), Some(Level::Warn));
+        assert_eq!(level("level=info msg=ok"), Some(Level::Info));
+        assert_eq!(level("TRACE enter"), Some(Level::Debug));
+        assert_eq!(level("plain text"), None);
+        assert_eq!(level("terrorism"), None);
+    }
+
+    #[test]
+    fn test_format_preserves_text() {
+        let mut styler = LogStyler::new(None, false).unwrap();
+        let line = "2024-03-01T10:00:00Z WARN cache miss for key=42";
+        let styled = styler.format(line).unwrap();
+        assert_ne!(styled, line);
+        assert_eq!(strip(&styled), line);
+    }
+
+    #[test]
+    fn test_filter_drops_lines() {
+        let mut styler = LogStyler::new(Some("db|cache"), false).unwrap();
+        assert!(styler.format("INFO cache warm").is_some());
+        assert!(styler.format("INFO http ok").is_none());
+        assert!(LogStyler::new(Some("("), false).is_err());
+    }
+
+    #[test]
+    fn test_relative_deltas() {
+        let mut styler = LogStyler::new(None, true).unwrap();
+        let first = strip(&styler.format("10:00:00.000 INFO start").unwrap());
+        let second = strip(&styler.format("10:00:01.500 INFO next").unwrap());
+        let third = strip(&styler.format("10:05:01 INFO late").unwrap());
+        assert_eq!(first, "10:00:00.000 INFO start");
+        assert_eq!(second, "10:00:01.500 (+1.5s) INFO next");
+        assert_eq!(third, "10:05:01 (+4m59s) INFO late");
+    }
+
+    #[test]
+    fn test_relative_across_dates() {
+        let mut styler = LogStyler::new(None, true).unwrap();
+        styler.format("2024-02-28 23:59:59 INFO a");
+        let next = strip(&styler.format("2024-02-29 00:00:01 INFO b").unwrap());
+        assert!(next.contains("(+2.0s)"));
+    }
+
+    #[test]
+    fn test_format_gap() {
+        assert_eq!(format_gap(0.35), "350ms");
+        assert_eq!(format_gap(75.0), "1m15s");
+        assert_eq!(format_gap(3720.0), "1h02m");
+    }
+
+    #[test]
+    fn test_complete_lines_keeps_partial_tail() {
+        let (lines, consumed) = complete_lines(b"one\r\ntwo\nthr");
+        assert_eq!(lines, vec!["one", "two"]);
+        assert_eq!(consumed, 9);
+        assert_eq!(complete_lines(b"partial"), (Vec::new(), 0));
+    }
+}
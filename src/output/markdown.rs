@@ -0,0 +1,419 @@
+use crate::output::banner::{self, BannerLayout};
+use crate::output::input;
+use crate::output::syntax::Highlighter;
+use crate::output::table::{self, TableOptions};
+use crate::text;
+use owo_colors::{OwoColorize, Style};
+
+/// Render a Markdown file (or `-` for stdin) to the terminal
+pub fn render(source: &str) {
+    let text = match input::read_text(source) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let width = crossterm::terminal::size()
+        .map(|(w, _)| w as usize)
+        .unwrap_or(80);
+    for line in render_lines(&text, width) {
+        println!("{}", line);
+    }
+}
+
+/// Convert Markdown into styled terminal lines
+pub fn render_lines(text: &str, width: usize) -> Vec<String> {
+    let source: Vec<&str> = text.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < source.len() {
+        let line = source[i];
+        let trimmed = line.trim_start();
+
+        // Fenced code block
+        if let Some(fence) = ["```", "~~~"].iter().find(|f| trimmed.starts_with(**f)) {
+            let lang = trimmed[fence.len()..].trim().to_string();
+            let mut code = Vec::new();
+            i += 1;
+            while i < source.len() && !source[i].trim_start().starts_with(fence) {
+                code.push(source[i].replace('\t', "    "));
+                i += 1;
+            }
+            i += 1; // closing fence
+            out.extend(code_block(&code, &lang, width));
+            continue;
+        }
+
+        // Table: header row followed by a |---| separator
+        if trimmed.starts_with('|') && source.get(i + 1).is_some_and(|l| is_table_separator(l)) {
+            let headers = table_cells(trimmed);
+            let mut rows = Vec::new();
+            i += 2;
+            while i < source.len() && source[i].trim_start().starts_with('|') {
+                rows.push(table_cells(source[i].trim_start()));
+                i += 1;
+            }
+            let options = TableOptions {
+                max_width: Some(width / headers.len().max(1)),
+                ..Default::default()
+            };
+            out.extend(table::table_lines(&headers, &rows, &options));
+            continue;
+        }
+
+        i += 1;
+
+        if trimmed.is_empty() {
+            // Collapse runs of blank lines
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+        } else if let Some((level, title)) = heading(trimmed) {
+            out.extend(heading_lines(level, title, width));
+        } else if is_rule(trimmed) {
+            out.push("─".repeat(width.min(100)).bright_black().to_string());
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            out.push(format!(
+                "{} {}",
+                "│".bright_black(),
+                inline(quote.trim_start()).italic()
+            ));
+        } else if let Some((indent, marker, item)) = list_item(line) {
+            out.push(format!(
+                "{}{} {}",
+                "  ".repeat(indent / 2 + 1),
+                marker,
+                inline(item)
+            ));
+        } else {
+            out.push(inline(trimmed));
+        }
+    }
+
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    out
+}
+
+/// "## Title" → (2, "Title")
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        Some((level, line[level..].trim().trim_end_matches('#').trim_end()))
+    } else {
+        None
+    }
+}
+
+fn heading_lines(level: usize, title: &str, width: usize) -> Vec<String> {
+    match level {
+        1 => {
+            let layout = BannerLayout {
+                padding: 0,
+                max_width: Some(width.min(100)),
+                ..Default::default()
+            };
+            // '|' would be read as a banner subtitle separator
+            banner::banner_lines(&title.replace('|', "/"), None, &layout, width)
+        }
+        2 => vec![
+            title.bright_cyan().bold().to_string(),
+            "─".repeat(text::display_width(title)).cyan().to_string(),
+        ],
+        3 => vec![format!(
+            "{} {}",
+            "▸".bright_magenta(),
+            title.bright_magenta().bold()
+        )],
+        _ => vec![title.bold().to_string()],
+    }
+}
+
+/// `---`, `***` or `___` (three or more, spaces allowed)
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|m| compact.chars().all(|c| c == *m))
+}
+
+/// Bullet, numbered and task list items: (indent, rendered marker, text)
+fn list_item(line: &str) -> Option<(usize, String, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = line.trim_start();
+
+    if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|m| rest.strip_prefix(m)) {
+        if let Some(task) = item.strip_prefix("[ ] ") {
+            return Some((indent, "☐".bright_black().to_string(), task));
+        }
+        if let Some(task) = item
+            .strip_prefix("[x] ")
+            .or_else(|| item.strip_prefix("[X] "))
+        {
+            return Some((indent, "☑".green().to_string(), task));
+        }
+        return Some((indent, "•".bright_cyan().to_string(), item));
+    }
+
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(item) = rest[digits..].strip_prefix(". ") {
+            return Some((
+                indent,
+                format!("{}.", &rest[..digits]).bright_cyan().to_string(),
+                item,
+            ));
+        }
+    }
+    None
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    line.trim()
+        .trim_start_matches('|')
+        .trim_end_matches('|')
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// Render inline spans: **bold**, *italic*, ~~strike~~, `code` and [links](url)
+fn inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut plain = String::new();
+    let (mut bold, mut italic, mut strike) = (false, false, false);
+    let mut i = 0;
+
+    let flush = |plain: &mut String, out: &mut String, bold: bool, italic: bool, strike: bool| {
+        if plain.is_empty() {
+            return;
+        }
+        let mut style = Style::new();
+        if bold {
+            style = style.bold();
+        }
+        if italic {
+            style = style.italic();
+        }
+        if strike {
+            style = style.strikethrough();
+        }
+        out.push_str(&plain.style(style).to_string());
+        plain.clear();
+    };
+
+    while i < chars.len() {
+        let rest = &chars[i..];
+        if starts_with(rest, "**") || starts_with(rest, "__") {
+            flush(&mut plain, &mut out, bold, italic, strike);
+            bold = !bold;
+            i += 2;
+        } else if starts_with(rest, "~~") {
+            flush(&mut plain, &mut out, bold, italic, strike);
+            strike = !strike;
+            i += 2;
+        } else if (chars[i] == '*'
+            && (italic || chars.get(i + 1).is_some_and(|c| !c.is_whitespace())))
+            || (chars[i] == '_' && (i == 0 || !chars[i - 1].is_alphanumeric() || italic))
+        {
+            flush(&mut plain, &mut out, bold, italic, strike);
+            italic = !italic;
+            i += 1;
+        } else if chars[i] == '`' {
+            match chars[i + 1..].iter().position(|c| *c == '`') {
+                Some(len) => {
+                    flush(&mut plain, &mut out, bold, italic, strike);
+                    let code: String = chars[i + 1..i + 1 + len].iter().collect();
+                    out.push_str(&code.truecolor(230, 180, 80).to_string());
+                    i += len + 2;
+                }
+                None => {
+                    plain.push('`');
+                    i += 1;
+                }
+            }
+        } else if let Some((label, url, len)) = link(&chars[i..]) {
+            flush(&mut plain, &mut out, bold, italic, strike);
+            out.push_str(&label.bright_blue().underline().to_string());
+            if label != url {
+                out.push_str(&format!(" ({})", url).bright_black().to_string());
+            }
+            i += len;
+        } else {
+            plain.push(chars[i]);
+            i += 1;
+        }
+    }
+    flush(&mut plain, &mut out, bold, italic, strike);
+    out
+}
+
+fn starts_with(chars: &[char], pattern: &str) -> bool {
+    pattern.chars().count() <= chars.len() && pattern.chars().zip(chars).all(|(p, c)| p == *c)
+}
+
+/// Parse "[label](url)" at the start of `chars`, returning its length in chars
+fn link(chars: &[char]) -> Option<(String, String, usize)> {
+    if chars.first() != Some(&'[') {
+        return None;
+    }
+    let close = chars.iter().position(|c| *c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = close + 2 + chars[close + 2..].iter().position(|c| *c == ')')?;
+    let label: String = chars[1..close].iter().collect();
+    let url: String = chars[close + 2..end].iter().collect();
+    Some((label, url, end + 1))
+}
+
+/// Code in a rounded box with the language on the top border
+fn code_block(code: &[String], lang: &str, width: usize) -> Vec<String> {
+    let max_inner = width.saturating_sub(4).max(10);
+    let inner = code
+        .iter()
+        .map(|l| text::display_width(l))
+        .max()
+        .unwrap_or(0)
+        .max(text::display_width(lang) + 2)
+        .min(max_inner);
+
+    let label = if lang.is_empty() {
+        String::new()
+    } else {
+        format!(" {} ", lang)
+    };
+    let mut lines = vec![format!(
+        "{}{}{}{}",
+        "╭─".bright_black(),
+        label.bright_cyan(),
+        "─"
+            .repeat((inner + 1).saturating_sub(text::display_width(&label)))
+            .bright_black(),
+        "╮".bright_black()
+    )];
+    let mut highlighter = Highlighter::new(lang);
+    for line in code {
+        let visible: String = truncate(line, inner);
+        let pad = inner - text::display_width(&visible);
+        lines.push(format!(
+            "{} {}{} {}",
+            "│".bright_black(),
+            highlighter.line(&visible),
+            " ".repeat(pad),
+            "│".bright_black()
+        ));
+    }
+    lines.push(
+        format!("╰{}╯", "─".repeat(inner + 2))
+            .bright_black()
+            .to_string(),
+    );
+    lines
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let w = text::char_width(ch);
+        if used + w > width {
+            break;
+        }
+        used += w;
+        out.push(ch);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[String]) -> Vec<String> {
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        lines
+            .iter()
+            .map(|l| ansi.replace_all(l, "").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_heading_levels() {
+        assert_eq!(heading("# Title"), Some((1, "Title")));
+        assert_eq!(heading("### Deep ###"), Some((3, "Deep")));
+        assert_eq!(heading("#hashtag"), None);
+    }
+
+    #[test]
+    fn test_h1_renders_as_banner() {
+        let lines = plain(&render_lines("# Hello", 40));
+        assert!(lines[0].starts_with('╔'));
+        assert!(lines[1].contains("Hello"));
+    }
+
+    #[test]
+    fn test_inline_styles_strip_markers() {
+        let lines = plain(&[inline("a **bold** and *it* and `code` ~~x~~")]);
+        assert_eq!(lines[0], "a bold and it and code x");
+    }
+
+    #[test]
+    fn test_inline_link_shows_url() {
+        let lines = plain(&[inline("see [docs](https://x.dev) now")]);
+        assert_eq!(lines[0], "see docs (https://x.dev) now");
+    }
+
+    #[test]
+    fn test_snake_case_and_math_are_not_italic() {
+        let lines = plain(&[inline("call my_fn_name with 2 * 3")]);
+        assert_eq!(lines[0], "call my_fn_name with 2 * 3");
+    }
+
+    #[test]
+    fn test_lists_and_tasks() {
+        let lines = plain(&render_lines("- one\n  - nested\n1. first\n- [x] done", 80));
+        assert_eq!(lines[0], "  • one");
+        assert_eq!(lines[1], "    • nested");
+        assert_eq!(lines[2], "  1. first");
+        assert_eq!(lines[3], "  ☑ done");
+    }
+
+    #[test]
+    fn test_code_block_boxed_with_language() {
+        let md = "```rust\nlet x = 1; // note\n```";
+        let lines = plain(&render_lines(md, 80));
+        assert!(lines[0].starts_with("╭─ rust "));
+        assert!(lines[1].contains("let x = 1; // note"));
+        assert!(lines[2].starts_with('╰'));
+        let widths: Vec<usize> = lines.iter().map(|l| text::display_width(l)).collect();
+        assert!(widths.iter().all(|w| *w == widths[0]));
+    }
+
+    #[test]
+    fn test_table_uses_table_renderer() {
+        let md = "| Name | Age |\n|------|----:|\n| Bob | 3 |";
+        let lines = plain(&render_lines(md, 80));
+        assert!(lines[0].starts_with('┌'));
+        assert!(lines.iter().any(|l| l.contains("Bob")));
+    }
+
+    #[test]
+    fn test_rule_and_blank_collapse() {
+        let lines = plain(&render_lines("a\n\n\n\n---\nb", 20));
+        assert_eq!(lines, vec!["a", "", &"─".repeat(20), "b"]);
+    }
+}
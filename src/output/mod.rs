@@ -1,22 +1,47 @@
+pub mod atomic;
 pub mod banner;
+pub mod cache;
+pub mod calendar;
 pub mod checklist;
+pub mod clock;
+pub mod code;
+pub mod color;
 pub mod dashboard;
 pub mod diff;
+pub mod footer;
 pub mod gauge;
+pub mod gradient;
 pub mod heatmap;
+pub mod inline;
+pub mod input;
 pub mod layout;
+pub mod log;
+pub mod markdown;
 pub mod notification;
 pub mod palette;
 pub mod preview;
+pub mod profile;
 pub mod progress;
+pub mod pty;
+pub mod query;
 pub mod record;
 pub mod regex_filter;
+pub mod sensors;
+pub mod sources;
 pub mod spinner;
 pub mod stats;
 pub mod style;
 pub mod styled_box;
+pub mod styled_text;
+pub mod syntax;
 pub mod table;
+pub mod table_sort;
 pub mod timeline;
+pub mod timer;
+pub mod trace;
 pub mod tree;
 pub mod typewriter;
+pub mod update;
+pub mod vt;
 pub mod watch;
+pub mod width;
@@ -1,9 +1,53 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
 /// Style configuration for notifications
 #[allow(dead_code)]
 struct NotificationStyle {
     emoji: &'static str,
     color: &'static str,
     sound: &'static str,
+    /// Freedesktop icon name for notify-send
+    icon: &'static str,
+}
+
+/// Desktop extras; each is skipped where the backend can't do it
+#[derive(Debug, Default)]
+pub struct NotificationOptions {
+    /// Percent done, drawn as a bar
+    pub progress: Option<u8>,
+    /// Replace the earlier notification sent with the same id
+    pub id: Option<String>,
+    /// How long the notification stays up
+    pub timeout: Option<Duration>,
+    pub actions: Vec<Action>,
+}
+
+/// A button that runs a shell command when clicked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Action {
+    pub label: String,
+    pub command: String,
+}
+
+impl Action {
+    /// Parse `LABEL:COMMAND`, e.g. `Open:xdg-open https://example.com`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match spec.split_once(':') {
+            Some((label, command)) if !label.trim().is_empty() && !command.trim().is_empty() => {
+                Ok(Action {
+                    label: label.trim().to_string(),
+                    command: command.trim().to_string(),
+                })
+            }
+            _ => Err(format!("Invalid action '{}' (use LABEL:COMMAND)", spec)),
+        }
+    }
+
+    fn run(&self) {
+        let _ = Command::new("sh").arg("-c").arg(&self.command).status();
+    }
 }
 
 impl NotificationStyle {
@@ -13,27 +57,34 @@ impl NotificationStyle {
                 emoji: "✅",
                 color: "\x1b[32m", // Green
                 sound: "Glass",
+                icon: "emblem-default",
             },
             "warning" => NotificationStyle {
                 emoji: "⚠️",
                 color: "\x1b[33m", // Yellow
                 sound: "Ping",
+                icon: "dialog-warning",
             },
             "error" => NotificationStyle {
                 emoji: "❌",
                 color: "\x1b[31m", // Red
                 sound: "Basso",
+                icon: "dialog-error",
             },
             _ => NotificationStyle {
                 emoji: "ℹ️",
                 color: "\x1b[36m", // Cyan
                 sound: "Glass",
+                icon: "dialog-information",
             },
         }
     }
 }
 
 /// Render a notification with both terminal and desktop components
+///
+/// With `desktop_only`, the terminal box is still drawn when no desktop
+/// notification could be shown.
 pub fn render(
     message: &str,
     title: Option<&str>,
@@ -41,22 +92,31 @@ pub fn render(
     sound: bool,
     terminal_only: bool,
     desktop_only: bool,
+    options: &NotificationOptions,
 ) {
     let notification_style = NotificationStyle::from_name(style);
 
     // Show terminal notification (unless desktop-only)
     if !desktop_only {
-        render_terminal(message, title, &notification_style);
+        render_terminal(message, title, &notification_style, options.progress);
     }
 
     // Show desktop notification (unless terminal-only)
     if !terminal_only {
-        render_desktop(message, title, &notification_style, sound);
+        let shown = render_desktop(message, title, &notification_style, sound, options);
+        if !shown && desktop_only {
+            render_terminal(message, title, &notification_style, options.progress);
+        }
     }
 }
 
 /// Render terminal notification with styled box and bell character
-fn render_terminal(message: &str, title: Option<&str>, style: &NotificationStyle) {
+fn render_terminal(
+    message: &str,
+    title: Option<&str>,
+    style: &NotificationStyle,
+    progress: Option<u8>,
+) {
     let reset = "\x1b[0m";
     let bold = "\x1b[1m";
 
@@ -91,6 +151,15 @@ fn render_terminal(message: &str, title: Option<&str>, style: &NotificationStyle
     for line in wrapped_lines {
         println!("{}{}│ {:<39} │{}", style.color, bold, line, reset);
     }
+    if let Some(percent) = progress {
+        println!(
+            "{}{}│ {:<39} │{}",
+            style.color,
+            bold,
+            progress_bar(percent, 32),
+            reset
+        );
+    }
 
     // Bottom border
     println!(
@@ -99,16 +168,56 @@ fn render_terminal(message: &str, title: Option<&str>, style: &NotificationStyle
     );
 }
 
-/// Render desktop notification using macOS osascript
-fn render_desktop(message: &str, title: Option<&str>, style: &NotificationStyle, sound: bool) {
+/// Show the desktop notification, returning whether one was shown
+///
+/// Linux uses notify-send; macOS uses terminal-notifier when installed (it
+/// can replace by id and run an action on click), else osascript.
+fn render_desktop(
+    message: &str,
+    title: Option<&str>,
+    style: &NotificationStyle,
+    sound: bool,
+    options: &NotificationOptions,
+) -> bool {
+    let body = match options.progress {
+        Some(percent) => format!("{} ({}%)", message, percent),
+        None => message.to_string(),
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        notify_send(&body, title, style, sound, options)
+    }
+
     #[cfg(target_os = "macos")]
     {
-        use std::process::Command;
         let notification_title = format!("{} {}", style.emoji, title.unwrap_or("Notification"));
+        if options.id.is_some() || !options.actions.is_empty() {
+            let mut cmd = Command::new("terminal-notifier");
+            cmd.args([
+                "-title",
+                notification_title.as_str(),
+                "-message",
+                body.as_str(),
+            ]);
+            if let Some(id) = &options.id {
+                cmd.args(["-group", id.as_str()]);
+            }
+            // terminal-notifier runs one command, on click
+            if let Some(action) = options.actions.first() {
+                cmd.args(["-execute", action.command.as_str()]);
+            }
+            if sound {
+                cmd.args(["-sound", style.sound]);
+            }
+            if cmd.output().is_ok_and(|out| out.status.success()) {
+                return true;
+            }
+        }
 
         let mut script = format!(
             "display notification \"{}\" with title \"{}\"",
-            message.replace('"', "\\\""),
+            body.replace('"', "\\\""),
             notification_title.replace('"', "\\\"")
         );
 
@@ -122,14 +231,124 @@ fn render_desktop(message: &str, title: Option<&str>, style: &NotificationStyle,
         if let Err(_e) = result {
             // Silently fail - terminal notification already shown
             eprintln!("Note: Desktop notification unavailable");
+            return false;
         }
+        true
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     {
-        // On non-macOS, silently skip desktop notification
-        let _ = (message, title, style, sound);
+        // No desktop backend here; the terminal box stands in
+        let _ = (body, title, style, sound, options);
+        false
+    }
+}
+
+/// Send with notify-send, replacing the notification last sent under
+/// `options.id`; with actions, waits for a click and runs the chosen one
+#[cfg(target_os = "linux")]
+fn notify_send(
+    body: &str,
+    title: Option<&str>,
+    style: &NotificationStyle,
+    sound: bool,
+    options: &NotificationOptions,
+) -> bool {
+    let summary = format!("{} {}", style.emoji, title.unwrap_or("Notification"));
+    let mut cmd = Command::new("notify-send");
+    cmd.args(["--app-name=termgfx", "--print-id", "--icon", style.icon]);
+    if let Some(previous) = options.id.as_deref().and_then(saved_id) {
+        cmd.arg(format!("--replace-id={}", previous));
     }
+    if let Some(percent) = options.progress {
+        cmd.arg(format!("--hint=int:value:{}", percent));
+    }
+    if let Some(timeout) = options.timeout {
+        cmd.arg(format!("--expire-time={}", timeout.as_millis()));
+    }
+    if sound {
+        cmd.arg("--hint=string:sound-name:message-new-instant");
+    }
+    for (i, action) in options.actions.iter().enumerate() {
+        cmd.arg(format!("--action={}={}", i, action.label));
+    }
+    cmd.arg("--").arg(&summary).arg(body);
+
+    let Ok(output) = cmd.output() else {
+        return false;
+    };
+    if !output.status.success() {
+        return false;
+    }
+    let (id, chosen) = parse_reply(&String::from_utf8_lossy(&output.stdout));
+    if let (Some(name), Some(id)) = (&options.id, id) {
+        save_id(name, id);
+    }
+    if let Some(action) = chosen.and_then(|i| options.actions.get(i)) {
+        action.run();
+    }
+    true
+}
+
+/// The notification id and the index of the clicked action from
+/// notify-send's output (`--print-id` prints the id first)
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_reply(stdout: &str) -> (Option<u32>, Option<usize>) {
+    let mut lines = stdout.lines().map(str::trim).filter(|l| !l.is_empty());
+    let id = lines.next().and_then(|l| l.parse().ok());
+    let chosen = lines.next().and_then(|l| l.parse().ok());
+    (id, chosen)
+}
+
+/// Where the desktop id for a `--id` name is kept between runs
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn id_path(name: &str) -> Option<PathBuf> {
+    let file: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Some(
+        dirs::cache_dir()?
+            .join("termgfx")
+            .join("notifications")
+            .join(file),
+    )
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn saved_id(name: &str) -> Option<u32> {
+    std::fs::read_to_string(id_path(name)?)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn save_id(name: &str, id: u32) {
+    if let Some(path) = id_path(name) {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, id.to_string());
+    }
+}
+
+/// `width` cells of bar for `percent`, then the number
+fn progress_bar(percent: u8, width: usize) -> String {
+    let filled = (percent.min(100) as usize * width + 50) / 100;
+    format!(
+        "{}{} {:>3}%",
+        "█".repeat(filled),
+        "░".repeat(width - filled),
+        percent
+    )
 }
 
 /// Wrap text to fit within a specified width
@@ -179,6 +398,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_action_parse() {
+        assert_eq!(
+            Action::parse("Open: xdg-open https://example.com"),
+            Ok(Action {
+                label: "Open".to_string(),
+                command: "xdg-open https://example.com".to_string(),
+            })
+        );
+        assert_eq!(
+            Action::parse("Open"),
+            Err("Invalid action 'Open' (use LABEL:COMMAND)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        assert_eq!(parse_reply("42\n"), (Some(42), None));
+        assert_eq!(parse_reply("42\n1\n"), (Some(42), Some(1)));
+        assert_eq!(parse_reply(""), (None, None));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(50, 4), "██░░  50%");
+        assert_eq!(progress_bar(100, 4), "████ 100%");
+    }
+
     #[test]
     fn test_notification_styles() {
         let info = NotificationStyle::from_name("info");
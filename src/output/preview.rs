@@ -3,8 +3,8 @@
 //! Displays a list of items with a header, count, and action buttons.
 //! Useful for confirming bulk operations, file deletions, etc.
 
+use crate::text;
 use owo_colors::{OwoColorize, Style};
-use unicode_width::UnicodeWidthStr;
 
 /// Border character set for the preview pane
 struct BorderChars {
@@ -156,7 +156,7 @@ pub fn render(items: &[String], config: &PreviewConfig) {
         .max()
         .unwrap_or(0);
 
-    let header_width = UnicodeWidthStr::width(header_text.as_str());
+    let header_width = text::display_width(&header_text);
     let action_button = format!("[{}]", config.action);
     let cancel_button = format!("[{}]", config.cancel_label);
     let footer_width = action_button.len() + cancel_button.len() + 3; // space between buttons
@@ -212,7 +212,7 @@ pub fn render(items: &[String], config: &PreviewConfig) {
         } else {
             (*item).clone()
         };
-        let content_width = UnicodeWidthStr::width(content.as_str());
+        let content_width = text::display_width(&content);
         let total_padding = box_width - content_width;
         let right_padding = total_padding - padding;
 
@@ -230,7 +230,7 @@ pub fn render(items: &[String], config: &PreviewConfig) {
     // Show truncation indicator if needed
     if truncated {
         let more_text = format!("... and {} more", total_items - config.max_items);
-        let more_width = UnicodeWidthStr::width(more_text.as_str());
+        let more_width = text::display_width(&more_text);
         let total_padding = box_width - more_width;
         let right_padding = total_padding - padding;
 
@@ -326,7 +326,7 @@ pub fn render_with_columns(items: &[Vec<String>], columns: &[String], config: &P
         total_items,
         if total_items == 1 { "item" } else { "items" }
     );
-    let header_width = UnicodeWidthStr::width(header_text.as_str());
+    let header_width = text::display_width(&header_text);
 
     let row_content_width: usize =
         col_widths.iter().sum::<usize>() + (columns.len().saturating_sub(1)) * column_sep.len();
@@ -392,7 +392,7 @@ pub fn render_with_columns(items: &[Vec<String>], columns: &[String], config: &P
         })
         .collect::<Vec<_>>()
         .join(column_sep);
-    let col_header_width = UnicodeWidthStr::width(col_header.as_str());
+    let col_header_width = text::display_width(&col_header);
     let col_header_padding = box_width - col_header_width;
     let right_col_pad = col_header_padding - padding;
 
@@ -419,7 +419,7 @@ pub fn render_with_columns(items: &[Vec<String>], columns: &[String], config: &P
             })
             .collect::<Vec<_>>()
             .join(column_sep);
-        let row_width = UnicodeWidthStr::width(row_content.as_str());
+        let row_width = text::display_width(&row_content);
         let row_padding = box_width - row_width;
         let right_row_pad = row_padding - padding;
 
@@ -436,7 +436,7 @@ pub fn render_with_columns(items: &[Vec<String>], columns: &[String], config: &P
     // Truncation indicator
     if truncated {
         let more_text = format!("... and {} more", total_items - config.max_items);
-        let more_width = UnicodeWidthStr::width(more_text.as_str());
+        let more_width = text::display_width(&more_text);
         let total_padding = box_width - more_width;
         let right_padding = total_padding - padding;
 
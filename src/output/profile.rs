@@ -0,0 +1,324 @@
+//! Render diagnostics for the hidden `--profile-render` flag
+//!
+//! Steps run in [`time`] with one of four phases, which also opens a tracing
+//! span when built with the `tracing` feature. The crate's `print!` family
+//! times every line it formats (render) and writes (write), so all commands
+//! report at least those; commands with a sizing step or their own parsing
+//! wrap it too. Phases don't nest: work inside a phase counts towards the
+//! outermost one. When profiling is off the wrapper just calls through, so
+//! instrumented code costs one atomic load. Allocation counts come from
+//! [`CountingAlloc`], which the binary installs as its global allocator and
+//! which only counts once [`enable`] has been called.
+
+use owo_colors::OwoColorize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static SAMPLES: Mutex<Vec<Sample>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static IN_PHASE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// System allocator that counts allocations and bytes requested while
+/// profiling is enabled
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if is_enabled() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if is_enabled() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(new_size.saturating_sub(layout.size()), Ordering::Relaxed);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Stage of a command's output pipeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// Arguments and input data
+    Parse,
+    /// Sizing: column widths, scaling, wrapping
+    Layout,
+    /// Building styled lines or escape sequences
+    Render,
+    /// Writing to the terminal
+    Write,
+}
+
+impl Phase {
+    const ALL: [Phase; 4] = [Phase::Parse, Phase::Layout, Phase::Render, Phase::Write];
+
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Layout => "layout",
+            Phase::Render => "render",
+            Phase::Write => "write",
+        }
+    }
+}
+
+/// Point-in-time reading of the clock and allocation counters
+#[derive(Debug, Clone, Copy)]
+pub struct Snapshot {
+    at: Instant,
+    allocations: usize,
+    bytes: usize,
+}
+
+impl Snapshot {
+    pub fn now() -> Self {
+        Self {
+            at: Instant::now(),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    fn since(&self, start: &Snapshot) -> Sample {
+        Sample {
+            phase: None,
+            elapsed: self.at.duration_since(start.at),
+            allocations: self.allocations.saturating_sub(start.allocations),
+            bytes: self.bytes.saturating_sub(start.bytes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Sample {
+    phase: Option<Phase>,
+    elapsed: Duration,
+    allocations: usize,
+    bytes: usize,
+}
+
+impl Sample {
+    fn add(&mut self, other: &Sample) {
+        self.elapsed += other.elapsed;
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Record the work done since `start` under `phase`
+pub fn record(phase: Phase, start: Snapshot) {
+    if !is_enabled() {
+        return;
+    }
+    let mut sample = Snapshot::now().since(&start);
+    sample.phase = Some(phase);
+    if let Ok(mut samples) = SAMPLES.lock() {
+        samples.push(sample);
+    }
+}
+
+/// Run `f`, recording its time and allocations under `phase` unless it runs
+/// inside another phase
+pub fn time<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    if IN_PHASE.with(|p| p.replace(true)) {
+        return f();
+    }
+    let _outermost = Outermost;
+    let _span = super::trace::enter(phase);
+    if !is_enabled() {
+        return f();
+    }
+    let start = Snapshot::now();
+    let result = f();
+    record(phase, start);
+    result
+}
+
+/// Marks the thread as outside any phase again when dropped, even on panic
+struct Outermost;
+
+impl Drop for Outermost {
+    fn drop(&mut self) {
+        IN_PHASE.with(|p| p.set(false));
+    }
+}
+
+/// Print the per-phase breakdown for everything since `start` to stderr
+pub fn report(command: &str, start: Snapshot) {
+    let total = Snapshot::now().since(&start);
+    let samples = SAMPLES.lock().map(|s| s.clone()).unwrap_or_default();
+    for line in report_lines(command, &samples, total) {
+        eprintln!("{}", line);
+    }
+}
+
+/// Prints the report when dropped, so early returns are still covered
+pub struct ReportGuard {
+    command: String,
+    start: Snapshot,
+}
+
+impl ReportGuard {
+    pub fn new(command: impl Into<String>, start: Snapshot) -> Self {
+        Self {
+            command: command.into(),
+            start,
+        }
+    }
+}
+
+impl Drop for ReportGuard {
+    fn drop(&mut self) {
+        report(&self.command, self.start);
+    }
+}
+
+fn report_lines(command: &str, samples: &[Sample], total: Sample) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{} {}",
+        "profile".bright_cyan().bold(),
+        command.bold()
+    )];
+    let mut accounted = Sample::default();
+
+    for phase in Phase::ALL {
+        let mut sum = Sample::default();
+        let mut seen = false;
+        for sample in samples.iter().filter(|s| s.phase == Some(phase)) {
+            sum.add(sample);
+            seen = true;
+        }
+        if seen {
+            accounted.add(&sum);
+            lines.push(row(phase.name(), &sum));
+        } else {
+            lines.push(format!("  {:<8}{}", phase.name(), "—".dimmed()));
+        }
+    }
+
+    // Whatever the command did outside instrumented phases
+    let other = Sample {
+        phase: None,
+        elapsed: total.elapsed.saturating_sub(accounted.elapsed),
+        allocations: total.allocations.saturating_sub(accounted.allocations),
+        bytes: total.bytes.saturating_sub(accounted.bytes),
+    };
+    lines.push(row("other", &other).dimmed().to_string());
+    lines.push(row("total", &total).bold().to_string());
+    lines
+}
+
+fn row(name: &str, sample: &Sample) -> String {
+    format!(
+        "  {:<8}{:>10}  {:>8} allocs  {:>10}",
+        name,
+        format_duration(sample.elapsed),
+        sample.allocations,
+        format_bytes(sample.bytes)
+    )
+}
+
+fn format_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", d.as_secs_f64())
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*m")
+            .unwrap()
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    fn sample(phase: Phase, ms: u64, allocations: usize) -> Sample {
+        Sample {
+            phase: Some(phase),
+            elapsed: Duration::from_millis(ms),
+            allocations,
+            bytes: allocations * 100,
+        }
+    }
+
+    #[test]
+    fn test_report_sums_phases_and_other() {
+        let samples = [
+            sample(Phase::Parse, 2, 10),
+            sample(Phase::Layout, 1, 5),
+            sample(Phase::Layout, 1, 5),
+            sample(Phase::Write, 3, 0),
+        ];
+        let total = Sample {
+            phase: None,
+            elapsed: Duration::from_millis(10),
+            allocations: 30,
+            bytes: 3000,
+        };
+        let lines: Vec<String> = report_lines("table", &samples, total)
+            .iter()
+            .map(|l| plain(l))
+            .collect();
+
+        assert_eq!(lines[0], "profile table");
+        assert!(lines[2].starts_with("  layout"));
+        assert!(lines[2].contains("2.00ms"));
+        assert!(lines[2].contains("10 allocs"));
+        assert_eq!(lines[3], "  render  —");
+        assert!(lines[5].starts_with("  other"));
+        assert!(lines[5].contains("3.00ms"));
+        assert!(lines[5].contains("10 allocs"));
+        assert!(lines[6].contains("10.00ms"));
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_duration(Duration::from_micros(250)), "250µs");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50s");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
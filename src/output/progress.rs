@@ -1,31 +1,30 @@
-use owo_colors::OwoColorize;
+use crate::animation::easing::Easing;
+use crate::animation::engine::Frames;
+use crate::animation::policy;
+use crate::output::inline::{self, Span};
+use crate::render::{progress, Doc};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::ExecutableCommand;
 use std::io::{self, IsTerminal, Write};
-use std::thread;
 use std::time::Duration;
 
 pub fn render(percent: u8, style: &str, from: Option<&str>, to: Option<&str>) {
-    let percent = percent.min(100);
+    println!("{}", doc(percent, style, from, to).to_ansi());
+}
 
-    // If custom colors provided, use custom gradient
+/// The bar as a [`Doc`], for printing or turning into HTML or SVG; `from`
+/// or `to` fade the filled part between two colors
+pub fn doc(percent: u8, style: &str, from: Option<&str>, to: Option<&str>) -> Doc {
     if from.is_some() || to.is_some() {
-        let start = from.map(parse_color).unwrap_or((63, 185, 80));
-        let end = to.map(parse_color).unwrap_or((88, 166, 255));
-        render_custom_gradient(percent, start, end);
-        return;
-    }
-
-    match style {
-        "blocks" => render_blocks(percent),
-        "gradient" => render_gradient(percent),
-        "modern" => render_modern(percent),
-        "classic" => render_classic(percent),
-        "thin" => render_thin(percent),
-        "animated" => render_animated(percent),
-        _ => render_gradient(percent),
+        let (start, end) = progress::MODERN;
+        let start = from.map(parse_color).unwrap_or(start);
+        let end = to.map(parse_color).unwrap_or(end);
+        return progress::fade(percent, start, end);
     }
+    progress::bar(percent, style)
 }
 
-fn parse_color(color: &str) -> (u8, u8, u8) {
+pub fn parse_color(color: &str) -> (u8, u8, u8) {
     // Handle hex colors
     if color.starts_with('#') {
         let hex = color.trim_start_matches('#');
@@ -48,133 +47,70 @@ fn parse_color(color: &str) -> (u8, u8, u8) {
         "orange" => (255, 149, 0),
         "pink" => (255, 121, 198),
         "white" => (255, 255, 255),
+        "grey" | "gray" => (139, 148, 158),
         _ => (255, 255, 255),
     }
 }
 
-fn render_custom_gradient(percent: u8, start: (u8, u8, u8), end: (u8, u8, u8)) {
-    let width = 30;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-
-    for i in 0..filled {
-        let t = i as f32 / width as f32;
-        let r = (start.0 as f32 + t * (end.0 as f32 - start.0 as f32)) as u8;
-        let g = (start.1 as f32 + t * (end.1 as f32 - start.1 as f32)) as u8;
-        let b = (start.2 as f32 + t * (end.2 as f32 - start.2 as f32)) as u8;
-        bar.push_str(&format!("\x1b[38;2;{};{};{}m█\x1b[0m", r, g, b));
-    }
-    for _ in 0..empty {
-        bar.push_str("\x1b[38;2;72;79;88m░\x1b[0m");
-    }
-    let percent_str = format!(
-        "\x1b[1m\x1b[38;2;{};{};{}m{}%\x1b[0m",
-        end.0, end.1, end.2, percent
-    );
-    println!("{} {}", bar, percent_str);
+/// Print the bar as a single prompt-safe line (no newline, no cursor moves)
+pub fn render_inline(percent: u8, style: &str, from: Option<&str>, to: Option<&str>, shell: &str) {
+    let shell = inline::shell_or_exit(shell);
+    inline::print(&inline_spans(percent, style, from, to), shell);
 }
 
-fn render_blocks(percent: u8) {
-    let width = 20;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-    for _ in 0..filled {
-        bar.push('█');
-    }
-    for _ in 0..empty {
-        bar.push('░');
-    }
-    let percent_str = format!("{}%", percent);
-    println!("{} {}", bar.cyan(), percent_str.bright_cyan().bold());
-}
+/// Compact 10-cell bar followed by the percentage
+pub fn inline_spans(percent: u8, style: &str, from: Option<&str>, to: Option<&str>) -> Vec<Span> {
+    const WIDTH: usize = 10;
+    const EMPTY: (u8, u8, u8) = (72, 79, 88);
+    const CYAN: (u8, u8, u8) = (86, 214, 214);
 
-fn render_gradient(percent: u8) {
-    let width = 20;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-    for i in 0..filled {
-        let progress = (i as f32 / width as f32) * 100.0;
-        let char = if progress < 33.0 {
-            '█'.red().to_string()
-        } else if progress < 66.0 {
-            '█'.yellow().to_string()
-        } else {
-            '█'.green().to_string()
-        };
-        bar.push_str(&char);
-    }
-    for _ in 0..empty {
-        bar.push_str(&"░".bright_black().to_string());
-    }
-    let percent_display = format!("{}%", percent);
-    let percent_colored = if percent < 33 {
-        percent_display.red().to_string()
-    } else if percent < 66 {
-        percent_display.yellow().to_string()
+    let percent = percent.min(100);
+    let filled = (WIDTH * percent as usize) / 100;
+    let (full, empty) = match style {
+        "classic" => ("=", "-"),
+        "thin" => ("━", "─"),
+        _ => ("█", "░"),
+    };
+    let gradient = if from.is_some() || to.is_some() {
+        Some((
+            from.map(parse_color).unwrap_or((63, 185, 80)),
+            to.map(parse_color).unwrap_or((88, 166, 255)),
+        ))
+    } else if style == "modern" {
+        Some(((63, 185, 80), (88, 166, 255)))
     } else {
-        percent_display.green().to_string()
+        None
     };
-    println!("{} {}", bar, percent_colored.bold());
-}
-
-fn render_classic(percent: u8) {
-    let width = 20;
-    let filled = (width * percent as usize) / 100;
-    let empty = width.saturating_sub(filled + 1);
-    let mut bar = String::new();
-    bar.push('[');
-    for _ in 0..filled {
-        bar.push_str(&"=".cyan().to_string());
-    }
-    if filled < width {
-        bar.push_str(&">".bright_cyan().to_string());
-    }
-    for _ in 0..empty {
-        bar.push(' ');
-    }
-    bar.push(']');
-    let percent_str = format!("{}%", percent);
-    println!("{} {}", bar, percent_str.bright_cyan().bold());
-}
-
-fn render_thin(percent: u8) {
-    let width = 20;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-    for _ in 0..filled {
-        bar.push_str(&"━".cyan().to_string());
-    }
-    for _ in 0..empty {
-        bar.push_str(&"━".bright_black().to_string());
-    }
-    let percent_str = format!("{}%", percent);
-    println!("{} {}", bar, percent_str.bright_cyan().bold());
-}
 
-fn render_animated(percent: u8) {
-    let width = 30;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
+    let mut spans = Vec::new();
     for i in 0..filled {
-        let char = match i % 4 {
-            0 => '█',
-            1 => '▓',
-            2 => '▒',
-            3 => '░',
-            _ => '█',
+        let color = match (gradient, style) {
+            (Some((start, end)), _) => lerp(start, end, i as f32 / WIDTH as f32),
+            (None, "gradient") => inline::level_color(i as f64 * 100.0 / WIDTH as f64),
+            _ => CYAN,
         };
-        bar.push_str(&char.cyan().to_string());
+        inline::push(&mut spans, Span::colored(full, color));
     }
-    for _ in 0..empty {
-        bar.push(' ');
+    for _ in filled..WIDTH {
+        inline::push(&mut spans, Span::colored(empty, EMPTY));
     }
-    let percent_str = format!("{}%", percent);
-    println!("{} {}", bar, percent_str.bright_cyan().bold());
+    let label_color = match (gradient, style) {
+        (Some((_, end)), _) => end,
+        (None, "gradient") => inline::level_color(percent as f64),
+        _ => CYAN,
+    };
+    spans.push(Span::plain(" "));
+    spans.push(Span::colored(format!("{}%", percent), label_color).bold());
+    spans
+}
+
+fn lerp(start: (u8, u8, u8), end: (u8, u8, u8), t: f32) -> (u8, u8, u8) {
+    let mix = |a: u8, b: u8| (a as f32 + t * (b as f32 - a as f32)) as u8;
+    (
+        mix(start.0, end.0),
+        mix(start.1, end.1),
+        mix(start.2, end.2),
+    )
 }
 
 pub fn render_animated_progress(
@@ -183,102 +119,25 @@ pub fn render_animated_progress(
     from: Option<&str>,
     to: Option<&str>,
     duration_ms: u64,
+    easing: Easing,
 ) {
     let target = target.min(100);
 
     // If not a TTY (piped/captured), just show final result
-    if !io::stdout().is_terminal() {
+    if !io::stdout().is_terminal() || !policy::enabled() {
         render(target, style, from, to);
         return;
     }
 
-    let steps = 30;
-    let step_delay = Duration::from_millis(duration_ms / steps as u64);
-
-    for i in 0..=steps {
-        let current = (i * target as u32 / steps) as u8;
+    for progress in Frames::new(30, Duration::from_millis(duration_ms), easing) {
+        let current = (target as f64 * progress).round().clamp(0.0, 100.0) as u8;
 
-        // Build the progress bar string
-        let bar = if from.is_some() || to.is_some() {
-            let start = from.map(parse_color).unwrap_or((63, 185, 80));
-            let end = to.map(parse_color).unwrap_or((88, 166, 255));
-            build_custom_gradient_bar(current, start, end)
-        } else {
-            match style {
-                "modern" => build_modern_bar(current),
-                _ => build_modern_bar(current),
-            }
-        };
-
-        // Use \r to return to start of line for in-place updates
-        print!("\r{}", bar);
-        io::stdout().flush().unwrap();
-        thread::sleep(step_delay);
+        // Use \r to return to start of line for in-place updates; the
+        // label can get shorter when the curve swings back
+        print!("\r{}", doc(current, style, from, to).to_ansi());
+        let mut stdout = io::stdout();
+        stdout.execute(Clear(ClearType::UntilNewLine)).ok();
+        stdout.flush().unwrap();
     }
     println!(); // Final newline when done
 }
-
-fn build_custom_gradient_bar(percent: u8, start: (u8, u8, u8), end: (u8, u8, u8)) -> String {
-    let width = 30;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-
-    for i in 0..filled {
-        let t = i as f32 / width as f32;
-        let r = (start.0 as f32 + t * (end.0 as f32 - start.0 as f32)) as u8;
-        let g = (start.1 as f32 + t * (end.1 as f32 - start.1 as f32)) as u8;
-        let b = (start.2 as f32 + t * (end.2 as f32 - start.2 as f32)) as u8;
-        bar.push_str(&format!("\x1b[38;2;{};{};{}m█\x1b[0m", r, g, b));
-    }
-    for _ in 0..empty {
-        bar.push_str("\x1b[38;2;72;79;88m░\x1b[0m");
-    }
-    let percent_str = format!(
-        "\x1b[1m\x1b[38;2;{};{};{}m{:>3}%\x1b[0m",
-        end.0, end.1, end.2, percent
-    );
-    format!("{} {}", bar, percent_str)
-}
-
-fn build_modern_bar(percent: u8) -> String {
-    let width = 30;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-
-    for i in 0..filled {
-        let t = i as f32 / width as f32;
-        let r = (63.0 + t * (88.0 - 63.0)) as u8;
-        let g = (185.0 + t * (166.0 - 185.0)) as u8;
-        let b = (80.0 + t * (255.0 - 80.0)) as u8;
-        bar.push_str(&format!("\x1b[38;2;{};{};{}m█\x1b[0m", r, g, b));
-    }
-    for _ in 0..empty {
-        bar.push_str("\x1b[38;2;72;79;88m░\x1b[0m");
-    }
-    let percent_str = format!("\x1b[1m\x1b[38;2;88;166;255m{:>3}%\x1b[0m", percent);
-    format!("{} {}", bar, percent_str)
-}
-
-fn render_modern(percent: u8) {
-    let width = 30;
-    let filled = (width * percent as usize) / 100;
-    let empty = width - filled;
-    let mut bar = String::new();
-
-    // Smooth RGB gradient: green (#3fb950) → cyan (#58a6ff)
-    // Start: (63, 185, 80)  End: (88, 166, 255)
-    for i in 0..filled {
-        let t = i as f32 / width as f32;
-        let r = (63.0 + t * (88.0 - 63.0)) as u8;
-        let g = (185.0 + t * (166.0 - 185.0)) as u8;
-        let b = (80.0 + t * (255.0 - 80.0)) as u8;
-        bar.push_str(&format!("\x1b[38;2;{};{};{}m█\x1b[0m", r, g, b));
-    }
-    for _ in 0..empty {
-        bar.push_str("\x1b[38;2;72;79;88m░\x1b[0m");
-    }
-    let percent_str = format!("\x1b[1m\x1b[38;2;88;166;255m{}%\x1b[0m", percent);
-    println!("{} {}", bar, percent_str);
-}
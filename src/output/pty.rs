@@ -0,0 +1,92 @@
+//! Running commands on a pseudo-terminal
+//!
+//! A command on a pty sees a real terminal: it gets colors, a size and a
+//! controlling terminal for job control, and its output arrives exactly as
+//! a terminal would receive it. `record` and the studio's command runner
+//! start their children this way.
+
+use std::fs::File;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+
+/// Start `cmd` on a new pseudo-terminal of `size` (columns, rows); returns
+/// the master side, which reads what the child prints, and the child
+pub fn spawn(mut cmd: Command, (width, height): (u16, u16)) -> io::Result<(File, Child)> {
+    let (mut master, mut slave) = (0, 0);
+    let size = libc::winsize {
+        ws_row: height,
+        ws_col: width,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: openpty writes two descriptors we take ownership of below
+    let opened = unsafe {
+        libc::openpty(
+            &mut master,
+            &mut slave,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &size as *const _ as *mut _,
+        )
+    };
+    if opened != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both descriptors are open and owned by nobody else
+    let (master, slave) = unsafe { (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave)) };
+    // The child mustn't inherit the master, or its exit never reaches us
+    // SAFETY: F_SETFD on a descriptor we own
+    unsafe { libc::fcntl(master.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) };
+
+    cmd.stdin(Stdio::from(slave.try_clone()?))
+        .stdout(Stdio::from(slave.try_clone()?))
+        .stderr(Stdio::from(slave));
+    // SAFETY: only async-signal-safe calls between fork and exec
+    unsafe {
+        cmd.pre_exec(|| {
+            // Own session with the pty as controlling terminal, so job
+            // control and Ctrl+C work inside the child
+            if libc::setsid() < 0 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let child = cmd.spawn()?;
+    Ok((File::from(master), child))
+}
+
+/// Decode the complete UTF-8 prefix of `pending`, keeping a character split
+/// across reads for next time
+pub fn take_utf8(pending: &mut Vec<u8>) -> String {
+    let valid = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        // Invalid bytes become U+FFFD; only an unfinished tail waits
+        Err(e) if e.error_len().is_some() => pending.len(),
+        Err(e) => e.valid_up_to(),
+    };
+    let rest = pending.split_off(valid);
+    let text = String::from_utf8_lossy(pending).into_owned();
+    *pending = rest;
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_utf8_keeps_split_characters() {
+        let bytes = "é!".as_bytes();
+        let mut pending = bytes[..1].to_vec();
+        assert_eq!(take_utf8(&mut pending), "");
+        pending.extend_from_slice(&bytes[1..]);
+        assert_eq!(take_utf8(&mut pending), "é!");
+        assert!(pending.is_empty());
+
+        let mut pending = vec![b'a', 0xff, b'b'];
+        assert_eq!(take_utf8(&mut pending), "a\u{fffd}b");
+    }
+}
@@ -0,0 +1,176 @@
+//! jq-style queries over JSON input
+//!
+//! Commands that read JSON accept `--query` to reshape the document before
+//! rendering, e.g. `.items[] | {label: .name, value: .count}`. Queries run
+//! on jaq with its standard library, so most jq filters work unchanged.
+
+use jaq_core::load::{self, Arena, File, Loader};
+use jaq_core::{compile, Compiler, Ctx, RcIter};
+use jaq_json::Val;
+use serde_json::Value;
+
+/// Run `query` on `input`
+///
+/// A query producing exactly one value returns it as is; any other number of
+/// results (`.items[]`, `empty`, ...) is collected into an array so it can be
+/// rendered as rows.
+pub fn apply(input: Value, query: &str) -> Result<Value, String> {
+    let program = File {
+        code: query,
+        path: (),
+    };
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let modules = loader
+        .load(&arena, program)
+        .map_err(|errs| load_error(query, errs))?;
+    let filter = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(compile_error)?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let mut results = filter
+        .run((Ctx::new([], &inputs), Val::from(input)))
+        .map(|out| {
+            out.map(Value::from)
+                .map_err(|e| format!("Query failed: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(if results.len() == 1 {
+        results.remove(0)
+    } else {
+        Value::Array(results)
+    })
+}
+
+/// Parse `text` as JSON and apply `query` if one is given
+pub fn parse(text: &str, query: Option<&str>) -> Result<Value, String> {
+    let value: Value =
+        serde_json::from_str(text.trim()).map_err(|e| format!("Invalid JSON: {}", e))?;
+    match query {
+        Some(query) => apply(value, query),
+        None => Ok(value),
+    }
+}
+
+/// Column of `part` within `query`, counting from 1
+fn column(query: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(query.as_ptr() as usize);
+    query[..offset.min(query.len())].chars().count() + 1
+}
+
+fn load_error(query: &str, errs: load::Errors<&str, ()>) -> String {
+    let mut messages = Vec::new();
+    for (_, err) in errs {
+        match err {
+            load::Error::Io(errs) => {
+                messages.extend(errs.into_iter().map(|(_, e)| e));
+            }
+            load::Error::Lex(errs) => {
+                messages.extend(
+                    errs.into_iter()
+                        .map(|(expect, at): load::lex::Error<&str>| {
+                            format!(
+                                "expected {} at column {}",
+                                expect.as_str(),
+                                column(query, at)
+                            )
+                        }),
+                );
+            }
+            load::Error::Parse(errs) => {
+                messages.extend(
+                    errs.into_iter()
+                        .map(|(expect, at): load::parse::Error<&str>| {
+                            if at.is_empty() {
+                                format!("expected {} at end of query", expect.as_str())
+                            } else {
+                                format!(
+                                    "expected {} at column {}, found '{}'",
+                                    expect.as_str(),
+                                    column(query, at),
+                                    at
+                                )
+                            }
+                        }),
+                );
+            }
+        }
+    }
+    format!("Invalid query: {}", messages.join("; "))
+}
+
+fn compile_error(errs: compile::Errors<&str, ()>) -> String {
+    let messages: Vec<String> = errs
+        .into_iter()
+        .flat_map(|(_, errs)| errs)
+        .map(|(name, undefined)| match undefined {
+            compile::Undefined::Filter(arity) => format!("unknown function {}/{}", name, arity),
+            compile::Undefined::Var => format!("unknown variable {}", name),
+            other => format!("undefined {:?} {}", other, name),
+        })
+        .collect();
+    format!("Invalid query: {}", messages.join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_single_result_is_returned_as_is() {
+        let input = json!({"items": [1, 2, 3]});
+        assert_eq!(apply(input, ".items").unwrap(), json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_stream_is_collected_into_array() {
+        let input = json!({"items": [
+            {"name": "a", "count": 1},
+            {"name": "b", "count": 2}
+        ]});
+        let out = apply(input, ".items[] | {label: .name, value: .count}").unwrap();
+        assert_eq!(
+            out,
+            json!([{"label": "a", "value": 1}, {"label": "b", "value": 2}])
+        );
+        assert_eq!(apply(json!(1), "empty").unwrap(), json!([]));
+    }
+
+    #[test]
+    fn test_standard_library_is_available() {
+        let input = json!([3, 1, 2]);
+        assert_eq!(apply(input.clone(), "sort").unwrap(), json!([1, 2, 3]));
+        assert_eq!(
+            apply(input, "map(select(. > 1)) | length").unwrap(),
+            json!(2)
+        );
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let err = apply(json!({}), ".items[").unwrap_err();
+        assert!(err.starts_with("Invalid query: expected"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_function() {
+        let err = apply(json!({}), "frobnicate").unwrap_err();
+        assert_eq!(err, "Invalid query: unknown function frobnicate/0");
+    }
+
+    #[test]
+    fn test_runtime_error() {
+        let err = apply(json!(1), ".[0]").unwrap_err();
+        assert!(err.starts_with("Query failed:"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_with_and_without_query() {
+        assert_eq!(parse(" [1] \n", None).unwrap(), json!([1]));
+        assert_eq!(parse("{\"a\": 2}", Some(".a")).unwrap(), json!(2));
+        assert!(parse("{", None).unwrap_err().starts_with("Invalid JSON"));
+    }
+}
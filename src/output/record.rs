@@ -1,19 +1,302 @@
+//! Terminal session recording and playback
+//!
+//! Sessions run on a pseudo-terminal and are saved as asciicast v2 (`.cast`),
+//! the format asciinema.org and agg read: a JSON header line, then one
+//! `[time, code, data]` array per event. The earlier single-JSON format is
+//! still written with `--format legacy` and read by `play` and `export`.
+
+use crate::output::{atomic, color, input, pty};
+use crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind};
+use crossterm::terminal;
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::io::{self, Write};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::fs::{self, File};
+use std::io::{self, IsTerminal, Read, Write};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Environment variable pointing `record mark` at the running recorder's marks file
+const MARKS_ENV: &str = "TERMGFX_RECORD_MARKS";
+
+/// Names accepted by [`RecordFormat::from_name`]
+pub const FORMATS: [&str; 2] = ["cast", "legacy"];
+
+/// File format written by `record start`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordFormat {
+    /// asciicast v2
+    Cast,
+    /// termgfx's original single JSON document
+    Legacy,
+}
+
+impl RecordFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cast" | "asciicast" => Some(RecordFormat::Cast),
+            "legacy" | "json" => Some(RecordFormat::Legacy),
+            _ => None,
+        }
+    }
+}
+
+/// Part of a recording to export, in seconds from its start
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimeRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+impl TimeRange {
+    /// From `--from` and `--to` values like `45`, `00:12` or `1:02:03.5`
+    pub fn parse(from: Option<&str>, to: Option<&str>) -> Result<Self, String> {
+        let range = TimeRange {
+            from: from.map(parse_timestamp).transpose()?,
+            to: to.map(parse_timestamp).transpose()?,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if to <= from {
+                return Err("--to must be after --from".to_string());
+            }
+        }
+        Ok(range)
+    }
+}
+
+/// Seconds in `[[hh:]mm:]ss[.fff]`
+fn parse_timestamp(time: &str) -> Result<f64, String> {
+    let invalid = || format!("Invalid time '{}' (use seconds, mm:ss or hh:mm:ss)", time);
+    let parts: Vec<&str> = time.trim().split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+    let mut seconds = 0.0;
+    for (i, part) in parts.iter().enumerate() {
+        let value: f64 = part.parse().map_err(|_| invalid())?;
+        let last = i == parts.len() - 1;
+        // Only the seconds may be fractional, and only leading fields may pass 59
+        if !value.is_finite() || value < 0.0 || (!last && value.fract() != 0.0) {
+            return Err(invalid());
+        }
+        if i > 0 && value >= 60.0 {
+            return Err(invalid());
+        }
+        seconds = seconds * 60.0 + value;
+    }
+    Ok(seconds)
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Recording {
     version: u8,
     width: u16,
     height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    /// Unix time the recording started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+    /// Longest pause kept on playback, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idle_time_limit: Option<f64>,
     events: Vec<Event>,
 }
 
+/// First line of a `.cast` file; `author` is ours, other readers skip it
+#[derive(Serialize, Deserialize, Debug)]
+struct CastHeader {
+    version: u8,
+    width: u16,
+    height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idle_time_limit: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+}
+
+impl Recording {
+    /// Read a `.cast` or legacy recording, from stdin for `-`
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = input::read_text(path).map_err(|e| {
+            format!(
+                "Failed to read recording {}: {}",
+                input::display_name(path),
+                e
+            )
+        })?;
+        Self::parse(&content)
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        if let Ok(recording) = serde_json::from_str::<Recording>(content) {
+            return Ok(recording);
+        }
+        Self::from_cast(content)
+    }
+
+    fn from_cast(content: &str) -> Result<Self, String> {
+        let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+        let header: CastHeader = lines
+            .next()
+            .and_then(|line| serde_json::from_str(line).ok())
+            .ok_or("Not a termgfx or asciicast v2 recording")?;
+        if header.version != 2 {
+            return Err(format!(
+                "Unsupported asciicast version {} (only v2 is supported)",
+                header.version
+            ));
+        }
+        let mut events = Vec::new();
+        for (n, line) in lines.enumerate() {
+            let (time, code, data): (f64, String, String) = serde_json::from_str(line)
+                .map_err(|e| format!("Bad event on line {}: {}", n + 2, e))?;
+            // Input ("i") and resize ("r") events aren't replayed
+            if code == "o" || code == "m" {
+                events.push(Event {
+                    time,
+                    event_type: code,
+                    data,
+                });
+            }
+        }
+        Ok(Recording {
+            version: 2,
+            width: header.width,
+            height: header.height,
+            title: header.title,
+            author: header.author,
+            timestamp: header.timestamp,
+            idle_time_limit: header.idle_time_limit,
+            events,
+        })
+    }
+
+    /// Columns and rows of the terminal it was recorded in
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Output and when it was written, with pauses longer than `idle_limit`
+    /// seconds (else the recording's own limit) shortened to it
+    pub fn output(&self, idle_limit: Option<f64>) -> Vec<(f64, String)> {
+        let mut events = self.events.clone();
+        if let Some(limit) = idle_limit.or(self.idle_time_limit) {
+            compress_idle(&mut events, limit.max(0.0));
+        }
+        events
+            .into_iter()
+            .filter(|e| !e.is_marker())
+            .map(|e| (e.time, e.data))
+            .collect()
+    }
+
+    /// Keep only what happens within `range`, shifted to start at zero
+    ///
+    /// Output from before the cut is replayed at once, so the first frame
+    /// shows the screen as it was at `from`; the last one holds until `to`.
+    pub fn clip(&mut self, range: TimeRange) -> Result<(), String> {
+        let end = self.events.last().map_or(0.0, |e| e.time);
+        let from = range.from.unwrap_or(0.0);
+        if from > end {
+            return Err(format!(
+                "--from {:.1}s is past the end of the recording ({:.1}s)",
+                from, end
+            ));
+        }
+        let to = range.to.unwrap_or(f64::INFINITY);
+        let mut before = String::new();
+        let mut kept = Vec::new();
+        for event in self.events.drain(..) {
+            if event.time < from {
+                if !event.is_marker() {
+                    before.push_str(&event.data);
+                }
+            } else if event.time <= to {
+                kept.push(Event {
+                    time: event.time - from,
+                    ..event
+                });
+            }
+        }
+        if !before.is_empty() {
+            kept.insert(0, Event::new(0.0, before));
+        }
+        if to < end {
+            kept.push(Event::new(to - from, String::new()));
+        }
+        self.events = kept;
+        Ok(())
+    }
+
+    /// Snap events to `fps` frames, joining output that lands in the same one
+    pub fn resample(&mut self, fps: u32) {
+        let fps = fps as f64;
+        let mut events: Vec<Event> = Vec::with_capacity(self.events.len());
+        for mut event in self.events.drain(..) {
+            // Output shows on the first frame at or after it was written
+            let frame = (event.time * fps - 1e-9).ceil();
+            event.time = if frame > 0.0 { frame / fps } else { 0.0 };
+            match events.last_mut() {
+                Some(last)
+                    if !last.is_marker() && !event.is_marker() && last.time == event.time =>
+                {
+                    last.data.push_str(&event.data)
+                }
+                _ => events.push(event),
+            }
+        }
+        self.events = events;
+    }
+
+    /// The recording as asciicast v2
+    fn to_cast(&self) -> String {
+        let header = CastHeader {
+            version: 2,
+            width: self.width,
+            height: self.height,
+            timestamp: self.timestamp,
+            idle_time_limit: self.idle_time_limit,
+            title: self.title.clone(),
+            author: self.author.clone(),
+        };
+        let mut out = serde_json::to_string(&header).unwrap_or_default();
+        out.push('\n');
+        for event in &self.events {
+            // Microsecond precision, like asciinema
+            let time = (event.time * 1e6).round() / 1e6;
+            let line = serde_json::to_string(&(time, &event.event_type, &event.data));
+            out.push_str(&line.unwrap_or_default());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The recording in the legacy single-document format
+    fn to_legacy(&self) -> String {
+        let legacy = Recording {
+            version: 1,
+            title: self.title.clone(),
+            author: self.author.clone(),
+            events: self.events.clone(),
+            ..*self
+        };
+        serde_json::to_string_pretty(&legacy).unwrap_or_default()
+    }
+}
+
+/// Output (`o`) or chapter marker (`m`), as in the asciicast format
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Event {
     time: f64,
@@ -29,178 +312,451 @@ impl Event {
             data,
         }
     }
+
+    fn marker(time: f64, label: String) -> Self {
+        Self {
+            time,
+            event_type: "m".to_string(),
+            data: label,
+        }
+    }
+
+    fn is_marker(&self) -> bool {
+        self.event_type == "m"
+    }
 }
 
-pub fn start(output: &str) {
-    let output_path = PathBuf::from(output);
+/// Options for `record start`
+pub struct RecordOptions<'a> {
+    pub title: Option<&'a str>,
+    pub author: Option<&'a str>,
+    /// Insert a chapter marker after this many seconds without output
+    pub idle_marker: Option<f64>,
+    /// Record this command instead of an interactive shell
+    pub command: Option<&'a str>,
+    pub format: RecordFormat,
+}
 
-    println!("🔴 Recording started...");
-    println!("Output: {}", output_path.display());
-    println!("\nPress Ctrl+C to stop recording\n");
+pub fn start(output: &str, options: &RecordOptions) {
+    let output_path = PathBuf::from(output);
+    let marks_path = std::env::temp_dir().join(format!("termgfx-marks-{}", std::process::id()));
+    fs::write(&marks_path, "").ok();
 
-    // Get terminal size
     let (width, height) = get_terminal_size();
+    let (mut master, mut child) = match spawn_pty(options.command, (width, height), &marks_path) {
+        Ok(pty) => pty,
+        Err(e) => {
+            eprintln!("Error: Failed to start the recorded shell: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if options.command.is_none() {
+        eprintln!("🔴 Recording started...");
+        eprintln!("Output: {}", output_path.display());
+        eprintln!("Press Ctrl+D or type exit to stop recording");
+        eprintln!("Add a chapter marker with: termgfx record mark \"label\"\n");
+    }
 
     let mut recording = Recording {
-        version: 1,
+        version: 2,
         width,
         height,
+        title: options.title.map(String::from),
+        author: options.author.map(String::from),
+        timestamp: Some(epoch_seconds() as u64),
+        idle_time_limit: None,
         events: Vec::new(),
     };
-
     let start_time = Instant::now();
+    let start_epoch = epoch_seconds();
 
-    // Use script command to record terminal session
-    #[cfg(target_os = "macos")]
-    let mut child = Command::new("script")
-        .arg("-q")
-        .arg("/dev/null")
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .expect("Failed to start script command");
-
-    #[cfg(target_os = "linux")]
-    let mut child = Command::new("script")
-        .arg("-q")
-        .arg("-c")
-        .arg("bash")
-        .arg("/dev/null")
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .expect("Failed to start script command");
-
-    if let Some(stdout) = child.stdout.take() {
-        use std::io::BufRead;
-        let reader = io::BufReader::new(stdout);
-
-        for line in reader.lines() {
-            match line {
-                Ok(data) => {
-                    let elapsed = start_time.elapsed().as_secs_f64();
-                    print!("{}", data);
-                    io::stdout().flush().unwrap();
-                    recording.events.push(Event::new(elapsed, data + "\n"));
-                }
-                Err(_) => break,
+    // Keys go straight to the shell; the pty does the line editing
+    let raw = io::stdin().is_terminal();
+    if raw {
+        terminal::enable_raw_mode().ok();
+    }
+    if let Ok(mut input) = master.try_clone() {
+        thread::spawn(move || io::copy(&mut io::stdin(), &mut input));
+    }
+
+    let mut buffer = [0u8; 8192];
+    let mut pending = Vec::new();
+    let mut last_output = 0.0;
+    let mut idle_chapters = 0;
+    let mut stdout = io::stdout();
+    loop {
+        let n = match master.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // EIO once the shell and everything it started have exited
+            Err(_) => break,
+        };
+        stdout.write_all(&buffer[..n]).ok();
+        stdout.flush().ok();
+        pending.extend_from_slice(&buffer[..n]);
+        let data = pty::take_utf8(&mut pending);
+        if data.is_empty() {
+            continue;
+        }
+        let elapsed = start_time.elapsed().as_secs_f64();
+        if let Some(idle) = options.idle_marker {
+            if idle_gap(&recording.events, last_output, elapsed, idle) {
+                idle_chapters += 1;
+                let label = format!("Chapter {}", idle_chapters);
+                recording.events.push(Event::marker(elapsed, label));
             }
         }
+        recording.events.push(Event::new(elapsed, data));
+        last_output = elapsed;
     }
-
     child.wait().ok();
+    if raw {
+        terminal::disable_raw_mode().ok();
+    }
+
+    // Merge markers added with `record mark` from inside the session
+    if let Ok(marks) = fs::read_to_string(&marks_path) {
+        recording.events.extend(parse_marks(&marks, start_epoch));
+        recording.events.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+    fs::remove_file(&marks_path).ok();
+
+    let contents = match options.format {
+        RecordFormat::Cast => recording.to_cast(),
+        RecordFormat::Legacy => recording.to_legacy(),
+    };
+    if let Err(e) = atomic::write(&output_path, contents) {
+        eprintln!("Error: Failed to write recording file: {}", e);
+        std::process::exit(1);
+    }
+
+    eprintln!("\n✅ Recording saved to {}", output_path.display());
+    eprintln!("Events captured: {}", recording.events.len());
+    let markers = marker_indices(&recording.events).len();
+    if markers > 0 {
+        eprintln!("Chapter markers: {}", markers);
+    }
+}
+
+/// Start `command` (or the user's shell) on a new pseudo-terminal of `size`;
+/// returns the master side and the child
+fn spawn_pty(
+    command: Option<&str>,
+    size: (u16, u16),
+    marks_path: &Path,
+) -> io::Result<(File, Child)> {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let mut cmd = Command::new(&shell);
+    if let Some(command) = command {
+        cmd.arg("-c").arg(command);
+    }
+    cmd.env(MARKS_ENV, marks_path);
+    pty::spawn(cmd, size)
+}
+
+/// `record mark`: add a chapter marker to the recording running in this shell
+pub fn mark(label: &str) {
+    let Some(path) = std::env::var_os(MARKS_ENV) else {
+        eprintln!(
+            "Error: Not inside a termgfx recording ({} is not set)",
+            MARKS_ENV
+        );
+        std::process::exit(1);
+    };
+    let line = format!(
+        "{}\t{}\n",
+        epoch_seconds(),
+        label.replace(['\n', '\t'], " ")
+    );
+    let written = fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+    if let Err(e) = written {
+        eprintln!("Error: Failed to add marker: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn epoch_seconds() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// Marker events from `epoch<TAB>label` lines, timed relative to `start_epoch`
+fn parse_marks(content: &str, start_epoch: f64) -> Vec<Event> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (epoch, label) = line.split_once('\t')?;
+            let time = (epoch.parse::<f64>().ok()? - start_epoch).max(0.0);
+            Some(Event::marker(time, label.to_string()))
+        })
+        .collect()
+}
+
+/// Whether output at `now` follows a quiet spell long enough to start a chapter
+fn idle_gap(events: &[Event], last_output: f64, now: f64, idle: f64) -> bool {
+    !events.is_empty() && now - last_output >= idle
+}
+
+fn marker_indices(events: &[Event]) -> Vec<usize> {
+    events
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_marker())
+        .map(|(i, _)| i)
+        .collect()
+}
 
-    // Save recording
-    let json = serde_json::to_string_pretty(&recording).expect("Failed to serialize recording");
-    fs::write(&output_path, json).expect("Failed to write recording file");
+/// First marker at or after `next`, the index of the next event to play
+fn next_marker(markers: &[usize], next: usize) -> Option<usize> {
+    markers.iter().copied().find(|&m| m >= next)
+}
 
-    println!("\n\n✅ Recording saved to {}", output_path.display());
-    println!("Events captured: {}", recording.events.len());
+/// Start of the chapter before the one being played, or the beginning
+fn previous_marker(markers: &[usize], next: usize) -> usize {
+    let played: Vec<usize> = markers.iter().copied().filter(|&m| m < next).collect();
+    if played.len() >= 2 {
+        played[played.len() - 2]
+    } else {
+        0
+    }
 }
 
-pub fn play(input: &str, speed: f64) {
-    let content = fs::read_to_string(input).expect("Failed to read recording file");
-    let recording: Recording = serde_json::from_str(&content).expect("Failed to parse recording");
+/// Marker index for `--chapter`: a 1-based number or a label
+fn find_chapter(events: &[Event], chapter: &str) -> Option<usize> {
+    let markers = marker_indices(events);
+    if let Ok(n) = chapter.parse::<usize>() {
+        return n.checked_sub(1).and_then(|i| markers.get(i).copied());
+    }
+    markers
+        .into_iter()
+        .find(|&i| events[i].data.eq_ignore_ascii_case(chapter))
+}
+
+/// Player key actions
+enum Nav {
+    Next,
+    Previous,
+    Pause,
+    Quit,
+}
+
+/// Shift events so no pause lasts longer than `limit` seconds
+fn compress_idle(events: &mut [Event], limit: f64) {
+    let (mut previous, mut shift) = (0.0, 0.0);
+    for event in events {
+        let gap = event.time - previous;
+        previous = event.time;
+        if gap > limit {
+            shift += gap - limit;
+        }
+        event.time -= shift;
+    }
+}
+
+/// Output with line endings for the current mode: raw terminals need "\r\n"
+fn line_endings(data: &str, newline: &str) -> String {
+    data.replace("\r\n", "\n").replace('\n', newline)
+}
+
+pub fn play(input: &str, speed: f64, chapter: Option<&str>, idle_limit: Option<f64>) {
+    let mut recording = Recording::load(input).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    // --idle-limit wins over the limit stored in the recording
+    if let Some(limit) = idle_limit.or(recording.idle_time_limit) {
+        compress_idle(&mut recording.events, limit.max(0.0));
+    }
+    let events = &recording.events;
+    let markers = marker_indices(events);
+
+    let mut next = 0;
+    if let Some(chapter) = chapter {
+        match find_chapter(events, chapter) {
+            Some(index) => next = index,
+            None => {
+                eprintln!("Error: Chapter not found: {}", chapter);
+                std::process::exit(1);
+            }
+        }
+    }
 
     println!("▶️  Playing recording: {}", input);
+    if let Some(title) = &recording.title {
+        println!("Title: {}", title);
+    }
+    if let Some(author) = &recording.author {
+        println!("Author: {}", author);
+    }
     println!("Speed: {}x", speed);
     println!(
         "Duration: {:.2}s",
-        recording.events.last().map(|e| e.time).unwrap_or(0.0)
+        events.last().map(|e| e.time).unwrap_or(0.0)
     );
+    if !markers.is_empty() {
+        println!("Chapters:");
+        for (n, &index) in markers.iter().enumerate() {
+            let event = &events[index];
+            println!("  {}. {} ({:.1}s)", n + 1, event.data, event.time);
+        }
+    }
+
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if interactive && !markers.is_empty() {
+        println!("Keys: n/→ next chapter, p/← previous, space pause, q quit");
+    }
     println!("\n{}", "=".repeat(recording.width as usize));
 
     thread::sleep(Duration::from_millis(500));
 
+    if interactive {
+        terminal::enable_raw_mode().ok();
+    }
+    let newline = if interactive { "\r\n" } else { "\n" };
     let mut last_time = 0.0;
+    if next > 0 {
+        last_time = seek(events, next, newline);
+    }
 
-    for event in &recording.events {
-        let wait_time = (event.time - last_time) / speed;
-        if wait_time > 0.0 {
+    while next < events.len() {
+        let event = &events[next];
+        let wait_time = ((event.time - last_time) / speed).max(0.0);
+
+        if interactive {
+            match wait_for_key(Duration::from_secs_f64(wait_time)) {
+                Some(Nav::Next) => {
+                    if let Some(index) = next_marker(&markers, next) {
+                        last_time = seek(events, index, newline);
+                        next = index;
+                    }
+                    continue;
+                }
+                Some(Nav::Previous) => {
+                    let index = previous_marker(&markers, next);
+                    last_time = seek(events, index, newline);
+                    next = index;
+                    continue;
+                }
+                Some(Nav::Pause) => {
+                    // Resume on any key but q; time spent paused doesn't count
+                    if matches!(wait_for_key(Duration::MAX), Some(Nav::Quit)) {
+                        break;
+                    }
+                    continue;
+                }
+                Some(Nav::Quit) => break,
+                None => {}
+            }
+        } else if wait_time > 0.0 {
             thread::sleep(Duration::from_secs_f64(wait_time));
         }
 
-        print!("{}", event.data);
-        io::stdout().flush().unwrap();
+        if !event.is_marker() {
+            print!("{}", line_endings(&event.data, newline));
+            io::stdout().flush().unwrap();
+        }
 
         last_time = event.time;
+        next += 1;
     }
 
+    if interactive {
+        terminal::disable_raw_mode().ok();
+    }
     println!("\n{}", "=".repeat(recording.width as usize));
     println!("✅ Playback complete");
 }
 
-pub fn export(input: &str, format: &str, output: &str) {
-    let recording_path = PathBuf::from(input);
+/// Redraw the screen with everything before `index`; returns the new clock time
+fn seek(events: &[Event], index: usize, newline: &str) -> f64 {
+    let mut stdout = color::stdout();
+    write!(stdout, "\x1b[2J\x1b[H").ok();
+    for event in events[..index].iter().filter(|e| !e.is_marker()) {
+        write!(stdout, "{}", line_endings(&event.data, newline)).ok();
+    }
+    stdout.flush().ok();
+    events.get(index).map(|e| e.time).unwrap_or(0.0)
+}
 
-    if !recording_path.exists() {
-        eprintln!("❌ Recording file not found: {}", input);
+/// Wait up to `timeout` for a navigation key
+fn wait_for_key(timeout: Duration) -> Option<Nav> {
+    let deadline = Instant::now().checked_add(timeout);
+    loop {
+        let remaining = match deadline {
+            Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+            None => Duration::from_secs(3600),
+        };
+        if remaining.is_zero() || !event::poll(remaining).unwrap_or(false) {
+            if deadline.is_some() {
+                return None;
+            }
+            continue;
+        }
+        if let Ok(TermEvent::Key(key)) = event::read() {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            return match key.code {
+                KeyCode::Char('n') | KeyCode::Right => Some(Nav::Next),
+                KeyCode::Char('p') | KeyCode::Left => Some(Nav::Previous),
+                KeyCode::Char(' ') => Some(Nav::Pause),
+                KeyCode::Char('q') | KeyCode::Esc => Some(Nav::Quit),
+                // Any other key resumes a pause
+                _ if deadline.is_none() => Some(Nav::Pause),
+                _ => continue,
+            };
+        }
+    }
+}
+
+/// Write a recording as asciicast v2 (`cast`) or the legacy format (`json`),
+/// cut to `range` and, given `fps`, resampled to it
+pub fn export(input: &str, format: &str, output: &str, range: TimeRange, fps: Option<u32>) {
+    if fps == Some(0) {
+        eprintln!("Error: --fps must be at least 1");
         std::process::exit(1);
     }
+    let mut recording = Recording::load(input)
+        .and_then(|mut recording| recording.clip(range).map(|()| recording))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+    if let Some(fps) = fps {
+        recording.resample(fps);
+    }
 
     match format {
-        "gif" => {
-            // Try to use external tools for GIF export
-            if Command::new("agg").output().is_ok() {
-                println!("🎬 Exporting to GIF using 'agg'...");
-                let status = Command::new("agg").arg(input).arg(output).status();
-
-                match status {
-                    Ok(s) if s.success() => {
-                        println!("✅ GIF exported to {}", output);
-                    }
-                    _ => {
-                        eprintln!("❌ Failed to export GIF with agg");
-                        std::process::exit(1);
-                    }
-                }
-            } else if Command::new("vhs").output().is_ok() {
-                println!("🎬 Exporting to GIF using 'vhs'...");
-                println!("💡 Note: vhs requires a .tape file. Creating one...");
-
-                let tape_content = format!("Output {}\nPlayback {}\nSleep 1s", output, input);
-
-                let tape_file = input.replace(".recording", ".tape");
-                fs::write(&tape_file, tape_content).expect("Failed to write tape file");
-
-                let status = Command::new("vhs").arg(&tape_file).status();
-
-                match status {
-                    Ok(s) if s.success() => {
-                        println!("✅ GIF exported to {}", output);
-                        fs::remove_file(&tape_file).ok();
-                    }
-                    _ => {
-                        eprintln!("❌ Failed to export GIF with vhs");
-                        std::process::exit(1);
-                    }
-                }
+        "cast" | "json" => {
+            let contents = if format == "cast" {
+                recording.to_cast()
             } else {
-                eprintln!("❌ No GIF export tool found!");
-                eprintln!("\n💡 Install one of these tools:");
-                eprintln!("  • agg: cargo install agg");
-                eprintln!("  • vhs: brew install vhs");
+                recording.to_legacy()
+            };
+            if let Err(e) = atomic::write(Path::new(output), contents) {
+                eprintln!("Error: Failed to write {}: {}", output, e);
                 std::process::exit(1);
             }
-        }
-        "json" => {
-            // Just copy the recording as-is
-            fs::copy(input, output).expect("Failed to copy recording");
-            println!("✅ Recording copied to {}", output);
+            println!("✅ Recording exported to {}", output);
         }
         _ => {
-            eprintln!("❌ Unsupported format: {}", format);
-            eprintln!("Supported formats: gif, json");
+            eprintln!(
+                "Error: Invalid format '{}' (use gif, apng, webm, cast, json)",
+                format
+            );
             std::process::exit(1);
         }
     }
 }
 
 fn get_terminal_size() -> (u16, u16) {
-    use std::os::fd::AsRawFd;
-
     unsafe {
         let mut size: libc::winsize = std::mem::zeroed();
         libc::ioctl(io::stdout().as_raw_fd(), libc::TIOCGWINSZ, &mut size);
@@ -212,3 +768,234 @@ fn get_terminal_size() -> (u16, u16) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::new(0.0, "intro\n".to_string()),
+            Event::marker(1.0, "Build".to_string()),
+            Event::new(1.5, "cargo build\n".to_string()),
+            Event::marker(3.0, "Test".to_string()),
+            Event::new(3.5, "cargo test\n".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_marker_navigation() {
+        let markers = marker_indices(&events());
+        assert_eq!(markers, vec![1, 3]);
+
+        assert_eq!(next_marker(&markers, 0), Some(1));
+        assert_eq!(next_marker(&markers, 2), Some(3));
+        assert_eq!(next_marker(&markers, 4), None);
+
+        // Inside "Build" goes back to the start, inside "Test" back to "Build"
+        assert_eq!(previous_marker(&markers, 2), 0);
+        assert_eq!(previous_marker(&markers, 4), 1);
+    }
+
+    #[test]
+    fn test_find_chapter_by_number_or_label() {
+        let events = events();
+        assert_eq!(find_chapter(&events, "2"), Some(3));
+        assert_eq!(find_chapter(&events, "build"), Some(1));
+        assert_eq!(find_chapter(&events, "0"), None);
+        assert_eq!(find_chapter(&events, "Deploy"), None);
+    }
+
+    #[test]
+    fn test_parse_marks_relative_to_start() {
+        let marks = parse_marks("100.5\tstep 2\nnot a mark\n99\tearly\n", 100.0);
+        assert_eq!(marks.len(), 2);
+        assert_eq!(marks[0].time, 0.5);
+        assert_eq!(marks[0].data, "step 2");
+        assert!(marks[0].is_marker());
+        assert_eq!(marks[1].time, 0.0);
+    }
+
+    #[test]
+    fn test_idle_gap() {
+        let events = events();
+        assert!(idle_gap(&events, 1.0, 4.0, 2.0));
+        assert!(!idle_gap(&events, 1.0, 2.5, 2.0));
+        // No chapter before the first output
+        assert!(!idle_gap(&[], 0.0, 10.0, 2.0));
+    }
+
+    #[test]
+    fn test_recording_without_metadata_still_parses() {
+        let json = r#"{"version":1,"width":80,"height":24,"events":[]}"#;
+        let recording: Recording = serde_json::from_str(json).unwrap();
+        assert!(recording.title.is_none());
+        assert!(!serde_json::to_string(&recording).unwrap().contains("title"));
+    }
+
+    #[test]
+    fn test_cast_round_trip() {
+        let recording = Recording {
+            version: 2,
+            width: 80,
+            height: 24,
+            title: Some("Demo".to_string()),
+            author: None,
+            timestamp: Some(1_700_000_000),
+            idle_time_limit: None,
+            events: events(),
+        };
+        let cast = recording.to_cast();
+        let mut lines = cast.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            r#"{"version":2,"width":80,"height":24,"timestamp":1700000000,"title":"Demo"}"#
+        );
+        assert_eq!(lines.next().unwrap(), r#"[0.0,"o","intro\n"]"#);
+        assert_eq!(lines.next().unwrap(), r#"[1.0,"m","Build"]"#);
+
+        let parsed = Recording::parse(&cast).unwrap();
+        assert_eq!(parsed.title.as_deref(), Some("Demo"));
+        assert_eq!(parsed.events.len(), 5);
+        assert_eq!(parsed.events[4].data, "cargo test\n");
+    }
+
+    #[test]
+    fn test_cast_skips_input_and_resize_events() {
+        let cast = "{\"version\": 2, \"width\": 100, \"height\": 30, \"idle_time_limit\": 2.5}\n\
+                    [0.5, \"i\", \"l\"]\n[0.6, \"o\", \"ls\\r\\n\"]\n[0.7, \"r\", \"90x20\"]\n";
+        let recording = Recording::parse(cast).unwrap();
+        assert_eq!(recording.width, 100);
+        assert_eq!(recording.idle_time_limit, Some(2.5));
+        assert_eq!(recording.events.len(), 1);
+        assert_eq!(recording.events[0].data, "ls\r\n");
+
+        let err = Recording::parse("{\"version\": 1, \"width\": 80, \"height\": 24}").unwrap_err();
+        assert!(err.contains("version 1"));
+        assert!(Recording::parse("hello").is_err());
+    }
+
+    #[test]
+    fn test_legacy_round_trip() {
+        let cast = "{\"version\":2,\"width\":80,\"height\":24}\n[0.25,\"o\",\"hi\"]\n";
+        let legacy = Recording::parse(cast).unwrap().to_legacy();
+        assert!(legacy.contains("\"version\": 1"));
+        assert!(legacy.contains("\"event_type\": \"o\""));
+        let parsed = Recording::parse(&legacy).unwrap();
+        assert_eq!(parsed.events[0].time, 0.25);
+    }
+
+    #[test]
+    fn test_output_skips_markers_and_compresses() {
+        let mut recording = Recording::parse(
+            "{\"version\":2,\"width\":80,\"height\":24,\"idle_time_limit\":1}\n\
+             [0.5,\"o\",\"a\"]\n[1.0,\"m\",\"ch\"]\n[5.0,\"o\",\"b\"]\n",
+        )
+        .unwrap();
+        assert_eq!(recording.size(), (80, 24));
+        let times = |output: Vec<(f64, String)>| output.iter().map(|(t, _)| *t).collect::<Vec<_>>();
+        assert_eq!(times(recording.output(None)), [0.5, 2.0]);
+        assert_eq!(times(recording.output(Some(0.25))), [0.25, 0.75]);
+        recording.idle_time_limit = None;
+        assert_eq!(recording.output(None)[1], (5.0, "b".to_string()));
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("45"), Ok(45.0));
+        assert_eq!(parse_timestamp("00:12"), Ok(12.0));
+        assert_eq!(parse_timestamp("1:02:03.5"), Ok(3723.5));
+        assert_eq!(parse_timestamp("90:00"), Ok(5400.0));
+        for bad in ["", "1:60", "a:10", "1.5:00", "-3", "1:2:3:4"] {
+            assert!(parse_timestamp(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_time_range_order() {
+        let range = TimeRange::parse(Some("00:12"), Some("00:45")).unwrap();
+        assert_eq!((range.from, range.to), (Some(12.0), Some(45.0)));
+        assert!(TimeRange::parse(Some("1:00"), Some("30")).is_err());
+        assert_eq!(TimeRange::parse(None, None), Ok(TimeRange::default()));
+    }
+
+    fn recording(events: Vec<Event>) -> Recording {
+        Recording {
+            version: 2,
+            width: 80,
+            height: 24,
+            title: None,
+            author: None,
+            timestamp: None,
+            idle_time_limit: None,
+            events,
+        }
+    }
+
+    #[test]
+    fn test_clip_replays_earlier_output() {
+        let mut rec = recording(events());
+        let range = TimeRange {
+            from: Some(1.2),
+            to: Some(3.2),
+        };
+        rec.clip(range).unwrap();
+        let times: Vec<(f64, &str)> = rec
+            .events
+            .iter()
+            .map(|e| ((e.time * 10.0).round() / 10.0, e.data.as_str()))
+            .collect();
+        assert_eq!(
+            times,
+            [
+                (0.0, "intro\n"),
+                (0.3, "cargo build\n"),
+                (1.8, "Test"),
+                (2.0, ""),
+            ]
+        );
+
+        let mut rec = recording(events());
+        let past = TimeRange {
+            from: Some(10.0),
+            to: None,
+        };
+        assert!(rec.clip(past).unwrap_err().contains("past the end"));
+    }
+
+    #[test]
+    fn test_resample_merges_within_a_frame() {
+        let mut rec = recording(vec![
+            Event::new(0.0, "a".to_string()),
+            Event::new(0.02, "b".to_string()),
+            Event::new(0.09, "c".to_string()),
+            Event::marker(0.09, "M".to_string()),
+            Event::new(0.3, "d".to_string()),
+        ]);
+        rec.resample(10);
+        let events: Vec<(f64, &str)> = rec
+            .events
+            .iter()
+            .map(|e| (e.time, e.data.as_str()))
+            .collect();
+        assert_eq!(events, [(0.0, "a"), (0.1, "bc"), (0.1, "M"), (0.3, "d")]);
+    }
+
+    #[test]
+    fn test_compress_idle() {
+        let mut compressed = events();
+        compress_idle(&mut compressed, 0.5);
+        let times: Vec<f64> = compressed.iter().map(|e| e.time).collect();
+        assert_eq!(times, vec![0.0, 0.5, 1.0, 1.5, 2.0]);
+
+        let mut unchanged = events();
+        compress_idle(&mut unchanged, 10.0);
+        assert_eq!(unchanged[4].time, 3.5);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        assert_eq!(line_endings("a\r\nb\n", "\r\n"), "a\r\nb\r\n");
+        assert_eq!(line_endings("a\r\nb\n", "\n"), "a\nb\n");
+    }
+}
@@ -3,9 +3,9 @@
 //! Displays items with regex pattern matching, showing which entries match
 //! and which don't. Useful for previewing bulk operations before execution.
 
+use crate::text;
 use owo_colors::{OwoColorize, Style};
 use regex::Regex;
-use unicode_width::UnicodeWidthStr;
 
 /// Border character set for the filter pane
 struct BorderChars {
@@ -192,8 +192,8 @@ pub fn render(items: &[String], config: &RegexFilterConfig) -> Result<FilterResu
         .max()
         .unwrap_or(0);
 
-    let header_width = UnicodeWidthStr::width(header_text.as_str());
-    let match_info_width = UnicodeWidthStr::width(match_info.as_str());
+    let header_width = text::display_width(&header_text);
+    let match_info_width = text::display_width(&match_info);
     let action_button = format!("[{}]", config.action);
     let cancel_button = format!("[{}]", config.cancel_label);
     let footer_width = action_button.len() + cancel_button.len() + 3;
@@ -264,7 +264,7 @@ pub fn render(items: &[String], config: &RegexFilterConfig) -> Result<FilterResu
     // Matching items
     for item in &display_matches {
         let content = format!(" ☑ {}", item);
-        let content_width = UnicodeWidthStr::width(content.as_str());
+        let content_width = text::display_width(&content);
         let total_padding = box_width.saturating_sub(content_width);
         let right_padding = total_padding.saturating_sub(padding);
 
@@ -284,7 +284,7 @@ pub fn render(items: &[String], config: &RegexFilterConfig) -> Result<FilterResu
             "  ... and {} more matches",
             result.matches.len() - config.max_items
         );
-        let more_width = UnicodeWidthStr::width(more_text.as_str());
+        let more_width = text::display_width(&more_text);
         let total_padding = box_width.saturating_sub(more_width);
         let right_padding = total_padding.saturating_sub(padding);
 
@@ -302,7 +302,7 @@ pub fn render(items: &[String], config: &RegexFilterConfig) -> Result<FilterResu
     if config.show_non_matches && !display_non_matches.is_empty() {
         for item in &display_non_matches {
             let content = format!(" ☐ {} (no match)", item);
-            let content_width = UnicodeWidthStr::width(content.as_str());
+            let content_width = text::display_width(&content);
             let total_padding = box_width.saturating_sub(content_width);
             let right_padding = total_padding.saturating_sub(padding);
 
@@ -0,0 +1,489 @@
+//! Battery and temperature widgets for status scripts
+//!
+//! Readings come from sysfs on Linux (`/sys/class/power_supply`,
+//! `/sys/class/hwmon`, falling back to `/sys/class/thermal`), `pmset` on
+//! macOS and `nvidia-smi` for NVIDIA GPUs when it is installed. Each reading
+//! is drawn with the gauge's `▰▱` meter, either one per line or as a single
+//! prompt-safe line with `--inline`.
+
+use crate::output::gauge;
+use crate::output::inline::{self, Shell, Span};
+use crate::text;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const LABEL_COLOR: (u8, u8, u8) = (86, 214, 214);
+const RED: (u8, u8, u8) = (255, 85, 85);
+const YELLOW: (u8, u8, u8) = (224, 175, 104);
+const GREEN: (u8, u8, u8) = (63, 185, 80);
+const METER_SEGMENTS: usize = 10;
+
+/// Battery charge at or below this is drawn red
+const BATTERY_CRITICAL: f64 = 15.0;
+/// Battery charge at or below this is drawn yellow
+const BATTERY_LOW: f64 = 35.0;
+/// Fallbacks when a sensor reports no limits of its own (°C)
+const TEMP_HIGH: f64 = 70.0;
+const TEMP_CRITICAL: f64 = 90.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChargeState {
+    Charging,
+    Discharging,
+    Full,
+    Unknown,
+}
+
+impl ChargeState {
+    fn from_status(status: &str) -> Self {
+        match status.trim().to_lowercase().as_str() {
+            "charging" => ChargeState::Charging,
+            "discharging" => ChargeState::Discharging,
+            "full" | "charged" => ChargeState::Full,
+            _ => ChargeState::Unknown,
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            ChargeState::Charging => "⚡",
+            ChargeState::Full => "🔌",
+            _ => "🔋",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Battery {
+    pub name: String,
+    pub percent: f64,
+    pub state: ChargeState,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Temperature {
+    pub label: String,
+    pub celsius: f64,
+    /// Temperature the sensor considers high, if it reports one
+    pub high: Option<f64>,
+    /// Temperature the sensor considers critical, if it reports one
+    pub critical: Option<f64>,
+}
+
+/// Print every battery, or exit with an error when there is none
+pub fn render_battery(inline_mode: bool, shell: &str) {
+    let shell = inline::shell_or_exit(shell);
+    let batteries = read_batteries();
+    if batteries.is_empty() {
+        eprintln!("Error: No battery found");
+        std::process::exit(1);
+    }
+    let rows: Vec<Vec<Span>> = batteries.iter().map(battery_spans).collect();
+    print_rows(rows, inline_mode, shell);
+}
+
+/// Print temperature sensors whose label contains `filter`
+pub fn render_temps(filter: Option<&str>, inline_mode: bool, shell: &str) {
+    let shell = inline::shell_or_exit(shell);
+    let mut temps = read_temperatures();
+    if let Some(f) = filter {
+        let f = f.to_lowercase();
+        temps.retain(|t| t.label.to_lowercase().contains(&f));
+    }
+    if temps.is_empty() {
+        eprintln!("Error: No temperature sensors found");
+        std::process::exit(1);
+    }
+    let width = temps
+        .iter()
+        .map(|t| text::display_width(&t.label))
+        .max()
+        .unwrap_or(0);
+    let rows: Vec<Vec<Span>> = temps
+        .iter()
+        .map(|t| temperature_spans(t, if inline_mode { 0 } else { width }))
+        .collect();
+    print_rows(rows, inline_mode, shell);
+}
+
+/// One reading per line, or all readings on one prompt line
+fn print_rows(rows: Vec<Vec<Span>>, inline_mode: bool, shell: Shell) {
+    if inline_mode {
+        let mut line = Vec::new();
+        for (idx, row) in rows.into_iter().enumerate() {
+            if idx > 0 {
+                line.push(Span::plain("  "));
+            }
+            line.extend(row);
+        }
+        inline::print(&line, shell);
+    } else {
+        for row in rows {
+            println!("{}", inline::render(&row, shell));
+        }
+    }
+}
+
+/// `🔋 BAT0 ▰▰▰▰▰▰▰▱▱▱ 72%`, colored by how much charge is left
+fn battery_spans(battery: &Battery) -> Vec<Span> {
+    let color = battery_color(battery.percent);
+    let mut spans = vec![Span::colored(
+        format!("{} {} ", battery.state.icon(), battery.name),
+        LABEL_COLOR,
+    )];
+    spans.extend(gauge::meter_spans(battery.percent, color, METER_SEGMENTS));
+    spans.push(Span::plain(" "));
+    spans.push(Span::colored(format!("{:.0}%", battery.percent), color).bold());
+    spans
+}
+
+fn battery_color(percent: f64) -> (u8, u8, u8) {
+    if percent <= BATTERY_CRITICAL {
+        RED
+    } else if percent <= BATTERY_LOW {
+        YELLOW
+    } else {
+        GREEN
+    }
+}
+
+/// `coretemp Core 0 ▰▰▰▰▰▱▱▱▱▱ 48°C`, the meter spanning 0 °C to critical
+fn temperature_spans(temp: &Temperature, label_width: usize) -> Vec<Span> {
+    let color = temperature_color(temp);
+    let max = temp.critical.unwrap_or(TEMP_CRITICAL).max(1.0);
+    let pad = label_width.saturating_sub(text::display_width(&temp.label));
+    let mut spans = vec![Span::colored(
+        format!("{}{} ", temp.label, " ".repeat(pad)),
+        LABEL_COLOR,
+    )];
+    spans.extend(gauge::meter_spans(
+        temp.celsius / max * 100.0,
+        color,
+        METER_SEGMENTS,
+    ));
+    spans.push(Span::plain(" "));
+    spans.push(Span::colored(format!("{:.0}°C", temp.celsius), color).bold());
+    spans
+}
+
+fn temperature_color(temp: &Temperature) -> (u8, u8, u8) {
+    let critical = temp.critical.unwrap_or(TEMP_CRITICAL);
+    let high = temp.high.unwrap_or(TEMP_HIGH).min(critical);
+    if temp.celsius >= critical {
+        RED
+    } else if temp.celsius >= high {
+        YELLOW
+    } else {
+        GREEN
+    }
+}
+
+/// Batteries on this machine, empty when none can be read
+pub fn read_batteries() -> Vec<Battery> {
+    let batteries = read_sysfs_batteries(Path::new("/sys/class/power_supply"));
+    if !batteries.is_empty() || !cfg!(target_os = "macos") {
+        return batteries;
+    }
+    Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .map(|out| parse_pmset(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+/// Temperature sensors on this machine, empty when none can be read
+pub fn read_temperatures() -> Vec<Temperature> {
+    let mut temps = read_hwmon(Path::new("/sys/class/hwmon"));
+    if temps.is_empty() {
+        temps = read_thermal_zones(Path::new("/sys/class/thermal"));
+    }
+    if let Ok(out) = Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=name,temperature.gpu",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+    {
+        if out.status.success() {
+            temps.extend(parse_nvidia_smi(&String::from_utf8_lossy(&out.stdout)));
+        }
+    }
+    temps
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+fn read_number(path: &Path) -> Option<f64> {
+    read_trimmed(path)?.parse().ok()
+}
+
+/// Entries of `dir` sorted by name, so sensors keep a stable order
+fn sorted_entries(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .map(|rd| rd.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+fn read_sysfs_batteries(root: &Path) -> Vec<Battery> {
+    sorted_entries(root)
+        .into_iter()
+        .filter(|dir| read_trimmed(&dir.join("type")).as_deref() == Some("Battery"))
+        .filter_map(|dir| {
+            let percent = read_number(&dir.join("capacity")).or_else(|| {
+                let now = read_number(&dir.join("energy_now"))
+                    .or_else(|| read_number(&dir.join("charge_now")))?;
+                let full = read_number(&dir.join("energy_full"))
+                    .or_else(|| read_number(&dir.join("charge_full")))?;
+                (full > 0.0).then(|| now / full * 100.0)
+            })?;
+            let state = read_trimmed(&dir.join("status"))
+                .map(|s| ChargeState::from_status(&s))
+                .unwrap_or(ChargeState::Unknown);
+            Some(Battery {
+                name: dir.file_name()?.to_string_lossy().to_string(),
+                percent: percent.clamp(0.0, 100.0),
+                state,
+            })
+        })
+        .collect()
+}
+
+/// Parse `pmset -g batt`, e.g. `-InternalBattery-0 (id=1)<TAB>85%; charging; 0:40 remaining`
+fn parse_pmset(output: &str) -> Vec<Battery> {
+    output
+        .lines()
+        .filter(|line| line.trim_start().starts_with('-'))
+        .filter_map(|line| {
+            let name = line.trim_start().trim_start_matches('-');
+            let name = name.split([' ', '\t']).next()?.to_string();
+            let (_, rest) = line.split_once('\t')?;
+            let mut fields = rest.split(';').map(str::trim);
+            let percent = fields.next()?.trim_end_matches('%').parse().ok()?;
+            let state = fields
+                .next()
+                .map(ChargeState::from_status)
+                .unwrap_or(ChargeState::Unknown);
+            Some(Battery {
+                name,
+                percent,
+                state,
+            })
+        })
+        .collect()
+}
+
+fn read_hwmon(root: &Path) -> Vec<Temperature> {
+    let mut temps = Vec::new();
+    for dir in sorted_entries(root) {
+        let chip = read_trimmed(&dir.join("name")).unwrap_or_else(|| "hwmon".to_string());
+        let mut inputs: Vec<String> = sorted_entries(&dir)
+            .iter()
+            .filter_map(|p| p.file_name()?.to_str().map(String::from))
+            .filter(|f| f.starts_with("temp") && f.ends_with("_input"))
+            .collect();
+        // temp2 before temp10
+        inputs.sort_by_key(|f| f[4..f.len() - 6].parse::<u32>().unwrap_or(u32::MAX));
+        for input in inputs {
+            let prefix = input.trim_end_matches("_input");
+            let Some(milli) = read_number(&dir.join(&input)) else {
+                continue;
+            };
+            let sensor = read_trimmed(&dir.join(format!("{}_label", prefix)))
+                .unwrap_or_else(|| prefix.to_string());
+            let limit = |suffix: &str| {
+                read_number(&dir.join(format!("{}_{}", prefix, suffix)))
+                    .map(|m| m / 1000.0)
+                    .filter(|c| *c > 0.0)
+            };
+            temps.push(Temperature {
+                label: format!("{} {}", chip, sensor),
+                celsius: milli / 1000.0,
+                high: limit("max"),
+                critical: limit("crit"),
+            });
+        }
+    }
+    temps
+}
+
+fn read_thermal_zones(root: &Path) -> Vec<Temperature> {
+    sorted_entries(root)
+        .into_iter()
+        .filter(|dir| {
+            dir.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("thermal_zone"))
+        })
+        .filter_map(|dir| {
+            let milli = read_number(&dir.join("temp"))?;
+            let label = read_trimmed(&dir.join("type"))
+                .unwrap_or_else(|| dir.file_name().unwrap_or_default().to_string_lossy().into());
+            Some(Temperature {
+                label,
+                celsius: milli / 1000.0,
+                high: None,
+                critical: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `nvidia-smi --query-gpu=name,temperature.gpu --format=csv,noheader,nounits`
+fn parse_nvidia_smi(output: &str) -> Vec<Temperature> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, temp) = line.rsplit_once(',')?;
+            Some(Temperature {
+                label: name.trim().to_string(),
+                celsius: temp.trim().parse().ok()?,
+                high: None,
+                critical: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path: PathBuf = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn plain(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_read_sysfs_batteries() {
+        let root = TempDir::new().unwrap();
+        write(root.path(), "AC/type", "Mains\n");
+        write(root.path(), "BAT0/type", "Battery\n");
+        write(root.path(), "BAT0/capacity", "72\n");
+        write(root.path(), "BAT0/status", "Charging\n");
+        write(root.path(), "BAT1/type", "Battery\n");
+        write(root.path(), "BAT1/energy_now", "25000\n");
+        write(root.path(), "BAT1/energy_full", "50000\n");
+        write(root.path(), "BAT1/status", "Discharging\n");
+
+        let batteries = read_sysfs_batteries(root.path());
+        assert_eq!(
+            batteries,
+            vec![
+                Battery {
+                    name: "BAT0".to_string(),
+                    percent: 72.0,
+                    state: ChargeState::Charging,
+                },
+                Battery {
+                    name: "BAT1".to_string(),
+                    percent: 50.0,
+                    state: ChargeState::Discharging,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pmset() {
+        let output = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=4653155)\t85%; charging; 0:40 remaining present: true\n";
+        let batteries = parse_pmset(output);
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "InternalBattery-0");
+        assert_eq!(batteries[0].percent, 85.0);
+        assert_eq!(batteries[0].state, ChargeState::Charging);
+    }
+
+    #[test]
+    fn test_read_hwmon_orders_and_labels_sensors() {
+        let root = TempDir::new().unwrap();
+        write(root.path(), "hwmon0/name", "coretemp\n");
+        write(root.path(), "hwmon0/temp10_input", "61000\n");
+        write(root.path(), "hwmon0/temp2_input", "48500\n");
+        write(root.path(), "hwmon0/temp2_label", "Core 0\n");
+        write(root.path(), "hwmon0/temp2_max", "80000\n");
+        write(root.path(), "hwmon0/temp2_crit", "100000\n");
+
+        let temps = read_hwmon(root.path());
+        assert_eq!(temps.len(), 2);
+        assert_eq!(temps[0].label, "coretemp Core 0");
+        assert_eq!(temps[0].celsius, 48.5);
+        assert_eq!(temps[0].high, Some(80.0));
+        assert_eq!(temps[0].critical, Some(100.0));
+        assert_eq!(temps[1].label, "coretemp temp10");
+        assert_eq!(temps[1].critical, None);
+    }
+
+    #[test]
+    fn test_read_thermal_zones() {
+        let root = TempDir::new().unwrap();
+        write(root.path(), "thermal_zone0/type", "x86_pkg_temp\n");
+        write(root.path(), "thermal_zone0/temp", "55000\n");
+        write(root.path(), "cooling_device0/type", "Fan\n");
+
+        let temps = read_thermal_zones(root.path());
+        assert_eq!(temps.len(), 1);
+        assert_eq!(temps[0].label, "x86_pkg_temp");
+        assert_eq!(temps[0].celsius, 55.0);
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi() {
+        let temps = parse_nvidia_smi("NVIDIA GeForce RTX 3080, 64\n");
+        assert_eq!(temps[0].label, "NVIDIA GeForce RTX 3080");
+        assert_eq!(temps[0].celsius, 64.0);
+    }
+
+    #[test]
+    fn test_battery_spans() {
+        let battery = Battery {
+            name: "BAT0".to_string(),
+            percent: 72.0,
+            state: ChargeState::Charging,
+        };
+        assert_eq!(plain(&battery_spans(&battery)), "⚡ BAT0 ▰▰▰▰▰▰▰▱▱▱ 72%");
+        assert_eq!(battery_color(10.0), RED);
+        assert_eq!(battery_color(30.0), YELLOW);
+        assert_eq!(battery_color(80.0), GREEN);
+    }
+
+    #[test]
+    fn test_temperature_spans_and_thresholds() {
+        let temp = Temperature {
+            label: "gpu".to_string(),
+            celsius: 45.0,
+            high: Some(80.0),
+            critical: Some(100.0),
+        };
+        assert_eq!(
+            plain(&temperature_spans(&temp, 6)),
+            "gpu    ▰▰▰▰▰▱▱▱▱▱ 45°C"
+        );
+        assert_eq!(temperature_color(&temp), GREEN);
+
+        let hot = Temperature {
+            celsius: 85.0,
+            ..temp.clone()
+        };
+        assert_eq!(temperature_color(&hot), YELLOW);
+
+        let unknown_limits = Temperature {
+            celsius: 92.0,
+            high: None,
+            critical: None,
+            ..temp
+        };
+        assert_eq!(temperature_color(&unknown_limits), RED);
+    }
+}
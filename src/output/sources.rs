@@ -0,0 +1,246 @@
+//! Built-in readings for `--source`: CPU and memory use, disk space and
+//! network throughput
+//!
+//! Percentages for `cpu`, `mem` and `disk[:mount]`, and KB/s in plus out for
+//! `net:<iface>`. Reading them needs the `sources` feature (sysinfo); without
+//! it a source still parses, and sampling reports how to enable it.
+
+use super::watch;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Names listed in errors and help
+pub const SOURCES: &str = "cpu, mem, disk[:mount], net:<iface>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Cpu,
+    Mem,
+    /// Space used on the disk mounted here
+    Disk(String),
+    /// Bytes in and out of this interface
+    Net(String),
+}
+
+impl Source {
+    /// Parse "cpu", "mem", "disk", "disk:/home" or "net:eth0"
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (name, arg) = match s.trim().split_once(':') {
+            Some((name, arg)) => (name, Some(arg.trim()).filter(|a| !a.is_empty())),
+            None => (s.trim(), None),
+        };
+        match (name.to_lowercase().as_str(), arg) {
+            ("cpu", None) => Ok(Source::Cpu),
+            ("mem", None) => Ok(Source::Mem),
+            ("disk", mount) => Ok(Source::Disk(mount.unwrap_or("/").to_string())),
+            ("net", Some(iface)) => Ok(Source::Net(iface.to_string())),
+            _ => Err(format!("Invalid source '{}' (use {})", s, SOURCES)),
+        }
+    }
+
+    /// Short label for a gauge, e.g. "CPU" or "NET eth0"
+    pub fn label(&self) -> String {
+        match self {
+            Source::Cpu => "CPU".to_string(),
+            Source::Mem => "MEM".to_string(),
+            Source::Disk(mount) => format!("DISK {}", mount),
+            Source::Net(iface) => format!("NET {}", iface),
+        }
+    }
+}
+
+/// Reads one source over and over; rates and CPU use are measured between
+/// consecutive samples
+#[cfg(feature = "sources")]
+pub struct Sampler {
+    source: Source,
+    system: sysinfo::System,
+    disks: sysinfo::Disks,
+    networks: sysinfo::Networks,
+    /// When the last reading was taken
+    last: std::time::Instant,
+    /// The interface's byte total at that reading
+    last_bytes: u64,
+}
+
+#[cfg(feature = "sources")]
+impl Sampler {
+    pub fn new(source: Source) -> Result<Self, String> {
+        use sysinfo::{Disks, Networks, System};
+
+        let mut sampler = Sampler {
+            system: System::new(),
+            disks: Disks::new(),
+            networks: Networks::new(),
+            last: std::time::Instant::now(),
+            last_bytes: 0,
+            source,
+        };
+        match &sampler.source {
+            Source::Cpu => sampler.system.refresh_cpu_usage(),
+            Source::Mem => {}
+            Source::Disk(mount) => {
+                sampler.disks.refresh(true);
+                sampler.disk(mount)?;
+            }
+            Source::Net(iface) => {
+                sampler.networks.refresh(true);
+                sampler.last_bytes = sampler.net_bytes(iface)?;
+            }
+        }
+        Ok(sampler)
+    }
+
+    /// The current reading; the first one of CPU or network waits briefly
+    /// so there is something to measure against
+    pub fn sample(&mut self) -> Result<f64, String> {
+        let elapsed = self.last.elapsed();
+        if elapsed < sysinfo::MINIMUM_CPU_UPDATE_INTERVAL
+            && matches!(self.source, Source::Cpu | Source::Net(_))
+        {
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL - elapsed);
+        }
+        let value = match self.source.clone() {
+            Source::Cpu => {
+                self.system.refresh_cpu_usage();
+                self.system.global_cpu_usage() as f64
+            }
+            Source::Mem => {
+                self.system.refresh_memory();
+                match self.system.total_memory() {
+                    0 => 0.0,
+                    total => self.system.used_memory() as f64 / total as f64 * 100.0,
+                }
+            }
+            Source::Disk(mount) => {
+                self.disks.refresh(true);
+                let disk = self.disk(&mount)?;
+                match disk.total_space() {
+                    0 => 0.0,
+                    total => (total - disk.available_space()) as f64 / total as f64 * 100.0,
+                }
+            }
+            Source::Net(iface) => {
+                self.networks.refresh(true);
+                let bytes = self.net_bytes(&iface)?;
+                let seconds = self.last.elapsed().as_secs_f64().max(f64::EPSILON);
+                let rate = bytes.saturating_sub(self.last_bytes) as f64 / 1024.0 / seconds;
+                self.last_bytes = bytes;
+                rate
+            }
+        };
+        self.last = std::time::Instant::now();
+        Ok(value)
+    }
+
+    fn disk(&self, mount: &str) -> Result<&sysinfo::Disk, String> {
+        self.disks
+            .list()
+            .iter()
+            .find(|disk| disk.mount_point() == std::path::Path::new(mount))
+            .ok_or_else(|| format!("No disk mounted at '{}'", mount))
+    }
+
+    fn net_bytes(&self, iface: &str) -> Result<u64, String> {
+        match self.networks.get(iface) {
+            Some(data) => Ok(data.total_received() + data.total_transmitted()),
+            None => {
+                let mut names: Vec<&str> = self.networks.keys().map(String::as_str).collect();
+                names.sort_unstable();
+                Err(format!(
+                    "No network interface '{}' (found: {})",
+                    iface,
+                    names.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "sources"))]
+pub struct Sampler;
+
+#[cfg(not(feature = "sources"))]
+impl Sampler {
+    pub fn new(_source: Source) -> Result<Self, String> {
+        Err(
+            "--source needs the `sources` feature (cargo install termgfx --features sources)"
+                .to_string(),
+        )
+    }
+
+    pub fn sample(&mut self) -> Result<f64, String> {
+        unreachable!("a Sampler can't be built without the sources feature")
+    }
+}
+
+/// A sampler for `source` text, or the error to print
+pub fn sampler(source: &str) -> Result<Sampler, String> {
+    Sampler::new(Source::parse(source)?)
+}
+
+/// Redraw the screen with `draw` every `interval` until Ctrl+C, each time
+/// with a fresh reading
+pub fn watch(
+    sampler: &mut Sampler,
+    interval: Duration,
+    mut draw: impl FnMut(f64),
+) -> Result<(), String> {
+    let running = watch::setup_ctrl_c();
+    while running.load(Ordering::SeqCst) {
+        let value = sampler.sample()?;
+        print!("\x1b[2J\x1b[H");
+        draw(value);
+        let mut slept = Duration::ZERO;
+        while slept < interval && running.load(Ordering::SeqCst) {
+            let step = (interval - slept).min(Duration::from_millis(100));
+            std::thread::sleep(step);
+            slept += step;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sources() {
+        assert_eq!(Source::parse("cpu"), Ok(Source::Cpu));
+        assert_eq!(Source::parse("MEM"), Ok(Source::Mem));
+        assert_eq!(Source::parse("disk"), Ok(Source::Disk("/".to_string())));
+        assert_eq!(
+            Source::parse("disk:/home"),
+            Ok(Source::Disk("/home".to_string()))
+        );
+        assert_eq!(
+            Source::parse("net:eth0"),
+            Ok(Source::Net("eth0".to_string()))
+        );
+        for bad in ["net", "cpu:1", "gpu"] {
+            let err = Source::parse(bad).unwrap_err();
+            assert!(err.starts_with(&format!("Invalid source '{}'", bad)));
+        }
+    }
+
+    #[cfg(feature = "sources")]
+    #[test]
+    fn test_sampler_reads_percentages() {
+        let mut mem = sampler("mem").unwrap();
+        let used = mem.sample().unwrap();
+        assert!((0.0..=100.0).contains(&used));
+        assert!(sampler("disk:/no/such/mount")
+            .err()
+            .unwrap()
+            .contains("No disk mounted at '/no/such/mount'"));
+    }
+
+    #[cfg(not(feature = "sources"))]
+    #[test]
+    fn test_sampler_needs_feature() {
+        let err = sampler("cpu").err().unwrap();
+        assert!(err.contains("`sources` feature"));
+        assert!(sampler("gpu").err().unwrap().starts_with("Invalid source"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::output::color;
 use crossterm::{
     cursor::{Hide, MoveToColumn, Show},
     terminal::{Clear, ClearType},
@@ -10,20 +11,8 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 /// Get spinner frames for different styles
-fn get_spinner_frames(style: &str) -> Vec<&'static str> {
-    match style {
-        "dots" => vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
-        "line" => vec!["|", "/", "-", "\\"],
-        "arc" => vec!["◜", "◠", "◝", "◞", "◡", "◟"],
-        "bouncing" => vec!["⠁", "⠂", "⠄", "⠂"],
-        "clock" => vec![
-            "🕐", "🕑", "🕒", "🕓", "🕔", "🕕", "🕖", "🕗", "🕘", "🕙", "🕚", "🕛",
-        ],
-        "circle" => vec!["◐", "◓", "◑", "◒"],
-        "bounce" => vec!["⠁", "⠂", "⠄", "⡀", "⢀", "⠠", "⠐", "⠈"],
-        "moon" => vec!["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"],
-        _ => vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"], // Default to dots
-    }
+pub fn get_spinner_frames(style: &str) -> Vec<&'static str> {
+    crate::render::spinner::frames(style).to_vec()
 }
 
 /// Render an animated loading spinner
@@ -46,7 +35,7 @@ pub fn render(message: &str, style: &str, duration: Option<u64>) {
     })
     .expect("Error setting Ctrl-C handler");
 
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
 
     // Hide cursor
     stdout.execute(Hide).unwrap();
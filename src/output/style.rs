@@ -1,5 +1,7 @@
+use crate::design::theme::{self, Theme};
+use crate::output::color;
 use owo_colors::{OwoColorize, Style};
-use std::io::{stdout, Write};
+use std::io::Write;
 
 /// Border style type for presets
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -14,6 +16,18 @@ pub enum BorderStyle {
 }
 
 impl BorderStyle {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "single" => Some(BorderStyle::Single),
+            "double" => Some(BorderStyle::Double),
+            "rounded" => Some(BorderStyle::Rounded),
+            "thick" => Some(BorderStyle::Thick),
+            "ascii" => Some(BorderStyle::Ascii),
+            "none" => Some(BorderStyle::None),
+            _ => None,
+        }
+    }
+
     /// Get border characters: (top_left, horizontal, top_right, vertical, bottom_left, bottom_right)
     pub fn chars(
         &self,
@@ -66,8 +80,35 @@ pub enum PresetCategory {
 }
 
 impl StylePreset {
-    /// Get all available style presets
+    /// Get all available style presets, recolored by the active theme if any
     pub fn all() -> Vec<StylePreset> {
+        match theme::active() {
+            Some(theme) => Self::builtin()
+                .into_iter()
+                .map(|p| p.themed(theme))
+                .collect(),
+            None => Self::builtin(),
+        }
+    }
+
+    /// Semantic presets take their color and border from `theme`; design
+    /// presets keep their own look
+    pub fn themed(mut self, theme: &Theme) -> Self {
+        if self.category != PresetCategory::Semantic {
+            return self;
+        }
+        if let Some(color) = theme.semantic_color(self.name) {
+            self.colors.primary = color;
+            self.colors.secondary = color;
+            self.colors.border = Theme::rgb(&theme.colors.border);
+        }
+        if let Some(border) = BorderStyle::from_name(&theme.border_style) {
+            self.border_style = border;
+        }
+        self
+    }
+
+    fn builtin() -> Vec<StylePreset> {
         vec![
             // Semantic presets
             StylePreset {
@@ -328,7 +369,7 @@ fn render_sample_table(preset: &StylePreset) -> String {
 }
 
 /// Render a single preset section for the preview
-fn render_preset_section(stdout: &mut std::io::Stdout, preset: &StylePreset) {
+fn render_preset_section(stdout: &mut color::Stdout, preset: &StylePreset) {
     let (pr, pg, pb) = preset.colors.primary;
 
     let _ = writeln!(
@@ -390,7 +431,7 @@ fn render_preset_section(stdout: &mut std::io::Stdout, preset: &StylePreset) {
 
 /// Preview all style presets side by side
 pub fn render_all_preview() {
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
 
     let _ = writeln!(
         stdout,
@@ -502,7 +543,7 @@ pub fn render_all_preview() {
 /// Preview a single style preset in detail
 pub fn render_preset_preview(preset_name: &str) {
     if let Some(preset) = StylePreset::find(preset_name) {
-        let mut stdout = stdout();
+        let mut stdout = color::stdout();
 
         let _ = writeln!(
             stdout,
@@ -576,7 +617,7 @@ pub fn render_preset_preview(preset_name: &str) {
 
 /// List all available style presets
 pub fn render_style_list() {
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
     let presets = StylePreset::all();
 
     let _ = writeln!(stdout, "\nAvailable Style Presets:\n");
@@ -628,6 +669,24 @@ mod tests {
         assert!(StylePreset::find("retro").is_some());
     }
 
+    #[test]
+    fn test_themed_recolors_semantic_presets_only() {
+        let nord = Theme::nord();
+        let info = StylePreset::find("info").unwrap().themed(&nord);
+        assert_eq!(info.colors.primary, Theme::rgb(&nord.colors.info));
+        assert_eq!(info.colors.border, Theme::rgb(&nord.colors.border));
+
+        let mut monokai = Theme::monokai();
+        monokai.border_style = "ascii".to_string();
+        let danger = StylePreset::find("danger").unwrap().themed(&monokai);
+        assert_eq!(danger.border_style, BorderStyle::Ascii);
+
+        let retro = StylePreset::find("retro").unwrap();
+        let themed = retro.clone().themed(&nord);
+        assert_eq!(themed.colors.primary, retro.colors.primary);
+        assert_eq!(themed.border_style, retro.border_style);
+    }
+
     #[test]
     fn test_find_other_presets() {
         assert!(StylePreset::find("gradient").is_some());
@@ -1,89 +1,29 @@
+use crate::animation::policy;
+use crate::design::theme;
+use crate::output::color;
+use crate::output::profile::{self, Phase};
+use crate::output::{input, layout, width};
+pub use crate::render::boxed::TitleAlign;
+use crate::render::boxed::{self, BorderChars, Frame};
+use crate::render::{self, Color, Doc};
+use crate::text;
 use owo_colors::{OwoColorize, Style};
-use std::io::{stdout, Write};
+use std::io::{stdin, IsTerminal, Write};
 use std::thread;
 use std::time::Duration;
-use unicode_width::UnicodeWidthStr;
-
-/// Border character set for different styles
-#[derive(Debug, Clone)]
-struct BorderChars {
-    top_left: &'static str,
-    top_right: &'static str,
-    bottom_left: &'static str,
-    bottom_right: &'static str,
-    horizontal: &'static str,
-    vertical: &'static str,
-    header_left: &'static str,
-    header_right: &'static str,
-}
 
-impl BorderChars {
-    fn get(border_style: &str) -> Self {
-        match border_style.to_lowercase().as_str() {
-            "single" => BorderChars {
-                top_left: "┌",
-                top_right: "┐",
-                bottom_left: "└",
-                bottom_right: "┘",
-                horizontal: "─",
-                vertical: "│",
-                header_left: "├",
-                header_right: "┤",
-            },
-            "double" => BorderChars {
-                top_left: "╔",
-                top_right: "╗",
-                bottom_left: "╚",
-                bottom_right: "╝",
-                horizontal: "═",
-                vertical: "║",
-                header_left: "╠",
-                header_right: "╣",
-            },
-            "rounded" => BorderChars {
-                top_left: "╭",
-                top_right: "╮",
-                bottom_left: "╰",
-                bottom_right: "╯",
-                horizontal: "─",
-                vertical: "│",
-                header_left: "├",
-                header_right: "┤",
-            },
-            "heavy" | "thick" => BorderChars {
-                top_left: "┏",
-                top_right: "┓",
-                bottom_left: "┗",
-                bottom_right: "┛",
-                horizontal: "━",
-                vertical: "┃",
-                header_left: "┣",
-                header_right: "┫",
-            },
-            "ascii" => BorderChars {
-                top_left: "+",
-                top_right: "+",
-                bottom_left: "+",
-                bottom_right: "+",
-                horizontal: "-",
-                vertical: "|",
-                header_left: "+",
-                header_right: "+",
-            },
-            _ => BorderChars::get("rounded"),
-        }
+/// Get color style for the box; the active theme's colors win when set
+fn get_style(style_name: &str) -> render::Style {
+    if let Some((r, g, b)) = theme::active().and_then(|t| t.semantic_color(style_name)) {
+        return render::Style::fg(Color::Rgb(r, g, b)).bold();
     }
-}
-
-/// Get color style for the box
-fn get_style(style_name: &str) -> Style {
     match style_name.to_lowercase().as_str() {
-        "success" => Style::new().bright_green().bold(),
-        "warning" => Style::new().bright_yellow().bold(),
-        "danger" | "error" => Style::new().bright_red().bold(),
-        "info" => Style::new().bright_blue().bold(),
-        "gradient" => Style::new().bright_magenta().bold(),
-        _ => Style::new().white(),
+        "success" => render::Style::fg(Color::BRIGHT_GREEN).bold(),
+        "warning" => render::Style::fg(Color::BRIGHT_YELLOW).bold(),
+        "danger" | "error" => render::Style::fg(Color::BRIGHT_RED).bold(),
+        "info" => render::Style::fg(Color::BRIGHT_BLUE).bold(),
+        "gradient" => render::Style::fg(Color::BRIGHT_MAGENTA).bold(),
+        _ => render::Style::fg(Color::WHITE),
     }
 }
 
@@ -99,9 +39,63 @@ fn get_default_emoji(style_name: &str) -> Option<&'static str> {
     }
 }
 
+/// Layout options for a styled box
+#[derive(Debug, Clone, Copy)]
+pub struct BoxOptions<'a> {
+    pub style: &'a str,
+    pub border: &'a str,
+    pub emoji: Option<&'a str>,
+    /// Text embedded in the top border
+    pub title: Option<&'a str>,
+    /// Text embedded in the bottom border
+    pub footer: Option<&'a str>,
+    /// Where title and footer sit along their border
+    pub title_align: TitleAlign,
+    /// Spaces between the border and the content on each side
+    pub padding: usize,
+    /// Maximum outer width; longer lines are word-wrapped
+    pub max_width: Option<usize>,
+}
+
+impl Default for BoxOptions<'_> {
+    fn default() -> Self {
+        Self {
+            style: "info",
+            border: "rounded",
+            emoji: None,
+            title: None,
+            footer: None,
+            title_align: TitleAlign::Left,
+            padding: 2,
+            max_width: None,
+        }
+    }
+}
+
+/// Message from the argument, `--file`, or piped stdin (argument omitted or `-`)
+pub fn read_message(message: Option<&str>, file: Option<&str>) -> Result<String, String> {
+    if let Some(text) = message.filter(|m| !input::is_stdin(m)) {
+        return Ok(text.to_string());
+    }
+    if file.is_none() && stdin().is_terminal() {
+        return Err("No message given (pass text, --file, or pipe to stdin)".to_string());
+    }
+    let text = input::read_text(file.unwrap_or(input::STDIN)).map_err(|e| e.to_string())?;
+    if file.is_none() && text.is_empty() {
+        return Err("No message given (stdin was empty)".to_string());
+    }
+    Ok(text)
+}
+
 /// Render a styled box with the given message
 pub fn render(message: &str, style: &str, border: &str, emoji: Option<&str>) {
-    render_animated(message, style, border, emoji, false, 500);
+    let options = BoxOptions {
+        style,
+        border,
+        emoji,
+        ..BoxOptions::default()
+    };
+    render_animated(message, &options, false, 500);
 }
 
 /// Render a danger zone box with header
@@ -113,6 +107,7 @@ pub fn render_danger_zone(
     animate: bool,
     animation_time_ms: u64,
 ) {
+    let animate = policy::gate(animate);
     let borders = BorderChars::get(border);
     let danger_style = Style::new().bright_red().bold();
     let header_style = Style::new().on_bright_red().white().bold();
@@ -122,10 +117,10 @@ pub fn render_danger_zone(
     let padding = 2;
 
     // Calculate widths
-    let title_width = UnicodeWidthStr::width(title_text);
+    let title_width = text::display_width(title_text);
     let max_content_width = lines
         .iter()
-        .map(|l| UnicodeWidthStr::width(*l))
+        .map(|l| text::display_width(l))
         .max()
         .unwrap_or(0);
     let max_width = title_width.max(max_content_width);
@@ -138,7 +133,7 @@ pub fn render_danger_zone(
     } else {
         Duration::ZERO
     };
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
 
     // Top border
     let top_border = format!(
@@ -186,7 +181,7 @@ pub fn render_danger_zone(
 
     // Content lines
     for line in &lines {
-        let content_width = UnicodeWidthStr::width(*line);
+        let content_width = text::display_width(line);
         let total_padding = box_width - content_width;
         let left_padding = padding;
         let right_padding = total_padding - left_padding;
@@ -222,95 +217,101 @@ pub fn render_danger_zone(
 
 /// Render a styled box with optional animation
 /// animation_time_ms: total animation duration in milliseconds (delay is calculated per line)
-pub fn render_animated(
-    message: &str,
-    style: &str,
-    border: &str,
-    emoji: Option<&str>,
-    animate: bool,
-    animation_time_ms: u64,
-) {
-    let borders = BorderChars::get(border);
-    let color_style = get_style(style);
-    let emoji_str = emoji.or_else(|| get_default_emoji(style));
-    let lines: Vec<&str> = message.lines().collect();
-    let padding = 2;
-    let mut max_width = 0;
-
-    for line in &lines {
-        let mut line_width = UnicodeWidthStr::width(*line);
-        if emoji_str.is_some() && lines.iter().position(|&l| l == *line) == Some(0) {
-            line_width += 2;
-        }
-        max_width = max_width.max(line_width);
+///
+/// Without an explicit `max_width`, content wraps at the terminal width.
+pub fn render_animated(message: &str, options: &BoxOptions, animate: bool, animation_time_ms: u64) {
+    let animate = policy::gate(animate);
+    let mut options = *options;
+    if options.max_width.is_none() {
+        // Some ptys report a zero size; treat that as unknown rather than 0 columns
+        options.max_width = crossterm::terminal::size()
+            .ok()
+            .map(|(w, _)| w as usize)
+            .filter(|w| *w > 0);
     }
+    let ansi = box_doc(message, &options).to_ansi();
+    let lines: Vec<&str> = ansi.lines().collect();
 
-    let box_width = max_width + (padding * 2);
-    let top_border = format!(
-        "{}{}{}",
-        borders.top_left,
-        borders.horizontal.repeat(box_width),
-        borders.top_right
-    );
-
-    // Calculate delay per line: total_time / (lines + 2 borders)
-    let total_elements = lines.len() + 2; // content lines + top + bottom border
-    let delay = if animate && total_elements > 0 {
-        Duration::from_millis(animation_time_ms / total_elements as u64)
+    // Calculate delay per line: total_time / (content lines + 2 borders)
+    let delay = if animate && !lines.is_empty() {
+        Duration::from_millis(animation_time_ms / lines.len() as u64)
     } else {
         Duration::ZERO
     };
-    let mut stdout = stdout();
-
-    // Print top border
-    println!("{}", top_border.style(color_style));
-    if animate {
-        stdout.flush().unwrap();
-        thread::sleep(delay);
-    }
-
-    for (idx, line) in lines.iter().enumerate() {
-        let mut content = String::new();
-        if idx == 0 {
-            if let Some(emoji) = emoji_str {
-                content.push_str(emoji);
-                content.push(' ');
-            }
-        }
-        content.push_str(line);
-        let content_width = UnicodeWidthStr::width(content.as_str());
-        let total_padding = box_width - content_width;
-        let left_padding = padding;
-        let right_padding = total_padding - left_padding;
-        let formatted_line = format!(
-            "{}{}{}{:width$}{}",
-            borders.vertical,
-            " ".repeat(left_padding),
-            content,
-            "",
-            borders.vertical,
-            width = right_padding
-        );
+    let mut stdout = color::stdout();
 
-        println!("{}", formatted_line.style(color_style));
+    for line in &lines {
+        println!("{}", line);
         if animate {
             stdout.flush().unwrap();
             thread::sleep(delay);
         }
     }
+}
 
-    let bottom_border = format!(
-        "{}{}{}",
-        borders.bottom_left,
-        borders.horizontal.repeat(box_width),
-        borders.bottom_right
-    );
+/// The box as a [`Doc`], for printing or turning into HTML or SVG
+///
+/// Content that carries its own ANSI colors (piped from another termgfx
+/// command, say) keeps them between the styled borders.
+pub fn box_doc(message: &str, options: &BoxOptions) -> Doc {
+    let style = get_style(options.style);
+    profile::time(Phase::Layout, || {
+        let content: Vec<Vec<render::Span>> = content_lines(message, options)
+            .into_iter()
+            .map(|line| {
+                if line.contains('\x1b') {
+                    Doc::from_ansi(&line).lines()[0].clone()
+                } else {
+                    vec![render::Span { text: line, style }]
+                }
+            })
+            .collect();
+        boxed::framed(
+            &content,
+            &Frame {
+                border: options.border,
+                style,
+                title: options.title,
+                footer: options.footer,
+                title_align: options.title_align,
+                padding: options.padding,
+                max_width: options.max_width,
+            },
+        )
+    })
+}
 
-    println!("{}", bottom_border.style(color_style));
-    if animate {
-        stdout.flush().unwrap();
-        thread::sleep(delay);
+/// Message lines with the emoji prefix, tabs expanded and long lines wrapped
+fn content_lines(message: &str, options: &BoxOptions) -> Vec<String> {
+    let emoji = options.emoji.or_else(|| get_default_emoji(options.style));
+    let wrap_at = options
+        .max_width
+        .map(|w| w.saturating_sub(2 + options.padding * 2).max(1));
+
+    let mut lines = Vec::new();
+    for (idx, line) in message.lines().enumerate() {
+        let mut text = line.replace('\t', "    ");
+        if idx == 0 {
+            if let Some(emoji) = emoji {
+                text = format!("{} {}", emoji, text);
+            }
+        }
+        match wrap_at {
+            // Lines that already fit keep their indentation and spacing
+            Some(limit) if text::display_width(&text) > limit => {
+                if text.contains('\x1b') {
+                    lines.extend(layout::wrap_ansi(&text, limit));
+                } else {
+                    lines.extend(width::wrap_words(&text, limit));
+                }
+            }
+            _ => lines.push(text),
+        }
     }
+    if lines.is_empty() {
+        lines.push(emoji.map(String::from).unwrap_or_default());
+    }
+    lines
 }
 
 #[cfg(test)]
@@ -318,31 +319,141 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_border_chars_single() {
-        let borders = BorderChars::get("single");
-        assert_eq!(borders.top_left, "┌");
-        assert_eq!(borders.horizontal, "─");
+    fn test_get_default_emoji() {
+        assert_eq!(get_default_emoji("success"), Some("✓"));
+        assert_eq!(get_default_emoji("warning"), Some("⚠"));
+        assert_eq!(get_default_emoji("danger"), Some("🚨"));
+    }
+
+    fn ansi_lines(message: &str, options: &BoxOptions) -> Vec<String> {
+        box_doc(message, options)
+            .to_ansi()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    fn plain_lines(message: &str, options: &BoxOptions) -> Vec<String> {
+        ansi_lines(message, options)
+            .iter()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect()
     }
 
     #[test]
-    fn test_border_chars_double() {
-        let borders = BorderChars::get("double");
-        assert_eq!(borders.top_left, "╔");
-        assert_eq!(borders.horizontal, "═");
+    fn test_box_lines_multiline_alignment() {
+        let options = BoxOptions {
+            style: "plain",
+            ..BoxOptions::default()
+        };
+        let lines = plain_lines("one\nthree 日本", &options);
+        assert_eq!(
+            lines,
+            vec![
+                "╭──────────────╮",
+                "│  one         │",
+                "│  three 日本  │",
+                "╰──────────────╯",
+            ]
+        );
     }
 
     #[test]
-    fn test_border_chars_ascii() {
-        let borders = BorderChars::get("ascii");
-        assert_eq!(borders.top_left, "+");
-        assert_eq!(borders.horizontal, "-");
+    fn test_box_lines_wraps_to_max_width() {
+        let options = BoxOptions {
+            style: "plain",
+            padding: 1,
+            max_width: Some(12),
+            ..BoxOptions::default()
+        };
+        let lines = plain_lines("the quick brown fox", &options);
+        assert_eq!(lines[1], "│ the   │");
+        assert_eq!(lines[2], "│ quick │");
+        for line in &lines {
+            assert!(text::display_width(line) <= 12);
+        }
     }
 
     #[test]
-    fn test_get_default_emoji() {
-        assert_eq!(get_default_emoji("success"), Some("✓"));
-        assert_eq!(get_default_emoji("warning"), Some("⚠"));
-        assert_eq!(get_default_emoji("danger"), Some("🚨"));
+    fn test_box_lines_title_in_top_border() {
+        let options = BoxOptions {
+            style: "plain",
+            title: Some("Deploy"),
+            ..BoxOptions::default()
+        };
+        let lines = plain_lines("ok", &options);
+        assert_eq!(lines[0], "╭─ Deploy ─╮");
+        assert_eq!(
+            text::display_width(&lines[1]),
+            text::display_width(&lines[0])
+        );
+    }
+
+    #[test]
+    fn test_title_and_footer_alignment() {
+        let mut options = BoxOptions {
+            style: "plain",
+            title: Some("Deploy"),
+            footer: Some("v1.2"),
+            padding: 6,
+            ..BoxOptions::default()
+        };
+        let lines = plain_lines("ok", &options);
+        assert_eq!(lines[0], "╭─ Deploy ─────╮");
+        assert_eq!(lines[2], "╰─ v1.2 ───────╯");
+
+        options.title_align = TitleAlign::Right;
+        let lines = plain_lines("ok", &options);
+        assert_eq!(lines[0], "╭───── Deploy ─╮");
+        assert_eq!(lines[2], "╰─────── v1.2 ─╯");
+    }
+
+    #[test]
+    fn test_footer_widens_box() {
+        let options = BoxOptions {
+            style: "plain",
+            footer: Some("release 2024"),
+            padding: 1,
+            ..BoxOptions::default()
+        };
+        let lines = plain_lines("x", &options);
+        assert_eq!(lines[2], "╰─ release 2024 ─╯");
+        assert_eq!(
+            text::display_width(&lines[1]),
+            text::display_width(&lines[2])
+        );
+    }
+
+    #[test]
+    fn test_box_lines_keep_ansi_content_intact() {
+        let options = BoxOptions {
+            style: "success",
+            padding: 1,
+            ..BoxOptions::default()
+        };
+        let lines = ansi_lines("\x1b[31mred\x1b[0m\nplain", &options);
+        let plain = plain_lines("\x1b[31mred\x1b[0m\nplain", &options);
+        assert!(lines[1].contains("\x1b[31mred\x1b[0m"));
+        // The right border is re-styled after the content's reset
+        assert!(lines[1].contains("red\x1b[0m\x1b[1;92m"));
+        assert_eq!(plain[1].chars().count(), plain[2].chars().count());
+    }
+
+    #[test]
+    fn test_title_align_from_name() {
+        assert_eq!(TitleAlign::from_name("CENTER"), Some(TitleAlign::Center));
+        assert_eq!(TitleAlign::from_name("right"), Some(TitleAlign::Right));
+        assert_eq!(TitleAlign::from_name("other"), None);
+    }
+
+    #[test]
+    fn test_box_lines_expands_tabs() {
+        let options = BoxOptions {
+            style: "plain",
+            padding: 0,
+            ..BoxOptions::default()
+        };
+        assert_eq!(plain_lines("\tx", &options)[1], "│    x│");
     }
 
     #[test]
@@ -0,0 +1,110 @@
+use crate::output::gradient::{self, Gradient};
+use owo_colors::{OwoColorize, Style};
+
+/// How `termgfx text` styles its string
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextOptions {
+    /// Color sweep across each line; `None` leaves the terminal's color
+    pub gradient: Option<Gradient>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// Spaces inserted between characters
+    pub spacing: usize,
+}
+
+pub fn render(text: &str, options: &TextOptions) {
+    for line in styled_lines(text, options) {
+        println!("{}", line);
+    }
+}
+
+/// Styled output lines; every input line gets the full gradient
+pub fn styled_lines(text: &str, options: &TextOptions) -> Vec<String> {
+    let mut style = Style::new();
+    if options.bold {
+        style = style.bold();
+    }
+    if options.italic {
+        style = style.italic();
+    }
+    if options.underline {
+        style = style.underline();
+    }
+    let has_style = options.bold || options.italic || options.underline;
+
+    text.lines()
+        .map(|line| {
+            let spaced = letter_spaced(line, options.spacing);
+            let painted = match options.gradient {
+                Some(g) => gradient::paint(&spaced, g),
+                None => spaced,
+            };
+            if has_style && !painted.is_empty() {
+                painted.style(style).to_string()
+            } else {
+                painted
+            }
+        })
+        .collect()
+}
+
+fn letter_spaced(line: &str, spacing: usize) -> String {
+    if spacing == 0 {
+        return line.to_string();
+    }
+    let gap = " ".repeat(spacing);
+    line.chars()
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .join(&gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn test_plain_text_passes_through() {
+        let lines = styled_lines("hello", &TextOptions::default());
+        assert_eq!(lines, vec!["hello"]);
+    }
+
+    #[test]
+    fn test_letter_spacing() {
+        let options = TextOptions {
+            spacing: 2,
+            ..TextOptions::default()
+        };
+        assert_eq!(styled_lines("abc", &options), vec!["a  b  c"]);
+    }
+
+    #[test]
+    fn test_attributes_wrap_the_gradient() {
+        let options = TextOptions {
+            gradient: Some(Gradient::Rainbow),
+            bold: true,
+            underline: true,
+            ..TextOptions::default()
+        };
+        let line = &styled_lines("Hi", &options)[0];
+        assert!(line.starts_with("\x1b[1;4m"));
+        assert!(line.ends_with("\x1b[0m"));
+        assert!(line.contains("\x1b[91mH"));
+        assert!(line.contains("\x1b[95mi"));
+        assert_eq!(text::display_width(line), 2);
+    }
+
+    #[test]
+    fn test_each_line_gets_the_full_gradient() {
+        let options = TextOptions {
+            gradient: Some(Gradient::RedOrange),
+            ..TextOptions::default()
+        };
+        let lines = styled_lines("ab\ncd", &options);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("\x1b[91mc"));
+        assert!(lines[1].contains("\x1b[93md"));
+    }
+}
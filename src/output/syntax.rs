@@ -0,0 +1,425 @@
+//! Small built-in syntax highlighter shared by `code` and `md`
+
+use owo_colors::OwoColorize;
+
+/// Tokenizer rules for one language
+struct LangSpec {
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is",
+    "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while",
+    "with", "yield",
+];
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+const GO_KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "false",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "nil",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "true",
+    "type",
+    "var",
+];
+const C_KEYWORDS: &[&str] = &[
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "if",
+    "include",
+    "int",
+    "long",
+    "namespace",
+    "new",
+    "nullptr",
+    "private",
+    "public",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "true",
+    "typedef",
+    "union",
+    "unsigned",
+    "using",
+    "void",
+    "volatile",
+    "while",
+    "abstract",
+    "extends",
+    "final",
+    "implements",
+    "import",
+    "package",
+    "null",
+    "throws",
+    "try",
+    "catch",
+    "finally",
+];
+const SHELL_KEYWORDS: &[&str] = &[
+    "case", "do", "done", "echo", "elif", "else", "esac", "exit", "export", "fi", "for",
+    "function", "if", "in", "local", "return", "then", "until", "while",
+];
+const SQL_KEYWORDS: &[&str] = &[
+    "and", "as", "by", "create", "delete", "from", "group", "insert", "into", "join", "left",
+    "limit", "not", "null", "on", "or", "order", "select", "set", "table", "update", "values",
+    "where", "AND", "AS", "BY", "CREATE", "DELETE", "FROM", "GROUP", "INSERT", "INTO", "JOIN",
+    "LEFT", "LIMIT", "NOT", "NULL", "ON", "OR", "ORDER", "SELECT", "SET", "TABLE", "UPDATE",
+    "VALUES", "WHERE",
+];
+const DATA_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+fn spec(lang: &str) -> Option<LangSpec> {
+    let spec = match lang.to_lowercase().as_str() {
+        "rust" | "rs" => LangSpec {
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+            keywords: RUST_KEYWORDS,
+        },
+        "python" | "py" => LangSpec {
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+            keywords: PYTHON_KEYWORDS,
+        },
+        "javascript" | "js" | "typescript" | "ts" | "jsx" | "tsx" => LangSpec {
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\'', '`'],
+            keywords: JS_KEYWORDS,
+        },
+        "go" => LangSpec {
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '`'],
+            keywords: GO_KEYWORDS,
+        },
+        "c" | "h" | "cpp" | "c++" | "hpp" | "cc" | "java" | "cs" | "csharp" | "kotlin" | "kt"
+        | "swift" => LangSpec {
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+            keywords: C_KEYWORDS,
+        },
+        "sh" | "bash" | "zsh" | "shell" => LangSpec {
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+            keywords: SHELL_KEYWORDS,
+        },
+        "sql" => LangSpec {
+            line_comment: Some("--"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['\''],
+            keywords: SQL_KEYWORDS,
+        },
+        "toml" | "yaml" | "yml" => LangSpec {
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+            keywords: DATA_KEYWORDS,
+        },
+        "ruby" | "rb" | "perl" | "r" => LangSpec {
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+            keywords: DATA_KEYWORDS,
+        },
+        "lua" | "haskell" | "hs" => LangSpec {
+            line_comment: Some("--"),
+            block_comment: None,
+            quotes: &['"'],
+            keywords: DATA_KEYWORDS,
+        },
+        "json" => LangSpec {
+            line_comment: None,
+            block_comment: None,
+            quotes: &['"'],
+            keywords: DATA_KEYWORDS,
+        },
+        _ => return None,
+    };
+    Some(spec)
+}
+
+/// Guess a language name from a file extension
+pub fn language_for_path(path: &str) -> Option<&'static str> {
+    let ext = std::path::Path::new(path).extension()?.to_str()?;
+    let lang = match ext.to_lowercase().as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "hpp" | "cxx" => "cpp",
+        "java" => "java",
+        "kt" => "kotlin",
+        "swift" => "swift",
+        "cs" => "csharp",
+        "sh" | "bash" | "zsh" => "sh",
+        "sql" => "sql",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "json" => "json",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+/// Line-by-line highlighter that carries block comments across lines
+pub struct Highlighter {
+    spec: Option<LangSpec>,
+    in_block_comment: bool,
+}
+
+impl Highlighter {
+    /// Highlighter for `lang`; unknown languages pass text through unchanged
+    pub fn new(lang: &str) -> Self {
+        Self {
+            spec: spec(lang),
+            in_block_comment: false,
+        }
+    }
+
+    /// Highlight a single line of source
+    pub fn line(&mut self, line: &str) -> String {
+        let Some(spec) = &self.spec else {
+            return line.to_string();
+        };
+
+        let mut out = String::new();
+        let mut rest = line;
+
+        while !rest.is_empty() {
+            if self.in_block_comment {
+                let (_, end) = spec
+                    .block_comment
+                    .expect("block comment state without markers");
+                let (comment, tail) = match rest.find(end) {
+                    Some(pos) => {
+                        self.in_block_comment = false;
+                        rest.split_at(pos + end.len())
+                    }
+                    None => (rest, ""),
+                };
+                out.push_str(&comment.bright_black().italic().to_string());
+                rest = tail;
+                continue;
+            }
+
+            if let Some(marker) = spec.line_comment {
+                if rest.starts_with(marker) {
+                    out.push_str(&rest.bright_black().italic().to_string());
+                    break;
+                }
+            }
+            if let Some((start, _)) = spec.block_comment {
+                if rest.starts_with(start) {
+                    self.in_block_comment = true;
+                    out.push_str(&start.bright_black().italic().to_string());
+                    rest = &rest[start.len()..];
+                    continue;
+                }
+            }
+
+            let ch = rest.chars().next().unwrap();
+            if spec.quotes.contains(&ch) {
+                let len = string_len(rest, ch);
+                out.push_str(&(&rest[..len]).green().to_string());
+                rest = &rest[len..];
+            } else if ch.is_ascii_digit() {
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+                    .unwrap_or(rest.len());
+                out.push_str(&(&rest[..len]).yellow().to_string());
+                rest = &rest[len..];
+            } else if ch.is_alphabetic() || ch == '_' {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                if spec.keywords.contains(&word) {
+                    out.push_str(&word.magenta().bold().to_string());
+                } else if word.starts_with(char::is_uppercase) {
+                    out.push_str(&word.cyan().to_string());
+                } else {
+                    out.push_str(word);
+                }
+                rest = &rest[len..];
+            } else {
+                out.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+        out
+    }
+}
+
+/// Byte length of the string literal at the start of `text`, honoring escapes
+fn string_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (idx, ch) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return idx + ch.len_utf8();
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(text: &str) -> String {
+        regex::Regex::new(r"\x1b\[[0-9;]*m")
+            .unwrap()
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    #[test]
+    fn test_highlight_preserves_text() {
+        let mut h = Highlighter::new("rust");
+        let line = r#"let s = "a \" b"; // done"#;
+        assert_eq!(strip(&h.line(line)), line);
+    }
+
+    #[test]
+    fn test_keywords_are_styled() {
+        let mut h = Highlighter::new("python");
+        assert_ne!(h.line("def f():"), "def f():");
+    }
+
+    #[test]
+    fn test_unknown_language_is_plain() {
+        let mut h = Highlighter::new("brainfuck");
+        assert_eq!(h.line("+++[>+<-]"), "+++[>+<-]");
+    }
+
+    #[test]
+    fn test_block_comment_spans_lines() {
+        let mut h = Highlighter::new("c");
+        h.line("int x; /* start");
+        assert!(h.in_block_comment);
+        let line = h.line("still comment */ int y;");
+        assert!(!h.in_block_comment);
+        assert_eq!(strip(&line), "still comment */ int y;");
+    }
+
+    #[test]
+    fn test_string_len_handles_escapes() {
+        assert_eq!(string_len(r#""a\"b" rest"#, '"'), 6);
+        assert_eq!(string_len(r#""open"#, '"'), 5);
+    }
+
+    #[test]
+    fn test_language_for_path() {
+        assert_eq!(language_for_path("src/main.rs"), Some("rust"));
+        assert_eq!(language_for_path("script.PY"), Some("python"));
+        assert_eq!(language_for_path("Makefile"), None);
+    }
+}
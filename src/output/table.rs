@@ -1,152 +1,66 @@
-use owo_colors::OwoColorize;
+use crate::animation::policy;
+use crate::image::Thumbnail;
+use crate::output::color;
+use crate::output::profile::{self, Phase};
+use crate::output::table_sort::{self, TableSort};
+use crate::output::{input, layout, query, width};
+use crate::render::table::{grid, Alignment, Grid, Row};
+use crate::render::{Color, Doc, Style};
+use crate::text;
 use serde_json::Value;
-use std::io::{self, Read, Write};
+use std::io::Write;
 use std::thread;
 use std::time::Duration;
 
-#[derive(Clone)]
-pub enum BorderStyle {
-    Single,
-    Double,
-    Rounded,
-    None,
-}
-
-impl BorderStyle {
-    fn from_str(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "double" => BorderStyle::Double,
-            "rounded" => BorderStyle::Rounded,
-            "none" => BorderStyle::None,
-            _ => BorderStyle::Single,
-        }
-    }
-
-    fn chars(&self) -> BorderChars {
-        match self {
-            BorderStyle::Single => BorderChars {
-                top_left: "┌",
-                top_right: "┐",
-                bottom_left: "└",
-                bottom_right: "┘",
-                horizontal: "─",
-                vertical: "│",
-                cross: "┼",
-                t_down: "┬",
-                t_up: "┴",
-                t_right: "├",
-                t_left: "┤",
-            },
-            BorderStyle::Double => BorderChars {
-                top_left: "╔",
-                top_right: "╗",
-                bottom_left: "╚",
-                bottom_right: "╝",
-                horizontal: "═",
-                vertical: "║",
-                cross: "╬",
-                t_down: "╦",
-                t_up: "╩",
-                t_right: "╠",
-                t_left: "╣",
-            },
-            BorderStyle::Rounded => BorderChars {
-                top_left: "╭",
-                top_right: "╮",
-                bottom_left: "╰",
-                bottom_right: "╯",
-                horizontal: "─",
-                vertical: "│",
-                cross: "┼",
-                t_down: "┬",
-                t_up: "┴",
-                t_right: "├",
-                t_left: "┤",
-            },
-            BorderStyle::None => BorderChars {
-                top_left: " ",
-                top_right: " ",
-                bottom_left: " ",
-                bottom_right: " ",
-                horizontal: " ",
-                vertical: " ",
-                cross: " ",
-                t_down: " ",
-                t_up: " ",
-                t_right: " ",
-                t_left: " ",
-            },
-        }
-    }
-}
-
-struct BorderChars {
-    top_left: &'static str,
-    top_right: &'static str,
-    bottom_left: &'static str,
-    bottom_right: &'static str,
-    horizontal: &'static str,
-    vertical: &'static str,
-    cross: &'static str,
-    t_down: &'static str,
-    t_up: &'static str,
-    t_right: &'static str,
-    t_left: &'static str,
-}
-
-#[derive(Clone)]
-pub enum Alignment {
-    Left,
-    Right,
-    Center,
-}
-
-impl Alignment {
-    fn from_str(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "right" => Alignment::Right,
-            "center" => Alignment::Center,
-            _ => Alignment::Left,
-        }
-    }
-
-    fn align(&self, text: &str, width: usize) -> String {
-        let text_width = unicode_width::UnicodeWidthStr::width(text);
-        if text_width >= width {
-            return text.to_string();
-        }
-
-        let padding = width - text_width;
-        match self {
-            Alignment::Left => format!("{}{}", text, " ".repeat(padding)),
-            Alignment::Right => format!("{}{}", " ".repeat(padding), text),
-            Alignment::Center => {
-                let left_pad = padding / 2;
-                let right_pad = padding - left_pad;
-                format!("{}{}{}", " ".repeat(left_pad), text, " ".repeat(right_pad))
-            }
-        }
-    }
-}
+/// Widest an `img(path)` cell's thumbnail is drawn, in columns
+const THUMBNAIL_COLS: u32 = 8;
 
 pub struct TableOptions {
-    pub border: BorderStyle,
+    /// Border name, see [`BorderChars::get`](crate::render::table::BorderChars::get)
+    pub border: String,
     pub alignment: Alignment,
     pub header_color: bool,
     pub row_striping: bool,
     pub max_width: Option<usize>,
+    /// Wrap cells wider than `max_width` onto more lines instead of cutting them
+    pub wrap: bool,
+    /// Summary row drawn under the data
+    pub footer: Option<Vec<String>>,
     pub animate: bool,
     pub animation_time_ms: u64,
 }
 
+impl TableOptions {
+    /// Options for `termgfx table`: the view's cell layout and footer, and
+    /// the named border and alignment
+    pub fn for_view(
+        view: &TableView,
+        headers: &[String],
+        rows: &[Vec<String>],
+        border: &str,
+        alignment: &str,
+    ) -> Self {
+        Self {
+            border: border.to_string(),
+            alignment: Alignment::from_name(alignment),
+            max_width: view.max_col_width,
+            wrap: view.wrap,
+            footer: view.footer.map(|spec| footer_row(spec, headers, rows)),
+            ..Default::default()
+        }
+    }
+}
+
 impl Default for TableOptions {
     fn default() -> Self {
         Self {
-            border: BorderStyle::Single,
+            border: "single".to_string(),
             alignment: Alignment::Left,
             header_color: true,
             row_striping: true,
             max_width: None,
+            wrap: false,
+            footer: None,
             animate: false,
             animation_time_ms: 500,
         }
@@ -161,48 +75,200 @@ pub fn render(
     border: &str,
     alignment: &str,
 ) {
-    render_animated(headers_str, rows_str, file, border, alignment, false, 500);
+    render_animated(
+        headers_str,
+        rows_str,
+        file,
+        None,
+        None,
+        &TableView::default(),
+        border,
+        alignment,
+        false,
+        500,
+    );
+}
+
+/// Which rows and columns are drawn, and how cells are laid out
+#[derive(Default)]
+pub struct TableView<'a> {
+    /// Reorder rows by a column
+    pub sort: Option<TableSort>,
+    /// Columns to show, in this order, by header name or 1-based index
+    pub columns: Option<&'a str>,
+    /// Columns to leave out, named the same way
+    pub hide: Option<&'a str>,
+    /// Cut (or with `wrap`, wrap) cells wider than this
+    pub max_col_width: Option<usize>,
+    pub wrap: bool,
+    /// Summary row, see [`footer_row`]
+    pub footer: Option<&'a str>,
 }
 
 /// Render table with optional animation
 /// animation_time_ms: total animation duration in milliseconds (delay is calculated per row)
+/// file: CSV or TSV (the delimiter is detected), or JSON; `-` reads stdin,
+/// which is also read when no other source is given
+/// json: a JSON file (array of objects), columns taken from the keys
+/// query: jq filter applied to JSON input; makes `file` JSON instead of CSV
+#[allow(clippy::too_many_arguments)]
 pub fn render_animated(
     headers_str: Option<&str>,
     rows_str: Option<&str>,
     file: Option<&str>,
+    json: Option<&str>,
+    query: Option<&str>,
+    view: &TableView,
     border: &str,
     alignment: &str,
     animate: bool,
     animation_time_ms: u64,
 ) {
-    let border_style = BorderStyle::from_str(border);
-    let align = Alignment::from_str(alignment);
+    let (headers, rows) = match load(headers_str, rows_str, file, json, query, view) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
     let options = TableOptions {
-        border: border_style,
-        alignment: align,
         animate,
         animation_time_ms,
-        ..Default::default()
+        ..TableOptions::for_view(view, &headers, &rows, border, alignment)
     };
+    render_table(&headers, &rows, &options);
+}
 
+/// Headers and rows from the first source given (see [`render_animated`]),
+/// sorted, with the view's columns picked
+pub fn load(
+    headers_str: Option<&str>,
+    rows_str: Option<&str>,
+    file: Option<&str>,
+    json: Option<&str>,
+    query: Option<&str>,
+    view: &TableView,
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
     // Try to get data from different sources
-    let (headers, rows) = if let (Some(h), Some(r)) = (headers_str, rows_str) {
-        // Inline data via --headers and --rows
-        parse_inline_data(h, r)
-    } else if let Some(filepath) = file {
-        // From file (CSV)
-        parse_csv_file(filepath)
-    } else {
-        // From stdin (JSON)
-        parse_json_stdin()
-    };
+    let (headers, mut rows) = profile::time(Phase::Parse, || {
+        if let (Some(h), Some(r)) = (headers_str, rows_str) {
+            // Inline data via --headers and --rows
+            parse_inline_data(h, r)
+        } else {
+            let source = json.or(file).unwrap_or(input::STDIN);
+            parse_source(source, json.is_some() || query.is_some(), query)
+        }
+    });
 
     if headers.is_empty() {
-        eprintln!("Error: No data to display");
-        return;
+        return Err("No data to display".to_string());
     }
 
-    render_table(&headers, &rows, &options);
+    // Sorted before columns are picked so rows can be ordered by a hidden one
+    if let Some(sort) = &view.sort {
+        sort.apply(&headers, &mut rows)?;
+    }
+
+    select_columns(headers, rows, view.columns, view.hide)
+}
+
+/// Keep the `columns` listed, in that order, then drop the `hide` ones;
+/// both are comma-separated header names or 1-based indexes
+pub fn select_columns(
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    columns: Option<&str>,
+    hide: Option<&str>,
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let lookup = |list: &str| -> Result<Vec<usize>, String> {
+        list.split(',')
+            .map(|name| {
+                table_sort::find_column(&headers, name).ok_or_else(|| {
+                    format!(
+                        "Unknown column '{}' (use a header name or 1-{})",
+                        name.trim(),
+                        headers.len()
+                    )
+                })
+            })
+            .collect()
+    };
+    let mut keep = match columns {
+        Some(list) => lookup(list)?,
+        None => (0..headers.len()).collect(),
+    };
+    if let Some(list) = hide {
+        let hidden = lookup(list)?;
+        keep.retain(|idx| !hidden.contains(idx));
+    }
+    if keep.is_empty() {
+        return Err("Every column is hidden".to_string());
+    }
+
+    let pick = |row: &[String]| -> Vec<String> {
+        keep.iter()
+            .map(|&idx| row.get(idx).cloned().unwrap_or_default())
+            .collect()
+    };
+    Ok((pick(&headers), rows.iter().map(|row| pick(row)).collect()))
+}
+
+/// Cells of a `--footer` row, comma-separated like `--rows`
+///
+/// `{sum}`, `{avg}`, `{min}` and `{max}` in a cell are worked out from the
+/// numbers in its column and `{count}` from the non-empty cells.
+pub fn footer_row(spec: &str, headers: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    let mut cells: Vec<String> = spec.split(',').map(|c| c.trim().to_string()).collect();
+    cells.resize(headers.len(), String::new());
+    for (col, cell) in cells.iter_mut().enumerate() {
+        if !cell.contains('{') {
+            continue;
+        }
+        let column: Vec<String> = rows
+            .iter()
+            .filter_map(|row| row.get(col))
+            .map(|c| text::strip_ansi(c).trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect();
+        let numbers: Vec<f64> = column
+            .iter()
+            .filter_map(|c| table_sort::parse_number(c))
+            .collect();
+        let sum: f64 = numbers.iter().sum();
+        let aggregate = |value: Option<f64>| value.map(format_number).unwrap_or_default();
+        *cell = cell
+            .replace(
+                "{sum}",
+                &aggregate(Some(sum).filter(|_| !numbers.is_empty())),
+            )
+            .replace(
+                "{avg}",
+                &aggregate(Some(sum / numbers.len() as f64).filter(|_| !numbers.is_empty())),
+            )
+            .replace(
+                "{min}",
+                &aggregate(numbers.iter().copied().reduce(f64::min)),
+            )
+            .replace(
+                "{max}",
+                &aggregate(numbers.iter().copied().reduce(f64::max)),
+            )
+            .replace("{count}", &column.len().to_string());
+    }
+    cells
+}
+
+/// Whole numbers without a fraction, others to two decimal places
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{:.0}", n)
+    } else {
+        format!("{:.2}", n)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
 }
 
 fn parse_inline_data(headers_str: &str, rows_str: &str) -> (Vec<String>, Vec<Vec<String>>) {
@@ -219,43 +285,91 @@ fn parse_inline_data(headers_str: &str, rows_str: &str) -> (Vec<String>, Vec<Vec
     (headers, rows)
 }
 
-fn parse_csv_file(filepath: &str) -> (Vec<String>, Vec<Vec<String>>) {
-    let content = match std::fs::read_to_string(filepath) {
-        Ok(c) => c,
+/// Headers and rows from a file or stdin: JSON when `json` is set or the
+/// text starts like JSON, otherwise CSV or TSV
+fn parse_source(source: &str, json: bool, query: Option<&str>) -> (Vec<String>, Vec<Vec<String>>) {
+    let content = match input::read_text(source) {
+        Ok(content) => content,
         Err(e) => {
-            eprintln!("Error reading file: {}", e);
+            eprintln!("Error reading {}: {}", input::display_name(source), e);
             return (vec![], vec![]);
         }
     };
+    if json || content.trim_start().starts_with(['[', '{']) {
+        parse_json(&content, query)
+    } else {
+        delimited_rows(&content)
+    }
+}
 
-    let mut lines = content.lines();
-    let headers: Vec<String> = lines
-        .next()
-        .map(|line| line.split(',').map(|s| s.trim().to_string()).collect())
-        .unwrap_or_default();
-
-    let rows: Vec<Vec<String>> = lines
-        .map(|line| line.split(',').map(|s| s.trim().to_string()).collect())
-        .collect();
-
+/// CSV, or TSV when the header line holds a tab
+fn delimited_rows(content: &str) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return (vec![], vec![]);
+    };
+    let delimiter = if header.contains('\t') { '\t' } else { ',' };
+    let headers = split_record(header, delimiter);
+    let rows = lines.map(|line| split_record(line, delimiter)).collect();
     (headers, rows)
 }
 
-fn parse_json_stdin() -> (Vec<String>, Vec<Vec<String>>) {
-    let mut buffer = String::new();
-    if io::stdin().read_to_string(&mut buffer).is_err() {
-        eprintln!("Error reading from stdin");
-        return (vec![], vec![]);
+/// Fields of one CSV/TSV line; a field in double quotes can hold the
+/// delimiter, and `""` inside it is a quote
+fn split_record(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
     }
+    fields.push(field.trim().to_string());
+    fields
+}
 
-    let json: Value = match serde_json::from_str(&buffer) {
+fn parse_json(content: &str, query: Option<&str>) -> (Vec<String>, Vec<Vec<String>>) {
+    let json = match serde_json::from_str(content) {
         Ok(v) => v,
         Err(e) => {
             eprintln!("Error parsing JSON: {}", e);
             return (vec![], vec![]);
         }
     };
+    let json = match query {
+        Some(q) => match query::apply(json, q) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return (vec![], vec![]);
+            }
+        },
+        None => json,
+    };
+    json_rows(json)
+}
 
+/// Headers and rows from an array of objects (or a single object)
+fn json_rows(json: Value) -> (Vec<String>, Vec<Vec<String>>) {
+    let json = match json {
+        Value::Object(_) => Value::Array(vec![json]),
+        other => other,
+    };
     let array = match json.as_array() {
         Some(arr) => arr,
         None => {
@@ -277,7 +391,15 @@ fn parse_json_stdin() -> (Vec<String>, Vec<Vec<String>>) {
         }
     };
 
-    let headers: Vec<String> = first_obj.keys().map(|k| k.to_string()).collect();
+    // Keys of the first object, then any new ones later objects bring
+    let mut headers: Vec<String> = first_obj.keys().map(|k| k.to_string()).collect();
+    for obj in array.iter().skip(1).filter_map(Value::as_object) {
+        for key in obj.keys() {
+            if !headers.contains(key) {
+                headers.push(key.to_string());
+            }
+        }
+    }
 
     // Extract rows
     let rows: Vec<Vec<String>> = array
@@ -305,19 +427,87 @@ fn parse_json_stdin() -> (Vec<String>, Vec<Vec<String>>) {
     (headers, rows)
 }
 
-fn render_table(headers: &[String], rows: &[Vec<String>], options: &TableOptions) {
-    let border_chars = options.border.chars();
+/// Print the table, revealing one row at a time when animated
+pub fn render_table(headers: &[String], rows: &[Vec<String>], options: &TableOptions) {
+    let lines = table_lines(headers, rows, options);
 
-    // Calculate column widths
-    let mut col_widths: Vec<usize> = headers
-        .iter()
-        .map(|h| unicode_width::UnicodeWidthStr::width(h.as_str()))
-        .collect();
+    // Calculate delay per row: total_time / number_of_rows
+    let animate = policy::gate(options.animate);
+    let delay = if animate && !rows.is_empty() {
+        Duration::from_millis(options.animation_time_ms / rows.len() as u64)
+    } else {
+        Duration::ZERO
+    };
+    let mut stdout = color::stdout();
+
+    // Top border, headers and separator come first, then one line per row
+    profile::time(Phase::Write, || {
+        for (idx, line) in lines.iter().enumerate() {
+            println!("{}", line);
+            if animate && idx >= 3 && idx < lines.len() - 1 {
+                stdout.flush().unwrap();
+                thread::sleep(delay);
+            }
+        }
+        stdout.flush().unwrap();
+    });
+}
+
+/// Build the table as styled lines (borders, header, rows) without printing
+pub fn table_lines(
+    headers: &[String],
+    rows: &[Vec<String>],
+    options: &TableOptions,
+) -> Vec<String> {
+    table_doc(headers, rows, options)
+        .to_ansi()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// The table as a [`Doc`], for printing or turning into HTML or SVG
+pub fn table_doc(headers: &[String], rows: &[Vec<String>], options: &TableOptions) -> Doc {
+    let thumbnails: Vec<Vec<Option<Thumbnail>>> = profile::time(Phase::Parse, || {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| thumbnail(cell)).collect())
+            .collect()
+    });
+    let col_widths = profile::time(Phase::Layout, || {
+        column_widths(headers, rows, &thumbnails, options)
+    });
+    profile::time(Phase::Render, || {
+        styled_doc(headers, rows, &thumbnails, options, &col_widths)
+    })
+}
+
+/// An `img(path)` cell as a one-row thumbnail; cells whose image can't be
+/// read keep their text
+fn thumbnail(cell: &str) -> Option<Thumbnail> {
+    let path = cell.trim().strip_prefix("img(")?.strip_suffix(')')?;
+    Thumbnail::load(path.trim(), THUMBNAIL_COLS, 1).ok()
+}
 
-    for row in rows {
-        for (i, cell) in row.iter().enumerate() {
+fn column_widths(
+    headers: &[String],
+    rows: &[Vec<String>],
+    thumbnails: &[Vec<Option<Thumbnail>>],
+    options: &TableOptions,
+) -> Vec<usize> {
+    let mut col_widths: Vec<usize> = headers.iter().map(|h| text::display_width(h)).collect();
+    for (i, cell) in options.footer.iter().flatten().enumerate() {
+        if i < col_widths.len() {
+            col_widths[i] = col_widths[i].max(text::display_width(cell));
+        }
+    }
+
+    for (row, thumbs) in rows.iter().zip(thumbnails) {
+        for (i, (cell, thumb)) in row.iter().zip(thumbs).enumerate() {
             if i < col_widths.len() {
-                let width = unicode_width::UnicodeWidthStr::width(cell.as_str());
+                let width = match thumb {
+                    Some(thumb) => thumb.size().0 as usize,
+                    None => text::display_width(cell),
+                };
                 col_widths[i] = col_widths[i].max(width);
             }
         }
@@ -327,127 +517,127 @@ fn render_table(headers: &[String], rows: &[Vec<String>], options: &TableOptions
     if let Some(max) = options.max_width {
         col_widths.iter_mut().for_each(|w| *w = (*w).min(max));
     }
+    col_widths
+}
 
-    // Top border
-    print_border_line(&col_widths, &border_chars, BorderLineType::Top);
-
-    // Headers
-    print!("{}", border_chars.vertical);
-    for (i, header) in headers.iter().enumerate() {
-        let width = col_widths.get(i).copied().unwrap_or(0);
-        let truncated = truncate(header, width);
-        let aligned = options.alignment.align(&truncated, width);
-
-        if options.header_color {
-            print!(" {} ", aligned.bright_cyan().bold());
-        } else {
-            print!(" {} ", aligned);
-        }
-        print!("{}", border_chars.vertical);
-    }
-    println!();
-
-    // Header separator
-    print_border_line(&col_widths, &border_chars, BorderLineType::Middle);
-
-    // Calculate delay per row: total_time / number_of_rows
-    let delay = if options.animate && !rows.is_empty() {
-        Duration::from_millis(options.animation_time_ms / rows.len() as u64)
+fn styled_doc(
+    headers: &[String],
+    rows: &[Vec<String>],
+    thumbnails: &[Vec<Option<Thumbnail>>],
+    options: &TableOptions,
+    col_widths: &[usize],
+) -> Doc {
+    let width = |i: usize| col_widths.get(i).copied().unwrap_or(0);
+    let header_style = if options.header_color {
+        Style::fg(Color::BRIGHT_CYAN).bold()
     } else {
-        Duration::ZERO
+        Style::PLAIN
     };
-    let mut stdout = io::stdout();
-
-    // Rows
-    for (row_idx, row) in rows.iter().enumerate() {
-        print!("{}", border_chars.vertical);
-        for (i, cell) in row.iter().enumerate() {
-            let width = col_widths.get(i).copied().unwrap_or(0);
-            let truncated = truncate(cell, width);
-            let aligned = options.alignment.align(&truncated, width);
-
-            if options.row_striping && row_idx % 2 == 1 {
-                print!(" {} ", aligned.truecolor(180, 180, 180));
+    let header: Vec<Row> = vec![headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| cell_doc(&cell_lines(header, width(i), options), header_style))
+        .collect()];
+
+    let body: Vec<Row> = rows
+        .iter()
+        .zip(thumbnails)
+        .enumerate()
+        .map(|(row_idx, (row, thumbs))| {
+            let style = if options.row_striping && row_idx % 2 == 1 {
+                Style::fg(Color::Rgb(180, 180, 180))
             } else {
-                print!(" {} ", aligned);
-            }
-            print!("{}", border_chars.vertical);
-        }
-        println!();
+                Style::PLAIN
+            };
+            row.iter()
+                .zip(thumbs)
+                .enumerate()
+                .map(|(i, (cell, thumb))| {
+                    // Thumbnails narrowed by --max-width fall back to the text
+                    match thumb.as_ref().filter(|t| t.size().0 as usize <= width(i)) {
+                        Some(thumb) => thumbnail_doc(&thumb.line()),
+                        None => cell_doc(&cell_lines(cell, width(i), options), style),
+                    }
+                })
+                .collect()
+        })
+        .collect();
 
-        if options.animate {
-            stdout.flush().unwrap();
-            thread::sleep(delay);
-        }
-    }
+    // Footer, set off from the rows like the header
+    let footer: Vec<Row> = options
+        .footer
+        .iter()
+        .map(|footer| {
+            footer
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    cell_doc(&cell_lines(cell, width(i), options), Style::PLAIN.bold())
+                })
+                .collect()
+        })
+        .collect();
 
-    // Bottom border
-    print_border_line(&col_widths, &border_chars, BorderLineType::Bottom);
+    let mut sections: Vec<&[Row]> = vec![&header, &body];
+    if !footer.is_empty() {
+        sections.push(&footer);
+    }
+    grid(
+        &sections,
+        col_widths,
+        &Grid {
+            border: &options.border,
+            alignment: options.alignment,
+            style: Style::PLAIN,
+        },
+    )
 }
 
-enum BorderLineType {
-    Top,
-    Middle,
-    Bottom,
+/// A thumbnail's line; Kitty and iTerm images are escapes a [`Doc`] can't
+/// style, so they pass through as they are
+fn thumbnail_doc(line: &str) -> Doc {
+    if text::strip_sgr(line).contains('\x1b') {
+        let mut doc = Doc::new();
+        doc.plain(line);
+        doc
+    } else {
+        Doc::from_ansi(line)
+    }
 }
 
-fn print_border_line(col_widths: &[usize], chars: &BorderChars, line_type: BorderLineType) {
-    match line_type {
-        BorderLineType::Top => {
-            print!("{}", chars.top_left);
-            for (i, width) in col_widths.iter().enumerate() {
-                print!("{}", chars.horizontal.repeat(width + 2));
-                if i < col_widths.len() - 1 {
-                    print!("{}", chars.t_down);
-                }
-            }
-            println!("{}", chars.top_right);
-        }
-        BorderLineType::Middle => {
-            print!("{}", chars.t_right);
-            for (i, width) in col_widths.iter().enumerate() {
-                print!("{}", chars.horizontal.repeat(width + 2));
-                if i < col_widths.len() - 1 {
-                    print!("{}", chars.cross);
-                }
-            }
-            println!("{}", chars.t_left);
+/// A cell's lines in `style`; lines with their own colors keep them
+fn cell_doc(lines: &[String], style: Style) -> Doc {
+    let mut doc = Doc::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            doc.newline();
         }
-        BorderLineType::Bottom => {
-            print!("{}", chars.bottom_left);
-            for (i, width) in col_widths.iter().enumerate() {
-                print!("{}", chars.horizontal.repeat(width + 2));
-                if i < col_widths.len() - 1 {
-                    print!("{}", chars.t_up);
-                }
-            }
-            println!("{}", chars.bottom_right);
+        if line.contains('\x1b') {
+            doc.extend(&Doc::from_ansi(line).lines()[0]);
+        } else {
+            doc.push(line, style);
         }
     }
+    doc
 }
 
-fn truncate(text: &str, max_width: usize) -> String {
-    let width = unicode_width::UnicodeWidthStr::width(text);
-    if width <= max_width {
-        return text.to_string();
+/// The lines a cell takes up: cut to `width`, or with `wrap` spread over as
+/// many lines as it needs
+fn cell_lines(cell: &str, width: usize, options: &TableOptions) -> Vec<String> {
+    if !options.wrap || text::display_width(cell) <= width {
+        return vec![truncate(cell, width)];
     }
-
-    if max_width <= 3 {
-        return "...".chars().take(max_width).collect();
+    if text::strip_ansi(cell).len() != cell.len() {
+        // Colored cells are broken anywhere so their escapes stay whole
+        layout::wrap_ansi(cell, width)
+    } else {
+        width::wrap_words(cell, width)
     }
+}
 
-    let mut result = String::new();
-    let mut current_width = 0;
-
-    for ch in text.chars() {
-        let ch_width = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
-        if current_width + ch_width + 3 > max_width {
-            break;
-        }
-        result.push(ch);
-        current_width += ch_width;
+fn truncate(text: &str, max_width: usize) -> String {
+    if max_width <= 3 && text::display_width(text) > max_width {
+        return "...".chars().take(max_width).collect();
     }
-
-    result.push_str("...");
-    result
+    text::truncate(text, max_width, "...")
 }
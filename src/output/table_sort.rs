@@ -0,0 +1,617 @@
+//! Row ordering for `termgfx table --sort`
+//!
+//! A column is compared by what its cells hold, not their raw text: numbers
+//! ("1,204", "$3.50", "12%"), human sizes ("1.2 GiB", "512K"), dates
+//! ("2024-03-01", "1 Mar 2024") and semver ("v1.10.0-rc.1"). Auto detection
+//! picks the type most non-empty cells parse as, tolerating a few placeholders
+//! such as "n/a" or "-", and falls back to text collated with the Unicode
+//! Collation Algorithm. Cells that don't parse sort after those that do, and
+//! empty cells always sort last.
+
+use crate::output::calendar::{days_from_civil, days_in_month};
+use crate::text;
+use feruca::{Collator, Locale, Tailoring};
+use std::cmp::Ordering;
+use std::env;
+
+/// Names accepted by [`SortType::from_name`]
+pub const SORT_TYPES: [&str; 6] = ["auto", "numeric", "size", "date", "semver", "string"];
+
+/// How cells of the sort column are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortType {
+    #[default]
+    Auto,
+    Numeric,
+    Size,
+    Date,
+    Semver,
+    String,
+}
+
+impl SortType {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(SortType::Auto),
+            "numeric" | "number" => Some(SortType::Numeric),
+            "size" => Some(SortType::Size),
+            "date" => Some(SortType::Date),
+            "semver" | "version" => Some(SortType::Semver),
+            "string" | "text" => Some(SortType::String),
+            _ => None,
+        }
+    }
+
+    /// The type the non-empty cells parse as, in order of specificity. Up to
+    /// one cell in five (at least one, but never half) may fail to parse;
+    /// those sort last like with an explicit type.
+    pub fn detect<'a>(cells: impl IntoIterator<Item = &'a str>) -> Self {
+        let cells: Vec<&str> = cells
+            .into_iter()
+            .map(|c| c.trim())
+            .filter(|c| !c.is_empty())
+            .collect();
+        let max_unparsed = (cells.len() / 5).max(1);
+        let mut best = (SortType::String, 0);
+        for ty in [
+            SortType::Numeric,
+            SortType::Size,
+            SortType::Date,
+            SortType::Semver,
+        ] {
+            let parsed = cells.iter().filter(|c| ty.key(c).is_some()).count();
+            let unparsed = cells.len() - parsed;
+            let tolerated = unparsed == 0 || (unparsed <= max_unparsed && parsed > unparsed);
+            if tolerated && parsed > best.1 {
+                best = (ty, parsed);
+            }
+        }
+        best.0
+    }
+
+    /// Comparable value of `cell`, `None` if it isn't of this type
+    fn key(self, cell: &str) -> Option<Key> {
+        match self {
+            SortType::Numeric => parse_number(cell).map(Key::Number),
+            SortType::Size => parse_size(cell).map(Key::Number),
+            SortType::Date => parse_date(cell).map(Key::Date),
+            SortType::Semver => parse_semver(cell).map(Key::Semver),
+            SortType::Auto | SortType::String => Some(Key::Text(cell.to_string())),
+        }
+    }
+}
+
+/// A `--sort` request: column by header name or 1-based index
+#[derive(Debug, Clone)]
+pub struct TableSort {
+    pub column: String,
+    pub sort_type: SortType,
+    pub descending: bool,
+}
+
+impl TableSort {
+    /// A `--sort` value: `COLUMN`, or `COLUMN:desc` / `COLUMN:asc`, whose
+    /// suffix wins over `descending`
+    pub fn parse(spec: &str, sort_type: SortType, descending: bool) -> Self {
+        let (column, descending) = match spec.rsplit_once(':') {
+            Some((column, dir)) if dir.eq_ignore_ascii_case("desc") => (column, true),
+            Some((column, dir)) if dir.eq_ignore_ascii_case("asc") => (column, false),
+            _ => (spec, descending),
+        };
+        Self {
+            column: column.to_string(),
+            sort_type,
+            descending,
+        }
+    }
+
+    /// Index of the sort column in `headers`
+    pub fn column_index(&self, headers: &[String]) -> Result<usize, String> {
+        find_column(headers, &self.column).ok_or_else(|| {
+            format!(
+                "Unknown sort column '{}' (use a header name or 1-{})",
+                self.column,
+                headers.len()
+            )
+        })
+    }
+
+    /// Sort `rows` in place; returns the type the column was compared as
+    pub fn apply(&self, headers: &[String], rows: &mut [Vec<String>]) -> Result<SortType, String> {
+        let col = self.column_index(headers)?;
+        let (order, sort_type) = self.order(col, rows);
+        let sorted: Vec<Vec<String>> = order.iter().map(|&i| rows[i].clone()).collect();
+        rows.clone_from_slice(&sorted);
+        Ok(sort_type)
+    }
+
+    /// Indexes of `rows` in sorted order by column `col` (the `column`
+    /// field isn't looked at), and the type the column was compared as
+    pub fn order(&self, col: usize, rows: &[Vec<String>]) -> (Vec<usize>, SortType) {
+        let plain: Vec<String> = rows
+            .iter()
+            .map(|r| {
+                text::strip_ansi(r.get(col).map_or("", |c| c))
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+        let sort_type = match self.sort_type {
+            SortType::Auto => SortType::detect(plain.iter().map(|c| c.as_str())),
+            explicit => explicit,
+        };
+
+        // Cells that don't parse as the chosen type go after those that do
+        let keys: Vec<Option<Key>> = plain
+            .iter()
+            .map(|c| (!c.is_empty()).then(|| sort_type.key(c)).flatten())
+            .collect();
+        let mut collator = Collator::new(Tailoring::Cldr(collation_locale()), true, true);
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        order.sort_by(|&a, &b| match (&keys[a], &keys[b]) {
+            (Some(ka), Some(kb)) => {
+                let ord = ka.cmp(kb, &mut collator);
+                if self.descending {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => plain[b].is_empty().cmp(&plain[a].is_empty()).reverse(),
+        });
+        (order, sort_type)
+    }
+}
+
+/// Collation tailoring for the user's locale (`LC_ALL`, `LC_COLLATE`, `LANG`)
+fn collation_locale() -> Locale {
+    let locale = ["LC_ALL", "LC_COLLATE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .unwrap_or_default();
+    let lang = locale.split(['_', '.', '-']).next().unwrap_or("");
+    match lang {
+        "ar" | "fa" | "ur" | "ps" | "ku" | "sd" | "ug" => Locale::ArabicScript,
+        _ => Locale::Root,
+    }
+}
+
+/// Parsed cell value
+#[derive(Debug, Clone, PartialEq)]
+enum Key {
+    Number(f64),
+    /// Seconds since the Unix epoch
+    Date(i64),
+    Semver(Semver),
+    Text(String),
+}
+
+impl Key {
+    fn cmp(&self, other: &Key, collator: &mut Collator) -> Ordering {
+        match (self, other) {
+            (Key::Number(a), Key::Number(b)) => a.total_cmp(b),
+            (Key::Date(a), Key::Date(b)) => a.cmp(b),
+            (Key::Semver(a), Key::Semver(b)) => a.cmp(b),
+            (Key::Text(a), Key::Text(b)) => collator.collate(a.as_str(), b.as_str()),
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// Index of `column` in `headers`, by name (ignoring case) or 1-based index
+pub fn find_column(headers: &[String], column: &str) -> Option<usize> {
+    let column = column.trim();
+    if let Some(idx) = headers
+        .iter()
+        .position(|h| text::strip_ansi(h).trim().eq_ignore_ascii_case(column))
+    {
+        return Some(idx);
+    }
+    match column.parse::<usize>() {
+        Ok(n) if (1..=headers.len()).contains(&n) => Some(n - 1),
+        _ => None,
+    }
+}
+
+/// A number, allowing a sign, currency symbol, thousands separators and `%`
+pub fn parse_number(cell: &str) -> Option<f64> {
+    let s = cell.trim();
+    let s = s.strip_suffix('%').unwrap_or(s).trim_end();
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let s = s.trim_start_matches(['$', '€', '£', '¥']).trim_start();
+    if !s.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let digits = if s.contains(',') {
+        // Commas must group the integer part in threes: 1,234,567.8
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        let mut groups = int.split(',');
+        let first = groups.next()?;
+        if first.is_empty() || first.len() > 3 || groups.clone().any(|g| g.len() != 3) {
+            return None;
+        }
+        if frac.is_empty() {
+            int.replace(',', "")
+        } else {
+            format!("{}.{}", int.replace(',', ""), frac)
+        }
+    } else {
+        s.to_string()
+    };
+    if !digits
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return None;
+    }
+    let value: f64 = digits.parse().ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// Bytes in a human-readable size like "1.2 GiB", "512K" or "20 MB"
+///
+/// Bare numbers count as bytes so `0` can sit in a column of sizes.
+fn parse_size(cell: &str) -> Option<f64> {
+    let s = cell.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(s.len());
+    let value = parse_number(&s[..split])?;
+    let unit = s[split..].trim().to_ascii_lowercase();
+    let unit = unit
+        .strip_suffix("bytes")
+        .or_else(|| unit.strip_suffix("byte"))
+        .map_or(
+            unit.as_str(),
+            |prefix| if prefix.is_empty() { "b" } else { prefix },
+        );
+    let multiplier = match unit {
+        "" | "b" => 1.0,
+        "k" | "kib" => 1024f64,
+        "m" | "mib" => 1024f64.powi(2),
+        "g" | "gib" => 1024f64.powi(3),
+        "t" | "tib" => 1024f64.powi(4),
+        "p" | "pib" => 1024f64.powi(5),
+        "kb" => 1e3,
+        "mb" => 1e6,
+        "gb" => 1e9,
+        "tb" => 1e12,
+        "pb" => 1e15,
+        _ => return None,
+    };
+    Some(value * multiplier)
+}
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+fn month_number(name: &str) -> Option<u32> {
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|m| name.starts_with(m))
+        .map(|i| i as u32 + 1)
+}
+
+/// Seconds since the epoch for ISO dates (`2024-03-01`, `2024/03/01`,
+/// optionally with `T`/space, `HH:MM[:SS]` and `Z` or `±HH:MM`) and
+/// month-name dates (`1 Mar 2024`, `Mar 1, 2024`)
+fn parse_date(cell: &str) -> Option<i64> {
+    let s = cell.trim();
+    let (date, time) = match s.find(['T', ' ']) {
+        Some(i) if s[..i].contains(['-', '/']) => (&s[..i], Some(s[i + 1..].trim())),
+        _ => (s, None),
+    };
+    let iso: Vec<&str> = date.split(['-', '/']).collect();
+    let (year, month, day) = if iso.len() == 3 && iso[0].len() == 4 {
+        (
+            iso[0].parse().ok()?,
+            iso[1].parse().ok()?,
+            iso[2].parse().ok()?,
+        )
+    } else {
+        // "1 Mar 2024" or "Mar 1, 2024"
+        let words: Vec<&str> = s.split([' ', ',']).filter(|w| !w.is_empty()).collect();
+        let [a, b, year] = words.as_slice() else {
+            return None;
+        };
+        let (month, day) = match month_number(a) {
+            Some(m) => (m, b.parse().ok()?),
+            None => (month_number(b)?, a.parse().ok()?),
+        };
+        return epoch_seconds(year.parse().ok()?, month, day, 0);
+    };
+    let seconds = match time {
+        Some(t) if !t.is_empty() => parse_time(t)?,
+        _ => 0,
+    };
+    epoch_seconds(year, month, day, seconds)
+}
+
+/// Seconds into the day in UTC for `HH:MM[:SS][.fff][Z|±HH:MM]`
+fn parse_time(t: &str) -> Option<i64> {
+    let (clock, offset) = match t.find(['Z', 'z', '+', '-']) {
+        Some(i) => (&t[..i], &t[i..]),
+        None => (t, ""),
+    };
+    let mut parts = clock.trim().split(':');
+    let h: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let sec: f64 = parts.next().map_or(Some(0.0), |s| s.parse().ok())?;
+    if parts.next().is_some() || h > 23 || m > 59 || sec >= 61.0 {
+        return None;
+    }
+    let offset = match offset.chars().next() {
+        None | Some('Z') | Some('z') => 0,
+        Some(sign) => {
+            let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+            if digits.len() != 4 {
+                return None;
+            }
+            let mins = digits[..2].parse::<i64>().ok()? * 60 + digits[2..].parse::<i64>().ok()?;
+            if sign == '-' {
+                -mins * 60
+            } else {
+                mins * 60
+            }
+        }
+    };
+    Some(h * 3600 + m * 60 + sec as i64 - offset)
+}
+
+fn epoch_seconds(year: i64, month: u32, day: u32, seconds: i64) -> Option<i64> {
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * 86_400 + seconds)
+}
+
+/// A semantic version; build metadata is ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Semver {
+    core: [u64; 3],
+    pre: Vec<String>,
+}
+
+impl Ord for Semver {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.core.cmp(&other.core).then_with(|| {
+            // A pre-release sorts before its release
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    for (a, b) in self.pre.iter().zip(&other.pre) {
+                        let ord = match (a.parse::<u64>(), b.parse::<u64>()) {
+                            (Ok(x), Ok(y)) => x.cmp(&y),
+                            (Ok(_), Err(_)) => Ordering::Less,
+                            (Err(_), Ok(_)) => Ordering::Greater,
+                            (Err(_), Err(_)) => a.cmp(b),
+                        };
+                        if ord != Ordering::Equal {
+                            return ord;
+                        }
+                    }
+                    self.pre.len().cmp(&other.pre.len())
+                }
+            }
+        })
+    }
+}
+
+impl PartialOrd for Semver {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `1.2.3`, `v1.2.3-rc.1+build`, or `v1.2` with the `v` prefix
+pub fn parse_semver(cell: &str) -> Option<Semver> {
+    let s = cell.trim();
+    let (prefixed, s) = match s.strip_prefix(['v', 'V']) {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let s = s.split('+').next()?;
+    let (core, pre) = match s.split_once('-') {
+        Some((core, pre)) => (core, pre.split('.').map(str::to_string).collect()),
+        None => (s, Vec::new()),
+    };
+    let parts: Vec<u64> = core
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let core = match parts.as_slice() {
+        [a, b, c] => [*a, *b, *c],
+        [a, b] if prefixed => [*a, *b, 0],
+        _ => return None,
+    };
+    let pre: Vec<String> = pre;
+    if pre.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+    Some(Semver { core, pre })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(cells: &[&str], sort_type: SortType, descending: bool) -> Vec<String> {
+        let headers = vec!["value".to_string()];
+        let mut rows: Vec<Vec<String>> = cells.iter().map(|c| vec![c.to_string()]).collect();
+        let sort = TableSort {
+            column: "value".to_string(),
+            sort_type,
+            descending,
+        };
+        sort.apply(&headers, &mut rows).unwrap();
+        rows.into_iter().map(|r| r[0].clone()).collect()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(SortType::detect(["10", "9", "-1.5"]), SortType::Numeric);
+        assert_eq!(
+            SortType::detect(["1,204", "$3.50", "12%"]),
+            SortType::Numeric
+        );
+        assert_eq!(SortType::detect(["1.2 GiB", "512K", "0"]), SortType::Size);
+        assert_eq!(
+            SortType::detect(["2024-03-01", "1 Mar 2023", ""]),
+            SortType::Date
+        );
+        assert_eq!(
+            SortType::detect(["1.10.0", "v1.9.2-rc.1"]),
+            SortType::Semver
+        );
+        assert_eq!(SortType::detect(["10", "ten"]), SortType::String);
+        assert_eq!(
+            SortType::detect(["10", "9", "n/a", "100", "-"]),
+            SortType::String
+        );
+        assert_eq!(
+            SortType::detect(["10", "9", "n/a", "100", "7", "12"]),
+            SortType::Numeric
+        );
+        assert_eq!(
+            SortType::detect(["2024-03-01", "-", "1 Mar 2023"]),
+            SortType::Date
+        );
+        assert_eq!(SortType::detect(["", " "]), SortType::String);
+    }
+
+    #[test]
+    fn test_auto_sort_puts_placeholders_last() {
+        assert_eq!(
+            column(&["10", "n/a", "9", "100", "", "2"], SortType::Auto, false),
+            ["2", "9", "10", "100", "n/a", ""]
+        );
+        assert_eq!(
+            column(&["10", "n/a", "9", "100", "2"], SortType::Auto, true),
+            ["100", "10", "9", "2", "n/a"]
+        );
+    }
+
+    #[test]
+    fn test_numeric_sort_is_not_lexicographic() {
+        assert_eq!(
+            column(&["10", "9", "100", "-2"], SortType::Auto, false),
+            ["-2", "9", "10", "100"]
+        );
+        assert_eq!(
+            column(&["1,000", "999", "12,500.5"], SortType::Auto, true),
+            ["12,500.5", "1,000", "999"]
+        );
+    }
+
+    #[test]
+    fn test_size_sort() {
+        assert_eq!(
+            column(
+                &["1.2 GiB", "900 MB", "512K", "3 bytes"],
+                SortType::Auto,
+                false
+            ),
+            ["3 bytes", "512K", "900 MB", "1.2 GiB"]
+        );
+    }
+
+    #[test]
+    fn test_date_sort() {
+        assert_eq!(
+            column(
+                &[
+                    "2024-03-01",
+                    "Feb 29, 2024",
+                    "2023-12-31 23:00Z",
+                    "2024-01-01T00:30:00+02:00"
+                ],
+                SortType::Auto,
+                false
+            ),
+            [
+                "2024-01-01T00:30:00+02:00",
+                "2023-12-31 23:00Z",
+                "Feb 29, 2024",
+                "2024-03-01"
+            ]
+        );
+        assert_eq!(parse_date("2023-02-29"), None);
+    }
+
+    #[test]
+    fn test_semver_sort() {
+        assert_eq!(
+            column(
+                &["1.10.0", "1.9.2", "1.10.0-rc.1", "1.10.0-alpha", "v1.2"],
+                SortType::Auto,
+                false
+            ),
+            ["v1.2", "1.9.2", "1.10.0-alpha", "1.10.0-rc.1", "1.10.0"]
+        );
+    }
+
+    #[test]
+    fn test_string_sort_uses_collation() {
+        assert_eq!(
+            column(&["Peng", "Peña", "ernie", "Émile"], SortType::Auto, false),
+            ["Émile", "ernie", "Peña", "Peng"]
+        );
+    }
+
+    #[test]
+    fn test_empty_and_unparsable_cells_sort_last() {
+        assert_eq!(
+            column(&["", "3", "n/a", "1"], SortType::Numeric, false),
+            ["1", "3", "n/a", ""]
+        );
+        assert_eq!(
+            column(&["", "3", "n/a", "1"], SortType::Numeric, true),
+            ["3", "1", "n/a", ""]
+        );
+    }
+
+    #[test]
+    fn test_override_type() {
+        assert_eq!(
+            column(&["10", "9", "100"], SortType::String, false),
+            ["10", "100", "9"]
+        );
+    }
+
+    #[test]
+    fn test_column_index() {
+        let headers = vec!["Name".to_string(), "Size".to_string()];
+        let sort = |column: &str| TableSort {
+            column: column.to_string(),
+            sort_type: SortType::Auto,
+            descending: false,
+        };
+        assert_eq!(sort("size").column_index(&headers), Ok(1));
+        assert_eq!(sort("1").column_index(&headers), Ok(0));
+        assert!(sort("3").column_index(&headers).is_err());
+        assert!(sort("owner").column_index(&headers).is_err());
+    }
+
+    #[test]
+    fn test_parse_direction_suffix() {
+        let sort = TableSort::parse("size:DESC", SortType::Auto, false);
+        assert_eq!((sort.column.as_str(), sort.descending), ("size", true));
+        let sort = TableSort::parse("size:asc", SortType::Auto, true);
+        assert_eq!((sort.column.as_str(), sort.descending), ("size", false));
+        let sort = TableSort::parse("time:ms", SortType::Auto, true);
+        assert_eq!((sort.column.as_str(), sort.descending), ("time:ms", true));
+    }
+}
@@ -1,16 +1,36 @@
+use crate::animation::policy;
+use crate::output::calendar::{civil_from_days, days_from_civil, days_in_month};
+use crate::text;
 use owo_colors::OwoColorize;
 use std::io::{self, IsTerminal};
 use std::thread;
 use std::time::Duration;
 
 pub struct TimelineArgs {
-    pub events: String,
+    pub events: Option<String>,
+    /// Extra lanes as `NAME=EVENTS`
+    pub groups: Vec<String>,
+    /// Day to mark as "now": `today` or a date
+    pub now: Option<String>,
     pub style: String,
     pub color: Option<String>,
     pub animate: bool,
     pub vertical: bool,
 }
 
+/// Events sharing a row label on the date axis
+struct Lane {
+    name: Option<String>,
+    events: Vec<Event>,
+}
+
+/// Where an event sits on the date axis, in days since 1970-01-01
+enum Mark {
+    /// From the first day up to (not including) the second
+    Span(i64, i64),
+    Milestone(i64),
+}
+
 #[derive(Debug)]
 struct Event {
     date: Option<String>,
@@ -31,16 +51,184 @@ impl Event {
             }
         }
     }
+
+    fn is_span(&self) -> bool {
+        self.date.as_deref().is_some_and(|d| d.contains(".."))
+    }
+
+    fn mark(&self) -> Result<Mark, String> {
+        let date = self
+            .date
+            .as_deref()
+            .ok_or_else(|| format!("Event '{}' needs a date to go on the date axis", self.label))?;
+        let invalid = || {
+            format!(
+                "Invalid date '{}' (use YYYY, YYYY-MM or YYYY-MM-DD, or FROM..TO)",
+                date
+            )
+        };
+        match date.split_once("..") {
+            Some((from, to)) => {
+                let (start, _) = parse_period(from).ok_or_else(invalid)?;
+                let (_, end) = parse_period(to).ok_or_else(invalid)?;
+                if end <= start {
+                    return Err(format!("Event '{}' ends before it starts", self.label));
+                }
+                Ok(Mark::Span(start, end))
+            }
+            None => parse_period(date)
+                .map(|(start, _)| Mark::Milestone(start))
+                .ok_or_else(invalid),
+        }
+    }
+}
+
+fn parse_events(events: &str) -> Vec<Event> {
+    events.split(',').map(|s| Event::parse(s.trim())).collect()
+}
+
+fn parse_lanes(args: &TimelineArgs) -> Result<Vec<Lane>, String> {
+    let mut lanes = Vec::new();
+    if let Some(events) = &args.events {
+        lanes.push(Lane {
+            name: None,
+            events: parse_events(events),
+        });
+    }
+    for group in &args.groups {
+        let (name, events) = group
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid group '{}' (use NAME=EVENTS)", group))?;
+        lanes.push(Lane {
+            name: Some(name.trim().to_string()),
+            events: parse_events(events),
+        });
+    }
+    Ok(lanes)
+}
+
+/// The days covered by `2024`, `2024-03` or `2024-03-15`: the first, and
+/// the one after the last (days since 1970-01-01)
+pub fn parse_period(text: &str) -> Option<(i64, i64)> {
+    let parts: Vec<&str> = text.trim().split('-').collect();
+    if parts[0].len() != 4 {
+        return None;
+    }
+    let year: i64 = parts[0].parse().ok()?;
+    let month = |m: &str| m.parse::<u32>().ok().filter(|m| (1..=12).contains(m));
+    match parts[..] {
+        [_] => Some((days_from_civil(year, 1, 1), days_from_civil(year + 1, 1, 1))),
+        [_, m] => {
+            let m = month(m)?;
+            let start = days_from_civil(year, m, 1);
+            Some((start, start + days_in_month(year, m) as i64))
+        }
+        [_, m, d] => {
+            let m = month(m)?;
+            let d: u32 = d.parse().ok()?;
+            if d == 0 || d > days_in_month(year, m) {
+                return None;
+            }
+            let day = days_from_civil(year, m, d);
+            Some((day, day + 1))
+        }
+        _ => None,
+    }
+}
+
+/// Today in UTC, as days since 1970-01-01
+fn today() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| (d.as_secs() / 86_400) as i64)
+        .unwrap_or(0)
+}
+
+fn format_day(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// A span of days laid across `width` columns, for anything drawn against
+/// dates (timeline bars, gantt rows)
+pub struct DateAxis {
+    /// First day shown
+    pub start: i64,
+    /// Day after the last one shown
+    pub end: i64,
+    pub width: usize,
+}
+
+impl DateAxis {
+    fn span(&self) -> i64 {
+        (self.end - self.start).max(1)
+    }
+
+    /// Column holding `day`, clamped to the axis
+    pub fn column(&self, day: i64) -> usize {
+        let col = (day - self.start) * self.width as i64 / self.span();
+        col.clamp(0, self.width as i64 - 1) as usize
+    }
+
+    /// Columns `from..to` for the days `start..end`, at least one wide
+    pub fn bar(&self, start: i64, end: i64) -> (usize, usize) {
+        let from = self.column(start);
+        let to = ((end - self.start) * self.width as i64 / self.span()).clamp(0, self.width as i64);
+        (from, (to as usize).max(from + 1))
+    }
+
+    /// Labelled ticks that fit side by side: years on long ranges, months
+    /// on medium ones, days otherwise
+    pub fn ticks(&self) -> Vec<(usize, String)> {
+        let days = self.end - self.start;
+        let (mut year, mut month, _) = civil_from_days(self.start);
+        let mut candidates = Vec::new();
+        if days >= 3 * 365 {
+            while days_from_civil(year, 1, 1) < self.end {
+                candidates.push((days_from_civil(year, 1, 1), year.to_string()));
+                year += 1;
+            }
+        } else if days >= 60 {
+            while days_from_civil(year, month, 1) < self.end {
+                candidates.push((
+                    days_from_civil(year, month, 1),
+                    format!("{:04}-{:02}", year, month),
+                ));
+                (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+            }
+        } else {
+            for day in self.start..self.end {
+                let (_, m, d) = civil_from_days(day);
+                candidates.push((day, format!("{:02}-{:02}", m, d)));
+            }
+        }
+
+        let mut ticks: Vec<(usize, String)> = Vec::new();
+        for (day, label) in candidates {
+            if day < self.start {
+                continue;
+            }
+            let col = self.column(day);
+            let clear = ticks
+                .last()
+                .is_none_or(|(last, text)| col > last + text.len());
+            if clear && col + label.len() <= self.width {
+                ticks.push((col, label));
+            }
+        }
+        ticks
+    }
 }
 
 pub fn render_timeline(args: &TimelineArgs) -> io::Result<()> {
-    let events: Vec<Event> = args
-        .events
-        .split(',')
-        .map(|s| Event::parse(s.trim()))
-        .collect();
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let lanes = parse_lanes(args).map_err(invalid)?;
 
-    if events.is_empty() {
+    if lanes.iter().all(|lane| lane.events.is_empty()) {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
             "No events provided",
@@ -48,12 +236,191 @@ pub fn render_timeline(args: &TimelineArgs) -> io::Result<()> {
     }
 
     let is_tty = io::stdout().is_terminal();
-    let can_animate = args.animate && is_tty;
+    let can_animate = policy::gate(args.animate) && is_tty;
+
+    // Spans, lanes and a now marker need real dates on a scaled axis
+    let on_axis = args.now.is_some()
+        || lanes.iter().any(|lane| lane.name.is_some())
+        || lanes
+            .iter()
+            .flat_map(|lane| &lane.events)
+            .any(Event::is_span);
 
     if args.vertical {
-        render_vertical_timeline(&events, &args.style, &args.color, can_animate)
+        for (i, lane) in lanes.iter().enumerate() {
+            if let Some(name) = &lane.name {
+                if i > 0 {
+                    println!();
+                }
+                println!("{}", name.bold());
+            }
+            render_vertical_timeline(&lane.events, &args.style, &args.color, can_animate)?;
+        }
+        Ok(())
+    } else if on_axis {
+        let now = match args.now.as_deref() {
+            None => None,
+            Some("today") => Some(today()),
+            Some(date) => Some(
+                parse_period(date)
+                    .map(|(day, _)| day)
+                    .ok_or_else(|| invalid(format!("Invalid --now date '{}'", date)))?,
+            ),
+        };
+        let width = crossterm::terminal::size()
+            .map(|(w, _)| w as usize)
+            .unwrap_or(80);
+        let lines = axis_lines(&lanes, now, &args.color, width).map_err(invalid)?;
+        for line in lines {
+            if can_animate {
+                thread::sleep(Duration::from_millis(100));
+            }
+            println!("{}", line);
+        }
+        Ok(())
+    } else {
+        render_horizontal_timeline(&lanes[0].events, &args.style, &args.color, can_animate)
+    }
+}
+
+/// The timeline on a date axis: tick labels, the axis, then one row per
+/// event (spans as bars, single dates as milestone diamonds) with lane
+/// names on the left
+fn axis_lines(
+    lanes: &[Lane],
+    now: Option<i64>,
+    color: &Option<String>,
+    width: usize,
+) -> Result<Vec<String>, String> {
+    let mut rows = Vec::new();
+    for (lane_index, lane) in lanes.iter().enumerate() {
+        for (i, event) in lane.events.iter().enumerate() {
+            let name = lane.name.as_deref().filter(|_| i == 0);
+            rows.push((lane_index, name, event, event.mark()?));
+        }
+    }
+    let bounds = rows.iter().map(|(_, _, _, mark)| match *mark {
+        Mark::Span(start, end) => (start, end),
+        Mark::Milestone(day) => (day, day + 1),
+    });
+    let bounds = bounds.chain(now.map(|day| (day, day + 1)));
+    let start = bounds.clone().map(|(s, _)| s).min().unwrap_or(0);
+    let end = bounds.map(|(_, e)| e).max().unwrap_or(start + 1);
+
+    let name_width = lanes
+        .iter()
+        .filter_map(|lane| lane.name.as_deref())
+        .map(text::display_width)
+        .max();
+    let gutter = name_width.map_or(0, |w| w + 2);
+    let axis = DateAxis {
+        start,
+        end,
+        width: width.saturating_sub(gutter + 1).max(20),
+    };
+    let now_col = now.map(|day| axis.column(day));
+    let pad = " ".repeat(gutter);
+
+    let mut labels = vec![' '; axis.width];
+    let mut line: Vec<String> = vec!["─".to_string(); axis.width];
+    for (col, label) in axis.ticks() {
+        labels.splice(col..col + label.len(), label.chars());
+        line[col] = "┬".to_string();
+    }
+    if let Some(col) = now_col {
+        line[col] = "▼".bright_red().bold().to_string();
+    }
+    let mut lines = vec![
+        format!("{}{}", pad, labels.iter().collect::<String>().trim_end()),
+        format!("{}{}", pad, line.concat().bright_black()),
+    ];
+
+    let mut milestones = false;
+    for (lane_index, name, event, mark) in &rows {
+        let mut cells = vec![" ".to_string(); axis.width];
+        let (from, to) = match *mark {
+            Mark::Span(start, end) => {
+                let (from, to) = axis.bar(start, end);
+                // One colored run rather than a sequence per cell
+                cells[from] = lane_color(&"█".repeat(to - from), *lane_index, color);
+                for cell in &mut cells[from + 1..to] {
+                    cell.clear();
+                }
+                (from, to)
+            }
+            Mark::Milestone(day) => {
+                milestones = true;
+                let col = axis.column(day);
+                cells[col] = "◆".bright_yellow().to_string();
+                (col, col + 1)
+            }
+        };
+        if let Some(col) = now_col {
+            if cells[col] == " " {
+                cells[col] = "┊".bright_red().to_string();
+            }
+        }
+        place_label(&mut cells, &event.label, from, to);
+
+        let gutter_text = match (name, name_width) {
+            (Some(name), Some(w)) => format!(
+                "{}{}  ",
+                lane_color(name, *lane_index, color),
+                " ".repeat(w - text::display_width(name))
+            ),
+            _ => pad.clone(),
+        };
+        lines.push(format!("{}{}", gutter_text, cells.concat().trim_end()));
+    }
+
+    let mut legend = Vec::new();
+    if milestones {
+        legend.push(format!("{} milestone", "◆".bright_yellow()));
+    }
+    if let Some(day) = now {
+        legend.push(format!("{} now ({})", "▼".bright_red(), format_day(day)));
+    }
+    if !legend.is_empty() {
+        lines.push(format!("{}{}", pad, legend.join("   ").dimmed()));
+    }
+    Ok(lines)
+}
+
+/// Write `label` beside the bar at `from..to`: after it when there is room,
+/// else before it, else cut to whichever side is wider
+fn place_label(cells: &mut [String], label: &str, from: usize, to: usize) {
+    let width = text::display_width(label);
+    let after = cells.len().saturating_sub(to + 1);
+    let before = from.saturating_sub(1);
+    let (start, room) = if width <= after || after >= before {
+        (to + 1, after)
+    } else if width <= before {
+        (from - 1 - width, before)
     } else {
-        render_horizontal_timeline(&events, &args.style, &args.color, can_animate)
+        (0, before)
+    };
+    if room == 0 {
+        return;
+    }
+    let label = text::truncate(label, room, "…");
+    let used = text::display_width(&label);
+    cells[start] = label;
+    for cell in &mut cells[start + 1..start + used] {
+        cell.clear();
+    }
+}
+
+/// `text` in the lane's color: `--accent` when given, else one per lane
+fn lane_color(text: &str, lane: usize, color: &Option<String>) -> String {
+    if color.is_some() {
+        return apply_color(text, color);
+    }
+    match lane % 5 {
+        0 => text.cyan().to_string(),
+        1 => text.green().to_string(),
+        2 => text.magenta().to_string(),
+        3 => text.yellow().to_string(),
+        _ => text.blue().to_string(),
     }
 }
 
@@ -195,6 +562,76 @@ mod tests {
         assert_eq!(event.label, "Start");
     }
 
+    #[test]
+    fn test_parse_period() {
+        let jan = days_from_civil(2024, 1, 1);
+        assert_eq!(parse_period("2024"), Some((jan, jan + 366)));
+        assert_eq!(parse_period("2024-02"), Some((jan + 31, jan + 60)));
+        assert_eq!(parse_period("2024-02-29"), Some((jan + 59, jan + 60)));
+        assert_eq!(parse_period("2023-02-29"), None);
+        assert_eq!(parse_period("Q1"), None);
+    }
+
+    #[test]
+    fn test_event_mark() {
+        let design = Event::parse("2024-01..2024-03:Design");
+        assert!(matches!(
+            design.mark(),
+            Ok(Mark::Span(start, end)) if end - start == 31 + 29 + 31
+        ));
+        assert!(matches!(
+            Event::parse("2024-04-01:Launch").mark(),
+            Ok(Mark::Milestone(_))
+        ));
+        assert!(Event::parse("2024-03..2024-01:Oops").mark().is_err());
+        assert!(Event::parse("Undated").mark().is_err());
+    }
+
+    #[test]
+    fn test_date_axis_columns_and_ticks() {
+        let start = days_from_civil(2024, 1, 1);
+        let axis = DateAxis {
+            start,
+            end: days_from_civil(2024, 5, 1),
+            width: 40,
+        };
+        assert_eq!(axis.column(start), 0);
+        assert_eq!(axis.bar(start, days_from_civil(2024, 3, 1)), (0, 19));
+        let ticks: Vec<String> = axis.ticks().into_iter().map(|(_, t)| t).collect();
+        assert_eq!(ticks, ["2024-01", "2024-02", "2024-03", "2024-04"]);
+    }
+
+    #[test]
+    fn test_axis_lines_lanes_and_now() {
+        let args = TimelineArgs {
+            events: None,
+            groups: vec![
+                "Design=2024-01..2024-02:Mockups".to_string(),
+                "Build=2024-02..2024-04:Code,2024-04-15:Launch".to_string(),
+            ],
+            now: None,
+            style: "arrow".to_string(),
+            color: None,
+            animate: false,
+            vertical: false,
+        };
+        let lanes = parse_lanes(&args).unwrap();
+        let now = parse_period("2024-03-01").map(|(d, _)| d);
+        let lines: Vec<String> = axis_lines(&lanes, now, &None, 60)
+            .unwrap()
+            .iter()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect();
+        assert!(lines[0].starts_with("        2024-01"));
+        assert!(lines[1].contains('▼'));
+        assert!(lines[2].starts_with("Design  ████"));
+        assert!(lines[2].contains("Mockups"));
+        assert!(lines[3].starts_with("Build   "));
+        assert!(lines[4].starts_with("        "));
+        assert!(lines[4].contains("◆ Launch") || lines[4].contains("Launch ◆"));
+        assert_eq!(lines[5].trim(), "◆ milestone   ▼ now (2024-03-01)");
+    }
+
     #[test]
     fn test_event_parse_without_date() {
         let event = Event::parse("Start");
@@ -0,0 +1,263 @@
+//! Countdown timer and stopwatch, redrawn in place until done or cancelled
+
+use crate::animation::engine::Animator;
+use crate::output::banner::fonts::{Builtin, Font};
+use crate::output::{layout, notification};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use owo_colors::OwoColorize;
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+/// Names accepted by `--style`
+pub const STYLES: [&str; 2] = ["digital", "minimal"];
+
+/// Count `duration` down, then ring the bell and, with `notify`, send a
+/// desktop notification. Returns false when cancelled with Ctrl+C.
+pub fn render_timer(duration: Duration, label: Option<&str>, style: &str, notify: bool) -> bool {
+    let animator = Animator::new(100);
+    let mut finished = false;
+    let completed = animator.run_block(|_, elapsed| {
+        if finished {
+            return None;
+        }
+        let remaining = duration.saturating_sub(elapsed);
+        finished = remaining.is_zero();
+        let fraction = if duration.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+        };
+        // Round up so the clock reads 00:00 only once time is up
+        let clock = clock(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0));
+        let status = if finished {
+            "done".to_string()
+        } else {
+            format!("{:.0}% elapsed", fraction * 100.0)
+        };
+        Some(timer_lines(
+            &clock, fraction, label, &status, style, finished,
+        ))
+    });
+
+    if completed {
+        print!("\x07");
+        std::io::stdout().flush().ok();
+        if notify {
+            let message = format!("{} is up", label.unwrap_or("Timer"));
+            notification::render(
+                &message,
+                Some("termgfx timer"),
+                "success",
+                true,
+                false,
+                true,
+                &notification::NotificationOptions::default(),
+            );
+        }
+    }
+    completed
+}
+
+/// Count up until Enter or q (returns true) or Esc/Ctrl+C (returns false);
+/// Space or l records a lap. Keys are read only when stdin is a terminal.
+pub fn render_stopwatch(label: Option<&str>, style: &str) -> bool {
+    let keys = std::io::stdin().is_terminal() && terminal::enable_raw_mode().is_ok();
+    let animator = Animator::new(50);
+    let mut laps: Vec<Duration> = Vec::new();
+    let mut stopped = false;
+
+    let completed = animator.run_block(|_, elapsed| {
+        if stopped {
+            return None;
+        }
+        while keys && event::poll(Duration::ZERO).unwrap_or(false) {
+            let Ok(Event::Key(key)) = event::read() else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char(' ') | KeyCode::Char('l') => laps.push(elapsed),
+                KeyCode::Enter | KeyCode::Char('q') => stopped = true,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    animator.stop()
+                }
+                KeyCode::Esc => animator.stop(),
+                _ => {}
+            }
+        }
+        let hint = if !keys || stopped || !animator.is_running() {
+            None
+        } else {
+            Some("space lap · enter stop · esc cancel")
+        };
+        Some(stopwatch_lines(elapsed, &laps, label, hint, style))
+    });
+
+    if keys {
+        terminal::disable_raw_mode().ok();
+    }
+    completed
+}
+
+fn timer_lines(
+    clock: &str,
+    fraction: f64,
+    label: Option<&str>,
+    status: &str,
+    style: &str,
+    finished: bool,
+) -> Vec<String> {
+    let caption = match label {
+        Some(l) => format!("{} · {}", l.bold(), status.dimmed()),
+        None => status.dimmed().to_string(),
+    };
+
+    if style == "minimal" {
+        let clock = if finished {
+            clock.green().bold().to_string()
+        } else {
+            clock.cyan().bold().to_string()
+        };
+        return vec![format!("{}  {}  {}", clock, bar(fraction, 20), caption)];
+    }
+
+    let ring = ring_lines(fraction, finished).join("\n");
+    let digits = big_digits(clock, finished).join("\n");
+    let mut lines = layout::side_by_side(&[ring, digits], 3, "left");
+    lines.push(caption);
+    lines
+        .into_iter()
+        .map(|l| l.trim_end().to_string())
+        .collect()
+}
+
+fn stopwatch_lines(
+    elapsed: Duration,
+    laps: &[Duration],
+    label: Option<&str>,
+    hint: Option<&str>,
+    style: &str,
+) -> Vec<String> {
+    let clock = precise_clock(elapsed);
+    let mut lines = if style == "minimal" {
+        vec![clock.cyan().bold().to_string()]
+    } else {
+        big_digits(&clock, false)
+    };
+    if let Some(l) = label {
+        lines.push(l.bold().to_string());
+    }
+    let mut previous = Duration::ZERO;
+    for (i, lap) in laps.iter().enumerate() {
+        lines.push(format!(
+            "Lap {:<3} {}  {}",
+            i + 1,
+            precise_clock(*lap),
+            format!("+{}", precise_clock(lap.saturating_sub(previous))).dimmed()
+        ));
+        previous = *lap;
+    }
+    if let Some(hint) = hint {
+        lines.push(hint.dimmed().to_string());
+    }
+    lines
+}
+
+/// `MM:SS`, or `H:MM:SS` from an hour up
+fn clock(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// [`clock`] with tenths of a second
+fn precise_clock(d: Duration) -> String {
+    format!("{}.{}", clock(d.as_secs()), d.subsec_millis() / 100)
+}
+
+fn big_digits(clock: &str, finished: bool) -> Vec<String> {
+    Font::Builtin(Builtin::Block)
+        .render(clock)
+        .into_iter()
+        .map(|row| {
+            if finished {
+                row.green().to_string()
+            } else {
+                row.cyan().to_string()
+            }
+        })
+        .collect()
+}
+
+/// A ring of dots filled clockwise from the top to `fraction`
+fn ring_lines(fraction: f64, finished: bool) -> Vec<String> {
+    const RADIUS: i32 = 3;
+
+    (-RADIUS..=RADIUS)
+        .map(|y| {
+            let mut row = String::new();
+            for x in -RADIUS..=RADIUS {
+                let distance = ((x * x + y * y) as f64).sqrt();
+                if (distance - RADIUS as f64).abs() >= 0.6 {
+                    row.push_str("  ");
+                    continue;
+                }
+                let angle = (x as f64)
+                    .atan2(-y as f64)
+                    .rem_euclid(std::f64::consts::TAU);
+                if angle / std::f64::consts::TAU < fraction {
+                    let dot = if finished {
+                        "●".green().to_string()
+                    } else {
+                        "●".cyan().to_string()
+                    };
+                    row.push_str(&dot);
+                } else {
+                    row.push_str(&"○".bright_black().to_string());
+                }
+                row.push(' ');
+            }
+            row.trim_end().to_string()
+        })
+        .collect()
+}
+
+fn bar(fraction: f64, width: usize) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!(
+        "{}{}",
+        "█".repeat(filled).cyan(),
+        "░".repeat(width - filled).bright_black()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(0), "00:00");
+        assert_eq!(clock(300), "05:00");
+        assert_eq!(clock(3725), "1:02:05");
+        assert_eq!(precise_clock(Duration::from_millis(12_345)), "00:12.3");
+    }
+
+    #[test]
+    fn test_ring_fills_clockwise_from_top() {
+        let empty = ring_lines(0.0, false).join("");
+        assert!(!empty.contains('●'));
+        let full = ring_lines(1.0, false).join("");
+        assert!(!full.contains('○'));
+
+        // A quarter lights the top and the upper-right
+        let quarter = ring_lines(0.25, false);
+        assert!(quarter[0].contains('●'));
+        assert!(!quarter[6].contains('●'));
+    }
+}
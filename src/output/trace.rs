@@ -0,0 +1,113 @@
+//! Diagnostic traces for `--log-level` and `--log-file`
+//!
+//! With the `tracing` feature, every [`profile::time`] phase runs inside a
+//! span named parse, layout, render or io, and the CLI logs the terminal it
+//! detected. Reading input files, formatting and writing each `print!`ed
+//! line and `--format` output all run in phases, so every command traces. Span close events carry their timings, so a trace from an
+//! unusual terminal shows what was chosen and where the time went. Without
+//! the feature the spans compile to nothing and the flags report how to
+//! enable them.
+//!
+//! [`profile::time`]: super::profile::time
+
+use super::profile::Phase;
+
+pub const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Span entered for a phase; dropping it closes the span
+#[cfg(feature = "tracing")]
+pub type PhaseGuard = tracing::span::EnteredSpan;
+#[cfg(not(feature = "tracing"))]
+pub struct PhaseGuard;
+
+/// Enter the span for `phase`
+#[cfg(feature = "tracing")]
+pub fn enter(phase: Phase) -> PhaseGuard {
+    match phase {
+        Phase::Parse => tracing::debug_span!("parse").entered(),
+        Phase::Layout => tracing::debug_span!("layout").entered(),
+        Phase::Render => tracing::debug_span!("render").entered(),
+        Phase::Write => tracing::debug_span!("io").entered(),
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub fn enter(_phase: Phase) -> PhaseGuard {
+    PhaseGuard
+}
+
+/// Send traces at `level` and above to `file`, or to stderr
+///
+/// Either flag turns logging on: a file alone logs at debug, a level alone
+/// writes to stderr.
+pub fn init(level: Option<&str>, file: Option<&str>) -> Result<(), String> {
+    if level.is_none() && file.is_none() {
+        return Ok(());
+    }
+    let level = level.unwrap_or("debug").to_lowercase();
+    if !LEVELS.contains(&level.as_str()) {
+        return Err(format!(
+            "Invalid log level '{}' (use {})",
+            level,
+            LEVELS.join(", ")
+        ));
+    }
+    install(&level, file)
+}
+
+#[cfg(feature = "tracing")]
+fn install(level: &str, file: Option<&str>) -> Result<(), String> {
+    use std::sync::Mutex;
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    let level: tracing::Level = level.parse().map_err(|_| level.to_string())?;
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false);
+    let result = match file {
+        Some(path) => {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("Cannot open log file {}: {}", path, e))?;
+            builder.with_writer(Mutex::new(file)).try_init()
+        }
+        None => builder.with_writer(std::io::stderr).try_init(),
+    };
+    result.map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "tracing"))]
+fn install(_level: &str, _file: Option<&str>) -> Result<(), String> {
+    Err(
+        "--log-level and --log-file need termgfx built with the `tracing` feature \
+         (cargo install termgfx --features tracing)"
+            .to_string(),
+    )
+}
+
+/// Log the terminal as the command sees it, for bug reports
+#[cfg(feature = "tracing")]
+pub fn environment(command: &str) {
+    use std::io::IsTerminal;
+
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let size = crossterm::terminal::size().ok();
+    tracing::info!(
+        command,
+        version = env!("CARGO_PKG_VERSION"),
+        term = var("TERM"),
+        term_program = var("TERM_PROGRAM"),
+        colorterm = var("COLORTERM"),
+        size = ?size,
+        stdout_tty = std::io::stdout().is_terminal(),
+        color = super::color::enabled(),
+        color_depth = super::color::depth().name(),
+        "terminal"
+    );
+}
+
+#[cfg(not(feature = "tracing"))]
+pub fn environment(_command: &str) {}
@@ -1,3 +1,6 @@
+use crate::animation::policy;
+use crate::output::color;
+use crate::output::{input, query};
 use owo_colors::OwoColorize;
 use serde_json::Value;
 use std::io::Write;
@@ -6,15 +9,15 @@ use std::time::Duration;
 
 /// Tree characters for drawing hierarchical structures
 #[derive(Debug, Clone)]
-struct TreeChars {
-    branch: &'static str,   // ├──
-    last: &'static str,     // └──
-    vertical: &'static str, // │
-    space: &'static str,    // "   "
+pub struct TreeChars {
+    pub branch: &'static str,   // ├──
+    pub last: &'static str,     // └──
+    pub vertical: &'static str, // │
+    pub space: &'static str,    // "   "
 }
 
 impl TreeChars {
-    fn unicode() -> Self {
+    pub fn unicode() -> Self {
         TreeChars {
             branch: "├── ",
             last: "└── ",
@@ -25,7 +28,7 @@ impl TreeChars {
 }
 
 /// Get color for depth level (cycling through rainbow colors)
-fn get_depth_color(depth: usize) -> owo_colors::Style {
+pub fn get_depth_color(depth: usize) -> owo_colors::Style {
     let colors = [
         owo_colors::Style::new().cyan(),
         owo_colors::Style::new().green(),
@@ -118,6 +121,7 @@ fn render_inline_tree(data: &str) {
 /// Render a tree from inline data with optional animation
 /// animation_time_ms: total animation duration in milliseconds (delay is calculated per node)
 fn render_inline_tree_animated(data: &str, animate: bool, animation_time_ms: u64) {
+    let animate = policy::gate(animate);
     let chars = TreeChars::unicode();
     let parts: Vec<&str> = data.split('>').collect();
 
@@ -136,7 +140,7 @@ fn render_inline_tree_animated(data: &str, animate: bool, animation_time_ms: u64
     } else {
         Duration::ZERO
     };
-    let mut stdout = std::io::stdout();
+    let mut stdout = color::stdout();
 
     println!("{} {}", "📁".bright_cyan(), parts[0].bright_cyan().bold());
     if animate {
@@ -173,49 +177,591 @@ fn render_inline_tree_animated(data: &str, animate: bool, animation_time_ms: u64
 /// Main render function - handles all tree types
 #[allow(dead_code)]
 pub fn render(data: Option<&str>, path: Option<&str>) {
-    render_animated(data, path, false, 500);
+    render_animated(data, path, None, false, 500);
 }
 
 /// Render tree with optional animation
 /// animation_time_ms: total animation duration in milliseconds (delay is calculated per node)
+/// query: jq filter applied to the JSON before it is drawn
 pub fn render_animated(
     data: Option<&str>,
     path: Option<&str>,
+    query: Option<&str>,
     animate: bool,
     animation_time_ms: u64,
 ) {
     let chars = TreeChars::unicode();
 
-    if let Some(_p) = path {
-        eprintln!(
-            "{} Directory tree requires 'walkdir' feature. Use JSON input instead.",
-            "Warning:".bright_yellow().bold()
-        );
-        std::process::exit(1);
-    } else if let Some(d) = data {
+    if let Some(d) = data {
         render_inline_tree_animated(d, animate, animation_time_ms);
     } else {
-        use std::io::{self, Read};
-        let mut buffer = String::new();
+        // JSON from --path (a file, or `-`), falling back to stdin
+        let source = path.unwrap_or(input::STDIN);
+        let buffer = match input::read_text(source) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to read {}: {}",
+                    "Error:".bright_red().bold(),
+                    input::display_name(source),
+                    e
+                );
+                std::process::exit(1);
+            }
+        };
+
+        match query::parse(&buffer, query) {
+            Ok(json) => {
+                println!("{} {}", "📁".bright_cyan(), "root".bright_cyan().bold());
+                render_json_tree(&json, "", true, 0, &chars);
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".bright_red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// What `--dir` shows of the filesystem
+#[derive(Debug, Clone, Default)]
+pub struct DirOptions {
+    /// Levels below the root to descend, all of them when `None`
+    pub depth: Option<usize>,
+    /// Include hidden files and directories
+    pub all: bool,
+    /// Show what .gitignore (and .ignore) files exclude too
+    pub no_ignore: bool,
+    /// Annotate files with their size
+    pub sizes: bool,
+}
 
-        if let Err(e) = io::stdin().read_to_string(&mut buffer) {
+/// One file or directory found by [`dir_lines`]
+struct Entry {
+    depth: usize,
+    name: String,
+    is_dir: bool,
+    is_symlink: bool,
+    size: u64,
+}
+
+/// Draw the directory tree under `root` like the `tree` utility, followed
+/// by a count of directories and files
+pub fn render_dir(root: &str, options: &DirOptions, animate: bool, animation_time_ms: u64) {
+    let animate = policy::gate(animate);
+    let lines = match dir_lines(root, options) {
+        Ok(lines) => lines,
+        Err(e) => {
             eprintln!(
-                "{} Failed to read stdin: {}",
+                "{} Failed to read {}: {}",
                 "Error:".bright_red().bold(),
+                root,
                 e
             );
             std::process::exit(1);
         }
+    };
+    let delay = if animate && !lines.is_empty() {
+        Duration::from_millis(animation_time_ms / lines.len() as u64)
+    } else {
+        Duration::ZERO
+    };
+    let mut stdout = color::stdout();
+    for line in lines {
+        println!("{}", line);
+        if animate {
+            stdout.flush().unwrap();
+            thread::sleep(delay);
+        }
+    }
+}
 
-        match serde_json::from_str::<Value>(buffer.trim()) {
-            Ok(json) => {
-                println!("{} {}", "📁".bright_cyan(), "root".bright_cyan().bold());
-                render_json_tree(&json, "", true, 0, &chars);
-            }
-            Err(e) => {
-                eprintln!("{} Invalid JSON: {}", "Error:".bright_red().bold(), e);
-                std::process::exit(1);
+/// The tree under `root` as styled lines, entries sorted by name at each
+/// level; `.git` is always left out
+fn dir_lines(root: &str, options: &DirOptions) -> std::io::Result<Vec<String>> {
+    let meta = std::fs::metadata(root)?;
+    if !meta.is_dir() {
+        return Err(std::io::Error::other("not a directory"));
+    }
+
+    let mut builder = ignore::WalkBuilder::new(root);
+    // Spelled out, as `OwoColorize::hidden` would be picked otherwise
+    ignore::WalkBuilder::hidden(&mut builder, !options.all)
+        .git_ignore(!options.no_ignore)
+        .git_global(!options.no_ignore)
+        .git_exclude(!options.no_ignore)
+        .ignore(!options.no_ignore)
+        .parents(!options.no_ignore)
+        // A .gitignore counts outside a git checkout as well
+        .require_git(false)
+        .max_depth(options.depth)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(|entry| entry.file_name() != ".git");
+    let walker = builder.build();
+    let mut entries = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(std::io::Error::other)?;
+        if entry.depth() == 0 {
+            continue;
+        }
+        let file_type = entry.file_type();
+        entries.push(Entry {
+            depth: entry.depth(),
+            name: entry.file_name().to_string_lossy().into_owned(),
+            is_dir: file_type.is_some_and(|t| t.is_dir()),
+            is_symlink: file_type.is_some_and(|t| t.is_symlink()),
+            size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+        });
+    }
+
+    let chars = TreeChars::unicode();
+    let mut lines = vec![format!(
+        "{} {}",
+        "📁".bright_cyan(),
+        root.bright_cyan().bold()
+    )];
+    // Whether each open ancestor level still has siblings to come
+    let mut open: Vec<bool> = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let is_last = entries[i + 1..]
+            .iter()
+            .take_while(|next| next.depth >= entry.depth)
+            .all(|next| next.depth != entry.depth);
+        open.truncate(entry.depth - 1);
+        let color = get_depth_color(entry.depth - 1);
+        let prefix: String = open
+            .iter()
+            .map(|&more| if more { chars.vertical } else { chars.space })
+            .collect();
+        let connector = if is_last { chars.last } else { chars.branch };
+        let name = if entry.is_dir {
+            entry.name.style(color.bold()).to_string()
+        } else {
+            entry.name.clone()
+        };
+        let size = if options.sizes && !entry.is_dir {
+            format!(
+                " {}",
+                format!("({})", human_size(entry.size)).bright_black()
+            )
+        } else {
+            String::new()
+        };
+        lines.push(format!(
+            "{}{}{} {}{}",
+            prefix.style(color),
+            connector.style(color),
+            file_icon(entry),
+            name,
+            size
+        ));
+        open.push(!is_last);
+    }
+
+    let dirs = entries.iter().filter(|e| e.is_dir).count();
+    lines.push(String::new());
+    lines.push(
+        format!(
+            "{}, {}",
+            plural(dirs, "directory", "directories"),
+            plural(entries.len() - dirs, "file", "files")
+        )
+        .bright_black()
+        .to_string(),
+    );
+    Ok(lines)
+}
+
+/// An icon for the kind of file, picked by extension
+fn file_icon(entry: &Entry) -> &'static str {
+    if entry.is_dir {
+        return "📁";
+    }
+    if entry.is_symlink {
+        return "🔗";
+    }
+    let ext = entry
+        .name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "rs" => "🦀",
+        "py" => "🐍",
+        "js" | "mjs" | "ts" | "tsx" | "jsx" | "go" | "c" | "h" | "cpp" | "java" | "rb" => "📜",
+        "sh" | "bash" | "zsh" | "fish" => "💲",
+        "md" | "txt" | "rst" => "📝",
+        "json" | "toml" | "yaml" | "yml" | "ini" | "cfg" | "conf" => "🔧",
+        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "ico" => "🖼️",
+        "zip" | "tar" | "gz" | "tgz" | "xz" | "bz2" | "7z" => "📦",
+        "lock" => "🔒",
+        _ => "📄",
+    }
+}
+
+/// Bytes as B, KB, MB or GB (powers of 1024)
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn plural(n: usize, one: &str, many: &str) -> String {
+    format!("{} {}", n, if n == 1 { one } else { many })
+}
+
+/// How a node differs between the old and new document
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Change {
+    Same,
+    /// Container present on both sides with changes somewhere below
+    Contains,
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Counts of top-most changes in a tree diff
+#[derive(Debug, Default, PartialEq)]
+pub struct DiffStats {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+/// Render two JSON files (either may be `-`) as one merged tree; `query`
+/// reshapes both sides before comparing
+pub fn render_diff(old_path: &str, new_path: &str, only_changes: bool, query: Option<&str>) {
+    if let Err(e) = input::check_single_stdin([old_path, new_path]) {
+        eprintln!("{} {}", "Error:".bright_red().bold(), e);
+        std::process::exit(1);
+    }
+    let old = read_json(old_path, query);
+    let new = read_json(new_path, query);
+
+    let (lines, stats) = diff_lines(&old, &new, only_changes);
+    if stats == DiffStats::default() {
+        println!("{}", "No differences".bright_black());
+        if only_changes {
+            return;
+        }
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    if stats != DiffStats::default() {
+        println!();
+        println!(
+            "{}  {}  {}",
+            format!("+{} added", stats.added).green(),
+            format!("-{} removed", stats.removed).red(),
+            format!("~{} changed", stats.changed).yellow()
+        );
+    }
+}
+
+fn read_json(path: &str, query: Option<&str>) -> Value {
+    let text = match input::read_text(path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!(
+                "{} Failed to read {}: {}",
+                "Error:".bright_red().bold(),
+                input::display_name(path),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    let value = match serde_json::from_str(text.trim()) {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!(
+                "{} Invalid JSON in {}: {}",
+                "Error:".bright_red().bold(),
+                input::display_name(path),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    match query {
+        Some(q) => query::apply(value, q).unwrap_or_else(|e| {
+            eprintln!("{} {}", "Error:".bright_red().bold(), e);
+            std::process::exit(1);
+        }),
+        None => value,
+    }
+}
+
+/// Build the merged diff tree; `only_changes` folds identical siblings into one line
+pub fn diff_lines(old: &Value, new: &Value, only_changes: bool) -> (Vec<String>, DiffStats) {
+    let mut lines = Vec::new();
+    let mut stats = DiffStats::default();
+    let chars = TreeChars::unicode();
+    diff_node(
+        "root",
+        Some(old),
+        Some(new),
+        ("", ""),
+        true,
+        only_changes,
+        &chars,
+        &mut stats,
+        &mut lines,
+    );
+    (lines, stats)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diff_node(
+    label: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    (prefix, connector): (&str, &str),
+    count: bool,
+    only_changes: bool,
+    chars: &TreeChars,
+    stats: &mut DiffStats,
+    lines: &mut Vec<String>,
+) {
+    let change = classify(old, new);
+    let value = new.or(old).expect("diff node without a value");
+    let container = change != Change::Changed && is_container(value);
+
+    if count {
+        match change {
+            Change::Added => stats.added += 1,
+            Change::Removed => stats.removed += 1,
+            Change::Changed => stats.changed += 1,
+            _ => {}
+        }
+    }
+
+    let sign = match change {
+        Change::Added => "+",
+        Change::Removed => "-",
+        Change::Changed => "~",
+        _ => " ",
+    };
+    let text = match (change, old, new) {
+        (Change::Changed, Some(o), Some(n)) => format!("{}: {} → {}", label, brief(o), brief(n)),
+        _ if container => label.to_string(),
+        _ => format!("{}: {}", label, brief(value)),
+    };
+    let line = format!(
+        "{} {}{}{} {}",
+        sign,
+        prefix,
+        connector,
+        json_icon(value),
+        text
+    );
+    lines.push(match change {
+        Change::Added => line.green().to_string(),
+        Change::Removed => line.red().to_string(),
+        Change::Changed => line.yellow().to_string(),
+        Change::Same => line.bright_black().to_string(),
+        Change::Contains => line,
+    });
+
+    if !container {
+        return;
+    }
+
+    let child_prefix = if connector.is_empty() {
+        String::new()
+    } else if connector == chars.last {
+        format!("{}{}", prefix, chars.space)
+    } else {
+        format!("{}{}", prefix, chars.vertical)
+    };
+    let mut children = diff_children(old, new);
+    let mut hidden = 0;
+    if only_changes && change == Change::Contains {
+        let before = children.len();
+        children.retain(|(_, o, n)| o != n);
+        hidden = before - children.len();
+    }
+
+    let count_children = matches!(change, Change::Same | Change::Contains);
+    let total = children.len() + usize::from(hidden > 0);
+    for (i, (key, o, n)) in children.into_iter().enumerate() {
+        let child_connector = if i + 1 == total {
+            chars.last
+        } else {
+            chars.branch
+        };
+        diff_node(
+            &key,
+            o,
+            n,
+            (&child_prefix, child_connector),
+            count_children,
+            only_changes,
+            chars,
+            stats,
+            lines,
+        );
+    }
+    if hidden > 0 {
+        lines.push(
+            format!("  {}{}… {} unchanged", child_prefix, chars.last, hidden)
+                .bright_black()
+                .to_string(),
+        );
+    }
+}
+
+fn classify(old: Option<&Value>, new: Option<&Value>) -> Change {
+    match (old, new) {
+        (Some(o), Some(n)) if o == n => Change::Same,
+        (Some(Value::Object(_)), Some(Value::Object(_)))
+        | (Some(Value::Array(_)), Some(Value::Array(_))) => Change::Contains,
+        (Some(_), Some(_)) => Change::Changed,
+        (Some(_), None) => Change::Removed,
+        _ => Change::Added,
+    }
+}
+
+/// Children of a node on both sides, keyed by object key or `[index]`
+fn diff_children<'a>(
+    old: Option<&'a Value>,
+    new: Option<&'a Value>,
+) -> Vec<(String, Option<&'a Value>, Option<&'a Value>)> {
+    let mut children = Vec::new();
+    if let (Some(Value::Array(_)), _) | (_, Some(Value::Array(_))) = (old, new) {
+        let old_items = old.and_then(Value::as_array);
+        let new_items = new.and_then(Value::as_array);
+        let len = old_items
+            .map_or(0, Vec::len)
+            .max(new_items.map_or(0, Vec::len));
+        for i in 0..len {
+            children.push((
+                format!("[{}]", i),
+                old_items.and_then(|a| a.get(i)),
+                new_items.and_then(|a| a.get(i)),
+            ));
+        }
+        return children;
+    }
+
+    let old_map = old.and_then(Value::as_object);
+    let new_map = new.and_then(Value::as_object);
+    if let Some(map) = old_map {
+        for (key, value) in map {
+            children.push((key.clone(), Some(value), new_map.and_then(|m| m.get(key))));
+        }
+    }
+    if let Some(map) = new_map {
+        for (key, value) in map {
+            if !old_map.is_some_and(|m| m.contains_key(key)) {
+                children.push((key.clone(), None, Some(value)));
             }
         }
     }
+    children
+}
+
+fn is_container(value: &Value) -> bool {
+    matches!(value, Value::Object(_) | Value::Array(_))
+}
+
+fn json_icon(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "📁",
+        Value::Array(_) => "📦",
+        Value::Null => "📄",
+        _ => "📌",
+    }
+}
+
+/// Compact one-line form of a value; containers are abbreviated
+fn brief(value: &Value) -> String {
+    match value {
+        Value::Object(map) if !map.is_empty() => "{…}".to_string(),
+        Value::Array(items) if !items.is_empty() => "[…]".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(512), "512 B");
+        assert_eq!(human_size(1536), "1.5 KB");
+        assert_eq!(human_size(5 * 1024 * 1024), "5.0 MB");
+    }
+    use serde_json::json;
+
+    fn plain(lines: &[String]) -> Vec<String> {
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        lines
+            .iter()
+            .map(|l| ansi.replace_all(l, "").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_marks_added_removed_changed() {
+        let old = json!({"port": 80, "debug": true, "name": "api"});
+        let new = json!({"port": 8080, "name": "api", "tls": {"enabled": true}});
+        let (lines, stats) = diff_lines(&old, &new, false);
+        let lines = plain(&lines);
+
+        assert_eq!(
+            stats,
+            DiffStats {
+                added: 1,
+                removed: 1,
+                changed: 1
+            }
+        );
+        assert!(lines.contains(&"- ├── 📌 debug: true".to_string()));
+        assert!(lines.contains(&"  ├── 📌 name: \"api\"".to_string()));
+        assert!(lines.contains(&"~ ├── 📌 port: 80 → 8080".to_string()));
+        assert!(lines.contains(&"+ └── 📁 tls".to_string()));
+        assert!(lines.contains(&"+     └── 📌 enabled: true".to_string()));
+    }
+
+    #[test]
+    fn test_diff_arrays_by_index() {
+        let (lines, stats) = diff_lines(&json!([1, 2]), &json!([1, 3, 4]), false);
+        let lines = plain(&lines);
+        assert_eq!(stats.changed, 1);
+        assert_eq!(stats.added, 1);
+        assert!(lines.contains(&"~ ├── 📌 [1]: 2 → 3".to_string()));
+        assert!(lines.contains(&"+ └── 📌 [2]: 4".to_string()));
+    }
+
+    #[test]
+    fn test_only_changes_folds_identical_siblings() {
+        let old = json!({"a": 1, "b": {"x": 1}, "c": 3});
+        let new = json!({"a": 1, "b": {"x": 1}, "c": 4});
+        let (lines, _) = diff_lines(&old, &new, true);
+        let lines = plain(&lines);
+        assert_eq!(
+            lines,
+            vec!["  📁 root", "~ ├── 📌 c: 3 → 4", "  └── … 2 unchanged",]
+        );
+    }
+
+    #[test]
+    fn test_identical_documents_have_no_stats() {
+        let value = json!({"a": [1, 2]});
+        let (_, stats) = diff_lines(&value, &value, false);
+        assert_eq!(stats, DiffStats::default());
+    }
 }
@@ -1,15 +1,17 @@
+use crate::animation::policy;
+use crate::output::color;
 use std::io::{stdout, IsTerminal, Write};
 use std::thread;
 use std::time::Duration;
 
 pub fn render(message: &str, speed: u64) {
     // If not a TTY (piped/captured), just print the full message
-    if !stdout().is_terminal() {
+    if !stdout().is_terminal() || !policy::enabled() {
         println!("{}", message);
         return;
     }
 
-    let mut stdout = stdout();
+    let mut stdout = color::stdout();
     for ch in message.chars() {
         print!("{}", ch);
         stdout.flush().unwrap();
@@ -0,0 +1,523 @@
+//! Update checks and `termgfx self-update`
+//!
+//! Releases come from the GitHub API. `--version` mentions a newer release
+//! at most once a week, remembering the last answer in the user cache
+//! directory; `check = false` under `[updates]` in `config.toml` or setting
+//! `TERMGFX_NO_UPDATE_CHECK` turns that off. Replacing the binary needs the
+//! `self-update` feature and only happens once the download matches the
+//! release's published SHA-256 checksum.
+
+use crate::output::atomic;
+use crate::output::table_sort::{parse_semver, Semver};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Set to anything to skip the weekly check in `--version`
+pub const NO_CHECK_ENV: &str = "TERMGFX_NO_UPDATE_CHECK";
+/// Environment variable overriding where `config.toml` is read from
+pub const CONFIG_FILE_ENV: &str = "TERMGFX_CONFIG_FILE";
+
+const RELEASES_URL: &str = "https://api.github.com/repos/ybouhjira/termgfx/releases";
+const CURRENT: &str = env!("CARGO_PKG_VERSION");
+const CHECK_INTERVAL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// `--version` should never hang on a slow network
+const NOTICE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A GitHub release, as much of it as termgfx reads
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[cfg(feature = "self-update")]
+    #[serde(default)]
+    pub name: Option<String>,
+    #[cfg(feature = "self-update")]
+    #[serde(default)]
+    pub body: Option<String>,
+    #[cfg(feature = "self-update")]
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+/// A file attached to a release
+#[cfg(feature = "self-update")]
+#[derive(Debug, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+/// All releases, newest first as GitHub lists them
+fn fetch(timeout: Duration) -> anyhow::Result<Vec<Release>> {
+    let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+    let body = agent
+        .get(RELEASES_URL)
+        .set("User-Agent", concat!("termgfx/", env!("CARGO_PKG_VERSION")))
+        .set("Accept", "application/vnd.github+json")
+        .call()?
+        .into_string()?;
+    let releases = serde_json::from_str(&body)?;
+    Ok(releases)
+}
+
+/// Published, non-prerelease releases newer than `current`, newest first
+pub fn newer_than(releases: Vec<Release>, current: &Semver) -> Vec<Release> {
+    let mut newer: Vec<(Semver, Release)> = releases
+        .into_iter()
+        .filter(|r| !r.draft && !r.prerelease)
+        .filter_map(|r| Some((parse_semver(&r.tag_name)?, r)))
+        .filter(|(version, _)| version > current)
+        .collect();
+    newer.sort_by(|a, b| b.0.cmp(&a.0));
+    newer.into_iter().map(|(_, r)| r).collect()
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Config {
+    #[serde(default)]
+    updates: UpdatesConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdatesConfig {
+    #[serde(default = "default_check")]
+    check: bool,
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        UpdatesConfig {
+            check: default_check(),
+        }
+    }
+}
+
+fn default_check() -> bool {
+    true
+}
+
+/// `$TERMGFX_CONFIG_FILE`, else `config.toml` in the termgfx config directory
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_FILE_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    Some(dirs::config_dir()?.join("termgfx").join("config.toml"))
+}
+
+/// Whether the config file allows update checks; a broken file doesn't
+/// silence them
+fn config_allows_check(content: &str) -> bool {
+    toml::from_str::<Config>(content).map_or(true, |config| config.updates.check)
+}
+
+/// Whether `--version` may look for a newer release
+pub fn checks_enabled() -> bool {
+    if env::var_os(NO_CHECK_ENV).is_some() {
+        return false;
+    }
+    config_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .is_none_or(|content| config_allows_check(&content))
+}
+
+/// Result of the last check, so `--version` only asks once a week
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckState {
+    /// Seconds since the Unix epoch
+    checked_at: u64,
+    /// Newest release tag at that time, if any was newer than the build
+    latest: Option<String>,
+}
+
+fn state_path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("termgfx").join("update-check.json"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// The newest release tag, re-checked with `check` once `state` is a week
+/// old; returns the state to save when a check ran
+fn latest_tag(
+    state: Option<CheckState>,
+    now: u64,
+    check: impl FnOnce() -> Option<Option<String>>,
+) -> (Option<String>, Option<CheckState>) {
+    match state {
+        Some(state) if now.saturating_sub(state.checked_at) < CHECK_INTERVAL.as_secs() => {
+            (state.latest, None)
+        }
+        previous => match check() {
+            Some(latest) => (
+                latest.clone(),
+                Some(CheckState {
+                    checked_at: now,
+                    latest,
+                }),
+            ),
+            // Offline: keep the last answer and try again next time
+            None => (previous.and_then(|s| s.latest), None),
+        },
+    }
+}
+
+fn save_state(state: &CheckState) -> io::Result<()> {
+    let Some(path) = state_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string(state).map_err(io::Error::other)?;
+    atomic::write(path, json)
+}
+
+/// Mention a newer release after `--version`, at most one network check a week
+///
+/// Only runs when stderr is a terminal, so scripts reading the version
+/// never wait on the network.
+pub fn version_notice() {
+    if !io::stderr().is_terminal() || !checks_enabled() {
+        return;
+    }
+    let Some(current) = parse_semver(CURRENT) else {
+        return;
+    };
+    let state = state_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let (latest, checked) = latest_tag(state, now_secs(), || {
+        let releases = fetch(NOTICE_TIMEOUT).ok()?;
+        Some(
+            newer_than(releases, &current)
+                .into_iter()
+                .next()
+                .map(|r| r.tag_name),
+        )
+    });
+    if let Some(state) = checked {
+        let _ = save_state(&state);
+    }
+    // The build may have been upgraded since the last check
+    let Some(latest) = latest.filter(|tag| parse_semver(tag).is_some_and(|v| v > current)) else {
+        return;
+    };
+
+    eprintln!();
+    eprintln!(
+        "{} termgfx {} is available (you have {})",
+        "↑".yellow(),
+        latest.trim_start_matches('v').bold(),
+        CURRENT
+    );
+    if cfg!(feature = "self-update") {
+        eprintln!(
+            "  Run {} to see what's new",
+            "termgfx self-update --check".cyan()
+        );
+    } else {
+        eprintln!("  https://github.com/ybouhjira/termgfx/releases");
+    }
+}
+
+/// Release notes of `newer`, oldest first, as Markdown
+#[cfg(feature = "self-update")]
+fn changelog(newer: &[Release]) -> String {
+    let mut doc = String::new();
+    for release in newer.iter().rev() {
+        let title = release
+            .name
+            .as_deref()
+            .filter(|n| !n.trim().is_empty() && n.trim() != release.tag_name)
+            .map_or(release.tag_name.clone(), |n| {
+                format!("{} - {}", release.tag_name, n.trim())
+            });
+        doc.push_str(&format!("## {}\n\n", title));
+        match release.body.as_deref().map(str::trim) {
+            Some(body) if !body.is_empty() => doc.push_str(body),
+            _ => doc.push_str("_No release notes._"),
+        }
+        doc.push_str("\n\n");
+    }
+    doc
+}
+
+/// Name of the release asset built for this platform
+#[cfg(feature = "self-update")]
+fn asset_name() -> String {
+    format!(
+        "termgfx-{}-{}{}",
+        env::consts::ARCH,
+        env::consts::OS,
+        env::consts::EXE_SUFFIX
+    )
+}
+
+/// Lowercase hex SHA-256 of `bytes`
+#[cfg(feature = "self-update")]
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Check `bytes` against a `.sha256` file (`<hex>  <file name>` or bare hex)
+#[cfg(feature = "self-update")]
+fn verify(bytes: &[u8], checksum_file: &str) -> anyhow::Result<()> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Malformed checksum file");
+    }
+    let actual = sha256_hex(bytes);
+    if actual != expected {
+        anyhow::bail!(
+            "Checksum mismatch (expected {}, got {}); the binary was not replaced",
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "self-update")]
+fn download(url: &str) -> anyhow::Result<Vec<u8>> {
+    use std::io::Read;
+    let response = ureq::get(url)
+        .set("User-Agent", concat!("termgfx/", env!("CARGO_PKG_VERSION")))
+        .call()?;
+    let mut bytes = Vec::new();
+    response.into_reader().read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Swap the running executable for `bytes`
+///
+/// The new binary is written next to the old one with its permissions, then
+/// renamed over it, so a failed update leaves the old binary working.
+#[cfg(feature = "self-update")]
+fn replace_exe(bytes: &[u8]) -> io::Result<PathBuf> {
+    let exe = env::current_exe()?.canonicalize()?;
+    let name = exe
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp = exe.with_file_name(format!(".{}.{}.new", name, std::process::id()));
+    let result = fs::write(&tmp, bytes)
+        .and_then(|_| fs::set_permissions(&tmp, fs::metadata(&exe)?.permissions()))
+        .and_then(|_| {
+            // Windows can't replace a running executable, only rename it
+            if cfg!(windows) {
+                let old = exe.with_file_name(format!("{}.old", name));
+                fs::remove_file(&old).ok();
+                fs::rename(&exe, old)?;
+            }
+            fs::rename(&tmp, &exe)
+        });
+    match result {
+        Ok(()) => Ok(exe),
+        Err(e) => {
+            fs::remove_file(&tmp).ok();
+            Err(e)
+        }
+    }
+}
+
+/// Download, verify and install `release`
+#[cfg(feature = "self-update")]
+fn install(release: &Release) -> anyhow::Result<PathBuf> {
+    let name = asset_name();
+    let find = |file: &str| release.assets.iter().find(|a| a.name == file);
+    let asset = find(&name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} has no build for this platform ({})",
+            release.tag_name,
+            name
+        )
+    })?;
+    let checksum = find(&format!("{}.sha256", name)).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} publishes no checksum for {}; refusing to install",
+            release.tag_name,
+            name
+        )
+    })?;
+
+    let binary = download(&asset.browser_download_url)?;
+    let checksum = String::from_utf8(download(&checksum.browser_download_url)?)?;
+    verify(&binary, &checksum)?;
+    Ok(replace_exe(&binary)?)
+}
+
+/// `termgfx self-update`: show what's new since this build, then install it
+/// unless `check_only`
+#[cfg(feature = "self-update")]
+pub fn render(check_only: bool) {
+    let current = parse_semver(CURRENT).expect("package version is semver");
+    let releases = match fetch(Duration::from_secs(15)) {
+        Ok(releases) => releases,
+        Err(e) => {
+            eprintln!("Error: Could not fetch releases: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let newer = newer_than(releases, &current);
+    let _ = save_state(&CheckState {
+        checked_at: now_secs(),
+        latest: newer.first().map(|r| r.tag_name.clone()),
+    });
+    let Some(latest) = newer.first() else {
+        println!("{} termgfx {} is up to date", "✓".green(), CURRENT);
+        return;
+    };
+
+    println!(
+        "{} {} {} {}",
+        "termgfx".bold(),
+        CURRENT.red(),
+        "→".dimmed(),
+        latest.tag_name.trim_start_matches('v').green().bold()
+    );
+    println!();
+    let width = crossterm::terminal::size()
+        .map(|(w, _)| w as usize)
+        .unwrap_or(80);
+    for line in crate::output::markdown::render_lines(&changelog(&newer), width) {
+        println!("{}", line);
+    }
+
+    if check_only {
+        println!("Run {} to install it", "termgfx self-update".cyan());
+        return;
+    }
+    match install(latest) {
+        Ok(path) => println!(
+            "{} Updated {} to {}",
+            "✓".green(),
+            path.display(),
+            latest.tag_name
+        ),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str) -> Release {
+        serde_json::from_str(&format!(r#"{{"tag_name": "{}"}}"#, tag)).unwrap()
+    }
+
+    #[test]
+    fn test_newer_than_sorts_and_skips_prereleases() {
+        let mut rc = release("v0.6.0-rc.1");
+        rc.prerelease = true;
+        let releases = vec![
+            release("v0.3.0"),
+            release("v0.5.0"),
+            rc,
+            release("v0.10.0"),
+            release("nightly"),
+            release("v0.4.0"),
+        ];
+        let current = parse_semver("0.4.0").unwrap();
+        let tags: Vec<String> = newer_than(releases, &current)
+            .into_iter()
+            .map(|r| r.tag_name)
+            .collect();
+        assert_eq!(tags, ["v0.10.0", "v0.5.0"]);
+    }
+
+    #[test]
+    fn test_config_allows_check() {
+        assert!(config_allows_check(""));
+        assert!(config_allows_check("[updates]\n"));
+        assert!(!config_allows_check("[updates]\ncheck = false\n"));
+        assert!(config_allows_check("not = [valid"));
+    }
+
+    #[test]
+    fn test_latest_tag_checks_once_a_week() {
+        let week = CHECK_INTERVAL.as_secs();
+        let fresh = CheckState {
+            checked_at: 1_000,
+            latest: Some("v0.5.0".into()),
+        };
+        let (latest, saved) = latest_tag(Some(fresh), 1_000 + week - 1, || {
+            panic!("should not hit the network")
+        });
+        assert_eq!(latest.as_deref(), Some("v0.5.0"));
+        assert!(saved.is_none());
+
+        let stale = CheckState {
+            checked_at: 1_000,
+            latest: Some("v0.5.0".into()),
+        };
+        let (latest, saved) = latest_tag(Some(stale), 1_000 + week, || Some(Some("v0.6.0".into())));
+        assert_eq!(latest.as_deref(), Some("v0.6.0"));
+        assert_eq!(saved.unwrap().checked_at, 1_000 + week);
+    }
+
+    #[test]
+    fn test_latest_tag_offline_keeps_last_answer() {
+        let stale = CheckState {
+            checked_at: 0,
+            latest: Some("v0.5.0".into()),
+        };
+        let (latest, saved) = latest_tag(Some(stale), u64::MAX, || None);
+        assert_eq!(latest.as_deref(), Some("v0.5.0"));
+        assert!(saved.is_none());
+        let (latest, saved) = latest_tag(None, 0, || None);
+        assert!(latest.is_none() && saved.is_none());
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_verify_checksum() {
+        let hash = sha256_hex(b"abc");
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(verify(b"abc", &format!("{}  termgfx-x86_64-linux\n", hash)).is_ok());
+        assert!(verify(b"abc", &hash.to_uppercase()).is_ok());
+        let err = verify(b"abd", &hash).unwrap_err().to_string();
+        assert!(err.starts_with("Checksum mismatch"));
+        assert!(verify(b"abc", "").is_err());
+    }
+
+    #[cfg(feature = "self-update")]
+    #[test]
+    fn test_changelog_lists_oldest_first() {
+        let mut newest = release("v0.6.0");
+        newest.body = Some("- Faster tables".into());
+        let mut older = release("v0.5.0");
+        older.name = Some("Spring cleaning".into());
+        let doc = changelog(&[newest, older]);
+        let v5 = doc.find("## v0.5.0 - Spring cleaning").unwrap();
+        let v6 = doc.find("## v0.6.0").unwrap();
+        assert!(v5 < v6);
+        assert!(doc.contains("_No release notes._"));
+        assert!(doc.contains("- Faster tables"));
+    }
+}
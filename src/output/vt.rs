@@ -0,0 +1,477 @@
+//! Terminal emulation for captured ANSI output
+//!
+//! Output is replayed onto a grid of character cells, so carriage returns
+//! and cursor moves leave only what the terminal would finally show. The
+//! SVG and image exports draw from it, and the studio shows command output
+//! with it.
+
+use crate::output::color;
+use crate::text;
+
+pub type Rgb = (u8, u8, u8);
+
+/// SGR attributes of one cell
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub inverse: bool,
+}
+
+impl Style {
+    /// Apply SGR parameters like "1;38;2;255;0;0"
+    pub fn apply(&mut self, params: &str) {
+        let parts: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let mut i = 0;
+        while i < parts.len() {
+            let extended = |i: usize| -> Option<(Rgb, usize)> {
+                match parts.get(i + 1) {
+                    Some(5) => Some((color::palette_rgb(*parts.get(i + 2)? as u8), 3)),
+                    Some(2) => Some((
+                        (
+                            *parts.get(i + 2)? as u8,
+                            *parts.get(i + 3)? as u8,
+                            *parts.get(i + 4)? as u8,
+                        ),
+                        5,
+                    )),
+                    _ => None,
+                }
+            };
+            match parts[i] {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                n @ 30..=37 => self.fg = Some(color::palette_rgb((n - 30) as u8)),
+                n @ 90..=97 => self.fg = Some(color::palette_rgb((n - 90 + 8) as u8)),
+                n @ 40..=47 => self.bg = Some(color::palette_rgb((n - 40) as u8)),
+                n @ 100..=107 => self.bg = Some(color::palette_rgb((n - 100 + 8) as u8)),
+                39 => self.fg = None,
+                49 => self.bg = None,
+                38 | 48 => {
+                    if let Some((rgb, len)) = extended(i) {
+                        if parts[i] == 38 {
+                            self.fg = Some(rgb);
+                        } else {
+                            self.bg = Some(rgb);
+                        }
+                        i += len;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Foreground and background on a terminal with colors `fg` and `bg`,
+    /// after `inverse` and `dim`
+    pub fn paint(&self, fg: Rgb, bg: Rgb) -> (Rgb, Rgb) {
+        let (mut fore, back) = (self.fg.unwrap_or(fg), self.bg.unwrap_or(bg));
+        if self.dim {
+            fore = mix(back, fore, 128);
+        }
+        if self.inverse {
+            (back, fore)
+        } else {
+            (fore, back)
+        }
+    }
+}
+
+/// `from` moved toward `to` by `amount` out of 255
+pub fn mix(from: Rgb, to: Rgb, amount: u8) -> Rgb {
+    let channel = |a: u8, b: u8| {
+        let (a, b, t) = (a as u32, b as u32, amount as u32);
+        ((a * (255 - t) + b * t + 127) / 255) as u8
+    };
+    (
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    )
+}
+
+/// One occupied cell; wide characters take this cell and the next
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub text: String,
+    pub style: Style,
+}
+
+/// A terminal screen that ANSI output is replayed onto
+///
+/// Unsized, it grows to fit everything written, which is what a one-off
+/// export wants. Sized, it wraps lines at the last column and scrolls once
+/// the cursor passes the last row, like the terminal a recording came from.
+#[derive(Debug, Clone, Default)]
+pub struct Screen {
+    lines: Vec<Vec<Option<Cell>>>,
+    /// Cursor line in `lines` and column
+    row: usize,
+    col: usize,
+    style: Style,
+    /// Columns and rows, when sized
+    size: Option<(usize, usize)>,
+    /// First line on screen; earlier ones have scrolled off
+    top: usize,
+    cursor_hidden: bool,
+    /// An escape sequence cut off at the end of the last write
+    pending: String,
+}
+
+impl Screen {
+    pub fn sized(cols: usize, rows: usize) -> Self {
+        Self {
+            size: Some((cols.max(1), rows.max(1))),
+            ..Self::default()
+        }
+    }
+
+    /// The visible lines of a sized screen, padded to its height
+    pub fn visible(&self) -> Vec<Vec<Option<Cell>>> {
+        let rows = self.size.map_or(self.lines.len(), |(_, rows)| rows);
+        (self.top..self.top + rows)
+            .map(|i| self.lines.get(i).cloned().unwrap_or_default())
+            .collect()
+    }
+
+    /// Cursor row and column on screen, unless the output hid it
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        (!self.cursor_hidden).then(|| (self.row - self.top, self.col))
+    }
+
+    fn line(&mut self) -> &mut Vec<Option<Cell>> {
+        while self.lines.len() <= self.row {
+            self.lines.push(Vec::new());
+        }
+        &mut self.lines[self.row]
+    }
+
+    /// Move the cursor to line `row`, scrolling a sized screen to keep it in view
+    fn go_to_row(&mut self, row: usize) {
+        self.row = row.max(self.top);
+        if let Some((_, rows)) = self.size {
+            if self.row >= self.top + rows {
+                self.top = self.row + 1 - rows;
+            }
+        }
+    }
+
+    fn clamp_col(&mut self) {
+        if let Some((cols, _)) = self.size {
+            self.col = self.col.min(cols - 1);
+        }
+    }
+
+    pub fn write(&mut self, ansi: &str) {
+        let input = std::mem::take(&mut self.pending) + ansi;
+        let mut chars = input.char_indices().peekable();
+
+        while let Some((start, ch)) = chars.next() {
+            match ch {
+                '\x1b' => match chars.next() {
+                    Some((_, '[')) => {
+                        let mut params = String::new();
+                        let mut last = None;
+                        for (_, c) in chars.by_ref() {
+                            if ('\x40'..='\x7e').contains(&c) {
+                                last = Some(c);
+                                break;
+                            }
+                            params.push(c);
+                        }
+                        match last {
+                            Some(last) => self.csi(&params, last),
+                            None => self.pending = input[start..].to_string(),
+                        }
+                    }
+                    // OSC (titles, hyperlinks): skip to BEL or ST
+                    Some((_, ']')) => {
+                        let mut done = false;
+                        while let Some((_, c)) = chars.next() {
+                            if c == '\x07'
+                                || (c == '\x1b' && chars.next_if(|(_, c)| *c == '\\').is_some())
+                            {
+                                done = true;
+                                break;
+                            }
+                        }
+                        if !done {
+                            self.pending = input[start..].to_string();
+                        }
+                    }
+                    Some(_) => {}
+                    None => self.pending = input[start..].to_string(),
+                },
+                '\n' => {
+                    self.go_to_row(self.row + 1);
+                    self.col = 0;
+                }
+                '\r' => self.col = 0,
+                '\x08' => self.col = self.col.saturating_sub(1),
+                '\t' => {
+                    self.col = (self.col / 8 + 1) * 8;
+                    self.clamp_col();
+                }
+                c if c.is_control() => {}
+                c => self.put(c),
+            }
+        }
+    }
+
+    fn put(&mut self, c: char) {
+        let width = text::char_width(c);
+        if width == 0 {
+            // Combining marks and variation selectors join the last cell
+            let col = self.col;
+            if let Some(Some(cell)) = col.checked_sub(1).and_then(|i| self.line().get_mut(i)) {
+                cell.text.push(c);
+            }
+            return;
+        }
+        if let Some((cols, _)) = self.size {
+            if self.col + width > cols {
+                self.go_to_row(self.row + 1);
+                self.col = 0;
+            }
+        }
+        let (col, style) = (self.col, self.style);
+        let line = self.line();
+        if line.len() < col + width {
+            line.resize(col + width, None);
+        }
+        line[col] = Some(Cell {
+            text: c.to_string(),
+            style,
+        });
+        for covered in &mut line[col + 1..col + width] {
+            *covered = None;
+        }
+        self.col += width;
+    }
+
+    /// Apply the control sequence `ESC [ params last`
+    fn csi(&mut self, params: &str, last: char) {
+        let n = params.parse::<usize>().unwrap_or(1).max(1);
+        // Erase modes default to 0 rather than 1
+        let mode = params.parse::<usize>().unwrap_or(0);
+        match last {
+            'm' => self.style.apply(params),
+            'A' => self.row = self.row.saturating_sub(n).max(self.top),
+            'B' => self.go_to_row(self.row + n),
+            'C' => {
+                self.col += n;
+                self.clamp_col();
+            }
+            'D' => self.col = self.col.saturating_sub(n),
+            'G' => {
+                self.col = n - 1;
+                self.clamp_col();
+            }
+            'd' => self.go_to_row(self.top + n - 1),
+            'H' | 'f' => {
+                let mut parts = params.split(';').map(|p| p.parse::<usize>().unwrap_or(1));
+                let row = parts.next().unwrap_or(1).max(1);
+                let col = parts.next().unwrap_or(1).max(1);
+                self.go_to_row(self.top + row - 1);
+                self.col = col - 1;
+                self.clamp_col();
+            }
+            'J' => {
+                let (row, col) = (self.row, self.col);
+                match mode {
+                    0 => {
+                        self.lines.truncate(row + 1);
+                        self.line().truncate(col);
+                    }
+                    1 => {
+                        for line in self.lines.iter_mut().take(row).skip(self.top) {
+                            line.clear();
+                        }
+                        self.erase_line_to(col + 1);
+                    }
+                    _ => self.lines.truncate(self.top),
+                }
+            }
+            'K' => match mode {
+                0 => {
+                    let col = self.col;
+                    self.line().truncate(col);
+                }
+                1 => self.erase_line_to(self.col + 1),
+                _ => self.line().clear(),
+            },
+            // Delete and insert characters, used by line editors
+            'P' => {
+                let col = self.col;
+                let line = self.line();
+                if col < line.len() {
+                    line.drain(col..(col + n).min(line.len()));
+                }
+            }
+            '@' => {
+                let col = self.col;
+                let line = self.line();
+                if col < line.len() {
+                    line.splice(col..col, std::iter::repeat_n(None, n));
+                }
+            }
+            'h' | 'l' => {
+                let set = last == 'h';
+                match params {
+                    "?25" => self.cursor_hidden = !set,
+                    // Entering or leaving the alternate screen starts blank
+                    "?1049" | "?47" | "?1047" => {
+                        self.lines.truncate(self.top);
+                        self.row = self.top;
+                        self.col = 0;
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Blank the cursor line up to column `end`
+    fn erase_line_to(&mut self, end: usize) {
+        let line = self.line();
+        let end = end.min(line.len());
+        line[..end].fill(None);
+    }
+}
+
+/// The final screen contents of `ansi`, one row per line
+pub fn grid(ansi: &str) -> Vec<Vec<Option<Cell>>> {
+    let mut screen = Screen::default();
+    screen.write(ansi);
+    let mut rows = screen.lines;
+    if rows.is_empty() {
+        rows.push(Vec::new());
+    }
+    while rows.last().is_some_and(|r| r.iter().all(Option::is_none)) {
+        rows.pop();
+    }
+    rows
+}
+
+/// `ansi` as plain text, after cursor movement is applied
+pub fn plain(ansi: &str) -> String {
+    grid(ansi)
+        .iter()
+        .map(|row| {
+            let mut line = String::new();
+            let mut skip = 0;
+            for cell in row {
+                match cell {
+                    Some(cell) => {
+                        line.push_str(&cell.text);
+                        skip = cell.text.chars().next().map_or(1, text::char_width) - 1;
+                    }
+                    None if skip > 0 => skip -= 1,
+                    None => line.push(' '),
+                }
+            }
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_plain_applies_carriage_returns_and_cursor_moves() {
+        assert_eq!(plain("loading 10%\rloading 100%\n"), "loading 100%");
+        assert_eq!(plain("a\nb\n\x1b[1Ac"), "a\nc");
+        assert_eq!(plain("\x1b]8;;https://x\x1b\\link\x1b]8;;\x07"), "link");
+        assert_eq!(plain("\x1b[31mred\x1b[0m  \n\n"), "red");
+    }
+
+    #[test]
+    fn test_sgr_colors() {
+        let mut style = Style::default();
+        style.apply("1;38;2;255;128;0;48;5;21");
+        assert!(style.bold);
+        assert_eq!(style.fg, Some((255, 128, 0)));
+        assert_eq!(style.bg, Some((0, 0, 255)));
+        style.apply("39;22");
+        assert_eq!(style.fg, None);
+        assert!(!style.bold);
+        style.apply("91");
+        assert_eq!(style.fg, Some((255, 0, 0)));
+        style.apply("0");
+        assert_eq!(style, Style::default());
+    }
+
+    #[test]
+    fn test_screen_clears_and_positions() {
+        assert_eq!(plain("old\x1b[2J\x1b[Hnew"), "new");
+        assert_eq!(plain("abc\x1b[2;3Hx"), "abc\n  x");
+        assert_eq!(plain("abcdef\r\x1b[3C\x1b[K"), "abc");
+        assert_eq!(plain("abcdef\x1b[4D\x1b[2P"), "abef");
+        assert_eq!(plain("ab\x08c"), "ac");
+    }
+
+    #[test]
+    fn test_sized_screen_wraps_and_scrolls() {
+        let mut screen = Screen::sized(4, 2);
+        screen.write("abcdef\r\n");
+        screen.write("gh\x1b[3");
+        // A sequence split across writes still applies
+        screen.write("1mi");
+        let text = |line: &Vec<Option<Cell>>| -> String {
+            line.iter().flatten().map(|c| c.text.as_str()).collect()
+        };
+        let visible = screen.visible();
+        assert_eq!(visible.len(), 2);
+        assert_eq!(text(&visible[0]), "ef");
+        assert_eq!(text(&visible[1]), "ghi");
+        assert_eq!(screen.cursor(), Some((1, 3)));
+        assert_eq!(
+            visible[1][2]
+                .as_ref()
+                .unwrap()
+                .style
+                .paint((0, 0, 0), (0, 0, 0))
+                .0,
+            (205, 0, 0)
+        );
+        screen.write("\x1b[?25l");
+        assert_eq!(screen.cursor(), None);
+    }
+
+    #[test]
+    fn test_paint_inverse_and_dim() {
+        let mut style = Style::default();
+        style.apply("7");
+        assert_eq!(
+            style.paint((200, 200, 200), (0, 0, 0)),
+            ((0, 0, 0), (200, 200, 200))
+        );
+        style.apply("0;2");
+        assert_eq!(style.paint((200, 200, 200), (0, 0, 0)).0, (100, 100, 100));
+    }
+
+    #[test]
+    fn test_wide_characters_take_two_cells() {
+        assert_eq!(plain("📊 x"), "📊 x");
+        let rows = grid("📊x");
+        assert_eq!(rows[0].len(), 3);
+        assert!(rows[0][1].is_none());
+    }
+}
@@ -1,11 +1,12 @@
+use crate::output::color;
 use crossterm::{cursor, terminal, ExecutableCommand};
-use std::io::{self, Write};
+use std::io::Write;
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-/// Parse interval string like "1s", "500ms", "2.5s" into Duration
+/// Parse interval string like "1s", "500ms", "2.5s", "5m", "1h" into Duration
 pub fn parse_interval(interval: &str) -> Result<Duration, String> {
     let interval = interval.trim();
 
@@ -20,14 +21,46 @@ pub fn parse_interval(interval: &str) -> Result<Duration, String> {
             .trim_end_matches('s')
             .parse::<f64>()
             .map_err(|_| format!("Invalid seconds: {}", interval))?;
-        Ok(Duration::from_secs_f64(secs))
+        seconds(secs, interval)
+    } else if interval.ends_with('m') || interval.ends_with('h') {
+        let (value, unit) = interval.split_at(interval.len() - 1);
+        let value = value
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid interval: {}", interval))?;
+        let scale = if unit == "m" { 60.0 } else { 3600.0 };
+        seconds(value * scale, interval)
     } else {
         // Default to seconds
         let secs = interval
             .parse::<f64>()
             .map_err(|_| format!("Invalid interval: {}", interval))?;
-        Ok(Duration::from_secs_f64(secs))
+        seconds(secs, interval)
+    }
+}
+
+/// Shortest time between refreshes; anything faster re-runs in a busy loop
+pub const MIN_REFRESH: Duration = Duration::from_millis(100);
+
+/// Parse how often to re-run or re-sample something, at least [`MIN_REFRESH`]
+pub fn parse_refresh(interval: &str) -> Result<Duration, String> {
+    let duration = parse_interval(interval)?;
+    if duration < MIN_REFRESH {
+        return Err(format!(
+            "Invalid interval: {} (must be at least 100ms)",
+            interval.trim()
+        ));
     }
+    Ok(duration)
+}
+
+/// `secs` as a Duration; negative, NaN and out-of-range values are errors
+fn seconds(secs: f64, interval: &str) -> Result<Duration, String> {
+    Duration::try_from_secs_f64(secs).map_err(|_| {
+        format!(
+            "Invalid interval: {} (use e.g. 500ms, 2.5s, 5m or 1h)",
+            interval
+        )
+    })
 }
 
 /// Execute a command and return its stdout output
@@ -62,20 +95,10 @@ pub fn setup_ctrl_c() -> Arc<AtomicBool> {
     running
 }
 
-/// Clear screen and move cursor to top
-pub fn clear_screen() {
-    let mut stdout = io::stdout();
-    stdout
-        .execute(terminal::Clear(terminal::ClearType::All))
-        .ok();
-    stdout.execute(cursor::MoveTo(0, 0)).ok();
-    stdout.flush().ok();
-}
-
 /// Clear the current line and move cursor to beginning
 #[allow(dead_code)]
 pub fn clear_line() {
-    let mut stdout = io::stdout();
+    let mut stdout = color::stdout();
     stdout
         .execute(terminal::Clear(terminal::ClearType::CurrentLine))
         .ok();
@@ -86,7 +109,7 @@ pub fn clear_line() {
 /// Move cursor to beginning of line
 #[allow(dead_code)]
 pub fn move_to_line_start() {
-    let mut stdout = io::stdout();
+    let mut stdout = color::stdout();
     stdout.execute(cursor::MoveToColumn(0)).ok();
     stdout.flush().ok();
 }
@@ -118,42 +141,42 @@ pub fn render(
 
     while running.load(Ordering::SeqCst) {
         iteration += 1;
-        clear_screen();
 
-        // Print header unless no_title
+        // Build the whole frame before touching the screen, so a slow
+        // command or Ctrl+C never leaves a cleared or half-drawn frame
+        let mut frame = String::new();
         if !no_title {
             let elapsed = format_duration(start_time.elapsed());
-            println!(
-                "\x1b[7m Every {} | {} | Elapsed: {} | Iteration: {} \x1b[0m",
+            frame.push_str(&format!(
+                "\x1b[7m Every {} | {} | Elapsed: {} | Iteration: {} \x1b[0m\n\n",
                 format_interval(interval),
                 command,
                 elapsed,
                 iteration
-            );
-            println!();
+            ));
         }
 
         // Execute command
-        match exec_command(command) {
+        let failed = match exec_command(command) {
             Ok(output) => {
-                if differences {
+                match (&last_output, differences) {
                     // Show differences from last output
-                    if let Some(ref prev) = last_output {
-                        print_with_differences(prev, &output);
-                    } else {
-                        println!("{}", output);
-                    }
+                    (Some(prev), true) => frame.push_str(&format_with_differences(prev, &output)),
+                    _ => frame.push_str(&format!("{}\n", output)),
+                }
+                if differences {
                     last_output = Some(output);
-                } else {
-                    println!("{}", output);
                 }
+                None
             }
             Err(e) => {
-                println!("\x1b[31mError: {}\x1b[0m", e);
-                if exit_on_error {
-                    return Err(e.into());
-                }
+                frame.push_str(&format!("\x1b[31mError: {}\x1b[0m\n", e));
+                Some(e)
             }
+        };
+        draw_frame(&frame);
+        if let Some(e) = failed.filter(|_| exit_on_error) {
+            return Err(e.into());
         }
 
         // Wait for interval, checking for Ctrl+C
@@ -182,22 +205,33 @@ fn format_interval(d: Duration) -> String {
     }
 }
 
-/// Print output with differences highlighted
-fn print_with_differences(old: &str, new: &str) {
+/// Replace the screen with `frame` in a single write
+fn draw_frame(frame: &str) {
+    let mut stdout = color::stdout();
+    let mut bytes = Vec::with_capacity(frame.len() + 8);
+    bytes.extend_from_slice(b"\x1b[2J\x1b[H");
+    bytes.extend_from_slice(frame.as_bytes());
+    stdout.write_all(&bytes).ok();
+    stdout.flush().ok();
+}
+
+/// Output with lines that differ from `old` highlighted
+fn format_with_differences(old: &str, new: &str) -> String {
     let old_lines: Vec<&str> = old.lines().collect();
-    let new_lines: Vec<&str> = new.lines().collect();
+    let mut out = String::new();
 
-    for (i, new_line) in new_lines.iter().enumerate() {
+    for (i, new_line) in new.lines().enumerate() {
         if i >= old_lines.len() {
             // New line added
-            println!("\x1b[32m{}\x1b[0m", new_line);
-        } else if old_lines[i] != *new_line {
+            out.push_str(&format!("\x1b[32m{}\x1b[0m\n", new_line));
+        } else if old_lines[i] != new_line {
             // Line changed
-            println!("\x1b[33m{}\x1b[0m", new_line);
+            out.push_str(&format!("\x1b[33m{}\x1b[0m\n", new_line));
         } else {
-            println!("{}", new_line);
+            out.push_str(&format!("{}\n", new_line));
         }
     }
+    out
 }
 
 #[cfg(test)]
@@ -223,6 +257,47 @@ mod tests {
         assert_eq!(parse_interval("1").unwrap(), Duration::from_secs(1));
     }
 
+    #[test]
+    fn test_parse_interval_rejects_negative_nan_and_overflow() {
+        for bad in ["-5s", "-1", "NaNs", "nan", "infs", "1e20s", "1e300"] {
+            assert!(parse_interval(bad).is_err(), "{}", bad);
+        }
+        assert_eq!(
+            parse_interval("-5s").unwrap_err(),
+            "Invalid interval: -5s (use e.g. 500ms, 2.5s, 5m or 1h)"
+        );
+    }
+
+    #[test]
+    fn test_parse_refresh_rejects_busy_loops() {
+        assert_eq!(parse_refresh("100ms").unwrap(), MIN_REFRESH);
+        assert_eq!(parse_refresh("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(
+            parse_refresh("0s").unwrap_err(),
+            "Invalid interval: 0s (must be at least 100ms)"
+        );
+        assert!(parse_refresh("0").is_err());
+        assert!(parse_refresh("50ms").is_err());
+        assert!(parse_refresh("-1s").unwrap_err().contains("use e.g."));
+    }
+
+    #[test]
+    fn test_parse_interval_minutes_and_hours() {
+        assert_eq!(parse_interval("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_interval("1.5h").unwrap(), Duration::from_secs(5400));
+        assert!(parse_interval("xm").is_err());
+        assert!(parse_interval("-1m").is_err());
+        assert!(parse_interval("1e20h").is_err());
+    }
+
+    #[test]
+    fn test_format_with_differences() {
+        assert_eq!(
+            format_with_differences("a\nb", "a\nc\nd"),
+            "a\n\x1b[33mc\x1b[0m\n\x1b[32md\x1b[0m\n"
+        );
+    }
+
     #[test]
     fn test_exec_command() {
         let result = exec_command("echo 42");
@@ -0,0 +1,344 @@
+//! Display width measurement with per-terminal emoji overrides
+//!
+//! Terminals disagree on how many cells emoji take, especially sequences
+//! with variation selectors, skin tones or zero-width joiners. The override
+//! table maps such clusters to the width a specific terminal actually uses.
+//! It lives in `~/.config/termgfx/emoji_widths.json` and can be filled by
+//! hand or by `termgfx emoji-widths --detect`, which prints each sample and
+//! asks the terminal where the cursor ended up.
+//!
+//! Word wrapping lives here too so wrapped text is measured the same way.
+
+use crate::output::{atomic, color};
+use crossterm::{cursor, terminal};
+use owo_colors::OwoColorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Cluster → cell width
+pub type WidthTable = BTreeMap<String, usize>;
+
+/// Emoji probed by `--detect`; chosen because terminals commonly disagree on them
+pub const PROBE_SAMPLES: &[&str] = &[
+    "✓",
+    "✗",
+    "✔️",
+    "✨",
+    "⚠️",
+    "ℹ️",
+    "❤️",
+    "☀️",
+    "⭐",
+    "🚀",
+    "🔥",
+    "👍",
+    "👍🏽",
+    "👨‍💻",
+    "👨‍👩‍👧",
+    "🏳️‍🌈",
+    "1️⃣",
+    "🇫🇷",
+];
+
+static TABLE: OnceLock<WidthTable> = OnceLock::new();
+
+/// Display width of `text` as the configured terminal renders it
+pub fn str_width(text: &str) -> usize {
+    width_with(text, table())
+}
+
+/// Display width of `text` using `overrides` for any cluster they contain
+pub fn width_with(text: &str, overrides: &WidthTable) -> usize {
+    if overrides.is_empty() || text.is_ascii() {
+        return UnicodeWidthStr::width(text);
+    }
+    clusters(text)
+        .into_iter()
+        .map(|cluster| {
+            overrides
+                .get(cluster)
+                .copied()
+                .unwrap_or_else(|| UnicodeWidthStr::width(cluster))
+        })
+        .sum()
+}
+
+/// Override table from the config file, loaded once per process
+pub fn table() -> &'static WidthTable {
+    TABLE.get_or_init(|| load().unwrap_or_default())
+}
+
+fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("termgfx")
+        .join("emoji_widths.json")
+}
+
+fn load() -> Option<WidthTable> {
+    let content = fs::read_to_string(config_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save(table: &WidthTable) -> io::Result<()> {
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(table).map_err(io::Error::other)?;
+    atomic::write(path, json)
+}
+
+/// Split text into emoji-aware clusters
+///
+/// Not full grapheme segmentation: a cluster is a base character plus any
+/// variation selectors, skin-tone modifiers, keycap marks and tag characters,
+/// joined to the next one by ZWJ. Regional indicators pair into flags.
+pub fn clusters(text: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    let mut indicators = 0;
+
+    for (idx, ch) in text.char_indices() {
+        let joins = match prev {
+            None => false,
+            Some('\u{200D}') => true,
+            Some(_) if is_extender(ch) || ch == '\u{200D}' => true,
+            Some(_) => is_regional_indicator(ch) && indicators % 2 == 1,
+        };
+        if !joins && idx > 0 {
+            result.push(&text[start..idx]);
+            start = idx;
+            indicators = 0;
+        }
+        if is_regional_indicator(ch) {
+            indicators += 1;
+        }
+        prev = Some(ch);
+    }
+    if start < text.len() {
+        result.push(&text[start..]);
+    }
+    result
+}
+
+fn is_extender(ch: char) -> bool {
+    matches!(ch,
+        '\u{FE0E}' | '\u{FE0F}'          // variation selectors
+        | '\u{20E3}'                     // combining keycap
+        | '\u{1F3FB}'..='\u{1F3FF}'      // skin tones
+        | '\u{E0020}'..='\u{E007F}') // tag sequences
+}
+
+fn is_regional_indicator(ch: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch)
+}
+
+/// Greedy word wrap by display width; words wider than `width` are split
+pub fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while str_width(word.as_str()) > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let mut head = String::new();
+            let mut rest = String::new();
+            for ch in word.chars() {
+                // A character wider than `width` still gets a line of its own
+                if rest.is_empty()
+                    && (head.is_empty()
+                        || str_width(head.as_str()) + UnicodeWidthChar::width(ch).unwrap_or(0)
+                            <= width)
+                {
+                    head.push(ch);
+                } else {
+                    rest.push(ch);
+                }
+            }
+            lines.push(head);
+            word = rest;
+        }
+        if word.is_empty() {
+            continue;
+        }
+        let current_width = str_width(current.as_str());
+        if current.is_empty() {
+            current = word;
+        } else if current_width + 1 + str_width(word.as_str()) <= width {
+            current.push(' ');
+            current.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+        }
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// `termgfx emoji-widths`: show, edit or detect the override table
+pub fn render(detect: bool, set: &[String], reset: bool) {
+    if let Err(e) = run(detect, set, reset) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(detect: bool, set: &[String], reset: bool) -> io::Result<()> {
+    let mut table = if reset {
+        WidthTable::new()
+    } else {
+        load().unwrap_or_default()
+    };
+
+    for entry in set {
+        let (cluster, width) = parse_entry(entry).map_err(io::Error::other)?;
+        table.insert(cluster, width);
+    }
+    if detect {
+        for (cluster, width) in probe_all(PROBE_SAMPLES)? {
+            if width == UnicodeWidthStr::width(cluster) {
+                table.remove(cluster);
+            } else {
+                table.insert(cluster.to_string(), width);
+            }
+        }
+    }
+
+    if detect || reset || !set.is_empty() {
+        save(&table)?;
+        println!(
+            "{} {}",
+            "Saved".bright_green(),
+            config_path().display().to_string().dimmed()
+        );
+    }
+    print_table(&table);
+    Ok(())
+}
+
+/// Parse `CLUSTER=WIDTH`
+fn parse_entry(entry: &str) -> Result<(String, usize), String> {
+    let (cluster, width) = entry
+        .rsplit_once('=')
+        .ok_or_else(|| format!("Invalid override '{}' (expected EMOJI=WIDTH)", entry))?;
+    let cluster = cluster.trim();
+    if cluster.is_empty() {
+        return Err(format!("Invalid override '{}' (missing emoji)", entry));
+    }
+    let width = width
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|w| *w <= 4)
+        .ok_or_else(|| format!("Invalid width in '{}' (expected 0-4)", entry))?;
+    Ok((cluster.to_string(), width))
+}
+
+fn print_table(table: &WidthTable) {
+    if table.is_empty() {
+        println!(
+            "{}",
+            "No emoji width overrides; using Unicode defaults".dimmed()
+        );
+        return;
+    }
+    println!("{}", "Emoji  Default  Override".bold());
+    for (cluster, width) in table {
+        let padding = 7usize.saturating_sub(*width);
+        println!(
+            "{}{}{:>7}  {:>8}",
+            cluster,
+            " ".repeat(padding),
+            UnicodeWidthStr::width(cluster.as_str()),
+            width.bright_cyan()
+        );
+    }
+}
+
+/// Print each sample at column 0 and read back the cursor column
+fn probe_all<'a>(samples: &[&'a str]) -> io::Result<Vec<(&'a str, usize)>> {
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "--detect needs an interactive terminal to probe",
+        ));
+    }
+
+    terminal::enable_raw_mode()?;
+    let results = probe_each(samples);
+    terminal::disable_raw_mode()?;
+    results
+}
+
+fn probe_each<'a>(samples: &[&'a str]) -> io::Result<Vec<(&'a str, usize)>> {
+    let mut stdout = color::stdout();
+    let mut results = Vec::new();
+    for sample in samples {
+        write!(stdout, "\r\x1b[2K{}", sample)?;
+        stdout.flush()?;
+        let (column, _) = cursor::position()?;
+        results.push((*sample, column as usize));
+    }
+    write!(stdout, "\r\x1b[2K")?;
+    stdout.flush()?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clusters_keep_emoji_sequences_together() {
+        assert_eq!(clusters("a❤️b"), vec!["a", "❤️", "b"]);
+        assert_eq!(clusters("👍🏽!"), vec!["👍🏽", "!"]);
+        assert_eq!(clusters("👨‍👩‍👧x"), vec!["👨‍👩‍👧", "x"]);
+        assert_eq!(clusters("🇫🇷🇩🇪"), vec!["🇫🇷", "🇩🇪"]);
+        assert_eq!(clusters("1️⃣"), vec!["1️⃣"]);
+    }
+
+    #[test]
+    fn test_width_with_overrides() {
+        let mut table = WidthTable::new();
+        assert_eq!(width_with("ok ❤️", &table), UnicodeWidthStr::width("ok ❤️"));
+
+        table.insert("❤️".to_string(), 1);
+        table.insert("👨‍💻".to_string(), 4);
+        assert_eq!(width_with("ok ❤️", &table), 4);
+        assert_eq!(width_with("👨‍💻 dev", &table), 8);
+        assert_eq!(width_with("plain", &table), 5);
+    }
+
+    #[test]
+    fn test_wrap_words_splits_on_width() {
+        let lines = wrap_words("Hello Wide World", 10);
+        assert_eq!(lines, vec!["Hello Wide", "World"]);
+    }
+
+    #[test]
+    fn test_wrap_words_breaks_long_word() {
+        let lines = wrap_words("abcdefgh", 3);
+        assert_eq!(lines, vec!["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(parse_entry("❤️=1"), Ok(("❤️".to_string(), 1)));
+        assert_eq!(parse_entry(" 🚀 = 2 "), Ok(("🚀".to_string(), 2)));
+        assert!(parse_entry("🚀").is_err());
+        assert!(parse_entry("=2").is_err());
+        assert!(parse_entry("🚀=9").is_err());
+    }
+}
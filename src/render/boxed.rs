@@ -0,0 +1,302 @@
+//! Bordered boxes: `termgfx box`, the studio preview and the browser demo's
+//! `box` all draw through [`framed`]
+
+use super::{Doc, Span, Style};
+use crate::text;
+
+/// Border character set for different styles
+#[derive(Debug, Clone)]
+pub struct BorderChars {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub header_left: &'static str,
+    pub header_right: &'static str,
+}
+
+impl BorderChars {
+    /// Characters for `single`, `double`, `rounded`, `heavy` (or `thick`)
+    /// and `ascii`; anything else is rounded
+    pub fn get(border_style: &str) -> Self {
+        match border_style.to_lowercase().as_str() {
+            "single" => BorderChars {
+                top_left: "┌",
+                top_right: "┐",
+                bottom_left: "└",
+                bottom_right: "┘",
+                horizontal: "─",
+                vertical: "│",
+                header_left: "├",
+                header_right: "┤",
+            },
+            "double" => BorderChars {
+                top_left: "╔",
+                top_right: "╗",
+                bottom_left: "╚",
+                bottom_right: "╝",
+                horizontal: "═",
+                vertical: "║",
+                header_left: "╠",
+                header_right: "╣",
+            },
+            "heavy" | "thick" => BorderChars {
+                top_left: "┏",
+                top_right: "┓",
+                bottom_left: "┗",
+                bottom_right: "┛",
+                horizontal: "━",
+                vertical: "┃",
+                header_left: "┣",
+                header_right: "┫",
+            },
+            "ascii" => BorderChars {
+                top_left: "+",
+                top_right: "+",
+                bottom_left: "+",
+                bottom_right: "+",
+                horizontal: "-",
+                vertical: "|",
+                header_left: "+",
+                header_right: "+",
+            },
+            _ => BorderChars {
+                top_left: "╭",
+                top_right: "╮",
+                bottom_left: "╰",
+                bottom_right: "╯",
+                horizontal: "─",
+                vertical: "│",
+                header_left: "├",
+                header_right: "┤",
+            },
+        }
+    }
+}
+
+/// Position of a label embedded in a border line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TitleAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl TitleAlign {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "left" => Some(TitleAlign::Left),
+            "center" | "centre" => Some(TitleAlign::Center),
+            "right" => Some(TitleAlign::Right),
+            _ => None,
+        }
+    }
+}
+
+/// How the border around a box's content is drawn
+#[derive(Debug, Clone, Copy)]
+pub struct Frame<'a> {
+    pub border: &'a str,
+    /// Style of the border and its labels
+    pub style: Style,
+    /// Text embedded in the top border
+    pub title: Option<&'a str>,
+    /// Text embedded in the bottom border
+    pub footer: Option<&'a str>,
+    /// Where title and footer sit along their border
+    pub title_align: TitleAlign,
+    /// Spaces between the border and the content on each side
+    pub padding: usize,
+    /// Maximum outer width; labels are cut to fit, content comes pre-wrapped
+    pub max_width: Option<usize>,
+}
+
+/// `content` lines inside a border, padded out to the widest one
+pub fn framed(content: &[Vec<Span>], frame: &Frame) -> Doc {
+    let borders = BorderChars::get(frame.border);
+    let padding = frame.padding;
+
+    // Labels keep one border character on each side and never widen the box
+    // past `max_width`
+    let label_limit = frame.max_width.map(|w| w.saturating_sub(4));
+    let label = |text: Option<&str>| {
+        text.map(|t| {
+            let t = t.trim();
+            let t = match label_limit {
+                Some(limit) => text::truncate(t, limit.saturating_sub(2), "…"),
+                None => t.to_string(),
+            };
+            format!(" {} ", t)
+        })
+    };
+    let title = label(frame.title);
+    let footer = label(frame.footer);
+
+    let label_width = |l: &Option<String>| l.as_deref().map(text::display_width).unwrap_or(0);
+    let widths: Vec<usize> = content.iter().map(|line| spans_width(line)).collect();
+    let content_width = widths.iter().copied().max().unwrap_or(0);
+    let box_width = (content_width + padding * 2)
+        .max(label_width(&title) + 2)
+        .max(label_width(&footer) + 2);
+
+    let mut doc = Doc::new();
+    doc.push(
+        &border_line(
+            borders.top_left,
+            borders.top_right,
+            borders.horizontal,
+            title.as_deref(),
+            frame.title_align,
+            box_width,
+        ),
+        frame.style,
+    );
+    for (line, width) in content.iter().zip(widths) {
+        let right_padding = box_width - padding - width;
+        doc.newline()
+            .push(
+                &format!("{}{}", borders.vertical, " ".repeat(padding)),
+                frame.style,
+            )
+            .extend(line)
+            .push(
+                &format!("{}{}", " ".repeat(right_padding), borders.vertical),
+                frame.style,
+            );
+    }
+    doc.newline().push(
+        &border_line(
+            borders.bottom_left,
+            borders.bottom_right,
+            borders.horizontal,
+            footer.as_deref(),
+            frame.title_align,
+            box_width,
+        ),
+        frame.style,
+    );
+    doc
+}
+
+/// Cells a line of spans takes up
+fn spans_width(spans: &[Span]) -> usize {
+    spans.iter().map(|s| text::display_width(&s.text)).sum()
+}
+
+/// Horizontal border `width` cells wide between its corners, with an optional label
+fn border_line(
+    left: &str,
+    right: &str,
+    horizontal: &str,
+    label: Option<&str>,
+    align: TitleAlign,
+    width: usize,
+) -> String {
+    let Some(label) = label else {
+        return format!("{}{}{}", left, horizontal.repeat(width), right);
+    };
+    let label_width = text::display_width(label);
+    let space = width - label_width;
+    let before = match align {
+        TitleAlign::Left => 1,
+        TitleAlign::Center => space / 2,
+        TitleAlign::Right => space - 1,
+    };
+    format!(
+        "{}{}{}{}{}",
+        left,
+        horizontal.repeat(before),
+        label,
+        horizontal.repeat(space - before),
+        right
+    )
+}
+
+/// `message` with the style's icon inside a one-line box, the browser demo's `box`
+#[cfg(any(feature = "wasm", test))]
+pub fn message_box(message: &str, style: &str, border: &str) -> Doc {
+    use super::Color;
+
+    let (color, emoji) = match style {
+        "success" => (Color::GREEN, "✓"),
+        "warning" => (Color::YELLOW, "⚠"),
+        "danger" => (Color::RED, "✗"),
+        "gradient" => (Color::MAGENTA, "✨"),
+        _ => (Color::BLUE, "ℹ"),
+    };
+    let content = vec![Span {
+        text: format!("{} {}", emoji, message),
+        style: Style::PLAIN,
+    }];
+    framed(
+        &[content],
+        &Frame {
+            border,
+            style: Style::fg(color),
+            title: None,
+            footer: None,
+            title_align: TitleAlign::Left,
+            padding: 2,
+            max_width: None,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_lines(doc: &Doc) -> Vec<String> {
+        doc.to_ansi()
+            .lines()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_border_chars() {
+        assert_eq!(BorderChars::get("single").top_left, "┌");
+        assert_eq!(BorderChars::get("DOUBLE").horizontal, "═");
+        assert_eq!(BorderChars::get("ascii").top_left, "+");
+        assert_eq!(BorderChars::get("other").top_left, "╭");
+    }
+
+    #[test]
+    fn test_box_lines_line_up() {
+        let doc = message_box("hi", "success", "double");
+        assert_eq!(
+            plain_lines(&doc),
+            ["╔════════╗", "║  ✓ hi  ║", "╚════════╝"]
+        );
+        assert!(doc.to_html().starts_with("<span class=\"green\">╔"));
+    }
+
+    #[test]
+    fn test_labels_are_aligned_and_cut_to_fit() {
+        let content = [vec![Span {
+            text: "ok".to_string(),
+            style: Style::PLAIN,
+        }]];
+        let mut frame = Frame {
+            border: "rounded",
+            style: Style::PLAIN,
+            title: Some("Deploy"),
+            footer: Some("v1.2"),
+            title_align: TitleAlign::Center,
+            padding: 6,
+            max_width: None,
+        };
+        let lines = plain_lines(&framed(&content, &frame));
+        assert_eq!(lines[0], "╭─── Deploy ───╮");
+        assert_eq!(lines[2], "╰──── v1.2 ────╯");
+
+        frame.title = Some("A very long deployment title");
+        frame.padding = 2;
+        frame.max_width = Some(16);
+        let lines = plain_lines(&framed(&content, &frame));
+        assert!(lines.iter().all(|l| text::display_width(l) <= 16));
+        assert!(lines[0].contains('…'));
+    }
+}
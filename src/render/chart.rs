@@ -0,0 +1,290 @@
+//! Bar, line and pie charts from [`Options::data`]
+
+use super::{Color, Doc, Options, Style};
+use crate::text;
+
+/// One bar per `Label:Value`, scaled so the largest fills `width`
+pub fn bar(options: &Options) -> Result<Doc, String> {
+    bars(options, &options.labeled()?, 1.0)
+}
+
+/// Frames for the bars to grow to full length, as in `termgfx chart bar
+/// --animate`
+pub const BUILD_FRAMES: u32 = 30;
+
+/// A bar chart whose bars grow over [`BUILD_FRAMES`] calls to
+/// [`tick`](Self::tick)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarBuildState {
+    options: Options,
+    data: Vec<(String, f64)>,
+    frame: u32,
+}
+
+impl BarBuildState {
+    pub fn new(options: Options) -> Result<Self, String> {
+        let data = options.labeled()?;
+        options.color_or(Color::CYAN)?;
+        Ok(Self {
+            options,
+            data,
+            frame: 0,
+        })
+    }
+
+    /// Advance one frame; false once the bars are full length
+    pub fn tick(&mut self) -> bool {
+        if self.frame < BUILD_FRAMES {
+            self.frame += 1;
+        }
+        !self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.frame >= BUILD_FRAMES
+    }
+
+    pub fn doc(&self) -> Doc {
+        let progress = self.frame as f64 / BUILD_FRAMES as f64;
+        bars(&self.options, &self.data, progress).unwrap_or_else(|e| Doc::error(&e))
+    }
+}
+
+/// The bar chart with every value at `progress` of its size, scaled
+/// against the full values
+fn bars(options: &Options, data: &[(String, f64)], progress: f64) -> Result<Doc, String> {
+    let fixed = match options.color {
+        Some(_) => Some(options.color_or(Color::CYAN)?),
+        None => None,
+    };
+    let max = data.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+    let label_width = data
+        .iter()
+        .map(|(l, _)| text::display_width(l))
+        .max()
+        .unwrap_or(0);
+
+    let mut doc = title(options);
+    for (i, (label, value)) in data.iter().enumerate() {
+        let color = fixed.unwrap_or(Color::CYCLE[i % Color::CYCLE.len()]);
+        let value = value * progress;
+        let cells = if max > 0.0 {
+            ((value.max(0.0) / max) * options.width as f64).round() as usize
+        } else {
+            0
+        };
+        let pad = label_width - text::display_width(label);
+        doc.plain(&format!("{}{} ", " ".repeat(pad), label))
+            .push("│", Style::dimmed())
+            .push(&"█".repeat(cells), Style::fg(color))
+            .plain(&format!(" {}", number(value)));
+        if i + 1 < data.len() {
+            doc.newline();
+        }
+    }
+    Ok(doc)
+}
+
+/// The values plotted left to right across `width`, `height` rows tall,
+/// with the range marked on the axis
+pub fn line(options: &Options) -> Result<Doc, String> {
+    let values = options.values()?;
+    let color = options.color_or(Color::CYAN)?;
+    let (width, height) = (options.width.max(2), options.height.max(2));
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = if max > min { max - min } else { 1.0 };
+
+    // Row of each column, 0 at the top, with points and the lines between
+    let row = |v: f64| ((max - v) / span * (height - 1) as f64).round() as usize;
+    let column = |i: usize| match values.len() {
+        1 => 0,
+        n => i * (width - 1) / (n - 1),
+    };
+    let mut grid = vec![vec![' '; width]; height];
+    for (i, pair) in values.windows(2).enumerate() {
+        let (x0, x1) = (column(i), column(i + 1));
+        let between = (x0 + 1..x1).map(|x| (x, (x - x0) as f64 / (x1 - x0) as f64));
+        for (x, t) in between {
+            grid[row(pair[0] + (pair[1] - pair[0]) * t)][x] = '·';
+        }
+    }
+    for (i, &v) in values.iter().enumerate() {
+        grid[row(v)][column(i)] = '●';
+    }
+
+    let (top, bottom) = (number(max), number(min));
+    let axis_width = text::display_width(&top).max(text::display_width(&bottom));
+    let mut doc = title(options);
+    for (r, cells) in grid.iter().enumerate() {
+        let (label, tick) = match r {
+            0 => (top.as_str(), "┤"),
+            r if r == height - 1 => (bottom.as_str(), "┤"),
+            _ => ("", "│"),
+        };
+        doc.push(
+            &format!("{:>w$} {}", label, tick, w = axis_width),
+            Style::dimmed(),
+        )
+        .push(
+            cells.iter().collect::<String>().trim_end(),
+            Style::fg(color),
+        );
+        if r + 1 < height {
+            doc.newline();
+        }
+    }
+    Ok(doc)
+}
+
+/// A filled circle `height` rows tall, sliced clockwise from the top, with
+/// a legend beside it
+pub fn pie(options: &Options) -> Result<Doc, String> {
+    let data = options.labeled()?;
+    let total: f64 = data.iter().map(|(_, v)| v.max(0.0)).sum();
+    if total <= 0.0 {
+        return Err("Pie values must add up to more than zero".to_string());
+    }
+
+    // Where each slice ends, as a fraction of the turn
+    let mut ends = Vec::with_capacity(data.len());
+    let mut sum = 0.0;
+    for (_, value) in &data {
+        sum += value.max(0.0) / total;
+        ends.push(sum);
+    }
+    let slice = |turn: f64| {
+        ends.iter()
+            .position(|&end| turn < end)
+            .unwrap_or(data.len() - 1)
+    };
+    let color = |i: usize| Color::CYCLE[i % Color::CYCLE.len()];
+
+    // Cells are about twice as tall as wide, so two columns per unit
+    let height = options.height.max(data.len()).max(3);
+    let radius = height as f64 / 2.0;
+    let mut doc = title(options);
+    for r in 0..height {
+        let y = radius - (r as f64 + 0.5);
+        for c in 0..(radius * 4.0).ceil() as usize {
+            let x = (c as f64 + 0.5) / 2.0 - radius;
+            if x * x + y * y > radius * radius {
+                doc.plain(" ");
+                continue;
+            }
+            // Clockwise from twelve o'clock
+            let turn = x.atan2(y).rem_euclid(std::f64::consts::TAU) / std::f64::consts::TAU;
+            doc.push("█", Style::fg(color(slice(turn))));
+        }
+        if let Some((label, value)) = data.get(r) {
+            doc.plain("  ")
+                .push("■", Style::fg(color(r)))
+                .plain(&format!(
+                    " {} {:.1}%",
+                    label,
+                    value.max(0.0) / total * 100.0
+                ));
+        }
+        if r + 1 < height {
+            doc.newline();
+        }
+    }
+    Ok(doc)
+}
+
+/// A document starting with the bold title line, if there is one
+pub(super) fn title(options: &Options) -> Doc {
+    let mut doc = Doc::new();
+    if let Some(title) = &options.title {
+        doc.push(title, Style::PLAIN.bold()).newline();
+    }
+    doc
+}
+
+/// `12` rather than `12.0`, but `2.5` as is
+pub(super) fn number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(data: &str) -> Options {
+        Options {
+            data: data.to_string(),
+            width: 10,
+            height: 4,
+            ..Options::default()
+        }
+    }
+
+    fn plain(doc: &Doc) -> Vec<String> {
+        doc.to_ansi()
+            .lines()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_bar_scales_to_the_largest_value() {
+        let doc = bar(&options("A:10,Bee:5")).unwrap();
+        assert_eq!(plain(&doc), ["  A │██████████ 10", "Bee │█████ 5"]);
+        assert!(doc.to_html().contains("<span class=\"green\">█████</span>"));
+
+        let mut red = options("A:1");
+        red.color = Some("red".to_string());
+        assert!(bar(&red).unwrap().to_html().contains("class=\"red\""));
+    }
+
+    #[test]
+    fn test_bar_build_grows_to_the_full_chart() {
+        let opts = options("A:10,B:4");
+        let mut build = BarBuildState::new(opts.clone()).unwrap();
+        assert_eq!(plain(&build.doc()), ["A │ 0", "B │ 0"]);
+        for _ in 0..BUILD_FRAMES / 2 {
+            build.tick();
+        }
+        assert_eq!(plain(&build.doc()), ["A │█████ 5", "B │██ 2"]);
+        while build.tick() {}
+        assert!(build.is_done());
+        assert_eq!(build.doc(), bar(&opts).unwrap());
+        assert!(BarBuildState::new(options("A")).is_err());
+    }
+
+    #[test]
+    fn test_line_marks_points_and_range() {
+        let mut opts = options("0,3");
+        opts.title = Some("Up".to_string());
+        let lines = plain(&line(&opts).unwrap());
+        assert_eq!(lines[0], "Up");
+        assert_eq!(lines[1], "3 ┤        ·●");
+        assert_eq!(lines[4], "0 ┤●·");
+        assert!(lines[2].contains('·') && lines[3].contains('·'));
+        assert!(line(&options("x")).is_err());
+    }
+
+    #[test]
+    fn test_pie_slices_and_legend() {
+        let doc = pie(&options("A:3,B:1")).unwrap();
+        let lines = plain(&doc);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].ends_with("■ A 75.0%"));
+        assert!(lines[1].ends_with("■ B 25.0%"));
+        // The left upper quarter is B's, everything else A's
+        let html = doc.to_html();
+        assert!(html.contains("class=\"cyan\"") && html.contains("class=\"green\""));
+        assert!(pie(&options("A:0")).is_err());
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number(12.0), "12");
+        assert_eq!(number(2.5), "2.5");
+        assert_eq!(number(-3.0), "-3");
+    }
+}
@@ -0,0 +1,154 @@
+//! A reading between `min` and `max`, drawn like `termgfx gauge`
+
+use super::{Color, Doc, Options, Style};
+
+/// Styles accepted by [`gauge`]; anything else is `semicircle`
+#[cfg(feature = "wasm")]
+pub const STYLES: [&str; 3] = ["semicircle", "full", "minimal"];
+
+/// Cells in the `minimal` bar
+const MINIMAL_WIDTH: usize = 20;
+
+/// `value` as a semicircle bar `width` cells wide, a ring `height` cells in
+/// radius, or a one-line minimal bar; colored red, yellow or green by the
+/// reading unless `color` is set
+pub fn gauge(options: &Options) -> Result<Doc, String> {
+    let (value, min, max) = (options.value, options.min, options.max);
+    let percent = if max > min {
+        ((value - min) / (max - min) * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+    let auto = if percent < 33.0 {
+        Color::RED
+    } else if percent < 66.0 {
+        Color::YELLOW
+    } else {
+        Color::GREEN
+    };
+    let color = Style::fg(options.color_or(auto)?);
+
+    let mut doc = Doc::new();
+    match options.style.as_deref().unwrap_or("semicircle") {
+        "full" => {
+            let radius = options.height.max(2) as f64;
+            let size = options.height.max(2) * 2;
+            for y in 0..=size {
+                for x in 0..=size {
+                    let (dx, dy) = ((x as f64 - radius).abs(), (y as f64 - radius).abs());
+                    if ((dx * dx + dy * dy).sqrt() - radius).abs() < 1.5 {
+                        let angle = dy.atan2(dx).to_degrees();
+                        if angle <= percent * 3.6 {
+                            doc.push("●", color);
+                        } else {
+                            doc.push("○", Style::dimmed());
+                        }
+                    } else {
+                        doc.plain(" ");
+                    }
+                }
+                doc.newline();
+            }
+            label(&mut doc, options);
+            doc.push(&format!("{:.1}", value), color);
+        }
+        "minimal" => {
+            label(&mut doc, options);
+            doc.plain("[");
+            fill(&mut doc, MINIMAL_WIDTH, percent, color, "░");
+            doc.plain("] ");
+            reading(&mut doc, percent, value, color);
+        }
+        _ => {
+            let width = options.width.max(1);
+            let border = Style::dimmed();
+            doc.push(&format!("╭{}╮", "─".repeat(width)), border)
+                .newline()
+                .push("│", border);
+            fill(&mut doc, width, percent, color, "▁");
+            doc.push("│", border)
+                .newline()
+                .push(&format!("╰{}╯", "─".repeat(width)), border)
+                .plain(" ");
+            label(&mut doc, options);
+            reading(&mut doc, percent, value, color);
+        }
+    }
+    Ok(doc)
+}
+
+/// `width` cells, filled in `color` up to `percent`, the rest dim `empty`
+fn fill(doc: &mut Doc, width: usize, percent: f64, color: Style, empty: &str) {
+    let filled = (width as f64 * percent / 100.0) as usize;
+    doc.push(&"█".repeat(filled), color)
+        .push(&empty.repeat(width - filled), Style::dimmed());
+}
+
+fn label(doc: &mut Doc, options: &Options) {
+    if let Some(label) = &options.label {
+        doc.push(&format!("{}: ", label), Style::fg(Color::CYAN));
+    }
+}
+
+/// The percentage, and the raw value when it differs
+fn reading(doc: &mut Doc, percent: f64, value: f64, color: Style) {
+    let mut text = format!("{:.1}%", percent);
+    if value != percent {
+        text.push_str(&format!(" ({:.1})", value));
+    }
+    doc.push(&text, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    fn options(value: f64, style: &str) -> Options {
+        Options {
+            value,
+            style: Some(style.to_string()),
+            label: Some("CPU".to_string()),
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn test_minimal_matches_the_cli() {
+        let doc = gauge(&options(65.0, "minimal")).unwrap();
+        let ansi = doc.to_ansi();
+        assert_eq!(text::strip_ansi(&ansi), "CPU: [█████████████░░░░░░░] 65.0%");
+        assert!(doc
+            .to_html()
+            .starts_with("<span class=\"cyan\">CPU: </span>[<span class=\"yellow\">"));
+    }
+
+    #[test]
+    fn test_semicircle_spans_width_and_shows_raw_value() {
+        let mut opts = options(5.0, "semicircle");
+        opts.max = 10.0;
+        opts.width = 10;
+        let ansi = gauge(&opts).unwrap().to_ansi();
+        let lines: Vec<String> = ansi
+            .lines()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect();
+        assert_eq!(lines[0], "╭──────────╮");
+        assert_eq!(lines[1], "│█████▁▁▁▁▁│");
+        assert_eq!(lines[2], "╰──────────╯ CPU: 50.0% (5.0)");
+    }
+
+    #[test]
+    fn test_full_ring_and_color_override() {
+        let mut opts = options(100.0, "full");
+        opts.height = 3;
+        opts.color = Some("blue".to_string());
+        let doc = gauge(&opts).unwrap();
+        let html = doc.to_html();
+        assert!(html.contains("class=\"blue\">●") && !html.contains('○'));
+        assert_eq!(doc.lines().len(), 8);
+
+        opts.color = Some("plaid".to_string());
+        assert!(gauge(&opts).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! A grid of shaded cells, laid out like `termgfx heatmap`
+
+use super::chart::title;
+use super::options::labels;
+use super::{Color, Doc, Options, Style};
+
+/// Schemes accepted by [`heatmap`]; anything else leaves cells uncolored
+#[cfg(feature = "wasm")]
+pub const SCHEMES: [&str; 4] = ["blue-red", "green-red", "viridis", "magma"];
+
+/// Rows of `data` as ░▒▓█ cells shaded from the smallest value to the
+/// largest, with optional column and row labels
+pub fn heatmap(options: &Options) -> Result<Doc, String> {
+    let grid = options.matrix()?;
+    let scheme = options.scheme.as_deref().unwrap_or("blue-red");
+    let (x_labels, y_labels) = (
+        labels(options.x_labels.as_deref()),
+        labels(options.y_labels.as_deref()),
+    );
+    let values = grid.iter().flatten().copied();
+    let min = values.clone().fold(f64::INFINITY, f64::min);
+    let max = values.fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut doc = title(options);
+    if !x_labels.is_empty() {
+        let header: Vec<String> = x_labels
+            .iter()
+            .take(columns)
+            .map(|l| format!("{:^4}", l))
+            .collect();
+        doc.plain(&format!("      {}", header.join(" "))).newline();
+    }
+    for (r, row) in grid.iter().enumerate() {
+        let label = y_labels.get(r).map(String::as_str).unwrap_or("");
+        doc.plain(&format!("{:>5} ", label));
+        for (c, &value) in row.iter().enumerate() {
+            if c > 0 {
+                doc.plain(" ");
+            }
+            let normalized = if range > 0.0 {
+                (value - min) / range
+            } else {
+                0.5
+            };
+            let style = shade(normalized, scheme).map_or(Style::PLAIN, Style::fg);
+            doc.plain(" ").push(block(normalized), style).plain("  ");
+        }
+        if r + 1 < grid.len() {
+            doc.newline();
+        }
+    }
+    Ok(doc)
+}
+
+/// ░ for the low end through █ for the high end
+fn block(normalized: f64) -> &'static str {
+    const BLOCKS: [&str; 4] = ["░", "▒", "▓", "█"];
+    BLOCKS[((normalized * 3.0).round() as usize).min(3)]
+}
+
+/// The same eight-color approximations the CLI uses
+fn shade(normalized: f64, scheme: &str) -> Option<Color> {
+    let color = match scheme {
+        "blue-red" if normalized < 0.33 => Color::BLUE,
+        "blue-red" if normalized < 0.67 => Color::YELLOW,
+        "blue-red" => Color::RED,
+        "green-red" if normalized < 0.5 => Color::GREEN,
+        "green-red" => Color::RED,
+        "viridis" if normalized < 0.25 => Color::MAGENTA,
+        "viridis" if normalized < 0.5 => Color::BLUE,
+        "viridis" if normalized < 0.75 => Color::GREEN,
+        "viridis" => Color::YELLOW,
+        "magma" if normalized < 0.33 => Color::MAGENTA,
+        "magma" if normalized < 0.67 => Color::RED,
+        "magma" => Color::YELLOW,
+        _ => return None,
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn test_cells_and_labels_line_up() {
+        let options = Options {
+            data: "0,1;2,3".to_string(),
+            x_labels: Some("a,b".to_string()),
+            y_labels: Some("r1".to_string()),
+            ..Options::default()
+        };
+        let doc = heatmap(&options).unwrap();
+        let lines: Vec<String> = doc
+            .to_ansi()
+            .lines()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect();
+        assert_eq!(
+            lines,
+            ["       a    b  ", "   r1  ░    ▒  ", "       ▓    █  "]
+        );
+        let html = doc.to_html();
+        assert!(html.contains("<span class=\"blue\">░</span>"));
+        assert!(html.contains("<span class=\"red\">█</span>"));
+    }
+
+    #[test]
+    fn test_schemes() {
+        assert_eq!(shade(0.1, "viridis"), Some(Color::MAGENTA));
+        assert_eq!(shade(0.9, "magma"), Some(Color::YELLOW));
+        assert_eq!(shade(0.9, "none"), None);
+        assert_eq!(block(0.5), "▓");
+    }
+}
@@ -0,0 +1,166 @@
+//! HTML backend: one `<span>` per styled run
+//!
+//! Named colors and attributes become classes (`red`, `bright-white`,
+//! `bold`, `bg-blue`) so the page decides the palette; exact colors are
+//! inlined. Lines are joined with `\n`, meant for a `<pre>`; [`page`]
+//! wraps them in one along with the styles for those classes.
+
+use super::{Color, Doc, Style, COLOR_NAMES};
+use std::fmt::Write;
+
+pub(super) fn render(doc: &Doc) -> String {
+    let mut out = String::new();
+    for (i, line) in doc.lines().iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        for span in line {
+            let text = escape(&span.text);
+            if span.style == Style::PLAIN {
+                out.push_str(&text);
+                continue;
+            }
+            let (classes, css) = attributes(&span.style);
+            out.push_str("<span");
+            if !classes.is_empty() {
+                let _ = write!(out, " class=\"{}\"", classes.join(" "));
+            }
+            if !css.is_empty() {
+                let _ = write!(out, " style=\"{}\"", css.join(";"));
+            }
+            let _ = write!(out, ">{}</span>", text);
+        }
+    }
+    out
+}
+
+/// `doc` in a `<pre class="termgfx">` preceded by its stylesheet, with the
+/// 16 named colors taken from `palette`
+pub(super) fn page(
+    doc: &Doc,
+    palette: &[(u8, u8, u8); 16],
+    background: &str,
+    foreground: &str,
+) -> String {
+    let mut css = format!(
+        ".termgfx {{ background: {}; color: {}; padding: 1em; line-height: 1.2; \
+         font-family: ui-monospace, Menlo, Consolas, monospace; }}\n",
+        background, foreground
+    );
+    for (n, (r, g, b)) in (0..).zip(palette) {
+        let name = class_name(n);
+        let _ = writeln!(
+            css,
+            ".termgfx .{0} {{ color: #{1:02x}{2:02x}{3:02x}; }} \
+             .termgfx .bg-{0} {{ background: #{1:02x}{2:02x}{3:02x}; }}",
+            name, r, g, b
+        );
+    }
+    css.push_str(
+        ".termgfx .bold { font-weight: bold; } .termgfx .dim { opacity: 0.6; } \
+         .termgfx .italic { font-style: italic; } .termgfx .underline { text-decoration: underline; }\n",
+    );
+    format!(
+        "<style>\n{}</style>\n<pre class=\"termgfx\">{}</pre>\n",
+        css,
+        render(doc)
+    )
+}
+
+fn attributes(style: &Style) -> (Vec<String>, Vec<String>) {
+    let mut classes = Vec::new();
+    let mut css = Vec::new();
+    for (color, class_prefix, property) in
+        [(style.fg, "", "color"), (style.bg, "bg-", "background")]
+    {
+        match color {
+            Some(Color::Named(n)) => classes.push(format!("{}{}", class_prefix, class_name(n))),
+            Some(Color::Rgb(r, g, b)) => {
+                css.push(format!("{}:#{:02x}{:02x}{:02x}", property, r, g, b))
+            }
+            None => {}
+        }
+    }
+    for (on, class) in [
+        (style.bold, "bold"),
+        (style.dim, "dim"),
+        (style.italic, "italic"),
+        (style.underline, "underline"),
+    ] {
+        if on {
+            classes.push(class.to_string());
+        }
+    }
+    (classes, css)
+}
+
+fn class_name(n: u8) -> String {
+    let name = COLOR_NAMES[(n & 7) as usize];
+    if n >= 8 {
+        format!("bright-{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// `text` safe inside markup; terminal escapes passed through in a span
+/// (inline image protocols, say) are dropped
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in crate::text::strip_ansi(text).chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classes_and_inline_colors() {
+        let mut doc = Doc::new();
+        doc.push("ok", Style::fg(Color::CYAN).bold())
+            .plain(" ")
+            .push("hot", Style::fg(Color::Rgb(255, 128, 0)))
+            .newline()
+            .push("x", Style::fg(Color::BRIGHT_WHITE))
+            .push("y", Style::dimmed());
+        assert_eq!(
+            render(&doc),
+            "<span class=\"cyan bold\">ok</span> <span style=\"color:#ff8000\">hot</span>\n\
+             <span class=\"bright-white\">x</span><span class=\"dim\">y</span>"
+        );
+    }
+
+    #[test]
+    fn test_page_styles_every_class() {
+        let mut doc = Doc::new();
+        doc.push("ok", Style::fg(Color::GREEN));
+        let mut palette = [(0, 0, 0); 16];
+        palette[2] = (0, 205, 0);
+        let html = page(&doc, &palette, "#1e1e1e", "#d4d4d4");
+        assert!(html.contains(".termgfx { background: #1e1e1e; color: #d4d4d4;"));
+        assert!(html.contains(".termgfx .green { color: #00cd00; }"));
+        assert!(html.contains(".termgfx .bg-bright-white {"));
+        assert!(html.ends_with("<pre class=\"termgfx\"><span class=\"green\">ok</span></pre>\n"));
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        let mut doc = Doc::new();
+        doc.plain("<b>&</b>")
+            .push("\"q\"", Style::fg(Color::RED))
+            .plain("\x1b_Gf=100;AAAA\x1b\\ ");
+        assert_eq!(
+            render(&doc),
+            "&lt;b&gt;&amp;&lt;/b&gt;<span class=\"red\">&quot;q&quot;</span> "
+        );
+    }
+}
@@ -0,0 +1,429 @@
+//! Backend-neutral rendering shared by the CLI and the WASM bindings
+//!
+//! Components build a [`Doc`] of styled spans, which a backend turns into
+//! ANSI for terminals or HTML for pages. `termgfx box`, `table` and
+//! `progress` print the same docs the browser demo draws, and hand them to
+//! `--format html|svg` directly. Output of other commands can come back in
+//! through [`Doc::from_ansi`] for `--export`; SVG is drawn from ANSI by the
+//! CLI's export module, which also lays out cursor movement.
+
+pub mod boxed;
+mod html;
+pub mod progress;
+pub mod spinner;
+pub mod table;
+
+// Only the browser demo draws these; the CLI has its own terminal renderers
+#[cfg(any(feature = "wasm", test))]
+pub mod chart;
+#[cfg(any(feature = "wasm", test))]
+pub mod gauge;
+#[cfg(any(feature = "wasm", test))]
+pub mod heatmap;
+#[cfg(any(feature = "wasm", test))]
+mod options;
+#[cfg(any(feature = "wasm", test))]
+pub mod timeline;
+
+#[cfg(any(feature = "wasm", test))]
+pub use options::Options;
+
+use crate::text;
+use std::fmt::Write;
+
+/// Names of the eight normal colors; `bright-` names the other eight
+pub const COLOR_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Channel levels of the 6×6×6 color cube (indices 16-231)
+pub const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// A terminal color: one of the 16 named ones, or exact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// 0-7 normal, 8-15 bright
+    Named(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    pub const RED: Color = Color::Named(1);
+    pub const GREEN: Color = Color::Named(2);
+    pub const YELLOW: Color = Color::Named(3);
+    #[cfg(any(feature = "wasm", test))]
+    pub const BLUE: Color = Color::Named(4);
+    #[cfg(any(feature = "wasm", test))]
+    pub const MAGENTA: Color = Color::Named(5);
+    pub const CYAN: Color = Color::Named(6);
+    pub const WHITE: Color = Color::Named(7);
+    pub const BRIGHT_BLACK: Color = Color::Named(8);
+    pub const BRIGHT_RED: Color = Color::Named(9);
+    pub const BRIGHT_GREEN: Color = Color::Named(10);
+    pub const BRIGHT_YELLOW: Color = Color::Named(11);
+    pub const BRIGHT_BLUE: Color = Color::Named(12);
+    pub const BRIGHT_MAGENTA: Color = Color::Named(13);
+    pub const BRIGHT_CYAN: Color = Color::Named(14);
+    #[cfg(any(feature = "wasm", test))]
+    pub const BRIGHT_WHITE: Color = Color::Named(15);
+
+    /// Series colors, in the order charts hand them out
+    #[cfg(any(feature = "wasm", test))]
+    pub const CYCLE: [Color; 6] = [
+        Color::CYAN,
+        Color::GREEN,
+        Color::YELLOW,
+        Color::MAGENTA,
+        Color::BLUE,
+        Color::RED,
+    ];
+
+    /// `red`, `bright-blue`, `grey` and the like
+    #[cfg(any(feature = "wasm", test))]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name == "grey" || name == "gray" {
+            return Some(Color::Named(8));
+        }
+        let (base, bright) = match name.strip_prefix("bright-") {
+            Some(base) => (base, 8),
+            None => (name.as_str(), 0),
+        };
+        let n = COLOR_NAMES.iter().position(|c| *c == base)?;
+        Some(Color::Named(n as u8 + bright))
+    }
+
+    /// Color for a 256-palette index
+    fn indexed(n: u8) -> Self {
+        match n {
+            0..=15 => Color::Named(n),
+            _ => {
+                let (r, g, b) = indexed_rgb(n);
+                Color::Rgb(r, g, b)
+            }
+        }
+    }
+}
+
+/// RGB of a 256-palette index past the 16 named colors
+pub fn indexed_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=231 => {
+            let n = n.saturating_sub(16);
+            (
+                CUBE_LEVELS[(n / 36) as usize],
+                CUBE_LEVELS[(n / 6 % 6) as usize],
+                CUBE_LEVELS[(n % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + (n - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+/// How a span is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        fg: None,
+        bg: None,
+        bold: false,
+        dim: false,
+        italic: false,
+        underline: false,
+    };
+
+    pub const fn fg(color: Color) -> Self {
+        Style {
+            fg: Some(color),
+            ..Style::PLAIN
+        }
+    }
+
+    #[cfg(any(feature = "wasm", test))]
+    pub const fn dimmed() -> Self {
+        Style {
+            dim: true,
+            ..Style::PLAIN
+        }
+    }
+
+    pub const fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    /// SGR parameters selecting this style after a reset
+    fn sgr(&self) -> String {
+        let mut codes: Vec<String> = Vec::new();
+        for (on, code) in [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+        ] {
+            if on {
+                codes.push(code.to_string());
+            }
+        }
+        for (color, base) in [(self.fg, 30), (self.bg, 40)] {
+            match color {
+                Some(Color::Named(n)) if n < 8 => codes.push((base + n as u16).to_string()),
+                Some(Color::Named(n)) => codes.push((base + 60 + (n & 7) as u16).to_string()),
+                Some(Color::Rgb(r, g, b)) => {
+                    codes.push(format!("{};2;{};{};{}", base + 8, r, g, b))
+                }
+                None => {}
+            }
+        }
+        codes.join(";")
+    }
+
+    /// Apply SGR parameters, as a terminal would
+    fn apply(&mut self, params: &str) {
+        let parts: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let mut i = 0;
+        while i < parts.len() {
+            match parts[i] {
+                0 => *self = Style::PLAIN,
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                n @ 30..=37 => self.fg = Some(Color::Named((n - 30) as u8)),
+                n @ 90..=97 => self.fg = Some(Color::Named((n - 90 + 8) as u8)),
+                n @ 40..=47 => self.bg = Some(Color::Named((n - 40) as u8)),
+                n @ 100..=107 => self.bg = Some(Color::Named((n - 100 + 8) as u8)),
+                39 => self.fg = None,
+                49 => self.bg = None,
+                n @ (38 | 48) => {
+                    let (color, used) = match parts.get(i + 1) {
+                        Some(5) => (parts.get(i + 2).map(|n| Color::indexed(*n as u8)), 2),
+                        Some(2) => (
+                            match parts.get(i + 2..i + 5) {
+                                Some([r, g, b]) => Some(Color::Rgb(*r as u8, *g as u8, *b as u8)),
+                                _ => None,
+                            },
+                            4,
+                        ),
+                        _ => (None, 0),
+                    };
+                    if n == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                    i += used;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// A run of text in one style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// Lines of styled spans
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Doc {
+    lines: Vec<Vec<Span>>,
+}
+
+impl Doc {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![Vec::new()],
+        }
+    }
+
+    /// Add `text` to the last line, merging with a span of the same style
+    pub fn push(&mut self, text: &str, style: Style) -> &mut Self {
+        if text.is_empty() {
+            return self;
+        }
+        if self.lines.is_empty() {
+            self.lines.push(Vec::new());
+        }
+        let line = self.lines.last_mut().expect("a line was just added");
+        match line.last_mut() {
+            Some(span) if span.style == style => span.text.push_str(text),
+            _ => line.push(Span {
+                text: text.to_string(),
+                style,
+            }),
+        }
+        self
+    }
+
+    /// Add `spans` to the last line
+    pub fn extend(&mut self, spans: &[Span]) -> &mut Self {
+        for span in spans {
+            self.push(&span.text, span.style);
+        }
+        self
+    }
+
+    /// Add unstyled text
+    pub fn plain(&mut self, text: &str) -> &mut Self {
+        self.push(text, Style::PLAIN)
+    }
+
+    /// Start a new line
+    pub fn newline(&mut self) -> &mut Self {
+        self.lines.push(Vec::new());
+        self
+    }
+
+    pub fn lines(&self) -> &[Vec<Span>] {
+        &self.lines
+    }
+
+    /// A one-line document saying what went wrong, in red
+    #[cfg(any(feature = "wasm", test))]
+    pub fn error(message: &str) -> Self {
+        let mut doc = Doc::new();
+        doc.push(&format!("Error: {}", message), Style::fg(Color::RED));
+        doc
+    }
+
+    /// `ansi` split into styled spans; escapes other than colors are dropped
+    pub fn from_ansi(ansi: &str) -> Self {
+        let mut doc = Doc::new();
+        let mut style = Style::PLAIN;
+        let mut run = String::new();
+        let mut chars = ansi.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    let seq = text::take_escape(&mut chars);
+                    if let Some(params) = seq.strip_prefix('[').and_then(|s| s.strip_suffix('m')) {
+                        doc.push(&run, style);
+                        run.clear();
+                        style.apply(params);
+                    }
+                }
+                '\n' => {
+                    doc.push(&run, style);
+                    run.clear();
+                    doc.newline();
+                }
+                '\r' => {}
+                c => run.push(c),
+            }
+        }
+        doc.push(&run, style);
+        // A trailing newline ends the last line rather than starting one
+        if doc.lines.len() > 1 && doc.lines.last().is_some_and(Vec::is_empty) {
+            doc.lines.pop();
+        }
+        doc
+    }
+
+    /// Text with SGR sequences, each styled span reset at its end
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            for span in line {
+                if span.style == Style::PLAIN {
+                    out.push_str(&span.text);
+                } else {
+                    let _ = write!(out, "\x1b[{}m{}\x1b[0m", span.style.sgr(), span.text);
+                }
+            }
+        }
+        out
+    }
+
+    /// `<span>` markup for a page that styles the color classes
+    #[cfg(any(feature = "wasm", test))]
+    pub fn to_html(&self) -> String {
+        html::render(self)
+    }
+
+    /// A standalone `<pre>` with its own styles, named colors from `palette`
+    pub fn to_html_page(
+        &self,
+        palette: &[(u8, u8, u8); 16],
+        background: &str,
+        foreground: &str,
+    ) -> String {
+        html::page(self, palette, background, foreground)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_from_name() {
+        assert_eq!(Color::from_name("Cyan"), Some(Color::CYAN));
+        assert_eq!(Color::from_name("bright-white"), Some(Color::BRIGHT_WHITE));
+        assert_eq!(Color::from_name("grey"), Some(Color::Named(8)));
+        assert_eq!(Color::from_name("orange"), None);
+    }
+
+    #[test]
+    fn test_push_merges_same_style() {
+        let mut doc = Doc::new();
+        doc.push("ab", Style::fg(Color::RED))
+            .push("c", Style::fg(Color::RED))
+            .plain(" d")
+            .newline()
+            .plain("e");
+        assert_eq!(doc.lines().len(), 2);
+        assert_eq!(doc.lines()[0].len(), 2);
+        assert_eq!(doc.lines()[0][0].text, "abc");
+    }
+
+    #[test]
+    fn test_ansi_round_trip() {
+        let mut doc = Doc::new();
+        doc.push("hi", Style::fg(Color::CYAN).bold())
+            .plain(" there")
+            .newline()
+            .push("x", Style::fg(Color::Rgb(1, 2, 3)))
+            .push("y", Style::fg(Color::BRIGHT_WHITE));
+        let ansi = doc.to_ansi();
+        assert_eq!(
+            ansi,
+            "\x1b[1;36mhi\x1b[0m there\n\x1b[38;2;1;2;3mx\x1b[0m\x1b[97my\x1b[0m"
+        );
+        assert_eq!(Doc::from_ansi(&ansi), doc);
+    }
+
+    #[test]
+    fn test_from_ansi_colors() {
+        let doc = Doc::from_ansi("\x1b[38;5;196;48;5;4ma\x1b[39;2mb\x1b[22;49mc\x1b[2K\n");
+        let spans = &doc.lines()[0];
+        assert_eq!(doc.lines().len(), 1);
+        assert_eq!(spans[0].style.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(spans[0].style.bg, Some(Color::Named(4)));
+        assert_eq!(spans[1].style.fg, None);
+        assert!(spans[1].style.dim);
+        assert_eq!(spans[2].style, Style::PLAIN);
+        assert_eq!(spans[2].text, "c");
+    }
+}
@@ -0,0 +1,168 @@
+//! Settings for the chart-like components, one struct for all of them
+//!
+//! The WASM bindings deserialize it from a JS object with camelCase keys;
+//! each component reads the fields it needs and ignores the rest.
+
+use super::Color;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Options {
+    /// `Label:Value,...` for bar, pie and timeline (timeline labels may stand
+    /// alone), `1,4,2` for line, `1,2;3,4` rows for heatmap
+    pub data: String,
+    pub title: Option<String>,
+    /// Cells across the plot or bar
+    pub width: usize,
+    /// Rows of the plot, pie or gauge
+    pub height: usize,
+    /// Gauge reading, between `min` and `max`
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub label: Option<String>,
+    /// Gauge: semicircle, full, minimal; timeline: arrow, line, dots
+    pub style: Option<String>,
+    /// A color name, as in [`Color::from_name`]
+    pub color: Option<String>,
+    /// Heatmap column and row labels, comma-separated
+    pub x_labels: Option<String>,
+    pub y_labels: Option<String>,
+    /// Heatmap color scheme: blue-red, green-red, viridis, magma
+    pub scheme: Option<String>,
+    /// Timeline runs down the page instead of across
+    pub vertical: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            data: String::new(),
+            title: None,
+            width: 40,
+            height: 10,
+            value: 0.0,
+            min: 0.0,
+            max: 100.0,
+            label: None,
+            style: None,
+            color: None,
+            x_labels: None,
+            y_labels: None,
+            scheme: None,
+            vertical: false,
+        }
+    }
+}
+
+impl Options {
+    /// `color` by name, else `default`
+    pub(super) fn color_or(&self, default: Color) -> Result<Color, String> {
+        match &self.color {
+            Some(name) => Color::from_name(name).ok_or_else(|| format!("Unknown color '{}'", name)),
+            None => Ok(default),
+        }
+    }
+
+    /// `data` as `Label:Value` pairs
+    pub(super) fn labeled(&self) -> Result<Vec<(String, f64)>, String> {
+        let pairs: Vec<(String, f64)> = items(&self.data, ',')
+            .map(|item| {
+                let (label, value) = item
+                    .rsplit_once(':')
+                    .ok_or_else(|| format!("Expected Label:Value, got '{}'", item))?;
+                Ok((label.trim().to_string(), number(value)?))
+            })
+            .collect::<Result<_, String>>()?;
+        if pairs.is_empty() {
+            return Err("No data".to_string());
+        }
+        Ok(pairs)
+    }
+
+    /// `data` as comma-separated numbers
+    pub(super) fn values(&self) -> Result<Vec<f64>, String> {
+        let values: Vec<f64> = items(&self.data, ',')
+            .map(number)
+            .collect::<Result<_, _>>()?;
+        if values.is_empty() {
+            return Err("No data".to_string());
+        }
+        Ok(values)
+    }
+
+    /// `data` as rows of numbers, rows split by `;`
+    pub(super) fn matrix(&self) -> Result<Vec<Vec<f64>>, String> {
+        let rows: Vec<Vec<f64>> = items(&self.data, ';')
+            .map(|row| items(row, ',').map(number).collect::<Result<_, _>>())
+            .collect::<Result<_, _>>()?;
+        if rows.iter().all(Vec::is_empty) {
+            return Err("No data".to_string());
+        }
+        Ok(rows)
+    }
+}
+
+/// Comma-separated labels, trimmed
+pub(super) fn labels(list: Option<&str>) -> Vec<String> {
+    list.map(|l| items(l, ',').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn items(list: &str, separator: char) -> impl Iterator<Item = &str> {
+    list.split(separator)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn number(text: &str) -> Result<f64, String> {
+    text.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("'{}' is not a number", text.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(data: &str) -> Options {
+        Options {
+            data: data.to_string(),
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn test_deserializes_camel_case_with_defaults() {
+        let options: Options =
+            serde_json::from_str(r#"{"data": "1,2;3,4", "xLabels": "a,b", "height": 4}"#).unwrap();
+        assert_eq!(options.x_labels.as_deref(), Some("a,b"));
+        assert_eq!((options.width, options.height, options.max), (40, 4, 100.0));
+    }
+
+    #[test]
+    fn test_parsing_data() {
+        assert_eq!(
+            data("Q1: 10, 12:30:5").labeled(),
+            Ok(vec![("Q1".to_string(), 10.0), ("12:30".to_string(), 5.0)])
+        );
+        assert!(data("Q1").labeled().unwrap_err().contains("Label:Value"));
+        assert_eq!(data("1, 2.5,").values(), Ok(vec![1.0, 2.5]));
+        assert!(data("1,x").values().unwrap_err().contains("'x'"));
+        assert_eq!(data("1,2;3").matrix(), Ok(vec![vec![1.0, 2.0], vec![3.0]]));
+        assert_eq!(data("").values(), Err("No data".to_string()));
+    }
+
+    #[test]
+    fn test_color_or() {
+        let mut options = Options::default();
+        assert_eq!(options.color_or(Color::CYAN), Ok(Color::CYAN));
+        options.color = Some("red".to_string());
+        assert_eq!(options.color_or(Color::CYAN), Ok(Color::RED));
+        options.color = Some("plaid".to_string());
+        assert!(options.color_or(Color::CYAN).is_err());
+    }
+}
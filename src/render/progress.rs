@@ -0,0 +1,219 @@
+//! Percentage bars: `termgfx progress`, the studio and the browser demo's
+//! `progress` all draw through [`bar`]
+
+use super::{Color, Doc, Style};
+
+/// Styles accepted by [`bar`]; anything else is `gradient`
+pub const STYLES: [&str; 7] = [
+    "gradient", "modern", "animated", "blocks", "classic", "thin", "dots",
+];
+
+/// Ends of the `modern` style's fade, green to blue
+pub const MODERN: ((u8, u8, u8), (u8, u8, u8)) = ((63, 185, 80), (88, 166, 255));
+
+const TRACK: Color = Color::Rgb(72, 79, 88);
+
+/// A bar at `percent` in `style`, followed by the number
+pub fn bar(percent: u8, style: &str) -> Doc {
+    let percent = percent.min(100);
+    let label = Style::fg(Color::BRIGHT_CYAN).bold();
+    let cyan = Style::fg(Color::CYAN);
+
+    let mut doc = Doc::new();
+    match style {
+        "blocks" | "dots" => {
+            let (full, hollow) = if style == "dots" {
+                ("●", "○")
+            } else {
+                ("█", "░")
+            };
+            let (filled, empty) = split(percent, 20);
+            doc.push(&full.repeat(filled), cyan)
+                .push(&hollow.repeat(empty), cyan);
+        }
+        "classic" => {
+            let (filled, _) = split(percent, 20);
+            doc.plain("[").push(&"=".repeat(filled), cyan);
+            if filled < 20 {
+                doc.push(">", Style::fg(Color::BRIGHT_CYAN));
+            }
+            doc.plain(&" ".repeat(20usize.saturating_sub(filled + 1)))
+                .plain("]");
+        }
+        "thin" => {
+            let (filled, empty) = split(percent, 20);
+            doc.push(&"━".repeat(filled), cyan)
+                .push(&"━".repeat(empty), Style::fg(Color::BRIGHT_BLACK));
+        }
+        "animated" => {
+            let pattern = ['█', '▓', '▒', '░'];
+            let (filled, empty) = split(percent, 30);
+            let bar: String = (0..filled).map(|i| pattern[i % pattern.len()]).collect();
+            doc.push(&bar, cyan).plain(&" ".repeat(empty));
+        }
+        "modern" => return fade(percent, MODERN.0, MODERN.1),
+        _ => {
+            // Gradient: red -> yellow -> green
+            let (filled, empty) = split(percent, 20);
+            for i in 0..filled {
+                doc.push("█", Style::fg(level((i * 100 / 20) as u8)));
+            }
+            doc.push(&"░".repeat(empty), Style::fg(Color::BRIGHT_BLACK));
+            return labelled(doc, percent, Style::fg(level(percent)).bold());
+        }
+    }
+    labelled(doc, percent, label)
+}
+
+/// A 30-cell bar whose filled part fades from `start` to `end`
+pub fn fade(percent: u8, start: (u8, u8, u8), end: (u8, u8, u8)) -> Doc {
+    let percent = percent.min(100);
+    let (filled, empty) = split(percent, 30);
+    let mix = |a: u8, b: u8, t: f32| (a as f32 + t * (b as f32 - a as f32)) as u8;
+
+    let mut doc = Doc::new();
+    for i in 0..filled {
+        let t = i as f32 / 30.0;
+        let color = Color::Rgb(
+            mix(start.0, end.0, t),
+            mix(start.1, end.1, t),
+            mix(start.2, end.2, t),
+        );
+        doc.push("█", Style::fg(color));
+    }
+    doc.push(&"░".repeat(empty), Style::fg(TRACK));
+    let (r, g, b) = end;
+    labelled(doc, percent, Style::fg(Color::Rgb(r, g, b)).bold())
+}
+
+/// Filled and empty cells of a `width`-cell bar
+fn split(percent: u8, width: usize) -> (usize, usize) {
+    let filled = width * percent as usize / 100;
+    (filled, width - filled)
+}
+
+fn labelled(mut doc: Doc, percent: u8, style: Style) -> Doc {
+    doc.plain(" ").push(&format!("{}%", percent), style);
+    doc
+}
+
+/// Steps from empty to the target, as in `termgfx progress --animate`
+pub const STEPS: u32 = 30;
+
+/// A bar that fills up to `target` over [`STEPS`] calls to
+/// [`tick`](Self::tick)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressState {
+    target: u8,
+    step: u32,
+    style: String,
+}
+
+impl ProgressState {
+    pub fn new(target: u8, style: &str) -> Self {
+        Self {
+            target: target.min(100),
+            step: 0,
+            style: style.to_string(),
+        }
+    }
+
+    /// Advance one step; false once the bar has reached the target
+    pub fn tick(&mut self) -> bool {
+        if self.step < STEPS {
+            self.step += 1;
+        }
+        !self.is_done()
+    }
+
+    /// Fill towards a new target, starting over from the current percentage
+    pub fn set_target(&mut self, target: u8) {
+        let current = self.percent() as u32;
+        let target = target.min(100);
+        self.step = match target as u32 {
+            0 => STEPS,
+            t => (current * STEPS / t).min(STEPS),
+        };
+        self.target = target;
+    }
+
+    pub fn percent(&self) -> u8 {
+        (self.step * self.target as u32 / STEPS) as u8
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step >= STEPS
+    }
+
+    pub fn doc(&self) -> Doc {
+        bar(self.percent(), &self.style)
+    }
+}
+
+/// Red, yellow or green by thirds
+fn level(pos: u8) -> Color {
+    if pos < 33 {
+        Color::RED
+    } else if pos < 66 {
+        Color::YELLOW
+    } else {
+        Color::GREEN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn test_every_style_keeps_its_width() {
+        for style in STYLES {
+            let width = text::display_width(&bar(0, style).to_ansi()) - " 0%".len();
+            for percent in [0, 50, 100, 200] {
+                let ansi = bar(percent, style).to_ansi();
+                let label = format!(" {}%", percent.min(100));
+                assert_eq!(text::display_width(&ansi), width + label.len(), "{}", style);
+                assert!(ansi.ends_with(&format!("{}\x1b[0m", label.trim())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_classic_and_fade() {
+        let plain = |doc: Doc| text::strip_ansi(&doc.to_ansi()).into_owned();
+        assert_eq!(plain(bar(50, "classic")), "[==========>         ] 50%");
+        assert_eq!(plain(bar(100, "classic")), "[====================] 100%");
+        let ansi = fade(10, (0, 0, 0), (255, 255, 255)).to_ansi();
+        assert!(ansi.starts_with("\x1b[38;2;0;0;0m█\x1b[0m"));
+        assert!(ansi.ends_with("\x1b[1;38;2;255;255;255m10%\x1b[0m"));
+    }
+
+    #[test]
+    fn test_state_fills_to_the_target() {
+        let mut state = ProgressState::new(60, "blocks");
+        assert_eq!(state.percent(), 0);
+        for _ in 0..STEPS - 1 {
+            assert!(state.tick());
+        }
+        assert!(!state.tick());
+        assert_eq!(state.doc(), bar(60, "blocks"));
+        assert!(!state.tick());
+
+        state.set_target(90);
+        assert_eq!(state.percent(), 60);
+        assert!(!state.is_done());
+        state.set_target(0);
+        assert!(state.is_done());
+    }
+
+    #[test]
+    fn test_gradient_label_color() {
+        let html = bar(20, "gradient").to_html();
+        assert!(html.starts_with("<span class=\"red\">████</span><span class=\"bright-black\">░"));
+        assert!(html.ends_with("<span class=\"red bold\">20%</span>"));
+        assert!(bar(90, "gradient")
+            .to_html()
+            .ends_with("<span class=\"green bold\">90%</span>"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Spinner frames, and a state that steps through them one tick at a time
+
+use super::{Color, Doc, Style};
+
+/// Styles accepted by [`frames`]; anything else is `dots`
+pub const STYLES: [&str; 8] = [
+    "dots", "line", "arc", "bouncing", "clock", "circle", "bounce", "moon",
+];
+
+/// Milliseconds each frame stays up, as in `termgfx spinner`
+pub const FRAME_MS: u32 = 80;
+
+/// The frames of a spinner style
+pub fn frames(style: &str) -> &'static [&'static str] {
+    match style {
+        "line" => &["|", "/", "-", "\\"],
+        "arc" => &["◜", "◠", "◝", "◞", "◡", "◟"],
+        "bouncing" => &["⠁", "⠂", "⠄", "⠂"],
+        "clock" => &[
+            "🕐", "🕑", "🕒", "🕓", "🕔", "🕕", "🕖", "🕗", "🕘", "🕙", "🕚", "🕛",
+        ],
+        "circle" => &["◐", "◓", "◑", "◒"],
+        "bounce" => &["⠁", "⠂", "⠄", "⡀", "⢀", "⠠", "⠐", "⠈"],
+        "moon" => &["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"],
+        _ => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+    }
+}
+
+/// A spinner that the caller advances, one frame per [`tick`](Self::tick)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpinnerState {
+    frames: &'static [&'static str],
+    index: usize,
+    message: String,
+}
+
+impl SpinnerState {
+    pub fn new(style: &str, message: &str) -> Self {
+        Self {
+            frames: frames(style),
+            index: 0,
+            message: message.to_string(),
+        }
+    }
+
+    /// Move to the next frame, wrapping around
+    pub fn tick(&mut self) {
+        self.index = (self.index + 1) % self.frames.len();
+    }
+
+    #[cfg(feature = "wasm")]
+    pub fn set_message(&mut self, message: &str) {
+        self.message = message.to_string();
+    }
+
+    /// The current frame in cyan, then the message
+    pub fn doc(&self) -> Doc {
+        let mut doc = Doc::new();
+        doc.push(self.frames[self.index], Style::fg(Color::CYAN));
+        if !self.message.is_empty() {
+            doc.plain(" ").plain(&self.message);
+        }
+        doc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_wraps_around_the_frames() {
+        let mut spinner = SpinnerState::new("line", "Loading");
+        assert_eq!(spinner.doc().to_ansi(), "\x1b[36m|\x1b[0m Loading");
+        for _ in 0..3 {
+            spinner.tick();
+        }
+        assert!(spinner
+            .doc()
+            .to_html()
+            .starts_with("<span class=\"cyan\">\\</span>"));
+        spinner.tick();
+        assert_eq!(spinner, SpinnerState::new("line", "Loading"));
+    }
+
+    #[test]
+    fn test_unknown_style_is_dots() {
+        assert_eq!(frames("nope"), frames("dots"));
+        assert_eq!(frames("nope").len(), 10);
+    }
+}
@@ -0,0 +1,272 @@
+//! Bordered tables: `termgfx table`, markdown tables and the browser demo's
+//! `table` all draw through [`grid`]
+
+use super::{Doc, Span, Style};
+use crate::text;
+
+/// Border character set for different styles
+#[derive(Debug, Clone)]
+pub struct BorderChars {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub cross: &'static str,
+    pub t_down: &'static str,
+    pub t_up: &'static str,
+    pub t_right: &'static str,
+    pub t_left: &'static str,
+}
+
+impl BorderChars {
+    /// Characters for `double`, `rounded`, `heavy`, `ascii` and `none`;
+    /// anything else is single
+    pub fn get(border_style: &str) -> Self {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical, cross, t_down, t_up, t_right, t_left] =
+            match border_style.to_lowercase().as_str() {
+                "double" => ["╔", "╗", "╚", "╝", "═", "║", "╬", "╦", "╩", "╠", "╣"],
+                "rounded" => ["╭", "╮", "╰", "╯", "─", "│", "┼", "┬", "┴", "├", "┤"],
+                "heavy" => ["┏", "┓", "┗", "┛", "━", "┃", "╋", "┳", "┻", "┣", "┫"],
+                "ascii" => ["+", "+", "+", "+", "-", "|", "+", "+", "+", "+", "+"],
+                "none" => [" "; 11],
+                _ => ["┌", "┐", "└", "┘", "─", "│", "┼", "┬", "┴", "├", "┤"],
+            };
+        BorderChars {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            horizontal,
+            vertical,
+            cross,
+            t_down,
+            t_up,
+            t_right,
+            t_left,
+        }
+    }
+}
+
+/// Where a cell's text sits within its column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+impl Alignment {
+    /// `right` and `center`; anything else is left
+    pub fn from_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "right" => Alignment::Right,
+            "center" => Alignment::Center,
+            _ => Alignment::Left,
+        }
+    }
+
+    /// Spaces before and after `used` cells of text in a `width`-cell column
+    fn padding(self, used: usize, width: usize) -> (usize, usize) {
+        let space = width.saturating_sub(used);
+        match self {
+            Alignment::Left => (0, space),
+            Alignment::Right => (space, 0),
+            Alignment::Center => (space / 2, space - space / 2),
+        }
+    }
+}
+
+/// How the lines around the cells are drawn
+#[derive(Debug, Clone, Copy)]
+pub struct Grid<'a> {
+    pub border: &'a str,
+    pub alignment: Alignment,
+    /// Style of the border characters
+    pub style: Style,
+}
+
+/// One [`Doc`] per cell; a cell may take several lines
+pub type Row = Vec<Doc>;
+
+/// Widest line of each column's cells
+#[cfg(any(feature = "wasm", test))]
+pub fn column_widths(rows: &[Row]) -> Vec<usize> {
+    let mut widths = Vec::new();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            let width = cell.lines().iter().map(|l| spans_width(l)).max();
+            if i == widths.len() {
+                widths.push(0);
+            }
+            widths[i] = widths[i].max(width.unwrap_or(0));
+        }
+    }
+    widths
+}
+
+/// `sections` of rows (header, body, footer) between horizontal rules, in
+/// columns `widths` cells wide
+///
+/// Cells past the last column are dropped and missing ones left blank. A row
+/// is as tall as its tallest cell.
+pub fn grid(sections: &[&[Row]], widths: &[usize], grid: &Grid) -> Doc {
+    let chars = BorderChars::get(grid.border);
+    let rule = |left: &str, join: &str, right: &str| {
+        let cells: Vec<String> = widths
+            .iter()
+            .map(|w| chars.horizontal.repeat(w + 2))
+            .collect();
+        format!("{}{}{}", left, cells.join(join), right)
+    };
+
+    let mut doc = Doc::new();
+    doc.push(
+        &rule(chars.top_left, chars.t_down, chars.top_right),
+        grid.style,
+    );
+    for (i, rows) in sections.iter().enumerate() {
+        if i > 0 {
+            doc.newline()
+                .push(&rule(chars.t_right, chars.cross, chars.t_left), grid.style);
+        }
+        for row in rows.iter() {
+            row_lines(&mut doc, row, widths, &chars, grid);
+        }
+    }
+    doc.newline().push(
+        &rule(chars.bottom_left, chars.t_up, chars.bottom_right),
+        grid.style,
+    );
+    doc
+}
+
+fn row_lines(doc: &mut Doc, row: &[Doc], widths: &[usize], chars: &BorderChars, grid: &Grid) {
+    let height = row.iter().map(|c| c.lines().len()).max().unwrap_or(1);
+    for n in 0..height {
+        doc.newline().push(chars.vertical, grid.style);
+        for (i, width) in widths.iter().enumerate() {
+            let spans = row
+                .get(i)
+                .and_then(|cell| cell.lines().get(n))
+                .map_or(&[][..], Vec::as_slice);
+            let (before, after) = grid.alignment.padding(spans_width(spans), *width);
+            doc.plain(&" ".repeat(before + 1))
+                .extend(spans)
+                .plain(&" ".repeat(after + 1))
+                .push(chars.vertical, grid.style);
+        }
+    }
+}
+
+/// Cells a line of spans takes up
+fn spans_width(spans: &[Span]) -> usize {
+    spans.iter().map(|s| text::display_width(&s.text)).sum()
+}
+
+/// Columns from `headers` with zebra rows, the browser demo's `table`
+#[cfg(any(feature = "wasm", test))]
+pub fn table(headers: &[&str], rows: &[Vec<&str>], border: &str) -> Doc {
+    use super::Color;
+
+    let cell = |text: &str, style: Style| {
+        let mut doc = Doc::new();
+        doc.push(text, style);
+        doc
+    };
+    let header = vec![headers
+        .iter()
+        .map(|h| cell(h, Style::fg(Color::CYAN).bold()))
+        .collect()];
+    let body: Vec<Row> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let style = if i % 2 == 1 {
+                Style::dimmed()
+            } else {
+                Style::PLAIN
+            };
+            row.iter().map(|c| cell(c, style)).collect()
+        })
+        .collect();
+
+    let mut widths = column_widths(&header);
+    for (width, body_width) in widths.iter_mut().zip(column_widths(&body)) {
+        *width = (*width).max(body_width);
+    }
+    grid(
+        &[&header, &body],
+        &widths,
+        &Grid {
+            border,
+            alignment: Alignment::Left,
+            style: Style::dimmed(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_fit_widest_cell() {
+        let rows = vec![vec!["Alice", "30"], vec!["Bo", "4", "extra"]];
+        let ansi = table(&["Name", "Age"], &rows, "single").to_ansi();
+        let lines: Vec<String> = ansi
+            .lines()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "┌───────┬─────┐",
+                "│ Name  │ Age │",
+                "├───────┼─────┤",
+                "│ Alice │ 30  │",
+                "│ Bo    │ 4   │",
+                "└───────┴─────┘",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_and_zebra_styles() {
+        let rows = vec![vec!["a"], vec!["<b>"]];
+        let html = table(&["H"], &rows, "rounded").to_html();
+        assert!(html.contains("<span class=\"cyan bold\">H</span>"));
+        assert!(html.contains(" a "));
+        assert!(html.contains("<span class=\"dim\">&lt;b&gt;</span>"));
+    }
+
+    #[test]
+    fn test_tall_cells_and_alignment() {
+        let cell = |lines: &[&str]| {
+            let mut doc = Doc::new();
+            for (i, line) in lines.iter().enumerate() {
+                if i > 0 {
+                    doc.newline();
+                }
+                doc.plain(line);
+            }
+            doc
+        };
+        let rows = vec![vec![cell(&["ab", "c"]), cell(&["x"])]];
+        let doc = grid(
+            &[&rows],
+            &[3, 3],
+            &Grid {
+                border: "ascii",
+                alignment: Alignment::Right,
+                style: Style::PLAIN,
+            },
+        );
+        assert_eq!(
+            doc.to_ansi(),
+            "+-----+-----+\n|  ab |   x |\n|   c |     |\n+-----+-----+"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Events along a line, drawn like `termgfx timeline`
+
+use super::{Doc, Options, Style};
+use crate::text;
+
+/// Styles accepted by [`timeline`]; anything else is `arrow`
+#[cfg(feature = "wasm")]
+pub const STYLES: [&str; 3] = ["arrow", "line", "dots"];
+
+/// `data` as `date:label` or bare `label` events, across the page or, with
+/// `vertical`, down it
+pub fn timeline(options: &Options) -> Result<Doc, String> {
+    let events: Vec<(Option<&str>, &str)> = options
+        .data
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(|e| match e.split_once(':') {
+            Some((date, label)) => (Some(date), label),
+            None => (None, e),
+        })
+        .collect();
+    if events.is_empty() {
+        return Err("No events provided".to_string());
+    }
+    let style = match &options.color {
+        Some(_) => Style::fg(options.color_or(super::Color::CYAN)?),
+        None => Style::PLAIN,
+    };
+    let kind = options.style.as_deref().unwrap_or("arrow");
+
+    let mut doc = super::chart::title(options);
+    if options.vertical {
+        let (marker, connector) = match kind {
+            "line" => ("┼", "│"),
+            "dots" => ("●", "┊"),
+            _ => ("●", "│"),
+        };
+        for (i, (date, label)) in events.iter().enumerate() {
+            doc.push(marker, style).plain(" ").push(label, style);
+            if let Some(date) = date {
+                doc.plain(" (").push(date, style).plain(")");
+            }
+            if i + 1 < events.len() {
+                doc.newline().push(connector, style).newline();
+            }
+        }
+        return Ok(doc);
+    }
+
+    let (marker, connector) = match kind {
+        "line" => ("┬", "─"),
+        "dots" => ("●", "·"),
+        _ => ("●", "─"),
+    };
+    let segment = events
+        .iter()
+        .map(|(_, label)| text::display_width(label))
+        .max()
+        .unwrap_or(0)
+        .max(10);
+    // A column of `segment` cells per event, two apart
+    let row = |doc: &mut Doc, cells: &mut dyn Iterator<Item = &str>| {
+        for (i, cell) in cells.enumerate() {
+            if i > 0 {
+                doc.plain("  ");
+            }
+            doc.push(cell, style)
+                .plain(&" ".repeat(segment.saturating_sub(text::display_width(cell))));
+        }
+    };
+    if events.iter().any(|(date, _)| date.is_some()) {
+        row(
+            &mut doc,
+            &mut events.iter().map(|(date, _)| date.unwrap_or("")),
+        );
+        doc.newline();
+    }
+    for i in 0..events.len() {
+        doc.push(marker, style);
+        if i + 1 < events.len() {
+            doc.push(&connector.repeat(segment + 1), style);
+        }
+    }
+    doc.newline();
+    row(&mut doc, &mut events.iter().map(|(_, label)| *label));
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(doc: &Doc) -> Vec<String> {
+        doc.to_ansi()
+            .lines()
+            .map(|l| text::strip_ansi(l).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_horizontal_puts_dates_over_markers() {
+        let options = Options {
+            data: "2024:Start, End".to_string(),
+            ..Options::default()
+        };
+        assert_eq!(
+            plain(&timeline(&options).unwrap()),
+            [
+                "2024                  ",
+                "●───────────●",
+                "Start       End       ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vertical_with_color() {
+        let options = Options {
+            data: "Q1:Plan,Ship".to_string(),
+            vertical: true,
+            style: Some("dots".to_string()),
+            color: Some("green".to_string()),
+            ..Options::default()
+        };
+        let doc = timeline(&options).unwrap();
+        assert_eq!(plain(&doc), ["● Plan (Q1)", "┊", "● Ship"]);
+        assert!(doc.to_html().starts_with("<span class=\"green\">●</span>"));
+        assert!(timeline(&Options::default()).is_err());
+    }
+}
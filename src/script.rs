@@ -1,86 +1,420 @@
 use crate::charts;
 use crate::output;
+use std::collections::HashMap;
+use std::env;
 use std::fs;
-use std::io::{stdout, IsTerminal};
+use std::io::{stdout, IsTerminal, Write};
+use std::process::Command;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
-#[derive(Debug, Clone)]
+/// How often `--watch-file` checks the script for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(200);
+/// Quiet time after a change before re-running, so an editor writing a
+/// save in several steps triggers one run
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// One component invocation: `name arg "quoted arg" key:value`
+#[derive(Debug, Clone, PartialEq)]
 pub struct ScriptCommand {
+    pub line: usize,
     pub command: String,
     pub args: Vec<String>,
     pub options: Vec<(String, String)>,
 }
 
-pub fn parse_script(content: &str) -> Vec<ScriptCommand> {
-    let mut commands = Vec::new();
+/// A parsed script statement
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Command(ScriptCommand),
+    /// `set NAME = VALUE`
+    Set {
+        line: usize,
+        name: String,
+        value: String,
+    },
+    /// `loop N { ... }`, with `$i` counting from 1 inside the body
+    Loop {
+        line: usize,
+        count: String,
+        body: Vec<Statement>,
+    },
+    /// `if env VAR { ... } else { ... }`; `!env` negates
+    IfEnv {
+        var: String,
+        negate: bool,
+        then: Vec<Statement>,
+        otherwise: Vec<Statement>,
+    },
+}
 
-    for line in content.lines() {
-        let line = line.trim();
+/// A statement's source text, or the `}` closing a block
+#[derive(Debug, PartialEq)]
+enum Piece {
+    Text {
+        line: usize,
+        text: String,
+        opens: bool,
+    },
+    Close {
+        line: usize,
+    },
+}
 
-        // Skip empty lines and comments
-        if line.is_empty() || line.starts_with('#') {
-            continue;
+/// A whitespace-separated word with its quotes removed
+#[derive(Debug, PartialEq)]
+struct Word {
+    text: String,
+    /// Started with a quote, so it's an argument even if it contains `:`
+    quoted: bool,
+}
+
+/// Parse a script into statements. Statements end at a newline or `;`; a
+/// `{` ending a `loop` or `if` opens a block and `}` closes it. Lines
+/// starting with `#` are comments.
+pub fn parse_script(content: &str) -> Result<Vec<Statement>, String> {
+    let pieces = split_pieces(content)?;
+    let mut pos = 0;
+    parse_block(&pieces, &mut pos, None)
+}
+
+fn split_pieces(content: &str) -> Result<Vec<Piece>, String> {
+    fn flush(pieces: &mut Vec<Piece>, current: &mut String, line: usize, opens: bool) {
+        let text = current.trim();
+        if !text.is_empty() || opens {
+            pieces.push(Piece::Text {
+                line,
+                text: text.to_string(),
+                opens,
+            });
         }
+        current.clear();
+    }
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut line = 1;
+    let mut quoted = false;
+    let mut comment = false;
+    let mut in_var = false;
+    let mut prev = '\0';
 
-        // Parse command line
-        if let Some(cmd) = parse_command_line(line) {
-            commands.push(cmd);
+    for ch in content.chars() {
+        if ch == '\n' {
+            if quoted {
+                return Err(format!("line {}: unterminated quote", line));
+            }
+            flush(&mut pieces, &mut current, line, false);
+            line += 1;
+            comment = false;
+            in_var = false;
+        } else if comment {
+            continue;
+        } else if quoted {
+            quoted = ch != '"';
+            current.push(ch);
+        } else {
+            match ch {
+                '"' => {
+                    quoted = true;
+                    current.push(ch);
+                }
+                '#' if current.trim().is_empty() => comment = true,
+                // `${name}` braces belong to the variable, not a block
+                '{' if prev == '$' => {
+                    in_var = true;
+                    current.push(ch);
+                }
+                '}' if in_var => {
+                    in_var = false;
+                    current.push(ch);
+                }
+                '{' => flush(&mut pieces, &mut current, line, true),
+                '}' => {
+                    flush(&mut pieces, &mut current, line, false);
+                    pieces.push(Piece::Close { line });
+                }
+                ';' => flush(&mut pieces, &mut current, line, false),
+                _ => current.push(ch),
+            }
         }
+        prev = ch;
+    }
+    if quoted {
+        return Err(format!("line {}: unterminated quote", line));
     }
+    flush(&mut pieces, &mut current, line, false);
+    Ok(pieces)
+}
+
+fn parse_block(
+    pieces: &[Piece],
+    pos: &mut usize,
+    opened_at: Option<usize>,
+) -> Result<Vec<Statement>, String> {
+    let mut statements = Vec::new();
+
+    while let Some(piece) = pieces.get(*pos) {
+        *pos += 1;
+        let (line, text, opens) = match piece {
+            Piece::Close { line } => {
+                return match opened_at {
+                    Some(_) => Ok(statements),
+                    None => Err(format!("line {}: '}}' without a matching '{{'", line)),
+                };
+            }
+            Piece::Text { line, text, opens } => (*line, text, *opens),
+        };
 
-    commands
+        let words = split_words(text);
+        let keyword = words.first().map(|w| w.text.as_str()).unwrap_or("");
+        let statement = match keyword {
+            "loop" => {
+                let [_, count] = words.as_slice() else {
+                    return Err(format!("line {}: expected 'loop N {{'", line));
+                };
+                if !opens {
+                    return Err(format!("line {}: expected '{{' after 'loop'", line));
+                }
+                Statement::Loop {
+                    line,
+                    count: count.text.clone(),
+                    body: parse_block(pieces, pos, Some(line))?,
+                }
+            }
+            "if" => {
+                let negate = match words.get(1).map(|w| w.text.as_str()) {
+                    Some("env") => false,
+                    Some("!env") => true,
+                    _ => return Err(format!("line {}: expected 'if env VAR {{'", line)),
+                };
+                let [_, _, var] = words.as_slice() else {
+                    return Err(format!("line {}: expected 'if env VAR {{'", line));
+                };
+                if !opens {
+                    return Err(format!("line {}: expected '{{' after 'if'", line));
+                }
+                let then = parse_block(pieces, pos, Some(line))?;
+                let otherwise = match pieces.get(*pos) {
+                    Some(Piece::Text {
+                        line,
+                        text,
+                        opens: true,
+                    }) if text == "else" => {
+                        *pos += 1;
+                        parse_block(pieces, pos, Some(*line))?
+                    }
+                    _ => Vec::new(),
+                };
+                Statement::IfEnv {
+                    var: var.text.clone(),
+                    negate,
+                    then,
+                    otherwise,
+                }
+            }
+            _ if opens => {
+                return Err(format!(
+                    "line {}: unexpected '{{' (blocks follow 'loop' or 'if')",
+                    line
+                ))
+            }
+            "else" => return Err(format!("line {}: 'else' without an 'if'", line)),
+            "set" => {
+                let name = words.get(1).map(|w| w.text.clone()).unwrap_or_default();
+                if !is_name(&name) {
+                    return Err(format!("line {}: expected 'set NAME = VALUE'", line));
+                }
+                let rest = match words.get(2) {
+                    Some(w) if w.text == "=" && !w.quoted => &words[3..],
+                    _ => &words[2..],
+                };
+                let value = rest
+                    .iter()
+                    .map(|w| w.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                Statement::Set { line, name, value }
+            }
+            _ => Statement::Command(command_from_words(line, words)),
+        };
+        statements.push(statement);
+    }
+
+    match opened_at {
+        Some(line) => Err(format!("line {}: block is never closed with '}}'", line)),
+        None => Ok(statements),
+    }
 }
 
-fn parse_command_line(line: &str) -> Option<ScriptCommand> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.is_empty() {
-        return None;
+fn split_words(text: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut current: Option<Word> = None;
+    let mut quoted = false;
+
+    for ch in text.chars() {
+        if ch == '"' {
+            quoted = !quoted;
+            current.get_or_insert_with(|| Word {
+                text: String::new(),
+                quoted: true,
+            });
+        } else if ch.is_whitespace() && !quoted {
+            words.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| Word {
+                    text: String::new(),
+                    quoted: false,
+                })
+                .text
+                .push(ch);
+        }
     }
+    words.extend(current);
+    words
+}
 
-    let command = parts[0].to_string();
+/// `key:value` words become options; everything else is an argument
+fn command_from_words(line: usize, words: Vec<Word>) -> ScriptCommand {
+    let mut words = words.into_iter();
+    let command = words.next().map(|w| w.text).unwrap_or_default();
     let mut args = Vec::new();
     let mut options = Vec::new();
 
-    let mut i = 1;
-    while i < parts.len() {
-        let part = parts[i];
+    for word in words {
+        match word.text.split_once(':') {
+            Some((key, value)) if !word.quoted && is_name(key) => {
+                options.push((key.to_string(), value.to_string()))
+            }
+            _ => args.push(word.text),
+        }
+    }
 
-        // Check if it's an option (contains :)
-        if part.contains(':') {
-            let opt_parts: Vec<&str> = part.splitn(2, ':').collect();
-            if opt_parts.len() == 2 {
-                options.push((opt_parts[0].to_string(), opt_parts[1].to_string()));
+    ScriptCommand {
+        line,
+        command,
+        args,
+        options,
+    }
+}
+
+fn is_name(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Replace `$name` and `${name}` with script variables, falling back to the
+/// environment; `$$` is a literal `$`
+fn expand(text: &str, vars: &HashMap<String, String>, line: usize) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '$' {
+            out.push(ch);
+            continue;
+        }
+        let name: String = match chars.peek() {
+            Some('$') => {
+                chars.next();
+                out.push('$');
+                continue;
             }
-        } else {
-            // It's an argument - handle quoted strings
-            if part.starts_with('"') {
-                let mut quoted = part.trim_start_matches('"').to_string();
-                i += 1;
-                while i < parts.len() {
-                    if parts[i].ends_with('"') {
-                        quoted.push(' ');
-                        quoted.push_str(parts[i].trim_end_matches('"'));
+            Some('{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                name
+            }
+            _ => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
                         break;
-                    } else {
-                        quoted.push(' ');
-                        quoted.push_str(parts[i]);
                     }
-                    i += 1;
+                    name.push(c);
+                    chars.next();
                 }
-                args.push(quoted);
-            } else {
-                args.push(part.to_string());
+                if name.is_empty() {
+                    out.push('$');
+                    continue;
+                }
+                name
             }
+        };
+        match vars.get(&name).cloned().or_else(|| env::var(&name).ok()) {
+            Some(value) => out.push_str(&value),
+            None => return Err(format!("line {}: undefined variable '{}'", line, name)),
         }
-        i += 1;
     }
+    Ok(out)
+}
 
-    Some(ScriptCommand {
-        command,
-        args,
-        options,
-    })
+/// Runs statements, keeping the variables set so far
+struct Interpreter<'a> {
+    vars: HashMap<String, String>,
+    /// termgfx subcommands that scripts may invoke by name
+    components: &'a [String],
+}
+
+impl Interpreter<'_> {
+    fn run(&mut self, statements: &[Statement]) -> Result<(), String> {
+        for statement in statements {
+            match statement {
+                Statement::Command(cmd) => {
+                    let cmd = ScriptCommand {
+                        line: cmd.line,
+                        command: cmd.command.clone(),
+                        args: cmd
+                            .args
+                            .iter()
+                            .map(|a| expand(a, &self.vars, cmd.line))
+                            .collect::<Result<_, _>>()?,
+                        options: cmd
+                            .options
+                            .iter()
+                            .map(|(k, v)| Ok((k.clone(), expand(v, &self.vars, cmd.line)?)))
+                            .collect::<Result<_, String>>()?,
+                    };
+                    execute_command(&cmd, self.components)?;
+                }
+                Statement::Set { line, name, value } => {
+                    let value = expand(value, &self.vars, *line)?;
+                    self.vars.insert(name.clone(), value);
+                }
+                Statement::Loop { line, count, body } => {
+                    let count = expand(count, &self.vars, *line)?;
+                    let count: usize = count
+                        .parse()
+                        .map_err(|_| format!("line {}: invalid loop count '{}'", line, count))?;
+                    let outer = self.vars.get("i").cloned();
+                    for i in 1..=count {
+                        self.vars.insert("i".to_string(), i.to_string());
+                        self.run(body)?;
+                    }
+                    match outer {
+                        Some(i) => self.vars.insert("i".to_string(), i),
+                        None => self.vars.remove("i"),
+                    };
+                }
+                Statement::IfEnv {
+                    var,
+                    negate,
+                    then,
+                    otherwise,
+                } => {
+                    let set = env::var_os(var).is_some_and(|v| !v.is_empty());
+                    if set != *negate {
+                        self.run(then)?;
+                    } else {
+                        self.run(otherwise)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 fn get_option(options: &[(String, String)], key: &str) -> Option<String> {
@@ -110,13 +444,15 @@ fn parse_duration(duration_str: &str) -> Duration {
     }
 }
 
-pub fn execute_script(commands: Vec<ScriptCommand>) {
-    for cmd in commands {
-        execute_command(&cmd);
+pub fn execute_script(statements: &[Statement], components: &[String]) -> Result<(), String> {
+    Interpreter {
+        vars: HashMap::new(),
+        components,
     }
+    .run(statements)
 }
 
-fn execute_command(cmd: &ScriptCommand) {
+fn execute_command(cmd: &ScriptCommand, components: &[String]) -> Result<(), String> {
     match cmd.command.as_str() {
         "banner" => {
             let title = cmd.args.first().map(|s| s.as_str()).unwrap_or("Banner");
@@ -192,7 +528,7 @@ fn execute_command(cmd: &ScriptCommand) {
             output::typewriter::render(message, speed);
         }
 
-        "wait" => {
+        "wait" | "sleep" => {
             if let Some(duration_str) = cmd.args.first() {
                 let duration = parse_duration(duration_str);
                 thread::sleep(duration);
@@ -204,36 +540,140 @@ fn execute_command(cmd: &ScriptCommand) {
             charts::sparkline::render(data);
         }
 
+        "clear" => {
+            if stdout().is_terminal() {
+                print!("\x1b[2J\x1b[H");
+                stdout().flush().ok();
+            }
+        }
+
+        name if NOT_COMPONENTS.contains(&name) => {
+            return Err(format!(
+                "line {}: '{}' can't run inside a script",
+                cmd.line, name
+            ));
+        }
+        name if components.iter().any(|c| c == name) => return run_component(cmd),
+
         _ => {
             eprintln!("Unknown command: {}", cmd.command);
         }
     }
+    Ok(())
+}
+
+/// Subcommands a script can't call: `script` would start itself over and
+/// over, the others run until Ctrl+C or take over the terminal
+const NOT_COMPONENTS: [&str; 9] = [
+    "script",
+    "watch",
+    "dashboard",
+    "record",
+    "clock",
+    "tui",
+    "repl",
+    "playground",
+    "studio",
+];
+
+/// Run any other termgfx subcommand: `key:value` becomes `--key value`,
+/// `key:true` a bare `--key` and `key:false` is left out
+fn run_component(cmd: &ScriptCommand) -> Result<(), String> {
+    let exe = env::current_exe().map_err(|e| format!("Failed to run {}: {}", cmd.command, e))?;
+    let mut command = Command::new(exe);
+    command.arg(&cmd.command).args(&cmd.args);
+    for (key, value) in &cmd.options {
+        match value.as_str() {
+            "true" => {
+                command.arg(format!("--{}", key));
+            }
+            "false" => {}
+            _ => {
+                command.arg(format!("--{}", key)).arg(value);
+            }
+        }
+    }
+    stdout().flush().ok();
+    let status = command
+        .status()
+        .map_err(|e| format!("Failed to run {}: {}", cmd.command, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("line {}: '{}' failed", cmd.line, cmd.command))
+    }
 }
 
-pub fn run_script_file(path: &str) -> Result<(), String> {
+pub fn run_script_file(path: &str, components: &[String]) -> Result<(), String> {
     let content =
-        fs::read_to_string(path).map_err(|e| format!("Failed to read script file: {}", e))?;
+        output::input::read_text(path).map_err(|e| format!("Failed to read script file: {}", e))?;
 
-    let commands = parse_script(&content);
-    execute_script(commands);
+    execute_script(&parse_script(&content)?, components)
+}
 
-    Ok(())
+pub fn run_inline_script(script: &str, components: &[String]) -> Result<(), String> {
+    execute_script(&parse_script(script)?, components)
 }
 
-pub fn run_inline_script(script: &str) -> Result<(), String> {
-    let commands = parse_script(script);
-    execute_script(commands);
-    Ok(())
+/// Modification time and size, or None while the file is missing (editors
+/// that save by renaming briefly remove it)
+fn fingerprint(path: &str) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
 }
 
-pub fn run(file: Option<&str>, inline: Option<&str>) {
+/// Run the script file, then again after every change until Ctrl+C
+pub fn watch_script_file(path: &str, components: &[String]) -> Result<(), String> {
+    if output::input::is_stdin(path) {
+        return Err("--watch-file needs a script file, not stdin".to_string());
+    }
+    let mut last =
+        fingerprint(path).ok_or_else(|| format!("Failed to read script file: {}", path))?;
+    let interactive = stdout().is_terminal();
+    loop {
+        if interactive {
+            print!("\x1b[2J\x1b[H");
+        }
+        // A broken edit shouldn't end the session; report it and keep watching
+        if let Err(e) = run_script_file(path, components) {
+            eprintln!("Error: {}", e);
+        }
+        stdout().flush().ok();
+        eprintln!("Watching {} for changes (Ctrl+C to stop)", path);
+
+        let mut current = loop {
+            thread::sleep(WATCH_INTERVAL);
+            match fingerprint(path) {
+                Some(now) if now != last => break now,
+                _ => {}
+            }
+        };
+        loop {
+            thread::sleep(WATCH_DEBOUNCE);
+            match fingerprint(path) {
+                Some(now) if now != current => current = now,
+                _ => break,
+            }
+        }
+        last = current;
+    }
+}
+
+/// Run a script file or inline script; `components` are the termgfx
+/// subcommands a script may call beyond the built-in ones
+pub fn run(file: Option<&str>, inline: Option<&str>, watch: bool, components: &[String]) {
     if let Some(script_file) = file {
-        if let Err(e) = run_script_file(script_file) {
+        let result = if watch {
+            watch_script_file(script_file, components)
+        } else {
+            run_script_file(script_file, components)
+        };
+        if let Err(e) = result {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
     } else if let Some(inline_script) = inline {
-        if let Err(e) = run_inline_script(inline_script) {
+        if let Err(e) = run_inline_script(inline_script, components) {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
@@ -242,3 +682,96 @@ pub fn run(file: Option<&str>, inline: Option<&str>) {
         std::process::exit(1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(line: usize, name: &str, args: &[&str], options: &[(&str, &str)]) -> Statement {
+        Statement::Command(ScriptCommand {
+            line,
+            command: name.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            options: options
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_parse_commands_and_options() {
+        let script = "# intro\nbox \"Hello World\" style:success\nwait 1s; banner HI";
+        assert_eq!(
+            parse_script(script).unwrap(),
+            vec![
+                command(2, "box", &["Hello World"], &[("style", "success")]),
+                command(3, "wait", &["1s"], &[]),
+                command(3, "banner", &["HI"], &[]),
+            ]
+        );
+        // A quoted word is an argument even with a colon; a quoted value stays whole
+        assert_eq!(
+            parse_script("box \"a:b\" title:\"Two words\"").unwrap(),
+            vec![command(1, "box", &["a:b"], &[("title", "Two words")])]
+        );
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let script =
+            "set n = 3\nloop $n {\n  box \"${i}\"\n}\nif !env CI { clear } else { sleep 1s }";
+        assert_eq!(
+            parse_script(script).unwrap(),
+            vec![
+                Statement::Set {
+                    line: 1,
+                    name: "n".to_string(),
+                    value: "3".to_string(),
+                },
+                Statement::Loop {
+                    line: 2,
+                    count: "$n".to_string(),
+                    body: vec![command(3, "box", &["${i}"], &[])],
+                },
+                Statement::IfEnv {
+                    var: "CI".to_string(),
+                    negate: true,
+                    then: vec![command(5, "clear", &[], &[])],
+                    otherwise: vec![command(5, "sleep", &["1s"], &[])],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_script("loop 2 {\nbox a")
+            .unwrap_err()
+            .contains("never closed"));
+        assert!(parse_script("box a }").unwrap_err().contains("without"));
+        assert!(parse_script("loop {").is_err());
+        assert!(parse_script("if HOME {").is_err());
+        assert!(parse_script("box a {").is_err());
+        assert_eq!(
+            parse_script("box \"unterminated"),
+            Err("line 1: unterminated quote".to_string())
+        );
+        assert_eq!(
+            parse_script("banner HI\nbox \"a; b\nclear"),
+            Err("line 2: unterminated quote".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expand() {
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), "World".to_string());
+        assert_eq!(expand("Hi $name!", &vars, 1).unwrap(), "Hi World!");
+        assert_eq!(expand("${name}s", &vars, 1).unwrap(), "Worlds");
+        assert_eq!(expand("$$5 $", &vars, 1).unwrap(), "$5 $");
+        assert!(expand("$missing_var_xyz", &vars, 4)
+            .unwrap_err()
+            .contains("line 4"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Display width of terminal text
+//!
+//! Anything that pads, aligns or truncates text measures it here. Escape
+//! sequences (colors, hyperlinks) take no cells, and wide characters such as
+//! CJK and most emoji take two. The CLI build also applies the user's emoji
+//! width overrides from `termgfx emoji-widths`.
+
+use std::borrow::Cow;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Text with ANSI escape sequences removed
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            take_escape(&mut chars);
+        } else {
+            result.push(c);
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Text with color and style (SGR) sequences removed; cursor movement,
+/// clearing and hyperlinks are kept
+pub fn strip_sgr(text: &str) -> Cow<'_, str> {
+    map_sgr(text, |_| None)
+}
+
+/// Text with each SGR sequence's parameters passed through `f`; `None`
+/// drops the sequence. Other escape sequences are kept as they are.
+pub fn map_sgr(text: &str, mut f: impl FnMut(&str) -> Option<String>) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+        let seq = take_escape(&mut chars);
+        if seq.starts_with('[') && seq.ends_with('m') {
+            if let Some(params) = f(&seq[1..seq.len() - 1]) {
+                result.push_str("\x1b[");
+                result.push_str(&params);
+                result.push('m');
+            }
+        } else {
+            result.push(c);
+            result.push_str(&seq);
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Number of terminal cells `text` occupies
+pub fn display_width(text: &str) -> usize {
+    cell_width(&strip_ansi(text))
+}
+
+/// Number of cells one character takes; control characters take none
+pub fn char_width(ch: char) -> usize {
+    if ch.is_control() {
+        return 0;
+    }
+    cell_width(ch.encode_utf8(&mut [0; 4]))
+}
+
+/// Cut `text` to `width` cells, ending with `ellipsis` when shortened
+///
+/// Escape sequences are kept whole; if any were seen a reset is written
+/// before the ellipsis so colors don't bleed past the cut.
+pub fn truncate(text: &str, width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(display_width(ellipsis));
+
+    let mut out = String::new();
+    let mut used = 0;
+    let mut styled = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            out.push(ch);
+            out.push_str(&take_escape(&mut chars));
+            styled = true;
+            continue;
+        }
+        let w = char_width(ch);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        out.push(ch);
+    }
+    if styled {
+        out.push_str("\x1b[0m");
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+#[cfg(feature = "cli")]
+fn cell_width(visible: &str) -> usize {
+    crate::output::width::str_width(visible)
+}
+
+#[cfg(not(feature = "cli"))]
+fn cell_width(visible: &str) -> usize {
+    unicode_width::UnicodeWidthStr::width(visible)
+}
+
+/// Consume the rest of an escape sequence whose ESC was already read
+///
+/// Handles CSI (`ESC [ … final`), OSC, APC and DCS strings (`ESC ] … BEL`
+/// or `ESC ] … ESC \`) and two-character sequences.
+pub(crate) fn take_escape(chars: &mut Peekable<Chars>) -> String {
+    let mut seq = String::new();
+    match chars.next() {
+        Some('[') => {
+            seq.push('[');
+            for c in chars.by_ref() {
+                seq.push(c);
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        }
+        Some(c @ (']' | '_' | 'P')) => {
+            seq.push(c);
+            while let Some(c) = chars.next() {
+                seq.push(c);
+                if c == '\x07' {
+                    break;
+                }
+                if c == '\x1b' && chars.peek() == Some(&'\\') {
+                    seq.push('\\');
+                    chars.next();
+                    break;
+                }
+            }
+        }
+        Some(c) => seq.push(c),
+        None => {}
+    }
+    seq
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\x1b[31mRed\x1b[0m"), "Red");
+        assert_eq!(strip_ansi("plain"), "plain");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\"),
+            "link"
+        );
+        // Kitty graphics and saved cursors take no cells
+        assert_eq!(strip_ansi("\x1b7\x1b_Gf=100,m=0;AAAA\x1b\\\x1b8  "), "  ");
+    }
+
+    #[test]
+    fn test_strip_sgr_keeps_other_sequences() {
+        assert_eq!(strip_sgr("\x1b[1;31mRed\x1b[0m"), "Red");
+        assert_eq!(strip_sgr("\r\x1b[2K\x1b[32mok"), "\r\x1b[2Kok");
+        assert_eq!(
+            strip_sgr("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\"),
+            "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_map_sgr_rewrites_parameters() {
+        let out = map_sgr("\x1b[1;31mRed\x1b[0m\x1b[2K", |p| {
+            (p != "0").then(|| p.replace("31", "32"))
+        });
+        assert_eq!(out, "\x1b[1;32mRed\x1b[2K");
+    }
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("Hello"), 5);
+        assert_eq!(display_width("\x1b[31mRed\x1b[0m"), 3);
+        assert_eq!(display_width("\x1b[32m日本\x1b[0m"), 4);
+        assert_eq!(display_width("🚀 go"), 5);
+    }
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('日'), 2);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('\t'), 0);
+    }
+
+    #[test]
+    fn test_truncate_keeps_short_text() {
+        assert_eq!(truncate("short", 10, "…"), "short");
+    }
+
+    #[test]
+    fn test_truncate_multibyte_and_ansi() {
+        let fitted = truncate("\x1b[31mé日本語テキスト\x1b[0m", 6, "…");
+        assert!(fitted.starts_with("\x1b[31m"));
+        assert!(fitted.ends_with("\x1b[0m…"));
+        assert_eq!(display_width(&fitted), 6);
+    }
+
+    #[test]
+    fn test_truncate_never_splits_wide_chars() {
+        assert_eq!(truncate("日本語", 4, "…"), "日…");
+        assert_eq!(truncate("abcdef", 5, "..."), "ab...");
+    }
+}
@@ -172,3 +172,84 @@ fn test_spinner_duration_help() {
         .success()
         .stdout(predicate::str::is_match("(?i)duration").unwrap());
 }
+
+// ============================================================================
+// ANIMATION POLICY TESTS
+// ============================================================================
+
+#[test]
+fn test_no_animation_flag_skips_frames() {
+    termgfx()
+        .args(["box", "Deployed", "--animate", "--animation-time", "10000"])
+        .arg("--no-animation")
+        .timeout(std::time::Duration::from_secs(3))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deployed"));
+}
+
+#[test]
+fn test_no_animation_env_skips_frames() {
+    termgfx()
+        .args(["animate", "--effect-type", "bars", "--data", "A:10,B:20"])
+        .args(["--duration", "10"])
+        .env("TERMGFX_NO_ANIMATION", "1")
+        .timeout(std::time::Duration::from_secs(3))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("20"));
+}
+
+#[test]
+fn test_animate_env_off_prints_typewriter_text() {
+    termgfx()
+        .args(["animate", "--effect-type", "typewriter", "--text", "Hello"])
+        .args(["--speed", "1"])
+        .env("TERMGFX_ANIMATE", "false")
+        .timeout(std::time::Duration::from_secs(3))
+        .assert()
+        .success()
+        .stdout("Hello\n");
+}
+
+// ============================================================================
+// EASING TESTS
+// ============================================================================
+
+#[test]
+fn test_counter_with_easing_ends_at_target() {
+    termgfx()
+        .args(["animate", "--effect-type", "counter", "--to", "250"])
+        .args(["--duration", "0.3", "--easing", "elastic"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("250"));
+}
+
+#[test]
+fn test_progress_with_easing() {
+    termgfx()
+        .args(["progress", "60", "--animate", "--easing", "ease-out"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("60%"));
+}
+
+#[test]
+fn test_invalid_easing() {
+    termgfx()
+        .args(["gauge", "40", "--animate", "--easing", "wobble"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid easing 'wobble'"));
+}
+
+#[test]
+fn test_confetti_skipped_when_piped() {
+    termgfx()
+        .args(["animate", "--type", "confetti", "--duration", "30"])
+        .timeout(std::time::Duration::from_secs(3))
+        .assert()
+        .success()
+        .stdout("");
+}
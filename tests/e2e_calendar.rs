@@ -0,0 +1,105 @@
+#![allow(deprecated)]
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+fn termgfx() -> Command {
+    Command::cargo_bin("termgfx").unwrap()
+}
+
+// ============================================================================
+// CALENDAR COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_calendar_help() {
+    termgfx()
+        .args(["calendar", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("calendar heatmap"));
+}
+
+#[test]
+fn test_calendar_basic() {
+    termgfx()
+        .args([
+            "calendar",
+            "--data",
+            "2024-01-05:3,2024-01-06:10,2024-02-14:7",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Jan"))
+        .stdout(predicate::str::contains("Feb"))
+        .stdout(predicate::str::contains("Mon"))
+        .stdout(predicate::str::contains("Less"))
+        .stdout(predicate::str::contains("More"));
+}
+
+#[test]
+fn test_calendar_with_title_and_scheme() {
+    termgfx()
+        .args([
+            "calendar",
+            "--data",
+            "2024-03-01:1,2024-03-02:4",
+            "--title",
+            "Commits",
+            "--colors",
+            "magma",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Commits"));
+}
+
+#[test]
+fn test_calendar_explicit_range() {
+    termgfx()
+        .args([
+            "calendar",
+            "--data",
+            "2024-05-10:2",
+            "--from",
+            "2024-04-01",
+            "--to",
+            "2024-06-30",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Apr"))
+        .stdout(predicate::str::contains("Jun"));
+}
+
+#[test]
+fn test_calendar_from_file() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "2024-01-01:5").unwrap();
+    writeln!(file, "2024-01-02:8").unwrap();
+
+    termgfx()
+        .args(["calendar", "--file", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Jan"));
+}
+
+#[test]
+fn test_calendar_invalid_date() {
+    termgfx()
+        .args(["calendar", "--data", "2024-13-45:3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date"));
+}
+
+#[test]
+fn test_calendar_requires_data() {
+    termgfx()
+        .args(["calendar"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--data or --file"));
+}
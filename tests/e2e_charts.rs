@@ -59,6 +59,37 @@ fn test_chart_line_varying_values() {
         .success();
 }
 
+#[test]
+fn test_chart_line_horizon_single_row() {
+    let output = termgfx()
+        .args([
+            "chart",
+            "line",
+            "--data=-3,-1,0,2,5,9,4,1",
+            "--style",
+            "horizon",
+            "--bands",
+            "2",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // One folded row plus the band legend
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains("base 0.0"));
+    assert!(!stdout.contains('⠀'));
+}
+
+#[test]
+fn test_chart_line_invalid_style() {
+    termgfx()
+        .args(["chart", "line", "--data", "1,2,3", "--style", "bars"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid line style"));
+}
+
 // ============================================================================
 // BAR CHART TESTS
 // ============================================================================
@@ -72,6 +103,35 @@ fn test_chart_bar_basic() {
         .stdout(predicate::str::is_empty().not());
 }
 
+#[test]
+fn test_chart_bar_overshooting_easing_keeps_final_values() {
+    // Piped output skips the animation and shows the settled bars
+    termgfx()
+        .args([
+            "chart",
+            "bar",
+            "--data",
+            "A:10,B:20",
+            "--animate",
+            "--easing",
+            "elastic",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("20"));
+}
+
+#[test]
+fn test_chart_easing_rejects_unknown_curve() {
+    for kind in ["bar", "line"] {
+        termgfx()
+            .args(["chart", kind, "--data", "1,2,3", "--easing", "wobbly"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("Invalid easing 'wobbly'"));
+    }
+}
+
 #[test]
 fn test_chart_bar_single_bar() {
     termgfx()
@@ -137,3 +197,338 @@ fn test_chart_pie_unequal_distribution() {
         .assert()
         .success();
 }
+
+#[test]
+fn test_chart_pie_legend_beside_chart() {
+    // 20-column pie + legend fits the 80-column fallback width
+    let output = termgfx()
+        .args(["chart", "pie", "--data", "Yes:70,No:30"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 18);
+    assert!(stdout
+        .lines()
+        .any(|l| l.contains("█") && l.contains("Yes: 70.0%")));
+}
+
+#[test]
+fn test_chart_pie_legend_below() {
+    let output = termgfx()
+        .args([
+            "chart",
+            "pie",
+            "--data",
+            "Yes:70,No:30",
+            "--legend",
+            "below",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 18 + 1 + 2);
+    assert!(lines[19].contains("Yes: 70.0%"));
+}
+
+#[test]
+fn test_chart_pie_legend_collapsed_and_none() {
+    termgfx()
+        .args([
+            "chart",
+            "pie",
+            "--data",
+            "Yes:70,No:30",
+            "--legend",
+            "collapsed",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(2 entries"))
+        .stdout(predicate::str::contains("Yes").not());
+    termgfx()
+        .args(["chart", "pie", "--data", "Yes:70,No:30", "--legend", "none"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Yes").not());
+}
+
+#[test]
+fn test_chart_pie_legend_toggle_without_terminal_lists_entries() {
+    termgfx()
+        .args([
+            "chart",
+            "pie",
+            "--data",
+            "Yes:70,No:30",
+            "--legend",
+            "toggle",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No: 30.0%"));
+}
+
+#[test]
+fn test_chart_pie_invalid_legend() {
+    termgfx()
+        .args(["chart", "pie", "--data", "Yes:70,No:30", "--legend", "left"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid legend position 'left'"));
+}
+
+// ============================================================================
+// BOXPLOT CHART TESTS
+// ============================================================================
+
+#[test]
+fn test_chart_boxplot_basic() {
+    termgfx()
+        .args(["chart", "boxplot", "--data", "run1:1,2,3,4;run2:2,3,5,8"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("run1"))
+        .stdout(predicate::str::contains("run2"))
+        .stdout(predicate::str::contains("┃"));
+}
+
+#[test]
+fn test_chart_boxplot_outlier() {
+    termgfx()
+        .args(["chart", "boxplot", "--data", "bench:3,3.5,4,4.2,40"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("○"));
+}
+
+#[test]
+fn test_chart_boxplot_violin() {
+    termgfx()
+        .args([
+            "chart",
+            "boxplot",
+            "--data",
+            "a:1,2,2,2,3,4;b:2,3,5,8",
+            "--violin",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("▓"));
+}
+
+#[test]
+fn test_chart_boxplot_aligns_wide_labels() {
+    termgfx()
+        .args(["chart", "boxplot", "--data", "日本語:1,2,3;ab:2,3,4"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("日本語 ├"))
+        .stdout(predicate::str::contains("\n    ab  "))
+        .stdout(predicate::str::contains("\n       └"));
+}
+
+#[test]
+fn test_chart_boxplot_invalid_data() {
+    termgfx()
+        .args(["chart", "boxplot", "--data", "nothing here"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No valid data"));
+}
+
+// ============================================================================
+// FUNNEL CHART TESTS
+// ============================================================================
+
+#[test]
+fn test_chart_funnel_basic() {
+    termgfx()
+        .args([
+            "chart",
+            "funnel",
+            "--data",
+            "Visits:1000,Signups:300,Paid:50",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Visits"))
+        .stdout(predicate::str::contains("█"))
+        .stdout(predicate::str::contains("30.0% of prev"))
+        .stdout(predicate::str::contains("5.0% of total"));
+}
+
+#[test]
+fn test_chart_funnel_aligns_wide_labels() {
+    termgfx()
+        .args(["chart", "funnel", "--data", "日本語:100,ab:50"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("日本語 █"))
+        .stdout(predicate::str::contains("\n    ab  "));
+}
+
+#[test]
+fn test_chart_funnel_with_style() {
+    termgfx()
+        .args(["chart", "funnel", "--data", "A:10,B:5", "--style", "retro"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("50.0% of prev"));
+}
+
+#[test]
+fn test_chart_funnel_unknown_style() {
+    termgfx()
+        .args(["chart", "funnel", "--data", "A:10,B:5", "--style", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown style"));
+}
+
+#[test]
+fn test_chart_funnel_invalid_data() {
+    termgfx()
+        .args(["chart", "funnel", "--data", "Visits:lots"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value"));
+}
+
+// ============================================================================
+// RADAR CHART TESTS
+// ============================================================================
+
+#[test]
+fn test_chart_radar_basic() {
+    termgfx()
+        .args([
+            "chart",
+            "radar",
+            "--data",
+            "speed:8,power:6,defense:9",
+            "--max",
+            "10",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("speed (8)"))
+        .stdout(predicate::str::contains("power (6)"))
+        .stdout(predicate::str::contains("defense (9)"));
+}
+
+#[test]
+fn test_chart_radar_compare_shows_legend() {
+    termgfx()
+        .args([
+            "chart",
+            "radar",
+            "--data",
+            "a:5,b:6,c:7,d:8",
+            "--compare",
+            "a:8,b:7,c:6,d:5",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("compare"));
+}
+
+#[test]
+fn test_chart_radar_compare_legend_below() {
+    let output = termgfx()
+        .args([
+            "chart",
+            "radar",
+            "--data",
+            "a:5,b:6,c:7,d:8",
+            "--compare",
+            "a:8,b:7,c:6,d:5",
+            "--legend",
+            "below",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[lines.len() - 1].ends_with("compare"));
+    assert!(lines[lines.len() - 2].ends_with("data"));
+}
+
+#[test]
+fn test_chart_radar_compare_missing_axis() {
+    termgfx()
+        .args([
+            "chart",
+            "radar",
+            "--data",
+            "a:5,b:6,c:7",
+            "--compare",
+            "a:8,b:7",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing axis 'c'"));
+}
+
+#[test]
+fn test_chart_radar_too_few_axes() {
+    termgfx()
+        .args(["chart", "radar", "--data", "a:5,b:6"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 3 axes"));
+}
+
+// ============================================================================
+// RIDGELINE CHART TESTS
+// ============================================================================
+
+#[test]
+fn test_chart_ridgeline_from_csv_stdin() {
+    termgfx()
+        .args(["chart", "ridgeline", "--file", "-"])
+        .write_stdin("time,web-1,db-1\n00:00,1,9\n00:05,5,4\n00:10,9,1\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("web-1"))
+        .stdout(predicate::str::contains("db-1"))
+        .stdout(predicate::str::contains("00:00"))
+        .stdout(predicate::str::contains("00:10"))
+        .stdout(predicate::str::contains("└"));
+}
+
+#[test]
+fn test_chart_ridgeline_inline_data() {
+    termgfx()
+        .args([
+            "chart",
+            "ridgeline",
+            "--data",
+            "a:1,2,3;b:3,2,1",
+            "--height",
+            "2",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("▁"))
+        .stdout(predicate::str::contains("█"));
+}
+
+#[test]
+fn test_chart_ridgeline_requires_input() {
+    termgfx()
+        .args(["chart", "ridgeline"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--file or --data"));
+}
+
+#[test]
+fn test_chart_ridgeline_invalid_value() {
+    termgfx()
+        .args(["chart", "ridgeline", "--file", "-"])
+        .write_stdin("time,web\n1,abc\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value 'abc'"));
+}
@@ -244,3 +244,363 @@ fn test_chart_bar_missing_data() {
 fn test_chart_pie_missing_data() {
     termgfx().args(["chart", "pie"]).assert().failure();
 }
+
+// ============================================================================
+// COLOR POLICY TESTS
+// ============================================================================
+
+#[test]
+fn test_color_never_strips_ansi() {
+    termgfx()
+        .args(["--color", "never", "box", "hi", "--border", "rounded"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hi"))
+        .stdout(predicate::str::contains("╭"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_no_color_env_strips_ansi_and_uses_ascii_borders() {
+    termgfx()
+        .env("NO_COLOR", "1")
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+"))
+        .stdout(predicate::str::contains("╭").not())
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_no_color_table_uses_ascii_borders() {
+    termgfx()
+        .env("NO_COLOR", "1")
+        .args(["table", "--headers", "a,b", "--rows", "1,2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| a | b |"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_no_color_applies_to_errors() {
+    termgfx()
+        .env("NO_COLOR", "1")
+        .args(["tree", "--path", "-"])
+        .write_stdin("{")
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("Error: Invalid JSON"));
+}
+
+#[test]
+fn test_clicolor_force_overrides_no_color() {
+    termgfx()
+        .env("NO_COLOR", "1")
+        .env("CLICOLOR_FORCE", "1")
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[test]
+fn test_color_always_overrides_no_color() {
+    termgfx()
+        .env("NO_COLOR", "1")
+        .args(["--color", "always", "gauge", "50"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[test]
+fn test_piped_output_is_plain_with_unicode_borders() {
+    termgfx()
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("╭"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_color_flag_after_command() {
+    termgfx()
+        .args(["gauge", "50", "--accent", "red", "--color", "always"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[test]
+fn test_prompt_segment_stays_colored_when_captured() {
+    termgfx()
+        .args(["gauge", "50", "--inline", "--shell", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x01\x1b["));
+}
+
+#[test]
+fn test_invalid_color_mode() {
+    termgfx()
+        .args(["--color", "sometimes", "box", "hi"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid color mode 'sometimes'"));
+}
+
+#[test]
+fn test_color_depth_256_quantizes_gradients() {
+    termgfx()
+        .env("COLORTERM", "truecolor")
+        .args([
+            "--color",
+            "always",
+            "--color-depth",
+            "256",
+            "progress",
+            "60",
+            "--from",
+            "red",
+            "--to",
+            "blue",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[38;5;"))
+        .stdout(predicate::str::contains("38;2;").not());
+}
+
+#[test]
+fn test_color_depth_auto_follows_term() {
+    termgfx()
+        .env_remove("COLORTERM")
+        .env("TERM", "linux")
+        .args([
+            "--color", "always", "progress", "60", "--from", "red", "--to", "blue",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[9"))
+        .stdout(predicate::str::contains("38;2;").not())
+        .stdout(predicate::str::contains("38;5;").not());
+}
+
+#[test]
+fn test_color_depth_truecolor_keeps_rgb() {
+    termgfx()
+        .env("TERM", "xterm")
+        .args([
+            "--color",
+            "always",
+            "--color-depth",
+            "truecolor",
+            "progress",
+            "60",
+            "--from",
+            "red",
+            "--to",
+            "blue",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[38;2;"));
+}
+
+#[test]
+fn test_invalid_color_depth() {
+    termgfx()
+        .args(["--color-depth", "8", "box", "hi"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid color depth '8'"));
+}
+
+// ============================================================================
+// RENDER CACHE TESTS
+// ============================================================================
+
+fn cache_entries(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.map(|e| e.unwrap().path()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_cache_replays_stored_output() {
+    let cache = tempfile::tempdir().unwrap();
+    let data = cache.path().join("data.csv");
+    std::fs::write(&data, "name,count\nalpha,1\n").unwrap();
+    let render = || {
+        let mut c = termgfx();
+        c.env("TERMGFX_CACHE_DIR", cache.path().join("render"))
+            .args(["table", "--file", data.to_str().unwrap(), "--cache", "5m"]);
+        c
+    };
+
+    render()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alpha"));
+    let entries = cache_entries(&cache.path().join("render"));
+    assert_eq!(entries.len(), 1);
+
+    // A hit replays the stored bytes instead of rendering again
+    std::fs::write(&entries[0], "from cache\n").unwrap();
+    render()
+        .assert()
+        .success()
+        .stdout(predicate::eq("from cache\n"));
+
+    // Changing the input file changes the key
+    std::fs::write(&data, "name,count\nbeta,2\n").unwrap();
+    render()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("beta"));
+    assert_eq!(cache_entries(&cache.path().join("render")).len(), 2);
+}
+
+#[test]
+fn test_cache_keys_on_stdin() {
+    let cache = tempfile::tempdir().unwrap();
+    for input in ["{\"a\": 1}", "{\"a\": 2}"] {
+        termgfx()
+            .env("TERMGFX_CACHE_DIR", cache.path())
+            .args(["--cache", "1m", "tree", "--path", "-"])
+            .write_stdin(input)
+            .assert()
+            .success();
+    }
+    assert_eq!(cache_entries(cache.path()).len(), 2);
+}
+
+#[test]
+fn test_cache_skips_failed_runs() {
+    let cache = tempfile::tempdir().unwrap();
+    termgfx()
+        .env("TERMGFX_CACHE_DIR", cache.path())
+        .args(["tree", "--path", "-", "--cache", "1m"])
+        .write_stdin("{")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid JSON"));
+    assert!(cache_entries(cache.path()).is_empty());
+}
+
+#[test]
+fn test_cache_rejects_interactive_commands() {
+    termgfx()
+        .args(["--cache", "5m", "confirm", "Sure?"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'confirm' is live"));
+}
+
+#[test]
+fn test_cache_rejects_live_commands_and_flags() {
+    for args in [
+        &["json"][..],
+        &["spinner", "Loading"],
+        &["log", "app.log", "--follow", "--no-pager"],
+        &["gauge", "--source=echo 5"],
+    ] {
+        termgfx()
+            .args(["--cache", "1m"])
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!("'{}' is live", args[0])));
+    }
+    termgfx()
+        .args(["--cache", "1m", "code", "src/main.rs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("add --no-pager"));
+}
+
+#[test]
+fn test_cache_invalid_ttl() {
+    termgfx()
+        .args(["box", "hi", "--cache", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid cache TTL: soon"));
+}
+
+// ============================================================================
+// TRACING TESTS
+// ============================================================================
+
+#[test]
+fn test_log_level_invalid() {
+    termgfx()
+        .args(["--log-level", "loud", "box", "hi"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid log level 'loud' (use error, warn, info, debug, trace)",
+        ));
+}
+
+#[cfg(not(feature = "tracing"))]
+#[test]
+fn test_log_flags_need_tracing_feature() {
+    termgfx()
+        .args(["box", "hi", "--log-level", "debug"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`tracing` feature"));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_log_file_records_terminal_and_phases() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("termgfx.log");
+    termgfx()
+        .args(["table", "--headers", "a,b", "--rows", "1,2"])
+        .args(["--log-file", log.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("│ 1 │ 2 │"));
+
+    let content = std::fs::read_to_string(&log).unwrap();
+    assert!(content.contains("terminal command=\"table\""));
+    for span in ["layout", "render", "io"] {
+        assert!(content.contains(&format!("{}: ", span)), "{}", content);
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_every_command_traces_its_output() {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, args, spans) in [
+        ("box", &["box", "hi"][..], &["layout", "render", "io"][..]),
+        (
+            "chart",
+            &["chart", "bar", "--data", "A:1,B:2"][..],
+            &["parse", "layout", "render", "io"][..],
+        ),
+        (
+            "html",
+            &["progress", "50", "--format", "html"][..],
+            &["render", "io"][..],
+        ),
+    ] {
+        let log = dir.path().join(format!("{}.log", name));
+        termgfx()
+            .args(args)
+            .args(["--log-file", log.to_str().unwrap()])
+            .assert()
+            .success();
+        let content = std::fs::read_to_string(&log).unwrap();
+        for span in spans {
+            assert!(content.contains(&format!("{}: ", span)), "{}", content);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+#![allow(deprecated)]
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn termgfx() -> Command {
+    Command::cargo_bin("termgfx").unwrap()
+}
+
+// ============================================================================
+// CLOCK COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_clock_prints_once_when_piped() {
+    termgfx()
+        .args(["clock", "--style", "small", "--seconds", "--date"])
+        .args(["--timezone", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("▀"))
+        .stdout(predicate::str::is_match(r"day, \d{1,2} [A-Z][a-z]+ \d{4}").unwrap());
+}
+
+#[test]
+fn test_clock_named_zone_in_date_line() {
+    termgfx()
+        .args(["clock", "--date", "--timezone", "Asia/Tokyo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Asia/Tokyo"));
+}
+
+#[test]
+fn test_clock_invalid_timezone() {
+    termgfx()
+        .args(["clock", "--timezone", "Mars/Olympus"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown timezone 'Mars/Olympus'"));
+}
+
+#[test]
+fn test_clock_invalid_format() {
+    termgfx()
+        .args(["clock", "--format", "13h"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid format '13h' (use 24h, 12h)",
+        ));
+}
+
+#[test]
+fn test_clock_is_never_cached() {
+    termgfx()
+        .args(["--cache", "1m", "clock"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'clock' is live"));
+}
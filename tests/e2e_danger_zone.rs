@@ -92,6 +92,7 @@ fn test_danger_zone_multiline_message() {
 fn test_danger_zone_has_red_coloring() {
     // Check for ANSI red escape codes
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["danger-zone", "Red warning"])
         .assert()
         .success()
@@ -394,3 +394,235 @@ fn test_dashboard_default_layout() {
         .stdout(predicate::str::contains("1"))
         .stdout(predicate::str::contains("4"));
 }
+
+#[test]
+fn test_dashboard_chart_panel() {
+    termgfx()
+        .args([
+            "dashboard",
+            "--layout",
+            "1x1",
+            "--panels",
+            "chart:api:4;db:2",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("api ████"))
+        .stdout(predicate::str::contains("db"));
+}
+
+// ============================================================================
+// NESTED LAYOUT TESTS
+// ============================================================================
+
+fn dashboard_config(config: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), config).unwrap();
+    file.as_file_mut().sync_all().unwrap();
+    file
+}
+
+#[test]
+fn test_dashboard_nested_layout_with_titles() {
+    let file = dashboard_config(
+        r#"{"layout": {"rows": [
+            {"size": 5, "panel": 0},
+            {"columns": [{"size": "25%", "panel": 1}, {"panel": 2}]}
+        ]}, "panels": [
+            {"type": "text", "title": "Status", "content": "All good"},
+            {"type": "gauge", "title": "CPU", "content": "42"},
+            {"type": "box", "content": "Logs"}
+        ]}"#,
+    );
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("┌ Status ─"))
+        .stdout(predicate::str::contains("All good"))
+        .stdout(predicate::str::contains("┌ CPU ─"))
+        .stdout(predicate::str::contains("42%"))
+        .stdout(predicate::str::contains("┌ box ─"));
+}
+
+#[test]
+fn test_dashboard_grid_shows_panel_titles() {
+    let file = dashboard_config(
+        r#"{"layout": "1x2", "panels": [
+            {"type": "box", "title": "Left", "content": "A"},
+            {"type": "box", "content": "B"}
+        ]}"#,
+    );
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("┌─ Left ─"));
+}
+
+#[test]
+fn test_dashboard_nested_layout_unknown_panel() {
+    let file = dashboard_config(
+        r#"{"layout": {"columns": [{"panel": 0}, {"panel": 3}]},
+            "panels": [{"type": "box", "content": "A"}]}"#,
+    );
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Layout refers to panel 3, but there are 1 panels",
+        ));
+}
+
+#[test]
+fn test_dashboard_nested_layout_bad_size() {
+    let file = dashboard_config(
+        r#"{"layout": {"columns": [{"size": "half", "panel": 0}]},
+            "panels": [{"type": "box", "content": "A"}]}"#,
+    );
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid size 'half'"));
+}
+
+// ============================================================================
+// FILE AND COMMAND PANEL TESTS
+// ============================================================================
+
+#[test]
+fn test_dashboard_table_tree_and_log_panels() {
+    let csv = dashboard_config("name,cpu\napi,42\ndb,77\n");
+    let log = dashboard_config(
+        &(1..=20)
+            .map(|i| format!("line {}\n", i))
+            .collect::<String>(),
+    );
+    let panels = format!(
+        "table:{},tree:app>src,tests,log:{}",
+        csv.path().display(),
+        log.path().display()
+    );
+    termgfx()
+        .args(["dashboard", "--layout", "1x3", "--panels", &panels])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("name │ cpu"))
+        .stdout(predicate::str::contains("db   │ 77"))
+        .stdout(predicate::str::contains("├─ src"))
+        .stdout(predicate::str::contains("line 20"))
+        .stdout(predicate::str::contains("line 15").not());
+}
+
+#[test]
+fn test_dashboard_text_panel_runs_command_once() {
+    let file = dashboard_config(
+        r#"{"layout": "1x1", "panels": [
+            {"type": "text", "command": "printf 'first\\nsecond'"}
+        ]}"#,
+    );
+    let output = termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[1].starts_with("│first "));
+    assert!(lines[2].starts_with("│second "));
+}
+
+#[test]
+fn test_dashboard_image_panel() {
+    use image::{Rgba, RgbaImage};
+
+    let png = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+    RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255]))
+        .save(png.path())
+        .unwrap();
+    let config = format!(
+        r#"{{"layout": {{"columns": [{{"panel": 0}}]}},
+            "panels": [{{"type": "image", "content": "{}"}}]}}"#,
+        png.path().display()
+    );
+    let file = dashboard_config(&config);
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("▀"));
+
+    termgfx()
+        .args([
+            "dashboard",
+            "--layout",
+            "1x1",
+            "--panels",
+            "image:/no/such.png",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/no/such.png:"));
+}
+
+#[test]
+fn test_dashboard_rejects_bad_source() {
+    let file =
+        dashboard_config(r#"{"layout": "1x1", "panels": [{"type": "gauge", "source": "gpu"}]}"#);
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid source 'gpu'"));
+}
+
+#[cfg(feature = "sources")]
+#[test]
+fn test_dashboard_source_panel() {
+    let file =
+        dashboard_config(r#"{"layout": "1x1", "panels": [{"type": "gauge", "source": "mem"}]}"#);
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("%"));
+}
+
+// ============================================================================
+// LIVE MODE TESTS
+// ============================================================================
+
+#[test]
+fn test_dashboard_live_requires_tty() {
+    termgfx()
+        .args([
+            "dashboard",
+            "--layout",
+            "1x1",
+            "--panels",
+            "gauge:50",
+            "--live",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires an interactive terminal"));
+}
+
+#[test]
+fn test_dashboard_command_panels_render_content_without_live() {
+    let config = r#"{"layout": "1x2", "panels": [
+        {"type": "gauge", "content": "40", "command": "echo 90", "interval": "2s"},
+        {"type": "sparkline", "command": "echo 1"}
+    ]}"#;
+    let mut file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), config).unwrap();
+    file.as_file_mut().sync_all().unwrap();
+    termgfx()
+        .args(["dashboard", "--config", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("40%"));
+}
@@ -102,6 +102,102 @@ fn test_diff_nonexistent_file() {
         .failure();
 }
 
+#[test]
+fn test_diff_word_diff_highlights_changed_word() {
+    let mut file1 = NamedTempFile::new().unwrap();
+    let mut file2 = NamedTempFile::new().unwrap();
+
+    writeln!(file1, "let total = 10;").unwrap();
+    writeln!(file2, "let total = 25;").unwrap();
+
+    termgfx()
+        .env("CLICOLOR_FORCE", "1")
+        .args([
+            "diff",
+            file1.path().to_str().unwrap(),
+            file2.path().to_str().unwrap(),
+            "--unified",
+            "--word-diff",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[37;41;1m10"))
+        .stdout(predicate::str::contains("\x1b[30;42;1m25"));
+}
+
+#[test]
+fn test_diff_directories_summary() {
+    let old = tempfile::tempdir().unwrap();
+    let new = tempfile::tempdir().unwrap();
+    std::fs::create_dir(old.path().join("src")).unwrap();
+    std::fs::create_dir(new.path().join("src")).unwrap();
+    std::fs::write(old.path().join("src/lib.rs"), "a\nb\n").unwrap();
+    std::fs::write(new.path().join("src/lib.rs"), "a\nc\n").unwrap();
+    std::fs::write(old.path().join("same.txt"), "x\n").unwrap();
+    std::fs::write(new.path().join("same.txt"), "x\n").unwrap();
+    std::fs::write(new.path().join("added.txt"), "y\n").unwrap();
+
+    termgfx()
+        .args([
+            "diff",
+            old.path().to_str().unwrap(),
+            new.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("added.txt"))
+        .stdout(predicate::str::contains("lib.rs"))
+        .stdout(predicate::str::contains("2 files changed"))
+        .stdout(predicate::str::contains("1 unchanged"))
+        .stdout(predicate::str::contains("same.txt").not());
+}
+
+#[test]
+fn test_diff_directory_with_file_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = NamedTempFile::new().unwrap();
+
+    termgfx()
+        .args([
+            "diff",
+            dir.path().to_str().unwrap(),
+            file.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot compare a directory"));
+}
+
+#[test]
+fn test_diff_interactive_needs_tty_and_files() {
+    let mut file1 = NamedTempFile::new().unwrap();
+    let mut file2 = NamedTempFile::new().unwrap();
+    writeln!(file1, "a").unwrap();
+    writeln!(file2, "b").unwrap();
+    termgfx()
+        .args(["diff", "--interactive"])
+        .args([file1.path(), file2.path()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("requires an interactive terminal"));
+
+    let dir = tempfile::tempdir().unwrap();
+    termgfx()
+        .args(["diff", "-i"])
+        .args([dir.path(), dir.path()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not directories"));
+
+    termgfx()
+        .args(["diff", "--interactive", "--unified"])
+        .args([file1.path(), file2.path()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
 // ============================================================================
 // TABLE COMMAND TESTS
 // ============================================================================
@@ -123,6 +219,77 @@ fn test_table_inline_data() {
         .stdout(predicate::str::contains("Bob"));
 }
 
+#[test]
+fn test_table_aligns_cjk_emoji_and_colored_cells() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Name,城市",
+            "--rows",
+            "東京,\x1b[32mok\x1b[0m|Paris,🚀 go",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let plain = regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&stdout, "")
+        .to_string();
+    let widths: Vec<usize> = plain
+        .lines()
+        .map(unicode_width::UnicodeWidthStr::width)
+        .collect();
+    assert!(widths.iter().all(|w| *w == widths[0]), "{:?}", widths);
+}
+
+#[test]
+fn test_table_profile_render_reports_phases() {
+    termgfx()
+        .args([
+            "table",
+            "--headers",
+            "A,B",
+            "--rows",
+            "1,2",
+            "--profile-render",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile").not())
+        .stderr(predicate::str::contains("table"))
+        .stderr(predicate::str::contains("layout"))
+        .stderr(predicate::str::contains("allocs"))
+        .stderr(predicate::str::contains("total"));
+}
+
+#[test]
+fn test_profile_render_covers_every_command() {
+    for args in [
+        &["box", "Hello"][..],
+        &["chart", "bar", "--data", "A:1,B:2"][..],
+    ] {
+        termgfx()
+            .args(args)
+            .arg("--profile-render")
+            .assert()
+            .success()
+            .stderr(predicate::str::is_match(r"layout\s+\d").unwrap())
+            .stderr(predicate::str::is_match(r"render\s+\d").unwrap())
+            .stderr(predicate::str::is_match(r"write\s+\d").unwrap());
+    }
+}
+
+#[test]
+fn test_profile_render_is_hidden_from_help() {
+    termgfx()
+        .args(["table", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile-render").not());
+}
+
 #[test]
 fn test_table_single_row() {
     termgfx()
@@ -256,6 +423,286 @@ fn test_table_many_columns() {
         .success();
 }
 
+fn row_order(stdout: &str, names: &[&str]) -> Vec<usize> {
+    names
+        .iter()
+        .map(|n| stdout.find(n).unwrap_or_else(|| panic!("{} missing", n)))
+        .collect()
+}
+
+#[test]
+fn test_table_sort_numeric_column() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Name,Count",
+            "--rows",
+            "alpha,10|beta,9|gamma,100",
+            "--sort",
+            "count",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pos = row_order(&stdout, &["beta", "alpha", "gamma"]);
+    assert!(pos[0] < pos[1] && pos[1] < pos[2], "{}", stdout);
+}
+
+#[test]
+fn test_table_sort_sizes_descending_by_index() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "File,Size",
+            "--rows",
+            "a.iso,1.2 GiB|b.log,900 MB|c.txt,512K",
+            "--sort",
+            "2",
+            "--desc",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pos = row_order(&stdout, &["a.iso", "b.log", "c.txt"]);
+    assert!(pos[0] < pos[1] && pos[1] < pos[2], "{}", stdout);
+}
+
+#[test]
+fn test_table_sort_type_override() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Name,Count",
+            "--rows",
+            "alpha,10|beta,9|gamma,100",
+            "--sort",
+            "Count",
+            "--sort-type",
+            "string",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pos = row_order(&stdout, &["alpha", "gamma", "beta"]);
+    assert!(pos[0] < pos[1] && pos[1] < pos[2], "{}", stdout);
+}
+
+#[test]
+fn test_table_sort_unknown_column() {
+    termgfx()
+        .args(["table", "--headers", "A,B", "--rows", "1,2", "--sort", "C"])
+        .assert()
+        .stderr(predicate::str::contains("Unknown sort column 'C'"));
+}
+
+#[test]
+fn test_table_sort_invalid_type() {
+    termgfx()
+        .args([
+            "table",
+            "--headers",
+            "A",
+            "--rows",
+            "1",
+            "--sort",
+            "A",
+            "--sort-type",
+            "roman",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid sort type 'roman'"));
+}
+
+/// Output lines with colors removed
+fn plain_lines(stdout: &[u8]) -> Vec<String> {
+    let stdout = String::from_utf8_lossy(stdout);
+    regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&stdout, "")
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_table_sort_direction_suffix() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Name,Count",
+            "--rows",
+            "alpha,10|beta,9|gamma,100",
+            "--sort",
+            "count:desc",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pos = row_order(&stdout, &["gamma", "alpha", "beta"]);
+    assert!(pos[0] < pos[1] && pos[1] < pos[2], "{}", stdout);
+}
+
+#[test]
+fn test_table_max_col_width_truncates() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Name,Notes",
+            "--rows",
+            "alpha,a long note that wraps around",
+            "--max-col-width",
+            "8",
+        ])
+        .output()
+        .unwrap();
+    let lines = plain_lines(&output.stdout);
+    assert!(
+        lines.iter().any(|l| l.contains("│ a lon... │")),
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+fn test_table_wrap_keeps_columns_aligned() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Name,Notes",
+            "--rows",
+            "alpha,a long note that wraps|東京,日本語のテキストです",
+            "--max-col-width",
+            "10",
+            "--wrap",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines = plain_lines(&output.stdout);
+    // 2 rows spread over 3 + 2 lines, plus borders and the header
+    assert_eq!(lines.len(), 9, "{:?}", lines);
+    assert!(lines.iter().any(|l| l.contains("│       │ note that  │")));
+    let widths: Vec<usize> = lines
+        .iter()
+        .map(|l| unicode_width::UnicodeWidthStr::width(l.as_str()))
+        .collect();
+    assert!(widths.iter().all(|w| *w == widths[0]), "{:?}", widths);
+}
+
+#[test]
+fn test_table_max_col_width_rejects_zero() {
+    termgfx()
+        .args(["table", "--headers", "Name", "--rows", "alpha"])
+        .args(["--max-col-width", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-col-width"));
+}
+
+#[test]
+fn test_table_footer_summary_row() {
+    let output = termgfx()
+        .args([
+            "table",
+            "--headers",
+            "Item,Cost",
+            "--rows",
+            "a,$1.50|b,2|c,",
+            "--footer",
+            "{count} items,{sum}",
+        ])
+        .output()
+        .unwrap();
+    let lines = plain_lines(&output.stdout);
+    let footer = &lines[lines.len() - 2];
+    assert!(
+        footer.contains("3 items") && footer.contains("3.5"),
+        "{:?}",
+        lines
+    );
+    assert!(lines[lines.len() - 3].starts_with('├'));
+}
+
+#[test]
+fn test_table_json_file_columns_from_keys() {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(
+        file,
+        r#"[{{"name": "a", "age": 1}}, {{"name": "b", "age": 2, "team": "x"}}]"#
+    )
+    .unwrap();
+    let output = termgfx()
+        .args(["table", "--json", file.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines = plain_lines(&output.stdout);
+    // Keys only later objects have still get a column
+    assert!(
+        lines[1].contains("name") && lines[1].contains("team"),
+        "{:?}",
+        lines
+    );
+    assert!(
+        lines[4].contains('b') && lines[4].contains('x'),
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+fn test_table_tsv_stdin_with_columns() {
+    let output = termgfx()
+        .args(["table", "--columns", "city,1"])
+        .write_stdin("name\tage\tcity\nAda\t36\tLondon\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines = plain_lines(&output.stdout);
+    assert_eq!(lines[1], "│ city   │ name │");
+    assert_eq!(lines[3], "│ London │ Ada  │");
+}
+
+#[test]
+fn test_table_csv_stdin_hide_and_quotes() {
+    let output = termgfx()
+        .args(["table", "--hide", "id"])
+        .write_stdin("id,name,place\n1,Bob,\"Paris, FR\"\n")
+        .output()
+        .unwrap();
+    let lines = plain_lines(&output.stdout);
+    assert_eq!(lines[1], "│ name │ place     │");
+    assert_eq!(lines[3], "│ Bob  │ Paris, FR │");
+}
+
+#[test]
+fn test_table_unknown_column() {
+    termgfx()
+        .args([
+            "table",
+            "--headers",
+            "A,B",
+            "--rows",
+            "1,2",
+            "--columns",
+            "C",
+        ])
+        .assert()
+        .stderr(predicate::str::contains(
+            "Unknown column 'C' (use a header name or 1-2)",
+        ));
+}
+
 // ============================================================================
 // TREE COMMAND TESTS
 // ============================================================================
@@ -296,3 +743,333 @@ fn test_tree_simple_structure() {
         .assert()
         .success();
 }
+
+// ============================================================================
+// JSON VIEWER TESTS
+// ============================================================================
+
+#[test]
+fn test_json_prints_expanded_tree_when_piped() {
+    termgfx()
+        .args(["json", "-"])
+        .write_stdin(r#"{"server": {"port": 8080, "hosts": ["a", "b"]}}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<stdin>"))
+        .stdout(predicate::str::contains("port"))
+        .stdout(predicate::str::contains("8080"))
+        .stdout(predicate::str::contains("\"b\""))
+        .stdout(predicate::str::contains("└── "));
+}
+
+#[test]
+fn test_json_reads_file() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, r#"[{{"id": 1}}, {{"id": 2}}]"#).unwrap();
+    termgfx()
+        .args(["json", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[1]"))
+        .stdout(predicate::str::contains("id"));
+}
+
+#[test]
+fn test_json_invalid_input() {
+    termgfx()
+        .args(["json", "-"])
+        .write_stdin("{not json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid JSON"));
+}
+
+#[test]
+fn test_tree_diff_merged_tree() {
+    let mut old = NamedTempFile::new().unwrap();
+    let mut new = NamedTempFile::new().unwrap();
+    write!(old, r#"{{"port": 80, "debug": true}}"#).unwrap();
+    write!(new, r#"{{"port": 8080, "tls": true}}"#).unwrap();
+    termgfx()
+        .args([
+            "tree",
+            "--diff",
+            old.path().to_str().unwrap(),
+            new.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("port: 80 → 8080"))
+        .stdout(
+            predicate::str::contains("- └── 📌 debug: true")
+                .or(predicate::str::contains("- ├── 📌 debug: true")),
+        )
+        .stdout(predicate::str::contains("tls: true"))
+        .stdout(predicate::str::contains("+1 added"));
+}
+
+#[test]
+fn test_tree_diff_only_changes_from_stdin() {
+    let mut old = NamedTempFile::new().unwrap();
+    write!(old, r#"{{"a": 1, "b": 2}}"#).unwrap();
+    termgfx()
+        .args([
+            "tree",
+            "--diff",
+            old.path().to_str().unwrap(),
+            "-",
+            "--only-changes",
+        ])
+        .write_stdin(r#"{"a": 1, "b": 3}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("b: 2 → 3"))
+        .stdout(predicate::str::contains("1 unchanged"))
+        .stdout(predicate::str::contains("a: 1").not());
+}
+
+#[test]
+fn test_tree_diff_rejects_double_stdin() {
+    termgfx()
+        .args(["tree", "--diff", "-", "-"])
+        .write_stdin("{}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("stdin"));
+}
+
+#[test]
+fn test_tree_only_changes_requires_diff() {
+    termgfx()
+        .args(["tree", "--only-changes"])
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// QUERY (--query) TESTS
+// ============================================================================
+
+const QUERY_INPUT: &str =
+    r#"{"items":[{"name":"api","count":3,"owner":"ops"},{"name":"web","count":5,"owner":"dev"}]}"#;
+
+#[test]
+fn test_table_query_reshapes_stdin() {
+    termgfx()
+        .args([
+            "table",
+            "--query",
+            ".items[] | {label: .name, value: .count}",
+        ])
+        .write_stdin(QUERY_INPUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("label"))
+        .stdout(predicate::str::contains("api"))
+        .stdout(predicate::str::contains("owner").not());
+}
+
+#[test]
+fn test_table_query_reads_file_as_json() {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(file, "{}", QUERY_INPUT).unwrap();
+    termgfx()
+        .args([
+            "table",
+            "--file",
+            file.path().to_str().unwrap(),
+            "--query",
+            ".items | map(select(.count > 4))",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("web"))
+        .stdout(predicate::str::contains("api").not());
+}
+
+#[test]
+fn test_table_invalid_query() {
+    termgfx()
+        .args(["table", "--query", ".items["])
+        .write_stdin(QUERY_INPUT)
+        .assert()
+        .stderr(predicate::str::contains("Invalid query"));
+}
+
+#[test]
+fn test_tree_query() {
+    termgfx()
+        .args(["tree", "--path", "-", "--query", ".items[1]"])
+        .write_stdin(QUERY_INPUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("owner"))
+        .stdout(predicate::str::contains("items").not());
+}
+
+#[test]
+fn test_tree_diff_query_applies_to_both_sides() {
+    let mut old = NamedTempFile::new().unwrap();
+    let mut new = NamedTempFile::new().unwrap();
+    write!(old, r#"{{"a": 1, "noise": 1}}"#).unwrap();
+    write!(new, r#"{{"a": 1, "noise": 2}}"#).unwrap();
+    termgfx()
+        .args([
+            "tree",
+            "--diff",
+            old.path().to_str().unwrap(),
+            new.path().to_str().unwrap(),
+            "--query",
+            "del(.noise)",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No differences"));
+}
+
+fn project_dir() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src/inner")).unwrap();
+    std::fs::create_dir_all(root.join("target")).unwrap();
+    std::fs::write(root.join(".gitignore"), "target\n").unwrap();
+    std::fs::write(root.join(".env"), "").unwrap();
+    std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(root.join("src/inner/util.py"), "").unwrap();
+    std::fs::write(root.join("target/out"), "").unwrap();
+    dir
+}
+
+#[test]
+fn test_tree_dir_respects_gitignore_and_hidden() {
+    let dir = project_dir();
+    let output = termgfx()
+        .args(["tree", "--dir", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines = plain_lines(&output.stdout);
+    assert_eq!(
+        lines[1..],
+        [
+            "└── 📁 src",
+            "    ├── 📁 inner",
+            "    │   └── 🐍 util.py",
+            "    └── 🦀 main.rs",
+            "",
+            "2 directories, 2 files",
+        ]
+    );
+}
+
+#[test]
+fn test_tree_dir_all_no_ignore_and_depth() {
+    let dir = project_dir();
+    termgfx()
+        .args(["tree", "--dir", dir.path().to_str().unwrap()])
+        .args(["--all", "--no-ignore", "-L", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".env"))
+        .stdout(predicate::str::contains("target"))
+        .stdout(predicate::str::contains("main.rs").not())
+        .stdout(predicate::str::contains("2 directories, 2 files"));
+}
+
+#[test]
+fn test_tree_dir_sizes() {
+    let dir = project_dir();
+    termgfx()
+        .args(["tree", "--dir", dir.path().to_str().unwrap(), "--size"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(13 B)"));
+}
+
+#[test]
+fn test_tree_dir_missing() {
+    termgfx()
+        .args(["tree", "--dir", "/no/such/dir"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to read /no/such/dir"));
+}
+
+#[test]
+fn test_json_query() {
+    termgfx()
+        .args(["json", "-", "--query", ".items | map(.name)"])
+        .write_stdin(QUERY_INPUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"web\""))
+        .stdout(predicate::str::contains("owner").not());
+}
+
+#[test]
+fn test_json_query_unknown_function() {
+    termgfx()
+        .args(["json", "-", "--query", "frobnicate"])
+        .write_stdin(QUERY_INPUT)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown function frobnicate/0"));
+}
+
+// ============================================================================
+// LOG VIEWER TESTS
+// ============================================================================
+
+#[test]
+fn test_log_colors_levels_from_stdin() {
+    termgfx()
+        .env("CLICOLOR_FORCE", "1")
+        .arg("log")
+        .write_stdin("10:00:00 INFO started\n10:00:01 ERROR failed to bind\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("started"))
+        .stdout(predicate::str::contains("\x1b[31;1mERROR"))
+        .stdout(predicate::str::contains("failed to bind"));
+}
+
+#[test]
+fn test_log_filter_and_relative() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "12:00:00 INFO db connected").unwrap();
+    writeln!(file, "12:00:02 INFO http listening").unwrap();
+    writeln!(file, "12:01:00 WARN db slow query").unwrap();
+    termgfx()
+        .args([
+            "log",
+            file.path().to_str().unwrap(),
+            "--filter",
+            "db",
+            "--relative",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("db connected"))
+        .stdout(predicate::str::contains("http").not())
+        .stdout(predicate::str::contains("(+1m00s)"));
+}
+
+#[test]
+fn test_log_invalid_filter() {
+    termgfx()
+        .args(["log", "--filter", "("])
+        .write_stdin("INFO x\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid filter regex"));
+}
+
+#[test]
+fn test_log_follow_streams_stdin_until_eof() {
+    termgfx()
+        .args(["log", "-", "--follow"])
+        .write_stdin("WARN low disk\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("low disk"));
+}
@@ -357,3 +357,298 @@ fn test_svg_escapes_special_characters() {
         .stdout(predicate::str::contains("&gt;"))
         .stdout(predicate::str::contains("&quot;"));
 }
+
+#[test]
+fn test_export_replaces_file_atomically() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chart.svg");
+    fs::write(&path, "stale").unwrap();
+
+    cmd()
+        .args(["export", "progress", "40", "-o", path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    assert!(fs::read_to_string(&path).unwrap().contains("<svg"));
+    // Only the finished file is left behind, no temp file
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_export_to_missing_directory_fails_cleanly() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing").join("box.svg");
+
+    cmd()
+        .args(["export", "box", "hi", "-o", path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to write"));
+}
+
+// ============================================================================
+// ci-comment tests
+// ============================================================================
+
+#[test]
+fn test_ci_comment_writes_svg_and_markdown() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chart.svg");
+
+    cmd()
+        .args([
+            "ci-comment",
+            "--command",
+            "termgfx chart bar --data A:1,B:3",
+        ])
+        .args(["-o", path.to_str().unwrap(), "--title", "Coverage"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "<!-- termgfx chart bar - -data A:1,B:3 -->\n**Coverage**",
+        ))
+        .stdout(predicate::str::contains(format!(
+            "![termgfx chart bar --data A:1,B:3]({})",
+            path.display()
+        )))
+        .stdout(predicate::str::contains("<details>"))
+        .stdout(predicate::str::contains("\u{1b}[").not());
+
+    let svg = fs::read_to_string(&path).unwrap();
+    assert!(svg.starts_with("<?xml"));
+    assert!(svg.contains("xml:space=\"preserve\""));
+}
+
+#[test]
+fn test_ci_comment_uploader_url() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("box.svg");
+
+    cmd()
+        .args(["ci-comment", "-c", "box Hi"])
+        .args(["-o", path.to_str().unwrap()])
+        .args([
+            "--upload",
+            "echo uploading >&2; echo https://cdn.example/$(basename {file})",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("](https://cdn.example/box.svg)"));
+}
+
+#[test]
+fn test_ci_comment_uploader_from_env() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("box.svg");
+
+    cmd()
+        .args(["ci-comment", "-c", "box Hi", "-o", path.to_str().unwrap()])
+        .env("TERMGFX_UPLOADER", "echo https://x.example/u/")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("](https://x.example/u/"));
+}
+
+#[test]
+fn test_ci_comment_md_text_and_svg_formats() {
+    cmd()
+        .args(["ci-comment", "-c", "box Hi", "-f", "md-text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("```text\n"))
+        .stdout(predicate::str::contains("Hi"))
+        .stdout(predicate::str::contains("<details>").not());
+
+    cmd()
+        .args(["ci-comment", "-c", "box Hi", "-f", "svg"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<?xml"));
+}
+
+#[test]
+fn test_ci_comment_errors() {
+    cmd()
+        .args(["ci-comment", "-c", "box Hi", "-f", "png"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid format 'png'"));
+
+    cmd()
+        .args(["ci-comment", "-c", "select a b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is live"));
+
+    cmd()
+        .args(["ci-comment", "-c", "progress lots", "-f", "md-text"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Command failed"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("box.svg");
+    cmd()
+        .args(["ci-comment", "-c", "box Hi", "-o", path.to_str().unwrap()])
+        .args(["--upload", "false"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Uploader failed"));
+}
+
+// ============================================================================
+// --export flag tests
+// ============================================================================
+
+#[test]
+fn test_export_flag_writes_svg_with_colors() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("box.svg");
+    cmd()
+        .env("NO_COLOR", "1")
+        .args(["box", "Docs", "--style", "danger", "--export"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Exported to:"));
+
+    let svg = fs::read_to_string(&path).unwrap();
+    assert!(svg.starts_with("<?xml"));
+    assert!(svg.contains("Docs"));
+    // Colors are forced on for the capture, even under NO_COLOR
+    assert!(svg.contains("fill=\"#ff0000\""));
+}
+
+#[test]
+fn test_export_flag_writes_png_from_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("table.png");
+    cmd()
+        .args(["table", "--export", path.to_str().unwrap()])
+        .write_stdin(r#"[{"name": "api", "status": "up"}]"#)
+        .assert()
+        .success();
+    let png = fs::read(&path).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn test_export_flag_keeps_command_color_flags() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("gauge.svg");
+    cmd()
+        .args(["gauge", "70", "--accent", "red", "--export"])
+        .arg(&path)
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&path).unwrap().contains("70"));
+}
+
+#[test]
+fn test_export_flag_errors() {
+    cmd()
+        .args(["select", "a", "b", "--export", "out.svg"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--export works with box, banner"));
+
+    cmd()
+        .args(["box", "Hi", "--export", "out.gif"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use a .svg, .png or .html file"));
+}
+
+#[test]
+fn test_export_flag_writes_html_page() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("box.html");
+    cmd()
+        .args(["box", "Docs", "--style", "success", "--export"])
+        .arg(&path)
+        .assert()
+        .success();
+    let html = fs::read_to_string(&path).unwrap();
+    assert!(html.starts_with("<style>"));
+    assert!(html.contains("<pre class=\"termgfx\">"));
+    assert!(html.contains("Docs"));
+}
+
+// ============================================================================
+// --format flag tests
+// ============================================================================
+
+#[test]
+fn test_format_html_prints_styled_spans() {
+    cmd()
+        .env("NO_COLOR", "1")
+        .args(["box", "a < b", "--style", "danger", "--format", "html"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".termgfx .red {"))
+        .stdout(predicate::str::contains("a &lt; b"))
+        .stdout(predicate::str::contains("<span class=\""))
+        .stdout(predicate::str::contains("\x1b").not());
+}
+
+#[test]
+fn test_format_svg_for_table() {
+    cmd()
+        .args(["table", "--headers", "Name,Age", "--rows", "Alice,30"])
+        .args(["--format", "svg"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<?xml"))
+        .stdout(predicate::str::contains("Alice"));
+}
+
+#[test]
+fn test_format_for_progress() {
+    cmd()
+        .args(["progress", "50", "--style", "classic", "--format", "html"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<pre class=\"termgfx\">"))
+        .stdout(predicate::str::contains("50%"))
+        .stdout(predicate::str::contains("\x1b").not());
+
+    cmd()
+        .args(["progress", "50", "--format", "svg"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<svg"));
+
+    cmd()
+        .args(["progress", "50", "--format", "html", "--animate"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_format_ansi_is_the_default() {
+    cmd()
+        .args(["box", "Plain", "--format", "ansi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Plain"))
+        .stdout(predicate::str::contains("<span").not());
+}
+
+#[test]
+fn test_format_errors() {
+    cmd()
+        .args(["box", "Hi", "--format", "png"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid format 'png' (use ansi, html, svg)",
+        ));
+
+    cmd()
+        .args(["box", "Hi", "--format", "html", "--animate"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
@@ -51,6 +51,18 @@ fn test_form_json_config() {
     // We're just verifying it doesn't fail to parse the config
 }
 
+#[test]
+fn test_form_config_from_stdin() {
+    let config = r#"{"fields": [{"name": "username", "type": "text", "label": "Username"}]}"#;
+    Command::cargo_bin("termgfx")
+        .unwrap()
+        .args(["form", "--config", "-", "--default", "username=ada"])
+        .write_stdin(config)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"username\": \"ada\""));
+}
+
 #[test]
 fn test_form_field_format_validation() {
     let mut cmd = Command::cargo_bin("termgfx").unwrap();
@@ -146,3 +158,220 @@ fn test_form_field_types() {
 
     // Verify config loads without error
 }
+
+#[test]
+fn test_form_defaults_without_tty() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    let output = cmd
+        .args([
+            "form",
+            "-f",
+            "name:text:Name",
+            "-f",
+            "role:select:Role:Admin,User",
+        ])
+        .args(["--default", "name=ci", "--default", "role=User"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let values: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(values["name"], "ci");
+    assert_eq!(values["role"], "User");
+}
+
+#[test]
+fn test_form_without_tty_lists_missing_defaults() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "-f", "name:text:Name", "-f", "role:text:Role"])
+        .args(["--default", "name=ci"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --default for: role"));
+
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args([
+        "form",
+        "-f",
+        "name:text:Name",
+        "--no-tty-error",
+        "-o",
+        "env",
+    ])
+    .assert()
+    .success()
+    .stdout("NAME=\n");
+}
+
+#[test]
+fn test_form_config_value_is_default() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("form.json");
+    fs::write(
+        &config_path,
+        r#"{"fields": [{"name": "env", "type": "text", "label": "Env", "value": "staging"}]}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args([
+        "form",
+        "--config",
+        config_path.to_str().unwrap(),
+        "-o",
+        "csv",
+    ])
+    .assert()
+    .success()
+    .stdout("env,staging\n");
+}
+
+#[test]
+fn test_form_textarea_field_default() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    let output = cmd
+        .args(["form", "-f", "notes:textarea:Notes"])
+        .args(["--default", "notes=line one\nline two"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let values: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(values["notes"], "line one\nline two");
+}
+
+fn branching_config() -> tempfile::TempDir {
+    let dir = tempdir().unwrap();
+    let config = r#"{
+  "fields": [
+    {"name": "kind", "type": "select", "label": "Kind", "options": ["bug", "feature"]},
+    {"name": "severity", "type": "select", "label": "Severity", "options": ["low", "high"],
+     "when": {"field": "kind", "equals": "bug"}},
+    {"name": "votes", "type": "number", "label": "Votes", "required": true,
+     "validate": "number", "min": 0, "max": 10,
+     "when": {"field": "kind", "equals": ["feature", "idea"]}}
+  ]
+}"#;
+    fs::write(dir.path().join("form.json"), config).unwrap();
+    dir
+}
+
+#[test]
+fn test_form_when_skips_hidden_fields() {
+    let dir = branching_config();
+    let config = dir.path().join("form.json");
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    let output = cmd
+        .args(["form", "--config", config.to_str().unwrap()])
+        .args(["--default", "kind=bug", "--default", "severity=high"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let values: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(values["severity"], "high");
+    assert!(values.get("votes").is_none());
+}
+
+#[test]
+fn test_form_validates_defaults() {
+    let dir = branching_config();
+    let config = dir.path().join("form.json");
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "--config", config.to_str().unwrap()])
+        .args(["--default", "kind=feature", "--default", "votes=11"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid --default for votes: must be at most 10",
+        ));
+
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "--config", config.to_str().unwrap()])
+        .args(["--default", "kind=feature", "--no-tty-error"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("votes: an answer is required"));
+}
+
+#[test]
+fn test_form_when_must_name_an_earlier_field() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("form.json");
+    fs::write(
+        &config_path,
+        r#"{"fields": [
+  {"name": "a", "type": "text", "label": "A", "when": {"field": "b", "equals": "x"}},
+  {"name": "b", "type": "text", "label": "B"}
+]}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "--config", config_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Field 'a': 'when' needs an earlier field, not 'b'",
+        ));
+}
+
+#[test]
+fn test_form_yaml_and_toml_output() {
+    for (format, expected) in [
+        ("yaml", "name: \"Ada\"\nrole: \"Dev\"\n"),
+        ("toml", "name = \"Ada\"\nrole = \"Dev\"\n"),
+    ] {
+        let mut cmd = Command::cargo_bin("termgfx").unwrap();
+        cmd.args([
+            "form",
+            "--field",
+            "name:text:Name",
+            "--field",
+            "role:text:Role",
+        ])
+        .args(["--default", "name=Ada", "--default", "role=Dev"])
+        .args(["--output", format])
+        .assert()
+        .success()
+        .stdout(expected);
+    }
+}
+
+#[test]
+fn test_form_export_shell() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "--field", "user-name:text:Name"])
+        .args(["--default", "user-name=O'Brien", "--export-shell"])
+        .assert()
+        .success()
+        .stdout("export USER_NAME='O'\\''Brien'\n");
+}
+
+#[test]
+fn test_form_output_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("answers.toml");
+
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "--field", "name:text:Name", "--default", "name=Ada"])
+        .args(["--output", "toml", "--output-file", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "name = \"Ada\"\n");
+}
+
+#[test]
+fn test_form_invalid_output_format() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["form", "--field", "name:text:Name", "--output", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid output format 'xml' (use json, env, csv, yaml, toml)",
+        ));
+}
@@ -94,6 +94,25 @@ fn test_gauge_with_color() {
         .success();
 }
 
+#[test]
+fn test_gauge_with_accent() {
+    termgfx()
+        .args(["gauge", "80", "--accent", "magenta", "--color", "always"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[95m"))
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_gauge_color_name_is_deprecated_accent() {
+    termgfx()
+        .args(["gauge", "80", "--color", "green"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("use --accent green"));
+}
+
 #[test]
 fn test_gauge_animated() {
     termgfx()
@@ -124,3 +143,179 @@ fn test_gauge_label_and_color() {
         .success()
         .stdout(predicate::str::contains("Memory"));
 }
+
+#[test]
+fn test_gauge_inline_is_single_line() {
+    termgfx()
+        .args(["gauge", "75", "--label", "CPU", "--inline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CPU "))
+        .stdout(predicate::str::contains("▰▰▰▰▰▰"))
+        .stdout(predicate::str::contains("75%"))
+        .stdout(predicate::str::contains("\n").not());
+}
+
+#[test]
+fn test_gauge_inline_tmux() {
+    termgfx()
+        .args(["gauge", "20", "--inline", "--shell", "tmux"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#[fg=#"))
+        .stdout(predicate::str::contains("\x1b").not());
+}
+
+// ============================================================================
+// BATTERY / TEMPS COMMAND TESTS
+// Sensors differ per machine, so these accept either a reading or the
+// "not found" error
+// ============================================================================
+
+fn reading_or_missing(args: &[&str], missing: &str, unit: &str) {
+    let output = termgfx().args(args).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() {
+        assert!(stdout.contains(unit), "stdout: {}", stdout);
+    } else {
+        assert!(stderr.contains(missing), "stderr: {}", stderr);
+    }
+}
+
+#[test]
+fn test_battery_reading_or_missing() {
+    reading_or_missing(&["battery"], "No battery found", "%");
+}
+
+#[test]
+fn test_temps_reading_or_missing() {
+    reading_or_missing(&["temps"], "No temperature sensors found", "°C");
+}
+
+#[test]
+fn test_temps_unmatched_sensor_filter() {
+    termgfx()
+        .args(["temps", "--sensor", "no-such-sensor-anywhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No temperature sensors found"));
+}
+
+#[test]
+fn test_battery_shell_requires_inline() {
+    termgfx()
+        .args(["battery", "--shell", "tmux"])
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// SYSTEM SOURCE TESTS
+// ============================================================================
+
+#[test]
+fn test_gauge_needs_value_or_source() {
+    termgfx().args(["gauge"]).assert().failure();
+    termgfx()
+        .args(["gauge", "50", "--source", "cpu"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_gauge_invalid_source() {
+    termgfx()
+        .args(["gauge", "--source", "gpu"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid source 'gpu' (use cpu, mem, disk[:mount], net:<iface>)",
+        ));
+}
+
+#[cfg(feature = "sources")]
+#[test]
+fn test_gauge_mem_source() {
+    termgfx()
+        .args(["gauge", "--source", "mem", "--inline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MEM "))
+        .stdout(predicate::str::contains("%"));
+}
+
+#[cfg(not(feature = "sources"))]
+#[test]
+fn test_gauge_source_needs_feature() {
+    termgfx()
+        .args(["gauge", "--source", "cpu"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`sources` feature"));
+}
+
+#[test]
+fn test_sparkline_invalid_source() {
+    termgfx()
+        .args(["sparkline", "--source", "net"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid source 'net'"));
+}
+
+#[test]
+fn test_gauge_multi_side_by_side() {
+    let output = termgfx()
+        .args([
+            "gauge",
+            "--multi",
+            "CPU:75,MEM:60,DISK:90",
+            "--style",
+            "minimal",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("CPU: ["));
+    assert!(lines[0].contains("MEM: ["));
+    assert!(lines[0].contains("DISK: ["));
+}
+
+#[test]
+fn test_gauge_multi_invalid() {
+    termgfx()
+        .args(["gauge", "--multi", "CPU"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid gauge 'CPU'"));
+}
+
+#[test]
+fn test_gauge_thresholds_color() {
+    termgfx()
+        .args([
+            "--color",
+            "always",
+            "gauge",
+            "90",
+            "--thresholds",
+            "60:yellow,85:red",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[91m90.0%"));
+}
+
+#[test]
+fn test_gauge_thresholds_invalid() {
+    termgfx()
+        .args(["gauge", "50", "--thresholds", "60:purple"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid threshold '60:purple'"));
+}
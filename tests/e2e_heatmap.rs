@@ -155,3 +155,38 @@ fn test_heatmap_irregular_grid() {
         .success()
         .stdout(predicate::str::is_empty().not());
 }
+
+#[test]
+fn test_heatmap_annotate_missing_and_legend() {
+    termgfx()
+        .args(["heatmap", "--data", "1,2,3;4,,6", "--annotate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("      1 2 3\n"))
+        .stdout(predicate::str::contains("      4 · 6\n"))
+        .stdout(predicate::str::contains("1    3.5   6"));
+
+    termgfx()
+        .args(["heatmap", "--data", "1,250;3,4", "--annotate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(" 1  250"));
+}
+
+#[test]
+fn test_heatmap_log_scale_rejects_zero() {
+    termgfx()
+        .args(["heatmap", "--data", "0,10;100,1000", "--scale", "log"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Log scale needs positive values"));
+}
+
+#[test]
+fn test_heatmap_invalid_null_color() {
+    termgfx()
+        .args(["heatmap", "--data", "1,2", "--null-color", "plaid"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid null color 'plaid'"));
+}
@@ -39,6 +39,115 @@ fn test_input_password_flag_exists() {
         .stdout(predicate::str::contains("password"));
 }
 
+#[test]
+fn test_input_mask_and_type_flags_exist() {
+    termgfx()
+        .args(["input", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--mask"))
+        .stdout(predicate::str::contains("--type"));
+}
+
+#[test]
+fn test_input_invalid_type() {
+    termgfx()
+        .args(["input", "IP:", "--type", "email"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid input type"));
+}
+
+#[test]
+fn test_input_mask_conflicts_with_type() {
+    termgfx()
+        .args(["input", "IP:", "--mask", "###", "--type", "ipv4"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_input_validate_checks_default() {
+    termgfx()
+        .args(["input", "Port", "--validate", "number", "--min", "1"])
+        .args(["--max", "65535", "--default", "8080"])
+        .assert()
+        .success()
+        .stdout("8080\n");
+    termgfx()
+        .args(["input", "Port", "--validate", "number", "--max", "1024"])
+        .args(["--default", "8080"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be at most 1024"));
+    termgfx()
+        .args([
+            "input",
+            "Email",
+            "--validate",
+            "email",
+            "--default",
+            "nobody",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be an email address"));
+}
+
+#[test]
+fn test_input_invalid_validator() {
+    termgfx()
+        .args(["input", "Name", "--validate", "phone"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid validator 'phone'"));
+    termgfx()
+        .args(["input", "Name", "--min", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--validate"));
+}
+
+#[test]
+fn test_input_char_limit_checks_default() {
+    termgfx()
+        .args(["input", "Code", "--char-limit", "3", "--default", "abcd"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at most 3 characters"));
+}
+
+// ============================================================================
+// WRITE COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_write_help() {
+    termgfx()
+        .args(["write", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--header"))
+        .stdout(predicate::str::contains("--rows"))
+        .stdout(predicate::str::contains("Ctrl+D submit"));
+}
+
+#[test]
+fn test_write_default_without_tty() {
+    termgfx()
+        .args(["write", "--default", "first line\nsecond"])
+        .assert()
+        .success()
+        .stdout("first line\nsecond\n");
+    termgfx()
+        .args(["write"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Write requires an interactive terminal",
+        ));
+}
+
 // ============================================================================
 // SELECT COMMAND TESTS
 // ============================================================================
@@ -53,6 +162,92 @@ fn test_select_help() {
         .stdout(predicate::str::contains("options"));
 }
 
+#[test]
+fn test_select_danger_flag_exists() {
+    termgfx()
+        .args(["select", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--danger"));
+}
+
+#[test]
+fn test_select_danger_must_name_an_option() {
+    termgfx()
+        .args(["select", "Pick", "a", "b", "--danger", "c"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--danger option 'c' is not in the list",
+        ));
+}
+
+#[test]
+fn test_select_limit_and_selected_flags() {
+    termgfx()
+        .args(["select", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--selected"))
+        .stdout(predicate::str::contains("limit"));
+}
+
+#[test]
+fn test_select_selected_must_name_an_option() {
+    termgfx()
+        .args(["select", "Pick", "a", "b", "--multi", "--selected", "z"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--selected option 'z' is not in the list",
+        ));
+}
+
+#[test]
+fn test_select_prints_label_without_description() {
+    termgfx()
+        .args(["select", "Env", "prod::Production cluster", "dev"])
+        .args(["--default", "prod"])
+        .assert()
+        .success()
+        .stdout("prod\n");
+}
+
+// ============================================================================
+// FILTER COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_filter_preview_and_field_flags() {
+    termgfx()
+        .args(["filter", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--preview"))
+        .stdout(predicate::str::contains("--delimiter"))
+        .stdout(predicate::str::contains("--with-nth"));
+}
+
+#[test]
+fn test_filter_invalid_with_nth() {
+    termgfx()
+        .args(["filter", "--with-nth", "0"])
+        .write_stdin("a b\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid --with-nth '0'"));
+}
+
+#[test]
+fn test_filter_delimiter_needs_with_nth() {
+    termgfx()
+        .args(["filter", "--delimiter", ":"])
+        .write_stdin("a:b\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--with-nth"));
+}
+
 // ============================================================================
 // CONFIRM COMMAND TESTS
 // ============================================================================
@@ -83,3 +278,206 @@ fn test_confirm_style_flag() {
         .success()
         .stdout(predicate::str::contains("style"));
 }
+
+#[test]
+fn test_confirm_require_typed_flag() {
+    termgfx()
+        .args(["confirm", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--require-typed"));
+}
+
+// ============================================================================
+// KEYS COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_keys_help() {
+    termgfx()
+        .args(["keys", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("echo"))
+        .stdout(predicate::str::contains("fade"));
+}
+
+#[test]
+fn test_keys_requires_tty() {
+    termgfx()
+        .args(["keys", "--echo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("interactive terminal"));
+}
+
+// ============================================================================
+// REPL COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_repl_help() {
+    termgfx()
+        .args(["repl", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(":export"))
+        .stdout(predicate::str::contains(":theme"));
+}
+
+#[test]
+fn test_repl_requires_tty() {
+    termgfx()
+        .args(["repl"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("interactive terminal"));
+}
+
+// ============================================================================
+// EMOJIPICK COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_emojipick_list_searches_names_and_keywords() {
+    termgfx()
+        .args(["emojipick", "--list", "--query", "deploy"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("🚀  :rocket:"))
+        .stdout(predicate::str::contains("nf-fa-rocket"));
+}
+
+#[test]
+fn test_emojipick_list_category() {
+    let output = termgfx()
+        .args(["emojipick", "--list", "--category", "nerd"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.lines().count() > 10);
+    assert!(stdout
+        .lines()
+        .all(|l| l.contains(":nf-") && l.ends_with("nerd")));
+}
+
+#[test]
+fn test_emojipick_unknown_category() {
+    termgfx()
+        .args(["emojipick", "--list", "--category", "animals"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown category: animals"))
+        .stderr(predicate::str::contains("smileys"));
+}
+
+#[test]
+fn test_emojipick_requires_tty() {
+    termgfx()
+        .args(["emojipick"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("interactive terminal"))
+        .stderr(predicate::str::contains("--list"));
+}
+
+// ============================================================================
+// NON-TTY TESTS
+// assert_cmd runs without a TTY, as in CI
+// ============================================================================
+
+#[test]
+fn test_input_default_without_tty() {
+    termgfx()
+        .args(["input", "Region?", "--default", "us-east-1"])
+        .assert()
+        .success()
+        .stdout("us-east-1\n");
+}
+
+#[test]
+fn test_input_without_tty_needs_default() {
+    termgfx()
+        .args(["input", "Region?"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --default"));
+}
+
+#[test]
+fn test_input_no_tty_error_answers_empty() {
+    termgfx()
+        .args(["input", "Region?", "--no-tty-error"])
+        .assert()
+        .success()
+        .stdout("\n");
+}
+
+#[test]
+fn test_input_default_must_fit_type() {
+    termgfx()
+        .args(["input", "IP", "--type", "ipv4", "--default", "10.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid --default"));
+}
+
+#[test]
+fn test_select_default_without_tty() {
+    termgfx()
+        .args(["select", "Env", "dev", "prod", "--default", "prod"])
+        .assert()
+        .success()
+        .stdout("prod\n");
+    termgfx()
+        .args([
+            "select",
+            "Env",
+            "a",
+            "b",
+            "c",
+            "--multi",
+            "--default",
+            "a,c",
+        ])
+        .assert()
+        .success()
+        .stdout("a,c\n");
+}
+
+#[test]
+fn test_select_default_must_be_an_option() {
+    termgfx()
+        .args(["select", "Env", "dev", "prod", "--default", "test"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'test' is not in the list"));
+}
+
+#[test]
+fn test_confirm_default_exit_codes() {
+    termgfx()
+        .args(["confirm", "Deploy?", "--default", "yes"])
+        .assert()
+        .code(0)
+        .stdout("true\n");
+    termgfx()
+        .args(["confirm", "Deploy?", "--default", "no"])
+        .assert()
+        .code(1)
+        .stdout("false\n");
+}
+
+#[test]
+fn test_confirm_without_tty_needs_default() {
+    termgfx()
+        .args(["confirm", "Deploy?"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("pass --default"));
+    termgfx()
+        .args(["confirm", "Deploy?", "--no-tty-error"])
+        .assert()
+        .code(1);
+}
@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 use assert_cmd::Command;
 use predicates::prelude::*;
+use std::fs;
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -42,6 +43,479 @@ fn test_image_protocol_options() {
         .stdout(predicate::str::contains("halfblock"));
 }
 
+const TEST_IMAGE: &str = "docs/gifs/box.gif";
+
+fn halfblock_rows(args: &[&str]) -> Vec<String> {
+    let output = termgfx()
+        .args(["image", TEST_IMAGE, "--protocol", "halfblock"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let plain = regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&String::from_utf8_lossy(&output.stdout), "")
+        .into_owned();
+    plain.lines().map(str::to_string).collect()
+}
+
+#[test]
+fn test_image_width_sets_columns() {
+    let rows = halfblock_rows(&["--width", "10"]);
+    assert!(!rows.is_empty());
+    assert!(rows
+        .iter()
+        .all(|r| r.chars().filter(|c| *c == '▀').count() == 10));
+}
+
+#[test]
+fn test_image_width_and_height_stretch() {
+    let rows = halfblock_rows(&["--width", "12", "--height", "3"]);
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|r| r.chars().count() == 12));
+}
+
+#[test]
+fn test_image_center_alignment() {
+    // Without a terminal the width is taken as 80 columns
+    let rows = halfblock_rows(&["--width", "10", "--align", "center"]);
+    assert!(rows
+        .iter()
+        .all(|r| r.starts_with(&" ".repeat(35)) && r.ends_with('▀')));
+}
+
+#[test]
+fn test_image_invalid_size() {
+    termgfx()
+        .args(["image", TEST_IMAGE, "--width", "wide"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid size 'wide'"));
+}
+
+/// A 3-frame 4x4 GIF with 50ms frames
+fn animated_gif() -> NamedTempFile {
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba, RgbaImage};
+
+    let mut file = tempfile::Builder::new().suffix(".gif").tempfile().unwrap();
+    {
+        let mut encoder = GifEncoder::new(file.as_file_mut());
+        for shade in [0u8, 120, 240] {
+            let buffer = RgbaImage::from_pixel(4, 4, Rgba([shade, 0, 0, 255]));
+            let delay = Delay::from_numer_denom_ms(50, 1);
+            encoder
+                .encode_frame(Frame::from_parts(buffer, 0, 0, delay))
+                .unwrap();
+        }
+    }
+    file
+}
+
+#[test]
+fn test_animated_gif_loops_in_place() {
+    let gif = animated_gif();
+    let output = termgfx()
+        .args(["image", gif.path().to_str().unwrap()])
+        .args(["--protocol", "halfblock", "--width", "4", "--loop", "2"])
+        .args(["--fps", "100"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // 2 loops of 3 frames; every frame after the first redraws in place
+    assert_eq!(stdout.matches("\x1b[2A\r").count(), 5);
+    assert!(stdout.starts_with("\x1b[?25l"));
+    assert!(stdout.ends_with("\x1b[?25h"));
+}
+
+#[test]
+fn test_animated_gif_piped_shows_first_frame() {
+    let gif = animated_gif();
+    termgfx()
+        .args(["image", gif.path().to_str().unwrap()])
+        .args(["--protocol", "halfblock", "--width", "4"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("▀"))
+        .stdout(predicate::str::contains("\x1b[2A").not());
+}
+
+#[test]
+fn test_animated_gif_uses_kitty_animation() {
+    let gif = animated_gif();
+    termgfx()
+        .args(["image", gif.path().to_str().unwrap()])
+        .args(["--protocol", "kitty", "--loop", "3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a=f,"))
+        .stdout(predicate::str::contains("z=50"))
+        .stdout(predicate::str::contains("s=3,v=4"));
+}
+
+#[test]
+fn test_image_invalid_fps() {
+    termgfx()
+        .args(["image", TEST_IMAGE, "--fps", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--fps must be greater than 0"));
+}
+
+#[test]
+fn test_image_sixel_gray_palette() {
+    let output = termgfx()
+        .args(["image", TEST_IMAGE, "--protocol", "sixel", "--width", "8"])
+        .args(["--palette", "gray", "--dither"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let registers = regex::Regex::new(r"#\d+;2;(\d+);(\d+);(\d+)").unwrap();
+    assert!(registers.captures_iter(&stdout).count() > 0);
+    assert!(registers
+        .captures_iter(&stdout)
+        .all(|c| c[1] == c[2] && c[2] == c[3]));
+}
+
+#[test]
+fn test_image_invalid_palette() {
+    termgfx()
+        .args(["image", TEST_IMAGE, "--palette", "rainbow"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid palette 'rainbow' (use adaptive, web, gray)",
+        ));
+}
+
+#[test]
+fn test_image_halfblock_dither_uses_terminal_colors() {
+    let output = termgfx()
+        .args(["--color-depth", "16", "image", TEST_IMAGE])
+        .args(["--protocol", "halfblock", "--width", "10", "--dither"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains('▀'));
+    assert!(!stdout.contains("38;2;"));
+}
+
+#[test]
+fn test_image_quadblock_protocol() {
+    let output = termgfx()
+        .args([
+            "image",
+            TEST_IMAGE,
+            "--protocol",
+            "quadblock",
+            "--width",
+            "12",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let plain = regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&String::from_utf8_lossy(&output.stdout), "")
+        .into_owned();
+    let quadrants = "▘▝▀▖▌▞▛▗▚▐▜▄▙▟█ ";
+    assert!(!plain.is_empty());
+    for row in plain.lines() {
+        assert_eq!(row.chars().count(), 12);
+        assert!(row.chars().all(|c| quadrants.contains(c)));
+    }
+}
+
+#[test]
+fn test_image_braille_protocol_is_monochrome() {
+    let output = termgfx()
+        .args([
+            "image",
+            TEST_IMAGE,
+            "--protocol",
+            "braille",
+            "--width",
+            "12",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains('\x1b'));
+    for row in stdout.lines() {
+        assert_eq!(row.chars().count(), 12);
+        assert!(row.chars().all(|c| ('\u{2800}'..='\u{28ff}').contains(&c)));
+    }
+}
+
+#[test]
+fn test_image_invalid_protocol_lists_cell_modes() {
+    termgfx()
+        .args(["image", TEST_IMAGE, "--protocol", "ascii"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("quadblock, braille"));
+}
+
+fn strip_sgr(bytes: &[u8]) -> String {
+    regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&String::from_utf8_lossy(bytes), "")
+        .into_owned()
+}
+
+#[test]
+fn test_image_grid_lays_out_thumbnails_with_captions() {
+    let output = termgfx()
+        .args(["image", TEST_IMAGE, TEST_IMAGE, TEST_IMAGE])
+        .args(["--protocol", "halfblock", "--grid", "2", "--gap", "2"])
+        .args(["--width", "10", "--height", "4", "--captions"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let plain = strip_sgr(&output.stdout);
+    let lines: Vec<&str> = plain.lines().collect();
+    // Two rows of 4 lines plus a caption each, with 2 blank lines between
+    assert_eq!(lines.len(), 4 + 1 + 2 + 4 + 1);
+    assert_eq!(lines[4], " box.gif     box.gif");
+    assert!(lines[5].is_empty() && lines[6].is_empty());
+    assert_eq!(lines[11], " box.gif");
+    // Both thumbnails in a row are the same width, 2 cells apart
+    let first_row = lines[..4].iter().find(|l| l.contains('▀')).unwrap();
+    assert_eq!(first_row.chars().filter(|c| *c == '▀').count(), 20);
+    assert!(first_row.contains("▀  ▀"));
+}
+
+#[test]
+fn test_image_grid_expands_quoted_globs() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["b.gif", "a.gif"] {
+        fs::copy(TEST_IMAGE, dir.path().join(name)).unwrap();
+    }
+    fs::write(dir.path().join("notes.txt"), "not an image").unwrap();
+    let pattern = dir.path().join("*.gif");
+
+    let output = termgfx()
+        .args(["image", pattern.to_str().unwrap(), "--captions"])
+        .args(["--protocol", "braille", "--width", "8", "--height", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().last().unwrap(), " a.gif    b.gif");
+
+    termgfx()
+        .args(["image", dir.path().join("*.png").to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No images match"));
+}
+
+#[test]
+fn test_image_grid_rows_limit_images() {
+    termgfx()
+        .args(["image", TEST_IMAGE, TEST_IMAGE, TEST_IMAGE])
+        .args(["--protocol", "halfblock", "--grid", "2x1", "--width", "4"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Showing 2 of 3 images"));
+}
+
+#[test]
+fn test_image_grid_sixel_composes_rows() {
+    let output = termgfx()
+        .args(["image", TEST_IMAGE, TEST_IMAGE, "--grid", "2x1"])
+        .args(["--protocol", "sixel", "--width", "10", "--height", "5"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // One sixel image for the whole row: 2 boxes of 10 cells plus the gap
+    assert_eq!(stdout.matches("\x1bP").count(), 1);
+    assert!(stdout.contains(&format!("\"1;1;{};{}", 21 * 8, 5 * 16)));
+}
+
+#[test]
+fn test_image_invalid_grid() {
+    termgfx()
+        .args(["image", TEST_IMAGE, "--grid", "3by2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid grid '3by2'"));
+}
+
+#[test]
+fn test_table_img_cells_show_thumbnails() {
+    let rows = format!("box,img({})|missing,img(nope.png)", TEST_IMAGE);
+    let output = termgfx()
+        .env("TERM", "xterm-256color")
+        .env("COLORTERM", "truecolor")
+        .env_remove("TERM_PROGRAM")
+        .args(["table", "--headers", "Name,Preview", "--rows", &rows])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    // Half blocks in place of the path, which the missing image keeps
+    assert!(lines[3].contains("▀"), "{}", lines[3]);
+    assert!(!lines[3].contains("img("));
+    assert!(lines[4].contains("img(nope.png)"));
+}
+
+#[test]
+fn test_table_img_cells_use_kitty_graphics() {
+    let rows = format!("box,img({})", TEST_IMAGE);
+    termgfx()
+        .env("TERM", "xterm-kitty")
+        .env_remove("TERM_PROGRAM")
+        .args(["table", "--headers", "Name,Preview", "--rows", &rows])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b_Gf=100,a=T,"))
+        .stdout(predicate::str::contains("r=1,C=1,q=2"));
+}
+
+// ============================================================================
+// QR COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_qr_halfblock() {
+    let output = termgfx()
+        .args(["qr", "https://example.com"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 25 modules plus a 4-module quiet zone on each side
+    assert_eq!(lines.len(), 17);
+    assert!(lines.iter().all(|l| l.chars().count() == 33));
+    assert!(lines[0].chars().all(|c| c == '█'));
+}
+
+#[test]
+fn test_qr_invert_and_size() {
+    let output = termgfx()
+        .args(["qr", "hi", "--invert", "--size", "2"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 29);
+    assert_eq!(lines[0].chars().count(), 58);
+    assert!(lines[0].trim().is_empty());
+}
+
+#[test]
+fn test_qr_stdin() {
+    let from_arg = termgfx().args(["qr", "hello"]).output().unwrap();
+    termgfx()
+        .args(["qr", "-"])
+        .write_stdin("hello\n")
+        .assert()
+        .success()
+        .stdout(String::from_utf8(from_arg.stdout).unwrap());
+}
+
+#[test]
+fn test_qr_kitty() {
+    termgfx()
+        .args(["qr", "hi", "--protocol", "kitty"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("\x1b_Gf=100,a=T,c=29,r=15,"));
+}
+
+#[test]
+fn test_qr_invalid_error_correction() {
+    termgfx()
+        .args(["qr", "hi", "--error-correction", "X"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid error correction 'X' (use L, M, Q, H)",
+        ));
+}
+
+#[test]
+fn test_qr_too_long() {
+    termgfx()
+        .args(["qr", &"x".repeat(5000), "-e", "H"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Text too long for a QR code"));
+}
+
+// ============================================================================
+// BARCODE COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_barcode_code128() {
+    let output = termgfx().args(["barcode", "123456789"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    // Four lines of bars and the text centered underneath
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("██████████  █ ██"));
+    assert_eq!(lines[4].trim(), "123456789");
+}
+
+#[test]
+fn test_barcode_ean13_adds_check_digit() {
+    termgfx()
+        .args([
+            "barcode",
+            "590123412345",
+            "--symbology",
+            "ean13",
+            "--height",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("5 901234 123457"));
+}
+
+#[test]
+fn test_barcode_invert_no_text() {
+    let output = termgfx()
+        .args(["barcode", "96385074", "-y", "ean8", "--invert", "--no-text"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 4);
+    assert!(stdout.starts_with("          █ █"));
+}
+
+#[test]
+fn test_barcode_bad_check_digit() {
+    termgfx()
+        .args(["barcode", "5901234123450", "-y", "ean13"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid EAN-13 check digit 0 (expected 7)",
+        ));
+}
+
+#[test]
+fn test_barcode_invalid_symbology() {
+    termgfx()
+        .args(["barcode", "x", "-y", "upc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid symbology 'upc' (use code128, ean13, ean8)",
+        ));
+}
+
 // ============================================================================
 // RECORD COMMAND TESTS
 // ============================================================================
@@ -84,7 +558,67 @@ fn test_record_export_help() {
         .success()
         .stdout(predicate::str::is_match("(?i)input").unwrap())
         .stdout(predicate::str::is_match("(?i)format").unwrap())
-        .stdout(predicate::str::is_match("(?i)output").unwrap());
+        .stdout(predicate::str::is_match("(?i)output").unwrap())
+        .stdout(predicate::str::is_match("(?m)^Example: .*mac$").unwrap())
+        .stdout(predicate::str::is_match("(?m)^Example: .*00:45$").unwrap());
+}
+
+#[test]
+fn test_record_export_errors_use_error_prefix() {
+    let file = chapter_recording();
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "mp4", "out.mp4"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with(
+            "Error: Invalid format 'mp4' (use gif, apng, webm, cast, json)",
+        ))
+        .stderr(predicate::str::contains("❌").not());
+
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", "out.cast", "--fps", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with(
+            "Error: --fps must be at least 1",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_record_export_webm_failure_keeps_existing_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let file = chapter_recording();
+    let dir = tempfile::tempdir().unwrap();
+    // An ffmpeg that writes part of its output and then fails
+    let ffmpeg = dir.path().join("ffmpeg");
+    fs::write(
+        &ffmpeg,
+        "#!/bin/sh\ncat >/dev/null\nfor last; do :; done\necho partial > \"$last\"\nexit 1\n",
+    )
+    .unwrap();
+    fs::set_permissions(&ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+    let out = dir.path().join("demo.webm");
+    fs::write(&out, "old").unwrap();
+
+    termgfx()
+        .env("PATH", dir.path())
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "webm", out.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ffmpeg could not encode"));
+
+    assert_eq!(fs::read_to_string(&out).unwrap(), "old");
+    let mut names: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["demo.webm", "ffmpeg"]);
 }
 
 #[test]
@@ -95,6 +629,312 @@ fn test_record_play_nonexistent_file() {
         .failure();
 }
 
+fn chapter_recording() -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(
+        file,
+        r#"{{"version":1,"width":20,"height":5,"title":"Demo","author":"Ada",
+"events":[
+{{"time":0.0,"event_type":"o","data":"intro\n"}},
+{{"time":0.1,"event_type":"m","data":"Build"}},
+{{"time":0.2,"event_type":"o","data":"building\n"}},
+{{"time":0.3,"event_type":"m","data":"Test"}},
+{{"time":0.4,"event_type":"o","data":"testing\n"}}]}}"#
+    )
+    .unwrap();
+    file
+}
+
+#[test]
+fn test_record_play_shows_metadata_and_chapters() {
+    let file = chapter_recording();
+    termgfx()
+        .args(["record", "play", file.path().to_str().unwrap(), "-s", "100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Title: Demo"))
+        .stdout(predicate::str::contains("Author: Ada"))
+        .stdout(predicate::str::contains("1. Build"))
+        .stdout(predicate::str::contains("2. Test"))
+        .stdout(predicate::str::contains("intro"))
+        .stdout(predicate::str::contains("testing"));
+}
+
+#[test]
+fn test_record_play_from_stdin() {
+    let file = chapter_recording();
+    termgfx()
+        .args(["record", "play", "-", "-s", "100"])
+        .pipe_stdin(file.path())
+        .unwrap()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Title: Demo"))
+        .stdout(predicate::str::contains("testing"));
+}
+
+#[test]
+fn test_record_play_from_chapter() {
+    let file = chapter_recording();
+    termgfx()
+        .args([
+            "record",
+            "play",
+            file.path().to_str().unwrap(),
+            "--chapter",
+            "test",
+            "-s",
+            "100",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("testing"));
+
+    termgfx()
+        .args([
+            "record",
+            "play",
+            file.path().to_str().unwrap(),
+            "--chapter",
+            "9",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Chapter not found"));
+}
+
+#[test]
+fn test_record_mark_outside_recording_fails() {
+    termgfx()
+        .args(["record", "mark", "step 2"])
+        .env_remove("TERMGFX_RECORD_MARKS")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not inside a termgfx recording"));
+}
+
+#[test]
+fn test_record_mark_appends_to_marks_file() {
+    let marks = NamedTempFile::new().unwrap();
+    termgfx()
+        .args(["record", "mark", "step 2"])
+        .env("TERMGFX_RECORD_MARKS", marks.path())
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(marks.path()).unwrap();
+    assert!(content.ends_with("\tstep 2\n"));
+}
+
+#[test]
+fn test_record_command_writes_asciicast() {
+    let dir = tempfile::tempdir().unwrap();
+    let cast = dir.path().join("demo.cast");
+    termgfx()
+        .args(["record", "start", cast.to_str().unwrap(), "--title", "Demo"])
+        .args(["--command", "printf 'hello\\n'"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello"))
+        .stderr(predicate::str::contains("Recording saved"));
+
+    let content = fs::read_to_string(&cast).unwrap();
+    let mut lines = content.lines();
+    let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(header["version"], 2);
+    assert_eq!(header["title"], "Demo");
+    let event: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(event[1], "o");
+    // The pty turns \n into \r\n, as asciinema records it
+    assert_eq!(event[2], "hello\r\n");
+}
+
+#[test]
+fn test_record_legacy_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("demo.json");
+    termgfx()
+        .args([
+            "record",
+            "start",
+            file.to_str().unwrap(),
+            "--format",
+            "legacy",
+        ])
+        .args(["--command", "echo hi"])
+        .assert()
+        .success();
+
+    let recording: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+    assert_eq!(recording["version"], 1);
+    assert_eq!(recording["events"][0]["event_type"], "o");
+}
+
+#[test]
+fn test_record_play_asciicast_with_idle_limit() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, r#"{{"version": 2, "width": 20, "height": 5}}"#).unwrap();
+    writeln!(file, r#"[0.1, "o", "first\r\n"]"#).unwrap();
+    writeln!(file, r#"[30.0, "o", "second\r\n"]"#).unwrap();
+
+    let start = std::time::Instant::now();
+    termgfx()
+        .args(["record", "play", file.path().to_str().unwrap()])
+        .args(["--idle-limit", "0.2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Duration: 0.30s"))
+        .stdout(predicate::str::contains("second"));
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+}
+
+#[test]
+fn test_record_export_legacy_to_cast() {
+    let file = chapter_recording();
+    let dir = tempfile::tempdir().unwrap();
+    let cast = dir.path().join("out.cast");
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", cast.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&cast).unwrap();
+    assert!(content.starts_with(r#"{"version":2,"width":20,"height":5,"title":"Demo""#));
+    assert!(content.contains(r#"[0.1,"m","Build"]"#));
+}
+
+#[test]
+fn test_record_export_gif_and_apng() {
+    let file = chapter_recording();
+    let dir = tempfile::tempdir().unwrap();
+    let gif = dir.path().join("out.gif");
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "gif", gif.to_str().unwrap(), "--fps", "10"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GIF exported"));
+    assert!(fs::read(&gif).unwrap().starts_with(b"GIF89a"));
+
+    let apng = dir.path().join("out.png");
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args([
+            "--format",
+            "apng",
+            apng.to_str().unwrap(),
+            "--chrome",
+            "mac",
+        ])
+        .assert()
+        .success();
+    let bytes = fs::read(&apng).unwrap();
+    assert!(bytes.starts_with(b"\x89PNG"));
+    assert!(bytes.windows(4).any(|w| w == b"acTL"));
+}
+
+#[test]
+fn test_record_export_time_range() {
+    let file = chapter_recording();
+    let dir = tempfile::tempdir().unwrap();
+    let cast = dir.path().join("clip.cast");
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", cast.to_str().unwrap()])
+        .args(["--from", "0.15", "--to", "0:00.35"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&cast).unwrap();
+    // Earlier output replays at the start; the Build marker falls outside
+    assert!(content.contains(r#"[0.0,"o","intro\n"]"#));
+    assert!(content.contains(r#""o","building\n"]"#));
+    assert!(content.contains(r#""m","Test"]"#));
+    assert!(!content.contains("Build"));
+    assert!(!content.contains("testing"));
+
+    let gif = dir.path().join("clip.gif");
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args([
+            "--format",
+            "gif",
+            gif.to_str().unwrap(),
+            "--from",
+            "00:00.2",
+        ])
+        .assert()
+        .success();
+    assert!(fs::read(&gif).unwrap().starts_with(b"GIF89a"));
+}
+
+#[test]
+fn test_record_export_resamples_cast() {
+    let file = chapter_recording();
+    let dir = tempfile::tempdir().unwrap();
+    let cast = dir.path().join("slow.cast");
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", cast.to_str().unwrap(), "--fps", "2"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&cast).unwrap();
+    assert!(content.contains(r#"[0.0,"o","intro\n"]"#));
+    assert!(content.contains(r#"[0.5,"m","Build"]"#));
+    assert!(content.contains(r#"[0.5,"o","testing\n"]"#));
+}
+
+#[test]
+fn test_record_export_rejects_bad_options() {
+    let file = chapter_recording();
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "gif", "out.gif", "--chrome", "win95"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid chrome 'win95' (use none, mac)",
+        ));
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "gif", "out.gif", "--fps", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--fps must be between 1 and 50"));
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", "out.cast", "--from", "1:75"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid time '1:75'"));
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", "out.cast", "--from", "30", "--to", "10"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--to must be after --from"));
+    termgfx()
+        .args(["record", "export", file.path().to_str().unwrap()])
+        .args(["--format", "cast", "out.cast", "--from", "5:00"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("past the end of the recording"));
+}
+
+#[test]
+fn test_record_invalid_format() {
+    termgfx()
+        .args(["record", "start", "x.cast", "--format", "mp4"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid format 'mp4' (use cast, legacy)",
+        ));
+}
+
 // ============================================================================
 // SCRIPT COMMAND TESTS
 // ============================================================================
@@ -198,6 +1038,110 @@ fn test_script_unknown_command() {
         .stderr(predicate::str::contains("Unknown command"));
 }
 
+#[test]
+fn test_script_variables_and_loop() {
+    termgfx()
+        .args([
+            "script",
+            "--inline",
+            "set name = World; loop 2 { box \"Hi $name #$i\" }",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Hi World #1"))
+        .stdout(predicate::str::contains("Hi World #2"));
+}
+
+#[test]
+fn test_script_if_env() {
+    let script = "if env TERMGFX_DEMO { box on } else { box off }";
+    termgfx()
+        .args(["script", "--inline", script])
+        .env("TERMGFX_DEMO", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("on"))
+        .stdout(predicate::str::contains("off").not());
+    termgfx()
+        .args(["script", "--inline", script])
+        .env_remove("TERMGFX_DEMO")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("off"));
+}
+
+#[test]
+fn test_script_sleep_and_clear() {
+    termgfx()
+        .args(["script", "--inline", "sleep 10ms; clear; box done"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("done"))
+        .stdout(predicate::str::contains("\x1b[2J").not());
+}
+
+#[test]
+fn test_script_runs_any_component() {
+    termgfx()
+        .args(["script", "--inline", "gauge 42 label:CPU"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CPU"))
+        .stdout(predicate::str::contains("42"));
+}
+
+#[test]
+fn test_script_component_failure() {
+    termgfx()
+        .args(["script", "--inline", "gauge abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'gauge' failed"));
+}
+
+#[test]
+fn test_script_cannot_run_itself() {
+    let mut file = NamedTempFile::new().unwrap();
+    let path = file.path().display().to_string();
+    writeln!(file, "script file:{}", path).unwrap();
+    termgfx()
+        .args(["script", "--file", file.path().to_str().unwrap()])
+        .timeout(std::time::Duration::from_secs(10))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "line 1: 'script' can't run inside a script",
+        ));
+    termgfx()
+        .args(["script", "--inline", "watch date"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'watch' can't run inside a script",
+        ));
+}
+
+#[test]
+fn test_script_parse_errors() {
+    termgfx()
+        .args(["script", "--inline", "loop 2 {\nbox a"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("line 1: block is never closed"));
+    termgfx()
+        .args(["script", "--inline", "loop many { box a }"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid loop count 'many'"));
+    termgfx()
+        .args(["script", "--inline", "box $undefined_var"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "undefined variable 'undefined_var'",
+        ));
+}
+
 #[test]
 fn test_script_no_file_or_inline() {
     termgfx()
@@ -217,3 +1161,42 @@ fn test_script_nonexistent_file() {
         .failure()
         .stderr(predicate::str::contains("Failed to read"));
 }
+
+#[test]
+fn test_script_watch_file_requires_file() {
+    termgfx()
+        .args(["script", "--inline", "box hi", "--watch-file"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--file"));
+}
+
+#[test]
+fn test_script_watch_file_reruns_on_change() {
+    use std::io::Read;
+    use std::process::{Command as StdCommand, Stdio};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("demo.tgs");
+    fs::write(&path, "box \"first run\"\n").unwrap();
+
+    let mut child = StdCommand::new(assert_cmd::cargo::cargo_bin("termgfx"))
+        .args(["script", "--file", path.to_str().unwrap(), "--watch-file"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    fs::write(&path, "box \"second run\"\n").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let mut stdout = String::new();
+    child.stdout.unwrap().read_to_string(&mut stdout).unwrap();
+    let mut stderr = String::new();
+    child.stderr.unwrap().read_to_string(&mut stderr).unwrap();
+    assert!(stdout.contains("first run"));
+    assert!(stdout.contains("second run"));
+    assert_eq!(stderr.matches("Watching").count(), 2);
+}
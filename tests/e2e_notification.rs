@@ -108,3 +108,47 @@ fn test_notification_missing_message() {
         .failure()
         .stderr(predicate::str::contains("required"));
 }
+
+#[test]
+fn test_notification_progress_bar() {
+    termgfx()
+        .args([
+            "notification",
+            "Building",
+            "--terminal-only",
+            "--progress",
+            "40",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("░  40%"));
+}
+
+#[test]
+fn test_notification_progress_out_of_range() {
+    termgfx()
+        .args(["notification", "Building", "--progress", "140"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("140"));
+}
+
+#[test]
+fn test_notification_invalid_action() {
+    termgfx()
+        .args(["notification", "Done", "--action", "Open"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid action 'Open' (use LABEL:COMMAND)",
+        ));
+}
+
+#[test]
+fn test_notification_invalid_timeout() {
+    termgfx()
+        .args(["notification", "Done", "--timeout", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid timeout 'soon'"));
+}
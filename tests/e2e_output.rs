@@ -1,6 +1,8 @@
 #![allow(deprecated)]
 use assert_cmd::Command;
 use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
 
 fn termgfx() -> Command {
     Command::cargo_bin("termgfx").unwrap()
@@ -91,6 +93,115 @@ fn test_box_border_rounded() {
         .stdout(predicate::str::contains("Rounded border"));
 }
 
+#[test]
+fn test_box_reads_stdin() {
+    termgfx()
+        .args(["box", "--style", "plain"])
+        .write_stdin("first line\nsecond line\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first line"))
+        .stdout(predicate::str::contains("second line"));
+}
+
+#[test]
+fn test_box_stdin_keeps_ansi_and_aligns_borders() {
+    let output = termgfx()
+        .env("CLICOLOR_FORCE", "1")
+        .args(["box", "--stdin", "--style", "plain", "--title", "Sales"])
+        .write_stdin("\x1b[31mred bar\x1b[0m\nplain row that is longer\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\x1b[31mred bar\x1b[0m"));
+
+    let plain = regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&stdout, "")
+        .to_string();
+    let widths: Vec<usize> = plain.lines().map(|l| l.chars().count()).collect();
+    assert!(widths.iter().all(|w| *w == widths[0]), "{:?}", widths);
+}
+
+#[test]
+fn test_box_stdin_conflicts_with_message() {
+    termgfx().args(["box", "hi", "--stdin"]).assert().failure();
+}
+
+#[test]
+fn test_box_reads_file() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, b"from a file\n").unwrap();
+    termgfx()
+        .args(["box", "--file", file.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from a file"));
+}
+
+#[test]
+fn test_box_wraps_to_width() {
+    let output = termgfx()
+        .args([
+            "box",
+            "alpha beta gamma delta epsilon",
+            "--width",
+            "16",
+            "--style",
+            "plain",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let plain = regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(&stdout, "")
+        .to_string();
+    assert!(plain.lines().count() > 3);
+    for line in plain.lines() {
+        assert!(line.chars().count() <= 16, "line too wide: {:?}", line);
+    }
+}
+
+#[test]
+fn test_box_title_in_border() {
+    termgfx()
+        .args(["box", "body", "--title", "Deploy"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(" Deploy "));
+}
+
+#[test]
+fn test_box_footer_right_aligned() {
+    termgfx()
+        .args([
+            "box",
+            "body",
+            "--title",
+            "Deploy",
+            "--footer",
+            "v1.2.3",
+            "--title-align",
+            "right",
+            "--style",
+            "plain",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("─ Deploy ─╮"))
+        .stdout(predicate::str::contains("─ v1.2.3 ─╯"));
+}
+
+#[test]
+fn test_box_message_conflicts_with_file() {
+    termgfx()
+        .args(["box", "hi", "--file", "notes.txt"])
+        .assert()
+        .failure();
+}
+
 // ============================================================================
 // BANNER COMMAND TESTS
 // ============================================================================
@@ -122,6 +233,192 @@ fn test_banner_single_char() {
         .stdout(predicate::str::is_empty().not());
 }
 
+#[test]
+fn test_banner_align_center() {
+    termgfx()
+        .args(["banner", "HI", "--align", "center", "--max-width", "20"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("   "));
+}
+
+#[test]
+fn test_banner_max_width_wraps_text() {
+    // Three words at 14 columns wrap onto three rows between two padding rows
+    termgfx()
+        .args(["banner", "Hello Wide World", "--max-width", "14"])
+        .assert()
+        .success()
+        .stdout(predicate::function(|out: &str| out.lines().count() == 7));
+}
+
+#[test]
+fn test_banner_max_width_narrower_than_frame() {
+    termgfx()
+        .args(["banner", "HI", "--max-width", "3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-width 3 is too narrow"));
+}
+
+#[test]
+fn test_banner_font_shrinks_to_max_width() {
+    // Block letters don't fit 16 columns; the half-block small font does
+    termgfx()
+        .args(["banner", "HI", "--font", "block", "--max-width", "16"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("▀"))
+        .stdout(predicate::function(|out: &str| out.lines().count() == 7));
+}
+
+#[test]
+fn test_banner_invalid_align() {
+    termgfx()
+        .args(["banner", "HI", "--align", "diagonal"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid alignment"));
+}
+
+#[test]
+fn test_banner_builtin_fonts() {
+    for font in ["block", "slant", "small", "3d"] {
+        termgfx()
+            .args(["banner", "Hi!", "--font", font])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("█"));
+    }
+}
+
+#[test]
+fn test_banner_small_font_height() {
+    // Border, padding, three half-block rows, padding, border
+    termgfx()
+        .args(["banner", "HI", "--font", "small"])
+        .assert()
+        .success()
+        .stdout(predicate::function(|out: &str| out.lines().count() == 7));
+}
+
+#[test]
+fn test_banner_figlet_font_file() {
+    let mut flf = NamedTempFile::with_suffix(".flf").unwrap();
+    writeln!(flf, "flf2a$ 1 1 3 0 0").unwrap();
+    for code in 32u8..=126 {
+        let c = if code == b' ' { '$' } else { code as char };
+        writeln!(flf, "<{}>@@", c).unwrap();
+    }
+    termgfx()
+        .args(["banner", "ok", "--font", flf.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<"))
+        .stdout(predicate::str::contains(">"));
+}
+
+#[test]
+fn test_banner_unknown_font() {
+    termgfx()
+        .args(["banner", "HI", "--font", "gothic"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown font 'gothic'"));
+}
+
+// ============================================================================
+// TEXT COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_text_plain() {
+    termgfx()
+        .args(["text", "hello"])
+        .assert()
+        .success()
+        .stdout("hello\n");
+}
+
+#[test]
+fn test_text_gradient_colors_each_character() {
+    termgfx()
+        .env("CLICOLOR_FORCE", "1")
+        .args(["text", "ab", "--gradient", "red-orange"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[91ma"))
+        .stdout(predicate::str::contains("\x1b[93mb"));
+}
+
+#[test]
+fn test_text_rainbow_with_attributes() {
+    termgfx()
+        .env("CLICOLOR_FORCE", "1")
+        .args([
+            "text",
+            "Hi",
+            "--rainbow",
+            "--bold",
+            "--italic",
+            "--underline",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("\x1b[1;3;4m"))
+        .stdout(predicate::str::contains("\x1b[95mi"));
+}
+
+#[test]
+fn test_text_letter_spacing() {
+    termgfx()
+        .args(["text", "abc", "--spacing", "2"])
+        .assert()
+        .success()
+        .stdout("a  b  c\n");
+}
+
+#[test]
+fn test_text_invalid_gradient() {
+    termgfx()
+        .args(["text", "hi", "--gradient", "plaid"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid gradient 'plaid'"));
+}
+
+#[test]
+fn test_text_rainbow_conflicts_with_gradient() {
+    termgfx()
+        .args(["text", "hi", "--rainbow", "--gradient", "red-orange"])
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// FOOTER COMMAND TESTS
+// ============================================================================
+
+#[test]
+fn test_footer_passes_output_through_when_piped() {
+    termgfx()
+        .args(["footer", "--command", "echo one; echo two >&2"])
+        .assert()
+        .success()
+        .stdout("one\n")
+        .stderr(predicate::str::contains("two"))
+        .stderr(predicate::str::contains("finished in"));
+}
+
+#[test]
+fn test_footer_exits_with_command_status() {
+    termgfx()
+        .args(["footer", "--command", "exit 3"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("exited with 3"));
+}
+
 // ============================================================================
 // PROGRESS COMMAND TESTS
 // ============================================================================
@@ -135,6 +432,35 @@ fn test_progress_basic() {
         .stdout(predicate::str::contains("50%"));
 }
 
+#[test]
+fn test_progress_inline_zsh_escapes() {
+    termgfx()
+        .args(["progress", "50", "--inline", "--shell", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("%{\x1b["))
+        .stdout(predicate::str::contains("50%%"))
+        .stdout(predicate::str::contains("\n").not())
+        .stdout(predicate::str::contains("\r").not());
+}
+
+#[test]
+fn test_progress_inline_rejects_unknown_shell() {
+    termgfx()
+        .args(["progress", "50", "--inline", "--shell", "fish"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown shell"));
+}
+
+#[test]
+fn test_progress_shell_requires_inline() {
+    termgfx()
+        .args(["progress", "50", "--shell", "zsh"])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn test_progress_zero() {
     termgfx()
@@ -354,3 +680,180 @@ fn test_sparkline_many_values() {
         .success()
         .stdout(predicate::str::is_empty().not());
 }
+
+// ============================================================================
+// MARKDOWN TESTS
+// ============================================================================
+
+#[test]
+fn test_md_renders_from_stdin() {
+    termgfx()
+        .arg("md")
+        .write_stdin("## Install\n\n- run `cargo install termgfx`\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Install"))
+        .stdout(predicate::str::contains("•"))
+        .stdout(predicate::str::contains("cargo install termgfx"))
+        .stdout(predicate::str::contains("`").not());
+}
+
+#[test]
+fn test_md_renders_file_with_code_and_table() {
+    let path = std::env::temp_dir().join("termgfx_md_test.md");
+    std::fs::write(
+        &path,
+        "```sh\necho hi\n```\n\n| Key | Value |\n|-----|-------|\n| a | 1 |\n",
+    )
+    .unwrap();
+    termgfx()
+        .args(["md", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("╭─"))
+        .stdout(predicate::str::contains("sh"))
+        .stdout(predicate::str::contains("┌"))
+        .stdout(predicate::str::contains("Value"));
+}
+
+#[test]
+fn test_md_missing_file() {
+    termgfx()
+        .args(["md", "/nonexistent/README.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error"));
+}
+
+// ============================================================================
+// CODE VIEWER TESTS
+// ============================================================================
+
+#[test]
+fn test_code_renders_file_with_line_numbers() {
+    let path = std::env::temp_dir().join("termgfx_code_test.py");
+    std::fs::write(&path, "import os\n\ndef main():\n    print('hi')\n").unwrap();
+    termgfx()
+        .args(["code", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("termgfx_code_test.py"))
+        .stdout(predicate::str::contains("╭─"))
+        .stdout(predicate::str::contains("print"))
+        .stdout(predicate::str::contains("'hi'"));
+}
+
+#[test]
+fn test_code_line_range_from_stdin() {
+    termgfx()
+        .args(["code", "-", "--lines", "2-3", "--lang", "rust"])
+        .write_stdin("// one\nlet two = 2;\nlet three = 3;\nlet four = 4;\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<stdin>:2-3"))
+        .stdout(predicate::str::contains("three"))
+        .stdout(predicate::str::contains("four").not())
+        .stdout(predicate::str::contains("one").not());
+}
+
+#[test]
+fn test_code_and_pager_share_lang_flag() {
+    termgfx()
+        .args(["code", "-", "--language", "rust"])
+        .write_stdin("let x = 1;\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("let"));
+    termgfx()
+        .args(["pager", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--lang <LANGUAGE>"))
+        .stdout(predicate::str::contains("[aliases: --language]"));
+    termgfx()
+        .args(["pager", "--lang", "rust", "--markdown"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_code_invalid_range() {
+    termgfx()
+        .args(["code", "-", "--lines", "9-2"])
+        .write_stdin("a\nb\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid line range"));
+}
+
+#[test]
+fn test_code_missing_file() {
+    termgfx()
+        .args(["code", "/nonexistent/main.rs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error"));
+}
+
+// ============================================================================
+// EMOJI WIDTH TESTS
+// ============================================================================
+
+#[test]
+fn test_emoji_widths_set_saves_override() {
+    let config = tempfile::tempdir().unwrap();
+    termgfx()
+        .env("XDG_CONFIG_HOME", config.path())
+        .args(["emoji-widths", "--set", "❤️=1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved"))
+        .stdout(predicate::str::contains("❤️"));
+
+    let saved = std::fs::read_to_string(config.path().join("termgfx/emoji_widths.json")).unwrap();
+    assert!(saved.contains("\"❤️\": 1"));
+}
+
+#[test]
+fn test_box_uses_emoji_width_override() {
+    let config = tempfile::tempdir().unwrap();
+    let top_border = |output: &[u8]| {
+        String::from_utf8_lossy(output)
+            .lines()
+            .next()
+            .unwrap()
+            .matches('─')
+            .count()
+    };
+
+    let default = termgfx()
+        .env("XDG_CONFIG_HOME", config.path())
+        .args(["box", "hi ❤️"])
+        .output()
+        .unwrap();
+    termgfx()
+        .env("XDG_CONFIG_HOME", config.path())
+        .args(["emoji-widths", "--set", "❤️=1"])
+        .assert()
+        .success();
+    let overridden = termgfx()
+        .env("XDG_CONFIG_HOME", config.path())
+        .args(["box", "hi ❤️"])
+        .output()
+        .unwrap();
+
+    assert_eq!(
+        top_border(&overridden.stdout) + 1,
+        top_border(&default.stdout)
+    );
+}
+
+#[test]
+fn test_emoji_widths_rejects_bad_override() {
+    termgfx()
+        .args(["emoji-widths", "--set", "❤️"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("EMOJI=WIDTH"));
+}
@@ -0,0 +1,71 @@
+#![allow(deprecated)]
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn termgfx() -> Command {
+    Command::cargo_bin("termgfx").unwrap()
+}
+
+const DECK: &str = "# Welcome\n\nOpening words\n\n---\n\n## Numbers\n\n```termgfx\nsparkline 1,4,2,8\n```\n\n---\n\n```sh\n---\n```\n";
+
+// ============================================================================
+// PRESENT TESTS
+// ============================================================================
+
+#[test]
+fn test_present_prints_every_slide_when_piped() {
+    termgfx()
+        .args(["present", "-"])
+        .write_stdin(DECK)
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Opening words"))
+        .stdout(predicate::str::contains("Numbers"))
+        .stdout(predicate::str::contains("▁▄▂█"))
+        .stdout(predicate::str::contains("```termgfx").not());
+}
+
+#[test]
+fn test_present_failed_command_shows_on_slide() {
+    termgfx()
+        .args(["present", "-"])
+        .write_stdin("```termgfx\ngauge abc\nselect a b\n```\n")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✗ Command failed"))
+        .stdout(predicate::str::contains(
+            "'select' is live and can't run inside a slide",
+        ));
+}
+
+#[test]
+fn test_present_invalid_start() {
+    termgfx()
+        .args(["present", "-", "--start", "4"])
+        .write_stdin(DECK)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid slide 4 (the deck has 3)"));
+}
+
+#[test]
+fn test_present_empty_deck() {
+    termgfx()
+        .args(["present", "-"])
+        .write_stdin("\n---\n\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No slides"));
+}
+
+#[test]
+fn test_present_is_never_cached() {
+    termgfx()
+        .args(["--cache", "1m", "present", "-"])
+        .write_stdin(DECK)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'present' is live"));
+}
@@ -115,6 +115,7 @@ fn test_stats_no_color() {
 fn test_stats_percentage_coloring() {
     // Low percentage should be green (32m)
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["stats", "usage:30%"])
         .assert()
         .success()
@@ -122,6 +123,7 @@ fn test_stats_percentage_coloring() {
 
     // High percentage should be red (31m)
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["stats", "usage:95%"])
         .assert()
         .success()
@@ -132,6 +134,7 @@ fn test_stats_percentage_coloring() {
 fn test_stats_status_coloring() {
     // "ok" should be green
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["stats", "status:ok"])
         .assert()
         .success()
@@ -139,6 +142,7 @@ fn test_stats_status_coloring() {
 
     // "error" should be red
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["stats", "status:error"])
         .assert()
         .success()
@@ -149,6 +153,7 @@ fn test_stats_status_coloring() {
 fn test_stats_size_coloring() {
     // Size values should be yellow
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["stats", "size:100 MB"])
         .assert()
         .success()
@@ -159,6 +164,7 @@ fn test_stats_size_coloring() {
 fn test_stats_time_coloring() {
     // Time values should be cyan
     termgfx()
+        .env("CLICOLOR_FORCE", "1")
         .args(["stats", "modified:5m ago"])
         .assert()
         .success()
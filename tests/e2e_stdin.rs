@@ -0,0 +1,142 @@
+#![allow(deprecated)]
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+
+fn termgfx() -> Command {
+    Command::cargo_bin("termgfx").unwrap()
+}
+
+fn temp_file(name: &str, content: &[u8]) -> String {
+    let path = std::env::temp_dir().join(format!("termgfx_stdin_{}", name));
+    fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+// ============================================================================
+// DIFF
+// ============================================================================
+
+#[test]
+fn test_diff_reads_stdin_dash() {
+    let file = temp_file("diff_b.txt", b"alpha\nbeta\n");
+    termgfx()
+        .args(["diff", "-", &file, "--unified"])
+        .write_stdin("alpha\ngamma\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<stdin>"))
+        .stdout(predicate::str::contains("gamma"));
+}
+
+#[test]
+fn test_diff_rejects_double_stdin() {
+    termgfx()
+        .args(["diff", "-", "-"])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only be used for one input"));
+}
+
+#[test]
+fn test_diff_binary_files() {
+    let a = temp_file("diff_a.bin", b"\x89PNG\0\x01");
+    let b = temp_file("diff_c.bin", b"\x89PNG\0\x02");
+    termgfx()
+        .args(["diff", &a, &b])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Binary files"))
+        .stdout(predicate::str::contains("differ"));
+}
+
+// ============================================================================
+// TABLE / HEATMAP / CALENDAR
+// ============================================================================
+
+#[test]
+fn test_table_file_dash_reads_csv_from_stdin() {
+    termgfx()
+        .args(["table", "--file", "-"])
+        .write_stdin("Name,Age\nAlice,30\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Alice"));
+}
+
+#[test]
+fn test_table_file_rejects_binary() {
+    termgfx()
+        .args(["table", "--file", "-"])
+        .write_stdin(&b"Name\0Age"[..])
+        .assert()
+        .stderr(predicate::str::contains("binary"));
+}
+
+#[test]
+fn test_heatmap_file_dash_reads_stdin() {
+    termgfx()
+        .args(["heatmap", "--file", "-"])
+        .write_stdin("1,2,3\n4,5,6\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty().not());
+}
+
+#[test]
+fn test_calendar_file_dash_reads_stdin() {
+    termgfx()
+        .args(["calendar", "--file", "-"])
+        .write_stdin("2024-01-01:3\n2024-01-15:5\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Jan"));
+}
+
+// ============================================================================
+// TREE
+// ============================================================================
+
+#[test]
+fn test_tree_path_dash_reads_json_stdin() {
+    termgfx()
+        .args(["tree", "--path", "-"])
+        .write_stdin(r#"{"src": {"main.rs": null}}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("main.rs"));
+}
+
+#[test]
+fn test_tree_path_reads_json_file() {
+    let file = temp_file("tree.json", br#"{"docs": {"guide.md": null}}"#);
+    termgfx()
+        .args(["tree", "--path", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("guide.md"));
+}
+
+// ============================================================================
+// IMAGE
+// ============================================================================
+
+#[test]
+fn test_image_stdin_flag_rejects_non_image() {
+    termgfx()
+        .args(["image", "--stdin"])
+        .write_stdin("not an image")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error"));
+}
+
+#[test]
+fn test_image_requires_path_or_stdin() {
+    termgfx()
+        .arg("image")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("required"));
+}
@@ -2,6 +2,8 @@
 
 use assert_cmd::Command;
 use predicates::prelude::*;
+use termgfx::interactive::repl::split_args;
+use termgfx::interactive::studio::registry::{get_all_components, ParamType};
 
 #[allow(deprecated)]
 fn cmd() -> Command {
@@ -230,6 +232,267 @@ fn test_studio_help_shows_favorite_keys() {
         .stdout(predicate::str::contains("Delete"));
 }
 
+#[test]
+fn test_studio_help_shows_animation_playback() {
+    cmd()
+        .arg("studio")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Play/pause animation"));
+}
+
+#[test]
+fn test_studio_help_shows_favorite_shortcuts() {
+    cmd()
+        .arg("studio")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ctrl+S"))
+        .stdout(predicate::str::contains("Browse and load favorites"));
+}
+
+#[test]
+fn test_studio_help_shows_run_command() {
+    cmd()
+        .arg("studio")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Run the command for real"));
+}
+
+#[test]
+fn test_studio_help_shows_export() {
+    cmd()
+        .arg("studio")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Export preview"))
+        .stdout(predicate::str::contains("Cycle export format"));
+}
+
+#[test]
+fn test_studio_help_shows_theme_editor() {
+    cmd()
+        .arg("studio")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Edit the theme"));
+}
+
+// ============================================================================
+// registry drift tests
+// ============================================================================
+
+/// Commands the studio doesn't offer, as listed in the registry module doc
+const NOT_COMPONENTS: &[&str] = &[
+    // Prompts and apps that take over the terminal
+    "input",
+    "select",
+    "confirm",
+    "write",
+    "file",
+    "filter",
+    "emojipick",
+    "form",
+    "wizard",
+    "keys",
+    "stopwatch",
+    "pager",
+    "json",
+    "present",
+    "dashboard",
+    "watch",
+    "footer",
+    "tui",
+    "repl",
+    "playground",
+    "studio",
+    // Only lays out piped text
+    "columns",
+    // Tooling that doesn't draw a component
+    "record",
+    "script",
+    "demo",
+    "style",
+    "palette",
+    "theme",
+    "export",
+    "ci-comment",
+    "emoji-widths",
+    "help",
+];
+
+/// Flags every command takes, which the studio leaves alone
+const GLOBAL_FLAGS: &[&str] = &[
+    "cache",
+    "color",
+    "color-depth",
+    "export",
+    "theme",
+    "log-level",
+    "log-file",
+    "no-animation",
+    "demo",
+    "help",
+    "version",
+];
+
+/// Flags that read stdin or wait for keys, which a preview can't give them
+const NOT_PARAMS: &[&str] = &["stdin", "interactive", "follow"];
+
+fn help(command: &str) -> String {
+    let output = cmd()
+        .args(command.split_whitespace())
+        .arg("--help")
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// The long flags listed in a `--help` text
+fn help_flags(help: &str) -> Vec<String> {
+    help.lines()
+        .map(str::trim_start)
+        .filter_map(|line| {
+            let line = line.split_once(", ").map_or(line, |(short, rest)| {
+                if short.len() == 2 && short.starts_with('-') {
+                    rest
+                } else {
+                    line
+                }
+            });
+            let flag = line.strip_prefix("--")?;
+            Some(flag.split([' ', '[']).next()?.to_string())
+        })
+        .collect()
+}
+
+/// The subcommands listed in a `--help` text
+fn help_commands(help: &str) -> Vec<String> {
+    help.lines()
+        .skip_while(|line| *line != "Commands:")
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_registry_params_match_cli() {
+    for component in get_all_components() {
+        let help = help(component.name);
+        let flags = help_flags(&help);
+        for param in &component.params {
+            if param.positional {
+                let id = param.name.to_uppercase().replace('-', "_");
+                assert!(
+                    help.contains(&format!("<{}>", id)) || help.contains(&format!("[{}]", id)),
+                    "{} has no positional {}",
+                    component.name,
+                    id
+                );
+            } else {
+                assert!(
+                    flags.iter().any(|f| f == param.name),
+                    "{} has no --{}",
+                    component.name,
+                    param.name
+                );
+            }
+            if let ParamType::Enum(options) = &param.param_type {
+                for option in options {
+                    assert!(
+                        help.contains(option),
+                        "{} --{} doesn't mention {}",
+                        component.name,
+                        param.name,
+                        option
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_registry_covers_cli() {
+    let components = get_all_components();
+    let mut commands = help_commands(&help(""));
+    commands.extend(
+        help_commands(&help("chart"))
+            .into_iter()
+            .filter(|c| c != "help")
+            .map(|c| format!("chart {}", c)),
+    );
+
+    for command in commands {
+        if command == "chart" || NOT_COMPONENTS.contains(&command.as_str()) {
+            continue;
+        }
+        let component = components
+            .iter()
+            .find(|c| c.name == command)
+            .unwrap_or_else(|| panic!("{} is missing from the studio registry", command));
+        for flag in help_flags(&help(&command)) {
+            if GLOBAL_FLAGS.contains(&flag.as_str()) || NOT_PARAMS.contains(&flag.as_str()) {
+                continue;
+            }
+            assert!(
+                component.params.iter().any(|p| p.name == flag),
+                "{} --{} is missing from the studio registry",
+                command,
+                flag
+            );
+        }
+    }
+}
+
+#[test]
+fn test_registry_exclusions_are_documented() {
+    let commands = help_commands(&help(""));
+    let components = get_all_components();
+    let doc = include_str!("../src/interactive/studio/registry.rs");
+    for name in NOT_COMPONENTS {
+        assert!(
+            commands.iter().any(|c| c == name),
+            "{} is excluded but isn't a command",
+            name
+        );
+        assert!(
+            !components.iter().any(|c| c.name == *name),
+            "{} is both excluded and in the studio registry",
+            name
+        );
+        assert!(
+            doc.lines()
+                .take_while(|line| line.starts_with("//!"))
+                .any(|line| line.contains(&format!("`{}`", name))),
+            "{} isn't listed in the registry module doc",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_registry_commands_run() {
+    // Components that draw without files, devices or a terminal
+    for name in ["box", "chart pie", "table", "tree", "join", "heatmap", "qr"] {
+        let component = get_all_components()
+            .into_iter()
+            .find(|c| c.name == name)
+            .unwrap();
+        let command = component.generate_command(&Default::default());
+        let args = split_args(&command).unwrap();
+        cmd().args(&args[1..]).assert().success();
+    }
+}
+
 // ============================================================================
 // Unit tests for registry module are in registry.rs
 // ============================================================================
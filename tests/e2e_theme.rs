@@ -274,3 +274,218 @@ fn test_theme_help_shows_presets() {
         .stdout(predicate::str::contains("nord"))
         .stdout(predicate::str::contains("dracula"));
 }
+
+// ============================================================================
+// theme file tests
+// ============================================================================
+
+fn with_theme_file(dir: &tempfile::TempDir) -> Command {
+    let mut c = cmd();
+    c.env("TERMGFX_THEME_FILE", dir.path().join("theme.toml"))
+        .env_remove("TERMGFX_THEME");
+    c
+}
+
+#[test]
+fn test_theme_set_writes_file_and_show_reads_it() {
+    let dir = tempfile::tempdir().unwrap();
+    with_theme_file(&dir)
+        .args(["theme", "set", "nord"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Theme set to"));
+
+    let saved = std::fs::read_to_string(dir.path().join("theme.toml")).unwrap();
+    assert!(saved.contains("theme = \"nord\""));
+
+    with_theme_file(&dir)
+        .args(["theme", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nord"))
+        .stdout(predicate::str::contains("green-cyan"))
+        .stdout(predicate::str::contains("theme.toml"));
+}
+
+#[test]
+fn test_theme_set_rejects_unknown_theme() {
+    let dir = tempfile::tempdir().unwrap();
+    with_theme_file(&dir)
+        .args(["theme", "set", "plaid"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Theme 'plaid' not found"));
+    assert!(!dir.path().join("theme.toml").exists());
+}
+
+#[test]
+fn test_unknown_env_theme_falls_back_with_warning() {
+    let dir = tempfile::tempdir().unwrap();
+    with_theme_file(&dir)
+        .env("TERMGFX_THEME", "plaid")
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hi"))
+        .stderr(predicate::str::contains("Warning: Theme 'plaid' not found"));
+
+    with_theme_file(&dir)
+        .env("TERMGFX_THEME", "plaid")
+        .args(["theme", "set", "nord"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Theme set to"));
+}
+
+#[test]
+fn test_broken_theme_file_is_ignored_with_warning() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("theme.toml"), "theme = [oops").unwrap();
+
+    with_theme_file(&dir)
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: Failed to parse"));
+    with_theme_file(&dir)
+        .args(["theme", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nord"));
+    with_theme_file(&dir)
+        .args(["theme", "set", "nord"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Fix or remove the theme file"));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("theme.toml")).unwrap(),
+        "theme = [oops"
+    );
+}
+
+#[test]
+fn test_user_theme_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("theme.toml"),
+        "[themes.ocean]\nbase = \"light\"\nborder_style = \"double\"\ncolors = { info = \"#112233\" }\n",
+    )
+    .unwrap();
+
+    with_theme_file(&dir)
+        .args(["theme", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("User themes"))
+        .stdout(predicate::str::contains("ocean"));
+
+    with_theme_file(&dir)
+        .env("COLORTERM", "truecolor")
+        .env("CLICOLOR_FORCE", "1")
+        .args(["--theme", "ocean", "box", "hi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("╔"))
+        .stdout(predicate::str::contains(";38;2;17;34;51m"));
+}
+
+#[test]
+fn test_theme_flag_overrides_env() {
+    let dir = tempfile::tempdir().unwrap();
+    with_theme_file(&dir)
+        .env("TERMGFX_THEME", "dracula")
+        .args(["--theme", "monokai", "theme", "current"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("monokai"));
+}
+
+#[test]
+fn test_explicit_border_beats_theme() {
+    let dir = tempfile::tempdir().unwrap();
+    with_theme_file(&dir)
+        .args(["--theme", "gruvbox", "box", "hi", "--border", "ascii"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+"))
+        .stdout(predicate::str::contains("╔").not());
+}
+
+#[test]
+fn test_invalid_theme_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    with_theme_file(&dir)
+        .args(["--theme", "bogus", "box", "hi"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Theme 'bogus' not found"));
+}
+
+#[test]
+fn test_broken_user_theme_reports_error() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("theme.toml"),
+        "theme = \"bad\"\n[themes.bad]\nborder_style = \"wavy\"\n",
+    )
+    .unwrap();
+    with_theme_file(&dir)
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: Invalid theme 'bad'"))
+        .stderr(predicate::str::contains("unknown border style 'wavy'"));
+}
+
+#[test]
+fn test_theme_series_colors() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("theme.toml"),
+        "theme = \"mono\"\n[themes.mono]\nseries_colors = \"#112233, #445566\"\n",
+    )
+    .unwrap();
+
+    with_theme_file(&dir)
+        .args(["theme", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("series"))
+        .stdout(predicate::str::contains("#112233, #445566"));
+
+    with_theme_file(&dir)
+        .env("COLORTERM", "truecolor")
+        .env("CLICOLOR_FORCE", "1")
+        .args(["chart", "pie", "--data", "api:60,db:40"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[38;2;17;34;51"))
+        .stdout(predicate::str::contains("\x1b[38;2;68;85;102"));
+
+    // The environment wins over the theme
+    with_theme_file(&dir)
+        .env("COLORTERM", "truecolor")
+        .env("CLICOLOR_FORCE", "1")
+        .env("TERMGFX_SERIES_COLORS", "#AABBCC")
+        .args(["chart", "pie", "--data", "api:60,db:40"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[38;2;170;187;204"))
+        .stdout(predicate::str::contains("\x1b[38;2;17;34;51").not());
+}
+
+#[test]
+fn test_invalid_series_colors_in_theme() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("theme.toml"),
+        "theme = \"bad\"\n[themes.bad]\nseries_colors = \"rainbow\"\n",
+    )
+    .unwrap();
+    with_theme_file(&dir)
+        .args(["box", "hi"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: Invalid theme 'bad'"))
+        .stderr(predicate::str::contains("series_colors: 'rainbow'"));
+}
@@ -45,6 +45,37 @@ fn test_timeline_with_colors() {
     cmd.assert().success();
 }
 
+#[test]
+fn test_timeline_with_accent() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args([
+        "timeline",
+        "--events",
+        "Start,Middle,End",
+        "--accent",
+        "blue",
+    ]);
+    cmd.assert().success().stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_timeline_color_name_is_deprecated_accent() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["timeline", "--events", "Start,End", "--color", "blue"]);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("deprecated"));
+}
+
+#[test]
+fn test_timeline_rejects_unknown_color_mode_elsewhere() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["sparkline", "1,2,3", "--color", "blue"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid color mode"));
+}
+
 #[test]
 fn test_timeline_styles() {
     // Test arrow style
@@ -103,3 +134,42 @@ fn test_timeline_missing_events() {
     cmd.arg("timeline");
     cmd.assert().failure();
 }
+
+#[test]
+fn test_timeline_spans_lanes_and_now() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.arg("timeline")
+        .args(["--group", "Design=2024-01..2024-03:Wireframes"])
+        .args([
+            "--group",
+            "Build=2024-03..2024-06:Backend,2024-07-01:Launch",
+        ])
+        .args(["--now", "2024-04-10"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("2024-01"))
+        .stdout(predicate::str::contains("Design"))
+        .stdout(predicate::str::contains("███"))
+        .stdout(predicate::str::contains("◆"))
+        .stdout(predicate::str::contains("now (2024-04-10)"));
+}
+
+#[test]
+fn test_timeline_invalid_span_date() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.arg("timeline")
+        .arg("--events")
+        .arg("2024-13..2024-14:Oops");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date '2024-13..2024-14'"));
+}
+
+#[test]
+fn test_timeline_invalid_group() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.arg("timeline").arg("--group").arg("NoEquals");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Invalid group 'NoEquals' (use NAME=EVENTS)",
+    ));
+}
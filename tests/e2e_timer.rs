@@ -0,0 +1,85 @@
+#![allow(deprecated)]
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn termgfx() -> Command {
+    Command::cargo_bin("termgfx").unwrap()
+}
+
+// ============================================================================
+// TIMER / STOPWATCH TESTS
+// ============================================================================
+
+#[test]
+fn test_timer_finishes_with_bell() {
+    termgfx()
+        .args(["timer", "1s", "--label", "Deploy window"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deploy window"))
+        .stdout(predicate::str::contains("done"))
+        .stdout(predicate::str::contains("\x07"));
+}
+
+#[test]
+fn test_timer_minimal_shows_clock() {
+    termgfx()
+        .args(["timer", "500ms", "--style", "minimal"])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("00:00  ████"));
+}
+
+#[test]
+fn test_timer_invalid_duration() {
+    termgfx()
+        .args(["timer", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid interval: soon"));
+}
+
+#[test]
+fn test_timer_invalid_style() {
+    termgfx()
+        .args(["timer", "1s", "--style", "analog"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid style 'analog' (use digital, minimal)",
+        ));
+}
+
+#[test]
+fn test_stopwatch_help() {
+    termgfx()
+        .args(["stopwatch", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("lap"));
+}
+
+#[test]
+fn test_timer_negative_duration() {
+    termgfx()
+        .args(["timer", "--", "-5s"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid interval: -5s"));
+}
+
+#[test]
+fn test_timer_and_stopwatch_are_never_cached() {
+    for command in [&["timer", "1s"][..], &["stopwatch"]] {
+        termgfx()
+            .args(["--cache", "1m"])
+            .args(command)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!(
+                "'{}' is live",
+                command[0]
+            )));
+    }
+}
@@ -192,7 +192,7 @@ fn test_animate_typewriter_effect_tty() {
 
 // ============================================================================
 // DEMO COMMAND TTY TESTS
-// These work because demo sections auto-complete
+// In a terminal the demo is a guided tour that waits for keys
 // ============================================================================
 
 #[test]
@@ -202,30 +202,49 @@ fn test_demo_boxes_tty() {
         Some(TIMEOUT),
     )
     .unwrap();
-    // Demo should show styled boxes section
-    p.exp_string("STYLED BOXES").unwrap();
+    // The first step types its command, runs it and waits
+    p.exp_string("Styled boxes").unwrap();
+    p.exp_string("Deploy finished").unwrap();
+    p.exp_string("q quit").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
     p.exp_eof().unwrap();
 }
 
 #[test]
-fn test_demo_progress_tty() {
+fn test_demo_next_step_tty() {
     let mut p = spawn(
         &format!("{} demo --section progress", termgfx_bin()),
         Some(TIMEOUT),
     )
     .unwrap();
-    p.exp_string("PROGRESS").unwrap();
+    p.exp_string("Progress bars").unwrap();
+    p.exp_string("q quit").unwrap();
+    p.send("l").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Bar styles").unwrap();
+    p.exp_string("q quit").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
     p.exp_eof().unwrap();
 }
 
 #[test]
-fn test_demo_charts_tty() {
+fn test_demo_jump_to_section_tty() {
     let mut p = spawn(
         &format!("{} demo --section charts", termgfx_bin()),
         Some(TIMEOUT),
     )
     .unwrap();
-    p.exp_string("CHARTS").unwrap();
+    p.exp_string("Sparklines").unwrap();
+    p.exp_string("q quit").unwrap();
+    // Section 3 is data
+    p.send("3").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Tables").unwrap();
+    p.exp_string("q quit").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
     p.exp_eof().unwrap();
 }
 
@@ -250,6 +269,81 @@ fn test_input_shows_prompt_tty() {
     wait();
 }
 
+#[test]
+fn test_input_validate_shows_error_and_value_prefills_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} input 'Port:' --validate number --value 80a --char-limit 4",
+            termgfx_bin()
+        ),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("3/4").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("must be a number").unwrap();
+    p.send("\x7f0").unwrap();
+    p.flush().unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("800").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_write_submits_lines_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} write --header 'Notes' --placeholder 'Type here' --rows 3",
+            termgfx_bin()
+        ),
+        Some(6000),
+    )
+    .unwrap();
+    p.exp_string("Type here").unwrap();
+    p.send("one\rtwo").unwrap();
+    p.flush().unwrap();
+    p.send("\x04").unwrap();
+    p.flush().unwrap();
+    p.exp_string("one\r\ntwo").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_input_mask_shows_ghost_tty() {
+    let mut p = spawn(
+        &format!("{} input 'SSN:' --mask '###-##-####'", termgfx_bin()),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("SSN:").unwrap();
+    // Unfilled slots are drawn as ghost characters
+    p.exp_string("___-__-____").unwrap();
+    p.send("\x03").unwrap();
+    wait();
+}
+
+#[test]
+fn test_input_password_masks_symbols_and_esc_cancels_tty() {
+    let mut p = spawn(
+        &format!("{} input 'Secret:' --password", termgfx_bin()),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("Secret:").unwrap();
+    p.send("p@ss-w0rd!").unwrap();
+    p.flush().unwrap();
+    p.exp_string("**********").unwrap();
+    p.send("\x1b").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+    assert_eq!(
+        p.process.wait().unwrap(),
+        rexpect::process::wait::WaitStatus::Exited(p.process.child_pid, 130)
+    );
+}
+
 #[test]
 fn test_confirm_shows_prompt_tty() {
     let mut p = spawn(
@@ -264,6 +358,22 @@ fn test_confirm_shows_prompt_tty() {
     wait();
 }
 
+#[test]
+fn test_confirm_require_typed_shows_phrase_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} confirm 'Delete cluster?' --require-typed delete-prod",
+            termgfx_bin()
+        ),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("Delete cluster?").unwrap();
+    p.exp_string("delete-prod").unwrap();
+    p.send("\x1b").unwrap();
+    wait();
+}
+
 #[test]
 fn test_select_shows_options_tty() {
     let mut p = spawn(
@@ -279,6 +389,299 @@ fn test_select_shows_options_tty() {
     wait();
 }
 
+#[test]
+fn test_select_inline_scrolls_within_height_tty() {
+    // The pty never answers the cursor position query, so allow for its timeout
+    let mut p = spawn(
+        &format!(
+            "{} select 'Pick one:' alpha beta gamma delta --inline --height 2",
+            termgfx_bin()
+        ),
+        Some(6000),
+    )
+    .unwrap();
+    p.exp_string("Esc: Cancel").unwrap();
+    p.send("\x1b[B\x1b[B\x1b[B").unwrap();
+    p.flush().unwrap();
+    p.exp_string("delta").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    let (drawn, _) = p.exp_regex("delta\\s*$").unwrap();
+    assert!(
+        !drawn.contains("\x1b[?1049h"),
+        "inline mode used the alternate screen"
+    );
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_select_type_to_filter_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} select 'Pick one:' 'apple::A red fruit' banana cherry",
+            termgfx_bin()
+        ),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("A red fruit").unwrap();
+    p.send("err").unwrap();
+    p.flush().unwrap();
+    p.exp_string("1/1").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("cherry").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_select_multi_preselected_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} select 'Pick:' a b c --multi --selected b --selected c",
+            termgfx_bin()
+        ),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("Esc: Cancel").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("b,c").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_file_multi_marks_with_preview_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "alpha line\n").unwrap();
+    std::fs::write(dir.path().join("b.txt"), "beta line\n").unwrap();
+    std::fs::write(dir.path().join(".secret"), "").unwrap();
+    let mut p = spawn(
+        &format!(
+            "{} file --path {} --multi --preview",
+            termgfx_bin(),
+            dir.path().display()
+        ),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("Space: Mark").unwrap();
+    p.send(".").unwrap();
+    p.flush().unwrap();
+    p.exp_string(".secret").unwrap();
+    p.send(".\x1b[B").unwrap();
+    p.flush().unwrap();
+    p.exp_string("alpha line").unwrap();
+    p.send(" \x1b[B ").unwrap();
+    p.flush().unwrap();
+    p.exp_string("2 marked").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string(&format!("{}\r\n", dir.path().join("a.txt").display()))
+        .unwrap();
+    p.exp_string(&dir.path().join("b.txt").display().to_string())
+        .unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_file_save_takes_new_name_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.md"), "").unwrap();
+    let mut p = spawn(
+        &format!(
+            "{} file --path {} --save",
+            termgfx_bin(),
+            dir.path().display()
+        ),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("Name:").unwrap();
+    p.send("notes\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string(&dir.path().join("notes").display().to_string())
+        .unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_pager_search_and_sideways_scroll_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("long.txt");
+    let long = format!("start{}tail-end", "-".repeat(100));
+    std::fs::write(&file, format!("alpha\n{}\nbeta\nalpha again\n", long)).unwrap();
+    let mut p = spawn(
+        &format!("{} pager {}", termgfx_bin(), file.display()),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("Lines 1-4 of 4").unwrap();
+    p.send("/al.ha\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("match 1/2").unwrap();
+    p.send("n").unwrap();
+    p.flush().unwrap();
+    p.exp_string("match 2/2").unwrap();
+    p.send("/[\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Invalid regex '['").unwrap();
+    p.send("\x1b[C\x1b[C").unwrap();
+    p.flush().unwrap();
+    p.exp_string("tail-end").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_pager_follow_shows_appended_lines_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("app.log");
+    std::fs::write(&file, "first line\n").unwrap();
+    let mut p = spawn(
+        &format!("{} pager {} --follow", termgfx_bin(), file.display()),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("first line").unwrap();
+    let mut log = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&file)
+        .unwrap();
+    std::io::Write::write_all(&mut log, b"second line\n").unwrap();
+    p.exp_string("second line").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_filter_piped_with_preview_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("filter.sh");
+    std::fs::write(
+        &script,
+        format!(
+            "printf '1 alpha\\n2 beta\\n3 gamma\\n' | {} filter --with-nth 2.. --preview 'echo seen-{{}}'\n",
+            termgfx_bin()
+        ),
+    )
+    .unwrap();
+    let mut p = spawn(&format!("sh {}", script.display()), Some(5000)).unwrap();
+    p.exp_string("seen-1 alpha").unwrap();
+    p.send("gm").unwrap();
+    p.flush().unwrap();
+    p.exp_string("seen-3 gamma").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("3 gamma").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_filter_slow_preview_keeps_typing_and_esc_cancels_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("filter.sh");
+    std::fs::write(
+        &script,
+        format!(
+            "printf 'alpha\\nbeta\\ngamma\\n' | {} filter --preview 'sleep 3; echo slow-{{}}'\necho status=$?\n",
+            termgfx_bin()
+        ),
+    )
+    .unwrap();
+    let mut p = spawn(&format!("sh {}", script.display()), Some(2500)).unwrap();
+    p.exp_string("Loading").unwrap();
+    // The query narrows the list while the first preview still runs
+    p.send("gm").unwrap();
+    p.flush().unwrap();
+    p.exp_string("1/3 items").unwrap();
+    p.send("\x1b").unwrap();
+    p.flush().unwrap();
+    p.exp_string("status=130").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_table_interactive_prints_row_down_pipe_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("table.sh");
+    let out = dir.path().join("row.json");
+    std::fs::write(
+        &script,
+        format!(
+            "printf 'name,size\\nalpha,10\\nbeta,9\\ngamma,100\\n' | {} table --interactive --row-format json > {}\n",
+            termgfx_bin(),
+            out.display()
+        ),
+    )
+    .unwrap();
+    let mut p = spawn(&format!("sh {}", script.display()), Some(5000)).unwrap();
+    p.exp_string("3 rows").unwrap();
+    // Sort by size (the second column) descending, then search
+    p.send("lss").unwrap();
+    p.flush().unwrap();
+    p.exp_string("sorted by size").unwrap();
+    p.send("/bet\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("row 3/3").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&out).unwrap(),
+        "{\"name\":\"beta\",\"size\":\"9\"}\n"
+    );
+}
+
+#[test]
+fn test_diff_interactive_prints_accepted_hunks_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let (old, new) = (dir.path().join("old.txt"), dir.path().join("new.txt"));
+    std::fs::write(&old, "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n").unwrap();
+    std::fs::write(&new, "x\na\nb\nc\nd\ne\nf\ng\nh\ni\nJ\n").unwrap();
+    let mut p = spawn(
+        &format!(
+            "{} diff {} {} --interactive --context 1",
+            termgfx_bin(),
+            old.display(),
+            new.display()
+        ),
+        Some(3000),
+    )
+    .unwrap();
+    p.exp_string("Esc cancel").unwrap();
+    // Reject the added first line, accept the last change
+    p.send("r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Hunk 2/2").unwrap();
+    p.exp_string("Esc cancel").unwrap();
+    p.send("a").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("@@ -9,2 +9,2 @@").unwrap();
+    p.exp_string("+J").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_keys_echo_shows_hint_tty() {
+    let mut p = spawn(&format!("{} keys --echo", termgfx_bin()), Some(3000)).unwrap();
+    p.exp_string("Ctrl+C to quit").unwrap();
+    p.send("\x03").unwrap();
+    wait();
+}
+
+#[test]
+fn test_repl_shows_prompt_tty() {
+    let mut p = spawn(&format!("{} repl", termgfx_bin()), Some(3000)).unwrap();
+    p.exp_string("termgfx REPL").unwrap();
+    p.send("\x04").unwrap();
+    wait();
+}
+
 // ============================================================================
 // NEW INTERACTIVE COMMANDS TTY TESTS (v0.4.0+)
 // These test playground, tui, wizard, form, file picker, and filter
@@ -374,6 +777,77 @@ fn test_wizard_confirm_step_tty() {
     wait();
 }
 
+fn branching_wizard() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("wizard.json"),
+        r#"{"steps": [
+  {"id": "role", "type": "select", "prompt": "Role", "options": ["Admin", "User"],
+   "next": {"User": "done"}},
+  {"id": "key", "type": "input", "prompt": "Admin key", "validate": "[A-Z]{3}"},
+  {"id": "done", "type": "confirm", "prompt": "Finish?"}
+]}"#,
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn test_wizard_next_skips_steps_tty() {
+    let dir = branching_wizard();
+    let mut p = spawn(
+        &format!(
+            "{} wizard --config {}",
+            termgfx_bin(),
+            dir.path().join("wizard.json").display()
+        ),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("Step 1/3").unwrap();
+    p.send("\x1b[B").unwrap();
+    p.flush().unwrap();
+    wait();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    // "User" jumps straight to the last step
+    p.exp_string("Step 2/2").unwrap();
+    p.exp_string("Finish?").unwrap();
+    p.send("y").unwrap();
+    p.flush().unwrap();
+    let (output, _) = p.exp_regex(r#""done": "true""#).unwrap();
+    assert!(!output.contains("\"key\""));
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_wizard_validate_shows_error_tty() {
+    let dir = branching_wizard();
+    let mut p = spawn(
+        &format!(
+            "{} wizard --config {} --output env",
+            termgfx_bin(),
+            dir.path().join("wizard.json").display()
+        ),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("Role").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Admin key").unwrap();
+    p.send("ab\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("must match [A-Z]{3}").unwrap();
+    p.send("\x7f\x7fABC\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Finish?").unwrap();
+    p.send("y").unwrap();
+    p.flush().unwrap();
+    p.exp_string("KEY=ABC").unwrap();
+    p.exp_eof().unwrap();
+}
+
 #[test]
 fn test_form_shows_fields_tty() {
     let mut p = spawn(
@@ -419,6 +893,38 @@ fn test_form_select_field_tty() {
     wait();
 }
 
+#[test]
+fn test_form_required_field_and_review_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("form.json");
+    std::fs::write(
+        &config,
+        r#"{"fields": [{"name": "name", "type": "text", "label": "Name", "required": true}]}"#,
+    )
+    .unwrap();
+    let mut p = spawn(
+        &format!("{} form --config {}", termgfx_bin(), config.display()),
+        Some(5000),
+    )
+    .unwrap();
+    p.exp_string("Name *").unwrap();
+    // An empty answer is turned down
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("an answer is required").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.send("ada\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Review").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("\"name\": \"ada\"").unwrap();
+    p.exp_eof().unwrap();
+}
+
 #[test]
 fn test_file_picker_shows_ui_tty() {
     let mut p = spawn(&format!("{} file", termgfx_bin()), Some(3000)).unwrap();
@@ -429,3 +935,201 @@ fn test_file_picker_shows_ui_tty() {
     p.send("\x1b").unwrap();
     wait();
 }
+
+#[test]
+fn test_json_viewer_expands_and_quits_tty() {
+    let path = std::env::temp_dir().join("termgfx_json_tty.json");
+    std::fs::write(&path, r#"{"config": {"port": 8080}}"#).unwrap();
+    let mut p = spawn(
+        &format!("{} json {}", termgfx_bin(), path.display()),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("config").unwrap();
+    // Select the collapsed object and expand it
+    wait();
+    p.send("j").unwrap();
+    p.flush().unwrap();
+    wait();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string("8080").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_dashboard_live_runs_panel_commands_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dash.json");
+    std::fs::write(
+        &path,
+        r#"{"layout": "1x2", "panels": [
+            {"type": "text", "command": "echo live-$((40 + 2))", "interval": "200ms"},
+            {"type": "gauge", "command": "echo 75"}
+        ]}"#,
+    )
+    .unwrap();
+    let mut p = spawn(
+        &format!(
+            "{} dashboard --config {} --live",
+            termgfx_bin(),
+            path.display()
+        ),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("live-42").unwrap();
+    p.exp_string("75%").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+}
+
+// ============================================================================
+// FOOTER TTY TESTS
+// ============================================================================
+
+#[test]
+fn test_footer_pins_status_and_errors_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} footer --lines 2 -c 'echo started; sleep 0.3; echo error: boom >&2; sleep 0.3'",
+            termgfx_bin()
+        ),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    // Scroll region reserved above the footer
+    p.exp_regex(r"\x1b\[1;\d+r").unwrap();
+    p.exp_string("started").unwrap();
+    p.exp_string("lines").unwrap();
+    p.exp_string("error: boom").unwrap();
+    // Region released before the summary
+    p.exp_string("\x1b[r").unwrap();
+    p.exp_string("finished in").unwrap();
+    p.exp_eof().unwrap();
+}
+
+// ============================================================================
+// EMOJIPICK TTY TESTS
+// ============================================================================
+
+#[test]
+fn test_emojipick_search_and_pick_tty() {
+    let mut p = spawn(&format!("{} emojipick", termgfx_bin()), Some(TIMEOUT)).unwrap();
+    p.exp_string("Search:").unwrap();
+    p.send("tada").unwrap();
+    p.flush().unwrap();
+    p.exp_string(":tada:").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    // The glyph is printed after the alternate screen closes
+    p.exp_string("\x1b[?1049l").unwrap();
+    // rexpect reads bytes as chars, so undo that to compare UTF-8
+    let rest: Vec<u8> = p.exp_eof().unwrap().chars().map(|c| c as u8).collect();
+    assert_eq!(String::from_utf8(rest).unwrap(), "🎉\r\n");
+}
+
+#[test]
+fn test_emojipick_code_and_grid_moves_tty() {
+    let mut p = spawn(
+        &format!("{} emojipick --code --category nerd -q git", termgfx_bin()),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string(":nf-dev-git:").unwrap();
+    // Right arrow moves to the next match
+    p.send("\x1b[C").unwrap();
+    p.flush().unwrap();
+    p.exp_string(":nf-dev-git_branch:").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_string(":nf-dev-git_branch:\r\n").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_stopwatch_lap_and_stop_tty() {
+    let mut p = spawn(
+        &format!("{} stopwatch --style minimal --label Build", termgfx_bin()),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("space lap").unwrap();
+    p.send(" ").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Lap 1").unwrap();
+    p.send("\r").unwrap();
+    p.flush().unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_timer_counts_down_tty() {
+    let mut p = spawn(
+        &format!("{} timer 1s --label Tea", termgfx_bin()),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("Tea").unwrap();
+    p.exp_string("done").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_fireworks_fullscreen_tty() {
+    let mut p = spawn(
+        &format!("{} animate --type fireworks --duration 0.5", termgfx_bin()),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("\x1b[?1049h").unwrap();
+    p.exp_string("\x1b[?1049l").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_matrix_quits_on_q_tty() {
+    let mut p = spawn(
+        &format!(
+            "{} animate --type matrix --duration 60 --colors cyan",
+            termgfx_bin()
+        ),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("\x1b[?1049h").unwrap();
+    wait();
+    p.send("q").unwrap();
+    p.flush().unwrap();
+    p.exp_string("\x1b[?1049l").unwrap();
+    p.exp_eof().unwrap();
+}
+
+#[test]
+fn test_present_navigates_slides_tty() {
+    let dir = tempfile::tempdir().unwrap();
+    let deck = dir.path().join("deck.md");
+    std::fs::write(
+        &deck,
+        "# Intro\n\nFirst slide\n---\n## Second\n\nLast words\n",
+    )
+    .unwrap();
+    let mut p = spawn(
+        &format!("{} present {}", termgfx_bin(), deck.display()),
+        Some(TIMEOUT),
+    )
+    .unwrap();
+    p.exp_string("First slide").unwrap();
+    p.exp_string("1/2").unwrap();
+    p.send("l").unwrap();
+    p.flush().unwrap();
+    p.exp_string("Last words").unwrap();
+    p.exp_string("2/2").unwrap();
+    p.send("q").unwrap();
+    p.flush().unwrap();
+    p.exp_string("\x1b[?1049l").unwrap();
+    p.exp_eof().unwrap();
+}
@@ -38,3 +38,13 @@ fn test_watch_invalid_interval() {
         .failure()
         .stderr(predicate::str::contains("Invalid"));
 }
+
+#[test]
+fn test_watch_rejects_zero_interval() {
+    let mut cmd = Command::cargo_bin("termgfx").unwrap();
+    cmd.args(["watch", "echo test", "--interval", "0s"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("must be at least 100ms"));
+}
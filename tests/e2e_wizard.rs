@@ -308,3 +308,29 @@ fn test_wizard_unknown_step_type() {
     assert!(stderr.contains("Unknown step type") || stderr.contains("Error"));
     assert!(!output.status.success());
 }
+
+#[test]
+fn test_wizard_config_rules_are_checked() {
+    let dir = tempfile::tempdir().unwrap();
+    for (step, error) in [
+        (
+            r#"{"id": "a", "type": "input", "prompt": "A", "validate": "("}"#,
+            "Step 'a': invalid validate regex",
+        ),
+        (
+            r#"{"id": "a", "type": "select", "prompt": "A", "options": ["x"], "next": {"x": "b"}}"#,
+            "Step 'a': 'next' names unknown step 'b'",
+        ),
+    ] {
+        let config = dir.path().join("wizard.json");
+        std::fs::write(&config, format!(r#"{{"steps": [{}]}}"#, step)).unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_termgfx"))
+            .args(["wizard", "--config", config.to_str().unwrap()])
+            .output()
+            .expect("Failed to run wizard");
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{}", stderr);
+        assert!(!output.status.success());
+    }
+}